@@ -3,15 +3,18 @@
  * PURPOSE: Handle HTTP requests for authentication endpoints
  * This provides REST API endpoints for Google, Facebook, and email authentication
  */
+use crate::auth::middleware::ClientInfo;
 use crate::auth::models::*;
 use crate::auth::services::AuthService;
 use serde_json::json;
+use uuid::Uuid;
 use validator::Validate;
 use warp::{http::StatusCode, reply, Rejection, Reply};
 
 /// Handle Google OAuth authentication
 pub async fn handle_google_auth(
     auth_request: GoogleAuthRequest,
+    client: ClientInfo,
     auth_service: AuthService,
 ) -> Result<impl Reply, Rejection> {
     // Verify Google ID token
@@ -59,15 +62,14 @@ pub async fn handle_google_auth(
         }
     };
 
-    // Generate JWT token
-    let token = match auth_service.generate_token(&user) {
-        Ok(token) => token,
+    // Start a session and issue its tokens
+    let tokens = match auth_service
+        .start_session(&user, client.user_agent, client.ip_address)
+        .await
+    {
+        Ok(tokens) => tokens,
         Err(e) => {
-            tracing::error!(
-                "Failed to generate JWT token for user {}: {}",
-                user.email,
-                e
-            );
+            tracing::error!("Failed to start session for user {}: {}", user.email, e);
             return Ok(reply::with_status(
                 reply::json(&json!({
                     "error": "Authentication failed",
@@ -79,7 +81,8 @@ pub async fn handle_google_auth(
     };
 
     let response = AuthResponse {
-        token,
+        token: tokens.access_token,
+        refresh_token: Some(tokens.refresh_token),
         user: UserResponse::from(user),
     };
 
@@ -89,6 +92,7 @@ pub async fn handle_google_auth(
 /// Handle Facebook OAuth authentication
 pub async fn handle_facebook_auth(
     auth_request: FacebookAuthRequest,
+    client: ClientInfo,
     auth_service: AuthService,
 ) -> Result<impl Reply, Rejection> {
     // Verify Facebook token
@@ -144,15 +148,14 @@ pub async fn handle_facebook_auth(
         }
     };
 
-    // Generate JWT token
-    let token = match auth_service.generate_token(&user) {
-        Ok(token) => token,
+    // Start a session and issue its tokens
+    let tokens = match auth_service
+        .start_session(&user, client.user_agent, client.ip_address)
+        .await
+    {
+        Ok(tokens) => tokens,
         Err(e) => {
-            tracing::error!(
-                "Failed to generate JWT token for user {}: {}",
-                user.email,
-                e
-            );
+            tracing::error!("Failed to start session for user {}: {}", user.email, e);
             return Ok(reply::with_status(
                 reply::json(&json!({
                     "error": "Authentication failed",
@@ -164,7 +167,8 @@ pub async fn handle_facebook_auth(
     };
 
     let response = AuthResponse {
-        token,
+        token: tokens.access_token,
+        refresh_token: Some(tokens.refresh_token),
         user: UserResponse::from(user),
     };
 
@@ -174,6 +178,7 @@ pub async fn handle_facebook_auth(
 /// Handle email/password login
 pub async fn handle_login(
     login_request: LoginRequest,
+    client: ClientInfo,
     auth_service: AuthService,
 ) -> Result<impl Reply, Rejection> {
     // Validate request
@@ -205,15 +210,14 @@ pub async fn handle_login(
         }
     };
 
-    // Generate JWT token
-    let token = match auth_service.generate_token(&user) {
-        Ok(token) => token,
+    // Start a session and issue its tokens
+    let tokens = match auth_service
+        .start_session(&user, client.user_agent, client.ip_address)
+        .await
+    {
+        Ok(tokens) => tokens,
         Err(e) => {
-            tracing::error!(
-                "Failed to generate JWT token for user {}: {}",
-                user.email,
-                e
-            );
+            tracing::error!("Failed to start session for user {}: {}", user.email, e);
             return Ok(reply::with_status(
                 reply::json(&json!({
                     "error": "Authentication failed",
@@ -225,7 +229,8 @@ pub async fn handle_login(
     };
 
     let response = AuthResponse {
-        token,
+        token: tokens.access_token,
+        refresh_token: Some(tokens.refresh_token),
         user: UserResponse::from(user),
     };
 
@@ -235,6 +240,7 @@ pub async fn handle_login(
 /// Handle user registration
 pub async fn handle_register(
     register_request: RegisterRequest,
+    client: ClientInfo,
     auth_service: AuthService,
 ) -> Result<impl Reply, Rejection> {
     // Validate request
@@ -270,15 +276,14 @@ pub async fn handle_register(
         }
     };
 
-    // Generate JWT token
-    let token = match auth_service.generate_token(&user) {
-        Ok(token) => token,
+    // Start a session and issue its tokens
+    let tokens = match auth_service
+        .start_session(&user, client.user_agent, client.ip_address)
+        .await
+    {
+        Ok(tokens) => tokens,
         Err(e) => {
-            tracing::error!(
-                "Failed to generate JWT token for user {}: {}",
-                user.email,
-                e
-            );
+            tracing::error!("Failed to start session for user {}: {}", user.email, e);
             return Ok(reply::with_status(
                 reply::json(&json!({
                     "error": "Authentication failed",
@@ -290,7 +295,8 @@ pub async fn handle_register(
     };
 
     let response = AuthResponse {
-        token,
+        token: tokens.access_token,
+        refresh_token: Some(tokens.refresh_token),
         user: UserResponse::from(user),
     };
 
@@ -393,10 +399,68 @@ pub async fn handle_update_profile(
     ))
 }
 
+/// Handle token refresh
+///
+/// Exchanges a refresh token for a new access token and refresh token. The
+/// presented refresh token stops working.
+pub async fn handle_refresh(
+    refresh_request: RefreshTokenRequest,
+    auth_service: AuthService,
+) -> Result<impl Reply, Rejection> {
+    let (user, tokens) = match auth_service
+        .refresh_session(&refresh_request.refresh_token)
+        .await
+    {
+        Ok(refreshed) => refreshed,
+        Err(e) => {
+            tracing::warn!("Token refresh failed: {}", e);
+            return Ok(reply::with_status(
+                reply::json(&json!({
+                    "error": "Invalid refresh token",
+                    "message": "Your session has expired. Please sign in again."
+                })),
+                StatusCode::UNAUTHORIZED,
+            ));
+        }
+    };
+
+    let response = AuthResponse {
+        token: tokens.access_token,
+        refresh_token: Some(tokens.refresh_token),
+        user: UserResponse::from(user),
+    };
+
+    Ok(reply::with_status(reply::json(&response), StatusCode::OK))
+}
+
 /// Handle logout
-pub async fn handle_logout() -> Result<impl Reply, Rejection> {
-    // In a stateless JWT system, logout is typically handled client-side
-    // by removing the token. Here we just return a success message.
+///
+/// Revokes the session of the presented token, so neither it nor the
+/// session's refresh token can be used again.
+pub async fn handle_logout(
+    claims: Option<Claims>,
+    auth_service: AuthService,
+) -> Result<impl Reply, Rejection> {
+    let session: Option<(Uuid, Uuid)> = claims.and_then(|claims| {
+        Some((
+            claims.sub.parse().ok()?,
+            claims.sid.as_deref()?.parse().ok()?,
+        ))
+    });
+
+    if let Some((user_id, session_id)) = session {
+        if let Err(e) = auth_service.revoke_session(user_id, session_id).await {
+            tracing::error!("Failed to revoke session {}: {}", session_id, e);
+            return Ok(reply::with_status(
+                reply::json(&json!({
+                    "error": "Logout failed",
+                    "message": "Unable to end your session. Please try again."
+                })),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    }
+
     Ok(reply::with_status(
         reply::json(&json!({
             "message": "Logout successful"
@@ -69,6 +69,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_login_at: chrono::Utc::now(),
             is_active: true,
+            subscription_tier: SubscriptionTier::Free,
         };
 
         // Generate token
@@ -333,6 +334,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_login_at: chrono::Utc::now(),
             is_active: true,
+            subscription_tier: SubscriptionTier::Free,
         };
 
         let user_response = UserResponse::from(user.clone());
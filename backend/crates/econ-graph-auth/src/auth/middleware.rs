@@ -35,13 +35,15 @@ fn jwt_from_header(headers: &HeaderMap<HeaderValue>) -> Result<String, Rejection
 }
 
 /// Create authentication filter that extracts and validates JWT claims
+///
+/// Tokens of revoked or expired sessions are rejected.
 pub fn with_auth(auth_service: AuthService) -> BoxedFilter<(Claims,)> {
     headers_cloned()
         .map(move |headers: HeaderMap<HeaderValue>| (headers, auth_service.clone()))
         .and_then(
             |(headers, auth_service): (HeaderMap<HeaderValue>, AuthService)| async move {
                 match jwt_from_header(&headers) {
-                    Ok(token) => match auth_service.verify_token(&token) {
+                    Ok(token) => match auth_service.authenticate_token(&token).await {
                         Ok(claims) => Ok(claims),
                         Err(_) => Err(reject::custom(AuthError)),
                     },
@@ -59,7 +61,7 @@ pub fn with_optional_auth(auth_service: AuthService) -> BoxedFilter<(Option<Clai
         .and_then(
             |(headers, auth_service): (HeaderMap<HeaderValue>, AuthService)| async move {
                 match jwt_from_header(&headers) {
-                    Ok(token) => match auth_service.verify_token(&token) {
+                    Ok(token) => match auth_service.authenticate_token(&token).await {
                         Ok(claims) => Ok::<Option<Claims>, warp::Rejection>(Some(claims)),
                         Err(_) => Ok(None),
                     },
@@ -70,6 +72,40 @@ pub fn with_optional_auth(auth_service: AuthService) -> BoxedFilter<(Option<Clai
         .boxed()
 }
 
+/// Client details recorded with a new session
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// Extract the client's user agent and address
+///
+/// Behind the ingress the first `X-Forwarded-For` entry is the client.
+pub fn with_client_info(
+) -> impl Filter<Extract = (ClientInfo,), Error = std::convert::Infallible> + Clone {
+    warp::header::optional::<String>("user-agent")
+        .and(warp::header::optional::<String>("x-forwarded-for"))
+        .and(warp::addr::remote())
+        .map(
+            |user_agent: Option<String>,
+             forwarded_for: Option<String>,
+             remote: Option<std::net::SocketAddr>| {
+                let forwarded = forwarded_for.and_then(|header| {
+                    header
+                        .split(',')
+                        .next()
+                        .map(|ip| ip.trim().to_string())
+                        .filter(|ip| !ip.is_empty())
+                });
+                ClientInfo {
+                    user_agent,
+                    ip_address: forwarded.or_else(|| remote.map(|addr| addr.ip().to_string())),
+                }
+            },
+        )
+}
+
 /// Handle authentication rejection
 pub async fn handle_auth_rejection(err: Rejection) -> Result<impl warp::Reply, Infallible> {
     if err.find::<AuthError>().is_some() {
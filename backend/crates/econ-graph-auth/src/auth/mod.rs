@@ -5,6 +5,7 @@
  */
 pub mod handlers;
 pub mod middleware;
+pub mod organizations;
 pub mod routes;
 pub mod services;
 pub mod simple_test;
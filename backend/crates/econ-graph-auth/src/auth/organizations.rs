@@ -0,0 +1,88 @@
+/**
+ * REQUIREMENT: Organization-level access control for team collaboration
+ * PURPOSE: Resolve what a user may do inside an organization and on charts shared with it
+ * This lets teams manage chart access collectively instead of per-user invites
+ */
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{
+    highest_chart_permission, OrganizationChartShare, OrganizationMember, OrganizationRole,
+};
+use uuid::Uuid;
+
+/// Organization authorization checks backed by membership records
+#[derive(Clone)]
+pub struct OrganizationAccess {
+    pub db_pool: DatabasePool,
+}
+
+impl OrganizationAccess {
+    /// Create new organization access checker
+    pub fn new(db_pool: DatabasePool) -> Self {
+        OrganizationAccess { db_pool }
+    }
+
+    /// Require the user to hold at least `minimum` role in the organization
+    pub async fn require_role(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+        minimum: OrganizationRole,
+    ) -> AppResult<OrganizationMember> {
+        let member = OrganizationMember::find(&self.db_pool, organization_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::Forbidden("Not a member of this organization".to_string()))?;
+
+        if member.organization_role() < minimum {
+            return Err(AppError::Forbidden(format!(
+                "Organization role '{}' or higher required",
+                minimum
+            )));
+        }
+
+        Ok(member)
+    }
+
+    /// Require the user to be allowed to manage organization membership
+    pub async fn require_member_manager(
+        &self,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<OrganizationMember> {
+        self.require_role(organization_id, user_id, OrganizationRole::Admin)
+            .await
+    }
+
+    /// Highest chart permission the user holds through organization shares, if any
+    pub async fn chart_permission(
+        &self,
+        chart_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<&'static str>> {
+        OrganizationChartShare::permission_for_user(&self.db_pool, user_id, chart_id).await
+    }
+}
+
+/// Pick the most privileged chart permission from a set of grants
+pub fn highest_permission<'a>(levels: impl Iterator<Item = &'a str>) -> Option<&'static str> {
+    highest_chart_permission(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_permission() {
+        // REQUIREMENT: A user in several organizations gets the best grant among them
+        // PURPOSE: Verify permission levels are compared by privilege, not alphabetically
+        // This ensures an edit share is not hidden by a view share from another organization
+
+        assert_eq!(
+            highest_permission(["view", "edit", "comment"].into_iter()),
+            Some("edit")
+        );
+        assert_eq!(highest_permission(["ADMIN"].into_iter()), Some("admin"));
+        assert_eq!(highest_permission(std::iter::empty()), None);
+    }
+}
@@ -4,7 +4,9 @@
  * This provides the HTTP routes that the frontend expects for authentication
  */
 use crate::auth::handlers::*;
-use crate::auth::middleware::{handle_auth_rejection, with_auth};
+use crate::auth::middleware::{
+    handle_auth_rejection, with_auth, with_client_info, with_optional_auth,
+};
 use crate::auth::services::AuthService;
 use warp::{filters::BoxedFilter, Filter, Reply};
 
@@ -19,6 +21,7 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
     let google_auth = warp::path!("auth" / "google")
         .and(warp::post())
         .and(warp::body::json())
+        .and(with_client_info())
         .and(with_auth_service(auth_service.clone()))
         .and_then(handle_google_auth);
 
@@ -26,6 +29,7 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
     let facebook_auth = warp::path!("auth" / "facebook")
         .and(warp::post())
         .and(warp::body::json())
+        .and(with_client_info())
         .and(with_auth_service(auth_service.clone()))
         .and_then(handle_facebook_auth);
 
@@ -33,6 +37,7 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
     let login = warp::path!("auth" / "login")
         .and(warp::post())
         .and(warp::body::json())
+        .and(with_client_info())
         .and(with_auth_service(auth_service.clone()))
         .and_then(handle_login);
 
@@ -40,6 +45,7 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
     let register = warp::path!("auth" / "register")
         .and(warp::post())
         .and(warp::body::json())
+        .and(with_client_info())
         .and(with_auth_service(auth_service.clone()))
         .and_then(handle_register);
 
@@ -58,9 +64,18 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
         .and(with_auth_service(auth_service.clone()))
         .and_then(handle_update_profile);
 
-    // Logout route
+    // Token refresh route
+    let refresh = warp::path!("auth" / "refresh")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_auth_service(auth_service.clone()))
+        .and_then(handle_refresh);
+
+    // Logout route (revokes the current session)
     let logout = warp::path!("auth" / "logout")
         .and(warp::post())
+        .and(with_optional_auth(auth_service.clone()))
+        .and(with_auth_service(auth_service.clone()))
         .and_then(handle_logout);
 
     // Facebook data deletion callback route
@@ -76,6 +91,7 @@ pub fn auth_routes(auth_service: AuthService) -> BoxedFilter<(impl Reply,)> {
         .or(register)
         .or(get_profile)
         .or(update_profile)
+        .or(refresh)
         .or(logout)
         .or(facebook_data_deletion)
         .with(cors)
@@ -4,11 +4,13 @@
  * This enables secure authentication with Google and Facebook OAuth backends
  */
 use crate::auth::models::*;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{NewUserSession, UserSession};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use reqwest::Client;
+use sha2::{Digest, Sha256};
 use std::env;
 use uuid::Uuid;
 
@@ -23,6 +25,34 @@ const JWT_ISSUER: &str = "econ-graph";
 /// Token expiration time (24 hours)
 const TOKEN_EXPIRATION_HOURS: i64 = 24;
 
+/// Refresh token expiration time (30 days); every refresh starts a new period
+const REFRESH_TOKEN_EXPIRATION_DAYS: i64 = 30;
+
+/// Access and refresh token issued for a session
+#[derive(Debug, Clone)]
+pub struct SessionTokens {
+    pub session_id: Uuid,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+/// Random refresh token, hex encoded
+fn new_refresh_token() -> String {
+    rand::random::<[u8; 32]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hash stored in `user_sessions.token_hash`
+///
+/// Refresh tokens are random, so an unsalted fast hash is enough to keep a
+/// database leak from handing out usable tokens while still allowing lookup.
+fn hash_refresh_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
 /// Authentication service
 #[derive(Clone)]
 pub struct AuthService {
@@ -49,7 +79,14 @@ impl AuthService {
     }
 
     /// Generate JWT token for user
+    ///
+    /// The token is not bound to a session, so [`Self::authenticate_token`]
+    /// rejects it; clients get their tokens from [`Self::start_session`].
     pub fn generate_token(&self, user: &User) -> AppResult<String> {
+        self.encode_token(user, None)
+    }
+
+    fn encode_token(&self, user: &User, session_id: Option<Uuid>) -> AppResult<String> {
         let now = Utc::now();
         let expiration = now + Duration::hours(TOKEN_EXPIRATION_HOURS);
 
@@ -61,6 +98,8 @@ impl AuthService {
             exp: expiration.timestamp() as usize,
             iat: now.timestamp() as usize,
             iss: JWT_ISSUER.to_string(),
+            sid: session_id.map(|id| id.to_string()),
+            tier: user.subscription_tier,
         };
 
         let token = encode(
@@ -88,6 +127,110 @@ impl AuthService {
         Ok(token_data.claims)
     }
 
+    /// Verify a JWT and check that its session has not been revoked
+    ///
+    /// Used for every authenticated request, so a revoked session stops
+    /// working immediately rather than when its token expires.
+    pub async fn authenticate_token(&self, token: &str) -> AppResult<Claims> {
+        let claims = self.verify_token(token)?;
+
+        let session_id = claims
+            .sid
+            .as_deref()
+            .and_then(|sid| Uuid::parse_str(sid).ok())
+            .ok_or_else(|| {
+                AppError::AuthenticationError("Token is not bound to a session".to_string())
+            })?;
+
+        match UserSession::find_active(&self.db_pool, session_id).await? {
+            Some(session) if session.user_id.to_string() == claims.sub => Ok(claims),
+            _ => Err(AppError::AuthenticationError(
+                "Session has been revoked or has expired".to_string(),
+            )),
+        }
+    }
+
+    /// Start a session for a signed-in user and issue its first token pair
+    pub async fn start_session(
+        &self,
+        user: &User,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+    ) -> AppResult<SessionTokens> {
+        let refresh_token = new_refresh_token();
+        let refresh_expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_EXPIRATION_DAYS);
+
+        let session = UserSession::insert(
+            &self.db_pool,
+            &NewUserSession {
+                user_id: user.id,
+                token_hash: hash_refresh_token(&refresh_token),
+                expires_at: refresh_expires_at,
+                user_agent,
+                ip_address,
+            },
+        )
+        .await?;
+
+        Ok(SessionTokens {
+            session_id: session.id,
+            access_token: self.encode_token(user, Some(session.id))?,
+            refresh_token,
+            refresh_expires_at,
+        })
+    }
+
+    /// Exchange a refresh token for a new token pair
+    ///
+    /// The presented refresh token is invalidated; reusing it fails.
+    pub async fn refresh_session(&self, refresh_token: &str) -> AppResult<(User, SessionTokens)> {
+        let invalid =
+            || AppError::AuthenticationError("Invalid or expired refresh token".to_string());
+
+        let current_hash = hash_refresh_token(refresh_token);
+        let session = UserSession::find_active_by_token_hash(&self.db_pool, &current_hash)
+            .await?
+            .ok_or_else(invalid)?;
+
+        let user = self.refresh_user(session.user_id).await?;
+        if !user.is_active {
+            UserSession::revoke(&self.db_pool, session.id, Some(user.id)).await?;
+            return Err(AppError::AuthenticationError(
+                "User account is disabled".to_string(),
+            ));
+        }
+
+        let new_refresh_token = new_refresh_token();
+        let refresh_expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_EXPIRATION_DAYS);
+        let session = UserSession::rotate_token(
+            &self.db_pool,
+            session.id,
+            &current_hash,
+            &hash_refresh_token(&new_refresh_token),
+            refresh_expires_at,
+        )
+        .await?
+        .ok_or_else(invalid)?;
+
+        let tokens = SessionTokens {
+            session_id: session.id,
+            access_token: self.encode_token(&user, Some(session.id))?,
+            refresh_token: new_refresh_token,
+            refresh_expires_at,
+        };
+        Ok((user, tokens))
+    }
+
+    /// Revoke one of a user's sessions, returning whether it existed
+    pub async fn revoke_session(&self, user_id: Uuid, session_id: Uuid) -> AppResult<bool> {
+        UserSession::revoke(&self.db_pool, session_id, Some(user_id)).await
+    }
+
+    /// Revoke every session of a user, returning how many were revoked
+    pub async fn revoke_all_sessions(&self, user_id: Uuid) -> AppResult<usize> {
+        UserSession::revoke_all(&self.db_pool, user_id).await
+    }
+
     /// Verify Google OAuth ID token
     pub async fn verify_google_token(&self, id_token: &str) -> AppResult<GoogleUserInfo> {
         // First, verify the ID token with Google's tokeninfo endpoint
@@ -337,3 +480,24 @@ impl AuthService {
             .ok_or_else(|| AppError::AuthenticationError("User not found".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_tokens_are_random_and_hashed_for_lookup() {
+        // REQUIREMENT: Refresh tokens are stored hashed in user_sessions
+        // PURPOSE: Verify tokens are unguessable and their stored hash is deterministic
+
+        let first = new_refresh_token();
+        let second = new_refresh_token();
+
+        assert_eq!(first.len(), 64);
+        assert_ne!(first, second);
+        assert_eq!(hash_refresh_token(&first), hash_refresh_token(&first));
+        assert_ne!(hash_refresh_token(&first), first);
+        assert_ne!(hash_refresh_token(&first), hash_refresh_token(&second));
+        assert!(hash_refresh_token(&first).len() <= 255);
+    }
+}
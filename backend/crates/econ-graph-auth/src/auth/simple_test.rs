@@ -40,6 +40,7 @@ pub async fn test_auth_system() -> Result<(), Box<dyn std::error::Error>> {
         created_at: chrono::Utc::now(),
         last_login_at: chrono::Utc::now(),
         is_active: true,
+        subscription_tier: SubscriptionTier::Free,
     };
 
     // Generate token
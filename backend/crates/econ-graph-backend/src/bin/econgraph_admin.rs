@@ -0,0 +1,360 @@
+//! Operational tasks for EconGraph deployments
+//!
+//! `econgraph-admin` runs the maintenance jobs operators otherwise reach for
+//! SQL to do: migrations, seeding, dead-letter requeues, manual crawls,
+//! storage cleanup, one-off exports and data source API keys. Every
+//! subcommand goes through the same services the API server uses, and reads
+//! its configuration the same way.
+
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, NaiveDate};
+use clap::{Parser, Subcommand};
+use econ_graph_core::config::ConfigArgs;
+use econ_graph_core::database::{create_pool, DatabasePool};
+use econ_graph_core::models::{DataSource, ExportFormat, User, UserSession, MAX_EXPORT_SERIES};
+use econ_graph_core::secrets::{shared_secret_cipher, SecretString};
+use econ_graph_services::services::crawler::simple_crawler_service;
+use econ_graph_services::services::data_source_admin_service::{
+    AuditActor, DataSourceAdminService,
+};
+use econ_graph_services::services::export_service::{
+    self, load_export_rows, render_export, ExportWorker, FilesystemExportStorage,
+};
+use econ_graph_services::services::queue_service::{self, MAX_BULK_QUEUE_ITEMS};
+use econ_graph_services::services::revision_retention_service::{self, RetentionPolicy};
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Sources `trigger-crawl` can queue; see `simple_crawler_service::trigger_manual_crawl`
+const CRAWLABLE_SOURCES: [&str; 2] = ["FRED", "BLS"];
+
+/// Environment variable `create-api-key` reads the key from when `--key` is not given
+const API_KEY_ENV: &str = "ECONGRAPH_API_KEY";
+
+/// EconGraph administration
+#[derive(Parser)]
+#[command(name = "econgraph-admin")]
+#[command(about = "Operational tasks for EconGraph")]
+#[command(version)]
+struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Apply pending database migrations
+    Migrate,
+
+    /// Create the predefined data sources missing from the database
+    Seed,
+
+    /// Put dead-lettered crawl queue items back on the queue
+    RequeueDlq {
+        /// Requeue only these items
+        #[arg(long = "id", value_name = "ID")]
+        ids: Vec<Uuid>,
+
+        /// Requeue only items of this source
+        #[arg(short, long)]
+        source: Option<String>,
+
+        /// Most items requeued
+        #[arg(short, long, default_value_t = MAX_BULK_QUEUE_ITEMS as i64)]
+        limit: i64,
+    },
+
+    /// Crawl a data source's popular series, or the given series, now
+    TriggerCrawl {
+        /// Data source: FRED or BLS
+        source: String,
+
+        /// Crawl these series instead of the source's popular ones
+        #[arg(long, value_delimiter = ',')]
+        series: Vec<String>,
+    },
+
+    /// Delete expired revisions, queue items, sessions and export files
+    CompactStorage {
+        /// Report the revisions that would be deleted and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Write the observations of one or more series to a file
+    ExportSeries {
+        /// Series IDs, comma separated or repeated
+        #[arg(
+            long = "series",
+            value_name = "ID",
+            value_delimiter = ',',
+            required = true
+        )]
+        series_ids: Vec<Uuid>,
+
+        /// File to write
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// csv, parquet or xlsx
+        #[arg(short, long, default_value = "csv")]
+        format: ExportFormat,
+
+        /// First date included (YYYY-MM-DD)
+        #[arg(long)]
+        start_date: Option<NaiveDate>,
+
+        /// Last date included (YYYY-MM-DD)
+        #[arg(long)]
+        end_date: Option<NaiveDate>,
+    },
+
+    /// Store a data source's API key, encrypted
+    CreateApiKey {
+        /// Data source name, e.g. "Federal Reserve Economic Data (FRED)"
+        #[arg(long)]
+        source: String,
+
+        /// Administrator the change is recorded under in the audit log
+        #[arg(long, value_name = "USER_ID")]
+        admin: Uuid,
+
+        /// The API key; read from $ECONGRAPH_API_KEY when omitted, keeping it out of shell history
+        #[arg(long)]
+        key: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = cli.config.loader()?.load()?;
+    econ_graph_metrics::logging::init_with_filter(&config.logging.level);
+
+    if matches!(cli.command, Commands::Migrate) {
+        econ_graph_core::run_migrations(&config.database_url).await?;
+        println!("✅ Migrations applied");
+        return Ok(());
+    }
+
+    let pool = create_pool(&config.database_url)
+        .await
+        .context("Failed to connect to the database")?;
+
+    match cli.command {
+        Commands::Migrate => unreachable!("handled before connecting"),
+        Commands::Seed => seed_command(&pool).await,
+        Commands::RequeueDlq { ids, source, limit } => {
+            requeue_dlq_command(&pool, ids, source, limit).await
+        }
+        Commands::TriggerCrawl { source, series } => {
+            trigger_crawl_command(&pool, source, series).await
+        }
+        Commands::CompactStorage { dry_run } => compact_storage_command(&pool, dry_run).await,
+        Commands::ExportSeries {
+            series_ids,
+            output,
+            format,
+            start_date,
+            end_date,
+        } => export_series_command(&pool, series_ids, output, format, start_date, end_date).await,
+        Commands::CreateApiKey { source, admin, key } => {
+            create_api_key_command(&pool, source, admin, key).await
+        }
+    }
+}
+
+async fn seed_command(pool: &DatabasePool) -> Result<()> {
+    let created = DataSourceAdminService::seed_predefined(pool).await?;
+
+    for source in &created {
+        println!("  + {}", source.name);
+    }
+    println!(
+        "✅ Created {} data sources, {} already present",
+        created.len(),
+        DataSource::predefined().len() - created.len()
+    );
+    Ok(())
+}
+
+async fn requeue_dlq_command(
+    pool: &DatabasePool,
+    ids: Vec<Uuid>,
+    source: Option<String>,
+    limit: i64,
+) -> Result<()> {
+    if !(1..=MAX_BULK_QUEUE_ITEMS as i64).contains(&limit) {
+        bail!("--limit must be between 1 and {}", MAX_BULK_QUEUE_ITEMS);
+    }
+
+    let requeued = if ids.is_empty() {
+        let items =
+            queue_service::list_dead_letter_items(pool, source.as_deref(), limit, 0).await?;
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        queue_service::requeue_queue_items(pool, &ids).await?
+    } else {
+        let mut requeued = Vec::new();
+        for id in ids {
+            match queue_service::requeue_dead_letter_item(pool, id).await? {
+                Some(item) => requeued.push(item),
+                None => println!("  ! {} is not in the dead-letter queue", id),
+            }
+        }
+        requeued
+    };
+
+    for item in &requeued {
+        println!("  ↻ {} {} ({})", item.source, item.series_id, item.id);
+    }
+    println!(
+        "✅ Requeued {} items, {} left in the dead-letter queue",
+        requeued.len(),
+        queue_service::count_dead_letter_items(pool).await?
+    );
+    Ok(())
+}
+
+async fn trigger_crawl_command(
+    pool: &DatabasePool,
+    source: String,
+    series: Vec<String>,
+) -> Result<()> {
+    let source = source.to_uppercase();
+    if !CRAWLABLE_SOURCES.contains(&source.as_str()) {
+        bail!(
+            "Unknown source '{}'; expected one of {}",
+            source,
+            CRAWLABLE_SOURCES.join(", ")
+        );
+    }
+
+    let (sources, series_ids) = if series.is_empty() {
+        (Some(vec![source]), None)
+    } else {
+        (None, Some(series))
+    };
+    let queued = simple_crawler_service::trigger_manual_crawl(pool, sources, series_ids, 1).await?;
+
+    println!("✅ Crawl triggered: {} series queued", queued);
+    Ok(())
+}
+
+async fn compact_storage_command(pool: &DatabasePool, dry_run: bool) -> Result<()> {
+    let report =
+        revision_retention_service::apply_retention(pool, &RetentionPolicy::from_env(), dry_run)
+            .await?;
+    for line in report.render() {
+        println!("  {}", line);
+    }
+    if dry_run {
+        println!("✅ Dry run; queue items, sessions and export files were not checked");
+        return Ok(());
+    }
+
+    let queue_items = queue_service::cleanup_old_queue_items(pool).await?;
+    println!("  Deleted {} finished queue items", queue_items);
+
+    let sessions = UserSession::cleanup_expired(pool).await?;
+    println!("  Deleted {} expired sessions", sessions);
+
+    let worker = ExportWorker::new(
+        pool.clone(),
+        Arc::new(FilesystemExportStorage::from_env()),
+        Duration::hours(export_service::DEFAULT_EXPORT_RETENTION_HOURS),
+    );
+    let mut export_files = 0;
+    loop {
+        let removed = worker.remove_expired().await?;
+        if removed == 0 {
+            break;
+        }
+        export_files += removed;
+    }
+    println!("  Deleted {} expired export files", export_files);
+
+    println!("✅ Storage compacted");
+    Ok(())
+}
+
+async fn export_series_command(
+    pool: &DatabasePool,
+    series_ids: Vec<Uuid>,
+    output: PathBuf,
+    format: ExportFormat,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<()> {
+    if series_ids.len() > MAX_EXPORT_SERIES {
+        bail!(
+            "At most {} series can be exported at once",
+            MAX_EXPORT_SERIES
+        );
+    }
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if start > end {
+            bail!("--start-date must not be after --end-date");
+        }
+    }
+
+    let rows = load_export_rows(pool, &series_ids, start_date, end_date).await?;
+    let contents = tokio::task::spawn_blocking(move || {
+        render_export(format, &rows).map(|contents| (contents, rows.len()))
+    })
+    .await??;
+    let (contents, row_count) = contents;
+
+    tokio::fs::write(&output, &contents)
+        .await
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    println!(
+        "✅ Wrote {} rows ({} bytes) to {}",
+        row_count,
+        contents.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+async fn create_api_key_command(
+    pool: &DatabasePool,
+    source: String,
+    admin: Uuid,
+    key: Option<String>,
+) -> Result<()> {
+    let key = match key {
+        Some(key) => key,
+        None => std::env::var(API_KEY_ENV)
+            .with_context(|| format!("Pass --key or set {}", API_KEY_ENV))?,
+    };
+
+    let user = User::get_by_id(pool, admin).await?;
+    if user.role != "admin" {
+        bail!("{} is not an administrator", user.email);
+    }
+    let data_source = DataSource::find_by_name(pool, &source)
+        .await?
+        .with_context(|| format!("Unknown data source '{}'", source))?;
+
+    let actor = AuditActor {
+        user_id: user.id,
+        user_name: user.name,
+        ip_address: None,
+    };
+    DataSourceAdminService::store_api_key(
+        pool,
+        &actor,
+        shared_secret_cipher()?,
+        data_source.id,
+        &SecretString::new(key),
+    )
+    .await?;
+
+    println!("✅ Stored API key for {}", data_source.name);
+    Ok(())
+}
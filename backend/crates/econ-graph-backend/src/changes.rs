@@ -0,0 +1,224 @@
+//! Change feed endpoint for downstream caches
+//!
+//! `GET /changes?since=N` returns the series and data point mutations after
+//! sequence `N`, like the `changes` GraphQL query, for services that follow the
+//! feed without a user session. With `wait=S` the request is held for up to
+//! `S` seconds (at most [`MAX_WAIT_SECONDS`]) until there is something new, so
+//! a consumer can tail the feed with one request in flight:
+//!
+//! ```text
+//! GET /changes?since=1041&wait=30
+//! {"changes":[{"sequence":1042,"entity_type":"series_data","entity_id":"...","operation":"insert",...}],
+//!  "next_since":1042,"resync_required":false}
+//! ```
+//!
+//! Configuration:
+//! - `CHANGE_FEED_API_TOKEN`: bearer token consumers must send; the endpoint is disabled without it
+
+use econ_graph_core::models::{ChangeFeedEntry, ChangeFeedPage, DEFAULT_CHANGE_FEED_LIMIT};
+use econ_graph_core::{AppError, DatabasePool};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use warp::http::StatusCode;
+use warp::Filter;
+
+use crate::metrics;
+use crate::service_auth::ServiceToken;
+
+const ROUTE: &str = "/changes";
+
+/// Longest a request may wait for new changes
+pub const MAX_WAIT_SECONDS: u64 = 60;
+
+/// How often a waiting request checks the feed again
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Query parameters of `/changes`
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    /// Last sequence the consumer processed; 0 to start from the oldest change kept
+    pub since: i64,
+    /// Seconds to wait for changes when there are none yet
+    pub wait: Option<u64>,
+    pub limit: Option<i64>,
+}
+
+impl ChangesQuery {
+    /// How long to hold the request, capped at [`MAX_WAIT_SECONDS`]
+    pub fn wait(&self) -> Duration {
+        Duration::from_secs(self.wait.unwrap_or(0).min(MAX_WAIT_SECONDS))
+    }
+}
+
+/// Shared state of the change feed endpoint
+pub struct ChangesEndpoint {
+    token: ServiceToken,
+    pool: DatabasePool,
+}
+
+impl ChangesEndpoint {
+    pub fn new(token: Option<String>, pool: DatabasePool) -> Self {
+        Self {
+            token: ServiceToken::new(token),
+            pool,
+        }
+    }
+
+    /// Create the endpoint from environment variables
+    pub fn from_env(pool: DatabasePool) -> Self {
+        Self::new(std::env::var("CHANGE_FEED_API_TOKEN").ok(), pool)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_configured()
+    }
+
+    /// Changes after `query.since`, waiting for some until `query.wait()` has passed
+    async fn poll(&self, query: &ChangesQuery) -> Result<ChangeFeedPage, AppError> {
+        let deadline = Instant::now() + query.wait();
+        let limit = query.limit.unwrap_or(DEFAULT_CHANGE_FEED_LIMIT);
+
+        loop {
+            let page = ChangeFeedEntry::since(&self.pool, query.since, limit).await?;
+            if !page.changes.is_empty() || page.resync_required || Instant::now() >= deadline {
+                return Ok(page);
+            }
+            tokio::time::sleep(POLL_INTERVAL.min(deadline - Instant::now())).await;
+        }
+    }
+}
+
+/// `GET /changes`
+pub fn changes_route(
+    endpoint: Arc<ChangesEndpoint>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path("changes")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::any().map(move || endpoint.clone()))
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::query::<ChangesQuery>())
+        .and_then(changes_handler)
+}
+
+async fn changes_handler(
+    endpoint: Arc<ChangesEndpoint>,
+    authorization: Option<String>,
+    query: ChangesQuery,
+) -> Result<impl warp::Reply, Infallible> {
+    let start = Instant::now();
+    let reply = |status: StatusCode, body: serde_json::Value| {
+        metrics::record_http_request("GET", ROUTE, status.as_u16(), start.elapsed().as_secs_f64());
+        Ok::<_, Infallible>(warp::reply::with_status(warp::reply::json(&body), status))
+    };
+
+    if !endpoint.is_enabled() {
+        return reply(
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "error": "Change feed endpoint is not configured" }),
+        );
+    }
+    if !endpoint.token.authorizes(authorization.as_deref()) {
+        return reply(
+            StatusCode::UNAUTHORIZED,
+            json!({ "error": "Missing or invalid change feed token" }),
+        );
+    }
+
+    match endpoint.poll(&query).await {
+        Ok(page) => reply(StatusCode::OK, json!(page)),
+        Err(AppError::ValidationError(message)) => {
+            reply(StatusCode::BAD_REQUEST, json!({ "error": message }))
+        }
+        Err(e) => {
+            tracing::error!("Failed to read the change feed: {}", e);
+            reply(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Failed to read the change feed" }),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use econ_graph_core::models::{DataSource, EconomicSeries, NewDataSource, NewEconomicSeries};
+    use econ_graph_core::test_utils::TestContainer;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_changes_require_token_and_return_new_mutations() {
+        // REQUIREMENT: Downstream caches can tail series and data point mutations to stay in sync
+        // PURPOSE: Verify only the configured token reads the feed and a waiting request returns the changes made meanwhile
+        // This ensures cache invalidation signals reach consumers without exposing the feed publicly
+
+        let container = TestContainer::new().await;
+        let pool = container.pool().clone();
+        let route = changes_route(Arc::new(ChangesEndpoint::new(
+            Some("feed-token".to_string()),
+            pool.clone(),
+        )));
+        let since = ChangeFeedEntry::latest_sequence(&pool).await.unwrap();
+
+        let response = warp::test::request()
+            .method("GET")
+            .path(&format!("/changes?since={}", since))
+            .header("authorization", "Bearer wrong-token")
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let writer_pool = pool.clone();
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let source = DataSource::create(
+                &writer_pool,
+                NewDataSource {
+                    name: format!("Change Feed Route Source {}", Uuid::new_v4()),
+                    base_url: "https://changes.example.com/api".to_string(),
+                    ..NewDataSource::default()
+                },
+            )
+            .await
+            .unwrap();
+            EconomicSeries::create(
+                &writer_pool,
+                &NewEconomicSeries {
+                    source_id: source.id,
+                    external_id: "CHANGES_ROUTE_001".to_string(),
+                    title: "Change Feed Route Series".to_string(),
+                    frequency: "Monthly".to_string(),
+                    is_active: true,
+                    ..NewEconomicSeries::default()
+                },
+            )
+            .await
+            .unwrap()
+        });
+        let response = warp::test::request()
+            .method("GET")
+            .path(&format!("/changes?since={}&wait=5", since))
+            .header("authorization", "Bearer feed-token")
+            .reply(&route)
+            .await;
+        writer.await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(page["resync_required"], false);
+        // The request waited for the series created meanwhile
+        assert!(!page["changes"].as_array().unwrap().is_empty());
+        assert!(page["next_since"].as_i64().unwrap() > since);
+
+        let disabled = changes_route(Arc::new(ChangesEndpoint::new(None, pool)));
+        let response = warp::test::request()
+            .method("GET")
+            .path("/changes?since=0")
+            .reply(&disabled)
+            .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,454 @@
+//! Server-side chart images
+//!
+//! Draws line charts as SVG (title, value grid, date axis and legend) and
+//! rasterizes them to PNG with resvg. Text in PNGs uses the system fonts
+//! found at startup; without any installed fonts the lines still render but
+//! labels are left out.
+
+use chrono::{Datelike, NaiveDate};
+use econ_graph_core::{AppError, AppResult};
+use resvg::{tiny_skia, usvg};
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock};
+
+/// Default image size, the usual social preview size
+pub const DEFAULT_WIDTH: u32 = 1200;
+pub const DEFAULT_HEIGHT: u32 = 630;
+
+const MIN_SIZE: u32 = 200;
+const MAX_WIDTH: u32 = 2400;
+const MAX_HEIGHT: u32 = 1600;
+
+const FONT_FAMILY: &str = "DejaVu Sans, Liberation Sans, Arial, sans-serif";
+const PALETTE: [&str; 8] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b", "#e377c2", "#17becf",
+];
+
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const LEGEND_ROW_HEIGHT: f64 = 22.0;
+
+/// One plotted series
+#[derive(Debug, Clone)]
+pub struct ChartLine {
+    pub label: String,
+    /// Observations in date order
+    pub points: Vec<(NaiveDate, f64)>,
+}
+
+/// A chart to render
+#[derive(Debug, Clone)]
+pub struct ChartImage {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub lines: Vec<ChartLine>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Requested image size clamped to what the renderer accepts
+pub fn image_size(width: Option<u32>, height: Option<u32>) -> (u32, u32) {
+    (
+        width.unwrap_or(DEFAULT_WIDTH).clamp(MIN_SIZE, MAX_WIDTH),
+        height.unwrap_or(DEFAULT_HEIGHT).clamp(MIN_SIZE, MAX_HEIGHT),
+    )
+}
+
+/// Render a chart as a standalone SVG document
+pub fn render_svg(chart: &ChartImage) -> String {
+    let width = chart.width as f64;
+    let height = chart.height as f64;
+    let legend = legend_layout(&chart.lines, width);
+    let legend_rows = legend.last().map_or(0, |entry| entry.row + 1);
+
+    let top = if chart.subtitle.is_some() { 78.0 } else { 58.0 };
+    let bottom = height - 40.0 - legend_rows as f64 * LEGEND_ROW_HEIGHT;
+    let left = MARGIN_LEFT;
+    let right = width - MARGIN_RIGHT;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{FONT_FAMILY}">"#,
+        w = chart.width,
+        h = chart.height,
+    );
+    svg.push_str(r##"<rect width="100%" height="100%" fill="#ffffff"/>"##);
+    let _ = write!(
+        svg,
+        r##"<text x="{left}" y="34" font-size="22" font-weight="bold" fill="#1a1a1a">{}</text>"##,
+        escape(&chart.title)
+    );
+    if let Some(subtitle) = &chart.subtitle {
+        let _ = write!(
+            svg,
+            r##"<text x="{left}" y="58" font-size="14" fill="#555555">{}</text>"##,
+            escape(subtitle)
+        );
+    }
+
+    let plotted: Vec<&(NaiveDate, f64)> = chart
+        .lines
+        .iter()
+        .flat_map(|line| &line.points)
+        .filter(|(_, value)| value.is_finite())
+        .collect();
+    let (Some(first), Some(last)) = (
+        plotted.iter().map(|(date, _)| *date).min(),
+        plotted.iter().map(|(date, _)| *date).max(),
+    ) else {
+        let _ = write!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="16" fill="#777777" text-anchor="middle">No data for this chart</text></svg>"##,
+            (left + right) / 2.0,
+            (top + bottom) / 2.0
+        );
+        return svg;
+    };
+
+    let min_value = plotted
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::INFINITY, f64::min);
+    let max_value = plotted
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let scale = ValueScale::new(min_value, max_value);
+    let span_days = (last - first).num_days().max(1) as f64;
+
+    let x = |date: NaiveDate| left + (date - first).num_days() as f64 / span_days * (right - left);
+    let y = |value: f64| bottom - (value - scale.low) / (scale.high - scale.low) * (bottom - top);
+
+    // Value grid and labels
+    for tick in scale.ticks() {
+        let ty = y(tick);
+        let _ = write!(
+            svg,
+            r##"<line x1="{left}" y1="{ty:.1}" x2="{right}" y2="{ty:.1}" stroke="#e5e5e5" stroke-width="1"/><text x="{:.1}" y="{:.1}" font-size="12" fill="#555555" text-anchor="end">{}</text>"##,
+            left - 8.0,
+            ty + 4.0,
+            escape(&scale.label(tick))
+        );
+    }
+
+    // Date axis
+    let _ = write!(
+        svg,
+        r##"<line x1="{left}" y1="{bottom:.1}" x2="{right}" y2="{bottom:.1}" stroke="#999999" stroke-width="1"/>"##
+    );
+    for (date, label) in date_ticks(first, last) {
+        let tx = x(date);
+        let _ = write!(
+            svg,
+            r##"<line x1="{tx:.1}" y1="{bottom:.1}" x2="{tx:.1}" y2="{:.1}" stroke="#999999" stroke-width="1"/><text x="{tx:.1}" y="{:.1}" font-size="12" fill="#555555" text-anchor="middle">{label}</text>"##,
+            bottom + 5.0,
+            bottom + 20.0
+        );
+    }
+
+    // Series
+    for (index, line) in chart.lines.iter().enumerate() {
+        let points: Vec<String> = line
+            .points
+            .iter()
+            .filter(|(_, value)| value.is_finite())
+            .map(|(date, value)| format!("{:.1},{:.1}", x(*date), y(*value)))
+            .collect();
+        if points.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            svg,
+            r#"<polyline fill="none" stroke="{}" stroke-width="2" stroke-linejoin="round" points="{}"/>"#,
+            color(index),
+            points.join(" ")
+        );
+    }
+
+    // Legend
+    let legend_top = bottom + 36.0;
+    for entry in &legend {
+        let ly = legend_top + entry.row as f64 * LEGEND_ROW_HEIGHT;
+        let _ = write!(
+            svg,
+            r##"<rect x="{:.1}" y="{:.1}" width="14" height="4" fill="{}"/><text x="{:.1}" y="{:.1}" font-size="13" fill="#333333">{}</text>"##,
+            entry.x,
+            ly - 4.0,
+            color(entry.index),
+            entry.x + 20.0,
+            ly + 1.0,
+            escape(&chart.lines[entry.index].label)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Rasterize an SVG document to PNG
+pub fn render_png(svg: &str) -> AppResult<Vec<u8>> {
+    let options = usvg::Options {
+        fontdb: font_database(),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| AppError::InternalError(format!("Failed to parse chart SVG: {}", e)))?;
+
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| AppError::InternalError("Chart image has no area".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| AppError::InternalError(format!("Failed to encode chart PNG: {}", e)))
+}
+
+/// System fonts, loaded once
+fn font_database() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fonts = usvg::fontdb::Database::new();
+            fonts.load_system_fonts();
+            Arc::new(fonts)
+        })
+        .clone()
+}
+
+fn color(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct LegendEntry {
+    index: usize,
+    row: usize,
+    x: f64,
+}
+
+/// Legend entries laid out left to right, wrapping at the right margin
+fn legend_layout(lines: &[ChartLine], width: f64) -> Vec<LegendEntry> {
+    let mut entries = Vec::with_capacity(lines.len());
+    let (mut row, mut x) = (0, MARGIN_LEFT);
+
+    for (index, line) in lines.iter().enumerate() {
+        // Rough width of 13px text plus the swatch and spacing
+        let entry_width = 20.0 + line.label.chars().count() as f64 * 7.0 + 24.0;
+        if x > MARGIN_LEFT && x + entry_width > width - MARGIN_RIGHT {
+            row += 1;
+            x = MARGIN_LEFT;
+        }
+        entries.push(LegendEntry { index, row, x });
+        x += entry_width;
+    }
+
+    entries
+}
+
+/// Value axis range rounded out to whole grid steps
+struct ValueScale {
+    low: f64,
+    high: f64,
+    step: f64,
+}
+
+impl ValueScale {
+    fn new(min: f64, max: f64) -> Self {
+        let (min, max) = if max > min {
+            (min, max)
+        } else {
+            let pad = if min == 0.0 { 1.0 } else { min.abs() * 0.1 };
+            (min - pad, max + pad)
+        };
+        let step = nice_step((max - min) / 5.0);
+
+        Self {
+            low: (min / step).floor() * step,
+            high: (max / step).ceil() * step,
+            step,
+        }
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        let count = ((self.high - self.low) / self.step).round() as usize;
+        (0..=count)
+            .map(|i| self.low + i as f64 * self.step)
+            .collect()
+    }
+
+    /// Tick label, with K/M/B/T suffixes for large values
+    fn label(&self, value: f64) -> String {
+        let magnitude = self.low.abs().max(self.high.abs());
+        let (divisor, suffix) = if magnitude >= 1e12 {
+            (1e12, "T")
+        } else if magnitude >= 1e9 {
+            (1e9, "B")
+        } else if magnitude >= 1e6 {
+            (1e6, "M")
+        } else if magnitude >= 1e4 {
+            (1e3, "K")
+        } else {
+            (1.0, "")
+        };
+
+        let step = self.step / divisor;
+        let decimals = if step >= 1.0 {
+            0
+        } else {
+            // The epsilon keeps steps like 0.1 from rounding up to an extra digit
+            (-step.log10() - 1e-9).ceil() as usize
+        };
+        // Avoid "-0" from floating point noise around zero
+        let value = if value.abs() < self.step / 2.0 {
+            0.0
+        } else {
+            value
+        };
+
+        format!("{:.*}{}", decimals, value / divisor, suffix)
+    }
+}
+
+/// 1, 2 or 5 times a power of ten, at least `raw`
+fn nice_step(raw: f64) -> f64 {
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let normalized = raw / magnitude;
+    let nice = if normalized <= 1.0 {
+        1.0
+    } else if normalized <= 2.0 {
+        2.0
+    } else if normalized <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Date axis ticks: years for long ranges, months for shorter ones
+fn date_ticks(first: NaiveDate, last: NaiveDate) -> Vec<(NaiveDate, String)> {
+    const MAX_TICKS: i32 = 8;
+
+    if first == last {
+        return vec![(first, first.format("%b %Y").to_string())];
+    }
+
+    if (last - first).num_days() > 3 * 365 {
+        let years = last.year() - first.year();
+        let step = [1, 2, 5, 10, 20, 50, 100]
+            .into_iter()
+            .find(|step| years / step < MAX_TICKS)
+            .unwrap_or(100);
+        let mut year = first.year() + i32::from(first.ordinal() > 1);
+        year += (step - year.rem_euclid(step)) % step;
+
+        let mut ticks = Vec::new();
+        while let Some(date) = NaiveDate::from_ymd_opt(year, 1, 1).filter(|date| *date <= last) {
+            ticks.push((date, year.to_string()));
+            year += step;
+        }
+        return ticks;
+    }
+
+    let month_index = |date: NaiveDate| date.year() * 12 + date.month0() as i32;
+    let months = month_index(last) - month_index(first);
+    let step = [1, 2, 3, 6, 12]
+        .into_iter()
+        .find(|step| months / step < MAX_TICKS)
+        .unwrap_or(12);
+    let mut index = month_index(first) + i32::from(first.day() > 1);
+    index += (step - index.rem_euclid(step)) % step;
+
+    let mut ticks = Vec::new();
+    while let Some(date) =
+        NaiveDate::from_ymd_opt(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+            .filter(|date| *date <= last)
+    {
+        ticks.push((date, date.format("%b %Y").to_string()));
+        index += step;
+    }
+    ticks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_value_scale_and_date_ticks() {
+        // REQUIREMENT: Embedded charts have readable axes
+        // PURPOSE: Verify value grids land on round numbers and date ticks stay few and aligned
+        // This ensures chart images look the same as the frontend's charts at a glance
+
+        let scale = ValueScale::new(3.2, 27.9);
+        assert_eq!(scale.step, 5.0);
+        assert_eq!(scale.ticks(), vec![0.0, 5.0, 10.0, 15.0, 20.0, 25.0, 30.0]);
+        assert_eq!(scale.label(25.0), "25");
+
+        let gdp = ValueScale::new(18_000_000.0, 27_400_000.0);
+        assert_eq!(gdp.label(20_000_000.0), "20M");
+        let rates = ValueScale::new(0.012, 0.051);
+        assert_eq!(rates.label(0.03), "0.03");
+
+        let flat = ValueScale::new(4.0, 4.0);
+        assert!(flat.low < 4.0 && flat.high > 4.0);
+
+        let years = date_ticks(date(1990, 3, 1), date(2024, 6, 1));
+        assert!(years.len() <= 8);
+        assert_eq!(years[0], (date(1995, 1, 1), "1995".to_string()));
+
+        let months = date_ticks(date(2023, 1, 15), date(2024, 1, 1));
+        assert_eq!(months[0].0, date(2023, 3, 1));
+        assert!(months.len() <= 8);
+    }
+
+    #[test]
+    fn test_render_svg() {
+        // REQUIREMENT: Saved charts can be embedded as images
+        // PURPOSE: Verify the SVG has one line per series, escapes labels and handles empty charts
+        // This ensures user-provided titles cannot break the image markup
+
+        let chart = ChartImage {
+            title: "GDP & <Unemployment>".to_string(),
+            subtitle: Some("Quarterly".to_string()),
+            lines: vec![
+                ChartLine {
+                    label: "Real GDP".to_string(),
+                    points: vec![(date(2020, 1, 1), 100.0), (date(2021, 1, 1), 104.5)],
+                },
+                ChartLine {
+                    label: "Unemployment Rate".to_string(),
+                    points: vec![(date(2020, 1, 1), 3.5), (date(2021, 1, 1), f64::NAN)],
+                },
+            ],
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+        };
+
+        let svg = render_svg(&chart);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("GDP &amp; &lt;Unemployment&gt;"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(!svg.contains("NaN"));
+
+        let empty = render_svg(&ChartImage {
+            lines: Vec::new(),
+            ..chart
+        });
+        assert!(empty.contains("No data for this chart"));
+        assert_eq!(image_size(Some(10), Some(99_999)), (MIN_SIZE, MAX_HEIGHT));
+    }
+}
@@ -0,0 +1,163 @@
+//! Drain endpoint for deployments
+//!
+//! `POST /drain` puts the server into drain mode ahead of a shutdown: queue
+//! workers stop claiming new items, `/health` answers 503 so load balancers stop
+//! routing to the instance, and work already started runs to completion.
+//! `GET /drain` reports whether the server is draining and how much tracked
+//! work is still in flight, so a deployment can wait for zero before sending
+//! SIGTERM. Draining cannot be undone; the instance is expected to be replaced.
+//!
+//! Configuration:
+//! - `DRAIN_API_TOKEN`: bearer token deployment tooling must send; the endpoint is disabled without it
+
+use econ_graph_core::shutdown::ShutdownCoordinator;
+use serde::Serialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+use warp::http::{Method, StatusCode};
+use warp::Filter;
+
+use crate::metrics;
+use crate::service_auth::ServiceToken;
+
+const ROUTE: &str = "/drain";
+
+/// Drain state reported by `/drain`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DrainStatus {
+    pub draining: bool,
+    /// Tracked requests, crawls and deliveries still running
+    pub in_flight: usize,
+}
+
+/// Shared state of the drain endpoint
+pub struct DrainEndpoint {
+    token: ServiceToken,
+    coordinator: Arc<ShutdownCoordinator>,
+}
+
+impl DrainEndpoint {
+    pub fn new(token: Option<String>, coordinator: Arc<ShutdownCoordinator>) -> Self {
+        Self {
+            token: ServiceToken::new(token),
+            coordinator,
+        }
+    }
+
+    /// Create the endpoint from environment variables
+    pub fn from_env(coordinator: Arc<ShutdownCoordinator>) -> Self {
+        Self::new(std::env::var("DRAIN_API_TOKEN").ok(), coordinator)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_configured()
+    }
+
+    fn status(&self) -> DrainStatus {
+        DrainStatus {
+            draining: self.coordinator.is_draining(),
+            in_flight: self.coordinator.in_flight(),
+        }
+    }
+}
+
+/// `GET /drain` and `POST /drain`
+pub fn drain_route(
+    endpoint: Arc<DrainEndpoint>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path("drain")
+        .and(warp::path::end())
+        .and(warp::get().or(warp::post()).unify())
+        .and(warp::method())
+        .and(warp::any().map(move || endpoint.clone()))
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(drain_handler)
+}
+
+async fn drain_handler(
+    method: Method,
+    endpoint: Arc<DrainEndpoint>,
+    authorization: Option<String>,
+) -> Result<impl warp::Reply, Infallible> {
+    let start = Instant::now();
+    let reply = |status: StatusCode, body: serde_json::Value| {
+        metrics::record_http_request(
+            method.as_str(),
+            ROUTE,
+            status.as_u16(),
+            start.elapsed().as_secs_f64(),
+        );
+        Ok::<_, Infallible>(warp::reply::with_status(warp::reply::json(&body), status))
+    };
+
+    if !endpoint.is_enabled() {
+        return reply(
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "error": "Drain endpoint is not configured" }),
+        );
+    }
+    if !endpoint.token.authorizes(authorization.as_deref()) {
+        return reply(
+            StatusCode::UNAUTHORIZED,
+            json!({ "error": "Missing or invalid drain token" }),
+        );
+    }
+
+    if method == Method::POST && endpoint.coordinator.drain() {
+        tracing::warn!(
+            "🚰 Drain requested: no longer claiming new work, {} tasks in flight",
+            endpoint.coordinator.in_flight()
+        );
+    }
+
+    reply(StatusCode::OK, json!(endpoint.status()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_requires_token_and_reports_in_flight_work() {
+        // REQUIREMENT: Graceful shutdown and drain mode across services
+        // PURPOSE: Verify only the configured token can start draining and the status reports in-flight work
+        // This ensures deployments can drain an instance while nobody else can take it out of rotation
+
+        let coordinator = Arc::new(ShutdownCoordinator::new());
+        let _crawl = coordinator.track();
+        let route = drain_route(Arc::new(DrainEndpoint::new(
+            Some("deploy-token".to_string()),
+            coordinator.clone(),
+        )));
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/drain")
+            .header("authorization", "Bearer wrong-token")
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!coordinator.is_draining());
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/drain")
+            .header("authorization", "Bearer deploy-token")
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let status: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(status, json!({ "draining": true, "in_flight": 1 }));
+        assert!(coordinator.is_draining());
+
+        let disabled = drain_route(Arc::new(DrainEndpoint::new(None, coordinator)));
+        let response = warp::test::request()
+            .method("GET")
+            .path("/drain")
+            .reply(&disabled)
+            .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,275 @@
+//! Chart images for embedding
+//!
+//! `GET /embed/chart/{id}.png` and `GET /embed/chart/{id}.svg` render a saved
+//! chart with its date range and per-series transformations applied, for
+//! embedding in documents and for social preview images. Only charts their
+//! owner has made public are rendered; anything else is a 404. Images are
+//! served to anyone, so values are rounded like public tier API responses and
+//! series from sources that are not public are left out.
+//!
+//! Query parameters:
+//! - `width`, `height`: image size in pixels (default 1200x630)
+
+use bigdecimal::ToPrimitive;
+use econ_graph_core::models::{DataQueryParams, DataSource, DataTransformation, SavedChart};
+use econ_graph_core::{AppResult, DatabasePool};
+use econ_graph_graphql::graphql::public_tier::{shared_public_tier_policy, PublicTierPolicy};
+use econ_graph_graphql::graphql::query::apply_cached_transformation;
+use econ_graph_services::services::data_point_cache::{shared_data_point_cache, DataPointCacheKey};
+use econ_graph_services::services::series_service::{self, DownsamplingAlgorithm};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::time::Instant;
+use uuid::Uuid;
+use warp::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+use crate::chart_render::{self, ChartImage, ChartLine};
+use crate::metrics;
+
+const ROUTE: &str = "/embed/chart";
+
+/// How long clients and CDNs may reuse an image before asking again
+const CACHE_CONTROL_VALUE: &str = "public, max-age=300";
+
+/// Image format of an embed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// Chart ID and image format of an `{id}.png` or `{id}.svg` path segment
+pub fn parse_embed_file(file: &str) -> Option<(Uuid, ImageFormat)> {
+    let (id, extension) = file.rsplit_once('.')?;
+    let format = match extension.to_ascii_lowercase().as_str() {
+        "png" => ImageFormat::Png,
+        "svg" => ImageFormat::Svg,
+        _ => return None,
+    };
+    Some((Uuid::parse_str(id).ok()?, format))
+}
+
+/// Query parameters of an embed request
+#[derive(Debug, Deserialize)]
+pub struct EmbedQuery {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// `GET /embed/chart/{id}.png|svg`
+pub fn embed_route(
+    pool: DatabasePool,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("embed" / "chart" / String)
+        .and(warp::get())
+        .and(warp::any().map(move || pool.clone()))
+        .and(warp::query::<EmbedQuery>())
+        .and_then(embed_handler)
+}
+
+async fn embed_handler(
+    file: String,
+    pool: DatabasePool,
+    query: EmbedQuery,
+) -> Result<warp::reply::Response, Infallible> {
+    let start = Instant::now();
+    let reply = |status: StatusCode, response: warp::reply::Response| {
+        metrics::record_http_request("GET", ROUTE, status.as_u16(), start.elapsed().as_secs_f64());
+        Ok::<_, Infallible>(response)
+    };
+    let error = |status: StatusCode, message: &str| {
+        reply(
+            status,
+            warp::reply::with_status(warp::reply::json(&json!({ "error": message })), status)
+                .into_response(),
+        )
+    };
+
+    let Some((chart_id, format)) = parse_embed_file(&file) else {
+        return error(StatusCode::NOT_FOUND, "Chart not found");
+    };
+    let chart = match SavedChart::find_public(&pool, chart_id).await {
+        Ok(Some(chart)) => chart,
+        Ok(None) => return error(StatusCode::NOT_FOUND, "Chart not found"),
+        Err(e) => {
+            tracing::error!("Failed to load chart {} for embedding: {}", chart_id, e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render chart");
+        }
+    };
+
+    let (width, height) = chart_render::image_size(query.width, query.height);
+    let policy = shared_public_tier_policy();
+    let image = match chart_image(&pool, chart, policy, width, height).await {
+        Ok(image) => image,
+        Err(e) => {
+            tracing::error!("Failed to load data for chart {}: {}", chart_id, e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render chart");
+        }
+    };
+
+    let svg = chart_render::render_svg(&image);
+    let body = match format {
+        ImageFormat::Svg => svg.into_bytes(),
+        // Rasterizing is CPU bound, keep it off the request threads
+        ImageFormat::Png => {
+            match tokio::task::spawn_blocking(move || chart_render::render_png(&svg)).await {
+                Ok(Ok(png)) => png,
+                Ok(Err(e)) => {
+                    tracing::error!("Failed to rasterize chart {}: {}", chart_id, e);
+                    return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render chart");
+                }
+                Err(e) => {
+                    tracing::error!("Chart {} rendering task failed: {}", chart_id, e);
+                    return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to render chart");
+                }
+            }
+        }
+    };
+
+    let response = warp::reply::with_header(
+        warp::reply::with_header(body, CONTENT_TYPE, format.content_type()),
+        CACHE_CONTROL,
+        CACHE_CONTROL_VALUE,
+    );
+    reply(StatusCode::OK, response.into_response())
+}
+
+/// Load and transform the series of a saved chart for rendering
+///
+/// Series that no longer exist or that anonymous users may not read are left
+/// out. Each line is reduced to about one point per horizontal pixel and its
+/// values are rounded by `policy`.
+async fn chart_image(
+    pool: &DatabasePool,
+    chart: SavedChart,
+    policy: &PublicTierPolicy,
+    width: u32,
+    height: u32,
+) -> AppResult<ChartImage> {
+    let mut lines = Vec::with_capacity(chart.series_ids.len());
+
+    for &series_id in &chart.series_ids {
+        let Some(series) = series_service::get_series_by_id(pool, series_id).await? else {
+            continue;
+        };
+        let readable = DataSource::find_by_id(pool, series.source_id)
+            .await?
+            .is_some_and(|source| source.access_policy.permits(None));
+        if !readable {
+            continue;
+        }
+
+        let params = DataQueryParams {
+            series_id,
+            start_date: chart.start_date,
+            end_date: chart.end_date,
+            original_only: None,
+            latest_revision_only: Some(true),
+            exclude_corrections: None,
+            limit: None,
+            offset: None,
+        };
+        let key = DataPointCacheKey::from_params(&params);
+        let window = shared_data_point_cache()
+            .get_or_load(key.clone(), || {
+                series_service::get_series_data_window(pool, &params)
+            })
+            .await?;
+
+        let transformation = series_transformation(&chart.transformations, series_id);
+        let label = match transformation {
+            DataTransformation::None => series.title,
+            ref transformation => format!("{} ({})", series.title, transformation),
+        };
+        let points = match transformation {
+            DataTransformation::None => window,
+            transformation => {
+                apply_cached_transformation(key, &window, transformation.into()).await?
+            }
+        };
+
+        let points =
+            series_service::downsample(&points, width as usize, DownsamplingAlgorithm::MinMax);
+        lines.push(ChartLine {
+            label,
+            points: points
+                .iter()
+                .filter_map(|point| {
+                    Some((point.date, policy.value(point.value.as_ref()?).to_f64()?))
+                })
+                .collect(),
+        });
+    }
+
+    Ok(ChartImage {
+        title: chart.title,
+        subtitle: chart.description,
+        lines,
+        width,
+        height,
+    })
+}
+
+/// Transformation a chart applies to one of its series
+fn series_transformation(
+    transformations: &serde_json::Value,
+    series_id: Uuid,
+) -> DataTransformation {
+    transformations
+        .get(series_id.to_string())
+        .and_then(|name| name.as_str())
+        .map(|name| DataTransformation::from(name.to_string()))
+        .unwrap_or(DataTransformation::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_embed_file_and_transformation() {
+        // REQUIREMENT: Saved charts are embeddable at /embed/chart/{id}.png or .svg
+        // PURPOSE: Verify the path segment yields the chart ID and format, and transformations are read per series
+        // This ensures embeds render the chart the way its owner saved it
+
+        let id = Uuid::new_v4();
+        assert_eq!(
+            parse_embed_file(&format!("{}.png", id)),
+            Some((id, ImageFormat::Png))
+        );
+        assert_eq!(
+            parse_embed_file(&format!("{}.SVG", id)),
+            Some((id, ImageFormat::Svg))
+        );
+        assert_eq!(parse_embed_file(&format!("{}.gif", id)), None);
+        assert_eq!(parse_embed_file("chart.png"), None);
+        assert_eq!(parse_embed_file(&id.to_string()), None);
+
+        let other = Uuid::new_v4();
+        let transformations = json!({ id.to_string(): "YEAR_OVER_YEAR", other.to_string(): 7 });
+        assert_eq!(
+            series_transformation(&transformations, id),
+            DataTransformation::YearOverYear
+        );
+        assert_eq!(
+            series_transformation(&transformations, other),
+            DataTransformation::None
+        );
+        assert_eq!(
+            series_transformation(&transformations, Uuid::new_v4()),
+            DataTransformation::None
+        );
+    }
+}
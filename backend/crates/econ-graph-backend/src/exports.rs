@@ -0,0 +1,159 @@
+//! Export file downloads
+//!
+//! `GET /exports/{id}/download?expires=...&signature=...` returns the file of a
+//! completed export job. Links come from the `downloadUrl` field of an
+//! `ExportJob` in GraphQL. Their signature stands in for a session, so they
+//! work from a browser or curl until they expire.
+
+use chrono::{DateTime, Utc};
+use econ_graph_core::models::ExportJob;
+use econ_graph_core::{AppError, DatabasePool};
+use econ_graph_services::services::export_service::{
+    shared_download_signer, DownloadSigner, ExportStorage,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
+use warp::http::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE};
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+use crate::metrics;
+
+const ROUTE: &str = "/exports/download";
+
+/// Query parameters of a signed download link
+#[derive(Debug, Deserialize)]
+pub struct DownloadQuery {
+    /// Unix time the link expires at
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Whether a link was signed for this job and has not expired
+pub fn is_valid_download(
+    signer: &DownloadSigner,
+    job_id: Uuid,
+    query: &DownloadQuery,
+    now: DateTime<Utc>,
+) -> bool {
+    query.expires > now.timestamp() && signer.verify(job_id, query.expires, &query.signature)
+}
+
+/// `GET /exports/{id}/download`
+pub fn exports_route(
+    pool: DatabasePool,
+    storage: Arc<dyn ExportStorage>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("exports" / Uuid / "download")
+        .and(warp::get())
+        .and(warp::any().map(move || pool.clone()))
+        .and(warp::any().map(move || storage.clone()))
+        .and(warp::query::<DownloadQuery>())
+        .and_then(download_handler)
+}
+
+async fn download_handler(
+    job_id: Uuid,
+    pool: DatabasePool,
+    storage: Arc<dyn ExportStorage>,
+    query: DownloadQuery,
+) -> Result<warp::reply::Response, Infallible> {
+    let start = Instant::now();
+    let reply = |status: StatusCode, response: warp::reply::Response| {
+        metrics::record_http_request("GET", ROUTE, status.as_u16(), start.elapsed().as_secs_f64());
+        Ok::<_, Infallible>(response)
+    };
+    let error = |status: StatusCode, message: &str| {
+        reply(
+            status,
+            warp::reply::with_status(warp::reply::json(&json!({ "error": message })), status)
+                .into_response(),
+        )
+    };
+
+    let now = Utc::now();
+    if !is_valid_download(shared_download_signer(), job_id, &query, now) {
+        return error(
+            StatusCode::FORBIDDEN,
+            "Download link is invalid or has expired",
+        );
+    }
+
+    let job = match ExportJob::find_by_id(&pool, job_id).await {
+        Ok(Some(job)) => job,
+        Ok(None) => return error(StatusCode::NOT_FOUND, "Export not found"),
+        Err(e) => {
+            tracing::error!("Failed to load export {}: {}", job_id, e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load export");
+        }
+    };
+    let (Some(key), Ok(format)) = (job.storage_key.as_deref(), job.export_format()) else {
+        return error(StatusCode::GONE, "Export is no longer available");
+    };
+    if !job.is_downloadable(now) {
+        return error(StatusCode::GONE, "Export is no longer available");
+    }
+
+    let contents = match storage.get(key).await {
+        Ok(contents) => contents,
+        Err(AppError::NotFound(_)) => {
+            return error(StatusCode::GONE, "Export is no longer available")
+        }
+        Err(e) => {
+            tracing::error!("Failed to read export {}: {}", job_id, e);
+            return error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to load export");
+        }
+    };
+
+    let filename = format!("econgraph-export-{}.{}", job_id, format.as_str());
+    let response = warp::reply::with_header(
+        warp::reply::with_header(
+            warp::reply::with_header(contents, CONTENT_TYPE, format.content_type()),
+            CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        ),
+        CACHE_CONTROL,
+        "private, no-store",
+    );
+    reply(StatusCode::OK, response.into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_link_validation() {
+        // REQUIREMENT: Export files are only served through signed, time-limited links
+        // PURPOSE: Verify a link is accepted only for its own job, with its own expiry, before it expires
+        // This ensures a leaked or edited link cannot fetch another user's export
+
+        let signer = DownloadSigner::new("export-secret", "");
+        let job_id = Uuid::new_v4();
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let link = |expires: i64, signature: String| DownloadQuery { expires, signature };
+
+        let expires = now.timestamp() + 60;
+        let valid = link(expires, signer.signature(job_id, expires));
+        assert!(is_valid_download(&signer, job_id, &valid, now));
+        assert!(!is_valid_download(&signer, Uuid::new_v4(), &valid, now));
+        assert!(!is_valid_download(
+            &signer,
+            job_id,
+            &link(expires + 3600, valid.signature.clone()),
+            now
+        ));
+
+        let expired = now.timestamp() - 1;
+        assert!(!is_valid_download(
+            &signer,
+            job_id,
+            &link(expired, signer.signature(job_id, expired)),
+            now
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+//! HTTP caching for the GraphQL route
+//!
+//! Clients may send automatic persisted queries: once a query has been sent
+//! with its SHA-256 in the `persistedQuery` extension, later requests only need
+//! the hash, which keeps GET URLs short. GET queries are answered from the
+//! services' [`ResponseCache`](econ_graph_services::services::response_cache::ResponseCache)
+//! and carry an ETag derived from the `updated_at` of the series named in their
+//! variables, so clients revalidate with `If-None-Match` and get
+//! `304 Not Modified` while the data is unchanged.
+//!
+//! Other queries are cached by their fields' cache hints (see
+//! [`cache_control`](econ_graph_graphql::graphql::cache_control)): a response
+//! with a nonzero `maxAge` is reused for that long, shared between users when
+//! its scope is `PUBLIC` and kept per user when it is `PRIVATE`.
+
+use async_graphql::{Request, Response, ServerError, Value};
+use async_graphql_warp::GraphQLResponse;
+use econ_graph_core::DatabasePool;
+use econ_graph_graphql::graphql::cache_control::{CachePolicy, CacheScope};
+use econ_graph_services::services::response_cache::{
+    entity_tag, if_none_match_matches, query_hash, series_ids_in_variables, series_version,
+    shared_response_cache, ResponseCacheKey,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use warp::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, VARY};
+use warp::http::{HeaderValue, StatusCode};
+use warp::Reply;
+
+/// Error message Apollo clients expect when a hash is unknown; they resend the full query
+pub const PERSISTED_QUERY_NOT_FOUND: &str = "PersistedQueryNotFound";
+
+/// Query documents registered by automatic persisted queries, keyed by SHA-256
+pub struct PersistedQueries {
+    queries: Mutex<HashMap<String, String>>,
+    capacity: usize,
+}
+
+impl Default for PersistedQueries {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl PersistedQueries {
+    /// Create a store holding at most `capacity` queries
+    ///
+    /// When full, an arbitrary query is dropped; its clients get
+    /// [`PERSISTED_QUERY_NOT_FOUND`] once and register it again.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queries: Mutex::new(HashMap::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Fill in or register the query of a persisted query request
+    ///
+    /// Requests without the `persistedQuery` extension are left alone. Returns
+    /// the error response to send when the hash is unknown or does not match
+    /// the query sent with it.
+    pub fn resolve(&self, request: &mut Request) -> Result<(), Response> {
+        let Some(hash) = persisted_query_hash(request) else {
+            return Ok(());
+        };
+        let mut queries = self.queries.lock().unwrap_or_else(|e| e.into_inner());
+
+        if request.query.is_empty() {
+            return match queries.get(&hash) {
+                Some(query) => {
+                    request.query = query.clone();
+                    Ok(())
+                }
+                None => Err(error_response(PERSISTED_QUERY_NOT_FOUND)),
+            };
+        }
+
+        if query_hash(&request.query) != hash {
+            return Err(error_response("provided sha does not match query"));
+        }
+        if !queries.contains_key(&hash) && queries.len() >= self.capacity {
+            let evicted = queries.keys().next().cloned();
+            if let Some(evicted) = evicted {
+                queries.remove(&evicted);
+            }
+        }
+        queries.insert(hash, request.query.clone());
+        Ok(())
+    }
+}
+
+/// `sha256Hash` of the request's `persistedQuery` extension
+fn persisted_query_hash(request: &Request) -> Option<String> {
+    let Value::Object(persisted_query) = request.extensions.get("persistedQuery")? else {
+        return None;
+    };
+    match persisted_query.get("sha256Hash")? {
+        Value::String(hash) => Some(hash.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+fn error_response(message: &str) -> Response {
+    Response::from_errors(vec![ServerError::new(message, None)])
+}
+
+/// Answer a GET query from the response cache, running `execute` on a miss
+///
+/// Returns `304 Not Modified` when `if_none_match` names the current ETag.
+/// Only responses without errors are cached. If the series version cannot be
+/// read the query runs uncached.
+pub async fn cached_query<F, Fut>(
+    pool: &DatabasePool,
+    request: Request,
+    user_id: Option<uuid::Uuid>,
+    if_none_match: Option<&str>,
+    execute: F,
+) -> warp::reply::Response
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let cache = shared_response_cache();
+    let variables = serde_json::to_value(&request.variables).unwrap_or_default();
+    let series_ids = series_ids_in_variables(&variables);
+    let key = ResponseCacheKey::new(
+        query_hash(&request.query),
+        request.operation_name.clone(),
+        &variables,
+        user_id,
+    );
+
+    // Read the generation before the version so a write in between moves the ETag
+    let generation = cache.generation();
+    let version = match series_version(pool, &series_ids).await {
+        Ok(version) => version,
+        Err(e) => {
+            tracing::warn!(
+                "Serving GraphQL query uncached, series version unavailable: {}",
+                e
+            );
+            return GraphQLResponse::from(execute(request).await).into_response();
+        }
+    };
+    let etag = entity_tag(&key, version, generation);
+    let public = user_id.is_none();
+
+    if if_none_match.is_some_and(|header| if_none_match_matches(header, &etag)) {
+        let response = warp::reply::with_status(warp::reply(), StatusCode::NOT_MODIFIED);
+        return with_cache_headers(response.into_response(), &etag, public);
+    }
+
+    if let Some(body) = cache.get(&key, &etag) {
+        return with_cache_headers(json_response(body.as_str().to_owned()), &etag, public);
+    }
+
+    let response = execute(request).await;
+    if !response.is_ok() {
+        return GraphQLResponse::from(response).into_response();
+    }
+    let Ok(body) = serde_json::to_string(&response) else {
+        return GraphQLResponse::from(response).into_response();
+    };
+
+    let body = cache.insert(key, etag.clone(), body, series_ids);
+    with_cache_headers(json_response(body.as_str().to_owned()), &etag, public)
+}
+
+/// Answer a query from the responses its cache hints allow to reuse
+///
+/// Runs `execute` unless a response to the same query, variables and
+/// operation is still within its max age, either shared or cached for
+/// `user_id`. The response is cached when its policy allows; a `PRIVATE`
+/// response is only cached for signed-in users.
+pub async fn hinted_query<F, Fut>(
+    request: Request,
+    user_id: Option<uuid::Uuid>,
+    execute: F,
+) -> warp::reply::Response
+where
+    F: FnOnce(Request) -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let cache = shared_response_cache();
+    let variables = serde_json::to_value(&request.variables).unwrap_or_default();
+    let key_for = |user_id| {
+        ResponseCacheKey::new(
+            query_hash(&request.query),
+            request.operation_name.clone(),
+            &variables,
+            user_id,
+        )
+    };
+    let public_key = key_for(None);
+    let user_key = user_id.map(|user_id| key_for(Some(user_id)));
+
+    if let Some(body) = cache.get_hinted(&public_key) {
+        return json_response(body.as_str().to_owned());
+    }
+    if let Some(body) = user_key.as_ref().and_then(|key| cache.get_hinted(key)) {
+        return json_response(body.as_str().to_owned());
+    }
+
+    let response = execute(request).await;
+    let policy = match CachePolicy::from_response(&response) {
+        Some(policy) if policy.is_cacheable() && response.is_ok() => policy,
+        _ => return GraphQLResponse::from(response).into_response(),
+    };
+    let key = match policy.scope {
+        CacheScope::Public => public_key,
+        CacheScope::Private => match user_key {
+            Some(key) => key,
+            None => return GraphQLResponse::from(response).into_response(),
+        },
+    };
+    let Ok(body) = serde_json::to_string(&response) else {
+        return GraphQLResponse::from(response).into_response();
+    };
+
+    let max_age = Duration::from_secs(u64::from(policy.max_age));
+    let body = cache.insert_hinted(key, body, series_ids_in_variables(&variables), max_age);
+    json_response(body.as_str().to_owned())
+}
+
+fn json_response(body: String) -> warp::reply::Response {
+    warp::reply::with_header(body, CONTENT_TYPE, "application/json").into_response()
+}
+
+/// Add the ETag and revalidation headers to a cached query response
+///
+/// Anonymous responses may be kept by shared caches; responses for a signed-in
+/// user are private. Either way clients must revalidate before reuse.
+fn with_cache_headers(
+    mut response: warp::reply::Response,
+    etag: &str,
+    public: bool,
+) -> warp::reply::Response {
+    let headers = response.headers_mut();
+    if let Ok(etag) = HeaderValue::from_str(etag) {
+        headers.insert(ETAG, etag);
+    }
+    headers.insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static(if public {
+            "public, no-cache"
+        } else {
+            "private, no-cache"
+        }),
+    );
+    headers.insert(VARY, HeaderValue::from_static("Authorization"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn persisted(query: &str, hash: &str) -> Request {
+        let mut request = Request::new(query);
+        request.extensions.insert(
+            "persistedQuery".to_string(),
+            Value::from_json(serde_json::json!({ "version": 1, "sha256Hash": hash })).unwrap(),
+        );
+        request
+    }
+
+    #[test]
+    fn test_persisted_queries_register_and_resolve() {
+        // REQUIREMENT: GET requests can send a query hash instead of the full query
+        // PURPOSE: Verify a query is registered with its hash, found by hash later, and mismatches are rejected
+        // This ensures short GET URLs work and a hash can never be bound to a different query
+
+        let queries = PersistedQueries::default();
+        let query = "{ dataSources { name } }";
+        let hash = query_hash(query);
+
+        let unknown = queries.resolve(&mut persisted("", &hash)).unwrap_err();
+        assert_eq!(unknown.errors[0].message, PERSISTED_QUERY_NOT_FOUND);
+
+        assert!(queries.resolve(&mut persisted(query, &hash)).is_ok());
+        let mut by_hash = persisted("", &hash);
+        assert!(queries.resolve(&mut by_hash).is_ok());
+        assert_eq!(by_hash.query, query);
+
+        assert!(queries
+            .resolve(&mut persisted("{ me { id } }", &hash))
+            .is_err());
+
+        let mut plain = Request::new(query);
+        assert!(queries.resolve(&mut plain).is_ok());
+        assert_eq!(plain.query, query);
+    }
+
+    #[test]
+    fn test_cache_headers() {
+        // REQUIREMENT: Cached query responses can be revalidated but never shared across users
+        // PURPOSE: Verify the ETag, Cache-Control and Vary headers on public and private responses
+        // This ensures browsers and proxies revalidate and keep signed-in users' data private
+
+        let public = with_cache_headers(json_response("{}".to_string()), "W/\"abc\"", true);
+        assert_eq!(public.headers()[ETAG], "W/\"abc\"");
+        assert_eq!(public.headers()[CACHE_CONTROL], "public, no-cache");
+        assert_eq!(public.headers()[VARY], "Authorization");
+
+        let private = with_cache_headers(json_response("{}".to_string()), "W/\"abc\"", false);
+        assert_eq!(private.headers()[CACHE_CONTROL], "private, no-cache");
+    }
+}
@@ -0,0 +1,388 @@
+//! Security checks for the GraphQL route
+//!
+//! Runs [`SecurityMiddleware`] in front of every GraphQL request. Events are
+//! keyed by the real client IP. Requests with a valid token are rate limited
+//! per user under their role's quota, anonymous ones per IP (see
+//! [`rate_limit_key`]). Blocked requests are answered with the middleware's errors,
+//! their [`SecurityEvent`](econ_graph_graphql::security::SecurityEvent)s are
+//! forwarded to the [`SecurityMonitor`] and the configured event handler (which
+//! stores them for admins), and the middleware's metrics are mirrored into Prometheus.
+
+use async_graphql::{Request, Response};
+use econ_graph_core::auth_models::{Claims, UserRole};
+use econ_graph_graphql::graphql::context::rate_limit_key;
+use econ_graph_graphql::security::monitoring::{MonitoringConfig, SecurityMonitor};
+use econ_graph_graphql::security::{SecurityConfig, SecurityEventHandler, SecurityMiddleware};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use warp::http::HeaderMap;
+
+use crate::metrics;
+
+/// Source label attached to events recorded by the GraphQL route
+const EVENT_SOURCE: &str = "graphql";
+
+/// Security middleware and monitor shared by all GraphQL requests
+#[derive(Clone)]
+pub struct GraphQLSecurity {
+    middleware: Arc<SecurityMiddleware>,
+    monitor: Arc<SecurityMonitor>,
+    event_handler: Option<Arc<dyn SecurityEventHandler>>,
+}
+
+impl GraphQLSecurity {
+    /// Create the route security from explicit configuration
+    pub fn new(config: SecurityConfig, monitoring: MonitoringConfig) -> Self {
+        Self {
+            middleware: Arc::new(SecurityMiddleware::new(config)),
+            monitor: Arc::new(SecurityMonitor::new(monitoring)),
+            event_handler: None,
+        }
+    }
+
+    /// Also pass events of blocked requests to `event_handler`
+    pub fn with_event_handler(mut self, event_handler: Arc<dyn SecurityEventHandler>) -> Self {
+        self.event_handler = Some(event_handler);
+        self
+    }
+
+    /// Create the route security from defaults and environment overrides
+    ///
+    /// - `GRAPHQL_RATE_LIMIT_PER_MINUTE`: per-IP request limit per minute for anonymous requests
+    /// - `GRAPHQL_RATE_LIMIT_PER_MINUTE_{ADMIN,ANALYST,VIEWER}`: per-user request limit per minute by role
+    /// - `GRAPHQL_ALLOW_INTROSPECTION=true`: allow schema introspection for everyone (playground)
+    /// - `GRAPHQL_INTROSPECTION_ROLES`: comma-separated roles that may introspect, e.g.
+    ///   `admin,analyst` (default `admin`)
+    pub fn from_env() -> Self {
+        let mut config = SecurityConfig::default();
+
+        if let Some(limit) = env_limit("GRAPHQL_RATE_LIMIT_PER_MINUTE") {
+            config.rate_limit.requests_per_minute = limit;
+        }
+
+        for (role, suffix) in [
+            (UserRole::Admin, "ADMIN"),
+            (UserRole::Analyst, "ANALYST"),
+            (UserRole::Viewer, "VIEWER"),
+        ] {
+            if let Some(limit) = env_limit(&format!("GRAPHQL_RATE_LIMIT_PER_MINUTE_{}", suffix)) {
+                config
+                    .rate_limit
+                    .user_quotas
+                    .for_role_mut(&role)
+                    .requests_per_minute = limit;
+            }
+        }
+
+        if std::env::var("GRAPHQL_ALLOW_INTROSPECTION").is_ok_and(|value| value == "true") {
+            config.protect_introspection = false;
+        }
+
+        if let Ok(roles) = std::env::var("GRAPHQL_INTROSPECTION_ROLES") {
+            config.introspection_roles = roles
+                .split(',')
+                .map(str::trim)
+                .filter(|role| !role.is_empty())
+                .filter_map(|role| match role.to_lowercase().as_str() {
+                    "admin" => Some(UserRole::Admin),
+                    "analyst" => Some(UserRole::Analyst),
+                    "viewer" => Some(UserRole::Viewer),
+                    _ => {
+                        tracing::warn!(
+                            "Ignoring unknown role '{}' in GRAPHQL_INTROSPECTION_ROLES",
+                            role
+                        );
+                        None
+                    }
+                })
+                .collect();
+        }
+
+        Self::new(config, MonitoringConfig::default())
+    }
+
+    /// Whether callers with `role` may introspect the schema
+    ///
+    /// Lets the route skip the session lookup for everyone else.
+    pub fn trusts(&self, role: &UserRole) -> bool {
+        self.middleware.config().introspection_roles.contains(role)
+    }
+
+    /// Check a request, returning the error response to send when it is blocked
+    ///
+    /// `claims` are the caller's signature-verified token claims, if any, and
+    /// key the rate limit. `trusted_role` is the current role of a caller whose
+    /// session is active; only it can unlock introspection.
+    pub async fn check(
+        &self,
+        request: &Request,
+        client_ip: &str,
+        claims: Option<&Claims>,
+        trusted_role: Option<&UserRole>,
+    ) -> Result<(), Response> {
+        let violations = match claims {
+            Some(claims) => {
+                self.middleware
+                    .inspect_authenticated_request(request, client_ip, claims, trusted_role)
+                    .await
+            }
+            None => {
+                self.middleware
+                    .inspect_request_with_key(request, client_ip, &rate_limit_key(None, client_ip))
+                    .await
+            }
+        };
+
+        let blocked: Vec<_> = violations
+            .iter()
+            .map(|violation| violation.reason)
+            .collect();
+        metrics::record_graphql_security_check(&blocked, &self.middleware.metrics());
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let mut errors = Vec::with_capacity(violations.len());
+        for violation in violations {
+            if let Some(handler) = &self.event_handler {
+                match claims {
+                    Some(claims) => handler.handle_user_event(violation.event.clone(), claims),
+                    None => handler.handle_event(violation.event.clone()),
+                }
+            }
+            self.monitor
+                .record_event(violation.event, EVENT_SOURCE.to_string())
+                .await;
+            errors.push(violation.error);
+        }
+
+        Err(Response::from_errors(errors))
+    }
+}
+
+/// Numeric limit from an environment variable, if set and valid
+fn env_limit(name: &str) -> Option<u32> {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+}
+
+/// Resolve the client IP for a request
+///
+/// Forwarding headers are only honoured when the peer is a loopback or
+/// private address (our ingress or load balancer); otherwise any client could
+/// spoof `X-Forwarded-For` to dodge per-IP rate limits. `X-Forwarded-For` is
+/// walked right to left and the first hop that is not itself a trusted proxy
+/// is taken as the client.
+pub fn client_ip(headers: &HeaderMap, remote: Option<SocketAddr>) -> String {
+    let peer = remote.map(|addr| addr.ip().to_canonical());
+
+    if peer.is_none_or(is_trusted_proxy) {
+        if let Some(forwarded) = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+        {
+            let hops: Vec<IpAddr> = forwarded
+                .split(',')
+                .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+                .map(|ip| ip.to_canonical())
+                .collect();
+
+            if let Some(ip) = hops
+                .iter()
+                .rev()
+                .find(|ip| !is_trusted_proxy(**ip))
+                .or(hops.first())
+            {
+                return ip.to_string();
+            }
+        }
+
+        if let Some(ip) = headers
+            .get("x-real-ip")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok())
+        {
+            return ip.to_canonical().to_string();
+        }
+    }
+
+    peer.map(|ip| ip.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Whether an address belongs to infrastructure we run (and may set forwarding headers)
+fn is_trusted_proxy(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_loopback() || (v6.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use econ_graph_core::auth_models::SubscriptionTier;
+    use warp::http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_client_ip_behind_proxy() {
+        // REQUIREMENT: Rate limiting keyed by the real client IP behind the ingress
+        // PURPOSE: Verify X-Forwarded-For is resolved to the first untrusted hop
+        // This ensures all users behind the load balancer do not share one rate limit
+
+        let proxy = Some("10.0.0.5:443".parse().unwrap());
+
+        assert_eq!(
+            client_ip(
+                &headers(&[("x-forwarded-for", "203.0.113.7, 10.0.0.9")]),
+                proxy
+            ),
+            "203.0.113.7"
+        );
+        assert_eq!(
+            client_ip(
+                &headers(&[("x-forwarded-for", "198.51.100.1, 203.0.113.7")]),
+                proxy
+            ),
+            "203.0.113.7"
+        );
+        assert_eq!(
+            client_ip(&headers(&[("x-real-ip", "203.0.113.8")]), proxy),
+            "203.0.113.8"
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), proxy), "10.0.0.5");
+    }
+
+    fn claims(sub: &str, role: UserRole) -> Claims {
+        Claims {
+            sub: sub.to_string(),
+            email: format!("{}@example.com", sub),
+            name: sub.to_string(),
+            role,
+            exp: 0,
+            iat: 0,
+            iss: "econ-graph".to_string(),
+            sid: None,
+            tier: SubscriptionTier::Free,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_charged_per_user() {
+        // REQUIREMENT: Per-user rate limits for authenticated GraphQL requests
+        // PURPOSE: Verify users behind one address are limited independently by role quota
+        // This ensures one busy user cannot lock out colleagues or anonymous clients sharing a NAT
+
+        let mut config = SecurityConfig::default();
+        config.rate_limit.requests_per_minute = 1;
+        config.rate_limit.user_quotas.viewer.requests_per_minute = 1;
+        config.rate_limit.user_quotas.analyst.requests_per_minute = 2;
+        let security = GraphQLSecurity::new(config, MonitoringConfig::default());
+        let request = || Request::new("{ dataSources { id name } }");
+        let ip = "203.0.113.7";
+        let viewer = claims("a", UserRole::Viewer);
+        let analyst = claims("b", UserRole::Analyst);
+
+        assert!(security
+            .check(&request(), ip, Some(&viewer), None)
+            .await
+            .is_ok());
+        assert!(security
+            .check(&request(), ip, Some(&viewer), None)
+            .await
+            .is_err());
+        assert!(security
+            .check(&request(), ip, Some(&analyst), None)
+            .await
+            .is_ok());
+        assert!(security
+            .check(&request(), ip, Some(&analyst), None)
+            .await
+            .is_ok());
+        assert!(security
+            .check(&request(), ip, Some(&analyst), None)
+            .await
+            .is_err());
+        assert!(security.check(&request(), ip, None, None).await.is_ok());
+
+        let metrics = security.middleware.metrics();
+        assert_eq!(metrics.user_requests, 5);
+        assert_eq!(metrics.user_rate_limited_requests, 2);
+        assert_eq!(metrics.rate_limited_requests, 2);
+    }
+
+    #[tokio::test]
+    async fn test_introspection_for_trusted_roles() {
+        // REQUIREMENT: Schema introspection for trusted roles only
+        // PURPOSE: Verify admins with an active session may introspect while anonymous users, viewers and revoked admin tokens may not
+        // This ensures tooling works for admins without exposing the schema, and __typename works for all
+
+        let security = GraphQLSecurity::new(SecurityConfig::default(), MonitoringConfig::default());
+        let introspection = || Request::new("{ __schema { types { name } } }");
+        let ip = "203.0.113.7";
+        let admin = claims("a", UserRole::Admin);
+
+        assert!(security.trusts(&UserRole::Admin));
+        assert!(!security.trusts(&UserRole::Viewer));
+        assert!(security
+            .check(&introspection(), ip, Some(&admin), Some(&UserRole::Admin))
+            .await
+            .is_ok());
+        // A signed admin token whose session was revoked, or whose user was demoted
+        assert!(security
+            .check(&introspection(), ip, Some(&admin), None)
+            .await
+            .is_err());
+        assert!(security
+            .check(&introspection(), ip, Some(&admin), Some(&UserRole::Viewer))
+            .await
+            .is_err());
+        assert!(security
+            .check(&introspection(), ip, None, None)
+            .await
+            .is_err());
+        assert!(security
+            .check(
+                &introspection(),
+                ip,
+                Some(&claims("v", UserRole::Viewer)),
+                Some(&UserRole::Viewer)
+            )
+            .await
+            .is_err());
+        assert!(security
+            .check(
+                &Request::new("{ dataSources { __typename id name } }"),
+                ip,
+                None,
+                None
+            )
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn test_client_ip_ignores_spoofed_headers() {
+        // REQUIREMENT: Security checks cannot be bypassed by forged headers
+        // PURPOSE: Verify forwarding headers from public peers are ignored
+        // This ensures a client cannot rotate X-Forwarded-For to evade rate limits
+
+        let public_peer = Some("198.51.100.20:52000".parse().unwrap());
+
+        assert_eq!(
+            client_ip(&headers(&[("x-forwarded-for", "203.0.113.7")]), public_peer),
+            "198.51.100.20"
+        );
+        assert_eq!(client_ip(&HeaderMap::new(), None), "unknown");
+    }
+}
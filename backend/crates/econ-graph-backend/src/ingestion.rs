@@ -0,0 +1,182 @@
+//! HTTP endpoint for crawlers running outside the backend
+//!
+//! `POST /ingest/data-points` accepts an NDJSON or Arrow IPC stream of
+//! observations and hands it to [`StreamIngestor`], which validates and commits
+//! the stream batch by batch as it is read. The response is the
+//! [`StreamIngestionReport`](econ_graph_services::services::crawler::StreamIngestionReport).
+//!
+//! Configuration:
+//! - `INGEST_API_TOKEN`: bearer token crawlers must send; the endpoint is disabled without it
+//! - `INGEST_MAX_CONCURRENT_STREAMS`: streams processed at once (default 4); more get 429
+//! - `INGEST_BATCH_SIZE`: records validated and committed together (default 5000)
+
+use econ_graph_core::DatabasePool;
+use econ_graph_services::services::crawler::stream_ingestion::StreamIngestionConfig;
+use econ_graph_services::services::crawler::{StreamFormat, StreamIngestor};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio_stream::{Stream, StreamExt};
+use warp::http::StatusCode;
+use warp::{Buf, Filter};
+
+use crate::metrics;
+use crate::service_auth::ServiceToken;
+
+const ROUTE: &str = "/ingest/data-points";
+
+/// Source label used when the crawler does not name itself
+const DEFAULT_SOURCE: &str = "external";
+
+/// Query parameters of an ingestion request
+#[derive(Debug, Deserialize)]
+pub struct IngestQuery {
+    /// Name of the pushing crawler, used in logs and validation metrics
+    pub source: Option<String>,
+}
+
+/// Shared state of the ingestion endpoint
+pub struct IngestionEndpoint {
+    pool: DatabasePool,
+    token: ServiceToken,
+    ingestor: StreamIngestor,
+    streams: Semaphore,
+}
+
+impl IngestionEndpoint {
+    pub fn new(
+        pool: DatabasePool,
+        token: Option<String>,
+        config: StreamIngestionConfig,
+        max_concurrent_streams: usize,
+    ) -> Self {
+        Self {
+            pool,
+            token: ServiceToken::new(token),
+            ingestor: StreamIngestor::new(config),
+            streams: Semaphore::new(max_concurrent_streams),
+        }
+    }
+
+    /// Create the endpoint from environment variables
+    pub fn from_env(pool: DatabasePool) -> Self {
+        let parse = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<usize>().ok())
+                .filter(|value| *value > 0)
+        };
+        let mut config = StreamIngestionConfig::default();
+        if let Some(batch_size) = parse("INGEST_BATCH_SIZE") {
+            config.batch_size = batch_size;
+        }
+
+        Self::new(
+            pool,
+            std::env::var("INGEST_API_TOKEN").ok(),
+            config,
+            parse("INGEST_MAX_CONCURRENT_STREAMS").unwrap_or(4),
+        )
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_configured()
+    }
+}
+
+/// `POST /ingest/data-points`
+pub fn ingestion_route(
+    endpoint: Arc<IngestionEndpoint>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("ingest" / "data-points")
+        .and(warp::post())
+        .and(warp::any().map(move || endpoint.clone()))
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::query::<IngestQuery>())
+        .and(warp::body::stream())
+        .and_then(ingest_handler)
+}
+
+async fn ingest_handler<S, B>(
+    endpoint: Arc<IngestionEndpoint>,
+    authorization: Option<String>,
+    content_type: Option<String>,
+    query: IngestQuery,
+    body: S,
+) -> Result<impl warp::Reply, Infallible>
+where
+    S: Stream<Item = Result<B, warp::Error>> + Send + 'static,
+    B: Buf,
+{
+    let start = Instant::now();
+    let reply = |status: StatusCode, body: serde_json::Value| {
+        metrics::record_http_request(
+            "POST",
+            ROUTE,
+            status.as_u16(),
+            start.elapsed().as_secs_f64(),
+        );
+        Ok::<_, Infallible>(warp::reply::with_status(warp::reply::json(&body), status))
+    };
+
+    if !endpoint.is_enabled() {
+        return reply(
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "error": "Ingestion endpoint is not configured" }),
+        );
+    }
+    if !endpoint.token.authorizes(authorization.as_deref()) {
+        return reply(
+            StatusCode::UNAUTHORIZED,
+            json!({ "error": "Missing or invalid ingestion token" }),
+        );
+    }
+    let Some(format) = content_type
+        .as_deref()
+        .and_then(StreamFormat::from_content_type)
+    else {
+        return reply(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            json!({
+                "error": "Content-Type must be application/x-ndjson or application/vnd.apache.arrow.stream"
+            }),
+        );
+    };
+    let Ok(_permit) = endpoint.streams.try_acquire() else {
+        return reply(
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({ "error": "Too many ingestion streams in progress, retry later" }),
+        );
+    };
+
+    let source = query.source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+    let body = Box::pin(
+        body.map(|chunk| chunk.map(|mut chunk| chunk.copy_to_bytes(chunk.remaining()).to_vec())),
+    );
+
+    match endpoint
+        .ingestor
+        .ingest(&endpoint.pool, &source, format, body)
+        .await
+    {
+        Ok(report) => {
+            let status = if report.aborted.is_some() {
+                StatusCode::BAD_REQUEST
+            } else {
+                StatusCode::OK
+            };
+            reply(status, json!(report))
+        }
+        Err(e) => {
+            tracing::error!("Ingestion stream from {} failed: {}", source, e);
+            reply(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": "Failed to store ingested data points" }),
+            )
+        }
+    }
+}
@@ -37,8 +37,12 @@
 //! }
 //! ```
 
+pub mod graphql_security;
+pub mod health;
+pub mod ingestion;
 pub mod integration_tests;
 pub mod metrics;
+pub mod service_auth;
 
 // Re-export commonly used types if needed
 // pub use crate::metrics::*;
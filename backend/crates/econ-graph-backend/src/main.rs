@@ -2,79 +2,236 @@
 // Licensed under the Microsoft Reference Source License (MS-RSL).
 // See LICENSE file for complete terms and conditions.
 
-use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_warp::GraphQLResponse;
-use serde_json::json;
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig, MultipartOptions};
+use async_graphql::parser::types::DocumentOperations;
+use async_graphql_warp::{GraphQLResponse, GraphQLWebSocket};
+use clap::Parser;
 use std::convert::Infallible;
 use std::sync::Arc;
-use tokio::signal;
-use tracing::info;
-use warp::Filter;
+use std::time::Duration;
+use tracing::{info, Instrument};
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter};
+use warp::{Filter, Reply};
 
 // Import from our new crates
 use econ_graph_auth::auth::{routes::auth_routes, services::AuthService};
-use econ_graph_core::{create_pool, AppError, AppResult, Config, DatabasePool};
-use econ_graph_graphql::graphql::schema::create_schema_with_data;
+use econ_graph_core::models::{
+    CatalogStatistics, ChangeFeedEntry, DEFAULT_CATALOG_STATISTICS_REFRESH_SECONDS,
+    DEFAULT_CHANGE_FEED_PRUNE_INTERVAL_SECONDS, DEFAULT_CHANGE_FEED_RETENTION_DAYS,
+    DEFAULT_STATUS_RECORD_INTERVAL_SECONDS,
+};
+use econ_graph_core::shutdown::{
+    shutdown_coordinator, shutdown_deadline_from_env, shutdown_signal,
+};
+use econ_graph_core::{create_pool, database, AppError, AppResult, ConfigArgs, DatabasePool};
+use econ_graph_graphql::graphql::context::GraphQLContext;
+use econ_graph_graphql::graphql::explain::EXPLAIN_HEADER;
+use econ_graph_graphql::graphql::schema::{create_schema_with_data, federation_sdl};
+use econ_graph_graphql::graphql::versioning;
+use econ_graph_graphql::security::event_store::DatabaseSecurityEventHandler;
 use econ_graph_mcp::mcp_server::{mcp_handler, EconGraphMcpServer};
+use econ_graph_metrics::logging::{self, CorrelationLayer, LogFormat};
+use econ_graph_metrics::telemetry::{self, Telemetry};
+use econ_graph_services::services::crawler::release_scheduler;
+use econ_graph_services::services::data_quality_service::{self, QualityConfig};
+use econ_graph_services::services::email_service::{
+    self, EmailDispatcher, EmailSettings, QueuedEmailHook,
+};
+use econ_graph_services::services::export_service::{
+    self, ExportStorage, ExportWorker, FilesystemExportStorage,
+};
+use econ_graph_services::services::notification_service::notification_hub;
+use econ_graph_services::services::queue_service;
+use econ_graph_services::services::response_cache::shared_response_cache;
+use econ_graph_services::services::revision_retention_service::{self, RetentionPolicy};
+use econ_graph_services::services::webhook_service::{self, WebhookDispatcher};
 
+mod changes;
+mod chart_render;
+mod drain;
+mod embed;
+mod exports;
+mod graphql_cache;
+mod graphql_security;
+mod health;
+mod ingestion;
 mod integration_tests;
 mod metrics;
+mod service_auth;
+mod status;
 // use services::crawler::start_crawler; // TODO: Implement start_crawler function
 
+/// How often the configuration file is checked for changes
+const CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Largest file a GraphQL multipart request may upload by default
+const DEFAULT_UPLOAD_MAX_FILE_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Files one GraphQL multipart request may upload
+const MAX_UPLOAD_FILES: usize = 1;
+
+/// EconGraph API server
+#[derive(Parser)]
+#[command(name = "econ-graph-backend")]
+#[command(about = "EconGraph API server")]
+#[command(version)]
+struct Args {
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    /// Print the federation subgraph SDL for gateway composition and exit
+    #[arg(long)]
+    print_subgraph_schema: bool,
+
+    /// Print the schema SDL with its version header, for the release snapshot, and exit
+    #[arg(long)]
+    print_schema: bool,
+
+    /// Compare the schema with a release snapshot and exit, failing on breaking
+    /// changes made without a major version bump
+    #[arg(long, value_name = "SNAPSHOT")]
+    check_schema_compat: Option<std::path::PathBuf>,
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub pool: DatabasePool,
     pub schema: async_graphql::Schema<
         econ_graph_graphql::graphql::query::Query,
         econ_graph_graphql::graphql::mutation::Mutation,
-        async_graphql::EmptySubscription,
+        econ_graph_graphql::graphql::Subscription,
     >,
 }
 
+/// Execute a GraphQL request with its request-scoped context and record its metrics
+///
+/// A successful mutation clears the GraphQL response cache, since mutations can
+/// change anything a cached query read.
 async fn graphql_handler(
     schema: async_graphql::Schema<
         econ_graph_graphql::graphql::query::Query,
         econ_graph_graphql::graphql::mutation::Mutation,
-        async_graphql::EmptySubscription,
+        econ_graph_graphql::graphql::Subscription,
     >,
-    request: async_graphql::Request,
-) -> Result<GraphQLResponse, Infallible> {
-    let start_time = std::time::Instant::now();
-
+    mut request: async_graphql::Request,
+    context: Arc<GraphQLContext>,
+) -> async_graphql::Response {
     // Extract operation info for metrics before consuming request
-    let operation_name_clone = request.operation_name.clone();
-    let operation_type = operation_name_clone.as_deref().unwrap_or("unknown");
-    let operation_name = operation_name_clone.as_deref().unwrap_or("anonymous");
+    let operation_type = operation_type(&mut request);
+    let operation_name = request
+        .operation_name
+        .clone()
+        .unwrap_or_else(|| "anonymous".to_string());
 
-    let response = schema.execute(request).await;
-    let duration = start_time.elapsed().as_secs_f64();
+    let response = schema.execute(request.data(context.clone())).await;
 
     // Record GraphQL metrics
     metrics::record_graphql_query(
-        operation_type,
-        operation_name,
-        duration,
+        &operation_type,
+        &operation_name,
+        context.metrics.elapsed().as_secs_f64(),
         1.0, // Basic complexity for now
     );
+    let denials = context.metrics.authorization_denials();
+    if denials > 0 {
+        metrics::record_graphql_authorization_denials(denials);
+    }
+
+    if operation_type == "mutation" && response.is_ok() {
+        shared_response_cache().invalidate_all();
+    }
 
-    Ok(GraphQLResponse::from(response))
+    response
+}
+
+/// Operation type of a request ("query", "mutation" or "subscription") for metric labels
+fn operation_type(request: &mut async_graphql::Request) -> String {
+    let operation_name = request.operation_name.clone();
+    let Ok(document) = request.parsed_query() else {
+        return "invalid".to_string();
+    };
+
+    let operation = match &document.operations {
+        DocumentOperations::Single(operation) => Some(operation),
+        DocumentOperations::Multiple(operations) => operation_name
+            .as_deref()
+            .and_then(|name| operations.get(name)),
+    };
+    operation
+        .map(|operation| operation.node.ty.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Bearer token of an `Authorization` header value
+fn bearer_token(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("Bearer ")
+        .filter(|token| !token.is_empty())
 }
 
 async fn graphql_playground() -> Result<impl warp::Reply, Infallible> {
     Ok(warp::reply::html(playground_source(
-        GraphQLPlaygroundConfig::new("/graphql"),
+        GraphQLPlaygroundConfig::new("/graphql").subscription_endpoint("/graphql/ws"),
     )))
 }
 
-async fn health_check() -> Result<impl warp::Reply, Infallible> {
-    // Record health check metrics
-    metrics::record_http_request("GET", "/health", 200, 0.0);
+/// Build the connection data for a GraphQL WebSocket from its `connection_init` payload
+///
+/// Clients send `{"Authorization": "Bearer <token>"}`. Connections without a
+/// valid token are accepted but unauthenticated, so user subscriptions fail.
+async fn graphql_ws_connection_data(
+    pool: DatabasePool,
+    payload: serde_json::Value,
+) -> async_graphql::Result<async_graphql::Data> {
+    let token = payload
+        .as_object()
+        .and_then(|fields| {
+            fields
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("authorization"))
+        })
+        .and_then(|(_, value)| value.as_str())
+        .and_then(bearer_token);
+
+    let (claims, user) = authenticate(&pool, token).await;
 
-    Ok(warp::reply::json(&json!({
-        "status": "healthy",
-        "service": "econ-graph-backend",
-        "version": env!("CARGO_PKG_VERSION")
-    })))
+    let mut data = async_graphql::Data::default();
+    data.insert(Arc::new(
+        GraphQLContext::new(&pool, user).with_claims(claims),
+    ));
+    Ok(data)
+}
+
+/// Resolve the claims and user of a bearer token
+///
+/// Invalid, expired or revoked tokens yield `(None, None)`; the request then
+/// continues unauthenticated.
+async fn authenticate(
+    pool: &DatabasePool,
+    token: Option<&str>,
+) -> (
+    Option<econ_graph_core::auth_models::Claims>,
+    Option<econ_graph_core::models::User>,
+) {
+    let Some(token) = token else {
+        return (None, None);
+    };
+
+    match AuthService::new(pool.clone())
+        .authenticate_token(token)
+        .await
+    {
+        Ok(claims) => {
+            let user = econ_graph_core::models::User::get_by_id(
+                pool,
+                claims.sub.parse().unwrap_or_default(),
+            )
+            .await
+            .ok();
+            (Some(claims), user)
+        }
+        Err(_) => (None, None),
+    }
 }
 
 async fn root_handler() -> Result<impl warp::Reply, Infallible> {
@@ -114,9 +271,19 @@ async fn root_handler() -> Result<impl warp::Reply, Infallible> {
             <p><a href="/playground">Interactive GraphQL Playground</a> - Test queries and explore the schema</p>
         </div>
 
+        <div class="endpoint">
+            <div><span class="method">GET</span> <code>/graphql/changelog</code></div>
+            <p><a href="/graphql/changelog">GraphQL schema change log</a> - Schema version, changes and deprecations</p>
+        </div>
+
         <div class="endpoint">
             <div><span class="method">GET</span> <code>/health</code></div>
-            <p><a href="/health">Health check endpoint</a> - API status and version info</p>
+            <p><a href="/health">Health check endpoint</a> - Database, crawl queue, and crawler status</p>
+        </div>
+
+        <div class="endpoint">
+            <div><span class="method">GET</span> <code>/status</code></div>
+            <p><a href="/status">Status page</a> - Current status and 90-day uptime of the database, GraphQL API and crawlers</p>
         </div>
 
         <div class="endpoint">
@@ -129,6 +296,26 @@ async fn root_handler() -> Result<impl warp::Reply, Infallible> {
             <p>MCP (Model Context Protocol) server endpoint - AI model integration for economic data access</p>
         </div>
 
+        <div class="endpoint">
+            <div><span class="method">POST</span> <code>/ingest/data-points</code></div>
+            <p>Streaming ingestion of NDJSON or Arrow IPC data points from external crawlers (token required)</p>
+        </div>
+
+        <div class="endpoint">
+            <div><span class="method">GET</span> <code>/embed/chart/{id}.png|svg</code></div>
+            <p>Image of a public saved chart for embedding in documents and social previews</p>
+        </div>
+
+        <div class="endpoint">
+            <div><span class="method">GET</span> <code>/exports/{id}/download</code></div>
+            <p>File of a completed bulk export, through the signed link from the <code>downloadUrl</code> field</p>
+        </div>
+
+        <div class="endpoint">
+            <div><span class="method">GET</span> <code>/changes?since=N&amp;wait=S</code></div>
+            <p>Long-poll of series and data point mutations after a sequence, for cache invalidation (token required)</p>
+        </div>
+
         <h2>🚀 Quick Start</h2>
         <p>Visit the <a href="/playground">GraphQL Playground</a> to start exploring economic data!</p>
 
@@ -152,16 +339,105 @@ async fn root_handler() -> Result<impl warp::Reply, Infallible> {
     ))
 }
 
+/// Log filter for a validated `logging.level`
+fn log_filter(level: &str) -> EnvFilter {
+    EnvFilter::try_new(level).unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
+/// Span for one HTTP request, carrying its request id for log correlation
+///
+/// The caller's `x-request-id` is used when it is safe to log; otherwise a
+/// new id is generated.
+fn http_request_span(info: warp::trace::Info) -> tracing::Span {
+    let request_id = logging::request_id_from_header(
+        info.request_headers()
+            .get(logging::REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok()),
+    );
+
+    tracing::info_span!(
+        "request",
+        method = %info.method(),
+        path = %info.path(),
+        request_id = %request_id,
+    )
+}
+
+/// Report schema changes since the release snapshot, failing if the version doesn't account for them
+fn check_schema_compat(snapshot: &std::path::Path) -> AppResult<()> {
+    let report = versioning::check_compatibility(&std::fs::read_to_string(snapshot)?)?;
+
+    println!(
+        "Schema {} compared with released {}",
+        report.current_version, report.released_version
+    );
+    for change in &report.changes {
+        let marker = if change.breaking { "BREAKING" } else { "added" };
+        println!("  [{}] {}", marker, change);
+    }
+
+    if report.is_compatible() {
+        Ok(())
+    } else if report.current_version < report.released_version {
+        Err(AppError::ValidationError(format!(
+            "Schema version {} is older than the released {}",
+            report.current_version, report.released_version
+        )))
+    } else {
+        Err(AppError::ValidationError(format!(
+            "{} breaking schema changes need a major version above {}; bump CURRENT_SCHEMA_VERSION",
+            report.breaking_changes().count(),
+            report.released_version
+        )))
+    }
+}
+
 #[tokio::main]
 async fn main() -> AppResult<()> {
-    // Initialize tracing with more detailed output
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::INFO)
-        .with_target(false)
-        .with_thread_ids(true)
-        .with_thread_names(true)
+    let args = Args::parse();
+
+    if args.print_subgraph_schema {
+        print!("{}", federation_sdl());
+        return Ok(());
+    }
+    if args.print_schema {
+        print!("{}", versioning::versioned_sdl());
+        return Ok(());
+    }
+    if let Some(snapshot) = &args.check_schema_compat {
+        return check_schema_compat(snapshot);
+    }
+
+    // Load configuration first so logging can use its log level
+    let config_loader = args.config.loader()?;
+    let config = config_loader.load().map_err(|e| {
+        eprintln!("❌ Failed to load configuration: {}", e);
+        e
+    })?;
+
+    // Initialize tracing with more detailed output (JSON when LOG_FORMAT=json),
+    // exporting spans over OTLP when configured
+    let telemetry = Telemetry::from_env("econ-graph-backend")
+        .map_err(|e| AppError::InternalError(format!("Failed to initialize tracing: {}", e)))?;
+    let log_format = LogFormat::from_env();
+    let (log_filter, log_filter_handle) = reload::Layer::new(log_filter(&config.logging.level));
+    tracing_subscriber::registry()
+        .with(log_filter)
+        .with(CorrelationLayer)
+        .with(log_format.is_text().then(|| {
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_thread_ids(true)
+                .with_thread_names(true)
+        }))
+        .with(log_format.is_json().then(logging::json_layer))
+        .with(telemetry.layer())
         .init();
 
+    if telemetry.is_exporting() {
+        info!("📡 Exporting traces over OTLP");
+    }
+
     info!(
         "🚀 Starting EconGraph Backend Server v{}",
         env!("CARGO_PKG_VERSION")
@@ -173,6 +449,7 @@ async fn main() -> AppResult<()> {
         "  - RUST_LOG: {:?}",
         std::env::var("RUST_LOG").unwrap_or_else(|_| "not set".to_string())
     );
+    info!("  - LOG_FORMAT: {:?}", log_format);
     info!(
         "  - BACKEND_PORT: {:?}",
         std::env::var("BACKEND_PORT").unwrap_or_else(|_| "not set".to_string())
@@ -198,21 +475,35 @@ async fn main() -> AppResult<()> {
         }
     );
 
-    // Load configuration
-    info!("📋 Loading configuration from environment...");
-    let config = Config::from_env().map_err(|e| {
-        let error = AppError::ConfigError(format!("Failed to load configuration: {}", e));
-        error.log_with_context("Application startup configuration loading");
-        eprintln!("❌ Failed to load configuration: {}", e);
-        error
-    })?;
-
     info!("📊 Configuration loaded successfully:");
+    info!(
+        "  - Config file: {}",
+        config_loader
+            .file()
+            .map_or_else(|| "none".to_string(), |file| file.display().to_string())
+    );
+    info!("  - Log level: {}", config.logging.level);
     info!("  - Server host: {}", config.server.host);
     info!("  - Server port: {}", config.server.port);
     info!("  - CORS origins: {:?}", config.cors.allowed_origins);
     info!("  - Database URL: {}", config.database_url);
 
+    // Apply log level and slow-query threshold changes from the configuration file without a restart
+    database::set_slow_query_threshold(config.database.slow_query_threshold());
+    let mut runtime_settings = config_loader.watch(&config, CONFIG_RELOAD_INTERVAL);
+    tokio::spawn(async move {
+        while runtime_settings.changed().await.is_ok() {
+            let settings = runtime_settings.borrow_and_update().clone();
+            match log_filter_handle.reload(log_filter(&settings.log_level)) {
+                Ok(()) => info!("Log level set to {}", settings.log_level),
+                Err(e) => tracing::warn!("Failed to apply log level {}: {}", settings.log_level, e),
+            }
+            database::set_slow_query_threshold(Duration::from_millis(
+                settings.slow_query_threshold_ms,
+            ));
+        }
+    });
+
     // Create database connection pool
     info!("🗄️  Creating database connection pool...");
     info!("  - Database URL: {}", config.database_url);
@@ -226,6 +517,9 @@ async fn main() -> AppResult<()> {
 
     info!("✅ Database connection pool created successfully");
 
+    // Export pool utilization and wait times for contention diagnosis
+    database::spawn_pool_monitor(pool.clone(), config.database.pool_metrics_interval());
+
     // Run migrations
     info!("🔄 Running database migrations...");
     econ_graph_core::run_migrations(&config.database_url)
@@ -261,6 +555,285 @@ async fn main() -> AppResult<()> {
         }
     });
 
+    // Merge duplicate pending crawls and watch the crawl dead-letter queue so
+    // poison items surface in metrics and logs
+    let dead_letter_pool = pool.clone();
+    let dead_letter_threshold = std::env::var("CRAWL_DEAD_LETTER_ALERT_THRESHOLD")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(queue_service::DEFAULT_DEAD_LETTER_ALERT_THRESHOLD);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            if let Err(e) = queue_service::coalesce_pending_items(&dead_letter_pool).await {
+                tracing::warn!("Failed to coalesce pending crawl queue items: {}", e);
+            }
+            if let Err(e) =
+                queue_service::check_dead_letter_queue(&dead_letter_pool, dead_letter_threshold)
+                    .await
+            {
+                tracing::warn!("Failed to check dead-letter queue: {}", e);
+            }
+        }
+    });
+
+    // Score newly ingested data for outliers, gaps and staleness
+    let data_quality_pool = pool.clone();
+    let data_quality_interval = std::env::var("DATA_QUALITY_CHECK_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(data_quality_service::DEFAULT_QUALITY_CHECK_INTERVAL_SECONDS);
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(data_quality_interval));
+        loop {
+            interval.tick().await;
+            if let Err(e) = data_quality_service::run_quality_check(
+                &data_quality_pool,
+                &QualityConfig::default(),
+            )
+            .await
+            {
+                tracing::warn!("Failed to run data quality check: {}", e);
+            }
+        }
+    });
+
+    // Prune old data point revisions; deletes only when REVISION_RETENTION_ENABLED=true
+    let retention_pool = pool.clone();
+    let retention_interval = std::env::var("REVISION_RETENTION_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(revision_retention_service::DEFAULT_RETENTION_INTERVAL_SECONDS);
+    let retention_enabled = std::env::var("REVISION_RETENTION_ENABLED")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+    tokio::spawn(async move {
+        let policy = RetentionPolicy::from_env();
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(retention_interval));
+        loop {
+            interval.tick().await;
+            match revision_retention_service::apply_retention(
+                &retention_pool,
+                &policy,
+                !retention_enabled,
+            )
+            .await
+            {
+                Ok(report) => {
+                    for line in report.render() {
+                        info!("{}", line);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to apply revision retention: {}", e),
+            }
+        }
+    });
+
+    // Queue workers register with the coordinator, so a shutdown stops new claims
+    // and waits for the deliveries and exports already in progress
+    let coordinator = shutdown_coordinator();
+
+    // Send due webhook deliveries, retrying failed ones with backoff
+    let webhook_dispatcher = WebhookDispatcher::new(pool.clone());
+    let webhook_coordinator = coordinator.clone();
+    let webhook_interval = std::env::var("WEBHOOK_DISPATCH_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(webhook_service::DEFAULT_WEBHOOK_DISPATCH_INTERVAL_SECONDS);
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(webhook_interval));
+        loop {
+            interval.tick().await;
+            let Some(_in_flight) = webhook_coordinator.try_track() else {
+                break;
+            };
+            if let Err(e) = webhook_dispatcher.deliver_due().await {
+                tracing::warn!("Failed to dispatch webhook deliveries: {}", e);
+            }
+        }
+    });
+
+    // Queue emails for invites, alerts and weekly digests, and send them with retries
+    match email_service::transport_from_env() {
+        Ok(Some(transport)) => {
+            email_service::configure_email(EmailSettings::from_env());
+            notification_hub().set_email_hook(Arc::new(QueuedEmailHook::new(pool.clone())));
+
+            let email_dispatcher = EmailDispatcher::new(pool.clone(), transport);
+            let email_coordinator = coordinator.clone();
+            let email_interval = std::env::var("EMAIL_DISPATCH_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(email_service::DEFAULT_EMAIL_DISPATCH_INTERVAL_SECONDS);
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(tokio::time::Duration::from_secs(email_interval));
+                loop {
+                    interval.tick().await;
+                    let Some(_in_flight) = email_coordinator.try_track() else {
+                        break;
+                    };
+                    if let Err(e) = email_dispatcher.deliver_due().await {
+                        tracing::warn!("Failed to dispatch emails: {}", e);
+                    }
+                }
+            });
+
+            let digest_pool = pool.clone();
+            let digest_coordinator = coordinator.clone();
+            let digest_interval = std::env::var("EMAIL_DIGEST_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(email_service::DEFAULT_EMAIL_DIGEST_INTERVAL_SECONDS);
+            tokio::spawn(async move {
+                let mut interval =
+                    tokio::time::interval(tokio::time::Duration::from_secs(digest_interval));
+                loop {
+                    interval.tick().await;
+                    let Some(_in_flight) = digest_coordinator.try_track() else {
+                        break;
+                    };
+                    if let Err(e) = email_service::queue_weekly_digests(&digest_pool).await {
+                        tracing::warn!("Failed to queue weekly digests: {}", e);
+                    }
+                }
+            });
+            info!("📧 Email delivery enabled");
+        }
+        Ok(None) => info!("EMAIL_TRANSPORT not set; emails are not sent"),
+        Err(e) => tracing::warn!("Emails are not sent: {}", e),
+    }
+
+    // Produce queued bulk exports and delete expired export files
+    let export_storage: Arc<dyn ExportStorage> = Arc::new(FilesystemExportStorage::from_env());
+    let export_retention = std::env::var("EXPORT_RETENTION_HOURS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(export_service::DEFAULT_EXPORT_RETENTION_HOURS);
+    let export_worker = ExportWorker::new(
+        pool.clone(),
+        export_storage.clone(),
+        chrono::Duration::hours(export_retention),
+    );
+    let export_coordinator = coordinator.clone();
+    let export_interval = std::env::var("EXPORT_WORKER_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(export_service::DEFAULT_EXPORT_WORKER_INTERVAL_SECONDS);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(export_interval));
+        loop {
+            interval.tick().await;
+            let Some(_in_flight) = export_coordinator.try_track() else {
+                break;
+            };
+            if let Err(e) = export_worker.run().await {
+                tracing::warn!("Failed to run export worker: {}", e);
+            }
+        }
+    });
+
+    // Recompute the catalog statistics that triggers keep current, correcting any drift
+    let statistics_pool = pool.clone();
+    let statistics_interval = std::env::var("CATALOG_STATISTICS_REFRESH_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CATALOG_STATISTICS_REFRESH_SECONDS);
+    tokio::spawn(async move {
+        // The migration computed them, so the first refresh waits a full interval
+        let period = tokio::time::Duration::from_secs(statistics_interval);
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = CatalogStatistics::refresh(&statistics_pool).await {
+                tracing::warn!("Failed to refresh catalog statistics: {}", e);
+            }
+        }
+    });
+
+    // Prune change feed entries consumers have had time to read
+    let change_feed_pool = pool.clone();
+    let change_feed_retention = std::env::var("CHANGE_FEED_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CHANGE_FEED_RETENTION_DAYS);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            DEFAULT_CHANGE_FEED_PRUNE_INTERVAL_SECONDS,
+        ));
+        loop {
+            interval.tick().await;
+            let older_than = chrono::Utc::now() - chrono::Duration::days(change_feed_retention);
+            match ChangeFeedEntry::prune(&change_feed_pool, older_than).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Pruned {} change feed entries", deleted),
+                Err(e) => tracing::warn!("Failed to prune the change feed: {}", e),
+            }
+        }
+    });
+
+    // Queue crawls of series right after their expected releases
+    let release_pool = pool.clone();
+    let release_interval = std::env::var("RELEASE_SCHEDULER_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(release_scheduler::DEFAULT_RELEASE_SCHEDULER_INTERVAL_SECONDS);
+    tokio::spawn(async move {
+        // Releases within the next interval are queued too, so none is late
+        let horizon =
+            chrono::Duration::minutes(release_scheduler::DEFAULT_RELEASE_QUEUE_HORIZON_MINUTES)
+                .max(chrono::Duration::seconds(release_interval as i64));
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(release_interval));
+        loop {
+            interval.tick().await;
+            match release_scheduler::queue_upcoming_releases(&release_pool, horizon).await {
+                Ok(report) if report.releases == 0 => {}
+                Ok(report) => info!(
+                    "Queued {} crawls for {} upcoming releases ({} already queued)",
+                    report.queued, report.releases, report.skipped
+                ),
+                Err(e) => tracing::warn!("Failed to queue release crawls: {}", e),
+            }
+        }
+    });
+
+    // Learn release calendars from when series' data arrived
+    let learning_pool = pool.clone();
+    let learning_interval = std::env::var("RELEASE_CALENDAR_LEARNING_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(release_scheduler::DEFAULT_RELEASE_CALENDAR_LEARNING_INTERVAL_SECONDS);
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(learning_interval));
+        loop {
+            interval.tick().await;
+            match release_scheduler::learn_release_calendars(&learning_pool).await {
+                Ok(report) => info!(
+                    "Learned release calendars of {} of {} series",
+                    report.learned, report.series_examined
+                ),
+                Err(e) => tracing::warn!("Failed to learn release calendars: {}", e),
+            }
+        }
+    });
+
+    // Record component status changes for the public status page
+    let status_interval = std::env::var("STATUS_RECORD_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_STATUS_RECORD_INTERVAL_SECONDS);
+    let status_recorder = Arc::new(status::StatusRecorder::new(pool.clone(), schema.clone()));
+    tokio::spawn(status::run_status_recorder(
+        status_recorder,
+        Duration::from_secs(status_interval),
+    ));
+
     // Start background crawler (if enabled in config)
     // For now, crawler is always enabled - in production this could be configurable
     info!("🕷️  Starting background crawler...");
@@ -277,76 +850,234 @@ async fn main() -> AppResult<()> {
     // Create Warp filters
     let cors = warp::cors()
         .allow_any_origin()
-        .allow_headers(vec!["content-type", "authorization"])
+        .allow_headers(vec![
+            "content-type",
+            "authorization",
+            "traceparent",
+            "tracestate",
+            "x-request-id",
+            "if-none-match",
+            EXPLAIN_HEADER,
+        ])
+        .expose_headers(vec!["etag"])
         .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"]);
 
-    // GraphQL endpoint with authentication
+    // GraphQL endpoint with security checks and authentication
     let pool_for_graphql = pool.clone();
+    let graphql_security = graphql_security::GraphQLSecurity::from_env()
+        .with_event_handler(Arc::new(DatabaseSecurityEventHandler::new(pool.clone())));
+    let persisted_queries = Arc::new(graphql_cache::PersistedQueries::default());
+    let graphql_coordinator = coordinator.clone();
+    // Multipart requests carry file uploads such as annotation imports
+    let upload_max_file_size = std::env::var("GRAPHQL_UPLOAD_MAX_FILE_SIZE_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_UPLOAD_MAX_FILE_SIZE_BYTES);
+    let multipart_options = MultipartOptions::default()
+        .max_file_size(upload_max_file_size)
+        .max_num_files(MAX_UPLOAD_FILES);
     let graphql_filter = warp::path("graphql")
+        .and(warp::method())
         .and(warp::header::headers_cloned())
-        .and(async_graphql_warp::graphql(schema.clone()))
+        .and(warp::addr::remote())
+        .and(async_graphql_warp::graphql_opts(
+            schema.clone(),
+            multipart_options,
+        ))
         .and_then(
-            move |headers: warp::http::HeaderMap<warp::http::HeaderValue>,
+            move |method: warp::http::Method,
+                  headers: warp::http::HeaderMap<warp::http::HeaderValue>,
+                  remote: Option<std::net::SocketAddr>,
                   (schema, request): (
                 async_graphql::Schema<
                     econ_graph_graphql::graphql::query::Query,
                     econ_graph_graphql::graphql::mutation::Mutation,
-                    async_graphql::EmptySubscription,
+                    econ_graph_graphql::graphql::Subscription,
                 >,
                 async_graphql::Request,
             )| {
                 let pool_for_graphql = pool_for_graphql.clone();
+                let graphql_security = graphql_security.clone();
+                let persisted_queries = persisted_queries.clone();
+                let graphql_coordinator = graphql_coordinator.clone();
+                let client_ip = graphql_security::client_ip(&headers, remote);
+
+                // Continue the caller's trace so resolver and database spans join it
+                let request_span = tracing::info_span!(
+                    "graphql.http",
+                    otel.kind = "server",
+                    otel.name = %format!("{} /graphql", method),
+                    graphql.operation.name = request.operation_name.as_deref().unwrap_or("anonymous"),
+                    client.address = %client_ip,
+                    user_id = tracing::field::Empty,
+                );
+                telemetry::set_parent_from_headers(
+                    &request_span,
+                    headers
+                        .iter()
+                        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+                );
+
                 async move {
-                    // Extract JWT token from Authorization header
-                    let user = if let Some(auth_header) = headers.get("authorization") {
-                        if let Ok(auth_str) = std::str::from_utf8(auth_header.as_bytes()) {
-                            if auth_str.starts_with("Bearer ") {
-                                let token = auth_str.trim_start_matches("Bearer ");
-                                // Validate token and get user
-                                let auth_service =
-                                    econ_graph_auth::auth::services::AuthService::new(
-                                        pool_for_graphql.clone(),
-                                    );
-                                match auth_service.verify_token(token) {
-                                    Ok(claims) => econ_graph_core::models::User::get_by_id(
-                                        &pool_for_graphql,
-                                        claims.sub.parse().unwrap_or_default(),
-                                    )
-                                    .await
-                                    .ok(),
-                                    Err(_) => None, // Invalid token, continue without user
-                                }
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
+                    // Served while draining; the shutdown waits for the request to finish
+                    let _in_flight = graphql_coordinator.track();
+                    let mut request = request;
+                    // Persisted queries are resolved first so the security checks see the query text
+                    if let Err(response) = persisted_queries.resolve(&mut request) {
+                        return Ok::<_, Infallible>(GraphQLResponse::from(response).into_response());
+                    }
+
+                    let token = headers
+                        .get("authorization")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(bearer_token);
+
+                    // Signature check only, so the rate limit can be charged per
+                    // user (under their role's quota) without touching the database
+                    let verified_claims = token.and_then(|token| {
+                        AuthService::new(pool_for_graphql.clone())
+                            .verify_token(token)
+                            .ok()
+                    });
+
+                    // A signed token outlives a revoked session and carries the role it was
+                    // issued with, so introspection trust comes from the session-checked user.
+                    // Only tokens claiming a trusted role are looked up before the checks.
+                    let early_session = match &verified_claims {
+                        Some(claims) if graphql_security.trusts(&claims.role) => {
+                            Some(authenticate(&pool_for_graphql, token).await)
                         }
-                    } else {
-                        None
+                        _ => None,
                     };
+                    let trusted_role = early_session
+                        .as_ref()
+                        .and_then(|(_, user)| user.as_ref())
+                        .map(|user| user.to_auth_user().role);
+
+                    // Reject abusive requests before any further session or database lookups
+                    if let Err(response) = graphql_security
+                        .check(
+                            &request,
+                            &client_ip,
+                            verified_claims.as_ref(),
+                            trusted_role.as_ref(),
+                        )
+                        .await
+                    {
+                        return Ok::<_, Infallible>(GraphQLResponse::from(response).into_response());
+                    }
+
+                    let (claims, user) = match early_session {
+                        Some(session) => session,
+                        None => authenticate(&pool_for_graphql, token).await,
+                    };
+                    if let Some(user) = &user {
+                        tracing::Span::current().record("user_id", tracing::field::display(user.id));
+                    }
+
+                    // Build the request-scoped context (user, loaders, metrics, request id);
+                    // the request id is the one assigned to the HTTP request span
+                    let explain_requested = headers
+                        .get(EXPLAIN_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+                    let context = GraphQLContext::new(&pool_for_graphql, user)
+                        .with_claims(claims)
+                        .with_client_ip(client_ip)
+                        .with_request_id(logging::current_request_id().as_deref())
+                        .with_explain(explain_requested);
+
+                    let context = Arc::new(context);
 
-                    // Create authenticated GraphQL context
-                    let auth_context = std::sync::Arc::new(
-                        econ_graph_graphql::graphql::context::GraphQLContext::new(user),
-                    );
-                    let auth_schema = econ_graph_graphql::graphql::schema::create_schema_with_data(
-                        pool_for_graphql.clone(),
-                        auth_context,
-                    );
+                    // Explain output describes this execution, so it is never cached or served from cache
+                    if context.explain {
+                        return Ok::<_, Infallible>(
+                            GraphQLResponse::from(graphql_handler(schema, request, context).await)
+                                .into_response(),
+                        );
+                    }
 
-                    Ok::<_, Infallible>(GraphQLResponse::from(auth_schema.execute(request).await))
+                    // GET queries are cached with ETags, other queries by their cache
+                    // hints; mutations always execute
+                    let is_query = operation_type(&mut request) == "query";
+                    let user_id = context.user.as_ref().map(|user| user.id);
+                    if method == warp::http::Method::GET && is_query {
+                        let if_none_match = headers
+                            .get(warp::http::header::IF_NONE_MATCH)
+                            .and_then(|value| value.to_str().ok());
+                        return Ok::<_, Infallible>(
+                            graphql_cache::cached_query(
+                                &pool_for_graphql,
+                                request,
+                                user_id,
+                                if_none_match,
+                                |request| graphql_handler(schema, request, context),
+                            )
+                            .await,
+                        );
+                    }
+                    if is_query {
+                        return Ok::<_, Infallible>(
+                            graphql_cache::hinted_query(request, user_id, |request| {
+                                graphql_handler(schema, request, context)
+                            })
+                            .await,
+                        );
+                    }
+
+                    Ok::<_, Infallible>(
+                        GraphQLResponse::from(graphql_handler(schema, request, context).await)
+                            .into_response(),
+                    )
                 }
+                .instrument(request_span)
             },
         );
 
+    // GraphQL subscriptions over WebSocket
+    let pool_for_ws = pool.clone();
+    let ws_schema = schema.clone();
+    let graphql_ws_filter = warp::path!("graphql" / "ws")
+        .and(warp::ws())
+        .and(async_graphql_warp::graphql_protocol())
+        .map(
+            move |ws: warp::ws::Ws, protocol: async_graphql::http::WebSocketProtocols| {
+                let pool = pool_for_ws.clone();
+                let schema = ws_schema.clone();
+                let reply = ws.on_upgrade(move |socket| {
+                    GraphQLWebSocket::new(socket, schema, protocol)
+                        .on_connection_init(move |payload| {
+                            graphql_ws_connection_data(pool, payload)
+                        })
+                        .serve()
+                });
+                warp::reply::with_header(
+                    reply,
+                    "Sec-WebSocket-Protocol",
+                    protocol.sec_websocket_protocol(),
+                )
+            },
+        );
+
+    // Schema version, change log and deprecations
+    let changelog_filter = warp::path!("graphql" / "changelog")
+        .and(warp::get())
+        .map(|| warp::reply::json(&versioning::schema_changelog()));
+
     // GraphQL Playground
     let playground_filter = warp::path("playground")
         .and(warp::get())
         .and_then(graphql_playground);
 
-    // Health check
-    let health_filter = warp::path("health").and(warp::get()).and_then(health_check);
+    // Health check with live dependency probes
+    let health_checker = Arc::new(health::HealthChecker::new(pool.clone()));
+    let health_filter = warp::path("health")
+        .and(warp::get())
+        .and(warp::any().map(move || health_checker.clone()))
+        .and_then(health::health_handler);
+
+    // Public status page with uptime history
+    let status_filter = status::status_route(pool.clone());
 
     // Metrics endpoint for Prometheus
     let metrics_filter = warp::path("metrics")
@@ -376,16 +1107,51 @@ async fn main() -> AppResult<()> {
         .and(warp::body::bytes())
         .and_then(mcp_handler);
 
+    // Streaming ingestion for out-of-process crawlers
+    let ingestion_endpoint = Arc::new(ingestion::IngestionEndpoint::from_env(pool.clone()));
+    if !ingestion_endpoint.is_enabled() {
+        info!("⚠️  INGEST_API_TOKEN not set, /ingest/data-points is disabled");
+    }
+    let ingestion_filter = ingestion::ingestion_route(ingestion_endpoint);
+
+    // Chart images for embeds and social previews
+    let embed_filter = embed::embed_route(pool.clone());
+
+    // Downloads of completed bulk exports
+    let exports_filter = exports::exports_route(pool.clone(), export_storage);
+
+    // Drain mode for deployments
+    let drain_endpoint = Arc::new(drain::DrainEndpoint::from_env(coordinator.clone()));
+    if !drain_endpoint.is_enabled() {
+        info!("⚠️  DRAIN_API_TOKEN not set, /drain is disabled");
+    }
+    let drain_filter = drain::drain_route(drain_endpoint);
+
+    // Change feed for downstream caches
+    let changes_endpoint = Arc::new(changes::ChangesEndpoint::from_env(pool.clone()));
+    if !changes_endpoint.is_enabled() {
+        info!("⚠️  CHANGE_FEED_API_TOKEN not set, /changes is disabled");
+    }
+    let changes_filter = changes::changes_route(changes_endpoint);
+
     // Combine all routes
     let routes = root_filter
+        .or(graphql_ws_filter)
+        .or(changelog_filter)
         .or(graphql_filter)
         .or(playground_filter)
         .or(health_filter)
+        .or(status_filter)
         .or(metrics_filter)
         .or(auth_filter)
         .or(mcp_filter)
+        .or(ingestion_filter)
+        .or(embed_filter)
+        .or(exports_filter)
+        .or(drain_filter)
+        .or(changes_filter)
         .with(cors)
-        .with(warp::trace::request());
+        .with(warp::trace(http_request_span));
 
     // Initialize metrics
     info!("📊 Initializing Prometheus metrics...");
@@ -402,28 +1168,65 @@ async fn main() -> AppResult<()> {
         "❤️  Health check available at http://localhost:{}/health",
         port
     );
+    info!(
+        "🚦 Status page available at http://localhost:{}/status",
+        port
+    );
     info!(
         "📊 Prometheus metrics available at http://localhost:{}/metrics",
         port
     );
     info!("🔗 API endpoints:");
     info!("  - POST/GET /graphql - GraphQL API");
+    info!("  - WS /graphql/ws - GraphQL subscriptions");
     info!("  - GET /playground - GraphQL Playground");
     info!("  - GET /health - Health check");
+    info!("  - GET /status - Component status and uptime");
     info!("  - GET /metrics - Prometheus metrics");
+    info!("  - POST /ingest/data-points - Streaming data point ingestion");
+    info!("  - GET /embed/chart/{{id}}.png|svg - Chart images for embeds");
+    info!("  - GET /exports/{{id}}/download - Bulk export downloads");
+    info!("  - GET/POST /drain - Drain mode for deployments");
+    info!("  - GET /changes - Change feed of series and data point mutations");
     info!("  - GET / - API documentation");
 
     // Start the server
     info!("🚀 Starting HTTP server...");
+    let shutdown_deadline = shutdown_deadline_from_env();
+    let server_coordinator = coordinator.clone();
+    let (stopping, stopped) = tokio::sync::oneshot::channel();
     let (_, server) =
-        warp::serve(routes).bind_with_graceful_shutdown(([0, 0, 0, 0], port), async {
-            signal::ctrl_c().await.expect("Failed to listen for ctrl+c");
+        warp::serve(routes).bind_with_graceful_shutdown(([0, 0, 0, 0], port), async move {
+            shutdown_signal().await;
             info!("🛑 Received shutdown signal, gracefully shutting down...");
+            // A /drain request may have started draining already
+            server_coordinator.drain();
+            let _ = stopping.send(());
         });
 
     info!("✅ Server is now running and accepting connections!");
-    server.await;
+    let server = tokio::spawn(server);
+    let _ = stopped.await;
+
+    // Open connections (GraphQL subscriptions) would otherwise hold the server
+    // open, so the deadline covers the server as well as the tracked work
+    let deadline = tokio::time::Instant::now() + shutdown_deadline;
+    if tokio::time::timeout_at(deadline, server).await.is_err() {
+        tracing::warn!("Connections still open at the shutdown deadline");
+    }
+
+    let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+    if coordinator.wait_for_in_flight(remaining).await {
+        info!("✅ In-flight work finished");
+    } else {
+        tracing::warn!(
+            "Shutdown deadline reached with {} tasks still running",
+            coordinator.in_flight()
+        );
+    }
 
+    // Export the remaining spans before the process exits
+    drop(telemetry);
     info!("✅ Server shutdown complete");
     Ok(())
 }
@@ -4,9 +4,10 @@
 //! application performance, database operations, GraphQL queries, and more.
 //! All metrics are exposed via the /metrics endpoint for Prometheus scraping.
 
+use econ_graph_graphql::security::{BlockReason, SecurityMetrics};
 use prometheus::{
-    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
-    TextEncoder,
+    Gauge, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts,
+    Registry, TextEncoder,
 };
 use std::sync::Arc;
 use warp::Reply;
@@ -35,6 +36,16 @@ pub struct AppMetrics {
     /// GraphQL query complexity
     pub graphql_query_complexity: Histogram,
 
+    /// GraphQL permission checks that failed
+    pub graphql_authorization_denied_total: IntCounter,
+
+    /// GraphQL security middleware metrics
+    pub graphql_security_requests_total: IntCounter,
+    pub graphql_security_blocked_total: IntCounterVec,
+    pub graphql_security_average_complexity: Gauge,
+    pub graphql_security_average_depth: Gauge,
+    pub graphql_security_average_query_size_bytes: Gauge,
+
     /// Database connection pool metrics
     pub db_connections_active: IntGauge,
     pub db_connections_idle: IntGauge,
@@ -116,6 +127,46 @@ impl AppMetrics {
         ))?;
         registry.register(Box::new(graphql_query_complexity.clone()))?;
 
+        let graphql_authorization_denied_total = IntCounter::new(
+            "graphql_authorization_denied_total",
+            "Total number of GraphQL permission checks that failed",
+        )?;
+        registry.register(Box::new(graphql_authorization_denied_total.clone()))?;
+
+        // GraphQL security metrics
+        let graphql_security_requests_total = IntCounter::new(
+            "graphql_security_requests_total",
+            "Total number of GraphQL requests checked by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_requests_total.clone()))?;
+
+        let graphql_security_blocked_total = IntCounterVec::new(
+            Opts::new(
+                "graphql_security_blocked_total",
+                "Total number of failed GraphQL security checks",
+            ),
+            &["reason"],
+        )?;
+        registry.register(Box::new(graphql_security_blocked_total.clone()))?;
+
+        let graphql_security_average_complexity = Gauge::new(
+            "graphql_security_average_complexity",
+            "Average complexity of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_complexity.clone()))?;
+
+        let graphql_security_average_depth = Gauge::new(
+            "graphql_security_average_depth",
+            "Average depth of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_depth.clone()))?;
+
+        let graphql_security_average_query_size_bytes = Gauge::new(
+            "graphql_security_average_query_size_bytes",
+            "Average size of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_query_size_bytes.clone()))?;
+
         // Database metrics
         let db_connections_active = IntGauge::new(
             "db_connections_active",
@@ -221,6 +272,12 @@ impl AppMetrics {
             graphql_queries_total,
             graphql_query_duration_seconds,
             graphql_query_complexity,
+            graphql_authorization_denied_total,
+            graphql_security_requests_total,
+            graphql_security_blocked_total,
+            graphql_security_average_complexity,
+            graphql_security_average_depth,
+            graphql_security_average_query_size_bytes,
             db_connections_active,
             db_connections_idle,
             db_connections_total,
@@ -246,7 +303,9 @@ pub static METRICS: once_cell::sync::Lazy<AppMetrics> =
 /// Generate Prometheus metrics output
 pub fn generate_metrics() -> anyhow::Result<String> {
     let encoder = TextEncoder::new();
-    let metric_families = REGISTRY.gather();
+    let mut metric_families = REGISTRY.gather();
+    // Service metrics such as cache hit rates are registered with the shared registry
+    metric_families.extend(econ_graph_metrics::DEFAULT_REGISTRY.gather());
     encoder
         .encode_to_string(&metric_families)
         .map_err(|e| anyhow::anyhow!("Failed to encode metrics: {}", e))
@@ -305,6 +364,36 @@ pub fn record_graphql_query(
     METRICS.graphql_query_complexity.observe(complexity);
 }
 
+/// Record permission checks that failed while executing a GraphQL request
+pub fn record_graphql_authorization_denials(count: u64) {
+    METRICS.graphql_authorization_denied_total.inc_by(count);
+}
+
+/// Record the outcome of a GraphQL security check
+///
+/// `snapshot` is the middleware's running [`SecurityMetrics`]; its averages are
+/// exported as gauges while counters are incremented per request.
+pub fn record_graphql_security_check(blocked: &[BlockReason], snapshot: &SecurityMetrics) {
+    METRICS.graphql_security_requests_total.inc();
+
+    for reason in blocked {
+        METRICS
+            .graphql_security_blocked_total
+            .with_label_values(&[reason.as_str()])
+            .inc();
+    }
+
+    METRICS
+        .graphql_security_average_complexity
+        .set(snapshot.average_complexity);
+    METRICS
+        .graphql_security_average_depth
+        .set(snapshot.average_depth);
+    METRICS
+        .graphql_security_average_query_size_bytes
+        .set(snapshot.average_size);
+}
+
 /// Record database query metrics
 pub fn record_db_query(query_type: &str, table: &str, duration: f64) {
     METRICS
@@ -445,6 +534,40 @@ mod tests {
         ))?;
         registry.register(Box::new(graphql_query_complexity.clone()))?;
 
+        // GraphQL security metrics
+        let graphql_security_requests_total = IntCounter::new(
+            "graphql_security_requests_total",
+            "Total number of GraphQL requests checked by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_requests_total.clone()))?;
+
+        let graphql_security_blocked_total = IntCounterVec::new(
+            Opts::new(
+                "graphql_security_blocked_total",
+                "Total number of failed GraphQL security checks",
+            ),
+            &["reason"],
+        )?;
+        registry.register(Box::new(graphql_security_blocked_total.clone()))?;
+
+        let graphql_security_average_complexity = Gauge::new(
+            "graphql_security_average_complexity",
+            "Average complexity of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_complexity.clone()))?;
+
+        let graphql_security_average_depth = Gauge::new(
+            "graphql_security_average_depth",
+            "Average depth of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_depth.clone()))?;
+
+        let graphql_security_average_query_size_bytes = Gauge::new(
+            "graphql_security_average_query_size_bytes",
+            "Average size of GraphQL queries allowed by the security middleware",
+        )?;
+        registry.register(Box::new(graphql_security_average_query_size_bytes.clone()))?;
+
         // Database metrics
         let db_connections_active = IntGauge::new(
             "db_connections_active",
@@ -550,6 +673,11 @@ mod tests {
             graphql_queries_total,
             graphql_query_duration_seconds,
             graphql_query_complexity,
+            graphql_security_requests_total,
+            graphql_security_blocked_total,
+            graphql_security_average_complexity,
+            graphql_security_average_depth,
+            graphql_security_average_query_size_bytes,
             db_connections_active,
             db_connections_idle,
             db_connections_total,
@@ -604,6 +732,7 @@ mod tests {
         // Test recording various metrics using the global metrics instance
         record_http_request("GET", "/api/test", 200, 0.1);
         record_graphql_query("query", "testQuery", 0.05, 10.0);
+        record_graphql_security_check(&[BlockReason::RateLimit], &SecurityMetrics::default());
         record_db_query("SELECT", "users", 0.02);
         record_auth_attempt("google", "oauth");
         record_auth_success("google", "oauth");
@@ -621,6 +750,7 @@ mod tests {
         let metrics_output = generate_metrics().expect("Should generate metrics");
         assert!(metrics_output.contains("http_requests_total"));
         assert!(metrics_output.contains("graphql_queries_total"));
+        assert!(metrics_output.contains("graphql_security_blocked_total"));
         assert!(metrics_output.contains("db_queries_total"));
         assert!(metrics_output.contains("auth_attempts_total"));
         assert!(metrics_output.contains("crawler_requests_total"));
@@ -0,0 +1,373 @@
+//! Public status page
+//!
+//! `/health` answers load balancers with the state of this instance right now.
+//! `/status` answers users: whether the database, the GraphQL API and the
+//! crawler of each data source are working, since when, and how much of the
+//! last [`UPTIME_WINDOW_DAYS`] days they were healthy, so a delayed data feed
+//! is visible without access to metrics.
+//!
+//! A [`StatusRecorder`] probes the components every
+//! `STATUS_RECORD_INTERVAL_SECONDS` and records their status changes in
+//! `component_status_transitions`; the endpoint only reads that history.
+//!
+//! ```text
+//! GET /status
+//! {"status":"degraded","uptime_window_days":90,"components":[
+//!   {"name":"crawler:FRED","status":"degraded","since":"...","message":"Last crawl ...","uptime_percentage":99.2},...]}
+//! ```
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use econ_graph_core::database::{test_connection, DatabasePool};
+use econ_graph_core::models::{
+    uptime_percentage, ComponentStatusTransition, DataSource, UPTIME_WINDOW_DAYS,
+};
+use econ_graph_core::AppResult;
+use econ_graph_graphql::graphql::schema::AppSchema;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use warp::http::StatusCode;
+use warp::Filter;
+
+use crate::health::{source_crawl_health, HealthCheckConfig, HealthStatus};
+use crate::metrics;
+
+const ROUTE: &str = "/status";
+
+/// Name of the database component
+pub const DATABASE_COMPONENT: &str = "database";
+
+/// Name of the GraphQL API component
+pub const GRAPHQL_COMPONENT: &str = "graphql";
+
+/// Prefix of the component of a data source's crawler, followed by the source name
+pub const CRAWLER_COMPONENT_PREFIX: &str = "crawler:";
+
+/// Status of a probed component and the message shown with it
+type Probe = (HealthStatus, Option<String>);
+
+/// Probes components and records their status changes
+pub struct StatusRecorder {
+    pool: DatabasePool,
+    schema: AppSchema,
+    config: HealthCheckConfig,
+}
+
+impl StatusRecorder {
+    pub fn new(pool: DatabasePool, schema: AppSchema) -> Self {
+        Self {
+            pool,
+            schema,
+            config: HealthCheckConfig::default(),
+        }
+    }
+
+    /// Probe every component and record the ones whose status changed;
+    /// returns how many changed
+    ///
+    /// Messages are shown publicly, so they describe the problem without
+    /// internal error details.
+    pub async fn record(&self) -> AppResult<usize> {
+        let mut statuses = vec![
+            (DATABASE_COMPONENT.to_string(), self.check_database().await),
+            (GRAPHQL_COMPONENT.to_string(), self.check_graphql().await),
+        ];
+        statuses.extend(self.check_crawlers().await?);
+
+        let mut changed = 0;
+        for (component, (status, message)) in statuses {
+            if ComponentStatusTransition::record(
+                &self.pool,
+                &component,
+                status.as_str(),
+                message.as_deref(),
+            )
+            .await?
+            {
+                tracing::info!("Component {} is now {}", component, status.as_str());
+                changed += 1;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    async fn check_database(&self) -> Probe {
+        match tokio::time::timeout(self.config.check_timeout, test_connection(&self.pool)).await {
+            Ok(Ok(())) => (HealthStatus::Healthy, None),
+            Ok(Err(e)) => {
+                tracing::warn!("Status check of the database failed: {}", e);
+                (
+                    HealthStatus::Unhealthy,
+                    Some("Database is unreachable".to_string()),
+                )
+            }
+            Err(_) => (
+                HealthStatus::Unhealthy,
+                Some("Database is not responding".to_string()),
+            ),
+        }
+    }
+
+    /// Execute a trivial query, which fails when the schema cannot serve requests
+    async fn check_graphql(&self) -> Probe {
+        match tokio::time::timeout(
+            self.config.check_timeout,
+            self.schema.execute("{ __typename }"),
+        )
+        .await
+        {
+            Ok(response) if response.errors.is_empty() => (HealthStatus::Healthy, None),
+            Ok(response) => {
+                tracing::warn!("Status check of GraphQL failed: {:?}", response.errors);
+                (
+                    HealthStatus::Unhealthy,
+                    Some("GraphQL API is failing requests".to_string()),
+                )
+            }
+            Err(_) => (
+                HealthStatus::Unhealthy,
+                Some("GraphQL API is not responding".to_string()),
+            ),
+        }
+    }
+
+    /// A source's crawler is degraded once it has missed its crawl schedule
+    async fn check_crawlers(&self) -> AppResult<Vec<(String, Probe)>> {
+        let now = Utc::now();
+        let sources = DataSource::find_all(&self.pool).await?;
+
+        Ok(sources
+            .iter()
+            .filter(|source| source.is_enabled)
+            .map(|source| {
+                let health = source_crawl_health(source, now, self.config.stale_crawl_intervals);
+                let status = if health.stale {
+                    let message = match health.last_crawl_at {
+                        Some(at) => {
+                            format!("Data last updated {}", at.format("%Y-%m-%d %H:%M UTC"))
+                        }
+                        None => "Data has not been crawled yet".to_string(),
+                    };
+                    (HealthStatus::Degraded, Some(message))
+                } else {
+                    (HealthStatus::Healthy, None)
+                };
+                (
+                    format!("{}{}", CRAWLER_COMPONENT_PREFIX, source.name),
+                    status,
+                )
+            })
+            .collect())
+    }
+}
+
+/// Current status and uptime of one component
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub status: String,
+    /// When the component entered its current status
+    pub since: DateTime<Utc>,
+    pub message: Option<String>,
+    /// Share of the uptime window the component was healthy, `None` before it was first observed
+    pub uptime_percentage: Option<f64>,
+}
+
+/// Body of `/status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusPage {
+    /// `unhealthy` when the database or GraphQL API is, `degraded` when anything else is off
+    pub status: String,
+    pub uptime_window_days: i64,
+    pub components: Vec<ComponentStatus>,
+}
+
+impl StatusPage {
+    /// Build the page from the history of the uptime window
+    ///
+    /// Crawlers of sources not in `enabled_sources` are left out, so disabled
+    /// sources do not stay on the page with their last status.
+    pub fn from_history(
+        history: &[ComponentStatusTransition],
+        enabled_sources: &HashSet<String>,
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut by_component: HashMap<&str, Vec<ComponentStatusTransition>> = HashMap::new();
+        for transition in history {
+            by_component
+                .entry(transition.component.as_str())
+                .or_default()
+                .push(transition.clone());
+        }
+
+        let mut components: Vec<ComponentStatus> = by_component
+            .into_iter()
+            .filter(
+                |(name, _)| match name.strip_prefix(CRAWLER_COMPONENT_PREFIX) {
+                    Some(source) => enabled_sources.contains(source),
+                    None => true,
+                },
+            )
+            .filter_map(|(name, transitions)| {
+                let latest = transitions.last()?;
+                Some(ComponentStatus {
+                    name: name.to_string(),
+                    status: latest.status.clone(),
+                    since: latest.changed_at,
+                    message: latest.message.clone(),
+                    uptime_percentage: uptime_percentage(&transitions, since, now),
+                })
+            })
+            .collect();
+        components.sort_by(|a, b| component_order(&a.name).cmp(&component_order(&b.name)));
+
+        Self {
+            status: overall_status(&components).as_str().to_string(),
+            uptime_window_days: UPTIME_WINDOW_DAYS,
+            components,
+        }
+    }
+}
+
+/// Database and GraphQL first, then crawlers by source name
+fn component_order(name: &str) -> (u8, &str) {
+    match name {
+        DATABASE_COMPONENT => (0, name),
+        GRAPHQL_COMPONENT => (1, name),
+        _ => (2, name),
+    }
+}
+
+fn overall_status(components: &[ComponentStatus]) -> HealthStatus {
+    let is_core = |c: &&ComponentStatus| !c.name.starts_with(CRAWLER_COMPONENT_PREFIX);
+    if components
+        .iter()
+        .filter(is_core)
+        .any(|c| c.status == HealthStatus::Unhealthy.as_str())
+    {
+        HealthStatus::Unhealthy
+    } else if components
+        .iter()
+        .any(|c| c.status != HealthStatus::Healthy.as_str())
+    {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    }
+}
+
+async fn load_status_page(pool: &DatabasePool) -> AppResult<StatusPage> {
+    let now = Utc::now();
+    let since = now - ChronoDuration::days(UPTIME_WINDOW_DAYS);
+    let history = ComponentStatusTransition::history_since(pool, since).await?;
+    let enabled_sources = DataSource::find_all(pool)
+        .await?
+        .into_iter()
+        .filter(|source| source.is_enabled)
+        .map(|source| source.name)
+        .collect();
+
+    Ok(StatusPage::from_history(
+        &history,
+        &enabled_sources,
+        since,
+        now,
+    ))
+}
+
+/// `GET /status`
+pub fn status_route(
+    pool: DatabasePool,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path("status")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::any().map(move || pool.clone()))
+        .and_then(status_handler)
+}
+
+async fn status_handler(pool: DatabasePool) -> Result<impl warp::Reply, Infallible> {
+    let start = Instant::now();
+    let (status, body) = match load_status_page(&pool).await {
+        Ok(page) => (StatusCode::OK, json!(page)),
+        Err(e) => {
+            tracing::error!("Failed to load the status page: {}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                json!({
+                    "status": HealthStatus::Unhealthy.as_str(),
+                    "error": "Status history is unavailable",
+                }),
+            )
+        }
+    };
+
+    metrics::record_http_request("GET", ROUTE, status.as_u16(), start.elapsed().as_secs_f64());
+    Ok(warp::reply::with_status(warp::reply::json(&body), status))
+}
+
+/// Record component status every `interval` until the process exits
+pub async fn run_status_recorder(recorder: Arc<StatusRecorder>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        if let Err(e) = recorder.record().await {
+            tracing::warn!("Failed to record component status: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_page_from_history() {
+        // REQUIREMENT: The public status page shows current status and 90-day uptime per component
+        // PURPOSE: Verify the page reports each component's latest status and uptime, and only core outages make it unhealthy
+        // This ensures users see delayed data feeds without a stale crawler reading as a full outage
+
+        let now = Utc::now();
+        let since = now - ChronoDuration::days(UPTIME_WINDOW_DAYS);
+        let transition = |component: &str, status: &str, days_ago: i64| ComponentStatusTransition {
+            id: 0,
+            component: component.to_string(),
+            status: status.to_string(),
+            message: (status != "healthy").then(|| "Data last updated".to_string()),
+            changed_at: now - ChronoDuration::days(days_ago),
+        };
+        let history = vec![
+            transition("crawler:BLS", "healthy", 10),
+            transition("crawler:FRED", "healthy", 10),
+            transition("crawler:FRED", "degraded", 5),
+            transition("crawler:Retired", "degraded", 200),
+            transition("database", "healthy", 120),
+            transition("graphql", "healthy", 120),
+        ];
+        let enabled: HashSet<String> = ["BLS".to_string(), "FRED".to_string()].into();
+
+        let page = StatusPage::from_history(&history, &enabled, since, now);
+        let names: Vec<&str> = page.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["database", "graphql", "crawler:BLS", "crawler:FRED"]
+        );
+        assert_eq!(page.status, "degraded");
+
+        let fred = &page.components[3];
+        assert_eq!(fred.status, "degraded");
+        assert_eq!(fred.since, now - ChronoDuration::days(5));
+        assert!((fred.uptime_percentage.unwrap() - 50.0).abs() < 0.001);
+        assert_eq!(page.components[0].uptime_percentage, Some(100.0));
+
+        let mut outage = history.clone();
+        outage.push(transition("database", "unhealthy", 0));
+        let page = StatusPage::from_history(&outage, &enabled, since, now);
+        assert_eq!(page.status, "unhealthy");
+    }
+}
@@ -0,0 +1,629 @@
+//! HTTP client of the EconGraph API
+
+use futures::stream::{self, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::{ClientError, ClientResult, GraphQLError};
+use crate::queries;
+use crate::types::{
+    DataPoint, DataPointOptions, DataPointPage, FinancialStatement, SearchOptions, Series,
+    SeriesPage,
+};
+
+/// Results per search page when none is given; the server's default
+const DEFAULT_SEARCH_PAGE_SIZE: i32 = 50;
+
+/// Time allowed for one HTTP request
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How failed requests are retried
+///
+/// Only errors that may go away are retried (see
+/// [`ClientError::is_retryable`]), after a delay doubling from
+/// `initial_backoff` up to `max_backoff`. A `Retry-After` header sent by the
+/// server takes precedence over the computed delay.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry`, counting from 0
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+/// Tokens of the signed-in user
+#[derive(Debug, Default)]
+struct Session {
+    token: Option<String>,
+    refresh_token: Option<String>,
+}
+
+/// Token pair returned by `/auth/login` and `/auth/refresh`
+#[derive(Deserialize)]
+struct AuthTokens {
+    token: String,
+    refresh_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GraphQLResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphQLError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchSeriesData {
+    search_series: SearchSeriesResult,
+}
+
+#[derive(Deserialize)]
+struct SearchSeriesResult {
+    series: Vec<Series>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SeriesDataData {
+    series_data: DataPointConnection,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataPointConnection {
+    nodes: Vec<DataPoint>,
+    total_count: i64,
+    total_count_is_estimate: bool,
+    page_info: PageInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompanyFinancialsData {
+    company_financials: Vec<FinancialStatement>,
+}
+
+/// Builder of [`EconGraphClient`]
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    base_url: String,
+    token: Option<String>,
+    refresh_token: Option<String>,
+    retry_policy: RetryPolicy,
+    timeout: Duration,
+}
+
+impl ClientBuilder {
+    /// Access token sent as `Authorization: Bearer <token>`
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Refresh token used to get a new access token when it expires
+    pub fn refresh_token(mut self, refresh_token: impl Into<String>) -> Self {
+        self.refresh_token = Some(refresh_token.into());
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Time allowed for one HTTP request; 30 seconds by default
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn build(self) -> ClientResult<EconGraphClient> {
+        let base_url = self.base_url.trim_end_matches('/').to_string();
+        let url = reqwest::Url::parse(&base_url)
+            .map_err(|e| ClientError::InvalidUrl(format!("{}: {}", base_url, e)))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ClientError::InvalidUrl(format!(
+                "{}: expected an http or https URL",
+                base_url
+            )));
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .user_agent(concat!("econ-graph-client/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        Ok(EconGraphClient {
+            http,
+            base_url,
+            retry_policy: self.retry_policy,
+            session: Arc::new(RwLock::new(Session {
+                token: self.token,
+                refresh_token: self.refresh_token,
+            })),
+        })
+    }
+}
+
+/// Typed client of the EconGraph GraphQL API
+///
+/// Cloning is cheap; clones share the HTTP connection pool and the tokens, so
+/// a token refreshed by one clone is used by all of them.
+#[derive(Debug, Clone)]
+pub struct EconGraphClient {
+    http: reqwest::Client,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    session: Arc<RwLock<Session>>,
+}
+
+impl EconGraphClient {
+    /// Anonymous client with the default settings
+    ///
+    /// `base_url` is the server root, e.g. "https://api.econgraph.com".
+    pub fn new(base_url: impl Into<String>) -> ClientResult<Self> {
+        Self::builder(base_url).build()
+    }
+
+    pub fn builder(base_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            base_url: base_url.into(),
+            token: None,
+            refresh_token: None,
+            retry_policy: RetryPolicy::default(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sign in with email and password, keeping the returned tokens
+    pub async fn login(&self, email: &str, password: &str) -> ClientResult<()> {
+        let tokens = self
+            .post_auth(
+                "/auth/login",
+                &json!({ "email": email, "password": password }),
+            )
+            .await?;
+
+        let mut session = self.session.write().await;
+        session.token = Some(tokens.token);
+        session.refresh_token = tokens.refresh_token;
+        Ok(())
+    }
+
+    /// Exchange the refresh token for a new token pair
+    ///
+    /// Called automatically when the API rejects the access token, so this
+    /// is only needed to refresh ahead of time.
+    pub async fn refresh(&self) -> ClientResult<()> {
+        let mut session = self.session.write().await;
+        self.exchange_refresh_token(&mut session).await
+    }
+
+    /// Current access token
+    pub async fn token(&self) -> Option<String> {
+        self.session.read().await.token.clone()
+    }
+
+    /// Run a GraphQL document and deserialize its `data`
+    ///
+    /// Failed requests are retried according to the [`RetryPolicy`]; when the
+    /// API rejects the access token and a refresh token is held, the token is
+    /// refreshed and the request sent once more. Mutations are retried like
+    /// queries, so only send idempotent ones through this method.
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> ClientResult<T> {
+        let body = json!({ "query": query, "variables": variables });
+        let token = self.token().await;
+
+        let data = match self.send_with_retries(&body, token.as_deref()).await {
+            Err(error) if error.is_unauthenticated() => {
+                if !self.refresh_after(token.as_deref()).await? {
+                    return Err(error);
+                }
+                let token = self.token().await;
+                self.send_with_retries(&body, token.as_deref()).await?
+            }
+            result => result?,
+        };
+
+        serde_json::from_value(data).map_err(|e| ClientError::InvalidResponse(e.to_string()))
+    }
+
+    /// One page of series matching a full-text query, most relevant first
+    pub async fn search_series(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> ClientResult<SeriesPage> {
+        let page_size = options.page_size.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE);
+        let offset = options
+            .after
+            .as_deref()
+            .and_then(|cursor| cursor.parse::<i64>().ok())
+            .unwrap_or(0);
+
+        let data: SearchSeriesData = self
+            .execute(
+                queries::SEARCH_SERIES,
+                json!({
+                    "query": query,
+                    "source": options.source_id.map(|id| id.to_string()),
+                    "frequency": options.frequency,
+                    "first": page_size,
+                    "after": options.after,
+                }),
+            )
+            .await?;
+
+        // Search is paged by offset and does not report whether more results
+        // exist; a full page means there may be
+        let series = data.search_series.series;
+        let next_cursor = (page_size > 0 && series.len() >= page_size as usize)
+            .then(|| (offset + series.len() as i64).to_string());
+
+        Ok(SeriesPage {
+            series,
+            next_cursor,
+        })
+    }
+
+    /// One page of the data points of a series, by date
+    pub async fn get_data_points(
+        &self,
+        series_id: Uuid,
+        options: &DataPointOptions,
+    ) -> ClientResult<DataPointPage> {
+        let data: SeriesDataData = self
+            .execute(
+                queries::SERIES_DATA,
+                json!({
+                    "seriesId": series_id.to_string(),
+                    "filter": {
+                        "startDate": options.start_date,
+                        "endDate": options.end_date,
+                        "originalOnly": options.original_only,
+                        "latestRevisionOnly": options.latest_revision_only,
+                    },
+                    "transformation": options.transformation,
+                    "first": options.page_size,
+                    "after": options.after,
+                }),
+            )
+            .await?;
+
+        let connection = data.series_data;
+        let next_cursor = if connection.page_info.has_next_page {
+            connection.page_info.end_cursor
+        } else {
+            None
+        };
+
+        Ok(DataPointPage {
+            points: connection.nodes,
+            total_count: connection.total_count,
+            total_count_is_estimate: connection.total_count_is_estimate,
+            next_cursor,
+        })
+    }
+
+    /// Up to `limit` (at most 20) processed filings of a company, latest period first
+    pub async fn get_company_financials(
+        &self,
+        company_id: Uuid,
+        limit: i32,
+    ) -> ClientResult<Vec<FinancialStatement>> {
+        let data: CompanyFinancialsData = self
+            .execute(
+                queries::COMPANY_FINANCIALS,
+                json!({ "companyId": company_id.to_string(), "limit": limit }),
+            )
+            .await?;
+
+        Ok(data.company_financials)
+    }
+
+    /// All series matching a query, fetching pages as the stream is read
+    pub fn search_series_stream<'a>(
+        &'a self,
+        query: &'a str,
+        options: SearchOptions,
+    ) -> impl Stream<Item = ClientResult<Series>> + 'a {
+        paginate(options.after.clone(), move |after| {
+            let options = SearchOptions {
+                after,
+                ..options.clone()
+            };
+            async move {
+                let page = self.search_series(query, &options).await?;
+                Ok((page.series, page.next_cursor))
+            }
+        })
+    }
+
+    /// All data points of a series, fetching pages as the stream is read
+    pub fn data_points_stream(
+        &self,
+        series_id: Uuid,
+        options: DataPointOptions,
+    ) -> impl Stream<Item = ClientResult<DataPoint>> + '_ {
+        paginate(options.after.clone(), move |after| {
+            let options = DataPointOptions {
+                after,
+                ..options.clone()
+            };
+            async move {
+                let page = self.get_data_points(series_id, &options).await?;
+                Ok((page.points, page.next_cursor))
+            }
+        })
+    }
+
+    /// All data points of a series, reading every page
+    pub async fn get_all_data_points(
+        &self,
+        series_id: Uuid,
+        options: DataPointOptions,
+    ) -> ClientResult<Vec<DataPoint>> {
+        self.data_points_stream(series_id, options)
+            .try_collect()
+            .await
+    }
+
+    /// Refresh the token unless it changed since `used` was sent
+    ///
+    /// Refresh tokens are single-use, so when concurrent requests are all
+    /// rejected only the first one exchanges it. Returns whether a new token
+    /// is available.
+    async fn refresh_after(&self, used: Option<&str>) -> ClientResult<bool> {
+        let mut session = self.session.write().await;
+        if session.token.as_deref() != used {
+            return Ok(true);
+        }
+        if session.refresh_token.is_none() {
+            return Ok(false);
+        }
+
+        self.exchange_refresh_token(&mut session).await?;
+        Ok(true)
+    }
+
+    async fn exchange_refresh_token(&self, session: &mut Session) -> ClientResult<()> {
+        let refresh_token = session
+            .refresh_token
+            .clone()
+            .ok_or_else(|| ClientError::Authentication("No refresh token".to_string()))?;
+
+        let tokens = self
+            .post_auth("/auth/refresh", &json!({ "refresh_token": refresh_token }))
+            .await?;
+        session.token = Some(tokens.token);
+        session.refresh_token = tokens.refresh_token;
+        Ok(())
+    }
+
+    async fn post_auth(&self, path: &str, body: &Value) -> ClientResult<AuthTokens> {
+        let response = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .json(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ClientError::Authentication(format!(
+                "HTTP {}: {}",
+                status.as_u16(),
+                body
+            )));
+        }
+
+        response
+            .json::<AuthTokens>()
+            .await
+            .map_err(|e| ClientError::InvalidResponse(e.to_string()))
+    }
+
+    async fn send_with_retries(&self, body: &Value, token: Option<&str>) -> ClientResult<Value> {
+        let mut retry = 0;
+        loop {
+            match self.send(body, token).await {
+                Err(error) if error.is_retryable() && retry < self.retry_policy.max_retries => {
+                    let delay = error
+                        .retry_after()
+                        .unwrap_or_else(|| self.retry_policy.backoff(retry));
+                    tracing::debug!("Retrying EconGraph API request in {:?}: {}", delay, error);
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send(&self, body: &Value, token: Option<&str>) -> ClientResult<Value> {
+        let mut request = self
+            .http
+            .post(format!("{}/graphql", self.base_url))
+            .json(body);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let body = response.text().await.unwrap_or_default();
+            return Err(ClientError::Status {
+                status: status.as_u16(),
+                retry_after,
+                body,
+            });
+        }
+
+        parse_response(&response.bytes().await?)
+    }
+}
+
+/// The `data` of a GraphQL response, or its errors
+fn parse_response(body: &[u8]) -> ClientResult<Value> {
+    let response: GraphQLResponse =
+        serde_json::from_slice(body).map_err(|e| ClientError::InvalidResponse(e.to_string()))?;
+
+    if !response.errors.is_empty() {
+        return Err(ClientError::GraphQL(response.errors));
+    }
+
+    response
+        .data
+        .ok_or_else(|| ClientError::InvalidResponse("Response has no data".to_string()))
+}
+
+/// Stream of the items of consecutive pages, starting at cursor `first`
+///
+/// `fetch` returns the items of the page at a cursor and the cursor of the
+/// next page, `None` on the last one.
+fn paginate<'a, T, F, Fut>(
+    first: Option<String>,
+    mut fetch: F,
+) -> impl Stream<Item = ClientResult<T>> + 'a
+where
+    T: 'a,
+    F: FnMut(Option<String>) -> Fut + 'a,
+    Fut: Future<Output = ClientResult<(Vec<T>, Option<String>)>> + 'a,
+{
+    stream::try_unfold(Some(first), move |cursor: Option<Option<String>>| {
+        let page = cursor.map(&mut fetch);
+        async move {
+            match page {
+                Some(page) => {
+                    let (items, next_cursor) = page.await?;
+                    Ok(Some((items, next_cursor.map(Some))))
+                }
+                None => Ok(None),
+            }
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_and_error_classification() {
+        // REQUIREMENT: Typed Rust client with retries
+        // PURPOSE: Verify the backoff doubles up to its cap and only transient failures are retried
+        // This ensures rate limits and gateway errors are retried while bad queries fail at once
+
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+
+        let status = |status: u16| ClientError::Status {
+            status,
+            retry_after: None,
+            body: String::new(),
+        };
+        assert!(status(429).is_retryable());
+        assert!(status(503).is_retryable());
+        assert!(!status(400).is_retryable());
+        assert!(status(401).is_unauthenticated());
+
+        let rate_limited = parse_response(
+            br#"{"data": null, "errors": [{"message": "Rate limit exceeded. Please try again later."}]}"#,
+        )
+        .unwrap_err();
+        assert!(rate_limited.is_retryable());
+
+        let unauthenticated = parse_response(
+            br#"{"data": null, "errors": [{"message": "Sign in required", "path": ["seriesData"], "extensions": {"code": "UNAUTHENTICATED"}}]}"#,
+        )
+        .unwrap_err();
+        assert!(unauthenticated.is_unauthenticated());
+        assert!(!unauthenticated.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_data_point_page() {
+        // REQUIREMENT: Typed Rust client for the GraphQL API
+        // PURPOSE: Verify a seriesData response deserializes into typed data points with exact values
+        // This ensures values keep their precision and the next cursor is read from pageInfo
+
+        let data = parse_response(
+            br#"{"data": {"seriesData": {
+                "nodes": [{"date": "2024-01-01", "value": "3.14159265358979", "revisionDate": "2024-02-01", "isOriginalRelease": true},
+                          {"date": "2024-02-01", "value": null, "revisionDate": "2024-03-01", "isOriginalRelease": false}],
+                "totalCount": 120,
+                "totalCountIsEstimate": false,
+                "pageInfo": {"hasNextPage": true, "endCursor": "abc"}
+            }}}"#,
+        )
+        .unwrap();
+
+        let data: SeriesDataData = serde_json::from_value(data).unwrap();
+        let connection = data.series_data;
+        assert_eq!(connection.nodes.len(), 2);
+        assert_eq!(
+            connection.nodes[0].value.as_ref().unwrap().to_string(),
+            "3.14159265358979"
+        );
+        assert!(connection.nodes[1].value.is_none());
+        assert_eq!(connection.total_count, 120);
+        assert!(connection.page_info.has_next_page);
+        assert_eq!(connection.page_info.end_cursor.as_deref(), Some("abc"));
+    }
+}
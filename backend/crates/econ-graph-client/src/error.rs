@@ -0,0 +1,109 @@
+//! Errors returned by the client
+
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Extension code of errors raised for requests without a valid token
+pub const UNAUTHENTICATED: &str = "UNAUTHENTICATED";
+
+/// Message of the GraphQL error returned when the API rate limit is hit
+const RATE_LIMIT_MESSAGE: &str = "Rate limit exceeded";
+
+/// Result type of client calls
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// An error reported in the `errors` list of a GraphQL response
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+    /// Path of the field that failed; absent for errors in the query itself
+    #[serde(default)]
+    pub path: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub extensions: Option<serde_json::Value>,
+}
+
+impl GraphQLError {
+    /// The `code` extension, e.g. "UNAUTHENTICATED"
+    pub fn code(&self) -> Option<&str> {
+        self.extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get("code"))
+            .and_then(|code| code.as_str())
+    }
+
+    /// Whether this error is the API's rate limit rejection
+    pub fn is_rate_limited(&self) -> bool {
+        self.message.starts_with(RATE_LIMIT_MESSAGE)
+    }
+}
+
+impl std::fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.code() {
+            Some(code) => write!(f, "{} ({})", self.message, code),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Client error
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Invalid API URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("API returned HTTP {status}: {body}")]
+    Status {
+        status: u16,
+        /// Delay asked for by a `Retry-After` header
+        retry_after: Option<Duration>,
+        body: String,
+    },
+
+    #[error("GraphQL error: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    GraphQL(Vec<GraphQLError>),
+
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+}
+
+impl ClientError {
+    /// Whether the request may succeed when sent again
+    ///
+    /// Connection failures and timeouts, HTTP 429, 502, 503 and 504, and the
+    /// API's rate limit error are retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Http(error) => error.is_connect() || error.is_timeout(),
+            ClientError::Status { status, .. } => matches!(status, 429 | 502 | 503 | 504),
+            ClientError::GraphQL(errors) => errors.iter().any(GraphQLError::is_rate_limited),
+            _ => false,
+        }
+    }
+
+    /// Delay asked for by the server before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ClientError::Status { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether the API rejected the request for lack of a valid token
+    pub fn is_unauthenticated(&self) -> bool {
+        match self {
+            ClientError::Status { status, .. } => *status == 401,
+            ClientError::GraphQL(errors) => errors
+                .iter()
+                .any(|error| error.code() == Some(UNAUTHENTICATED)),
+            _ => false,
+        }
+    }
+}
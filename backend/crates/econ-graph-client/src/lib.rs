@@ -0,0 +1,53 @@
+// Copyright (c) 2024 EconGraph. All rights reserved.
+// Licensed under the Microsoft Reference Source License (MS-RSL).
+// See LICENSE file for complete terms and conditions.
+
+//! # EconGraph Client
+//!
+//! Typed Rust client for the `EconGraph` GraphQL API.
+//!
+//! ## Features
+//!
+//! - **Typed Methods**: Series search, data points and company financials as Rust types
+//! - **Authentication**: Sign in with email and password, or pass a token; expired tokens are refreshed
+//! - **Retries**: Rate limits, gateway errors and connection failures are retried with exponential backoff
+//! - **Pagination**: Streams that fetch further pages as they are read
+//! - **Raw Queries**: Any other GraphQL document through [`EconGraphClient::execute`]
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_client::{DataPointOptions, EconGraphClient, SearchOptions};
+//! use futures::TryStreamExt;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), econ_graph_client::ClientError> {
+//!     let client = EconGraphClient::new("https://api.econgraph.com")?;
+//!     client.login("analyst@example.com", "password").await?;
+//!
+//!     let page = client
+//!         .search_series("unemployment rate", &SearchOptions::default())
+//!         .await?;
+//!     let series = &page.series[0];
+//!
+//!     let points: Vec<_> = client
+//!         .data_points_stream(series.id, DataPointOptions::default())
+//!         .try_collect()
+//!         .await?;
+//!     println!("{}: {} observations", series.title, points.len());
+//!
+//!     Ok(())
+//! }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod queries;
+pub mod types;
+
+pub use client::{ClientBuilder, EconGraphClient, RetryPolicy};
+pub use error::{ClientError, ClientResult, GraphQLError};
+pub use types::{
+    DataPoint, DataPointOptions, DataPointPage, FinancialFact, FinancialStatement, Frequency,
+    SearchOptions, Series, SeriesPage, Transformation,
+};
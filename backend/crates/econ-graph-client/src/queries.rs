@@ -0,0 +1,76 @@
+//! GraphQL documents sent by the client
+//!
+//! `tests/schema_compatibility.rs` validates each document against the
+//! server schema, so a schema change that breaks the client fails the build
+//! of the workspace tests rather than requests in the field.
+
+/// Series matching a full-text query, paged by offset
+pub const SEARCH_SERIES: &str = r#"
+query SearchSeries($query: String!, $source: String, $frequency: SeriesFrequency, $first: Int, $after: String) {
+  searchSeries(query: $query, source: $source, frequency: $frequency, first: $first, after: $after) {
+    series {
+      id
+      sourceId
+      externalId
+      title
+      description
+      units
+      frequency
+      seasonalAdjustment
+      lastUpdated
+      startDate
+      endDate
+      isActive
+    }
+  }
+}
+"#;
+
+/// One page of the data points of a series
+pub const SERIES_DATA: &str = r#"
+query SeriesData($seriesId: ID!, $filter: DataFilter, $transformation: DataTransformation, $first: Int, $after: String) {
+  seriesData(seriesId: $seriesId, filter: $filter, transformation: $transformation, first: $first, after: $after) {
+    nodes {
+      date
+      value
+      revisionDate
+      isOriginalRelease
+    }
+    totalCount
+    totalCountIsEstimate
+    pageInfo {
+      hasNextPage
+      endCursor
+    }
+  }
+}
+"#;
+
+/// Latest processed filings of a company with their facts
+pub const COMPANY_FINANCIALS: &str = r#"
+query CompanyFinancials($companyId: ID!, $limit: Int!) {
+  companyFinancials(companyId: $companyId, limit: $limit) {
+    id
+    companyId
+    formType
+    accessionNumber
+    filingDate
+    periodEndDate
+    fiscalYear
+    fiscalQuarter
+    isAmended
+    facts {
+      lineItemId
+      concept
+      label
+      statementType
+      value
+      unit
+      normalizedValue
+      normalizedUnit
+      periodStartDate
+      periodEndDate
+    }
+  }
+}
+"#;
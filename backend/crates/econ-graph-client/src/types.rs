@@ -0,0 +1,149 @@
+//! Typed results and options of client calls
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Series frequency filter of `searchSeries`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+    Irregular,
+}
+
+/// Transformation applied to data points by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Transformation {
+    None,
+    YearOverYear,
+    QuarterOverQuarter,
+    MonthOverMonth,
+    PercentChange,
+    LogDifference,
+}
+
+/// An economic time series
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Series {
+    pub id: Uuid,
+    pub source_id: Uuid,
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    pub frequency: String,
+    pub seasonal_adjustment: Option<String>,
+    pub last_updated: Option<DateTime<Utc>>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub is_active: bool,
+}
+
+/// One page of search results
+#[derive(Debug, Clone)]
+pub struct SeriesPage {
+    pub series: Vec<Series>,
+    /// Cursor of the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// An observation of a series
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataPoint {
+    pub date: NaiveDate,
+    pub value: Option<BigDecimal>,
+    pub revision_date: NaiveDate,
+    pub is_original_release: bool,
+}
+
+/// One page of data points
+#[derive(Debug, Clone)]
+pub struct DataPointPage {
+    pub points: Vec<DataPoint>,
+    /// Number of data points matching the filter, over all pages
+    pub total_count: i64,
+    /// Whether `total_count` is an estimate (large result sets)
+    pub total_count_is_estimate: bool,
+    /// Cursor of the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// A processed filing of a company with its company-level XBRL facts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinancialStatement {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub form_type: String,
+    pub accession_number: String,
+    pub filing_date: NaiveDate,
+    pub period_end_date: NaiveDate,
+    pub fiscal_year: i32,
+    /// `None` for annual reports
+    pub fiscal_quarter: Option<i32>,
+    pub is_amended: bool,
+    pub facts: Vec<FinancialFact>,
+}
+
+/// A company-level XBRL fact, e.g. revenues for the period
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinancialFact {
+    pub line_item_id: Uuid,
+    /// Taxonomy concept, e.g. "us-gaap:Revenues"
+    pub concept: String,
+    pub label: Option<String>,
+    /// "income_statement", "balance_sheet", "cash_flow" or "equity"
+    pub statement_type: String,
+    pub value: Option<BigDecimal>,
+    pub unit: String,
+    /// Value in canonical units (absolute USD, shares, ...)
+    pub normalized_value: Option<BigDecimal>,
+    pub normalized_unit: Option<String>,
+    pub period_start_date: Option<NaiveDate>,
+    pub period_end_date: Option<NaiveDate>,
+}
+
+impl FinancialStatement {
+    /// The fact reported for a concept, if any
+    pub fn fact(&self, concept: &str) -> Option<&FinancialFact> {
+        self.facts.iter().find(|fact| fact.concept == concept)
+    }
+}
+
+/// Options of [`search_series`](crate::EconGraphClient::search_series)
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Only series of this data source
+    pub source_id: Option<Uuid>,
+    pub frequency: Option<Frequency>,
+    /// Results per page; 50 by default
+    pub page_size: Option<i32>,
+    /// Cursor of the page to fetch, from [`SeriesPage::next_cursor`]
+    pub after: Option<String>,
+}
+
+/// Options of [`get_data_points`](crate::EconGraphClient::get_data_points)
+#[derive(Debug, Clone, Default)]
+pub struct DataPointOptions {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Only first releases
+    pub original_only: bool,
+    /// Only the latest revision of each date
+    pub latest_revision_only: bool,
+    pub transformation: Option<Transformation>,
+    /// Points per page; 1000 by default, at most 10000
+    pub page_size: Option<i32>,
+    /// Cursor of the page to fetch, from [`DataPointPage::next_cursor`]
+    pub after: Option<String>,
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2024 EconGraph. All rights reserved.
+// Licensed under the Microsoft Reference Source License (MS-RSL).
+// See LICENSE file for complete terms and conditions.
+
+use async_graphql::{Request, Schema, Variables};
+use econ_graph_client::queries;
+use econ_graph_graphql::{Mutation, Query, Subscription};
+use serde_json::json;
+
+/// Run a client document against the server schema without a database
+///
+/// Validation errors (unknown fields, wrong variable types, ...) have no
+/// path. Once a document validates, the resolvers run and fail for lack of a
+/// database pool, and those errors carry the path of their field.
+async fn assert_document_matches_schema(name: &str, document: &str, variables: serde_json::Value) {
+    let schema = Schema::build(Query, Mutation, Subscription).finish();
+    let response = schema
+        .execute(Request::new(document).variables(Variables::from_json(variables)))
+        .await;
+
+    assert!(
+        !response.errors.is_empty(),
+        "{} ran without a database pool",
+        name
+    );
+    for error in &response.errors {
+        assert!(
+            !error.path.is_empty(),
+            "{} does not match the server schema: {}",
+            name,
+            error.message
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_client_documents_match_server_schema() {
+    // REQUIREMENT: Typed Rust client maintained against the GraphQL schema
+    // PURPOSE: Verify every document sent by the client validates against the server schema
+    // This ensures a schema change that breaks the client fails the tests, not client requests
+
+    assert_document_matches_schema(
+        "SEARCH_SERIES",
+        queries::SEARCH_SERIES,
+        json!({
+            "query": "unemployment",
+            "source": "2f8e1d3c-5b0a-4f6e-9c7d-1a2b3c4d5e6f",
+            "frequency": "MONTHLY",
+            "first": 10,
+            "after": "10",
+        }),
+    )
+    .await;
+
+    assert_document_matches_schema(
+        "SERIES_DATA",
+        queries::SERIES_DATA,
+        json!({
+            "seriesId": "2f8e1d3c-5b0a-4f6e-9c7d-1a2b3c4d5e6f",
+            "filter": {
+                "startDate": "2020-01-01",
+                "endDate": null,
+                "originalOnly": false,
+                "latestRevisionOnly": true,
+            },
+            "transformation": "YEAR_OVER_YEAR",
+            "first": 500,
+            "after": null,
+        }),
+    )
+    .await;
+
+    assert_document_matches_schema(
+        "COMPANY_FINANCIALS",
+        queries::COMPANY_FINANCIALS,
+        json!({
+            "companyId": "2f8e1d3c-5b0a-4f6e-9c7d-1a2b3c4d5e6f",
+            "limit": 4,
+        }),
+    )
+    .await;
+}
@@ -3,6 +3,7 @@
  * PURPOSE: Define user authentication data structures and JWT tokens
  * This enables secure user management with OAuth providers
  */
+pub use crate::enums::SubscriptionTier;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -23,6 +24,8 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub last_login_at: DateTime<Utc>,
     pub is_active: bool,
+    #[serde(default)]
+    pub subscription_tier: SubscriptionTier,
 }
 
 /// Authentication provider types
@@ -65,7 +68,7 @@ impl Default for UserPreferences {
 }
 
 /// JWT Claims structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // Subject (user ID)
     pub email: String,
@@ -74,6 +77,14 @@ pub struct Claims {
     pub exp: usize,  // Expiration time
     pub iat: usize,  // Issued at
     pub iss: String, // Issuer
+    /// Session (`user_sessions.id`) the token was issued for; revoking the
+    /// session invalidates the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    /// Subscription tier when the token was issued; tokens issued before
+    /// tiers existed are treated as free
+    #[serde(default)]
+    pub tier: SubscriptionTier,
 }
 
 /// Google OAuth user info
@@ -143,9 +154,18 @@ pub struct FacebookAuthRequest {
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
     pub token: String,
+    /// Single-use token exchanged at `/auth/refresh` for a new token pair
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
     pub user: UserResponse,
 }
 
+/// Refresh token exchange request
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
 /// User response (without sensitive data)
 #[derive(Debug, Serialize)]
 pub struct UserResponse {
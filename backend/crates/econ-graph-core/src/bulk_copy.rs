@@ -0,0 +1,434 @@
+//! Bulk loading with binary COPY
+//!
+//! Diesel sends every row of an insert as bind parameters, which makes large
+//! crawls slow and caps a statement at 65535 parameters. [`BulkCopy`] instead
+//! streams rows in PostgreSQL's binary `COPY` format into a temporary staging
+//! table and moves them into the target table with one `INSERT ... SELECT ...
+//! ON CONFLICT`, so conflicts are resolved the same way as on the regular
+//! insert path.
+//!
+//! COPY runs on a dedicated `tokio-postgres` connection, inside one
+//! transaction; the staging table is dropped when it commits.
+
+use bigdecimal::BigDecimal;
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::pg::data_types::PgNumeric;
+use futures::SinkExt;
+use std::pin::pin;
+use std::str::FromStr;
+use std::time::Instant;
+use tokio_postgres::{Client, NoTls, Transaction};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::{DataPoint, FinancialLineItem, NewDataPoint};
+
+/// Smallest batch sent through COPY; an estimate until the benchmark has been run
+pub const BULK_COPY_MIN_ROWS: usize = 1_000;
+
+/// Encoded rows buffered before they are sent to the server
+const COPY_CHUNK_BYTES: usize = 1 << 20;
+
+const DATA_POINT_COLUMNS: &str = "series_id, date, value, revision_date, is_original_release";
+
+const LINE_ITEM_COLUMNS: &str = "id, statement_id, taxonomy_concept, standard_label, \
+     custom_label, value, unit, context_ref, segment_ref, scenario_ref, \"precision\", decimals, \
+     is_credit, is_debit, statement_type, statement_section, parent_concept, level, order_index, \
+     is_calculated, calculation_formula, created_at, updated_at, normalized_value, \
+     normalized_unit, fx_rate, dimensions, period_start_date, period_end_date";
+
+fn pg_epoch() -> DateTime<Utc> {
+    DateTime::from_timestamp(946_684_800, 0).expect("2000-01-01 is a valid timestamp")
+}
+
+fn copy_error(e: tokio_postgres::Error) -> AppError {
+    AppError::DatabaseError(format!("Bulk copy failed: {}", e))
+}
+
+/// Rows encoded in PostgreSQL's binary COPY format
+///
+/// Start each row with [`BinaryCopyWriter::row`], then write one field per
+/// column in the order of the `COPY` column list.
+pub struct BinaryCopyWriter {
+    buffer: BytesMut,
+}
+
+impl Default for BinaryCopyWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinaryCopyWriter {
+    pub fn new() -> Self {
+        let mut buffer = BytesMut::with_capacity(COPY_CHUNK_BYTES);
+        buffer.put_slice(b"PGCOPY\n\xff\r\n\0");
+        buffer.put_i32(0); // flags
+        buffer.put_i32(0); // header extension length
+        Self { buffer }
+    }
+
+    /// Start a row of `columns` fields
+    pub fn row(&mut self, columns: i16) {
+        self.buffer.put_i16(columns);
+    }
+
+    pub fn null(&mut self) {
+        self.buffer.put_i32(-1);
+    }
+
+    /// Write `value` with `field`, or NULL
+    pub fn nullable<T>(&mut self, value: Option<T>, field: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => field(self, value),
+            None => self.null(),
+        }
+    }
+
+    fn field(&mut self, bytes: &[u8]) {
+        self.buffer.put_i32(bytes.len() as i32);
+        self.buffer.put_slice(bytes);
+    }
+
+    pub fn uuid(&mut self, value: Uuid) {
+        self.field(value.as_bytes());
+    }
+
+    /// TEXT or VARCHAR
+    pub fn text(&mut self, value: &str) {
+        self.field(value.as_bytes());
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.field(&[value as u8]);
+    }
+
+    pub fn int4(&mut self, value: i32) {
+        self.field(&value.to_be_bytes());
+    }
+
+    pub fn date(&mut self, value: NaiveDate) {
+        let days = (value - pg_epoch().date_naive()).num_days() as i32;
+        self.field(&days.to_be_bytes());
+    }
+
+    pub fn timestamptz(&mut self, value: DateTime<Utc>) {
+        let micros = (value - pg_epoch())
+            .num_microseconds()
+            .expect("timestamp within PostgreSQL's range");
+        self.field(&micros.to_be_bytes());
+    }
+
+    /// NUMERIC as base-10000 digits, the server's own representation
+    pub fn numeric(&mut self, value: &BigDecimal) {
+        let (sign, weight, scale, digits) = match PgNumeric::from(value) {
+            PgNumeric::Positive {
+                weight,
+                scale,
+                digits,
+            } => (0x0000, weight, scale, digits),
+            PgNumeric::Negative {
+                weight,
+                scale,
+                digits,
+            } => (0x4000, weight, scale, digits),
+            PgNumeric::NaN => (0xC000, 0, 0, Vec::new()),
+        };
+        self.buffer.put_i32(8 + 2 * digits.len() as i32);
+        self.buffer.put_i16(digits.len() as i16);
+        self.buffer.put_i16(weight);
+        self.buffer.put_u16(sign);
+        self.buffer.put_u16(scale);
+        for digit in digits {
+            self.buffer.put_i16(digit);
+        }
+    }
+
+    pub fn jsonb(&mut self, value: &serde_json::Value) {
+        let text = value.to_string();
+        self.buffer.put_i32(1 + text.len() as i32);
+        self.buffer.put_u8(1); // jsonb format version
+        self.buffer.put_slice(text.as_bytes());
+    }
+
+    /// Bytes encoded since the last [`BinaryCopyWriter::take`]
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Hand over the bytes encoded so far
+    pub fn take(&mut self) -> Bytes {
+        self.buffer.split().freeze()
+    }
+
+    /// Hand over the remaining bytes, followed by the end-of-data marker
+    pub fn finish(mut self) -> Bytes {
+        self.buffer.put_i16(-1);
+        self.buffer.freeze()
+    }
+}
+
+/// Dedicated connection for binary COPY loads
+pub struct BulkCopy {
+    client: Client,
+}
+
+impl BulkCopy {
+    pub async fn connect(database_url: &str) -> AppResult<Self> {
+        let (client, connection) = tokio_postgres::connect(database_url, NoTls)
+            .await
+            .map_err(copy_error)?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                warn!("Bulk copy connection closed with an error: {}", e);
+            }
+        });
+        Ok(Self { client })
+    }
+
+    /// Connect to the database of this process's pools
+    ///
+    /// `None` when that database is not known; see
+    /// [`pool_database_url`](crate::database::pool_database_url).
+    pub async fn connect_to_pool_database() -> AppResult<Option<Self>> {
+        match crate::database::pool_database_url() {
+            Some(url) => Ok(Some(Self::connect(&url).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Insert data points, skipping those that already exist
+    ///
+    /// Returns the inserted points.
+    pub async fn insert_data_points(
+        &mut self,
+        new_data_points: &[NewDataPoint],
+    ) -> AppResult<Vec<DataPoint>> {
+        let started = Instant::now();
+        let transaction = self.client.transaction().await.map_err(copy_error)?;
+
+        copy_into_staging(
+            &transaction,
+            "data_points",
+            DATA_POINT_COLUMNS,
+            new_data_points,
+            |writer, point| {
+                writer.row(5);
+                writer.uuid(point.series_id);
+                writer.date(point.date);
+                writer.nullable(point.value.as_ref(), BinaryCopyWriter::numeric);
+                writer.date(point.revision_date);
+                writer.bool(point.is_original_release);
+            },
+        )
+        .await?;
+
+        let statement = format!(
+            "INSERT INTO data_points ({columns})
+             SELECT {columns} FROM data_points_staging
+             ON CONFLICT (series_id, date, revision_date, is_original_release) DO NOTHING
+             RETURNING id, series_id, date, value::text, revision_date,
+                       is_original_release, created_at, updated_at",
+            columns = DATA_POINT_COLUMNS
+        );
+        let rows = transaction
+            .query(statement.as_str(), &[])
+            .await
+            .map_err(copy_error)?;
+        transaction.commit().await.map_err(copy_error)?;
+
+        let data_points = rows
+            .iter()
+            .map(|row| {
+                Ok(DataPoint {
+                    id: row.try_get(0).map_err(copy_error)?,
+                    series_id: row.try_get(1).map_err(copy_error)?,
+                    date: row.try_get(2).map_err(copy_error)?,
+                    value: row
+                        .try_get::<_, Option<String>>(3)
+                        .map_err(copy_error)?
+                        .map(|value| BigDecimal::from_str(&value))
+                        .transpose()?,
+                    revision_date: row.try_get(4).map_err(copy_error)?,
+                    is_original_release: row.try_get(5).map_err(copy_error)?,
+                    created_at: row.try_get(6).map_err(copy_error)?,
+                    updated_at: row.try_get(7).map_err(copy_error)?,
+                })
+            })
+            .collect::<AppResult<Vec<_>>>()?;
+
+        info!(
+            "Copied {} of {} data points in {:?}",
+            data_points.len(),
+            new_data_points.len(),
+            started.elapsed()
+        );
+        Ok(data_points)
+    }
+
+    /// Upsert line items by statement, concept, context and unit
+    ///
+    /// Matches the regular upsert in the SEC crawler's storage: existing line
+    /// items keep their id and annotations and take the new values. Returns
+    /// the number of line items inserted or updated.
+    pub async fn upsert_line_items(&mut self, line_items: &[FinancialLineItem]) -> AppResult<u64> {
+        let started = Instant::now();
+        let transaction = self.client.transaction().await.map_err(copy_error)?;
+
+        copy_into_staging(
+            &transaction,
+            "financial_line_items",
+            LINE_ITEM_COLUMNS,
+            line_items,
+            |writer, item| {
+                writer.row(29);
+                writer.uuid(item.id);
+                writer.uuid(item.statement_id);
+                writer.text(&item.taxonomy_concept);
+                writer.nullable(item.standard_label.as_deref(), BinaryCopyWriter::text);
+                writer.nullable(item.custom_label.as_deref(), BinaryCopyWriter::text);
+                writer.nullable(item.value.as_ref(), BinaryCopyWriter::numeric);
+                writer.text(&item.unit);
+                writer.text(&item.context_ref);
+                writer.nullable(item.segment_ref.as_deref(), BinaryCopyWriter::text);
+                writer.nullable(item.scenario_ref.as_deref(), BinaryCopyWriter::text);
+                writer.nullable(item.precision, BinaryCopyWriter::int4);
+                writer.nullable(item.decimals, BinaryCopyWriter::int4);
+                writer.nullable(item.is_credit, BinaryCopyWriter::bool);
+                writer.nullable(item.is_debit, BinaryCopyWriter::bool);
+                writer.text(item.statement_type.as_str());
+                writer.text(item.statement_section.as_str());
+                writer.nullable(item.parent_concept.as_deref(), BinaryCopyWriter::text);
+                writer.int4(item.level);
+                writer.nullable(item.order_index, BinaryCopyWriter::int4);
+                writer.bool(item.is_calculated);
+                writer.nullable(item.calculation_formula.as_deref(), BinaryCopyWriter::text);
+                writer.timestamptz(item.created_at);
+                writer.timestamptz(item.updated_at);
+                writer.nullable(item.normalized_value.as_ref(), BinaryCopyWriter::numeric);
+                writer.nullable(item.normalized_unit.as_deref(), BinaryCopyWriter::text);
+                writer.nullable(item.fx_rate.as_ref(), BinaryCopyWriter::numeric);
+                writer.jsonb(&item.dimensions);
+                writer.nullable(item.period_start_date, BinaryCopyWriter::date);
+                writer.nullable(item.period_end_date, BinaryCopyWriter::date);
+            },
+        )
+        .await?;
+
+        // DISTINCT ON because one statement cannot update the same row twice
+        let statement = format!(
+            "INSERT INTO financial_line_items ({columns})
+             SELECT DISTINCT ON (statement_id, taxonomy_concept, context_ref, unit) {columns}
+             FROM financial_line_items_staging
+             ORDER BY statement_id, taxonomy_concept, context_ref, unit
+             ON CONFLICT (statement_id, taxonomy_concept, context_ref, unit) DO UPDATE SET
+                 standard_label = EXCLUDED.standard_label,
+                 value = EXCLUDED.value,
+                 \"precision\" = EXCLUDED.\"precision\",
+                 decimals = EXCLUDED.decimals,
+                 statement_type = EXCLUDED.statement_type,
+                 statement_section = EXCLUDED.statement_section,
+                 normalized_value = EXCLUDED.normalized_value,
+                 normalized_unit = EXCLUDED.normalized_unit,
+                 fx_rate = EXCLUDED.fx_rate,
+                 dimensions = EXCLUDED.dimensions,
+                 period_start_date = EXCLUDED.period_start_date,
+                 period_end_date = EXCLUDED.period_end_date,
+                 updated_at = EXCLUDED.updated_at",
+            columns = LINE_ITEM_COLUMNS
+        );
+        let stored = transaction
+            .execute(statement.as_str(), &[])
+            .await
+            .map_err(copy_error)?;
+        transaction.commit().await.map_err(copy_error)?;
+
+        info!("Copied {} line items in {:?}", stored, started.elapsed());
+        Ok(stored)
+    }
+}
+
+/// Create `<table>_staging` for the transaction and COPY `rows` into it
+async fn copy_into_staging<T>(
+    transaction: &Transaction<'_>,
+    table: &str,
+    columns: &str,
+    rows: &[T],
+    encode: impl Fn(&mut BinaryCopyWriter, &T),
+) -> AppResult<u64> {
+    transaction
+        .batch_execute(&format!(
+            "CREATE TEMP TABLE {table}_staging (LIKE {table} INCLUDING DEFAULTS) ON COMMIT DROP"
+        ))
+        .await
+        .map_err(copy_error)?;
+
+    let statement = format!("COPY {table}_staging ({columns}) FROM STDIN (FORMAT binary)");
+    let mut sink = pin!(transaction
+        .copy_in::<_, Bytes>(statement.as_str())
+        .await
+        .map_err(copy_error)?);
+
+    let mut writer = BinaryCopyWriter::new();
+    for row in rows {
+        encode(&mut writer, row);
+        if writer.buffered_len() >= COPY_CHUNK_BYTES {
+            sink.send(writer.take()).await.map_err(copy_error)?;
+        }
+    }
+    sink.send(writer.finish()).await.map_err(copy_error)?;
+    sink.as_mut().finish().await.map_err(copy_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_encodes_binary_copy_rows() {
+        // REQUIREMENT: COPY-based bulk insert path for data points
+        // PURPOSE: Verify the header, field layout, NULLs and trailer of the binary COPY format
+        // This ensures the server accepts the stream and reads each column as written
+
+        let mut writer = BinaryCopyWriter::new();
+        writer.row(4);
+        writer.uuid(Uuid::from_u128(1));
+        writer.date(NaiveDate::from_ymd_opt(2000, 1, 2).unwrap());
+        writer.nullable(None::<&BigDecimal>, BinaryCopyWriter::numeric);
+        writer.bool(true);
+        let bytes = writer.finish();
+
+        let mut expected = b"PGCOPY\n\xff\r\n\0".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend_from_slice(&4i16.to_be_bytes());
+        expected.extend_from_slice(&16i32.to_be_bytes());
+        expected.extend_from_slice(&1u128.to_be_bytes());
+        expected.extend_from_slice(&4i32.to_be_bytes());
+        expected.extend_from_slice(&1i32.to_be_bytes());
+        expected.extend_from_slice(&(-1i32).to_be_bytes());
+        expected.extend_from_slice(&1i32.to_be_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&(-1i16).to_be_bytes());
+        assert_eq!(bytes.as_ref(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_writer_encodes_numeric_as_base_10000_digits() {
+        // REQUIREMENT: COPY-based bulk insert path for data points and XBRL facts
+        // PURPOSE: Verify NUMERIC values are sent as weight, sign, scale and base-10000 digits
+        // This ensures values copied in binary match those stored by the regular insert path
+
+        let mut writer = BinaryCopyWriter::new();
+        let header_len = writer.buffered_len();
+        writer.numeric(&BigDecimal::from_str("-12345.678").unwrap());
+        let bytes = writer.take();
+
+        let mut expected = 14i32.to_be_bytes().to_vec();
+        for word in [3i16, 1, 0x4000, 3, 1, 2345, 6780] {
+            expected.extend_from_slice(&word.to_be_bytes());
+        }
+        assert_eq!(&bytes[header_len..], expected.as_slice());
+    }
+}
@@ -1,8 +1,74 @@
+//! Application configuration
+//!
+//! Every binary reads the same [`Config`], layered from lowest to highest
+//! precedence:
+//!
+//! 1. Built-in defaults
+//! 2. A configuration file (TOML, YAML or JSON) named by `--config` or `ECON_GRAPH_CONFIG`
+//! 3. The established environment variables (`DATABASE_URL`, `BACKEND_PORT`, `RUST_LOG`, ...)
+//! 4. `ECON_GRAPH__<SECTION>__<FIELD>` environment variables, e.g. `ECON_GRAPH__CRAWLER__MAX_CONCURRENT_JOBS`
+//! 5. `--set key=value` command line overrides, e.g. `--set server.port=9000`
+//!
+//! The result is validated before use. Long-running processes can watch the
+//! configuration file with [`ConfigLoader::watch`]; the log level, crawler
+//! concurrency and slow-query threshold take effect without a restart, other
+//! changes are logged as needing one.
+
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+use tracing::{info, warn};
+
+use crate::error::{AppError, AppResult};
+
+/// Environment variable naming the configuration file
+pub const CONFIG_FILE_ENV: &str = "ECON_GRAPH_CONFIG";
+
+/// Prefix of structured configuration environment variables
+pub const ENV_PREFIX: &str = "ECON_GRAPH";
+
+/// Most concurrent crawl jobs a configuration may ask for
+pub const MAX_CRAWLER_CONCURRENCY: usize = 256;
+
+/// Established environment variables and the configuration keys they set
+const LEGACY_ENV_VARS: [(&str, &str); 17] = [
+    ("DATABASE_URL", "database_url"),
+    ("SERVER_HOST", "server.host"),
+    ("BACKEND_PORT", "server.port"),
+    ("CORS_ALLOWED_ORIGINS", "cors.allowed_origins"),
+    ("FRED_API_KEY", "crawler.fred_api_key"),
+    ("BLS_API_KEY", "crawler.bls_api_key"),
+    ("MAX_CONCURRENT_JOBS", "crawler.max_concurrent_jobs"),
+    (
+        "QUEUE_POLL_INTERVAL_SECONDS",
+        "crawler.queue_poll_interval_seconds",
+    ),
+    (
+        "FRED_RATE_LIMIT_PER_MINUTE",
+        "rate_limits.fred_rate_limit_per_minute",
+    ),
+    (
+        "BLS_RATE_LIMIT_PER_MINUTE",
+        "rate_limits.bls_rate_limit_per_minute",
+    ),
+    ("GOOGLE_CLIENT_ID", "oauth.google_client_id"),
+    ("GOOGLE_CLIENT_SECRET", "oauth.google_client_secret"),
+    ("FACEBOOK_APP_ID", "oauth.facebook_app_id"),
+    ("FACEBOOK_APP_SECRET", "oauth.facebook_app_secret"),
+    ("FACEBOOK_ACCESS_TOKEN", "oauth.facebook_access_token"),
+    ("JWT_SECRET", "oauth.jwt_secret"),
+    ("RUST_LOG", "logging.level"),
+];
+
+/// Configuration keys holding comma-separated lists when set from the environment
+const LIST_KEYS: [&str; 1] = ["cors.allowed_origins"];
 
 /// Application configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Config {
     pub database_url: String,
     pub server: ServerConfig,
@@ -10,20 +76,24 @@ pub struct Config {
     pub crawler: CrawlerConfig,
     pub rate_limits: RateLimitConfig,
     pub oauth: OAuthConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CorsConfig {
     pub allowed_origins: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CrawlerConfig {
     pub fred_api_key: Option<String>,
     pub bls_api_key: Option<String>,
@@ -31,13 +101,13 @@ pub struct CrawlerConfig {
     pub queue_poll_interval_seconds: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     pub fred_rate_limit_per_minute: u32,
     pub bls_rate_limit_per_minute: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OAuthConfig {
     pub google_client_id: Option<String>,
     pub google_client_secret: Option<String>,
@@ -47,71 +117,448 @@ pub struct OAuthConfig {
     pub jwt_secret: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// Log filter in `RUST_LOG` syntax, e.g. `info` or `info,econ_graph_services=debug`
+    pub level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// Statements slower than this are logged and counted as slow; 0 turns the log off
+    pub slow_query_threshold_ms: u64,
+    /// How often connection pool utilization is exported
+    pub pool_metrics_interval_seconds: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            slow_query_threshold_ms: 500,
+            pool_metrics_interval_seconds: 15,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    pub fn slow_query_threshold(&self) -> Duration {
+        Duration::from_millis(self.slow_query_threshold_ms)
+    }
+
+    pub fn pool_metrics_interval(&self) -> Duration {
+        Duration::from_secs(self.pool_metrics_interval_seconds)
+    }
+}
+
+/// Settings a running process picks up when the configuration file changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeSettings {
+    pub log_level: String,
+    pub crawler_max_concurrent_jobs: usize,
+    pub slow_query_threshold_ms: u64,
+}
+
 impl Config {
-    /// Load configuration from environment variables
+    /// Load configuration from the configuration file, environment variables and defaults
     pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
-        // Load .env file if it exists
-        dotenvy::dotenv().ok();
+        Ok(ConfigLoader::new().load()?)
+    }
 
-        Ok(Config {
-            database_url: env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "postgresql://localhost:5432/econ_graph".to_string()),
+    /// Built-in defaults, the lowest configuration layer
+    fn defaults(vars: &HashMap<String, String>) -> Self {
+        let frontend_port = vars.get("FRONTEND_PORT").map_or("3000", String::as_str);
 
+        Config {
+            database_url: "postgresql://localhost:5432/econ_graph".to_string(),
             server: ServerConfig {
-                host: env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
-                port: env::var("BACKEND_PORT")
-                    .unwrap_or_else(|_| "9876".to_string())
-                    .parse()
-                    .unwrap_or(9876),
+                host: "127.0.0.1".to_string(),
+                port: 9876,
             },
-
             cors: CorsConfig {
-                allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
-                    .unwrap_or_else(|_| {
-                        format!(
-                            "http://localhost:{}",
-                            env::var("FRONTEND_PORT").unwrap_or_else(|_| "3000".to_string())
-                        )
-                    })
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect(),
+                allowed_origins: vec![format!("http://localhost:{}", frontend_port)],
             },
-
             crawler: CrawlerConfig {
-                fred_api_key: env::var("FRED_API_KEY").ok(),
-                bls_api_key: env::var("BLS_API_KEY").ok(),
-                max_concurrent_jobs: env::var("MAX_CONCURRENT_JOBS")
-                    .unwrap_or_else(|_| "10".to_string())
-                    .parse()
-                    .unwrap_or(10),
-                queue_poll_interval_seconds: env::var("QUEUE_POLL_INTERVAL_SECONDS")
-                    .unwrap_or_else(|_| "5".to_string())
-                    .parse()
-                    .unwrap_or(5),
+                fred_api_key: None,
+                bls_api_key: None,
+                max_concurrent_jobs: 10,
+                queue_poll_interval_seconds: 5,
             },
-
             rate_limits: RateLimitConfig {
-                fred_rate_limit_per_minute: env::var("FRED_RATE_LIMIT_PER_MINUTE")
-                    .unwrap_or_else(|_| "120".to_string())
-                    .parse()
-                    .unwrap_or(120),
-                bls_rate_limit_per_minute: env::var("BLS_RATE_LIMIT_PER_MINUTE")
-                    .unwrap_or_else(|_| "500".to_string())
-                    .parse()
-                    .unwrap_or(500),
+                fred_rate_limit_per_minute: 120,
+                bls_rate_limit_per_minute: 500,
             },
-
             oauth: OAuthConfig {
-                google_client_id: env::var("GOOGLE_CLIENT_ID").ok(),
-                google_client_secret: env::var("GOOGLE_CLIENT_SECRET").ok(),
-                facebook_app_id: env::var("FACEBOOK_APP_ID").ok(),
-                facebook_app_secret: env::var("FACEBOOK_APP_SECRET").ok(),
-                facebook_access_token: env::var("FACEBOOK_ACCESS_TOKEN").ok(),
-                jwt_secret: env::var("JWT_SECRET")
-                    .unwrap_or_else(|_| "your-jwt-secret-key-change-in-production".to_string()),
+                google_client_id: None,
+                google_client_secret: None,
+                facebook_app_id: None,
+                facebook_app_secret: None,
+                facebook_access_token: None,
+                jwt_secret: "your-jwt-secret-key-change-in-production".to_string(),
             },
-        })
+            logging: LoggingConfig::default(),
+            database: DatabaseConfig::default(),
+        }
+    }
+
+    /// Check the configuration for values no binary can run with
+    ///
+    /// All problems are reported together.
+    pub fn validate(&self) -> AppResult<()> {
+        let mut problems = Vec::new();
+
+        if !(self.database_url.starts_with("postgres://")
+            || self.database_url.starts_with("postgresql://"))
+        {
+            problems.push("database_url must be a postgres:// or postgresql:// URL".to_string());
+        }
+        if self.server.host.trim().is_empty() {
+            problems.push("server.host must not be empty".to_string());
+        }
+        if self.server.port == 0 {
+            problems.push("server.port must not be 0".to_string());
+        }
+        if self
+            .cors
+            .allowed_origins
+            .iter()
+            .any(|origin| origin.trim().is_empty())
+        {
+            problems.push("cors.allowed_origins must not contain empty origins".to_string());
+        }
+        if !(1..=MAX_CRAWLER_CONCURRENCY).contains(&self.crawler.max_concurrent_jobs) {
+            problems.push(format!(
+                "crawler.max_concurrent_jobs must be between 1 and {}",
+                MAX_CRAWLER_CONCURRENCY
+            ));
+        }
+        if self.crawler.queue_poll_interval_seconds == 0 {
+            problems.push("crawler.queue_poll_interval_seconds must be at least 1".to_string());
+        }
+        if self.rate_limits.fred_rate_limit_per_minute == 0
+            || self.rate_limits.bls_rate_limit_per_minute == 0
+        {
+            problems.push("rate_limits must allow at least 1 request per minute".to_string());
+        }
+        if self.oauth.jwt_secret.is_empty() {
+            problems.push("oauth.jwt_secret must not be empty".to_string());
+        }
+        if let Err(problem) = validate_log_filter(&self.logging.level) {
+            problems.push(format!("logging.level: {}", problem));
+        }
+        if self.database.pool_metrics_interval_seconds == 0 {
+            problems.push("database.pool_metrics_interval_seconds must be at least 1".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::ConfigError(format!(
+                "Invalid configuration: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+
+    /// The settings that can change while the process runs
+    pub fn runtime_settings(&self) -> RuntimeSettings {
+        RuntimeSettings {
+            log_level: self.logging.level.clone(),
+            crawler_max_concurrent_jobs: self.crawler.max_concurrent_jobs,
+            slow_query_threshold_ms: self.database.slow_query_threshold_ms,
+        }
+    }
+
+    /// Whether `new` differs from this configuration in more than its runtime settings
+    pub fn requires_restart(&self, new: &Config) -> bool {
+        let mut new = new.clone();
+        new.logging.level = self.logging.level.clone();
+        new.crawler.max_concurrent_jobs = self.crawler.max_concurrent_jobs;
+        new.database.slow_query_threshold_ms = self.database.slow_query_threshold_ms;
+        new != *self
+    }
+}
+
+/// Check a log filter in `RUST_LOG` syntax: comma-separated `level` or `target=level` directives
+fn validate_log_filter(filter: &str) -> Result<(), String> {
+    const LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+    if filter.trim().is_empty() {
+        return Err("must not be empty".to_string());
+    }
+    for directive in filter.split(',').map(str::trim) {
+        let level = match directive.rsplit_once('=') {
+            Some((target, level)) if !target.trim().is_empty() => level,
+            Some(_) => return Err(format!("directive '{}' has no target", directive)),
+            // A bare target enables everything for it
+            None if !LEVELS.contains(&directive.to_ascii_lowercase().as_str()) => continue,
+            None => directive,
+        };
+        if !LEVELS.contains(&level.trim().to_ascii_lowercase().as_str()) {
+            return Err(format!("unknown level '{}' in '{}'", level, directive));
+        }
+    }
+    Ok(())
+}
+
+/// Command line options shared by every binary for choosing and overriding configuration
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ConfigArgs {
+    /// Configuration file (TOML, YAML or JSON); defaults to $ECON_GRAPH_CONFIG
+    #[arg(long = "config", value_name = "FILE", global = true)]
+    pub config_file: Option<PathBuf>,
+
+    /// Override a configuration value, e.g. --set crawler.max_concurrent_jobs=4
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    pub overrides: Vec<String>,
+}
+
+impl ConfigArgs {
+    /// A loader for the file and overrides given on the command line
+    pub fn loader(&self) -> AppResult<ConfigLoader> {
+        let mut loader = ConfigLoader::new();
+        if let Some(file) = &self.config_file {
+            loader = loader.with_file(file);
+        }
+        for assignment in &self.overrides {
+            let (key, value) = parse_override(assignment)?;
+            loader = loader.with_override(key, value);
+        }
+        Ok(loader)
+    }
+}
+
+/// Split a `key=value` command line override
+pub fn parse_override(assignment: &str) -> AppResult<(String, String)> {
+    match assignment.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(AppError::ConfigError(format!(
+            "Invalid override '{}', expected key=value",
+            assignment
+        ))),
+    }
+}
+
+/// Assembles a [`Config`] from its layers
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigLoader {
+    /// A loader reading the file named by `ECON_GRAPH_CONFIG`, if set
+    pub fn new() -> Self {
+        Self {
+            file: env::var_os(CONFIG_FILE_ENV).map(PathBuf::from),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Read `path` instead of the file named by `ECON_GRAPH_CONFIG`
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Set `key` (e.g. `server.port`) above every other layer
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// The configuration file this loader reads
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Load and validate the configuration
+    pub fn load(&self) -> AppResult<Config> {
+        // Load .env file if it exists
+        dotenvy::dotenv().ok();
+        self.load_from(env::vars().collect())
+    }
+
+    /// Load and validate the configuration against a snapshot of the environment
+    fn load_from(&self, vars: HashMap<String, String>) -> AppResult<Config> {
+        let mut builder = ::config::Config::builder()
+            .add_source(::config::Config::try_from(&Config::defaults(&vars))?);
+
+        if let Some(file) = &self.file {
+            builder = builder.add_source(::config::File::from(file.as_path()).required(true));
+        }
+
+        let mut legacy = ::config::Config::builder();
+        for (name, key) in LEGACY_ENV_VARS {
+            if let Some(value) = vars.get(name) {
+                legacy = legacy.set_override(key, config_value(key, value))?;
+            }
+        }
+
+        let structured = ::config::Environment::with_prefix(ENV_PREFIX)
+            .separator("__")
+            .try_parsing(true)
+            .list_separator(",")
+            .with_list_parse_key(LIST_KEYS[0])
+            .source(Some(vars.into_iter().collect()));
+
+        builder = builder.add_source(legacy.build()?).add_source(structured);
+        for (key, value) in &self.overrides {
+            builder = builder.set_override(key.as_str(), config_value(key, value))?;
+        }
+
+        let config: Config = builder.build()?.try_deserialize()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reload the configuration file whenever it changes, publishing the runtime settings
+    ///
+    /// The file is checked every `interval`. A change that fails to load or
+    /// validate is logged and ignored. Changes to anything but the runtime
+    /// settings are logged as needing a restart. Without a configuration file
+    /// the settings never change.
+    pub fn watch(self, current: &Config, interval: Duration) -> watch::Receiver<RuntimeSettings> {
+        let (sender, receiver) = watch::channel(current.runtime_settings());
+        let Some(file) = self.file.clone() else {
+            return receiver;
+        };
+
+        let mut current = current.clone();
+        let mut modified = modified_time(&file);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.tick().await;
+            while !sender.is_closed() {
+                ticks.tick().await;
+                let now_modified = modified_time(&file);
+                if now_modified == modified {
+                    continue;
+                }
+                modified = now_modified;
+
+                let new = match self.load() {
+                    Ok(new) => new,
+                    Err(e) => {
+                        warn!(
+                            "Ignoring changed configuration file {}: {}",
+                            file.display(),
+                            e
+                        );
+                        continue;
+                    }
+                };
+                if current.requires_restart(&new) {
+                    warn!(
+                        "Configuration file {} changed settings that need a restart to take effect",
+                        file.display()
+                    );
+                }
+                let settings = new.runtime_settings();
+                sender.send_if_modified(|published| {
+                    if *published == settings {
+                        return false;
+                    }
+                    info!("Applying reloaded configuration: {:?}", settings);
+                    *published = settings;
+                    true
+                });
+                current = new;
+            }
+        });
+
+        receiver
+    }
+}
+
+/// A string value for `key`, split into a list for list-valued keys
+fn config_value(key: &str, value: &str) -> ::config::Value {
+    if LIST_KEYS.contains(&key) {
+        let items: Vec<String> = value.split(',').map(|s| s.trim().to_string()).collect();
+        items.into()
+    } else {
+        value.into()
+    }
+}
+
+fn modified_time(file: &Path) -> Option<SystemTime> {
+    std::fs::metadata(file).and_then(|m| m.modified()).ok()
+}
+
+/// A concurrency limit that can be raised or lowered while work is running
+///
+/// Lowering the limit takes effect as running tasks finish; no task is
+/// interrupted.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    limit: Arc<Mutex<usize>>,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit: Arc::new(Mutex::new(limit)),
+        }
+    }
+
+    /// The current limit
+    pub fn limit(&self) -> usize {
+        *self.limit.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait for a slot; the slot is released when the permit is dropped
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("concurrency limit semaphore is never closed")
+    }
+
+    /// Change the limit, at least 1
+    pub fn set_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        let mut current = self.limit.lock().unwrap_or_else(|e| e.into_inner());
+        if limit > *current {
+            self.semaphore.add_permits(limit - *current);
+        } else if limit < *current {
+            // Retire slots as they are released
+            let semaphore = self.semaphore.clone();
+            let retired = (*current - limit) as u32;
+            tokio::spawn(async move {
+                if let Ok(permits) = semaphore.acquire_many_owned(retired).await {
+                    permits.forget();
+                }
+            });
+        }
+        *current = limit;
+    }
+
+    /// Follow the crawler concurrency of reloaded configuration
+    pub fn follow(&self, mut settings: watch::Receiver<RuntimeSettings>) {
+        let limit = self.clone();
+        tokio::spawn(async move {
+            while settings.changed().await.is_ok() {
+                let jobs = settings.borrow_and_update().crawler_max_concurrent_jobs;
+                if jobs != limit.limit() {
+                    info!("Crawler concurrency set to {}", jobs);
+                    limit.set_limit(jobs);
+                }
+            }
+        });
     }
 }
 
@@ -150,6 +597,8 @@ impl Default for Config {
                 facebook_access_token: None,
                 jwt_secret: "test-jwt-secret".to_string(),
             },
+            logging: LoggingConfig::default(),
+            database: DatabaseConfig::default(),
         }
     }
 }
@@ -261,4 +710,121 @@ mod tests {
             env::remove_var("FRONTEND_PORT");
         }
     }
+
+    fn config_file(contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("econ-graph-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_config_layer_precedence() {
+        // REQUIREMENT: Every binary layers configuration from defaults, file, environment and command line
+        // PURPOSE: Verify each layer overrides the ones below it and lists can be set from the environment
+        // This ensures a deployment can keep shared settings in a file and override them per process
+
+        let file = config_file(
+            r#"
+            [server]
+            port = 7000
+
+            [crawler]
+            max_concurrent_jobs = 3
+
+            [logging]
+            level = "debug"
+            "#,
+        );
+        let vars = HashMap::from([
+            ("BACKEND_PORT".to_string(), "8000".to_string()),
+            (
+                "ECON_GRAPH__CRAWLER__MAX_CONCURRENT_JOBS".to_string(),
+                "4".to_string(),
+            ),
+            (
+                "CORS_ALLOWED_ORIGINS".to_string(),
+                "https://a.example, https://b.example".to_string(),
+            ),
+        ]);
+
+        let config = ConfigLoader::default()
+            .with_file(&file)
+            .with_override("logging.level", "warn")
+            .load_from(vars)
+            .unwrap();
+        std::fs::remove_file(&file).ok();
+
+        assert_eq!(config.server.port, 8000);
+        assert_eq!(config.crawler.max_concurrent_jobs, 4);
+        assert_eq!(config.logging.level, "warn");
+        assert_eq!(
+            config.cors.allowed_origins,
+            vec!["https://a.example", "https://b.example"]
+        );
+        assert_eq!(
+            config.database_url,
+            "postgresql://localhost:5432/econ_graph"
+        );
+        assert_eq!(config.crawler.queue_poll_interval_seconds, 5);
+    }
+
+    #[test]
+    fn test_config_validation_and_reload_rules() {
+        // REQUIREMENT: Invalid configuration is rejected and only select fields change without a restart
+        // PURPOSE: Verify validation reports every problem, overrides parse, and restart detection ignores runtime settings
+        // This ensures a bad edit never starts or reconfigures a process and operators learn when to restart
+
+        let invalid = ConfigLoader::default()
+            .with_override("crawler.max_concurrent_jobs", "0")
+            .with_override("logging.level", "info,econ_graph=loud")
+            .load_from(HashMap::new())
+            .unwrap_err()
+            .to_string();
+        assert!(invalid.contains("crawler.max_concurrent_jobs"));
+        assert!(invalid.contains("logging.level"));
+
+        assert_eq!(
+            parse_override("server.port = 9000").unwrap(),
+            ("server.port".to_string(), "9000".to_string())
+        );
+        assert!(parse_override("server.port").is_err());
+        assert!(parse_override("=9000").is_err());
+
+        assert!(validate_log_filter("warp").is_ok());
+        assert!(validate_log_filter("info,tower_http=DEBUG").is_ok());
+        assert!(validate_log_filter("=debug").is_err());
+
+        let current = Config::default();
+        let mut new = current.clone();
+        new.logging.level = "debug".to_string();
+        new.crawler.max_concurrent_jobs = 8;
+        new.database.slow_query_threshold_ms = 100;
+        assert!(!current.requires_restart(&new));
+        assert_eq!(new.runtime_settings().crawler_max_concurrent_jobs, 8);
+        assert_eq!(new.runtime_settings().slow_query_threshold_ms, 100);
+        new.server.port += 1;
+        assert!(current.requires_restart(&new));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_adjusts() {
+        // REQUIREMENT: Crawler concurrency changes take effect without a restart
+        // PURPOSE: Verify raising the limit frees slots at once and lowering it retires slots as they are released
+        // This ensures a reload never interrupts running crawls
+
+        let limit = ConcurrencyLimit::new(2);
+        let first = limit.acquire().await;
+        let _second = limit.acquire().await;
+
+        limit.set_limit(3);
+        assert_eq!(limit.limit(), 3);
+        let third = limit.acquire().await;
+
+        limit.set_limit(1);
+        drop(first);
+        drop(third);
+        tokio::task::yield_now().await;
+        assert_eq!(limit.semaphore.available_permits(), 0);
+        assert_eq!(limit.limit(), 1);
+    }
 }
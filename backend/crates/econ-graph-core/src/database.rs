@@ -1,35 +1,277 @@
+use diesel::connection::{Instrumentation, InstrumentationEvent};
 use diesel_async::pooled_connection::{
-    bb8::Pool, bb8::PooledConnection, AsyncDieselConnectionManager,
+    bb8::Pool, bb8::PooledConnection, AsyncDieselConnectionManager, ManagerConfig,
 };
-use diesel_async::AsyncPgConnection;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use econ_graph_metrics::database::DATABASE_METRICS;
+use futures::FutureExt;
 // use diesel::prelude::*; // Not needed for async operations
-use std::time::Duration;
-use tracing::info;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 
 use crate::error::{AppError, AppResult};
 
+/// Most connections a pool opens
+pub const MAX_POOL_SIZE: u32 = 20;
+
+/// Longest a caller waits for a pool connection
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Statements slower than this are logged and counted as slow; 0 disables the log
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(500);
+
+/// Set the slow-query threshold for every connection in the process
+///
+/// A zero threshold turns the slow-query log off. Takes effect for statements
+/// that finish after the call.
+pub fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_THRESHOLD_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// The current slow-query threshold, `None` when the slow-query log is off
+pub fn slow_query_threshold() -> Option<Duration> {
+    match SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
 /// Type alias for the database pool
 pub type DatabasePool = Pool<AsyncPgConnection>;
 
 /// Type alias for a pooled connection
 pub type PooledConn<'a> = PooledConnection<'a, AsyncPgConnection>;
 
+/// Records a `db.query` span and duration metrics for every statement run on a connection
+///
+/// Spans are children of whatever span is current when the query starts
+/// (typically a GraphQL resolver or crawler request), so exported traces show
+/// which queries a request ran. Durations are exported per operation and
+/// table, and statements slower than the [slow-query
+/// threshold](set_slow_query_threshold) are logged. Bind values are never
+/// recorded.
+#[derive(Default)]
+pub struct QueryTracing {
+    in_flight: Vec<InFlightQuery>,
+}
+
+struct InFlightQuery {
+    span: tracing::Span,
+    started: Instant,
+    statement: String,
+}
+
+impl Instrumentation for QueryTracing {
+    fn on_connection_event(&mut self, event: InstrumentationEvent<'_>) {
+        match event {
+            InstrumentationEvent::StartQuery { query, .. } => {
+                let span = tracing::info_span!(
+                    "db.query",
+                    otel.kind = "client",
+                    db.system = "postgresql",
+                    db.statement = tracing::field::Empty,
+                    otel.status_code = tracing::field::Empty,
+                    error.message = tracing::field::Empty,
+                );
+                let sql = query.to_string();
+                let statement = sql
+                    .split(" -- binds:")
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                if !span.is_disabled() {
+                    span.record("db.statement", statement.as_str());
+                }
+                self.in_flight.push(InFlightQuery {
+                    span,
+                    started: Instant::now(),
+                    statement,
+                });
+            }
+            InstrumentationEvent::FinishQuery { error, .. } => {
+                let Some(query) = self.in_flight.pop() else {
+                    return;
+                };
+                let elapsed = query.started.elapsed();
+                crate::query_explain::record_statement(&query.statement, elapsed, error.is_some());
+                let (operation, table) = classify_statement(&query.statement);
+                DATABASE_METRICS.record_query(
+                    operation,
+                    &table,
+                    elapsed.as_secs_f64(),
+                    error.is_some(),
+                );
+                if let Some(error) = error {
+                    query.span.record("otel.status_code", "ERROR");
+                    query
+                        .span
+                        .record("error.message", tracing::field::display(error));
+                }
+                if slow_query_threshold().is_some_and(|threshold| elapsed >= threshold) {
+                    DATABASE_METRICS.record_slow_query(operation, &table);
+                    warn!(
+                        duration_ms = elapsed.as_millis() as u64,
+                        operation,
+                        table = table.as_str(),
+                        "Slow query: {}",
+                        query.statement
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Operation and main table of a SQL statement, used as metric labels
+///
+/// The table is the first one named after `FROM`, `INTO` or `UPDATE`, without
+/// quotes or schema. Statements that name none get "none", so labels stay
+/// bounded by the schema.
+pub fn classify_statement(sql: &str) -> (&'static str, String) {
+    let mut words = sql.split_whitespace();
+    let first = words.next().unwrap_or_default().to_ascii_uppercase();
+    let (operation, table_keyword) = match first.as_str() {
+        "SELECT" => ("select", "FROM"),
+        "INSERT" => ("insert", "INTO"),
+        "UPDATE" => ("update", "UPDATE"),
+        "DELETE" => ("delete", "FROM"),
+        "WITH" => ("with", "FROM"),
+        "BEGIN" | "COMMIT" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" => {
+            return ("transaction", "none".to_string())
+        }
+        _ => return ("other", "none".to_string()),
+    };
+
+    let mut words = sql.split_whitespace();
+    if operation == "update" {
+        words.next();
+    } else {
+        words
+            .by_ref()
+            .find(|word| word.eq_ignore_ascii_case(table_keyword));
+    }
+    let table = words
+        .next()
+        .map(|name| {
+            let name = name.rsplit('.').next().unwrap_or(name);
+            name.trim_matches(|c: char| c == '"' || c == '(' || c == ')' || c == ',' || c == ';')
+                .to_ascii_lowercase()
+        })
+        .filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .unwrap_or_else(|| "none".to_string());
+
+    (operation, table)
+}
+
+/// Databases the pools of this process connect to
+enum PoolDatabase {
+    Unknown,
+    One(String),
+    /// Pools to different databases exist, so a URL cannot be matched to a pool
+    Several,
+}
+
+static POOL_DATABASE: Mutex<PoolDatabase> = Mutex::new(PoolDatabase::Unknown);
+
+fn remember_pool_database(database_url: &str) {
+    let mut database = POOL_DATABASE.lock().unwrap_or_else(|e| e.into_inner());
+    *database = match &*database {
+        PoolDatabase::Unknown => PoolDatabase::One(database_url.to_string()),
+        PoolDatabase::One(url) if url == database_url => return,
+        _ => PoolDatabase::Several,
+    };
+}
+
+/// URL of the database every pool of this process connects to
+///
+/// Used to open connections outside the pool, such as those for binary COPY.
+/// `None` before the first pool is created and once pools to two different
+/// databases were created.
+pub fn pool_database_url() -> Option<String> {
+    match &*POOL_DATABASE.lock().unwrap_or_else(|e| e.into_inner()) {
+        PoolDatabase::One(url) => Some(url.clone()),
+        PoolDatabase::Unknown | PoolDatabase::Several => None,
+    }
+}
+
 /// Create a database connection pool
 pub async fn create_pool(database_url: &str) -> AppResult<DatabasePool> {
-    let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url);
+    let mut manager_config = ManagerConfig::<AsyncPgConnection>::default();
+    manager_config.custom_setup = Box::new(|url| {
+        AsyncPgConnection::establish(url)
+            .map(|connection| {
+                let mut connection = connection?;
+                connection.set_instrumentation(QueryTracing::default());
+                Ok(connection)
+            })
+            .boxed()
+    });
+    let config = AsyncDieselConnectionManager::<AsyncPgConnection>::new_with_config(
+        database_url,
+        manager_config,
+    );
 
     let pool = Pool::builder()
-        .max_size(20)
-        .connection_timeout(Duration::from_secs(30))
+        .max_size(MAX_POOL_SIZE)
+        .connection_timeout(CONNECTION_TIMEOUT)
         .idle_timeout(Some(Duration::from_secs(300))) // 5 minutes idle timeout
         .build(config)
         .await
         .map_err(|e| AppError::InternalError(format!("Failed to create database pool: {}", e)))?;
 
+    remember_pool_database(database_url);
     info!("Database connection pool created successfully");
     Ok(pool)
 }
 
+/// Export the pool's utilization and connection wait times every `interval`
+///
+/// The pool only reports totals, so each request that waited during an
+/// interval is recorded with the average wait of that interval.
+pub fn spawn_pool_monitor(pool: DatabasePool, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        let mut previous = pool.state().statistics;
+        loop {
+            ticks.tick().await;
+            let state = pool.state();
+            DATABASE_METRICS.set_pool_state(
+                MAX_POOL_SIZE,
+                state.connections,
+                state.idle_connections,
+            );
+
+            let current = state.statistics;
+            let direct = current.get_direct.saturating_sub(previous.get_direct);
+            let waited = current.get_waited.saturating_sub(previous.get_waited);
+            let timed_out = current.get_timed_out.saturating_sub(previous.get_timed_out);
+            DATABASE_METRICS.record_acquisitions("direct", direct);
+            DATABASE_METRICS.record_acquisitions("waited", waited);
+            DATABASE_METRICS.record_acquisitions("timed_out", timed_out);
+            if waited > 0 {
+                let wait_time = current.get_wait_time.saturating_sub(previous.get_wait_time);
+                DATABASE_METRICS
+                    .observe_acquire_wait(wait_time.as_secs_f64() / waited as f64, waited);
+            }
+            if timed_out > 0 {
+                DATABASE_METRICS.observe_acquire_wait(CONNECTION_TIMEOUT.as_secs_f64(), timed_out);
+                warn!(
+                    "{} database connection requests timed out in the last {:?}",
+                    timed_out, interval
+                );
+            }
+            previous = current;
+        }
+    })
+}
+
 /// Test database connectivity
 pub async fn test_connection(pool: &DatabasePool) -> AppResult<()> {
     let mut conn = pool.get().await.map_err(|e| {
@@ -133,6 +375,38 @@ pub async fn check_database_health(pool: &DatabasePool) -> AppResult<()> {
     test_connection(pool).await
 }
 
+#[derive(diesel::QueryableByName)]
+struct RowEstimate {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    estimate: i64,
+}
+
+/// Planner estimate of the number of rows a query returns
+///
+/// The query is built with PostgreSQL's `format()`: `%1$L`, `%2$L`, ... in
+/// `template` are replaced by `args` as quoted literals, so user input never
+/// ends up in the SQL text unescaped.
+pub async fn estimate_row_count(
+    pool: &DatabasePool,
+    template: &str,
+    args: Vec<String>,
+) -> AppResult<i64> {
+    use diesel::sql_types::{Array, Text};
+    use diesel_async::RunQueryDsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let row = diesel::sql_query("SELECT count_estimate(format($1, VARIADIC $2)) AS estimate")
+        .bind::<Text, _>(template)
+        .bind::<Array<Text>, _>(args)
+        .get_result::<RowEstimate>(&mut *conn)
+        .await?;
+
+    Ok(row.estimate.max(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +434,46 @@ mod tests {
             .expect("Should be able to get connection from pool");
     }
 
+    #[test]
+    fn test_classify_statement() {
+        // REQUIREMENT: Statement durations are exported per operation and table
+        // PURPOSE: Verify Diesel's generated SQL maps to bounded operation and table labels
+        // This ensures slow-query metrics point at the table under contention without exploding label cardinality
+
+        assert_eq!(
+            classify_statement(
+                r#"SELECT "data_points"."id", "data_points"."value" FROM "data_points" WHERE ("data_points"."series_id" = $1)"#
+            ),
+            ("select", "data_points".to_string())
+        );
+        assert_eq!(
+            classify_statement(
+                r#"INSERT INTO "public"."crawl_attempts" ("series_id") VALUES ($1) RETURNING "crawl_attempts"."id""#
+            ),
+            ("insert", "crawl_attempts".to_string())
+        );
+        assert_eq!(
+            classify_statement(r#"UPDATE "economic_series" SET "updated_at" = $1"#),
+            ("update", "economic_series".to_string())
+        );
+        assert_eq!(
+            classify_statement("delete from crawl_queue where id = $1"),
+            ("delete", "crawl_queue".to_string())
+        );
+        assert_eq!(
+            classify_statement("SELECT count_estimate(format($1, VARIADIC $2)) AS estimate"),
+            ("select", "none".to_string())
+        );
+        assert_eq!(
+            classify_statement("BEGIN"),
+            ("transaction", "none".to_string())
+        );
+        assert_eq!(
+            classify_statement("VACUUM ANALYZE"),
+            ("other", "none".to_string())
+        );
+    }
+
     #[tokio::test]
     #[serial_test::serial]
     async fn test_basic_query() {
@@ -118,15 +118,20 @@ pub enum StatementType {
     Equity,
 }
 
-impl ToSql<Text, Pg> for StatementType {
-    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
-        let value = match self {
+impl StatementType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
             StatementType::IncomeStatement => "income_statement",
             StatementType::BalanceSheet => "balance_sheet",
             StatementType::CashFlow => "cash_flow",
             StatementType::Equity => "equity",
-        };
-        <str as ToSql<Text, Pg>>::to_sql(value, out)
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for StatementType {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
     }
 }
 
@@ -164,9 +169,9 @@ pub enum StatementSection {
     Financing,
 }
 
-impl ToSql<Text, Pg> for StatementSection {
-    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
-        let value = match self {
+impl StatementSection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
             StatementSection::Revenue => "revenue",
             StatementSection::Expenses => "expenses",
             StatementSection::Assets => "assets",
@@ -175,8 +180,13 @@ impl ToSql<Text, Pg> for StatementSection {
             StatementSection::Operating => "operating",
             StatementSection::Investing => "investing",
             StatementSection::Financing => "financing",
-        };
-        <str as ToSql<Text, Pg>>::to_sql(value, out)
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for StatementSection {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
     }
 }
 
@@ -551,3 +561,118 @@ impl diesel::Queryable<Text, Pg> for TaxonomySourceType {
         Ok(row)
     }
 }
+
+/// Who may read a data source's observations, from least to most restrictive
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+    diesel::AsExpression,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "lowercase")]
+pub enum DataAccessPolicy {
+    /// Everyone, including anonymous users
+    #[default]
+    Public,
+    /// Signed-in users
+    Registered,
+    /// Users with a premium subscription
+    Premium,
+}
+
+impl ToSql<Text, Pg> for DataAccessPolicy {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for DataAccessPolicy {
+    fn from_sql(bytes: <Pg as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        match value.as_str() {
+            "public" => Ok(DataAccessPolicy::Public),
+            "registered" => Ok(DataAccessPolicy::Registered),
+            "premium" => Ok(DataAccessPolicy::Premium),
+            _ => Err(format!("Unknown access_policy value: {}", value).into()),
+        }
+    }
+}
+
+impl diesel::Queryable<Text, Pg> for DataAccessPolicy {
+    type Row = Self;
+    fn build(row: Self::Row) -> deserialize::Result<Self> {
+        Ok(row)
+    }
+}
+
+impl DataAccessPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataAccessPolicy::Public => "public",
+            DataAccessPolicy::Registered => "registered",
+            DataAccessPolicy::Premium => "premium",
+        }
+    }
+
+    /// Whether a user with `tier` may read the data; `None` is an anonymous user
+    pub fn permits(&self, tier: Option<SubscriptionTier>) -> bool {
+        match self {
+            DataAccessPolicy::Public => true,
+            DataAccessPolicy::Registered => tier.is_some(),
+            DataAccessPolicy::Premium => tier == Some(SubscriptionTier::Premium),
+        }
+    }
+}
+
+/// Subscription a user pays for
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, diesel::AsExpression,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionTier {
+    #[default]
+    Free,
+    Premium,
+}
+
+impl ToSql<Text, Pg> for SubscriptionTier {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for SubscriptionTier {
+    fn from_sql(bytes: <Pg as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        match value.as_str() {
+            "free" => Ok(SubscriptionTier::Free),
+            "premium" => Ok(SubscriptionTier::Premium),
+            _ => Err(format!("Unknown subscription_tier value: {}", value).into()),
+        }
+    }
+}
+
+impl diesel::Queryable<Text, Pg> for SubscriptionTier {
+    type Row = Self;
+    fn build(row: Self::Row) -> deserialize::Result<Self> {
+        Ok(row)
+    }
+}
+
+impl SubscriptionTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionTier::Free => "free",
+            SubscriptionTier::Premium => "premium",
+        }
+    }
+}
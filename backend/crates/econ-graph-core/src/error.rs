@@ -79,6 +79,9 @@ pub enum AppError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("External API error: {0}")]
     ExternalApiError(String),
 
@@ -225,6 +228,10 @@ pub fn handle_rejection(err: warp::Rejection) -> Result<impl Reply, std::convert
                     "Rate limit exceeded".to_string(),
                 )
             }
+            AppError::QuotaExceeded(msg) => {
+                tracing::warn!("Quota exceeded: {}", msg);
+                (StatusCode::TOO_MANY_REQUESTS, msg.clone())
+            }
             AppError::ExternalApiError(msg) => {
                 tracing::error!("External API error: {}", msg);
                 (StatusCode::BAD_GATEWAY, "External API error".to_string())
@@ -448,6 +455,10 @@ impl AppError {
             | AppError::PermissionDenied(_) => {
                 tracing::warn!("{} - {}: {}", context, self.error_type(), self);
             }
+            // Daily crawl quotas run out in normal operation; the work is deferred
+            AppError::QuotaExceeded(_) => {
+                tracing::warn!("{} - {}: {}", context, self.error_type(), self);
+            }
             // Not found errors (info level)
             AppError::NotFound(_)
             | AppError::SeriesNotFound(_)
@@ -482,6 +493,7 @@ impl AppError {
             AppError::InvalidDateFormat(_) => "InvalidDateFormat",
             AppError::InvalidTransformation(_) => "InvalidTransformation",
             AppError::RateLimitExceeded => "RateLimitExceeded",
+            AppError::QuotaExceeded(_) => "QuotaExceeded",
             AppError::ExternalApiError(_) => "ExternalApiError",
             AppError::ParserError(_) => "ParserError",
             AppError::MigrationError(_) => "MigrationError",
@@ -8,17 +8,23 @@
 //! This crate provides the foundation layer that other crates depend on.
 
 pub mod auth_models;
+pub mod bulk_copy;
 pub mod config;
 pub mod database;
 pub mod enums;
 pub mod error;
 pub mod models;
+pub mod query_explain;
+pub mod rate_limiter;
 pub mod schema;
+pub mod secrets;
+pub mod shutdown;
+pub mod validation;
 
 pub mod test_utils;
 
 // Re-export commonly used types
-pub use config::Config;
+pub use config::{ConcurrencyLimit, Config, ConfigArgs, ConfigLoader, RuntimeSettings};
 pub use database::{create_pool, run_migrations, DatabasePool};
 pub use error::{AppError, AppResult};
 
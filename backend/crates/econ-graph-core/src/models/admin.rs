@@ -87,6 +87,27 @@ pub struct SecurityEventUpdate {
     pub resolved_at: Option<DateTime<Utc>>,
 }
 
+/// Filters for listing audit log entries; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub user_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub resource_type: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// Filters for listing security events; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityEventFilter {
+    pub event_type: Option<String>,
+    pub severity: Option<String>,
+    pub resolved: Option<bool>,
+    pub ip_address: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
 impl AuditLog {
     /// Create a new audit log entry
     pub async fn create(
@@ -124,37 +145,35 @@ impl AuditLog {
         Ok(log)
     }
 
-    /// Get audit logs with filtering and pagination
+    /// Audit logs matching a filter, newest first
+    ///
+    /// Pages are keyed on `(created_at, id)`: pass the last entry of the
+    /// previous page as `before` to get the entries logged before it.
     pub async fn get_logs(
         pool: &DatabasePool,
-        user_id: Option<Uuid>,
-        action: Option<String>,
-        resource_type: Option<String>,
+        filter: &AuditLogFilter,
+        before: Option<(DateTime<Utc>, Uuid)>,
         limit: i64,
-        offset: i64,
     ) -> AppResult<Vec<AuditLog>> {
         let mut conn = pool.get().await.map_err(|e| {
             AppError::DatabaseError(format!("Failed to get database connection: {}", e))
         })?;
 
-        let mut query = audit_logs::table.into_boxed();
-
-        if let Some(user_id) = user_id {
-            query = query.filter(audit_logs::user_id.eq(user_id));
-        }
+        let mut query = Self::filtered(filter);
 
-        if let Some(action) = action {
-            query = query.filter(audit_logs::action.eq(action));
-        }
-
-        if let Some(resource_type) = resource_type {
-            query = query.filter(audit_logs::resource_type.eq(resource_type));
+        if let Some((created_at, id)) = before {
+            query = query.filter(
+                audit_logs::created_at
+                    .lt(created_at)
+                    .or(audit_logs::created_at
+                        .eq(created_at)
+                        .and(audit_logs::id.lt(id))),
+            );
         }
 
         let logs = query
-            .order(audit_logs::created_at.desc())
+            .order((audit_logs::created_at.desc(), audit_logs::id.desc()))
             .limit(limit)
-            .offset(offset)
             .get_results::<AuditLog>(&mut conn)
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -163,37 +182,44 @@ impl AuditLog {
     }
 
     /// Get total count of audit logs matching filters
-    pub async fn get_count(
-        pool: &DatabasePool,
-        user_id: Option<Uuid>,
-        action: Option<String>,
-        resource_type: Option<String>,
-    ) -> AppResult<i64> {
+    pub async fn get_count(pool: &DatabasePool, filter: &AuditLogFilter) -> AppResult<i64> {
         let mut conn = pool.get().await.map_err(|e| {
             AppError::DatabaseError(format!("Failed to get database connection: {}", e))
         })?;
 
+        let count = Self::filtered(filter)
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(count)
+    }
+
+    fn filtered(filter: &AuditLogFilter) -> audit_logs::BoxedQuery<'static, diesel::pg::Pg> {
         let mut query = audit_logs::table.into_boxed();
 
-        if let Some(user_id) = user_id {
+        if let Some(user_id) = filter.user_id {
             query = query.filter(audit_logs::user_id.eq(user_id));
         }
 
-        if let Some(action) = action {
-            query = query.filter(audit_logs::action.eq(action));
+        if let Some(action) = &filter.action {
+            query = query.filter(audit_logs::action.eq(action.clone()));
         }
 
-        if let Some(resource_type) = resource_type {
-            query = query.filter(audit_logs::resource_type.eq(resource_type));
+        if let Some(resource_type) = &filter.resource_type {
+            query = query.filter(audit_logs::resource_type.eq(resource_type.clone()));
         }
 
-        let count = query
-            .count()
-            .get_result::<i64>(&mut conn)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if let Some(created_after) = filter.created_after {
+            query = query.filter(audit_logs::created_at.ge(created_after));
+        }
 
-        Ok(count)
+        if let Some(created_before) = filter.created_before {
+            query = query.filter(audit_logs::created_at.lt(created_before));
+        }
+
+        query
     }
 }
 
@@ -234,12 +260,10 @@ impl SecurityEvent {
         Ok(event)
     }
 
-    /// Get security events with filtering
+    /// Get security events with filtering, newest first
     pub async fn get_events(
         pool: &DatabasePool,
-        event_type: Option<String>,
-        severity: Option<String>,
-        resolved: Option<bool>,
+        filter: &SecurityEventFilter,
         limit: i64,
     ) -> AppResult<Vec<SecurityEvent>> {
         let mut conn = pool.get().await.map_err(|e| {
@@ -248,18 +272,30 @@ impl SecurityEvent {
 
         let mut query = security_events::table.into_boxed();
 
-        if let Some(event_type) = event_type {
-            query = query.filter(security_events::event_type.eq(event_type));
+        if let Some(event_type) = &filter.event_type {
+            query = query.filter(security_events::event_type.eq(event_type.clone()));
         }
 
-        if let Some(severity) = severity {
-            query = query.filter(security_events::severity.eq(severity));
+        if let Some(severity) = &filter.severity {
+            query = query.filter(security_events::severity.eq(severity.clone()));
         }
 
-        if let Some(resolved) = resolved {
+        if let Some(resolved) = filter.resolved {
             query = query.filter(security_events::resolved.eq(resolved));
         }
 
+        if let Some(ip_address) = &filter.ip_address {
+            query = query.filter(security_events::ip_address.eq(ip_address.clone()));
+        }
+
+        if let Some(created_after) = filter.created_after {
+            query = query.filter(security_events::created_at.ge(created_after));
+        }
+
+        if let Some(created_before) = filter.created_before {
+            query = query.filter(security_events::created_at.lt(created_before));
+        }
+
         let events = query
             .order(security_events::created_at.desc())
             .limit(limit)
@@ -270,6 +306,21 @@ impl SecurityEvent {
         Ok(events)
     }
 
+    /// Get a security event by ID
+    pub async fn find_by_id(pool: &DatabasePool, event_id: Uuid) -> AppResult<SecurityEvent> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        security_events::table
+            .find(event_id)
+            .get_result::<SecurityEvent>(&mut conn)
+            .await
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound(format!("Security event {} not found", event_id)))
+    }
+
     /// Resolve a security event
     pub async fn resolve(
         pool: &DatabasePool,
@@ -286,13 +337,13 @@ impl SecurityEvent {
             resolved_at: Some(Utc::now()),
         };
 
-        let event = diesel::update(security_events::table.find(event_id))
+        diesel::update(security_events::table.find(event_id))
             .set(&update)
             .get_result::<SecurityEvent>(&mut conn)
             .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-        Ok(event)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound(format!("Security event {} not found", event_id)))
     }
 
     /// Get total count of unresolved security events
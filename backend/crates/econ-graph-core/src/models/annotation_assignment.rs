@@ -4,8 +4,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::enums::{AssignmentStatus, AssignmentType};
+use crate::error::{AppError, AppResult};
 use crate::schema::annotation_assignments;
 
+/// Most assignments returned by a pending review query
+pub const MAX_PENDING_ASSIGNMENTS: i64 = 100;
+
 /// Assignment for team workflow management
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Insertable)]
 #[diesel(table_name = annotation_assignments)]
@@ -23,6 +27,79 @@ pub struct AnnotationAssignment {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Step taken on an assignment after it was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssignmentAction {
+    /// The assignee starts working on it
+    Accept,
+    /// The assignee finished it
+    Complete,
+    /// The assignee declines it, or the assigner withdraws it
+    Reject,
+}
+
+impl AnnotationAssignment {
+    /// Whether the assignment still waits on its assignee
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            AssignmentStatus::Pending | AssignmentStatus::InProgress | AssignmentStatus::Overdue
+        )
+    }
+
+    /// Whether the assignment is open and past its due date
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.is_open() && self.due_date.is_some_and(|due_date| due_date < now)
+    }
+
+    /// Status the assignment moves to when `user_id` takes `action`
+    ///
+    /// Only the assignee accepts and completes an assignment; the assigner may
+    /// also reject it to withdraw it. An assignment is accepted before it is
+    /// completed, and a completed or cancelled one does not change again.
+    /// An assignment stored as overdue may be accepted or completed.
+    pub fn transition(
+        &self,
+        action: AssignmentAction,
+        user_id: Uuid,
+    ) -> AppResult<AssignmentStatus> {
+        let is_assignee = user_id == self.assignee_id;
+        match action {
+            AssignmentAction::Accept | AssignmentAction::Complete if !is_assignee => {
+                return Err(AppError::Forbidden(
+                    "Only the assignee can accept or complete an assignment".to_string(),
+                ));
+            }
+            AssignmentAction::Reject if !is_assignee && user_id != self.assigner_id => {
+                return Err(AppError::Forbidden(
+                    "Only the assignee or assigner can reject an assignment".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        match (action, self.status) {
+            (AssignmentAction::Accept, AssignmentStatus::Pending | AssignmentStatus::Overdue) => {
+                Ok(AssignmentStatus::InProgress)
+            }
+            (AssignmentAction::Accept, _) => Err(AppError::Conflict(
+                "Only pending assignments can be accepted".to_string(),
+            )),
+            (
+                AssignmentAction::Complete,
+                AssignmentStatus::InProgress | AssignmentStatus::Overdue,
+            ) => Ok(AssignmentStatus::Completed),
+            (AssignmentAction::Complete, _) => Err(AppError::Conflict(
+                "Only accepted assignments can be completed".to_string(),
+            )),
+            (AssignmentAction::Reject, _) if self.is_open() => Ok(AssignmentStatus::Cancelled),
+            (AssignmentAction::Reject, _) => Err(AppError::Conflict(
+                "Completed or cancelled assignments cannot be rejected".to_string(),
+            )),
+        }
+    }
+}
+
 /// New annotation assignment for insertion
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable)]
 #[diesel(table_name = annotation_assignments)]
@@ -88,6 +165,23 @@ impl NewAnnotationAssignment {
         self.notes = Some(notes);
         self
     }
+
+    /// Check the assignment can be made at `now`
+    ///
+    /// A due date must be in the future; users cannot assign work to themselves.
+    pub fn validate(&self, now: DateTime<Utc>) -> AppResult<()> {
+        if self.assignee_id == self.assigner_id {
+            return Err(AppError::ValidationError(
+                "Cannot assign an annotation review to yourself".to_string(),
+            ));
+        }
+        if self.due_date.is_some_and(|due_date| due_date <= now) {
+            return Err(AppError::ValidationError(
+                "Due date must be in the future".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Filter for querying annotation assignments
@@ -121,3 +215,110 @@ impl Default for AnnotationAssignmentFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn assignment(status: AssignmentStatus) -> AnnotationAssignment {
+        AnnotationAssignment {
+            id: Uuid::from_u128(1),
+            statement_id: Uuid::from_u128(2),
+            line_item_id: None,
+            assignee_id: Uuid::from_u128(3),
+            assigner_id: Uuid::from_u128(4),
+            assignment_type: AssignmentType::Review,
+            due_date: None,
+            status,
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_assignment_transitions() {
+        // REQUIREMENT: Annotation review workflow with accept, complete and reject steps
+        // PURPOSE: Verify each step is limited to the right user and the right starting status
+        // This ensures reviews cannot be completed by someone else, skipped ahead or reopened
+
+        let assignee = Uuid::from_u128(3);
+        let assigner = Uuid::from_u128(4);
+        let outsider = Uuid::from_u128(5);
+        let pending = assignment(AssignmentStatus::Pending);
+        let in_progress = assignment(AssignmentStatus::InProgress);
+
+        assert_eq!(
+            pending
+                .transition(AssignmentAction::Accept, assignee)
+                .unwrap(),
+            AssignmentStatus::InProgress
+        );
+        assert_eq!(
+            in_progress
+                .transition(AssignmentAction::Complete, assignee)
+                .unwrap(),
+            AssignmentStatus::Completed
+        );
+        assert_eq!(
+            pending
+                .transition(AssignmentAction::Reject, assigner)
+                .unwrap(),
+            AssignmentStatus::Cancelled
+        );
+
+        assert!(matches!(
+            pending.transition(AssignmentAction::Accept, assigner),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            pending.transition(AssignmentAction::Reject, outsider),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            pending.transition(AssignmentAction::Complete, assignee),
+            Err(AppError::Conflict(_))
+        ));
+        assert!(matches!(
+            assignment(AssignmentStatus::Completed).transition(AssignmentAction::Reject, assignee),
+            Err(AppError::Conflict(_))
+        ));
+    }
+
+    #[test]
+    fn test_assignment_due_dates() {
+        // REQUIREMENT: Assignments carry a due date and surface as overdue once it passes
+        // PURPOSE: Verify past due dates are rejected on creation and open assignments past due are overdue
+        // This ensures reviewers see which reviews are late and finished ones never are
+
+        let now = Utc::now();
+        let new_assignment = NewAnnotationAssignment::new(
+            Uuid::from_u128(2),
+            Uuid::from_u128(3),
+            Uuid::from_u128(4),
+            AssignmentType::Review,
+        );
+        assert!(new_assignment.validate(now).is_ok());
+        assert!(new_assignment
+            .clone()
+            .with_due_date(now - Duration::days(1))
+            .validate(now)
+            .is_err());
+        assert!(NewAnnotationAssignment::new(
+            Uuid::from_u128(2),
+            Uuid::from_u128(3),
+            Uuid::from_u128(3),
+            AssignmentType::Review,
+        )
+        .validate(now)
+        .is_err());
+
+        let mut late = assignment(AssignmentStatus::InProgress);
+        late.due_date = Some(now - Duration::hours(1));
+        assert!(late.is_overdue(now));
+        late.status = AssignmentStatus::Completed;
+        assert!(!late.is_overdue(now));
+        assert!(!assignment(AssignmentStatus::Pending).is_overdue(now));
+    }
+}
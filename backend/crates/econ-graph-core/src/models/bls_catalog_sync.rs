@@ -0,0 +1,69 @@
+//! Sync state of BLS survey catalogs
+//!
+//! BLS lists every series of a survey in a LABSTAT `.series` file. The HTTP
+//! validators (`ETag`, `Last-Modified`) of the copy last synced are kept per
+//! survey, so the next sync can ask for the file only if it changed.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+
+use crate::database::DatabasePool;
+use crate::error::{AppError, AppResult};
+use crate::schema::bls_catalog_syncs;
+
+/// Last sync of one survey's series list
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable, Serialize)]
+#[diesel(table_name = bls_catalog_syncs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BlsCatalogSync {
+    /// Survey abbreviation, e.g. "LN" for the Current Population Survey
+    pub survey: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Series in the file when it was last synced
+    pub series_listed: i32,
+    pub synced_at: DateTime<Utc>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl BlsCatalogSync {
+    /// Sync state of a survey, if it was ever synced
+    pub async fn find(pool: &DatabasePool, survey: &str) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let sync = bls_catalog_syncs::table
+            .find(survey)
+            .select(Self::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(sync)
+    }
+
+    /// Record a completed sync of a survey
+    pub async fn record(pool: &DatabasePool, sync: &Self) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::insert_into(bls_catalog_syncs::table)
+            .values(sync)
+            .on_conflict(bls_catalog_syncs::survey)
+            .do_update()
+            .set((
+                bls_catalog_syncs::etag.eq(excluded(bls_catalog_syncs::etag)),
+                bls_catalog_syncs::last_modified.eq(excluded(bls_catalog_syncs::last_modified)),
+                bls_catalog_syncs::series_listed.eq(excluded(bls_catalog_syncs::series_listed)),
+                bls_catalog_syncs::synced_at.eq(excluded(bls_catalog_syncs::synced_at)),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+//! Catalog statistics for admin dashboards
+//!
+//! Series and data point counts with coverage dates, per series and per data
+//! source. Database triggers keep them up to date as data is ingested (see the
+//! `create_catalog_statistics` migration), so reading them never scans
+//! `data_points`. [`CatalogStatistics::refresh`] recomputes everything and is
+//! run periodically to correct drift, e.g. after a `TRUNCATE`.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{data_source_statistics, series_statistics};
+
+/// How often the server recomputes every statistic by default
+pub const DEFAULT_CATALOG_STATISTICS_REFRESH_SECONDS: u64 = 6 * 60 * 60;
+
+/// Data points and coverage of one series
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize)]
+#[diesel(table_name = series_statistics)]
+#[diesel(primary_key(series_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SeriesStatistics {
+    pub series_id: Uuid,
+    pub source_id: Uuid,
+    pub data_point_count: i64,
+    pub earliest_date: Option<NaiveDate>,
+    pub latest_date: Option<NaiveDate>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Series, data points and coverage of one data source
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize)]
+#[diesel(table_name = data_source_statistics)]
+#[diesel(primary_key(source_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataSourceStatistics {
+    pub source_id: Uuid,
+    pub series_count: i64,
+    pub active_series_count: i64,
+    pub data_point_count: i64,
+    pub earliest_date: Option<NaiveDate>,
+    pub latest_date: Option<NaiveDate>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Totals of the whole catalog, with the statistics of each data source
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogStatistics {
+    pub series_count: i64,
+    pub active_series_count: i64,
+    pub data_point_count: i64,
+    pub earliest_date: Option<NaiveDate>,
+    pub latest_date: Option<NaiveDate>,
+    pub sources: Vec<DataSourceStatistics>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SeriesStatistics {
+    /// Statistics of the given series; series without data points are left out
+    pub async fn for_series(
+        pool: &crate::database::DatabasePool,
+        series_ids: &[Uuid],
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let statistics = series_statistics::table
+            .filter(series_statistics::series_id.eq_any(series_ids))
+            .select(SeriesStatistics::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(statistics)
+    }
+}
+
+impl DataSourceStatistics {
+    /// Statistics of the given data sources
+    pub async fn for_sources(
+        pool: &crate::database::DatabasePool,
+        source_ids: &[Uuid],
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let statistics = data_source_statistics::table
+            .filter(data_source_statistics::source_id.eq_any(source_ids))
+            .select(DataSourceStatistics::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(statistics)
+    }
+
+    /// Statistics of every data source
+    pub async fn all(pool: &crate::database::DatabasePool) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let statistics = data_source_statistics::table
+            .order(data_source_statistics::data_point_count.desc())
+            .select(DataSourceStatistics::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(statistics)
+    }
+}
+
+impl CatalogStatistics {
+    /// Totals of the catalog from the statistics of its data sources
+    pub fn from_sources(sources: Vec<DataSourceStatistics>) -> Self {
+        Self {
+            series_count: sources.iter().map(|s| s.series_count).sum(),
+            active_series_count: sources.iter().map(|s| s.active_series_count).sum(),
+            data_point_count: sources.iter().map(|s| s.data_point_count).sum(),
+            earliest_date: sources.iter().filter_map(|s| s.earliest_date).min(),
+            latest_date: sources.iter().filter_map(|s| s.latest_date).max(),
+            sources,
+        }
+    }
+
+    /// Current statistics of the catalog
+    pub async fn load(pool: &crate::database::DatabasePool) -> AppResult<Self> {
+        Ok(Self::from_sources(DataSourceStatistics::all(pool).await?))
+    }
+
+    /// Recompute every statistic from the series and data points
+    pub async fn refresh(pool: &crate::database::DatabasePool) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::sql_query("SELECT refresh_catalog_statistics()")
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        DataPoint, DataSource, EconomicSeries, NewDataPoint, NewDataSource, NewEconomicSeries,
+    };
+    use crate::test_utils::TestContainer;
+    use chrono::Datelike;
+
+    fn source_statistics(
+        series_count: i64,
+        data_point_count: i64,
+        earliest_date: Option<NaiveDate>,
+        latest_date: Option<NaiveDate>,
+    ) -> DataSourceStatistics {
+        DataSourceStatistics {
+            source_id: Uuid::new_v4(),
+            series_count,
+            active_series_count: series_count,
+            data_point_count,
+            earliest_date,
+            latest_date,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_catalog_totals_from_sources() {
+        // REQUIREMENT: Admin dashboards show catalog totals without scanning all series
+        // PURPOSE: Verify catalog totals add up counts and span the coverage of every source
+        // This ensures sources without data points do not hide the coverage of the others
+
+        let date = |y| NaiveDate::from_ymd_opt(y, 1, 1);
+        let catalog = CatalogStatistics::from_sources(vec![
+            source_statistics(3, 120, date(1990), date(2020)),
+            source_statistics(2, 40, date(2000), date(2024)),
+            source_statistics(1, 0, None, None),
+        ]);
+
+        assert_eq!(catalog.series_count, 6);
+        assert_eq!(catalog.active_series_count, 6);
+        assert_eq!(catalog.data_point_count, 160);
+        assert_eq!(catalog.earliest_date, date(1990));
+        assert_eq!(catalog.latest_date, date(2024));
+        assert_eq!(catalog.sources.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_statistics_follow_ingestion() {
+        // REQUIREMENT: Catalog statistics are maintained incrementally on ingestion
+        // PURPOSE: Verify inserting and deleting data points updates series and source statistics
+        // This ensures dashboards stay current without recomputing from data_points
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+
+        let source = DataSource::create(
+            pool,
+            NewDataSource {
+                name: format!("Statistics Source {}", Uuid::new_v4()),
+                base_url: "https://statistics.example.com/api".to_string(),
+                ..NewDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = EconomicSeries::create(
+            pool,
+            &NewEconomicSeries {
+                source_id: source.id,
+                external_id: "STATS_001".to_string(),
+                title: "Statistics Series".to_string(),
+                frequency: "Monthly".to_string(),
+                is_active: true,
+                ..NewEconomicSeries::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let point = |month| NewDataPoint {
+            series_id: series.id,
+            date: NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+            value: Some(bigdecimal::BigDecimal::from(month)),
+            revision_date: NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+            is_original_release: true,
+        };
+        let points = DataPoint::create_batch(pool, &[point(1), point(2), point(3)])
+            .await
+            .unwrap();
+
+        let series_stats = SeriesStatistics::for_series(pool, &[series.id])
+            .await
+            .unwrap();
+        assert_eq!(series_stats[0].data_point_count, 3);
+        assert_eq!(
+            series_stats[0].latest_date,
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+
+        let latest = points.iter().find(|p| p.date.month() == 3).unwrap().id;
+        let mut conn = pool.get().await.unwrap();
+        diesel::delete(crate::schema::data_points::table.find(latest))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let source_stats = DataSourceStatistics::for_sources(pool, &[source.id])
+            .await
+            .unwrap();
+        assert_eq!(source_stats[0].series_count, 1);
+        assert_eq!(source_stats[0].active_series_count, 1);
+        assert_eq!(source_stats[0].data_point_count, 2);
+        assert_eq!(
+            source_stats[0].earliest_date,
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+        );
+        assert_eq!(
+            source_stats[0].latest_date,
+            NaiveDate::from_ymd_opt(2024, 2, 1)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Change feed of series and data point mutations
+//!
+//! Database triggers add an entry for every series inserted, updated or
+//! deleted, and one per series whose data points a statement changed, in the
+//! transaction that makes the change (see the `create_change_feed` migration).
+//! A rolled-back mutation therefore never shows up in the feed.
+//!
+//! Entries are numbered when read, by `sequence_change_feed()`, in the order of
+//! the transactions that wrote them, and only once every transaction that
+//! could still write an earlier entry has finished. Consumers keep the last
+//! sequence they processed and ask for the entries after it; none are skipped.
+//! A transaction that stays open holds back the entries of later ones until it
+//! ends.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::Integer;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::change_feed;
+
+/// Default number of entries returned per read
+pub const DEFAULT_CHANGE_FEED_LIMIT: i64 = 500;
+
+/// Most entries returned per read
+pub const MAX_CHANGE_FEED_LIMIT: i64 = 5_000;
+
+/// Days entries are kept before pruning
+pub const DEFAULT_CHANGE_FEED_RETENTION_DAYS: i64 = 7;
+
+/// Seconds between prunes of the change feed
+pub const DEFAULT_CHANGE_FEED_PRUNE_INTERVAL_SECONDS: u64 = 3600;
+
+/// Entity type of a change to an `economic_series` row
+pub const SERIES_ENTITY: &str = "series";
+
+/// Entity type of a change to the data points of a series, identified by the series id
+pub const SERIES_DATA_ENTITY: &str = "series_data";
+
+/// One mutation in the change feed
+#[derive(Debug, Clone, PartialEq, Queryable, Serialize)]
+pub struct ChangeFeedEntry {
+    /// Position in the feed, increasing without gaps
+    pub sequence: i64,
+    /// [`SERIES_ENTITY`] or [`SERIES_DATA_ENTITY`]
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// `insert`, `update` or `delete`
+    pub operation: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Entries after a consumer's position
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangeFeedPage {
+    /// Entries in feed order
+    pub changes: Vec<ChangeFeedEntry>,
+    /// Sequence to read from next: that of the last entry, or the one asked for
+    pub next_since: i64,
+    /// Entries after the position asked for were pruned; the consumer has to
+    /// reload what it caches before following the feed from `next_since`
+    pub resync_required: bool,
+}
+
+#[derive(QueryableByName)]
+struct Sequenced {
+    #[diesel(sql_type = Integer)]
+    sequenced: i32,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl ChangeFeedEntry {
+    /// Number the entries of finished transactions; returns how many were numbered
+    pub async fn sequence(pool: &crate::database::DatabasePool) -> AppResult<i32> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let row = diesel::sql_query("SELECT sequence_change_feed() AS sequenced")
+            .get_result::<Sequenced>(&mut conn)
+            .await?;
+
+        Ok(row.sequenced)
+    }
+
+    /// Up to `limit` entries with a sequence above `since`, in feed order
+    pub async fn since(
+        pool: &crate::database::DatabasePool,
+        since: i64,
+        limit: i64,
+    ) -> AppResult<ChangeFeedPage> {
+        if since < 0 {
+            return Err(AppError::ValidationError(
+                "since must not be negative".to_string(),
+            ));
+        }
+        let limit = limit.clamp(1, MAX_CHANGE_FEED_LIMIT);
+
+        Self::sequence(pool).await?;
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let changes = change_feed::table
+            .filter(change_feed::sequence.gt(since))
+            .order(change_feed::sequence.asc())
+            .limit(limit)
+            .select((
+                change_feed::sequence.assume_not_null(),
+                change_feed::entity_type,
+                change_feed::entity_id,
+                change_feed::operation,
+                change_feed::changed_at,
+            ))
+            .load::<Self>(&mut conn)
+            .await?;
+        let oldest: Option<i64> = change_feed::table
+            .select(diesel::dsl::min(change_feed::sequence))
+            .first(&mut conn)
+            .await?;
+
+        Ok(ChangeFeedPage {
+            next_since: changes.last().map_or(since, |change| change.sequence),
+            resync_required: oldest.is_some_and(|oldest| since + 1 < oldest),
+            changes,
+        })
+    }
+
+    /// Sequence of the latest numbered entry, 0 while the feed is empty
+    pub async fn latest_sequence(pool: &crate::database::DatabasePool) -> AppResult<i64> {
+        Self::sequence(pool).await?;
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let latest: Option<i64> = change_feed::table
+            .select(diesel::dsl::max(change_feed::sequence))
+            .first(&mut conn)
+            .await?;
+
+        Ok(latest.unwrap_or(0))
+    }
+
+    /// Delete numbered entries recorded before `older_than`; returns how many were deleted
+    ///
+    /// The latest entry is always kept, so numbering carries on after it.
+    pub async fn prune(
+        pool: &crate::database::DatabasePool,
+        older_than: DateTime<Utc>,
+    ) -> AppResult<usize> {
+        let latest = Self::latest_sequence(pool).await?;
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            change_feed::table
+                .filter(change_feed::sequence.lt(latest))
+                .filter(change_feed::changed_at.lt(older_than)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        DataPoint, DataSource, EconomicSeries, NewDataPoint, NewDataSource, NewEconomicSeries,
+    };
+    use crate::test_utils::TestContainer;
+    use bigdecimal::BigDecimal;
+    use chrono::NaiveDate;
+    use diesel_async::scoped_futures::ScopedFutureExt;
+    use diesel_async::AsyncConnection;
+
+    #[tokio::test]
+    async fn test_mutations_are_fed_in_commit_order() {
+        // REQUIREMENT: Downstream caches can tail series and data point mutations to stay in sync
+        // PURPOSE: Verify series and data point changes get feed entries with increasing sequences, and rolled-back ones do not
+        // This ensures consumers following the feed from their last sequence invalidate exactly what changed
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let start = ChangeFeedEntry::latest_sequence(pool).await.unwrap();
+
+        let source = DataSource::create(
+            pool,
+            NewDataSource {
+                name: format!("Change Feed Source {}", Uuid::new_v4()),
+                base_url: "https://changes.example.com/api".to_string(),
+                ..NewDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = EconomicSeries::create(
+            pool,
+            &NewEconomicSeries {
+                source_id: source.id,
+                external_id: "CHANGES_001".to_string(),
+                title: "Change Feed Series".to_string(),
+                frequency: "Monthly".to_string(),
+                is_active: true,
+                ..NewEconomicSeries::default()
+            },
+        )
+        .await
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let points = DataPoint::create_batch(
+            pool,
+            &[NewDataPoint {
+                series_id: series.id,
+                date,
+                value: Some(BigDecimal::from(1)),
+                revision_date: date,
+                is_original_release: true,
+            }],
+        )
+        .await
+        .unwrap();
+
+        let mut conn = pool.get().await.unwrap();
+        diesel::delete(crate::schema::data_points::table.find(points[0].id))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        // A rolled-back mutation leaves nothing behind
+        let rolled_back = conn
+            .transaction::<(), diesel::result::Error, _>(|conn| {
+                async move {
+                    diesel::update(crate::schema::economic_series::table.find(series.id))
+                        .set(crate::schema::economic_series::title.eq("Renamed"))
+                        .execute(conn)
+                        .await?;
+                    Err(diesel::result::Error::RollbackTransaction)
+                }
+                .scope_boxed()
+            })
+            .await;
+        assert!(rolled_back.is_err());
+        drop(conn);
+
+        let page = ChangeFeedEntry::since(pool, start, MAX_CHANGE_FEED_LIMIT)
+            .await
+            .unwrap();
+        assert!(!page.resync_required);
+        let ours: Vec<(&str, &str)> = page
+            .changes
+            .iter()
+            .filter(|change| change.entity_id == series.id)
+            .map(|change| (change.entity_type.as_str(), change.operation.as_str()))
+            .collect();
+        assert_eq!(
+            ours,
+            vec![
+                (SERIES_ENTITY, "insert"),
+                (SERIES_DATA_ENTITY, "insert"),
+                (SERIES_DATA_ENTITY, "delete"),
+            ]
+        );
+        assert!(page
+            .changes
+            .windows(2)
+            .all(|pair| pair[1].sequence == pair[0].sequence + 1));
+        assert_eq!(page.next_since, page.changes.last().unwrap().sequence);
+
+        // Reading on from the last position returns nothing new
+        let next = ChangeFeedEntry::since(pool, page.next_since, MAX_CHANGE_FEED_LIMIT)
+            .await
+            .unwrap();
+        assert!(next
+            .changes
+            .iter()
+            .all(|change| change.entity_id != series.id));
+    }
+}
@@ -184,7 +184,7 @@ pub struct Company {
 #[diesel(table_name = companies)]
 pub struct NewCompany {
     /// SEC Central Index Key (CIK)
-    #[validate(length(min = 10, max = 10))]
+    #[validate(custom(function = "crate::validation::cik"))]
     pub cik: String,
 
     /// Stock ticker symbol
@@ -353,7 +353,7 @@ pub struct CompanySearchParams {
     pub limit: Option<i64>,
 
     /// Number of results to skip for pagination
-    #[validate(range(min = 0))]
+    #[validate(custom(function = "crate::validation::non_negative"))]
     pub offset: Option<i64>,
 }
 
@@ -404,10 +404,11 @@ pub struct CompanyComparisonParams {
     pub metrics: Vec<String>,
 
     /// Fiscal year for comparison
+    #[validate(custom(function = "crate::validation::fiscal_year"))]
     pub fiscal_year: Option<i32>,
 
     /// Fiscal quarter for comparison
-    #[validate(range(min = 1, max = 4))]
+    #[validate(custom(function = "crate::validation::fiscal_quarter"))]
     pub fiscal_quarter: Option<i32>,
 
     /// Include peer group averages
@@ -0,0 +1,187 @@
+//! Recent financial statements of a company with their company-level facts
+//!
+//! Only filings whose XBRL has been processed are listed. Facts reported with
+//! dimensions (segments, geographies, ...) are left out; see
+//! [`SegmentBreakdown`](crate::models::SegmentBreakdown) for those.
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::enums::ProcessingStatus;
+use crate::error::{AppError, AppResult};
+use crate::models::FinancialLineItem;
+use crate::schema::{financial_line_items, financial_statements};
+
+/// Most statements returned for one company
+pub const MAX_COMPANY_FINANCIAL_STATEMENTS: i64 = 20;
+
+/// A filing, without its stored XBRL document
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = financial_statements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FinancialFiling {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub form_type: String,
+    pub accession_number: String,
+    pub filing_date: NaiveDate,
+    pub period_end_date: NaiveDate,
+    pub fiscal_year: i32,
+    pub fiscal_quarter: Option<i32>,
+    pub is_amended: bool,
+}
+
+/// A filing with its undimensioned facts
+#[derive(Debug, Clone, Serialize)]
+pub struct CompanyFinancials {
+    pub filing: FinancialFiling,
+    /// By statement, then presentation order
+    pub facts: Vec<FinancialLineItem>,
+}
+
+impl CompanyFinancials {
+    /// Up to `limit` processed filings of a company, latest period first
+    pub async fn for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let filings = financial_statements::table
+            .filter(financial_statements::company_id.eq(company_id))
+            .filter(financial_statements::xbrl_processing_status.eq(ProcessingStatus::Completed))
+            .order((
+                financial_statements::period_end_date.desc(),
+                financial_statements::filing_date.desc(),
+            ))
+            .limit(limit.clamp(1, MAX_COMPANY_FINANCIAL_STATEMENTS))
+            .select(FinancialFiling::as_select())
+            .load::<FinancialFiling>(&mut conn)
+            .await?;
+
+        let filing_ids: Vec<Uuid> = filings.iter().map(|filing| filing.id).collect();
+        let facts = financial_line_items::table
+            .filter(financial_line_items::statement_id.eq_any(&filing_ids))
+            .filter(financial_line_items::dimensions.eq(serde_json::json!({})))
+            .select(FinancialLineItem::as_select())
+            .load::<FinancialLineItem>(&mut conn)
+            .await?;
+
+        Ok(Self::group(filings, facts))
+    }
+
+    /// Attach each fact to its filing, keeping the order of `filings`
+    pub fn group(filings: Vec<FinancialFiling>, facts: Vec<FinancialLineItem>) -> Vec<Self> {
+        let mut by_filing: HashMap<Uuid, Vec<FinancialLineItem>> = HashMap::new();
+        for fact in facts {
+            by_filing.entry(fact.statement_id).or_default().push(fact);
+        }
+
+        filings
+            .into_iter()
+            .map(|filing| {
+                let mut facts = by_filing.remove(&filing.id).unwrap_or_default();
+                facts.sort_by(|a, b| {
+                    a.statement_type
+                        .as_str()
+                        .cmp(b.statement_type.as_str())
+                        .then(
+                            a.order_index
+                                .unwrap_or(i32::MAX)
+                                .cmp(&b.order_index.unwrap_or(i32::MAX)),
+                        )
+                        .then_with(|| a.taxonomy_concept.cmp(&b.taxonomy_concept))
+                });
+                Self { filing, facts }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{StatementSection, StatementType};
+    use chrono::Utc;
+
+    fn filing(id: u128) -> FinancialFiling {
+        FinancialFiling {
+            id: Uuid::from_u128(id),
+            company_id: Uuid::from_u128(100),
+            form_type: "10-K".to_string(),
+            accession_number: format!("0000320193-24-{:06}", id),
+            filing_date: NaiveDate::from_ymd_opt(2024, 11, 1).unwrap(),
+            period_end_date: NaiveDate::from_ymd_opt(2024, 9, 28).unwrap(),
+            fiscal_year: 2024,
+            fiscal_quarter: None,
+            is_amended: false,
+        }
+    }
+
+    fn fact(statement: u128, concept: &str, statement_type: StatementType) -> FinancialLineItem {
+        FinancialLineItem {
+            id: Uuid::new_v4(),
+            statement_id: Uuid::from_u128(statement),
+            taxonomy_concept: concept.to_string(),
+            standard_label: None,
+            custom_label: None,
+            value: None,
+            unit: "USD".to_string(),
+            context_ref: "FY2024".to_string(),
+            segment_ref: None,
+            scenario_ref: None,
+            precision: None,
+            decimals: None,
+            is_credit: None,
+            is_debit: None,
+            statement_type,
+            statement_section: StatementSection::Revenue,
+            parent_concept: None,
+            level: 0,
+            order_index: None,
+            is_calculated: false,
+            calculation_formula: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            normalized_value: None,
+            normalized_unit: None,
+            fx_rate: None,
+            dimensions: serde_json::json!({}),
+            period_start_date: None,
+            period_end_date: None,
+        }
+    }
+
+    #[test]
+    fn test_group_attaches_facts_to_filings_in_order() {
+        // REQUIREMENT: Typed client access to a company's financials
+        // PURPOSE: Verify facts go to their own filing, filings keep their order and facts are sorted by statement
+        // This ensures companyFinancials lists each filing once, latest first, with its own facts
+
+        let facts = vec![
+            fact(1, "us-gaap:Revenues", StatementType::IncomeStatement),
+            fact(2, "us-gaap:Assets", StatementType::BalanceSheet),
+            fact(1, "us-gaap:Assets", StatementType::BalanceSheet),
+        ];
+
+        let financials = CompanyFinancials::group(vec![filing(2), filing(1), filing(3)], facts);
+
+        assert_eq!(financials.len(), 3);
+        assert_eq!(financials[0].filing.id, Uuid::from_u128(2));
+        assert_eq!(financials[0].facts.len(), 1);
+        let concepts: Vec<&str> = financials[1]
+            .facts
+            .iter()
+            .map(|fact| fact.taxonomy_concept.as_str())
+            .collect();
+        assert_eq!(concepts, vec!["us-gaap:Assets", "us-gaap:Revenues"]);
+        assert!(financials[2].facts.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+//! Status history of the components shown on the public status page
+//!
+//! The backend probes the database, the GraphQL API and the crawler of every
+//! enabled data source periodically, but only writes a row when a component's
+//! status differs from its latest one. The status of a component at any time
+//! is therefore that of its latest transition before then, and uptime over a
+//! window follows from the transitions in it plus the one preceding it.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::Serialize;
+
+use crate::database::DatabasePool;
+use crate::error::{AppError, AppResult};
+use crate::schema::component_status_transitions;
+
+/// Days of history uptime is reported over
+pub const UPTIME_WINDOW_DAYS: i64 = 90;
+
+/// Seconds between recordings of component status
+pub const DEFAULT_STATUS_RECORD_INTERVAL_SECONDS: u64 = 60;
+
+/// Statuses a component can be in
+pub const COMPONENT_STATUSES: [&str; 3] = ["healthy", "degraded", "unhealthy"];
+
+/// A change of a component's status
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Serialize)]
+#[diesel(table_name = component_status_transitions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ComponentStatusTransition {
+    pub id: i64,
+    /// `database`, `graphql` or `crawler:<data source name>`
+    pub component: String,
+    /// One of [`COMPONENT_STATUSES`]
+    pub status: String,
+    pub message: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = component_status_transitions)]
+struct NewComponentStatusTransition<'a> {
+    component: &'a str,
+    status: &'a str,
+    message: Option<&'a str>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl ComponentStatusTransition {
+    /// Record the status of a component unless it is already its latest one;
+    /// returns whether a transition was written
+    pub async fn record(
+        pool: &DatabasePool,
+        component: &str,
+        status: &str,
+        message: Option<&str>,
+    ) -> AppResult<bool> {
+        if !COMPONENT_STATUSES.contains(&status) {
+            return Err(AppError::ValidationError(format!(
+                "Unknown component status: {}",
+                status
+            )));
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let component = component.to_string();
+        let status = status.to_string();
+        let message = message.map(str::to_string);
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                // Instances recording at the same time must not both write the change
+                diesel::sql_query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+                    .bind::<Text, _>(format!("component_status:{}", component))
+                    .execute(conn)
+                    .await?;
+
+                let latest: Option<String> = component_status_transitions::table
+                    .filter(component_status_transitions::component.eq(&component))
+                    .order((
+                        component_status_transitions::changed_at.desc(),
+                        component_status_transitions::id.desc(),
+                    ))
+                    .select(component_status_transitions::status)
+                    .first(conn)
+                    .await
+                    .optional()?;
+                if latest.as_deref() == Some(status.as_str()) {
+                    return Ok(false);
+                }
+
+                diesel::insert_into(component_status_transitions::table)
+                    .values(NewComponentStatusTransition {
+                        component: &component,
+                        status: &status,
+                        message: message.as_deref(),
+                    })
+                    .execute(conn)
+                    .await?;
+
+                Ok(true)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Latest transition of every component, by component name
+    pub async fn current(pool: &DatabasePool) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let current = component_status_transitions::table
+            .distinct_on(component_status_transitions::component)
+            .order((
+                component_status_transitions::component.asc(),
+                component_status_transitions::changed_at.desc(),
+                component_status_transitions::id.desc(),
+            ))
+            .select(Self::as_select())
+            .load(&mut conn)
+            .await?;
+
+        Ok(current)
+    }
+
+    /// Transitions that determine the status of every component since `since`:
+    /// those after it and the latest one before it, ordered by component and time
+    pub async fn history_since(pool: &DatabasePool, since: DateTime<Utc>) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut history = component_status_transitions::table
+            .filter(component_status_transitions::changed_at.lt(since))
+            .distinct_on(component_status_transitions::component)
+            .order((
+                component_status_transitions::component.asc(),
+                component_status_transitions::changed_at.desc(),
+                component_status_transitions::id.desc(),
+            ))
+            .select(Self::as_select())
+            .load(&mut conn)
+            .await?;
+        history.extend(
+            component_status_transitions::table
+                .filter(component_status_transitions::changed_at.ge(since))
+                .select(Self::as_select())
+                .load::<Self>(&mut conn)
+                .await?,
+        );
+        history.sort_by(|a, b| {
+            (&a.component, a.changed_at, a.id).cmp(&(&b.component, b.changed_at, b.id))
+        });
+
+        Ok(history)
+    }
+}
+
+/// Percentage of the time between `since` and `now` a component was healthy
+///
+/// `transitions` are one component's, in time order, as returned by
+/// [`ComponentStatusTransition::history_since`]. Time before the first
+/// transition was not observed and does not count; `None` when none was.
+pub fn uptime_percentage(
+    transitions: &[ComponentStatusTransition],
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<f64> {
+    let mut observed = 0i64;
+    let mut healthy = 0i64;
+
+    for (i, transition) in transitions.iter().enumerate() {
+        let start = transition.changed_at.max(since);
+        let end = transitions
+            .get(i + 1)
+            .map_or(now, |next| next.changed_at)
+            .min(now);
+        let span = (end - start).num_milliseconds().max(0);
+
+        observed += span;
+        if transition.status == "healthy" {
+            healthy += span;
+        }
+    }
+
+    (observed > 0).then(|| healthy as f64 * 100.0 / observed as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestContainer;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_status_transitions_and_uptime() {
+        // REQUIREMENT: The public status page shows current status and 90-day uptime per component
+        // PURPOSE: Verify only status changes are recorded and uptime counts the time spent healthy
+        // This ensures users can tell from the status page whether and how long data feeds were delayed
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let component = format!("crawler:Status Source {}", Uuid::new_v4());
+
+        assert!(
+            ComponentStatusTransition::record(pool, &component, "healthy", None)
+                .await
+                .unwrap()
+        );
+        // The same status again is not a transition
+        assert!(
+            !ComponentStatusTransition::record(pool, &component, "healthy", None)
+                .await
+                .unwrap()
+        );
+        assert!(ComponentStatusTransition::record(
+            pool,
+            &component,
+            "degraded",
+            Some("Last crawl is stale")
+        )
+        .await
+        .unwrap());
+        assert!(matches!(
+            ComponentStatusTransition::record(pool, &component, "down", None).await,
+            Err(AppError::ValidationError(_))
+        ));
+
+        let current = ComponentStatusTransition::current(pool).await.unwrap();
+        let ours = current.iter().find(|t| t.component == component).unwrap();
+        assert_eq!(ours.status, "degraded");
+        assert_eq!(ours.message.as_deref(), Some("Last crawl is stale"));
+
+        let since = Utc::now() - Duration::days(UPTIME_WINDOW_DAYS);
+        let history = ComponentStatusTransition::history_since(pool, since)
+            .await
+            .unwrap();
+        assert_eq!(
+            history.iter().filter(|t| t.component == component).count(),
+            2
+        );
+
+        // Healthy for 3 of the 4 observed days; the time before the first transition is not counted
+        let now = Utc::now();
+        let transition = |status: &str, days_ago: i64| ComponentStatusTransition {
+            id: 0,
+            component: component.clone(),
+            status: status.to_string(),
+            message: None,
+            changed_at: now - Duration::days(days_ago),
+        };
+        let transitions = vec![
+            transition("healthy", 4),
+            transition("unhealthy", 2),
+            transition("healthy", 1),
+        ];
+        let uptime = uptime_percentage(&transitions, since, now).unwrap();
+        assert!((uptime - 75.0).abs() < 0.001);
+
+        // A transition before the window only counts from the start of the window
+        let transitions = vec![transition("unhealthy", 100), transition("healthy", 45)];
+        let uptime = uptime_percentage(&transitions, since, now).unwrap();
+        assert!((uptime - 50.0).abs() < 0.001);
+
+        assert_eq!(uptime_percentage(&[], since, now), None);
+    }
+}
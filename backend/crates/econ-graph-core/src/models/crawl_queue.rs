@@ -65,6 +65,8 @@ pub enum QueueStatus {
     Failed,
     Retrying,
     Cancelled,
+    /// Exhausted its retries and is waiting for an admin to requeue or discard it
+    DeadLetter,
 }
 
 impl std::fmt::Display for QueueStatus {
@@ -76,6 +78,7 @@ impl std::fmt::Display for QueueStatus {
             QueueStatus::Failed => write!(f, "failed"),
             QueueStatus::Retrying => write!(f, "retrying"),
             QueueStatus::Cancelled => write!(f, "cancelled"),
+            QueueStatus::DeadLetter => write!(f, "dead_letter"),
         }
     }
 }
@@ -89,6 +92,7 @@ impl From<String> for QueueStatus {
             "failed" => QueueStatus::Failed,
             "retrying" => QueueStatus::Retrying,
             "cancelled" => QueueStatus::Cancelled,
+            "dead_letter" => QueueStatus::DeadLetter,
             _ => QueueStatus::Pending,
         }
     }
@@ -129,10 +133,31 @@ pub struct QueueStatistics {
     pub completed_items: i64,
     pub failed_items: i64,
     pub retrying_items: i64,
+    pub dead_letter_items: i64,
     pub oldest_pending: Option<DateTime<Utc>>,
     pub average_processing_time: Option<f64>, // in seconds
 }
 
+/// Filters for listing crawl queue items
+#[derive(Debug, Clone, Default)]
+pub struct CrawlQueueFilter {
+    pub status: Option<QueueStatus>,
+    pub source: Option<String>,
+    /// Lowest priority included (1-10)
+    pub min_priority: Option<i32>,
+    /// Highest priority included (1-10)
+    pub max_priority: Option<i32>,
+}
+
+/// Outstanding queue items of one source in one status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueDepth {
+    pub source: String,
+    pub status: String,
+    pub items: i64,
+    pub oldest_created_at: Option<DateTime<Utc>>,
+}
+
 /// Queue item with processing information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueueItemWithProcessingInfo {
@@ -368,6 +393,13 @@ mod _inline_tests {
             QueueStatus::Processing
         );
 
+        // Test dead-letter round trip - stored as a snake_case database value
+        assert_eq!(
+            QueueStatus::from(QueueStatus::DeadLetter.to_string()),
+            QueueStatus::DeadLetter
+        );
+        assert_eq!(QueueStatus::DeadLetter.to_string(), "dead_letter");
+
         // Test unknown status defaults to Pending - safe fallback behavior
         assert_eq!(
             QueueStatus::from("unknown".to_string()),
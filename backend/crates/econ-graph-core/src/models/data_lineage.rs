@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::data_lineage;
+
+/// One thing done to a value between download and storage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessingStep {
+    /// Step name, e.g. "unit_normalization"
+    pub name: String,
+    /// What the step did to this value, when it changed or flagged it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ProcessingStep {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Where a stored value came from
+///
+/// Links either a data point to the crawl attempt that fetched it, or an SEC
+/// fact (financial line item) to the filing it was parsed from. The source URL
+/// and crawl time are copied here so they survive cleanup of old attempts.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = data_lineage)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataLineage {
+    pub id: Uuid,
+    pub data_point_id: Option<Uuid>,
+    pub financial_line_item_id: Option<Uuid>,
+    pub crawl_attempt_id: Option<Uuid>,
+    /// Filing an SEC fact was parsed from
+    pub statement_id: Option<Uuid>,
+    /// Data source name, e.g. "FRED"
+    pub source: String,
+    pub source_url: Option<String>,
+    pub crawled_at: DateTime<Utc>,
+    /// JSON array of [`ProcessingStep`]s, in the order they were applied
+    pub processing_steps: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New lineage record for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = data_lineage)]
+pub struct NewDataLineage {
+    pub data_point_id: Option<Uuid>,
+    pub financial_line_item_id: Option<Uuid>,
+    pub crawl_attempt_id: Option<Uuid>,
+    pub statement_id: Option<Uuid>,
+    pub source: String,
+    pub source_url: Option<String>,
+    pub crawled_at: DateTime<Utc>,
+    pub processing_steps: serde_json::Value,
+}
+
+impl NewDataLineage {
+    /// Lineage of a data point written by a crawl
+    pub fn for_data_point(
+        data_point_id: Uuid,
+        source: &str,
+        source_url: Option<String>,
+        crawl_attempt_id: Option<Uuid>,
+        crawled_at: DateTime<Utc>,
+        steps: &[ProcessingStep],
+    ) -> Self {
+        Self {
+            data_point_id: Some(data_point_id),
+            financial_line_item_id: None,
+            crawl_attempt_id,
+            statement_id: None,
+            source: source.to_string(),
+            source_url,
+            crawled_at,
+            processing_steps: steps_to_json(steps),
+        }
+    }
+
+    /// Lineage of an SEC fact parsed from a filing
+    pub fn for_financial_line_item(
+        financial_line_item_id: Uuid,
+        statement_id: Uuid,
+        document_url: &str,
+        crawled_at: DateTime<Utc>,
+        steps: &[ProcessingStep],
+    ) -> Self {
+        Self {
+            data_point_id: None,
+            financial_line_item_id: Some(financial_line_item_id),
+            crawl_attempt_id: None,
+            statement_id: Some(statement_id),
+            source: "SEC EDGAR".to_string(),
+            source_url: Some(document_url.to_string()),
+            crawled_at,
+            processing_steps: steps_to_json(steps),
+        }
+    }
+}
+
+fn steps_to_json(steps: &[ProcessingStep]) -> serde_json::Value {
+    serde_json::to_value(steps).unwrap_or_else(|_| serde_json::Value::Array(Vec::new()))
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl DataLineage {
+    /// Processing steps in the order they were applied
+    ///
+    /// Records that cannot be read as steps are treated as having none.
+    pub fn steps(&self) -> Vec<ProcessingStep> {
+        serde_json::from_value(self.processing_steps.clone()).unwrap_or_default()
+    }
+
+    /// Insert lineage records, returning how many were written
+    pub async fn create_batch(
+        pool: &crate::database::DatabasePool,
+        records: &[NewDataLineage],
+    ) -> AppResult<usize> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let written = diesel::insert_into(data_lineage::table)
+            .values(records)
+            .on_conflict_do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        Ok(written)
+    }
+
+    /// Lineage of a data point, if it was recorded
+    pub async fn find_for_data_point(
+        pool: &crate::database::DatabasePool,
+        data_point_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let lineage = data_lineage::table
+            .filter(data_lineage::data_point_id.eq(data_point_id))
+            .select(DataLineage::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(lineage)
+    }
+
+    /// Lineage of an SEC fact, if it was recorded
+    pub async fn find_for_financial_line_item(
+        pool: &crate::database::DatabasePool,
+        financial_line_item_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let lineage = data_lineage::table
+            .filter(data_lineage::financial_line_item_id.eq(financial_line_item_id))
+            .select(DataLineage::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(lineage)
+    }
+}
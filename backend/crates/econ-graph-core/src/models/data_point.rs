@@ -7,6 +7,9 @@ use validator::Validate;
 
 use crate::schema::data_points;
 
+/// Default number of rows fetched per batch by [`DataPoint::stream_by_series`]
+pub const DATA_POINT_STREAM_BATCH_SIZE: i64 = 5_000;
+
 /// **DataPoint Model**
 ///
 /// Represents a single observation in an economic time series, containing both the actual
@@ -284,6 +287,7 @@ pub struct DataPointWithSeries {
 ///     end_date: Some(NaiveDate::from_ymd_opt(2024, 11, 30).unwrap()),
 ///     original_only: Some(true),
 ///     latest_revision_only: Some(false),
+///     exclude_corrections: None,
 ///     limit: Some(12),
 ///     offset: Some(0),
 /// };
@@ -312,6 +316,11 @@ pub struct DataQueryParams {
     /// false/None: Include all revisions for complete revision history
     pub latest_revision_only: Option<bool>,
 
+    /// Leave out revisions written by manual data corrections
+    /// true: Show the data as published by the source
+    /// false/None: Include corrections, which supersede the values they correct
+    pub exclude_corrections: Option<bool>,
+
     /// Maximum number of data points to return
     /// Capped at 10,000 to prevent memory exhaustion and ensure reasonable response times
     #[validate(range(min = 1, max = 10000))]
@@ -740,6 +749,49 @@ impl DataPoint {
         Ok(data_points)
     }
 
+    /// Insert a batch of data points, skipping those that already exist
+    ///
+    /// Batches of at least [`BULK_COPY_MIN_ROWS`](crate::bulk_copy::BULK_COPY_MIN_ROWS)
+    /// points are loaded with binary COPY through a staging table; smaller
+    /// batches, and processes whose pool database is not known, use a regular
+    /// insert. Returns the inserted points.
+    pub async fn insert_bulk(
+        pool: &crate::database::DatabasePool,
+        new_data_points: &[NewDataPoint],
+    ) -> crate::error::AppResult<Vec<Self>> {
+        use crate::bulk_copy::{BulkCopy, BULK_COPY_MIN_ROWS};
+        use crate::schema::data_points::dsl;
+
+        if new_data_points.len() >= BULK_COPY_MIN_ROWS {
+            if let Some(mut copy) = BulkCopy::connect_to_pool_database().await? {
+                return copy.insert_data_points(new_data_points).await;
+            }
+        }
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let data_points = diesel_async::RunQueryDsl::get_results(
+            diesel::insert_into(dsl::data_points)
+                .values(new_data_points)
+                .on_conflict((
+                    dsl::series_id,
+                    dsl::date,
+                    dsl::revision_date,
+                    dsl::is_original_release,
+                ))
+                .do_nothing(),
+            &mut conn,
+        )
+        .await?;
+
+        Ok(data_points)
+    }
+
     /// Find data points for a series within a date range
     pub async fn find_by_series_and_date_range(
         pool: &crate::database::DatabasePool,
@@ -768,6 +820,94 @@ impl DataPoint {
         Ok(data_points)
     }
 
+    /// Stream data points for a series in date-ordered batches
+    ///
+    /// The date range and release filter are applied in SQL and rows are
+    /// fetched with keyset pagination on `(date, id)`, so no single query
+    /// returns the full history. One pool connection serves every batch and is
+    /// returned once the last batch has been read or the stream is dropped.
+    /// Memory stays bounded only if the caller processes each batch and drops
+    /// it rather than collecting them.
+    pub fn stream_by_series(
+        pool: &crate::database::DatabasePool,
+        series_id: uuid::Uuid,
+        start_date: Option<chrono::NaiveDate>,
+        end_date: Option<chrono::NaiveDate>,
+        original_only: bool,
+        batch_size: i64,
+    ) -> impl futures::Stream<Item = crate::error::AppResult<Vec<Self>>> + '_ {
+        let batch_size = batch_size.max(1);
+
+        futures::stream::try_unfold(
+            (
+                None::<crate::database::PooledConn<'_>>,
+                None::<(chrono::NaiveDate, uuid::Uuid)>,
+                false,
+            ),
+            move |(conn, cursor, exhausted)| async move {
+                use crate::schema::data_points::dsl;
+
+                if exhausted {
+                    return Ok(None);
+                }
+
+                let mut conn = match conn {
+                    Some(conn) => conn,
+                    None => pool.get().await.map_err(|e| {
+                        crate::error::AppError::DatabaseError(format!(
+                            "Failed to get database connection: {}",
+                            e
+                        ))
+                    })?,
+                };
+
+                let mut query = dsl::data_points
+                    .filter(dsl::series_id.eq(series_id))
+                    .into_boxed();
+
+                if let Some(start_date) = start_date {
+                    query = query.filter(dsl::date.ge(start_date));
+                }
+
+                if let Some(end_date) = end_date {
+                    query = query.filter(dsl::date.le(end_date));
+                }
+
+                if original_only {
+                    query = query.filter(dsl::is_original_release.eq(true));
+                }
+
+                if let Some((last_date, last_id)) = cursor {
+                    query = query.filter(
+                        dsl::date
+                            .gt(last_date)
+                            .or(dsl::date.eq(last_date).and(dsl::id.gt(last_id))),
+                    );
+                }
+
+                let batch: Vec<Self> = diesel_async::RunQueryDsl::load(
+                    query
+                        .order((dsl::date.asc(), dsl::id.asc()))
+                        .limit(batch_size)
+                        .select(Self::as_select()),
+                    &mut conn,
+                )
+                .await?;
+
+                let Some(last) = batch.last() else {
+                    return Ok(None);
+                };
+
+                let next_cursor = Some((last.date, last.id));
+                let exhausted = (batch.len() as i64) < batch_size;
+                // Give the connection back as soon as there is nothing left to read
+                let conn = (!exhausted).then_some(conn);
+
+                Ok(Some((batch, (conn, next_cursor, exhausted))))
+            },
+        )
+    }
+
     /// Get a data point by series ID and specific date
     pub async fn get_by_series_and_date(
         pool: &crate::database::DatabasePool,
@@ -794,6 +934,32 @@ impl DataPoint {
 
         Ok(data_point)
     }
+
+    /// Find a data point by its ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: uuid::Uuid,
+    ) -> crate::error::AppResult<Option<Self>> {
+        use crate::schema::data_points::dsl;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let data_point = diesel_async::RunQueryDsl::first(
+            dsl::data_points
+                .filter(dsl::id.eq(id))
+                .select(Self::as_select()),
+            &mut conn,
+        )
+        .await
+        .optional()?;
+
+        Ok(data_point)
+    }
 }
 
 // Inline tests moved to external test file
@@ -882,6 +1048,7 @@ mod inline_tests {
             end_date: None,
             original_only: None,
             latest_revision_only: None,
+            exclude_corrections: None,
             limit: Some(100),
             offset: Some(0),
         };
@@ -899,6 +1066,7 @@ mod inline_tests {
             end_date: None,
             original_only: None,
             latest_revision_only: None,
+            exclude_corrections: None,
             limit: Some(20000), // Exceeds maximum allowed limit
             offset: Some(0),
         };
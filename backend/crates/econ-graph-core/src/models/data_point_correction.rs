@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::{DataPoint, NewDataPoint};
+use crate::schema::{data_point_corrections, data_points};
+
+/// Longest reason accepted for a correction
+pub const MAX_CORRECTION_REASON_LENGTH: usize = 2000;
+
+/// A manual correction of an observation
+///
+/// The corrected value lives in `data_points` as an ordinary revision
+/// (`data_point_id`); this record marks that revision as a correction, so
+/// queries can leave corrections out, and keeps who made it and why.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = data_point_corrections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataPointCorrection {
+    pub id: Uuid,
+    /// Revision written by the correction
+    pub data_point_id: Uuid,
+    /// Revision that was found to be wrong
+    pub corrected_data_point_id: Option<Uuid>,
+    pub series_id: Uuid,
+    pub reason: String,
+    pub corrected_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New correction for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = data_point_corrections)]
+pub struct NewDataPointCorrection {
+    pub data_point_id: Uuid,
+    pub corrected_data_point_id: Option<Uuid>,
+    pub series_id: Uuid,
+    pub reason: String,
+    pub corrected_by: Option<Uuid>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl DataPointCorrection {
+    /// Write the correcting revision and mark it as a correction of `corrected_data_point_id`
+    ///
+    /// Both rows are written in one transaction, so a revision is never left
+    /// unmarked.
+    pub async fn record(
+        pool: &crate::database::DatabasePool,
+        revision: &NewDataPoint,
+        corrected_data_point_id: Uuid,
+        reason: &str,
+        corrected_by: Uuid,
+    ) -> AppResult<(DataPoint, Self)> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let revision = revision.clone();
+        let reason = reason.to_string();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let data_point: DataPoint = diesel::insert_into(data_points::table)
+                    .values(&revision)
+                    .get_result(conn)
+                    .await?;
+
+                let correction = diesel::insert_into(data_point_corrections::table)
+                    .values(&NewDataPointCorrection {
+                        data_point_id: data_point.id,
+                        corrected_data_point_id: Some(corrected_data_point_id),
+                        series_id: data_point.series_id,
+                        reason,
+                        corrected_by: Some(corrected_by),
+                    })
+                    .returning(DataPointCorrection::as_returning())
+                    .get_result::<Self>(conn)
+                    .await?;
+
+                Ok((data_point, correction))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Corrections of a series, most recent first
+    pub async fn list_for_series(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let corrections = data_point_corrections::table
+            .filter(data_point_corrections::series_id.eq(series_id))
+            .order(data_point_corrections::created_at.desc())
+            .select(DataPointCorrection::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(corrections)
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::enums::DataAccessPolicy;
 use crate::schema::data_sources;
 
 /// Data source model representing external data providers
@@ -29,6 +30,14 @@ pub struct DataSource {
     pub crawl_error_message: Option<String>,
     pub api_documentation_url: Option<String>,
     pub api_key_name: Option<String>,
+    /// Bytes the crawlers may download per UTC day; `None` is unlimited
+    pub daily_byte_quota: Option<i64>,
+    /// Requests the crawlers may send per UTC day; `None` is unlimited
+    pub daily_request_quota: Option<i32>,
+    /// Who may read the source's observations
+    pub access_policy: DataAccessPolicy,
+    /// Mirrors of `base_url` the crawlers fail over to, in order
+    pub fallback_urls: Vec<String>,
 }
 
 /// New data source for insertion
@@ -47,6 +56,7 @@ pub struct NewDataSource {
     pub is_visible: bool,
     pub is_enabled: bool,
     pub requires_admin_approval: bool,
+    #[validate(range(min = 1, max = 8760))]
     pub crawl_frequency_hours: i32,
     #[validate(url)]
     pub api_documentation_url: Option<String>,
@@ -68,6 +78,19 @@ pub struct UpdateDataSource {
     pub rate_limit_per_minute: Option<i32>,
     #[validate(url)]
     pub api_documentation_url: Option<String>,
+    pub is_visible: Option<bool>,
+    pub is_enabled: Option<bool>,
+    pub requires_admin_approval: Option<bool>,
+    #[validate(range(min = 1, max = 8760))]
+    pub crawl_frequency_hours: Option<i32>,
+    /// `Some(None)` clears the credential reference
+    pub api_key_name: Option<Option<String>>,
+    /// `Some(None)` removes the byte quota
+    pub daily_byte_quota: Option<Option<i64>>,
+    /// `Some(None)` removes the request quota
+    pub daily_request_quota: Option<Option<i32>>,
+    pub access_policy: Option<DataAccessPolicy>,
+    pub fallback_urls: Option<Vec<String>>,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -236,6 +259,29 @@ impl DataSource {
         }
     }
 
+    /// Create Eurostat (statistical office of the European Union) source
+    pub fn eurostat() -> NewDataSource {
+        NewDataSource {
+            name: "Eurostat".to_string(),
+            description: Some(
+                "European Union inflation, unemployment, national accounts and government finance statistics"
+                    .to_string(),
+            ),
+            base_url: "https://ec.europa.eu/eurostat/api/dissemination".to_string(),
+            api_key_required: false,
+            rate_limit_per_minute: 240,
+            is_visible: true,
+            is_enabled: true,
+            requires_admin_approval: false,
+            crawl_frequency_hours: 24,
+            api_documentation_url: Some(
+                "https://wikis.ec.europa.eu/display/EUROSTATHELP/API+Statistics+-+data+query"
+                    .to_string(),
+            ),
+            api_key_name: None,
+        }
+    }
+
     /// Create OECD (Organisation for Economic Co-operation and Development) source
     pub fn oecd() -> NewDataSource {
         NewDataSource {
@@ -424,6 +470,30 @@ impl DataSource {
         }
     }
 
+    /// Every predefined data source, as seeded into a new database
+    pub fn predefined() -> Vec<NewDataSource> {
+        vec![
+            Self::fred(),
+            Self::bls(),
+            Self::census(),
+            Self::world_bank(),
+            Self::bea(),
+            Self::imf(),
+            Self::fhfa(),
+            Self::ecb(),
+            Self::eurostat(),
+            Self::oecd(),
+            Self::boe(),
+            Self::wto(),
+            Self::boj(),
+            Self::rba(),
+            Self::boc(),
+            Self::snb(),
+            Self::unstats(),
+            Self::ilo(),
+        ]
+    }
+
     /// Find data source by name
     pub async fn find_by_name(
         pool: &crate::database::DatabasePool,
@@ -448,6 +518,29 @@ impl DataSource {
         Ok(source)
     }
 
+    /// Find data source by ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> crate::error::AppResult<Option<Self>> {
+        use crate::schema::data_sources::dsl;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let source = dsl::data_sources
+            .filter(dsl::id.eq(id))
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(source)
+    }
+
     /// Find all data sources
     pub async fn find_all(
         pool: &crate::database::DatabasePool,
@@ -492,6 +585,34 @@ impl DataSource {
         Ok(source)
     }
 
+    /// Apply an update to a data source
+    ///
+    /// Returns `None` if no data source has the given ID.
+    pub async fn update(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        changes: UpdateDataSource,
+    ) -> crate::error::AppResult<Option<Self>> {
+        use crate::schema::data_sources::dsl;
+
+        changes.validate()?;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let source = diesel::update(dsl::data_sources.filter(dsl::id.eq(id)))
+            .set(&changes)
+            .get_result::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(source)
+    }
+
     /// Get or create a data source by name
     pub async fn get_or_create(
         pool: &crate::database::DatabasePool,
@@ -551,6 +672,15 @@ impl Default for UpdateDataSource {
             api_key_required: None,
             rate_limit_per_minute: None,
             api_documentation_url: Some("https://example.com/api/docs".to_string()),
+            is_visible: None,
+            is_enabled: None,
+            requires_admin_approval: None,
+            crawl_frequency_hours: None,
+            api_key_name: None,
+            daily_byte_quota: None,
+            daily_request_quota: None,
+            access_policy: None,
+            fallback_urls: None,
             updated_at: Utc::now(),
         }
     }
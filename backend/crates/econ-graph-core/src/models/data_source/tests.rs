@@ -38,6 +38,21 @@ mod simple_tests {
         assert!(!source.api_key_required);
     }
 
+    #[test]
+    fn test_predefined_data_sources_are_unique() {
+        // REQUIREMENT: Seed a new database with every predefined data source
+        // PURPOSE: Verify the predefined list names each source once
+        // This ensures seeding by name creates one row per source and never collides
+
+        let sources = DataSource::predefined();
+        let mut names: Vec<&str> = sources.iter().map(|s| s.name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+
+        assert_eq!(names.len(), sources.len());
+        assert!(sources.iter().any(|s| s.name == DataSource::fred().name));
+    }
+
     #[test]
     fn test_update_data_source_creation() {
         // REQUIREMENT: Test data source update struct creation
@@ -51,7 +66,8 @@ mod simple_tests {
             api_key_required: Some(true),
             rate_limit_per_minute: Some(200),
             api_documentation_url: Some("https://api.updated.com/docs".to_string()),
-            updated_at: chrono::Utc::now(),
+            crawl_frequency_hours: Some(12),
+            ..Default::default()
         };
 
         assert_eq!(update.name, Some("Updated Source".to_string()));
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::data_source_credentials;
+use crate::secrets::EncryptedSecret;
+
+/// Encrypted API key of a data source
+///
+/// Only the ciphertext is stored; decrypting it needs the key named by
+/// `key_id` (see [`crate::secrets::SecretCipher`]). The type deliberately
+/// does not implement `Serialize`.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = data_source_credentials)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataSourceCredential {
+    pub id: Uuid,
+    pub data_source_id: Uuid,
+    pub key_id: String,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New or replacement credential for insertion
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = data_source_credentials)]
+pub struct NewDataSourceCredential {
+    pub data_source_id: Uuid,
+    pub key_id: String,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub updated_by: Option<Uuid>,
+}
+
+impl NewDataSourceCredential {
+    pub fn new(data_source_id: Uuid, encrypted: EncryptedSecret, updated_by: Option<Uuid>) -> Self {
+        Self {
+            data_source_id,
+            key_id: encrypted.key_id,
+            nonce: encrypted.nonce,
+            ciphertext: encrypted.ciphertext,
+            updated_by,
+        }
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl DataSourceCredential {
+    /// The stored ciphertext, ready for decryption
+    pub fn encrypted(&self) -> EncryptedSecret {
+        EncryptedSecret {
+            key_id: self.key_id.clone(),
+            nonce: self.nonce.clone(),
+            ciphertext: self.ciphertext.clone(),
+        }
+    }
+
+    /// Store the credential of a data source, replacing any previous one
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        credential: &NewDataSourceCredential,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = diesel::insert_into(data_source_credentials::table)
+            .values(credential)
+            .on_conflict(data_source_credentials::data_source_id)
+            .do_update()
+            .set((
+                data_source_credentials::key_id.eq(&credential.key_id),
+                data_source_credentials::nonce.eq(&credential.nonce),
+                data_source_credentials::ciphertext.eq(&credential.ciphertext),
+                data_source_credentials::updated_by.eq(credential.updated_by),
+                data_source_credentials::updated_at.eq(Utc::now()),
+            ))
+            .returning(DataSourceCredential::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Credential of a data source, if one is stored
+    pub async fn find_by_data_source(
+        pool: &crate::database::DatabasePool,
+        data_source_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let credential = data_source_credentials::table
+            .filter(data_source_credentials::data_source_id.eq(data_source_id))
+            .select(DataSourceCredential::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(credential)
+    }
+
+    /// Remove the credential of a data source; returns whether one was stored
+    pub async fn delete_for_data_source(
+        pool: &crate::database::DatabasePool,
+        data_source_id: Uuid,
+    ) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            data_source_credentials::table
+                .filter(data_source_credentials::data_source_id.eq(data_source_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+}
@@ -0,0 +1,327 @@
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Timestamptz};
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::DataSource;
+use crate::schema::{data_source_key_usage, data_source_usage};
+
+/// Bytes downloaded and requests sent for a data source on one UTC day
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize)]
+#[diesel(table_name = data_source_usage)]
+#[diesel(primary_key(data_source_id, usage_date))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataSourceUsage {
+    pub data_source_id: Uuid,
+    pub usage_date: NaiveDate,
+    pub bytes_downloaded: i64,
+    pub request_count: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = data_source_usage)]
+struct NewDataSourceUsage {
+    data_source_id: Uuid,
+    usage_date: NaiveDate,
+    bytes_downloaded: i64,
+    request_count: i32,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl DataSourceUsage {
+    /// Add to the usage of a data source on `usage_date` and return the new totals
+    pub async fn record(
+        pool: &crate::database::DatabasePool,
+        data_source_id: Uuid,
+        usage_date: NaiveDate,
+        bytes: i64,
+        requests: i32,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let usage = diesel::insert_into(data_source_usage::table)
+            .values(&NewDataSourceUsage {
+                data_source_id,
+                usage_date,
+                bytes_downloaded: bytes,
+                request_count: requests,
+            })
+            .on_conflict((
+                data_source_usage::data_source_id,
+                data_source_usage::usage_date,
+            ))
+            .do_update()
+            .set((
+                data_source_usage::bytes_downloaded.eq(data_source_usage::bytes_downloaded
+                    + excluded(data_source_usage::bytes_downloaded)),
+                data_source_usage::request_count
+                    .eq(data_source_usage::request_count
+                        + excluded(data_source_usage::request_count)),
+                data_source_usage::updated_at.eq(Utc::now()),
+            ))
+            .returning(DataSourceUsage::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(usage)
+    }
+
+    /// Usage of a data source on `usage_date`, if it sent any request that day
+    pub async fn find(
+        pool: &crate::database::DatabasePool,
+        data_source_id: Uuid,
+        usage_date: NaiveDate,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let usage = data_source_usage::table
+            .find((data_source_id, usage_date))
+            .select(DataSourceUsage::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(usage)
+    }
+
+    /// Usage of every data source that sent requests on `usage_date`
+    pub async fn for_date(
+        pool: &crate::database::DatabasePool,
+        usage_date: NaiveDate,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let usage = data_source_usage::table
+            .filter(data_source_usage::usage_date.eq(usage_date))
+            .select(DataSourceUsage::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(usage)
+    }
+}
+
+/// Requests sent with one API key of a data source on one UTC day
+///
+/// Keys are identified by `key_hash`, a short hash of the key that is also
+/// used as a metric label (see `ApiKeyRing` in the services crate).
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize)]
+#[diesel(table_name = data_source_key_usage)]
+#[diesel(primary_key(data_source_id, key_hash, usage_date))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DataSourceKeyUsage {
+    pub data_source_id: Uuid,
+    pub key_hash: String,
+    pub usage_date: NaiveDate,
+    pub bytes_downloaded: i64,
+    pub request_count: i32,
+    /// Requests answered with HTTP 429
+    pub throttled_count: i32,
+    pub last_throttled_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = data_source_key_usage)]
+struct NewDataSourceKeyUsage<'a> {
+    data_source_id: Uuid,
+    key_hash: &'a str,
+    usage_date: NaiveDate,
+    bytes_downloaded: i64,
+    request_count: i32,
+    throttled_count: i32,
+    last_throttled_at: Option<DateTime<Utc>>,
+}
+
+impl DataSourceKeyUsage {
+    /// Count one request sent with an API key on `usage_date`
+    pub async fn record(
+        pool: &crate::database::DatabasePool,
+        data_source_id: Uuid,
+        key_hash: &str,
+        usage_date: NaiveDate,
+        bytes: i64,
+        throttled: bool,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let now = Utc::now();
+
+        let usage = diesel::insert_into(data_source_key_usage::table)
+            .values(&NewDataSourceKeyUsage {
+                data_source_id,
+                key_hash,
+                usage_date,
+                bytes_downloaded: bytes,
+                request_count: 1,
+                throttled_count: i32::from(throttled),
+                last_throttled_at: throttled.then_some(now),
+            })
+            .on_conflict((
+                data_source_key_usage::data_source_id,
+                data_source_key_usage::key_hash,
+                data_source_key_usage::usage_date,
+            ))
+            .do_update()
+            .set((
+                data_source_key_usage::bytes_downloaded.eq(data_source_key_usage::bytes_downloaded
+                    + excluded(data_source_key_usage::bytes_downloaded)),
+                data_source_key_usage::request_count.eq(data_source_key_usage::request_count
+                    + excluded(data_source_key_usage::request_count)),
+                data_source_key_usage::throttled_count.eq(data_source_key_usage::throttled_count
+                    + excluded(data_source_key_usage::throttled_count)),
+                data_source_key_usage::last_throttled_at.eq(sql::<Nullable<Timestamptz>>(
+                    "COALESCE(EXCLUDED.last_throttled_at, data_source_key_usage.last_throttled_at)",
+                )),
+                data_source_key_usage::updated_at.eq(now),
+            ))
+            .returning(DataSourceKeyUsage::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(usage)
+    }
+
+    /// Usage of every API key that sent requests on `usage_date`, by source and key
+    pub async fn for_date(
+        pool: &crate::database::DatabasePool,
+        usage_date: NaiveDate,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let usage = data_source_key_usage::table
+            .filter(data_source_key_usage::usage_date.eq(usage_date))
+            .order((
+                data_source_key_usage::data_source_id,
+                data_source_key_usage::key_hash,
+            ))
+            .select(DataSourceKeyUsage::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(usage)
+    }
+}
+
+/// Daily quotas of a data source together with what has been used today
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DataSourceQuotaStatus {
+    pub data_source_id: Uuid,
+    pub usage_date: NaiveDate,
+    pub daily_byte_quota: Option<i64>,
+    pub daily_request_quota: Option<i32>,
+    pub bytes_downloaded: i64,
+    pub request_count: i32,
+}
+
+impl DataSourceQuotaStatus {
+    /// Combine a data source's quotas with its usage on `usage_date`
+    pub fn new(
+        source: &DataSource,
+        usage_date: NaiveDate,
+        usage: Option<&DataSourceUsage>,
+    ) -> Self {
+        Self {
+            data_source_id: source.id,
+            usage_date,
+            daily_byte_quota: source.daily_byte_quota,
+            daily_request_quota: source.daily_request_quota,
+            bytes_downloaded: usage.map_or(0, |usage| usage.bytes_downloaded),
+            request_count: usage.map_or(0, |usage| usage.request_count),
+        }
+    }
+
+    /// Bytes left today; `None` when the byte quota is unlimited
+    pub fn remaining_bytes(&self) -> Option<i64> {
+        self.daily_byte_quota
+            .map(|quota| (quota - self.bytes_downloaded).max(0))
+    }
+
+    /// Requests left today; `None` when the request quota is unlimited
+    pub fn remaining_requests(&self) -> Option<i32> {
+        self.daily_request_quota
+            .map(|quota| (quota - self.request_count).max(0))
+    }
+
+    /// Whether either quota is used up
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining_bytes() == Some(0) || self.remaining_requests() == Some(0)
+    }
+
+    /// When this usage is reset: midnight UTC after the usage date
+    pub fn resets_at(&self) -> DateTime<Utc> {
+        Self::reset_time(self.usage_date)
+    }
+
+    /// Midnight UTC after `usage_date`, when usage counted on that day expires
+    pub fn reset_time(usage_date: NaiveDate) -> DateTime<Utc> {
+        usage_date
+            .checked_add_days(Days::new(1))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|midnight| midnight.and_utc())
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(
+        daily_byte_quota: Option<i64>,
+        daily_request_quota: Option<i32>,
+        bytes_downloaded: i64,
+        request_count: i32,
+    ) -> DataSourceQuotaStatus {
+        DataSourceQuotaStatus {
+            data_source_id: Uuid::new_v4(),
+            usage_date: NaiveDate::from_ymd_opt(2025, 2, 15).unwrap(),
+            daily_byte_quota,
+            daily_request_quota,
+            bytes_downloaded,
+            request_count,
+        }
+    }
+
+    #[test]
+    fn test_quota_status_remaining_and_exhaustion() {
+        // REQUIREMENT: Crawlers stay within per-source daily byte and request budgets
+        // PURPOSE: Verify remaining quota is clamped at zero and either quota exhausts the source
+        // This ensures a single oversized response stops further requests for the day
+
+        let unlimited = status(None, None, 10_000_000, 5_000);
+        assert_eq!(unlimited.remaining_bytes(), None);
+        assert_eq!(unlimited.remaining_requests(), None);
+        assert!(!unlimited.is_exhausted());
+
+        let partly_used = status(Some(1_000), Some(10), 400, 3);
+        assert_eq!(partly_used.remaining_bytes(), Some(600));
+        assert_eq!(partly_used.remaining_requests(), Some(7));
+        assert!(!partly_used.is_exhausted());
+
+        let over_bytes = status(Some(1_000), Some(10), 1_500, 3);
+        assert_eq!(over_bytes.remaining_bytes(), Some(0));
+        assert!(over_bytes.is_exhausted());
+
+        let out_of_requests = status(None, Some(10), 0, 10);
+        assert!(out_of_requests.is_exhausted());
+    }
+
+    #[test]
+    fn test_quota_resets_at_next_utc_midnight() {
+        // REQUIREMENT: Deferred crawl work resumes when the daily quota resets
+        // PURPOSE: Verify the reset time is midnight UTC after the usage date
+
+        let status = status(Some(1_000), None, 1_000, 1);
+
+        assert_eq!(status.resets_at().to_rfc3339(), "2025-02-16T00:00:00+00:00");
+    }
+}
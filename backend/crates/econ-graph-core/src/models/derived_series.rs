@@ -0,0 +1,717 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::{AppError, AppResult};
+use crate::models::NewEconomicSeries;
+use crate::schema::{data_points, derived_series, derived_series_inputs, economic_series};
+
+/// Longest formula accepted
+pub const MAX_FORMULA_LENGTH: usize = 1000;
+
+/// Most input series one formula may use
+pub const MAX_FORMULA_INPUTS: usize = 10;
+
+/// Deepest nesting of parentheses and unary minus in a formula
+const MAX_FORMULA_DEPTH: usize = 32;
+
+/// Longest variable name, matching `derived_series_inputs.variable`
+const MAX_VARIABLE_LENGTH: usize = 64;
+
+/// Arithmetic operator in a formula
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormulaOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl FormulaOperator {
+    pub fn symbol(&self) -> char {
+        match self {
+            FormulaOperator::Add => '+',
+            FormulaOperator::Subtract => '-',
+            FormulaOperator::Multiply => '*',
+            FormulaOperator::Divide => '/',
+        }
+    }
+
+    fn apply(self, left: f64, right: f64) -> f64 {
+        match self {
+            FormulaOperator::Add => left + right,
+            FormulaOperator::Subtract => left - right,
+            FormulaOperator::Multiply => left * right,
+            FormulaOperator::Divide => left / right,
+        }
+    }
+}
+
+/// Parsed formula over named input series
+///
+/// Stored as JSON in `derived_series.formula_ast`, so recomputing a series
+/// never re-parses its formula.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FormulaExpr {
+    Number {
+        value: f64,
+    },
+    Variable {
+        name: String,
+    },
+    Negate {
+        operand: Box<FormulaExpr>,
+    },
+    Binary {
+        operator: FormulaOperator,
+        left: Box<FormulaExpr>,
+        right: Box<FormulaExpr>,
+    },
+}
+
+impl FormulaExpr {
+    /// Parse a formula such as `nominal / deflator * 100`
+    ///
+    /// Formulas combine numbers and variables with `+ - * /`, unary minus and
+    /// parentheses, with the usual precedence.
+    pub fn parse(formula: &str) -> AppResult<Self> {
+        if formula.len() > MAX_FORMULA_LENGTH {
+            return Err(invalid_formula(format!(
+                "longer than {} characters",
+                MAX_FORMULA_LENGTH
+            )));
+        }
+
+        let mut parser = FormulaParser {
+            tokens: tokenize(formula)?,
+            position: 0,
+            depth: 0,
+        };
+        let expr = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid_formula(format!("unexpected {}", token)));
+        }
+
+        Ok(expr)
+    }
+
+    /// Names of the variables the formula uses
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut variables = BTreeSet::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut BTreeSet<&'a str>) {
+        match self {
+            FormulaExpr::Number { .. } => {}
+            FormulaExpr::Variable { name } => {
+                variables.insert(name.as_str());
+            }
+            FormulaExpr::Negate { operand } => operand.collect_variables(variables),
+            FormulaExpr::Binary { left, right, .. } => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+        }
+    }
+
+    /// Evaluate the formula with one value per variable
+    ///
+    /// Returns `None` when a variable has no value or the result is not
+    /// finite, e.g. after a division by zero.
+    pub fn evaluate(&self, values: &HashMap<&str, f64>) -> Option<f64> {
+        let value = match self {
+            FormulaExpr::Number { value } => *value,
+            FormulaExpr::Variable { name } => *values.get(name.as_str())?,
+            FormulaExpr::Negate { operand } => -operand.evaluate(values)?,
+            FormulaExpr::Binary {
+                operator,
+                left,
+                right,
+            } => operator.apply(left.evaluate(values)?, right.evaluate(values)?),
+        };
+
+        value.is_finite().then_some(value)
+    }
+}
+
+/// Parse a formula and check it against the variables bound to input series
+///
+/// Every variable in the formula must be bound, and every bound variable used.
+pub fn parse_formula(formula: &str, variables: &[&str]) -> AppResult<FormulaExpr> {
+    if variables.is_empty() || variables.len() > MAX_FORMULA_INPUTS {
+        return Err(AppError::ValidationError(format!(
+            "A derived series needs between 1 and {} input series",
+            MAX_FORMULA_INPUTS
+        )));
+    }
+
+    let expr = FormulaExpr::parse(formula)?;
+    let bound: BTreeSet<&str> = variables.iter().copied().collect();
+    if bound.len() != variables.len() {
+        return Err(AppError::ValidationError(
+            "Each input series needs a distinct variable name".to_string(),
+        ));
+    }
+
+    let used = expr.variables();
+    if let Some(unbound) = used.difference(&bound).next() {
+        return Err(AppError::ValidationError(format!(
+            "Formula variable '{}' is not bound to an input series",
+            unbound
+        )));
+    }
+    if let Some(unused) = bound.difference(&used).next() {
+        return Err(AppError::ValidationError(format!(
+            "Input variable '{}' is not used in the formula",
+            unused
+        )));
+    }
+
+    Ok(expr)
+}
+
+fn invalid_formula(reason: impl std::fmt::Display) -> AppError {
+    AppError::ValidationError(format!("Invalid formula: {}", reason))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Operator(FormulaOperator),
+    OpenParen,
+    CloseParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "number {}", value),
+            Token::Identifier(name) => write!(f, "'{}'", name),
+            Token::Operator(operator) => write!(f, "'{}'", operator.symbol()),
+            Token::OpenParen => f.write_str("'('"),
+            Token::CloseParen => f.write_str("')'"),
+        }
+    }
+}
+
+fn tokenize(formula: &str) -> AppResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = formula.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == '.' || c.is_ascii_alphabetic() || c == '_' {
+            let is_number = !(c.is_ascii_alphabetic() || c == '_');
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                let continues = if is_number {
+                    c.is_ascii_digit() || c == '.'
+                } else {
+                    c.is_ascii_alphanumeric() || c == '_'
+                };
+                if !continues {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+
+            let text = &formula[start..end];
+            if is_number {
+                let value = text
+                    .parse::<f64>()
+                    .map_err(|_| invalid_formula(format!("'{}' is not a number", text)))?;
+                tokens.push(Token::Number(value));
+            } else if text.len() > MAX_VARIABLE_LENGTH {
+                return Err(invalid_formula(format!(
+                    "variable names are at most {} characters",
+                    MAX_VARIABLE_LENGTH
+                )));
+            } else {
+                tokens.push(Token::Identifier(text.to_string()));
+            }
+            continue;
+        }
+
+        let token = match c {
+            '+' => Token::Operator(FormulaOperator::Add),
+            '-' => Token::Operator(FormulaOperator::Subtract),
+            '*' => Token::Operator(FormulaOperator::Multiply),
+            '/' => Token::Operator(FormulaOperator::Divide),
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            other => {
+                return Err(invalid_formula(format!(
+                    "unexpected character '{}' at position {}",
+                    other, start
+                )))
+            }
+        };
+        chars.next();
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser: expression := term (('+' | '-') term)*,
+/// term := unary (('*' | '/') unary)*, unary := '-' unary | primary
+struct FormulaParser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl FormulaParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn expression(&mut self) -> AppResult<FormulaExpr> {
+        let mut expr = self.term()?;
+        while let Some(Token::Operator(
+            operator @ (FormulaOperator::Add | FormulaOperator::Subtract),
+        )) = self.peek()
+        {
+            let operator = *operator;
+            self.position += 1;
+            expr = FormulaExpr::Binary {
+                operator,
+                left: Box::new(expr),
+                right: Box::new(self.term()?),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> AppResult<FormulaExpr> {
+        let mut expr = self.unary()?;
+        while let Some(Token::Operator(
+            operator @ (FormulaOperator::Multiply | FormulaOperator::Divide),
+        )) = self.peek()
+        {
+            let operator = *operator;
+            self.position += 1;
+            expr = FormulaExpr::Binary {
+                operator,
+                left: Box::new(expr),
+                right: Box::new(self.unary()?),
+            };
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> AppResult<FormulaExpr> {
+        if self.peek() == Some(&Token::Operator(FormulaOperator::Subtract)) {
+            self.position += 1;
+            let operand = self.nested(Self::unary)?;
+            return Ok(FormulaExpr::Negate {
+                operand: Box::new(operand),
+            });
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> AppResult<FormulaExpr> {
+        match self.advance() {
+            Some(Token::Number(value)) => Ok(FormulaExpr::Number { value }),
+            Some(Token::Identifier(name)) => Ok(FormulaExpr::Variable { name }),
+            Some(Token::OpenParen) => {
+                let expr = self.nested(Self::expression)?;
+                match self.advance() {
+                    Some(Token::CloseParen) => Ok(expr),
+                    _ => Err(invalid_formula("missing closing parenthesis")),
+                }
+            }
+            Some(token) => Err(invalid_formula(format!("unexpected {}", token))),
+            None => Err(invalid_formula("formula ends unexpectedly")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Self) -> AppResult<FormulaExpr>) -> AppResult<FormulaExpr> {
+        if self.depth == MAX_FORMULA_DEPTH {
+            return Err(invalid_formula("nested too deeply"));
+        }
+        self.depth += 1;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+}
+
+/// Formula variable bound to the series it stands for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormulaBinding {
+    pub variable: String,
+    pub series_id: Uuid,
+}
+
+/// A series computed from a formula over other series
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = derived_series)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DerivedSeries {
+    pub id: Uuid,
+    /// Economic series holding the computed values
+    pub series_id: Uuid,
+    pub formula: String,
+    pub formula_ast: serde_json::Value,
+    pub created_by: Option<Uuid>,
+    pub last_computed_at: Option<DateTime<Utc>>,
+    /// Why the last recompute failed, if it did
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New derived series for insertion; its economic series is created with it
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = derived_series)]
+pub struct NewDerivedSeries {
+    pub formula: String,
+    pub formula_ast: serde_json::Value,
+    pub created_by: Option<Uuid>,
+}
+
+/// Input series bound to a formula variable
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = derived_series_inputs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DerivedSeriesInput {
+    pub derived_series_id: Uuid,
+    pub variable: String,
+    pub input_series_id: Uuid,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+fn input_rows(derived_series_id: Uuid, bindings: &[FormulaBinding]) -> Vec<DerivedSeriesInput> {
+    bindings
+        .iter()
+        .map(|binding| DerivedSeriesInput {
+            derived_series_id,
+            variable: binding.variable.clone(),
+            input_series_id: binding.series_id,
+        })
+        .collect()
+}
+
+impl DerivedSeries {
+    /// The stored formula AST
+    pub fn expression(&self) -> AppResult<FormulaExpr> {
+        Ok(serde_json::from_value(self.formula_ast.clone())?)
+    }
+
+    /// Store a derived series with the economic series holding its values and its input bindings
+    ///
+    /// All rows are written in one transaction, so no economic series is left
+    /// without its definition.
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        series: &NewEconomicSeries,
+        new_derived: &NewDerivedSeries,
+        bindings: &[FormulaBinding],
+    ) -> AppResult<(Self, Vec<DerivedSeriesInput>)> {
+        series.validate()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let series = series.clone();
+        let new_derived = new_derived.clone();
+        let bindings = bindings.to_vec();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let series_id = diesel::insert_into(economic_series::table)
+                    .values(&series)
+                    .returning(economic_series::id)
+                    .get_result::<Uuid>(conn)
+                    .await?;
+
+                let derived = diesel::insert_into(derived_series::table)
+                    .values((&new_derived, derived_series::series_id.eq(series_id)))
+                    .returning(DerivedSeries::as_returning())
+                    .get_result::<Self>(conn)
+                    .await?;
+
+                let inputs = diesel::insert_into(derived_series_inputs::table)
+                    .values(input_rows(derived.id, &bindings))
+                    .returning(DerivedSeriesInput::as_returning())
+                    .get_results::<DerivedSeriesInput>(conn)
+                    .await?;
+
+                Ok((derived, inputs))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Replace a derived series' formula and input bindings
+    ///
+    /// Values computed from the old formula are deleted in the same
+    /// transaction; the caller recomputes the series afterwards.
+    pub async fn redefine(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        formula: &str,
+        formula_ast: serde_json::Value,
+        bindings: &[FormulaBinding],
+    ) -> AppResult<(Self, Vec<DerivedSeriesInput>)> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let formula = formula.to_string();
+        let bindings = bindings.to_vec();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let derived =
+                    diesel::update(derived_series::table.filter(derived_series::id.eq(id)))
+                        .set((
+                            derived_series::formula.eq(&formula),
+                            derived_series::formula_ast.eq(&formula_ast),
+                            derived_series::last_computed_at.eq(None::<DateTime<Utc>>),
+                            derived_series::last_error.eq(None::<String>),
+                        ))
+                        .returning(DerivedSeries::as_returning())
+                        .get_result::<Self>(conn)
+                        .await
+                        .optional()?
+                        .ok_or_else(|| {
+                            AppError::NotFound(format!("Derived series {} not found", id))
+                        })?;
+
+                diesel::delete(
+                    derived_series_inputs::table
+                        .filter(derived_series_inputs::derived_series_id.eq(id)),
+                )
+                .execute(conn)
+                .await?;
+                let inputs = diesel::insert_into(derived_series_inputs::table)
+                    .values(input_rows(id, &bindings))
+                    .returning(DerivedSeriesInput::as_returning())
+                    .get_results::<DerivedSeriesInput>(conn)
+                    .await?;
+
+                diesel::delete(
+                    data_points::table.filter(data_points::series_id.eq(derived.series_id)),
+                )
+                .execute(conn)
+                .await?;
+
+                Ok((derived, inputs))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Find a derived series by ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let derived = derived_series::table
+            .filter(derived_series::id.eq(id))
+            .select(DerivedSeries::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(derived)
+    }
+
+    /// Derived series created by a user, newest first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let derived = derived_series::table
+            .filter(derived_series::created_by.eq(user_id))
+            .order(derived_series::created_at.desc())
+            .select(DerivedSeries::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(derived)
+    }
+
+    /// Derived series whose formula uses `input_series_id`
+    pub async fn find_dependents(
+        pool: &crate::database::DatabasePool,
+        input_series_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let derived = derived_series::table
+            .filter(
+                derived_series::id.eq_any(
+                    derived_series_inputs::table
+                        .filter(derived_series_inputs::input_series_id.eq(input_series_id))
+                        .select(derived_series_inputs::derived_series_id),
+                ),
+            )
+            .select(DerivedSeries::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(derived)
+    }
+
+    /// Input bindings of the given derived series
+    pub async fn inputs(
+        pool: &crate::database::DatabasePool,
+        derived_series_ids: &[Uuid],
+    ) -> AppResult<Vec<DerivedSeriesInput>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let inputs = derived_series_inputs::table
+            .filter(derived_series_inputs::derived_series_id.eq_any(derived_series_ids))
+            .order(derived_series_inputs::variable.asc())
+            .select(DerivedSeriesInput::as_select())
+            .load::<DerivedSeriesInput>(&mut conn)
+            .await?;
+
+        Ok(inputs)
+    }
+
+    /// Input series of every derived series, keyed by the series holding its values
+    pub async fn dependency_graph(
+        pool: &crate::database::DatabasePool,
+    ) -> AppResult<HashMap<Uuid, Vec<Uuid>>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let edges = derived_series_inputs::table
+            .inner_join(derived_series::table)
+            .select((
+                derived_series::series_id,
+                derived_series_inputs::input_series_id,
+            ))
+            .load::<(Uuid, Uuid)>(&mut conn)
+            .await?;
+
+        let mut graph: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (series_id, input_series_id) in edges {
+            graph.entry(series_id).or_default().push(input_series_id);
+        }
+
+        Ok(graph)
+    }
+
+    /// Store the outcome of a recompute
+    pub async fn record_computation(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        error: Option<&str>,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::update(derived_series::table.filter(derived_series::id.eq(id)))
+            .set((
+                derived_series::last_computed_at.eq(Utc::now()),
+                derived_series::last_error.eq(error),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete a derived series together with the economic series holding its values
+    pub async fn delete(pool: &crate::database::DatabasePool, id: Uuid) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            economic_series::table.filter(
+                economic_series::id.eq_any(
+                    derived_series::table
+                        .filter(derived_series::id.eq(id))
+                        .select(derived_series::series_id),
+                ),
+            ),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formula_parsing_and_evaluation() {
+        // REQUIREMENT: Users define derived series as formulas over existing series
+        // PURPOSE: Verify precedence, parentheses, unary minus, variable checks and evaluation
+        // This ensures real GDP = nominal / deflator * 100 computes what analysts expect
+
+        let expr = parse_formula("nominal / deflator * 100", &["nominal", "deflator"]).unwrap();
+        let values = HashMap::from([("nominal", 27_000.0), ("deflator", 120.0)]);
+        assert_eq!(expr.evaluate(&values), Some(22_500.0));
+
+        let expr = FormulaExpr::parse("-(a - b) * 2 + c / 4").unwrap();
+        assert_eq!(
+            expr.variables().into_iter().collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        let values = HashMap::from([("a", 1.0), ("b", 4.0), ("c", 8.0)]);
+        assert_eq!(expr.evaluate(&values), Some(8.0));
+
+        let stored: FormulaExpr =
+            serde_json::from_value(serde_json::to_value(&expr).unwrap()).unwrap();
+        assert_eq!(stored, expr);
+
+        let ratio = FormulaExpr::parse("a / b").unwrap();
+        assert_eq!(
+            ratio.evaluate(&HashMap::from([("a", 1.0), ("b", 0.0)])),
+            None
+        );
+        assert_eq!(ratio.evaluate(&HashMap::from([("a", 1.0)])), None);
+
+        for invalid in ["", "a +", "(a * b", "a b", "a % b", "1.2.3", "a)"] {
+            assert!(
+                matches!(
+                    FormulaExpr::parse(invalid),
+                    Err(AppError::ValidationError(_))
+                ),
+                "{:?} should not parse",
+                invalid
+            );
+        }
+        assert!(FormulaExpr::parse(&format!("{}a{}", "(".repeat(40), ")".repeat(40))).is_err());
+
+        assert!(parse_formula("a + b", &["a"]).is_err());
+        assert!(parse_formula("a", &["a", "b"]).is_err());
+        assert!(parse_formula("a + a", &["a", "a"]).is_err());
+    }
+}
@@ -0,0 +1,392 @@
+//! Content-addressed storage of SEC documents
+//!
+//! Filings and taxonomy files are stored once per distinct content in
+//! `document_blobs`, keyed by the lowercase hex SHA-256 of the uncompressed
+//! content. `financial_statements.xbrl_file_blob_hash` and the `blob_hash` of
+//! taxonomy schemas and linkbases point at them. Database triggers count those
+//! references (see the `create_document_blobs` migration); blobs whose count
+//! drops to zero are deleted by [`DocumentBlob::collect_garbage`].
+//!
+//! Hashing, compression and verification live in the SEC crawler's blob store;
+//! this model only moves rows.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::Serialize;
+
+use crate::enums::CompressionType;
+use crate::error::{AppError, AppResult};
+use crate::schema::{
+    document_blobs, financial_statements, xbrl_taxonomy_linkbases, xbrl_taxonomy_schemas,
+};
+
+/// Kind of document a blob holds, told apart by the rows referencing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum DocumentKind {
+    /// XBRL instance of a filing, referenced by `financial_statements`
+    Filing,
+    /// Taxonomy schema or linkbase
+    Taxonomy,
+}
+
+impl DocumentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentKind::Filing => "filing",
+            DocumentKind::Taxonomy => "taxonomy",
+        }
+    }
+}
+
+/// A stored document
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Serialize)]
+#[diesel(table_name = document_blobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DocumentBlob {
+    /// Lowercase hex SHA-256 of the uncompressed content
+    pub content_hash: String,
+    /// Content as stored, compressed with `compression_type`
+    #[serde(skip)]
+    pub content: Vec<u8>,
+    pub size_bytes: i64,
+    pub stored_size_bytes: i64,
+    pub compression_type: CompressionType,
+    /// Zstandard level the content was last encoded at, also recorded when
+    /// compressing did not make it smaller; `None` if compression was not tried
+    pub compression_level: Option<i16>,
+    /// Filings, taxonomy schemas and linkbases referencing the blob
+    pub reference_count: i32,
+    pub created_at: DateTime<Utc>,
+    /// Last time the content was found to match its hash
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+/// A document to store
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = document_blobs)]
+pub struct NewDocumentBlob {
+    pub content_hash: String,
+    pub content: Vec<u8>,
+    pub size_bytes: i64,
+    pub stored_size_bytes: i64,
+    pub compression_type: CompressionType,
+    pub compression_level: Option<i16>,
+}
+
+/// Space used by stored documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DocumentBlobStatistics {
+    pub blob_count: i64,
+    /// References to blobs, i.e. documents before deduplication
+    pub reference_count: i64,
+    /// Uncompressed size of the distinct documents
+    pub size_bytes: i64,
+    pub stored_size_bytes: i64,
+    /// Uncompressed size of every referenced document, counting duplicates
+    pub referenced_size_bytes: i64,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl DocumentBlob {
+    /// Store a document unless a blob with its hash exists
+    ///
+    /// Takes the connection of the transaction that goes on to reference the
+    /// blob: the existing row is locked until then, so garbage collection cannot
+    /// delete it in between.
+    pub async fn put(conn: &mut AsyncPgConnection, blob: &NewDocumentBlob) -> AppResult<()> {
+        diesel::insert_into(document_blobs::table)
+            .values(blob)
+            .on_conflict(document_blobs::content_hash)
+            .do_update()
+            .set(document_blobs::content_hash.eq(excluded(document_blobs::content_hash)))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Blob with the given hash
+    pub async fn find(
+        pool: &crate::database::DatabasePool,
+        content_hash: &str,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let blob = document_blobs::table
+            .find(content_hash)
+            .select(DocumentBlob::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(blob)
+    }
+
+    /// Blobs verified longest ago, never verified ones first
+    pub async fn least_recently_verified(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let blobs = document_blobs::table
+            .order(document_blobs::verified_at.asc().nulls_first())
+            .limit(limit)
+            .select(DocumentBlob::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(blobs)
+    }
+
+    /// Record that a blob's content matched its hash
+    pub async fn mark_verified(
+        pool: &crate::database::DatabasePool,
+        content_hash: &str,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::update(document_blobs::table.find(content_hash))
+            .set(document_blobs::verified_at.eq(Utc::now()))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Blobs of `kind` last encoded at a level other than `compression_level`,
+    /// in hash order
+    ///
+    /// Pages through them with `after`, the hash of the last blob of the previous page.
+    pub async fn encoded_at_other_level(
+        pool: &crate::database::DatabasePool,
+        kind: DocumentKind,
+        compression_level: Option<i16>,
+        after: Option<&str>,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = document_blobs::table
+            .filter(document_blobs::compression_level.is_distinct_from(compression_level))
+            .order(document_blobs::content_hash.asc())
+            .limit(limit)
+            .select(DocumentBlob::as_select())
+            .into_boxed();
+        query = match kind {
+            DocumentKind::Filing => query.filter(document_blobs::content_hash.nullable().eq_any(
+                financial_statements::table.select(financial_statements::xbrl_file_blob_hash),
+            )),
+            DocumentKind::Taxonomy => query.filter(
+                document_blobs::content_hash
+                    .nullable()
+                    .eq_any(xbrl_taxonomy_schemas::table.select(xbrl_taxonomy_schemas::blob_hash))
+                    .or(document_blobs::content_hash.nullable().eq_any(
+                        xbrl_taxonomy_linkbases::table.select(xbrl_taxonomy_linkbases::blob_hash),
+                    )),
+            ),
+        };
+        if let Some(after) = after {
+            query = query.filter(document_blobs::content_hash.gt(after.to_string()));
+        }
+
+        let blobs = query.load::<Self>(&mut conn).await?;
+
+        Ok(blobs)
+    }
+
+    /// Replace a blob's stored content with the same document encoded differently
+    ///
+    /// The compression recorded on the filings and taxonomy files referencing
+    /// the blob is updated in the same transaction.
+    pub async fn replace_content(
+        pool: &crate::database::DatabasePool,
+        blob: &NewDocumentBlob,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let blob = blob.clone();
+        let compressed = blob.compression_type != CompressionType::None;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let updated = diesel::update(document_blobs::table.find(&blob.content_hash))
+                    .set((
+                        document_blobs::content.eq(&blob.content),
+                        document_blobs::stored_size_bytes.eq(blob.stored_size_bytes),
+                        document_blobs::compression_type.eq(blob.compression_type),
+                        document_blobs::compression_level.eq(blob.compression_level),
+                    ))
+                    .execute(conn)
+                    .await?;
+                if updated == 0 {
+                    return Err(AppError::NotFound(format!(
+                        "Document blob {} not found",
+                        blob.content_hash
+                    )));
+                }
+
+                diesel::update(
+                    financial_statements::table
+                        .filter(financial_statements::xbrl_file_blob_hash.eq(&blob.content_hash)),
+                )
+                .set((
+                    financial_statements::xbrl_file_compressed.eq(compressed),
+                    financial_statements::xbrl_file_compression_type.eq(blob.compression_type),
+                ))
+                .execute(conn)
+                .await?;
+
+                diesel::update(
+                    xbrl_taxonomy_schemas::table
+                        .filter(xbrl_taxonomy_schemas::blob_hash.eq(&blob.content_hash)),
+                )
+                .set((
+                    xbrl_taxonomy_schemas::is_compressed.eq(compressed),
+                    xbrl_taxonomy_schemas::compression_type.eq(blob.compression_type),
+                ))
+                .execute(conn)
+                .await?;
+
+                diesel::update(
+                    xbrl_taxonomy_linkbases::table
+                        .filter(xbrl_taxonomy_linkbases::blob_hash.eq(&blob.content_hash)),
+                )
+                .set((
+                    xbrl_taxonomy_linkbases::is_compressed.eq(compressed),
+                    xbrl_taxonomy_linkbases::compression_type.eq(blob.compression_type),
+                ))
+                .execute(conn)
+                .await?;
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Delete blobs nothing references any more; returns how many were deleted
+    pub async fn collect_garbage(pool: &crate::database::DatabasePool) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted =
+            diesel::delete(document_blobs::table.filter(document_blobs::reference_count.eq(0)))
+                .execute(&mut conn)
+                .await?;
+
+        Ok(deleted)
+    }
+
+    /// Space used by the blobs and by the documents referencing them
+    pub async fn statistics(
+        pool: &crate::database::DatabasePool,
+    ) -> AppResult<DocumentBlobStatistics> {
+        use diesel::dsl::{count_star, sql};
+        use diesel::sql_types::BigInt;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let (blob_count, reference_count, size_bytes, stored_size_bytes, referenced_size_bytes) =
+            document_blobs::table
+                .select((
+                    count_star(),
+                    sql::<BigInt>("COALESCE(SUM(reference_count), 0)::bigint"),
+                    sql::<BigInt>("COALESCE(SUM(size_bytes), 0)::bigint"),
+                    sql::<BigInt>("COALESCE(SUM(stored_size_bytes), 0)::bigint"),
+                    sql::<BigInt>("COALESCE(SUM(size_bytes * reference_count), 0)::bigint"),
+                ))
+                .first::<(i64, i64, i64, i64, i64)>(&mut conn)
+                .await?;
+
+        Ok(DocumentBlobStatistics {
+            blob_count,
+            reference_count,
+            size_bytes,
+            stored_size_bytes,
+            referenced_size_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestContainer;
+    use diesel::sql_types::{BigInt, Text};
+
+    async fn add_linkbase(conn: &mut AsyncPgConnection, content_hash: &str) {
+        diesel::sql_query(
+            "INSERT INTO xbrl_taxonomy_linkbases \
+             (linkbase_filename, linkbase_type, file_size_bytes, file_hash, blob_hash) \
+             VALUES ('us-gaap-lab.xml', 'label_linkbase', $1, $2, $2)",
+        )
+        .bind::<BigInt, _>(6_i64)
+        .bind::<Text, _>(content_hash)
+        .execute(conn)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_blobs_are_shared_and_collected_when_unreferenced() {
+        // REQUIREMENT: Identical SEC documents are stored once with reference counting
+        // PURPOSE: Verify storing a document twice keeps one blob, references are counted, and unreferenced blobs are collected
+        // This ensures shared taxonomy files stop being duplicated without deleting content still in use
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let content_hash = format!("{:0>64}", format!("{:x}", uuid::Uuid::new_v4().as_u128()));
+        let blob = NewDocumentBlob {
+            content_hash: content_hash.clone(),
+            content: b"<xml/>".to_vec(),
+            size_bytes: 6,
+            stored_size_bytes: 6,
+            compression_type: CompressionType::None,
+            compression_level: None,
+        };
+
+        let mut conn = pool.get().await.unwrap();
+        DocumentBlob::put(&mut conn, &blob).await.unwrap();
+        add_linkbase(&mut conn, &content_hash).await;
+        DocumentBlob::put(&mut conn, &blob).await.unwrap();
+        add_linkbase(&mut conn, &content_hash).await;
+
+        let stored = DocumentBlob::find(pool, &content_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.reference_count, 2);
+        assert_eq!(stored.content, b"<xml/>");
+        assert_eq!(stored.compression_type, CompressionType::None);
+
+        diesel::delete(
+            crate::schema::xbrl_taxonomy_linkbases::table
+                .filter(crate::schema::xbrl_taxonomy_linkbases::blob_hash.eq(&content_hash)),
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+        drop(conn);
+        assert_eq!(
+            DocumentBlob::find(pool, &content_hash)
+                .await
+                .unwrap()
+                .unwrap()
+                .reference_count,
+            0
+        );
+
+        assert!(DocumentBlob::collect_garbage(pool).await.unwrap() >= 1);
+        assert!(DocumentBlob::find(pool, &content_hash)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
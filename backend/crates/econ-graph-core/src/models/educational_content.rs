@@ -290,6 +290,34 @@ pub enum LearningStatus {
     Abandoned,
 }
 
+impl LearningStatus {
+    /// Name stored in `learning_progress.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LearningStatus::NotStarted => "not_started",
+            LearningStatus::InProgress => "in_progress",
+            LearningStatus::Completed => "completed",
+            LearningStatus::Paused => "paused",
+            LearningStatus::Abandoned => "abandoned",
+        }
+    }
+}
+
+impl std::str::FromStr for LearningStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "not_started" => Ok(LearningStatus::NotStarted),
+            "in_progress" => Ok(LearningStatus::InProgress),
+            "completed" => Ok(LearningStatus::Completed),
+            "paused" => Ok(LearningStatus::Paused),
+            "abandoned" => Ok(LearningStatus::Abandoned),
+            _ => Err(format!("Invalid learning status: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AchievementType {
     ModuleCompletion,
@@ -301,6 +329,38 @@ pub enum AchievementType {
     CommunityContributor,
 }
 
+impl AchievementType {
+    /// Name stored in `learning_achievements.achievement_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AchievementType::ModuleCompletion => "module_completion",
+            AchievementType::PathCompletion => "path_completion",
+            AchievementType::PerfectScore => "perfect_score",
+            AchievementType::SpeedCompletion => "speed_completion",
+            AchievementType::Streak => "streak",
+            AchievementType::ExpertLevel => "expert_level",
+            AchievementType::CommunityContributor => "community_contributor",
+        }
+    }
+}
+
+impl std::str::FromStr for AchievementType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "module_completion" => Ok(AchievementType::ModuleCompletion),
+            "path_completion" => Ok(AchievementType::PathCompletion),
+            "perfect_score" => Ok(AchievementType::PerfectScore),
+            "speed_completion" => Ok(AchievementType::SpeedCompletion),
+            "streak" => Ok(AchievementType::Streak),
+            "expert_level" => Ok(AchievementType::ExpertLevel),
+            "community_contributor" => Ok(AchievementType::CommunityContributor),
+            _ => Err(format!("Invalid achievement type: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum InsightType {
     CaseStudy,
@@ -312,6 +372,116 @@ pub enum InsightType {
     TrendAnalysis,
 }
 
+/// Kinds of library content, used to derive stable IDs
+#[derive(Debug, Clone, Copy)]
+enum ContentKind {
+    Path = 1,
+    Module = 2,
+    Section = 3,
+    Question = 4,
+    Exercise = 5,
+    Insight = 6,
+}
+
+/// Stable ID of a piece of library content
+///
+/// Learners' progress refers to modules, sections and questions by ID, so
+/// the IDs must not change between releases.
+fn content_id(kind: ContentKind, index: u32) -> Uuid {
+    Uuid::from_u128(
+        0x6564_7563_0000_0000_0000_0000_0000_0000 | ((kind as u128) << 32) | index as u128,
+    )
+}
+
+/// Required modules of a learning path as `(module number, minutes)`
+fn path_modules(modules: &[(u32, i32)]) -> Vec<LearningPathModule> {
+    modules
+        .iter()
+        .enumerate()
+        .map(|(position, &(module, minutes))| LearningPathModule {
+            module_id: content_id(ContentKind::Module, module),
+            order_index: position as i32 + 1,
+            is_required: true,
+            estimated_duration_minutes: minutes,
+        })
+        .collect()
+}
+
+/// Outline of a library module; expanded by [`ModuleOutline::build`]
+struct ModuleOutline {
+    number: u32,
+    title: &'static str,
+    description: &'static str,
+    difficulty: LearningDifficulty,
+    category: LearningCategory,
+    minutes: i32,
+    objectives: &'static [&'static str],
+    /// `(title, text)` of each text section, in order
+    sections: &'static [(&'static str, &'static str)],
+    related_ratios: &'static [&'static str],
+    /// `(question, options, index of the correct option, explanation)`
+    question: (&'static str, &'static [&'static str], usize, &'static str),
+}
+
+impl ModuleOutline {
+    fn build(self, interactive_exercises: Vec<InteractiveExercise>) -> EducationalModule {
+        let related_ratios: Vec<String> =
+            self.related_ratios.iter().map(|r| r.to_string()).collect();
+        let (question, options, correct_option, explanation) = self.question;
+        let now = Utc::now();
+
+        EducationalModule {
+            id: content_id(ContentKind::Module, self.number),
+            title: self.title.to_string(),
+            description: self.description.to_string(),
+            difficulty: self.difficulty.clone(),
+            category: self.category,
+            estimated_duration_minutes: self.minutes,
+            prerequisites: if self.number > 1 && self.number != 5 {
+                vec![content_id(ContentKind::Module, self.number - 1)]
+            } else {
+                Vec::new()
+            },
+            learning_objectives: self.objectives.iter().map(|o| o.to_string()).collect(),
+            content_sections: self
+                .sections
+                .iter()
+                .enumerate()
+                .map(|(position, (title, content))| ContentSection {
+                    id: content_id(
+                        ContentKind::Section,
+                        self.number * 100 + position as u32 + 1,
+                    ),
+                    title: title.to_string(),
+                    content_type: ContentType::Text,
+                    content: content.to_string(),
+                    order_index: position as i32 + 1,
+                    interactive_elements: Vec::new(),
+                    related_ratios: related_ratios.clone(),
+                })
+                .collect(),
+            interactive_exercises,
+            assessment_questions: vec![AssessmentQuestion {
+                id: content_id(ContentKind::Question, self.number * 100 + 1),
+                question_type: QuestionType::MultipleChoice,
+                question: question.to_string(),
+                options: options.iter().map(|o| o.to_string()).collect(),
+                correct_answer: serde_json::json!(correct_option),
+                explanation: explanation.to_string(),
+                difficulty: self.difficulty,
+                related_ratios,
+                points: 10,
+            }],
+            resources: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            author: "EconGraph Education Team".to_string(),
+            rating: 0.0,
+            completion_count: 0,
+        }
+    }
+}
+
 /// Educational content library with predefined content
 pub struct EducationalContentLibrary;
 
@@ -319,37 +489,12 @@ impl EducationalContentLibrary {
     /// Get the "Warren Buffett's Investment Philosophy" learning path
     pub fn get_warren_buffett_path() -> LearningPath {
         LearningPath {
-            id: Uuid::new_v4(),
+            id: content_id(ContentKind::Path, 1),
             title: "Warren Buffett's Investment Philosophy".to_string(),
             description: "Master the investment principles and financial analysis techniques used by the Oracle of Omaha".to_string(),
             target_audience: TargetAudience::IndividualInvestor,
             estimated_duration_hours: 12,
-            modules: vec![
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 1,
-                    is_required: true,
-                    estimated_duration_minutes: 60,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 2,
-                    is_required: true,
-                    estimated_duration_minutes: 90,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 3,
-                    is_required: true,
-                    estimated_duration_minutes: 120,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 4,
-                    is_required: true,
-                    estimated_duration_minutes: 90,
-                },
-            ],
+            modules: path_modules(&[(1, 60), (2, 90), (3, 120), (4, 90)]),
             prerequisites: vec![
                 "Basic understanding of financial statements".to_string(),
                 "Familiarity with basic accounting concepts".to_string(),
@@ -371,43 +516,12 @@ impl EducationalContentLibrary {
     /// Get the "Modern Valuation Techniques" learning path
     pub fn get_modern_valuation_path() -> LearningPath {
         LearningPath {
-            id: Uuid::new_v4(),
+            id: content_id(ContentKind::Path, 2),
             title: "Modern Valuation Techniques for Forward-Thinking Analysts".to_string(),
             description: "Learn the advanced valuation methods preferred by professional analysts, including Enterprise Value metrics and DCF modeling".to_string(),
             target_audience: TargetAudience::ProfessionalAnalyst,
             estimated_duration_hours: 16,
-            modules: vec![
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 1,
-                    is_required: true,
-                    estimated_duration_minutes: 90,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 2,
-                    is_required: true,
-                    estimated_duration_minutes: 120,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 3,
-                    is_required: true,
-                    estimated_duration_minutes: 150,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 4,
-                    is_required: true,
-                    estimated_duration_minutes: 120,
-                },
-                LearningPathModule {
-                    module_id: Uuid::new_v4(),
-                    order_index: 5,
-                    is_required: true,
-                    estimated_duration_minutes: 180,
-                },
-            ],
+            modules: path_modules(&[(5, 90), (6, 120), (7, 150), (8, 120), (9, 180)]),
             prerequisites: vec![
                 "Advanced understanding of financial statements".to_string(),
                 "Knowledge of basic valuation concepts".to_string(),
@@ -432,7 +546,7 @@ impl EducationalContentLibrary {
     pub fn get_enterprise_value_insights() -> Vec<ExpertInsight> {
         vec![
             ExpertInsight {
-                id: Uuid::new_v4(),
+                id: content_id(ContentKind::Insight, 1),
                 title: "Why Professional Analysts Prefer EV/EBITDA Over P/E Ratios".to_string(),
                 content: "Enterprise Value to EBITDA has become the gold standard for valuation in professional finance because it eliminates the distortions caused by different capital structures, accounting methods, and tax rates. Unlike P/E ratios, EV/EBITDA provides a true apples-to-apples comparison between companies.".to_string(),
                 insight_type: InsightType::ExpertOpinion,
@@ -459,7 +573,7 @@ impl EducationalContentLibrary {
                 view_count: 2340,
             },
             ExpertInsight {
-                id: Uuid::new_v4(),
+                id: content_id(ContentKind::Insight, 2),
                 title: "Warren Buffett's Free Cash Flow Obsession".to_string(),
                 content: "Warren Buffett has repeatedly emphasized that free cash flow is the ultimate measure of business value. Unlike earnings, which can be manipulated through accounting tricks, free cash flow represents the actual cash a company generates that can be returned to shareholders or reinvested for growth.".to_string(),
                 insight_type: InsightType::CaseStudy,
@@ -492,7 +606,7 @@ impl EducationalContentLibrary {
     pub fn get_ratio_calculation_exercises() -> Vec<InteractiveExercise> {
         vec![
             InteractiveExercise {
-                id: Uuid::new_v4(),
+                id: content_id(ContentKind::Exercise, 1),
                 title: "Calculate Apple's EV/EBITDA".to_string(),
                 description: "Using Apple's financial data, calculate the Enterprise Value to EBITDA ratio and compare it to industry benchmarks".to_string(),
                 exercise_type: ExerciseType::RatioCalculation,
@@ -536,6 +650,251 @@ impl EducationalContentLibrary {
             },
         ]
     }
+
+    /// All learning paths in the library
+    pub fn learning_paths() -> Vec<LearningPath> {
+        vec![
+            Self::get_warren_buffett_path(),
+            Self::get_modern_valuation_path(),
+        ]
+    }
+
+    /// Learning path with the given ID
+    pub fn find_learning_path(id: Uuid) -> Option<LearningPath> {
+        Self::learning_paths()
+            .into_iter()
+            .find(|path| path.id == id)
+    }
+
+    /// Module with the given ID
+    pub fn find_module(id: Uuid) -> Option<EducationalModule> {
+        Self::modules().into_iter().find(|module| module.id == id)
+    }
+
+    /// All modules in the library, in learning path order
+    pub fn modules() -> Vec<EducationalModule> {
+        let outlines = [
+            ModuleOutline {
+                number: 1,
+                title: "Reading Financial Statements Like an Owner",
+                description: "How the income statement, balance sheet and cash flow statement fit together, read from the point of view of a business owner",
+                difficulty: LearningDifficulty::Beginner,
+                category: LearningCategory::FinancialStatements,
+                minutes: 60,
+                objectives: &[
+                    "Explain what each of the three financial statements measures",
+                    "Trace how net income flows into retained earnings and cash",
+                ],
+                sections: &[
+                    ("Three statements, one business", "The income statement reports what a business earned over a period, the balance sheet what it owns and owes at a point in time, and the cash flow statement how cash actually moved. An owner reads all three together: earnings that never turn into cash deserve suspicion."),
+                    ("Following the money", "Net income increases retained earnings on the balance sheet. The cash flow statement starts from the same net income and adds back non-cash charges such as depreciation, then adjusts for changes in working capital to arrive at operating cash flow."),
+                ],
+                related_ratios: &["return_on_equity", "net_profit_margin"],
+                question: (
+                    "Which statement shows a company's assets and liabilities at a point in time?",
+                    &["Income statement", "Balance sheet", "Cash flow statement", "Statement of shareholders' equity"],
+                    1,
+                    "The balance sheet is a snapshot at the reporting date; the other statements cover a period.",
+                ),
+            },
+            ModuleOutline {
+                number: 2,
+                title: "Free Cash Flow and Owner Earnings",
+                description: "Measure the cash a business can hand to its owners after keeping its competitive position",
+                difficulty: LearningDifficulty::Intermediate,
+                category: LearningCategory::FreeCashFlow,
+                minutes: 90,
+                objectives: &[
+                    "Calculate free cash flow from the cash flow statement",
+                    "Distinguish maintenance from growth capital expenditure",
+                ],
+                sections: &[
+                    ("Free cash flow", "Free cash flow is operating cash flow minus capital expenditure. It is the cash left for dividends, buybacks, debt repayment or acquisitions, and it is much harder to flatter with accounting choices than earnings."),
+                    ("Owner earnings", "Buffett's owner earnings add depreciation and other non-cash charges to reported earnings and subtract only the capital spending needed to maintain the business. Spending that grows the business is an investment decision, not a cost of standing still."),
+                ],
+                related_ratios: &["free_cash_flow", "free_cash_flow_yield"],
+                question: (
+                    "Free cash flow is operating cash flow minus which item?",
+                    &["Dividends paid", "Capital expenditure", "Interest expense", "Depreciation"],
+                    1,
+                    "Capital expenditure is the cash reinvested in long-lived assets; what remains is free for the owners.",
+                ),
+            },
+            ModuleOutline {
+                number: 3,
+                title: "Economic Moats and Return on Capital",
+                description: "Recognise durable competitive advantages through sustained high returns on invested capital",
+                difficulty: LearningDifficulty::Intermediate,
+                category: LearningCategory::InvestmentPhilosophy,
+                minutes: 120,
+                objectives: &[
+                    "Name common sources of durable competitive advantage",
+                    "Use return on invested capital to test whether a moat exists",
+                ],
+                sections: &[
+                    ("Sources of moats", "Brands, network effects, switching costs, cost advantages and regulatory licences let a company earn more than its cost of capital for years without competitors eroding the excess."),
+                    ("Returns as evidence", "A moat shows up in the numbers as return on invested capital that stays well above the cost of capital through a full business cycle. A single strong year proves little; consistency is the signal."),
+                ],
+                related_ratios: &["return_on_invested_capital", "return_on_equity"],
+                question: (
+                    "Which pattern best indicates a durable competitive advantage?",
+                    &["Rapid revenue growth in one year", "Return on invested capital persistently above the cost of capital", "A rising share price", "High dividend payout ratio"],
+                    1,
+                    "Competition pushes excess returns down; returns that stay high over many years point to a moat.",
+                ),
+            },
+            ModuleOutline {
+                number: 4,
+                title: "Margin of Safety and Intrinsic Value",
+                description: "Estimate what a business is worth and why to buy only well below that estimate",
+                difficulty: LearningDifficulty::Advanced,
+                category: LearningCategory::WarrenBuffett,
+                minutes: 90,
+                objectives: &[
+                    "Estimate intrinsic value from discounted owner earnings",
+                    "Size a margin of safety for the uncertainty of the estimate",
+                ],
+                sections: &[
+                    ("Intrinsic value", "Intrinsic value is the discounted value of the cash a business will generate over its remaining life. It is an estimate, best expressed as a range rather than a single number."),
+                    ("Margin of safety", "Buying at a large discount to estimated intrinsic value protects against errors in the estimate and bad luck. The less predictable the business, the wider the margin should be."),
+                ],
+                related_ratios: &["free_cash_flow_yield", "price_to_earnings"],
+                question: (
+                    "What is the purpose of a margin of safety?",
+                    &["To guarantee a profit", "To protect against errors in the value estimate", "To time the market", "To increase portfolio turnover"],
+                    1,
+                    "Intrinsic value can only be estimated; buying well below the estimate leaves room for being wrong.",
+                ),
+            },
+            ModuleOutline {
+                number: 5,
+                title: "Enterprise Value Fundamentals",
+                description: "Compute enterprise value and understand why it measures the price of the whole business",
+                difficulty: LearningDifficulty::Intermediate,
+                category: LearningCategory::EnterpriseValue,
+                minutes: 90,
+                objectives: &[
+                    "Calculate enterprise value from market capitalisation, debt and cash",
+                    "Explain why enterprise value is independent of capital structure",
+                ],
+                sections: &[
+                    ("What enterprise value measures", "Enterprise value is market capitalisation plus debt, preferred equity and minority interests, minus cash. It approximates what an acquirer would pay for the entire operating business."),
+                    ("Capital structure neutrality", "Two companies with identical operations but different mixes of debt and equity have different market capitalisations yet similar enterprise values. That makes enterprise value the right numerator for comparing operating performance."),
+                ],
+                related_ratios: &["enterprise_value", "enterprise_value_to_ebitda"],
+                question: (
+                    "How is enterprise value calculated?",
+                    &["Market cap minus debt plus cash", "Market cap plus debt minus cash", "Revenue times the P/E ratio", "Book equity plus debt"],
+                    1,
+                    "Debt is added because an acquirer assumes it; cash is subtracted because it comes with the business.",
+                ),
+            },
+            ModuleOutline {
+                number: 6,
+                title: "EV Multiples versus P/E",
+                description: "Compare companies with EV/EBITDA and EV/Sales and see where P/E misleads",
+                difficulty: LearningDifficulty::Intermediate,
+                category: LearningCategory::Valuation,
+                minutes: 120,
+                objectives: &[
+                    "Choose a matching numerator and denominator for valuation multiples",
+                    "Identify situations where P/E ratios distort comparisons",
+                ],
+                sections: &[
+                    ("Matching numerators and denominators", "Enterprise value belongs to all capital providers, so it pairs with measures before interest such as EBITDA, EBIT or sales. Equity value pairs with measures after interest such as net income."),
+                    ("Where P/E misleads", "P/E ratios move with leverage, tax rates and one-off items. Comparing a debt-free company with a heavily indebted peer on P/E mixes business quality with financing choices."),
+                ],
+                related_ratios: &["enterprise_value_to_ebitda", "enterprise_value_to_sales", "price_to_earnings"],
+                question: (
+                    "Why is EV/EBITDA often preferred over P/E when comparing companies?",
+                    &["It is always lower", "It is not affected by differences in capital structure", "It ignores debt", "It uses net income"],
+                    1,
+                    "EBITDA is earned before interest and EV includes debt, so leverage differences largely cancel out.",
+                ),
+            },
+            ModuleOutline {
+                number: 7,
+                title: "Building a Discounted Cash Flow Model",
+                description: "Project free cash flow, choose a discount rate and compute a terminal value",
+                difficulty: LearningDifficulty::Advanced,
+                category: LearningCategory::Valuation,
+                minutes: 150,
+                objectives: &[
+                    "Project unlevered free cash flow over an explicit forecast period",
+                    "Discount cash flows at the weighted average cost of capital",
+                ],
+                sections: &[
+                    ("Forecasting cash flow", "A DCF starts with unlevered free cash flow: operating profit after tax plus non-cash charges, minus capital expenditure and investment in working capital, projected year by year."),
+                    ("Discounting and terminal value", "Cash flows are discounted at the weighted average cost of capital. A terminal value, usually a perpetual growth or exit multiple estimate, captures the years beyond the forecast and often dominates the result."),
+                ],
+                related_ratios: &["free_cash_flow", "weighted_average_cost_of_capital"],
+                question: (
+                    "Which rate discounts unlevered free cash flow in a DCF?",
+                    &["The risk-free rate", "The weighted average cost of capital", "The dividend yield", "The inflation rate"],
+                    1,
+                    "Unlevered cash flow belongs to both debt and equity holders, so it is discounted at their blended required return.",
+                ),
+            },
+            ModuleOutline {
+                number: 8,
+                title: "Scenario and Sensitivity Analysis",
+                description: "Stress a valuation against the assumptions that drive it",
+                difficulty: LearningDifficulty::Advanced,
+                category: LearningCategory::ModernAnalytics,
+                minutes: 120,
+                objectives: &[
+                    "Build base, bull and bear scenarios from consistent assumptions",
+                    "Read a sensitivity table of discount rate against growth",
+                ],
+                sections: &[
+                    ("Scenarios", "A scenario changes several related assumptions together, for example slower growth with lower margins. Valuing each scenario shows the range of outcomes rather than false precision."),
+                    ("Sensitivity tables", "A sensitivity table varies one or two inputs, typically the discount rate and terminal growth, and shows how the value responds. Inputs that move the answer most deserve the most research."),
+                ],
+                related_ratios: &["weighted_average_cost_of_capital"],
+                question: (
+                    "What does a sensitivity table show?",
+                    &["Historical share prices", "How the valuation changes as key inputs vary", "The company's credit rating", "Analyst consensus estimates"],
+                    1,
+                    "It maps input assumptions to resulting values, revealing which assumptions matter most.",
+                ),
+            },
+            ModuleOutline {
+                number: 9,
+                title: "Comparable Company Analysis",
+                description: "Value a company against a well-chosen peer group",
+                difficulty: LearningDifficulty::Advanced,
+                category: LearningCategory::RatioAnalysis,
+                minutes: 180,
+                objectives: &[
+                    "Select a peer group with similar business models and growth",
+                    "Apply median peer multiples and explain premiums or discounts",
+                ],
+                sections: &[
+                    ("Choosing peers", "Good comparables share business model, end markets, size and growth profile. A smaller, closely matched group beats a large, loosely related one."),
+                    ("Applying multiples", "The median multiple of the peer group, applied to the target's metric, gives a reference value. Differences in growth, margins and risk justify trading above or below that reference."),
+                ],
+                related_ratios: &["enterprise_value_to_ebitda", "enterprise_value_to_sales"],
+                question: (
+                    "Which statistic is usually applied from a peer group's multiples?",
+                    &["The maximum", "The median", "The minimum", "The sum"],
+                    1,
+                    "The median is robust to outliers that often distort the mean in small peer groups.",
+                ),
+            },
+        ];
+
+        outlines
+            .into_iter()
+            .map(|outline| {
+                let exercises = if outline.number == 5 {
+                    Self::get_ratio_calculation_exercises()
+                } else {
+                    Vec::new()
+                };
+                outline.build(exercises)
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -579,6 +938,52 @@ mod tests {
         assert!(insights.iter().any(|i| i.title.contains("Free Cash Flow")));
     }
 
+    #[test]
+    fn test_learning_path_modules_exist_in_library() {
+        // REQUIREMENT: Learners can open every module listed in a learning path
+        // PURPOSE: Verify path module IDs resolve to library modules and IDs are stable
+        // This ensures recorded progress keeps pointing at the same content across releases
+
+        for path in EducationalContentLibrary::learning_paths() {
+            for path_module in &path.modules {
+                assert!(
+                    EducationalContentLibrary::find_module(path_module.module_id).is_some(),
+                    "Module {} of '{}' is missing",
+                    path_module.module_id,
+                    path.title
+                );
+            }
+        }
+
+        let first = EducationalContentLibrary::modules();
+        let second = EducationalContentLibrary::modules();
+        assert_eq!(
+            first.iter().map(|m| m.id).collect::<Vec<_>>(),
+            second.iter().map(|m| m.id).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            EducationalContentLibrary::find_learning_path(
+                EducationalContentLibrary::get_warren_buffett_path().id
+            )
+            .map(|path| path.title),
+            Some("Warren Buffett's Investment Philosophy".to_string())
+        );
+    }
+
+    #[test]
+    fn test_learning_status_round_trips_through_strings() {
+        for status in [
+            LearningStatus::NotStarted,
+            LearningStatus::InProgress,
+            LearningStatus::Completed,
+            LearningStatus::Paused,
+            LearningStatus::Abandoned,
+        ] {
+            assert_eq!(status.as_str().parse::<LearningStatus>(), Ok(status));
+        }
+        assert!("finished".parse::<LearningStatus>().is_err());
+    }
+
     #[test]
     fn test_interactive_exercises_creation() {
         let exercises = EducationalContentLibrary::get_ratio_calculation_exercises();
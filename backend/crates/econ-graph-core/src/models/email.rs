@@ -0,0 +1,341 @@
+//! Queued emails and the email preferences of users
+//!
+//! Emails are rendered when the event happens and stored in
+//! `email_deliveries`, so they survive restarts; a worker claims due
+//! deliveries and records each attempt. Which kinds of email a user receives
+//! is kept in `email_preferences`, with defaults for users who never changed
+//! them.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Timestamptz};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{email_deliveries, email_preferences};
+
+/// Kind of email, each governed by its own preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EmailTemplate {
+    /// Someone shared a chart with the recipient
+    Invite,
+    /// One of the recipient's series alert rules fired
+    AlertTriggered,
+    /// Weekly summary of the recipient's notifications
+    WeeklyDigest,
+}
+
+impl EmailTemplate {
+    /// Value stored in `email_deliveries.template`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailTemplate::Invite => "invite",
+            EmailTemplate::AlertTriggered => "alert_triggered",
+            EmailTemplate::WeeklyDigest => "weekly_digest",
+        }
+    }
+}
+
+impl std::fmt::Display for EmailTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a queued email stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmailDeliveryStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// The transport accepted the message
+    Sent,
+    /// Every attempt failed; no more retries
+    Failed,
+}
+
+impl EmailDeliveryStatus {
+    /// Value stored in `email_deliveries.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailDeliveryStatus::Pending => "pending",
+            EmailDeliveryStatus::Sent => "sent",
+            EmailDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Which emails a user receives
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = email_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct EmailPreferences {
+    pub user_id: Uuid,
+    /// Charts shared with the user
+    pub invites: bool,
+    /// The user's series alert rules firing
+    pub alerts: bool,
+    /// Weekly summary of notifications
+    pub weekly_digest: bool,
+    /// When the last weekly digest was queued
+    pub last_digest_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Preferences to change; `None` keeps the current value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailPreferencesUpdate {
+    pub invites: Option<bool>,
+    pub alerts: Option<bool>,
+    pub weekly_digest: Option<bool>,
+}
+
+/// One queued or sent email
+#[derive(
+    Debug, Clone, Queryable, QueryableByName, Selectable, Identifiable, Serialize, Deserialize,
+)]
+#[diesel(table_name = email_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct EmailDelivery {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub recipient: String,
+    pub template: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New email for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = email_deliveries)]
+pub struct NewEmailDelivery {
+    pub user_id: Option<Uuid>,
+    pub recipient: String,
+    pub template: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+}
+
+/// Result of one attempt at sending an email
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailSendAttempt {
+    /// Why the attempt failed; `None` when it succeeded
+    pub error: Option<String>,
+    /// When to try again after a failure; `None` gives up
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl EmailPreferences {
+    /// Preferences of a user who never changed them
+    pub fn defaults(user_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            invites: true,
+            alerts: true,
+            weekly_digest: false,
+            last_digest_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the user wants emails of this kind
+    pub fn allows(&self, template: EmailTemplate) -> bool {
+        match template {
+            EmailTemplate::Invite => self.invites,
+            EmailTemplate::AlertTriggered => self.alerts,
+            EmailTemplate::WeeklyDigest => self.weekly_digest,
+        }
+    }
+
+    /// A user's preferences, or the defaults if they never changed them
+    pub async fn for_user(pool: &crate::database::DatabasePool, user_id: Uuid) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let preferences = email_preferences::table
+            .filter(email_preferences::user_id.eq(user_id))
+            .select(EmailPreferences::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(preferences.unwrap_or_else(|| Self::defaults(user_id)))
+    }
+
+    /// Change some of a user's preferences
+    pub async fn update(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        update: &EmailPreferencesUpdate,
+    ) -> AppResult<Self> {
+        let mut preferences = Self::for_user(pool, user_id).await?;
+        preferences.invites = update.invites.unwrap_or(preferences.invites);
+        preferences.alerts = update.alerts.unwrap_or(preferences.alerts);
+        preferences.weekly_digest = update.weekly_digest.unwrap_or(preferences.weekly_digest);
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let preferences = diesel::insert_into(email_preferences::table)
+            .values(&preferences)
+            .on_conflict(email_preferences::user_id)
+            .do_update()
+            .set((
+                email_preferences::invites.eq(preferences.invites),
+                email_preferences::alerts.eq(preferences.alerts),
+                email_preferences::weekly_digest.eq(preferences.weekly_digest),
+            ))
+            .returning(EmailPreferences::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(preferences)
+    }
+
+    /// Claim the users whose weekly digest is due
+    ///
+    /// Marks the digest as sent for each returned user, so that other backend
+    /// instances and later runs skip them until `period` has passed again.
+    pub async fn claim_due_digests(
+        pool: &crate::database::DatabasePool,
+        period: chrono::Duration,
+    ) -> AppResult<Vec<Uuid>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let now = Utc::now();
+
+        let user_ids = diesel::update(
+            email_preferences::table
+                .filter(email_preferences::weekly_digest.eq(true))
+                .filter(
+                    email_preferences::last_digest_at
+                        .is_null()
+                        .or(email_preferences::last_digest_at.le(now - period)),
+                ),
+        )
+        .set(email_preferences::last_digest_at.eq(now))
+        .returning(email_preferences::user_id)
+        .get_results::<Uuid>(&mut conn)
+        .await?;
+
+        Ok(user_ids)
+    }
+}
+
+impl EmailDelivery {
+    /// Queue emails for their first attempt
+    pub async fn enqueue(
+        pool: &crate::database::DatabasePool,
+        emails: &[NewEmailDelivery],
+    ) -> AppResult<usize> {
+        if emails.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let queued = diesel::insert_into(email_deliveries::table)
+            .values(emails)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(queued)
+    }
+
+    /// Claim up to `limit` pending emails that are due
+    ///
+    /// Claimed emails are pushed back by `lease` so that other backend
+    /// instances skip them while this one sends them.
+    pub async fn claim_due(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+        lease: chrono::Duration,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let emails = diesel::sql_query(
+            "UPDATE email_deliveries SET next_attempt_at = $1
+             WHERE id IN (
+                 SELECT id FROM email_deliveries
+                 WHERE status = 'pending' AND next_attempt_at <= NOW()
+                 ORDER BY next_attempt_at
+                 LIMIT $2
+                 FOR UPDATE SKIP LOCKED)
+             RETURNING *",
+        )
+        .bind::<Timestamptz, _>(Utc::now() + lease)
+        .bind::<BigInt, _>(limit)
+        .load::<Self>(&mut conn)
+        .await?;
+
+        Ok(emails)
+    }
+
+    /// Store the outcome of an attempt
+    pub async fn record_attempt(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        attempt: &EmailSendAttempt,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let now = Utc::now();
+
+        let target = email_deliveries::table.filter(email_deliveries::id.eq(id));
+        let status = match (&attempt.error, attempt.retry_at) {
+            (None, _) => EmailDeliveryStatus::Sent,
+            (Some(_), Some(_)) => EmailDeliveryStatus::Pending,
+            (Some(_), None) => EmailDeliveryStatus::Failed,
+        };
+
+        diesel::update(target)
+            .set((
+                email_deliveries::status.eq(status.as_str()),
+                email_deliveries::attempts.eq(email_deliveries::attempts + 1),
+                email_deliveries::last_attempt_at.eq(now),
+                email_deliveries::last_error.eq(&attempt.error),
+                email_deliveries::next_attempt_at.eq(attempt.retry_at.unwrap_or(now)),
+                email_deliveries::sent_at.eq((status == EmailDeliveryStatus::Sent).then_some(now)),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferences_govern_each_template() {
+        // REQUIREMENT: Per-user notification preferences are enforced for email
+        // PURPOSE: Verify each template is governed by its own preference and the defaults
+        // This ensures users get invites and alerts unless they opt out, and digests only if they opt in
+
+        let mut preferences = EmailPreferences::defaults(Uuid::from_u128(1));
+        assert!(preferences.allows(EmailTemplate::Invite));
+        assert!(preferences.allows(EmailTemplate::AlertTriggered));
+        assert!(!preferences.allows(EmailTemplate::WeeklyDigest));
+
+        preferences.alerts = false;
+        preferences.weekly_digest = true;
+        assert!(!preferences.allows(EmailTemplate::AlertTriggered));
+        assert!(preferences.allows(EmailTemplate::WeeklyDigest));
+        assert_eq!(EmailTemplate::AlertTriggered.as_str(), "alert_triggered");
+    }
+}
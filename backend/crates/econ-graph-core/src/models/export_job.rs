@@ -0,0 +1,454 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{Int4, Timestamptz};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::export_jobs;
+
+/// Most series in one export
+pub const MAX_EXPORT_SERIES: usize = 100;
+
+/// Attempts before a job that keeps failing or timing out is given up
+pub const MAX_EXPORT_ATTEMPTS: i32 = 3;
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// Value stored in `export_jobs.format`, also used as the file extension
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+
+    /// MIME type of the file
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" => Ok(ExportFormat::Parquet),
+            "xlsx" => Ok(ExportFormat::Xlsx),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown export format '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where an export job stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportJobStatus {
+    /// Waiting for a worker
+    Pending,
+    /// A worker is writing the file
+    Running,
+    /// The file is ready to download
+    Completed,
+    /// The export could not be produced
+    Failed,
+    /// The file was deleted after its retention period
+    Expired,
+}
+
+impl ExportJobStatus {
+    /// Value stored in `export_jobs.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportJobStatus::Pending => "pending",
+            ExportJobStatus::Running => "running",
+            ExportJobStatus::Completed => "completed",
+            ExportJobStatus::Failed => "failed",
+            ExportJobStatus::Expired => "expired",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportJobStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ExportJobStatus::Pending),
+            "running" => Ok(ExportJobStatus::Running),
+            "completed" => Ok(ExportJobStatus::Completed),
+            "failed" => Ok(ExportJobStatus::Failed),
+            "expired" => Ok(ExportJobStatus::Expired),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown export job status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// A user's request for a bulk export, and the file it produced
+#[derive(
+    Debug, Clone, Queryable, QueryableByName, Selectable, Identifiable, Serialize, Deserialize,
+)]
+#[diesel(table_name = export_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExportJob {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub series_ids: Vec<Uuid>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub format: String,
+    pub status: String,
+    pub attempts: i32,
+    /// Location of the file in export storage, once written
+    pub storage_key: Option<String>,
+    pub file_size_bytes: Option<i64>,
+    pub row_count: Option<i64>,
+    pub error_message: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the file is deleted
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New export job for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = export_jobs)]
+pub struct NewExportJob {
+    pub user_id: Uuid,
+    pub series_ids: Vec<Uuid>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub format: String,
+}
+
+impl NewExportJob {
+    /// Check the series list and date range
+    pub fn validate_request(&self) -> AppResult<()> {
+        if self.series_ids.is_empty() {
+            return Err(AppError::ValidationError(
+                "An export needs at least one series".to_string(),
+            ));
+        }
+        if self.series_ids.len() > MAX_EXPORT_SERIES {
+            return Err(AppError::ValidationError(format!(
+                "An export can include at most {} series",
+                MAX_EXPORT_SERIES
+            )));
+        }
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if start > end {
+                return Err(AppError::ValidationError(
+                    "Export start date must not be after its end date".to_string(),
+                ));
+            }
+        }
+        self.format.parse::<ExportFormat>()?;
+
+        Ok(())
+    }
+}
+
+/// The file a worker wrote for a job
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportArtifact {
+    pub storage_key: String,
+    pub file_size_bytes: i64,
+    pub row_count: i64,
+    pub expires_at: DateTime<Utc>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl ExportJob {
+    /// Queue an export
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_job: &NewExportJob,
+    ) -> AppResult<Self> {
+        new_job.validate_request()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let job = diesel::insert_into(export_jobs::table)
+            .values(new_job)
+            .returning(ExportJob::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(job)
+    }
+
+    /// Find an export job by ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let job = export_jobs::table
+            .filter(export_jobs::id.eq(id))
+            .select(ExportJob::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(job)
+    }
+
+    /// A user's most recent export jobs, newest first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let jobs = export_jobs::table
+            .filter(export_jobs::user_id.eq(user_id))
+            .order(export_jobs::created_at.desc())
+            .limit(limit)
+            .select(ExportJob::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(jobs)
+    }
+
+    /// Number of a user's jobs that are pending or running
+    pub async fn count_unfinished(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<i64> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let count = export_jobs::table
+            .filter(export_jobs::user_id.eq(user_id))
+            .filter(export_jobs::status.eq_any([
+                ExportJobStatus::Pending.as_str(),
+                ExportJobStatus::Running.as_str(),
+            ]))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Claim the oldest pending job for this worker
+    ///
+    /// A running job whose worker has not finished within `lease` is claimed
+    /// again, so a job survives a worker crash or restart, up to
+    /// [`MAX_EXPORT_ATTEMPTS`] attempts.
+    pub async fn claim_next(
+        pool: &crate::database::DatabasePool,
+        lease: chrono::Duration,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let job = diesel::sql_query(
+            "UPDATE export_jobs
+             SET status = 'running', started_at = NOW(), attempts = attempts + 1
+             WHERE id = (
+                 SELECT id FROM export_jobs
+                 WHERE status = 'pending'
+                    OR (status = 'running' AND started_at < $1 AND attempts < $2)
+                 ORDER BY created_at
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED)
+             RETURNING *",
+        )
+        .bind::<Timestamptz, _>(Utc::now() - lease)
+        .bind::<Int4, _>(MAX_EXPORT_ATTEMPTS)
+        .get_result::<Self>(&mut conn)
+        .await
+        .optional()?;
+
+        Ok(job)
+    }
+
+    /// Fail running jobs that timed out on their last attempt
+    pub async fn fail_abandoned(
+        pool: &crate::database::DatabasePool,
+        lease: chrono::Duration,
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let failed = diesel::update(
+            export_jobs::table
+                .filter(export_jobs::status.eq(ExportJobStatus::Running.as_str()))
+                .filter(export_jobs::started_at.lt(Utc::now() - lease))
+                .filter(export_jobs::attempts.ge(MAX_EXPORT_ATTEMPTS)),
+        )
+        .set((
+            export_jobs::status.eq(ExportJobStatus::Failed.as_str()),
+            export_jobs::error_message.eq("Export timed out"),
+            export_jobs::completed_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        Ok(failed)
+    }
+
+    /// Record the file written for a job
+    pub async fn mark_completed(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        artifact: &ExportArtifact,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let job = diesel::update(export_jobs::table.filter(export_jobs::id.eq(id)))
+            .set((
+                export_jobs::status.eq(ExportJobStatus::Completed.as_str()),
+                export_jobs::storage_key.eq(&artifact.storage_key),
+                export_jobs::file_size_bytes.eq(artifact.file_size_bytes),
+                export_jobs::row_count.eq(artifact.row_count),
+                export_jobs::error_message.eq(None::<String>),
+                export_jobs::completed_at.eq(Utc::now()),
+                export_jobs::expires_at.eq(artifact.expires_at),
+            ))
+            .returning(ExportJob::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(job)
+    }
+
+    /// Record why a job failed
+    pub async fn mark_failed(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        error: &str,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::update(export_jobs::table.filter(export_jobs::id.eq(id)))
+            .set((
+                export_jobs::status.eq(ExportJobStatus::Failed.as_str()),
+                export_jobs::error_message.eq(error),
+                export_jobs::completed_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Completed jobs whose files are past their expiry
+    pub async fn find_expired(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let jobs = export_jobs::table
+            .filter(export_jobs::status.eq(ExportJobStatus::Completed.as_str()))
+            .filter(export_jobs::expires_at.le(Utc::now()))
+            .order(export_jobs::expires_at.asc())
+            .limit(limit)
+            .select(ExportJob::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(jobs)
+    }
+
+    /// Mark a job's file as deleted
+    pub async fn mark_expired(pool: &crate::database::DatabasePool, id: Uuid) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::update(export_jobs::table.filter(export_jobs::id.eq(id)))
+            .set((
+                export_jobs::status.eq(ExportJobStatus::Expired.as_str()),
+                export_jobs::storage_key.eq(None::<String>),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn export_format(&self) -> AppResult<ExportFormat> {
+        self.format.parse()
+    }
+
+    pub fn job_status(&self) -> AppResult<ExportJobStatus> {
+        self.status.parse()
+    }
+
+    /// Whether the file can be downloaded at `now`
+    pub fn is_downloadable(&self, now: DateTime<Utc>) -> bool {
+        self.status == ExportJobStatus::Completed.as_str()
+            && self.storage_key.is_some()
+            && self.expires_at.is_some_and(|expires_at| expires_at > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_job(series: usize, format: &str) -> NewExportJob {
+        NewExportJob {
+            user_id: Uuid::new_v4(),
+            series_ids: (0..series).map(|_| Uuid::new_v4()).collect(),
+            start_date: NaiveDate::from_ymd_opt(2020, 1, 1),
+            end_date: NaiveDate::from_ymd_opt(2024, 12, 31),
+            format: format.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_export_request_validation() {
+        // REQUIREMENT: Users request exports of a series set, date range and format
+        // PURPOSE: Verify malformed export requests are rejected before they are queued
+        // This ensures the worker only picks up jobs it can produce
+
+        assert!(new_job(1, "csv").validate_request().is_ok());
+        assert!(new_job(MAX_EXPORT_SERIES, "parquet")
+            .validate_request()
+            .is_ok());
+        assert!(new_job(0, "csv").validate_request().is_err());
+        assert!(new_job(MAX_EXPORT_SERIES + 1, "xlsx")
+            .validate_request()
+            .is_err());
+        assert!(new_job(1, "json").validate_request().is_err());
+
+        let mut reversed = new_job(1, "csv");
+        reversed.start_date = NaiveDate::from_ymd_opt(2025, 1, 1);
+        assert!(reversed.validate_request().is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::filing_sections;
+
+/// Narrative section extracted from a 10-K/10-Q primary document
+///
+/// Holds the plain-text body of sections such as Item 1A Risk Factors or
+/// Item 7 MD&A, linked to the financial statement the filing produced.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = filing_sections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FilingSection {
+    pub id: Uuid,
+    pub statement_id: Uuid,
+    pub section_type: String,
+    pub item_number: String,
+    pub part: Option<String>,
+    pub title: String,
+    pub content: String,
+    pub word_count: i32,
+    pub content_hash: String,
+    pub source_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New filing section for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = filing_sections)]
+pub struct NewFilingSection {
+    pub statement_id: Uuid,
+    pub section_type: String,
+    pub item_number: String,
+    pub part: Option<String>,
+    pub title: String,
+    pub content: String,
+    pub word_count: i32,
+    pub content_hash: String,
+    pub source_url: Option<String>,
+}
+
+impl FilingSection {
+    /// Insert or replace sections for a statement, keyed by section type
+    pub async fn upsert_many(
+        pool: &crate::database::DatabasePool,
+        sections: &[NewFilingSection],
+    ) -> crate::error::AppResult<Vec<Self>> {
+        use crate::schema::filing_sections::dsl;
+
+        if sections.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let stored = diesel::insert_into(dsl::filing_sections)
+            .values(sections)
+            .on_conflict((dsl::statement_id, dsl::section_type))
+            .do_update()
+            .set((
+                dsl::item_number.eq(excluded(dsl::item_number)),
+                dsl::part.eq(excluded(dsl::part)),
+                dsl::title.eq(excluded(dsl::title)),
+                dsl::content.eq(excluded(dsl::content)),
+                dsl::word_count.eq(excluded(dsl::word_count)),
+                dsl::content_hash.eq(excluded(dsl::content_hash)),
+                dsl::source_url.eq(excluded(dsl::source_url)),
+            ))
+            .get_results::<Self>(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Get all sections extracted for a statement
+    pub async fn find_by_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+    ) -> crate::error::AppResult<Vec<Self>> {
+        use crate::schema::filing_sections::dsl;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let sections = dsl::filing_sections
+            .filter(dsl::statement_id.eq(statement_id))
+            .order(dsl::item_number.asc())
+            .select(Self::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(sections)
+    }
+}
@@ -1,5 +1,5 @@
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -154,6 +154,30 @@ pub struct FinancialLineItem {
     /// Timestamp when this record was last modified
     /// Updated automatically on any field changes for change tracking
     pub updated_at: DateTime<Utc>,
+
+    /// Value in canonical units
+    /// Absolute amount in USD (or shares, USD/shares, pure) after applying the
+    /// reported scale and currency conversion; `None` when no FX rate was available
+    pub normalized_value: Option<BigDecimal>,
+
+    /// Canonical unit of `normalized_value`
+    /// Values: USD, shares, USD/shares, pure
+    pub normalized_unit: Option<String>,
+
+    /// Exchange rate applied to convert the reported currency into USD
+    /// `None` when the fact was already reported in USD or is not monetary
+    pub fx_rate: Option<BigDecimal>,
+
+    /// Explicit dimension members of the fact's context, by dimension (axis)
+    /// E.g. `{"us-gaap:StatementBusinessSegmentsAxis": "aapl:IPhoneMember"}`;
+    /// `{}` for facts about the entity as a whole
+    pub dimensions: serde_json::Value,
+
+    /// Start of the fact's reporting period; `None` for instants
+    pub period_start_date: Option<NaiveDate>,
+
+    /// End of the fact's reporting period, or its instant
+    pub period_end_date: Option<NaiveDate>,
 }
 
 /// **NewFinancialLineItem Model**
@@ -265,6 +289,24 @@ pub struct NewFinancialLineItem {
 
     /// Calculation formula
     pub calculation_formula: Option<String>,
+
+    /// Value in canonical units
+    pub normalized_value: Option<BigDecimal>,
+
+    /// Canonical unit of the normalized value
+    pub normalized_unit: Option<String>,
+
+    /// Exchange rate applied during normalization
+    pub fx_rate: Option<BigDecimal>,
+
+    /// Explicit dimension members by dimension (axis); `{}` when undimensioned
+    pub dimensions: serde_json::Value,
+
+    /// Start of the reporting period
+    pub period_start_date: Option<NaiveDate>,
+
+    /// End of the reporting period, or its instant
+    pub period_end_date: Option<NaiveDate>,
 }
 
 /// **FinancialLineItemWithStatement Model**
@@ -349,6 +391,7 @@ pub struct FinancialLineItemQueryParams {
     pub company_id: Option<Uuid>,
 
     /// CIK filter
+    #[validate(custom(function = "crate::validation::cik"))]
     pub cik: Option<String>,
 
     /// Ticker symbol filter
@@ -379,10 +422,11 @@ pub struct FinancialLineItemQueryParams {
     pub is_calculated: bool,
 
     /// Fiscal year filter
+    #[validate(custom(function = "crate::validation::fiscal_year"))]
     pub fiscal_year: Option<i32>,
 
     /// Fiscal quarter filter
-    #[validate(range(min = 1, max = 4))]
+    #[validate(custom(function = "crate::validation::fiscal_quarter"))]
     pub fiscal_quarter: Option<i32>,
 
     /// Maximum number of results to return
@@ -390,7 +434,7 @@ pub struct FinancialLineItemQueryParams {
     pub limit: Option<i64>,
 
     /// Number of results to skip for pagination
-    #[validate(range(min = 0))]
+    #[validate(custom(function = "crate::validation::non_negative"))]
     pub offset: Option<i64>,
 }
 
@@ -61,6 +61,7 @@ use crate::schema::financial_statements;
 ///     restatement_reason: None,
 ///     created_at: Utc::now(),
 ///     updated_at: Utc::now(),
+///     xbrl_file_blob_hash: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize, Deserialize)]
@@ -180,6 +181,10 @@ pub struct FinancialStatement {
     /// Timestamp when this record was last modified
     /// Updated automatically on any field changes for change tracking
     pub updated_at: DateTime<Utc>,
+
+    /// Hash of the XBRL file in `document_blobs` - Nullable, file may not be downloaded yet
+    /// Identical files are stored once; replaces `xbrl_file_content` for new filings
+    pub xbrl_file_blob_hash: Option<String>,
 }
 
 /// **NewFinancialStatement Model**
@@ -249,11 +254,11 @@ pub struct NewFinancialStatement {
     pub period_end_date: NaiveDate,
 
     /// Fiscal year
-    #[validate(range(min = 1900, max = 2100))]
+    #[validate(custom(function = "crate::validation::fiscal_year"))]
     pub fiscal_year: i32,
 
     /// Fiscal quarter
-    #[validate(range(min = 1, max = 4))]
+    #[validate(custom(function = "crate::validation::fiscal_quarter"))]
     pub fiscal_quarter: Option<i32>,
 
     /// Document type - Required, always available
@@ -270,7 +275,7 @@ pub struct NewFinancialStatement {
     pub xbrl_file_content: Option<Vec<u8>>,
 
     /// XBRL file size - Nullable, file may not be downloaded yet
-    #[validate(range(min = 0))]
+    #[validate(custom(function = "crate::validation::non_negative"))]
     pub xbrl_file_size_bytes: Option<i64>,
 
     /// Whether the XBRL file is compressed - Always known, defaults to true
@@ -384,6 +389,7 @@ pub struct FinancialStatementQueryParams {
     pub company_id: Option<Uuid>,
 
     /// CIK filter
+    #[validate(custom(function = "crate::validation::cik"))]
     pub cik: Option<String>,
 
     /// Ticker symbol filter
@@ -405,10 +411,11 @@ pub struct FinancialStatementQueryParams {
     pub period_end_date_end: Option<NaiveDate>,
 
     /// Fiscal year filter
+    #[validate(custom(function = "crate::validation::fiscal_year"))]
     pub fiscal_year: Option<i32>,
 
     /// Fiscal quarter filter
-    #[validate(range(min = 1, max = 4))]
+    #[validate(custom(function = "crate::validation::fiscal_quarter"))]
     pub fiscal_quarter: Option<i32>,
 
     /// XBRL processing status filter
@@ -425,7 +432,7 @@ pub struct FinancialStatementQueryParams {
     pub limit: Option<i64>,
 
     /// Number of results to skip for pagination
-    #[validate(range(min = 0))]
+    #[validate(custom(function = "crate::validation::non_negative"))]
     pub offset: Option<i64>,
 }
 
@@ -0,0 +1,134 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::fx_rates;
+
+/// Rows per insert statement, well under PostgreSQL's bind parameter limit
+const UPSERT_CHUNK_SIZE: usize = 1000;
+
+/// Daily foreign exchange reference rate
+///
+/// One unit of `base_currency` buys `rate` units of `quote_currency` on `rate_date`.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = fx_rates)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FxRate {
+    pub id: Uuid,
+    /// ISO 4217 code, e.g. "EUR"
+    pub base_currency: String,
+    /// ISO 4217 code, e.g. "USD"
+    pub quote_currency: String,
+    pub rate_date: NaiveDate,
+    pub rate: BigDecimal,
+    /// Data source name, e.g. "ECB"
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New rate for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = fx_rates)]
+pub struct NewFxRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate_date: NaiveDate,
+    pub rate: BigDecimal,
+    pub source: String,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl FxRate {
+    /// Insert rates, replacing any already stored for the same currency pair and day
+    ///
+    /// Returns how many rows were written.
+    pub async fn upsert_batch(
+        pool: &crate::database::DatabasePool,
+        rates: &[NewFxRate],
+    ) -> AppResult<usize> {
+        if rates.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut written = 0;
+        for chunk in rates.chunks(UPSERT_CHUNK_SIZE) {
+            written += diesel::insert_into(fx_rates::table)
+                .values(chunk)
+                .on_conflict((
+                    fx_rates::base_currency,
+                    fx_rates::quote_currency,
+                    fx_rates::rate_date,
+                ))
+                .do_update()
+                .set((
+                    fx_rates::rate.eq(excluded(fx_rates::rate)),
+                    fx_rates::source.eq(excluded(fx_rates::source)),
+                ))
+                .execute(&mut conn)
+                .await?;
+        }
+
+        Ok(written)
+    }
+
+    /// Most recent day with a stored rate against `base_currency`
+    pub async fn latest_date(
+        pool: &crate::database::DatabasePool,
+        base_currency: &str,
+    ) -> AppResult<Option<NaiveDate>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let latest = fx_rates::table
+            .filter(fx_rates::base_currency.eq(base_currency))
+            .select(diesel::dsl::max(fx_rates::rate_date))
+            .first::<Option<NaiveDate>>(&mut conn)
+            .await?;
+
+        Ok(latest)
+    }
+
+    /// Rates for a currency pair from `start` to `end`, oldest first
+    ///
+    /// The latest rate before `start` is included too, so every day in the
+    /// range can be matched to the last rate published on or before it.
+    pub async fn find_range_with_prior(
+        pool: &crate::database::DatabasePool,
+        base_currency: &str,
+        quote_currency: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let prior = fx_rates::table
+            .filter(fx_rates::base_currency.eq(base_currency))
+            .filter(fx_rates::quote_currency.eq(quote_currency))
+            .filter(fx_rates::rate_date.lt(start))
+            .order(fx_rates::rate_date.desc())
+            .select(FxRate::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        let in_range = fx_rates::table
+            .filter(fx_rates::base_currency.eq(base_currency))
+            .filter(fx_rates::quote_currency.eq(quote_currency))
+            .filter(fx_rates::rate_date.between(start, end))
+            .order(fx_rates::rate_date.asc())
+            .select(FxRate::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(prior.into_iter().chain(in_range).collect())
+    }
+}
@@ -1,10 +1,13 @@
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::error::{AppError, AppResult};
 use crate::schema::{
     countries, country_correlations, event_country_impacts, global_economic_events,
     global_economic_indicators, global_indicator_data, leading_indicators, trade_relationships,
@@ -396,3 +399,103 @@ impl std::fmt::Display for EventSeverity {
         }
     }
 }
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl Country {
+    /// Insert a country, or refresh the income group of an existing one
+    ///
+    /// Countries are matched on their ISO alpha-3 code; names, regions and
+    /// coordinates from the seed data are kept.
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        new_country: &NewCountry,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let country = diesel::insert_into(countries::table)
+            .values(new_country)
+            .on_conflict(countries::iso_code)
+            .do_update()
+            .set((
+                countries::income_group.eq(excluded(countries::income_group)),
+                countries::updated_at.eq(Utc::now()),
+            ))
+            .returning(Country::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(country)
+    }
+}
+
+impl GlobalEconomicIndicator {
+    /// Insert an indicator for a country, or update its description if it exists
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        new_indicator: &NewGlobalEconomicIndicator,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let indicator = diesel::insert_into(global_economic_indicators::table)
+            .values(new_indicator)
+            .on_conflict((
+                global_economic_indicators::country_id,
+                global_economic_indicators::indicator_code,
+            ))
+            .do_update()
+            .set((
+                global_economic_indicators::indicator_name
+                    .eq(excluded(global_economic_indicators::indicator_name)),
+                global_economic_indicators::category
+                    .eq(excluded(global_economic_indicators::category)),
+                global_economic_indicators::subcategory
+                    .eq(excluded(global_economic_indicators::subcategory)),
+                global_economic_indicators::unit.eq(excluded(global_economic_indicators::unit)),
+                global_economic_indicators::frequency
+                    .eq(excluded(global_economic_indicators::frequency)),
+                global_economic_indicators::updated_at.eq(Utc::now()),
+            ))
+            .returning(GlobalEconomicIndicator::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(indicator)
+    }
+}
+
+impl GlobalIndicatorData {
+    /// Insert observations, replacing values already stored for the same date
+    ///
+    /// Returns the number of rows written.
+    pub async fn upsert_batch(
+        pool: &crate::database::DatabasePool,
+        observations: &[NewGlobalIndicatorData],
+    ) -> AppResult<usize> {
+        if observations.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let written = diesel::insert_into(global_indicator_data::table)
+            .values(observations)
+            .on_conflict((
+                global_indicator_data::indicator_id,
+                global_indicator_data::date,
+            ))
+            .do_update()
+            .set((
+                global_indicator_data::value.eq(excluded(global_indicator_data::value)),
+                global_indicator_data::is_preliminary
+                    .eq(excluded(global_indicator_data::is_preliminary)),
+                global_indicator_data::data_source.eq(excluded(global_indicator_data::data_source)),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(written)
+    }
+}
@@ -0,0 +1,128 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::on_constraint;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::industry_benchmarks;
+
+/// How companies are grouped into peers for benchmarking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BenchmarkGroup {
+    /// Companies sharing a 4-digit SIC code
+    Sic,
+    /// Companies in the same sector
+    Sector,
+}
+
+impl BenchmarkGroup {
+    /// Value stored in `industry_benchmarks.group_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BenchmarkGroup::Sic => "sic",
+            BenchmarkGroup::Sector => "sector",
+        }
+    }
+}
+
+impl std::fmt::Display for BenchmarkGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Distribution of one ratio across a peer group for one fiscal period
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = industry_benchmarks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IndustryBenchmark {
+    pub id: Uuid,
+    pub group_type: String,
+    pub group_code: String,
+    pub ratio_name: String,
+    pub fiscal_year: i32,
+    pub fiscal_quarter: Option<i32>,
+    pub company_count: i32,
+    pub mean_value: BigDecimal,
+    pub median_value: BigDecimal,
+    pub p10_value: BigDecimal,
+    pub p25_value: BigDecimal,
+    pub p75_value: BigDecimal,
+    pub p90_value: BigDecimal,
+    pub calculated_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New or recomputed industry benchmark
+#[derive(Debug, Clone, PartialEq, Insertable, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = industry_benchmarks)]
+pub struct NewIndustryBenchmark {
+    pub group_type: String,
+    pub group_code: String,
+    pub ratio_name: String,
+    pub fiscal_year: i32,
+    pub fiscal_quarter: Option<i32>,
+    pub company_count: i32,
+    pub mean_value: BigDecimal,
+    pub median_value: BigDecimal,
+    pub p10_value: BigDecimal,
+    pub p25_value: BigDecimal,
+    pub p75_value: BigDecimal,
+    pub p90_value: BigDecimal,
+    pub calculated_at: DateTime<Utc>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl IndustryBenchmark {
+    /// Insert benchmarks, replacing any already stored for the same group and period
+    pub async fn upsert_many(
+        pool: &crate::database::DatabasePool,
+        benchmarks: &[NewIndustryBenchmark],
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut stored = 0;
+        for benchmark in benchmarks {
+            stored += diesel::insert_into(industry_benchmarks::table)
+                .values(benchmark)
+                .on_conflict(on_constraint("industry_benchmarks_period_unique"))
+                .do_update()
+                .set(benchmark)
+                .execute(&mut conn)
+                .await?;
+        }
+
+        Ok(stored)
+    }
+
+    /// Benchmarks of a ratio for one peer group, oldest period first
+    pub async fn find_for_group(
+        pool: &crate::database::DatabasePool,
+        group: BenchmarkGroup,
+        group_code: &str,
+        ratio_name: &str,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let benchmarks = industry_benchmarks::table
+            .filter(industry_benchmarks::group_type.eq(group.as_str()))
+            .filter(industry_benchmarks::group_code.eq(group_code))
+            .filter(industry_benchmarks::ratio_name.eq(ratio_name))
+            .order((
+                industry_benchmarks::fiscal_year.asc(),
+                industry_benchmarks::fiscal_quarter.asc(),
+            ))
+            .select(IndustryBenchmark::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(benchmarks)
+    }
+}
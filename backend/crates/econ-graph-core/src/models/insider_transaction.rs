@@ -0,0 +1,396 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{companies, insider_transactions};
+
+/// Most transactions returned by one query
+pub const MAX_INSIDER_TRANSACTIONS: i64 = 1000;
+
+/// Form 4 code of an open-market or private purchase
+pub const PURCHASE_CODE: &str = "P";
+
+/// Form 4 code of an open-market or private sale
+pub const SALE_CODE: &str = "S";
+
+/// One transaction line of an SEC Form 4
+///
+/// Lines come from both the non-derivative table (common stock) and the
+/// derivative table (options, RSUs, ...) of the filing. The issuer is kept by
+/// CIK, so filings of companies missing from `companies` are stored too.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = insider_transactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InsiderTransaction {
+    pub id: Uuid,
+    pub accession_number: String,
+    /// "4" or "4/A"
+    pub form_type: String,
+    pub filed_date: NaiveDate,
+    /// Position of the line within the filing
+    pub line_number: i32,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    pub issuer_ticker: Option<String>,
+    pub insider_cik: String,
+    pub insider_name: String,
+    pub is_director: bool,
+    pub is_officer: bool,
+    pub is_ten_percent_owner: bool,
+    pub is_other: bool,
+    pub officer_title: Option<String>,
+    pub security_title: String,
+    pub is_derivative: bool,
+    pub transaction_date: NaiveDate,
+    /// SEC transaction code: P purchase, S sale, A award, M exercise, F tax withholding, ...
+    pub transaction_code: String,
+    pub shares: BigDecimal,
+    pub price_per_share: Option<BigDecimal>,
+    /// "A" acquired or "D" disposed
+    pub acquired_disposed: String,
+    pub shares_owned_after: Option<BigDecimal>,
+    /// "D" direct or "I" indirect
+    pub ownership_nature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New insider transaction for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = insider_transactions)]
+pub struct NewInsiderTransaction {
+    pub accession_number: String,
+    pub form_type: String,
+    pub filed_date: NaiveDate,
+    pub line_number: i32,
+    pub issuer_cik: String,
+    pub issuer_name: String,
+    pub issuer_ticker: Option<String>,
+    pub insider_cik: String,
+    pub insider_name: String,
+    pub is_director: bool,
+    pub is_officer: bool,
+    pub is_ten_percent_owner: bool,
+    pub is_other: bool,
+    pub officer_title: Option<String>,
+    pub security_title: String,
+    pub is_derivative: bool,
+    pub transaction_date: NaiveDate,
+    pub transaction_code: String,
+    pub shares: BigDecimal,
+    pub price_per_share: Option<BigDecimal>,
+    pub acquired_disposed: String,
+    pub shares_owned_after: Option<BigDecimal>,
+    pub ownership_nature: String,
+}
+
+/// Filter for a company's insider transactions
+#[derive(Debug, Clone, Default)]
+pub struct InsiderTransactionFilter {
+    /// Earliest transaction date, inclusive
+    pub start_date: Option<NaiveDate>,
+    /// Latest transaction date, inclusive
+    pub end_date: Option<NaiveDate>,
+    /// Only these transaction codes; all codes when empty
+    pub transaction_codes: Vec<String>,
+    pub insider_cik: Option<String>,
+}
+
+/// Buying and selling by a company's insiders over a period
+///
+/// Only codes P and S count as purchases and sales. Awards, exercises and tax
+/// withholding are not trading decisions and are left out of the totals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsiderActivitySummary {
+    pub transaction_count: usize,
+    /// Distinct insiders with any transaction
+    pub insider_count: usize,
+    pub purchase_count: usize,
+    pub purchase_shares: BigDecimal,
+    /// Shares times price, for purchases with a reported price
+    pub purchase_value: BigDecimal,
+    pub sale_count: usize,
+    pub sale_shares: BigDecimal,
+    pub sale_value: BigDecimal,
+}
+
+impl InsiderActivitySummary {
+    pub fn from_transactions(transactions: &[InsiderTransaction]) -> Self {
+        let mut summary = Self {
+            transaction_count: transactions.len(),
+            insider_count: 0,
+            purchase_count: 0,
+            purchase_shares: BigDecimal::zero(),
+            purchase_value: BigDecimal::zero(),
+            sale_count: 0,
+            sale_shares: BigDecimal::zero(),
+            sale_value: BigDecimal::zero(),
+        };
+        let mut insiders = HashSet::new();
+
+        for transaction in transactions {
+            insiders.insert(transaction.insider_cik.as_str());
+            let value = transaction.value().unwrap_or_else(BigDecimal::zero);
+            match transaction.transaction_code.as_str() {
+                PURCHASE_CODE => {
+                    summary.purchase_count += 1;
+                    summary.purchase_shares += &transaction.shares;
+                    summary.purchase_value += value;
+                }
+                SALE_CODE => {
+                    summary.sale_count += 1;
+                    summary.sale_shares += &transaction.shares;
+                    summary.sale_value += value;
+                }
+                _ => {}
+            }
+        }
+
+        summary.insider_count = insiders.len();
+        summary
+    }
+
+    /// Shares bought minus shares sold
+    pub fn net_shares(&self) -> BigDecimal {
+        &self.purchase_shares - &self.sale_shares
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl InsiderTransaction {
+    /// Shares times price, when the filing reports a price
+    pub fn value(&self) -> Option<BigDecimal> {
+        self.price_per_share
+            .as_ref()
+            .map(|price| &self.shares * price)
+    }
+
+    /// Insider's relationship to the issuer as written on the form,
+    /// e.g. "Director, Chief Executive Officer"
+    pub fn relationship(&self) -> String {
+        let mut roles = Vec::new();
+        if self.is_director {
+            roles.push("Director");
+        }
+        if self.is_officer {
+            roles.push(self.officer_title.as_deref().unwrap_or("Officer"));
+        }
+        if self.is_ten_percent_owner {
+            roles.push("10% Owner");
+        }
+        if self.is_other {
+            roles.push("Other");
+        }
+        roles.join(", ")
+    }
+
+    /// Insert transactions, skipping lines of filings already stored
+    ///
+    /// Returns the number of rows inserted.
+    pub async fn insert_many(
+        pool: &crate::database::DatabasePool,
+        transactions: &[NewInsiderTransaction],
+    ) -> AppResult<usize> {
+        if transactions.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let inserted = diesel::insert_into(insider_transactions::table)
+            .values(transactions)
+            .on_conflict((
+                insider_transactions::accession_number,
+                insider_transactions::line_number,
+            ))
+            .do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        Ok(inserted)
+    }
+
+    /// Accession numbers among `accession_numbers` with stored transactions
+    pub async fn stored_accessions(
+        pool: &crate::database::DatabasePool,
+        accession_numbers: &[String],
+    ) -> AppResult<HashSet<String>> {
+        if accession_numbers.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = insider_transactions::table
+            .filter(insider_transactions::accession_number.eq_any(accession_numbers))
+            .select(insider_transactions::accession_number)
+            .distinct()
+            .load::<String>(&mut conn)
+            .await?;
+
+        Ok(stored.into_iter().collect())
+    }
+
+    /// A company's insider transactions, newest first
+    pub async fn find_for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+        filter: &InsiderTransactionFilter,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        Self::load_for_company(
+            pool,
+            company_id,
+            filter,
+            Some(limit.clamp(1, MAX_INSIDER_TRANSACTIONS)),
+        )
+        .await
+    }
+
+    async fn load_for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+        filter: &InsiderTransactionFilter,
+        limit: Option<i64>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let issuer_cik: String = companies::table
+            .find(company_id)
+            .select(companies::cik)
+            .first(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound(format!("Company {} not found", company_id)))?;
+
+        let mut query = insider_transactions::table
+            .filter(insider_transactions::issuer_cik.eq(issuer_cik))
+            .order((
+                insider_transactions::transaction_date.desc(),
+                insider_transactions::accession_number.desc(),
+                insider_transactions::line_number.asc(),
+            ))
+            .select(Self::as_select())
+            .into_boxed();
+        if let Some(start_date) = filter.start_date {
+            query = query.filter(insider_transactions::transaction_date.ge(start_date));
+        }
+        if let Some(end_date) = filter.end_date {
+            query = query.filter(insider_transactions::transaction_date.le(end_date));
+        }
+        if !filter.transaction_codes.is_empty() {
+            query = query
+                .filter(insider_transactions::transaction_code.eq_any(&filter.transaction_codes));
+        }
+        if let Some(insider_cik) = &filter.insider_cik {
+            query = query.filter(insider_transactions::insider_cik.eq(insider_cik));
+        }
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+
+        let transactions = query.load::<Self>(&mut conn).await?;
+
+        Ok(transactions)
+    }
+}
+
+impl InsiderActivitySummary {
+    /// Summarize a company's insider transactions between two dates, inclusive
+    pub async fn for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> AppResult<Self> {
+        let filter = InsiderTransactionFilter {
+            start_date: Some(start_date),
+            end_date: Some(end_date),
+            ..Default::default()
+        };
+        let transactions =
+            InsiderTransaction::load_for_company(pool, company_id, &filter, None).await?;
+
+        Ok(Self::from_transactions(&transactions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn transaction(
+        insider_cik: &str,
+        code: &str,
+        shares: &str,
+        price: Option<&str>,
+    ) -> InsiderTransaction {
+        InsiderTransaction {
+            id: Uuid::new_v4(),
+            accession_number: "0000320193-24-000001".to_string(),
+            form_type: "4".to_string(),
+            filed_date: NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(),
+            line_number: 1,
+            issuer_cik: "0000320193".to_string(),
+            issuer_name: "Apple Inc.".to_string(),
+            issuer_ticker: Some("AAPL".to_string()),
+            insider_cik: insider_cik.to_string(),
+            insider_name: "Insider".to_string(),
+            is_director: true,
+            is_officer: true,
+            is_ten_percent_owner: false,
+            is_other: false,
+            officer_title: Some("Chief Executive Officer".to_string()),
+            security_title: "Common Stock".to_string(),
+            is_derivative: false,
+            transaction_date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            transaction_code: code.to_string(),
+            shares: BigDecimal::from_str(shares).unwrap(),
+            price_per_share: price.map(|p| BigDecimal::from_str(p).unwrap()),
+            acquired_disposed: if code == SALE_CODE { "D" } else { "A" }.to_string(),
+            shares_owned_after: None,
+            ownership_nature: "D".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_insider_activity_summary() {
+        // REQUIREMENT: Summarize insider buying and selling per company
+        // PURPOSE: Verify purchases and sales are totalled while awards only count as transactions
+        // This ensures grants and option exercises do not show up as insider buying
+
+        let transactions = vec![
+            transaction("0000000001", "P", "100", Some("10.50")),
+            transaction("0000000001", "S", "40", Some("12")),
+            transaction("0000000002", "S", "10", None),
+            transaction("0000000003", "A", "5000", None),
+        ];
+
+        let summary = InsiderActivitySummary::from_transactions(&transactions);
+
+        assert_eq!(summary.transaction_count, 4);
+        assert_eq!(summary.insider_count, 3);
+        assert_eq!(summary.purchase_count, 1);
+        assert_eq!(
+            summary.purchase_value,
+            BigDecimal::from_str("1050").unwrap()
+        );
+        assert_eq!(summary.sale_count, 2);
+        assert_eq!(summary.sale_shares, BigDecimal::from(50));
+        assert_eq!(summary.sale_value, BigDecimal::from(480));
+        assert_eq!(summary.net_shares(), BigDecimal::from(50));
+        assert_eq!(
+            transactions[0].relationship(),
+            "Director, Chief Executive Officer"
+        );
+    }
+}
@@ -0,0 +1,616 @@
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{companies, cusip_mappings, institutional_filings, institutional_holdings};
+
+/// Most holders or position changes returned by one query
+pub const MAX_INSTITUTIONAL_RESULTS: i64 = 500;
+
+/// 13F-HR/A amendment type that replaces the quarter's holdings
+pub const AMENDMENT_RESTATEMENT: &str = "RESTATEMENT";
+
+/// 13F-HR/A amendment type that adds holdings to the quarter
+pub const AMENDMENT_NEW_HOLDINGS: &str = "NEW HOLDINGS";
+
+/// Holdings inserted per statement
+const HOLDING_INSERT_BATCH_SIZE: usize = 1000;
+
+/// A 13F-HR or 13F-HR/A filing by an institutional investment manager
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = institutional_filings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InstitutionalFiling {
+    pub id: Uuid,
+    pub accession_number: String,
+    pub form_type: String,
+    /// RESTATEMENT or NEW HOLDINGS for amendments
+    pub amendment_type: Option<String>,
+    pub manager_cik: String,
+    pub manager_name: String,
+    /// Quarter end the holdings are reported for
+    pub report_period: NaiveDate,
+    pub filed_date: NaiveDate,
+    pub holdings_count: i32,
+    pub total_value_usd: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New 13F filing for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = institutional_filings)]
+pub struct NewInstitutionalFiling {
+    pub accession_number: String,
+    pub form_type: String,
+    pub amendment_type: Option<String>,
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub report_period: NaiveDate,
+    pub filed_date: NaiveDate,
+    pub holdings_count: i32,
+    pub total_value_usd: BigDecimal,
+}
+
+impl NewInstitutionalFiling {
+    /// Whether the filing replaces the manager's holdings for the quarter,
+    /// rather than adding to them
+    pub fn replaces_holdings(&self) -> bool {
+        self.amendment_type.as_deref() != Some(AMENDMENT_NEW_HOLDINGS)
+    }
+}
+
+/// One row of a manager's quarterly 13F information table
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = institutional_holdings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InstitutionalHolding {
+    pub id: Uuid,
+    pub filing_id: Uuid,
+    pub manager_cik: String,
+    pub report_period: NaiveDate,
+    pub cusip: String,
+    pub issuer_name: String,
+    pub title_of_class: String,
+    /// Market value in dollars
+    pub value_usd: BigDecimal,
+    pub shares: BigDecimal,
+    /// SH for shares, PRN for a principal amount
+    pub share_type: String,
+    /// Put or Call for option positions
+    pub put_call: Option<String>,
+    pub investment_discretion: String,
+    pub voting_sole: i64,
+    pub voting_shared: i64,
+    pub voting_none: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Information table row parsed from a 13F filing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewInstitutionalHolding {
+    pub cusip: String,
+    pub issuer_name: String,
+    pub title_of_class: String,
+    pub value_usd: BigDecimal,
+    pub shares: BigDecimal,
+    pub share_type: String,
+    pub put_call: Option<String>,
+    pub investment_discretion: String,
+    pub voting_sole: i64,
+    pub voting_shared: i64,
+    pub voting_none: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = institutional_holdings)]
+struct HoldingRow<'a> {
+    filing_id: Uuid,
+    manager_cik: &'a str,
+    report_period: NaiveDate,
+    cusip: &'a str,
+    issuer_name: &'a str,
+    title_of_class: &'a str,
+    value_usd: &'a BigDecimal,
+    shares: &'a BigDecimal,
+    share_type: &'a str,
+    put_call: Option<&'a str>,
+    investment_discretion: &'a str,
+    voting_sole: i64,
+    voting_shared: i64,
+    voting_none: i64,
+}
+
+/// Company a 13F CUSIP belongs to
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = cusip_mappings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CusipMapping {
+    pub cusip: String,
+    pub company_id: Uuid,
+    /// Issuer name as written in the 13F information table
+    pub issuer_name: String,
+    /// name_match or manual
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New CUSIP mapping for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = cusip_mappings)]
+pub struct NewCusipMapping {
+    pub cusip: String,
+    pub company_id: Uuid,
+    pub issuer_name: String,
+    pub source: String,
+}
+
+/// A manager's position in a company at one quarter end
+///
+/// Share classes are added together. Option positions and principal amounts
+/// are left out, so the shares are comparable between managers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HolderPosition {
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub shares: BigDecimal,
+    pub value_usd: BigDecimal,
+}
+
+/// How a manager's position changed from one quarter to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionChangeKind {
+    New,
+    Increased,
+    Decreased,
+    Unchanged,
+    SoldOut,
+}
+
+/// A manager's position compared with the previous quarter
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionChange {
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub previous_shares: BigDecimal,
+    pub shares: BigDecimal,
+    pub kind: PositionChangeKind,
+}
+
+impl PositionChange {
+    /// Shares bought (positive) or sold (negative)
+    pub fn change(&self) -> BigDecimal {
+        &self.shares - &self.previous_shares
+    }
+
+    /// Change relative to the previous position; None for new positions
+    pub fn change_percent(&self) -> Option<f64> {
+        if self.previous_shares.is_zero() {
+            return None;
+        }
+        (self.change() / &self.previous_shares * BigDecimal::from(100)).to_f64()
+    }
+}
+
+/// Quarter end before `period`, e.g. 2024-03-31 for 2024-06-30
+pub fn previous_quarter_end(period: NaiveDate) -> NaiveDate {
+    let quarter_start_month = (period.month0() / 3) * 3 + 1;
+    NaiveDate::from_ymd_opt(period.year(), quarter_start_month, 1)
+        .and_then(|start| start.pred_opt())
+        .unwrap_or(period)
+}
+
+/// Whether `date` is the last day of a calendar quarter, as 13F periods are
+pub fn is_quarter_end(date: NaiveDate) -> bool {
+    date.month() % 3 == 0 && date.succ_opt().is_some_and(|next| next.day() == 1)
+}
+
+/// Add up holdings rows per manager, largest position first
+///
+/// `rows` are (manager CIK, manager name, shares, value) tuples; a manager
+/// may report a company on several rows, one per share class or per
+/// investment discretion.
+pub fn aggregate_positions(
+    rows: Vec<(String, String, BigDecimal, BigDecimal)>,
+) -> Vec<HolderPosition> {
+    let mut by_manager: BTreeMap<String, HolderPosition> = BTreeMap::new();
+    for (manager_cik, manager_name, shares, value_usd) in rows {
+        let position = by_manager
+            .entry(manager_cik.clone())
+            .or_insert_with(|| HolderPosition {
+                manager_cik,
+                manager_name,
+                shares: BigDecimal::zero(),
+                value_usd: BigDecimal::zero(),
+            });
+        position.shares += shares;
+        position.value_usd += value_usd;
+    }
+
+    let mut positions: Vec<HolderPosition> = by_manager.into_values().collect();
+    positions.sort_by(|a, b| b.shares.cmp(&a.shares));
+    positions
+}
+
+/// Compare each manager's position with the previous quarter
+///
+/// Managers missing from `current` sold out. Changes are ordered by the
+/// number of shares bought or sold, largest first.
+pub fn compare_positions(
+    previous: &[HolderPosition],
+    current: &[HolderPosition],
+) -> Vec<PositionChange> {
+    let before: BTreeMap<&str, &HolderPosition> = previous
+        .iter()
+        .map(|p| (p.manager_cik.as_str(), p))
+        .collect();
+    let now: HashSet<&str> = current.iter().map(|p| p.manager_cik.as_str()).collect();
+
+    let mut changes: Vec<PositionChange> = current
+        .iter()
+        .map(|position| {
+            let previous_shares = before
+                .get(position.manager_cik.as_str())
+                .map(|p| p.shares.clone())
+                .unwrap_or_else(BigDecimal::zero);
+            let kind = if previous_shares.is_zero() {
+                PositionChangeKind::New
+            } else if position.shares > previous_shares {
+                PositionChangeKind::Increased
+            } else if position.shares < previous_shares {
+                PositionChangeKind::Decreased
+            } else {
+                PositionChangeKind::Unchanged
+            };
+            PositionChange {
+                manager_cik: position.manager_cik.clone(),
+                manager_name: position.manager_name.clone(),
+                previous_shares,
+                shares: position.shares.clone(),
+                kind,
+            }
+        })
+        .collect();
+
+    changes.extend(
+        previous
+            .iter()
+            .filter(|p| !now.contains(p.manager_cik.as_str()))
+            .map(|p| PositionChange {
+                manager_cik: p.manager_cik.clone(),
+                manager_name: p.manager_name.clone(),
+                previous_shares: p.shares.clone(),
+                shares: BigDecimal::zero(),
+                kind: PositionChangeKind::SoldOut,
+            }),
+    );
+
+    changes.sort_by(|a, b| b.change().abs().cmp(&a.change().abs()));
+    changes
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl InstitutionalFiling {
+    /// Store a filing and update the manager's holdings for the quarter
+    ///
+    /// An original filing or restatement replaces the quarter's holdings,
+    /// unless a later-filed one is already stored; a NEW HOLDINGS amendment
+    /// adds to them. Returns the number of holdings stored, or None when the
+    /// filing was stored before.
+    pub async fn store(
+        pool: &crate::database::DatabasePool,
+        filing: &NewInstitutionalFiling,
+        holdings: &[NewInstitutionalHolding],
+    ) -> AppResult<Option<usize>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let filing = filing.clone();
+        let holdings = holdings.to_vec();
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            async move {
+                let Some(filing_id) = diesel::insert_into(institutional_filings::table)
+                    .values(&filing)
+                    .on_conflict(institutional_filings::accession_number)
+                    .do_nothing()
+                    .returning(institutional_filings::id)
+                    .get_result::<Uuid>(conn)
+                    .await
+                    .optional()?
+                else {
+                    return Ok(None);
+                };
+
+                let snapshot = institutional_holdings::table
+                    .filter(institutional_holdings::manager_cik.eq(&filing.manager_cik))
+                    .filter(institutional_holdings::report_period.eq(filing.report_period));
+
+                if filing.replaces_holdings() {
+                    let superseded = diesel::select(diesel::dsl::exists(
+                        institutional_filings::table
+                            .filter(institutional_filings::manager_cik.eq(&filing.manager_cik))
+                            .filter(institutional_filings::report_period.eq(filing.report_period))
+                            .filter(institutional_filings::id.ne(filing_id))
+                            .filter(institutional_filings::filed_date.gt(filing.filed_date))
+                            .filter(institutional_filings::amendment_type.is_null().or(
+                                institutional_filings::amendment_type.eq(AMENDMENT_RESTATEMENT),
+                            )),
+                    ))
+                    .get_result::<bool>(conn)
+                    .await?;
+                    if superseded {
+                        return Ok(Some(0));
+                    }
+
+                    diesel::delete(snapshot).execute(conn).await?;
+                }
+
+                for batch in holdings.chunks(HOLDING_INSERT_BATCH_SIZE) {
+                    let rows: Vec<HoldingRow> = batch
+                        .iter()
+                        .map(|holding| HoldingRow {
+                            filing_id,
+                            manager_cik: &filing.manager_cik,
+                            report_period: filing.report_period,
+                            cusip: &holding.cusip,
+                            issuer_name: &holding.issuer_name,
+                            title_of_class: &holding.title_of_class,
+                            value_usd: &holding.value_usd,
+                            shares: &holding.shares,
+                            share_type: &holding.share_type,
+                            put_call: holding.put_call.as_deref(),
+                            investment_discretion: &holding.investment_discretion,
+                            voting_sole: holding.voting_sole,
+                            voting_shared: holding.voting_shared,
+                            voting_none: holding.voting_none,
+                        })
+                        .collect();
+
+                    diesel::insert_into(institutional_holdings::table)
+                        .values(&rows)
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(Some(holdings.len()))
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Accession numbers among `accession_numbers` that are already stored
+    pub async fn stored_accessions(
+        pool: &crate::database::DatabasePool,
+        accession_numbers: &[String],
+    ) -> AppResult<HashSet<String>> {
+        if accession_numbers.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = institutional_filings::table
+            .filter(institutional_filings::accession_number.eq_any(accession_numbers))
+            .select(institutional_filings::accession_number)
+            .load::<String>(&mut conn)
+            .await?;
+
+        Ok(stored.into_iter().collect())
+    }
+}
+
+impl CusipMapping {
+    /// CUSIPs among `cusips` that already have a mapping
+    pub async fn mapped(
+        pool: &crate::database::DatabasePool,
+        cusips: &[String],
+    ) -> AppResult<HashSet<String>> {
+        if cusips.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mapped = cusip_mappings::table
+            .filter(cusip_mappings::cusip.eq_any(cusips))
+            .select(cusip_mappings::cusip)
+            .load::<String>(&mut conn)
+            .await?;
+
+        Ok(mapped.into_iter().collect())
+    }
+
+    /// Insert mappings, keeping existing ones
+    ///
+    /// Returns the number of mappings inserted.
+    pub async fn insert_missing(
+        pool: &crate::database::DatabasePool,
+        mappings: &[NewCusipMapping],
+    ) -> AppResult<usize> {
+        if mappings.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let inserted = diesel::insert_into(cusip_mappings::table)
+            .values(mappings)
+            .on_conflict(cusip_mappings::cusip)
+            .do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        Ok(inserted)
+    }
+}
+
+impl InstitutionalHolding {
+    /// CUSIPs of a company; fails when the company does not exist
+    async fn company_cusips(
+        conn: &mut diesel_async::AsyncPgConnection,
+        company_id: Uuid,
+    ) -> AppResult<Vec<String>> {
+        let exists = diesel::select(diesel::dsl::exists(companies::table.find(company_id)))
+            .get_result::<bool>(conn)
+            .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Company {} not found",
+                company_id
+            )));
+        }
+
+        let cusips = cusip_mappings::table
+            .filter(cusip_mappings::company_id.eq(company_id))
+            .select(cusip_mappings::cusip)
+            .load::<String>(conn)
+            .await?;
+
+        Ok(cusips)
+    }
+
+    /// Latest quarter with reported holdings of a company
+    pub async fn latest_period_for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+    ) -> AppResult<Option<NaiveDate>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let cusips = Self::company_cusips(&mut conn, company_id).await?;
+
+        let period = institutional_holdings::table
+            .filter(institutional_holdings::cusip.eq_any(&cusips))
+            .select(diesel::dsl::max(institutional_holdings::report_period))
+            .first::<Option<NaiveDate>>(&mut conn)
+            .await?;
+
+        Ok(period)
+    }
+
+    /// Managers' share positions in a company at a quarter end, largest first
+    pub async fn positions_for_company(
+        pool: &crate::database::DatabasePool,
+        company_id: Uuid,
+        report_period: NaiveDate,
+    ) -> AppResult<Vec<HolderPosition>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let cusips = Self::company_cusips(&mut conn, company_id).await?;
+        if cusips.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = institutional_holdings::table
+            .inner_join(institutional_filings::table)
+            .filter(institutional_holdings::cusip.eq_any(&cusips))
+            .filter(institutional_holdings::report_period.eq(report_period))
+            .filter(institutional_holdings::share_type.eq("SH"))
+            .filter(institutional_holdings::put_call.is_null())
+            .select((
+                institutional_holdings::manager_cik,
+                institutional_filings::manager_name,
+                institutional_holdings::shares,
+                institutional_holdings::value_usd,
+            ))
+            .load::<(String, String, BigDecimal, BigDecimal)>(&mut conn)
+            .await?;
+
+        Ok(aggregate_positions(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(cik: &str, shares: i64) -> HolderPosition {
+        HolderPosition {
+            manager_cik: cik.to_string(),
+            manager_name: format!("Manager {}", cik),
+            shares: BigDecimal::from(shares),
+            value_usd: BigDecimal::from(shares * 10),
+        }
+    }
+
+    #[test]
+    fn test_quarter_over_quarter_position_changes() {
+        // REQUIREMENT: Show how institutional positions in a company changed quarter over quarter
+        // PURPOSE: Verify rows are added up per manager and classified as new, increased, decreased or sold out
+        // This ensures share classes are not listed as separate holders and exits are reported
+
+        let current = aggregate_positions(vec![
+            (
+                "1".to_string(),
+                "Manager 1".to_string(),
+                BigDecimal::from(700),
+                BigDecimal::from(7000),
+            ),
+            (
+                "1".to_string(),
+                "Manager 1".to_string(),
+                BigDecimal::from(500),
+                BigDecimal::from(5000),
+            ),
+            (
+                "2".to_string(),
+                "Manager 2".to_string(),
+                BigDecimal::from(100),
+                BigDecimal::from(1000),
+            ),
+            (
+                "3".to_string(),
+                "Manager 3".to_string(),
+                BigDecimal::from(50),
+                BigDecimal::from(500),
+            ),
+        ]);
+        assert_eq!(current.len(), 3);
+        assert_eq!(current[0].shares, BigDecimal::from(1200));
+
+        let previous = vec![position("1", 1000), position("2", 400), position("4", 300)];
+        let changes = compare_positions(&previous, &current);
+
+        let kinds: Vec<(&str, PositionChangeKind)> = changes
+            .iter()
+            .map(|c| (c.manager_cik.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("2", PositionChangeKind::Decreased),
+                ("4", PositionChangeKind::SoldOut),
+                ("1", PositionChangeKind::Increased),
+                ("3", PositionChangeKind::New),
+            ]
+        );
+        assert_eq!(changes[0].change(), BigDecimal::from(-300));
+        assert_eq!(changes[0].change_percent(), Some(-75.0));
+        assert_eq!(changes[3].change_percent(), None);
+
+        assert_eq!(
+            previous_quarter_end(NaiveDate::from_ymd_opt(2024, 6, 30).unwrap()),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+        assert_eq!(
+            previous_quarter_end(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()),
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert!(is_quarter_end(
+            NaiveDate::from_ymd_opt(2024, 9, 30).unwrap()
+        ));
+        assert!(!is_quarter_end(
+            NaiveDate::from_ymd_opt(2024, 9, 29).unwrap()
+        ));
+        assert!(!is_quarter_end(
+            NaiveDate::from_ymd_opt(2024, 8, 31).unwrap()
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::educational_content::{
+    AchievementType, LearningAchievement, LearningProgress, LearningStatus, QuizScore,
+};
+use crate::schema::{learning_achievements, learning_progress};
+
+/// Stored progress of a user through one library module
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = learning_progress)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LearningProgressRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub module_id: Uuid,
+    pub status: String,
+    pub progress_percentage: f64,
+    pub time_spent_minutes: i32,
+    pub completed_sections: Vec<Uuid>,
+    pub completed_exercises: Vec<Uuid>,
+    pub quiz_scores: serde_json::Value,
+    pub started_at: DateTime<Utc>,
+    pub last_accessed_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Progress to store for a user and module
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = learning_progress)]
+pub struct NewLearningProgress {
+    pub user_id: Uuid,
+    pub module_id: Uuid,
+    pub status: String,
+    pub progress_percentage: f64,
+    pub time_spent_minutes: i32,
+    pub completed_sections: Vec<Uuid>,
+    pub completed_exercises: Vec<Uuid>,
+    pub quiz_scores: serde_json::Value,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl NewLearningProgress {
+    pub fn from_progress(progress: &LearningProgress, module_id: Uuid) -> AppResult<Self> {
+        Ok(Self {
+            user_id: progress.user_id,
+            module_id,
+            status: progress.status.as_str().to_string(),
+            progress_percentage: progress.progress_percentage,
+            time_spent_minutes: progress.time_spent_minutes,
+            completed_sections: progress.completed_sections.clone(),
+            completed_exercises: progress.completed_exercises.clone(),
+            quiz_scores: serde_json::to_value(&progress.quiz_scores)?,
+            completed_at: progress.completed_at,
+        })
+    }
+}
+
+/// Stored achievement of a user
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable)]
+#[diesel(table_name = learning_achievements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LearningAchievementRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub achievement_key: String,
+    pub achievement_type: String,
+    pub title: String,
+    pub description: String,
+    pub criteria: String,
+    pub earned_at: DateTime<Utc>,
+    pub module_id: Option<Uuid>,
+    pub learning_path_id: Option<Uuid>,
+    pub badge_url: Option<String>,
+}
+
+/// Achievement to award
+///
+/// `achievement_key` identifies what was achieved (for example
+/// `module_completion:<module id>`); a user earns each key once.
+#[derive(Debug, Clone, PartialEq, Insertable)]
+#[diesel(table_name = learning_achievements)]
+pub struct NewLearningAchievement {
+    pub user_id: Uuid,
+    pub achievement_key: String,
+    pub achievement_type: String,
+    pub title: String,
+    pub description: String,
+    pub criteria: String,
+    pub module_id: Option<Uuid>,
+    pub learning_path_id: Option<Uuid>,
+    pub badge_url: Option<String>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl LearningProgressRecord {
+    /// Store the progress of a user through a module, replacing earlier progress
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        progress: &NewLearningProgress,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = diesel::insert_into(learning_progress::table)
+            .values(progress)
+            .on_conflict((learning_progress::user_id, learning_progress::module_id))
+            .do_update()
+            .set((
+                learning_progress::status.eq(&progress.status),
+                learning_progress::progress_percentage.eq(progress.progress_percentage),
+                learning_progress::time_spent_minutes.eq(progress.time_spent_minutes),
+                learning_progress::completed_sections.eq(&progress.completed_sections),
+                learning_progress::completed_exercises.eq(&progress.completed_exercises),
+                learning_progress::quiz_scores.eq(&progress.quiz_scores),
+                learning_progress::last_accessed_at.eq(Utc::now()),
+                learning_progress::completed_at.eq(progress.completed_at),
+            ))
+            .returning(LearningProgressRecord::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Progress of a user through a module, if they have started it
+    pub async fn find_for_user_module(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        module_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let progress = learning_progress::table
+            .filter(learning_progress::user_id.eq(user_id))
+            .filter(learning_progress::module_id.eq(module_id))
+            .select(LearningProgressRecord::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(progress)
+    }
+
+    /// Progress of a user through every module they started, most recent first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let progress = learning_progress::table
+            .filter(learning_progress::user_id.eq(user_id))
+            .order(learning_progress::last_accessed_at.desc())
+            .select(LearningProgressRecord::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(progress)
+    }
+
+    /// Convert to the educational content model
+    pub fn into_progress(self) -> AppResult<LearningProgress> {
+        let status = self
+            .status
+            .parse::<LearningStatus>()
+            .map_err(AppError::DatabaseError)?;
+        let quiz_scores: Vec<QuizScore> = serde_json::from_value(self.quiz_scores)?;
+
+        Ok(LearningProgress {
+            id: self.id,
+            user_id: self.user_id,
+            module_id: Some(self.module_id),
+            learning_path_id: None,
+            status,
+            progress_percentage: self.progress_percentage,
+            time_spent_minutes: self.time_spent_minutes,
+            completed_sections: self.completed_sections,
+            completed_exercises: self.completed_exercises,
+            quiz_scores,
+            started_at: self.started_at,
+            last_accessed_at: self.last_accessed_at,
+            completed_at: self.completed_at,
+        })
+    }
+}
+
+impl LearningAchievementRecord {
+    /// Award an achievement; returns `None` when the user already earned it
+    pub async fn award(
+        pool: &crate::database::DatabasePool,
+        achievement: &NewLearningAchievement,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let awarded = diesel::insert_into(learning_achievements::table)
+            .values(achievement)
+            .on_conflict((
+                learning_achievements::user_id,
+                learning_achievements::achievement_key,
+            ))
+            .do_nothing()
+            .returning(LearningAchievementRecord::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(awarded)
+    }
+
+    /// Achievements of a user, most recent first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let achievements = learning_achievements::table
+            .filter(learning_achievements::user_id.eq(user_id))
+            .order(learning_achievements::earned_at.desc())
+            .select(LearningAchievementRecord::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(achievements)
+    }
+
+    /// Convert to the educational content model
+    pub fn into_achievement(self) -> AppResult<LearningAchievement> {
+        let achievement_type = self
+            .achievement_type
+            .parse::<AchievementType>()
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(LearningAchievement {
+            id: self.id,
+            user_id: self.user_id,
+            achievement_type,
+            title: self.title,
+            description: self.description,
+            criteria: self.criteria,
+            earned_at: self.earned_at,
+            module_id: self.module_id,
+            learning_path_id: self.learning_path_id,
+            badge_url: self.badge_url,
+        })
+    }
+}
@@ -2,43 +2,105 @@ pub mod admin;
 pub mod annotation_assignment;
 pub mod annotation_reply;
 pub mod annotation_template;
+pub mod bls_catalog_sync;
+pub mod catalog_statistics;
+pub mod change_feed;
 pub mod company;
+pub mod company_financials;
+pub mod component_status;
 pub mod crawl_attempt;
 pub mod crawl_queue;
+pub mod data_lineage;
 pub mod data_point;
+pub mod data_point_correction;
 pub mod data_source;
+pub mod data_source_credential;
+pub mod data_source_usage;
+pub mod derived_series;
+pub mod document_blob;
 pub mod economic_series;
 pub mod educational_content;
+pub mod email;
+pub mod export_job;
+pub mod filing_section;
 pub mod financial_annotation;
 pub mod financial_line_item;
 pub mod financial_ratios;
 pub mod financial_statement;
+pub mod fx_rate;
 pub mod global_analysis;
+pub mod industry_benchmark;
+pub mod insider_transaction;
+pub mod institutional_holding;
+pub mod learning_progress;
+pub mod notification;
+pub mod organization;
+pub mod release_calendar;
+pub mod saved_chart;
 pub mod search;
+pub mod segment_breakdown;
+pub mod series_alert_rule;
+pub mod series_link;
 pub mod series_metadata;
+pub mod series_quality_score;
+pub mod series_rollup;
 pub mod user;
+pub mod webhook;
+pub mod xbrl_calculation_discrepancy;
+pub mod xbrl_dts;
 pub mod xbrl_taxonomy_schema;
 
 pub use annotation_assignment::*;
 pub use annotation_reply::*;
 pub use annotation_template::*;
+pub use bls_catalog_sync::*;
+pub use catalog_statistics::*;
+pub use change_feed::*;
 pub use company::*;
+pub use company_financials::*;
+pub use component_status::*;
 pub use crawl_attempt::*;
 pub use crawl_queue::*;
+pub use data_lineage::*;
 pub use data_point::*;
+pub use data_point_correction::*;
 pub use data_source::*;
+pub use data_source_credential::*;
+pub use data_source_usage::*;
+pub use derived_series::*;
+pub use document_blob::*;
 pub use economic_series::*;
 pub use educational_content::{
-    AssessmentQuestion, ContentSection, EducationalModule, EducationalResource, ExpertInsight,
-    InteractiveExercise, LearningAchievement, LearningCategory, LearningDifficulty, LearningPath,
-    LearningProgress, ResourceType,
+    AchievementType, AssessmentQuestion, ContentSection, EducationalModule, EducationalResource,
+    ExpertInsight, InteractiveExercise, LearningAchievement, LearningCategory, LearningDifficulty,
+    LearningPath, LearningPathModule, LearningProgress, LearningStatus, QuizScore, ResourceType,
 };
+pub use email::*;
+pub use export_job::*;
+pub use filing_section::*;
 pub use financial_annotation::*;
 pub use financial_line_item::*;
 pub use financial_ratios::*;
 pub use financial_statement::*;
+pub use fx_rate::*;
 pub use global_analysis::*;
+pub use industry_benchmark::*;
+pub use insider_transaction::*;
+pub use institutional_holding::*;
+pub use learning_progress::*;
+pub use notification::*;
+pub use organization::*;
+pub use release_calendar::*;
+pub use saved_chart::*;
 pub use search::*;
+pub use segment_breakdown::*;
+pub use series_alert_rule::*;
+pub use series_link::*;
 pub use series_metadata::*;
+pub use series_quality_score::*;
+pub use series_rollup::*;
 pub use user::{AnnotationComment, ChartAnnotation, ChartCollaborator, NewUser, User, UserSession};
+pub use webhook::*;
+pub use xbrl_calculation_discrepancy::*;
+pub use xbrl_dts::*;
 pub use xbrl_taxonomy_schema::*;
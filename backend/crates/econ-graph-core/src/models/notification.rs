@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::notifications;
+
+/// Event a notification was created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NotificationKind {
+    /// The recipient was assigned to review an annotation
+    AnnotationAssigned,
+    /// An assignment the recipient made or received was accepted, completed or rejected
+    AssignmentUpdated,
+    /// Someone replied to an annotation the recipient wrote or was mentioned in
+    AnnotationReply,
+    /// Someone commented on a chart annotation the recipient follows
+    AnnotationComment,
+    /// An annotation or comment the recipient is involved in was resolved
+    AnnotationResolved,
+    /// One of the recipient's series alert rules fired
+    SeriesAlertTriggered,
+}
+
+impl NotificationKind {
+    /// Value stored in `notifications.notification_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::AnnotationAssigned => "annotation_assigned",
+            NotificationKind::AssignmentUpdated => "assignment_updated",
+            NotificationKind::AnnotationReply => "annotation_reply",
+            NotificationKind::AnnotationComment => "annotation_comment",
+            NotificationKind::AnnotationResolved => "annotation_resolved",
+            NotificationKind::SeriesAlertTriggered => "series_alert_triggered",
+        }
+    }
+}
+
+impl std::fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Kind of record a notification links to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NotificationSubject {
+    ChartAnnotation,
+    FinancialAnnotation,
+    AnnotationAssignment,
+    SeriesAlertRule,
+}
+
+impl NotificationSubject {
+    /// Value stored in `notifications.subject_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationSubject::ChartAnnotation => "chart_annotation",
+            NotificationSubject::FinancialAnnotation => "financial_annotation",
+            NotificationSubject::AnnotationAssignment => "annotation_assignment",
+            NotificationSubject::SeriesAlertRule => "series_alert_rule",
+        }
+    }
+}
+
+/// In-app notification for one recipient
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub notification_type: String,
+    pub subject_type: String,
+    pub subject_id: Uuid,
+    pub title: String,
+    pub message: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New notification for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = notifications)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub notification_type: String,
+    pub subject_type: String,
+    pub subject_id: Uuid,
+    pub title: String,
+    pub message: String,
+}
+
+impl NewNotification {
+    pub fn new(
+        user_id: Uuid,
+        kind: NotificationKind,
+        subject: NotificationSubject,
+        subject_id: Uuid,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            user_id,
+            actor_id: None,
+            notification_type: kind.as_str().to_string(),
+            subject_type: subject.as_str().to_string(),
+            subject_id,
+            title: title.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Record the user whose action triggered the notification
+    pub fn with_actor(mut self, actor_id: Uuid) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl Notification {
+    /// Whether the recipient has read the notification
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+
+    /// Store notifications, returning the created rows
+    pub async fn create_many(
+        pool: &crate::database::DatabasePool,
+        new_notifications: &[NewNotification],
+    ) -> AppResult<Vec<Self>> {
+        if new_notifications.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let created = diesel::insert_into(notifications::table)
+            .values(new_notifications)
+            .returning(Notification::as_returning())
+            .get_results::<Self>(&mut conn)
+            .await?;
+
+        Ok(created)
+    }
+
+    /// A user's notifications, newest first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = notifications::table
+            .filter(notifications::user_id.eq(user_id))
+            .into_boxed();
+        if unread_only {
+            query = query.filter(notifications::read_at.is_null());
+        }
+
+        let notifications = query
+            .order(notifications::created_at.desc())
+            .limit(limit)
+            .offset(offset)
+            .select(Notification::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(notifications)
+    }
+
+    /// Number of unread notifications for a user
+    pub async fn unread_count(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<i64> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let count = notifications::table
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read_at.is_null())
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Mark a user's notifications as read; `None` marks all of them
+    ///
+    /// Returns the number of notifications that were unread.
+    pub async fn mark_read(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        ids: Option<&[Uuid]>,
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = diesel::update(notifications::table)
+            .filter(notifications::user_id.eq(user_id))
+            .filter(notifications::read_at.is_null())
+            .into_boxed();
+        if let Some(ids) = ids {
+            query = query.filter(notifications::id.eq_any(ids));
+        }
+
+        let updated = query
+            .set(notifications::read_at.eq(diesel::dsl::now))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(updated)
+    }
+}
@@ -0,0 +1,532 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::AppError;
+use crate::schema::{organization_chart_shares, organization_members, organizations};
+
+/// Chart permission levels in ascending order of privilege
+/// (same vocabulary as `chart_collaborators.role`)
+pub const CHART_PERMISSION_LEVELS: [&str; 4] = ["view", "comment", "edit", "admin"];
+
+/// Organization (team) that owns memberships and shared charts
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = organizations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New organization for insertion
+#[derive(Debug, Clone, Insertable, Validate, Serialize, Deserialize)]
+#[diesel(table_name = organizations)]
+pub struct NewOrganization {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(min = 1, max = 100))]
+    pub slug: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+/// Membership of a user in an organization
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = organization_members)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OrganizationMember {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New organization member for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = organization_members)]
+pub struct NewOrganizationMember {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+}
+
+/// Chart shared with every member of an organization
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = organization_chart_shares)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OrganizationChartShare {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub chart_id: Uuid,
+    pub shared_by: Option<Uuid>,
+    pub permission_level: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New organization chart share for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = organization_chart_shares)]
+pub struct NewOrganizationChartShare {
+    pub organization_id: Uuid,
+    pub chart_id: Uuid,
+    pub shared_by: Option<Uuid>,
+    pub permission_level: String,
+}
+
+/// Role of a member within an organization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OrganizationRole {
+    Viewer,
+    Member,
+    Admin,
+    Owner,
+}
+
+impl std::fmt::Display for OrganizationRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrganizationRole::Viewer => write!(f, "viewer"),
+            OrganizationRole::Member => write!(f, "member"),
+            OrganizationRole::Admin => write!(f, "admin"),
+            OrganizationRole::Owner => write!(f, "owner"),
+        }
+    }
+}
+
+impl std::str::FromStr for OrganizationRole {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "owner" => Ok(OrganizationRole::Owner),
+            "admin" => Ok(OrganizationRole::Admin),
+            "member" => Ok(OrganizationRole::Member),
+            "viewer" => Ok(OrganizationRole::Viewer),
+            _ => Err(AppError::ValidationError(format!(
+                "Unknown organization role '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+impl OrganizationRole {
+    /// Whether the role may add, remove, or change the role of members
+    pub fn can_manage_members(&self) -> bool {
+        *self >= OrganizationRole::Admin
+    }
+
+    /// Whether the role may share charts and annotations with the organization
+    pub fn can_share(&self) -> bool {
+        *self >= OrganizationRole::Member
+    }
+
+    /// Highest chart permission a member may exercise through an organization share
+    pub fn max_chart_permission(&self) -> &'static str {
+        match self {
+            OrganizationRole::Owner | OrganizationRole::Admin => "admin",
+            OrganizationRole::Member => "edit",
+            OrganizationRole::Viewer => "view",
+        }
+    }
+
+    /// Permission a member gets on a chart shared with the organization at `share_level`
+    pub fn effective_chart_permission(&self, share_level: &str) -> &'static str {
+        let rank = |level: &str| {
+            CHART_PERMISSION_LEVELS
+                .iter()
+                .position(|candidate| candidate.eq_ignore_ascii_case(level))
+                .unwrap_or(0)
+        };
+        let effective = rank(share_level).min(rank(self.max_chart_permission()));
+        CHART_PERMISSION_LEVELS[effective]
+    }
+}
+
+/// Pick the most privileged chart permission from a set of grants
+pub fn highest_chart_permission<'a>(levels: impl Iterator<Item = &'a str>) -> Option<&'static str> {
+    levels
+        .filter_map(|level| {
+            CHART_PERMISSION_LEVELS
+                .iter()
+                .position(|candidate| candidate.eq_ignore_ascii_case(level))
+        })
+        .max()
+        .map(|index| CHART_PERMISSION_LEVELS[index])
+}
+
+impl OrganizationMember {
+    /// Parsed membership role; a stored role that is not recognised grants the least access
+    pub fn organization_role(&self) -> OrganizationRole {
+        self.role.parse().unwrap_or(OrganizationRole::Viewer)
+    }
+}
+
+/// Derive a URL-safe slug from an organization name
+pub fn slugify_organization_name(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').chars().take(100).collect()
+}
+
+fn connection_error(e: impl std::fmt::Display) -> crate::error::AppError {
+    crate::error::AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl Organization {
+    /// Create an organization and make the creator its owner
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_organization: &NewOrganization,
+        owner_id: Uuid,
+    ) -> crate::error::AppResult<Self> {
+        new_organization.validate()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let organization = diesel::insert_into(organizations::table)
+            .values(new_organization)
+            .returning(Organization::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        diesel::insert_into(organization_members::table)
+            .values(&NewOrganizationMember {
+                organization_id: organization.id,
+                user_id: owner_id,
+                role: OrganizationRole::Owner.to_string(),
+                invited_by: None,
+            })
+            .execute(&mut conn)
+            .await?;
+
+        Ok(organization)
+    }
+
+    /// Find an organization by ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> crate::error::AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let organization = organizations::table
+            .filter(organizations::id.eq(id))
+            .select(Organization::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(organization)
+    }
+
+    /// Get all organizations a user belongs to, with the user's membership
+    pub async fn find_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> crate::error::AppResult<Vec<(Self, OrganizationMember)>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let organizations = organizations::table
+            .inner_join(organization_members::table)
+            .filter(organization_members::user_id.eq(user_id))
+            .order(organizations::name.asc())
+            .select((Organization::as_select(), OrganizationMember::as_select()))
+            .load::<(Self, OrganizationMember)>(&mut conn)
+            .await?;
+
+        Ok(organizations)
+    }
+}
+
+impl OrganizationMember {
+    /// Find a user's membership in an organization
+    pub async fn find(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> crate::error::AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let member = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::user_id.eq(user_id))
+            .select(OrganizationMember::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(member)
+    }
+
+    /// Add a member, or change the role of an existing member
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        new_member: &NewOrganizationMember,
+    ) -> crate::error::AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let member = diesel::insert_into(organization_members::table)
+            .values(new_member)
+            .on_conflict((
+                organization_members::organization_id,
+                organization_members::user_id,
+            ))
+            .do_update()
+            .set(organization_members::role.eq(excluded(organization_members::role)))
+            .returning(OrganizationMember::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(member)
+    }
+
+    /// Remove a member from an organization
+    pub async fn remove(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+        user_id: Uuid,
+    ) -> crate::error::AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            organization_members::table
+                .filter(organization_members::organization_id.eq(organization_id))
+                .filter(organization_members::user_id.eq(user_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// List members of an organization
+    pub async fn list_for_organization(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+    ) -> crate::error::AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let members = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .order(organization_members::created_at.asc())
+            .select(OrganizationMember::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(members)
+    }
+
+    /// Count members holding a given role (used to protect the last owner)
+    pub async fn count_with_role(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+        role: OrganizationRole,
+    ) -> crate::error::AppResult<i64> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let count = organization_members::table
+            .filter(organization_members::organization_id.eq(organization_id))
+            .filter(organization_members::role.eq(role.to_string()))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        Ok(count)
+    }
+}
+
+impl OrganizationChartShare {
+    /// Share a chart with an organization, updating the level if already shared
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        new_share: &NewOrganizationChartShare,
+    ) -> crate::error::AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let share = diesel::insert_into(organization_chart_shares::table)
+            .values(new_share)
+            .on_conflict((
+                organization_chart_shares::organization_id,
+                organization_chart_shares::chart_id,
+            ))
+            .do_update()
+            .set((
+                organization_chart_shares::permission_level
+                    .eq(excluded(organization_chart_shares::permission_level)),
+                organization_chart_shares::shared_by
+                    .eq(excluded(organization_chart_shares::shared_by)),
+            ))
+            .returning(OrganizationChartShare::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(share)
+    }
+
+    /// Stop sharing a chart with an organization
+    pub async fn remove(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+        chart_id: Uuid,
+    ) -> crate::error::AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            organization_chart_shares::table
+                .filter(organization_chart_shares::organization_id.eq(organization_id))
+                .filter(organization_chart_shares::chart_id.eq(chart_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// List charts shared with an organization
+    pub async fn list_for_organization(
+        pool: &crate::database::DatabasePool,
+        organization_id: Uuid,
+    ) -> crate::error::AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let shares = organization_chart_shares::table
+            .filter(organization_chart_shares::organization_id.eq(organization_id))
+            .order(organization_chart_shares::created_at.desc())
+            .select(OrganizationChartShare::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(shares)
+    }
+
+    /// Shares of a chart that reach a user through their organization memberships
+    pub async fn find_for_user_chart(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        chart_id: Uuid,
+    ) -> crate::error::AppResult<Vec<(Self, OrganizationMember)>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let shares = organization_chart_shares::table
+            .inner_join(
+                organization_members::table.on(organization_members::organization_id
+                    .eq(organization_chart_shares::organization_id)),
+            )
+            .filter(organization_chart_shares::chart_id.eq(chart_id))
+            .filter(organization_members::user_id.eq(user_id))
+            .select((
+                OrganizationChartShare::as_select(),
+                OrganizationMember::as_select(),
+            ))
+            .load::<(Self, OrganizationMember)>(&mut conn)
+            .await?;
+
+        Ok(shares)
+    }
+
+    /// Highest chart permission a user holds through organization shares, if any
+    ///
+    /// Each share is capped by the user's role in the organization it was shared with.
+    pub async fn permission_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        chart_id: Uuid,
+    ) -> crate::error::AppResult<Option<&'static str>> {
+        let shares = Self::find_for_user_chart(pool, user_id, chart_id).await?;
+
+        Ok(highest_chart_permission(shares.iter().map(
+            |(share, member)| {
+                member
+                    .organization_role()
+                    .effective_chart_permission(&share.permission_level)
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_organization_role_capabilities() {
+        // REQUIREMENT: Organizations manage access collectively through membership roles
+        // PURPOSE: Verify role parsing and the capabilities attached to each role
+        // This ensures only owners and admins can manage membership
+
+        assert_eq!(
+            "OWNER".parse::<OrganizationRole>().unwrap(),
+            OrganizationRole::Owner
+        );
+        assert!(matches!(
+            "admn".parse::<OrganizationRole>(),
+            Err(AppError::ValidationError(_))
+        ));
+        assert_eq!(OrganizationRole::Admin.to_string(), "admin");
+
+        assert!(OrganizationRole::Owner.can_manage_members());
+        assert!(OrganizationRole::Admin.can_manage_members());
+        assert!(!OrganizationRole::Member.can_manage_members());
+        assert!(OrganizationRole::Member.can_share());
+        assert!(!OrganizationRole::Viewer.can_share());
+    }
+
+    #[test]
+    fn test_effective_chart_permission_is_capped_by_role() {
+        // REQUIREMENT: Org-scoped chart sharing must respect membership roles
+        // PURPOSE: Verify a member never gets more than their role allows on a shared chart
+        // This ensures viewers stay read-only even on charts shared with edit access
+
+        assert_eq!(
+            OrganizationRole::Viewer.effective_chart_permission("edit"),
+            "view"
+        );
+        assert_eq!(
+            OrganizationRole::Member.effective_chart_permission("admin"),
+            "edit"
+        );
+        assert_eq!(
+            OrganizationRole::Member.effective_chart_permission("comment"),
+            "comment"
+        );
+        assert_eq!(
+            OrganizationRole::Owner.effective_chart_permission("admin"),
+            "admin"
+        );
+    }
+
+    #[test]
+    fn test_slugify_organization_name() {
+        // REQUIREMENT: Organizations are addressable by a unique slug
+        // PURPOSE: Verify slugs are derived from names when none is provided
+        // This ensures generated slugs are URL-safe
+
+        assert_eq!(
+            slugify_organization_name("  Acme Research & Analytics "),
+            "acme-research-analytics"
+        );
+        assert_eq!(slugify_organization_name("Fed/Board"), "fed-board");
+    }
+}
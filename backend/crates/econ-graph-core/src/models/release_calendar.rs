@@ -0,0 +1,760 @@
+//! Release calendars of data sources and series
+//!
+//! Macro data is published on known schedules: CPI monthly around the 12th at
+//! 8:30 ET, the employment report on the first Friday of the month. A calendar
+//! describes such a schedule as a [`ReleaseRule`] in the publisher's time zone,
+//! either for one series or for every series of a source with the calendar's
+//! frequency. The release scheduler queues crawls right after each expected
+//! release; calendars of series without one are learned from when their data
+//! arrived (`is_learned`), and never replace a calendar an admin entered.
+//!
+//! Rules work on local date-times. Converting to and from the calendar's time
+//! zone is left to PostgreSQL, which knows the daylight saving rules.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use diesel::prelude::*;
+use diesel::sql_types::{Text, Timestamp, Timestamptz};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::release_calendars;
+
+/// Time zone of calendars that do not name one
+pub const DEFAULT_RELEASE_TIME_ZONE: &str = "America/New_York";
+
+/// Default minutes between a release and its crawl
+pub const DEFAULT_RELEASE_CRAWL_DELAY_MINUTES: i32 = 5;
+
+/// How often data is released
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReleaseFrequency {
+    /// Every weekday
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl ReleaseFrequency {
+    /// Value stored in `release_calendars.frequency`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseFrequency::Daily => "daily",
+            ReleaseFrequency::Weekly => "weekly",
+            ReleaseFrequency::Monthly => "monthly",
+            ReleaseFrequency::Quarterly => "quarterly",
+            ReleaseFrequency::Annual => "annual",
+        }
+    }
+
+    /// Frequency of a series from its `frequency` column, e.g. `Weekly, Ending Friday`
+    pub fn from_series_frequency(frequency: &str) -> Option<Self> {
+        let frequency = frequency.trim().to_lowercase();
+        [
+            ReleaseFrequency::Daily,
+            ReleaseFrequency::Weekly,
+            ReleaseFrequency::Monthly,
+            ReleaseFrequency::Quarterly,
+            ReleaseFrequency::Annual,
+        ]
+        .into_iter()
+        .find(|candidate| frequency.starts_with(candidate.as_str()))
+    }
+
+    /// Months between releases, for monthly and longer frequencies
+    fn months(&self) -> Option<u32> {
+        match self {
+            ReleaseFrequency::Daily | ReleaseFrequency::Weekly => None,
+            ReleaseFrequency::Monthly => Some(1),
+            ReleaseFrequency::Quarterly => Some(3),
+            ReleaseFrequency::Annual => Some(12),
+        }
+    }
+}
+
+impl std::str::FromStr for ReleaseFrequency {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(ReleaseFrequency::Daily),
+            "weekly" => Ok(ReleaseFrequency::Weekly),
+            "monthly" => Ok(ReleaseFrequency::Monthly),
+            "quarterly" => Ok(ReleaseFrequency::Quarterly),
+            "annual" => Ok(ReleaseFrequency::Annual),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown release frequency '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// ISO weekday number, 1 = Monday
+pub fn iso_weekday(weekday: Weekday) -> i16 {
+    weekday.number_from_monday() as i16
+}
+
+fn weekday_from_iso(day: i16) -> Option<Weekday> {
+    match day {
+        1 => Some(Weekday::Mon),
+        2 => Some(Weekday::Tue),
+        3 => Some(Weekday::Wed),
+        4 => Some(Weekday::Thu),
+        5 => Some(Weekday::Fri),
+        6 => Some(Weekday::Sat),
+        7 => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// When releases happen, in local time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReleaseRule {
+    pub frequency: ReleaseFrequency,
+    /// Release day of weekly rules, or with `week_of_month` the n-th weekday of the month
+    pub day_of_week: Option<Weekday>,
+    /// 1-4, or 5 for the last `day_of_week` of the month
+    pub week_of_month: Option<u32>,
+    /// Release day; months without it release on their last day
+    pub day_of_month: Option<u32>,
+    /// Month within the quarter (1-3) or year (1-12)
+    pub month_of_period: u32,
+    pub release_time: NaiveTime,
+}
+
+impl ReleaseRule {
+    /// Check that the rule names a release day for its frequency
+    pub fn check(&self) -> AppResult<()> {
+        let invalid = |message: &str| Err(AppError::ValidationError(message.to_string()));
+        match self.frequency {
+            ReleaseFrequency::Daily => Ok(()),
+            ReleaseFrequency::Weekly if self.day_of_week.is_none() => {
+                invalid("Weekly release calendars need a day of the week")
+            }
+            ReleaseFrequency::Weekly => Ok(()),
+            _ if self.day_of_month.is_some() == self.week_of_month.is_some() => {
+                invalid("Release calendars need either a day of the month or a week of the month")
+            }
+            _ if self.week_of_month.is_some() && self.day_of_week.is_none() => {
+                invalid("A week of the month needs a day of the week")
+            }
+            ReleaseFrequency::Quarterly if !(1..=3).contains(&self.month_of_period) => {
+                invalid("Quarterly releases happen in month 1, 2 or 3 of the quarter")
+            }
+            _ if !(1..=12).contains(&self.month_of_period) => {
+                invalid("The month of the period must be from 1 to 12")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// First release strictly after `after`
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let Some(months) = self.frequency.months() else {
+            // A week always holds the next daily or weekly release
+            return (0..=7)
+                .map(|days| after.date() + Duration::days(days))
+                .filter(|date| self.releases_on(date.weekday()))
+                .map(|date| date.and_time(self.release_time))
+                .find(|at| *at > after);
+        };
+
+        let first = after.date().with_day(1)?;
+        (0..=months + 1)
+            .filter_map(|offset| add_months(first, offset))
+            .filter(|month| (month.month0() + 12 - (self.month_of_period - 1)) % months == 0)
+            .filter_map(|month| self.release_day(month))
+            .map(|date| date.and_time(self.release_time))
+            .find(|at| *at > after)
+    }
+
+    fn releases_on(&self, weekday: Weekday) -> bool {
+        match self.frequency {
+            ReleaseFrequency::Weekly => self.day_of_week == Some(weekday),
+            _ => !matches!(weekday, Weekday::Sat | Weekday::Sun),
+        }
+    }
+
+    /// Release date in the month starting on `first`
+    fn release_day(&self, first: NaiveDate) -> Option<NaiveDate> {
+        let last = add_months(first, 1)?.pred_opt()?;
+        if let Some(day) = self.day_of_month {
+            return first.with_day(day.min(last.day()));
+        }
+
+        let weekday = self.day_of_week?;
+        let week = self.week_of_month?;
+        if week >= 5 {
+            let back =
+                (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+            return Some(last - Duration::days(i64::from(back)));
+        }
+        let ahead =
+            (weekday.num_days_from_monday() + 7 - first.weekday().num_days_from_monday()) % 7;
+        Some(first + Duration::days(i64::from(ahead + 7 * (week - 1))))
+    }
+}
+
+fn add_months(first: NaiveDate, months: u32) -> Option<NaiveDate> {
+    first.checked_add_months(chrono::Months::new(months))
+}
+
+/// A release schedule of a data source or series
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = release_calendars)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ReleaseCalendar {
+    pub id: Uuid,
+    /// Set for calendars covering every series of a source with the frequency
+    pub source_id: Option<Uuid>,
+    /// Set for calendars of one series, which take precedence over the source's
+    pub series_id: Option<Uuid>,
+    pub frequency: String,
+    /// ISO weekday, 1 = Monday
+    pub day_of_week: Option<i16>,
+    pub week_of_month: Option<i16>,
+    pub day_of_month: Option<i16>,
+    pub month_of_period: i16,
+    /// Local time of the release in `time_zone`
+    pub release_time: NaiveTime,
+    pub time_zone: String,
+    pub crawl_delay_minutes: i32,
+    /// Learned from data arrivals rather than entered by an admin
+    pub is_learned: bool,
+    /// Share of past releases that fit a learned calendar
+    pub confidence: Option<f64>,
+    pub sample_count: i32,
+    pub is_active: bool,
+    pub next_release_at: Option<DateTime<Utc>>,
+    /// Release whose crawls were queued last
+    pub last_queued_release_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New release calendar for insertion
+#[derive(Debug, Clone, Insertable, Validate, Serialize, Deserialize)]
+#[diesel(table_name = release_calendars)]
+pub struct NewReleaseCalendar {
+    pub source_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub frequency: String,
+    #[validate(range(min = 1, max = 7))]
+    pub day_of_week: Option<i16>,
+    #[validate(range(min = 1, max = 5))]
+    pub week_of_month: Option<i16>,
+    #[validate(range(min = 1, max = 31))]
+    pub day_of_month: Option<i16>,
+    #[validate(range(min = 1, max = 12))]
+    pub month_of_period: i16,
+    pub release_time: NaiveTime,
+    #[validate(length(min = 1, max = 50))]
+    pub time_zone: String,
+    #[validate(range(min = 0, max = 1440))]
+    pub crawl_delay_minutes: i32,
+}
+
+impl Default for NewReleaseCalendar {
+    fn default() -> Self {
+        Self {
+            source_id: None,
+            series_id: None,
+            frequency: ReleaseFrequency::Monthly.as_str().to_string(),
+            day_of_week: None,
+            week_of_month: None,
+            day_of_month: None,
+            month_of_period: 1,
+            release_time: NaiveTime::from_hms_opt(8, 30, 0).unwrap_or_default(),
+            time_zone: DEFAULT_RELEASE_TIME_ZONE.to_string(),
+            crawl_delay_minutes: DEFAULT_RELEASE_CRAWL_DELAY_MINUTES,
+        }
+    }
+}
+
+/// Rule from calendar columns
+fn rule_from_columns(
+    frequency: &str,
+    day_of_week: Option<i16>,
+    week_of_month: Option<i16>,
+    day_of_month: Option<i16>,
+    month_of_period: i16,
+    release_time: NaiveTime,
+) -> AppResult<ReleaseRule> {
+    let day_of_week = match day_of_week {
+        Some(day) => Some(
+            weekday_from_iso(day)
+                .ok_or_else(|| AppError::ValidationError(format!("Invalid ISO weekday {}", day)))?,
+        ),
+        None => None,
+    };
+
+    Ok(ReleaseRule {
+        frequency: frequency.parse()?,
+        day_of_week,
+        week_of_month: week_of_month.map(|week| week.max(1) as u32),
+        day_of_month: day_of_month.map(|day| day.max(1) as u32),
+        month_of_period: month_of_period.max(1) as u32,
+        release_time,
+    })
+}
+
+impl NewReleaseCalendar {
+    /// Check what the derived validation cannot: scope and release day
+    pub fn validate_schedule(&self) -> AppResult<ReleaseRule> {
+        self.validate()?;
+
+        if self.source_id.is_some() == self.series_id.is_some() {
+            return Err(AppError::ValidationError(
+                "A release calendar must cover either a data source or a series".to_string(),
+            ));
+        }
+        let rule = rule_from_columns(
+            &self.frequency,
+            self.day_of_week,
+            self.week_of_month,
+            self.day_of_month,
+            self.month_of_period,
+            self.release_time,
+        )?;
+        rule.check()?;
+
+        Ok(rule)
+    }
+}
+
+/// A learned schedule for a series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LearnedRelease {
+    pub rule: ReleaseRule,
+    /// Share of past releases that fit the rule
+    pub confidence: f64,
+    /// Releases the rule was inferred from
+    pub sample_count: i32,
+}
+
+#[derive(QueryableByName)]
+struct LocalTime {
+    #[diesel(sql_type = Timestamp)]
+    local: NaiveDateTime,
+}
+
+#[derive(QueryableByName)]
+struct UtcTime {
+    #[diesel(sql_type = Timestamptz)]
+    utc: DateTime<Utc>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+/// `at` as local time in `time_zone`
+async fn to_local(
+    conn: &mut AsyncPgConnection,
+    at: DateTime<Utc>,
+    time_zone: &str,
+) -> AppResult<NaiveDateTime> {
+    let row = diesel::sql_query("SELECT ($1 AT TIME ZONE $2) AS local")
+        .bind::<Timestamptz, _>(at)
+        .bind::<Text, _>(time_zone)
+        .get_result::<LocalTime>(conn)
+        .await
+        .map_err(|e| {
+            AppError::ValidationError(format!("Invalid time zone '{}': {}", time_zone, e))
+        })?;
+
+    Ok(row.local)
+}
+
+/// Local time `local` in `time_zone` as an instant
+async fn to_utc(
+    conn: &mut AsyncPgConnection,
+    local: NaiveDateTime,
+    time_zone: &str,
+) -> AppResult<DateTime<Utc>> {
+    let row = diesel::sql_query("SELECT ($1 AT TIME ZONE $2) AS utc")
+        .bind::<Timestamp, _>(local)
+        .bind::<Text, _>(time_zone)
+        .get_result::<UtcTime>(conn)
+        .await?;
+
+    Ok(row.utc)
+}
+
+/// First release of `rule` after `after`, with the rule read in `time_zone`
+async fn next_release(
+    conn: &mut AsyncPgConnection,
+    rule: &ReleaseRule,
+    time_zone: &str,
+    after: DateTime<Utc>,
+) -> AppResult<Option<DateTime<Utc>>> {
+    let local = to_local(conn, after, time_zone).await?;
+    match rule.next_after(local) {
+        Some(next) => Ok(Some(to_utc(conn, next, time_zone).await?)),
+        None => Ok(None),
+    }
+}
+
+impl ReleaseCalendar {
+    /// The calendar's schedule
+    pub fn rule(&self) -> AppResult<ReleaseRule> {
+        rule_from_columns(
+            &self.frequency,
+            self.day_of_week,
+            self.week_of_month,
+            self.day_of_month,
+            self.month_of_period,
+            self.release_time,
+        )
+    }
+
+    /// When crawls of a release should run
+    pub fn crawl_at(&self, release_at: DateTime<Utc>) -> DateTime<Utc> {
+        release_at + Duration::minutes(i64::from(self.crawl_delay_minutes))
+    }
+
+    /// Create a calendar, with its next release computed from now
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_calendar: &NewReleaseCalendar,
+    ) -> AppResult<Self> {
+        let rule = new_calendar.validate_schedule()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let next_release_at =
+            next_release(&mut conn, &rule, &new_calendar.time_zone, Utc::now()).await?;
+
+        let calendar = diesel::insert_into(release_calendars::table)
+            .values((
+                new_calendar,
+                release_calendars::next_release_at.eq(next_release_at),
+            ))
+            .returning(ReleaseCalendar::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(calendar)
+    }
+
+    /// All calendars, optionally of one data source or series, by next release
+    pub async fn list(
+        pool: &crate::database::DatabasePool,
+        source_id: Option<Uuid>,
+        series_id: Option<Uuid>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = release_calendars::table.into_boxed();
+        if let Some(source_id) = source_id {
+            query = query.filter(release_calendars::source_id.eq(source_id));
+        }
+        if let Some(series_id) = series_id {
+            query = query.filter(release_calendars::series_id.eq(series_id));
+        }
+
+        let calendars = query
+            .order(release_calendars::next_release_at.asc().nulls_last())
+            .select(ReleaseCalendar::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(calendars)
+    }
+
+    /// The calendar of a series, if it has its own
+    pub async fn find_for_series(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let calendar = release_calendars::table
+            .filter(release_calendars::series_id.eq(series_id))
+            .select(ReleaseCalendar::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(calendar)
+    }
+
+    /// Active calendars with a release before `until`, soonest first
+    pub async fn due(
+        pool: &crate::database::DatabasePool,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let calendars = release_calendars::table
+            .filter(release_calendars::is_active.eq(true))
+            .filter(release_calendars::next_release_at.le(until))
+            .order(release_calendars::next_release_at.asc())
+            .select(ReleaseCalendar::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(calendars)
+    }
+
+    /// Record that crawls of the next release were queued and move on to the one after
+    ///
+    /// Releases missed while the scheduler was down are skipped rather than
+    /// queued one by one.
+    pub async fn advance(&self, pool: &crate::database::DatabasePool) -> AppResult<Self> {
+        let Some(queued) = self.next_release_at else {
+            return Err(AppError::ValidationError(format!(
+                "Release calendar {} has no upcoming release",
+                self.id
+            )));
+        };
+        let rule = self.rule()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let next_release_at =
+            next_release(&mut conn, &rule, &self.time_zone, queued.max(Utc::now())).await?;
+
+        let calendar = diesel::update(release_calendars::table.find(self.id))
+            .set((
+                release_calendars::last_queued_release_at.eq(Some(queued)),
+                release_calendars::next_release_at.eq(next_release_at),
+            ))
+            .returning(ReleaseCalendar::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(calendar)
+    }
+
+    /// Store the calendar learned for a series
+    ///
+    /// Replaces an earlier learned calendar; a calendar an admin entered is
+    /// kept and `None` is returned.
+    pub async fn record_learned(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+        learned: &LearnedRelease,
+    ) -> AppResult<Option<Self>> {
+        let existing = Self::find_for_series(pool, series_id).await?;
+        if existing
+            .as_ref()
+            .is_some_and(|calendar| !calendar.is_learned)
+        {
+            return Ok(None);
+        }
+
+        let rule = &learned.rule;
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let time_zone = existing
+            .as_ref()
+            .map_or(DEFAULT_RELEASE_TIME_ZONE, |calendar| &calendar.time_zone)
+            .to_string();
+        let next_release_at = next_release(&mut conn, rule, &time_zone, Utc::now()).await?;
+
+        let values = (
+            release_calendars::frequency.eq(rule.frequency.as_str()),
+            release_calendars::day_of_week.eq(rule.day_of_week.map(iso_weekday)),
+            release_calendars::week_of_month.eq(rule.week_of_month.map(|week| week as i16)),
+            release_calendars::day_of_month.eq(rule.day_of_month.map(|day| day as i16)),
+            release_calendars::month_of_period.eq(rule.month_of_period as i16),
+            release_calendars::release_time.eq(rule.release_time),
+            release_calendars::confidence.eq(Some(learned.confidence)),
+            release_calendars::sample_count.eq(learned.sample_count),
+            release_calendars::next_release_at.eq(next_release_at),
+        );
+
+        let calendar = match existing {
+            Some(calendar) => {
+                diesel::update(release_calendars::table.find(calendar.id))
+                    .set(values)
+                    .returning(ReleaseCalendar::as_returning())
+                    .get_result::<Self>(&mut conn)
+                    .await?
+            }
+            None => {
+                diesel::insert_into(release_calendars::table)
+                    .values((
+                        release_calendars::series_id.eq(Some(series_id)),
+                        release_calendars::time_zone.eq(&time_zone),
+                        release_calendars::is_learned.eq(true),
+                        values,
+                    ))
+                    .returning(ReleaseCalendar::as_returning())
+                    .get_result::<Self>(&mut conn)
+                    .await?
+            }
+        };
+
+        Ok(Some(calendar))
+    }
+
+    /// Pause or resume a calendar
+    ///
+    /// A resumed calendar continues with the next release from now, not with
+    /// the ones missed while it was paused.
+    pub async fn set_active(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        is_active: bool,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let calendar = release_calendars::table
+            .find(id)
+            .select(ReleaseCalendar::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound(format!("Release calendar {} not found", id)))?;
+        let next_release_at = match is_active {
+            true => {
+                next_release(
+                    &mut conn,
+                    &calendar.rule()?,
+                    &calendar.time_zone,
+                    Utc::now(),
+                )
+                .await?
+            }
+            false => calendar.next_release_at,
+        };
+
+        let calendar = diesel::update(release_calendars::table.find(id))
+            .set((
+                release_calendars::is_active.eq(is_active),
+                release_calendars::next_release_at.eq(next_release_at),
+            ))
+            .returning(ReleaseCalendar::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(calendar)
+    }
+
+    /// Delete a calendar
+    pub async fn delete(pool: &crate::database::DatabasePool, id: Uuid) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(release_calendars::table.find(id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn rule(frequency: ReleaseFrequency) -> ReleaseRule {
+        ReleaseRule {
+            frequency,
+            day_of_week: None,
+            week_of_month: None,
+            day_of_month: None,
+            month_of_period: 1,
+            release_time: NaiveTime::from_hms_opt(8, 30, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_release_rules_find_the_next_release() {
+        // REQUIREMENT: Crawls are scheduled right after the known release times of macro data
+        // PURPOSE: Verify monthly, n-th weekday, quarterly, weekly and daily rules give the next local release after a time
+        // This ensures CPI, the employment report and GDP crawls are queued for the right day and time
+
+        // CPI: the 12th at 8:30
+        let cpi = ReleaseRule {
+            day_of_month: Some(12),
+            ..rule(ReleaseFrequency::Monthly)
+        };
+        assert!(cpi.check().is_ok());
+        assert_eq!(
+            cpi.next_after(at("2024-03-12", "08:29")),
+            Some(at("2024-03-12", "08:30"))
+        );
+        assert_eq!(
+            cpi.next_after(at("2024-03-12", "08:30")),
+            Some(at("2024-04-12", "08:30"))
+        );
+
+        // Days past the end of the month fall on its last day
+        let month_end = ReleaseRule {
+            day_of_month: Some(31),
+            ..rule(ReleaseFrequency::Monthly)
+        };
+        assert_eq!(
+            month_end.next_after(at("2024-02-01", "00:00")),
+            Some(at("2024-02-29", "08:30"))
+        );
+
+        // Employment report: the first Friday
+        let jobs = ReleaseRule {
+            day_of_week: Some(Weekday::Fri),
+            week_of_month: Some(1),
+            ..rule(ReleaseFrequency::Monthly)
+        };
+        assert_eq!(
+            jobs.next_after(at("2024-03-08", "09:00")),
+            Some(at("2024-04-05", "08:30"))
+        );
+        let last_wednesday = ReleaseRule {
+            day_of_week: Some(Weekday::Wed),
+            week_of_month: Some(5),
+            ..rule(ReleaseFrequency::Monthly)
+        };
+        assert_eq!(
+            last_wednesday.next_after(at("2024-05-01", "00:00")),
+            Some(at("2024-05-29", "08:30"))
+        );
+
+        // GDP: the 30th of the first month of each quarter
+        let gdp = ReleaseRule {
+            day_of_month: Some(30),
+            ..rule(ReleaseFrequency::Quarterly)
+        };
+        assert_eq!(
+            gdp.next_after(at("2024-05-15", "00:00")),
+            Some(at("2024-07-30", "08:30"))
+        );
+
+        // Jobless claims: Thursdays; daily releases skip weekends
+        let claims = ReleaseRule {
+            day_of_week: Some(Weekday::Thu),
+            ..rule(ReleaseFrequency::Weekly)
+        };
+        assert_eq!(
+            claims.next_after(at("2024-03-14", "08:30")),
+            Some(at("2024-03-21", "08:30"))
+        );
+        assert_eq!(
+            rule(ReleaseFrequency::Daily).next_after(at("2024-03-15", "12:00")),
+            Some(at("2024-03-18", "08:30"))
+        );
+
+        // A monthly rule needs exactly one way of naming the day
+        assert!(rule(ReleaseFrequency::Monthly).check().is_err());
+        assert!(ReleaseRule {
+            day_of_month: Some(12),
+            month_of_period: 4,
+            ..rule(ReleaseFrequency::Quarterly)
+        }
+        .check()
+        .is_err());
+    }
+}
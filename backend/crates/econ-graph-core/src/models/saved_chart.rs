@@ -0,0 +1,301 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::saved_charts;
+
+/// Maximum number of series a single saved chart may plot
+pub const MAX_SAVED_CHART_SERIES: u64 = 50;
+
+/// Chart configuration persisted for a user
+///
+/// `chart_config` is opaque to the backend and owned by the frontend
+/// (chart type, colors, axes). `transformations` maps series IDs to the
+/// transformation applied to that series. Public charts can be rendered as
+/// embeddable images by anyone who knows their ID.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = saved_charts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SavedChart {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub chart_config: serde_json::Value,
+    pub series_ids: Vec<Uuid>,
+    pub transformations: serde_json::Value,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub is_public: bool,
+}
+
+/// New saved chart for insertion
+#[derive(Debug, Clone, Insertable, Validate, Serialize, Deserialize)]
+#[diesel(table_name = saved_charts)]
+pub struct NewSavedChart {
+    pub user_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    pub chart_config: serde_json::Value,
+    #[validate(length(max = MAX_SAVED_CHART_SERIES))]
+    pub series_ids: Vec<Uuid>,
+    pub transformations: serde_json::Value,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub is_public: bool,
+}
+
+/// Partial update of a saved chart; `None` leaves a field unchanged
+#[derive(Debug, Clone, Default, AsChangeset, Validate, Serialize, Deserialize)]
+#[diesel(table_name = saved_charts)]
+pub struct UpdateSavedChart {
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    pub description: Option<Option<String>>,
+    pub chart_config: Option<serde_json::Value>,
+    #[validate(length(max = MAX_SAVED_CHART_SERIES))]
+    pub series_ids: Option<Vec<Uuid>>,
+    pub transformations: Option<serde_json::Value>,
+    pub start_date: Option<Option<NaiveDate>>,
+    pub end_date: Option<Option<NaiveDate>>,
+    pub is_public: Option<bool>,
+}
+
+impl UpdateSavedChart {
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.chart_config.is_none()
+            && self.series_ids.is_none()
+            && self.transformations.is_none()
+            && self.start_date.is_none()
+            && self.end_date.is_none()
+            && self.is_public.is_none()
+    }
+}
+
+/// Check that a chart's date range and transformations are well formed
+pub fn validate_saved_chart_fields(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    transformations: &serde_json::Value,
+) -> AppResult<()> {
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if start > end {
+            return Err(AppError::ValidationError(
+                "Chart start date must not be after end date".to_string(),
+            ));
+        }
+    }
+
+    if !transformations.is_object() {
+        return Err(AppError::ValidationError(
+            "Chart transformations must be an object keyed by series ID".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SavedChart {
+    /// Save a new chart for a user
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_chart: &NewSavedChart,
+    ) -> AppResult<Self> {
+        new_chart.validate()?;
+        validate_saved_chart_fields(
+            new_chart.start_date,
+            new_chart.end_date,
+            &new_chart.transformations,
+        )?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let chart = diesel::insert_into(saved_charts::table)
+            .values(new_chart)
+            .returning(SavedChart::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(chart)
+    }
+
+    /// Find a saved chart owned by a user
+    pub async fn find_for_user(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let chart = saved_charts::table
+            .filter(saved_charts::id.eq(id))
+            .filter(saved_charts::user_id.eq(user_id))
+            .select(SavedChart::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(chart)
+    }
+
+    /// Find a saved chart by ID
+    ///
+    /// Does not check access; callers check the user's permission on the chart
+    /// (owner, collaborator or organization share) first.
+    pub async fn find(pool: &crate::database::DatabasePool, id: Uuid) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let chart = saved_charts::table
+            .filter(saved_charts::id.eq(id))
+            .select(SavedChart::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(chart)
+    }
+
+    /// Find a chart its owner has made public for embedding
+    pub async fn find_public(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let chart = saved_charts::table
+            .filter(saved_charts::id.eq(id))
+            .filter(saved_charts::is_public.eq(true))
+            .select(SavedChart::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(chart)
+    }
+
+    /// List a user's saved charts, most recently updated first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let charts = saved_charts::table
+            .filter(saved_charts::user_id.eq(user_id))
+            .order(saved_charts::updated_at.desc())
+            .select(SavedChart::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(charts)
+    }
+
+    /// Apply a partial update to a saved chart
+    ///
+    /// Returns `None` when the chart does not exist. Like [`Self::find`], this
+    /// does not check access; callers check the user may edit the chart first.
+    pub async fn update(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        changes: &UpdateSavedChart,
+    ) -> AppResult<Option<Self>> {
+        changes.validate()?;
+
+        let Some(existing) = Self::find(pool, id).await? else {
+            return Ok(None);
+        };
+
+        if changes.is_empty() {
+            return Ok(Some(existing));
+        }
+
+        validate_saved_chart_fields(
+            changes.start_date.unwrap_or(existing.start_date),
+            changes.end_date.unwrap_or(existing.end_date),
+            changes
+                .transformations
+                .as_ref()
+                .unwrap_or(&existing.transformations),
+        )?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let chart = diesel::update(saved_charts::table.filter(saved_charts::id.eq(id)))
+            .set(changes)
+            .returning(SavedChart::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(chart)
+    }
+
+    /// Delete a chart owned by a user
+    pub async fn delete_for_user(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            saved_charts::table
+                .filter(saved_charts::id.eq(id))
+                .filter(saved_charts::user_id.eq(user_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_saved_chart_fields() {
+        // REQUIREMENT: Saved dashboards must reload into a valid chart
+        // PURPOSE: Verify inverted date ranges and malformed transformations are rejected
+        // This ensures the frontend never receives a chart it cannot render
+
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1);
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31);
+        let transformations = serde_json::json!({});
+
+        assert!(validate_saved_chart_fields(start, end, &transformations).is_ok());
+        assert!(validate_saved_chart_fields(None, end, &transformations).is_ok());
+        assert!(validate_saved_chart_fields(end, start, &transformations).is_err());
+        assert!(validate_saved_chart_fields(start, end, &serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn test_update_saved_chart_is_empty() {
+        // REQUIREMENT: Partial chart updates from the frontend
+        // PURPOSE: Verify an update with no fields is detected before hitting the database
+        // This ensures empty updates return the chart instead of a query builder error
+
+        assert!(UpdateSavedChart::default().is_empty());
+        assert!(!UpdateSavedChart {
+            start_date: Some(None),
+            ..Default::default()
+        }
+        .is_empty());
+    }
+}
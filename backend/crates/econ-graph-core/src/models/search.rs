@@ -25,6 +25,47 @@ pub struct SeriesSearchResult {
     pub rank: f32,
     /// Trigram similarity score for spelling correction (0.0 to 1.0)
     pub similarity_score: f32,
+    /// HTML-escaped title with matched words wrapped in `<mark>`
+    pub title_highlight: String,
+    /// HTML-escaped fragments of the description around matched words, marked the same way
+    pub description_highlight: Option<String>,
+}
+
+/// Search result for a company with its full-text ranking score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanySearchResult {
+    pub id: Uuid,
+    pub cik: String,
+    pub ticker: Option<String>,
+    pub name: String,
+    pub industry: Option<String>,
+    pub sector: Option<String>,
+    pub is_active: bool,
+    /// Full-text search ranking score (higher is better; exact ticker matches rank first)
+    pub rank: f32,
+}
+
+/// Number of matching series sharing one value of a facet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FacetCount {
+    /// Value to filter on, e.g. a data source ID or "Monthly"
+    pub value: String,
+    /// Value as shown to users, e.g. the data source's name
+    pub label: String,
+    pub count: i64,
+}
+
+/// Counts of matching series per source, frequency, units and geography
+///
+/// Each facet applies every filter of the search except its own, so a
+/// faceted UI can show how many results the other values of a facet hold.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFacets {
+    pub sources: Vec<FacetCount>,
+    pub frequencies: Vec<FacetCount>,
+    pub units: Vec<FacetCount>,
+    /// Covered areas of series with enriched catalog metadata
+    pub geographies: Vec<FacetCount>,
 }
 
 /// Search parameters for economic series
@@ -60,6 +101,12 @@ pub struct SearchParams {
     /// Filter by series frequency
     pub frequency: Option<String>,
 
+    /// Filter by covered area, e.g. "California" (from enriched series metadata)
+    pub geography: Option<String>,
+
+    /// Filter by catalog category or topic tag (from enriched series metadata)
+    pub topic: Option<String>,
+
     /// Include inactive series in results
     pub include_inactive: Option<bool>,
 
@@ -89,6 +136,8 @@ impl Default for SearchParams {
             offset: Some(0),
             source_id: None,
             frequency: None,
+            geography: None,
+            topic: None,
             include_inactive: Some(false),
             sort_by: Some(SearchSortOrder::Relevance),
         }
@@ -340,6 +389,8 @@ mod _inline_tests {
             is_active: true,
             rank: 0.85,
             similarity_score: 0.92,
+            title_highlight: "<mark>Real</mark> GDP".to_string(),
+            description_highlight: None,
         };
 
         // Verify all fields are accessible
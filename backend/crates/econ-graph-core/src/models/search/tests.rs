@@ -29,6 +29,8 @@ mod simple_tests {
             offset: Some(0),
             source_id: None,
             frequency: None,
+            geography: None,
+            topic: None,
             include_inactive: Some(false),
             sort_by: Some(SearchSortOrder::Relevance),
         };
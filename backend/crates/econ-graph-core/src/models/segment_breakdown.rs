@@ -0,0 +1,214 @@
+//! Segment breakdowns of financial line items
+//!
+//! Filings break totals down along dimensions (axes) such as business segments
+//! or geographies: a fact whose context has the explicit member
+//! `aapl:IPhoneMember` on `us-gaap:StatementBusinessSegmentsAxis` is the iPhone
+//! share of the concept. A breakdown collects, for one concept and axis, the
+//! facts of each member next to the undimensioned total of the same period.
+//! Facts with members on other axes as well (e.g. segment by geography) are
+//! finer breakdowns and left out.
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::models::FinancialLineItem;
+use crate::schema::financial_line_items;
+
+/// A concept broken down along one axis for one reporting period
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentBreakdown {
+    pub concept: String,
+    pub axis: String,
+    /// `None` for instants
+    pub period_start_date: Option<NaiveDate>,
+    pub period_end_date: Option<NaiveDate>,
+    /// Undimensioned fact of the period, if the filing reports one
+    pub total: Option<FinancialLineItem>,
+    /// Facts of each member, largest value first
+    pub members: Vec<SegmentMember>,
+}
+
+/// The fact of one member of an axis
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentMember {
+    /// Member QName, e.g. `aapl:IPhoneMember`
+    pub member: String,
+    pub line_item: FinancialLineItem,
+}
+
+type PeriodKey = (Option<NaiveDate>, Option<NaiveDate>);
+
+impl SegmentBreakdown {
+    /// Breakdowns of `concept` along `axis` in a statement, latest period first
+    pub async fn for_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+        concept: &str,
+        axis: &str,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let line_items = financial_line_items::table
+            .filter(financial_line_items::statement_id.eq(statement_id))
+            .filter(financial_line_items::taxonomy_concept.eq(concept))
+            .select(FinancialLineItem::as_select())
+            .load::<FinancialLineItem>(&mut conn)
+            .await?;
+
+        Ok(Self::from_line_items(concept, axis, line_items))
+    }
+
+    /// Group the facts of one concept into breakdowns along `axis`
+    ///
+    /// Only periods with at least one member are returned; periods ending later
+    /// come first, and of periods ending together the shorter one.
+    pub fn from_line_items(
+        concept: &str,
+        axis: &str,
+        line_items: Vec<FinancialLineItem>,
+    ) -> Vec<Self> {
+        let mut totals: BTreeMap<PeriodKey, FinancialLineItem> = BTreeMap::new();
+        let mut members: BTreeMap<PeriodKey, Vec<SegmentMember>> = BTreeMap::new();
+
+        for line_item in line_items {
+            let Some(dimensions) = line_item.dimensions.as_object() else {
+                continue;
+            };
+            let period = (line_item.period_start_date, line_item.period_end_date);
+
+            if dimensions.is_empty() {
+                totals.entry(period).or_insert(line_item);
+            } else if dimensions.len() == 1 {
+                if let Some(member) = dimensions.get(axis).and_then(|m| m.as_str()) {
+                    let member = member.to_string();
+                    members
+                        .entry(period)
+                        .or_default()
+                        .push(SegmentMember { member, line_item });
+                }
+            }
+        }
+
+        let mut breakdowns: Vec<Self> = members
+            .into_iter()
+            .map(|(period, mut members)| {
+                members.sort_by(|a, b| {
+                    b.line_item
+                        .value
+                        .cmp(&a.line_item.value)
+                        .then_with(|| a.member.cmp(&b.member))
+                });
+                Self {
+                    concept: concept.to_string(),
+                    axis: axis.to_string(),
+                    period_start_date: period.0,
+                    period_end_date: period.1,
+                    total: totals.remove(&period),
+                    members,
+                }
+            })
+            .collect();
+
+        breakdowns.sort_by(|a, b| {
+            b.period_end_date
+                .cmp(&a.period_end_date)
+                .then_with(|| b.period_start_date.cmp(&a.period_start_date))
+        });
+        breakdowns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{StatementSection, StatementType};
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+    use serde_json::json;
+
+    const SEGMENTS: &str = "us-gaap:StatementBusinessSegmentsAxis";
+
+    fn fact(year: i32, dimensions: serde_json::Value, value: i64) -> FinancialLineItem {
+        FinancialLineItem {
+            id: Uuid::new_v4(),
+            statement_id: Uuid::new_v4(),
+            taxonomy_concept: "us-gaap:Revenues".to_string(),
+            standard_label: None,
+            custom_label: None,
+            value: Some(BigDecimal::from(value)),
+            unit: "USD".to_string(),
+            context_ref: format!("FY{}", year),
+            segment_ref: None,
+            scenario_ref: None,
+            precision: None,
+            decimals: Some(-6),
+            is_credit: None,
+            is_debit: None,
+            statement_type: StatementType::IncomeStatement,
+            statement_section: StatementSection::Revenue,
+            parent_concept: None,
+            level: 0,
+            order_index: None,
+            is_calculated: false,
+            calculation_formula: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            normalized_value: None,
+            normalized_unit: None,
+            fx_rate: None,
+            dimensions,
+            period_start_date: NaiveDate::from_ymd_opt(year, 1, 1),
+            period_end_date: NaiveDate::from_ymd_opt(year, 12, 31),
+        }
+    }
+
+    #[test]
+    fn test_breakdown_by_period_and_member() {
+        // REQUIREMENT: Segment-level financials such as revenue by segment
+        // PURPOSE: Verify facts are grouped by period with the member facts of the axis next to the total
+        // This ensures segments of different fiscal years are not mixed and finer breakdowns are left out
+
+        let breakdowns = SegmentBreakdown::from_line_items(
+            "us-gaap:Revenues",
+            SEGMENTS,
+            vec![
+                fact(2023, json!({}), 90),
+                fact(2023, json!({ SEGMENTS: "acme:HardwareMember" }), 60),
+                fact(2024, json!({}), 100),
+                fact(2024, json!({ SEGMENTS: "acme:ServicesMember" }), 40),
+                fact(2024, json!({ SEGMENTS: "acme:HardwareMember" }), 60),
+                fact(
+                    2024,
+                    json!({ SEGMENTS: "acme:HardwareMember", "srt:StatementGeographicalAxis": "country:US" }),
+                    35,
+                ),
+                fact(
+                    2024,
+                    json!({ "srt:StatementGeographicalAxis": "country:US" }),
+                    70,
+                ),
+            ],
+        );
+
+        assert_eq!(breakdowns.len(), 2);
+        let latest = &breakdowns[0];
+        assert_eq!(
+            latest.period_end_date,
+            NaiveDate::from_ymd_opt(2024, 12, 31)
+        );
+        assert_eq!(
+            latest.total.as_ref().and_then(|t| t.value.clone()),
+            Some(BigDecimal::from(100))
+        );
+        let members: Vec<&str> = latest.members.iter().map(|m| m.member.as_str()).collect();
+        assert_eq!(members, vec!["acme:HardwareMember", "acme:ServicesMember"]);
+        assert_eq!(breakdowns[1].members.len(), 1);
+    }
+}
@@ -0,0 +1,397 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::series_alert_rules;
+
+/// Largest number of consecutive observations a rule can require
+pub const MAX_ALERT_WINDOW_PERIODS: i32 = 36;
+
+/// What a series alert rule compares against its threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AlertCondition {
+    /// Observation value is above the threshold
+    ValueAbove,
+    /// Observation value is below the threshold
+    ValueBelow,
+    /// Year-over-year change in percent is above the threshold
+    YoyChangeAbove,
+    /// Year-over-year change in percent is below the threshold
+    YoyChangeBelow,
+}
+
+impl AlertCondition {
+    /// Value stored in `series_alert_rules.condition`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertCondition::ValueAbove => "value_above",
+            AlertCondition::ValueBelow => "value_below",
+            AlertCondition::YoyChangeAbove => "yoy_above",
+            AlertCondition::YoyChangeBelow => "yoy_below",
+        }
+    }
+
+    /// Whether the condition compares year-over-year changes instead of values
+    pub fn is_yoy(&self) -> bool {
+        matches!(
+            self,
+            AlertCondition::YoyChangeAbove | AlertCondition::YoyChangeBelow
+        )
+    }
+
+    /// Whether `metric` satisfies the condition for `threshold`
+    pub fn is_met(&self, metric: &BigDecimal, threshold: &BigDecimal) -> bool {
+        match self {
+            AlertCondition::ValueAbove | AlertCondition::YoyChangeAbove => metric > threshold,
+            AlertCondition::ValueBelow | AlertCondition::YoyChangeBelow => metric < threshold,
+        }
+    }
+}
+
+impl std::str::FromStr for AlertCondition {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "value_above" => Ok(AlertCondition::ValueAbove),
+            "value_below" => Ok(AlertCondition::ValueBelow),
+            "yoy_above" => Ok(AlertCondition::YoyChangeAbove),
+            "yoy_below" => Ok(AlertCondition::YoyChangeBelow),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown alert condition '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Alert a user set up on an economic series
+///
+/// The rule fires when its condition holds for each of the latest
+/// `window_periods` observations. `is_triggered` remembers that it fired so
+/// the user is told once when a level is crossed, not on every new observation
+/// that stays past it.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = series_alert_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SeriesAlertRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub series_id: Uuid,
+    pub name: String,
+    pub condition: String,
+    pub threshold: BigDecimal,
+    pub window_periods: i32,
+    pub is_active: bool,
+    pub is_triggered: bool,
+    pub last_evaluated_at: Option<DateTime<Utc>>,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    pub last_triggered_date: Option<NaiveDate>,
+    pub last_triggered_value: Option<BigDecimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SeriesAlertRule {
+    /// Parsed alert condition
+    pub fn condition(&self) -> AppResult<AlertCondition> {
+        self.condition.parse()
+    }
+}
+
+/// New series alert rule for insertion
+#[derive(Debug, Clone, Insertable, Validate, Serialize, Deserialize)]
+#[diesel(table_name = series_alert_rules)]
+pub struct NewSeriesAlertRule {
+    pub user_id: Uuid,
+    pub series_id: Uuid,
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub condition: String,
+    pub threshold: BigDecimal,
+    #[validate(range(min = 1, max = MAX_ALERT_WINDOW_PERIODS))]
+    pub window_periods: i32,
+    pub is_active: bool,
+}
+
+/// Partial update of an alert rule; `None` leaves a field unchanged
+///
+/// Changing the condition, threshold or window re-arms the rule.
+#[derive(Debug, Clone, Default, AsChangeset, Validate, Serialize, Deserialize)]
+#[diesel(table_name = series_alert_rules)]
+pub struct UpdateSeriesAlertRule {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    pub condition: Option<String>,
+    pub threshold: Option<BigDecimal>,
+    #[validate(range(min = 1, max = MAX_ALERT_WINDOW_PERIODS))]
+    pub window_periods: Option<i32>,
+    pub is_active: Option<bool>,
+    pub is_triggered: Option<bool>,
+}
+
+impl UpdateSeriesAlertRule {
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.condition.is_none()
+            && self.threshold.is_none()
+            && self.window_periods.is_none()
+            && self.is_active.is_none()
+            && self.is_triggered.is_none()
+    }
+
+    /// Whether the update changes what the rule fires on
+    pub fn changes_trigger(&self) -> bool {
+        self.condition.is_some() || self.threshold.is_some() || self.window_periods.is_some()
+    }
+}
+
+/// Outcome of evaluating a rule against the latest data
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRuleState {
+    pub is_triggered: bool,
+    /// Set when the rule fired in this evaluation
+    pub fired: Option<(NaiveDate, BigDecimal)>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SeriesAlertRule {
+    /// Create an alert rule
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_rule: &NewSeriesAlertRule,
+    ) -> AppResult<Self> {
+        new_rule.validate()?;
+        new_rule.condition.parse::<AlertCondition>()?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let rule = diesel::insert_into(series_alert_rules::table)
+            .values(new_rule)
+            .returning(SeriesAlertRule::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(rule)
+    }
+
+    /// Find an alert rule owned by a user
+    pub async fn find_for_user(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let rule = series_alert_rules::table
+            .filter(series_alert_rules::id.eq(id))
+            .filter(series_alert_rules::user_id.eq(user_id))
+            .select(SeriesAlertRule::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(rule)
+    }
+
+    /// A user's alert rules, optionally for one series, newest first
+    pub async fn list_for_user(
+        pool: &crate::database::DatabasePool,
+        user_id: Uuid,
+        series_id: Option<Uuid>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = series_alert_rules::table
+            .filter(series_alert_rules::user_id.eq(user_id))
+            .into_boxed();
+        if let Some(series_id) = series_id {
+            query = query.filter(series_alert_rules::series_id.eq(series_id));
+        }
+
+        let rules = query
+            .order(series_alert_rules::created_at.desc())
+            .select(SeriesAlertRule::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(rules)
+    }
+
+    /// Active rules watching a series
+    pub async fn find_active_for_series(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let rules = series_alert_rules::table
+            .filter(series_alert_rules::series_id.eq(series_id))
+            .filter(series_alert_rules::is_active.eq(true))
+            .select(SeriesAlertRule::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(rules)
+    }
+
+    /// Apply a partial update to a rule owned by a user
+    ///
+    /// Returns `None` when the rule does not exist or belongs to someone else.
+    pub async fn update_for_user(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        user_id: Uuid,
+        changes: &UpdateSeriesAlertRule,
+    ) -> AppResult<Option<Self>> {
+        changes.validate()?;
+        if let Some(condition) = &changes.condition {
+            condition.parse::<AlertCondition>()?;
+        }
+
+        if changes.is_empty() {
+            return Self::find_for_user(pool, id, user_id).await;
+        }
+
+        let mut changes = changes.clone();
+        if changes.changes_trigger() {
+            changes.is_triggered = Some(false);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let rule = diesel::update(
+            series_alert_rules::table
+                .filter(series_alert_rules::id.eq(id))
+                .filter(series_alert_rules::user_id.eq(user_id)),
+        )
+        .set(&changes)
+        .returning(SeriesAlertRule::as_returning())
+        .get_result::<Self>(&mut conn)
+        .await
+        .optional()?;
+
+        Ok(rule)
+    }
+
+    /// Delete a rule owned by a user
+    pub async fn delete_for_user(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            series_alert_rules::table
+                .filter(series_alert_rules::id.eq(id))
+                .filter(series_alert_rules::user_id.eq(user_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Store the outcome of an evaluation
+    pub async fn record_evaluation(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        state: &AlertRuleState,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let now = Utc::now();
+
+        let target = series_alert_rules::table.filter(series_alert_rules::id.eq(id));
+        match &state.fired {
+            Some((date, value)) => {
+                diesel::update(target)
+                    .set((
+                        series_alert_rules::is_triggered.eq(state.is_triggered),
+                        series_alert_rules::last_evaluated_at.eq(now),
+                        series_alert_rules::last_triggered_at.eq(now),
+                        series_alert_rules::last_triggered_date.eq(date),
+                        series_alert_rules::last_triggered_value.eq(value),
+                    ))
+                    .execute(&mut conn)
+                    .await?;
+            }
+            None => {
+                diesel::update(target)
+                    .set((
+                        series_alert_rules::is_triggered.eq(state.is_triggered),
+                        series_alert_rules::last_evaluated_at.eq(now),
+                    ))
+                    .execute(&mut conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_condition_round_trip_and_comparison() {
+        // REQUIREMENT: Users choose how an alert compares a series to its threshold
+        // PURPOSE: Verify stored condition names parse back and compare in the right direction
+        // This ensures "above" and "below" rules do not fire on the wrong side of the level
+
+        for condition in [
+            AlertCondition::ValueAbove,
+            AlertCondition::ValueBelow,
+            AlertCondition::YoyChangeAbove,
+            AlertCondition::YoyChangeBelow,
+        ] {
+            assert_eq!(
+                condition.as_str().parse::<AlertCondition>().unwrap(),
+                condition
+            );
+        }
+        assert!("crosses".parse::<AlertCondition>().is_err());
+
+        let threshold = BigDecimal::from(5);
+        assert!(AlertCondition::ValueAbove.is_met(&BigDecimal::from(6), &threshold));
+        assert!(!AlertCondition::ValueAbove.is_met(&BigDecimal::from(5), &threshold));
+        assert!(AlertCondition::YoyChangeBelow.is_met(&BigDecimal::from(-1), &threshold));
+        assert!(AlertCondition::YoyChangeBelow.is_yoy());
+    }
+
+    #[test]
+    fn test_update_rearms_rule_when_trigger_changes() {
+        // REQUIREMENT: Editing an alert's level should allow it to fire again
+        // PURPOSE: Verify only condition, threshold and window edits count as trigger changes
+        // This ensures renaming an alert does not cause a repeat notification
+
+        assert!(UpdateSeriesAlertRule::default().is_empty());
+        assert!(!UpdateSeriesAlertRule {
+            name: Some("Unemployment above 5%".to_string()),
+            ..Default::default()
+        }
+        .changes_trigger());
+        assert!(UpdateSeriesAlertRule {
+            threshold: Some(BigDecimal::from(5)),
+            ..Default::default()
+        }
+        .changes_trigger());
+    }
+}
@@ -0,0 +1,400 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::series_links;
+
+/// Largest number of series followed when collecting the equivalents of a series
+pub const MAX_EQUIVALENT_SERIES: usize = 100;
+
+/// Who decided that two series are equivalent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeriesLinkSource {
+    /// Linked by a curator
+    Curated,
+    /// Linked by the title matcher
+    Heuristic,
+}
+
+impl SeriesLinkSource {
+    /// Value stored in `series_links.link_source`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeriesLinkSource::Curated => "curated",
+            SeriesLinkSource::Heuristic => "heuristic",
+        }
+    }
+}
+
+impl std::str::FromStr for SeriesLinkSource {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "curated" => Ok(SeriesLinkSource::Curated),
+            "heuristic" => Ok(SeriesLinkSource::Heuristic),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown series link source '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Link between two equivalent series from different data sources
+///
+/// Links are undirected: each pair is stored once with the smaller ID in
+/// `series_id`. Use [`SeriesLink::other`] to get the far side of a link.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = series_links)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SeriesLink {
+    pub id: Uuid,
+    pub series_id: Uuid,
+    pub linked_series_id: Uuid,
+    pub link_source: String,
+    /// How sure the linker is that the series measure the same thing, in (0, 1]
+    pub confidence: f64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SeriesLink {
+    /// Parsed link source
+    pub fn link_source(&self) -> AppResult<SeriesLinkSource> {
+        self.link_source.parse()
+    }
+
+    /// The series on the other side of the link from `series_id`
+    pub fn other(&self, series_id: Uuid) -> Uuid {
+        if self.series_id == series_id {
+            self.linked_series_id
+        } else {
+            self.series_id
+        }
+    }
+}
+
+/// New series link for insertion
+#[derive(Debug, Clone, PartialEq, Insertable)]
+#[diesel(table_name = series_links)]
+pub struct NewSeriesLink {
+    pub series_id: Uuid,
+    pub linked_series_id: Uuid,
+    pub link_source: String,
+    pub confidence: f64,
+    pub created_by: Option<Uuid>,
+}
+
+impl NewSeriesLink {
+    /// Link two series, storing the pair in canonical order
+    pub fn new(
+        a: Uuid,
+        b: Uuid,
+        link_source: SeriesLinkSource,
+        confidence: f64,
+        created_by: Option<Uuid>,
+    ) -> AppResult<Self> {
+        if a == b {
+            return Err(AppError::ValidationError(
+                "A series cannot be linked to itself".to_string(),
+            ));
+        }
+        if !(confidence > 0.0 && confidence <= 1.0) {
+            return Err(AppError::ValidationError(
+                "Link confidence must be greater than 0 and at most 1".to_string(),
+            ));
+        }
+
+        let (series_id, linked_series_id) = canonical_pair(a, b);
+        Ok(Self {
+            series_id,
+            linked_series_id,
+            link_source: link_source.as_str().to_string(),
+            confidence,
+            created_by,
+        })
+    }
+}
+
+/// A series equivalent to another, with how it was reached
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquivalentSeries {
+    pub series_id: Uuid,
+    /// Weakest link confidence on the strongest path to the series
+    pub confidence: f64,
+    /// Whether every link on that path was made by a curator
+    pub curated: bool,
+    /// Number of links between the two series
+    pub hops: usize,
+}
+
+/// Order a pair of series IDs the way `series_links` stores them
+pub fn canonical_pair(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Series reachable from `series_id` through `links`, excluding the series itself
+///
+/// Each series is reported through its most confident path; a path is as
+/// confident as its weakest link. Ties prefer curated paths, then fewer hops.
+pub fn equivalents_from_links(series_id: Uuid, links: &[SeriesLink]) -> Vec<EquivalentSeries> {
+    let mut best: HashMap<Uuid, EquivalentSeries> = HashMap::new();
+    best.insert(
+        series_id,
+        EquivalentSeries {
+            series_id,
+            confidence: 1.0,
+            curated: true,
+            hops: 0,
+        },
+    );
+
+    // Relax until nothing improves; each pass extends paths by one link
+    loop {
+        let mut improved = false;
+        for link in links {
+            for (from, to) in [
+                (link.series_id, link.linked_series_id),
+                (link.linked_series_id, link.series_id),
+            ] {
+                let Some(reached) = best.get(&from) else {
+                    continue;
+                };
+                let candidate = EquivalentSeries {
+                    series_id: to,
+                    confidence: reached.confidence.min(link.confidence),
+                    curated: reached.curated
+                        && link.link_source().ok() == Some(SeriesLinkSource::Curated),
+                    hops: reached.hops + 1,
+                };
+                let better = match best.get(&to) {
+                    None => true,
+                    Some(current) => {
+                        (
+                            candidate.confidence,
+                            candidate.curated,
+                            Reverse(candidate.hops),
+                        ) > (current.confidence, current.curated, Reverse(current.hops))
+                    }
+                };
+                if better {
+                    best.insert(to, candidate);
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    best.remove(&series_id);
+    let mut equivalents: Vec<EquivalentSeries> = best.into_values().collect();
+    equivalents.sort_by_key(|equivalent| equivalent.series_id);
+    equivalents
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SeriesLink {
+    /// Store a curated link, replacing a heuristic link between the same series
+    pub async fn upsert_curated(
+        pool: &crate::database::DatabasePool,
+        new_link: &NewSeriesLink,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let link = diesel::insert_into(series_links::table)
+            .values(new_link)
+            .on_conflict((series_links::series_id, series_links::linked_series_id))
+            .do_update()
+            .set((
+                series_links::link_source.eq(&new_link.link_source),
+                series_links::confidence.eq(new_link.confidence),
+                series_links::created_by.eq(new_link.created_by),
+                series_links::created_at.eq(Utc::now()),
+            ))
+            .returning(SeriesLink::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(link)
+    }
+
+    /// Store a link unless the series are already linked; returns `None` when they are
+    pub async fn create_if_missing(
+        pool: &crate::database::DatabasePool,
+        new_link: &NewSeriesLink,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let link = diesel::insert_into(series_links::table)
+            .values(new_link)
+            .on_conflict((series_links::series_id, series_links::linked_series_id))
+            .do_nothing()
+            .returning(SeriesLink::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(link)
+    }
+
+    /// Remove the link between two series
+    pub async fn delete_pair(
+        pool: &crate::database::DatabasePool,
+        a: Uuid,
+        b: Uuid,
+    ) -> AppResult<bool> {
+        let (series_id, linked_series_id) = canonical_pair(a, b);
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(
+            series_links::table
+                .filter(series_links::series_id.eq(series_id))
+                .filter(series_links::linked_series_id.eq(linked_series_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Links touching any of `series_ids`
+    pub async fn find_touching(
+        pool: &crate::database::DatabasePool,
+        series_ids: &[Uuid],
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let links = series_links::table
+            .filter(
+                series_links::series_id
+                    .eq_any(series_ids)
+                    .or(series_links::linked_series_id.eq_any(series_ids)),
+            )
+            .select(SeriesLink::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(links)
+    }
+
+    /// Every link in the group of series equivalent to `series_id`
+    ///
+    /// Follows links one hop at a time and stops growing the group at
+    /// [`MAX_EQUIVALENT_SERIES`] series.
+    pub async fn find_connected(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut seen: BTreeSet<Uuid> = BTreeSet::from([series_id]);
+        let mut frontier = vec![series_id];
+        let mut links: HashMap<Uuid, Self> = HashMap::new();
+
+        while !frontier.is_empty() && seen.len() < MAX_EQUIVALENT_SERIES {
+            let found = Self::find_touching(pool, &frontier).await?;
+            frontier.clear();
+            for link in found {
+                for id in [link.series_id, link.linked_series_id] {
+                    if seen.len() < MAX_EQUIVALENT_SERIES && seen.insert(id) {
+                        frontier.push(id);
+                    }
+                }
+                links.insert(link.id, link);
+            }
+        }
+
+        Ok(links
+            .into_values()
+            .filter(|link| seen.contains(&link.series_id) && seen.contains(&link.linked_series_id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(a: Uuid, b: Uuid, source: SeriesLinkSource, confidence: f64) -> SeriesLink {
+        let new_link = NewSeriesLink::new(a, b, source, confidence, None).unwrap();
+        SeriesLink {
+            id: Uuid::new_v4(),
+            series_id: new_link.series_id,
+            linked_series_id: new_link.linked_series_id,
+            link_source: new_link.link_source,
+            confidence,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_new_link_is_canonical_and_validated() {
+        // REQUIREMENT: Each pair of equivalent series is stored once
+        // PURPOSE: Verify links are ordered by ID and reject self links and bad confidences
+        // This ensures linking A to B and B to A update the same row
+
+        let (a, b) = canonical_pair(Uuid::new_v4(), Uuid::new_v4());
+        let forward = NewSeriesLink::new(a, b, SeriesLinkSource::Curated, 1.0, None).unwrap();
+        let backward = NewSeriesLink::new(b, a, SeriesLinkSource::Curated, 1.0, None).unwrap();
+        assert_eq!(forward, backward);
+        assert_eq!((forward.series_id, forward.linked_series_id), (a, b));
+
+        assert!(NewSeriesLink::new(a, a, SeriesLinkSource::Curated, 1.0, None).is_err());
+        assert!(NewSeriesLink::new(a, b, SeriesLinkSource::Heuristic, 0.0, None).is_err());
+        assert!(NewSeriesLink::new(a, b, SeriesLinkSource::Heuristic, 1.5, None).is_err());
+        assert_eq!(
+            "heuristic".parse::<SeriesLinkSource>().unwrap(),
+            SeriesLinkSource::Heuristic
+        );
+    }
+
+    #[test]
+    fn test_equivalents_follow_links_transitively() {
+        // REQUIREMENT: GDP linked FRED -> World Bank and World Bank -> Eurostat is one group
+        // PURPOSE: Verify equivalents are reached through chains of links via their strongest path
+        // This ensures curators do not have to link every pair of sources by hand
+
+        let fred = Uuid::new_v4();
+        let world_bank = Uuid::new_v4();
+        let eurostat = Uuid::new_v4();
+        let unrelated = Uuid::new_v4();
+        let links = vec![
+            link(fred, world_bank, SeriesLinkSource::Curated, 1.0),
+            link(world_bank, eurostat, SeriesLinkSource::Heuristic, 0.7),
+            link(fred, eurostat, SeriesLinkSource::Heuristic, 0.9),
+        ];
+
+        let equivalents = equivalents_from_links(fred, &links);
+        assert_eq!(equivalents.len(), 2);
+        assert!(!equivalents.iter().any(|e| e.series_id == unrelated));
+
+        let to_world_bank = equivalents
+            .iter()
+            .find(|e| e.series_id == world_bank)
+            .unwrap();
+        assert!(to_world_bank.curated);
+        assert_eq!(to_world_bank.hops, 1);
+
+        let to_eurostat = equivalents
+            .iter()
+            .find(|e| e.series_id == eurostat)
+            .unwrap();
+        assert_eq!(to_eurostat.confidence, 0.9);
+        assert_eq!(to_eurostat.hops, 1);
+        assert!(!to_eurostat.curated);
+    }
+}
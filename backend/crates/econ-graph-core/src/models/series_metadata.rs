@@ -45,6 +45,14 @@ pub struct SeriesMetadata {
     pub created_at: Option<DateTime<Utc>>,
     /// Last update timestamp
     pub updated_at: Option<DateTime<Utc>>,
+    /// Area the series covers, e.g. "California"
+    pub geography: Option<String>,
+    /// Catalog categories the source files the series under
+    pub categories: Vec<String>,
+    /// Topic keywords from the source catalog
+    pub tags: Vec<String>,
+    /// When the enrichment pass last ran for this series
+    pub enriched_at: Option<DateTime<Utc>>,
 }
 
 /// New series metadata for insertion
@@ -97,6 +105,20 @@ pub struct UpdateSeriesMetadata {
     pub is_active: bool,
 }
 
+/// Category, tag and geography metadata from a source catalog
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, AsChangeset)]
+#[diesel(table_name = series_metadata)]
+pub struct SeriesEnrichment {
+    /// Kind of area covered: National, Region, State, MSA or County
+    pub geographic_level: Option<String>,
+    /// Area covered, e.g. "United States" or "California"
+    pub geography: Option<String>,
+    /// Catalog categories the source files the series under
+    pub categories: Vec<String>,
+    /// Topic keywords
+    pub tags: Vec<String>,
+}
+
 impl SeriesMetadata {
     /// Get or create series metadata
     pub async fn get_or_create(
@@ -245,6 +267,63 @@ impl SeriesMetadata {
         Ok(metadata)
     }
 
+    /// Active series of a source never enriched, or last enriched before
+    /// `enriched_before`, never-enriched first
+    pub async fn due_for_enrichment(
+        pool: &DatabasePool,
+        source_id: Uuid,
+        enriched_before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        use crate::schema::series_metadata::dsl;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        // NULLs sort last ascending, so order by whether the series was enriched first
+        let metadata = dsl::series_metadata
+            .filter(dsl::source_id.eq(source_id))
+            .filter(dsl::is_active.eq(true))
+            .filter(
+                dsl::enriched_at
+                    .is_null()
+                    .or(dsl::enriched_at.lt(enriched_before)),
+            )
+            .order((dsl::enriched_at.is_not_null(), dsl::enriched_at.asc()))
+            .limit(limit)
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(metadata)
+    }
+
+    /// Store catalog metadata found for a series and mark it enriched
+    pub async fn apply_enrichment(
+        pool: &DatabasePool,
+        id: Uuid,
+        enrichment: &SeriesEnrichment,
+    ) -> AppResult<Self> {
+        use crate::schema::series_metadata::dsl;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            crate::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let updated = diesel::update(dsl::series_metadata.filter(dsl::id.eq(id)))
+            .set((enrichment, dsl::enriched_at.eq(diesel::dsl::now)))
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(updated)
+    }
+
     /// Find all series metadata
     pub async fn find_all(pool: &DatabasePool) -> AppResult<Vec<Self>> {
         use crate::schema::series_metadata::dsl;
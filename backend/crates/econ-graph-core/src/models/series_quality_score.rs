@@ -0,0 +1,187 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::dsl::{exists, now};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{data_points, data_sources, economic_series, series_quality_scores};
+
+/// Data quality of a series as of its last check
+///
+/// `score` runs from 0 (unusable) to 1 (no outliers, gaps or staleness found).
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = series_quality_scores)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SeriesQualityScore {
+    pub series_id: Uuid,
+    pub score: f64,
+    pub observation_count: i32,
+    /// Observations whose change from the previous one is an outlier
+    pub outlier_count: i32,
+    /// Periods between the first and last observation with no value
+    pub missing_periods: i32,
+    pub last_observation_date: Option<NaiveDate>,
+    /// Whether the latest observation is older than the series' frequency allows
+    pub is_stale: bool,
+    /// Newest data point creation time this score covers
+    pub checked_through: DateTime<Utc>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Score to store for a series, replacing its previous one
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = series_quality_scores)]
+pub struct NewSeriesQualityScore {
+    pub series_id: Uuid,
+    pub score: f64,
+    pub observation_count: i32,
+    pub outlier_count: i32,
+    pub missing_periods: i32,
+    pub last_observation_date: Option<NaiveDate>,
+    pub is_stale: bool,
+    pub checked_through: DateTime<Utc>,
+}
+
+/// Quality figures of one scored series, labelled with its data source
+#[derive(Debug, Clone, PartialEq, Queryable)]
+pub struct SourceSeriesQuality {
+    pub source_name: String,
+    pub score: f64,
+    pub outlier_count: i32,
+    pub missing_periods: i32,
+    pub is_stale: bool,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SeriesQualityScore {
+    /// Store a series' score, replacing the previous one
+    pub async fn upsert(
+        pool: &crate::database::DatabasePool,
+        new_score: &NewSeriesQualityScore,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = diesel::insert_into(series_quality_scores::table)
+            .values(new_score)
+            .on_conflict(series_quality_scores::series_id)
+            .do_update()
+            .set((
+                series_quality_scores::score.eq(excluded(series_quality_scores::score)),
+                series_quality_scores::observation_count
+                    .eq(excluded(series_quality_scores::observation_count)),
+                series_quality_scores::outlier_count
+                    .eq(excluded(series_quality_scores::outlier_count)),
+                series_quality_scores::missing_periods
+                    .eq(excluded(series_quality_scores::missing_periods)),
+                series_quality_scores::last_observation_date
+                    .eq(excluded(series_quality_scores::last_observation_date)),
+                series_quality_scores::is_stale.eq(excluded(series_quality_scores::is_stale)),
+                series_quality_scores::checked_through
+                    .eq(excluded(series_quality_scores::checked_through)),
+                series_quality_scores::computed_at.eq(now),
+            ))
+            .returning(SeriesQualityScore::as_returning())
+            .get_result(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Stored score of a series, if it has been checked
+    pub async fn find_by_series(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let score = series_quality_scores::table
+            .find(series_id)
+            .select(SeriesQualityScore::as_select())
+            .first(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(score)
+    }
+
+    /// Series with data points that have never been scored
+    pub async fn unscored_series(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+    ) -> AppResult<Vec<Uuid>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let series_ids = economic_series::table
+            .left_join(series_quality_scores::table)
+            .filter(series_quality_scores::series_id.nullable().is_null())
+            .filter(exists(
+                data_points::table.filter(data_points::series_id.eq(economic_series::id)),
+            ))
+            .select(economic_series::id)
+            .limit(limit)
+            .load::<Uuid>(&mut conn)
+            .await?;
+
+        Ok(series_ids)
+    }
+
+    /// Scored series with data points added since their last check, or last
+    /// checked before `rescore_before`, least recently checked first
+    ///
+    /// Rescoring old checks keeps staleness current for series that stopped
+    /// receiving data.
+    pub async fn due_for_rescore(
+        pool: &crate::database::DatabasePool,
+        rescore_before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<Uuid>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let series_ids = series_quality_scores::table
+            .filter(
+                series_quality_scores::computed_at
+                    .lt(rescore_before)
+                    .or(exists(
+                        data_points::table
+                            .filter(data_points::series_id.eq(series_quality_scores::series_id))
+                            .filter(
+                                data_points::created_at.gt(series_quality_scores::checked_through),
+                            ),
+                    )),
+            )
+            .order(series_quality_scores::computed_at.asc())
+            .select(series_quality_scores::series_id)
+            .limit(limit)
+            .load::<Uuid>(&mut conn)
+            .await?;
+
+        Ok(series_ids)
+    }
+
+    /// Quality figures of every scored series with its data source name
+    pub async fn list_with_sources(
+        pool: &crate::database::DatabasePool,
+    ) -> AppResult<Vec<SourceSeriesQuality>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let rows = series_quality_scores::table
+            .inner_join(economic_series::table.inner_join(data_sources::table))
+            .select((
+                data_sources::name,
+                series_quality_scores::score,
+                series_quality_scores::outlier_count,
+                series_quality_scores::missing_periods,
+                series_quality_scores::is_stale,
+            ))
+            .load::<SourceSeriesQuality>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+}
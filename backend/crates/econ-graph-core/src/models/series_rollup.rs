@@ -0,0 +1,217 @@
+//! Pre-aggregated rollups of series for dashboards
+//!
+//! First, minimum, maximum, mean and last value of each series per month and
+//! per year, from the latest revision of every observation. Database triggers
+//! recompute the affected years whenever data points are inserted, revised or
+//! deleted (see the `create_series_rollups` migration), so reading a rollup
+//! never scans `data_points`.
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::series_rollups;
+
+/// Length of the periods a series is rolled up into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RollupPeriod {
+    Month,
+    Year,
+}
+
+impl RollupPeriod {
+    /// Value stored in `series_rollups.period`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollupPeriod::Month => "month",
+            RollupPeriod::Year => "year",
+        }
+    }
+}
+
+impl std::str::FromStr for RollupPeriod {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "month" => Ok(RollupPeriod::Month),
+            "year" => Ok(RollupPeriod::Year),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown rollup period '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Summary of a series' observations in one month or year
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Serialize)]
+#[diesel(table_name = series_rollups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SeriesRollup {
+    pub series_id: Uuid,
+    pub period: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Value of the first observation in the period
+    pub first_value: BigDecimal,
+    pub min_value: BigDecimal,
+    pub max_value: BigDecimal,
+    pub mean_value: BigDecimal,
+    /// Value of the last observation in the period
+    pub last_value: BigDecimal,
+    pub last_date: NaiveDate,
+    /// Observations with a value; missing values are left out of every statistic
+    pub observation_count: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl SeriesRollup {
+    /// Rollups of a series, oldest first, for periods starting within the dates given
+    pub async fn for_series(
+        pool: &crate::database::DatabasePool,
+        series_id: Uuid,
+        period: RollupPeriod,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = series_rollups::table
+            .filter(series_rollups::series_id.eq(series_id))
+            .filter(series_rollups::period.eq(period.as_str()))
+            .into_boxed();
+        if let Some(start_date) = start_date {
+            query = query.filter(series_rollups::period_start.ge(start_date));
+        }
+        if let Some(end_date) = end_date {
+            query = query.filter(series_rollups::period_start.le(end_date));
+        }
+
+        let rollups = query
+            .order(series_rollups::period_start.asc())
+            .select(SeriesRollup::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(rollups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        DataPoint, DataSource, EconomicSeries, NewDataPoint, NewDataSource, NewEconomicSeries,
+    };
+    use crate::test_utils::TestContainer;
+
+    #[tokio::test]
+    async fn test_rollups_follow_ingestion_and_revisions() {
+        // REQUIREMENT: Monthly and yearly rollups are maintained on ingestion
+        // PURPOSE: Verify rollups use the latest revision of each observation and follow deletes
+        // This ensures dashboards read the same values charts show without aggregating data_points
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+
+        let source = DataSource::create(
+            pool,
+            NewDataSource {
+                name: format!("Rollup Source {}", Uuid::new_v4()),
+                base_url: "https://rollups.example.com/api".to_string(),
+                ..NewDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = EconomicSeries::create(
+            pool,
+            &NewEconomicSeries {
+                source_id: source.id,
+                external_id: "ROLLUP_001".to_string(),
+                title: "Rollup Series".to_string(),
+                frequency: "Daily".to_string(),
+                is_active: true,
+                ..NewEconomicSeries::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let point = |month, day, value: i32, revised: bool| NewDataPoint {
+            series_id: series.id,
+            date: date(month, day),
+            value: Some(BigDecimal::from(value)),
+            revision_date: if revised { date(12, 31) } else { date(12, 1) },
+            is_original_release: !revised,
+        };
+        let points = DataPoint::create_batch(
+            pool,
+            &[
+                point(1, 2, 10, false),
+                point(1, 15, 30, false),
+                point(1, 31, 21, false),
+                point(2, 1, 40, false),
+            ],
+        )
+        .await
+        .unwrap();
+        // A later revision replaces the January 15 value
+        DataPoint::create_batch(pool, &[point(1, 15, 5, true)])
+            .await
+            .unwrap();
+
+        let months = SeriesRollup::for_series(pool, series.id, RollupPeriod::Month, None, None)
+            .await
+            .unwrap();
+        assert_eq!(months.len(), 2);
+        let january = &months[0];
+        assert_eq!(
+            (january.period_start, january.period_end),
+            (date(1, 1), date(1, 31))
+        );
+        assert_eq!(january.first_value, BigDecimal::from(10));
+        assert_eq!(january.min_value, BigDecimal::from(5));
+        assert_eq!(january.max_value, BigDecimal::from(21));
+        assert_eq!(january.mean_value, BigDecimal::from(12));
+        assert_eq!(january.last_value, BigDecimal::from(21));
+        assert_eq!(january.last_date, date(1, 31));
+        assert_eq!(january.observation_count, 3);
+
+        let february = points.iter().find(|p| p.date == date(2, 1)).unwrap().id;
+        let mut conn = pool.get().await.unwrap();
+        diesel::delete(crate::schema::data_points::table.find(february))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let years = SeriesRollup::for_series(pool, series.id, RollupPeriod::Year, None, None)
+            .await
+            .unwrap();
+        assert_eq!(years.len(), 1);
+        assert_eq!(years[0].period_end, date(12, 31));
+        assert_eq!(years[0].max_value, BigDecimal::from(21));
+        assert_eq!(years[0].last_date, date(1, 31));
+        assert_eq!(years[0].observation_count, 3);
+        assert!(SeriesRollup::for_series(
+            pool,
+            series.id,
+            RollupPeriod::Month,
+            Some(date(2, 1)),
+            None
+        )
+        .await
+        .unwrap()
+        .is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database::DatabasePool;
+use crate::enums::SubscriptionTier;
 use crate::error::{AppError, AppResult};
 /**
  * REQUIREMENT: User authentication models for OAuth and collaboration
@@ -42,6 +43,8 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_login_at: Option<DateTime<Utc>>,
+    /// Decides which licensed data sources the user may read
+    pub subscription_tier: SubscriptionTier,
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -335,6 +338,36 @@ impl User {
         Ok(user)
     }
 
+    /// Change a user's subscription tier
+    ///
+    /// The user's access tokens keep the old tier until they are refreshed.
+    pub async fn set_subscription_tier(
+        pool: &DatabasePool,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+    ) -> AppResult<User> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let user = diesel::update(users::table.find(user_id))
+            .set((
+                users::subscription_tier.eq(tier),
+                users::updated_at.eq(Utc::now()),
+            ))
+            .returning(User::as_select())
+            .get_result(&mut conn)
+            .await
+            .map_err(|e| match e {
+                diesel::result::Error::NotFound => {
+                    AppError::NotFound(format!("User {} not found", user_id))
+                }
+                e => AppError::DatabaseError(e.to_string()),
+            })?;
+
+        Ok(user)
+    }
+
     /// Get user by ID
     pub async fn get_by_id(pool: &DatabasePool, user_id: Uuid) -> AppResult<User> {
         let mut conn = pool.get().await.map_err(|e| {
@@ -407,6 +440,7 @@ impl User {
             created_at: self.created_at,
             last_login_at: self.last_login_at.unwrap_or_else(Utc::now),
             is_active: self.is_active,
+            subscription_tier: self.subscription_tier,
         }
     }
 }
@@ -522,6 +556,145 @@ impl UserSession {
         Ok(())
     }
 
+    /// Store a session identified by the hash of its refresh token
+    pub async fn insert(pool: &DatabasePool, new_session: &NewUserSession) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let session = diesel::insert_into(user_sessions::table)
+            .values(new_session)
+            .returning(UserSession::as_select())
+            .get_result::<UserSession>(&mut conn)
+            .await?;
+
+        Ok(session)
+    }
+
+    /// Unexpired session by ID
+    pub async fn find_active(pool: &DatabasePool, session_id: Uuid) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let session = user_sessions::table
+            .filter(user_sessions::id.eq(session_id))
+            .filter(user_sessions::expires_at.gt(Utc::now()))
+            .select(UserSession::as_select())
+            .first::<UserSession>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(session)
+    }
+
+    /// Unexpired session whose current refresh token has the given hash
+    pub async fn find_active_by_token_hash(
+        pool: &DatabasePool,
+        token_hash: &str,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let session = user_sessions::table
+            .filter(user_sessions::token_hash.eq(token_hash))
+            .filter(user_sessions::expires_at.gt(Utc::now()))
+            .select(UserSession::as_select())
+            .first::<UserSession>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(session)
+    }
+
+    /// Replace a session's refresh token
+    ///
+    /// Only succeeds while the session still holds `current_hash`, so a
+    /// refresh token can be exchanged once even under concurrent requests.
+    pub async fn rotate_token(
+        pool: &DatabasePool,
+        session_id: Uuid,
+        current_hash: &str,
+        new_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let session = diesel::update(
+            user_sessions::table
+                .filter(user_sessions::id.eq(session_id))
+                .filter(user_sessions::token_hash.eq(current_hash)),
+        )
+        .set((
+            user_sessions::token_hash.eq(new_hash),
+            user_sessions::expires_at.eq(expires_at),
+            user_sessions::last_used_at.eq(Utc::now()),
+        ))
+        .returning(UserSession::as_select())
+        .get_result::<UserSession>(&mut conn)
+        .await
+        .optional()?;
+
+        Ok(session)
+    }
+
+    /// Active sessions of a user, most recently used first
+    pub async fn list_active_for_user(pool: &DatabasePool, user_id: Uuid) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let sessions = user_sessions::table
+            .filter(user_sessions::user_id.eq(user_id))
+            .filter(user_sessions::expires_at.gt(Utc::now()))
+            .order(user_sessions::last_used_at.desc())
+            .select(UserSession::as_select())
+            .load::<UserSession>(&mut conn)
+            .await?;
+
+        Ok(sessions)
+    }
+
+    /// Revoke a session, restricted to one user's sessions when `user_id` is given
+    ///
+    /// Returns whether a session was revoked.
+    pub async fn revoke(
+        pool: &DatabasePool,
+        session_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let mut query = diesel::delete(user_sessions::table)
+            .filter(user_sessions::id.eq(session_id))
+            .into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(user_sessions::user_id.eq(user_id));
+        }
+
+        let deleted = query.execute(&mut conn).await?;
+        Ok(deleted > 0)
+    }
+
+    /// Revoke every session of a user, returning how many were revoked
+    pub async fn revoke_all(pool: &DatabasePool, user_id: Uuid) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let deleted = diesel::delete(user_sessions::table)
+            .filter(user_sessions::user_id.eq(user_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted)
+    }
+
     /// Clean up expired sessions
     pub async fn cleanup_expired(pool: &DatabasePool) -> AppResult<usize> {
         let mut conn = pool.get().await.map_err(|e| {
@@ -558,6 +731,7 @@ pub struct ChartAnnotation {
     pub tags: Option<Vec<Option<String>>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub organization_id: Option<Uuid>,
 }
 
 /// New chart annotation for insertion
@@ -576,6 +750,7 @@ pub struct NewChartAnnotation {
     pub is_visible: Option<bool>,
     pub is_pinned: Option<bool>,
     pub tags: Option<Vec<Option<String>>>,
+    pub organization_id: Option<Uuid>,
 }
 
 /// Annotation comment model for discussion threads
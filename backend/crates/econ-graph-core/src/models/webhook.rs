@@ -0,0 +1,630 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Timestamptz};
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{webhook_deliveries, webhooks};
+use crate::secrets::{EncryptedSecret, SecretCipher, SecretString};
+
+/// Shortest accepted signing secret
+pub const MIN_WEBHOOK_SECRET_LENGTH: u64 = 16;
+
+/// Event a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// New data points were stored for a series
+    SeriesUpdated,
+    /// Crawling a series failed
+    CrawlFailed,
+}
+
+impl WebhookEvent {
+    /// Value stored in `webhooks.event_types` and `webhook_deliveries.event_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::SeriesUpdated => "series_updated",
+            WebhookEvent::CrawlFailed => "crawl_failed",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookEvent {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "series_updated" => Ok(WebhookEvent::SeriesUpdated),
+            "crawl_failed" => Ok(WebhookEvent::CrawlFailed),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown webhook event '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a delivery stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// The endpoint answered with a 2xx status
+    Delivered,
+    /// Every attempt failed; no more retries
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    /// Value stored in `webhook_deliveries.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Endpoint notified of events for one data source or one series
+///
+/// The key for the HMAC-SHA256 signature of each delivery is stored encrypted
+/// with [`SecretCipher`] (see [`Webhook::signing_secret`]) and never sent back
+/// to clients.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = webhooks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    /// Plaintext secret of a webhook created before secrets were encrypted;
+    /// cleared once the webhook dispatcher has encrypted it
+    #[serde(skip_serializing)]
+    pub secret: Option<String>,
+    pub source_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Id of the key that encrypted the secret
+    #[serde(skip_serializing)]
+    pub secret_key_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub secret_nonce: Option<Vec<u8>>,
+    #[serde(skip_serializing)]
+    pub secret_ciphertext: Option<Vec<u8>>,
+}
+
+/// New webhook as submitted; [`Webhook::create`] encrypts the secret
+#[derive(Debug, Clone, Validate, Serialize, Deserialize)]
+pub struct NewWebhook {
+    #[validate(url)]
+    pub url: String,
+    #[validate(length(min = MIN_WEBHOOK_SECRET_LENGTH, max = 255))]
+    pub secret: String,
+    pub source_id: Option<Uuid>,
+    pub series_id: Option<Uuid>,
+    #[validate(length(min = 1))]
+    pub event_types: Vec<String>,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+}
+
+impl NewWebhook {
+    /// Check what the derived validation cannot: scope, scheme and event names
+    pub fn validate_subscription(&self) -> AppResult<()> {
+        self.validate()?;
+
+        if self.source_id.is_some() == self.series_id.is_some() {
+            return Err(AppError::ValidationError(
+                "A webhook must watch either a data source or a series".to_string(),
+            ));
+        }
+        if !self.url.starts_with("https://") && !self.url.starts_with("http://") {
+            return Err(AppError::ValidationError(
+                "Webhook URLs must use http or https".to_string(),
+            ));
+        }
+        for event_type in &self.event_types {
+            event_type.parse::<WebhookEvent>()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One event queued for, or sent to, one webhook
+#[derive(
+    Debug, Clone, Queryable, QueryableByName, Selectable, Identifiable, Serialize, Deserialize,
+)]
+#[diesel(table_name = webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New delivery for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+}
+
+/// Result of one delivery attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryAttempt {
+    /// HTTP status, if the endpoint answered
+    pub response_status: Option<i32>,
+    /// Why the attempt failed; `None` when it succeeded
+    pub error: Option<String>,
+    /// When to try again after a failure; `None` gives up
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl Webhook {
+    /// Register a webhook, encrypting its secret with `cipher`
+    ///
+    /// The secret is bound to the webhook's id, so a ciphertext copied to
+    /// another webhook does not decrypt there.
+    pub async fn create(
+        pool: &crate::database::DatabasePool,
+        new_webhook: &NewWebhook,
+        cipher: &SecretCipher,
+    ) -> AppResult<Self> {
+        new_webhook.validate_subscription()?;
+
+        let id = Uuid::new_v4();
+        let encrypted = cipher.encrypt(
+            &SecretString::new(new_webhook.secret.clone()),
+            id.as_bytes(),
+        )?;
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhook = diesel::insert_into(webhooks::table)
+            .values((
+                webhooks::id.eq(id),
+                webhooks::url.eq(&new_webhook.url),
+                webhooks::secret_key_id.eq(encrypted.key_id),
+                webhooks::secret_nonce.eq(encrypted.nonce),
+                webhooks::secret_ciphertext.eq(encrypted.ciphertext),
+                webhooks::source_id.eq(new_webhook.source_id),
+                webhooks::series_id.eq(new_webhook.series_id),
+                webhooks::event_types.eq(&new_webhook.event_types),
+                webhooks::description.eq(&new_webhook.description),
+                webhooks::created_by.eq(new_webhook.created_by),
+            ))
+            .returning(Webhook::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await?;
+
+        Ok(webhook)
+    }
+
+    /// The stored ciphertext of the secret, if it has been encrypted
+    pub fn encrypted_secret(&self) -> Option<EncryptedSecret> {
+        Some(EncryptedSecret {
+            key_id: self.secret_key_id.clone()?,
+            nonce: self.secret_nonce.clone()?,
+            ciphertext: self.secret_ciphertext.clone()?,
+        })
+    }
+
+    /// The secret deliveries are signed with
+    ///
+    /// Decrypting needs `cipher`; a webhook whose secret has not been
+    /// encrypted yet returns its plaintext secret.
+    pub fn signing_secret(&self, cipher: Option<&SecretCipher>) -> AppResult<SecretString> {
+        match (self.encrypted_secret(), &self.secret) {
+            (Some(encrypted), _) => {
+                let cipher = cipher.ok_or_else(|| {
+                    AppError::ConfigError(
+                        "Webhook secrets are encrypted but no encryption key is configured"
+                            .to_string(),
+                    )
+                })?;
+                cipher.decrypt(&encrypted, self.id.as_bytes())
+            }
+            (None, Some(secret)) => Ok(SecretString::new(secret.clone())),
+            (None, None) => Err(AppError::InternalError(format!(
+                "Webhook {} has no signing secret",
+                self.id
+            ))),
+        }
+    }
+
+    /// Webhooks whose secret is still stored in plain text
+    pub async fn with_plaintext_secret(
+        pool: &crate::database::DatabasePool,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhooks = webhooks::table
+            .filter(webhooks::secret.is_not_null())
+            .select(Webhook::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    /// Store an encrypted secret and clear the plaintext one
+    pub async fn store_encrypted_secret(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        encrypted: EncryptedSecret,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::update(webhooks::table.filter(webhooks::id.eq(id)))
+            .set((
+                webhooks::secret.eq(None::<String>),
+                webhooks::secret_key_id.eq(encrypted.key_id),
+                webhooks::secret_nonce.eq(encrypted.nonce),
+                webhooks::secret_ciphertext.eq(encrypted.ciphertext),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Find a webhook by ID
+    pub async fn find_by_id(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+    ) -> AppResult<Option<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhook = webhooks::table
+            .filter(webhooks::id.eq(id))
+            .select(Webhook::as_select())
+            .first::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(webhook)
+    }
+
+    /// Webhooks with the given IDs
+    pub async fn find_by_ids(
+        pool: &crate::database::DatabasePool,
+        ids: &[Uuid],
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhooks = webhooks::table
+            .filter(webhooks::id.eq_any(ids))
+            .select(Webhook::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    /// All webhooks, optionally for one data source or series, newest first
+    pub async fn list(
+        pool: &crate::database::DatabasePool,
+        source_id: Option<Uuid>,
+        series_id: Option<Uuid>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = webhooks::table.into_boxed();
+        if let Some(source_id) = source_id {
+            query = query.filter(webhooks::source_id.eq(source_id));
+        }
+        if let Some(series_id) = series_id {
+            query = query.filter(webhooks::series_id.eq(series_id));
+        }
+
+        let webhooks = query
+            .order(webhooks::created_at.desc())
+            .select(Webhook::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    /// Active webhooks subscribed to `event` on a data source or one of its series
+    pub async fn subscribers(
+        pool: &crate::database::DatabasePool,
+        event: WebhookEvent,
+        source_id: Uuid,
+        series_id: Option<Uuid>,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhooks = webhooks::table
+            .filter(webhooks::is_active.eq(true))
+            .filter(webhooks::event_types.contains(vec![event.as_str().to_string()]))
+            .filter(
+                webhooks::source_id
+                    .eq(source_id)
+                    .or(webhooks::series_id.eq(series_id)),
+            )
+            .select(Webhook::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(webhooks)
+    }
+
+    /// Pause or resume a webhook
+    pub async fn set_active(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        is_active: bool,
+    ) -> AppResult<Self> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let webhook = diesel::update(webhooks::table.filter(webhooks::id.eq(id)))
+            .set(webhooks::is_active.eq(is_active))
+            .returning(Webhook::as_returning())
+            .get_result::<Self>(&mut conn)
+            .await
+            .optional()?;
+
+        webhook.ok_or_else(|| AppError::NotFound(format!("Webhook {} not found", id)))
+    }
+
+    /// Delete a webhook and its delivery log
+    pub async fn delete(pool: &crate::database::DatabasePool, id: Uuid) -> AppResult<bool> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deleted = diesel::delete(webhooks::table.filter(webhooks::id.eq(id)))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+}
+
+impl WebhookDelivery {
+    /// Queue deliveries for their first attempt
+    pub async fn enqueue(
+        pool: &crate::database::DatabasePool,
+        deliveries: &[NewWebhookDelivery],
+    ) -> AppResult<usize> {
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let queued = diesel::insert_into(webhook_deliveries::table)
+            .values(deliveries)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(queued)
+    }
+
+    /// Claim up to `limit` pending deliveries that are due
+    ///
+    /// Claimed deliveries are pushed back by `lease` so that other backend
+    /// instances skip them while this one sends them.
+    pub async fn claim_due(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+        lease: chrono::Duration,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let deliveries = diesel::sql_query(
+            "UPDATE webhook_deliveries SET next_attempt_at = $1
+             WHERE id IN (
+                 SELECT id FROM webhook_deliveries
+                 WHERE status = 'pending' AND next_attempt_at <= NOW()
+                 ORDER BY next_attempt_at
+                 LIMIT $2
+                 FOR UPDATE SKIP LOCKED)
+             RETURNING *",
+        )
+        .bind::<Timestamptz, _>(Utc::now() + lease)
+        .bind::<BigInt, _>(limit)
+        .load::<Self>(&mut conn)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Store the outcome of an attempt
+    pub async fn record_attempt(
+        pool: &crate::database::DatabasePool,
+        id: Uuid,
+        attempt: &DeliveryAttempt,
+    ) -> AppResult<()> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+        let now = Utc::now();
+
+        let target = webhook_deliveries::table.filter(webhook_deliveries::id.eq(id));
+        let status = match (&attempt.error, attempt.retry_at) {
+            (None, _) => WebhookDeliveryStatus::Delivered,
+            (Some(_), Some(_)) => WebhookDeliveryStatus::Pending,
+            (Some(_), None) => WebhookDeliveryStatus::Failed,
+        };
+
+        diesel::update(target)
+            .set((
+                webhook_deliveries::status.eq(status.as_str()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::last_attempt_at.eq(now),
+                webhook_deliveries::response_status.eq(attempt.response_status),
+                webhook_deliveries::last_error.eq(&attempt.error),
+                webhook_deliveries::next_attempt_at.eq(attempt.retry_at.unwrap_or(now)),
+                webhook_deliveries::delivered_at
+                    .eq((status == WebhookDeliveryStatus::Delivered).then_some(now)),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// A webhook's most recent deliveries, newest first
+    pub async fn list_for_webhook(
+        pool: &crate::database::DatabasePool,
+        webhook_id: Uuid,
+        status: Option<WebhookDeliveryStatus>,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let mut query = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(webhook_deliveries::status.eq(status.as_str()));
+        }
+
+        let deliveries = query
+            .order(webhook_deliveries::created_at.desc())
+            .limit(limit)
+            .select(WebhookDelivery::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(deliveries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_webhook() -> NewWebhook {
+        NewWebhook {
+            url: "https://consumer.example.com/hooks/econ-graph".to_string(),
+            secret: "0123456789abcdef0123".to_string(),
+            source_id: Some(Uuid::new_v4()),
+            series_id: None,
+            event_types: vec!["series_updated".to_string()],
+            description: None,
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn test_webhook_subscription_validation() {
+        // REQUIREMENT: Webhooks are registered per data source or series with a signing secret
+        // PURPOSE: Verify scope, URL, secret length and event names are validated
+        // This ensures every stored webhook can be matched to events and signed
+
+        assert!(new_webhook().validate_subscription().is_ok());
+        assert_eq!(
+            "crawl_failed".parse::<WebhookEvent>().unwrap(),
+            WebhookEvent::CrawlFailed
+        );
+
+        let both_scopes = NewWebhook {
+            series_id: Some(Uuid::new_v4()),
+            ..new_webhook()
+        };
+        assert!(both_scopes.validate_subscription().is_err());
+
+        let short_secret = NewWebhook {
+            secret: "short".to_string(),
+            ..new_webhook()
+        };
+        assert!(short_secret.validate_subscription().is_err());
+
+        let unknown_event = NewWebhook {
+            event_types: vec!["series_deleted".to_string()],
+            ..new_webhook()
+        };
+        assert!(unknown_event.validate_subscription().is_err());
+
+        let ftp = NewWebhook {
+            url: "ftp://consumer.example.com/hooks".to_string(),
+            ..new_webhook()
+        };
+        assert!(ftp.validate_subscription().is_err());
+    }
+
+    fn webhook(id: Uuid) -> Webhook {
+        Webhook {
+            id,
+            url: "https://consumer.example.com/hooks/econ-graph".to_string(),
+            secret: None,
+            source_id: Some(Uuid::new_v4()),
+            series_id: None,
+            event_types: vec!["series_updated".to_string()],
+            description: None,
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            secret_key_id: None,
+            secret_nonce: None,
+            secret_ciphertext: None,
+        }
+    }
+
+    #[test]
+    fn test_signing_secret_is_encrypted_per_webhook() {
+        // REQUIREMENT: Webhook signing secrets are not stored in plain text
+        // PURPOSE: Verify encrypted secrets decrypt only for their own webhook and legacy plaintext secrets still sign
+        // This ensures a database dump does not leak secrets and a copied ciphertext is useless
+
+        let cipher = SecretCipher::new(std::sync::Arc::new(
+            crate::secrets::EnvKeyProvider::with_key("test-1", [7u8; 32]),
+        ));
+        let secret = SecretString::new("0123456789abcdef0123");
+
+        let mut encrypted = webhook(Uuid::new_v4());
+        let stored = cipher.encrypt(&secret, encrypted.id.as_bytes()).unwrap();
+        encrypted.secret_key_id = Some(stored.key_id.clone());
+        encrypted.secret_nonce = Some(stored.nonce.clone());
+        encrypted.secret_ciphertext = Some(stored.ciphertext.clone());
+        assert_eq!(encrypted.signing_secret(Some(&cipher)).unwrap(), secret);
+        assert!(encrypted.signing_secret(None).is_err());
+
+        let copied = Webhook {
+            id: Uuid::new_v4(),
+            ..encrypted.clone()
+        };
+        assert!(copied.signing_secret(Some(&cipher)).is_err());
+
+        let legacy = Webhook {
+            secret: Some(secret.expose().to_string()),
+            ..webhook(Uuid::new_v4())
+        };
+        assert_eq!(legacy.encrypted_secret(), None);
+        assert_eq!(legacy.signing_secret(None).unwrap(), secret);
+        assert!(webhook(Uuid::new_v4())
+            .signing_secret(Some(&cipher))
+            .is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::xbrl_calculation_discrepancies;
+
+/// A calculation linkbase summation that a filing's facts do not satisfy
+///
+/// For example `Assets = AssetsCurrent + AssetsNoncurrent` where the reported
+/// `Assets` differs from the weighted sum of its reported children by more
+/// than the rounding tolerance.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_calculation_discrepancies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct XbrlCalculationDiscrepancy {
+    pub id: Uuid,
+    pub statement_id: Uuid,
+    pub link_role: String,
+    pub parent_concept: String,
+    pub child_concepts: Vec<String>,
+    pub context_ref: String,
+    pub unit_ref: Option<String>,
+    pub reported_value: BigDecimal,
+    pub calculated_value: BigDecimal,
+    pub difference: BigDecimal,
+    pub tolerance: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New calculation discrepancy for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_calculation_discrepancies)]
+pub struct NewXbrlCalculationDiscrepancy {
+    pub statement_id: Uuid,
+    pub link_role: String,
+    pub parent_concept: String,
+    pub child_concepts: Vec<String>,
+    pub context_ref: String,
+    pub unit_ref: Option<String>,
+    pub reported_value: BigDecimal,
+    pub calculated_value: BigDecimal,
+    pub difference: BigDecimal,
+    pub tolerance: BigDecimal,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl XbrlCalculationDiscrepancy {
+    /// Replace the discrepancies stored for a statement with a fresh validation run
+    pub async fn replace_for_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+        discrepancies: &[NewXbrlCalculationDiscrepancy],
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::delete(
+            xbrl_calculation_discrepancies::table
+                .filter(xbrl_calculation_discrepancies::statement_id.eq(statement_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        if discrepancies.is_empty() {
+            return Ok(0);
+        }
+
+        let stored = diesel::insert_into(xbrl_calculation_discrepancies::table)
+            .values(discrepancies)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Discrepancies found for a statement, grouped by calculation network
+    pub async fn find_by_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let discrepancies = xbrl_calculation_discrepancies::table
+            .filter(xbrl_calculation_discrepancies::statement_id.eq(statement_id))
+            .order((
+                xbrl_calculation_discrepancies::link_role.asc(),
+                xbrl_calculation_discrepancies::parent_concept.asc(),
+                xbrl_calculation_discrepancies::context_ref.asc(),
+            ))
+            .select(XbrlCalculationDiscrepancy::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(discrepancies)
+    }
+}
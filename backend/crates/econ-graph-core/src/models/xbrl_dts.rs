@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+use crate::schema::{xbrl_dts_dependencies, xbrl_instance_dts_references};
+
+/// An edge of the taxonomy dependency graph
+///
+/// For example the us-gaap entry point `xs:import`ing the dei schema. The
+/// child is unresolved (`child_schema_id` is `None`) until its schema has been
+/// downloaded and stored.
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_dts_dependencies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct XbrlDtsDependency {
+    pub id: Uuid,
+    pub parent_schema_id: Uuid,
+    pub child_schema_id: Option<Uuid>,
+    pub child_namespace: String,
+    /// `import`, `include` or `reference` (a `linkbaseRef` in the schema)
+    pub dependency_type: String,
+    pub dependency_location: Option<String>,
+    pub is_resolved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New DTS dependency for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_dts_dependencies)]
+pub struct NewXbrlDtsDependency {
+    pub parent_schema_id: Uuid,
+    pub child_schema_id: Option<Uuid>,
+    pub child_namespace: String,
+    pub dependency_type: String,
+    pub dependency_location: Option<String>,
+    pub is_resolved: bool,
+}
+
+/// A `schemaRef` or `linkbaseRef` of a filing and how it was resolved
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_instance_dts_references)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct XbrlInstanceDtsReference {
+    pub id: Uuid,
+    pub statement_id: Uuid,
+    pub reference_type: String,
+    pub reference_role: Option<String>,
+    pub reference_href: String,
+    pub reference_arcrole: Option<String>,
+    pub resolved_schema_id: Option<Uuid>,
+    pub resolved_linkbase_id: Option<Uuid>,
+    pub is_resolved: bool,
+    pub resolution_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// New instance DTS reference for insertion
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = xbrl_instance_dts_references)]
+pub struct NewXbrlInstanceDtsReference {
+    pub statement_id: Uuid,
+    pub reference_type: String,
+    pub reference_role: Option<String>,
+    pub reference_href: String,
+    pub reference_arcrole: Option<String>,
+    pub resolved_schema_id: Option<Uuid>,
+    pub resolved_linkbase_id: Option<Uuid>,
+    pub is_resolved: bool,
+    pub resolution_error: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+impl XbrlDtsDependency {
+    /// Insert dependencies, updating the resolution of edges already stored
+    pub async fn upsert_many(
+        pool: &crate::database::DatabasePool,
+        dependencies: &[NewXbrlDtsDependency],
+    ) -> AppResult<usize> {
+        if dependencies.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let stored = diesel::insert_into(xbrl_dts_dependencies::table)
+            .values(dependencies)
+            .on_conflict((
+                xbrl_dts_dependencies::parent_schema_id,
+                xbrl_dts_dependencies::child_namespace,
+            ))
+            .do_update()
+            .set((
+                xbrl_dts_dependencies::child_schema_id
+                    .eq(excluded(xbrl_dts_dependencies::child_schema_id)),
+                xbrl_dts_dependencies::dependency_type
+                    .eq(excluded(xbrl_dts_dependencies::dependency_type)),
+                xbrl_dts_dependencies::dependency_location
+                    .eq(excluded(xbrl_dts_dependencies::dependency_location)),
+                xbrl_dts_dependencies::is_resolved.eq(excluded(xbrl_dts_dependencies::is_resolved)),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// Dependencies whose schema has not been stored yet
+    pub async fn find_unresolved(
+        pool: &crate::database::DatabasePool,
+        limit: i64,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let dependencies = xbrl_dts_dependencies::table
+            .filter(xbrl_dts_dependencies::is_resolved.eq(false))
+            .order(xbrl_dts_dependencies::created_at.asc())
+            .limit(limit)
+            .select(XbrlDtsDependency::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(dependencies)
+    }
+
+    /// Mark every edge pointing at a location as resolved to a stored schema
+    pub async fn mark_resolved(
+        pool: &crate::database::DatabasePool,
+        dependency_location: &str,
+        child_schema_id: Uuid,
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let updated = diesel::update(
+            xbrl_dts_dependencies::table
+                .filter(xbrl_dts_dependencies::dependency_location.eq(dependency_location))
+                .filter(xbrl_dts_dependencies::is_resolved.eq(false)),
+        )
+        .set((
+            xbrl_dts_dependencies::child_schema_id.eq(Some(child_schema_id)),
+            xbrl_dts_dependencies::is_resolved.eq(true),
+        ))
+        .execute(&mut conn)
+        .await?;
+
+        Ok(updated)
+    }
+}
+
+impl XbrlInstanceDtsReference {
+    /// Replace the references stored for a statement with a fresh resolution run
+    pub async fn replace_for_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+        references: &[NewXbrlInstanceDtsReference],
+    ) -> AppResult<usize> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        diesel::delete(
+            xbrl_instance_dts_references::table
+                .filter(xbrl_instance_dts_references::statement_id.eq(statement_id)),
+        )
+        .execute(&mut conn)
+        .await?;
+
+        if references.is_empty() {
+            return Ok(0);
+        }
+
+        let stored = diesel::insert_into(xbrl_instance_dts_references::table)
+            .values(references)
+            .execute(&mut conn)
+            .await?;
+
+        Ok(stored)
+    }
+
+    /// References of a statement in the order they appear in the filing
+    pub async fn find_by_statement(
+        pool: &crate::database::DatabasePool,
+        statement_id: Uuid,
+    ) -> AppResult<Vec<Self>> {
+        let mut conn = pool.get().await.map_err(connection_error)?;
+
+        let references = xbrl_instance_dts_references::table
+            .filter(xbrl_instance_dts_references::statement_id.eq(statement_id))
+            .order(xbrl_instance_dts_references::id.asc())
+            .select(XbrlInstanceDtsReference::as_select())
+            .load::<Self>(&mut conn)
+            .await?;
+
+        Ok(references)
+    }
+}
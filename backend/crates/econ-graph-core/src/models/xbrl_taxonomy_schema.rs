@@ -48,6 +48,7 @@ use crate::schema::xbrl_taxonomy_schemas;
 ///     relationships_extracted: 0,
 ///     created_at: Utc::now(),
 ///     updated_at: Utc::now(),
+///     blob_hash: None,
 /// };
 /// ```
 
@@ -78,6 +79,8 @@ pub struct XbrlTaxonomySchema {
     pub relationships_extracted: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Hash of the file in `document_blobs`, where new files are stored
+    pub blob_hash: Option<String>,
 }
 
 impl XbrlTaxonomySchema {
@@ -116,6 +119,7 @@ impl XbrlTaxonomySchema {
             relationships_extracted: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            blob_hash: None,
         }
     }
 
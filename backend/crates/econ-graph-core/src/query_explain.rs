@@ -0,0 +1,152 @@
+//! # Query Explain
+//!
+//! Collects what one request did, for diagnosing slow requests without a
+//! profiler: every SQL statement with its duration, and the lookups of
+//! in-process caches. Collection is scoped to a task with [`explain_scope`];
+//! outside a scope, and in tasks spawned from it, the `record_*` functions do
+//! nothing, so instrumented code calls them unconditionally.
+//!
+//! Bind values are never recorded, only statement text.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Most statements kept per request; later ones are only counted
+pub const MAX_EXPLAINED_STATEMENTS: usize = 500;
+
+tokio::task_local! {
+    static CURRENT: Arc<QueryExplain>;
+}
+
+/// A statement run while explaining
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainedStatement {
+    pub statement: String,
+    pub duration_ms: f64,
+    pub failed: bool,
+}
+
+/// Lookups of one cache while explaining
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheLookups {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// What a request did, as collected so far
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainReport {
+    pub statements: Vec<ExplainedStatement>,
+    /// Statements run, including those beyond [`MAX_EXPLAINED_STATEMENTS`]
+    pub statement_count: usize,
+    pub statement_total_ms: f64,
+    /// Lookups per cache name
+    pub caches: BTreeMap<String, CacheLookups>,
+}
+
+/// Collector of one request's statements and cache lookups
+#[derive(Debug, Default)]
+pub struct QueryExplain {
+    report: Mutex<ExplainReport>,
+}
+
+impl QueryExplain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ExplainReport> {
+        self.report.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn record_statement(&self, statement: &str, duration: Duration, failed: bool) {
+        let mut report = self.lock();
+        let duration_ms = duration.as_secs_f64() * 1000.0;
+        report.statement_count += 1;
+        report.statement_total_ms += duration_ms;
+        if report.statements.len() < MAX_EXPLAINED_STATEMENTS {
+            report.statements.push(ExplainedStatement {
+                statement: statement.to_string(),
+                duration_ms,
+                failed,
+            });
+        }
+    }
+
+    pub fn record_cache_lookup(&self, cache: &str, hit: bool) {
+        let mut report = self.lock();
+        let lookups = report.caches.entry(cache.to_string()).or_default();
+        if hit {
+            lookups.hits += 1;
+        } else {
+            lookups.misses += 1;
+        }
+    }
+
+    /// Everything collected so far
+    pub fn report(&self) -> ExplainReport {
+        self.lock().clone()
+    }
+}
+
+/// Run `future` collecting its statements and cache lookups into `explain`
+pub async fn explain_scope<F: Future>(explain: Arc<QueryExplain>, future: F) -> F::Output {
+    CURRENT.scope(explain, future).await
+}
+
+/// Record a finished statement in the current scope, if any
+pub fn record_statement(statement: &str, duration: Duration, failed: bool) {
+    let _ = CURRENT.try_with(|explain| explain.record_statement(statement, duration, failed));
+}
+
+/// Record a cache lookup in the current scope, if any
+pub fn record_cache_lookup(cache: &str, hit: bool) {
+    let _ = CURRENT.try_with(|explain| explain.record_cache_lookup(cache, hit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_only_work_inside_the_scope_is_recorded() {
+        // REQUIREMENT: Explain mode reports the SQL statements and cache hits of one request
+        // PURPOSE: Verify statements and lookups are collected inside the scope and ignored outside it
+        // This ensures concurrent requests never see each other's statements in their explain output
+
+        let explain = Arc::new(QueryExplain::new());
+        record_statement("SELECT 1", Duration::from_millis(5), false);
+
+        explain_scope(explain.clone(), async {
+            record_statement(
+                "SELECT * FROM data_points WHERE series_id = $1",
+                Duration::from_millis(12),
+                false,
+            );
+            record_statement("SELECT broken", Duration::from_millis(3), true);
+            record_cache_lookup("data_points", false);
+            record_cache_lookup("data_points", true);
+            record_cache_lookup("data_points", true);
+        })
+        .await;
+        record_cache_lookup("data_points", true);
+
+        let report = explain.report();
+        assert_eq!(report.statement_count, 2);
+        assert_eq!(
+            report.statements[0].statement,
+            "SELECT * FROM data_points WHERE series_id = $1"
+        );
+        assert!(report.statements[1].failed);
+        assert!((report.statement_total_ms - 15.0).abs() < 1e-9);
+        assert_eq!(
+            report.caches["data_points"],
+            CacheLookups { hits: 2, misses: 1 }
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! # Outbound Rate Limiting
+//!
+//! Token-bucket rate limiter shared by the SEC and economic data crawlers.
+//! Budgets are configured per host, and each API key used against a host
+//! gets its own bucket so that quotas tied to a key are respected no matter
+//! which crawler or task issues the request.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_core::rate_limiter::{shared_rate_limiter, FRED_HOST};
+//!
+//! # async fn example(api_key: &str) {
+//! // Waits until the FRED budget for this key allows another request
+//! shared_rate_limiter().acquire(FRED_HOST, Some(api_key)).await;
+//! # }
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// FRED API host
+pub const FRED_HOST: &str = "api.stlouisfed.org";
+/// BLS public API host
+pub const BLS_HOST: &str = "api.bls.gov";
+/// BEA API host
+pub const BEA_HOST: &str = "apps.bea.gov";
+/// World Bank Indicators API host
+pub const WORLD_BANK_HOST: &str = "api.worldbank.org";
+/// IMF SDMX data service host
+pub const IMF_HOST: &str = "dataservices.imf.org";
+/// Eurostat dissemination API host
+pub const EUROSTAT_HOST: &str = "ec.europa.eu";
+/// SEC EDGAR archive host
+pub const SEC_HOST: &str = "www.sec.gov";
+/// SEC EDGAR structured data host
+pub const SEC_DATA_HOST: &str = "data.sec.gov";
+
+/// Request budget for one bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Requests allowed per `period` once the burst is used up
+    pub requests: u32,
+    pub period: Duration,
+    /// Requests that may be made back to back from a full bucket
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Budget of `requests` per `period`, with a burst of the same size
+    pub fn new(requests: u32, period: Duration) -> Self {
+        let requests = requests.max(1);
+        Self {
+            requests,
+            period,
+            burst: requests,
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Override the burst size (at least one request)
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Time for one token to be refilled
+    fn interval(&self) -> Duration {
+        self.period / self.requests
+    }
+}
+
+/// Token bucket state
+///
+/// `tokens` may go negative: each waiter reserves its token up front and
+/// sleeps for the deficit, so concurrent callers are served in order instead
+/// of racing for the next refill.
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        let refilled = elapsed.as_secs_f64() / self.limit.interval().as_secs_f64();
+        self.tokens = (self.tokens + refilled).min(self.limit.burst as f64);
+        self.refilled_at = now;
+    }
+
+    /// Time until a token is available, without taking one
+    fn wait_time(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            self.limit.interval().mul_f64(1.0 - self.tokens)
+        }
+    }
+
+    /// Reserve a token, returning how long the caller must wait before using it
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let wait = self.wait_time(now);
+        self.tokens -= 1.0;
+        wait
+    }
+}
+
+/// Keyed token-bucket rate limiter
+///
+/// Hosts without a configured budget use the default budget. API keys are
+/// only stored as a hash.
+#[derive(Debug)]
+pub struct RateLimiter {
+    default_limit: RateLimit,
+    host_limits: HashMap<String, RateLimit>,
+    buckets: Mutex<HashMap<(String, u64), Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter applying `default_limit` to every host
+    pub fn new(default_limit: RateLimit) -> Self {
+        Self {
+            default_limit,
+            host_limits: HashMap::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the budget for a host
+    pub fn with_host_limit(mut self, host: &str, limit: RateLimit) -> Self {
+        self.host_limits.insert(host.to_string(), limit);
+        self
+    }
+
+    /// Budgets for the public data APIs we crawl
+    ///
+    /// FRED and BLS budgets follow the `FRED_RATE_LIMIT_PER_MINUTE` and
+    /// `BLS_RATE_LIMIT_PER_MINUTE` settings from [`crate::config::RateLimitConfig`].
+    pub fn for_data_sources(limits: &crate::config::RateLimitConfig) -> Self {
+        Self::new(RateLimit::per_second(5))
+            .with_host_limit(
+                FRED_HOST,
+                RateLimit::per_minute(limits.fred_rate_limit_per_minute).with_burst(10),
+            )
+            .with_host_limit(
+                BLS_HOST,
+                RateLimit::per_minute(limits.bls_rate_limit_per_minute).with_burst(10),
+            )
+            .with_host_limit(BEA_HOST, RateLimit::per_minute(100).with_burst(10))
+            .with_host_limit(WORLD_BANK_HOST, RateLimit::per_second(8).with_burst(16))
+            // The IMF allows 10 requests per 5 seconds per client
+            .with_host_limit(
+                IMF_HOST,
+                RateLimit::new(10, Duration::from_secs(5)).with_burst(5),
+            )
+            .with_host_limit(EUROSTAT_HOST, RateLimit::per_second(4).with_burst(8))
+            .with_host_limit(SEC_HOST, RateLimit::per_second(10))
+            .with_host_limit(SEC_DATA_HOST, RateLimit::per_second(10))
+    }
+
+    /// Budget applied to a host
+    pub fn limit_for(&self, host: &str) -> RateLimit {
+        self.host_limits
+            .get(host)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// Wait until a request to `host` (with an optional API key) is allowed
+    pub async fn acquire(&self, host: &str, api_key: Option<&str>) {
+        let wait = self.with_bucket(host, api_key, |bucket, now| bucket.reserve(now));
+        if !wait.is_zero() {
+            debug!("Rate limit reached for {}, waiting {:?}", host, wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take a permit if one is available right now
+    ///
+    /// Returns the time until the next permit when the budget is exhausted.
+    pub fn try_acquire(&self, host: &str, api_key: Option<&str>) -> Result<(), Duration> {
+        self.with_bucket(host, api_key, |bucket, now| {
+            let wait = bucket.wait_time(now);
+            if wait.is_zero() {
+                bucket.tokens -= 1.0;
+                Ok(())
+            } else {
+                Err(wait)
+            }
+        })
+    }
+
+    /// Time until a permit is available, without taking one
+    pub fn time_until_available(&self, host: &str, api_key: Option<&str>) -> Duration {
+        self.with_bucket(host, api_key, |bucket, now| bucket.wait_time(now))
+    }
+
+    fn with_bucket<T>(
+        &self,
+        host: &str,
+        api_key: Option<&str>,
+        f: impl FnOnce(&mut Bucket, Instant) -> T,
+    ) -> T {
+        let now = Instant::now();
+        let key = (host.to_string(), api_key.map(key_fingerprint).unwrap_or(0));
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets
+            .entry(key)
+            .or_insert_with(|| Bucket::new(self.limit_for(host), now));
+        f(bucket, now)
+    }
+}
+
+/// Hash identifying an API key's bucket without keeping the key in memory
+fn key_fingerprint(api_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    api_key.hash(&mut hasher);
+    // Reserve 0 for requests made without a key
+    hasher.finish().max(1)
+}
+
+/// Process-wide limiter shared by all crawlers
+///
+/// Budgets are only respected if every request to a host goes through the
+/// same limiter, so discovery services and crawlers should use this instance.
+pub fn shared_rate_limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| {
+        let limits = crate::config::Config::default().rate_limits;
+        let per_minute = |name: &str, default: u32| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        RateLimiter::for_data_sources(&crate::config::RateLimitConfig {
+            fred_rate_limit_per_minute: per_minute(
+                "FRED_RATE_LIMIT_PER_MINUTE",
+                limits.fred_rate_limit_per_minute,
+            ),
+            bls_rate_limit_per_minute: per_minute(
+                "BLS_RATE_LIMIT_PER_MINUTE",
+                limits.bls_rate_limit_per_minute,
+            ),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_burst_then_refill() {
+        // REQUIREMENT: Crawlers respect upstream API quotas
+        // PURPOSE: Verify a full bucket allows its burst and then spaces requests by the refill interval
+        // This ensures FRED and BLS see at most the configured request rate
+
+        let limit = RateLimit::per_second(10).with_burst(2);
+        let start = Instant::now();
+        let mut bucket = Bucket::new(limit, start);
+
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+        assert_eq!(bucket.reserve(start), Duration::ZERO);
+
+        let wait = bucket.reserve(start);
+        assert!((wait.as_secs_f64() - 0.1).abs() < 1e-6);
+        let wait = bucket.reserve(start);
+        assert!((wait.as_secs_f64() - 0.2).abs() < 1e-6);
+
+        // After a full second the bucket is refilled, but never above the burst
+        let later = start + Duration::from_secs(1);
+        bucket.refill(later);
+        assert!((bucket.tokens - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_budgets_are_per_host_and_api_key() {
+        // REQUIREMENT: Per-host and per-API-key request budgets
+        // PURPOSE: Verify exhausting one key's budget does not block other keys or hosts
+        // This ensures separate API keys get their own quota as the providers enforce it
+
+        let limiter = RateLimiter::new(RateLimit::per_second(100))
+            .with_host_limit(FRED_HOST, RateLimit::per_minute(60).with_burst(1));
+
+        assert!(limiter.try_acquire(FRED_HOST, Some("key-a")).is_ok());
+        let wait = limiter.try_acquire(FRED_HOST, Some("key-a")).unwrap_err();
+        assert!(wait > Duration::from_millis(900));
+
+        assert!(limiter.try_acquire(FRED_HOST, Some("key-b")).is_ok());
+        assert!(limiter.try_acquire(BLS_HOST, Some("key-a")).is_ok());
+        assert_eq!(limiter.limit_for(BLS_HOST), RateLimit::per_second(100));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill() {
+        // REQUIREMENT: Async crawlers wait for a permit instead of failing
+        // PURPOSE: Verify acquire sleeps once the burst is used up
+        // This ensures discovery runs slow down rather than trigger HTTP 429s
+
+        let limiter = RateLimiter::new(RateLimit::per_second(20).with_burst(1));
+
+        let start = Instant::now();
+        limiter.acquire("example.org", None).await;
+        limiter.acquire("example.org", None).await;
+        limiter.acquire("example.org", None).await;
+
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
@@ -78,6 +78,31 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    bls_catalog_syncs (survey) {
+        #[max_length = 10]
+        survey -> Varchar,
+        etag -> Nullable<Text>,
+        last_modified -> Nullable<Text>,
+        series_listed -> Int4,
+        synced_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    change_feed (id) {
+        id -> Int8,
+        sequence -> Nullable<Int8>,
+        #[max_length = 20]
+        entity_type -> Varchar,
+        entity_id -> Uuid,
+        #[max_length = 10]
+        operation -> Varchar,
+        txid -> Int8,
+        changed_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chart_annotations (id) {
         id -> Uuid,
@@ -99,6 +124,7 @@ diesel::table! {
         tags -> Nullable<Array<Nullable<Text>>>,
         created_at -> Nullable<Timestamptz>,
         updated_at -> Nullable<Timestamptz>,
+        organization_id -> Nullable<Uuid>,
     }
 }
 
@@ -172,6 +198,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    component_status_transitions (id) {
+        id -> Int8,
+        #[max_length = 255]
+        component -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+        message -> Nullable<Text>,
+        changed_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     countries (id) {
         id -> Uuid,
@@ -269,6 +307,48 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    cusip_mappings (cusip) {
+        #[max_length = 9]
+        cusip -> Varchar,
+        company_id -> Uuid,
+        #[max_length = 255]
+        issuer_name -> Varchar,
+        #[max_length = 20]
+        source -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    data_lineage (id) {
+        id -> Uuid,
+        data_point_id -> Nullable<Uuid>,
+        financial_line_item_id -> Nullable<Uuid>,
+        crawl_attempt_id -> Nullable<Uuid>,
+        statement_id -> Nullable<Uuid>,
+        #[max_length = 50]
+        source -> Varchar,
+        source_url -> Nullable<Text>,
+        crawled_at -> Timestamptz,
+        processing_steps -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    data_point_corrections (id) {
+        id -> Uuid,
+        data_point_id -> Uuid,
+        corrected_data_point_id -> Nullable<Uuid>,
+        series_id -> Uuid,
+        reason -> Text,
+        corrected_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     data_points (id) {
         id -> Uuid,
@@ -282,6 +362,56 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    data_source_credentials (id) {
+        id -> Uuid,
+        data_source_id -> Uuid,
+        #[max_length = 100]
+        key_id -> Varchar,
+        nonce -> Bytea,
+        ciphertext -> Bytea,
+        updated_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    data_source_key_usage (data_source_id, key_hash, usage_date) {
+        data_source_id -> Uuid,
+        #[max_length = 16]
+        key_hash -> Varchar,
+        usage_date -> Date,
+        bytes_downloaded -> Int8,
+        request_count -> Int4,
+        throttled_count -> Int4,
+        last_throttled_at -> Nullable<Timestamptz>,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    data_source_statistics (source_id) {
+        source_id -> Uuid,
+        series_count -> Int8,
+        active_series_count -> Int8,
+        data_point_count -> Int8,
+        earliest_date -> Nullable<Date>,
+        latest_date -> Nullable<Date>,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    data_source_usage (data_source_id, usage_date) {
+        data_source_id -> Uuid,
+        usage_date -> Date,
+        bytes_downloaded -> Int8,
+        request_count -> Int4,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     data_sources (id) {
         id -> Uuid,
@@ -306,6 +436,50 @@ diesel::table! {
         api_documentation_url -> Nullable<Varchar>,
         #[max_length = 255]
         api_key_name -> Nullable<Varchar>,
+        daily_byte_quota -> Nullable<Int8>,
+        daily_request_quota -> Nullable<Int4>,
+        #[max_length = 20]
+        access_policy -> Varchar,
+        fallback_urls -> Array<Text>,
+    }
+}
+
+diesel::table! {
+    derived_series (id) {
+        id -> Uuid,
+        series_id -> Uuid,
+        formula -> Text,
+        formula_ast -> Jsonb,
+        created_by -> Nullable<Uuid>,
+        last_computed_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    derived_series_inputs (derived_series_id, variable) {
+        derived_series_id -> Uuid,
+        #[max_length = 64]
+        variable -> Varchar,
+        input_series_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    document_blobs (content_hash) {
+        #[max_length = 64]
+        content_hash -> Varchar,
+        content -> Bytea,
+        size_bytes -> Int8,
+        stored_size_bytes -> Int8,
+        #[max_length = 10]
+        compression_type -> Varchar,
+        reference_count -> Int4,
+        created_at -> Timestamptz,
+        verified_at -> Nullable<Timestamptz>,
+        compression_level -> Nullable<Int2>,
     }
 }
 
@@ -339,6 +513,40 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    email_deliveries (id) {
+        id -> Uuid,
+        user_id -> Nullable<Uuid>,
+        #[max_length = 255]
+        recipient -> Varchar,
+        #[max_length = 50]
+        template -> Varchar,
+        subject -> Text,
+        text_body -> Text,
+        html_body -> Text,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_attempt_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        sent_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    email_preferences (user_id) {
+        user_id -> Uuid,
+        invites -> Bool,
+        alerts -> Bool,
+        weekly_digest -> Bool,
+        last_digest_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     event_country_impacts (id) {
         id -> Uuid,
@@ -354,6 +562,52 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    export_jobs (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        series_ids -> Array<Uuid>,
+        start_date -> Nullable<Date>,
+        end_date -> Nullable<Date>,
+        #[max_length = 10]
+        format -> Varchar,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        storage_key -> Nullable<Text>,
+        file_size_bytes -> Nullable<Int8>,
+        row_count -> Nullable<Int8>,
+        error_message -> Nullable<Text>,
+        started_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+        expires_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    filing_sections (id) {
+        id -> Uuid,
+        statement_id -> Uuid,
+        #[max_length = 50]
+        section_type -> Varchar,
+        #[max_length = 10]
+        item_number -> Varchar,
+        #[max_length = 10]
+        part -> Nullable<Varchar>,
+        #[max_length = 500]
+        title -> Varchar,
+        content -> Text,
+        word_count -> Int4,
+        #[max_length = 64]
+        content_hash -> Varchar,
+        source_url -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     financial_annotations (id) {
         id -> Uuid,
@@ -406,6 +660,13 @@ diesel::table! {
         calculation_formula -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        normalized_value -> Nullable<Numeric>,
+        #[max_length = 50]
+        normalized_unit -> Nullable<Varchar>,
+        fx_rate -> Nullable<Numeric>,
+        dimensions -> Jsonb,
+        period_start_date -> Nullable<Date>,
+        period_end_date -> Nullable<Date>,
     }
 }
 
@@ -469,6 +730,23 @@ diesel::table! {
         restatement_reason -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 64]
+        xbrl_file_blob_hash -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    fx_rates (id) {
+        id -> Uuid,
+        #[max_length = 3]
+        base_currency -> Varchar,
+        #[max_length = 3]
+        quote_currency -> Varchar,
+        rate_date -> Date,
+        rate -> Numeric,
+        #[max_length = 50]
+        source -> Varchar,
+        created_at -> Timestamptz,
     }
 }
 
@@ -526,6 +804,143 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    industry_benchmarks (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        group_type -> Varchar,
+        #[max_length = 100]
+        group_code -> Varchar,
+        #[max_length = 100]
+        ratio_name -> Varchar,
+        fiscal_year -> Int4,
+        fiscal_quarter -> Nullable<Int4>,
+        company_count -> Int4,
+        mean_value -> Numeric,
+        median_value -> Numeric,
+        p10_value -> Numeric,
+        p25_value -> Numeric,
+        p75_value -> Numeric,
+        p90_value -> Numeric,
+        calculated_at -> Timestamptz,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    insider_crawl_days (index_date) {
+        index_date -> Date,
+        filings_found -> Int4,
+        filings_stored -> Int4,
+        filings_failed -> Int4,
+        transactions_stored -> Int4,
+        crawled_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    insider_transactions (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        accession_number -> Varchar,
+        #[max_length = 10]
+        form_type -> Varchar,
+        filed_date -> Date,
+        line_number -> Int4,
+        #[max_length = 10]
+        issuer_cik -> Varchar,
+        #[max_length = 255]
+        issuer_name -> Varchar,
+        #[max_length = 10]
+        issuer_ticker -> Nullable<Varchar>,
+        #[max_length = 10]
+        insider_cik -> Varchar,
+        #[max_length = 255]
+        insider_name -> Varchar,
+        is_director -> Bool,
+        is_officer -> Bool,
+        is_ten_percent_owner -> Bool,
+        is_other -> Bool,
+        #[max_length = 255]
+        officer_title -> Nullable<Varchar>,
+        #[max_length = 255]
+        security_title -> Varchar,
+        is_derivative -> Bool,
+        transaction_date -> Date,
+        #[max_length = 1]
+        transaction_code -> Varchar,
+        shares -> Numeric,
+        price_per_share -> Nullable<Numeric>,
+        #[max_length = 1]
+        acquired_disposed -> Varchar,
+        shares_owned_after -> Nullable<Numeric>,
+        #[max_length = 1]
+        ownership_nature -> Varchar,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    institutional_crawl_days (index_date) {
+        index_date -> Date,
+        filings_found -> Int4,
+        filings_stored -> Int4,
+        filings_failed -> Int4,
+        holdings_stored -> Int4,
+        crawled_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    institutional_filings (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        accession_number -> Varchar,
+        #[max_length = 10]
+        form_type -> Varchar,
+        #[max_length = 20]
+        amendment_type -> Nullable<Varchar>,
+        #[max_length = 10]
+        manager_cik -> Varchar,
+        #[max_length = 255]
+        manager_name -> Varchar,
+        report_period -> Date,
+        filed_date -> Date,
+        holdings_count -> Int4,
+        total_value_usd -> Numeric,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    institutional_holdings (id) {
+        id -> Uuid,
+        filing_id -> Uuid,
+        #[max_length = 10]
+        manager_cik -> Varchar,
+        report_period -> Date,
+        #[max_length = 9]
+        cusip -> Varchar,
+        #[max_length = 255]
+        issuer_name -> Varchar,
+        #[max_length = 150]
+        title_of_class -> Varchar,
+        value_usd -> Numeric,
+        shares -> Numeric,
+        #[max_length = 3]
+        share_type -> Varchar,
+        #[max_length = 4]
+        put_call -> Nullable<Varchar>,
+        #[max_length = 10]
+        investment_discretion -> Varchar,
+        voting_sole -> Int8,
+        voting_shared -> Int8,
+        voting_none -> Int8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     leading_indicators (id) {
         id -> Uuid,
@@ -542,6 +957,157 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    learning_achievements (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 200]
+        achievement_key -> Varchar,
+        #[max_length = 50]
+        achievement_type -> Varchar,
+        #[max_length = 255]
+        title -> Varchar,
+        description -> Text,
+        criteria -> Text,
+        earned_at -> Timestamptz,
+        module_id -> Nullable<Uuid>,
+        learning_path_id -> Nullable<Uuid>,
+        #[max_length = 500]
+        badge_url -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    learning_progress (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        module_id -> Uuid,
+        #[max_length = 20]
+        status -> Varchar,
+        progress_percentage -> Float8,
+        time_spent_minutes -> Int4,
+        completed_sections -> Array<Uuid>,
+        completed_exercises -> Array<Uuid>,
+        quiz_scores -> Jsonb,
+        started_at -> Timestamptz,
+        last_accessed_at -> Timestamptz,
+        completed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        #[max_length = 50]
+        notification_type -> Varchar,
+        #[max_length = 50]
+        subject_type -> Varchar,
+        subject_id -> Uuid,
+        #[max_length = 255]
+        title -> Varchar,
+        message -> Text,
+        read_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organization_chart_shares (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        chart_id -> Uuid,
+        shared_by -> Nullable<Uuid>,
+        #[max_length = 20]
+        permission_level -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organization_members (id) {
+        id -> Uuid,
+        organization_id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 20]
+        role -> Varchar,
+        invited_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    organizations (id) {
+        id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 100]
+        slug -> Varchar,
+        description -> Nullable<Text>,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    release_calendars (id) {
+        id -> Uuid,
+        source_id -> Nullable<Uuid>,
+        series_id -> Nullable<Uuid>,
+        #[max_length = 20]
+        frequency -> Varchar,
+        day_of_week -> Nullable<Int2>,
+        week_of_month -> Nullable<Int2>,
+        day_of_month -> Nullable<Int2>,
+        month_of_period -> Int2,
+        release_time -> Time,
+        #[max_length = 50]
+        time_zone -> Varchar,
+        crawl_delay_minutes -> Int4,
+        is_learned -> Bool,
+        confidence -> Nullable<Float8>,
+        sample_count -> Int4,
+        is_active -> Bool,
+        next_release_at -> Nullable<Timestamptz>,
+        last_queued_release_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    saved_charts (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        #[max_length = 255]
+        title -> Varchar,
+        description -> Nullable<Text>,
+        chart_config -> Jsonb,
+        series_ids -> Array<Uuid>,
+        transformations -> Jsonb,
+        start_date -> Nullable<Date>,
+        end_date -> Nullable<Date>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        is_public -> Bool,
+    }
+}
+
+diesel::table! {
+    search_synonyms (id) {
+        id -> Uuid,
+        #[max_length = 100]
+        term -> Varchar,
+        synonyms -> Array<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     security_events (id) {
         id -> Uuid,
@@ -563,6 +1129,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    series_alert_rules (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        series_id -> Uuid,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 20]
+        condition -> Varchar,
+        threshold -> Numeric,
+        window_periods -> Int4,
+        is_active -> Bool,
+        is_triggered -> Bool,
+        last_evaluated_at -> Nullable<Timestamptz>,
+        last_triggered_at -> Nullable<Timestamptz>,
+        last_triggered_date -> Nullable<Date>,
+        last_triggered_value -> Nullable<Numeric>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    series_links (id) {
+        id -> Uuid,
+        series_id -> Uuid,
+        linked_series_id -> Uuid,
+        #[max_length = 20]
+        link_source -> Varchar,
+        confidence -> Float8,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     series_metadata (id) {
         id -> Uuid,
@@ -584,6 +1185,54 @@ diesel::table! {
         is_active -> Bool,
         created_at -> Nullable<Timestamptz>,
         updated_at -> Nullable<Timestamptz>,
+        #[max_length = 255]
+        geography -> Nullable<Varchar>,
+        categories -> Array<Text>,
+        tags -> Array<Text>,
+        enriched_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    series_quality_scores (series_id) {
+        series_id -> Uuid,
+        score -> Float8,
+        observation_count -> Int4,
+        outlier_count -> Int4,
+        missing_periods -> Int4,
+        last_observation_date -> Nullable<Date>,
+        is_stale -> Bool,
+        checked_through -> Timestamptz,
+        computed_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    series_rollups (series_id, period, period_start) {
+        series_id -> Uuid,
+        #[max_length = 10]
+        period -> Varchar,
+        period_start -> Date,
+        period_end -> Date,
+        first_value -> Numeric,
+        min_value -> Numeric,
+        max_value -> Numeric,
+        mean_value -> Numeric,
+        last_value -> Numeric,
+        last_date -> Date,
+        observation_count -> Int4,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    series_statistics (series_id) {
+        series_id -> Uuid,
+        source_id -> Uuid,
+        data_point_count -> Int8,
+        earliest_date -> Nullable<Date>,
+        latest_date -> Nullable<Date>,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -658,6 +1307,81 @@ diesel::table! {
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
         last_login_at -> Nullable<Timestamptz>,
+        #[max_length = 20]
+        subscription_tier -> Varchar,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Uuid,
+        webhook_id -> Uuid,
+        #[max_length = 50]
+        event_type -> Varchar,
+        payload -> Jsonb,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_attempt_at -> Nullable<Timestamptz>,
+        response_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Uuid,
+        url -> Text,
+        #[max_length = 255]
+        secret -> Nullable<Varchar>,
+        source_id -> Nullable<Uuid>,
+        series_id -> Nullable<Uuid>,
+        event_types -> Array<Text>,
+        description -> Nullable<Text>,
+        is_active -> Bool,
+        created_by -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        #[max_length = 100]
+        secret_key_id -> Nullable<Varchar>,
+        secret_nonce -> Nullable<Bytea>,
+        secret_ciphertext -> Nullable<Bytea>,
+    }
+}
+
+diesel::table! {
+    xbrl_batch_checkpoints (batch_name) {
+        #[max_length = 100]
+        batch_name -> Varchar,
+        last_statement_id -> Nullable<Uuid>,
+        filings_completed -> Int4,
+        filings_failed -> Int4,
+        started_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        finished_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    xbrl_calculation_discrepancies (id) {
+        id -> Uuid,
+        statement_id -> Uuid,
+        link_role -> Text,
+        #[max_length = 255]
+        parent_concept -> Varchar,
+        child_concepts -> Array<Text>,
+        #[max_length = 255]
+        context_ref -> Varchar,
+        #[max_length = 100]
+        unit_ref -> Nullable<Varchar>,
+        reported_value -> Numeric,
+        calculated_value -> Numeric,
+        difference -> Numeric,
+        tolerance -> Numeric,
+        created_at -> Timestamptz,
     }
 }
 
@@ -728,6 +1452,8 @@ diesel::table! {
         relationships_extracted -> Integer,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 64]
+        blob_hash -> Nullable<Varchar>,
     }
 }
 
@@ -755,6 +1481,8 @@ diesel::table! {
         labels_extracted -> Integer,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        #[max_length = 64]
+        blob_hash -> Nullable<Varchar>,
     }
 }
 
@@ -793,25 +1521,71 @@ diesel::joinable!(annotation_comments -> chart_annotations (annotation_id));
 diesel::joinable!(annotation_comments -> users (user_id));
 diesel::joinable!(annotation_replies -> financial_annotations (annotation_id));
 diesel::joinable!(audit_logs -> users (user_id));
+diesel::joinable!(chart_annotations -> organizations (organization_id));
 diesel::joinable!(chart_annotations -> users (user_id));
 diesel::joinable!(crawl_attempts -> economic_series (series_id));
+diesel::joinable!(cusip_mappings -> companies (company_id));
+diesel::joinable!(data_lineage -> crawl_attempts (crawl_attempt_id));
+diesel::joinable!(data_lineage -> data_points (data_point_id));
+diesel::joinable!(data_lineage -> financial_line_items (financial_line_item_id));
+diesel::joinable!(data_lineage -> financial_statements (statement_id));
+diesel::joinable!(data_point_corrections -> economic_series (series_id));
+diesel::joinable!(data_point_corrections -> users (corrected_by));
 diesel::joinable!(data_points -> economic_series (series_id));
+diesel::joinable!(data_source_credentials -> data_sources (data_source_id));
+diesel::joinable!(data_source_credentials -> users (updated_by));
+diesel::joinable!(data_source_key_usage -> data_sources (data_source_id));
+diesel::joinable!(data_source_statistics -> data_sources (source_id));
+diesel::joinable!(data_source_usage -> data_sources (data_source_id));
+diesel::joinable!(derived_series -> economic_series (series_id));
+diesel::joinable!(derived_series -> users (created_by));
+diesel::joinable!(derived_series_inputs -> derived_series (derived_series_id));
+diesel::joinable!(derived_series_inputs -> economic_series (input_series_id));
 diesel::joinable!(economic_series -> data_sources (source_id));
+diesel::joinable!(email_deliveries -> users (user_id));
+diesel::joinable!(email_preferences -> users (user_id));
 diesel::joinable!(event_country_impacts -> countries (country_id));
 diesel::joinable!(event_country_impacts -> global_economic_events (event_id));
+diesel::joinable!(export_jobs -> users (user_id));
+diesel::joinable!(filing_sections -> financial_statements (statement_id));
 diesel::joinable!(financial_annotations -> financial_line_items (line_item_id));
 diesel::joinable!(financial_annotations -> financial_statements (statement_id));
 diesel::joinable!(financial_line_items -> financial_statements (statement_id));
 diesel::joinable!(financial_ratios -> financial_statements (statement_id));
 diesel::joinable!(financial_statements -> companies (company_id));
+diesel::joinable!(financial_statements -> document_blobs (xbrl_file_blob_hash));
 diesel::joinable!(global_economic_events -> countries (primary_country_id));
 diesel::joinable!(global_economic_indicators -> countries (country_id));
 diesel::joinable!(global_indicator_data -> global_economic_indicators (indicator_id));
+diesel::joinable!(institutional_holdings -> institutional_filings (filing_id));
+diesel::joinable!(learning_achievements -> users (user_id));
+diesel::joinable!(learning_progress -> users (user_id));
+diesel::joinable!(notifications -> users (user_id));
+diesel::joinable!(organization_chart_shares -> organizations (organization_id));
+diesel::joinable!(organization_chart_shares -> users (shared_by));
+diesel::joinable!(organization_members -> organizations (organization_id));
+diesel::joinable!(organization_members -> users (user_id));
+diesel::joinable!(release_calendars -> data_sources (source_id));
+diesel::joinable!(release_calendars -> economic_series (series_id));
+diesel::joinable!(saved_charts -> users (user_id));
+diesel::joinable!(series_alert_rules -> economic_series (series_id));
+diesel::joinable!(series_alert_rules -> users (user_id));
+diesel::joinable!(series_links -> users (created_by));
 diesel::joinable!(series_metadata -> data_sources (source_id));
+diesel::joinable!(series_quality_scores -> economic_series (series_id));
+diesel::joinable!(series_rollups -> economic_series (series_id));
+diesel::joinable!(series_statistics -> economic_series (series_id));
 diesel::joinable!(user_data_source_preferences -> data_sources (data_source_id));
 diesel::joinable!(user_data_source_preferences -> users (user_id));
 diesel::joinable!(user_sessions -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> data_sources (source_id));
+diesel::joinable!(webhooks -> economic_series (series_id));
+diesel::joinable!(webhooks -> users (created_by));
+diesel::joinable!(xbrl_calculation_discrepancies -> financial_statements (statement_id));
 diesel::joinable!(xbrl_processing_logs -> financial_statements (statement_id));
+diesel::joinable!(xbrl_taxonomy_linkbases -> document_blobs (blob_hash));
+diesel::joinable!(xbrl_taxonomy_schemas -> document_blobs (blob_hash));
 
 diesel::allow_tables_to_appear_in_same_query!(
     annotation_assignments,
@@ -819,32 +1593,74 @@ diesel::allow_tables_to_appear_in_same_query!(
     annotation_replies,
     annotation_templates,
     audit_logs,
+    bls_catalog_syncs,
+    change_feed,
     chart_annotations,
     chart_collaborators,
     companies,
     company_comparisons,
+    component_status_transitions,
     countries,
     country_correlations,
     crawl_attempts,
     crawl_queue,
+    cusip_mappings,
+    data_lineage,
+    data_point_corrections,
     data_points,
+    data_source_credentials,
+    data_source_key_usage,
+    data_source_statistics,
+    data_source_usage,
     data_sources,
+    derived_series,
+    derived_series_inputs,
+    document_blobs,
     economic_series,
+    email_deliveries,
+    email_preferences,
     event_country_impacts,
+    export_jobs,
+    filing_sections,
     financial_annotations,
     financial_line_items,
     financial_ratios,
     financial_statements,
+    fx_rates,
     global_economic_events,
     global_economic_indicators,
     global_indicator_data,
+    industry_benchmarks,
+    insider_crawl_days,
+    insider_transactions,
+    institutional_crawl_days,
+    institutional_filings,
+    institutional_holdings,
     leading_indicators,
+    learning_achievements,
+    learning_progress,
+    notifications,
+    organization_chart_shares,
+    organization_members,
+    organizations,
+    release_calendars,
+    saved_charts,
+    search_synonyms,
     security_events,
+    series_alert_rules,
+    series_links,
     series_metadata,
+    series_quality_scores,
+    series_rollups,
+    series_statistics,
     trade_relationships,
     user_data_source_preferences,
     user_sessions,
     users,
+    webhook_deliveries,
+    webhooks,
+    xbrl_batch_checkpoints,
+    xbrl_calculation_discrepancies,
     xbrl_processing_logs,
     xbrl_taxonomy_concepts,
     xbrl_taxonomy_schemas,
@@ -0,0 +1,419 @@
+//! # Secret encryption
+//!
+//! AES-256-GCM encryption for credentials stored in the database, such as
+//! data source API keys. Keys come from a [`KeyProvider`]; the default reads
+//! them from the environment, and a KMS-backed provider can be plugged in
+//! without changing callers.
+//!
+//! Every ciphertext records the id of the key that produced it, so keys can be
+//! rotated: new secrets use the current key while older ones still decrypt
+//! with the previous keys the provider knows about.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::error::{AppError, AppResult};
+
+/// Environment variable holding the current key, base64 encoded (32 bytes)
+pub const ENCRYPTION_KEY_ENV: &str = "SECRETS_ENCRYPTION_KEY";
+/// Environment variable naming the current key; defaults to [`DEFAULT_KEY_ID`]
+pub const ENCRYPTION_KEY_ID_ENV: &str = "SECRETS_ENCRYPTION_KEY_ID";
+/// Environment variable with retired keys as `id:base64` pairs separated by commas
+pub const PREVIOUS_KEYS_ENV: &str = "SECRETS_PREVIOUS_ENCRYPTION_KEYS";
+/// Key id used when [`ENCRYPTION_KEY_ID_ENV`] is not set
+pub const DEFAULT_KEY_ID: &str = "env-1";
+
+/// Text written in place of a secret
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Length of an AES-256 key in bytes
+const KEY_LENGTH: usize = 32;
+/// Length of an AES-GCM nonce in bytes
+const NONCE_LENGTH: usize = 12;
+
+/// Query parameters that carry credentials in provider URLs
+const CREDENTIAL_PARAMETERS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "key",
+    "registrationkey",
+    "userid",
+    "token",
+    "access_token",
+    "subscription-key",
+];
+
+/// Source of the data keys used to encrypt secrets
+///
+/// Implementations backed by a KMS typically unwrap the data key once and
+/// cache it.
+pub trait KeyProvider: Send + Sync {
+    /// Id of the key new secrets are encrypted with
+    fn current_key_id(&self) -> &str;
+
+    /// The 256-bit data key with the given id
+    fn data_key(&self, key_id: &str) -> AppResult<[u8; KEY_LENGTH]>;
+}
+
+/// Keys read from the environment
+pub struct EnvKeyProvider {
+    current_key_id: String,
+    keys: HashMap<String, [u8; KEY_LENGTH]>,
+}
+
+impl EnvKeyProvider {
+    /// Read [`ENCRYPTION_KEY_ENV`], [`ENCRYPTION_KEY_ID_ENV`] and [`PREVIOUS_KEYS_ENV`]
+    pub fn from_env() -> AppResult<Self> {
+        let encoded = std::env::var(ENCRYPTION_KEY_ENV).map_err(|_| {
+            AppError::ConfigError(format!(
+                "{} is not set; stored credentials cannot be encrypted",
+                ENCRYPTION_KEY_ENV
+            ))
+        })?;
+        let current_key_id =
+            std::env::var(ENCRYPTION_KEY_ID_ENV).unwrap_or_else(|_| DEFAULT_KEY_ID.to_string());
+
+        let mut keys = HashMap::new();
+        if let Ok(previous) = std::env::var(PREVIOUS_KEYS_ENV) {
+            for entry in previous.split(',').filter(|entry| !entry.trim().is_empty()) {
+                let (key_id, encoded) = entry.trim().split_once(':').ok_or_else(|| {
+                    AppError::ConfigError(format!(
+                        "{} entries must be written as id:base64-key",
+                        PREVIOUS_KEYS_ENV
+                    ))
+                })?;
+                keys.insert(key_id.to_string(), decode_key(key_id, encoded)?);
+            }
+        }
+        keys.insert(
+            current_key_id.clone(),
+            decode_key(&current_key_id, &encoded)?,
+        );
+
+        Ok(Self {
+            current_key_id,
+            keys,
+        })
+    }
+
+    /// Provider with a single key, mainly for tests
+    pub fn with_key(key_id: &str, key: [u8; KEY_LENGTH]) -> Self {
+        Self {
+            current_key_id: key_id.to_string(),
+            keys: HashMap::from([(key_id.to_string(), key)]),
+        }
+    }
+
+    /// Add a retired key that can still decrypt older secrets
+    pub fn with_previous_key(mut self, key_id: &str, key: [u8; KEY_LENGTH]) -> Self {
+        self.keys.insert(key_id.to_string(), key);
+        self
+    }
+}
+
+impl KeyProvider for EnvKeyProvider {
+    fn current_key_id(&self) -> &str {
+        &self.current_key_id
+    }
+
+    fn data_key(&self, key_id: &str) -> AppResult<[u8; KEY_LENGTH]> {
+        self.keys.get(key_id).copied().ok_or_else(|| {
+            AppError::ConfigError(format!("Encryption key '{}' is not configured", key_id))
+        })
+    }
+}
+
+fn decode_key(key_id: &str, encoded: &str) -> AppResult<[u8; KEY_LENGTH]> {
+    BASE64
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| <[u8; KEY_LENGTH]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            AppError::ConfigError(format!(
+                "Encryption key '{}' must be {} bytes, base64 encoded",
+                key_id, KEY_LENGTH
+            ))
+        })
+}
+
+/// A secret held in memory
+///
+/// `Debug` and `Display` print [`REDACTED`] and the type is not
+/// serializable, so a secret cannot end up in logs or API responses by
+/// accident; [`SecretString::expose`] has to be called to use it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// The secret itself, for building the request that needs it
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString({})", REDACTED)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// An encrypted secret as stored in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSecret {
+    pub key_id: String,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+/// Encrypts and decrypts secrets with keys from a [`KeyProvider`]
+///
+/// Callers pass associated data, such as the id of the row a secret belongs
+/// to, which must match on decryption. This stops a ciphertext copied to
+/// another row from decrypting there.
+#[derive(Clone)]
+pub struct SecretCipher {
+    provider: Arc<dyn KeyProvider>,
+}
+
+impl SecretCipher {
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Cipher using keys from the environment
+    pub fn from_env() -> AppResult<Self> {
+        Ok(Self::new(Arc::new(EnvKeyProvider::from_env()?)))
+    }
+
+    /// Encrypt a secret with the current key and a fresh nonce
+    pub fn encrypt(
+        &self,
+        secret: &SecretString,
+        associated_data: &[u8],
+    ) -> AppResult<EncryptedSecret> {
+        let key_id = self.provider.current_key_id().to_string();
+        let cipher = self.cipher(&key_id)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: secret.expose().as_bytes(),
+                    aad: associated_data,
+                },
+            )
+            .map_err(|_| AppError::InternalError("Failed to encrypt secret".to_string()))?;
+
+        Ok(EncryptedSecret {
+            key_id,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Decrypt a secret with the key it was encrypted with
+    pub fn decrypt(
+        &self,
+        encrypted: &EncryptedSecret,
+        associated_data: &[u8],
+    ) -> AppResult<SecretString> {
+        if encrypted.nonce.len() != NONCE_LENGTH {
+            return Err(AppError::InternalError(
+                "Stored secret has an invalid nonce".to_string(),
+            ));
+        }
+        let cipher = self.cipher(&encrypted.key_id)?;
+
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&encrypted.nonce),
+                Payload {
+                    msg: &encrypted.ciphertext,
+                    aad: associated_data,
+                },
+            )
+            .map_err(|_| {
+                AppError::InternalError(format!(
+                    "Failed to decrypt secret with key '{}'",
+                    encrypted.key_id
+                ))
+            })?;
+
+        String::from_utf8(plaintext)
+            .map(SecretString)
+            .map_err(|_| AppError::InternalError("Decrypted secret is not UTF-8".to_string()))
+    }
+
+    fn cipher(&self, key_id: &str) -> AppResult<Aes256Gcm> {
+        let key = self.provider.data_key(key_id)?;
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+    }
+}
+
+/// Process-wide cipher configured from the environment
+///
+/// Returns an error when no encryption key is configured, in which case
+/// credentials can only come from environment variables.
+pub fn shared_secret_cipher() -> AppResult<&'static SecretCipher> {
+    static CIPHER: OnceLock<Result<SecretCipher, String>> = OnceLock::new();
+    CIPHER
+        .get_or_init(|| SecretCipher::from_env().map_err(|e| e.to_string()))
+        .as_ref()
+        .map_err(|e| AppError::ConfigError(e.clone()))
+}
+
+/// Replace every occurrence of a secret in a message
+pub fn redact_secret(message: &str, secret: &SecretString) -> String {
+    if secret.expose().is_empty() {
+        return message.to_string();
+    }
+    message.replace(secret.expose(), REDACTED)
+}
+
+/// Replace credential query parameters in URLs within a message
+///
+/// Request errors include the URL, and several providers take the API key
+/// as a query parameter (`api_key=...`, `registrationkey=...`).
+pub fn redact_credentials(message: &str) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(position) = rest.find(|c| c == '?' || c == '&') {
+        let (before, after) = rest.split_at(position + 1);
+        redacted.push_str(before);
+        rest = after;
+
+        let name_end = rest.find('=').unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        let is_credential = name_end < rest.len()
+            && CREDENTIAL_PARAMETERS
+                .iter()
+                .any(|parameter| parameter.eq_ignore_ascii_case(name));
+        if is_credential {
+            let value_end = rest[name_end + 1..]
+                .find(|c: char| c == '&' || c == '#' || c == '"' || c == ')' || c.is_whitespace())
+                .map(|end| name_end + 1 + end)
+                .unwrap_or(rest.len());
+            redacted.push_str(&rest[..=name_end]);
+            redacted.push_str(REDACTED);
+            rest = &rest[value_end..];
+        }
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher_with(key_id: &str, key: [u8; KEY_LENGTH]) -> SecretCipher {
+        SecretCipher::new(Arc::new(EnvKeyProvider::with_key(key_id, key)))
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        // REQUIREMENT: API keys are stored encrypted and decrypted only when used
+        // PURPOSE: Verify secrets round-trip, ciphertexts differ per encryption, and associated data is enforced
+        // This ensures a stored key cannot be moved to another data source row
+
+        let cipher = cipher_with("test-1", [7u8; KEY_LENGTH]);
+        let secret = SecretString::new("abcdef0123456789");
+
+        let first = cipher.encrypt(&secret, b"source-a").unwrap();
+        let second = cipher.encrypt(&secret, b"source-a").unwrap();
+        assert_eq!(first.key_id, "test-1");
+        assert_ne!(first.nonce, second.nonce);
+        assert_ne!(first.ciphertext, second.ciphertext);
+        assert!(!first
+            .ciphertext
+            .windows(secret.expose().len())
+            .any(|window| window == secret.expose().as_bytes()));
+
+        assert_eq!(cipher.decrypt(&first, b"source-a").unwrap(), secret);
+        assert!(cipher.decrypt(&first, b"source-b").is_err());
+
+        let mut tampered = first.clone();
+        tampered.ciphertext[0] ^= 1;
+        assert!(cipher.decrypt(&tampered, b"source-a").is_err());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        // REQUIREMENT: Encryption keys can be rotated without re-entering credentials
+        // PURPOSE: Verify secrets written with a retired key still decrypt and new ones use the current key
+        // This lets operators rotate keys and re-encrypt stored secrets gradually
+
+        let old_cipher = cipher_with("old", [1u8; KEY_LENGTH]);
+        let encrypted = old_cipher
+            .encrypt(&SecretString::new("old-secret"), b"row")
+            .unwrap();
+
+        let rotated = SecretCipher::new(Arc::new(
+            EnvKeyProvider::with_key("new", [2u8; KEY_LENGTH])
+                .with_previous_key("old", [1u8; KEY_LENGTH]),
+        ));
+        assert_eq!(
+            rotated.decrypt(&encrypted, b"row").unwrap().expose(),
+            "old-secret"
+        );
+        assert_eq!(
+            rotated
+                .encrypt(&SecretString::new("new-secret"), b"row")
+                .unwrap()
+                .key_id,
+            "new"
+        );
+
+        let unknown = cipher_with("other", [3u8; KEY_LENGTH]);
+        assert!(unknown.decrypt(&encrypted, b"row").is_err());
+    }
+
+    #[test]
+    fn test_secrets_are_redacted() {
+        // REQUIREMENT: Credentials never appear in logs or error messages
+        // PURPOSE: Verify secrets format as a placeholder and URL credential parameters are masked
+        // This covers request errors, which include the full URL
+
+        let secret = SecretString::new("s3cr3t");
+        assert_eq!(format!("{}", secret), REDACTED);
+        assert_eq!(format!("{:?}", secret), "SecretString([REDACTED])");
+        assert_eq!(
+            redact_secret("request with s3cr3t failed", &secret),
+            "request with [REDACTED] failed"
+        );
+
+        assert_eq!(
+            redact_credentials(
+                "error sending request for url (https://api.stlouisfed.org/fred/series?series_id=GDP&api_key=abc123&file_type=json)"
+            ),
+            "error sending request for url (https://api.stlouisfed.org/fred/series?series_id=GDP&api_key=[REDACTED]&file_type=json)"
+        );
+        assert_eq!(
+            redact_credentials("https://api.bls.gov/x?registrationKey=k1"),
+            "https://api.bls.gov/x?registrationKey=[REDACTED]"
+        );
+        assert_eq!(
+            redact_credentials("no credentials here: ?q=1&keyword=gdp"),
+            "no credentials here: ?q=1&keyword=gdp"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! # Graceful Shutdown
+//!
+//! A process shuts down in two phases. Draining starts on SIGTERM, Ctrl-C or
+//! an operator's drain request: from then on queue consumers stop claiming new
+//! work and the health check reports the process as unavailable, so load
+//! balancers route requests elsewhere. Work already started keeps running, and
+//! the process waits for it, up to a deadline, before flushing and exiting.
+//!
+//! Work registers itself with [`ShutdownCoordinator::try_track`] before it
+//! claims anything and holds the returned [`InFlightGuard`] until it is done.
+//! Requests that must still be served while draining use
+//! [`ShutdownCoordinator::track`] instead.
+
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+
+/// How long a shutdown waits for in-flight work by default
+pub const DEFAULT_SHUTDOWN_DEADLINE_SECONDS: u64 = 30;
+
+static COORDINATOR: Lazy<Arc<ShutdownCoordinator>> =
+    Lazy::new(|| Arc::new(ShutdownCoordinator::new()));
+
+/// The process-wide coordinator
+pub fn shutdown_coordinator() -> Arc<ShutdownCoordinator> {
+    COORDINATOR.clone()
+}
+
+/// Tracks in-flight work and whether the process is draining
+#[derive(Debug)]
+pub struct ShutdownCoordinator {
+    draining: watch::Sender<bool>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self {
+            draining: watch::Sender::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Stop accepting new work; returns false if draining had already started
+    pub fn drain(&self) -> bool {
+        !self.draining.send_replace(true)
+    }
+
+    pub fn is_draining(&self) -> bool {
+        *self.draining.borrow()
+    }
+
+    /// Completes once draining starts
+    pub async fn draining(&self) {
+        let mut receiver = self.draining.subscribe();
+        // The sender lives as long as `self`, so this cannot fail
+        let _ = receiver.wait_for(|draining| *draining).await;
+    }
+
+    /// Number of tracked tasks still running
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Register new work, unless the process is draining
+    pub fn try_track(self: &Arc<Self>) -> Option<InFlightGuard> {
+        // Counted before the check, so a drain that starts in between waits for it
+        let guard = self.track();
+        if self.is_draining() {
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Register work that runs even while draining, such as an HTTP request
+    pub fn track(self: &Arc<Self>) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            coordinator: self.clone(),
+        }
+    }
+
+    /// Wait up to `deadline` for tracked work to finish; returns whether it all did
+    pub async fn wait_for_in_flight(&self, deadline: Duration) -> bool {
+        tokio::time::timeout(deadline, async {
+            loop {
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// Marks a tracked task as running until dropped
+#[derive(Debug)]
+pub struct InFlightGuard {
+    coordinator: Arc<ShutdownCoordinator>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.coordinator.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.coordinator.idle.notify_waiters();
+        }
+    }
+}
+
+/// Completes on Ctrl-C or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Shutdown deadline from `SHUTDOWN_DEADLINE_SECONDS`
+pub fn shutdown_deadline_from_env() -> Duration {
+    Duration::from_secs(
+        std::env::var("SHUTDOWN_DEADLINE_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_SHUTDOWN_DEADLINE_SECONDS),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_draining_refuses_new_work_and_waits_for_in_flight() {
+        // REQUIREMENT: Graceful shutdown and drain mode across services
+        // PURPOSE: Verify draining stops new claims while work already started is awaited
+        // This ensures a deployment never kills a crawl or request midway
+
+        let coordinator = Arc::new(ShutdownCoordinator::new());
+        let running = coordinator.try_track().expect("not draining yet");
+        let request = coordinator.track();
+        assert_eq!(coordinator.in_flight(), 2);
+
+        assert!(coordinator.drain());
+        assert!(!coordinator.drain());
+        assert!(coordinator.is_draining());
+        assert!(coordinator.try_track().is_none());
+        assert_eq!(coordinator.in_flight(), 2);
+
+        let finisher = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(running);
+            drop(request);
+        });
+        assert!(coordinator.wait_for_in_flight(Duration::from_secs(5)).await);
+        assert_eq!(coordinator.in_flight(), 0);
+        finisher.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_in_flight_gives_up_at_deadline() {
+        // REQUIREMENT: Shutdown waits for in-flight work only up to a deadline
+        // PURPOSE: Verify a stuck task does not hold the process open past the deadline
+        // This ensures deployments finish even when a crawl hangs
+
+        let coordinator = Arc::new(ShutdownCoordinator::new());
+        let _stuck = coordinator.track();
+        coordinator.drain();
+        coordinator.draining().await;
+
+        assert!(
+            !coordinator
+                .wait_for_in_flight(Duration::from_millis(20))
+                .await
+        );
+        assert_eq!(coordinator.in_flight(), 1);
+    }
+}
@@ -0,0 +1,243 @@
+//! # Model Validation
+//!
+//! Models declare their rules with `#[derive(Validate)]` attributes. Rules
+//! that several models share are defined here once and referenced with
+//! `custom(function = ...)`, so a fiscal quarter or a CIK is checked the same
+//! way in filings, line items and companies. Optional fields are only checked
+//! when set:
+//!
+//! ```rust,ignore
+//! #[derive(Validate)]
+//! struct NewFiling {
+//!     #[validate(custom(function = "crate::validation::cik"))]
+//!     cik: String,
+//!     #[validate(custom(function = "crate::validation::fiscal_quarter"))]
+//!     fiscal_quarter: Option<i32>,
+//! }
+//! ```
+//!
+//! Failed validation surfaces as [`AppError::ValidationErrors`]; [`field_errors`]
+//! flattens it into one [`FieldError`] per failed rule, which the GraphQL API
+//! returns in the `fieldErrors` error extension.
+
+use serde::Serialize;
+use std::borrow::Cow;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::error::{AppError, AppResult};
+
+/// Earliest fiscal year accepted for financial data
+pub const MIN_FISCAL_YEAR: i32 = 1900;
+
+/// Latest fiscal year accepted for financial data
+pub const MAX_FISCAL_YEAR: i32 = 2100;
+
+/// Digits of a zero-padded SEC Central Index Key
+pub const CIK_LENGTH: usize = 10;
+
+/// One failed rule of one field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// Path of the field, e.g. `fiscal_quarter` or `line_items[2].taxonomy_concept`
+    pub field: String,
+    /// Rule that failed, e.g. `range`, `length` or `fiscal_quarter`
+    pub code: String,
+    pub message: String,
+}
+
+/// Every failed rule in `errors`, nested structs and lists included, sorted by field
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut collected = Vec::new();
+    collect_field_errors("", errors, &mut collected);
+    collected.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.code.cmp(&b.code)));
+    collected
+}
+
+fn collect_field_errors(prefix: &str, errors: &ValidationErrors, collected: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                collected.extend(field_errors.iter().map(|error| FieldError {
+                    field: path.clone(),
+                    code: error.code.to_string(),
+                    message: describe(error),
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(&path, nested, collected),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(&format!("{}[{}]", path, index), nested, collected);
+                }
+            }
+        }
+    }
+}
+
+/// The rule's own message, or one built from the parameters of the built-in rules
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name).map(|value| value.to_string());
+    match (&*error.code, param("min"), param("max")) {
+        ("range", Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+        ("range", Some(min), None) => format!("must be at least {}", min),
+        ("range", None, Some(max)) => format!("must be at most {}", max),
+        ("length", Some(min), Some(max)) if min == max => {
+            format!("must have exactly {} characters or items", min)
+        }
+        ("length", Some(min), Some(max)) => {
+            format!("must have between {} and {} characters or items", min, max)
+        }
+        ("length", Some(min), None) => format!("must have at least {} characters or items", min),
+        ("length", None, Some(max)) => format!("must have at most {} characters or items", max),
+        ("url", _, _) => "must be a URL".to_string(),
+        ("email", _, _) => "must be an email address".to_string(),
+        (code, _, _) => format!("is invalid ({})", code),
+    }
+}
+
+/// Check a model's rules, for crates that do not depend on `validator` themselves
+pub fn validate<T: Validate>(model: &T) -> AppResult<()> {
+    model.validate().map_err(AppError::from)
+}
+
+impl AppError {
+    /// Field-level errors of a failed validation, if this is one
+    pub fn field_errors(&self) -> Option<Vec<FieldError>> {
+        match self {
+            AppError::ValidationErrors(errors) => Some(field_errors(errors)),
+            _ => None,
+        }
+    }
+}
+
+fn invalid(code: &'static str, message: String) -> ValidationError {
+    let mut error = ValidationError::new(code);
+    error.message = Some(Cow::Owned(message));
+    error
+}
+
+/// Fiscal quarters are numbered 1 to 4
+pub fn fiscal_quarter(quarter: &i32) -> Result<(), ValidationError> {
+    if (1..=4).contains(quarter) {
+        Ok(())
+    } else {
+        Err(invalid(
+            "fiscal_quarter",
+            "must be a fiscal quarter from 1 to 4".to_string(),
+        ))
+    }
+}
+
+/// Fiscal years between [`MIN_FISCAL_YEAR`] and [`MAX_FISCAL_YEAR`]
+pub fn fiscal_year(year: &i32) -> Result<(), ValidationError> {
+    if (MIN_FISCAL_YEAR..=MAX_FISCAL_YEAR).contains(year) {
+        Ok(())
+    } else {
+        Err(invalid(
+            "fiscal_year",
+            format!(
+                "must be a fiscal year from {} to {}",
+                MIN_FISCAL_YEAR, MAX_FISCAL_YEAR
+            ),
+        ))
+    }
+}
+
+/// CIKs are stored zero-padded to [`CIK_LENGTH`] digits, e.g. `0000320193`
+pub fn cik(cik: &str) -> Result<(), ValidationError> {
+    if cik.len() == CIK_LENGTH && cik.bytes().all(|b| b.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(invalid(
+            "cik",
+            format!("must be a CIK of {} digits, zero-padded", CIK_LENGTH),
+        ))
+    }
+}
+
+/// Counts and offsets cannot be negative
+pub fn non_negative(value: &i64) -> Result<(), ValidationError> {
+    if *value >= 0 {
+        Ok(())
+    } else {
+        Err(invalid("non_negative", "must not be negative".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Validate)]
+    struct Filing {
+        #[validate(custom(function = "crate::validation::cik"))]
+        cik: String,
+        #[validate(custom(function = "crate::validation::fiscal_quarter"))]
+        fiscal_quarter: Option<i32>,
+        #[validate(length(min = 1, max = 10))]
+        form_type: String,
+        #[validate(nested)]
+        line_items: Vec<LineItem>,
+    }
+
+    #[derive(Validate)]
+    struct LineItem {
+        #[validate(range(min = 0, max = 10))]
+        level: i32,
+    }
+
+    #[test]
+    fn test_field_errors_list_every_failed_rule_by_path() {
+        // REQUIREMENT: Validation failures are reported per field so clients can mark the offending inputs
+        // PURPOSE: Verify shared rules, built-in rules and nested list items produce one field error each with a readable message
+        // This ensures GraphQL clients get the same error shape whichever model rejected the input
+
+        let filing = Filing {
+            cik: "320193".to_string(),
+            fiscal_quarter: Some(5),
+            form_type: "10-K".to_string(),
+            line_items: vec![LineItem { level: 2 }, LineItem { level: 11 }],
+        };
+        let error = AppError::from(filing.validate().unwrap_err());
+
+        let errors = error.field_errors().unwrap();
+        assert_eq!(
+            errors[..2],
+            [
+                FieldError {
+                    field: "cik".to_string(),
+                    code: "cik".to_string(),
+                    message: "must be a CIK of 10 digits, zero-padded".to_string(),
+                },
+                FieldError {
+                    field: "fiscal_quarter".to_string(),
+                    code: "fiscal_quarter".to_string(),
+                    message: "must be a fiscal quarter from 1 to 4".to_string(),
+                },
+            ]
+        );
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[2].field, "line_items[1].level");
+        assert_eq!(errors[2].code, "range");
+        assert!(errors[2].message.starts_with("must be between 0"));
+
+        let valid = Filing {
+            cik: "0000320193".to_string(),
+            fiscal_quarter: None,
+            form_type: "10-Q".to_string(),
+            line_items: Vec::new(),
+        };
+        assert!(valid.validate().is_ok());
+        assert!(AppError::NotFound("filing".to_string())
+            .field_errors()
+            .is_none());
+    }
+}
@@ -6,8 +6,14 @@
 use clap::{Parser, Subcommand};
 use econ_graph_core::database::{create_pool, DatabasePool};
 use econ_graph_core::models::data_source::DataSource;
+use econ_graph_core::secrets::{redact_credentials, redact_secret, SecretString};
 use econ_graph_services::services::crawler::catalog_downloader::CatalogDownloader;
+use econ_graph_services::services::crawler::crawl_plan::CrawlPlanner;
 use econ_graph_services::services::crawler::series_downloader::SeriesDownloader;
+use econ_graph_services::services::data_source_admin_service::resolve_api_key;
+use econ_graph_services::services::series_discovery::bls_catalog::{
+    catalog_surveys_from_env, sync_bls_catalog,
+};
 use econ_graph_services::services::series_discovery::SeriesDiscoveryService;
 use reqwest::Client;
 use std::collections::HashMap;
@@ -38,7 +44,7 @@ enum Commands {
         #[arg(long, num_args = 0..)]
         api_key: Vec<String>,
 
-        /// Dry run - don't actually download data
+        /// Dry run - print the crawl plan without discovering or downloading anything
         #[arg(long)]
         dry_run: bool,
 
@@ -64,7 +70,7 @@ enum Commands {
         #[arg(long)]
         api_key: Option<String>,
 
-        /// Dry run - don't actually download data
+        /// Dry run - print the crawl plan without discovering or downloading anything
         #[arg(long)]
         dry_run: bool,
 
@@ -87,6 +93,17 @@ enum Commands {
         #[arg(long, default_value = "1000")]
         bulk_insert_limit: usize,
     },
+
+    /// Sync the full BLS series catalog of surveys such as CPS (LN) and CES (CE)
+    SyncBlsCatalog {
+        /// Database URL
+        #[arg(long)]
+        database_url: String,
+
+        /// Survey abbreviation to sync, repeatable (default: BLS_CATALOG_SURVEYS or LN and CE)
+        #[arg(long)]
+        survey: Vec<String>,
+    },
 }
 
 #[tokio::main]
@@ -136,6 +153,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             output_file,
             bulk_insert_limit,
         } => export_catalog_to_sql(database_url, output_file, bulk_insert_limit).await,
+        Commands::SyncBlsCatalog {
+            database_url,
+            survey,
+        } => sync_bls_catalog_command(database_url, survey).await,
     }
 }
 
@@ -159,7 +180,6 @@ async fn crawl_all_sources(
     let client = Client::new();
 
     // Create services
-    let discovery_service = SeriesDiscoveryService::new(None, None, None, None);
     let catalog_downloader = CatalogDownloader::new(client.clone());
     let series_downloader = SeriesDownloader::new(client);
 
@@ -178,8 +198,17 @@ async fn crawl_all_sources(
 
         info!("Crawling data source: {}", data_source.name);
 
-        // Get API key for this source
-        let api_key = api_key_map.get(&data_source.name.to_uppercase());
+        // Get API key for this source: command line first, then stored or environment
+        let api_key = match api_key_map.get(&data_source.name.to_uppercase()) {
+            Some(key) => Some(SecretString::new(key.clone())),
+            None => stored_api_key(&pool, &data_source).await,
+        };
+        let discovery_service = SeriesDiscoveryService::new(
+            api_key.as_ref().map(|k| k.expose().to_string()),
+            None,
+            None,
+            None,
+        );
 
         match crawl_data_source(
             &data_source,
@@ -187,7 +216,7 @@ async fn crawl_all_sources(
             &discovery_service,
             &catalog_downloader,
             &series_downloader,
-            api_key.map(|k| k.as_str()),
+            api_key.as_ref().map(|k| k.expose()),
             series_count,
             dry_run,
             skip_data_download,
@@ -203,7 +232,11 @@ async fn crawl_all_sources(
                 );
             }
             Err(e) => {
-                error!("Failed to crawl {}: {}", data_source.name, e);
+                error!(
+                    "Failed to crawl {}: {}",
+                    data_source.name,
+                    redact_error(&e.to_string(), api_key.as_ref())
+                );
                 // Continue with other sources
             }
         }
@@ -243,11 +276,22 @@ async fn crawl_single_source(
         return Err(format!("Data source '{}' is disabled", source_name).into());
     }
 
+    // Use the key given on the command line, else the stored or environment key
+    let api_key = match api_key {
+        Some(key) => Some(SecretString::new(key)),
+        None => stored_api_key(&pool, &data_source).await,
+    };
+
     // Create HTTP client
     let client = Client::new();
 
     // Create services
-    let discovery_service = SeriesDiscoveryService::new(api_key.clone(), None, None, None);
+    let discovery_service = SeriesDiscoveryService::new(
+        api_key.as_ref().map(|k| k.expose().to_string()),
+        None,
+        None,
+        None,
+    );
     let catalog_downloader = CatalogDownloader::new(client.clone());
     let series_downloader = SeriesDownloader::new(client);
 
@@ -258,12 +302,13 @@ async fn crawl_single_source(
         &discovery_service,
         &catalog_downloader,
         &series_downloader,
-        api_key.as_deref(),
+        api_key.as_ref().map(|k| k.expose()),
         series_count,
         dry_run,
         skip_data_download,
     )
-    .await?;
+    .await
+    .map_err(|e| redact_error(&e.to_string(), api_key.as_ref()))?;
 
     info!(
         "Completed {}: discovered {}, downloaded {}",
@@ -285,6 +330,15 @@ async fn crawl_data_source(
     dry_run: bool,
     skip_data_download: bool,
 ) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    // Discovery writes the catalog, so a dry run plans from what is already stored
+    if dry_run {
+        let plan = CrawlPlanner::new(pool)
+            .plan_source(&data_source.name, series_count)
+            .await?;
+        print!("{}", plan.render());
+        return Ok((plan.crawls.len(), 0));
+    }
+
     info!("Discovering series for {}", data_source.name);
 
     // Discover series using the appropriate method
@@ -297,6 +351,7 @@ async fn crawl_data_source(
         "imf" => discovery_service.discover_imf_series(pool).await?,
         "fhfa" => discovery_service.discover_fhfa_series(pool).await?,
         "ecb" => discovery_service.discover_ecb_series(pool).await?,
+        "eurostat" => discovery_service.discover_eurostat_series(pool).await?,
         "oecd" => discovery_service.discover_oecd_series(pool).await?,
         "bank of england" | "boe" => discovery_service.discover_boe_series(pool).await?,
         "wto" => discovery_service.discover_wto_series(pool).await?,
@@ -320,11 +375,6 @@ async fn crawl_data_source(
         data_source.name
     );
 
-    if dry_run {
-        info!("Dry run mode - skipping data download");
-        return Ok((discovered_series.len(), 0));
-    }
-
     if skip_data_download {
         info!("Skipping data download as requested");
         return Ok((discovered_series.len(), 0));
@@ -365,6 +415,29 @@ async fn crawl_data_source(
     Ok((discovered_series.len(), downloaded_count))
 }
 
+/// API key stored for a data source or named by its `api_key_name`
+///
+/// Stored keys are decrypted here, just before the crawl that uses them.
+/// Failures are logged and the source is crawled without a key.
+async fn stored_api_key(pool: &DatabasePool, data_source: &DataSource) -> Option<SecretString> {
+    match resolve_api_key(pool, data_source).await {
+        Ok(api_key) => api_key,
+        Err(e) => {
+            warn!("Could not load API key for {}: {}", data_source.name, e);
+            None
+        }
+    }
+}
+
+/// Error message with the API key and URL credential parameters masked
+fn redact_error(message: &str, api_key: Option<&SecretString>) -> String {
+    let message = redact_credentials(message);
+    match api_key {
+        Some(api_key) => redact_secret(&message, api_key),
+        None => message,
+    }
+}
+
 /// Parse API keys from command line arguments
 fn parse_api_keys(api_keys: Vec<String>) -> HashMap<String, String> {
     let mut map = HashMap::new();
@@ -379,6 +452,37 @@ fn parse_api_keys(api_keys: Vec<String>) -> HashMap<String, String> {
 }
 
 /// Export catalog data to SQL migration file
+/// Sync BLS survey catalogs without a full discovery run
+async fn sync_bls_catalog_command(
+    database_url: String,
+    surveys: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let surveys = if surveys.is_empty() {
+        catalog_surveys_from_env()
+    } else {
+        surveys.iter().map(|survey| survey.to_uppercase()).collect()
+    };
+    info!("Syncing BLS catalogs for surveys: {}", surveys.join(", "));
+
+    let pool = create_pool(&database_url).await?;
+    let bls_source = DataSource::get_or_create(&pool, DataSource::bls()).await?;
+    // Survey names come from the BLS API; the enrichment pass adds categories later
+    let reports = sync_bls_catalog(
+        &Client::new(),
+        &pool,
+        bls_source.id,
+        &surveys,
+        &HashMap::new(),
+    )
+    .await;
+
+    let failed = reports.iter().filter(|report| report.is_err()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} BLS catalog syncs failed", failed, reports.len()).into());
+    }
+    Ok(())
+}
+
 async fn export_catalog_to_sql(
     database_url: String,
     output_file: String,
@@ -42,3 +42,6 @@
 //! ```
 
 // This crate primarily contains binaries, but we can add shared utilities here if needed
+
+#[cfg(any(test, feature = "mock-sources"))]
+pub mod mock_sources;
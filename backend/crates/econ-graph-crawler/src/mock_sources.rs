@@ -0,0 +1,335 @@
+//! # Mock Data Sources
+//!
+//! A local HTTP server standing in for FRED, BLS and SEC EDGAR, so crawler
+//! retry, rate limit and parsing logic can be tested without the network.
+//! Enabled by the `mock-sources` feature.
+//!
+//! [`MockSources`] serves canned fixtures from `fixtures/` and can be told to
+//! fail an endpoint a given number of times, with HTTP 429, HTTP 500, a
+//! truncated body or a slow response, before the fixture is served again.
+//! Crawlers are pointed at it through their base URL settings, for example
+//! `CrawlerService::with_fred_base_url(sources.fred_base_url())`.
+//!
+//! ```rust,no_run
+//! use econ_graph_crawler::mock_sources::{Endpoint, Failure, MockSources};
+//!
+//! # async fn example() {
+//! let sources = MockSources::start().await;
+//! sources.serve_fixtures().await;
+//! sources
+//!     .inject(Endpoint::FredObservations, Failure::TooManyRequests { retry_after: Some(5) }, 1)
+//!     .await;
+//! // ... crawl against sources.fred_base_url() ...
+//! assert_eq!(sources.request_count(Endpoint::FredObservations).await, 2);
+//! # }
+//! ```
+
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockBuilder, MockServer, ResponseTemplate};
+
+/// FRED series served by the fixtures
+pub const FRED_FIXTURE_SERIES: &str = "GDPC1";
+
+/// Observations in the FRED fixture that have a value; one more is missing (".")
+pub const FRED_FIXTURE_OBSERVATIONS: usize = 3;
+
+/// BLS series served by the fixtures
+pub const BLS_FIXTURE_SERIES: &str = "CUUR0000SA0";
+
+/// SEC company served by the fixtures, zero padded
+pub const SEC_FIXTURE_CIK: &str = "0000320193";
+
+const FRED_SERIES_FIXTURE: &str = include_str!("../fixtures/fred_series_gdpc1.json");
+const FRED_OBSERVATIONS_FIXTURE: &str = include_str!("../fixtures/fred_observations_gdpc1.json");
+const BLS_TIMESERIES_FIXTURE: &str = include_str!("../fixtures/bls_timeseries_cuur0000sa0.json");
+const SEC_SUBMISSIONS_FIXTURE: &str = include_str!("../fixtures/sec_submissions_0000320193.json");
+
+/// Injected failures take precedence over fixtures (lower is higher in wiremock)
+const FAILURE_PRIORITY: u8 = 1;
+const FIXTURE_PRIORITY: u8 = 10;
+
+/// An endpoint the mock sources serve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    /// FRED `/series`
+    FredSeries,
+    /// FRED `/series/observations`
+    FredObservations,
+    /// BLS `/timeseries/data/`
+    BlsTimeseries,
+    /// SEC `/submissions/CIK##########.json`
+    SecSubmissions,
+}
+
+impl Endpoint {
+    /// Request path, relative to the server root
+    pub fn path(&self) -> String {
+        match self {
+            Endpoint::FredSeries => "/fred/series".to_string(),
+            Endpoint::FredObservations => "/fred/series/observations".to_string(),
+            Endpoint::BlsTimeseries => "/publicAPI/v2/timeseries/data/".to_string(),
+            Endpoint::SecSubmissions => format!("/submissions/CIK{}.json", SEC_FIXTURE_CIK),
+        }
+    }
+
+    fn method(&self) -> &'static str {
+        match self {
+            Endpoint::BlsTimeseries => "POST",
+            _ => "GET",
+        }
+    }
+
+    fn fixture(&self) -> &'static str {
+        match self {
+            Endpoint::FredSeries => FRED_SERIES_FIXTURE,
+            Endpoint::FredObservations => FRED_OBSERVATIONS_FIXTURE,
+            Endpoint::BlsTimeseries => BLS_TIMESERIES_FIXTURE,
+            Endpoint::SecSubmissions => SEC_SUBMISSIONS_FIXTURE,
+        }
+    }
+
+    fn matching(&self) -> MockBuilder {
+        let mock = Mock::given(method(self.method())).and(path(self.path()));
+        match self {
+            Endpoint::FredSeries | Endpoint::FredObservations => {
+                mock.and(query_param("series_id", FRED_FIXTURE_SERIES))
+            }
+            _ => mock,
+        }
+    }
+
+    const ALL: [Endpoint; 4] = [
+        Endpoint::FredSeries,
+        Endpoint::FredObservations,
+        Endpoint::BlsTimeseries,
+        Endpoint::SecSubmissions,
+    ];
+}
+
+/// How an endpoint misbehaves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// HTTP 429, with a Retry-After header in seconds when given
+    TooManyRequests { retry_after: Option<u64> },
+    /// HTTP 500
+    ServerError,
+    /// HTTP 200 with the first half of the fixture, as if the connection dropped
+    TruncatedBody,
+    /// The fixture, sent after a delay
+    Slow(Duration),
+}
+
+impl Failure {
+    fn response(&self, endpoint: Endpoint) -> ResponseTemplate {
+        match *self {
+            Failure::TooManyRequests { retry_after } => {
+                let response = ResponseTemplate::new(429);
+                match retry_after {
+                    Some(seconds) => response.insert_header("retry-after", seconds.to_string()),
+                    None => response,
+                }
+            }
+            Failure::ServerError => {
+                ResponseTemplate::new(500).set_body_string("Internal Server Error")
+            }
+            Failure::TruncatedBody => {
+                let fixture = endpoint.fixture();
+                json_response(&fixture[..fixture.len() / 2])
+            }
+            Failure::Slow(delay) => json_response(endpoint.fixture()).set_delay(delay),
+        }
+    }
+}
+
+fn json_response(body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(body.as_bytes().to_vec(), "application/json")
+}
+
+/// Local server imitating FRED, BLS and SEC EDGAR
+pub struct MockSources {
+    server: MockServer,
+}
+
+impl MockSources {
+    /// Start a server on a free local port; it stops when dropped
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Root of the server, e.g. `http://127.0.0.1:41234`
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Replacement for the FRED API root
+    pub fn fred_base_url(&self) -> String {
+        format!("{}/fred", self.uri())
+    }
+
+    /// Replacement for the BLS public API root
+    pub fn bls_base_url(&self) -> String {
+        format!("{}/publicAPI/v2", self.uri())
+    }
+
+    /// Serve every endpoint's fixture, after any injected failures
+    pub async fn serve_fixtures(&self) {
+        self.serve_fixtures_with_latency(Duration::ZERO).await;
+    }
+
+    /// Serve every endpoint's fixture after `latency`
+    pub async fn serve_fixtures_with_latency(&self, latency: Duration) {
+        for endpoint in Endpoint::ALL {
+            endpoint
+                .matching()
+                .respond_with(json_response(endpoint.fixture()).set_delay(latency))
+                .with_priority(FIXTURE_PRIORITY)
+                .mount(&self.server)
+                .await;
+        }
+    }
+
+    /// Make the next `times` requests to `endpoint` fail
+    pub async fn inject(&self, endpoint: Endpoint, failure: Failure, times: u64) {
+        self.mount_failure(endpoint.matching(), endpoint, failure, times)
+            .await;
+    }
+
+    /// Make the next `times` requests to `endpoint` made with `api_key` fail
+    ///
+    /// Requests with other keys are unaffected, which is how per-key throttling
+    /// looks to a crawler.
+    pub async fn inject_for_api_key(
+        &self,
+        endpoint: Endpoint,
+        api_key: &str,
+        failure: Failure,
+        times: u64,
+    ) {
+        let matching = endpoint.matching().and(query_param("api_key", api_key));
+        self.mount_failure(matching, endpoint, failure, times).await;
+    }
+
+    async fn mount_failure(
+        &self,
+        matching: MockBuilder,
+        endpoint: Endpoint,
+        failure: Failure,
+        times: u64,
+    ) {
+        matching
+            .respond_with(failure.response(endpoint))
+            .with_priority(FAILURE_PRIORITY)
+            .up_to_n_times(times)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Requests received by `endpoint` so far, failed or not
+    pub async fn request_count(&self, endpoint: Endpoint) -> usize {
+        self.api_keys_used(endpoint).await.len()
+    }
+
+    /// `api_key` query parameter of each request to `endpoint`, in order
+    ///
+    /// Requests without the parameter are listed as an empty string.
+    pub async fn api_keys_used(&self, endpoint: Endpoint) -> Vec<String> {
+        let endpoint_path = endpoint.path();
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == endpoint_path)
+            .map(|request| {
+                request
+                    .url
+                    .query_pairs()
+                    .find(|(name, _)| name == "api_key")
+                    .map(|(_, key)| key.into_owned())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_injected_failures_precede_fixtures() {
+        // REQUIREMENT: Crawler retry and rate limit logic is tested without the network
+        // PURPOSE: Verify injected 429, 500, truncated and slow responses are served the given number of times before the fixture
+        // This ensures tests built on the harness see exactly the failures they asked for
+
+        let sources = MockSources::start().await;
+        sources.serve_fixtures().await;
+        sources
+            .inject(
+                Endpoint::FredSeries,
+                Failure::TooManyRequests {
+                    retry_after: Some(7),
+                },
+                1,
+            )
+            .await;
+        sources
+            .inject(Endpoint::FredSeries, Failure::ServerError, 1)
+            .await;
+        sources
+            .inject(Endpoint::SecSubmissions, Failure::TruncatedBody, 1)
+            .await;
+        sources
+            .inject(
+                Endpoint::FredObservations,
+                Failure::Slow(Duration::from_millis(200)),
+                1,
+            )
+            .await;
+
+        let client = reqwest::Client::new();
+        let series_url = format!(
+            "{}/series?series_id={}&api_key=key-a",
+            sources.fred_base_url(),
+            FRED_FIXTURE_SERIES
+        );
+
+        let throttled = client.get(&series_url).send().await.unwrap();
+        assert_eq!(throttled.status(), 429);
+        assert_eq!(throttled.headers()["retry-after"], "7");
+        let failed = client.get(&series_url).send().await.unwrap();
+        assert_eq!(failed.status(), 500);
+        let served = client.get(&series_url).send().await.unwrap();
+        assert_eq!(served.status(), 200);
+        assert!(served
+            .text()
+            .await
+            .unwrap()
+            .contains("Real Gross Domestic Product"));
+        assert_eq!(
+            sources.api_keys_used(Endpoint::FredSeries).await,
+            vec!["key-a"; 3]
+        );
+
+        let submissions_url = format!("{}{}", sources.uri(), Endpoint::SecSubmissions.path());
+        let truncated = client.get(&submissions_url).send().await.unwrap();
+        assert!(
+            serde_json::from_str::<serde_json::Value>(&truncated.text().await.unwrap()).is_err()
+        );
+        let complete = client.get(&submissions_url).send().await.unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&complete.text().await.unwrap()).is_ok());
+
+        let observations_url = format!(
+            "{}/series/observations?series_id={}",
+            sources.fred_base_url(),
+            FRED_FIXTURE_SERIES
+        );
+        let started = Instant::now();
+        client.get(&observations_url).send().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
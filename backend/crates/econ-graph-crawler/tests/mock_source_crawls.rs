@@ -0,0 +1,138 @@
+//! Crawls against the mock data sources
+//!
+//! These tests run the FRED crawler end to end against
+//! [`MockSources`](econ_graph_crawler::mock_sources::MockSources) instead of the
+//! real API, with a test database. Run them with
+//! `cargo test -p econ-graph-crawler --features mock-sources`.
+
+use chrono::{NaiveDate, Utc};
+use econ_graph_core::models::{DataPoint, DataSource, DataSourceKeyUsage, EconomicSeries};
+use econ_graph_core::secrets::SecretString;
+use econ_graph_core::test_utils::TestContainer;
+use econ_graph_crawler::mock_sources::{
+    Endpoint, Failure, MockSources, FRED_FIXTURE_OBSERVATIONS, FRED_FIXTURE_SERIES,
+};
+use econ_graph_services::services::crawler::api_key_ring::api_key_hash;
+use econ_graph_services::services::crawler::legacy_crawler_service::CrawlerService;
+use serial_test::serial;
+
+const FRED_SOURCE_NAME: &str = "Federal Reserve Economic Data (FRED)";
+
+async fn fred_source(pool: &econ_graph_core::database::DatabasePool) -> DataSource {
+    DataSource::find_by_name(pool, FRED_SOURCE_NAME)
+        .await
+        .unwrap()
+        .expect("the crawl creates the FRED source")
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fred_crawl_rotates_throttled_key() {
+    // REQUIREMENT: Crawler rate limit handling is tested deterministically
+    // PURPOSE: Verify a FRED crawl retries a throttled request with the next API key, stores the observations and records per-key usage
+    // This ensures key rotation keeps crawls going when FRED throttles one key
+
+    let container = TestContainer::new().await;
+    container.clean_database().await.unwrap();
+    let pool = container.pool();
+
+    let sources = MockSources::start().await;
+    sources.serve_fixtures().await;
+    sources
+        .inject_for_api_key(
+            Endpoint::FredObservations,
+            "key-a",
+            Failure::TooManyRequests {
+                retry_after: Some(30),
+            },
+            1,
+        )
+        .await;
+
+    let crawler = CrawlerService::new(Some("key-a,key-b".to_string()), None)
+        .with_fred_base_url(sources.fred_base_url());
+    crawler
+        .crawl_fred_series(pool, FRED_FIXTURE_SERIES)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        sources.api_keys_used(Endpoint::FredSeries).await,
+        vec!["key-a"]
+    );
+    assert_eq!(
+        sources.api_keys_used(Endpoint::FredObservations).await,
+        vec!["key-a", "key-b"]
+    );
+
+    let source = fred_source(pool).await;
+    let series = EconomicSeries::find_by_external_id(pool, FRED_FIXTURE_SERIES, source.id)
+        .await
+        .unwrap();
+    let data_points = DataPoint::find_by_series_and_date_range(
+        pool,
+        series.id,
+        NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(data_points.len(), FRED_FIXTURE_OBSERVATIONS);
+
+    let usage = DataSourceKeyUsage::for_date(pool, Utc::now().date_naive())
+        .await
+        .unwrap();
+    let key_a = api_key_hash(&SecretString::new("key-a"));
+    let key_b = api_key_hash(&SecretString::new("key-b"));
+    let throttled: Vec<(String, i32, i32)> = usage
+        .into_iter()
+        .map(|usage| (usage.key_hash, usage.request_count, usage.throttled_count))
+        .collect();
+    assert!(throttled.contains(&(key_a, 2, 1)));
+    assert!(throttled.contains(&(key_b, 1, 0)));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_fred_crawl_fails_cleanly_on_bad_responses() {
+    // REQUIREMENT: Crawler error handling is tested deterministically
+    // PURPOSE: Verify server errors and truncated bodies fail the crawl with an external API error, and the next crawl succeeds
+    // This ensures a bad response never stores partial data or poisons later crawls
+
+    let container = TestContainer::new().await;
+    container.clean_database().await.unwrap();
+    let pool = container.pool();
+
+    let sources = MockSources::start().await;
+    sources.serve_fixtures().await;
+    sources
+        .inject(Endpoint::FredSeries, Failure::ServerError, 1)
+        .await;
+    sources
+        .inject(Endpoint::FredObservations, Failure::TruncatedBody, 1)
+        .await;
+
+    let crawler = CrawlerService::new(Some("key-a".to_string()), None)
+        .with_fred_base_url(sources.fred_base_url());
+
+    let server_error = crawler
+        .crawl_fred_series(pool, FRED_FIXTURE_SERIES)
+        .await
+        .unwrap_err();
+    assert!(server_error.to_string().contains("500"));
+
+    let truncated = crawler
+        .crawl_fred_series(pool, FRED_FIXTURE_SERIES)
+        .await
+        .unwrap_err();
+    assert!(truncated
+        .to_string()
+        .contains("Failed to parse FRED observations"));
+
+    crawler
+        .crawl_fred_series(pool, FRED_FIXTURE_SERIES)
+        .await
+        .unwrap();
+    assert_eq!(sources.request_count(Endpoint::FredSeries).await, 3);
+    assert_eq!(sources.request_count(Endpoint::FredObservations).await, 2);
+}
@@ -0,0 +1,386 @@
+//! # Cache Hints
+//!
+//! Fields whose results change rarely, such as data source and series
+//! metadata, carry a cache hint: how long their value may be reused
+//! (`maxAge`, in seconds) and whether it may be shared between users
+//! (`PUBLIC`) or only reused for the same user (`PRIVATE`). Hints are listed in
+//! [`FIELD_CACHE_HINTS`].
+//!
+//! [`CacheControlHints`] reports the hints of every resolved field in the
+//! `cacheControl` response extension, together with the policy of the whole
+//! response:
+//!
+//! ```json
+//! "extensions": {
+//!   "cacheControl": {
+//!     "version": 1,
+//!     "maxAge": 300,
+//!     "scope": "PUBLIC",
+//!     "hints": [{ "path": ["dataSources"], "maxAge": 300, "scope": "PUBLIC" }]
+//!   }
+//! }
+//! ```
+//!
+//! The policy takes the smallest `maxAge` of the hinted fields and is
+//! `PRIVATE` if any of them is, or if the request read a data source that is
+//! not public (see [`data_access`](super::data_access)). A top-level field
+//! without a hint makes the response uncacheable (`maxAge` 0); nested fields
+//! without a hint follow their parent. The HTTP layer reuses responses for
+//! their policy's max age.
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{QueryPathNode, QueryPathSegment, Response, ServerResult, Value};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+use crate::graphql::context::GraphQLContext;
+
+/// Name of the response extension the hints are reported in
+pub const CACHE_CONTROL_EXTENSION: &str = "cacheControl";
+
+/// Who a cached value may be served to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheScope {
+    /// Any user, including anonymous ones
+    Public,
+    /// Only the user the value was computed for
+    Private,
+}
+
+impl CacheScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheScope::Public => "PUBLIC",
+            CacheScope::Private => "PRIVATE",
+        }
+    }
+}
+
+/// How long, and for whom, a field's value may be reused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheHint {
+    /// Seconds; 0 means the value must not be reused
+    pub max_age: u32,
+    pub scope: CacheScope,
+}
+
+impl CacheHint {
+    pub const fn public(max_age: u32) -> Self {
+        Self {
+            max_age,
+            scope: CacheScope::Public,
+        }
+    }
+
+    pub const fn private(max_age: u32) -> Self {
+        Self {
+            max_age,
+            scope: CacheScope::Private,
+        }
+    }
+
+    /// Hint of a field that must always be recomputed
+    pub const fn uncached() -> Self {
+        Self::public(0)
+    }
+}
+
+/// Cache hints by GraphQL type and field name
+///
+/// Observations are never hinted; nested fields that read them are listed as
+/// uncached so a metadata query asking for them is not reused.
+pub const FIELD_CACHE_HINTS: &[(&str, &str, CacheHint)] = &[
+    ("Query", "dataSources", CacheHint::public(300)),
+    ("Query", "dataSource", CacheHint::public(300)),
+    ("Query", "series", CacheHint::public(60)),
+    ("Query", "seriesList", CacheHint::public(60)),
+    ("DataSourceType", "lastCrawlAt", CacheHint::public(60)),
+    ("DataSourceType", "crawlStatus", CacheHint::public(60)),
+    // Only administrators see these
+    ("DataSourceType", "apiKeyName", CacheHint::private(300)),
+    ("DataSourceType", "hasStoredApiKey", CacheHint::private(300)),
+    // Depends on who is asking
+    ("DataSourceType", "accessible", CacheHint::private(300)),
+    ("Query", "countryIndicatorSnapshot", CacheHint::private(300)),
+    (
+        "EconomicSeriesType",
+        "recentDataPoints",
+        CacheHint::uncached(),
+    ),
+    (
+        "EconomicSeriesType",
+        "dataPointCount",
+        CacheHint::uncached(),
+    ),
+    (
+        "EconomicSeriesType",
+        "dataPointsConnection",
+        CacheHint::uncached(),
+    ),
+    ("EconomicSeriesType", "dataPoints", CacheHint::uncached()),
+];
+
+/// Cache hint of a field, if it has one
+pub fn field_cache_hint(parent_type: &str, field: &str) -> Option<CacheHint> {
+    FIELD_CACHE_HINTS
+        .iter()
+        .find(|(type_name, name, _)| *type_name == parent_type && *name == field)
+        .map(|(_, _, hint)| *hint)
+}
+
+/// Cache policy of a whole response, from the hints of its fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub max_age: u32,
+    pub scope: CacheScope,
+    /// Hinted fields by response path
+    pub hints: Vec<(Vec<Value>, CacheHint)>,
+}
+
+impl CachePolicy {
+    /// Policy before any field is resolved; [`CachePolicy::finish`] caps it
+    fn new() -> Self {
+        Self {
+            max_age: u32::MAX,
+            scope: CacheScope::Public,
+            hints: Vec::new(),
+        }
+    }
+
+    /// Policy once every field is resolved
+    ///
+    /// A response without resolved fields is not cached.
+    fn finish(mut self) -> Self {
+        if self.max_age == u32::MAX {
+            self.max_age = 0;
+        }
+        self
+    }
+
+    /// Take a resolved field into account
+    pub fn observe(&mut self, path: Vec<Value>, top_level: bool, hint: Option<CacheHint>) {
+        let Some(hint) = hint else {
+            if top_level {
+                self.max_age = 0;
+            }
+            return;
+        };
+
+        self.max_age = self.max_age.min(hint.max_age);
+        if hint.scope == CacheScope::Private {
+            self.scope = CacheScope::Private;
+        }
+        self.hints.push((path, hint));
+    }
+
+    /// Whether the response may be reused at all
+    pub fn is_cacheable(&self) -> bool {
+        self.max_age > 0
+    }
+
+    /// Value of the `cacheControl` response extension
+    pub fn to_value(&self) -> Value {
+        let hints: Vec<_> = self
+            .hints
+            .iter()
+            .map(|(path, hint)| {
+                json!({
+                    "path": path,
+                    "maxAge": hint.max_age,
+                    "scope": hint.scope.as_str(),
+                })
+            })
+            .collect();
+
+        Value::from_json(json!({
+            "version": 1,
+            "maxAge": self.max_age,
+            "scope": self.scope.as_str(),
+            "hints": hints,
+        }))
+        .unwrap_or(Value::Null)
+    }
+
+    /// Policy reported in a response's `cacheControl` extension
+    ///
+    /// Hints are not read back. Returns `None` when the response has no
+    /// readable policy.
+    pub fn from_response(response: &Response) -> Option<Self> {
+        let Value::Object(extension) = response.extensions.get(CACHE_CONTROL_EXTENSION)? else {
+            return None;
+        };
+        let max_age = match extension.get("maxAge")? {
+            Value::Number(max_age) => u32::try_from(max_age.as_u64()?).ok()?,
+            _ => return None,
+        };
+        let scope = match extension.get("scope")? {
+            Value::String(scope) if scope == "PRIVATE" => CacheScope::Private,
+            Value::String(scope) if scope == "PUBLIC" => CacheScope::Public,
+            _ => return None,
+        };
+
+        Some(Self {
+            max_age,
+            scope,
+            hints: Vec::new(),
+        })
+    }
+}
+
+/// Path of a field in the response, e.g. `["dataSources", 0, "name"]`
+fn response_path(node: &QueryPathNode<'_>) -> Vec<Value> {
+    let mut path = Vec::new();
+    let mut current = Some(node);
+    while let Some(node) = current {
+        path.push(match node.segment {
+            QueryPathSegment::Index(index) => Value::from(index as u64),
+            QueryPathSegment::Name(name) => Value::from(name),
+        });
+        current = node.parent;
+    }
+    path.reverse();
+    path
+}
+
+/// Extension reporting cache hints in the `cacheControl` response extension
+pub struct CacheControlHints;
+
+impl ExtensionFactory for CacheControlHints {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(CacheControlExtension {
+            policy: Mutex::new(CachePolicy::new()),
+        })
+    }
+}
+
+struct CacheControlExtension {
+    policy: Mutex<CachePolicy>,
+}
+
+#[async_trait::async_trait]
+impl Extension for CacheControlExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if !info.is_for_introspection {
+            let hint = field_cache_hint(info.parent_type, info.name);
+            let top_level = info.path_node.parent.is_none();
+            self.policy
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .observe(response_path(info.path_node), top_level, hint);
+        }
+
+        next.run(ctx, info).await
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let response = next.run(ctx, operation_name).await;
+
+        let mut policy = self
+            .policy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .finish();
+        // Licensed data may only be reused for the user it was served to
+        if let Some(context) = ctx.data_opt::<Arc<GraphQLContext>>() {
+            if context.metrics.restricted_reads() > 0 {
+                policy.scope = CacheScope::Private;
+            }
+        }
+        response.extension(CACHE_CONTROL_EXTENSION, policy.to_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<Value> {
+        segments
+            .iter()
+            .map(|segment| Value::from(*segment))
+            .collect()
+    }
+
+    #[test]
+    fn test_policy_takes_most_restrictive_hint() {
+        // REQUIREMENT: Responses are only reused as long as every field in them allows
+        // PURPOSE: Verify the policy takes the smallest max age, turns private with any private field, and is uncacheable with an unhinted top-level field
+        // This ensures a metadata query that also reads observations or user data is never shared or reused
+
+        let mut policy = CachePolicy::new();
+        policy.observe(
+            path(&["dataSources"]),
+            true,
+            field_cache_hint("Query", "dataSources"),
+        );
+        policy.observe(path(&["dataSources", "name"]), false, None);
+        policy.observe(
+            path(&["dataSources", "lastCrawlAt"]),
+            false,
+            field_cache_hint("DataSourceType", "lastCrawlAt"),
+        );
+        assert_eq!(policy.max_age, 60);
+        assert_eq!(policy.scope, CacheScope::Public);
+        assert_eq!(policy.hints.len(), 2);
+
+        policy.observe(
+            path(&["dataSources", "apiKeyName"]),
+            false,
+            field_cache_hint("DataSourceType", "apiKeyName"),
+        );
+        assert_eq!(policy.scope, CacheScope::Private);
+        assert!(policy.is_cacheable());
+
+        policy.observe(path(&["me"]), true, field_cache_hint("Query", "me"));
+        assert!(!policy.is_cacheable());
+
+        let mut series = CachePolicy::new();
+        series.observe(path(&["series"]), true, field_cache_hint("Query", "series"));
+        series.observe(
+            path(&["series", "dataPoints"]),
+            false,
+            field_cache_hint("EconomicSeriesType", "dataPoints"),
+        );
+        assert!(!series.is_cacheable());
+    }
+
+    #[test]
+    fn test_policy_round_trips_through_response_extension() {
+        // REQUIREMENT: Cache hints are emitted in the GraphQL response extensions
+        // PURPOSE: Verify the cacheControl extension carries the policy and hints and can be read back from a response
+        // This ensures clients and the HTTP cache see the same policy
+
+        let mut policy = CachePolicy::new();
+        policy.observe(
+            path(&["dataSource"]),
+            true,
+            field_cache_hint("Query", "dataSource"),
+        );
+
+        let policy = policy.finish();
+        let value = policy.to_value().into_json().unwrap();
+        assert_eq!(value["maxAge"], 300);
+        assert_eq!(value["scope"], "PUBLIC");
+        assert_eq!(value["hints"][0]["path"], json!(["dataSource"]));
+
+        let response =
+            Response::new(Value::Null).extension(CACHE_CONTROL_EXTENSION, policy.to_value());
+        let read = CachePolicy::from_response(&response).unwrap();
+        assert_eq!(read.max_age, 300);
+        assert_eq!(read.scope, CacheScope::Public);
+        assert!(CachePolicy::from_response(&Response::new(Value::Null)).is_none());
+    }
+}
@@ -21,8 +21,13 @@
 //! - All authorization decisions must be auditable
 //! - Permission checks must be granular and specific
 
+use crate::graphql::dataloaders::DataLoaders;
 use crate::imports::*;
+use econ_graph_core::auth_models::Claims;
+use econ_graph_metrics::logging::usable_request_id;
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// User permissions for fine-grained access control
@@ -194,25 +199,94 @@ impl UserRole {
     }
 }
 
+/// Counters collected while a single GraphQL request executes
+#[derive(Debug)]
+pub struct RequestMetrics {
+    started_at: Instant,
+    authorization_denials: AtomicU64,
+    restricted_reads: AtomicU64,
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            authorization_denials: AtomicU64::new(0),
+            restricted_reads: AtomicU64::new(0),
+        }
+    }
+
+    /// Time since the request context was created
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Count a permission check that failed during this request
+    pub fn record_authorization_denial(&self) {
+        self.authorization_denials.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Permission checks that failed during this request
+    pub fn authorization_denials(&self) -> u64 {
+        self.authorization_denials.load(Ordering::Relaxed)
+    }
+
+    /// Count a read of a data source that is not public
+    pub fn record_restricted_read(&self) {
+        self.restricted_reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reads of non-public data sources during this request
+    pub fn restricted_reads(&self) -> u64 {
+        self.restricted_reads.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for RequestMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Any of these makes a user an admin
+const ADMIN_PERMISSIONS: &[Permission] = &[
+    Permission::ReadSystemConfig,
+    Permission::UpdateSystemConfig,
+    Permission::ManageCrawlers,
+    Permission::ViewLogs,
+    Permission::ManageSecurity,
+];
+
 /// GraphQL context containing the authenticated user and enhanced security
+///
+/// Built once per HTTP request (or WebSocket connection) and attached to the
+/// request with [`async_graphql::Request::data`], wrapped in an `Arc`.
 #[derive(Clone)]
 pub struct GraphQLContext {
     pub user: Option<User>,
+    /// JWT claims the request was authenticated with
+    pub claims: Option<Claims>,
     /// User's role with permissions
     pub user_role: Option<UserRole>,
     /// User's specific permissions (for fine-grained control)
     pub permissions: HashSet<Permission>,
+    /// DataLoaders scoped to this request, so batches never mix requests
+    pub data_loaders: Arc<DataLoaders>,
+    /// Counters collected while the request executes
+    pub metrics: Arc<RequestMetrics>,
     /// Client IP address for security logging
     pub client_ip: Option<String>,
     /// Request timestamp for audit trail
     pub request_timestamp: chrono::DateTime<chrono::Utc>,
     /// Request ID for tracking
     pub request_id: String,
+    /// Whether the response carries the `explain` extension; see [`explain`](super::explain)
+    pub explain: bool,
 }
 
 impl GraphQLContext {
     /// Create a new GraphQL context
-    pub fn new(user: Option<User>) -> Self {
+    pub fn new(pool: &DatabasePool, user: Option<User>) -> Self {
         let user_role = user
             .as_ref()
             .and_then(|u| UserRole::from_role_string(&u.role));
@@ -223,32 +297,70 @@ impl GraphQLContext {
 
         Self {
             user,
+            claims: None,
             user_role,
             permissions,
+            data_loaders: Arc::new(DataLoaders::new(pool.clone())),
+            metrics: Arc::new(RequestMetrics::new()),
             client_ip: None,
             request_timestamp: chrono::Utc::now(),
             request_id: uuid::Uuid::new_v4().to_string(),
+            explain: false,
         }
     }
 
     /// Create a new GraphQL context with client information
-    pub fn new_with_client_info(user: Option<User>, client_ip: Option<String>) -> Self {
-        let user_role = user
-            .as_ref()
-            .and_then(|u| UserRole::from_role_string(&u.role));
-        let permissions = user_role
-            .as_ref()
-            .map(|role| role.get_permissions())
-            .unwrap_or_default();
-
+    pub fn new_with_client_info(
+        pool: &DatabasePool,
+        user: Option<User>,
+        client_ip: Option<String>,
+    ) -> Self {
         Self {
-            user,
-            user_role,
-            permissions,
             client_ip,
-            request_timestamp: chrono::Utc::now(),
-            request_id: uuid::Uuid::new_v4().to_string(),
+            ..Self::new(pool, user)
+        }
+    }
+
+    /// Attach the JWT claims the user was authenticated with
+    pub fn with_claims(mut self, claims: Option<Claims>) -> Self {
+        self.claims = claims;
+        self
+    }
+
+    /// Attach the resolved client IP address
+    pub fn with_client_ip(mut self, client_ip: impl Into<String>) -> Self {
+        self.client_ip = Some(client_ip.into());
+        self
+    }
+
+    /// Use the caller's request ID (e.g. `x-request-id`) when it is usable
+    ///
+    /// IDs that are empty, too long or contain anything but ASCII
+    /// alphanumerics, `-` and `_` are ignored so they cannot forge log lines.
+    pub fn with_request_id(mut self, request_id: Option<&str>) -> Self {
+        if let Some(request_id) = request_id.and_then(usable_request_id) {
+            self.request_id = request_id.to_string();
         }
+        self
+    }
+
+    /// Describe the execution in the `explain` extension if asked to; only admins may
+    pub fn with_explain(mut self, requested: bool) -> Self {
+        self.explain = requested && self.has_any_permission(ADMIN_PERMISSIONS);
+        self
+    }
+
+    /// Key this request's rate limit is charged to
+    pub fn rate_limit_key(&self) -> String {
+        let user_id = self
+            .claims
+            .as_ref()
+            .map(|claims| claims.sub.clone())
+            .or_else(|| self.user.as_ref().map(|user| user.id.to_string()));
+        rate_limit_key(
+            user_id.as_deref(),
+            self.client_ip.as_deref().unwrap_or("unknown"),
+        )
     }
 
     /// Get the current authenticated user
@@ -268,6 +380,7 @@ impl GraphQLContext {
         let user = self.current_user()?;
 
         if !self.has_permission(permission) {
+            self.metrics.record_authorization_denial();
             warn!(
                 "Permission denied for user {} (role: {}): {}",
                 user.id,
@@ -297,6 +410,7 @@ impl GraphQLContext {
         let user = self.current_user()?;
 
         if !self.has_any_permission(permissions) {
+            self.metrics.record_authorization_denial();
             warn!(
                 "Permission denied for user {} (role: {}): any of {:?}",
                 user.id, user.role, permissions
@@ -322,6 +436,7 @@ impl GraphQLContext {
         let user = self.current_user()?;
 
         if !self.has_all_permissions(permissions) {
+            self.metrics.record_authorization_denial();
             warn!(
                 "Permission denied for user {} (role: {}): all of {:?}",
                 user.id, user.role, permissions
@@ -339,13 +454,7 @@ impl GraphQLContext {
 
     /// Check if the current user has admin role
     pub fn require_admin(&self) -> Result<&User> {
-        self.require_any_permission(&[
-            Permission::ReadSystemConfig,
-            Permission::UpdateSystemConfig,
-            Permission::ManageCrawlers,
-            Permission::ViewLogs,
-            Permission::ManageSecurity,
-        ])
+        self.require_any_permission(ADMIN_PERMISSIONS)
     }
 
     /// Check if the current user has super admin role
@@ -372,6 +481,7 @@ impl GraphQLContext {
             return Ok(user);
         }
 
+        self.metrics.record_authorization_denial();
         Err(GraphQLError::new(
             "Insufficient permissions to manage this user",
         ))
@@ -518,6 +628,33 @@ impl GraphQLContext {
     }
 }
 
+/// Rate limit key for a request
+///
+/// Authenticated requests are limited per user, so users sharing an address
+/// (offices, NAT) do not exhaust each other's budget; anonymous ones per IP.
+pub fn rate_limit_key(user_id: Option<&str>, client_ip: &str) -> String {
+    match user_id {
+        Some(user_id) => format!("user:{}", user_id),
+        None => format!("ip:{}", client_ip),
+    }
+}
+
+/// Helper function to get the request context
+pub fn request_context<'a>(ctx: &'a Context<'a>) -> Result<&'a GraphQLContext> {
+    Ok(ctx.data::<Arc<GraphQLContext>>()?.as_ref())
+}
+
+/// Helper function to get the DataLoaders of the current request
+///
+/// Falls back to the schema-wide loaders when the schema is executed without
+/// a request context, as the MCP server and tests do.
+pub fn data_loaders<'a>(ctx: &'a Context<'a>) -> Result<&'a DataLoaders> {
+    if let Some(context) = ctx.data_opt::<Arc<GraphQLContext>>() {
+        return Ok(&context.data_loaders);
+    }
+    Ok(ctx.data::<Arc<DataLoaders>>()?.as_ref())
+}
+
 /// Helper function to get the current user from GraphQL context
 pub fn current_user<'a>(ctx: &'a Context<'a>) -> Result<&'a User> {
     let context = ctx.data::<Arc<GraphQLContext>>()?;
@@ -607,3 +744,47 @@ pub fn can_view_security_events<'a>(ctx: &'a Context<'a>) -> Result<&'a User> {
     let context = ctx.data::<Arc<GraphQLContext>>()?;
     context.can_view_security_events()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use econ_graph_metrics::logging::MAX_REQUEST_ID_LENGTH;
+
+    #[test]
+    fn test_rate_limit_key() {
+        // REQUIREMENT: Per-user rate limits for authenticated GraphQL requests
+        // PURPOSE: Verify authenticated requests are keyed by user and anonymous ones by IP
+        // This ensures users sharing an address do not share a rate limit budget
+
+        assert_eq!(rate_limit_key(Some("42"), "203.0.113.7"), "user:42");
+        assert_eq!(rate_limit_key(None, "203.0.113.7"), "ip:203.0.113.7");
+    }
+
+    #[test]
+    fn test_usable_request_id() {
+        // REQUIREMENT: Request IDs propagate from callers for log correlation
+        // PURPOSE: Verify well-formed IDs are kept and anything else is rejected
+        // This ensures a client cannot inject arbitrary text into audit logs
+
+        assert_eq!(usable_request_id(" req-123_abc "), Some("req-123_abc"));
+        assert_eq!(usable_request_id(""), None);
+        assert_eq!(usable_request_id("bad id\nUser: admin"), None);
+        assert_eq!(
+            usable_request_id(&"a".repeat(MAX_REQUEST_ID_LENGTH + 1)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_request_metrics_counts_denials() {
+        // REQUIREMENT: Authorization denials are visible in request metrics
+        // PURPOSE: Verify denials recorded during a request are counted
+        // This ensures failed permission checks reach the Prometheus counter
+
+        let metrics = RequestMetrics::new();
+        assert_eq!(metrics.authorization_denials(), 0);
+        metrics.record_authorization_denial();
+        metrics.record_authorization_denial();
+        assert_eq!(metrics.authorization_denials(), 2);
+    }
+}
@@ -0,0 +1,271 @@
+//! # Data Source Access Policies
+//!
+//! Some data sources are licensed for signed-in users or premium subscribers
+//! only. A source's [`DataAccessPolicy`] decides who may read its series and
+//! observations:
+//!
+//! - `PUBLIC`: everyone, including anonymous users
+//! - `REGISTERED`: signed-in users
+//! - `PREMIUM`: users with a premium subscription
+//!
+//! The reader's subscription tier comes from the JWT claims the request was
+//! authenticated with, so a tier change applies once the user's token is
+//! refreshed. Administrators may read every source. Requests executed without
+//! a [`GraphQLContext`] are treated as anonymous, so a caller that forgets to
+//! attach one cannot read restricted data.
+//!
+//! Denied reads fail with an error whose extensions say what is missing:
+//!
+//! ```json
+//! {
+//!   "message": "Data from Bloomberg requires a premium subscription",
+//!   "extensions": {
+//!     "code": "SUBSCRIPTION_REQUIRED",
+//!     "requiredAccess": "PREMIUM",
+//!     "dataSourceId": "…",
+//!     "dataSourceName": "Bloomberg"
+//!   }
+//! }
+//! ```
+//!
+//! `code` is `UNAUTHENTICATED` when the reader is anonymous.
+//!
+//! Reading a restricted source marks the response `PRIVATE` for caching, so it
+//! is never served to another user.
+
+use async_graphql::ErrorExtensions;
+
+use crate::graphql::context::data_loaders;
+use crate::imports::*;
+
+/// Error code of reads that need the reader to sign in
+pub const UNAUTHENTICATED_CODE: &str = "UNAUTHENTICATED";
+
+/// Error code of reads that need a better subscription
+pub const SUBSCRIPTION_REQUIRED_CODE: &str = "SUBSCRIPTION_REQUIRED";
+
+/// Who is reading, as far as access policies are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataReader {
+    /// Administrator
+    Unrestricted,
+    /// Anonymous request
+    Anonymous,
+    /// Signed-in user with their subscription tier
+    Subscriber(SubscriptionTier),
+}
+
+impl DataReader {
+    /// Reader of the request being resolved
+    pub fn of(ctx: &Context<'_>) -> Self {
+        let Some(context) = ctx.data_opt::<Arc<GraphQLContext>>() else {
+            return DataReader::Anonymous;
+        };
+
+        match (&context.claims, &context.user) {
+            (Some(claims), _) if claims.role == UserRole::Admin => DataReader::Unrestricted,
+            (Some(claims), _) => DataReader::Subscriber(claims.tier),
+            (None, Some(user)) if user.role == "admin" => DataReader::Unrestricted,
+            (None, Some(user)) => DataReader::Subscriber(user.subscription_tier),
+            (None, None) => DataReader::Anonymous,
+        }
+    }
+
+    /// Whether the reader may read data of a source with `policy`
+    pub fn may_read(&self, policy: DataAccessPolicy) -> bool {
+        match self {
+            DataReader::Unrestricted => true,
+            DataReader::Anonymous => policy.permits(None),
+            DataReader::Subscriber(tier) => policy.permits(Some(*tier)),
+        }
+    }
+}
+
+/// Check the request may read `source`'s series and observations
+pub fn require_source_access(ctx: &Context<'_>, source: &DataSource) -> Result<()> {
+    let reader = DataReader::of(ctx);
+
+    if may_read_source(ctx, source) {
+        return Ok(());
+    }
+
+    if let Some(context) = ctx.data_opt::<Arc<GraphQLContext>>() {
+        context.metrics.record_authorization_denial();
+    }
+    Err(access_denied(source, reader))
+}
+
+/// Whether the request may read `source`'s data, noting restricted reads for caching
+fn may_read_source(ctx: &Context<'_>, source: &DataSource) -> bool {
+    if !DataReader::of(ctx).may_read(source.access_policy) {
+        return false;
+    }
+    if source.access_policy != DataAccessPolicy::Public {
+        if let Some(context) = ctx.data_opt::<Arc<GraphQLContext>>() {
+            context.metrics.record_restricted_read();
+        }
+    }
+    true
+}
+
+/// Check the request may read data of the source with `source_id`
+///
+/// An unknown source has no policy to enforce.
+pub async fn require_source_access_by_id(ctx: &Context<'_>, source_id: Uuid) -> Result<()> {
+    let source = match data_loaders(ctx)?
+        .data_source_loader
+        .try_load(source_id)
+        .await
+    {
+        Ok(source) => source,
+        Err(_) => DataSource::find_by_id(ctx.data::<DatabasePool>()?, source_id).await?,
+    };
+
+    match source {
+        Some(source) => require_source_access(ctx, &source),
+        None => Ok(()),
+    }
+}
+
+/// Check the request may read data recorded under the data source name `name`
+///
+/// Global indicator data and provenance name their source rather than
+/// referencing it. A name that is not a data source has no policy to enforce.
+pub async fn require_source_access_by_name(ctx: &Context<'_>, name: &str) -> Result<()> {
+    match DataSource::find_by_name(ctx.data::<DatabasePool>()?, name).await? {
+        Some(source) => require_source_access(ctx, &source),
+        None => Ok(()),
+    }
+}
+
+/// Whether the request may read data recorded under the data source name `name`
+///
+/// For resolvers that leave out what the reader may not see instead of failing.
+pub async fn may_read_source_by_name(ctx: &Context<'_>, name: &str) -> Result<bool> {
+    let source = DataSource::find_by_name(ctx.data::<DatabasePool>()?, name).await?;
+    Ok(match source {
+        Some(source) => may_read_source(ctx, &source),
+        None => true,
+    })
+}
+
+/// Check the request may read data of the series with `series_id`
+///
+/// An unknown series is left to the resolver to report.
+pub async fn require_series_access(ctx: &Context<'_>, series_id: Uuid) -> Result<()> {
+    let pool = ctx.data::<DatabasePool>()?;
+
+    match series_service::get_series_by_id(pool, series_id).await? {
+        Some(series) => require_source_access_by_id(ctx, series.source_id).await,
+        None => Ok(()),
+    }
+}
+
+/// Error for a reader that may not read `source`'s data
+fn access_denied(source: &DataSource, reader: DataReader) -> GraphQLError {
+    let (code, message) = match reader {
+        DataReader::Anonymous => (
+            UNAUTHENTICATED_CODE,
+            format!("Sign in to read data from {}", source.name),
+        ),
+        _ => (
+            SUBSCRIPTION_REQUIRED_CODE,
+            format!("Data from {} requires a premium subscription", source.name),
+        ),
+    };
+    let required_access = source.access_policy.as_str().to_uppercase();
+    let source_id = source.id.to_string();
+    let source_name = source.name.clone();
+
+    GraphQLError::new(message).extend_with(move |_, extensions| {
+        extensions.set("code", code);
+        extensions.set("requiredAccess", required_access);
+        extensions.set("dataSourceId", source_id);
+        extensions.set("dataSourceName", source_name);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::Value;
+
+    fn source(access_policy: DataAccessPolicy) -> DataSource {
+        DataSource {
+            id: Uuid::new_v4(),
+            name: "Bloomberg".to_string(),
+            description: None,
+            base_url: "https://api.bloomberg.com".to_string(),
+            api_key_required: true,
+            rate_limit_per_minute: 60,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_visible: true,
+            is_enabled: true,
+            requires_admin_approval: false,
+            crawl_frequency_hours: 24,
+            last_crawl_at: None,
+            crawl_status: None,
+            crawl_error_message: None,
+            api_documentation_url: None,
+            api_key_name: None,
+            daily_byte_quota: None,
+            daily_request_quota: None,
+            access_policy,
+            fallback_urls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_readers_by_policy() {
+        // REQUIREMENT: Licensed data sources are only readable by the users they are licensed to
+        // PURPOSE: Verify which readers may read public, registered and premium sources
+        // This ensures premium data never reaches anonymous or free users while administrators see everything
+
+        let free = DataReader::Subscriber(SubscriptionTier::Free);
+        let premium = DataReader::Subscriber(SubscriptionTier::Premium);
+
+        assert!(DataReader::Anonymous.may_read(DataAccessPolicy::Public));
+        assert!(!DataReader::Anonymous.may_read(DataAccessPolicy::Registered));
+        assert!(free.may_read(DataAccessPolicy::Registered));
+        assert!(!free.may_read(DataAccessPolicy::Premium));
+        assert!(premium.may_read(DataAccessPolicy::Premium));
+        assert!(DataReader::Unrestricted.may_read(DataAccessPolicy::Premium));
+    }
+
+    #[test]
+    fn test_denial_extensions() {
+        // REQUIREMENT: Clients can tell why restricted data was refused
+        // PURPOSE: Verify denials carry the error code, required access and data source in their extensions
+        // This ensures the frontend can prompt anonymous users to sign in and free users to upgrade
+
+        let source = source(DataAccessPolicy::Premium);
+
+        let anonymous = access_denied(&source, DataReader::Anonymous);
+        let extensions = anonymous.extensions.unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::from(UNAUTHENTICATED_CODE))
+        );
+        assert_eq!(
+            extensions.get("requiredAccess"),
+            Some(&Value::from("PREMIUM"))
+        );
+
+        let free = access_denied(&source, DataReader::Subscriber(SubscriptionTier::Free));
+        assert!(free.message.contains("premium subscription"));
+        let extensions = free.extensions.unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::from(SUBSCRIPTION_REQUIRED_CODE))
+        );
+        assert_eq!(
+            extensions.get("dataSourceId"),
+            Some(&Value::from(source.id.to_string()))
+        );
+        assert_eq!(
+            extensions.get("dataSourceName"),
+            Some(&Value::from("Bloomberg"))
+        );
+    }
+}
@@ -131,6 +131,8 @@ impl BatchFn<Uuid, Vec<DataPoint>> for DataPointsBySeriesBatcher {
 }
 
 /// DataLoader batcher for efficiently loading data point counts by series ID
+///
+/// Counts come from the maintained series statistics, not from `data_points`.
 pub struct DataPointCountBatcher {
     pub pool: DatabasePool,
 }
@@ -142,7 +144,7 @@ impl BatchFn<Uuid, i32> for DataPointCountBatcher {
         async move {
             use diesel::prelude::*;
             use diesel_async::RunQueryDsl;
-            use econ_graph_core::schema::data_points::dsl;
+            use econ_graph_core::schema::series_statistics::dsl;
 
             let mut conn = match pool.get().await {
                 Ok(conn) => conn,
@@ -152,10 +154,9 @@ impl BatchFn<Uuid, i32> for DataPointCountBatcher {
                 }
             };
 
-            let counts = match dsl::data_points
+            let counts = match dsl::series_statistics
                 .filter(dsl::series_id.eq_any(&keys))
-                .group_by(dsl::series_id)
-                .select((dsl::series_id, diesel::dsl::count(dsl::id)))
+                .select((dsl::series_id, dsl::data_point_count))
                 .load::<(Uuid, i64)>(&mut conn)
                 .await
             {
@@ -241,6 +242,8 @@ impl BatchFn<Uuid, Vec<EconomicSeries>> for SeriesBySourceBatcher {
 }
 
 /// DataLoader batcher for efficiently loading series counts by source ID
+///
+/// Counts active series, from the maintained data source statistics.
 pub struct SeriesCountBatcher {
     pub pool: DatabasePool,
 }
@@ -252,7 +255,7 @@ impl BatchFn<Uuid, i32> for SeriesCountBatcher {
         async move {
             use diesel::prelude::*;
             use diesel_async::RunQueryDsl;
-            use econ_graph_core::schema::economic_series::dsl;
+            use econ_graph_core::schema::data_source_statistics::dsl;
 
             let mut conn = match pool.get().await {
                 Ok(conn) => conn,
@@ -262,11 +265,9 @@ impl BatchFn<Uuid, i32> for SeriesCountBatcher {
                 }
             };
 
-            let counts = match dsl::economic_series
+            let counts = match dsl::data_source_statistics
                 .filter(dsl::source_id.eq_any(&keys))
-                .filter(dsl::is_active.eq(true))
-                .group_by(dsl::source_id)
-                .select((dsl::source_id, diesel::dsl::count(dsl::id)))
+                .select((dsl::source_id, dsl::active_series_count))
                 .load::<(Uuid, i64)>(&mut conn)
                 .await
             {
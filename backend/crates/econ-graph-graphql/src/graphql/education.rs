@@ -0,0 +1,454 @@
+//! # Educational Content GraphQL Types
+//!
+//! GraphQL types for the educational content library: learning paths,
+//! modules and the signed-in user's progress and achievements.
+//!
+//! Answers to assessment questions and solutions of exercises are never
+//! exposed; progress updates are graded on the server.
+
+use crate::imports::*;
+use econ_graph_core::models::{
+    AchievementType, AssessmentQuestion, ContentSection, EducationalModule, InteractiveExercise,
+    LearningAchievement, LearningPath, LearningPathModule, LearningProgress, LearningStatus,
+    QuizScore,
+};
+use econ_graph_services::services::education_service::{
+    ProgressOutcome, ProgressUpdate, QuizAnswer,
+};
+
+/// Where a learner is in a module
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "LearningStatus")]
+pub enum LearningStatusType {
+    NotStarted,
+    InProgress,
+    Completed,
+    Paused,
+    Abandoned,
+}
+
+impl From<LearningStatus> for LearningStatusType {
+    fn from(status: LearningStatus) -> Self {
+        match status {
+            LearningStatus::NotStarted => Self::NotStarted,
+            LearningStatus::InProgress => Self::InProgress,
+            LearningStatus::Completed => Self::Completed,
+            LearningStatus::Paused => Self::Paused,
+            LearningStatus::Abandoned => Self::Abandoned,
+        }
+    }
+}
+
+/// Kind of learning achievement
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AchievementType")]
+pub enum AchievementTypeType {
+    ModuleCompletion,
+    PathCompletion,
+    PerfectScore,
+    SpeedCompletion,
+    Streak,
+    ExpertLevel,
+    CommunityContributor,
+}
+
+impl From<AchievementType> for AchievementTypeType {
+    fn from(achievement_type: AchievementType) -> Self {
+        match achievement_type {
+            AchievementType::ModuleCompletion => Self::ModuleCompletion,
+            AchievementType::PathCompletion => Self::PathCompletion,
+            AchievementType::PerfectScore => Self::PerfectScore,
+            AchievementType::SpeedCompletion => Self::SpeedCompletion,
+            AchievementType::Streak => Self::Streak,
+            AchievementType::ExpertLevel => Self::ExpertLevel,
+            AchievementType::CommunityContributor => Self::CommunityContributor,
+        }
+    }
+}
+
+/// GraphQL representation of a learning path
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningPath")]
+pub struct LearningPathType {
+    pub id: ID,
+    pub title: String,
+    pub description: String,
+    /// Intended audience, e.g. "IndividualInvestor"
+    pub target_audience: String,
+    pub estimated_duration_hours: i32,
+    /// Modules in the order they should be studied
+    pub modules: Vec<LearningPathModuleType>,
+    pub prerequisites: Vec<String>,
+    pub learning_outcomes: Vec<String>,
+    pub author: String,
+}
+
+impl From<LearningPath> for LearningPathType {
+    fn from(path: LearningPath) -> Self {
+        Self {
+            id: ID::from(path.id),
+            title: path.title,
+            description: path.description,
+            target_audience: format!("{:?}", path.target_audience),
+            estimated_duration_hours: path.estimated_duration_hours,
+            modules: path
+                .modules
+                .into_iter()
+                .map(LearningPathModuleType::from)
+                .collect(),
+            prerequisites: path.prerequisites,
+            learning_outcomes: path.learning_outcomes,
+            author: path.author,
+        }
+    }
+}
+
+/// Module within a learning path
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningPathModule")]
+pub struct LearningPathModuleType {
+    pub module_id: ID,
+    pub order_index: i32,
+    pub is_required: bool,
+    pub estimated_duration_minutes: i32,
+}
+
+impl From<LearningPathModule> for LearningPathModuleType {
+    fn from(module: LearningPathModule) -> Self {
+        Self {
+            module_id: ID::from(module.module_id),
+            order_index: module.order_index,
+            is_required: module.is_required,
+            estimated_duration_minutes: module.estimated_duration_minutes,
+        }
+    }
+}
+
+/// GraphQL representation of an educational module
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningModule")]
+pub struct LearningModuleType {
+    pub id: ID,
+    pub title: String,
+    pub description: String,
+    /// Difficulty, e.g. "Beginner"
+    pub difficulty: String,
+    /// Category, e.g. "Valuation"
+    pub category: String,
+    pub estimated_duration_minutes: i32,
+    /// Modules to study first
+    pub prerequisite_ids: Vec<ID>,
+    pub learning_objectives: Vec<String>,
+    pub content_sections: Vec<ContentSectionType>,
+    pub exercises: Vec<LearningExerciseType>,
+    pub assessment_questions: Vec<AssessmentQuestionType>,
+    pub author: String,
+}
+
+impl From<EducationalModule> for LearningModuleType {
+    fn from(module: EducationalModule) -> Self {
+        Self {
+            id: ID::from(module.id),
+            title: module.title,
+            description: module.description,
+            difficulty: format!("{:?}", module.difficulty),
+            category: format!("{:?}", module.category),
+            estimated_duration_minutes: module.estimated_duration_minutes,
+            prerequisite_ids: module.prerequisites.into_iter().map(ID::from).collect(),
+            learning_objectives: module.learning_objectives,
+            content_sections: module
+                .content_sections
+                .into_iter()
+                .map(ContentSectionType::from)
+                .collect(),
+            exercises: module
+                .interactive_exercises
+                .into_iter()
+                .map(LearningExerciseType::from)
+                .collect(),
+            assessment_questions: module
+                .assessment_questions
+                .into_iter()
+                .map(AssessmentQuestionType::from)
+                .collect(),
+            author: module.author,
+        }
+    }
+}
+
+/// Section of module content
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "ContentSection")]
+pub struct ContentSectionType {
+    pub id: ID,
+    pub title: String,
+    /// Content type, e.g. "Text"
+    pub content_type: String,
+    pub content: String,
+    pub order_index: i32,
+    /// Ratios discussed in the section
+    pub related_ratios: Vec<String>,
+}
+
+impl From<ContentSection> for ContentSectionType {
+    fn from(section: ContentSection) -> Self {
+        Self {
+            id: ID::from(section.id),
+            title: section.title,
+            content_type: format!("{:?}", section.content_type),
+            content: section.content,
+            order_index: section.order_index,
+            related_ratios: section.related_ratios,
+        }
+    }
+}
+
+/// Calculation exercise of a module; the expected results are not exposed
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningExercise")]
+pub struct LearningExerciseType {
+    pub id: ID,
+    pub title: String,
+    pub description: String,
+    /// Exercise type, e.g. "RatioCalculation"
+    pub exercise_type: String,
+    pub instructions: String,
+    /// Financial data the exercise works from
+    pub data: serde_json::Value,
+    /// Ratios to calculate
+    pub ratios_to_calculate: Vec<String>,
+    pub hints: Vec<String>,
+    pub difficulty: String,
+    pub estimated_time_minutes: i32,
+}
+
+impl From<InteractiveExercise> for LearningExerciseType {
+    fn from(exercise: InteractiveExercise) -> Self {
+        Self {
+            id: ID::from(exercise.id),
+            title: exercise.title,
+            description: exercise.description,
+            exercise_type: format!("{:?}", exercise.exercise_type),
+            instructions: exercise.instructions,
+            data: exercise.data,
+            ratios_to_calculate: exercise
+                .expected_calculations
+                .into_iter()
+                .map(|calculation| calculation.ratio_name)
+                .collect(),
+            hints: exercise.hints,
+            difficulty: format!("{:?}", exercise.difficulty),
+            estimated_time_minutes: exercise.estimated_time_minutes,
+        }
+    }
+}
+
+/// Assessment question; the correct answer is not exposed
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "AssessmentQuestion")]
+pub struct AssessmentQuestionType {
+    pub id: ID,
+    /// Question type, e.g. "MultipleChoice"
+    pub question_type: String,
+    pub question: String,
+    /// Options of a multiple choice question; answer with the option's index
+    pub options: Vec<String>,
+    pub difficulty: String,
+    pub points: i32,
+}
+
+impl From<AssessmentQuestion> for AssessmentQuestionType {
+    fn from(question: AssessmentQuestion) -> Self {
+        Self {
+            id: ID::from(question.id),
+            question_type: format!("{:?}", question.question_type),
+            question: question.question,
+            options: question.options,
+            difficulty: format!("{:?}", question.difficulty),
+            points: question.points,
+        }
+    }
+}
+
+/// Graded answer to an assessment question
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "QuizScore")]
+pub struct QuizScoreType {
+    pub question_id: ID,
+    pub user_answer: serde_json::Value,
+    pub is_correct: bool,
+    pub points_earned: i32,
+    pub time_taken_seconds: i32,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl From<QuizScore> for QuizScoreType {
+    fn from(score: QuizScore) -> Self {
+        Self {
+            question_id: ID::from(score.question_id),
+            user_answer: score.user_answer,
+            is_correct: score.is_correct,
+            points_earned: score.points_earned,
+            time_taken_seconds: score.time_taken_seconds,
+            attempted_at: score.attempted_at,
+        }
+    }
+}
+
+/// The current user's progress through a module
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningProgress")]
+pub struct LearningProgressType {
+    pub id: ID,
+    pub module_id: Option<ID>,
+    pub status: LearningStatusType,
+    /// Share of sections, exercises and questions done, 0-100
+    pub progress_percentage: f64,
+    pub time_spent_minutes: i32,
+    pub completed_section_ids: Vec<ID>,
+    pub completed_exercise_ids: Vec<ID>,
+    pub quiz_scores: Vec<QuizScoreType>,
+    pub started_at: DateTime<Utc>,
+    pub last_accessed_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<LearningProgress> for LearningProgressType {
+    fn from(progress: LearningProgress) -> Self {
+        Self {
+            id: ID::from(progress.id),
+            module_id: progress.module_id.map(ID::from),
+            status: progress.status.into(),
+            progress_percentage: progress.progress_percentage,
+            time_spent_minutes: progress.time_spent_minutes,
+            completed_section_ids: progress
+                .completed_sections
+                .into_iter()
+                .map(ID::from)
+                .collect(),
+            completed_exercise_ids: progress
+                .completed_exercises
+                .into_iter()
+                .map(ID::from)
+                .collect(),
+            quiz_scores: progress
+                .quiz_scores
+                .into_iter()
+                .map(QuizScoreType::from)
+                .collect(),
+            started_at: progress.started_at,
+            last_accessed_at: progress.last_accessed_at,
+            completed_at: progress.completed_at,
+        }
+    }
+}
+
+/// Achievement earned by the current user
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningAchievement")]
+pub struct LearningAchievementType {
+    pub id: ID,
+    pub achievement_type: AchievementTypeType,
+    pub title: String,
+    pub description: String,
+    pub criteria: String,
+    pub earned_at: DateTime<Utc>,
+    pub module_id: Option<ID>,
+    pub learning_path_id: Option<ID>,
+    pub badge_url: Option<String>,
+}
+
+impl From<LearningAchievement> for LearningAchievementType {
+    fn from(achievement: LearningAchievement) -> Self {
+        Self {
+            id: ID::from(achievement.id),
+            achievement_type: achievement.achievement_type.into(),
+            title: achievement.title,
+            description: achievement.description,
+            criteria: achievement.criteria,
+            earned_at: achievement.earned_at,
+            module_id: achievement.module_id.map(ID::from),
+            learning_path_id: achievement.learning_path_id.map(ID::from),
+            badge_url: achievement.badge_url,
+        }
+    }
+}
+
+/// Progress after an update together with achievements it earned
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "LearningProgressResult")]
+pub struct LearningProgressResultType {
+    pub progress: LearningProgressType,
+    /// Achievements earned by this update
+    pub new_achievements: Vec<LearningAchievementType>,
+}
+
+impl From<ProgressOutcome> for LearningProgressResultType {
+    fn from(outcome: ProgressOutcome) -> Self {
+        Self {
+            progress: outcome.progress.into(),
+            new_achievements: outcome
+                .new_achievements
+                .into_iter()
+                .map(LearningAchievementType::from)
+                .collect(),
+        }
+    }
+}
+
+/// Answer to an assessment question
+#[derive(InputObject)]
+pub struct QuizAnswerInput {
+    pub question_id: ID,
+    /// The answer; the option index for multiple choice questions
+    pub answer: serde_json::Value,
+    pub time_taken_seconds: Option<i32>,
+}
+
+/// Progress made in a module since the last update
+#[derive(InputObject)]
+pub struct RecordLearningProgressInput {
+    pub module_id: ID,
+    /// Sections read since the last update
+    #[graphql(default)]
+    pub completed_section_ids: Vec<ID>,
+    /// Exercises finished since the last update
+    #[graphql(default)]
+    pub completed_exercise_ids: Vec<ID>,
+    /// Answers given since the last update; replace earlier answers
+    #[graphql(default)]
+    pub quiz_answers: Vec<QuizAnswerInput>,
+    /// Minutes studied since the last update
+    #[graphql(default)]
+    pub time_spent_minutes: i32,
+}
+
+impl TryFrom<RecordLearningProgressInput> for ProgressUpdate {
+    type Error = uuid::Error;
+
+    fn try_from(input: RecordLearningProgressInput) -> std::result::Result<Self, Self::Error> {
+        let parse_ids = |ids: Vec<ID>| {
+            ids.iter()
+                .map(|id| Uuid::parse_str(id))
+                .collect::<std::result::Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            module_id: Uuid::parse_str(&input.module_id)?,
+            completed_section_ids: parse_ids(input.completed_section_ids)?,
+            completed_exercise_ids: parse_ids(input.completed_exercise_ids)?,
+            quiz_answers: input
+                .quiz_answers
+                .into_iter()
+                .map(|answer| {
+                    Ok(QuizAnswer {
+                        question_id: Uuid::parse_str(&answer.question_id)?,
+                        answer: answer.answer,
+                        time_taken_seconds: answer.time_taken_seconds.unwrap_or(0),
+                    })
+                })
+                .collect::<std::result::Result<Vec<_>, uuid::Error>>()?,
+            time_spent_minutes: input.time_spent_minutes,
+        })
+    }
+}
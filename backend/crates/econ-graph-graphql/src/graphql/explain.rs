@@ -0,0 +1,246 @@
+//! # Explain Mode
+//!
+//! Admins diagnosing a slow query can send it with the [`EXPLAIN_HEADER`]
+//! header set to `true`. The response then carries an `explain` extension
+//! describing how it was executed: time spent per field, the SQL statements run
+//! with their durations, and the hits and misses of in-process caches.
+//!
+//! ```json
+//! "extensions": {
+//!   "explain": {
+//!     "durationMs": 41.7,
+//!     "resolvers": [
+//!       { "field": "Query.seriesData", "calls": 1, "totalMs": 38.2, "maxMs": 38.2, "rows": 120 }
+//!     ],
+//!     "sql": {
+//!       "count": 2,
+//!       "totalMs": 30.1,
+//!       "statements": [{ "statement": "SELECT ...", "durationMs": 27.9, "failed": false }]
+//!     },
+//!     "caches": { "data_points": { "hits": 0, "misses": 1 } }
+//!   }
+//! }
+//! ```
+//!
+//! Resolver times include the fields below them, and `rows` counts the items
+//! of list results. Statements are collected with
+//! [`query_explain`](econ_graph_core::query_explain), so those run by
+//! DataLoader batches, which execute in tasks of their own, are not listed.
+//! The header is ignored for everyone but admins.
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{Response, ServerResult, Value};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use econ_graph_core::query_explain::{explain_scope, ExplainReport, QueryExplain};
+
+use crate::graphql::context::GraphQLContext;
+
+/// Request header that asks for the `explain` extension
+pub const EXPLAIN_HEADER: &str = "x-econgraph-explain";
+
+/// Name of the response extension the execution is described in
+pub const EXPLAIN_EXTENSION: &str = "explain";
+
+/// Time spent resolving one field of one type, over all its calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolverTiming {
+    /// `Type.field`
+    pub field: String,
+    pub calls: usize,
+    pub total_ms: f64,
+    pub max_ms: f64,
+    /// Items of the list results of the field
+    pub rows: usize,
+}
+
+/// Resolver timings of a request, slowest field first
+#[derive(Debug, Default)]
+pub struct ResolverTimings {
+    fields: HashMap<String, ResolverTiming>,
+}
+
+impl ResolverTimings {
+    pub fn record(&mut self, parent_type: &str, field: &str, duration_ms: f64, rows: usize) {
+        let name = format!("{}.{}", parent_type, field);
+        let timing = self
+            .fields
+            .entry(name.clone())
+            .or_insert_with(|| ResolverTiming {
+                field: name,
+                ..ResolverTiming::default()
+            });
+        timing.calls += 1;
+        timing.total_ms += duration_ms;
+        timing.max_ms = timing.max_ms.max(duration_ms);
+        timing.rows += rows;
+    }
+
+    pub fn slowest_first(&self) -> Vec<ResolverTiming> {
+        let mut timings: Vec<ResolverTiming> = self.fields.values().cloned().collect();
+        timings.sort_by(|a, b| {
+            b.total_ms
+                .total_cmp(&a.total_ms)
+                .then_with(|| a.field.cmp(&b.field))
+        });
+        timings
+    }
+}
+
+/// The `explain` extension value
+pub fn explain_value(
+    duration_ms: f64,
+    resolvers: Vec<ResolverTiming>,
+    report: ExplainReport,
+) -> Value {
+    let explain = json!({
+        "durationMs": duration_ms,
+        "resolvers": resolvers,
+        "sql": {
+            "count": report.statement_count,
+            "totalMs": report.statement_total_ms,
+            "statements": report.statements,
+        },
+        "caches": report.caches,
+    });
+    Value::from_json(explain).unwrap_or(Value::Null)
+}
+
+/// Whether the request asked for explain output and may have it
+fn is_explained(ctx: &ExtensionContext<'_>) -> bool {
+    ctx.data_opt::<Arc<GraphQLContext>>()
+        .is_some_and(|context| context.explain)
+}
+
+/// Extension adding the `explain` extension to responses of admins who ask for it
+pub struct QueryExplainer;
+
+impl ExtensionFactory for QueryExplainer {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryExplainerExtension {
+            explain: Arc::new(QueryExplain::new()),
+            resolvers: Mutex::new(ResolverTimings::default()),
+        })
+    }
+}
+
+struct QueryExplainerExtension {
+    explain: Arc<QueryExplain>,
+    resolvers: Mutex<ResolverTimings>,
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryExplainerExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if info.is_for_introspection || !is_explained(ctx) {
+            return next.run(ctx, info).await;
+        }
+
+        let (parent_type, field) = (info.parent_type, info.name);
+        let started = Instant::now();
+        let result = next.run(ctx, info).await;
+        let rows = match &result {
+            Ok(Some(Value::List(items))) => items.len(),
+            _ => 0,
+        };
+        self.resolvers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(
+                parent_type,
+                field,
+                started.elapsed().as_secs_f64() * 1000.0,
+                rows,
+            );
+        result
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        if !is_explained(ctx) {
+            return next.run(ctx, operation_name).await;
+        }
+
+        let started = Instant::now();
+        let response = explain_scope(self.explain.clone(), next.run(ctx, operation_name)).await;
+        let resolvers = self
+            .resolvers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .slowest_first();
+        response.extension(
+            EXPLAIN_EXTENSION,
+            explain_value(
+                started.elapsed().as_secs_f64() * 1000.0,
+                resolvers,
+                self.explain.report(),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use econ_graph_core::query_explain::{record_cache_lookup, record_statement};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_explain_value_reports_resolvers_sql_and_caches() {
+        // REQUIREMENT: Admins can diagnose slow dashboard queries from the response itself
+        // PURPOSE: Verify resolver timings are aggregated per field, slowest first, next to the SQL and cache lookups
+        // This ensures the explain output stays small for large responses while pointing at the slow field
+
+        let mut timings = ResolverTimings::default();
+        timings.record("Query", "seriesData", 38.0, 120);
+        timings.record("DataPoint", "value", 0.5, 0);
+        timings.record("DataPoint", "value", 1.5, 0);
+
+        let explain = Arc::new(QueryExplain::new());
+        explain_scope(explain.clone(), async {
+            record_statement(
+                "SELECT * FROM data_points",
+                Duration::from_millis(30),
+                false,
+            );
+            record_cache_lookup("data_points", false);
+        })
+        .await;
+
+        let value = explain_value(41.0, timings.slowest_first(), explain.report())
+            .into_json()
+            .unwrap();
+        assert_eq!(
+            value["resolvers"],
+            json!([
+                { "field": "Query.seriesData", "calls": 1, "totalMs": 38.0, "maxMs": 38.0, "rows": 120 },
+                { "field": "DataPoint.value", "calls": 2, "totalMs": 2.0, "maxMs": 1.5, "rows": 0 }
+            ])
+        );
+        assert_eq!(value["sql"]["count"], 1);
+        assert_eq!(
+            value["sql"]["statements"][0]["statement"],
+            "SELECT * FROM data_points"
+        );
+        assert_eq!(
+            value["caches"],
+            json!({ "data_points": { "hits": 0, "misses": 1 } })
+        );
+    }
+}
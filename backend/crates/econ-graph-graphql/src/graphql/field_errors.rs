@@ -0,0 +1,153 @@
+//! # Field Errors
+//!
+//! When a model rejects an input, the error a resolver returns carries the
+//! rules that failed (see [`econ_graph_core::validation`]). The
+//! [`FieldErrorReporter`] extension lists them in the error's extensions, with
+//! field paths in the API's camel case, so clients can mark each offending
+//! input:
+//!
+//! ```json
+//! "extensions": {
+//!   "code": "VALIDATION_FAILED",
+//!   "fieldErrors": [
+//!     { "field": "fiscalQuarter", "code": "fiscal_quarter", "message": "must be a fiscal quarter from 1 to 4" },
+//!     { "field": "lineItems[1].level", "code": "range", "message": "must be between 0 and 10" }
+//!   ]
+//! }
+//! ```
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo,
+};
+use async_graphql::{ServerError, ServerResult, Value};
+use std::sync::Arc;
+
+use econ_graph_core::error::AppError;
+use econ_graph_core::validation::FieldError;
+
+/// Error code of inputs rejected by model validation
+pub const VALIDATION_FAILED_CODE: &str = "VALIDATION_FAILED";
+
+/// `fiscal_quarter` as `fiscalQuarter`, `line_items[1].unit_ref` as `lineItems[1].unitRef`
+pub fn camel_case_path(path: &str) -> String {
+    let mut camel = String::with_capacity(path.len());
+    let mut upper_next = false;
+    for c in path.chars() {
+        match c {
+            '_' => upper_next = true,
+            c if upper_next => {
+                camel.push(c.to_ascii_uppercase());
+                upper_next = false;
+            }
+            c => camel.push(c),
+        }
+    }
+    camel
+}
+
+/// The `fieldErrors` extension value
+pub fn field_errors_value(field_errors: &[FieldError]) -> Value {
+    Value::List(
+        field_errors
+            .iter()
+            .map(|error| {
+                Value::from_json(serde_json::json!({
+                    "field": camel_case_path(&error.field),
+                    "code": error.code,
+                    "message": error.message,
+                }))
+                .unwrap_or(Value::Null)
+            })
+            .collect(),
+    )
+}
+
+/// Add the failed rules to an error that came from model validation
+pub fn report_field_errors(mut error: ServerError) -> ServerError {
+    let Some(field_errors) = error.source::<AppError>().and_then(AppError::field_errors) else {
+        return error;
+    };
+
+    let extensions = error.extensions.get_or_insert_with(Default::default);
+    extensions.set("code", VALIDATION_FAILED_CODE);
+    extensions.set("fieldErrors", field_errors_value(&field_errors));
+    error
+}
+
+/// Extension adding `fieldErrors` to errors of inputs rejected by model validation
+pub struct FieldErrorReporter;
+
+impl ExtensionFactory for FieldErrorReporter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(FieldErrorReporterExtension)
+    }
+}
+
+struct FieldErrorReporterExtension;
+
+#[async_trait::async_trait]
+impl Extension for FieldErrorReporterExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        next.run(ctx, info).await.map_err(report_field_errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::Pos;
+    use econ_graph_core::models::CompanyComparisonParams;
+    use econ_graph_core::validation::validate;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_validation_errors_are_reported_per_field() {
+        // REQUIREMENT: GraphQL clients can tell which input a validation failure is about
+        // PURPOSE: Verify errors from model validation get a VALIDATION_FAILED code and camel-cased field errors, and other errors are left alone
+        // This ensures forms can highlight fields without parsing error messages
+
+        let params = CompanyComparisonParams {
+            company_ids: vec![Uuid::new_v4(), Uuid::new_v4()],
+            metrics: vec!["revenue".to_string()],
+            fiscal_year: Some(2023),
+            fiscal_quarter: Some(7),
+            include_peer_averages: false,
+            include_industry_benchmarks: false,
+        };
+        let app_error = validate(&params).unwrap_err();
+        let error = async_graphql::Error::from(app_error).into_server_error(Pos::default());
+
+        let reported = report_field_errors(error);
+        let extensions = reported.extensions.unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::from(VALIDATION_FAILED_CODE))
+        );
+        assert_eq!(
+            extensions
+                .get("fieldErrors")
+                .cloned()
+                .unwrap()
+                .into_json()
+                .unwrap(),
+            serde_json::json!([{
+                "field": "fiscalQuarter",
+                "code": "fiscal_quarter",
+                "message": "must be a fiscal quarter from 1 to 4",
+            }])
+        );
+
+        let other = async_graphql::Error::from(AppError::NotFound("series".to_string()))
+            .into_server_error(Pos::default());
+        assert!(report_field_errors(other).extensions.is_none());
+        assert_eq!(
+            camel_case_path("line_items[1].unit_ref"),
+            "lineItems[1].unitRef"
+        );
+    }
+}
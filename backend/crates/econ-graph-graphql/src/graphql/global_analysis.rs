@@ -422,6 +422,41 @@ impl From<CountryCorrelation> for CountryCorrelationType {
     }
 }
 
+/// Leading indicator relationship GraphQL type
+#[derive(SimpleObject)]
+pub struct LeadingIndicatorType {
+    pub id: String,
+    pub leading_country_id: String,
+    pub following_country_id: String,
+    pub indicator_category: String,
+    /// Months the leading country's indicator moves ahead of the follower's
+    pub lead_time_months: i32,
+    /// Correlation at the lead time
+    pub correlation_strength: String,
+    /// Share of moves the leader called in the right direction (0-1)
+    pub predictive_accuracy: Option<String>,
+    pub time_period_start: String,
+    pub time_period_end: String,
+    pub calculated_at: String,
+}
+
+impl From<LeadingIndicator> for LeadingIndicatorType {
+    fn from(indicator: LeadingIndicator) -> Self {
+        Self {
+            id: indicator.id.to_string(),
+            leading_country_id: indicator.leading_country_id.to_string(),
+            following_country_id: indicator.following_country_id.to_string(),
+            indicator_category: indicator.indicator_category,
+            lead_time_months: indicator.lead_time_months,
+            correlation_strength: indicator.correlation_strength.to_string(),
+            predictive_accuracy: indicator.predictive_accuracy.map(|a| a.to_string()),
+            time_period_start: indicator.time_period_start.to_string(),
+            time_period_end: indicator.time_period_end.to_string(),
+            calculated_at: indicator.calculated_at.to_rfc3339(),
+        }
+    }
+}
+
 #[Object]
 impl CountryType {
     async fn id(&self) -> &str {
@@ -4,17 +4,29 @@
 //! This module provides the GraphQL API layer that bridges the core domain
 //! models with the external API consumers.
 
+pub mod admin_audit;
+pub mod cache_control;
 pub mod context;
+pub mod data_access;
 pub mod dataloaders;
+pub mod education;
+pub mod explain;
+pub mod field_errors;
 pub mod global_analysis;
 pub mod mutation;
+pub mod pagination;
+pub mod public_tier;
 pub mod query;
 pub mod schema;
+pub mod subscription;
+pub mod versioning;
 
 #[cfg(test)]
 pub mod n_plus_one_tests;
 
 // Re-export commonly used types
+pub use context::GraphQLContext;
 pub use mutation::Mutation;
 pub use query::Query;
-pub use schema::{create_schema, create_schema_with_data, GraphQLContext};
+pub use schema::{create_schema, create_schema_with_data, AppSchema};
+pub use subscription::Subscription;
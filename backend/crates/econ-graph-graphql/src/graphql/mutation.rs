@@ -17,8 +17,11 @@
 //! - Database transactions must be atomic and consistent
 //! - All mutations must have comprehensive documentation
 
+use crate::graphql::data_access::{require_series_access, DataReader};
+use crate::graphql::education::{LearningProgressResultType, RecordLearningProgressInput};
 use crate::imports::*;
 use crate::types::*;
+use std::io::Read;
 
 /// Root mutation object
 pub struct Mutation;
@@ -68,6 +71,10 @@ impl Mutation {
 
         let user_id = uuid::Uuid::parse_str(&input.user_id)?;
         let series_id = uuid::Uuid::parse_str(&input.series_id)?;
+        let organization_id = input
+            .organization_id
+            .map(|id| uuid::Uuid::parse_str(&id))
+            .transpose()?;
 
         let annotation = collaboration_service
             .create_annotation(
@@ -80,12 +87,59 @@ impl Mutation {
                 input.annotation_type,
                 input.color,
                 input.is_public.unwrap_or(false),
+                organization_id,
             )
             .await?;
 
         Ok(ChartAnnotationType::from(annotation))
     }
 
+    /// Import chart annotations from an uploaded CSV file
+    ///
+    /// Valid rows are created even when others fail; the report gives the
+    /// outcome of every row.
+    async fn import_annotations(
+        &self,
+        ctx: &Context<'_>,
+        file: Upload,
+        organization_id: Option<ID>,
+    ) -> Result<AnnotationImportReportType> {
+        let user = can_create_annotations(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_id = organization_id
+            .map(|id| uuid::Uuid::parse_str(&id))
+            .transpose()?;
+
+        // Reading the upload blocks, so it runs off the executor and stops past the size limit
+        let upload = file.value(ctx)?;
+        let content = tokio::task::spawn_blocking(move || {
+            let mut content = Vec::new();
+            upload
+                .into_read()
+                .take(annotation_import_service::MAX_IMPORT_BYTES + 1)
+                .read_to_end(&mut content)
+                .map(|_| content)
+        })
+        .await??;
+        if content.len() as u64 > annotation_import_service::MAX_IMPORT_BYTES {
+            return Err(GraphQLError::new(format!(
+                "Annotations file is larger than {} bytes",
+                annotation_import_service::MAX_IMPORT_BYTES
+            )));
+        }
+
+        let reader = DataReader::of(ctx);
+        let report = annotation_import_service::import_annotations(
+            pool,
+            user.id,
+            organization_id,
+            &content,
+            |policy| reader.may_read(policy),
+        )
+        .await?;
+        Ok(AnnotationImportReportType::from(report))
+    }
+
     /// Add a comment to an annotation
     async fn add_comment(
         &self,
@@ -105,6 +159,24 @@ impl Mutation {
         Ok(AnnotationCommentType::from(comment))
     }
 
+    /// Resolve a comment thread (comment author or annotation owner only)
+    async fn resolve_comment(
+        &self,
+        ctx: &Context<'_>,
+        comment_id: ID,
+    ) -> Result<AnnotationCommentType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let collaboration_service = CollaborationService::new(pool.clone());
+
+        let comment_uuid = uuid::Uuid::parse_str(&comment_id)?;
+        let comment = collaboration_service
+            .resolve_comment(user.id, comment_uuid)
+            .await?;
+
+        Ok(AnnotationCommentType::from(comment))
+    }
+
     /// Share a chart with another user
     async fn share_chart(
         &self,
@@ -152,6 +224,1197 @@ impl Mutation {
         Ok(true)
     }
 
+    // Annotation Review Mutations
+
+    /// Ask another user to review a financial statement's annotations
+    async fn assign_annotation_review(
+        &self,
+        ctx: &Context<'_>,
+        input: AssignAnnotationReviewInput,
+    ) -> Result<AnnotationAssignmentType> {
+        let user = can_create_annotations(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let statement_id = Uuid::parse_str(&input.statement_id)?;
+        let assignee_id = Uuid::parse_str(&input.assignee_id)?;
+        let mut new_assignment = match input.line_item_id {
+            Some(line_item_id) => NewAnnotationAssignment::for_line_item(
+                statement_id,
+                Uuid::parse_str(&line_item_id)?,
+                assignee_id,
+                user.id,
+                input.assignment_type.into(),
+            ),
+            None => NewAnnotationAssignment::new(
+                statement_id,
+                assignee_id,
+                user.id,
+                input.assignment_type.into(),
+            ),
+        };
+        new_assignment.due_date = input.due_date;
+        new_assignment.notes = input.notes;
+
+        let assignment = AnnotationWorkflowService::new(pool.clone())
+            .assign(&new_assignment)
+            .await?;
+        Ok(AnnotationAssignmentType::from(assignment))
+    }
+
+    /// Accept an annotation assignment made to the current user
+    async fn accept_annotation_assignment(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<AnnotationAssignmentType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let assignment = AnnotationWorkflowService::new(pool.clone())
+            .accept(Uuid::parse_str(&id)?, user.id)
+            .await?;
+        Ok(AnnotationAssignmentType::from(assignment))
+    }
+
+    /// Complete an accepted annotation assignment made to the current user
+    async fn complete_annotation_assignment(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<AnnotationAssignmentType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let assignment = AnnotationWorkflowService::new(pool.clone())
+            .complete(Uuid::parse_str(&id)?, user.id)
+            .await?;
+        Ok(AnnotationAssignmentType::from(assignment))
+    }
+
+    /// Reject an annotation assignment made to the current user, or withdraw one they made
+    async fn reject_annotation_assignment(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        reason: Option<String>,
+    ) -> Result<AnnotationAssignmentType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let assignment = AnnotationWorkflowService::new(pool.clone())
+            .reject(Uuid::parse_str(&id)?, user.id, reason.as_deref())
+            .await?;
+        Ok(AnnotationAssignmentType::from(assignment))
+    }
+
+    // Notification Mutations
+
+    /// Mark some of the current user's notifications as read
+    ///
+    /// Returns the number of notifications that were unread.
+    async fn mark_notifications_read(&self, ctx: &Context<'_>, ids: Vec<ID>) -> Result<i32> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let ids = ids
+            .iter()
+            .map(|id| uuid::Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let updated = NotificationService::new(pool.clone())
+            .mark_read(user.id, Some(&ids))
+            .await?;
+
+        Ok(updated as i32)
+    }
+
+    /// Mark all of the current user's notifications as read
+    async fn mark_all_notifications_read(&self, ctx: &Context<'_>) -> Result<i32> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let updated = NotificationService::new(pool.clone())
+            .mark_read(user.id, None)
+            .await?;
+
+        Ok(updated as i32)
+    }
+
+    /// Change which emails the current user receives
+    async fn update_email_preferences(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateEmailPreferencesInput,
+    ) -> Result<EmailPreferencesType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let preferences = EmailPreferences::update(pool, user.id, &input.into()).await?;
+        Ok(EmailPreferencesType::from(preferences))
+    }
+
+    // Organization Mutations
+
+    /// Create an organization owned by the current user
+    async fn create_organization(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateOrganizationInput,
+    ) -> Result<OrganizationType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let slug = input
+            .slug
+            .filter(|slug| !slug.trim().is_empty())
+            .unwrap_or_else(|| models::slugify_organization_name(&input.name));
+
+        let new_organization = NewOrganization {
+            name: input.name.trim().to_string(),
+            slug,
+            description: input.description,
+            created_by: Some(user.id),
+        };
+
+        let organization = Organization::create(pool, &new_organization, user.id).await?;
+        Ok(OrganizationType::from(organization))
+    }
+
+    /// Add a member to an organization or change their role (organization admins only)
+    async fn set_organization_member(
+        &self,
+        ctx: &Context<'_>,
+        input: OrganizationMemberInput,
+    ) -> Result<OrganizationMemberType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_id = uuid::Uuid::parse_str(&input.organization_id)?;
+        let member_user_id = uuid::Uuid::parse_str(&input.user_id)?;
+
+        let manager = OrganizationAccess::new(pool.clone())
+            .require_member_manager(organization_id, user.id)
+            .await?;
+
+        let role: OrganizationRole = input.role.parse()?;
+        if role == OrganizationRole::Owner && manager.organization_role() != OrganizationRole::Owner
+        {
+            return Err(GraphQLError::new("Only owners can grant the owner role"));
+        }
+
+        // Demoting an owner must leave at least one owner behind
+        if let Some(existing) =
+            OrganizationMember::find(pool, organization_id, member_user_id).await?
+        {
+            if existing.organization_role() == OrganizationRole::Owner
+                && role != OrganizationRole::Owner
+            {
+                if manager.organization_role() != OrganizationRole::Owner {
+                    return Err(GraphQLError::new("Only owners can change an owner's role"));
+                }
+                let owners = OrganizationMember::count_with_role(
+                    pool,
+                    organization_id,
+                    OrganizationRole::Owner,
+                )
+                .await?;
+                if owners <= 1 {
+                    return Err(GraphQLError::new(
+                        "An organization must keep at least one owner",
+                    ));
+                }
+            }
+        }
+
+        let member = OrganizationMember::upsert(
+            pool,
+            &NewOrganizationMember {
+                organization_id,
+                user_id: member_user_id,
+                role: role.to_string(),
+                invited_by: Some(user.id),
+            },
+        )
+        .await?;
+
+        Ok(OrganizationMemberType::from(member))
+    }
+
+    /// Remove a member from an organization (organization admins, or the member themselves)
+    async fn remove_organization_member(
+        &self,
+        ctx: &Context<'_>,
+        organization_id: ID,
+        user_id: ID,
+    ) -> Result<bool> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_uuid = uuid::Uuid::parse_str(&organization_id)?;
+        let member_uuid = uuid::Uuid::parse_str(&user_id)?;
+
+        if member_uuid != user.id {
+            OrganizationAccess::new(pool.clone())
+                .require_member_manager(organization_uuid, user.id)
+                .await?;
+        }
+
+        let Some(member) = OrganizationMember::find(pool, organization_uuid, member_uuid).await?
+        else {
+            return Ok(false);
+        };
+
+        if member.organization_role() == OrganizationRole::Owner {
+            let owners = OrganizationMember::count_with_role(
+                pool,
+                organization_uuid,
+                OrganizationRole::Owner,
+            )
+            .await?;
+            if owners <= 1 {
+                return Err(GraphQLError::new(
+                    "An organization must keep at least one owner",
+                ));
+            }
+        }
+
+        Ok(OrganizationMember::remove(pool, organization_uuid, member_uuid).await?)
+    }
+
+    /// Share a chart with every member of an organization
+    async fn share_chart_with_organization(
+        &self,
+        ctx: &Context<'_>,
+        input: ShareChartWithOrganizationInput,
+    ) -> Result<OrganizationChartShareType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_id = uuid::Uuid::parse_str(&input.organization_id)?;
+        let chart_id = uuid::Uuid::parse_str(&input.chart_id)?;
+
+        let permission_level = input.permission_level.to_lowercase();
+        if !models::CHART_PERMISSION_LEVELS.contains(&permission_level.as_str()) {
+            return Err(GraphQLError::new(
+                "Permission level must be one of: view, comment, edit, admin",
+            ));
+        }
+
+        OrganizationAccess::new(pool.clone())
+            .require_role(organization_id, user.id, OrganizationRole::Member)
+            .await?;
+        require_chart_admin(pool, user.id, chart_id).await?;
+
+        let share = OrganizationChartShare::upsert(
+            pool,
+            &NewOrganizationChartShare {
+                organization_id,
+                chart_id,
+                shared_by: Some(user.id),
+                permission_level,
+            },
+        )
+        .await?;
+
+        Ok(OrganizationChartShareType::from(share))
+    }
+
+    /// Stop sharing a chart with an organization
+    async fn unshare_chart_from_organization(
+        &self,
+        ctx: &Context<'_>,
+        organization_id: ID,
+        chart_id: ID,
+    ) -> Result<bool> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_uuid = uuid::Uuid::parse_str(&organization_id)?;
+        let chart_uuid = uuid::Uuid::parse_str(&chart_id)?;
+
+        OrganizationAccess::new(pool.clone())
+            .require_role(organization_uuid, user.id, OrganizationRole::Member)
+            .await?;
+        require_chart_admin(pool, user.id, chart_uuid).await?;
+
+        Ok(OrganizationChartShare::remove(pool, organization_uuid, chart_uuid).await?)
+    }
+
+    // Saved Chart Mutations
+
+    /// Save a chart configuration for the current user
+    async fn create_saved_chart(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateSavedChartInput,
+    ) -> Result<SavedChartType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let series_ids = input
+            .series_ids
+            .iter()
+            .map(|id| uuid::Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let new_chart = NewSavedChart {
+            user_id: user.id,
+            title: input.title.trim().to_string(),
+            description: input.description,
+            chart_config: input.chart_config.unwrap_or_else(|| serde_json::json!({})),
+            series_ids,
+            transformations: input
+                .transformations
+                .unwrap_or_else(|| serde_json::json!({})),
+            start_date: input.start_date,
+            end_date: input.end_date,
+            is_public: input.is_public.unwrap_or(false),
+        };
+
+        let chart = SavedChart::create(pool, &new_chart).await?;
+        Ok(SavedChartType::from(chart))
+    }
+
+    /// Update a saved chart the current user may edit
+    ///
+    /// Owners, editors and organization members with edit access may change the
+    /// chart; only chart admins may change whether it is public.
+    async fn update_saved_chart(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateSavedChartInput,
+    ) -> Result<SavedChartType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let chart_uuid = uuid::Uuid::parse_str(&input.id)?;
+
+        let permission = CollaborationService::new(pool.clone())
+            .chart_permission(user.id, chart_uuid)
+            .await?;
+        let Some(permission) = permission.filter(|permission| permission.can_edit()) else {
+            return Err(GraphQLError::new("Saved chart not found"));
+        };
+        if input.is_public.is_some() && !permission.can_admin() {
+            return Err(GraphQLError::new(
+                "Only chart admins can change whether a chart is public",
+            ));
+        }
+
+        let series_ids = input
+            .series_ids
+            .map(|ids| {
+                ids.iter()
+                    .map(|id| uuid::Uuid::parse_str(id))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let changes = models::UpdateSavedChart {
+            title: input.title.map(|title| title.trim().to_string()),
+            description: input.description.into(),
+            chart_config: input.chart_config,
+            series_ids,
+            transformations: input.transformations,
+            start_date: input.start_date.into(),
+            end_date: input.end_date.into(),
+            is_public: input.is_public,
+        };
+
+        SavedChart::update(pool, chart_uuid, &changes)
+            .await?
+            .map(SavedChartType::from)
+            .ok_or_else(|| GraphQLError::new("Saved chart not found"))
+    }
+
+    /// Delete one of the current user's saved charts
+    async fn delete_saved_chart(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let chart_uuid = uuid::Uuid::parse_str(&id)?;
+
+        Ok(SavedChart::delete_for_user(pool, chart_uuid, user.id).await?)
+    }
+
+    // Learning Mutations
+
+    /// Record the current user's progress through a learning module
+    ///
+    /// Quiz answers are graded on the server; achievements earned by the
+    /// update are awarded and returned.
+    async fn record_learning_progress(
+        &self,
+        ctx: &Context<'_>,
+        input: RecordLearningProgressInput,
+    ) -> Result<LearningProgressResultType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let update = ProgressUpdate::try_from(input)?;
+
+        let outcome = EducationService::new(pool.clone())
+            .record_progress(user.id, update)
+            .await?;
+        Ok(LearningProgressResultType::from(outcome))
+    }
+
+    // Series Alert Mutations
+
+    /// Create an alert that notifies the current user when a series crosses a threshold
+    async fn create_series_alert_rule(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateSeriesAlertRuleInput,
+    ) -> Result<SeriesAlertRuleType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = uuid::Uuid::parse_str(&input.series_id)?;
+
+        if series_service::get_series_by_id(pool, series_uuid)
+            .await?
+            .is_none()
+        {
+            return Err(GraphQLError::new("Series not found"));
+        }
+
+        let new_rule = NewSeriesAlertRule {
+            user_id: user.id,
+            series_id: series_uuid,
+            name: input.name.trim().to_string(),
+            condition: AlertCondition::from(input.condition).as_str().to_string(),
+            threshold: input.threshold,
+            window_periods: input.window_periods.unwrap_or(1),
+            is_active: true,
+        };
+
+        let rule = SeriesAlertRule::create(pool, &new_rule).await?;
+        SeriesAlertRuleType::try_from(rule)
+    }
+
+    /// Update one of the current user's series alert rules
+    async fn update_series_alert_rule(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateSeriesAlertRuleInput,
+    ) -> Result<SeriesAlertRuleType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let rule_uuid = uuid::Uuid::parse_str(&input.id)?;
+
+        let changes = UpdateSeriesAlertRule {
+            name: input.name.map(|name| name.trim().to_string()),
+            condition: input
+                .condition
+                .map(|condition| AlertCondition::from(condition).as_str().to_string()),
+            threshold: input.threshold,
+            window_periods: input.window_periods,
+            is_active: input.is_active,
+            is_triggered: None,
+        };
+
+        let rule = SeriesAlertRule::update_for_user(pool, rule_uuid, user.id, &changes)
+            .await?
+            .ok_or_else(|| GraphQLError::new("Alert rule not found"))?;
+        SeriesAlertRuleType::try_from(rule)
+    }
+
+    /// Delete one of the current user's series alert rules
+    async fn delete_series_alert_rule(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let rule_uuid = uuid::Uuid::parse_str(&id)?;
+
+        Ok(SeriesAlertRule::delete_for_user(pool, rule_uuid, user.id).await?)
+    }
+
+    // Webhook Mutations
+
+    /// Register a webhook for crawl events on a data source or series (admin only)
+    ///
+    /// The secret is stored encrypted, so `SECRETS_ENCRYPTION_KEY` must be set.
+    async fn create_webhook(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateWebhookInput,
+    ) -> Result<WebhookType> {
+        let admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let mut event_types: Vec<String> = input
+            .event_types
+            .into_iter()
+            .map(|event| WebhookEvent::from(event).as_str().to_string())
+            .collect();
+        event_types.sort();
+        event_types.dedup();
+
+        let new_webhook = NewWebhook {
+            url: input.url.trim().to_string(),
+            secret: input.secret,
+            source_id: input
+                .source_id
+                .map(|id| uuid::Uuid::parse_str(&id))
+                .transpose()?,
+            series_id: input
+                .series_id
+                .map(|id| uuid::Uuid::parse_str(&id))
+                .transpose()?,
+            event_types,
+            description: input.description,
+            created_by: Some(admin_user.id),
+        };
+
+        let webhook = Webhook::create(pool, &new_webhook, shared_secret_cipher()?).await?;
+        WebhookType::try_from(webhook)
+    }
+
+    /// Pause or resume a webhook (admin only)
+    async fn set_webhook_active(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        is_active: bool,
+    ) -> Result<WebhookType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let webhook_id = uuid::Uuid::parse_str(&id)?;
+
+        let webhook = Webhook::set_active(pool, webhook_id, is_active).await?;
+        WebhookType::try_from(webhook)
+    }
+
+    /// Delete a webhook and its delivery log (admin only)
+    async fn delete_webhook(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let webhook_id = uuid::Uuid::parse_str(&id)?;
+
+        Ok(Webhook::delete(pool, webhook_id).await?)
+    }
+
+    // Release Calendar Mutations
+
+    /// Record when a data source or series publishes data, so crawls follow releases (admin only)
+    async fn create_release_calendar(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateReleaseCalendarInput,
+    ) -> Result<ReleaseCalendarType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let defaults = NewReleaseCalendar::default();
+        let new_calendar = NewReleaseCalendar {
+            source_id: input
+                .source_id
+                .map(|id| uuid::Uuid::parse_str(&id))
+                .transpose()?,
+            series_id: input
+                .series_id
+                .map(|id| uuid::Uuid::parse_str(&id))
+                .transpose()?,
+            frequency: ReleaseFrequency::from(input.frequency).as_str().to_string(),
+            day_of_week: input.day_of_week.map(i16::try_from).transpose()?,
+            week_of_month: input.week_of_month.map(i16::try_from).transpose()?,
+            day_of_month: input.day_of_month.map(i16::try_from).transpose()?,
+            month_of_period: i16::try_from(input.month_of_period)?,
+            release_time: input.release_time,
+            time_zone: input
+                .time_zone
+                .map(|time_zone| time_zone.trim().to_string())
+                .unwrap_or(defaults.time_zone),
+            crawl_delay_minutes: input
+                .crawl_delay_minutes
+                .unwrap_or(defaults.crawl_delay_minutes),
+        };
+
+        let calendar = ReleaseCalendar::create(pool, &new_calendar).await?;
+        ReleaseCalendarType::try_from(calendar)
+    }
+
+    /// Pause or resume crawls scheduled from a release calendar (admin only)
+    async fn set_release_calendar_active(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        is_active: bool,
+    ) -> Result<ReleaseCalendarType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let calendar_id = uuid::Uuid::parse_str(&id)?;
+
+        let calendar = ReleaseCalendar::set_active(pool, calendar_id, is_active).await?;
+        ReleaseCalendarType::try_from(calendar)
+    }
+
+    /// Delete a release calendar (admin only)
+    async fn delete_release_calendar(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let calendar_id = uuid::Uuid::parse_str(&id)?;
+
+        Ok(ReleaseCalendar::delete(pool, calendar_id).await?)
+    }
+
+    // Derived Series Mutations
+
+    /// Create a series computed from a formula over other series (analysts and admins)
+    async fn create_derived_series(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateDerivedSeriesInput,
+    ) -> Result<DerivedSeriesType> {
+        let user = can_create_charts(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let definition = DerivedSeriesDefinition {
+            title: input.title,
+            description: input.description,
+            units: input.units,
+            formula: input.formula,
+            inputs: input
+                .inputs
+                .into_iter()
+                .map(FormulaBinding::try_from)
+                .collect::<Result<Vec<_>>>()?,
+        };
+        for binding in &definition.inputs {
+            require_series_access(ctx, binding.series_id).await?;
+        }
+
+        let created = DerivedSeriesService::new(pool.clone())
+            .create(definition, Some(user.id))
+            .await?;
+        Ok(DerivedSeriesType::from(created))
+    }
+
+    /// Update one of the current user's derived series
+    async fn update_derived_series(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateDerivedSeriesInput,
+    ) -> Result<DerivedSeriesType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let derived_uuid = own_derived_series(pool, &input.id, user.id).await?;
+
+        let changes = DerivedSeriesChanges {
+            title: input.title,
+            description: input.description,
+            units: input.units,
+            formula: input.formula,
+            inputs: input
+                .inputs
+                .map(|inputs| {
+                    inputs
+                        .into_iter()
+                        .map(FormulaBinding::try_from)
+                        .collect::<Result<Vec<_>>>()
+                })
+                .transpose()?,
+        };
+        for binding in changes.inputs.iter().flatten() {
+            require_series_access(ctx, binding.series_id).await?;
+        }
+
+        let updated = DerivedSeriesService::new(pool.clone())
+            .update(derived_uuid, changes)
+            .await?;
+        Ok(DerivedSeriesType::from(updated))
+    }
+
+    /// Delete one of the current user's derived series and its values
+    async fn delete_derived_series(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let derived_uuid = own_derived_series(pool, &id, user.id).await?;
+
+        Ok(DerivedSeriesService::new(pool.clone())
+            .delete(derived_uuid)
+            .await?)
+    }
+
+    // Export Mutations
+
+    /// Queue an export of series data as CSV, Parquet or XLSX
+    ///
+    /// Poll `exportJob` until it completes, then fetch the file from its `downloadUrl`.
+    async fn request_export(
+        &self,
+        ctx: &Context<'_>,
+        input: RequestExportInput,
+    ) -> Result<ExportJobType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let series_ids = input
+            .series_ids
+            .iter()
+            .map(|id| Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        // The job runs without the request, so access is checked before queueing
+        for &series_id in &series_ids {
+            require_series_access(ctx, series_id).await?;
+        }
+
+        let job = ExportService::new(pool.clone())
+            .request(
+                user.id,
+                series_ids,
+                input.start_date,
+                input.end_date,
+                input.format.into(),
+            )
+            .await?;
+        ExportJobType::try_from(job)
+    }
+
+    // Series Link Mutations
+
+    /// Mark two series from different data sources as equivalent (curators only)
+    async fn link_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        linked_series_id: ID,
+    ) -> Result<SeriesLinkType> {
+        let user = can_write_economic_data(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        let linked_uuid = Uuid::parse_str(&linked_series_id)?;
+
+        let link = SeriesLinkService::new(pool.clone())
+            .link(series_uuid, linked_uuid, user.id)
+            .await?;
+        SeriesLinkType::try_from(link)
+    }
+
+    /// Remove the link between two series (curators only)
+    async fn unlink_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        linked_series_id: ID,
+    ) -> Result<bool> {
+        can_write_economic_data(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        let linked_uuid = Uuid::parse_str(&linked_series_id)?;
+
+        Ok(SeriesLinkService::new(pool.clone())
+            .unlink(series_uuid, linked_uuid)
+            .await?)
+    }
+
+    /// Link a series to similarly titled series from other sources (curators only)
+    ///
+    /// Returns the links that were added; existing links are kept as they are.
+    async fn auto_link_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        min_confidence: Option<f64>,
+    ) -> Result<Vec<SeriesLinkType>> {
+        can_write_economic_data(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+
+        SeriesLinkService::new(pool.clone())
+            .auto_link(
+                series_uuid,
+                min_confidence.unwrap_or(DEFAULT_MIN_MATCH_CONFIDENCE),
+            )
+            .await?
+            .into_iter()
+            .map(SeriesLinkType::try_from)
+            .collect()
+    }
+
+    /// Put a dead-lettered crawl queue item back on the queue (admin only)
+    async fn requeue_failed_item(&self, ctx: &Context<'_>, id: ID) -> Result<CrawlQueueItemType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let item_uuid = uuid::Uuid::parse_str(&id)?;
+
+        queue_service::requeue_dead_letter_item(pool, item_uuid)
+            .await?
+            .map(CrawlQueueItemType::from)
+            .ok_or_else(|| GraphQLError::new("Failed queue item not found"))
+    }
+
+    /// Permanently remove a dead-lettered crawl queue item (admin only)
+    async fn discard_failed_item(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let item_uuid = uuid::Uuid::parse_str(&id)?;
+
+        Ok(queue_service::discard_dead_letter_item(pool, item_uuid).await?)
+    }
+
+    /// Put crawl queue items back on the queue, including jobs stuck in processing (admin only)
+    async fn requeue_crawl_queue_items(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<ID>,
+    ) -> Result<CrawlQueueBulkResultType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let item_uuids = ids
+            .iter()
+            .map(|id| uuid::Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let requeued = queue_service::requeue_queue_items(pool, &item_uuids).await?;
+        Ok(CrawlQueueBulkResultType::new(&item_uuids, requeued))
+    }
+
+    /// Cancel crawl queue items so no worker picks them up (admin only)
+    async fn cancel_crawl_queue_items(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<ID>,
+    ) -> Result<CrawlQueueBulkResultType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let item_uuids = ids
+            .iter()
+            .map(|id| uuid::Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let cancelled = queue_service::cancel_queue_items(pool, &item_uuids).await?;
+        Ok(CrawlQueueBulkResultType::new(&item_uuids, cancelled))
+    }
+
+    /// Recompute industry and sector benchmarks for all financial ratios (admin only)
+    async fn refresh_industry_benchmarks(&self, ctx: &Context<'_>) -> Result<BenchmarkRefreshType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let summary = BenchmarkingService::refresh_benchmarks(pool).await?;
+
+        Ok(summary.into())
+    }
+
+    /// Rebuild the vocabulary search spelling corrections are drawn from (admin only)
+    async fn refresh_search_vocabulary(&self, ctx: &Context<'_>) -> Result<bool> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        SearchService::new(Arc::new(pool.clone()))
+            .refresh_vocabulary()
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Compute and store rolling correlations and lead/lag relationships (admin only)
+    async fn run_cross_series_analysis(
+        &self,
+        ctx: &Context<'_>,
+        input: RunCrossSeriesAnalysisInput,
+    ) -> Result<CrossSeriesAnalysisType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let mut config = CrossSeriesAnalysisConfig::new(
+            input.indicator_category,
+            input.start_date,
+            input.end_date,
+        );
+        config.indicator_code = input.indicator_code;
+        config.pairs = input
+            .country_pairs
+            .unwrap_or_default()
+            .iter()
+            .map(|pair| {
+                Ok((
+                    uuid::Uuid::parse_str(&pair.country_a_id)?,
+                    uuid::Uuid::parse_str(&pair.country_b_id)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (value, target) in [
+            (input.window_months, &mut config.window_months),
+            (input.step_months, &mut config.step_months),
+            (input.max_lag_months, &mut config.max_lag_months),
+        ] {
+            if let Some(value) = value {
+                *target = usize::try_from(value)
+                    .map_err(|_| GraphQLError::new("Month counts must not be negative"))?;
+            }
+        }
+
+        let summary = GlobalAnalysisService::run_cross_series_analysis(pool, &config).await?;
+
+        Ok(summary.into())
+    }
+
+    // Admin Data Source Management Mutations
+
+    /// Register a new data source (admin only)
+    async fn create_data_source(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateDataSourceInput,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let source = DataSourceAdminService::create(pool, &actor, input.into()).await?;
+
+        Ok(source.into())
+    }
+
+    /// Update a data source's descriptive and rate limit settings (admin only)
+    async fn update_data_source(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        input: UpdateDataSourceInput,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source =
+            DataSourceAdminService::update(pool, &actor, source_uuid, input.into()).await?;
+
+        Ok(source.into())
+    }
+
+    /// Enable or disable crawling of a data source (admin only)
+    async fn set_data_source_enabled(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        enabled: bool,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source =
+            DataSourceAdminService::set_enabled(pool, &actor, source_uuid, enabled).await?;
+
+        Ok(source.into())
+    }
+
+    /// Set how many hours pass between crawls of a data source (admin only)
+    async fn set_data_source_crawl_frequency(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        hours: i32,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source =
+            DataSourceAdminService::set_crawl_frequency(pool, &actor, source_uuid, hours).await?;
+
+        Ok(source.into())
+    }
+
+    /// Record the environment variable that holds a data source's API key (admin only)
+    ///
+    /// Pass the variable name, e.g. FRED_API_KEY, never the key itself. Omit
+    /// `api_key_name` to clear the reference.
+    async fn set_data_source_api_credentials(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        api_key_required: bool,
+        api_key_name: Option<String>,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source = DataSourceAdminService::set_api_credentials(
+            pool,
+            &actor,
+            source_uuid,
+            api_key_required,
+            api_key_name,
+        )
+        .await?;
+
+        Ok(source.into())
+    }
+
+    /// Set a data source's daily crawl quotas (admin only)
+    ///
+    /// Omit a quota to make it unlimited. Work for a source whose quota is
+    /// used up is deferred until midnight UTC.
+    async fn set_data_source_quotas(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        daily_byte_quota: Option<i64>,
+        daily_request_quota: Option<i32>,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source = DataSourceAdminService::set_quotas(
+            pool,
+            &actor,
+            source_uuid,
+            daily_byte_quota,
+            daily_request_quota,
+        )
+        .await?;
+
+        Ok(source.into())
+    }
+
+    /// Set the mirrors a data source's crawlers fail over to (admin only)
+    ///
+    /// Mirrors must accept the same request paths as `baseUrl`. An empty list
+    /// turns failover off.
+    async fn set_data_source_fallback_urls(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        fallback_urls: Vec<String>,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source =
+            DataSourceAdminService::set_fallback_urls(pool, &actor, source_uuid, fallback_urls)
+                .await?;
+
+        Ok(source.into())
+    }
+
+    /// Set who may read a data source's series and observations (admin only)
+    async fn set_data_source_access_policy(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        access_policy: DataAccessPolicyType,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source = DataSourceAdminService::set_access_policy(
+            pool,
+            &actor,
+            source_uuid,
+            access_policy.into(),
+        )
+        .await?;
+
+        Ok(source.into())
+    }
+
+    /// Store a data source's API key, encrypted (admin only)
+    ///
+    /// Send the key as a GraphQL variable rather than inline in the query
+    /// text. It is never returned; `hasStoredApiKey` reports whether one is
+    /// stored. A stored key takes precedence over the `apiKeyName` variable.
+    async fn store_data_source_api_key(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        api_key: String,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+        let cipher = shared_secret_cipher()?;
+
+        let source = DataSourceAdminService::store_api_key(
+            pool,
+            &actor,
+            cipher,
+            source_uuid,
+            &SecretString::new(api_key),
+        )
+        .await?;
+
+        Ok(source.into())
+    }
+
+    /// Delete a data source's stored API key (admin only)
+    async fn remove_data_source_api_key(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<DataSourceType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let source = DataSourceAdminService::remove_api_key(pool, &actor, source_uuid).await?;
+
+        Ok(source.into())
+    }
+
+    // Data Correction Mutations
+
+    /// Correct a data point's value (admin only)
+    ///
+    /// Writes a new revision instead of changing the existing row, marks it as
+    /// a correction and records the reason in the audit log.
+    async fn correct_data_point(
+        &self,
+        ctx: &Context<'_>,
+        input: CorrectDataPointInput,
+    ) -> Result<DataPointType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let data_point_uuid = uuid::Uuid::parse_str(&input.data_point_id)?;
+
+        let (data_point, _) = DataCorrectionService::correct_data_point(
+            pool,
+            &actor,
+            data_point_uuid,
+            input.value,
+            &input.reason,
+        )
+        .await?;
+
+        Ok(data_point.into())
+    }
+
+    // Session Mutations
+
+    /// Revoke a session so its access and refresh tokens stop working
+    ///
+    /// Users can revoke their own sessions; user admins can revoke anyone's.
+    async fn revoke_session(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let session_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let session = models::UserSession::find_active(pool, session_uuid)
+            .await?
+            .ok_or_else(|| GraphQLError::new("Session not found"))?;
+        can_manage_user(ctx, session.user_id)?;
+
+        Ok(models::UserSession::revoke(pool, session.id, Some(session.user_id)).await?)
+    }
+
+    /// Revoke every session of the current user, signing them out everywhere
+    async fn revoke_all_sessions(&self, ctx: &Context<'_>) -> Result<i32> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let revoked = models::UserSession::revoke_all(pool, user.id).await?;
+        Ok(revoked as i32)
+    }
+
+    /// Change the current user's theme, default chart type and notification settings
+    async fn update_user_preferences(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateUserPreferencesInput,
+    ) -> Result<UserPreferencesType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let updated = UserProfileService::update_preferences(pool, user.id, input.into()).await?;
+        Ok(UserPreferencesType::from(&updated))
+    }
+
     // Admin User Management Mutations
 
     /// Create a new user (admin only)
@@ -224,10 +1487,18 @@ impl Mutation {
         input: UpdateUserInput,
     ) -> Result<UserType> {
         // Require admin role
-        let _admin_user = require_admin(ctx)?;
+        let actor = audit_actor(ctx)?;
         let pool = ctx.data::<DatabasePool>()?;
         let user_id = uuid::Uuid::parse_str(&id)?;
 
+        PreferenceChanges {
+            theme: input.theme.clone(),
+            default_chart_type: input.default_chart_type.clone(),
+            notifications_enabled: None,
+            collaboration_enabled: None,
+        }
+        .validate()?;
+
         use diesel::prelude::*;
         use diesel_async::RunQueryDsl;
         use econ_graph_core::models::user::UpdateUser;
@@ -245,6 +1516,7 @@ impl Mutation {
             .optional()?;
 
         let existing_user = existing_user.ok_or_else(|| GraphQLError::new("User not found"))?;
+        let before = existing_user.clone();
 
         // Check if email is being changed and if it already exists
         if let Some(new_email) = &input.email {
@@ -323,9 +1595,42 @@ impl Mutation {
                 .await?;
         }
 
+        UserProfileService::record_admin_changes(pool, &actor, &before, &final_user).await?;
+
         Ok(UserType::from(final_user))
     }
 
+    /// Set a user's subscription tier (admin only)
+    ///
+    /// The new tier applies to data access once the user's token is refreshed.
+    async fn set_user_subscription_tier(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        tier: SubscriptionTierType,
+    ) -> Result<UserType> {
+        let actor = audit_actor(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let user_id = uuid::Uuid::parse_str(&id)?;
+
+        let user =
+            UserProfileService::set_subscription_tier(pool, &actor, user_id, tier.into()).await?;
+
+        Ok(UserType::from(user))
+    }
+
+    /// Mark a security event as resolved by the current admin
+    async fn resolve_security_event(&self, ctx: &Context<'_>, id: ID) -> Result<SecurityEventType> {
+        let admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let event_id = uuid::Uuid::parse_str(&id)?;
+
+        let event =
+            core_models::admin::SecurityEvent::resolve(pool, event_id, admin_user.id).await?;
+
+        Ok(event.into())
+    }
+
     /// Delete a user (admin only)
     async fn delete_user(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
         // Require admin role
@@ -461,6 +1766,42 @@ impl Mutation {
     }
 }
 
+/// ID of a derived series created by `user_id`
+async fn own_derived_series(pool: &DatabasePool, id: &ID, user_id: Uuid) -> Result<Uuid> {
+    let derived_uuid = Uuid::parse_str(id)?;
+
+    match DerivedSeries::find_by_id(pool, derived_uuid).await? {
+        Some(derived) if derived.created_by == Some(user_id) => Ok(derived_uuid),
+        _ => Err(GraphQLError::new("Derived series not found")),
+    }
+}
+
+/// Require the user to own or administer a chart before changing who it is shared with
+async fn require_chart_admin(pool: &DatabasePool, user_id: Uuid, chart_id: Uuid) -> Result<()> {
+    if CollaborationService::new(pool.clone())
+        .check_admin_permission(user_id, chart_id)
+        .await?
+    {
+        Ok(())
+    } else {
+        Err(GraphQLError::new(
+            "Only the chart's owner or admins can change its sharing",
+        ))
+    }
+}
+
+/// Require an admin and describe them for the audit log
+fn audit_actor(ctx: &Context<'_>) -> Result<AuditActor> {
+    let admin_user = require_admin(ctx)?;
+    let context = ctx.data::<Arc<GraphQLContext>>()?;
+
+    Ok(AuditActor {
+        user_id: admin_user.id,
+        user_name: admin_user.name.clone(),
+        ip_address: context.client_ip.clone(),
+    })
+}
+
 impl Default for Mutation {
     fn default() -> Self {
         Self
@@ -0,0 +1,210 @@
+//! # Cursor Pagination
+//!
+//! Relay-style pagination for connections. Cursors are opaque (base64 JSON of
+//! the keyset position of the row they point at), so pages stay stable while
+//! new observations are inserted and deep pages cost the same as the first.
+//!
+//! `totalCount` is exact for small result sets and the query planner's
+//! estimate above [`series_service::EXACT_COUNT_THRESHOLD`] rows, as reported
+//! by `totalCountIsEstimate`.
+
+use crate::imports::*;
+use crate::types::*;
+use async_graphql::connection::{CursorType, OpaqueCursor};
+use serde::de::DeserializeOwned;
+
+/// Data points returned when `first` is not given
+pub const DEFAULT_DATA_POINT_PAGE_SIZE: usize = 1_000;
+/// Largest data point page; larger `first` values are clamped
+pub const MAX_DATA_POINT_PAGE_SIZE: usize = 10_000;
+/// Series returned when `first` is not given
+pub const DEFAULT_SERIES_PAGE_SIZE: usize = 50;
+/// Largest series page; larger `first` values are clamped
+pub const MAX_SERIES_PAGE_SIZE: usize = 100;
+/// Audit log entries returned when `first` is not given
+pub const DEFAULT_AUDIT_LOG_PAGE_SIZE: usize = 50;
+/// Largest audit log page; larger `first` values are clamped
+pub const MAX_AUDIT_LOG_PAGE_SIZE: usize = 500;
+
+/// Page size for a `first` argument
+pub fn page_size(first: Option<i32>, default: usize, max: usize) -> Result<usize> {
+    match first {
+        None => Ok(default),
+        Some(first) if first < 0 => Err(GraphQLError::new("`first` must not be negative")),
+        Some(first) => Ok((first as usize).min(max)),
+    }
+}
+
+/// Opaque cursor for a keyset position
+pub fn encode_cursor<T: Serialize + DeserializeOwned>(position: T) -> String {
+    OpaqueCursor(position).encode_cursor()
+}
+
+/// Keyset position from an `after` argument
+pub fn decode_cursor<T: Serialize + DeserializeOwned>(cursor: Option<&str>) -> Result<Option<T>> {
+    cursor
+        .map(|cursor| {
+            OpaqueCursor::<T>::decode_cursor(cursor)
+                .map(|cursor| cursor.0)
+                .map_err(|_| GraphQLError::new("Invalid cursor"))
+        })
+        .transpose()
+}
+
+/// Page of an in-memory window following `after`
+///
+/// Used for transformed data, which is computed over the whole window.
+pub fn page_after(
+    points: &[models::DataPoint],
+    after: Option<DataPointPosition>,
+    first: usize,
+) -> Page<models::DataPoint> {
+    let mut following: Vec<&models::DataPoint> = points
+        .iter()
+        .filter(|point| after.is_none_or(|after| DataPointPosition::from(*point) > after))
+        .collect();
+    following.sort_by_key(|point| DataPointPosition::from(*point));
+
+    Page {
+        has_next_page: following.len() > first,
+        items: following.into_iter().take(first).cloned().collect(),
+    }
+}
+
+/// Connection over a series' data points
+///
+/// Untransformed pages are read from the database by keyset. Transformations
+/// need the whole window (year-over-year looks back a year), so the window is
+/// loaded through the data point cache, transformed, and paged in memory.
+pub async fn data_point_connection(
+    pool: &DatabasePool,
+    params: models::DataQueryParams,
+    transformation: Option<DataTransformationType>,
+    first: Option<i32>,
+    after: Option<String>,
+) -> Result<DataPointConnection> {
+    let first = page_size(
+        first,
+        DEFAULT_DATA_POINT_PAGE_SIZE,
+        MAX_DATA_POINT_PAGE_SIZE,
+    )?;
+    let after = decode_cursor::<DataPointPosition>(after.as_deref())?;
+    let params = models::DataQueryParams {
+        limit: None,
+        offset: None,
+        ..params
+    };
+
+    let total = series_service::count_series_data(pool, &params).await?;
+
+    let page = match transformation {
+        Some(transformation) => {
+            let cache_key = DataPointCacheKey::from_params(&params);
+            let window = shared_data_point_cache()
+                .get_or_load(cache_key.clone(), || {
+                    series_service::get_series_data_window(pool, &params)
+                })
+                .await?;
+            let transformed = crate::graphql::query::apply_cached_transformation(
+                cache_key,
+                &window,
+                transformation,
+            )
+            .await?;
+            page_after(&transformed, after, first)
+        }
+        None => series_service::get_series_data_page(pool, &params, after, first).await?,
+    };
+
+    Ok(DataPointConnection::from_page(page, after.is_some(), total))
+}
+
+/// Connection over the series matching a filter
+pub async fn series_connection(
+    pool: &DatabasePool,
+    params: SeriesSearchParams,
+    first: Option<i32>,
+    after: Option<String>,
+) -> Result<SeriesConnection> {
+    let first = page_size(first, DEFAULT_SERIES_PAGE_SIZE, MAX_SERIES_PAGE_SIZE)?;
+    let after = decode_cursor::<SeriesPosition>(after.as_deref())?;
+
+    let total = series_service::count_series(pool, &params).await?;
+    let page = series_service::list_series_page(pool, &params, after, first).await?;
+
+    Ok(SeriesConnection::from_page(page, after.is_some(), total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn point(day: u32, revision_day: u32) -> models::DataPoint {
+        models::DataPoint {
+            id: Uuid::new_v4(),
+            series_id: Uuid::nil(),
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            value: Some(BigDecimal::from(day)),
+            revision_date: NaiveDate::from_ymd_opt(2024, 2, revision_day).unwrap(),
+            is_original_release: revision_day == 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_page_after_walks_window_without_gaps() {
+        // REQUIREMENT: Large data point queries are paginated with stable cursors
+        // PURPOSE: Verify paging a window by cursor visits every point exactly once
+        // This covers revisions of the same date that straddle a page boundary
+
+        let window = vec![
+            point(3, 1),
+            point(1, 1),
+            point(1, 2),
+            point(2, 1),
+            point(2, 2),
+        ];
+
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = page_after(&window, after, 2);
+            assert!(page.items.len() <= 2);
+            seen.extend(page.items.iter().map(|p| (p.date, p.revision_date)));
+            if !page.has_next_page {
+                break;
+            }
+            after = page.items.last().map(DataPointPosition::from);
+        }
+
+        let mut expected: Vec<_> = window.iter().map(|p| (p.date, p.revision_date)).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn test_cursor_round_trip_and_invalid_cursor() {
+        // REQUIREMENT: Cursors are opaque to clients
+        // PURPOSE: Verify cursors decode to the position they were made from and garbage is rejected
+
+        let position = DataPointPosition::from(&point(5, 1));
+        let cursor = encode_cursor(position);
+
+        assert_eq!(
+            decode_cursor::<DataPointPosition>(Some(&cursor)).unwrap(),
+            Some(position)
+        );
+        assert!(decode_cursor::<DataPointPosition>(Some("42")).is_err());
+        assert!(decode_cursor::<DataPointPosition>(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_page_size_clamps_first() {
+        assert_eq!(page_size(None, 50, 100).unwrap(), 50);
+        assert_eq!(page_size(Some(500), 50, 100).unwrap(), 100);
+        assert_eq!(page_size(Some(0), 50, 100).unwrap(), 0);
+        assert!(page_size(Some(-1), 50, 100).is_err());
+    }
+}
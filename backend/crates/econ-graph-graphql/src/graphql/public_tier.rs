@@ -0,0 +1,235 @@
+//! # Public API Tier
+//!
+//! Anonymous requests get trends, not exact figures: data point values are
+//! rounded to a number of significant digits and long point lists can be
+//! thinned to every n-th observation. Signed-in users get exact, complete data.
+//!
+//! The policy is read once from the environment:
+//!
+//! - `PUBLIC_API_SIGNIFICANT_DIGITS`: significant digits kept for anonymous
+//!   users (default 3; `0` keeps exact values)
+//! - `PUBLIC_API_SAMPLE_EVERY`: keep every n-th data point for anonymous users
+//!   (default 1, i.e. no sampling)
+//!
+//! Requests executed without a [`GraphQLContext`] are served as anonymous,
+//! so a caller that forgets to attach one never receives exact values.
+
+use std::sync::OnceLock;
+
+use crate::imports::*;
+use crate::types::{DataPointConnection, DataPointType};
+
+/// Significant digits kept for anonymous users unless configured otherwise
+pub const DEFAULT_PUBLIC_SIGNIFICANT_DIGITS: u64 = 3;
+
+/// Which access tier a request is served under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiTier {
+    /// Anonymous request
+    Public,
+    /// Signed-in user
+    Authenticated,
+}
+
+impl ApiTier {
+    /// Tier of the request being resolved
+    pub fn of(ctx: &Context<'_>) -> Self {
+        match ctx.data_opt::<Arc<GraphQLContext>>() {
+            Some(context) if context.user.is_some() => ApiTier::Authenticated,
+            _ => ApiTier::Public,
+        }
+    }
+}
+
+/// How data is coarsened for the public tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicTierPolicy {
+    /// Significant digits kept in values; `None` keeps exact values
+    pub significant_digits: Option<u64>,
+    /// Keep every n-th data point; 1 keeps all of them
+    pub sample_every: usize,
+}
+
+impl Default for PublicTierPolicy {
+    fn default() -> Self {
+        Self {
+            significant_digits: Some(DEFAULT_PUBLIC_SIGNIFICANT_DIGITS),
+            sample_every: 1,
+        }
+    }
+}
+
+impl PublicTierPolicy {
+    /// Policy that leaves data untouched
+    pub fn exact() -> Self {
+        Self {
+            significant_digits: None,
+            sample_every: 1,
+        }
+    }
+
+    /// Policy from `PUBLIC_API_SIGNIFICANT_DIGITS` and `PUBLIC_API_SAMPLE_EVERY`
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var("PUBLIC_API_SIGNIFICANT_DIGITS")
+                .ok()
+                .as_deref(),
+            std::env::var("PUBLIC_API_SAMPLE_EVERY").ok().as_deref(),
+        )
+    }
+
+    /// Policy from raw setting values; unparsable values fall back to the defaults
+    pub fn from_values(significant_digits: Option<&str>, sample_every: Option<&str>) -> Self {
+        let default = Self::default();
+        let significant_digits = match significant_digits.map(|value| value.trim().parse::<u64>()) {
+            Some(Ok(0)) => None,
+            Some(Ok(digits)) => Some(digits),
+            _ => default.significant_digits,
+        };
+        let sample_every = sample_every
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|every| *every > 0)
+            .unwrap_or(default.sample_every);
+
+        Self {
+            significant_digits,
+            sample_every,
+        }
+    }
+
+    /// Policy that applies to the request being resolved
+    ///
+    /// Schemas can carry their own policy as data; otherwise the process-wide
+    /// policy from the environment is used.
+    pub fn for_request(ctx: &Context<'_>) -> Self {
+        match ApiTier::of(ctx) {
+            ApiTier::Authenticated => Self::exact(),
+            ApiTier::Public => ctx
+                .data_opt::<PublicTierPolicy>()
+                .copied()
+                .unwrap_or_else(|| *shared_public_tier_policy()),
+        }
+    }
+
+    /// Value as the tier may see it
+    pub fn value(&self, value: &BigDecimal) -> BigDecimal {
+        match self.significant_digits {
+            Some(digits) => value.with_prec(digits).normalized(),
+            None => value.clone(),
+        }
+    }
+
+    /// Computed floating point value as the tier may see it
+    pub fn float_value(&self, value: f64) -> f64 {
+        match self.significant_digits {
+            Some(digits) if value.is_finite() && digits > 0 => {
+                format!("{:.*e}", digits as usize - 1, value)
+                    .parse()
+                    .unwrap_or(value)
+            }
+            _ => value,
+        }
+    }
+
+    /// Optional value as the tier may see it
+    pub fn optional_value(&self, value: &Option<BigDecimal>) -> Option<BigDecimal> {
+        value.as_ref().map(|value| self.value(value))
+    }
+
+    /// Every n-th item of `items`, always keeping the first
+    pub fn sample<T>(&self, items: Vec<T>) -> Vec<T> {
+        if self.sample_every <= 1 {
+            return items;
+        }
+        items.into_iter().step_by(self.sample_every).collect()
+    }
+
+    /// Data points the tier may see
+    pub fn data_points(&self, points: Vec<DataPointType>) -> Vec<DataPointType> {
+        self.sample(points)
+    }
+
+    /// Connection page the tier may see
+    ///
+    /// Sampled edges keep their own cursors and the page info is left as
+    /// computed for the full page, so paging continues where the page ended.
+    pub fn data_point_connection(
+        &self,
+        mut connection: DataPointConnection,
+    ) -> DataPointConnection {
+        if self.sample_every > 1 {
+            connection.edges = self.sample(connection.edges);
+            connection.nodes = self.sample(connection.nodes);
+        }
+        connection
+    }
+}
+
+/// Process-wide public tier policy, read from the environment on first use
+pub fn shared_public_tier_policy() -> &'static PublicTierPolicy {
+    static POLICY: OnceLock<PublicTierPolicy> = OnceLock::new();
+    POLICY.get_or_init(PublicTierPolicy::from_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_policy_from_values() {
+        // REQUIREMENT: Operators configure how much precision anonymous users get
+        // PURPOSE: Verify settings parse, 0 digits means exact, and bad values fall back to defaults
+        // This ensures a typo in configuration never exposes exact values or breaks requests
+
+        assert_eq!(
+            PublicTierPolicy::from_values(None, None),
+            PublicTierPolicy::default()
+        );
+        assert_eq!(
+            PublicTierPolicy::from_values(Some("2"), Some("7")),
+            PublicTierPolicy {
+                significant_digits: Some(2),
+                sample_every: 7,
+            }
+        );
+        assert_eq!(
+            PublicTierPolicy::from_values(Some("0"), None).significant_digits,
+            None
+        );
+        assert_eq!(
+            PublicTierPolicy::from_values(Some("many"), Some("0")),
+            PublicTierPolicy::default()
+        );
+    }
+
+    #[test]
+    fn test_policy_rounds_and_samples() {
+        // REQUIREMENT: Anonymous users see trends without full precision
+        // PURPOSE: Verify values keep only the configured significant digits and lists are thinned
+        // This ensures the public tier shows the shape of a series but not its exact figures
+
+        let policy = PublicTierPolicy {
+            significant_digits: Some(3),
+            sample_every: 3,
+        };
+        assert_eq!(
+            policy.value(&BigDecimal::from_str("27360.123").unwrap()),
+            BigDecimal::from_str("27400").unwrap()
+        );
+        assert_eq!(
+            policy.value(&BigDecimal::from_str("0.0123456").unwrap()),
+            BigDecimal::from_str("0.0123").unwrap()
+        );
+        assert_eq!(policy.optional_value(&None), None);
+        assert_eq!(policy.float_value(27360.123), 27400.0);
+        assert_eq!(policy.float_value(-0.0123456), -0.0123);
+        assert_eq!(policy.sample((1..=7).collect()), vec![1, 4, 7]);
+
+        let exact = PublicTierPolicy::exact();
+        let value = BigDecimal::from_str("27360.123").unwrap();
+        assert_eq!(exact.value(&value), value);
+        assert_eq!(exact.float_value(27360.123), 27360.123);
+        assert_eq!(exact.sample(vec![1, 2, 3]), vec![1, 2, 3]);
+    }
+}
@@ -17,6 +17,19 @@
 //! - Error messages must be user-friendly and actionable
 //! - All resolvers must have comprehensive documentation
 
+use crate::graphql::data_access::{
+    may_read_source_by_name, require_series_access, require_source_access_by_id,
+    require_source_access_by_name,
+};
+use crate::graphql::education::{
+    LearningAchievementType, LearningModuleType, LearningPathType, LearningProgressType,
+};
+use crate::graphql::global_analysis::{CountryCorrelationType, LeadingIndicatorType};
+use crate::graphql::pagination::{
+    data_point_connection, decode_cursor, page_size, series_connection,
+    DEFAULT_AUDIT_LOG_PAGE_SIZE, MAX_AUDIT_LOG_PAGE_SIZE,
+};
+use crate::graphql::public_tier::PublicTierPolicy;
 use crate::imports::*;
 use crate::types::*;
 
@@ -31,11 +44,50 @@ impl Query {
         let series_uuid = Uuid::parse_str(&id)?;
 
         match series_service::get_series_by_id(&pool, series_uuid).await? {
-            Some(series) => Ok(Some(series.into())),
+            Some(series) => {
+                require_source_access_by_id(ctx, series.source_id).await?;
+                Ok(Some(series.into()))
+            }
             None => Ok(None),
         }
     }
 
+    /// Resolve a series referenced from another subgraph by its `id` key
+    #[graphql(entity)]
+    async fn find_series_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<Option<EconomicSeriesType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&id)?;
+
+        let series = series_service::get_series_by_id(pool, series_uuid).await?;
+        if let Some(series) = &series {
+            require_source_access_by_id(ctx, series.source_id).await?;
+        }
+
+        Ok(series.map(Into::into))
+    }
+
+    /// Resolve a data point referenced from another subgraph by its `id` key
+    #[graphql(entity)]
+    async fn find_data_point_by_id(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<Option<DataPointType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let data_point_uuid = Uuid::parse_str(&id)?;
+
+        let data_point = models::DataPoint::find_by_id(pool, data_point_uuid).await?;
+        if let Some(data_point) = &data_point {
+            require_series_access(ctx, data_point.series_id).await?;
+        }
+
+        Ok(data_point.map(Into::into))
+    }
+
     /// List economic series with filtering and pagination
     async fn series_list(
         &self,
@@ -45,47 +97,10 @@ impl Query {
     ) -> Result<SeriesConnection> {
         let pool = ctx.data::<DatabasePool>()?;
 
-        // Convert GraphQL inputs to service parameters
         let search_params = convert_series_filter_to_params(filter);
-        let series = series_service::list_series(&pool, search_params).await?;
-
-        // Apply pagination (simplified implementation)
         let pagination = pagination.unwrap_or_default();
-        let first = pagination.first.unwrap_or(50).min(100) as usize;
-        let after_index = pagination
-            .after
-            .and_then(|cursor| cursor.parse::<usize>().ok())
-            .unwrap_or(0);
-
-        let total_count = series.len();
-        let end_index = (after_index + first).min(total_count);
-        let page_series = if after_index < total_count {
-            series[after_index..end_index].to_vec()
-        } else {
-            Vec::new()
-        };
 
-        Ok(SeriesConnection {
-            nodes: page_series
-                .into_iter()
-                .map(EconomicSeriesType::from)
-                .collect(),
-            total_count: total_count as i32,
-            page_info: PageInfo {
-                has_next_page: end_index < total_count,
-                has_previous_page: after_index > 0,
-                start_cursor: if after_index > 0 {
-                    Some(after_index.to_string())
-                } else {
-                    None
-                },
-                end_cursor: if end_index < total_count {
-                    Some(end_index.to_string())
-                } else {
-                    None
-                },
-            },
-        })
+        series_connection(pool, search_params, pagination.first, pagination.after).await
     }
 
     /// Get a specific data source by ID
@@ -127,6 +142,9 @@ impl Query {
     }
 
     /// Get data points for a specific series with filtering and transformation
+    ///
+    /// Paginated with `first` (default 1000, at most 10000) and the `endCursor`
+    /// of the previous page as `after`.
     async fn series_data(
         &self,
         ctx: &Context<'_>,
@@ -138,43 +156,607 @@ impl Query {
     ) -> Result<DataPointConnection> {
         let pool = ctx.data::<DatabasePool>()?;
         let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
 
-        // Convert GraphQL inputs to service parameters
         let query_params = models::DataQueryParams {
             series_id: series_uuid,
             start_date: filter.as_ref().and_then(|f| f.start_date),
             end_date: filter.as_ref().and_then(|f| f.end_date),
             original_only: filter.as_ref().and_then(|f| f.original_only),
             latest_revision_only: filter.as_ref().and_then(|f| f.latest_revision_only),
-            limit: first.map(|f| f as i64),
-            offset: after.and_then(|cursor| cursor.parse::<i64>().ok()),
+            exclude_corrections: filter.as_ref().and_then(|f| f.exclude_corrections),
+            limit: None,
+            offset: None,
         };
 
-        let data_points = series_service::get_series_data(&pool, query_params).await?;
-        let total_count = data_points.len();
+        let connection =
+            data_point_connection(pool, query_params, transformation, first, after).await?;
+        Ok(PublicTierPolicy::for_request(ctx).data_point_connection(connection))
+    }
+
+    /// Data points of a series reduced to at most `maxPoints` for chart rendering
+    ///
+    /// Unless the filter says otherwise only the latest revision of each date
+    /// is used, since a chart plots one value per date.
+    async fn downsampled_data_points(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        max_points: i32,
+        #[graphql(default_with = "DownsamplingAlgorithmType::Lttb")]
+        algorithm: DownsamplingAlgorithmType,
+        filter: Option<DataFilterInput>,
+    ) -> Result<Vec<DataPointType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let query_params = models::DataQueryParams {
+            series_id: series_uuid,
+            start_date: filter.as_ref().and_then(|f| f.start_date),
+            end_date: filter.as_ref().and_then(|f| f.end_date),
+            original_only: filter.as_ref().and_then(|f| f.original_only),
+            latest_revision_only: Some(
+                filter
+                    .as_ref()
+                    .and_then(|f| f.latest_revision_only)
+                    .unwrap_or(true),
+            ),
+            exclude_corrections: filter.as_ref().and_then(|f| f.exclude_corrections),
+            limit: None,
+            offset: None,
+        };
+
+        let points = series_service::get_downsampled_series_data(
+            pool,
+            &query_params,
+            usize::try_from(max_points).unwrap_or(0),
+            algorithm.into(),
+        )
+        .await?;
+
+        Ok(PublicTierPolicy::for_request(ctx)
+            .data_points(points.into_iter().map(DataPointType::from).collect()))
+    }
+
+    /// A series aggregated to a lower frequency, e.g. daily to monthly
+    ///
+    /// Periods the data does not fully cover (the ends of the window, gaps)
+    /// are left out unless `includeIncomplete` is set, and flagged when included.
+    async fn resampled_data_points(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        frequency: ResampleFrequencyType,
+        #[graphql(default_with = "ResampleMethodType::Mean")] method: ResampleMethodType,
+        filter: Option<DataFilterInput>,
+        #[graphql(default)] include_incomplete: bool,
+    ) -> Result<Vec<ResampledDataPointType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let query_params = models::DataQueryParams {
+            series_id: series_uuid,
+            start_date: filter.as_ref().and_then(|f| f.start_date),
+            end_date: filter.as_ref().and_then(|f| f.end_date),
+            original_only: filter.as_ref().and_then(|f| f.original_only),
+            // One value per date, or sums would count every revision
+            latest_revision_only: Some(true),
+            exclude_corrections: filter.as_ref().and_then(|f| f.exclude_corrections),
+            limit: None,
+            offset: None,
+        };
+
+        let periods = series_service::get_resampled_series_data(
+            pool,
+            &query_params,
+            frequency.into(),
+            method.into(),
+            include_incomplete,
+        )
+        .await?;
+
+        let policy = PublicTierPolicy::for_request(ctx);
+        Ok(periods
+            .into_iter()
+            .map(|period| ResampledDataPointType::new(period, &policy))
+            .collect())
+    }
+
+    /// Monthly or yearly minimum, maximum, mean and last value of a series
+    ///
+    /// Served from rollups kept up to date as data is ingested, using the
+    /// latest revision of each observation. Dates filter on the period start.
+    async fn series_rollup(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        period: RollupPeriodType,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<SeriesRollupType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let rollups = models::SeriesRollup::for_series(
+            pool,
+            series_uuid,
+            period.into(),
+            start_date,
+            end_date,
+        )
+        .await?;
+
+        let policy = PublicTierPolicy::for_request(ctx);
+        Ok(rollups
+            .into_iter()
+            .map(|rollup| SeriesRollupType::new(rollup, &policy))
+            .collect())
+    }
+
+    /// Several series as they were known on `asOf`, aligned on one date index
+    ///
+    /// Each series uses the latest revision published on or before `asOf`. With
+    /// a `frequency`, series observed more often are resampled with `method`
+    /// and only fully covered periods are kept. At most 20 series.
+    async fn aligned_series(
+        &self,
+        ctx: &Context<'_>,
+        series_ids: Vec<ID>,
+        as_of: NaiveDate,
+        frequency: Option<ResampleFrequencyType>,
+        #[graphql(default_with = "ResampleMethodType::Mean")] method: ResampleMethodType,
+    ) -> Result<AlignedSeriesType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuids = series_ids
+            .iter()
+            .map(|id| Uuid::parse_str(id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for series_uuid in &series_uuids {
+            require_series_access(ctx, *series_uuid).await?;
+        }
+
+        let aligned = aligned_series_service::get_aligned_series(
+            pool,
+            &series_uuids,
+            as_of,
+            frequency.map(Into::into),
+            method.into(),
+        )
+        .await?;
+
+        Ok(AlignedSeriesType::new(
+            aligned,
+            &PublicTierPolicy::for_request(ctx),
+        ))
+    }
+
+    /// Manual corrections made to a series, most recent first
+    async fn data_point_corrections(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<Vec<DataPointCorrectionType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let corrections = models::DataPointCorrection::list_for_series(pool, series_uuid).await?;
+
+        Ok(corrections.into_iter().map(Into::into).collect())
+    }
+
+    /// Where a data point came from: source URL, crawl time and processing steps
+    async fn data_point_provenance(
+        &self,
+        ctx: &Context<'_>,
+        data_point_id: ID,
+    ) -> Result<Option<ProvenanceType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let data_point_uuid = Uuid::parse_str(&data_point_id)?;
+        if let Some(data_point) = models::DataPoint::find_by_id(pool, data_point_uuid).await? {
+            require_series_access(ctx, data_point.series_id).await?;
+        }
+
+        let provenance = provenance_service::data_point_provenance(pool, data_point_uuid).await?;
+
+        Ok(provenance.map(Into::into))
+    }
+
+    /// Where an SEC fact came from: the filing it was parsed from
+    async fn financial_line_item_provenance(
+        &self,
+        ctx: &Context<'_>,
+        line_item_id: ID,
+    ) -> Result<Option<ProvenanceType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let line_item_uuid = Uuid::parse_str(&line_item_id)?;
+
+        let provenance =
+            provenance_service::financial_line_item_provenance(pool, line_item_uuid).await?;
+        if let Some(provenance) = &provenance {
+            require_source_access_by_name(ctx, &provenance.source).await?;
+        }
+
+        Ok(provenance.map(Into::into))
+    }
+
+    /// Seasonally adjusted version of a monthly or quarterly series
+    async fn seasonally_adjusted(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<SeasonalAdjustmentType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let result = shared_seasonal_adjustment_service()
+            .seasonally_adjusted(pool, series_uuid)
+            .await?;
+
+        Ok(SeasonalAdjustmentType::new(
+            result.as_ref(),
+            &PublicTierPolicy::for_request(ctx),
+        ))
+    }
+
+    /// A monetary series converted into another currency with ECB reference rates
+    ///
+    /// Each observation uses the rate of its date, or the last rate before it.
+    /// The source currency is read from the series units unless given.
+    async fn convert_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+        target_currency: String,
+        source_currency: Option<String>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<CurrencyConversionType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        require_series_access(ctx, series_uuid).await?;
+
+        let conversion = currency_conversion_service::convert_series(
+            pool,
+            series_uuid,
+            &target_currency,
+            source_currency.as_deref(),
+            start_date,
+            end_date,
+        )
+        .await?;
+
+        Ok(CurrencyConversionType::new(
+            conversion,
+            &PublicTierPolicy::for_request(ctx),
+        ))
+    }
+
+    /// Stored country correlations for an indicator category, including rolling windows
+    async fn country_correlations(
+        &self,
+        ctx: &Context<'_>,
+        indicator_category: String,
+        country_id: Option<ID>,
+        min_correlation: Option<f64>,
+    ) -> Result<Vec<CountryCorrelationType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let country_uuid = country_id.map(|id| Uuid::parse_str(&id)).transpose()?;
+        for source in
+            GlobalAnalysisService::indicator_data_sources(pool, Some(&indicator_category)).await?
+        {
+            require_source_access_by_name(ctx, &source).await?;
+        }
+
+        let correlations = GlobalAnalysisService::get_country_correlations(
+            pool,
+            &indicator_category,
+            country_uuid,
+            min_correlation.unwrap_or(0.0),
+        )
+        .await?;
+
+        Ok(correlations.into_iter().map(Into::into).collect())
+    }
+
+    /// Latest value of a global indicator for every country, for heatmaps and choropleths
+    ///
+    /// Each country uses its latest value on or before `date`; countries without
+    /// one in the two years before, or whose data source the reader may not
+    /// read, are left out. Includes the min, max and quintile breaks of the
+    /// values for color scaling.
+    async fn country_indicator_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        indicator_code: String,
+        date: NaiveDate,
+    ) -> Result<CountryIndicatorSnapshotType> {
+        let pool = ctx.data::<DatabasePool>()?;
 
-        // Apply transformation if requested
-        let result_points = if let Some(transformation) = transformation {
-            // Apply the requested transformation to the data points
-            apply_data_transformation(data_points, transformation)
+        let snapshot =
+            country_snapshot_service::get_country_indicator_snapshot(pool, &indicator_code, date)
+                .await?;
+
+        let sources: std::collections::BTreeSet<&str> = snapshot
+            .countries
+            .iter()
+            .map(|country| country.data_source.as_str())
+            .collect();
+        let mut readable = std::collections::HashSet::new();
+        for source in sources {
+            if may_read_source_by_name(ctx, source).await? {
+                readable.insert(source);
+            }
+        }
+        let snapshot =
+            snapshot.retain_countries(|country| readable.contains(country.data_source.as_str()));
+
+        Ok(CountryIndicatorSnapshotType::new(
+            &snapshot,
+            &PublicTierPolicy::for_request(ctx),
+        ))
+    }
+
+    /// Stored lead/lag relationships between countries, strongest first
+    async fn leading_indicators(
+        &self,
+        ctx: &Context<'_>,
+        indicator_category: Option<String>,
+        country_id: Option<ID>,
+    ) -> Result<Vec<LeadingIndicatorType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let country_uuid = country_id.map(|id| Uuid::parse_str(&id)).transpose()?;
+        for source in
+            GlobalAnalysisService::indicator_data_sources(pool, indicator_category.as_deref())
                 .await?
+        {
+            require_source_access_by_name(ctx, &source).await?;
+        }
+
+        let indicators = GlobalAnalysisService::get_leading_indicators(
+            pool,
+            indicator_category.as_deref(),
+            country_uuid,
+        )
+        .await?;
+
+        Ok(indicators.into_iter().map(Into::into).collect())
+    }
+
+    /// Compare a company's financial ratio with its SIC and sector peers
+    async fn company_benchmark(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        ratio_name: String,
+    ) -> Result<CompanyBenchmarkType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let benchmark =
+            BenchmarkingService::company_benchmark(pool, company_uuid, ratio_name.trim()).await?;
+
+        Ok(benchmark.into())
+    }
+
+    /// A company's financial ratio across fiscal periods, oldest first, for charting
+    async fn ratio_history(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        ratio_name: String,
+    ) -> Result<Vec<RatioHistoryPointType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let history =
+            BenchmarkingService::ratio_history(pool, company_uuid, ratio_name.trim()).await?;
+
+        Ok(history.into_iter().map(Into::into).collect())
+    }
+
+    /// A company's percentile within its sector for a financial ratio, per fiscal period
+    async fn ratio_peer_percentile(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        ratio_name: String,
+    ) -> Result<RatioPeerPercentileType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let percentile =
+            BenchmarkingService::ratio_peer_percentile(pool, company_uuid, ratio_name.trim())
+                .await?;
+
+        Ok(percentile.into())
+    }
+
+    /// Calculation linkbase inconsistencies found in a financial statement's XBRL filing
+    async fn xbrl_validation_report(
+        &self,
+        ctx: &Context<'_>,
+        statement_id: ID,
+    ) -> Result<ValidationReportType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let statement_uuid = Uuid::parse_str(&statement_id)?;
+
+        let discrepancies =
+            XbrlCalculationDiscrepancy::find_by_statement(pool, statement_uuid).await?;
+
+        Ok(ValidationReportType::new(statement_uuid, discrepancies))
+    }
+
+    /// A concept of a financial statement broken down by the members of an XBRL axis
+    ///
+    /// One breakdown per reporting period in the filing, latest first, e.g.
+    /// revenue by business segment for each fiscal year a 10-K compares.
+    async fn segment_breakdown(
+        &self,
+        ctx: &Context<'_>,
+        statement_id: ID,
+        /// Taxonomy concept, e.g. "us-gaap:Revenues"
+        concept: String,
+        /// Dimension, e.g. "us-gaap:StatementBusinessSegmentsAxis" or "srt:StatementGeographicalAxis"
+        axis: String,
+    ) -> Result<Vec<SegmentBreakdownType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let statement_uuid = Uuid::parse_str(&statement_id)?;
+
+        let breakdowns =
+            SegmentBreakdown::for_statement(pool, statement_uuid, concept.trim(), axis.trim())
+                .await?;
+
+        Ok(breakdowns.into_iter().map(Into::into).collect())
+    }
+
+    /// Latest processed filings of a company with their company-level XBRL facts
+    ///
+    /// Latest period first; at most 20 filings.
+    async fn company_financials(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        #[graphql(default = 4)] limit: i32,
+    ) -> Result<Vec<FinancialStatementType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let financials = CompanyFinancials::for_company(pool, company_uuid, limit as i64).await?;
+
+        Ok(financials.into_iter().map(Into::into).collect())
+    }
+
+    /// Form 4 insider transactions of a company, newest first
+    async fn insider_transactions(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        /// Only these SEC transaction codes, e.g. ["P", "S"]
+        transaction_codes: Option<Vec<String>>,
+        insider_cik: Option<String>,
+        #[graphql(default = 100)] limit: i32,
+    ) -> Result<Vec<InsiderTransactionType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let filter = InsiderTransactionFilter {
+            start_date,
+            end_date,
+            transaction_codes: transaction_codes
+                .unwrap_or_default()
                 .into_iter()
-                .map(DataPointType::from)
-                .collect()
-        } else {
-            data_points.into_iter().map(DataPointType::from).collect()
+                .map(|code| code.trim().to_uppercase())
+                .collect(),
+            insider_cik,
         };
+        let transactions =
+            InsiderTransaction::find_for_company(pool, company_uuid, &filter, limit as i64).await?;
 
-        Ok(DataPointConnection {
-            nodes: result_points,
-            total_count: total_count as i32,
-            page_info: PageInfo {
-                has_next_page: false, // Simplified - implement proper pagination
-                has_previous_page: false,
-                start_cursor: None,
-                end_cursor: None,
-            },
-        })
+        Ok(transactions.into_iter().map(Into::into).collect())
+    }
+
+    /// Insider buying and selling of a company; covers the last 90 days by default
+    async fn insider_activity(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<InsiderActivityType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let end_date = end_date.unwrap_or_else(|| Utc::now().date_naive());
+        let start_date = start_date.unwrap_or(end_date - chrono::Duration::days(90));
+        if start_date > end_date {
+            return Err(GraphQLError::new("startDate must not be after endDate"));
+        }
+
+        let summary =
+            InsiderActivitySummary::for_company(pool, company_uuid, start_date, end_date).await?;
+
+        Ok(InsiderActivityType::new(
+            company_uuid,
+            start_date,
+            end_date,
+            summary,
+        ))
+    }
+
+    /// Largest 13F institutional holders of a company; the latest reported quarter by default
+    async fn top_institutional_holders(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        /// Quarter end, e.g. 2024-06-30
+        report_period: Option<NaiveDate>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<InstitutionalHoldersType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+
+        let report_period = institutional_report_period(pool, company_uuid, report_period).await?;
+        let positions = match report_period {
+            Some(period) => {
+                InstitutionalHolding::positions_for_company(pool, company_uuid, period).await?
+            }
+            None => Vec::new(),
+        };
+        let limit = limit.clamp(1, models::MAX_INSTITUTIONAL_RESULTS as i32) as usize;
+
+        Ok(InstitutionalHoldersType::new(
+            company_uuid,
+            report_period,
+            positions,
+            limit,
+        ))
+    }
+
+    /// 13F holders of a company that opened, changed or closed a position since the previous quarter
+    async fn institutional_position_changes(
+        &self,
+        ctx: &Context<'_>,
+        company_id: ID,
+        /// Quarter end, e.g. 2024-06-30; the latest reported quarter by default
+        report_period: Option<NaiveDate>,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<InstitutionalPositionChangesType> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let company_uuid = Uuid::parse_str(&company_id)?;
+        let limit = limit.clamp(1, models::MAX_INSTITUTIONAL_RESULTS as i32) as usize;
+
+        let Some(period) = institutional_report_period(pool, company_uuid, report_period).await?
+        else {
+            return Ok(InstitutionalPositionChangesType::new(
+                company_uuid,
+                None,
+                None,
+                Vec::new(),
+                limit,
+            ));
+        };
+        let previous_period = models::previous_quarter_end(period);
+
+        let current =
+            InstitutionalHolding::positions_for_company(pool, company_uuid, period).await?;
+        let previous =
+            InstitutionalHolding::positions_for_company(pool, company_uuid, previous_period)
+                .await?;
+
+        Ok(InstitutionalPositionChangesType::new(
+            company_uuid,
+            Some(period),
+            Some(previous_period),
+            models::compare_positions(&previous, &current),
+            limit,
+        ))
     }
 
     /// Get crawler and queue statistics for monitoring
@@ -225,25 +807,149 @@ impl Query {
 
         let stats = queue_service::get_queue_statistics(&pool).await?;
 
-        Ok(QueueStatisticsType {
-            total_items: stats.total_items as i32,
-            pending_items: stats.pending_items as i32,
-            processing_items: stats.processing_items as i32,
-            completed_items: stats.completed_items as i32,
-            failed_items: stats.failed_items as i32,
-            retrying_items: stats.retrying_items as i32,
-            oldest_pending: stats.oldest_pending,
-            average_processing_time: stats.average_processing_time,
+        Ok(stats.into())
+    }
+
+    /// List crawl queue items in processing order, with optional filters (admin only)
+    async fn crawl_queue_items(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<CrawlQueueFilterInput>,
+        #[graphql(default = 50)] limit: i32,
+        #[graphql(default = 0)] offset: i32,
+    ) -> Result<CrawlQueueItemPageType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let filter = CrawlQueueFilter::from(filter.unwrap_or_default());
+
+        let items = queue_service::list_queue_items(
+            pool,
+            &filter,
+            i64::from(limit.clamp(1, 500)),
+            i64::from(offset.max(0)),
+        )
+        .await?;
+        let total_count = queue_service::count_queue_items(pool, &filter).await?;
+
+        Ok(CrawlQueueItemPageType {
+            items: items.into_iter().map(CrawlQueueItemType::from).collect(),
+            total_count: total_count as i32,
         })
     }
 
+    /// Current crawl queue statistics and depth per source (admin only)
+    async fn crawl_queue_snapshot(&self, ctx: &Context<'_>) -> Result<CrawlQueueSnapshotType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        CrawlQueueSnapshotType::load(pool).await
+    }
+
+    /// Series, data point and coverage totals of the catalog (admin only)
+    ///
+    /// Served from statistics maintained on ingestion, so it never scans the
+    /// series or their data points.
+    async fn catalog_statistics(&self, ctx: &Context<'_>) -> Result<CatalogStatisticsType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        Ok(CatalogStatistics::load(pool).await?.into())
+    }
+
+    /// Series and data point mutations after the `since` sequence, oldest first (admin only)
+    ///
+    /// Consumers keep the `nextSince` of each page and pass it back to tail
+    /// the feed; services without a GraphQL session can long-poll `/changes`.
+    async fn changes(
+        &self,
+        ctx: &Context<'_>,
+        since: i64,
+        #[graphql(default = 500)] limit: i32,
+    ) -> Result<ChangeFeedPageType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        Ok(ChangeFeedEntry::since(pool, since, i64::from(limit))
+            .await?
+            .into())
+    }
+
+    /// Today's crawl quotas and usage of every data source (admin only)
+    async fn data_source_quotas(&self, ctx: &Context<'_>) -> Result<Vec<DataSourceQuotaType>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let statuses = quota_client::quota_statuses(pool).await?;
+        let mut key_usage = quota_client::key_usage(pool).await?;
+
+        Ok(statuses
+            .into_iter()
+            .map(|(source, status)| {
+                let (source_keys, other_keys) = key_usage
+                    .drain(..)
+                    .partition(|usage| usage.data_source_id == source.id);
+                key_usage = other_keys;
+                (source, status, source_keys).into()
+            })
+            .collect())
+    }
+
+    /// List crawl queue items that exhausted their retries (admin only)
+    async fn failed_items(
+        &self,
+        ctx: &Context<'_>,
+        source: Option<String>,
+        #[graphql(default = 50)] limit: i32,
+        #[graphql(default = 0)] offset: i32,
+    ) -> Result<Vec<CrawlQueueItemType>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let items = queue_service::list_dead_letter_items(
+            pool,
+            source.as_deref(),
+            i64::from(limit.clamp(1, 500)),
+            i64::from(offset.max(0)),
+        )
+        .await?;
+
+        Ok(items.into_iter().map(CrawlQueueItemType::from).collect())
+    }
+
+    /// Crawl success rate, response times, freshness and errors per data source (admin only)
+    ///
+    /// Covers the `windowHours` up to now. Results are cached for a minute.
+    async fn crawl_analytics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 24)] window_hours: i32,
+        source_id: Option<ID>,
+    ) -> Result<CrawlAnalyticsType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_id = source_id.map(|id| Uuid::parse_str(&id)).transpose()?;
+
+        let (since, until) = crawl_analytics_service::analytics_window(i64::from(window_hours))?;
+        let report =
+            crawl_analytics_service::get_crawl_analytics(pool, since, until, source_id).await?;
+
+        Ok(CrawlAnalyticsType::from(report.as_ref()))
+    }
+
     /// Search economic series using full-text search with spelling correction
+    ///
+    /// `geography` (e.g. "California") and `topic` (a catalog category or tag)
+    /// filter on catalog metadata, which is available for enriched FRED and BLS series.
+    /// `highlights` mark the matched words; `facets` count all matches per source,
+    /// frequency, units and geography.
     async fn search_series(
         &self,
         ctx: &Context<'_>,
         query: String,
         source: Option<String>,
         frequency: Option<SeriesFrequencyType>,
+        geography: Option<String>,
+        topic: Option<String>,
         first: Option<i32>,
         after: Option<String>,
     ) -> Result<SearchResult> {
@@ -262,22 +968,64 @@ impl Query {
             offset: after.and_then(|cursor| cursor.parse::<i32>().ok()),
             source_id: source.and_then(|s| uuid::Uuid::parse_str(&s).ok()),
             frequency: frequency.map(|f| format!("{:?}", f)),
+            geography,
+            topic,
             include_inactive: Some(false),
             sort_by: Some(SearchSortOrder::Relevance),
         };
 
-        let results = search_service.search_series(&search_params).await?;
+        // Facets need a second query over every match, so skip it unless selected
+        let (results, facets) = if ctx.look_ahead().field("facets").exists() {
+            let (results, facets) = search_service
+                .search_series_with_facets(&search_params)
+                .await?;
+            (results, SearchFacetsType::from(facets))
+        } else {
+            let results = search_service.search_series(&search_params).await?;
+            (results, SearchFacetsType::default())
+        };
         let took_ms = start_time.elapsed().as_millis() as i32;
         let total_count = results.len() as i32;
+        let highlights = results.iter().map(SeriesHighlightType::from).collect();
 
         Ok(SearchResult {
             series: results.into_iter().map(EconomicSeriesType::from).collect(),
+            highlights,
+            facets,
             total_count,
             query,
             took_ms,
         })
     }
 
+    /// Search series and companies, ranked by relevance
+    ///
+    /// Queries accept websearch syntax ("quoted phrases", -excluded, OR) and are
+    /// expanded with known synonyms. A query that matches nothing is retried with
+    /// misspelled words corrected, reported in `didYouMean`.
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<SearchResultsType> {
+        let start_time = std::time::Instant::now();
+        let pool = ctx.data::<DatabasePool>()?;
+        let search_service = SearchService::new(Arc::new(pool.clone()));
+
+        let search_params = search::SearchParams {
+            limit: Some(limit),
+            ..search::SearchParams::simple(&query)
+        };
+        let results = search_service.search(&search_params).await?;
+
+        Ok(SearchResultsType::new(
+            query,
+            results,
+            start_time.elapsed().as_millis() as i32,
+        ))
+    }
+
     /// Get search suggestions for partial queries
     async fn search_suggestions(
         &self,
@@ -341,6 +1089,71 @@ impl Query {
             .collect())
     }
 
+    /// Annotation assignments waiting on the current user, earliest due first
+    async fn my_pending_annotation_reviews(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<AnnotationAssignmentType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let assignments = AnnotationWorkflowService::new(pool.clone())
+            .pending_reviews(user.id, limit as i64)
+            .await?;
+        Ok(assignments
+            .into_iter()
+            .map(AnnotationAssignmentType::from)
+            .collect())
+    }
+
+    /// The current user's notifications, newest first
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] unread_only: bool,
+        #[graphql(default = 50)] limit: i32,
+        #[graphql(default = 0)] offset: i32,
+    ) -> Result<Vec<NotificationType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let notifications = NotificationService::new(pool.clone())
+            .list_for_user(
+                user.id,
+                unread_only,
+                i64::from(limit.clamp(1, 200)),
+                i64::from(offset.max(0)),
+            )
+            .await?;
+
+        Ok(notifications
+            .into_iter()
+            .map(NotificationType::from)
+            .collect())
+    }
+
+    /// Number of unread notifications for the current user
+    async fn unread_notification_count(&self, ctx: &Context<'_>) -> Result<i32> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let count = NotificationService::new(pool.clone())
+            .unread_count(user.id)
+            .await?;
+
+        Ok(count as i32)
+    }
+
+    /// Which emails the current user receives
+    async fn email_preferences(&self, ctx: &Context<'_>) -> Result<EmailPreferencesType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let preferences = EmailPreferences::for_user(pool, user.id).await?;
+        Ok(EmailPreferencesType::from(preferences))
+    }
+
     /// Get collaborators for a specific chart
     async fn chart_collaborators(
         &self,
@@ -358,6 +1171,356 @@ impl Query {
             .collect())
     }
 
+    /// Get the organizations the current user belongs to
+    async fn my_organizations(&self, ctx: &Context<'_>) -> Result<Vec<OrganizationType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let organizations = Organization::find_for_user(pool, user.id).await?;
+        Ok(organizations
+            .into_iter()
+            .map(|(organization, membership)| {
+                let mut organization = OrganizationType::from(organization);
+                organization.viewer_role = Some(membership.role);
+                organization
+            })
+            .collect())
+    }
+
+    /// Get members of an organization (members only)
+    async fn organization_members(
+        &self,
+        ctx: &Context<'_>,
+        organization_id: ID,
+    ) -> Result<Vec<OrganizationMemberType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_uuid = uuid::Uuid::parse_str(&organization_id)?;
+
+        OrganizationAccess::new(pool.clone())
+            .require_role(organization_uuid, user.id, OrganizationRole::Viewer)
+            .await?;
+
+        let members = OrganizationMember::list_for_organization(pool, organization_uuid).await?;
+        Ok(members
+            .into_iter()
+            .map(OrganizationMemberType::from)
+            .collect())
+    }
+
+    /// Get charts shared with an organization (members only)
+    ///
+    /// Each share's `chart` field loads the chart itself.
+    async fn organization_charts(
+        &self,
+        ctx: &Context<'_>,
+        organization_id: ID,
+    ) -> Result<Vec<OrganizationChartShareType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let organization_uuid = uuid::Uuid::parse_str(&organization_id)?;
+
+        OrganizationAccess::new(pool.clone())
+            .require_role(organization_uuid, user.id, OrganizationRole::Viewer)
+            .await?;
+
+        let shares = OrganizationChartShare::list_for_organization(pool, organization_uuid).await?;
+        Ok(shares
+            .into_iter()
+            .map(OrganizationChartShareType::from)
+            .collect())
+    }
+
+    /// Get the current user's saved charts, most recently updated first
+    async fn my_saved_charts(&self, ctx: &Context<'_>) -> Result<Vec<SavedChartType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let charts = SavedChart::list_for_user(pool, user.id).await?;
+        Ok(charts.into_iter().map(SavedChartType::from).collect())
+    }
+
+    /// Get a saved chart the current user owns, collaborates on or can see
+    /// through an organization share
+    async fn saved_chart(&self, ctx: &Context<'_>, id: ID) -> Result<Option<SavedChartType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let chart_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let chart = viewable_saved_chart(pool, user.id, chart_uuid).await?;
+        Ok(chart.map(SavedChartType::from))
+    }
+
+    /// Get the learning paths of the educational content library
+    async fn learning_paths(&self, ctx: &Context<'_>) -> Result<Vec<LearningPathType>> {
+        current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let paths = EducationService::new(pool.clone()).learning_paths();
+        Ok(paths.into_iter().map(LearningPathType::from).collect())
+    }
+
+    /// Get a learning path by ID
+    async fn learning_path(&self, ctx: &Context<'_>, id: ID) -> Result<Option<LearningPathType>> {
+        current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let path_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let path = EducationService::new(pool.clone()).learning_path(path_uuid);
+        Ok(path.map(LearningPathType::from))
+    }
+
+    /// Get the content of a learning module by ID
+    async fn learning_module(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<Option<LearningModuleType>> {
+        current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let module_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let module = EducationService::new(pool.clone()).module(module_uuid);
+        Ok(module.map(LearningModuleType::from))
+    }
+
+    /// Get the current user's progress through learning modules, most recent first
+    async fn my_learning_progress(&self, ctx: &Context<'_>) -> Result<Vec<LearningProgressType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let progress = EducationService::new(pool.clone())
+            .progress_for_user(user.id)
+            .await?;
+        Ok(progress
+            .into_iter()
+            .map(LearningProgressType::from)
+            .collect())
+    }
+
+    /// Get the current user's learning achievements, most recent first
+    async fn my_learning_achievements(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<LearningAchievementType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let achievements = EducationService::new(pool.clone())
+            .achievements_for_user(user.id)
+            .await?;
+        Ok(achievements
+            .into_iter()
+            .map(LearningAchievementType::from)
+            .collect())
+    }
+
+    /// Get the current user's active sessions, most recently used first
+    async fn my_sessions(&self, ctx: &Context<'_>) -> Result<Vec<UserSessionType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let sessions = models::UserSession::list_active_for_user(pool, user.id).await?;
+        Ok(sessions.into_iter().map(UserSessionType::from).collect())
+    }
+
+    /// Get the current user's series alert rules, optionally for one series
+    async fn my_series_alert_rules(
+        &self,
+        ctx: &Context<'_>,
+        series_id: Option<ID>,
+    ) -> Result<Vec<SeriesAlertRuleType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = series_id.map(|id| uuid::Uuid::parse_str(&id)).transpose()?;
+
+        SeriesAlertRule::list_for_user(pool, user.id, series_uuid)
+            .await?
+            .into_iter()
+            .map(SeriesAlertRuleType::try_from)
+            .collect()
+    }
+
+    /// Get one of the current user's series alert rules
+    async fn series_alert_rule(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+    ) -> Result<Option<SeriesAlertRuleType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let rule_uuid = uuid::Uuid::parse_str(&id)?;
+
+        SeriesAlertRule::find_for_user(pool, rule_uuid, user.id)
+            .await?
+            .map(SeriesAlertRuleType::try_from)
+            .transpose()
+    }
+
+    /// Get webhooks, optionally for one data source or series (admin only)
+    async fn webhooks(
+        &self,
+        ctx: &Context<'_>,
+        source_id: Option<ID>,
+        series_id: Option<ID>,
+    ) -> Result<Vec<WebhookType>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = source_id.map(|id| uuid::Uuid::parse_str(&id)).transpose()?;
+        let series_uuid = series_id.map(|id| uuid::Uuid::parse_str(&id)).transpose()?;
+
+        Webhook::list(pool, source_uuid, series_uuid)
+            .await?
+            .into_iter()
+            .map(WebhookType::try_from)
+            .collect()
+    }
+
+    /// Get release calendars, optionally of one data source or series (admin only)
+    async fn release_calendars(
+        &self,
+        ctx: &Context<'_>,
+        source_id: Option<ID>,
+        series_id: Option<ID>,
+    ) -> Result<Vec<ReleaseCalendarType>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = source_id.map(|id| uuid::Uuid::parse_str(&id)).transpose()?;
+        let series_uuid = series_id.map(|id| uuid::Uuid::parse_str(&id)).transpose()?;
+
+        ReleaseCalendar::list(pool, source_uuid, series_uuid)
+            .await?
+            .into_iter()
+            .map(ReleaseCalendarType::try_from)
+            .collect()
+    }
+
+    /// Get a webhook's most recent deliveries, newest first (admin only)
+    async fn webhook_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        webhook_id: ID,
+        status: Option<WebhookDeliveryStatusType>,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<WebhookDeliveryType>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let webhook_uuid = uuid::Uuid::parse_str(&webhook_id)?;
+
+        let deliveries = WebhookDelivery::list_for_webhook(
+            pool,
+            webhook_uuid,
+            status.map(WebhookDeliveryStatus::from),
+            limit.clamp(1, 500) as i64,
+        )
+        .await?;
+
+        Ok(deliveries
+            .into_iter()
+            .map(WebhookDeliveryType::from)
+            .collect())
+    }
+
+    /// Get a derived series' formula and inputs
+    async fn derived_series(&self, ctx: &Context<'_>, id: ID) -> Result<Option<DerivedSeriesType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let derived_uuid = uuid::Uuid::parse_str(&id)?;
+
+        let Some(derived) = DerivedSeries::find_by_id(pool, derived_uuid).await? else {
+            return Ok(None);
+        };
+        let inputs = DerivedSeries::inputs(pool, &[derived.id]).await?;
+        Ok(Some(DerivedSeriesType::from((derived, inputs))))
+    }
+
+    /// Get the derived series the current user created, newest first
+    async fn my_derived_series(&self, ctx: &Context<'_>) -> Result<Vec<DerivedSeriesType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let derived = DerivedSeries::list_for_user(pool, user.id).await?;
+        let ids: Vec<uuid::Uuid> = derived.iter().map(|derived| derived.id).collect();
+        let mut inputs_by_series: std::collections::HashMap<uuid::Uuid, Vec<DerivedSeriesInput>> =
+            std::collections::HashMap::new();
+        for input in DerivedSeries::inputs(pool, &ids).await? {
+            inputs_by_series
+                .entry(input.derived_series_id)
+                .or_default()
+                .push(input);
+        }
+
+        Ok(derived
+            .into_iter()
+            .map(|derived| {
+                let inputs = inputs_by_series.remove(&derived.id).unwrap_or_default();
+                DerivedSeriesType::from((derived, inputs))
+            })
+            .collect())
+    }
+
+    /// Get one of the current user's export jobs, with a fresh download URL once completed
+    async fn export_job(&self, ctx: &Context<'_>, id: ID) -> Result<Option<ExportJobType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let job_uuid = uuid::Uuid::parse_str(&id)?;
+
+        match ExportJob::find_by_id(pool, job_uuid).await? {
+            Some(job) if job.user_id == user.id => Ok(Some(ExportJobType::try_from(job)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the current user's most recent export jobs, newest first
+    async fn my_export_jobs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<ExportJobType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        ExportJob::list_for_user(pool, user.id, limit.clamp(1, 100) as i64)
+            .await?
+            .into_iter()
+            .map(ExportJobType::try_from)
+            .collect()
+    }
+
+    /// Get series from other data sources that measure the same thing as a series
+    ///
+    /// Signed-in users see series from their favorite data sources first and
+    /// series from sources they hid last.
+    async fn equivalent_series(
+        &self,
+        ctx: &Context<'_>,
+        series_id: ID,
+    ) -> Result<Vec<EquivalentSeriesType>> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let series_uuid = Uuid::parse_str(&series_id)?;
+        let user_id = ctx
+            .data_opt::<Arc<GraphQLContext>>()
+            .and_then(|context| context.user.as_ref())
+            .map(|user| user.id);
+
+        let equivalents = SeriesLinkService::new(pool.clone())
+            .equivalent_series(series_uuid, user_id)
+            .await?;
+        Ok(equivalents
+            .into_iter()
+            .map(EquivalentSeriesType::from)
+            .collect())
+    }
+
+    /// Get the signed-in user, including their preferences
+    async fn me(&self, ctx: &Context<'_>) -> Result<UserType> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        // Read back from the database so changes made earlier in this session show
+        let user = models::User::get_by_id(pool, user.id).await?;
+        Ok(UserType::from(user))
+    }
+
     /// Get user information by ID
     async fn user(&self, ctx: &Context<'_>, user_id: ID) -> Result<Option<UserType>> {
         let pool = ctx.data::<DatabasePool>()?;
@@ -626,44 +1789,98 @@ impl Query {
         })
     }
 
-    /// Get security events (admin only)
+    /// Get security events, newest first (admin only)
     async fn security_events(
         &self,
         ctx: &Context<'_>,
-        _limit: Option<i32>,
+        filter: Option<SecurityEventFilterInput>,
+        #[graphql(default = 50)] limit: i32,
     ) -> Result<Vec<SecurityEventType>> {
-        // Require admin role
-        let _admin_user = require_admin(ctx)?;
-        let _context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
+        let _admin_user = can_view_security_events(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let filter: core_models::admin::SecurityEventFilter = filter.unwrap_or_default().into();
+        let events = core_models::admin::SecurityEvent::get_events(
+            pool,
+            &filter,
+            limit.clamp(1, 500) as i64,
+        )
+        .await?;
+
+        Ok(events.into_iter().map(SecurityEventType::from).collect())
+    }
+
+    /// Get a security event by ID (admin only)
+    async fn security_event(&self, ctx: &Context<'_>, id: ID) -> Result<SecurityEventType> {
+        let _admin_user = can_view_security_events(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let event_id = uuid::Uuid::parse_str(&id)?;
+
+        let event = core_models::admin::SecurityEvent::find_by_id(pool, event_id).await?;
 
-        // Get security events logic would go here
-        // For now, return empty vector
-        Ok(vec![])
+        Ok(event.into())
     }
 
-    /// Get audit logs (admin only)
+    /// Audit log entries, newest first (admin only)
     async fn audit_logs(
         &self,
         ctx: &Context<'_>,
-        _filter: Option<AuditLogFilterInput>,
-        _pagination: Option<PaginationInput>,
+        filter: Option<AuditLogFilterInput>,
+        pagination: Option<PaginationInput>,
     ) -> Result<AuditLogConnection> {
-        // Require admin role
         let _admin_user = require_admin(ctx)?;
-        let _context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
+        let pool = ctx.data::<DatabasePool>()?;
 
-        // Get audit logs logic would go here
-        // For now, return empty connection
-        Ok(AuditLogConnection {
-            nodes: vec![],
-            total_count: 0,
-            page_info: PageInfo {
-                has_next_page: false,
-                has_previous_page: false,
-                start_cursor: None,
-                end_cursor: None,
-            },
-        })
+        let filter = core_models::admin::AuditLogFilter::try_from(filter.unwrap_or_default())?;
+        let pagination = pagination.unwrap_or_default();
+        let first = page_size(
+            pagination.first,
+            DEFAULT_AUDIT_LOG_PAGE_SIZE,
+            MAX_AUDIT_LOG_PAGE_SIZE,
+        )?;
+        let after = decode_cursor::<AuditLogPosition>(pagination.after.as_deref())?;
+
+        let total_count = AuditLogService::count(pool, &filter).await?;
+        let page = AuditLogService::page(pool, &filter, after, first).await?;
+
+        Ok(AuditLogConnection::from_page(
+            page,
+            after.is_some(),
+            total_count,
+        ))
+    }
+
+    /// Audit log entries matching a filter as CSV, newest first (admin only)
+    ///
+    /// At most 50,000 entries are exported; `truncated` tells when more matched.
+    async fn export_audit_logs(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<AuditLogFilterInput>,
+    ) -> Result<AuditLogExportType> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+
+        let filter = core_models::admin::AuditLogFilter::try_from(filter.unwrap_or_default())?;
+        let export = AuditLogService::export_csv(pool, &filter).await?;
+
+        Ok(export.into())
+    }
+}
+
+/// Quarter a 13F query covers: the requested quarter end, or the latest
+/// quarter with reported holdings of the company
+async fn institutional_report_period(
+    pool: &DatabasePool,
+    company_id: Uuid,
+    report_period: Option<NaiveDate>,
+) -> Result<Option<NaiveDate>> {
+    match report_period {
+        Some(period) if !models::is_quarter_end(period) => Err(GraphQLError::new(
+            "reportPeriod must be a quarter end, e.g. 2024-06-30",
+        )),
+        Some(period) => Ok(Some(period)),
+        None => Ok(InstitutionalHolding::latest_period_for_company(pool, company_id).await?),
     }
 }
 
@@ -707,6 +1924,24 @@ impl Default for PaginationInput {
     }
 }
 
+/// Apply a transformation, reusing the cached result for the same window
+///
+/// `key` identifies the untransformed window `data_points` was read from.
+pub async fn apply_cached_transformation(
+    key: DataPointCacheKey,
+    data_points: &[econ_graph_core::models::DataPoint],
+    transformation: DataTransformationType,
+) -> Result<Arc<Vec<econ_graph_core::models::DataPoint>>> {
+    let cache = shared_data_point_cache();
+    let key = key.with_transformation(format!("{:?}", transformation));
+    if let Some(points) = cache.get(&key) {
+        return Ok(points);
+    }
+
+    let transformed = apply_data_transformation(data_points.to_vec(), transformation).await?;
+    Ok(cache.insert(key, transformed))
+}
+
 /// Apply data transformation to a series of data points
 pub async fn apply_data_transformation(
     data_points: Vec<econ_graph_core::models::DataPoint>,
@@ -896,6 +2131,118 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_member_loads_chart_shared_with_organization() {
+        // REQUIREMENT: Charts shared with an organization are accessible to its members
+        // PURPOSE: Verify a non-owner member can load a shared chart through savedChart and organizationCharts
+        // This ensures organization sharing works in the API, while outsiders still cannot read the chart
+
+        let container = econ_graph_core::test_utils::get_test_db().await;
+        let pool = container.pool().clone();
+        let schema = crate::graphql::schema::create_schema(pool.clone());
+
+        let mut users = Vec::new();
+        for name in ["owner", "member", "outsider"] {
+            users.push(
+                User::create_with_email(
+                    &pool,
+                    format!("{}-{}@example.com", name, Uuid::new_v4()),
+                    "password123".to_string(),
+                    name.to_string(),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        let (owner, member, outsider) = (&users[0], &users[1], &users[2]);
+
+        let chart = SavedChart::create(
+            &pool,
+            &NewSavedChart {
+                user_id: owner.id,
+                title: "Team Inflation Chart".to_string(),
+                description: None,
+                chart_config: serde_json::json!({}),
+                series_ids: Vec::new(),
+                transformations: serde_json::json!({}),
+                start_date: None,
+                end_date: None,
+                is_public: false,
+            },
+        )
+        .await
+        .unwrap();
+        let organization = Organization::create(
+            &pool,
+            &NewOrganization {
+                name: "Research Team".to_string(),
+                slug: format!("research-{}", Uuid::new_v4()),
+                description: None,
+                created_by: Some(owner.id),
+            },
+            owner.id,
+        )
+        .await
+        .unwrap();
+        OrganizationMember::upsert(
+            &pool,
+            &NewOrganizationMember {
+                organization_id: organization.id,
+                user_id: member.id,
+                role: OrganizationRole::Member.to_string(),
+                invited_by: Some(owner.id),
+            },
+        )
+        .await
+        .unwrap();
+        OrganizationChartShare::upsert(
+            &pool,
+            &NewOrganizationChartShare {
+                organization_id: organization.id,
+                chart_id: chart.id,
+                shared_by: Some(owner.id),
+                permission_level: "view".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let saved_chart_title = |user: &User| {
+            let request = async_graphql::Request::new(format!(
+                r#"{{ savedChart(id: "{}") {{ title }} }}"#,
+                chart.id
+            ))
+            .data(std::sync::Arc::new(GraphQLContext::new(
+                &pool,
+                Some(user.clone()),
+            )));
+            let schema = schema.clone();
+            async move {
+                let response = schema.execute(request).await;
+                assert!(response.errors.is_empty(), "{:?}", response.errors);
+                response.data.into_json().unwrap()["savedChart"]["title"].clone()
+            }
+        };
+
+        assert_eq!(saved_chart_title(member).await, "Team Inflation Chart");
+        assert!(saved_chart_title(outsider).await.is_null());
+
+        let request = async_graphql::Request::new(format!(
+            r#"{{ organizationCharts(organizationId: "{}") {{ chart {{ title }} }} }}"#,
+            organization.id
+        ))
+        .data(std::sync::Arc::new(GraphQLContext::new(
+            &pool,
+            Some(member.clone()),
+        )));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap()["organizationCharts"][0]["chart"]["title"],
+            "Team Inflation Chart"
+        );
+    }
+
     #[test]
     fn test_default_pagination() {
         // REQUIREMENT: GraphQL API should provide reasonable pagination defaults
@@ -916,4 +2263,144 @@ mod tests {
             "Default pagination should start from beginning"
         );
     }
+
+    #[tokio::test]
+    async fn test_country_snapshot_leaves_out_unreadable_sources() {
+        // REQUIREMENT: Licensed global indicator data is only served to readers of its data source
+        // PURPOSE: Verify countryIndicatorSnapshot drops countries from a premium source for anonymous readers and rescales
+        // This ensures a shared map cannot reveal premium values, directly or through its color scale
+
+        use econ_graph_core::models::{
+            GlobalEconomicIndicator, GlobalIndicatorData, NewCountry, NewGlobalEconomicIndicator,
+            NewGlobalIndicatorData,
+        };
+
+        let container = econ_graph_core::test_utils::get_test_db().await;
+        let pool = container.pool().clone();
+        let schema = crate::graphql::schema::create_schema(pool.clone());
+
+        let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+        let vendor = DataSource::create(
+            &pool,
+            NewDataSource {
+                name: format!("Licensed Vendor {}", &suffix[..8]),
+                description: None,
+                base_url: "https://vendor.example.com".to_string(),
+                api_key_required: true,
+                rate_limit_per_minute: 60,
+                is_visible: true,
+                is_enabled: true,
+                requires_admin_approval: false,
+                crawl_frequency_hours: 24,
+                api_documentation_url: None,
+                api_key_name: None,
+            },
+        )
+        .await
+        .unwrap();
+        DataSource::update(
+            &pool,
+            vendor.id,
+            UpdateDataSource {
+                access_policy: Some(DataAccessPolicy::Premium),
+                api_documentation_url: None,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let indicator_code = format!("SNAP_{}", &suffix[..8]);
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        for (index, (value, data_source)) in
+            [(1234, "Open Statistics"), (98765, vendor.name.as_str())]
+                .into_iter()
+                .enumerate()
+        {
+            let country = Country::upsert(
+                &pool,
+                &NewCountry {
+                    iso_code: format!("{}{}", &suffix[8..10], index),
+                    iso_code_2: format!("{}{}", &suffix[10..11], index),
+                    name: format!("Test Country {} {}", suffix, index),
+                    region: "Test Region".to_string(),
+                    sub_region: None,
+                    income_group: None,
+                    population: None,
+                    gdp_usd: None,
+                    gdp_per_capita_usd: None,
+                    latitude: None,
+                    longitude: None,
+                    currency_code: None,
+                    is_active: Some(true),
+                },
+            )
+            .await
+            .unwrap();
+            let indicator = GlobalEconomicIndicator::upsert(
+                &pool,
+                &NewGlobalEconomicIndicator {
+                    country_id: country.id,
+                    indicator_code: indicator_code.clone(),
+                    indicator_name: "Test indicator".to_string(),
+                    category: "TEST".to_string(),
+                    subcategory: None,
+                    unit: None,
+                    frequency: "Annual".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+            GlobalIndicatorData::upsert_batch(
+                &pool,
+                &[NewGlobalIndicatorData {
+                    indicator_id: indicator.id,
+                    date,
+                    value: Some(BigDecimal::from(value)),
+                    is_preliminary: Some(false),
+                    data_source: data_source.to_string(),
+                }],
+            )
+            .await
+            .unwrap();
+        }
+
+        let query = format!(
+            r#"{{ countryIndicatorSnapshot(indicatorCode: "{}", date: "2024-06-30") {{ countries {{ value }} maxValue }} }}"#,
+            indicator_code
+        );
+
+        let anonymous = schema.execute(query.as_str()).await;
+        assert!(anonymous.errors.is_empty(), "{:?}", anonymous.errors);
+        let snapshot = &anonymous.data.into_json().unwrap()["countryIndicatorSnapshot"];
+        assert_eq!(snapshot["countries"].as_array().unwrap().len(), 1);
+        // Anonymous readers get values rounded to the public tier's significant digits
+        assert_eq!(snapshot["countries"][0]["value"], serde_json::json!(1230.0));
+        assert_eq!(snapshot["maxValue"], serde_json::json!(1230.0));
+
+        let subscriber = User::create_with_email(
+            &pool,
+            format!("premium-{}@example.com", Uuid::new_v4()),
+            "password123".to_string(),
+            "Premium Reader".to_string(),
+        )
+        .await
+        .unwrap();
+        let subscriber =
+            User::set_subscription_tier(&pool, subscriber.id, SubscriptionTier::Premium)
+                .await
+                .unwrap();
+        let premium = schema
+            .execute(
+                async_graphql::Request::new(query.as_str()).data(std::sync::Arc::new(
+                    GraphQLContext::new(&pool, Some(subscriber)),
+                )),
+            )
+            .await;
+        assert!(premium.errors.is_empty(), "{:?}", premium.errors);
+        let snapshot = &premium.data.into_json().unwrap()["countryIndicatorSnapshot"];
+        assert_eq!(snapshot["countries"].as_array().unwrap().len(), 2);
+        assert_eq!(snapshot["countries"][0]["value"], serde_json::json!(1234.0));
+        assert_eq!(snapshot["maxValue"], serde_json::json!(98765.0));
+    }
 }
@@ -2,28 +2,38 @@
 //!
 //! Schema creation and configuration for the EconGraph GraphQL API.
 //! Provides the main entry point for GraphQL operations.
+//!
+//! The schema is an Apollo Federation v2 subgraph: `EconomicSeriesType` and
+//! `DataPoint` are entities keyed by `id`, so a gateway can compose them with
+//! other subgraphs (see [`federation_sdl`]).
 
-use async_graphql::{EmptySubscription, Schema};
+use async_graphql::{extensions::Tracing, SDLExportOptions, Schema};
 use std::sync::Arc;
 
+use crate::graphql::admin_audit::AdminAuditLog;
+use crate::graphql::cache_control::CacheControlHints;
 use crate::graphql::dataloaders::DataLoaders;
-use crate::graphql::{mutation::Mutation, query::Query};
-use crate::security::{SecurityConfig, SecurityMiddleware};
+use crate::graphql::explain::QueryExplainer;
+use crate::graphql::field_errors::FieldErrorReporter;
+use crate::graphql::{mutation::Mutation, query::Query, subscription::Subscription};
 use econ_graph_core::database::DatabasePool;
 
-/// GraphQL context containing shared resources
-#[derive(Clone)]
-pub struct GraphQLContext {
-    /// Database connection pool
-    pub pool: Arc<DatabasePool>,
-    /// DataLoaders for efficient N+1 query prevention
-    pub data_loaders: Arc<DataLoaders>,
-    /// Security middleware
-    pub security: Arc<SecurityMiddleware>,
-}
+/// The EconGraph GraphQL schema
+pub type AppSchema = Schema<Query, Mutation, Subscription>;
 
 /// Create a new GraphQL schema with the provided context
 ///
+/// The schema only holds shared resources. Per-request state (user, claims,
+/// DataLoaders, metrics) travels in a
+/// [`GraphQLContext`](crate::graphql::context::GraphQLContext) attached to each
+/// request; the schema-wide DataLoaders serve requests executed without one.
+///
+/// Responses carry the cache hints of their fields in the `cacheControl`
+/// extension; see [`cache_control`](crate::graphql::cache_control). Admin
+/// mutations without an audit entry of their own are logged by
+/// [`AdminAuditLog`]. Admins can ask for a description of the execution in the
+/// `explain` extension; see [`explain`](crate::graphql::explain).
+///
 /// # Parameters
 /// - `pool`: Database connection pool for data access
 ///
@@ -44,19 +54,17 @@ pub struct GraphQLContext {
 ///     Ok(())
 /// }
 /// ```
-pub fn create_schema(pool: DatabasePool) -> Schema<Query, Mutation, EmptySubscription> {
-    let pool_arc = Arc::new(pool.clone());
+pub fn create_schema(pool: DatabasePool) -> AppSchema {
     let data_loaders = Arc::new(DataLoaders::new(pool.clone()));
-    let security_config = SecurityConfig::default();
-    let security = Arc::new(SecurityMiddleware::new(security_config));
-    let context = GraphQLContext {
-        pool: pool_arc,
-        data_loaders,
-        security,
-    };
-
-    Schema::build(Query, Mutation, EmptySubscription)
-        .data(context)
+
+    Schema::build(Query, Mutation, Subscription)
+        .enable_federation()
+        .extension(Tracing)
+        .extension(CacheControlHints)
+        .extension(AdminAuditLog)
+        .extension(QueryExplainer)
+        .extension(FieldErrorReporter)
+        .data(data_loaders)
         .data(pool) // Add pool as separate context data
         .finish()
 }
@@ -72,24 +80,42 @@ pub fn create_schema(pool: DatabasePool) -> Schema<Query, Mutation, EmptySubscri
 pub fn create_schema_with_data<T: Send + Sync + 'static>(
     pool: DatabasePool,
     additional_data: T,
-) -> Schema<Query, Mutation, EmptySubscription> {
-    let pool_arc = Arc::new(pool.clone());
+) -> AppSchema {
     let data_loaders = Arc::new(DataLoaders::new(pool.clone()));
-    let security_config = SecurityConfig::default();
-    let security = Arc::new(SecurityMiddleware::new(security_config));
-    let context = GraphQLContext {
-        pool: pool_arc,
-        data_loaders,
-        security,
-    };
-
-    Schema::build(Query, Mutation, EmptySubscription)
-        .data(context)
+
+    Schema::build(Query, Mutation, Subscription)
+        .enable_federation()
+        .extension(Tracing)
+        .extension(CacheControlHints)
+        .extension(AdminAuditLog)
+        .extension(QueryExplainer)
+        .extension(FieldErrorReporter)
+        .data(data_loaders)
         .data(pool) // Add pool as separate context data
         .data(additional_data)
         .finish()
 }
 
+/// Federation SDL of the schema, as a gateway composes it
+///
+/// Gateways fetch the same SDL from the running server through
+/// `{ _service { sdl } }`; this lets CI compose the supergraph without a
+/// database.
+pub fn federation_sdl() -> String {
+    Schema::build(Query, Mutation, Subscription)
+        .enable_federation()
+        .finish()
+        .sdl_with_options(SDLExportOptions::new().federation())
+}
+
+/// SDL of the public schema, without federation directives
+///
+/// This is the schema clients see; [`versioning`](crate::graphql::versioning)
+/// diffs it between releases.
+pub fn schema_sdl() -> String {
+    Schema::build(Query, Mutation, Subscription).finish().sdl()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +150,17 @@ mod tests {
         // This test just ensures the schema can be created without panicking
         assert!(std::ptr::addr_of!(schema) != std::ptr::null());
     }
+
+    #[test]
+    fn test_federation_sdl_declares_entities() {
+        // REQUIREMENT: Series and data points compose with other subgraphs behind one gateway
+        // PURPOSE: Verify the exported SDL is a federation v2 subgraph with id-keyed entities
+        // This ensures the gateway can resolve references to our types by id
+
+        let sdl = federation_sdl();
+
+        assert!(sdl.contains("https://specs.apollo.dev/federation/v2"));
+        assert!(sdl.contains("type EconomicSeriesType @key(fields: \"id\")"));
+        assert!(sdl.contains("type DataPoint @key(fields: \"id\")"));
+    }
 }
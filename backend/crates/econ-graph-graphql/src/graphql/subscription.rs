@@ -0,0 +1,60 @@
+//! # GraphQL Subscription Resolvers
+//!
+//! Real-time feeds for the EconGraph API, served over the `graphql/ws`
+//! WebSocket endpoint. Subscriptions require an authenticated connection.
+
+use async_graphql::Subscription;
+use futures::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::imports::*;
+use crate::types::{CrawlQueueSnapshotType, NotificationType};
+
+/// Root subscription object
+pub struct Subscription;
+
+#[Subscription]
+impl Subscription {
+    /// Notifications for the current user as they are created
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = NotificationType>> {
+        let user_id = current_user(ctx)?.id;
+        let receiver = notification_hub().subscribe();
+
+        Ok(stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(notification) if notification.user_id == user_id => {
+                        return Some((NotificationType::from(notification), receiver));
+                    }
+                    // Slow clients can re-sync missed notifications from the `notifications` query
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+
+    /// Crawl queue statistics and depth every `intervalSeconds` (1-60, admin only)
+    async fn crawl_queue_snapshots(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 5)] interval_seconds: i32,
+    ) -> Result<impl Stream<Item = Result<CrawlQueueSnapshotType>>> {
+        let _admin_user = require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?.clone();
+        let period = std::time::Duration::from_secs(interval_seconds.clamp(1, 60) as u64);
+        let ticks = tokio::time::interval(period);
+
+        Ok(stream::unfold(
+            (pool, ticks),
+            |(pool, mut ticks)| async move {
+                ticks.tick().await;
+                let snapshot = CrawlQueueSnapshotType::load(&pool).await;
+                Some((snapshot, (pool, ticks)))
+            },
+        ))
+    }
+}
@@ -5,6 +5,7 @@ use diesel::SelectableHelper;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::graphql::context::data_loaders;
 use econ_graph_core::models::{
     AnnotationComment, ChartAnnotation, ChartCollaborator, DataPoint, DataSource, EconomicSeries,
     SearchSortOrder, SearchSuggestion, SeriesSearchResult, SuggestionType, User,
@@ -89,8 +90,7 @@ impl EconomicSeriesType {
 
     /// Fetch the data source using DataLoader for efficient batching
     async fn source(&self, ctx: &Context<'_>) -> Result<Option<DataSourceType>> {
-        let context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
-        let data_loaders = &context.data_loaders;
+        let data_loaders = data_loaders(ctx)?;
         let source_uuid = Uuid::parse_str(&self.source_id)?;
 
         let source = data_loaders.data_source_loader.load(source_uuid).await;
@@ -103,11 +103,13 @@ impl EconomicSeriesType {
         ctx: &Context<'_>,
         #[graphql(default = 100)] limit: i32,
     ) -> Result<Vec<DataPointType>> {
-        let context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
-        let data_loaders = &context.data_loaders;
+        let data_loaders = data_loaders(ctx)?;
         let series_uuid = Uuid::parse_str(&self.id)?;
 
-        let data_points = data_loaders.data_points_by_series_loader.load(series_uuid).await;
+        let data_points = data_loaders
+            .data_points_by_series_loader
+            .load(series_uuid)
+            .await;
         let limited_points = data_points
             .into_iter()
             .take(limit as usize)
@@ -119,8 +121,7 @@ impl EconomicSeriesType {
 
     /// Get data point count using DataLoader for efficient batching
     async fn data_point_count(&self, ctx: &Context<'_>) -> Result<i32> {
-        let context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
-        let data_loaders = &context.data_loaders;
+        let data_loaders = data_loaders(ctx)?;
         let series_uuid = Uuid::parse_str(&self.id)?;
 
         let count = data_loaders.data_point_count_loader.load(series_uuid).await;
@@ -308,8 +309,7 @@ impl DataSourceType {
         #[graphql(default = 50)] first: i32,
         after: Option<String>,
     ) -> Result<SeriesConnection> {
-        let context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
-        let data_loaders = &context.data_loaders;
+        let data_loaders = data_loaders(ctx)?;
         let source_uuid = Uuid::parse_str(&self.id)?;
 
         let all_series = data_loaders.series_by_source_loader.load(source_uuid).await;
@@ -350,8 +350,7 @@ impl DataSourceType {
 
     /// Get count of active series for this data source
     async fn series_count(&self, ctx: &Context<'_>) -> Result<i32> {
-        let context = ctx.data::<crate::graphql::schema::GraphQLContext>()?;
-        let data_loaders = &context.data_loaders;
+        let data_loaders = data_loaders(ctx)?;
         let source_uuid = Uuid::parse_str(&self.id)?;
 
         let count = data_loaders.series_count_loader.load(source_uuid).await;
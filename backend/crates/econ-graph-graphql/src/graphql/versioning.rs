@@ -0,0 +1,774 @@
+//! # GraphQL Schema Versioning
+//!
+//! Version number, change log and deprecation registry of the public GraphQL
+//! schema, plus the compatibility check that compares the schema against the
+//! SDL snapshot of the last release.
+//!
+//! A breaking change (a removed field, a field that may now return null, a new
+//! required argument, ...) needs a major version bump. Every `@deprecated`
+//! annotation in the schema must have an entry in [`DEPRECATIONS`]; the
+//! registry supplies the reason and the version that deprecated it.
+
+use async_graphql::parser::{
+    parse_schema,
+    types::{
+        BaseType, ConstDirective, FieldDefinition, InputValueDefinition, Type, TypeDefinition,
+        TypeKind, TypeSystemDefinition,
+    },
+    Positioned,
+};
+use async_graphql::{Name, Value};
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::graphql::schema::schema_sdl;
+use econ_graph_core::{AppError, AppResult};
+
+/// Version of the schema served by this build
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion::new(1, 21);
+
+/// Header line that carries the version in a schema snapshot
+pub const SCHEMA_VERSION_HEADER: &str = "# schema-version: ";
+
+/// Schema version as `major.minor`
+///
+/// The major version changes with breaking changes, the minor version with
+/// additions and deprecations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SchemaVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl SchemaVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl std::str::FromStr for SchemaVersion {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError::ValidationError(format!("Invalid schema version: {}", s));
+        let (major, minor) = s.trim().split_once('.').ok_or_else(invalid)?;
+        Ok(Self::new(
+            major.parse().map_err(|_| invalid())?,
+            minor.parse().map_err(|_| invalid())?,
+        ))
+    }
+}
+
+impl Serialize for SchemaVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A deprecated field, argument, input field or enum value
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deprecation {
+    /// Schema coordinate, e.g. `Query.series`, `Query.series(id:)` or `DataTransformation.NONE`
+    pub coordinate: &'static str,
+    /// Reason shown in `@deprecated`; names the replacement
+    pub reason: &'static str,
+    /// Version that deprecated it
+    pub since: SchemaVersion,
+}
+
+/// Everything deprecated in the current schema
+///
+/// Add an entry here together with `#[graphql(deprecation = "...")]` on the
+/// field, using the same reason. Deprecated elements are removed only in a
+/// major version.
+pub const DEPRECATIONS: &[Deprecation] = &[];
+
+/// Changes released in one schema version
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogEntry {
+    pub version: SchemaVersion,
+    pub changes: &'static [&'static str],
+}
+
+/// Schema change log, newest first
+pub const SCHEMA_CHANGELOG: &[ChangelogEntry] = &[
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 21),
+        changes: &[
+            "Add OrganizationChartShare.chart: the shared chart, for members who may view it",
+            "savedChart returns charts shared with the current user, not only their own",
+            "updateSavedChart accepts users with edit access to a shared chart; changing isPublic needs chart admin",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 20),
+        changes: &[
+            "Add ratioHistory: a company financial ratio across fiscal periods",
+            "Add ratioPeerPercentile: a company percentile within its sector for a financial ratio, per fiscal period",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 19),
+        changes: &[
+            "searchSeries returns highlights: title and description fragments with matched words in <mark>",
+            "searchSeries returns facets: match counts per source, frequency, units and geography",
+            "Add titleHighlight and descriptionHighlight to SeriesSearchResult",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 18),
+        changes: &["Add importAnnotations: chart annotations created from an uploaded CSV file, with a per-row report"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 17),
+        changes: &["Add releaseCalendars, createReleaseCalendar, setReleaseCalendarActive and deleteReleaseCalendar: release schedules that queue crawls right after publication"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 16),
+        changes: &["Add changes: series and data point mutations after a change feed sequence"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 15),
+        changes: &["Add setDataSourceFallbackUrls and DataSource.fallbackUrls: mirror URLs crawlers fail over to"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 14),
+        changes: &["Add seriesRollup: monthly or yearly first, min, max, mean and last values of a series"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 13),
+        changes: &[
+            "auditLogs returns stored entries, filtered by user, action, resource type and date range, with cursor pagination",
+            "Add exportAuditLogs: audit log entries matching a filter as CSV",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 12),
+        changes: &["Add companyFinancials: latest processed filings of a company with their company-level XBRL facts"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 11),
+        changes: &["Add emailPreferences and updateEmailPreferences: which emails (invites, alerts, weekly digest) the current user receives"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 10),
+        changes: &["Add segmentBreakdown: XBRL facts of a statement by the members of an axis, per period"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 9),
+        changes: &["Add countryIndicatorSnapshot: a global indicator across all countries with its color scale"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 8),
+        changes: &["Add alignedSeries: several series as known on a vintage date, on one date index"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 7),
+        changes: &["Add catalogStatistics: series, data point and coverage totals per data source"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 6),
+        changes: &[
+            "Add data source access policies: DataSource.accessPolicy, DataSource.accessible, User.subscriptionTier, setDataSourceAccessPolicy and setUserSubscriptionTier",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 5),
+        changes: &["Add per API key usage to dataSourceQuotas: DataSourceQuota.apiKeys"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 4),
+        changes: &[
+            "Add the annotation review workflow: assignAnnotationReview, accept/complete/rejectAnnotationAssignment and myPendingAnnotationReviews",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 3),
+        changes: &[
+            "Add SEC 13F institutional holdings: topInstitutionalHolders and institutionalPositionChanges",
+        ],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 2),
+        changes: &["Add SEC Form 4 insider activity: insiderTransactions and insiderActivity"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 1),
+        changes: &["Add bulk exports: requestExport, exportJob and myExportJobs"],
+    },
+    ChangelogEntry {
+        version: SchemaVersion::new(1, 0),
+        changes: &["First versioned release; later breaking changes require a new major version"],
+    },
+];
+
+/// Body of the `/graphql/changelog` endpoint
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaChangelog {
+    pub current_version: SchemaVersion,
+    pub changelog: &'static [ChangelogEntry],
+    pub deprecations: &'static [Deprecation],
+}
+
+/// Current version, change log and deprecations
+pub fn schema_changelog() -> SchemaChangelog {
+    SchemaChangelog {
+        current_version: CURRENT_SCHEMA_VERSION,
+        changelog: SCHEMA_CHANGELOG,
+        deprecations: DEPRECATIONS,
+    }
+}
+
+/// SDL of the current schema with its version header, as stored in a snapshot
+pub fn versioned_sdl() -> String {
+    format!(
+        "{}{}\n{}",
+        SCHEMA_VERSION_HEADER,
+        CURRENT_SCHEMA_VERSION,
+        schema_sdl()
+    )
+}
+
+/// One difference between two versions of the schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    /// Schema coordinate of the changed element
+    pub coordinate: String,
+    pub description: String,
+    /// Whether existing clients can break
+    pub breaking: bool,
+}
+
+impl SchemaChange {
+    fn breaking(coordinate: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            coordinate: coordinate.into(),
+            description: description.into(),
+            breaking: true,
+        }
+    }
+
+    fn safe(coordinate: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            coordinate: coordinate.into(),
+            description: description.into(),
+            breaking: false,
+        }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.coordinate, self.description)
+    }
+}
+
+/// Result of comparing the current schema with a released snapshot
+#[derive(Debug)]
+pub struct CompatibilityReport {
+    pub released_version: SchemaVersion,
+    pub current_version: SchemaVersion,
+    pub changes: Vec<SchemaChange>,
+}
+
+impl CompatibilityReport {
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    /// Whether the version number accounts for the changes
+    ///
+    /// The version may not go backwards, and breaking changes need a higher
+    /// major version than the released one.
+    pub fn is_compatible(&self) -> bool {
+        if self.current_version < self.released_version {
+            return false;
+        }
+        self.breaking_changes().next().is_none()
+            || self.current_version.major > self.released_version.major
+    }
+}
+
+/// Compare the current schema with a snapshot written by [`versioned_sdl`]
+pub fn check_compatibility(snapshot: &str) -> AppResult<CompatibilityReport> {
+    let released_version = snapshot
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(SCHEMA_VERSION_HEADER))
+        .ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Schema snapshot must start with '{}<major>.<minor>'",
+                SCHEMA_VERSION_HEADER
+            ))
+        })?
+        .parse()?;
+
+    Ok(CompatibilityReport {
+        released_version,
+        current_version: CURRENT_SCHEMA_VERSION,
+        changes: diff_schemas(snapshot, &schema_sdl())?,
+    })
+}
+
+/// Differences from the `old` SDL to the `new` one
+///
+/// Federation and introspection types and fields (names starting with `_`) are
+/// ignored; the gateway owns those.
+pub fn diff_schemas(old: &str, new: &str) -> AppResult<Vec<SchemaChange>> {
+    let old_types = parse_types(old)?;
+    let new_types = parse_types(new)?;
+    let mut changes = Vec::new();
+
+    for (name, old_type) in &old_types {
+        match new_types.get(name) {
+            Some(new_type) => diff_type(name, old_type, new_type, &mut changes),
+            None => changes.push(SchemaChange::breaking(name.as_str(), "type removed")),
+        }
+    }
+    for name in new_types
+        .keys()
+        .filter(|name| !old_types.contains_key(*name))
+    {
+        changes.push(SchemaChange::safe(name.as_str(), "type added"));
+    }
+
+    Ok(changes)
+}
+
+/// `@deprecated` reasons in the SDL by schema coordinate
+pub fn deprecated_coordinates(sdl: &str) -> AppResult<BTreeMap<String, String>> {
+    let mut deprecated = BTreeMap::new();
+    for (type_name, definition) in parse_types(sdl)? {
+        let mut record = |coordinate: String, directives: &[Positioned<ConstDirective>]| {
+            if let Some(reason) = deprecation_reason(directives) {
+                deprecated.insert(coordinate, reason);
+            }
+        };
+        match &definition.kind {
+            TypeKind::Object(object) => {
+                fields_deprecations(&type_name, &object.fields, &mut record)
+            }
+            TypeKind::Interface(interface) => {
+                fields_deprecations(&type_name, &interface.fields, &mut record)
+            }
+            TypeKind::InputObject(input) => {
+                for field in &input.fields {
+                    let coordinate = format!("{}.{}", type_name, field.node.name.node);
+                    record(coordinate, &field.node.directives);
+                }
+            }
+            TypeKind::Enum(enum_type) => {
+                for value in &enum_type.values {
+                    let coordinate = format!("{}.{}", type_name, value.node.value.node);
+                    record(coordinate, &value.node.directives);
+                }
+            }
+            TypeKind::Scalar | TypeKind::Union(_) => {}
+        }
+    }
+    Ok(deprecated)
+}
+
+fn fields_deprecations(
+    type_name: &str,
+    fields: &[Positioned<FieldDefinition>],
+    record: &mut impl FnMut(String, &[Positioned<ConstDirective>]),
+) {
+    for field in fields {
+        let field_name = &field.node.name.node;
+        record(
+            format!("{}.{}", type_name, field_name),
+            &field.node.directives,
+        );
+        for argument in &field.node.arguments {
+            let coordinate = format!("{}.{}({}:)", type_name, field_name, argument.node.name.node);
+            record(coordinate, &argument.node.directives);
+        }
+    }
+}
+
+fn deprecation_reason(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    let directive = directives
+        .iter()
+        .find(|directive| directive.node.name.node.as_str() == "deprecated")?;
+    let reason = directive
+        .node
+        .arguments
+        .iter()
+        .find(|(name, _)| name.node.as_str() == "reason")
+        .map(|(_, value)| match &value.node {
+            Value::String(reason) => reason.clone(),
+            other => other.to_string(),
+        });
+    Some(reason.unwrap_or_else(|| "No longer supported".to_string()))
+}
+
+fn parse_types(sdl: &str) -> AppResult<BTreeMap<String, TypeDefinition>> {
+    let document = parse_schema(sdl)
+        .map_err(|e| AppError::ParserError(format!("Invalid schema SDL: {}", e)))?;
+
+    Ok(document
+        .definitions
+        .into_iter()
+        .filter_map(|definition| match definition {
+            TypeSystemDefinition::Type(definition) => Some(definition.node),
+            _ => None,
+        })
+        .filter(|definition| !definition.name.node.starts_with('_'))
+        .map(|definition| (definition.name.node.to_string(), definition))
+        .collect())
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object(_) => "object",
+        TypeKind::Interface(_) => "interface",
+        TypeKind::Union(_) => "union",
+        TypeKind::Enum(_) => "enum",
+        TypeKind::InputObject(_) => "input object",
+    }
+}
+
+fn diff_type(
+    name: &str,
+    old: &TypeDefinition,
+    new: &TypeDefinition,
+    changes: &mut Vec<SchemaChange>,
+) {
+    match (&old.kind, &new.kind) {
+        (TypeKind::Object(old_object), TypeKind::Object(new_object)) => {
+            diff_implements(
+                name,
+                &old_object.implements,
+                &new_object.implements,
+                changes,
+            );
+            diff_fields(name, &old_object.fields, &new_object.fields, changes);
+        }
+        (TypeKind::Interface(old_interface), TypeKind::Interface(new_interface)) => {
+            diff_implements(
+                name,
+                &old_interface.implements,
+                &new_interface.implements,
+                changes,
+            );
+            diff_fields(name, &old_interface.fields, &new_interface.fields, changes);
+        }
+        (TypeKind::Union(old_union), TypeKind::Union(new_union)) => {
+            let old_members: Vec<_> = old_union.members.iter().collect();
+            let new_members: Vec<_> = new_union.members.iter().collect();
+            for member in names_missing(&old_members, &new_members) {
+                changes.push(SchemaChange::breaking(
+                    name,
+                    format!("member {} removed", member),
+                ));
+            }
+            for member in names_missing(&new_members, &old_members) {
+                changes.push(SchemaChange::safe(name, format!("member {} added", member)));
+            }
+        }
+        (TypeKind::Enum(old_enum), TypeKind::Enum(new_enum)) => {
+            let old_values: Vec<_> = old_enum.values.iter().map(|v| &v.node.value).collect();
+            let new_values: Vec<_> = new_enum.values.iter().map(|v| &v.node.value).collect();
+            for value in names_missing(&old_values, &new_values) {
+                changes.push(SchemaChange::breaking(
+                    format!("{}.{}", name, value),
+                    "enum value removed",
+                ));
+            }
+            for value in names_missing(&new_values, &old_values) {
+                changes.push(SchemaChange::safe(
+                    format!("{}.{}", name, value),
+                    "enum value added",
+                ));
+            }
+        }
+        (TypeKind::InputObject(old_input), TypeKind::InputObject(new_input)) => {
+            diff_input_values(
+                |field| format!("{}.{}", name, field),
+                &old_input.fields,
+                &new_input.fields,
+                changes,
+            );
+        }
+        (TypeKind::Scalar, TypeKind::Scalar) => {}
+        (old_kind, new_kind) => changes.push(SchemaChange::breaking(
+            name,
+            format!(
+                "changed from {} to {}",
+                kind_name(old_kind),
+                kind_name(new_kind)
+            ),
+        )),
+    }
+}
+
+/// Names in `names` that are not in `other`
+fn names_missing<'a>(
+    names: &[&'a Positioned<Name>],
+    other: &[&'a Positioned<Name>],
+) -> impl Iterator<Item = &'a str> {
+    let other: Vec<&str> = other.iter().map(|name| name.node.as_str()).collect();
+    names
+        .iter()
+        .map(|name| name.node.as_str())
+        .filter(move |name| !other.contains(name))
+}
+
+fn diff_implements(
+    name: &str,
+    old: &[Positioned<Name>],
+    new: &[Positioned<Name>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let old: Vec<_> = old.iter().collect();
+    let new: Vec<_> = new.iter().collect();
+    for interface in names_missing(&old, &new) {
+        changes.push(SchemaChange::breaking(
+            name,
+            format!("no longer implements {}", interface),
+        ));
+    }
+}
+
+fn diff_fields(
+    type_name: &str,
+    old: &[Positioned<FieldDefinition>],
+    new: &[Positioned<FieldDefinition>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in old.iter().map(|field| &field.node) {
+        if old_field.name.node.starts_with('_') {
+            continue;
+        }
+        let coordinate = format!("{}.{}", type_name, old_field.name.node);
+        let Some(new_field) = new
+            .iter()
+            .map(|field| &field.node)
+            .find(|field| field.name.node == old_field.name.node)
+        else {
+            changes.push(SchemaChange::breaking(coordinate, "field removed"));
+            continue;
+        };
+
+        if !output_type_compatible(&old_field.ty.node, &new_field.ty.node) {
+            changes.push(SchemaChange::breaking(
+                coordinate.as_str(),
+                format!(
+                    "type changed from {} to {}",
+                    old_field.ty.node, new_field.ty.node
+                ),
+            ));
+        }
+        diff_input_values(
+            |argument| format!("{}({}:)", coordinate, argument),
+            &old_field.arguments,
+            &new_field.arguments,
+            changes,
+        );
+    }
+
+    for new_field in new.iter().map(|field| &field.node) {
+        let added = !new_field.name.node.starts_with('_')
+            && !old
+                .iter()
+                .any(|field| field.node.name.node == new_field.name.node);
+        if added {
+            changes.push(SchemaChange::safe(
+                format!("{}.{}", type_name, new_field.name.node),
+                "field added",
+            ));
+        }
+    }
+}
+
+/// Compare the arguments of a field, or the fields of an input object
+fn diff_input_values(
+    coordinate_of: impl Fn(&str) -> String,
+    old: &[Positioned<InputValueDefinition>],
+    new: &[Positioned<InputValueDefinition>],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_value in old.iter().map(|value| &value.node) {
+        let coordinate = coordinate_of(old_value.name.node.as_str());
+        let Some(new_value) = new
+            .iter()
+            .map(|value| &value.node)
+            .find(|value| value.name.node == old_value.name.node)
+        else {
+            changes.push(SchemaChange::breaking(coordinate, "input removed"));
+            continue;
+        };
+
+        if !input_type_compatible(&old_value.ty.node, &new_value.ty.node) {
+            changes.push(SchemaChange::breaking(
+                coordinate.as_str(),
+                format!(
+                    "type changed from {} to {}",
+                    old_value.ty.node, new_value.ty.node
+                ),
+            ));
+        }
+    }
+
+    for new_value in new.iter().map(|value| &value.node) {
+        if old
+            .iter()
+            .any(|value| value.node.name.node == new_value.name.node)
+        {
+            continue;
+        }
+        let coordinate = coordinate_of(new_value.name.node.as_str());
+        if new_value.ty.node.nullable || new_value.default_value.is_some() {
+            changes.push(SchemaChange::safe(coordinate, "optional input added"));
+        } else {
+            changes.push(SchemaChange::breaking(coordinate, "required input added"));
+        }
+    }
+}
+
+/// Whether clients reading the old output type can read the new one
+///
+/// A nullable field may become non-null, not the other way round.
+fn output_type_compatible(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old_name), BaseType::Named(new_name)) => old_name == new_name,
+        (BaseType::List(old_item), BaseType::List(new_item)) => {
+            output_type_compatible(old_item, new_item)
+        }
+        _ => false,
+    }
+}
+
+/// Whether values clients send for the old input type are valid for the new one
+///
+/// A non-null input may become nullable, not the other way round.
+fn input_type_compatible(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old_name), BaseType::Named(new_name)) => old_name == new_name,
+        (BaseType::List(old_item), BaseType::List(new_item)) => {
+            input_type_compatible(old_item, new_item)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_SDL: &str = r#"
+        type Query {
+          series(id: ID!, limit: Int): Series
+          sources: [String!]!
+        }
+        type Series {
+          id: ID!
+          title: String
+        }
+        enum Transformation {
+          NONE
+          YEAR_OVER_YEAR
+        }
+        input SeriesFilter {
+          query: String
+        }
+    "#;
+
+    fn breaking(changes: &[SchemaChange]) -> Vec<String> {
+        changes
+            .iter()
+            .filter(|change| change.breaking)
+            .map(|change| change.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_flags_breaking_changes() {
+        // REQUIREMENT: Releases must not break clients without a major version bump
+        // PURPOSE: Verify the SDL diff tells breaking changes from additions
+        // This ensures removals and tightened types fail the compatibility check while new fields pass
+
+        let additive = BASE_SDL
+            .replace(
+                "title: String\n",
+                "title: String!\n          units: String\n",
+            )
+            .replace("limit: Int)", "limit: Int, offset: Int = 0)")
+            .replace("YEAR_OVER_YEAR", "YEAR_OVER_YEAR\n          LOG_DIFFERENCE");
+        let changes = diff_schemas(BASE_SDL, &additive).unwrap();
+        assert!(breaking(&changes).is_empty(), "{:?}", changes);
+        assert!(changes.contains(&SchemaChange::safe("Series.units", "field added")));
+
+        let breaking_sdl = BASE_SDL
+            .replace("sources: [String!]!", "sources: [String]!")
+            .replace(
+                "id: ID!, limit: Int",
+                "id: ID!, limit: Int!, region: String!",
+            )
+            .replace("NONE\n", "")
+            .replace("query: String", "query: String!");
+        let changes = diff_schemas(BASE_SDL, &breaking_sdl).unwrap();
+        assert_eq!(
+            breaking(&changes),
+            vec![
+                "Query.series(limit:): type changed from Int to Int!",
+                "Query.series(region:): required input added",
+                "Query.sources: type changed from [String!]! to [String]!",
+                "SeriesFilter.query: type changed from String to String!",
+                "Transformation.NONE: enum value removed",
+            ]
+        );
+
+        let report = CompatibilityReport {
+            released_version: SchemaVersion::new(1, 3),
+            current_version: SchemaVersion::new(1, 4),
+            changes,
+        };
+        assert!(!report.is_compatible());
+        let bumped = CompatibilityReport {
+            current_version: SchemaVersion::new(2, 0),
+            ..report
+        };
+        assert!(bumped.is_compatible());
+    }
+
+    #[test]
+    fn test_deprecations_match_registry() {
+        // REQUIREMENT: Deprecations are driven from one central registry
+        // PURPOSE: Verify every @deprecated in the schema is registered with the same reason, and vice versa
+        // This ensures the change log endpoint lists exactly what the schema deprecates
+
+        let in_schema = deprecated_coordinates(&schema_sdl()).unwrap();
+        let registered: BTreeMap<String, String> = DEPRECATIONS
+            .iter()
+            .map(|d| (d.coordinate.to_string(), d.reason.to_string()))
+            .collect();
+        assert_eq!(in_schema, registered);
+
+        assert!(DEPRECATIONS
+            .iter()
+            .all(|d| d.since <= CURRENT_SCHEMA_VERSION));
+        assert_eq!(SCHEMA_CHANGELOG[0].version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            check_compatibility(&versioned_sdl()).unwrap().changes,
+            Vec::new()
+        );
+    }
+}
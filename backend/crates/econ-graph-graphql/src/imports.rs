@@ -24,14 +24,28 @@
 pub use econ_graph_core::{
     auth_models::{AuthProvider, User as AuthUser, UserRole},
     database::DatabasePool,
+    // Annotation review workflow and data access policies
+    enums::{AssignmentStatus, AssignmentType, DataAccessPolicy, SubscriptionTier},
     error::{AppError, AppResult},
     // Additional imports for missing modules
     models as core_models,
     models::{
+        // Series alerts
+        AlertCondition,
+        // Annotation review workflow
+        AnnotationAssignment,
         AnnotationComment,
+        // Catalog statistics
+        CatalogStatistics,
+        // Change feed
+        ChangeFeedEntry,
+        ChangeFeedPage,
         // Chart annotations
         ChartAnnotation,
         ChartCollaborator,
+        // Company financial statements
+        CompanyFinancials,
+        CompanySearchResult,
         CorrelationConnection,
         CorrelationNetworkNode,
         // Global analysis
@@ -39,55 +53,163 @@ pub use econ_graph_core::{
         CountryCorrelation,
         CountryImpactDetail,
         CountryWithEconomicData,
+        // Crawl queue
+        CrawlQueueFilter,
+        CrawlQueueItem,
         DataPoint,
         DataQueryParams,
         DataSource,
+        DataSourceCredential,
+        DataSourceKeyUsage,
+        DataSourceQuotaStatus,
+        DataSourceStatistics,
         // Data transformations
         DataTransformation,
+        // Derived series
+        DerivedSeries,
+        DerivedSeriesInput,
         // Core data models
         EconomicSeries,
+        // Email preferences
+        EmailPreferences,
+        EmailPreferencesUpdate,
         EventCountryImpact,
+        // Bulk exports
+        ExportFormat,
+        ExportJob,
+        ExportJobStatus,
+        FacetCount,
+        FinancialLineItem,
+        FormulaBinding,
         GlobalEconomicEvent,
         GlobalEventWithImpacts,
+        // SEC Form 13F institutional holdings
+        HolderPosition,
+        // Company benchmarking
+        IndustryBenchmark,
+        // SEC Form 4 insider transactions
+        InsiderActivitySummary,
+        InsiderTransaction,
+        InsiderTransactionFilter,
+        InstitutionalHolding,
+        LeadingIndicator,
+        NewAnnotationAssignment,
+        // Data source administration
+        NewDataSource,
+        // Organizations
+        NewOrganization,
+        NewOrganizationChartShare,
+        NewOrganizationMember,
+        NewReleaseCalendar,
+        NewSavedChart,
+        NewSeriesAlertRule,
         // User management
         NewUser,
+        NewWebhook,
+        // Notifications
+        Notification,
+        Organization,
+        OrganizationChartShare,
+        OrganizationMember,
+        OrganizationRole,
+        PositionChange,
+        PositionChangeKind,
+        QueueDepth,
+        QueueStatistics,
+        QueueStatus,
+        // Release calendars
+        ReleaseCalendar,
+        ReleaseFrequency,
+        // Saved charts
+        SavedChart,
+        // Search facets
+        SearchFacets,
         // Search ordering
         SearchSortOrder,
         SearchSuggestion,
+        // XBRL segment breakdowns
+        SegmentBreakdown,
+        SegmentMember,
+        SeriesAlertRule,
+        // Cross-source series links
+        SeriesLink,
+        SeriesLinkSource,
         // Search parameters
         SeriesSearchParams,
         // Search and discovery
         SeriesSearchResult,
         SuggestionType,
         TradePartner,
+        UpdateDataSource,
+        UpdateSeriesAlertRule,
         User,
+        // Webhooks
+        Webhook,
+        WebhookDelivery,
+        WebhookDeliveryStatus,
+        WebhookEvent,
+        // XBRL validation
+        XbrlCalculationDiscrepancy,
     },
     search,
+    // Stored credential encryption
+    secrets::{shared_secret_cipher, SecretString},
 };
 
 // Re-export the models module for easy access
 pub use econ_graph_core::models;
 
+// Auth crate imports
+pub use econ_graph_auth::auth::organizations::OrganizationAccess;
+
 // Services crate imports
 pub use econ_graph_services::services::{
+    aligned_series_service::{self, AlignedRow, AlignedSeries},
+    annotation_import_service::{self, AnnotationImportReport},
+    annotation_workflow_service::AnnotationWorkflowService,
+    audit_log_service::{AuditLogExport, AuditLogPosition, AuditLogService},
+    benchmarking_service::{
+        decimal_to_f64, BenchmarkRefreshSummary, BenchmarkingService, CompanyBenchmark,
+        CompanyBenchmarkPeriod, RatioHistoryPoint, RatioPeerPercentile, RatioPercentilePeriod,
+    },
     collaboration_service::{CollaborationService, PermissionLevel},
-    crawler::{crawler_service, simple_crawler_service},
-    global_analysis_service::GlobalAnalysisService,
+    country_snapshot_service::{
+        self, CountryIndicatorSnapshot, CountryIndicatorValue, QuantileBreak,
+    },
+    crawl_analytics_service::{self, CrawlAnalyticsReport, CrawlErrorCount, SourceCrawlAnalytics},
+    crawler::{crawler_service, quota_client, simple_crawler_service},
+    currency_conversion_service::{self, ConvertedDataPoint, CurrencyConversion},
+    data_correction_service::DataCorrectionService,
+    data_point_cache::{shared_data_point_cache, DataPointCacheKey},
+    data_source_admin_service::{AuditActor, DataSourceAdminService},
+    derived_series_service::{DerivedSeriesChanges, DerivedSeriesDefinition, DerivedSeriesService},
+    education_service::{EducationService, ProgressUpdate},
+    export_service::{shared_download_signer, ExportService},
+    global_analysis_service::{
+        CrossSeriesAnalysisConfig, CrossSeriesAnalysisSummary, GlobalAnalysisService,
+    },
+    notification_service::{notification_hub, NotificationService},
+    provenance_service::{self, Provenance},
     queue_service,
     // Core services
-    search_service::SearchService,
-    series_service,
+    search_service::{SearchResults, SearchService},
+    seasonal_adjustment_service::{
+        shared_seasonal_adjustment_service, SeasonalAdjustmentResult, SeasonalComponentPoint,
+    },
+    series_link_service::{EquivalentSeriesMatch, SeriesLinkService, DEFAULT_MIN_MATCH_CONFIDENCE},
+    series_service::{self, DataPointPosition, Page, RowCount, SeriesPosition},
+    user_profile_service::{PreferenceChanges, UserProfileService},
 };
 
 // GraphQL framework imports
 pub use async_graphql::{
-    Context, EmptyMutation, EmptySubscription, Enum, Error as GraphQLError, InputObject, Object,
-    Result, Schema, SimpleObject, ID,
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Enum, Error as GraphQLError,
+    InputObject, MaybeUndefined, Object, Result, Schema, SimpleObject, Upload, ID,
 };
 
 // Standard library and external crate imports
 pub use bigdecimal::BigDecimal;
-pub use chrono::{DateTime, NaiveDate, Utc};
+pub use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 pub use diesel::SelectableHelper;
 pub use rust_decimal::Decimal;
 pub use serde::{Deserialize, Serialize};
@@ -98,4 +220,7 @@ pub use uuid::Uuid;
 // Note: These are already imported above, so we don't need to redefine them
 
 // Re-export GraphQL context utilities
-pub use crate::graphql::context::{current_user, require_admin, GraphQLContext};
+pub use crate::graphql::context::{
+    can_create_annotations, can_create_charts, can_manage_user, can_view_security_events,
+    can_write_economic_data, current_user, require_admin, GraphQLContext,
+};
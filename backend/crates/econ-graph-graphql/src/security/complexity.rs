@@ -10,6 +10,9 @@
 //! - List complexity: Lists multiply complexity by their size
 //! - Nested complexity: Nested fields add to the total complexity
 //! - Custom complexity: Fields can have custom complexity values
+//! - Connection complexity: Selections under paginated connections are weighted
+//!   by the requested page size (`first`), so `seriesData(first: 10000)` costs
+//!   far more than a default page
 //!
 //! # Security Benefits
 //!
@@ -25,11 +28,32 @@
 //! - `list_complexity_multiplier`: Multiplier for list fields
 //! - `nested_complexity_multiplier`: Multiplier for nested fields
 
-use async_graphql::parser::types::{ExecutableDocument, OperationType, Selection, SelectionSet};
+use async_graphql::parser::types::{
+    ExecutableDocument, Field, OperationType, Selection, SelectionSet,
+};
 use async_graphql::parser::Pos;
+use async_graphql::Value;
 use std::collections::HashMap;
 use tracing::{debug, warn};
 
+/// Page sizes of a paginated connection field
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionWeight {
+    /// Page size when `first` is not given
+    pub default_page_size: u32,
+    /// Largest page the resolver returns; assumed when `first` is a variable
+    pub max_page_size: u32,
+    /// Items that count as one repetition of the selection's complexity
+    pub items_per_unit: u32,
+}
+
+impl ConnectionWeight {
+    /// Multiplier applied to the complexity of the connection's selection
+    fn multiplier(&self, page_size: u32) -> u32 {
+        page_size.div_ceil(self.items_per_unit.max(1)).max(1)
+    }
+}
+
 /// Query complexity analyzer
 pub struct ComplexityAnalyzer {
     /// Maximum allowed complexity score
@@ -40,6 +64,8 @@ pub struct ComplexityAnalyzer {
     list_complexity_multiplier: f64,
     /// Complexity multiplier for nested fields
     nested_complexity_multiplier: f64,
+    /// Paginated connection fields, weighted by requested page size
+    connection_weights: HashMap<String, ConnectionWeight>,
 }
 
 impl ComplexityAnalyzer {
@@ -54,11 +80,28 @@ impl ComplexityAnalyzer {
         field_complexity.insert("annotations".to_string(), 2);
         field_complexity.insert("comments".to_string(), 1);
 
+        // Page sizes mirror the resolvers in graphql::pagination
+        let data_points = ConnectionWeight {
+            default_page_size: 1_000,
+            max_page_size: 10_000,
+            items_per_unit: 100,
+        };
+        let series = ConnectionWeight {
+            default_page_size: 50,
+            max_page_size: 100,
+            items_per_unit: 10,
+        };
+        let mut connection_weights = HashMap::new();
+        connection_weights.insert("seriesData".to_string(), data_points);
+        connection_weights.insert("dataPointsConnection".to_string(), data_points);
+        connection_weights.insert("seriesList".to_string(), series);
+
         Self {
             max_complexity,
             field_complexity,
             list_complexity_multiplier: 1.5,
             nested_complexity_multiplier: 1.2,
+            connection_weights,
         }
     }
 
@@ -138,12 +181,15 @@ impl ComplexityAnalyzer {
                         (field_complexity as f64 * self.list_complexity_multiplier) as u32;
                 }
 
-                // Add complexity from nested selections
+                // Add complexity from nested selections, repeated per page of a connection
                 if !field.node.selection_set.node.items.is_empty() {
-                    field_complexity += self.calculate_operation_complexity(
+                    let nested_complexity = self.calculate_operation_complexity(
                         &field.node.selection_set.node,
                         depth + 1,
                     )?;
+                    field_complexity = field_complexity.saturating_add(
+                        nested_complexity.saturating_mul(self.connection_multiplier(&field.node)),
+                    );
                 }
 
                 Ok(field_complexity)
@@ -171,6 +217,14 @@ impl ComplexityAnalyzer {
         self.field_complexity.get(field_name).copied().unwrap_or(1)
     }
 
+    /// How many times a connection field's selection is counted
+    fn connection_multiplier(&self, field: &Field) -> u32 {
+        match self.connection_weights.get(field.name.node.as_str()) {
+            Some(weight) => weight.multiplier(requested_page_size(field, weight)),
+            None => 1,
+        }
+    }
+
     /// Check if a field is a list field
     fn is_list_field(&self, field_name: &str) -> bool {
         // List fields that typically return multiple items
@@ -199,6 +253,39 @@ impl ComplexityAnalyzer {
     pub fn get_field_complexity_value(&self, field_name: &str) -> u32 {
         self.get_field_complexity(field_name)
     }
+
+    /// Add or update the page size weighting of a connection field
+    pub fn set_connection_weight(&mut self, field_name: String, weight: ConnectionWeight) {
+        self.connection_weights.insert(field_name, weight);
+    }
+}
+
+/// Page size a connection field asks for
+///
+/// `first` is read from the field or from its `pagination` input. Values only
+/// known at execution time (variables) are assumed to be the maximum page size.
+fn requested_page_size(field: &Field, weight: &ConnectionWeight) -> u32 {
+    // None: not given; Some(None): not a literal
+    let first = match field.get_argument("first") {
+        Some(first) => Some(first.node.clone().into_const()),
+        None => field.get_argument("pagination").and_then(|pagination| {
+            match pagination.node.clone().into_const() {
+                Some(Value::Object(fields)) => fields.get("first").cloned().map(Some),
+                Some(_) => None,
+                None => Some(None),
+            }
+        }),
+    };
+
+    match first {
+        None | Some(Some(Value::Null)) => weight.default_page_size,
+        Some(Some(Value::Number(first))) => {
+            first.as_u64().map_or(weight.default_page_size, |first| {
+                first.min(weight.max_page_size as u64) as u32
+            })
+        }
+        Some(_) => weight.max_page_size,
+    }
 }
 
 #[cfg(test)]
@@ -260,6 +347,65 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_connection_complexity_scales_with_page_size() {
+        // REQUIREMENT: Paginated data point queries are weighted by page size
+        // PURPOSE: Verify large pages cost more and nested large pages are rejected
+        // This keeps a single request from fetching hundreds of thousands of rows
+
+        let analyzer = ComplexityAnalyzer::new(1000);
+        let page = |first: &str| {
+            format!(
+                r#"query {{ seriesData(seriesId: "1", {}) {{ nodes {{ date value }} }} }}"#,
+                first
+            )
+        };
+
+        let small = analyzer.calculate_complexity(&page("first: 100")).unwrap();
+        let default = analyzer
+            .calculate_complexity(&page("after: \"x\""))
+            .unwrap();
+        let large = analyzer
+            .calculate_complexity(&page("first: 10000"))
+            .unwrap();
+        let clamped = analyzer
+            .calculate_complexity(&page("first: 50000"))
+            .unwrap();
+        assert!(small < default && default < large);
+        assert_eq!(large, clamped);
+
+        let nested = r#"
+            query {
+                seriesList(pagination: { first: 100 }) {
+                    nodes {
+                        dataPointsConnection(first: 10000) {
+                            nodes { date value }
+                        }
+                    }
+                }
+            }
+        "#;
+        assert!(analyzer.validate_complexity(nested).is_err());
+    }
+
+    #[test]
+    fn test_connection_page_size_variable_assumes_maximum() {
+        let analyzer = ComplexityAnalyzer::new(1000);
+
+        let variable = analyzer
+            .calculate_complexity(
+                r#"query($n: Int) { seriesData(seriesId: "1", first: $n) { nodes { value } } }"#,
+            )
+            .unwrap();
+        let maximum = analyzer
+            .calculate_complexity(
+                r#"query { seriesData(seriesId: "1", first: 10000) { nodes { value } } }"#,
+            )
+            .unwrap();
+
+        assert_eq!(variable, maximum);
+    }
+
     #[test]
     fn test_custom_field_complexity() {
         let mut analyzer = ComplexityAnalyzer::new(100);
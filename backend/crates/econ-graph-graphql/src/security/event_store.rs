@@ -0,0 +1,172 @@
+//! # Security Event Store
+//!
+//! Persists [`SecurityEvent`]s into the `security_events` table so admins can
+//! review and resolve them. Events are written in the background, so handling
+//! one never delays the request that raised it.
+//!
+//! A client that keeps hitting a limit raises the same event on every request.
+//! Only the first event of a type per client (or user) is stored within
+//! [`DEFAULT_DEDUP_WINDOW`]; repeats are logged but not written.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::security::{LoggingSecurityEventHandler, SecurityEvent, SecurityEventHandler};
+use econ_graph_core::auth_models::Claims;
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::models::admin;
+
+/// Window within which repeated events of one type from one client are stored once
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(60);
+
+/// Admits the first event of a type per actor within a window
+struct RepeatFilter {
+    window: Duration,
+    /// Last admitted event time by (event type, client or user)
+    last_admitted: Mutex<HashMap<(&'static str, String), Instant>>,
+}
+
+impl RepeatFilter {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_admitted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether an event from `actor` is new within the window, recording it if so
+    fn admit(&self, event_type: &'static str, actor: &str, now: Instant) -> bool {
+        let mut last_admitted = self.last_admitted.lock().unwrap();
+        last_admitted.retain(|_, admitted_at| now.duration_since(*admitted_at) < self.window);
+
+        let key = (event_type, actor.to_string());
+        if last_admitted.contains_key(&key) {
+            return false;
+        }
+        last_admitted.insert(key, now);
+        true
+    }
+}
+
+/// Security event handler that logs events and stores them in `security_events`
+pub struct DatabaseSecurityEventHandler {
+    pool: DatabasePool,
+    repeats: RepeatFilter,
+}
+
+impl DatabaseSecurityEventHandler {
+    /// Create a handler storing events with the default deduplication window
+    pub fn new(pool: DatabasePool) -> Self {
+        Self::with_dedup_window(pool, DEFAULT_DEDUP_WINDOW)
+    }
+
+    /// Create a handler storing repeated events at most once per `dedup_window`
+    pub fn with_dedup_window(pool: DatabasePool, dedup_window: Duration) -> Self {
+        Self {
+            pool,
+            repeats: RepeatFilter::new(dedup_window),
+        }
+    }
+
+    fn store(&self, event: SecurityEvent, claims: Option<&Claims>) {
+        LoggingSecurityEventHandler.handle_event(event.clone());
+
+        let actor = claims.map_or(event.client_ip(), |claims| claims.sub.as_str());
+        if !self
+            .repeats
+            .admit(event.event_type(), actor, Instant::now())
+        {
+            debug!(
+                "Skipping repeated {} event from {}",
+                event.event_type(),
+                actor
+            );
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!("No async runtime to store {} event", event.event_type());
+            return;
+        };
+
+        let pool = self.pool.clone();
+        let user_id = claims.and_then(|claims| Uuid::parse_str(&claims.sub).ok());
+        let user_email = claims.map(|claims| claims.email.clone());
+        runtime.spawn(async move {
+            let metadata = serde_json::to_value(&event).ok();
+            if let Err(e) = admin::SecurityEvent::create(
+                &pool,
+                event.event_type().to_string(),
+                user_id,
+                user_email,
+                event.severity().as_str().to_string(),
+                Some(event.client_ip().to_string()),
+                None,
+                event.description(),
+                metadata,
+            )
+            .await
+            {
+                warn!("Failed to store {} event: {}", event.event_type(), e);
+            }
+        });
+    }
+}
+
+impl SecurityEventHandler for DatabaseSecurityEventHandler {
+    fn handle_event(&self, event: SecurityEvent) {
+        self.store(event, None);
+    }
+
+    fn handle_user_event(&self, event: SecurityEvent, claims: &Claims) {
+        self.store(event, Some(claims));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_event_classification() {
+        // REQUIREMENT: Persist security events with a severity classification
+        // PURPOSE: Verify stored type, severity and description, and that repeats are stored once per window
+        // This ensures admins see each incident once, ranked by how serious it is
+
+        let event = SecurityEvent::ComplexityExceeded {
+            client_ip: "203.0.113.7".to_string(),
+            complexity: 2500,
+            max_complexity: 1000,
+            query: "{ series { id } }".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert_eq!(event.event_type(), "complexity_exceeded");
+        assert_eq!(event.severity().as_str(), "high");
+        assert_eq!(event.client_ip(), "203.0.113.7");
+        assert_eq!(
+            event.description(),
+            "Query complexity 2500 exceeds limit of 1000"
+        );
+
+        let introspection = SecurityEvent::IntrospectionBlocked {
+            client_ip: "203.0.113.7".to_string(),
+            query: "{ __schema { types { name } } }".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert_eq!(introspection.severity().as_str(), "low");
+
+        let repeats = RepeatFilter::new(DEFAULT_DEDUP_WINDOW);
+        let now = Instant::now();
+        assert!(repeats.admit("rate_limit_exceeded", "203.0.113.7", now));
+        assert!(!repeats.admit("rate_limit_exceeded", "203.0.113.7", now));
+        assert!(repeats.admit("rate_limit_exceeded", "198.51.100.1", now));
+        assert!(repeats.admit(
+            "rate_limit_exceeded",
+            "203.0.113.7",
+            now + DEFAULT_DEDUP_WINDOW
+        ));
+    }
+}
@@ -26,18 +26,43 @@
 //! - Role-based access control for introspection
 //! - Environment-based configuration
 //!
+//! `__typename` is not schema introspection: clients (Apollo, Relay) add it to
+//! every selection to normalise their caches, so it is never blocked.
+//!
 //! # Configuration
 //!
 //! - `enabled`: Whether introspection protection is enabled
 //! - `block_all`: Whether to block all introspection queries
 //! - `allowed_fields`: List of allowed introspection fields
 //! - `blocked_fields`: List of blocked introspection fields
+//! - `trusted_roles`: Roles whose authenticated users may always introspect
 
 use async_graphql::parser::types::{
     ExecutableDocument, Field, OperationType, Selection, SelectionSet,
 };
+use econ_graph_core::auth_models::UserRole;
 use tracing::{debug, warn};
 
+/// Schema introspection fields, lowercase
+const INTROSPECTION_FIELDS: [&str; 7] = [
+    "__schema",
+    "__type",
+    "__directive",
+    "__field",
+    "__enumvalue",
+    "__inputvalue",
+    "__directivelocation",
+];
+
+/// Whether the query names a schema introspection field
+///
+/// Names are compared whole, so `__typename` does not match `__type`.
+fn names_introspection_field(query: &str) -> bool {
+    query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|name| INTROSPECTION_FIELDS.contains(&name.to_lowercase().as_str()))
+}
+
 /// Introspection protection configuration
 #[derive(Debug, Clone)]
 pub struct IntrospectionConfig {
@@ -51,6 +76,8 @@ pub struct IntrospectionConfig {
     pub blocked_fields: Vec<String>,
     /// Allow introspection in development
     pub allow_in_development: bool,
+    /// Roles allowed to introspect even when protection is enabled
+    pub trusted_roles: Vec<UserRole>,
 }
 
 impl Default for IntrospectionConfig {
@@ -62,7 +89,6 @@ impl Default for IntrospectionConfig {
             blocked_fields: vec![
                 "__schema".to_string(),
                 "__type".to_string(),
-                "__typename".to_string(),
                 "__directive".to_string(),
                 "__field".to_string(),
                 "__enumValue".to_string(),
@@ -70,6 +96,7 @@ impl Default for IntrospectionConfig {
                 "__directiveLocation".to_string(),
             ],
             allow_in_development: false,
+            trusted_roles: vec![UserRole::Admin],
         }
     }
 }
@@ -94,12 +121,35 @@ impl IntrospectionProtector {
         }
     }
 
+    /// Create a protector that lets authenticated users with `trusted_roles` introspect
+    pub fn with_trusted_roles(enabled: bool, trusted_roles: Vec<UserRole>) -> Self {
+        let mut protector = Self::new(enabled);
+        protector.config.trusted_roles = trusted_roles;
+        protector
+    }
+
     /// Validate introspection queries
     pub fn validate_introspection(&self, query: &str) -> Result<(), String> {
+        self.validate_introspection_for(query, None)
+    }
+
+    /// Validate introspection queries from a caller with `role`, if authenticated
+    ///
+    /// Callers with a trusted role pass; everyone else is checked as usual.
+    pub fn validate_introspection_for(
+        &self,
+        query: &str,
+        role: Option<&UserRole>,
+    ) -> Result<(), String> {
         if !self.config.enabled {
             return Ok(());
         }
 
+        if self.is_trusted(role) {
+            debug!("Allowing introspection for trusted role {:?}", role);
+            return Ok(());
+        }
+
         // Allow introspection in development if configured
         if self.is_development && self.config.allow_in_development {
             debug!("Allowing introspection in development environment");
@@ -120,29 +170,14 @@ impl IntrospectionProtector {
         Ok(())
     }
 
+    /// Whether a caller with `role` may introspect regardless of protection
+    pub fn is_trusted(&self, role: Option<&UserRole>) -> bool {
+        role.is_some_and(|role| self.config.trusted_roles.contains(role))
+    }
+
     /// Check if query contains introspection
     fn contains_introspection_query(&self, query: &str) -> bool {
-        let query_lower = query.to_lowercase();
-
-        // Check for common introspection fields
-        let introspection_fields = vec![
-            "__schema",
-            "__type",
-            "__typename",
-            "__directive",
-            "__field",
-            "__enumvalue",
-            "__inputvalue",
-            "__directivelocation",
-        ];
-
-        for field in introspection_fields {
-            if query_lower.contains(field) {
-                return true;
-            }
-        }
-
-        false
+        names_introspection_field(query)
     }
 
     /// Validate selective introspection
@@ -328,17 +363,7 @@ impl IntrospectionPatterns {
 
     /// Check if a query matches common introspection patterns
     pub fn is_introspection_pattern(query: &str) -> bool {
-        let query_lower = query.to_lowercase();
-
-        // Check for common introspection patterns
-        query_lower.contains("__schema")
-            || query_lower.contains("__type")
-            || query_lower.contains("__typename")
-            || query_lower.contains("__directive")
-            || query_lower.contains("__field")
-            || query_lower.contains("__enumvalue")
-            || query_lower.contains("__inputvalue")
-            || query_lower.contains("__directivelocation")
+        names_introspection_field(query)
     }
 
     /// Get a list of all introspection fields
@@ -346,7 +371,6 @@ impl IntrospectionPatterns {
         vec![
             "__schema",
             "__type",
-            "__typename",
             "__directive",
             "__field",
             "__enumValue",
@@ -434,5 +458,8 @@ mod tests {
         assert!(!IntrospectionPatterns::is_introspection_pattern(
             "query { series { id name } }"
         ));
+        assert!(!IntrospectionPatterns::is_introspection_pattern(
+            "query { series { __typename id } }"
+        ));
     }
 }
@@ -31,6 +31,7 @@
 
 pub mod complexity;
 pub mod depth_limit;
+pub mod event_store;
 pub mod input_validation;
 pub mod introspection;
 pub mod monitoring;
@@ -41,6 +42,7 @@ pub mod timeout;
 pub mod whitelist;
 
 use async_graphql::{Request, Response, ServerError};
+use econ_graph_core::auth_models::{Claims, UserRole};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
@@ -57,6 +59,8 @@ pub struct SecurityConfig {
     pub query_timeout: u64,
     /// Enable introspection protection
     pub protect_introspection: bool,
+    /// Roles whose authenticated users may introspect while protection is enabled
+    pub introspection_roles: Vec<UserRole>,
     /// Rate limiting configuration
     pub rate_limit: RateLimitConfig,
     /// Query whitelist/blacklist configuration
@@ -72,6 +76,8 @@ pub struct RateLimitConfig {
     pub requests_per_hour: u32,
     /// Maximum requests per day per IP
     pub requests_per_day: u32,
+    /// Per-user quotas for authenticated requests, by role
+    pub user_quotas: rate_limit::RoleQuotas,
     /// Enable rate limiting
     pub enabled: bool,
 }
@@ -103,21 +109,20 @@ impl Default for SecurityConfig {
             max_query_size: 10000, // 10KB
             query_timeout: 30,     // 30 seconds
             protect_introspection: true,
+            introspection_roles: vec![UserRole::Admin],
             rate_limit: RateLimitConfig {
                 requests_per_minute: 60,
                 requests_per_hour: 1000,
                 requests_per_day: 10000,
+                user_quotas: rate_limit::RoleQuotas::default(),
                 enabled: true,
             },
             query_filter: QueryFilterConfig {
                 enable_whitelist: false,
                 enable_blacklist: true,
                 whitelist_patterns: Vec::new(),
-                blacklist_patterns: vec![
-                    "__schema".to_string(),
-                    "__type".to_string(),
-                    "__typename".to_string(),
-                ],
+                // Introspection is left to the role-aware introspection protector
+                blacklist_patterns: Vec::new(),
                 case_sensitive: false,
                 use_regex: false,
                 allow_partial_matches: true,
@@ -136,6 +141,7 @@ pub struct SecurityMiddleware {
     introspection_protector: introspection::IntrospectionProtector,
     timeout_manager: timeout::TimeoutManager,
     query_filter: whitelist::QueryFilter,
+    metrics: std::sync::RwLock<SecurityMetrics>,
 }
 
 impl SecurityMiddleware {
@@ -151,8 +157,9 @@ impl SecurityMiddleware {
                 enabled: config.rate_limit.enabled,
             }),
             query_analyzer: query_analysis::QueryAnalyzer::new(config.max_query_size),
-            introspection_protector: introspection::IntrospectionProtector::new(
+            introspection_protector: introspection::IntrospectionProtector::with_trusted_roles(
                 config.protect_introspection,
+                config.introspection_roles.clone(),
             ),
             timeout_manager: timeout::TimeoutManager::new(config.query_timeout),
             query_filter: whitelist::QueryFilter::new(whitelist::QueryFilterConfig {
@@ -164,6 +171,7 @@ impl SecurityMiddleware {
                 use_regex: config.query_filter.use_regex,
                 allow_partial_matches: config.query_filter.allow_partial_matches,
             }),
+            metrics: std::sync::RwLock::new(SecurityMetrics::default()),
             config,
         }
     }
@@ -174,74 +182,249 @@ impl SecurityMiddleware {
         request: &Request,
         client_ip: &str,
     ) -> Result<(), Vec<ServerError>> {
-        let mut errors = Vec::new();
+        let violations = self.inspect_request(request, client_ip).await;
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations
+                .into_iter()
+                .map(|violation| violation.error)
+                .collect())
+        }
+    }
+
+    /// Run all security checks and report each failed check with its event
+    ///
+    /// Also updates the middleware's [`SecurityMetrics`]; callers that only
+    /// need the client-facing errors should use [`Self::validate_request`].
+    pub async fn inspect_request(
+        &self,
+        request: &Request,
+        client_ip: &str,
+    ) -> Vec<SecurityViolation> {
+        self.inspect_request_with_key(request, client_ip, client_ip)
+            .await
+    }
+
+    /// Run all security checks, charging the rate limit to `rate_limit_key`
+    ///
+    /// The key is limited by the configured per-IP quota; events keep
+    /// reporting the client IP.
+    pub async fn inspect_request_with_key(
+        &self,
+        request: &Request,
+        client_ip: &str,
+        rate_limit_key: &str,
+    ) -> Vec<SecurityViolation> {
+        let quota = self.rate_limiter.config().quota();
+        self.inspect(request, client_ip, rate_limit_key, quota, false, None)
+            .await
+    }
+
+    /// Run all security checks for a request with verified token claims
+    ///
+    /// The rate limit is charged to the user (see
+    /// [`rate_limit_key`](crate::graphql::context::rate_limit_key)) under
+    /// their role's quota from [`RateLimitConfig::user_quotas`], so accounts
+    /// sharing an address are limited independently.
+    ///
+    /// `claims` only need a valid signature, but the token outlives a revoked
+    /// session and its role may be stale, so introspection trust is decided by
+    /// `trusted_role` instead: the current role of a caller whose session was
+    /// checked, or `None`. Callers with one of the
+    /// [`SecurityConfig::introspection_roles`] may introspect the schema;
+    /// their introspection queries still count against the rate limit and the
+    /// size, depth and complexity limits.
+    pub async fn inspect_authenticated_request(
+        &self,
+        request: &Request,
+        client_ip: &str,
+        claims: &Claims,
+        trusted_role: Option<&UserRole>,
+    ) -> Vec<SecurityViolation> {
+        let rate_limit_key = crate::graphql::context::rate_limit_key(Some(&claims.sub), client_ip);
+        let quota = self.config.rate_limit.user_quotas.for_role(&claims.role);
+        self.inspect(
+            request,
+            client_ip,
+            &rate_limit_key,
+            quota,
+            true,
+            trusted_role,
+        )
+        .await
+    }
+
+    /// Run all security checks, charging the rate limit to `rate_limit_key` under `quota`
+    ///
+    /// `role` is the caller's session-checked role, used for introspection trust.
+    async fn inspect(
+        &self,
+        request: &Request,
+        client_ip: &str,
+        rate_limit_key: &str,
+        quota: rate_limit::RateLimitQuota,
+        authenticated: bool,
+        role: Option<&UserRole>,
+    ) -> Vec<SecurityViolation> {
+        let query = &request.query;
+        let timestamp = chrono::Utc::now();
+        let mut violations = Vec::new();
 
         // 1. Rate limiting check
         if self.config.rate_limit.enabled {
-            if let Err(e) = self.rate_limiter.check_rate_limit(client_ip).await {
-                error!("Rate limit exceeded for IP {}: {}", client_ip, e);
-                errors.push(ServerError::new(
-                    "Rate limit exceeded. Please try again later.",
-                    None,
-                ));
+            if let Err(e) = self
+                .rate_limiter
+                .check_rate_limit_with_quota(rate_limit_key, quota)
+                .await
+            {
+                error!("Rate limit exceeded for {}: {}", rate_limit_key, e);
+                let status = self
+                    .rate_limiter
+                    .get_rate_limit_status(rate_limit_key)
+                    .await;
+                violations.push(SecurityViolation {
+                    reason: BlockReason::RateLimit,
+                    event: SecurityEvent::RateLimitExceeded {
+                        client_ip: client_ip.to_string(),
+                        requests_per_minute: status.requests_per_minute,
+                        timestamp,
+                    },
+                    error: ServerError::new("Rate limit exceeded. Please try again later.", None),
+                });
             }
         }
 
         // 2. Query size check
-        if let Err(e) = self.query_analyzer.validate_query_size(&request.query) {
+        if let Err(e) = self.query_analyzer.validate_query_size(query) {
             warn!("Query size exceeded: {}", e);
-            errors.push(ServerError::new(
-                "Query too large. Please reduce the query size.",
-                None,
-            ));
+            violations.push(SecurityViolation {
+                reason: BlockReason::Size,
+                event: SecurityEvent::QuerySizeExceeded {
+                    client_ip: client_ip.to_string(),
+                    size: query.len(),
+                    max_size: self.config.max_query_size,
+                    timestamp,
+                },
+                error: ServerError::new("Query too large. Please reduce the query size.", None),
+            });
         }
 
         // 3. Query depth check
-        if let Err(e) = self.depth_limiter.validate_depth(&request.query) {
+        if let Err(e) = self.depth_limiter.validate_depth(query) {
             warn!("Query depth exceeded: {}", e);
-            errors.push(ServerError::new(
-                "Query too deep. Please reduce the nesting level.",
-                None,
-            ));
+            violations.push(SecurityViolation {
+                reason: BlockReason::Depth,
+                event: SecurityEvent::DepthExceeded {
+                    client_ip: client_ip.to_string(),
+                    depth: self.depth_limiter.calculate_depth(query).unwrap_or(0),
+                    max_depth: self.config.max_depth,
+                    query: event_query(query),
+                    timestamp,
+                },
+                error: ServerError::new("Query too deep. Please reduce the nesting level.", None),
+            });
         }
 
         // 4. Query complexity check
-        if let Err(e) = self.complexity_analyzer.validate_complexity(&request.query) {
+        if let Err(e) = self.complexity_analyzer.validate_complexity(query) {
             warn!("Query complexity exceeded: {}", e);
-            errors.push(ServerError::new(
-                "Query too complex. Please simplify the query.",
-                None,
-            ));
+            violations.push(SecurityViolation {
+                reason: BlockReason::Complexity,
+                event: SecurityEvent::ComplexityExceeded {
+                    client_ip: client_ip.to_string(),
+                    complexity: self
+                        .complexity_analyzer
+                        .calculate_complexity(query)
+                        .unwrap_or(0),
+                    max_complexity: self.config.max_complexity,
+                    query: event_query(query),
+                    timestamp,
+                },
+                error: ServerError::new("Query too complex. Please simplify the query.", None),
+            });
         }
 
         // 5. Introspection protection
         if let Err(e) = self
             .introspection_protector
-            .validate_introspection(&request.query)
+            .validate_introspection_for(query, role)
         {
             warn!("Introspection query blocked: {}", e);
-            errors.push(ServerError::new(
-                "Introspection queries are not allowed.",
-                None,
-            ));
+            violations.push(SecurityViolation {
+                reason: BlockReason::Introspection,
+                event: SecurityEvent::IntrospectionBlocked {
+                    client_ip: client_ip.to_string(),
+                    query: event_query(query),
+                    timestamp,
+                },
+                error: ServerError::new("Introspection queries are not allowed.", None),
+            });
         }
 
         // 6. Query filtering (whitelist/blacklist)
-        if let Err(e) = self.query_filter.validate_query(&request.query) {
+        let filtered = if self.introspection_protector.is_trusted(role) {
+            self.query_filter.validate_trusted_query(query)
+        } else {
+            self.query_filter.validate_query(query)
+        };
+        if let Err(e) = filtered {
             warn!("Query filtered: {}", e);
-            errors.push(ServerError::new(
-                "Query not allowed by security policy.",
-                None,
-            ));
+            violations.push(SecurityViolation {
+                reason: BlockReason::Filtered,
+                event: SecurityEvent::QueryFiltered {
+                    client_ip: client_ip.to_string(),
+                    query: event_query(query),
+                    reason: e,
+                    timestamp,
+                },
+                error: ServerError::new("Query not allowed by security policy.", None),
+            });
         }
 
-        if errors.is_empty() {
-            Ok(())
+        self.record_metrics(query, &violations, authenticated);
+        violations
+    }
+
+    /// Fold the outcome of a checked request into the running metrics
+    fn record_metrics(&self, query: &str, violations: &[SecurityViolation], authenticated: bool) {
+        let mut metrics = self
+            .metrics
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if authenticated {
+            metrics.record_user_request(
+                violations
+                    .iter()
+                    .any(|violation| violation.reason == BlockReason::RateLimit),
+            );
+        }
+
+        if violations.is_empty() {
+            let complexity = self
+                .complexity_analyzer
+                .calculate_complexity(query)
+                .unwrap_or(0);
+            let depth = self.depth_limiter.calculate_depth(query).unwrap_or(0);
+            metrics.update_request(complexity, depth, query.len());
         } else {
-            Err(errors)
+            metrics.total_requests += 1;
+            for violation in violations {
+                metrics.record_blocked(violation.reason);
+            }
         }
     }
 
+    /// Snapshot of the security metrics collected by this middleware
+    pub fn metrics(&self) -> SecurityMetrics {
+        self.metrics
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
     /// Get the current security configuration
     pub fn config(&self) -> &SecurityConfig {
         &self.config
@@ -259,8 +442,10 @@ impl SecurityMiddleware {
             enabled: config.rate_limit.enabled,
         });
         self.query_analyzer = query_analysis::QueryAnalyzer::new(config.max_query_size);
-        self.introspection_protector =
-            introspection::IntrospectionProtector::new(config.protect_introspection);
+        self.introspection_protector = introspection::IntrospectionProtector::with_trusted_roles(
+            config.protect_introspection,
+            config.introspection_roles.clone(),
+        );
         self.timeout_manager = timeout::TimeoutManager::new(config.query_timeout);
         self.query_filter = whitelist::QueryFilter::new(whitelist::QueryFilterConfig {
             enable_whitelist: config.query_filter.enable_whitelist,
@@ -321,10 +506,120 @@ pub enum SecurityEvent {
     },
 }
 
+impl SecurityEvent {
+    /// Event type as stored in `security_events.event_type`
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            SecurityEvent::RateLimitExceeded { .. } => "rate_limit_exceeded",
+            SecurityEvent::ComplexityExceeded { .. } => "complexity_exceeded",
+            SecurityEvent::DepthExceeded { .. } => "depth_exceeded",
+            SecurityEvent::QuerySizeExceeded { .. } => "query_size_exceeded",
+            SecurityEvent::IntrospectionBlocked { .. } => "introspection_blocked",
+            SecurityEvent::QueryFiltered { .. } => "query_filtered",
+        }
+    }
+
+    /// Client the offending request came from
+    pub fn client_ip(&self) -> &str {
+        match self {
+            SecurityEvent::RateLimitExceeded { client_ip, .. }
+            | SecurityEvent::ComplexityExceeded { client_ip, .. }
+            | SecurityEvent::DepthExceeded { client_ip, .. }
+            | SecurityEvent::QuerySizeExceeded { client_ip, .. }
+            | SecurityEvent::IntrospectionBlocked { client_ip, .. }
+            | SecurityEvent::QueryFiltered { client_ip, .. } => client_ip,
+        }
+    }
+
+    /// How serious the event is
+    ///
+    /// Exceeding a limit is medium; exceeding it more than twice over looks
+    /// deliberate and is high. Blocked introspection is routine and low.
+    pub fn severity(&self) -> monitoring::EventSeverity {
+        use monitoring::EventSeverity;
+
+        let escalate = |value: usize, limit: usize| {
+            if value > limit * 2 {
+                EventSeverity::High
+            } else {
+                EventSeverity::Medium
+            }
+        };
+
+        match self {
+            SecurityEvent::RateLimitExceeded { .. } => EventSeverity::Medium,
+            SecurityEvent::ComplexityExceeded {
+                complexity,
+                max_complexity,
+                ..
+            } => escalate(*complexity as usize, *max_complexity as usize),
+            SecurityEvent::DepthExceeded {
+                depth, max_depth, ..
+            } => escalate(*depth as usize, *max_depth as usize),
+            SecurityEvent::QuerySizeExceeded { size, max_size, .. } => escalate(*size, *max_size),
+            SecurityEvent::IntrospectionBlocked { .. } => EventSeverity::Low,
+            SecurityEvent::QueryFiltered { .. } => EventSeverity::Medium,
+        }
+    }
+
+    /// One-line summary for admins
+    pub fn description(&self) -> String {
+        match self {
+            SecurityEvent::RateLimitExceeded {
+                requests_per_minute,
+                ..
+            } => format!(
+                "Rate limit of {} requests per minute exceeded",
+                requests_per_minute
+            ),
+            SecurityEvent::ComplexityExceeded {
+                complexity,
+                max_complexity,
+                ..
+            } => format!(
+                "Query complexity {} exceeds limit of {}",
+                complexity, max_complexity
+            ),
+            SecurityEvent::DepthExceeded {
+                depth, max_depth, ..
+            } => format!("Query depth {} exceeds limit of {}", depth, max_depth),
+            SecurityEvent::QuerySizeExceeded { size, max_size, .. } => {
+                format!("Query size of {} bytes exceeds limit of {}", size, max_size)
+            }
+            SecurityEvent::IntrospectionBlocked { .. } => "Introspection query blocked".to_string(),
+            SecurityEvent::QueryFiltered { reason, .. } => format!("Query filtered: {}", reason),
+        }
+    }
+}
+
+/// A security check that rejected a request
+#[derive(Debug, Clone)]
+pub struct SecurityViolation {
+    /// Which check failed
+    pub reason: BlockReason,
+    /// Event to forward to monitoring
+    pub event: SecurityEvent,
+    /// Error returned to the client
+    pub error: ServerError,
+}
+
+/// Maximum number of query characters copied into a security event
+const MAX_EVENT_QUERY_LENGTH: usize = 2000;
+
+/// Query text to attach to a security event, truncated to keep events small
+fn event_query(query: &str) -> String {
+    query.chars().take(MAX_EVENT_QUERY_LENGTH).collect()
+}
+
 /// Security event handler for monitoring and alerting
 pub trait SecurityEventHandler: Send + Sync {
     /// Handle a security event
     fn handle_event(&self, event: SecurityEvent);
+
+    /// Handle a security event raised by an authenticated user's request
+    fn handle_user_event(&self, event: SecurityEvent, _claims: &Claims) {
+        self.handle_event(event);
+    }
 }
 
 /// Default security event handler that logs events
@@ -414,6 +709,10 @@ pub struct SecurityMetrics {
     pub total_requests: u64,
     /// Requests blocked by rate limiting
     pub rate_limited_requests: u64,
+    /// Requests checked against a user's own rate limit bucket
+    pub user_requests: u64,
+    /// Authenticated requests blocked by their user's rate limit
+    pub user_rate_limited_requests: u64,
     /// Requests blocked by complexity
     pub complexity_blocked_requests: u64,
     /// Requests blocked by depth
@@ -445,6 +744,14 @@ impl SecurityMetrics {
         self.average_size = (self.average_size * (total - 1.0) + size as f64) / total;
     }
 
+    /// Record a request from an authenticated user
+    pub fn record_user_request(&mut self, rate_limited: bool) {
+        self.user_requests += 1;
+        if rate_limited {
+            self.user_rate_limited_requests += 1;
+        }
+    }
+
     /// Record a blocked request
     pub fn record_blocked(&mut self, reason: BlockReason) {
         match reason {
@@ -459,7 +766,7 @@ impl SecurityMetrics {
 }
 
 /// Reasons for blocking a request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockReason {
     RateLimit,
     Complexity,
@@ -468,3 +775,17 @@ pub enum BlockReason {
     Introspection,
     Filtered,
 }
+
+impl BlockReason {
+    /// Stable label for logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockReason::RateLimit => "rate_limit",
+            BlockReason::Complexity => "complexity",
+            BlockReason::Depth => "depth",
+            BlockReason::Size => "size",
+            BlockReason::Introspection => "introspection",
+            BlockReason::Filtered => "filtered",
+        }
+    }
+}
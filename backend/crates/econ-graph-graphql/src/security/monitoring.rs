@@ -182,6 +182,18 @@ pub enum EventSeverity {
     Critical,
 }
 
+impl EventSeverity {
+    /// Severity as stored in `security_events.severity`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSeverity::Low => "low",
+            EventSeverity::Medium => "medium",
+            EventSeverity::High => "high",
+            EventSeverity::Critical => "critical",
+        }
+    }
+}
+
 /// Security alert
 #[derive(Debug, Clone)]
 pub struct SecurityAlert {
@@ -346,38 +358,7 @@ impl SecurityMonitor {
 
     /// Determine event severity
     fn determine_severity(&self, event: &SecurityEvent) -> EventSeverity {
-        match event {
-            SecurityEvent::RateLimitExceeded { .. } => EventSeverity::Medium,
-            SecurityEvent::ComplexityExceeded {
-                complexity,
-                max_complexity,
-                ..
-            } => {
-                if *complexity > *max_complexity * 2 {
-                    EventSeverity::High
-                } else {
-                    EventSeverity::Medium
-                }
-            }
-            SecurityEvent::DepthExceeded {
-                depth, max_depth, ..
-            } => {
-                if *depth > *max_depth * 2 {
-                    EventSeverity::High
-                } else {
-                    EventSeverity::Medium
-                }
-            }
-            SecurityEvent::QuerySizeExceeded { size, max_size, .. } => {
-                if *size > *max_size * 2 {
-                    EventSeverity::High
-                } else {
-                    EventSeverity::Medium
-                }
-            }
-            SecurityEvent::IntrospectionBlocked { .. } => EventSeverity::Low,
-            SecurityEvent::QueryFiltered { .. } => EventSeverity::Medium,
-        }
+        event.severity()
     }
 
     /// Extract metadata from event
@@ -760,6 +741,8 @@ impl SecurityMonitor {
         SecurityMetrics {
             total_requests: 0, // Would be tracked separately
             rate_limited_requests: rate_limit_violations as u64,
+            user_requests: 0,
+            user_rate_limited_requests: 0,
             complexity_blocked_requests: complexity_violations as u64,
             depth_blocked_requests: 0,
             size_blocked_requests: 0,
@@ -28,7 +28,12 @@
 //! - `requests_per_hour`: Maximum requests per hour per IP
 //! - `requests_per_day`: Maximum requests per day per IP
 //! - `enabled`: Whether rate limiting is enabled
+//!
+//! Authenticated users are charged to their own bucket with a quota chosen
+//! by the role in their token claims (see [`RoleQuotas`]), so accounts behind
+//! one NAT address are limited independently.
 
+use econ_graph_core::auth_models::UserRole;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -48,6 +53,81 @@ pub struct RateLimitConfig {
     pub enabled: bool,
 }
 
+impl RateLimitConfig {
+    /// Quota applied to keys checked without an explicit quota
+    pub fn quota(&self) -> RateLimitQuota {
+        RateLimitQuota {
+            requests_per_minute: self.requests_per_minute,
+            requests_per_hour: self.requests_per_hour,
+            requests_per_day: self.requests_per_day,
+        }
+    }
+}
+
+/// Request limits of a single rate limit bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    /// Maximum requests per minute
+    pub requests_per_minute: u32,
+    /// Maximum requests per hour
+    pub requests_per_hour: u32,
+    /// Maximum requests per day
+    pub requests_per_day: u32,
+}
+
+/// Per-user quotas for authenticated requests, by role
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleQuotas {
+    /// Quota for administrators
+    pub admin: RateLimitQuota,
+    /// Quota for analysts
+    pub analyst: RateLimitQuota,
+    /// Quota for viewers
+    pub viewer: RateLimitQuota,
+}
+
+impl RoleQuotas {
+    /// Quota for a user with the given role
+    pub fn for_role(&self, role: &UserRole) -> RateLimitQuota {
+        match role {
+            UserRole::Admin => self.admin,
+            UserRole::Analyst => self.analyst,
+            UserRole::Viewer => self.viewer,
+        }
+    }
+
+    /// Quota for a role, mutably, for configuration overrides
+    pub fn for_role_mut(&mut self, role: &UserRole) -> &mut RateLimitQuota {
+        match role {
+            UserRole::Admin => &mut self.admin,
+            UserRole::Analyst => &mut self.analyst,
+            UserRole::Viewer => &mut self.viewer,
+        }
+    }
+}
+
+impl Default for RoleQuotas {
+    fn default() -> Self {
+        Self {
+            admin: RateLimitQuota {
+                requests_per_minute: 600,
+                requests_per_hour: 12000,
+                requests_per_day: 100000,
+            },
+            analyst: RateLimitQuota {
+                requests_per_minute: 300,
+                requests_per_hour: 6000,
+                requests_per_day: 50000,
+            },
+            viewer: RateLimitQuota {
+                requests_per_minute: 120,
+                requests_per_hour: 2000,
+                requests_per_day: 20000,
+            },
+        }
+    }
+}
+
 /// Rate limit entry for tracking requests
 #[derive(Debug, Clone)]
 struct RateLimitEntry {
@@ -129,8 +209,20 @@ impl RateLimiter {
         }
     }
 
-    /// Check if a request is allowed for the given IP
-    pub async fn check_rate_limit(&self, client_ip: &str) -> Result<(), String> {
+    /// Check if a request is allowed for the given key under the configured quota
+    pub async fn check_rate_limit(&self, key: &str) -> Result<(), String> {
+        self.check_rate_limit_with_quota(key, self.config.quota())
+            .await
+    }
+
+    /// Check if a request is allowed for the given key under `quota`
+    ///
+    /// Used for authenticated users, whose quota depends on their role.
+    pub async fn check_rate_limit_with_quota(
+        &self,
+        key: &str,
+        quota: RateLimitQuota,
+    ) -> Result<(), String> {
         if !self.config.enabled {
             return Ok(());
         }
@@ -140,7 +232,7 @@ impl RateLimiter {
 
         let mut entries = self.entries.write().await;
         let entry = entries
-            .entry(client_ip.to_string())
+            .entry(key.to_string())
             .or_insert_with(RateLimitEntry::new);
 
         // Add the current request
@@ -150,36 +242,36 @@ impl RateLimiter {
         let (requests_per_minute, requests_per_hour, requests_per_day) = entry.cleanup_and_count();
 
         // Check rate limits
-        if requests_per_minute > self.config.requests_per_minute {
+        if requests_per_minute > quota.requests_per_minute {
             return Err(format!(
                 "Rate limit exceeded: {} requests per minute (limit: {})",
-                requests_per_minute, self.config.requests_per_minute
+                requests_per_minute, quota.requests_per_minute
             ));
         }
 
-        if requests_per_hour > self.config.requests_per_hour {
+        if requests_per_hour > quota.requests_per_hour {
             return Err(format!(
                 "Rate limit exceeded: {} requests per hour (limit: {})",
-                requests_per_hour, self.config.requests_per_hour
+                requests_per_hour, quota.requests_per_hour
             ));
         }
 
-        if requests_per_day > self.config.requests_per_day {
+        if requests_per_day > quota.requests_per_day {
             return Err(format!(
                 "Rate limit exceeded: {} requests per day (limit: {})",
-                requests_per_day, self.config.requests_per_day
+                requests_per_day, quota.requests_per_day
             ));
         }
 
         debug!(
-            "Rate limit check passed for IP {}: {}/{} per minute, {}/{} per hour, {}/{} per day",
-            client_ip,
+            "Rate limit check passed for {}: {}/{} per minute, {}/{} per hour, {}/{} per day",
+            key,
             requests_per_minute,
-            self.config.requests_per_minute,
+            quota.requests_per_minute,
             requests_per_hour,
-            self.config.requests_per_hour,
+            quota.requests_per_hour,
             requests_per_day,
-            self.config.requests_per_day
+            quota.requests_per_day
         );
 
         Ok(())
@@ -426,4 +518,51 @@ mod tests {
         limiter.reset_rate_limit(client_ip).await;
         assert!(limiter.check_rate_limit(client_ip).await.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_role_quotas_per_user() {
+        // REQUIREMENT: Per-user rate limits with quotas by role
+        // PURPOSE: Verify each user bucket is limited by its own role's quota
+        // This ensures a busy viewer cannot exhaust an analyst's budget and roles get their own limits
+
+        let config = RateLimitConfig {
+            requests_per_minute: 1,
+            requests_per_hour: 100,
+            requests_per_day: 1000,
+            enabled: true,
+        };
+        let limiter = RateLimiter::new(config);
+        let mut quotas = RoleQuotas::default();
+        quotas.for_role_mut(&UserRole::Viewer).requests_per_minute = 2;
+        quotas.for_role_mut(&UserRole::Analyst).requests_per_minute = 4;
+
+        let viewer = quotas.for_role(&UserRole::Viewer);
+        let analyst = quotas.for_role(&UserRole::Analyst);
+
+        for _ in 0..2 {
+            assert!(limiter
+                .check_rate_limit_with_quota("user:viewer", viewer)
+                .await
+                .is_ok());
+        }
+        assert!(limiter
+            .check_rate_limit_with_quota("user:viewer", viewer)
+            .await
+            .is_err());
+
+        for _ in 0..4 {
+            assert!(limiter
+                .check_rate_limit_with_quota("user:analyst", analyst)
+                .await
+                .is_ok());
+        }
+        assert!(limiter
+            .check_rate_limit_with_quota("user:analyst", analyst)
+            .await
+            .is_err());
+
+        // Anonymous keys keep the configured per-IP quota
+        assert!(limiter.check_rate_limit("ip:203.0.113.7").await.is_ok());
+        assert!(limiter.check_rate_limit("ip:203.0.113.7").await.is_err());
+    }
 }
@@ -38,7 +38,8 @@ use async_graphql::{Request, Response, ServerError};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
-use crate::graphql::schema::{create_schema, GraphQLContext};
+use crate::graphql::context::GraphQLContext;
+use crate::graphql::schema::create_schema;
 use crate::security::{
     BlockReason, LoggingSecurityEventHandler, SecurityConfig, SecurityEvent, SecurityEventHandler,
     SecurityMetrics, SecurityMiddleware,
@@ -129,8 +130,10 @@ impl SecureGraphQLServer {
             }
         }
 
-        // Create schema with security context
+        // Create schema with an anonymous request context
         let schema = create_schema((*self.pool).clone());
+        let context = GraphQLContext::new(&self.pool, None).with_client_ip(client_ip);
+        let request = request.data(Arc::new(context));
 
         // Execute GraphQL request with timeout
         let execution_result = self
@@ -55,9 +55,6 @@ impl Default for QueryFilterConfig {
             enable_blacklist: true,
             whitelist_patterns: Vec::new(),
             blacklist_patterns: vec![
-                "__schema".to_string(),
-                "__type".to_string(),
-                "__typename".to_string(),
                 "system".to_string(),
                 "admin".to_string(),
                 "root".to_string(),
@@ -150,9 +147,22 @@ impl QueryFilter {
 
     /// Validate a query against the filter
     pub fn validate_query(&self, query: &str) -> Result<(), String> {
+        self.validate(query, false)
+    }
+
+    /// Validate a query from a caller trusted to introspect
+    ///
+    /// Patterns and rules naming introspection fields (starting with `__`)
+    /// are skipped; whether the caller may introspect is decided by the
+    /// [`IntrospectionProtector`](super::introspection::IntrospectionProtector).
+    pub fn validate_trusted_query(&self, query: &str) -> Result<(), String> {
+        self.validate(query, true)
+    }
+
+    fn validate(&self, query: &str, allow_introspection: bool) -> Result<(), String> {
         // Check blacklist first
         if self.config.enable_blacklist {
-            self.check_blacklist(query)?;
+            self.check_blacklist(query, allow_introspection)?;
         }
 
         // Check whitelist
@@ -161,15 +171,18 @@ impl QueryFilter {
         }
 
         // Check custom rules
-        self.check_rules(query)?;
+        self.check_rules(query, allow_introspection)?;
 
         debug!("Query passed all filter checks");
         Ok(())
     }
 
     /// Check query against blacklist
-    fn check_blacklist(&self, query: &str) -> Result<(), String> {
+    fn check_blacklist(&self, query: &str, allow_introspection: bool) -> Result<(), String> {
         for pattern in &self.config.blacklist_patterns {
+            if allow_introspection && pattern.starts_with("__") {
+                continue;
+            }
             if self.matches_pattern(query, pattern) {
                 return Err(format!("Query blocked by blacklist pattern: {}", pattern));
             }
@@ -193,13 +206,13 @@ impl QueryFilter {
     }
 
     /// Check query against custom rules
-    fn check_rules(&self, query: &str) -> Result<(), String> {
+    fn check_rules(&self, query: &str, allow_introspection: bool) -> Result<(), String> {
         // Sort rules by priority (higher priority first)
         let mut sorted_rules = self.rules.clone();
         sorted_rules.sort_by(|a, b| b.priority.cmp(&a.priority));
 
         for rule in sorted_rules {
-            if !rule.enabled {
+            if !rule.enabled || (allow_introspection && rule.pattern.starts_with("__")) {
                 continue;
             }
 
@@ -17,6 +17,11 @@
 //! - Output types must be optimized for GraphQL serialization
 //! - All types must have comprehensive documentation
 
+use crate::graphql::context::data_loaders;
+use crate::graphql::data_access::{require_source_access_by_id, DataReader};
+use crate::graphql::global_analysis::{CountryCorrelationType, LeadingIndicatorType};
+use crate::graphql::pagination::encode_cursor;
+use crate::graphql::public_tier::PublicTierPolicy;
 use crate::imports::*;
 
 /// GraphQL representation of an economic series
@@ -124,6 +129,7 @@ impl EconomicSeriesType {
     ) -> Result<Vec<DataPointType>> {
         let pool = ctx.data::<DatabasePool>()?;
         let series_uuid = Uuid::parse_str(&self.id)?;
+        require_source_access_by_id(ctx, Uuid::parse_str(&self.source_id)?).await?;
 
         use diesel::{ExpressionMethods, QueryDsl};
         use diesel_async::RunQueryDsl;
@@ -139,29 +145,62 @@ impl EconomicSeriesType {
 
         let limited_points = data_points.into_iter().map(DataPointType::from).collect();
 
-        Ok(limited_points)
+        Ok(PublicTierPolicy::for_request(ctx).data_points(limited_points))
     }
 
-    /// Get data point count using direct database query
+    /// Get data point count from the catalog statistics
     async fn data_point_count(&self, ctx: &Context<'_>) -> Result<i32> {
-        let pool = ctx.data::<DatabasePool>()?;
         let series_uuid = Uuid::parse_str(&self.id)?;
 
-        use diesel::{ExpressionMethods, QueryDsl};
-        use diesel_async::RunQueryDsl;
-        use econ_graph_core::schema::data_points::dsl;
+        Ok(data_loaders(ctx)?
+            .data_point_count_loader
+            .load(series_uuid)
+            .await)
+    }
 
-        let mut conn = pool.get().await?;
-        let count = dsl::data_points
-            .filter(dsl::series_id.eq(series_uuid))
-            .count()
-            .get_result::<i64>(&mut conn)
-            .await?;
+    /// Data points with filters, paginated by cursor
+    ///
+    /// Prefer this over `dataPoints` for long series: `first` defaults to 1000
+    /// and is capped at 10000.
+    async fn data_points_connection(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<DataFilterInput>,
+        transformation: Option<DataTransformationType>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<DataPointConnection> {
+        let pool = ctx.data::<DatabasePool>()?;
+        let filter = filter.unwrap_or_default();
+        require_source_access_by_id(ctx, Uuid::parse_str(&self.source_id)?).await?;
+
+        let params = models::DataQueryParams {
+            series_id: Uuid::parse_str(&self.id)?,
+            start_date: filter.start_date,
+            end_date: filter.end_date,
+            original_only: filter.original_only,
+            latest_revision_only: filter.latest_revision_only,
+            exclude_corrections: filter.exclude_corrections,
+            limit: None,
+            offset: None,
+        };
 
-        Ok(count as i32)
+        let connection = crate::graphql::pagination::data_point_connection(
+            pool,
+            params,
+            transformation,
+            first,
+            after,
+        )
+        .await?;
+        Ok(PublicTierPolicy::for_request(ctx).data_point_connection(connection))
     }
 
-    /// Fetch data points with filters using a custom DataLoader
+    /// Fetch data points with filters
+    ///
+    /// The series is read from the database in date-ordered batches, but the
+    /// whole filtered window is still returned in one response, so memory
+    /// grows with the window. Use `dataPointsConnection` to page long series.
     async fn data_points(
         &self,
         ctx: &Context<'_>,
@@ -169,51 +208,79 @@ impl EconomicSeriesType {
         transformation: Option<DataTransformationType>,
     ) -> Result<Vec<DataPointType>> {
         use econ_graph_core::database::DatabasePool;
+        use futures::TryStreamExt;
 
         let pool = ctx.data::<DatabasePool>()?;
         let series_uuid = Uuid::parse_str(&self.id)?;
+        require_source_access_by_id(ctx, Uuid::parse_str(&self.source_id)?).await?;
 
         let filter = filter.unwrap_or_default();
+        let cache = shared_data_point_cache();
+        let cache_key = DataPointCacheKey::new(series_uuid, filter.start_date, filter.end_date)
+            .with_original_only(filter.original_only.unwrap_or(false));
 
-        // Query data points directly from database
-        use diesel::{ExpressionMethods, QueryDsl};
-        use diesel_async::RunQueryDsl;
-        use econ_graph_core::schema::data_points::dsl;
-
-        let mut conn = pool.get().await?;
-        let mut query = dsl::data_points
-            .filter(dsl::series_id.eq(series_uuid))
-            .into_boxed();
-
-        if let Some(start_date) = filter.start_date {
-            query = query.filter(dsl::date.ge(start_date));
-        }
+        let points = match cache.get(&cache_key) {
+            Some(points) => points,
+            None => {
+                // Date range and release filters are pushed down into the batched query
+                let mut batches = std::pin::pin!(models::DataPoint::stream_by_series(
+                    pool,
+                    series_uuid,
+                    filter.start_date,
+                    filter.end_date,
+                    filter.original_only.unwrap_or(false),
+                    models::DATA_POINT_STREAM_BATCH_SIZE,
+                ));
 
-        if let Some(end_date) = filter.end_date {
-            query = query.filter(dsl::date.le(end_date));
-        }
-
-        if filter.original_only.unwrap_or(false) {
-            query = query.filter(dsl::is_original_release.eq(true));
-        }
+                // Transformations such as YoY change need the full window at once
+                if transformation.is_some() {
+                    let mut data_points = Vec::new();
+                    while let Some(batch) = batches.try_next().await? {
+                        data_points.extend(batch);
+                    }
+                    cache.insert(cache_key.clone(), data_points)
+                } else {
+                    // Each batch's rows are converted and dropped as it arrives, but the
+                    // converted window is returned whole; only small windows are cached
+                    let mut cacheable = Some(Vec::new());
+                    let mut result = Vec::new();
+                    while let Some(batch) = batches.try_next().await? {
+                        if let Some(buffer) = cacheable.as_mut() {
+                            if buffer.len() + batch.len() <= cache.max_entry_points() {
+                                buffer.extend(batch.iter().cloned());
+                            } else {
+                                cacheable = None;
+                            }
+                        }
+                        result.extend(batch.into_iter().map(DataPointType::from));
+                    }
 
-        let data_points = query
-            .order(dsl::date.asc())
-            .load::<models::DataPoint>(&mut conn)
-            .await?;
+                    if let Some(points) = cacheable {
+                        cache.insert(cache_key, points);
+                    }
+                    return Ok(PublicTierPolicy::for_request(ctx).data_points(result));
+                }
+            }
+        };
 
-        // Apply transformation if requested
         if let Some(transformation) = transformation {
-            // Transform the data points using the GraphQL transformation function
-            let transformed =
-                crate::graphql::query::apply_data_transformation(data_points, transformation)
-                    .await?;
-
-            // Convert transformed data points to GraphQL types
-            Ok(transformed.into_iter().map(DataPointType::from).collect())
-        } else {
-            Ok(data_points.into_iter().map(DataPointType::from).collect())
+            let transformed = crate::graphql::query::apply_cached_transformation(
+                cache_key,
+                &points,
+                transformation,
+            )
+            .await?;
+            return Ok(PublicTierPolicy::for_request(ctx).data_points(
+                transformed
+                    .iter()
+                    .cloned()
+                    .map(DataPointType::from)
+                    .collect(),
+            ));
         }
+
+        Ok(PublicTierPolicy::for_request(ctx)
+            .data_points(points.iter().cloned().map(DataPointType::from).collect()))
     }
 }
 
@@ -261,11 +328,13 @@ impl From<search::SeriesSearchResult> for EconomicSeriesType {
 
 /// GraphQL representation of a data point
 #[derive(SimpleObject, Clone)]
-#[graphql(name = "DataPoint")]
+#[graphql(name = "DataPoint", complex)]
 pub struct DataPointType {
     pub id: ID,
     pub series_id: ID,
     pub date: NaiveDate,
+    /// Exact value; served through the `value` resolver
+    #[graphql(skip)]
     pub value: Option<BigDecimal>,
     pub revision_date: NaiveDate,
     pub is_original_release: bool,
@@ -273,6 +342,14 @@ pub struct DataPointType {
     pub updated_at: DateTime<Utc>,
 }
 
+#[ComplexObject]
+impl DataPointType {
+    /// Observed value; rounded for anonymous users (see the public API tier)
+    async fn value(&self, ctx: &Context<'_>) -> Option<BigDecimal> {
+        PublicTierPolicy::for_request(ctx).optional_value(&self.value)
+    }
+}
+
 impl From<DataPoint> for DataPointType {
     fn from(data_point: DataPoint) -> Self {
         Self {
@@ -288,6 +365,86 @@ impl From<DataPoint> for DataPointType {
     }
 }
 
+/// GraphQL representation of a manual data point correction
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "DataPointCorrection")]
+pub struct DataPointCorrectionType {
+    pub id: ID,
+    /// Revision written by the correction
+    pub data_point_id: ID,
+    /// Revision that was corrected
+    pub corrected_data_point_id: Option<ID>,
+    pub series_id: ID,
+    pub reason: String,
+    pub corrected_by: Option<ID>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<models::DataPointCorrection> for DataPointCorrectionType {
+    fn from(correction: models::DataPointCorrection) -> Self {
+        Self {
+            id: ID::from(correction.id.to_string()),
+            data_point_id: ID::from(correction.data_point_id.to_string()),
+            corrected_data_point_id: correction
+                .corrected_data_point_id
+                .map(|id| ID::from(id.to_string())),
+            series_id: ID::from(correction.series_id.to_string()),
+            reason: correction.reason,
+            corrected_by: correction.corrected_by.map(|id| ID::from(id.to_string())),
+            created_at: correction.created_at,
+        }
+    }
+}
+
+/// One thing done to a value between download and storage
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "ProcessingStep")]
+pub struct ProcessingStepType {
+    /// Step name, e.g. "unit_normalization"
+    pub name: String,
+    /// What the step did to this value, when it changed or flagged it
+    pub detail: Option<String>,
+}
+
+/// Where a data point or SEC fact came from
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "Provenance")]
+pub struct ProvenanceType {
+    /// Data source name, e.g. "FRED"
+    pub source: String,
+    /// URL the value was fetched from, without credentials
+    pub source_url: Option<String>,
+    pub crawled_at: Option<DateTime<Utc>>,
+    pub crawl_attempt_id: Option<ID>,
+    /// Filing an SEC fact was parsed from
+    pub statement_id: Option<ID>,
+    /// Steps applied during ingestion, in order
+    pub processing_steps: Vec<ProcessingStepType>,
+    /// Whether lineage was recorded at ingestion; inferred provenance has no steps
+    pub recorded: bool,
+}
+
+impl From<Provenance> for ProvenanceType {
+    fn from(provenance: Provenance) -> Self {
+        Self {
+            source: provenance.source,
+            source_url: provenance.source_url,
+            crawled_at: provenance.crawled_at,
+            crawl_attempt_id: provenance.crawl_attempt_id.map(ID::from),
+            statement_id: provenance.statement_id.map(ID::from),
+            processing_steps: provenance
+                .processing_steps
+                .into_iter()
+                .map(|step| ProcessingStepType {
+                    name: step.name,
+                    detail: step.detail,
+                })
+                .collect(),
+            recorded: provenance.recorded,
+        }
+    }
+}
+
 /// GraphQL representation of a data source
 #[derive(Clone)]
 pub struct DataSourceType {
@@ -297,6 +454,17 @@ pub struct DataSourceType {
     pub base_url: String,
     pub api_key_required: bool,
     pub rate_limit_per_minute: i32,
+    pub is_visible: bool,
+    pub is_enabled: bool,
+    pub requires_admin_approval: bool,
+    pub crawl_frequency_hours: i32,
+    pub last_crawl_at: Option<DateTime<Utc>>,
+    pub crawl_status: Option<String>,
+    pub api_key_name: Option<String>,
+    pub daily_byte_quota: Option<i64>,
+    pub daily_request_quota: Option<i32>,
+    pub access_policy: DataAccessPolicy,
+    pub fallback_urls: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -327,6 +495,74 @@ impl DataSourceType {
         self.rate_limit_per_minute
     }
 
+    async fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    async fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    async fn requires_admin_approval(&self) -> bool {
+        self.requires_admin_approval
+    }
+
+    async fn crawl_frequency_hours(&self) -> i32 {
+        self.crawl_frequency_hours
+    }
+
+    /// Bytes the crawlers may download per UTC day; null is unlimited
+    async fn daily_byte_quota(&self) -> Option<i64> {
+        self.daily_byte_quota
+    }
+
+    /// Requests the crawlers may send per UTC day; null is unlimited
+    async fn daily_request_quota(&self) -> Option<i32> {
+        self.daily_request_quota
+    }
+
+    /// Mirrors of `baseUrl` the crawlers fail over to, in order
+    async fn fallback_urls(&self) -> Vec<String> {
+        self.fallback_urls.clone()
+    }
+
+    /// Who may read the source's series and observations
+    async fn access_policy(&self) -> DataAccessPolicyType {
+        self.access_policy.into()
+    }
+
+    /// Whether the current user may read the source's series and observations
+    async fn accessible(&self, ctx: &Context<'_>) -> bool {
+        DataReader::of(ctx).may_read(self.access_policy)
+    }
+
+    async fn last_crawl_at(&self) -> Option<DateTime<Utc>> {
+        self.last_crawl_at
+    }
+
+    async fn crawl_status(&self) -> &Option<String> {
+        &self.crawl_status
+    }
+
+    /// Name of the environment variable holding the API key (admin only)
+    async fn api_key_name(&self, ctx: &Context<'_>) -> Result<&Option<String>> {
+        require_admin(ctx)?;
+        Ok(&self.api_key_name)
+    }
+
+    /// Whether an encrypted API key is stored for this source (admin only)
+    ///
+    /// The key itself is never returned.
+    async fn has_stored_api_key(&self, ctx: &Context<'_>) -> Result<bool> {
+        require_admin(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let source_uuid = Uuid::parse_str(&self.id)?;
+
+        Ok(DataSourceCredential::find_by_data_source(pool, source_uuid)
+            .await?
+            .is_some())
+    }
+
     async fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -335,7 +571,7 @@ impl DataSourceType {
         self.updated_at
     }
 
-    /// Fetch all series for this data source using DataLoader
+    /// Series of this data source, paginated by cursor
     async fn series(
         &self,
         ctx: &Context<'_>,
@@ -345,70 +581,26 @@ impl DataSourceType {
         let pool = ctx.data::<DatabasePool>()?;
         let source_uuid = Uuid::parse_str(&self.id)?;
 
-        use diesel::{ExpressionMethods, QueryDsl};
-        use diesel_async::RunQueryDsl;
-        use econ_graph_core::schema::economic_series::dsl;
-
-        let mut conn = pool.get().await?;
-        let all_series = dsl::economic_series
-            .filter(dsl::source_id.eq(source_uuid))
-            .filter(dsl::is_active.eq(true))
-            .select(models::EconomicSeries::as_select())
-            .load::<models::EconomicSeries>(&mut conn)
-            .await?;
-
-        // Apply pagination (simplified - in production you'd want cursor-based pagination)
-        let start_index = after
-            .and_then(|cursor| cursor.parse::<usize>().ok())
-            .unwrap_or(0);
-
-        let end_index = (start_index + first as usize).min(all_series.len());
-        let series_page = all_series[start_index..end_index].to_vec();
+        let params = SeriesSearchParams {
+            query: None,
+            source_id: Some(source_uuid),
+            frequency: None,
+            is_active: Some(true),
+            limit: None,
+            offset: None,
+        };
 
-        let has_next_page = end_index < all_series.len();
-        let has_previous_page = start_index > 0;
-
-        Ok(SeriesConnection {
-            nodes: series_page
-                .into_iter()
-                .map(EconomicSeriesType::from)
-                .collect(),
-            total_count: all_series.len() as i32,
-            page_info: PageInfo {
-                has_next_page,
-                has_previous_page,
-                start_cursor: if start_index > 0 {
-                    Some(start_index.to_string())
-                } else {
-                    None
-                },
-                end_cursor: if has_next_page {
-                    Some(end_index.to_string())
-                } else {
-                    None
-                },
-            },
-        })
+        crate::graphql::pagination::series_connection(pool, params, Some(first), after).await
     }
 
-    /// Get count of active series for this data source
+    /// Get count of active series for this data source from the catalog statistics
     async fn series_count(&self, ctx: &Context<'_>) -> Result<i32> {
-        let pool = ctx.data::<DatabasePool>()?;
         let source_uuid = Uuid::parse_str(&self.id)?;
 
-        use diesel::{ExpressionMethods, QueryDsl};
-        use diesel_async::RunQueryDsl;
-        use econ_graph_core::schema::economic_series::dsl;
-
-        let mut conn = pool.get().await?;
-        let count = dsl::economic_series
-            .filter(dsl::source_id.eq(source_uuid))
-            .filter(dsl::is_active.eq(true))
-            .count()
-            .get_result::<i64>(&mut conn)
-            .await?;
-
-        Ok(count as i32)
+        Ok(data_loaders(ctx)?
+            .series_count_loader
+            .load(source_uuid)
+            .await)
     }
 }
 
@@ -421,12 +613,83 @@ impl From<DataSource> for DataSourceType {
             base_url: source.base_url,
             api_key_required: source.api_key_required,
             rate_limit_per_minute: source.rate_limit_per_minute,
+            is_visible: source.is_visible,
+            is_enabled: source.is_enabled,
+            requires_admin_approval: source.requires_admin_approval,
+            crawl_frequency_hours: source.crawl_frequency_hours,
+            last_crawl_at: source.last_crawl_at,
+            crawl_status: source.crawl_status,
+            api_key_name: source.api_key_name,
+            daily_byte_quota: source.daily_byte_quota,
+            daily_request_quota: source.daily_request_quota,
+            access_policy: source.access_policy,
+            fallback_urls: source.fallback_urls,
             created_at: source.created_at,
             updated_at: source.updated_at,
         }
     }
 }
 
+/// Who may read a data source's series and observations
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "DataAccessPolicy")]
+pub enum DataAccessPolicyType {
+    /// Everyone, including anonymous users
+    Public,
+    /// Signed-in users
+    Registered,
+    /// Users with a premium subscription
+    Premium,
+}
+
+impl From<DataAccessPolicy> for DataAccessPolicyType {
+    fn from(policy: DataAccessPolicy) -> Self {
+        match policy {
+            DataAccessPolicy::Public => Self::Public,
+            DataAccessPolicy::Registered => Self::Registered,
+            DataAccessPolicy::Premium => Self::Premium,
+        }
+    }
+}
+
+impl From<DataAccessPolicyType> for DataAccessPolicy {
+    fn from(policy: DataAccessPolicyType) -> Self {
+        match policy {
+            DataAccessPolicyType::Public => Self::Public,
+            DataAccessPolicyType::Registered => Self::Registered,
+            DataAccessPolicyType::Premium => Self::Premium,
+        }
+    }
+}
+
+/// Subscription a user pays for
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "SubscriptionTier")]
+pub enum SubscriptionTierType {
+    /// Public and registered data sources
+    Free,
+    /// Every data source, including premium ones
+    Premium,
+}
+
+impl From<SubscriptionTier> for SubscriptionTierType {
+    fn from(tier: SubscriptionTier) -> Self {
+        match tier {
+            SubscriptionTier::Free => Self::Free,
+            SubscriptionTier::Premium => Self::Premium,
+        }
+    }
+}
+
+impl From<SubscriptionTierType> for SubscriptionTier {
+    fn from(tier: SubscriptionTierType) -> Self {
+        match tier {
+            SubscriptionTierType::Free => Self::Free,
+            SubscriptionTierType::Premium => Self::Premium,
+        }
+    }
+}
+
 /// Transformed data point for GraphQL responses
 #[derive(SimpleObject, Clone)]
 #[graphql(name = "TransformedDataPoint")]
@@ -439,544 +702,3642 @@ pub struct TransformedDataPointType {
     pub is_original_release: bool,
 }
 
-/// Data transformation enumeration for GraphQL
-#[derive(Enum, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[graphql(name = "DataTransformation")]
-pub enum DataTransformationType {
-    None,
-    YearOverYear,
-    QuarterOverQuarter,
-    MonthOverMonth,
-    PercentChange,
-    LogDifference,
+/// Seasonally adjusted series with its decomposition components
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "SeasonalAdjustment")]
+pub struct SeasonalAdjustmentType {
+    pub series_id: ID,
+    pub frequency: String,
+    pub period: i32,
+    pub method: String,
+    pub computed_at: DateTime<Utc>,
+    pub points: Vec<SeasonalComponentPointType>,
 }
 
-impl From<models::DataTransformation> for DataTransformationType {
-    fn from(transformation: models::DataTransformation) -> Self {
-        match transformation {
-            models::DataTransformation::None => DataTransformationType::None,
-            models::DataTransformation::YearOverYear => DataTransformationType::YearOverYear,
-            models::DataTransformation::QuarterOverQuarter => {
-                DataTransformationType::QuarterOverQuarter
-            }
-            models::DataTransformation::MonthOverMonth => DataTransformationType::MonthOverMonth,
-            models::DataTransformation::PercentChange => DataTransformationType::PercentChange,
-            models::DataTransformation::LogDifference => DataTransformationType::LogDifference,
+/// Single observation of a seasonal decomposition
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "SeasonalComponentPoint")]
+pub struct SeasonalComponentPointType {
+    pub date: NaiveDate,
+    pub observed: f64,
+    pub trend: Option<f64>,
+    pub seasonal: f64,
+    pub irregular: Option<f64>,
+    pub adjusted: f64,
+}
+
+impl SeasonalAdjustmentType {
+    /// Decomposition as the public tier may see it
+    pub fn new(result: &SeasonalAdjustmentResult, policy: &PublicTierPolicy) -> Self {
+        let points = result
+            .points
+            .iter()
+            .map(|point| SeasonalComponentPointType::new(point, policy))
+            .collect();
+
+        Self {
+            series_id: ID::from(result.series_id.to_string()),
+            frequency: result.frequency.clone(),
+            period: result.period as i32,
+            method: result.method.to_string(),
+            computed_at: result.computed_at,
+            points: policy.sample(points),
         }
     }
 }
 
-impl From<DataTransformationType> for models::DataTransformation {
-    fn from(transformation: DataTransformationType) -> Self {
-        match transformation {
-            DataTransformationType::None => models::DataTransformation::None,
-            DataTransformationType::YearOverYear => models::DataTransformation::YearOverYear,
-            DataTransformationType::QuarterOverQuarter => {
-                models::DataTransformation::QuarterOverQuarter
-            }
-            DataTransformationType::MonthOverMonth => models::DataTransformation::MonthOverMonth,
-            DataTransformationType::PercentChange => models::DataTransformation::PercentChange,
-            DataTransformationType::LogDifference => models::DataTransformation::LogDifference,
+impl SeasonalComponentPointType {
+    fn new(point: &SeasonalComponentPoint, policy: &PublicTierPolicy) -> Self {
+        Self {
+            date: point.date,
+            observed: policy.float_value(point.observed),
+            trend: point.trend.map(|trend| policy.float_value(trend)),
+            seasonal: policy.float_value(point.seasonal),
+            irregular: point
+                .irregular
+                .map(|irregular| policy.float_value(irregular)),
+            adjusted: policy.float_value(point.adjusted),
         }
     }
 }
 
-/// Series frequency enumeration for GraphQL
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-#[graphql(name = "SeriesFrequency")]
-#[derive(Debug)]
-pub enum SeriesFrequencyType {
-    Daily,
-    Weekly,
-    Monthly,
-    Quarterly,
-    Annual,
-    Irregular,
+/// Series converted into another currency
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "CurrencyConversion")]
+pub struct CurrencyConversionType {
+    pub series_id: ID,
+    pub source_currency: String,
+    pub target_currency: String,
+    pub data_points: Vec<ConvertedDataPointType>,
+    /// Observations dated before the first available exchange rate
+    pub missing_rate_count: i32,
 }
 
-/// Paginated result for series
-#[derive(SimpleObject)]
-#[graphql(name = "SeriesConnection")]
-pub struct SeriesConnection {
-    pub nodes: Vec<EconomicSeriesType>,
-    pub total_count: i32,
-    pub page_info: PageInfo,
+/// Single observation converted into another currency
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "ConvertedDataPoint")]
+pub struct ConvertedDataPointType {
+    pub date: NaiveDate,
+    pub original_value: Option<BigDecimal>,
+    /// Null when the observation has no value or no rate was published yet
+    pub value: Option<BigDecimal>,
+    /// Units of target currency per unit of source currency
+    pub rate: Option<BigDecimal>,
+    /// Day the rate was published; earlier than `date` when carried forward
+    pub rate_date: Option<NaiveDate>,
 }
 
-/// Paginated result for data points
-#[derive(SimpleObject)]
-#[graphql(name = "DataPointConnection")]
-pub struct DataPointConnection {
-    pub nodes: Vec<DataPointType>,
-    pub total_count: i32,
-    pub page_info: PageInfo,
+impl CurrencyConversionType {
+    /// Conversion as the public tier may see it
+    pub fn new(conversion: CurrencyConversion, policy: &PublicTierPolicy) -> Self {
+        let data_points = conversion
+            .points
+            .into_iter()
+            .map(|point| ConvertedDataPointType::new(point, policy))
+            .collect();
+
+        Self {
+            series_id: ID::from(conversion.series_id.to_string()),
+            source_currency: conversion.source_currency,
+            target_currency: conversion.target_currency,
+            data_points: policy.sample(data_points),
+            missing_rate_count: conversion.missing_rate_count as i32,
+        }
+    }
 }
 
-/// Page information for pagination
-#[derive(SimpleObject)]
-#[graphql(name = "PageInfo")]
-pub struct PageInfo {
-    pub has_next_page: bool,
-    pub has_previous_page: bool,
-    pub start_cursor: Option<String>,
-    pub end_cursor: Option<String>,
+impl ConvertedDataPointType {
+    fn new(point: ConvertedDataPoint, policy: &PublicTierPolicy) -> Self {
+        Self {
+            date: point.date,
+            original_value: policy.optional_value(&point.original_value),
+            value: policy.optional_value(&point.value),
+            rate: point.rate,
+            rate_date: point.rate_date,
+        }
+    }
 }
 
-/// Search result type
-#[derive(SimpleObject)]
-#[graphql(name = "SearchResult")]
-pub struct SearchResult {
-    pub series: Vec<EconomicSeriesType>,
-    pub total_count: i32,
-    pub query: String,
-    pub took_ms: i32,
+/// Input for a cross-series correlation and lead/lag run
+#[derive(InputObject)]
+pub struct RunCrossSeriesAnalysisInput {
+    /// Indicator category to analyze (e.g. GDP_GROWTH)
+    pub indicator_category: String,
+    /// Indicator code to use for every country (defaults to each country's longest series)
+    pub indicator_code: Option<String>,
+    /// Start of the analysis period
+    pub start_date: NaiveDate,
+    /// End of the analysis period
+    pub end_date: NaiveDate,
+    /// Country pairs to analyze; omit to analyze every active country pair
+    pub country_pairs: Option<Vec<CountryPairInput>>,
+    /// Rolling window length in months (default 36)
+    pub window_months: Option<i32>,
+    /// Months between rolling window starts (default 12)
+    pub step_months: Option<i32>,
+    /// Largest lead or lag to search, in months (default 12, at most 24)
+    pub max_lag_months: Option<i32>,
 }
 
-/// Queue statistics for monitoring
-#[derive(SimpleObject)]
-#[graphql(name = "QueueStatistics")]
-pub struct QueueStatisticsType {
-    pub total_items: i32,
-    pub pending_items: i32,
-    pub processing_items: i32,
-    pub completed_items: i32,
-    pub failed_items: i32,
-    pub retrying_items: i32,
-    pub oldest_pending: Option<DateTime<Utc>>,
-    pub average_processing_time: Option<f64>,
+/// Pair of countries to compare
+#[derive(InputObject)]
+pub struct CountryPairInput {
+    pub country_a_id: ID,
+    pub country_b_id: ID,
 }
 
-/// Crawler status information
+/// Outcome of a cross-series analysis run
 #[derive(SimpleObject)]
-#[graphql(name = "CrawlerStatus")]
-pub struct CrawlerStatusType {
-    pub is_running: bool,
-    pub active_workers: i32,
-    pub last_crawl: Option<DateTime<Utc>>,
-    pub next_scheduled_crawl: Option<DateTime<Utc>>,
+#[graphql(name = "CrossSeriesAnalysis")]
+pub struct CrossSeriesAnalysisType {
+    pub pairs_analyzed: i32,
+    /// Pairs without enough overlapping observations
+    pub pairs_skipped: i32,
+    /// Full-period and rolling-window correlations that were stored
+    pub correlations: Vec<CountryCorrelationType>,
+    pub leading_indicators: Vec<LeadingIndicatorType>,
 }
 
-/// Input types for mutations and complex queries
-#[derive(InputObject)]
-#[graphql(name = "SeriesFilter")]
-pub struct SeriesFilterInput {
-    pub source_id: Option<ID>,
+impl From<CrossSeriesAnalysisSummary> for CrossSeriesAnalysisType {
+    fn from(summary: CrossSeriesAnalysisSummary) -> Self {
+        Self {
+            pairs_analyzed: summary.pairs_analyzed as i32,
+            pairs_skipped: summary.pairs_skipped as i32,
+            correlations: summary.correlations.into_iter().map(Into::into).collect(),
+            leading_indicators: summary
+                .leading_indicators
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+/// Distribution of a financial ratio across a peer group for one period
+#[derive(SimpleObject)]
+#[graphql(name = "RatioBenchmark")]
+pub struct RatioBenchmarkType {
+    /// Peer grouping: "sic" or "sector"
+    pub group_type: String,
+    /// SIC code or sector name
+    pub group_code: String,
+    /// Companies in the peer group
+    pub company_count: i32,
+    pub mean: f64,
+    pub median: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub calculated_at: DateTime<Utc>,
+}
+
+impl From<IndustryBenchmark> for RatioBenchmarkType {
+    fn from(benchmark: IndustryBenchmark) -> Self {
+        Self {
+            group_type: benchmark.group_type,
+            group_code: benchmark.group_code,
+            company_count: benchmark.company_count,
+            mean: decimal_to_f64(&benchmark.mean_value),
+            median: decimal_to_f64(&benchmark.median_value),
+            p10: decimal_to_f64(&benchmark.p10_value),
+            p25: decimal_to_f64(&benchmark.p25_value),
+            p75: decimal_to_f64(&benchmark.p75_value),
+            p90: decimal_to_f64(&benchmark.p90_value),
+            calculated_at: benchmark.calculated_at,
+        }
+    }
+}
+
+/// A company's ratio in one fiscal period compared with its peers
+#[derive(SimpleObject)]
+#[graphql(name = "CompanyBenchmarkPeriod")]
+pub struct CompanyBenchmarkPeriodType {
+    pub fiscal_year: i32,
+    /// None for annual periods
+    pub fiscal_quarter: Option<i32>,
+    /// The company's ratio value
+    pub value: f64,
+    /// Share of SIC peers (0-100) with a lower value
+    pub industry_percentile_rank: Option<f64>,
+    /// Benchmark across companies with the same SIC code
+    pub industry: Option<RatioBenchmarkType>,
+    /// Benchmark across companies in the same sector
+    pub sector: Option<RatioBenchmarkType>,
+}
+
+impl From<CompanyBenchmarkPeriod> for CompanyBenchmarkPeriodType {
+    fn from(period: CompanyBenchmarkPeriod) -> Self {
+        Self {
+            fiscal_year: period.fiscal_year,
+            fiscal_quarter: period.fiscal_quarter,
+            value: period.value,
+            industry_percentile_rank: period.industry_percentile_rank,
+            industry: period.industry.map(Into::into),
+            sector: period.sector.map(Into::into),
+        }
+    }
+}
+
+/// Benchmark history of one financial ratio for a company
+#[derive(SimpleObject)]
+#[graphql(name = "CompanyBenchmark")]
+pub struct CompanyBenchmarkType {
+    pub company_id: ID,
+    pub ratio_name: String,
+    pub sic_code: Option<String>,
+    pub sector: Option<String>,
+    /// One entry per fiscal period with a reported ratio, oldest first
+    pub periods: Vec<CompanyBenchmarkPeriodType>,
+}
+
+impl From<CompanyBenchmark> for CompanyBenchmarkType {
+    fn from(benchmark: CompanyBenchmark) -> Self {
+        Self {
+            company_id: ID::from(benchmark.company_id.to_string()),
+            ratio_name: benchmark.ratio_name,
+            sic_code: benchmark.sic_code,
+            sector: benchmark.sector,
+            periods: benchmark.periods.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A company's financial ratio in one fiscal period
+#[derive(SimpleObject)]
+#[graphql(name = "RatioHistoryPoint")]
+pub struct RatioHistoryPointType {
+    pub fiscal_year: i32,
+    /// None for annual periods
+    pub fiscal_quarter: Option<i32>,
+    pub period_end_date: NaiveDate,
+    pub value: f64,
+    /// Statement the value was calculated from
+    pub statement_id: ID,
+}
+
+impl From<RatioHistoryPoint> for RatioHistoryPointType {
+    fn from(point: RatioHistoryPoint) -> Self {
+        Self {
+            fiscal_year: point.fiscal_year,
+            fiscal_quarter: point.fiscal_quarter,
+            period_end_date: point.period_end_date,
+            value: point.value,
+            statement_id: ID::from(point.statement_id.to_string()),
+        }
+    }
+}
+
+/// A company's financial ratio in one fiscal period ranked within its sector
+#[derive(SimpleObject)]
+#[graphql(name = "RatioPercentilePeriod")]
+pub struct RatioPercentilePeriodType {
+    pub fiscal_year: i32,
+    /// None for annual periods
+    pub fiscal_quarter: Option<i32>,
+    /// The company's ratio value
+    pub value: f64,
+    /// Share of sector peers (0-100) with a lower value; null when fewer than 3 companies reported
+    pub percentile: Option<f64>,
+    /// Companies in the sector with a value for the period, including this one
+    pub peer_count: i32,
+}
+
+impl From<RatioPercentilePeriod> for RatioPercentilePeriodType {
+    fn from(period: RatioPercentilePeriod) -> Self {
+        Self {
+            fiscal_year: period.fiscal_year,
+            fiscal_quarter: period.fiscal_quarter,
+            value: period.value,
+            percentile: period.percentile,
+            peer_count: period.peer_count as i32,
+        }
+    }
+}
+
+/// Sector percentile of one financial ratio for a company over time
+#[derive(SimpleObject)]
+#[graphql(name = "RatioPeerPercentile")]
+pub struct RatioPeerPercentileType {
+    pub company_id: ID,
+    pub ratio_name: String,
+    pub sector: Option<String>,
+    /// One entry per fiscal period with a reported ratio, oldest first
+    pub periods: Vec<RatioPercentilePeriodType>,
+}
+
+impl From<RatioPeerPercentile> for RatioPeerPercentileType {
+    fn from(percentile: RatioPeerPercentile) -> Self {
+        Self {
+            company_id: ID::from(percentile.company_id.to_string()),
+            ratio_name: percentile.ratio_name,
+            sector: percentile.sector,
+            periods: percentile.periods.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Outcome of a benchmark refresh
+#[derive(SimpleObject)]
+#[graphql(name = "BenchmarkRefresh")]
+pub struct BenchmarkRefreshType {
+    /// Company ratio values considered
+    pub observations: i32,
+    /// Peer group benchmarks written
+    pub benchmarks_stored: i32,
+    /// Company ratio rows updated with benchmark values
+    pub ratios_updated: i32,
+}
+
+impl From<BenchmarkRefreshSummary> for BenchmarkRefreshType {
+    fn from(summary: BenchmarkRefreshSummary) -> Self {
+        Self {
+            observations: summary.observations as i32,
+            benchmarks_stored: summary.benchmarks_stored as i32,
+            ratios_updated: summary.ratios_updated as i32,
+        }
+    }
+}
+
+/// A calculation linkbase summation the filing's facts do not satisfy
+#[derive(SimpleObject)]
+#[graphql(name = "CalculationDiscrepancy")]
+pub struct CalculationDiscrepancyType {
+    /// Extended link role of the calculation network, usually one statement
+    pub link_role: String,
+    /// Total concept, e.g. "us-gaap:Assets"
+    pub parent_concept: String,
+    /// Children reported in the same context and unit
+    pub child_concepts: Vec<String>,
+    pub context_ref: String,
+    pub unit_ref: Option<String>,
+    pub reported_value: f64,
+    /// Weighted sum of the child concepts
+    pub calculated_value: f64,
+    /// Reported minus calculated value
+    pub difference: f64,
+    /// Rounding tolerance derived from the facts' decimals
+    pub tolerance: f64,
+}
+
+impl From<XbrlCalculationDiscrepancy> for CalculationDiscrepancyType {
+    fn from(discrepancy: XbrlCalculationDiscrepancy) -> Self {
+        Self {
+            link_role: discrepancy.link_role,
+            parent_concept: discrepancy.parent_concept,
+            child_concepts: discrepancy.child_concepts,
+            context_ref: discrepancy.context_ref,
+            unit_ref: discrepancy.unit_ref,
+            reported_value: decimal_to_f64(&discrepancy.reported_value),
+            calculated_value: decimal_to_f64(&discrepancy.calculated_value),
+            difference: decimal_to_f64(&discrepancy.difference),
+            tolerance: decimal_to_f64(&discrepancy.tolerance),
+        }
+    }
+}
+
+/// Calculation consistency of a financial statement's XBRL facts
+#[derive(SimpleObject)]
+#[graphql(name = "ValidationReport")]
+pub struct ValidationReportType {
+    pub statement_id: ID,
+    /// True when no summation was found to be inconsistent
+    pub is_valid: bool,
+    pub discrepancy_count: i32,
+    pub discrepancies: Vec<CalculationDiscrepancyType>,
+}
+
+impl ValidationReportType {
+    pub fn new(statement_id: Uuid, discrepancies: Vec<XbrlCalculationDiscrepancy>) -> Self {
+        Self {
+            statement_id: ID::from(statement_id.to_string()),
+            is_valid: discrepancies.is_empty(),
+            discrepancy_count: discrepancies.len() as i32,
+            discrepancies: discrepancies.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A concept of a financial statement broken down along one XBRL axis, for one period
+#[derive(SimpleObject)]
+#[graphql(name = "SegmentBreakdown")]
+pub struct SegmentBreakdownType {
+    /// Taxonomy concept, e.g. "us-gaap:Revenues"
+    pub concept: String,
+    /// Dimension the concept is broken down along, e.g. "us-gaap:StatementBusinessSegmentsAxis"
+    pub axis: String,
+    /// Null for instants
+    pub period_start_date: Option<NaiveDate>,
+    pub period_end_date: Option<NaiveDate>,
+    /// Undimensioned fact of the period, if the filing reports one
+    pub total: Option<SegmentFactType>,
+    /// Facts of each member of the axis, largest value first
+    pub members: Vec<SegmentMemberType>,
+}
+
+impl From<SegmentBreakdown> for SegmentBreakdownType {
+    fn from(breakdown: SegmentBreakdown) -> Self {
+        Self {
+            concept: breakdown.concept,
+            axis: breakdown.axis,
+            period_start_date: breakdown.period_start_date,
+            period_end_date: breakdown.period_end_date,
+            total: breakdown.total.map(Into::into),
+            members: breakdown.members.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The fact of one member of an XBRL axis
+#[derive(SimpleObject)]
+#[graphql(name = "SegmentMember")]
+pub struct SegmentMemberType {
+    /// Member QName, e.g. "aapl:ServicesMember"
+    pub member: String,
+    pub fact: SegmentFactType,
+}
+
+impl From<SegmentMember> for SegmentMemberType {
+    fn from(member: SegmentMember) -> Self {
+        Self {
+            member: member.member,
+            fact: member.line_item.into(),
+        }
+    }
+}
+
+/// A stored XBRL fact of a segment breakdown
+#[derive(SimpleObject)]
+#[graphql(name = "SegmentFact")]
+pub struct SegmentFactType {
+    /// Line item ID, e.g. for `financialLineItemProvenance`
+    pub line_item_id: ID,
+    pub context_ref: String,
+    /// Value as reported
+    pub value: Option<BigDecimal>,
+    pub unit: String,
+    /// Value in canonical units (absolute USD, shares, ...)
+    pub normalized_value: Option<BigDecimal>,
+    pub normalized_unit: Option<String>,
+}
+
+impl From<FinancialLineItem> for SegmentFactType {
+    fn from(line_item: FinancialLineItem) -> Self {
+        Self {
+            line_item_id: ID::from(line_item.id.to_string()),
+            context_ref: line_item.context_ref,
+            value: line_item.value,
+            unit: line_item.unit,
+            normalized_value: line_item.normalized_value,
+            normalized_unit: line_item.normalized_unit,
+        }
+    }
+}
+
+/// A processed filing of a company with its company-level XBRL facts
+#[derive(SimpleObject)]
+#[graphql(name = "FinancialStatement")]
+pub struct FinancialStatementType {
+    pub id: ID,
+    pub company_id: ID,
+    /// e.g. "10-K" or "10-Q"
+    pub form_type: String,
+    pub accession_number: String,
+    pub filing_date: NaiveDate,
+    pub period_end_date: NaiveDate,
+    pub fiscal_year: i32,
+    /// Null for annual reports
+    pub fiscal_quarter: Option<i32>,
+    pub is_amended: bool,
+    /// Facts reported without dimensions, by statement, then presentation order
+    pub facts: Vec<FinancialFactType>,
+}
+
+impl From<CompanyFinancials> for FinancialStatementType {
+    fn from(financials: CompanyFinancials) -> Self {
+        let filing = financials.filing;
+        Self {
+            id: ID::from(filing.id.to_string()),
+            company_id: ID::from(filing.company_id.to_string()),
+            form_type: filing.form_type,
+            accession_number: filing.accession_number,
+            filing_date: filing.filing_date,
+            period_end_date: filing.period_end_date,
+            fiscal_year: filing.fiscal_year,
+            fiscal_quarter: filing.fiscal_quarter,
+            is_amended: filing.is_amended,
+            facts: financials.facts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A company-level XBRL fact of a financial statement
+#[derive(SimpleObject)]
+#[graphql(name = "FinancialFact")]
+pub struct FinancialFactType {
+    /// Line item ID, e.g. for `financialLineItemProvenance`
+    pub line_item_id: ID,
+    /// Taxonomy concept, e.g. "us-gaap:Revenues"
+    pub concept: String,
+    pub label: Option<String>,
+    /// "income_statement", "balance_sheet", "cash_flow" or "equity"
+    pub statement_type: String,
+    /// Value as reported
+    pub value: Option<BigDecimal>,
+    pub unit: String,
+    /// Value in canonical units (absolute USD, shares, ...)
+    pub normalized_value: Option<BigDecimal>,
+    pub normalized_unit: Option<String>,
+    /// Null for instants
+    pub period_start_date: Option<NaiveDate>,
+    pub period_end_date: Option<NaiveDate>,
+}
+
+impl From<FinancialLineItem> for FinancialFactType {
+    fn from(line_item: FinancialLineItem) -> Self {
+        Self {
+            line_item_id: ID::from(line_item.id.to_string()),
+            concept: line_item.taxonomy_concept,
+            label: line_item.standard_label,
+            statement_type: line_item.statement_type.as_str().to_string(),
+            value: line_item.value,
+            unit: line_item.unit,
+            normalized_value: line_item.normalized_value,
+            normalized_unit: line_item.normalized_unit,
+            period_start_date: line_item.period_start_date,
+            period_end_date: line_item.period_end_date,
+        }
+    }
+}
+
+/// A transaction reported by a company insider on SEC Form 4
+#[derive(SimpleObject)]
+#[graphql(name = "InsiderTransaction")]
+pub struct InsiderTransactionType {
+    pub id: ID,
+    pub accession_number: String,
+    /// "4" or "4/A"
+    pub form_type: String,
+    pub filed_date: NaiveDate,
+    pub insider_cik: String,
+    pub insider_name: String,
+    /// Roles as written on the form, e.g. "Director, Chief Executive Officer"
+    pub relationship: String,
+    pub is_director: bool,
+    pub is_officer: bool,
+    pub is_ten_percent_owner: bool,
+    pub officer_title: Option<String>,
+    pub security_title: String,
+    /// True for options, RSUs and other derivative securities
+    pub is_derivative: bool,
+    pub transaction_date: NaiveDate,
+    /// SEC transaction code: P purchase, S sale, A award, M exercise, F tax withholding, ...
+    pub transaction_code: String,
+    pub shares: f64,
+    pub price_per_share: Option<f64>,
+    /// Shares times price, when a price is reported
+    pub value: Option<f64>,
+    /// True when the shares were acquired, false when disposed of
+    pub acquired: bool,
+    pub shares_owned_after: Option<f64>,
+    /// True for shares held directly, false for indirect holdings such as trusts
+    pub direct_ownership: bool,
+}
+
+impl From<InsiderTransaction> for InsiderTransactionType {
+    fn from(transaction: InsiderTransaction) -> Self {
+        Self {
+            id: ID::from(transaction.id.to_string()),
+            relationship: transaction.relationship(),
+            value: transaction.value().as_ref().map(decimal_to_f64),
+            accession_number: transaction.accession_number,
+            form_type: transaction.form_type,
+            filed_date: transaction.filed_date,
+            insider_cik: transaction.insider_cik,
+            insider_name: transaction.insider_name,
+            is_director: transaction.is_director,
+            is_officer: transaction.is_officer,
+            is_ten_percent_owner: transaction.is_ten_percent_owner,
+            officer_title: transaction.officer_title,
+            security_title: transaction.security_title,
+            is_derivative: transaction.is_derivative,
+            transaction_date: transaction.transaction_date,
+            transaction_code: transaction.transaction_code,
+            shares: decimal_to_f64(&transaction.shares),
+            price_per_share: transaction.price_per_share.as_ref().map(decimal_to_f64),
+            acquired: transaction.acquired_disposed == "A",
+            shares_owned_after: transaction.shares_owned_after.as_ref().map(decimal_to_f64),
+            direct_ownership: transaction.ownership_nature == "D",
+        }
+    }
+}
+
+/// Insider buying and selling of a company over a period
+///
+/// Only open-market purchases (P) and sales (S) are totalled; awards,
+/// exercises and tax withholding only count as transactions.
+#[derive(SimpleObject)]
+#[graphql(name = "InsiderActivity")]
+pub struct InsiderActivityType {
+    pub company_id: ID,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub transaction_count: i32,
+    /// Distinct insiders with any transaction
+    pub insider_count: i32,
+    pub purchase_count: i32,
+    pub purchase_shares: f64,
+    pub purchase_value: f64,
+    pub sale_count: i32,
+    pub sale_shares: f64,
+    pub sale_value: f64,
+    /// Shares bought minus shares sold
+    pub net_shares: f64,
+}
+
+impl InsiderActivityType {
+    pub fn new(
+        company_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        summary: InsiderActivitySummary,
+    ) -> Self {
+        Self {
+            company_id: ID::from(company_id.to_string()),
+            start_date,
+            end_date,
+            transaction_count: summary.transaction_count as i32,
+            insider_count: summary.insider_count as i32,
+            purchase_count: summary.purchase_count as i32,
+            purchase_shares: decimal_to_f64(&summary.purchase_shares),
+            purchase_value: decimal_to_f64(&summary.purchase_value),
+            sale_count: summary.sale_count as i32,
+            sale_shares: decimal_to_f64(&summary.sale_shares),
+            sale_value: decimal_to_f64(&summary.sale_value),
+            net_shares: decimal_to_f64(&summary.net_shares()),
+        }
+    }
+}
+
+/// A manager's 13F position in a company at a quarter end
+#[derive(SimpleObject)]
+#[graphql(name = "InstitutionalHolder")]
+pub struct InstitutionalHolderType {
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub shares: f64,
+    /// Market value in dollars
+    pub value_usd: f64,
+    /// Percent of the shares reported by all 13F filers
+    pub percent_of_reported: f64,
+}
+
+/// Largest 13F holders of a company at a quarter end
+///
+/// Share classes are added together; option positions are left out.
+#[derive(SimpleObject)]
+#[graphql(name = "InstitutionalHolders")]
+pub struct InstitutionalHoldersType {
+    pub company_id: ID,
+    /// Null when no holdings of the company have been reported
+    pub report_period: Option<NaiveDate>,
+    /// Managers reporting a position
+    pub holder_count: i32,
+    /// Shares reported by all managers
+    pub total_shares: f64,
+    pub total_value_usd: f64,
+    pub holders: Vec<InstitutionalHolderType>,
+}
+
+impl InstitutionalHoldersType {
+    pub fn new(
+        company_id: Uuid,
+        report_period: Option<NaiveDate>,
+        positions: Vec<HolderPosition>,
+        limit: usize,
+    ) -> Self {
+        let total_shares: f64 = positions.iter().map(|p| decimal_to_f64(&p.shares)).sum();
+        let total_value_usd = positions.iter().map(|p| decimal_to_f64(&p.value_usd)).sum();
+        let holder_count = positions.len() as i32;
+
+        let holders = positions
+            .into_iter()
+            .take(limit)
+            .map(|position| {
+                let shares = decimal_to_f64(&position.shares);
+                InstitutionalHolderType {
+                    manager_cik: position.manager_cik,
+                    manager_name: position.manager_name,
+                    shares,
+                    value_usd: decimal_to_f64(&position.value_usd),
+                    percent_of_reported: if total_shares > 0.0 {
+                        shares / total_shares * 100.0
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+
+        Self {
+            company_id: ID::from(company_id.to_string()),
+            report_period,
+            holder_count,
+            total_shares,
+            total_value_usd,
+            holders,
+        }
+    }
+}
+
+/// How a manager's position changed from the previous quarter
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "PositionChangeKind")]
+pub enum PositionChangeKindType {
+    New,
+    Increased,
+    Decreased,
+    Unchanged,
+    SoldOut,
+}
+
+impl From<PositionChangeKind> for PositionChangeKindType {
+    fn from(kind: PositionChangeKind) -> Self {
+        match kind {
+            PositionChangeKind::New => Self::New,
+            PositionChangeKind::Increased => Self::Increased,
+            PositionChangeKind::Decreased => Self::Decreased,
+            PositionChangeKind::Unchanged => Self::Unchanged,
+            PositionChangeKind::SoldOut => Self::SoldOut,
+        }
+    }
+}
+
+/// A manager's 13F position compared with the previous quarter
+#[derive(SimpleObject)]
+#[graphql(name = "InstitutionalPositionChange")]
+pub struct InstitutionalPositionChangeType {
+    pub manager_cik: String,
+    pub manager_name: String,
+    pub previous_shares: f64,
+    pub shares: f64,
+    /// Shares bought (positive) or sold (negative)
+    pub change: f64,
+    /// Null for new positions
+    pub change_percent: Option<f64>,
+    pub kind: PositionChangeKindType,
+}
+
+impl From<PositionChange> for InstitutionalPositionChangeType {
+    fn from(change: PositionChange) -> Self {
+        Self {
+            change: decimal_to_f64(&change.change()),
+            change_percent: change.change_percent(),
+            previous_shares: decimal_to_f64(&change.previous_shares),
+            shares: decimal_to_f64(&change.shares),
+            kind: change.kind.into(),
+            manager_cik: change.manager_cik,
+            manager_name: change.manager_name,
+        }
+    }
+}
+
+/// Quarter-over-quarter changes in a company's 13F holders, largest first
+#[derive(SimpleObject)]
+#[graphql(name = "InstitutionalPositionChanges")]
+pub struct InstitutionalPositionChangesType {
+    pub company_id: ID,
+    /// Null when no holdings of the company have been reported
+    pub report_period: Option<NaiveDate>,
+    pub previous_period: Option<NaiveDate>,
+    pub new_positions: i32,
+    pub sold_out_positions: i32,
+    pub changes: Vec<InstitutionalPositionChangeType>,
+}
+
+impl InstitutionalPositionChangesType {
+    pub fn new(
+        company_id: Uuid,
+        report_period: Option<NaiveDate>,
+        previous_period: Option<NaiveDate>,
+        changes: Vec<PositionChange>,
+        limit: usize,
+    ) -> Self {
+        let count = |kind: PositionChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+
+        Self {
+            company_id: ID::from(company_id.to_string()),
+            report_period,
+            previous_period,
+            new_positions: count(PositionChangeKind::New) as i32,
+            sold_out_positions: count(PositionChangeKind::SoldOut) as i32,
+            changes: changes.into_iter().take(limit).map(Into::into).collect(),
+        }
+    }
+}
+
+/// Data transformation enumeration for GraphQL
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[graphql(name = "DataTransformation")]
+pub enum DataTransformationType {
+    None,
+    YearOverYear,
+    QuarterOverQuarter,
+    MonthOverMonth,
+    PercentChange,
+    LogDifference,
+}
+
+impl From<models::DataTransformation> for DataTransformationType {
+    fn from(transformation: models::DataTransformation) -> Self {
+        match transformation {
+            models::DataTransformation::None => DataTransformationType::None,
+            models::DataTransformation::YearOverYear => DataTransformationType::YearOverYear,
+            models::DataTransformation::QuarterOverQuarter => {
+                DataTransformationType::QuarterOverQuarter
+            }
+            models::DataTransformation::MonthOverMonth => DataTransformationType::MonthOverMonth,
+            models::DataTransformation::PercentChange => DataTransformationType::PercentChange,
+            models::DataTransformation::LogDifference => DataTransformationType::LogDifference,
+        }
+    }
+}
+
+impl From<DataTransformationType> for models::DataTransformation {
+    fn from(transformation: DataTransformationType) -> Self {
+        match transformation {
+            DataTransformationType::None => models::DataTransformation::None,
+            DataTransformationType::YearOverYear => models::DataTransformation::YearOverYear,
+            DataTransformationType::QuarterOverQuarter => {
+                models::DataTransformation::QuarterOverQuarter
+            }
+            DataTransformationType::MonthOverMonth => models::DataTransformation::MonthOverMonth,
+            DataTransformationType::PercentChange => models::DataTransformation::PercentChange,
+            DataTransformationType::LogDifference => models::DataTransformation::LogDifference,
+        }
+    }
+}
+
+/// Downsampling algorithm enumeration for GraphQL
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "DownsamplingAlgorithm")]
+pub enum DownsamplingAlgorithmType {
+    /// Largest-Triangle-Three-Buckets, preserving the shape of the line
+    Lttb,
+    /// Minimum and maximum of each bucket, preserving spikes
+    MinMax,
+}
+
+impl From<DownsamplingAlgorithmType> for series_service::DownsamplingAlgorithm {
+    fn from(algorithm: DownsamplingAlgorithmType) -> Self {
+        match algorithm {
+            DownsamplingAlgorithmType::Lttb => series_service::DownsamplingAlgorithm::Lttb,
+            DownsamplingAlgorithmType::MinMax => series_service::DownsamplingAlgorithm::MinMax,
+        }
+    }
+}
+
+/// Target frequency of a resampled series
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ResampleFrequency")]
+pub enum ResampleFrequencyType {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl From<series_service::ResampleFrequency> for ResampleFrequencyType {
+    fn from(frequency: series_service::ResampleFrequency) -> Self {
+        match frequency {
+            series_service::ResampleFrequency::Weekly => ResampleFrequencyType::Weekly,
+            series_service::ResampleFrequency::Monthly => ResampleFrequencyType::Monthly,
+            series_service::ResampleFrequency::Quarterly => ResampleFrequencyType::Quarterly,
+            series_service::ResampleFrequency::Annual => ResampleFrequencyType::Annual,
+        }
+    }
+}
+
+impl From<ResampleFrequencyType> for series_service::ResampleFrequency {
+    fn from(frequency: ResampleFrequencyType) -> Self {
+        match frequency {
+            ResampleFrequencyType::Weekly => series_service::ResampleFrequency::Weekly,
+            ResampleFrequencyType::Monthly => series_service::ResampleFrequency::Monthly,
+            ResampleFrequencyType::Quarterly => series_service::ResampleFrequency::Quarterly,
+            ResampleFrequencyType::Annual => series_service::ResampleFrequency::Annual,
+        }
+    }
+}
+
+/// How the observations of a period are combined when resampling
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ResampleMethod")]
+pub enum ResampleMethodType {
+    /// Average of the period, for rates and levels
+    Mean,
+    /// Total of the period, for flows
+    Sum,
+    /// Last observation of the period, for stocks and prices
+    EndOfPeriod,
+    /// First observation of the period
+    StartOfPeriod,
+}
+
+impl From<ResampleMethodType> for series_service::ResampleMethod {
+    fn from(method: ResampleMethodType) -> Self {
+        match method {
+            ResampleMethodType::Mean => series_service::ResampleMethod::Mean,
+            ResampleMethodType::Sum => series_service::ResampleMethod::Sum,
+            ResampleMethodType::EndOfPeriod => series_service::ResampleMethod::EndOfPeriod,
+            ResampleMethodType::StartOfPeriod => series_service::ResampleMethod::StartOfPeriod,
+        }
+    }
+}
+
+/// One period of a resampled series
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "ResampledDataPoint")]
+pub struct ResampledDataPointType {
+    /// First day of the period
+    pub date: NaiveDate,
+    pub period_end: NaiveDate,
+    pub value: BigDecimal,
+    pub observation_count: i32,
+    /// Whether the observations cover the whole period
+    pub is_complete: bool,
+}
+
+impl ResampledDataPointType {
+    /// Period as the public tier may see it
+    pub fn new(point: series_service::ResampledPoint, policy: &PublicTierPolicy) -> Self {
+        Self {
+            date: point.period_start,
+            period_end: point.period_end,
+            value: policy.value(&point.value),
+            observation_count: point.observation_count as i32,
+            is_complete: point.is_complete,
+        }
+    }
+}
+
+/// Length of the periods of a series rollup
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "RollupPeriod")]
+pub enum RollupPeriodType {
+    Month,
+    Year,
+}
+
+impl From<RollupPeriodType> for models::RollupPeriod {
+    fn from(period: RollupPeriodType) -> Self {
+        match period {
+            RollupPeriodType::Month => models::RollupPeriod::Month,
+            RollupPeriodType::Year => models::RollupPeriod::Year,
+        }
+    }
+}
+
+/// Pre-aggregated statistics of a series in one month or year
+#[derive(SimpleObject, Clone)]
+#[graphql(name = "SeriesRollup")]
+pub struct SeriesRollupType {
+    /// First day of the period
+    pub date: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Value of the first observation in the period
+    pub first_value: BigDecimal,
+    pub min_value: BigDecimal,
+    pub max_value: BigDecimal,
+    pub mean_value: BigDecimal,
+    /// Value of the last observation in the period
+    pub last_value: BigDecimal,
+    /// Date of the last observation in the period
+    pub last_date: NaiveDate,
+    pub observation_count: i32,
+}
+
+impl SeriesRollupType {
+    /// Rollup as the public tier may see it
+    pub fn new(rollup: models::SeriesRollup, policy: &PublicTierPolicy) -> Self {
+        Self {
+            date: rollup.period_start,
+            period_end: rollup.period_end,
+            first_value: policy.value(&rollup.first_value),
+            min_value: policy.value(&rollup.min_value),
+            max_value: policy.value(&rollup.max_value),
+            mean_value: policy.value(&rollup.mean_value),
+            last_value: policy.value(&rollup.last_value),
+            last_date: rollup.last_date,
+            observation_count: rollup.observation_count,
+        }
+    }
+}
+
+/// Several series as known on a vintage date, aligned on one date index
+#[derive(SimpleObject)]
+#[graphql(name = "AlignedSeries")]
+pub struct AlignedSeriesType {
+    /// Vintage date: revisions published later are left out
+    pub as_of: NaiveDate,
+    /// Frequency the series were resampled to, if any
+    pub frequency: Option<ResampleFrequencyType>,
+    /// Columns of the table, in the order they were requested
+    pub series: Vec<EconomicSeriesType>,
+    /// Dates any series has a value for, oldest first
+    pub rows: Vec<AlignedSeriesRowType>,
+}
+
+impl AlignedSeriesType {
+    /// Aligned view as the public tier may see it
+    pub fn new(aligned: AlignedSeries, policy: &PublicTierPolicy) -> Self {
+        Self {
+            as_of: aligned.as_of,
+            frequency: aligned.frequency.map(Into::into),
+            series: aligned.series.into_iter().map(Into::into).collect(),
+            rows: aligned
+                .rows
+                .into_iter()
+                .map(|row| AlignedSeriesRowType::new(row, policy))
+                .collect(),
+        }
+    }
+}
+
+/// One date of an aligned series table
+#[derive(SimpleObject)]
+#[graphql(name = "AlignedSeriesRow")]
+pub struct AlignedSeriesRowType {
+    /// Observation date, or the first day of the period when resampled
+    pub date: NaiveDate,
+    /// One value per series, null where a series has no value for the date
+    pub values: Vec<Option<BigDecimal>>,
+}
+
+impl AlignedSeriesRowType {
+    fn new(row: AlignedRow, policy: &PublicTierPolicy) -> Self {
+        Self {
+            date: row.date,
+            values: row
+                .values
+                .iter()
+                .map(|value| policy.optional_value(value))
+                .collect(),
+        }
+    }
+}
+
+/// One global indicator across all countries, with the scale to color a map by
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CountryIndicatorSnapshot")]
+pub struct CountryIndicatorSnapshotType {
+    pub indicator_code: String,
+    /// Snapshot date: each country's latest value on or before it is used
+    pub date: NaiveDate,
+    /// Countries with a value in the two years before the date, by ISO code
+    pub countries: Vec<CountryIndicatorValueType>,
+    /// Smallest value of any country; null when no country has a value
+    pub min_value: Option<f64>,
+    /// Largest value of any country; null when no country has a value
+    pub max_value: Option<f64>,
+    /// Quintile breaks of the values, lowest first
+    pub quantiles: Vec<QuantileBreakType>,
+}
+
+impl CountryIndicatorSnapshotType {
+    /// Snapshot as the public tier may see it
+    ///
+    /// Values and the color scale are rounded; every country is kept.
+    pub fn new(snapshot: &CountryIndicatorSnapshot, policy: &PublicTierPolicy) -> Self {
+        Self {
+            indicator_code: snapshot.indicator_code.clone(),
+            date: snapshot.date,
+            countries: snapshot
+                .countries
+                .iter()
+                .map(|value| CountryIndicatorValueType::new(value, policy))
+                .collect(),
+            min_value: snapshot.min_value.map(|value| policy.float_value(value)),
+            max_value: snapshot.max_value.map(|value| policy.float_value(value)),
+            quantiles: snapshot
+                .quantiles
+                .iter()
+                .map(|quantile| QuantileBreakType {
+                    level: quantile.level,
+                    value: policy.float_value(quantile.value),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Value of a global indicator for one country
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CountryIndicatorValue")]
+pub struct CountryIndicatorValueType {
+    pub country_id: ID,
+    /// ISO 3166-1 alpha-3 code
+    pub iso_code: String,
+    /// ISO 3166-1 alpha-2 code
+    pub iso_code_2: String,
+    pub country_name: String,
+    pub region: String,
+    pub unit: Option<String>,
+    /// Date of the observation used
+    pub date: NaiveDate,
+    pub value: f64,
+}
+
+impl CountryIndicatorValueType {
+    fn new(value: &CountryIndicatorValue, policy: &PublicTierPolicy) -> Self {
+        Self {
+            country_id: ID::from(value.country_id),
+            iso_code: value.iso_code.clone(),
+            iso_code_2: value.iso_code_2.clone(),
+            country_name: value.country_name.clone(),
+            region: value.region.clone(),
+            unit: value.unit.clone(),
+            date: value.date,
+            value: policy.float_value(value.value),
+        }
+    }
+}
+
+/// Value below which a share of the countries fall
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "QuantileBreak")]
+pub struct QuantileBreakType {
+    /// Share of countries (0-1)
+    pub level: f64,
+    pub value: f64,
+}
+
+/// Series frequency enumeration for GraphQL
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "SeriesFrequency")]
+#[derive(Debug)]
+pub enum SeriesFrequencyType {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+    Irregular,
+}
+
+/// Paginated result for series
+#[derive(SimpleObject)]
+#[graphql(name = "SeriesConnection")]
+pub struct SeriesConnection {
+    pub edges: Vec<SeriesEdge>,
+    pub nodes: Vec<EconomicSeriesType>,
+    /// Number of series matching the filter
+    pub total_count: i32,
+    /// Whether `totalCount` is a planner estimate (large result sets)
+    pub total_count_is_estimate: bool,
+    pub page_info: PageInfo,
+}
+
+/// Series with the cursor pointing at it
+#[derive(SimpleObject)]
+#[graphql(name = "SeriesEdge")]
+pub struct SeriesEdge {
+    pub cursor: String,
+    pub node: EconomicSeriesType,
+}
+
+impl SeriesConnection {
+    /// Connection over one page of series
+    pub fn from_page(page: Page<EconomicSeries>, has_previous_page: bool, total: RowCount) -> Self {
+        let edges: Vec<SeriesEdge> = page
+            .items
+            .into_iter()
+            .map(|series| SeriesEdge {
+                cursor: encode_cursor(SeriesPosition::from(&series)),
+                node: EconomicSeriesType::from(series),
+            })
+            .collect();
+
+        Self {
+            nodes: edges.iter().map(|edge| edge.node.clone()).collect(),
+            total_count: total.count.min(i32::MAX as i64) as i32,
+            total_count_is_estimate: total.is_estimate,
+            page_info: PageInfo {
+                has_next_page: page.has_next_page,
+                has_previous_page,
+                start_cursor: edges.first().map(|edge| edge.cursor.clone()),
+                end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+            },
+            edges,
+        }
+    }
+}
+
+/// Paginated result for data points
+#[derive(SimpleObject)]
+#[graphql(name = "DataPointConnection")]
+pub struct DataPointConnection {
+    pub edges: Vec<DataPointEdge>,
+    pub nodes: Vec<DataPointType>,
+    /// Number of data points matching the filter
+    pub total_count: i32,
+    /// Whether `totalCount` is a planner estimate (large result sets)
+    pub total_count_is_estimate: bool,
+    pub page_info: PageInfo,
+}
+
+/// Data point with the cursor pointing at it
+#[derive(SimpleObject)]
+#[graphql(name = "DataPointEdge")]
+pub struct DataPointEdge {
+    pub cursor: String,
+    pub node: DataPointType,
+}
+
+impl DataPointConnection {
+    /// Connection over one page of data points
+    pub fn from_page(page: Page<DataPoint>, has_previous_page: bool, total: RowCount) -> Self {
+        let edges: Vec<DataPointEdge> = page
+            .items
+            .into_iter()
+            .map(|point| DataPointEdge {
+                cursor: encode_cursor(DataPointPosition::from(&point)),
+                node: DataPointType::from(point),
+            })
+            .collect();
+
+        Self {
+            nodes: edges.iter().map(|edge| edge.node.clone()).collect(),
+            total_count: total.count.min(i32::MAX as i64) as i32,
+            total_count_is_estimate: total.is_estimate,
+            page_info: PageInfo {
+                has_next_page: page.has_next_page,
+                has_previous_page,
+                start_cursor: edges.first().map(|edge| edge.cursor.clone()),
+                end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+            },
+            edges,
+        }
+    }
+}
+
+/// Page information for pagination
+#[derive(SimpleObject)]
+#[graphql(name = "PageInfo")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+/// Search result type
+#[derive(SimpleObject)]
+#[graphql(name = "SearchResult")]
+pub struct SearchResult {
+    pub series: Vec<EconomicSeriesType>,
+    /// Highlighted snippets of each series, in the order of `series`
+    pub highlights: Vec<SeriesHighlightType>,
+    /// Matches counted per facet; only computed when requested
+    pub facets: SearchFacetsType,
+    pub total_count: i32,
+    pub query: String,
+    pub took_ms: i32,
+}
+
+/// Title and description of a matching series with matched words highlighted
+///
+/// Both are HTML-escaped, with matched words wrapped in `<mark>` tags.
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "SeriesHighlight")]
+pub struct SeriesHighlightType {
+    pub series_id: ID,
+    pub title: String,
+    /// Fragments of the description around matched words
+    pub description: Option<String>,
+}
+
+impl From<&SeriesSearchResult> for SeriesHighlightType {
+    fn from(result: &SeriesSearchResult) -> Self {
+        Self {
+            series_id: ID::from(result.id.to_string()),
+            title: result.title_highlight.clone(),
+            description: result.description_highlight.clone(),
+        }
+    }
+}
+
+/// Number of matching series sharing one facet value
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "FacetCount")]
+pub struct FacetCountType {
+    /// Value to filter on, e.g. a data source ID
+    pub value: String,
+    /// Value as shown to users, e.g. the data source's name
+    pub label: String,
+    pub count: i64,
+}
+
+impl From<FacetCount> for FacetCountType {
+    fn from(facet: FacetCount) -> Self {
+        Self {
+            value: facet.value,
+            label: facet.label,
+            count: facet.count,
+        }
+    }
+}
+
+/// Matching series counted per facet, most frequent values first
+///
+/// Each facet applies every filter of the search except its own.
+#[derive(Clone, Default, SimpleObject)]
+#[graphql(name = "SearchFacets")]
+pub struct SearchFacetsType {
+    pub sources: Vec<FacetCountType>,
+    pub frequencies: Vec<FacetCountType>,
+    pub units: Vec<FacetCountType>,
+    pub geographies: Vec<FacetCountType>,
+}
+
+impl From<SearchFacets> for SearchFacetsType {
+    fn from(facets: SearchFacets) -> Self {
+        let convert = |values: Vec<FacetCount>| values.into_iter().map(Into::into).collect();
+        Self {
+            sources: convert(facets.sources),
+            frequencies: convert(facets.frequencies),
+            units: convert(facets.units),
+            geographies: convert(facets.geographies),
+        }
+    }
+}
+
+/// Queue statistics for monitoring
+#[derive(SimpleObject)]
+#[graphql(name = "QueueStatistics")]
+pub struct QueueStatisticsType {
+    pub total_items: i32,
+    pub pending_items: i32,
+    pub processing_items: i32,
+    pub completed_items: i32,
+    pub failed_items: i32,
+    pub retrying_items: i32,
+    pub dead_letter_items: i32,
+    pub oldest_pending: Option<DateTime<Utc>>,
+    pub average_processing_time: Option<f64>,
+}
+
+impl From<QueueStatistics> for QueueStatisticsType {
+    fn from(stats: QueueStatistics) -> Self {
+        Self {
+            total_items: stats.total_items as i32,
+            pending_items: stats.pending_items as i32,
+            processing_items: stats.processing_items as i32,
+            completed_items: stats.completed_items as i32,
+            failed_items: stats.failed_items as i32,
+            retrying_items: stats.retrying_items as i32,
+            dead_letter_items: stats.dead_letter_items as i32,
+            oldest_pending: stats.oldest_pending,
+            average_processing_time: stats.average_processing_time,
+        }
+    }
+}
+
+/// Crawl queue item, as shown in the queue admin views
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CrawlQueueItem")]
+pub struct CrawlQueueItemType {
+    /// Queue item ID
+    pub id: ID,
+    /// Data source name (e.g. FRED, BLS)
+    pub source: String,
+    /// External series identifier
+    pub series_id: String,
+    /// Processing priority (1-10)
+    pub priority: i32,
+    /// Queue status
+    pub status: String,
+    /// Attempts made so far
+    pub retry_count: i32,
+    /// Attempts allowed before the item is dead-lettered
+    pub max_retries: i32,
+    /// Last failure message
+    pub error_message: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp (when it was dead-lettered, for failed items)
+    pub updated_at: DateTime<Utc>,
+    /// Earliest time the item may be picked up
+    pub scheduled_for: Option<DateTime<Utc>>,
+    /// Worker holding the item, while processing
+    pub locked_by: Option<String>,
+    /// When the worker picked the item up
+    pub locked_at: Option<DateTime<Utc>>,
+}
+
+impl From<CrawlQueueItem> for CrawlQueueItemType {
+    fn from(item: CrawlQueueItem) -> Self {
+        Self {
+            id: ID::from(item.id),
+            source: item.source,
+            series_id: item.series_id,
+            priority: item.priority,
+            status: item.status,
+            retry_count: item.retry_count,
+            max_retries: item.max_retries,
+            error_message: item.error_message,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+            scheduled_for: item.scheduled_for,
+            locked_by: item.locked_by,
+            locked_at: item.locked_at,
+        }
+    }
+}
+
+/// Crawl queue status enumeration for GraphQL
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "CrawlQueueStatus")]
+pub enum CrawlQueueStatusType {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+    Retrying,
+    Cancelled,
+    DeadLetter,
+}
+
+impl From<CrawlQueueStatusType> for QueueStatus {
+    fn from(status: CrawlQueueStatusType) -> Self {
+        match status {
+            CrawlQueueStatusType::Pending => QueueStatus::Pending,
+            CrawlQueueStatusType::Processing => QueueStatus::Processing,
+            CrawlQueueStatusType::Completed => QueueStatus::Completed,
+            CrawlQueueStatusType::Failed => QueueStatus::Failed,
+            CrawlQueueStatusType::Retrying => QueueStatus::Retrying,
+            CrawlQueueStatusType::Cancelled => QueueStatus::Cancelled,
+            CrawlQueueStatusType::DeadLetter => QueueStatus::DeadLetter,
+        }
+    }
+}
+
+/// Filter for listing crawl queue items
+#[derive(InputObject, Default)]
+#[graphql(name = "CrawlQueueFilter")]
+pub struct CrawlQueueFilterInput {
+    pub status: Option<CrawlQueueStatusType>,
+    /// Data source name (e.g. FRED)
+    pub source: Option<String>,
+    /// Lowest priority included (1-10)
+    pub min_priority: Option<i32>,
+    /// Highest priority included (1-10)
+    pub max_priority: Option<i32>,
+}
+
+impl From<CrawlQueueFilterInput> for CrawlQueueFilter {
+    fn from(filter: CrawlQueueFilterInput) -> Self {
+        Self {
+            status: filter.status.map(Into::into),
+            source: filter.source,
+            min_priority: filter.min_priority,
+            max_priority: filter.max_priority,
+        }
+    }
+}
+
+/// Page of crawl queue items
+#[derive(SimpleObject)]
+#[graphql(name = "CrawlQueueItemPage")]
+pub struct CrawlQueueItemPageType {
+    pub items: Vec<CrawlQueueItemType>,
+    /// Number of items matching the filter
+    pub total_count: i32,
+}
+
+/// Outcome of a bulk requeue or cancel
+#[derive(SimpleObject)]
+#[graphql(name = "CrawlQueueBulkResult")]
+pub struct CrawlQueueBulkResultType {
+    /// Items that were changed
+    pub updated: Vec<CrawlQueueItemType>,
+    /// Requested items that do not exist or were not in an eligible status
+    pub skipped_ids: Vec<ID>,
+}
+
+impl CrawlQueueBulkResultType {
+    pub fn new(requested: &[Uuid], updated: Vec<CrawlQueueItem>) -> Self {
+        let skipped_ids = requested
+            .iter()
+            .filter(|id| !updated.iter().any(|item| item.id == **id))
+            .map(|id| ID::from(*id))
+            .collect();
+
+        Self {
+            updated: updated.into_iter().map(Into::into).collect(),
+            skipped_ids,
+        }
+    }
+}
+
+/// Outstanding crawl queue items of one source in one status
+#[derive(SimpleObject)]
+#[graphql(name = "CrawlQueueDepth")]
+pub struct CrawlQueueDepthType {
+    pub source: String,
+    pub status: String,
+    pub items: i32,
+    /// Creation time of the oldest of these items
+    pub oldest_created_at: Option<DateTime<Utc>>,
+}
+
+impl From<QueueDepth> for CrawlQueueDepthType {
+    fn from(depth: QueueDepth) -> Self {
+        Self {
+            source: depth.source,
+            status: depth.status,
+            items: depth.items as i32,
+            oldest_created_at: depth.oldest_created_at,
+        }
+    }
+}
+
+/// Point-in-time view of the crawl queue for the admin dashboard
+#[derive(SimpleObject)]
+#[graphql(name = "CrawlQueueSnapshot")]
+pub struct CrawlQueueSnapshotType {
+    pub sampled_at: DateTime<Utc>,
+    pub statistics: QueueStatisticsType,
+    /// Outstanding items per source and status
+    pub depth: Vec<CrawlQueueDepthType>,
+}
+
+impl CrawlQueueSnapshotType {
+    /// Read the current queue statistics and depth
+    pub async fn load(pool: &DatabasePool) -> Result<Self> {
+        let statistics = queue_service::get_queue_statistics(pool).await?;
+        let depth = queue_service::get_queue_depth(pool).await?;
+
+        Ok(Self {
+            sampled_at: Utc::now(),
+            statistics: statistics.into(),
+            depth: depth.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+/// Today's crawl quotas and usage of a data source
+#[derive(SimpleObject)]
+#[graphql(name = "DataSourceQuota")]
+pub struct DataSourceQuotaType {
+    pub data_source_id: ID,
+    pub data_source_name: String,
+    /// UTC day the usage was counted on
+    pub usage_date: NaiveDate,
+    pub daily_byte_quota: Option<i64>,
+    pub daily_request_quota: Option<i32>,
+    pub bytes_downloaded: i64,
+    pub request_count: i32,
+    /// Null when the byte quota is unlimited
+    pub remaining_bytes: Option<i64>,
+    /// Null when the request quota is unlimited
+    pub remaining_requests: Option<i32>,
+    /// Whether crawls of this source are deferred until the reset
+    pub exhausted: bool,
+    pub resets_at: DateTime<Utc>,
+    /// Today's usage of each of the source's API keys
+    pub api_keys: Vec<DataSourceKeyUsageType>,
+}
+
+impl From<(DataSource, DataSourceQuotaStatus, Vec<DataSourceKeyUsage>)> for DataSourceQuotaType {
+    fn from(
+        (source, status, key_usage): (DataSource, DataSourceQuotaStatus, Vec<DataSourceKeyUsage>),
+    ) -> Self {
+        Self {
+            data_source_id: ID::from(source.id.to_string()),
+            data_source_name: source.name,
+            usage_date: status.usage_date,
+            daily_byte_quota: status.daily_byte_quota,
+            daily_request_quota: status.daily_request_quota,
+            bytes_downloaded: status.bytes_downloaded,
+            request_count: status.request_count,
+            remaining_bytes: status.remaining_bytes(),
+            remaining_requests: status.remaining_requests(),
+            exhausted: status.is_exhausted(),
+            resets_at: status.resets_at(),
+            api_keys: key_usage.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Today's usage of one API key of a data source
+#[derive(SimpleObject)]
+#[graphql(name = "DataSourceKeyUsage")]
+pub struct DataSourceKeyUsageType {
+    /// Short SHA-256 hash of the key, as used in crawler metrics
+    pub key_hash: String,
+    pub request_count: i32,
+    /// Requests answered with HTTP 429
+    pub throttled_count: i32,
+    pub bytes_downloaded: i64,
+    pub last_throttled_at: Option<DateTime<Utc>>,
+}
+
+impl From<DataSourceKeyUsage> for DataSourceKeyUsageType {
+    fn from(usage: DataSourceKeyUsage) -> Self {
+        Self {
+            key_hash: usage.key_hash,
+            request_count: usage.request_count,
+            throttled_count: usage.throttled_count,
+            bytes_downloaded: usage.bytes_downloaded,
+            last_throttled_at: usage.last_throttled_at,
+        }
+    }
+}
+
+/// Series, data points and coverage of the whole catalog
+#[derive(SimpleObject)]
+#[graphql(name = "CatalogStatistics")]
+pub struct CatalogStatisticsType {
+    pub series_count: i64,
+    pub active_series_count: i64,
+    pub data_point_count: i64,
+    /// Earliest observation date of any series
+    pub earliest_date: Option<NaiveDate>,
+    /// Latest observation date of any series
+    pub latest_date: Option<NaiveDate>,
+    /// Statistics of each data source, most data points first
+    pub sources: Vec<DataSourceStatisticsType>,
+}
+
+impl From<CatalogStatistics> for CatalogStatisticsType {
+    fn from(catalog: CatalogStatistics) -> Self {
+        Self {
+            series_count: catalog.series_count,
+            active_series_count: catalog.active_series_count,
+            data_point_count: catalog.data_point_count,
+            earliest_date: catalog.earliest_date,
+            latest_date: catalog.latest_date,
+            sources: catalog.sources.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Series, data points and coverage of one data source
+#[derive(SimpleObject)]
+#[graphql(name = "DataSourceStatistics", complex)]
+pub struct DataSourceStatisticsType {
+    pub data_source_id: ID,
+    pub series_count: i64,
+    pub active_series_count: i64,
+    pub data_point_count: i64,
+    pub earliest_date: Option<NaiveDate>,
+    pub latest_date: Option<NaiveDate>,
+    /// When these statistics last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+#[ComplexObject]
+impl DataSourceStatisticsType {
+    /// The data source, loaded through the DataLoader
+    async fn data_source(&self, ctx: &Context<'_>) -> Result<Option<DataSourceType>> {
+        let source_uuid = Uuid::parse_str(&self.data_source_id)?;
+
+        Ok(data_loaders(ctx)?
+            .data_source_loader
+            .load(source_uuid)
+            .await
+            .map(Into::into))
+    }
+}
+
+impl From<DataSourceStatistics> for DataSourceStatisticsType {
+    fn from(statistics: DataSourceStatistics) -> Self {
+        Self {
+            data_source_id: ID::from(statistics.source_id.to_string()),
+            series_count: statistics.series_count,
+            active_series_count: statistics.active_series_count,
+            data_point_count: statistics.data_point_count,
+            earliest_date: statistics.earliest_date,
+            latest_date: statistics.latest_date,
+            updated_at: statistics.updated_at,
+        }
+    }
+}
+
+/// A series or data point mutation in the change feed
+#[derive(SimpleObject)]
+#[graphql(name = "Change")]
+pub struct ChangeType {
+    /// Position in the feed, increasing without gaps
+    pub sequence: i64,
+    /// `series` for the series itself, `series_data` for its data points
+    pub entity_type: String,
+    /// Id of the series
+    pub entity_id: ID,
+    /// `insert`, `update` or `delete`
+    pub operation: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<ChangeFeedEntry> for ChangeType {
+    fn from(entry: ChangeFeedEntry) -> Self {
+        Self {
+            sequence: entry.sequence,
+            entity_type: entry.entity_type,
+            entity_id: ID::from(entry.entity_id.to_string()),
+            operation: entry.operation,
+            changed_at: entry.changed_at,
+        }
+    }
+}
+
+/// Changes after a consumer's position in the change feed
+#[derive(SimpleObject)]
+#[graphql(name = "ChangeFeedPage")]
+pub struct ChangeFeedPageType {
+    /// Changes in feed order
+    pub changes: Vec<ChangeType>,
+    /// Sequence to pass as `since` next
+    pub next_since: i64,
+    /// Changes after `since` were pruned: reload cached data before following the feed
+    pub resync_required: bool,
+}
+
+impl From<ChangeFeedPage> for ChangeFeedPageType {
+    fn from(page: ChangeFeedPage) -> Self {
+        Self {
+            changes: page.changes.into_iter().map(Into::into).collect(),
+            next_since: page.next_since,
+            resync_required: page.resync_required,
+        }
+    }
+}
+
+/// Crawl analytics of every data source over a time window
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CrawlAnalytics")]
+pub struct CrawlAnalyticsType {
+    /// Start of the window (inclusive)
+    pub since: DateTime<Utc>,
+    /// End of the window (exclusive)
+    pub until: DateTime<Utc>,
+    /// Data sources with crawl attempts in the window, by name
+    pub sources: Vec<SourceCrawlAnalyticsType>,
+}
+
+impl From<&CrawlAnalyticsReport> for CrawlAnalyticsType {
+    fn from(report: &CrawlAnalyticsReport) -> Self {
+        Self {
+            since: report.since,
+            until: report.until,
+            sources: report
+                .sources
+                .iter()
+                .map(|source| SourceCrawlAnalyticsType::new(source, report.until))
+                .collect(),
+        }
+    }
+}
+
+/// Crawl attempt statistics for one data source
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "SourceCrawlAnalytics")]
+pub struct SourceCrawlAnalyticsType {
+    pub source_id: ID,
+    pub source_name: String,
+    pub total_attempts: i64,
+    pub successful_attempts: i64,
+    /// Share of attempts that succeeded (0-1)
+    pub success_rate: f64,
+    /// Share of attempts that found new data (0-1)
+    pub data_found_rate: f64,
+    /// Data points added by the window's crawls
+    pub new_data_points: i64,
+    pub avg_response_time_ms: Option<f64>,
+    pub p95_response_time_ms: Option<f64>,
+    /// Average age of the newest observation when data was found
+    pub avg_freshness_hours: Option<f64>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Hours from the last successful crawl to the end of the window
+    pub hours_since_last_success: Option<f64>,
+    /// Failed attempts by error type, most frequent first
+    pub errors: Vec<CrawlErrorCountType>,
+}
+
+impl SourceCrawlAnalyticsType {
+    fn new(source: &SourceCrawlAnalytics, until: DateTime<Utc>) -> Self {
+        let totals = &source.totals;
+        Self {
+            source_id: ID::from(totals.source_id),
+            source_name: totals.source_name.clone(),
+            total_attempts: totals.total_attempts,
+            successful_attempts: totals.successful_attempts,
+            success_rate: source.success_rate(),
+            data_found_rate: source.data_found_rate(),
+            new_data_points: totals.new_data_points,
+            avg_response_time_ms: totals.avg_response_time_ms,
+            p95_response_time_ms: totals.p95_response_time_ms,
+            avg_freshness_hours: totals.avg_freshness_hours,
+            last_attempt_at: totals.last_attempt_at,
+            last_success_at: totals.last_success_at,
+            hours_since_last_success: source.hours_since_last_success(until),
+            errors: source
+                .errors
+                .iter()
+                .map(CrawlErrorCountType::from)
+                .collect(),
+        }
+    }
+}
+
+/// Failed crawl attempts sharing an error type
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CrawlErrorCount")]
+pub struct CrawlErrorCountType {
+    /// Error type recorded by the crawler, or "unknown"
+    pub error_type: String,
+    pub count: i64,
+    pub last_seen_at: DateTime<Utc>,
+    pub last_error_message: Option<String>,
+}
+
+impl From<&CrawlErrorCount> for CrawlErrorCountType {
+    fn from(error: &CrawlErrorCount) -> Self {
+        Self {
+            error_type: error.error_type.clone(),
+            count: error.count,
+            last_seen_at: error.last_seen_at,
+            last_error_message: error.last_error_message.clone(),
+        }
+    }
+}
+
+/// Crawler status information
+#[derive(SimpleObject)]
+#[graphql(name = "CrawlerStatus")]
+pub struct CrawlerStatusType {
+    pub is_running: bool,
+    pub active_workers: i32,
+    pub last_crawl: Option<DateTime<Utc>>,
+    pub next_scheduled_crawl: Option<DateTime<Utc>>,
+}
+
+/// Input types for mutations and complex queries
+#[derive(InputObject)]
+#[graphql(name = "SeriesFilter")]
+pub struct SeriesFilterInput {
+    pub source_id: Option<ID>,
     pub frequency: Option<SeriesFrequencyType>,
     pub is_active: Option<bool>,
-    pub search_query: Option<String>,
+    pub search_query: Option<String>,
+}
+
+#[derive(InputObject)]
+#[graphql(name = "DataFilter")]
+pub struct DataFilterInput {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub original_only: Option<bool>,
+    pub latest_revision_only: Option<bool>,
+    /// Leave out revisions written by manual data corrections
+    pub exclude_corrections: Option<bool>,
+}
+
+impl Default for DataFilterInput {
+    fn default() -> Self {
+        Self {
+            start_date: None,
+            end_date: None,
+            original_only: Some(false),
+            latest_revision_only: Some(false),
+            exclude_corrections: Some(false),
+        }
+    }
+}
+
+#[derive(InputObject)]
+#[graphql(name = "PaginationInput")]
+pub struct PaginationInput {
+    pub first: Option<i32>,
+    pub after: Option<String>,
+    pub last: Option<i32>,
+    pub before: Option<String>,
+}
+
+#[derive(InputObject)]
+#[graphql(name = "TriggerCrawlInput")]
+pub struct TriggerCrawlInput {
+    pub sources: Option<Vec<String>>,
+    pub series_ids: Option<Vec<String>>,
+    pub priority: Option<i32>,
+}
+
+/// GraphQL representation of a search result
+#[derive(Clone, SimpleObject)]
+pub struct SeriesSearchResultType {
+    /// Unique identifier for the series
+    pub id: ID,
+    /// Series title
+    pub title: String,
+    /// Series description
+    pub description: Option<String>,
+    /// External identifier from data source
+    pub external_id: String,
+    /// Data source ID
+    pub source_id: ID,
+    /// Data frequency (Monthly, Quarterly, etc.)
+    pub frequency: String,
+    /// Data units
+    pub units: String,
+    /// Series start date
+    pub start_date: NaiveDate,
+    /// Series end date (if applicable)
+    pub end_date: Option<NaiveDate>,
+    /// Last update timestamp
+    pub last_updated: DateTime<Utc>,
+    /// Whether the series is active
+    pub is_active: bool,
+    /// Search relevance ranking score
+    pub rank: f32,
+    /// Similarity score for fuzzy matching
+    pub similarity_score: f32,
+    /// HTML-escaped title with matched words wrapped in `<mark>`
+    pub title_highlight: String,
+    /// HTML-escaped description fragments with matched words wrapped in `<mark>`
+    pub description_highlight: Option<String>,
+}
+
+impl From<SeriesSearchResult> for SeriesSearchResultType {
+    fn from(result: SeriesSearchResult) -> Self {
+        Self {
+            id: ID::from(result.id),
+            title: result.title,
+            description: result.description,
+            external_id: result.external_id,
+            source_id: ID::from(result.source_id),
+            frequency: result.frequency,
+            units: result.units,
+            start_date: result.start_date,
+            end_date: result.end_date,
+            last_updated: DateTime::from_naive_utc_and_offset(result.last_updated, Utc),
+            is_active: result.is_active,
+            rank: result.rank,
+            similarity_score: result.similarity_score,
+            title_highlight: result.title_highlight,
+            description_highlight: result.description_highlight,
+        }
+    }
+}
+
+/// GraphQL representation of a company search result
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "CompanySearchResult")]
+pub struct CompanySearchResultType {
+    pub id: ID,
+    pub cik: String,
+    pub ticker: Option<String>,
+    pub name: String,
+    pub industry: Option<String>,
+    pub sector: Option<String>,
+    pub is_active: bool,
+    /// Search relevance ranking score
+    pub rank: f32,
+}
+
+impl From<CompanySearchResult> for CompanySearchResultType {
+    fn from(result: CompanySearchResult) -> Self {
+        Self {
+            id: ID::from(result.id),
+            cik: result.cik,
+            ticker: result.ticker,
+            name: result.name,
+            industry: result.industry,
+            sector: result.sector,
+            is_active: result.is_active,
+            rank: result.rank,
+        }
+    }
+}
+
+/// Ranked series and companies matching a search query
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "SearchResults")]
+pub struct SearchResultsType {
+    /// Query as sent
+    pub query: String,
+    pub series: Vec<SeriesSearchResultType>,
+    pub companies: Vec<CompanySearchResultType>,
+    /// Spelling-corrected query the results are for, when the query as sent matched nothing
+    pub did_you_mean: Option<String>,
+    /// Synonyms the query was expanded with
+    pub synonyms: Vec<String>,
+    pub took_ms: i32,
+}
+
+impl SearchResultsType {
+    pub fn new(query: String, results: SearchResults, took_ms: i32) -> Self {
+        Self {
+            query,
+            series: results.series.into_iter().map(Into::into).collect(),
+            companies: results.companies.into_iter().map(Into::into).collect(),
+            did_you_mean: results.corrected_query,
+            synonyms: results.synonyms,
+            took_ms,
+        }
+    }
+}
+
+/// GraphQL representation of search suggestions
+#[derive(Clone, SimpleObject)]
+pub struct SearchSuggestionType {
+    /// Suggested query text
+    pub suggestion: String,
+    /// Number of matching series
+    pub match_count: i32,
+    /// Type of suggestion
+    pub suggestion_type: SuggestionTypeEnum,
+    /// Confidence score
+    pub confidence: f32,
+}
+
+impl From<SearchSuggestion> for SearchSuggestionType {
+    fn from(suggestion: SearchSuggestion) -> Self {
+        Self {
+            suggestion: suggestion.suggestion,
+            match_count: suggestion.match_count,
+            suggestion_type: suggestion.suggestion_type.into(),
+            confidence: suggestion.confidence,
+        }
+    }
+}
+
+/// GraphQL enum for suggestion types
+#[derive(Clone, Copy, Enum, Eq, PartialEq)]
+pub enum SuggestionTypeEnum {
+    /// Spelling correction
+    Correction,
+    /// Query completion
+    Completion,
+    /// Synonym expansion
+    Synonym,
+}
+
+impl From<SuggestionType> for SuggestionTypeEnum {
+    fn from(suggestion_type: SuggestionType) -> Self {
+        match suggestion_type {
+            SuggestionType::Correction => SuggestionTypeEnum::Correction,
+            SuggestionType::Completion => SuggestionTypeEnum::Completion,
+            SuggestionType::Synonym => SuggestionTypeEnum::Synonym,
+        }
+    }
+}
+
+/// GraphQL enum for search sort order
+#[derive(Clone, Copy, Enum, Eq, PartialEq)]
+pub enum SearchSortOrderEnum {
+    /// Sort by relevance score
+    Relevance,
+    /// Sort by title alphabetically
+    Title,
+    /// Sort by last updated date
+    LastUpdated,
+    /// Sort by start date
+    StartDate,
+}
+
+impl From<SearchSortOrderEnum> for SearchSortOrder {
+    fn from(sort_order: SearchSortOrderEnum) -> Self {
+        match sort_order {
+            SearchSortOrderEnum::Relevance => SearchSortOrder::Relevance,
+            SearchSortOrderEnum::Title => SearchSortOrder::Title,
+            SearchSortOrderEnum::LastUpdated => SearchSortOrder::LastUpdated,
+            SearchSortOrderEnum::StartDate => SearchSortOrder::StartDate,
+        }
+    }
+}
+
+/// GraphQL input for search parameters
+#[derive(Clone, InputObject)]
+pub struct SearchParamsInput {
+    /// Search query text
+    pub query: String,
+    /// Minimum similarity threshold for fuzzy matching
+    pub similarity_threshold: Option<f32>,
+    /// Maximum number of results
+    pub limit: Option<i32>,
+    /// Offset for pagination
+    pub offset: Option<i32>,
+    /// Filter by data source ID
+    pub source_id: Option<ID>,
+    /// Filter by series frequency
+    pub frequency: Option<String>,
+    /// Include inactive series
+    pub include_inactive: Option<bool>,
+    /// Sort order for results
+    pub sort_by: Option<SearchSortOrderEnum>,
+}
+
+/// GraphQL representation of a chart annotation
+#[derive(Clone, SimpleObject)]
+pub struct ChartAnnotationType {
+    /// Annotation ID
+    pub id: ID,
+    /// User who created the annotation
+    pub user_id: ID,
+    /// Associated series ID (if applicable)
+    pub series_id: Option<String>,
+    /// Associated chart ID (if applicable)
+    pub chart_id: Option<ID>,
+    /// Date the annotation refers to
+    pub annotation_date: NaiveDate,
+    /// Value the annotation refers to (if applicable)
+    pub annotation_value: Option<BigDecimal>,
+    /// Annotation title
+    pub title: String,
+    /// Annotation description/content
+    pub description: Option<String>,
+    /// Annotation color for display
+    pub color: Option<String>,
+    /// Type of annotation (note, highlight, warning, etc.)
+    pub annotation_type: Option<String>,
+    /// Whether the annotation is visible to others
+    pub is_visible: Option<bool>,
+    /// Whether the annotation is pinned
+    pub is_pinned: Option<bool>,
+    /// Tags associated with the annotation
+    pub tags: Option<Vec<Option<String>>>,
+    /// Organization the annotation is shared with (if scoped to one)
+    pub organization_id: Option<ID>,
+    /// Creation timestamp
+    pub created_at: Option<DateTime<Utc>>,
+    /// Last update timestamp
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<ChartAnnotation> for ChartAnnotationType {
+    fn from(annotation: ChartAnnotation) -> Self {
+        Self {
+            id: ID::from(annotation.id),
+            user_id: ID::from(annotation.user_id),
+            series_id: annotation.series_id,
+            chart_id: annotation.chart_id.map(ID::from),
+            annotation_date: annotation.annotation_date,
+            annotation_value: annotation.annotation_value,
+            title: annotation.title,
+            description: annotation.description,
+            color: annotation.color,
+            annotation_type: annotation.annotation_type,
+            is_visible: annotation.is_visible,
+            is_pinned: annotation.is_pinned,
+            tags: annotation.tags,
+            organization_id: annotation.organization_id.map(ID::from),
+            created_at: annotation.created_at,
+            updated_at: annotation.updated_at,
+        }
+    }
+}
+
+/// Outcome of one row of an annotation import
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "AnnotationImportRow")]
+pub struct AnnotationImportRowType {
+    /// Line of the file, counting the header as line 1
+    pub line: i32,
+    /// Created annotation, if the row was imported
+    pub annotation_id: Option<ID>,
+    /// Why the row was not imported
+    pub error: Option<String>,
+}
+
+/// Outcome of importing a CSV of annotations
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "AnnotationImportReport")]
+pub struct AnnotationImportReportType {
+    /// Annotations created
+    pub created: i32,
+    /// Rows that were not imported
+    pub failed: i32,
+    /// Every row in file order
+    pub rows: Vec<AnnotationImportRowType>,
+}
+
+impl From<AnnotationImportReport> for AnnotationImportReportType {
+    fn from(report: AnnotationImportReport) -> Self {
+        Self {
+            created: report.created() as i32,
+            failed: report.failed() as i32,
+            rows: report
+                .rows
+                .into_iter()
+                .map(|row| AnnotationImportRowType {
+                    line: row.line as i32,
+                    annotation_id: row.annotation_id.map(ID::from),
+                    error: row.error,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GraphQL representation of an annotation comment
+#[derive(Clone, SimpleObject)]
+pub struct AnnotationCommentType {
+    /// Comment ID
+    pub id: ID,
+    /// Associated annotation ID
+    pub annotation_id: ID,
+    /// User who created the comment
+    pub user_id: ID,
+    /// Comment content
+    pub content: String,
+    /// Whether the comment thread is resolved
+    pub is_resolved: Option<bool>,
+    /// Creation timestamp
+    pub created_at: Option<DateTime<Utc>>,
+    /// Last update timestamp
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<AnnotationComment> for AnnotationCommentType {
+    fn from(comment: AnnotationComment) -> Self {
+        Self {
+            id: ID::from(comment.id),
+            annotation_id: ID::from(comment.annotation_id),
+            user_id: ID::from(comment.user_id),
+            content: comment.content,
+            is_resolved: comment.is_resolved,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+/// In-app notification for the current user
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "Notification")]
+pub struct NotificationType {
+    /// Notification ID
+    pub id: ID,
+    /// User whose action triggered the notification
+    pub actor_id: Option<ID>,
+    /// Event type, e.g. `annotation_assigned` or `annotation_reply`
+    pub notification_type: String,
+    /// Kind of record the notification links to
+    pub subject_type: String,
+    /// ID of the linked record
+    pub subject_id: ID,
+    /// Short summary
+    pub title: String,
+    /// Notification body
+    pub message: String,
+    /// Whether the notification has been read
+    pub is_read: bool,
+    /// When the notification was read
+    pub read_at: Option<DateTime<Utc>>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Notification> for NotificationType {
+    fn from(notification: Notification) -> Self {
+        Self {
+            id: ID::from(notification.id),
+            actor_id: notification.actor_id.map(ID::from),
+            is_read: notification.is_read(),
+            notification_type: notification.notification_type,
+            subject_type: notification.subject_type,
+            subject_id: ID::from(notification.subject_id),
+            title: notification.title,
+            message: notification.message,
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+        }
+    }
+}
+
+/// Which emails the current user receives
+///
+/// Emails also require notifications to be enabled on the user.
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "EmailPreferences")]
+pub struct EmailPreferencesType {
+    /// Email when someone shares a chart with the user
+    pub invites: bool,
+    /// Email when one of the user's series alert rules fires
+    pub alerts: bool,
+    /// Weekly summary of the user's notifications
+    pub weekly_digest: bool,
+    /// When the last weekly digest was sent
+    pub last_digest_at: Option<DateTime<Utc>>,
+}
+
+impl From<EmailPreferences> for EmailPreferencesType {
+    fn from(preferences: EmailPreferences) -> Self {
+        Self {
+            invites: preferences.invites,
+            alerts: preferences.alerts,
+            weekly_digest: preferences.weekly_digest,
+            last_digest_at: preferences.last_digest_at,
+        }
+    }
+}
+
+/// Input for changing email preferences; omitted fields are left unchanged
+#[derive(InputObject)]
+pub struct UpdateEmailPreferencesInput {
+    pub invites: Option<bool>,
+    pub alerts: Option<bool>,
+    pub weekly_digest: Option<bool>,
+}
+
+impl From<UpdateEmailPreferencesInput> for EmailPreferencesUpdate {
+    fn from(input: UpdateEmailPreferencesInput) -> Self {
+        Self {
+            invites: input.invites,
+            alerts: input.alerts,
+            weekly_digest: input.weekly_digest,
+        }
+    }
+}
+
+/// GraphQL representation of a chart collaborator
+#[derive(Clone, SimpleObject)]
+pub struct ChartCollaboratorType {
+    /// Collaborator entry ID
+    pub id: ID,
+    /// Chart ID being shared
+    pub chart_id: ID,
+    /// User ID of the collaborator
+    pub user_id: ID,
+    /// User who invited this collaborator
+    pub invited_by: Option<ID>,
+    /// Role/permission level
+    pub role: Option<String>,
+    /// Detailed permissions (JSON)
+    pub permissions: Option<String>, // Serialize JSON as string for GraphQL
+    /// Creation timestamp
+    pub created_at: Option<DateTime<Utc>>,
+    /// Last access timestamp
+    pub last_accessed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ChartCollaborator> for ChartCollaboratorType {
+    fn from(collaborator: ChartCollaborator) -> Self {
+        Self {
+            id: ID::from(collaborator.id),
+            chart_id: ID::from(collaborator.chart_id),
+            user_id: ID::from(collaborator.user_id),
+            invited_by: collaborator.invited_by.map(ID::from),
+            role: collaborator.role,
+            permissions: collaborator
+                .permissions
+                .map(|p| serde_json::to_string(&p).unwrap_or_default()),
+            created_at: collaborator.created_at,
+            last_accessed_at: collaborator.last_accessed_at,
+        }
+    }
+}
+
+/// GraphQL representation of an organization
+#[derive(Clone, SimpleObject)]
+pub struct OrganizationType {
+    /// Organization ID
+    pub id: ID,
+    /// Display name
+    pub name: String,
+    /// Unique URL-safe identifier
+    pub slug: String,
+    /// Description
+    pub description: Option<String>,
+    /// User who created the organization
+    pub created_by: Option<ID>,
+    /// Current user's role in the organization (when listed for a user)
+    pub viewer_role: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Organization> for OrganizationType {
+    fn from(organization: Organization) -> Self {
+        Self {
+            id: ID::from(organization.id),
+            name: organization.name,
+            slug: organization.slug,
+            description: organization.description,
+            created_by: organization.created_by.map(ID::from),
+            viewer_role: None,
+            created_at: organization.created_at,
+            updated_at: organization.updated_at,
+        }
+    }
+}
+
+/// GraphQL representation of an organization membership
+#[derive(Clone, SimpleObject)]
+pub struct OrganizationMemberType {
+    /// Membership ID
+    pub id: ID,
+    /// Organization ID
+    pub organization_id: ID,
+    /// Member user ID
+    pub user_id: ID,
+    /// Membership role (owner, admin, member, viewer)
+    pub role: String,
+    /// User who added this member
+    pub invited_by: Option<ID>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OrganizationMember> for OrganizationMemberType {
+    fn from(member: OrganizationMember) -> Self {
+        Self {
+            id: ID::from(member.id),
+            organization_id: ID::from(member.organization_id),
+            user_id: ID::from(member.user_id),
+            role: member.role,
+            invited_by: member.invited_by.map(ID::from),
+            created_at: member.created_at,
+        }
+    }
+}
+
+/// GraphQL representation of a chart shared with an organization
+#[derive(Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct OrganizationChartShareType {
+    /// Share ID
+    pub id: ID,
+    /// Organization ID
+    pub organization_id: ID,
+    /// Shared chart ID
+    pub chart_id: ID,
+    /// User who shared the chart
+    pub shared_by: Option<ID>,
+    /// Permission level granted to members (view, comment, edit, admin)
+    pub permission_level: String,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+#[ComplexObject]
+impl OrganizationChartShareType {
+    /// The shared chart, if the current user may view it
+    async fn chart(&self, ctx: &Context<'_>) -> Result<Option<SavedChartType>> {
+        let user = current_user(ctx)?;
+        let pool = ctx.data::<DatabasePool>()?;
+        let chart_uuid = Uuid::parse_str(&self.chart_id)?;
+
+        Ok(viewable_saved_chart(pool, user.id, chart_uuid)
+            .await?
+            .map(SavedChartType::from))
+    }
+}
+
+impl From<OrganizationChartShare> for OrganizationChartShareType {
+    fn from(share: OrganizationChartShare) -> Self {
+        Self {
+            id: ID::from(share.id),
+            organization_id: ID::from(share.organization_id),
+            chart_id: ID::from(share.chart_id),
+            shared_by: share.shared_by.map(ID::from),
+            permission_level: share.permission_level,
+            created_at: share.created_at,
+        }
+    }
+}
+
+/// GraphQL representation of a saved chart configuration
+#[derive(Clone, SimpleObject)]
+pub struct SavedChartType {
+    /// Saved chart ID
+    pub id: ID,
+    /// Owner user ID
+    pub user_id: ID,
+    /// Chart title
+    pub title: String,
+    /// Description
+    pub description: Option<String>,
+    /// Frontend chart configuration (chart type, colors, axes)
+    pub chart_config: serde_json::Value,
+    /// Series plotted on the chart
+    pub series_ids: Vec<ID>,
+    /// Transformation applied to each series, keyed by series ID
+    pub transformations: serde_json::Value,
+    /// Start of the displayed date range
+    pub start_date: Option<NaiveDate>,
+    /// End of the displayed date range
+    pub end_date: Option<NaiveDate>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+    /// Whether anyone may render the chart at /embed/chart/{id}.png or .svg
+    pub is_public: bool,
+}
+
+impl From<SavedChart> for SavedChartType {
+    fn from(chart: SavedChart) -> Self {
+        Self {
+            id: ID::from(chart.id),
+            user_id: ID::from(chart.user_id),
+            title: chart.title,
+            description: chart.description,
+            chart_config: chart.chart_config,
+            series_ids: chart.series_ids.into_iter().map(ID::from).collect(),
+            transformations: chart.transformations,
+            start_date: chart.start_date,
+            end_date: chart.end_date,
+            created_at: chart.created_at,
+            updated_at: chart.updated_at,
+            is_public: chart.is_public,
+        }
+    }
+}
+
+/// Load a saved chart if `user_id` may view it
+///
+/// Owners, collaborators and members of organizations the chart is shared
+/// with may view it (see [`CollaborationService::chart_permission`]).
+pub(crate) async fn viewable_saved_chart(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    chart_id: Uuid,
+) -> Result<Option<SavedChart>> {
+    let permission = CollaborationService::new(pool.clone())
+        .chart_permission(user_id, chart_id)
+        .await?;
+    if !permission.is_some_and(|permission| permission.can_view()) {
+        return Ok(None);
+    }
+
+    Ok(SavedChart::find(pool, chart_id).await?)
+}
+
+/// Comparison a series alert rule makes against its threshold
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AlertCondition")]
+pub enum AlertConditionType {
+    /// Value is above the threshold
+    ValueAbove,
+    /// Value is below the threshold
+    ValueBelow,
+    /// Year-over-year change in percent is above the threshold
+    YoyChangeAbove,
+    /// Year-over-year change in percent is below the threshold
+    YoyChangeBelow,
+}
+
+impl From<AlertCondition> for AlertConditionType {
+    fn from(condition: AlertCondition) -> Self {
+        match condition {
+            AlertCondition::ValueAbove => Self::ValueAbove,
+            AlertCondition::ValueBelow => Self::ValueBelow,
+            AlertCondition::YoyChangeAbove => Self::YoyChangeAbove,
+            AlertCondition::YoyChangeBelow => Self::YoyChangeBelow,
+        }
+    }
+}
+
+impl From<AlertConditionType> for AlertCondition {
+    fn from(condition: AlertConditionType) -> Self {
+        match condition {
+            AlertConditionType::ValueAbove => Self::ValueAbove,
+            AlertConditionType::ValueBelow => Self::ValueBelow,
+            AlertConditionType::YoyChangeAbove => Self::YoyChangeAbove,
+            AlertConditionType::YoyChangeBelow => Self::YoyChangeBelow,
+        }
+    }
+}
+
+/// Alert the current user set up on a series
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "SeriesAlertRule")]
+pub struct SeriesAlertRuleType {
+    /// Alert rule ID
+    pub id: ID,
+    /// Watched series ID
+    pub series_id: ID,
+    /// Name shown in notifications
+    pub name: String,
+    pub condition: AlertConditionType,
+    /// Level the value (or YoY change in percent) is compared against
+    pub threshold: BigDecimal,
+    /// Consecutive latest observations that must meet the condition
+    pub window_periods: i32,
+    /// Whether the rule is evaluated when new data arrives
+    pub is_active: bool,
+    /// Whether the condition held at the last evaluation
+    pub is_triggered: bool,
+    pub last_evaluated_at: Option<DateTime<Utc>>,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+    /// Observation date that last fired the rule
+    pub last_triggered_date: Option<NaiveDate>,
+    /// Value or YoY change that last fired the rule
+    pub last_triggered_value: Option<BigDecimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<SeriesAlertRule> for SeriesAlertRuleType {
+    type Error = GraphQLError;
+
+    fn try_from(rule: SeriesAlertRule) -> Result<Self> {
+        Ok(Self {
+            id: ID::from(rule.id),
+            series_id: ID::from(rule.series_id),
+            condition: rule.condition()?.into(),
+            name: rule.name,
+            threshold: rule.threshold,
+            window_periods: rule.window_periods,
+            is_active: rule.is_active,
+            is_triggered: rule.is_triggered,
+            last_evaluated_at: rule.last_evaluated_at,
+            last_triggered_at: rule.last_triggered_at,
+            last_triggered_date: rule.last_triggered_date,
+            last_triggered_value: rule.last_triggered_value,
+            created_at: rule.created_at,
+            updated_at: rule.updated_at,
+        })
+    }
+}
+
+/// How two series came to be linked as equivalent
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "SeriesLinkSource")]
+pub enum SeriesLinkSourceType {
+    /// Linked by a curator
+    Curated,
+    /// Linked by the title matcher
+    Heuristic,
+}
+
+impl From<SeriesLinkSource> for SeriesLinkSourceType {
+    fn from(source: SeriesLinkSource) -> Self {
+        match source {
+            SeriesLinkSource::Curated => Self::Curated,
+            SeriesLinkSource::Heuristic => Self::Heuristic,
+        }
+    }
+}
+
+/// Link between two equivalent series from different data sources
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "SeriesLink")]
+pub struct SeriesLinkType {
+    /// Link ID
+    pub id: ID,
+    /// One of the linked series
+    pub series_id: ID,
+    /// The other linked series
+    pub linked_series_id: ID,
+    pub link_source: SeriesLinkSourceType,
+    /// How sure the linker is that the series are equivalent, in (0, 1]
+    pub confidence: f64,
+    /// Curator who made the link
+    pub created_by: Option<ID>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<SeriesLink> for SeriesLinkType {
+    type Error = GraphQLError;
+
+    fn try_from(link: SeriesLink) -> Result<Self> {
+        Ok(Self {
+            id: ID::from(link.id),
+            series_id: ID::from(link.series_id),
+            linked_series_id: ID::from(link.linked_series_id),
+            link_source: link.link_source()?.into(),
+            confidence: link.confidence,
+            created_by: link.created_by.map(ID::from),
+            created_at: link.created_at,
+        })
+    }
+}
+
+/// A series from another data source that measures the same thing
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "EquivalentSeries")]
+pub struct EquivalentSeriesType {
+    pub series: EconomicSeriesType,
+    /// Weakest link confidence on the path to this series
+    pub confidence: f64,
+    /// Whether curators made every link on the path
+    pub curated: bool,
+    /// Number of links between the requested series and this one
+    pub hops: i32,
+}
+
+impl From<EquivalentSeriesMatch> for EquivalentSeriesType {
+    fn from(equivalent: EquivalentSeriesMatch) -> Self {
+        Self {
+            series: equivalent.series.into(),
+            confidence: equivalent.confidence,
+            curated: equivalent.curated,
+            hops: equivalent.hops as i32,
+        }
+    }
+}
+
+/// GraphQL representation of a user
+#[derive(Clone, SimpleObject)]
+pub struct UserType {
+    /// User ID
+    pub id: ID,
+    /// Email address
+    pub email: String,
+    /// Display name
+    pub name: String,
+    /// Avatar URL
+    pub avatar_url: Option<String>,
+    /// Authentication provider
+    pub provider: String,
+    /// User role
+    pub role: String,
+    /// Organization
+    pub organization: Option<String>,
+    /// UI theme preference
+    pub theme: String,
+    /// Default chart type preference
+    pub default_chart_type: String,
+    /// Whether notifications are enabled
+    pub notifications_enabled: bool,
+    /// Whether collaboration features are enabled
+    pub collaboration_enabled: bool,
+    /// Whether the account is active
+    pub is_active: bool,
+    /// Whether email is verified
+    pub email_verified: bool,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+    /// Last login timestamp
+    pub last_login_at: Option<DateTime<Utc>>,
+    /// Subscription deciding which licensed data sources the user may read
+    pub subscription_tier: SubscriptionTierType,
+    /// Display and notification preferences
+    pub preferences: UserPreferencesType,
+}
+
+impl From<User> for UserType {
+    fn from(user: User) -> Self {
+        let preferences = UserPreferencesType::from(&user);
+        Self {
+            id: ID::from(user.id),
+            email: user.email,
+            name: user.name,
+            avatar_url: user.avatar_url,
+            provider: user.provider,
+            role: user.role,
+            organization: user.organization,
+            theme: user.theme,
+            default_chart_type: user.default_chart_type,
+            notifications_enabled: user.notifications_enabled,
+            collaboration_enabled: user.collaboration_enabled,
+            is_active: user.is_active,
+            email_verified: user.email_verified,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            last_login_at: user.last_login_at,
+            subscription_tier: user.subscription_tier.into(),
+            preferences,
+        }
+    }
+}
+
+/// A user's display and notification preferences
+#[derive(Clone, SimpleObject)]
+pub struct UserPreferencesType {
+    /// UI theme ("light" or "dark")
+    pub theme: String,
+    /// Chart type new charts start with ("line", "bar", "area" or "scatter")
+    pub default_chart_type: String,
+    /// Whether notifications are enabled
+    pub notifications_enabled: bool,
+    /// Whether collaboration features are enabled
+    pub collaboration_enabled: bool,
+}
+
+impl From<&User> for UserPreferencesType {
+    fn from(user: &User) -> Self {
+        Self {
+            theme: user.theme.clone(),
+            default_chart_type: user.default_chart_type.clone(),
+            notifications_enabled: user.notifications_enabled,
+            collaboration_enabled: user.collaboration_enabled,
+        }
+    }
+}
+
+/// Input for changing the current user's preferences; omitted fields are unchanged
+#[derive(InputObject)]
+pub struct UpdateUserPreferencesInput {
+    /// UI theme ("light" or "dark")
+    pub theme: Option<String>,
+    /// Chart type new charts start with ("line", "bar", "area" or "scatter")
+    pub default_chart_type: Option<String>,
+    /// Whether notifications are enabled
+    pub notifications_enabled: Option<bool>,
+    /// Whether collaboration features are enabled
+    pub collaboration_enabled: Option<bool>,
+}
+
+impl From<UpdateUserPreferencesInput> for PreferenceChanges {
+    fn from(input: UpdateUserPreferencesInput) -> Self {
+        Self {
+            theme: input.theme,
+            default_chart_type: input.default_chart_type,
+            notifications_enabled: input.notifications_enabled,
+            collaboration_enabled: input.collaboration_enabled,
+        }
+    }
+}
+
+/// Input for creating a new annotation
+#[derive(InputObject)]
+pub struct CreateAnnotationInput {
+    /// User ID creating the annotation
+    pub user_id: ID,
+    /// Series ID the annotation is for
+    pub series_id: ID,
+    /// Date the annotation refers to
+    pub annotation_date: NaiveDate,
+    /// Value the annotation refers to (optional)
+    pub annotation_value: Option<BigDecimal>,
+    /// Annotation title
+    pub title: String,
+    /// Annotation content/description
+    pub content: String,
+    /// Type of annotation (note, highlight, warning, etc.)
+    pub annotation_type: String,
+    /// Color for display (optional)
+    pub color: Option<String>,
+    /// Whether the annotation is public (visible to others)
+    pub is_public: Option<bool>,
+    /// Organization to share the annotation with (optional)
+    pub organization_id: Option<ID>,
+}
+
+/// Input for adding a comment to an annotation
+#[derive(InputObject)]
+pub struct AddCommentInput {
+    /// User ID adding the comment
+    pub user_id: ID,
+    /// Annotation ID to comment on
+    pub annotation_id: ID,
+    /// Comment content
+    pub content: String,
+}
+
+/// Input for sharing a chart with another user
+#[derive(InputObject)]
+pub struct ShareChartInput {
+    /// Owner user ID (who is sharing)
+    pub owner_user_id: ID,
+    /// Target user ID (who to share with)
+    pub target_user_id: ID,
+    /// Chart ID to share
+    pub chart_id: ID,
+    /// Permission level (view, comment, edit, admin)
+    pub permission_level: String,
+}
+
+/// Input for creating an organization
+#[derive(InputObject)]
+pub struct CreateOrganizationInput {
+    /// Display name
+    pub name: String,
+    /// Unique URL-safe identifier (derived from the name when omitted)
+    pub slug: Option<String>,
+    /// Description (optional)
+    pub description: Option<String>,
+}
+
+/// Input for adding a member to an organization or changing their role
+#[derive(InputObject)]
+pub struct OrganizationMemberInput {
+    /// Organization ID
+    pub organization_id: ID,
+    /// User ID of the member
+    pub user_id: ID,
+    /// Membership role (owner, admin, member, viewer)
+    pub role: String,
+}
+
+/// Input for sharing a chart with an organization
+#[derive(InputObject)]
+pub struct ShareChartWithOrganizationInput {
+    /// Organization ID
+    pub organization_id: ID,
+    /// Chart ID to share
+    pub chart_id: ID,
+    /// Permission level granted to members (view, comment, edit, admin)
+    pub permission_level: String,
+}
+
+/// Input for saving a chart configuration
+#[derive(InputObject)]
+pub struct CreateSavedChartInput {
+    /// Chart title
+    pub title: String,
+    /// Description (optional)
+    pub description: Option<String>,
+    /// Frontend chart configuration (defaults to an empty object)
+    pub chart_config: Option<serde_json::Value>,
+    /// Series plotted on the chart
+    pub series_ids: Vec<ID>,
+    /// Transformation applied to each series, keyed by series ID
+    pub transformations: Option<serde_json::Value>,
+    /// Start of the displayed date range
+    pub start_date: Option<NaiveDate>,
+    /// End of the displayed date range
+    pub end_date: Option<NaiveDate>,
+    /// Allow anyone to render the chart as an embeddable image (defaults to false)
+    pub is_public: Option<bool>,
+}
+
+/// Input for updating a saved chart; omitted fields are left unchanged
+#[derive(InputObject)]
+pub struct UpdateSavedChartInput {
+    /// Saved chart ID
+    pub id: ID,
+    /// Chart title
+    pub title: Option<String>,
+    /// Description (null clears it)
+    pub description: MaybeUndefined<String>,
+    /// Frontend chart configuration
+    pub chart_config: Option<serde_json::Value>,
+    /// Series plotted on the chart
+    pub series_ids: Option<Vec<ID>>,
+    /// Transformation applied to each series, keyed by series ID
+    pub transformations: Option<serde_json::Value>,
+    /// Start of the displayed date range (null clears it)
+    pub start_date: MaybeUndefined<NaiveDate>,
+    /// End of the displayed date range (null clears it)
+    pub end_date: MaybeUndefined<NaiveDate>,
+    /// Allow anyone to render the chart as an embeddable image
+    pub is_public: Option<bool>,
+}
+
+/// Input for creating a series alert rule
+#[derive(InputObject)]
+pub struct CreateSeriesAlertRuleInput {
+    /// Series to watch
+    pub series_id: ID,
+    /// Name shown in notifications, e.g. "Unemployment above 5%"
+    pub name: String,
+    pub condition: AlertConditionType,
+    /// Level the value (or YoY change in percent) is compared against
+    pub threshold: BigDecimal,
+    /// Consecutive latest observations that must meet the condition (default 1)
+    pub window_periods: Option<i32>,
+}
+
+/// Input for updating a series alert rule; omitted fields are left unchanged
+///
+/// Changing the condition, threshold or window lets the rule fire again.
+#[derive(InputObject)]
+pub struct UpdateSeriesAlertRuleInput {
+    /// Alert rule ID
+    pub id: ID,
+    pub name: Option<String>,
+    pub condition: Option<AlertConditionType>,
+    pub threshold: Option<BigDecimal>,
+    pub window_periods: Option<i32>,
+    pub is_active: Option<bool>,
 }
 
-#[derive(InputObject)]
-#[graphql(name = "DataFilter")]
-pub struct DataFilterInput {
-    pub start_date: Option<NaiveDate>,
-    pub end_date: Option<NaiveDate>,
-    pub original_only: Option<bool>,
-    pub latest_revision_only: Option<bool>,
+/// Event a webhook can subscribe to
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "WebhookEvent")]
+pub enum WebhookEventType {
+    /// New data points were stored for a series
+    SeriesUpdated,
+    /// Crawling a series failed
+    CrawlFailed,
 }
 
-impl Default for DataFilterInput {
-    fn default() -> Self {
-        Self {
-            start_date: None,
-            end_date: None,
-            original_only: Some(false),
-            latest_revision_only: Some(false),
+impl From<WebhookEvent> for WebhookEventType {
+    fn from(event: WebhookEvent) -> Self {
+        match event {
+            WebhookEvent::SeriesUpdated => Self::SeriesUpdated,
+            WebhookEvent::CrawlFailed => Self::CrawlFailed,
         }
     }
 }
 
-#[derive(InputObject)]
-#[graphql(name = "PaginationInput")]
-pub struct PaginationInput {
-    pub first: Option<i32>,
-    pub after: Option<String>,
-    pub last: Option<i32>,
-    pub before: Option<String>,
+impl From<WebhookEventType> for WebhookEvent {
+    fn from(event: WebhookEventType) -> Self {
+        match event {
+            WebhookEventType::SeriesUpdated => Self::SeriesUpdated,
+            WebhookEventType::CrawlFailed => Self::CrawlFailed,
+        }
+    }
 }
 
-#[derive(InputObject)]
-#[graphql(name = "TriggerCrawlInput")]
-pub struct TriggerCrawlInput {
-    pub sources: Option<Vec<String>>,
-    pub series_ids: Option<Vec<String>>,
-    pub priority: Option<i32>,
+/// Where a webhook delivery stands
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "WebhookDeliveryStatus")]
+pub enum WebhookDeliveryStatusType {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// The endpoint accepted the delivery
+    Delivered,
+    /// Every attempt failed
+    Failed,
 }
 
-/// GraphQL representation of a search result
+impl From<WebhookDeliveryStatusType> for WebhookDeliveryStatus {
+    fn from(status: WebhookDeliveryStatusType) -> Self {
+        match status {
+            WebhookDeliveryStatusType::Pending => Self::Pending,
+            WebhookDeliveryStatusType::Delivered => Self::Delivered,
+            WebhookDeliveryStatusType::Failed => Self::Failed,
+        }
+    }
+}
+
+/// Endpoint notified of crawl events for a data source or series
+///
+/// The signing secret is write-only and never returned.
 #[derive(Clone, SimpleObject)]
-pub struct SeriesSearchResultType {
-    /// Unique identifier for the series
+#[graphql(name = "Webhook")]
+pub struct WebhookType {
+    /// Webhook ID
     pub id: ID,
-    /// Series title
-    pub title: String,
-    /// Series description
+    /// URL deliveries are POSTed to
+    pub url: String,
+    /// Watched data source, if the webhook covers a whole source
+    pub source_id: Option<ID>,
+    /// Watched series, if the webhook covers one series
+    pub series_id: Option<ID>,
+    pub event_types: Vec<WebhookEventType>,
     pub description: Option<String>,
-    /// External identifier from data source
-    pub external_id: String,
-    /// Data source ID
-    pub source_id: ID,
-    /// Data frequency (Monthly, Quarterly, etc.)
-    pub frequency: String,
-    /// Data units
-    pub units: String,
-    /// Series start date
-    pub start_date: NaiveDate,
-    /// Series end date (if applicable)
-    pub end_date: Option<NaiveDate>,
-    /// Last update timestamp
-    pub last_updated: DateTime<Utc>,
-    /// Whether the series is active
+    /// Whether new events are delivered
     pub is_active: bool,
-    /// Search relevance ranking score
-    pub rank: f32,
-    /// Similarity score for fuzzy matching
-    pub similarity_score: f32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
-impl From<SeriesSearchResult> for SeriesSearchResultType {
-    fn from(result: SeriesSearchResult) -> Self {
-        Self {
-            id: ID::from(result.id),
-            title: result.title,
-            description: result.description,
-            external_id: result.external_id,
-            source_id: ID::from(result.source_id),
-            frequency: result.frequency,
-            units: result.units,
-            start_date: result.start_date,
-            end_date: result.end_date,
-            last_updated: DateTime::from_naive_utc_and_offset(result.last_updated, Utc),
-            is_active: result.is_active,
-            rank: result.rank,
-            similarity_score: result.similarity_score,
-        }
+impl TryFrom<Webhook> for WebhookType {
+    type Error = GraphQLError;
+
+    fn try_from(webhook: Webhook) -> Result<Self> {
+        let event_types = webhook
+            .event_types
+            .iter()
+            .map(|event| event.parse::<WebhookEvent>().map(WebhookEventType::from))
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(Self {
+            id: ID::from(webhook.id),
+            url: webhook.url,
+            source_id: webhook.source_id.map(ID::from),
+            series_id: webhook.series_id.map(ID::from),
+            event_types,
+            description: webhook.description,
+            is_active: webhook.is_active,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        })
     }
 }
 
-/// GraphQL representation of search suggestions
+/// One event queued for, or sent to, a webhook
 #[derive(Clone, SimpleObject)]
-pub struct SearchSuggestionType {
-    /// Suggested query text
-    pub suggestion: String,
-    /// Number of matching series
-    pub match_count: i32,
-    /// Type of suggestion
-    pub suggestion_type: SuggestionTypeEnum,
-    /// Confidence score
-    pub confidence: f32,
+#[graphql(name = "WebhookDelivery")]
+pub struct WebhookDeliveryType {
+    /// Delivery ID, sent as the X-EconGraph-Delivery header
+    pub id: ID,
+    pub webhook_id: ID,
+    pub event_type: String,
+    /// JSON body sent to the endpoint
+    pub payload: serde_json::Value,
+    /// pending, delivered or failed
+    pub status: String,
+    pub attempts: i32,
+    /// When the next attempt is due, while pending
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
 }
 
-impl From<SearchSuggestion> for SearchSuggestionType {
-    fn from(suggestion: SearchSuggestion) -> Self {
+impl From<WebhookDelivery> for WebhookDeliveryType {
+    fn from(delivery: WebhookDelivery) -> Self {
         Self {
-            suggestion: suggestion.suggestion,
-            match_count: suggestion.match_count,
-            suggestion_type: suggestion.suggestion_type.into(),
-            confidence: suggestion.confidence,
+            id: ID::from(delivery.id),
+            webhook_id: ID::from(delivery.webhook_id),
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_attempt_at: delivery.last_attempt_at,
+            response_status: delivery.response_status,
+            last_error: delivery.last_error,
+            delivered_at: delivery.delivered_at,
+            created_at: delivery.created_at,
         }
     }
 }
 
-/// GraphQL enum for suggestion types
-#[derive(Clone, Copy, Enum, Eq, PartialEq)]
-pub enum SuggestionTypeEnum {
-    /// Spelling correction
-    Correction,
-    /// Query completion
-    Completion,
-    /// Synonym expansion
-    Synonym,
+/// Input for registering a webhook; set exactly one of `sourceId` and `seriesId`
+#[derive(InputObject)]
+pub struct CreateWebhookInput {
+    /// http(s) URL deliveries are POSTed to
+    pub url: String,
+    /// Key for the HMAC-SHA256 signature header, at least 16 characters
+    pub secret: String,
+    /// Data source whose series to watch
+    pub source_id: Option<ID>,
+    /// Single series to watch
+    pub series_id: Option<ID>,
+    pub event_types: Vec<WebhookEventType>,
+    pub description: Option<String>,
 }
 
-impl From<SuggestionType> for SuggestionTypeEnum {
-    fn from(suggestion_type: SuggestionType) -> Self {
-        match suggestion_type {
-            SuggestionType::Correction => SuggestionTypeEnum::Correction,
-            SuggestionType::Completion => SuggestionTypeEnum::Completion,
-            SuggestionType::Synonym => SuggestionTypeEnum::Synonym,
+/// How often a release calendar's data is published
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ReleaseFrequency")]
+pub enum ReleaseFrequencyType {
+    /// Every weekday
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl From<ReleaseFrequency> for ReleaseFrequencyType {
+    fn from(frequency: ReleaseFrequency) -> Self {
+        match frequency {
+            ReleaseFrequency::Daily => Self::Daily,
+            ReleaseFrequency::Weekly => Self::Weekly,
+            ReleaseFrequency::Monthly => Self::Monthly,
+            ReleaseFrequency::Quarterly => Self::Quarterly,
+            ReleaseFrequency::Annual => Self::Annual,
         }
     }
 }
 
-/// GraphQL enum for search sort order
-#[derive(Clone, Copy, Enum, Eq, PartialEq)]
-pub enum SearchSortOrderEnum {
-    /// Sort by relevance score
-    Relevance,
-    /// Sort by title alphabetically
-    Title,
-    /// Sort by last updated date
-    LastUpdated,
-    /// Sort by start date
-    StartDate,
+impl From<ReleaseFrequencyType> for ReleaseFrequency {
+    fn from(frequency: ReleaseFrequencyType) -> Self {
+        match frequency {
+            ReleaseFrequencyType::Daily => Self::Daily,
+            ReleaseFrequencyType::Weekly => Self::Weekly,
+            ReleaseFrequencyType::Monthly => Self::Monthly,
+            ReleaseFrequencyType::Quarterly => Self::Quarterly,
+            ReleaseFrequencyType::Annual => Self::Annual,
+        }
+    }
 }
 
-impl From<SearchSortOrderEnum> for SearchSortOrder {
-    fn from(sort_order: SearchSortOrderEnum) -> Self {
-        match sort_order {
-            SearchSortOrderEnum::Relevance => SearchSortOrder::Relevance,
-            SearchSortOrderEnum::Title => SearchSortOrder::Title,
-            SearchSortOrderEnum::LastUpdated => SearchSortOrder::LastUpdated,
-            SearchSortOrderEnum::StartDate => SearchSortOrder::StartDate,
-        }
+/// When a data source or series publishes new data; crawls are queued right after
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "ReleaseCalendar")]
+pub struct ReleaseCalendarType {
+    /// Release calendar ID
+    pub id: ID,
+    /// Data source whose series of this frequency the calendar covers
+    pub source_id: Option<ID>,
+    /// Single series the calendar covers
+    pub series_id: Option<ID>,
+    pub frequency: ReleaseFrequencyType,
+    /// ISO weekday of the release, 1 = Monday
+    pub day_of_week: Option<i32>,
+    /// Week of the month of `dayOfWeek`, 5 = last
+    pub week_of_month: Option<i32>,
+    pub day_of_month: Option<i32>,
+    /// Month within the quarter or year
+    pub month_of_period: i32,
+    /// Local release time in `timeZone`
+    pub release_time: NaiveTime,
+    /// IANA time zone, e.g. America/New_York
+    pub time_zone: String,
+    /// Minutes after the release the crawl is scheduled
+    pub crawl_delay_minutes: i32,
+    /// Learned from data arrivals rather than entered by an admin
+    pub is_learned: bool,
+    /// Share of past releases a learned calendar fits
+    pub confidence: Option<f64>,
+    pub sample_count: i32,
+    pub is_active: bool,
+    pub next_release_at: Option<DateTime<Utc>>,
+    pub last_queued_release_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<ReleaseCalendar> for ReleaseCalendarType {
+    type Error = GraphQLError;
+
+    fn try_from(calendar: ReleaseCalendar) -> Result<Self> {
+        Ok(Self {
+            id: ID::from(calendar.id),
+            source_id: calendar.source_id.map(ID::from),
+            series_id: calendar.series_id.map(ID::from),
+            frequency: calendar.frequency.parse::<ReleaseFrequency>()?.into(),
+            day_of_week: calendar.day_of_week.map(i32::from),
+            week_of_month: calendar.week_of_month.map(i32::from),
+            day_of_month: calendar.day_of_month.map(i32::from),
+            month_of_period: i32::from(calendar.month_of_period),
+            release_time: calendar.release_time,
+            time_zone: calendar.time_zone,
+            crawl_delay_minutes: calendar.crawl_delay_minutes,
+            is_learned: calendar.is_learned,
+            confidence: calendar.confidence,
+            sample_count: calendar.sample_count,
+            is_active: calendar.is_active,
+            next_release_at: calendar.next_release_at,
+            last_queued_release_at: calendar.last_queued_release_at,
+            created_at: calendar.created_at,
+            updated_at: calendar.updated_at,
+        })
     }
 }
 
-/// GraphQL input for search parameters
-#[derive(Clone, InputObject)]
-pub struct SearchParamsInput {
-    /// Search query text
-    pub query: String,
-    /// Minimum similarity threshold for fuzzy matching
-    pub similarity_threshold: Option<f32>,
-    /// Maximum number of results
-    pub limit: Option<i32>,
-    /// Offset for pagination
-    pub offset: Option<i32>,
-    /// Filter by data source ID
+/// Input for a release calendar; set exactly one of `sourceId` and `seriesId`
+///
+/// Weekly calendars need `dayOfWeek`; monthly and longer ones either
+/// `dayOfMonth` or `weekOfMonth` with `dayOfWeek`.
+#[derive(InputObject)]
+pub struct CreateReleaseCalendarInput {
+    /// Data source whose series of this frequency to schedule
     pub source_id: Option<ID>,
-    /// Filter by series frequency
-    pub frequency: Option<String>,
-    /// Include inactive series
-    pub include_inactive: Option<bool>,
-    /// Sort order for results
-    pub sort_by: Option<SearchSortOrderEnum>,
+    /// Single series to schedule
+    pub series_id: Option<ID>,
+    pub frequency: ReleaseFrequencyType,
+    /// ISO weekday, 1 = Monday
+    pub day_of_week: Option<i32>,
+    /// 1-4, or 5 for the last `dayOfWeek` of the month
+    pub week_of_month: Option<i32>,
+    pub day_of_month: Option<i32>,
+    /// Month within the quarter (1-3) or year (1-12)
+    #[graphql(default = 1)]
+    pub month_of_period: i32,
+    /// Local release time, e.g. 08:30:00
+    pub release_time: NaiveTime,
+    /// IANA time zone, America/New_York when omitted
+    pub time_zone: Option<String>,
+    /// Minutes after the release to crawl, 5 when omitted
+    pub crawl_delay_minutes: Option<i32>,
 }
 
-/// GraphQL representation of a chart annotation
+/// Series a formula variable stands for
 #[derive(Clone, SimpleObject)]
-pub struct ChartAnnotationType {
-    /// Annotation ID
+#[graphql(name = "FormulaBinding")]
+pub struct FormulaBindingType {
+    /// Variable name used in the formula
+    pub variable: String,
+    pub series_id: ID,
+}
+
+/// Series computed from a formula over other series
+#[derive(Clone, SimpleObject)]
+#[graphql(name = "DerivedSeries")]
+pub struct DerivedSeriesType {
+    /// Derived series ID
     pub id: ID,
-    /// User who created the annotation
-    pub user_id: ID,
-    /// Associated series ID (if applicable)
-    pub series_id: Option<String>,
-    /// Associated chart ID (if applicable)
-    pub chart_id: Option<ID>,
-    /// Date the annotation refers to
-    pub annotation_date: NaiveDate,
-    /// Value the annotation refers to (if applicable)
-    pub annotation_value: Option<BigDecimal>,
-    /// Annotation title
+    /// Series holding the computed values; query its data like any other series
+    pub series_id: ID,
+    /// Formula as written, e.g. `nominal / deflator * 100`
+    pub formula: String,
+    /// Series bound to the formula's variables
+    pub inputs: Vec<FormulaBindingType>,
+    pub created_by: Option<ID>,
+    /// When the values were last recomputed
+    pub last_computed_at: Option<DateTime<Utc>>,
+    /// Why the last recompute failed, if it did
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<(DerivedSeries, Vec<DerivedSeriesInput>)> for DerivedSeriesType {
+    fn from((derived, inputs): (DerivedSeries, Vec<DerivedSeriesInput>)) -> Self {
+        Self {
+            id: ID::from(derived.id),
+            series_id: ID::from(derived.series_id),
+            formula: derived.formula,
+            inputs: inputs
+                .into_iter()
+                .map(|input| FormulaBindingType {
+                    variable: input.variable,
+                    series_id: ID::from(input.input_series_id),
+                })
+                .collect(),
+            created_by: derived.created_by.map(ID::from),
+            last_computed_at: derived.last_computed_at,
+            last_error: derived.last_error,
+            created_at: derived.created_at,
+            updated_at: derived.updated_at,
+        }
+    }
+}
+
+/// Binds a formula variable to a series
+#[derive(InputObject)]
+pub struct FormulaBindingInput {
+    /// Variable name used in the formula: letters, digits and underscores
+    pub variable: String,
+    pub series_id: ID,
+}
+
+impl TryFrom<FormulaBindingInput> for FormulaBinding {
+    type Error = GraphQLError;
+
+    fn try_from(input: FormulaBindingInput) -> Result<Self> {
+        Ok(Self {
+            variable: input.variable.trim().to_string(),
+            series_id: Uuid::parse_str(&input.series_id)?,
+        })
+    }
+}
+
+/// Input for creating a derived series
+#[derive(InputObject)]
+pub struct CreateDerivedSeriesInput {
+    /// Title of the new series
     pub title: String,
-    /// Annotation description/content
     pub description: Option<String>,
-    /// Annotation color for display
-    pub color: Option<String>,
-    /// Type of annotation (note, highlight, warning, etc.)
-    pub annotation_type: Option<String>,
-    /// Whether the annotation is visible to others
-    pub is_visible: Option<bool>,
-    /// Whether the annotation is pinned
-    pub is_pinned: Option<bool>,
-    /// Tags associated with the annotation
-    pub tags: Option<Vec<Option<String>>>,
-    /// Creation timestamp
-    pub created_at: Option<DateTime<Utc>>,
-    /// Last update timestamp
-    pub updated_at: Option<DateTime<Utc>>,
+    pub units: Option<String>,
+    /// Formula over the input variables using `+ - * /` and parentheses
+    pub formula: String,
+    /// One binding per formula variable
+    pub inputs: Vec<FormulaBindingInput>,
 }
 
-impl From<ChartAnnotation> for ChartAnnotationType {
-    fn from(annotation: ChartAnnotation) -> Self {
-        Self {
-            id: ID::from(annotation.id),
-            user_id: ID::from(annotation.user_id),
-            series_id: annotation.series_id,
-            chart_id: annotation.chart_id.map(ID::from),
-            annotation_date: annotation.annotation_date,
-            annotation_value: annotation.annotation_value,
-            title: annotation.title,
-            description: annotation.description,
-            color: annotation.color,
-            annotation_type: annotation.annotation_type,
-            is_visible: annotation.is_visible,
-            is_pinned: annotation.is_pinned,
-            tags: annotation.tags,
-            created_at: annotation.created_at,
-            updated_at: annotation.updated_at,
+/// Input for updating a derived series; omitted fields are kept
+#[derive(InputObject)]
+pub struct UpdateDerivedSeriesInput {
+    /// Derived series to update
+    pub id: ID,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    /// New formula; replaces all computed values
+    pub formula: Option<String>,
+    /// New bindings; replaces all computed values
+    pub inputs: Option<Vec<FormulaBindingInput>>,
+}
+
+/// File format of a bulk export
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ExportFormat")]
+pub enum ExportFormatType {
+    Csv,
+    Parquet,
+    Xlsx,
+}
+
+impl From<ExportFormatType> for ExportFormat {
+    fn from(format: ExportFormatType) -> Self {
+        match format {
+            ExportFormatType::Csv => Self::Csv,
+            ExportFormatType::Parquet => Self::Parquet,
+            ExportFormatType::Xlsx => Self::Xlsx,
         }
     }
 }
 
-/// GraphQL representation of an annotation comment
-#[derive(Clone, SimpleObject)]
-pub struct AnnotationCommentType {
-    /// Comment ID
-    pub id: ID,
-    /// Associated annotation ID
-    pub annotation_id: ID,
-    /// User who created the comment
-    pub user_id: ID,
-    /// Comment content
-    pub content: String,
-    /// Whether the comment thread is resolved
-    pub is_resolved: Option<bool>,
-    /// Creation timestamp
-    pub created_at: Option<DateTime<Utc>>,
-    /// Last update timestamp
-    pub updated_at: Option<DateTime<Utc>>,
+impl From<ExportFormat> for ExportFormatType {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Csv => Self::Csv,
+            ExportFormat::Parquet => Self::Parquet,
+            ExportFormat::Xlsx => Self::Xlsx,
+        }
+    }
 }
 
-impl From<AnnotationComment> for AnnotationCommentType {
-    fn from(comment: AnnotationComment) -> Self {
-        Self {
-            id: ID::from(comment.id),
-            annotation_id: ID::from(comment.annotation_id),
-            user_id: ID::from(comment.user_id),
-            content: comment.content,
-            is_resolved: comment.is_resolved,
-            created_at: comment.created_at,
-            updated_at: comment.updated_at,
+/// Where an export job stands
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "ExportJobStatus")]
+pub enum ExportJobStatusType {
+    /// Waiting for a worker
+    Pending,
+    /// The file is being written
+    Running,
+    /// The file is ready to download
+    Completed,
+    /// The export could not be produced; see `errorMessage`
+    Failed,
+    /// The file was deleted after its retention period
+    Expired,
+}
+
+impl From<ExportJobStatus> for ExportJobStatusType {
+    fn from(status: ExportJobStatus) -> Self {
+        match status {
+            ExportJobStatus::Pending => Self::Pending,
+            ExportJobStatus::Running => Self::Running,
+            ExportJobStatus::Completed => Self::Completed,
+            ExportJobStatus::Failed => Self::Failed,
+            ExportJobStatus::Expired => Self::Expired,
         }
     }
 }
 
-/// GraphQL representation of a chart collaborator
+/// A bulk export and, once written, its file
 #[derive(Clone, SimpleObject)]
-pub struct ChartCollaboratorType {
-    /// Collaborator entry ID
+#[graphql(name = "ExportJob")]
+pub struct ExportJobType {
+    /// Export job ID
     pub id: ID,
-    /// Chart ID being shared
-    pub chart_id: ID,
-    /// User ID of the collaborator
-    pub user_id: ID,
-    /// User who invited this collaborator
-    pub invited_by: Option<ID>,
-    /// Role/permission level
-    pub role: Option<String>,
-    /// Detailed permissions (JSON)
-    pub permissions: Option<String>, // Serialize JSON as string for GraphQL
-    /// Creation timestamp
-    pub created_at: Option<DateTime<Utc>>,
-    /// Last access timestamp
-    pub last_accessed_at: Option<DateTime<Utc>>,
+    pub series_ids: Vec<ID>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub format: ExportFormatType,
+    pub status: ExportJobStatusType,
+    pub row_count: Option<i64>,
+    pub file_size_bytes: Option<i64>,
+    /// Why the export failed, if it did
+    pub error_message: Option<String>,
+    /// Signed link to the file while it is kept; fetch a fresh one when it expires
+    pub download_url: Option<String>,
+    /// When `downloadUrl` stops working
+    pub download_url_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the file is deleted
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
-impl From<ChartCollaborator> for ChartCollaboratorType {
-    fn from(collaborator: ChartCollaborator) -> Self {
-        Self {
-            id: ID::from(collaborator.id),
-            chart_id: ID::from(collaborator.chart_id),
-            user_id: ID::from(collaborator.user_id),
-            invited_by: collaborator.invited_by.map(ID::from),
-            role: collaborator.role,
-            permissions: collaborator
-                .permissions
-                .map(|p| serde_json::to_string(&p).unwrap_or_default()),
-            created_at: collaborator.created_at,
-            last_accessed_at: collaborator.last_accessed_at,
+impl TryFrom<ExportJob> for ExportJobType {
+    type Error = GraphQLError;
+
+    fn try_from(job: ExportJob) -> Result<Self> {
+        let download = shared_download_signer().download_url_for(&job, Utc::now());
+
+        Ok(Self {
+            id: ID::from(job.id),
+            series_ids: job.series_ids.iter().map(|id| ID::from(*id)).collect(),
+            start_date: job.start_date,
+            end_date: job.end_date,
+            format: job.export_format()?.into(),
+            status: job.job_status()?.into(),
+            row_count: job.row_count,
+            file_size_bytes: job.file_size_bytes,
+            error_message: job.error_message,
+            download_url_expires_at: download.as_ref().map(|(_, expires_at)| *expires_at),
+            download_url: download.map(|(url, _)| url),
+            created_at: job.created_at,
+            completed_at: job.completed_at,
+            expires_at: job.expires_at,
+        })
+    }
+}
+
+/// Input for requesting a bulk export
+#[derive(InputObject)]
+pub struct RequestExportInput {
+    /// Series to include, at most 100
+    pub series_ids: Vec<ID>,
+    /// First observation date to include
+    pub start_date: Option<NaiveDate>,
+    /// Last observation date to include
+    pub end_date: Option<NaiveDate>,
+    pub format: ExportFormatType,
+}
+
+/// Kind of work an annotation assignment asks for
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AssignmentType")]
+pub enum AssignmentKindType {
+    Review,
+    Analyze,
+    Verify,
+    Approve,
+    Investigate,
+}
+
+impl From<AssignmentKindType> for AssignmentType {
+    fn from(kind: AssignmentKindType) -> Self {
+        match kind {
+            AssignmentKindType::Review => Self::Review,
+            AssignmentKindType::Analyze => Self::Analyze,
+            AssignmentKindType::Verify => Self::Verify,
+            AssignmentKindType::Approve => Self::Approve,
+            AssignmentKindType::Investigate => Self::Investigate,
         }
     }
 }
 
-/// GraphQL representation of a user
+impl From<AssignmentType> for AssignmentKindType {
+    fn from(kind: AssignmentType) -> Self {
+        match kind {
+            AssignmentType::Review => Self::Review,
+            AssignmentType::Analyze => Self::Analyze,
+            AssignmentType::Verify => Self::Verify,
+            AssignmentType::Approve => Self::Approve,
+            AssignmentType::Investigate => Self::Investigate,
+        }
+    }
+}
+
+/// Where an annotation assignment stands
+#[derive(Enum, Debug, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "AssignmentStatus")]
+pub enum AssignmentStatusType {
+    /// Waiting for the assignee to accept it
+    Pending,
+    /// Accepted and being worked on
+    InProgress,
+    /// Done
+    Completed,
+    /// Stored as overdue; `overdue` reports every open assignment past its due date
+    Overdue,
+    /// Rejected by the assignee or withdrawn by the assigner
+    Cancelled,
+}
+
+impl From<AssignmentStatus> for AssignmentStatusType {
+    fn from(status: AssignmentStatus) -> Self {
+        match status {
+            AssignmentStatus::Pending => Self::Pending,
+            AssignmentStatus::InProgress => Self::InProgress,
+            AssignmentStatus::Completed => Self::Completed,
+            AssignmentStatus::Overdue => Self::Overdue,
+            AssignmentStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// Request for a user to review a financial statement's annotations
 #[derive(Clone, SimpleObject)]
-pub struct UserType {
-    /// User ID
+#[graphql(name = "AnnotationAssignment")]
+pub struct AnnotationAssignmentType {
+    /// Assignment ID
     pub id: ID,
-    /// Email address
-    pub email: String,
-    /// Display name
-    pub name: String,
-    /// Avatar URL
-    pub avatar_url: Option<String>,
-    /// Authentication provider
-    pub provider: String,
-    /// User role
-    pub role: String,
-    /// Organization
-    pub organization: Option<String>,
-    /// UI theme preference
-    pub theme: String,
-    /// Default chart type preference
-    pub default_chart_type: String,
-    /// Whether notifications are enabled
-    pub notifications_enabled: bool,
-    /// Whether collaboration features are enabled
-    pub collaboration_enabled: bool,
-    /// Whether the account is active
-    pub is_active: bool,
-    /// Whether email is verified
-    pub email_verified: bool,
-    /// Creation timestamp
+    pub statement_id: ID,
+    /// Line item the assignment is limited to, if any
+    pub line_item_id: Option<ID>,
+    pub assignee_id: ID,
+    pub assigner_id: ID,
+    pub assignment_type: AssignmentKindType,
+    pub status: AssignmentStatusType,
+    pub due_date: Option<DateTime<Utc>>,
+    /// Whether the assignment is still open and past its due date
+    pub overdue: bool,
+    /// Instructions, followed by the rejection reason once rejected
+    pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
-    /// Last update timestamp
     pub updated_at: DateTime<Utc>,
-    /// Last login timestamp
-    pub last_login_at: Option<DateTime<Utc>>,
 }
 
-impl From<User> for UserType {
-    fn from(user: User) -> Self {
+impl From<AnnotationAssignment> for AnnotationAssignmentType {
+    fn from(assignment: AnnotationAssignment) -> Self {
         Self {
-            id: ID::from(user.id),
-            email: user.email,
-            name: user.name,
-            avatar_url: user.avatar_url,
-            provider: user.provider,
-            role: user.role,
-            organization: user.organization,
-            theme: user.theme,
-            default_chart_type: user.default_chart_type,
-            notifications_enabled: user.notifications_enabled,
-            collaboration_enabled: user.collaboration_enabled,
-            is_active: user.is_active,
-            email_verified: user.email_verified,
-            created_at: user.created_at,
-            updated_at: user.updated_at,
-            last_login_at: user.last_login_at,
+            overdue: assignment.is_overdue(Utc::now()),
+            id: ID::from(assignment.id),
+            statement_id: ID::from(assignment.statement_id),
+            line_item_id: assignment.line_item_id.map(ID::from),
+            assignee_id: ID::from(assignment.assignee_id),
+            assigner_id: ID::from(assignment.assigner_id),
+            assignment_type: assignment.assignment_type.into(),
+            status: assignment.status.into(),
+            due_date: assignment.due_date,
+            notes: assignment.notes,
+            created_at: assignment.created_at,
+            updated_at: assignment.updated_at,
         }
     }
 }
 
-/// Input for creating a new annotation
-#[derive(InputObject)]
-pub struct CreateAnnotationInput {
-    /// User ID creating the annotation
-    pub user_id: ID,
-    /// Series ID the annotation is for
-    pub series_id: ID,
-    /// Date the annotation refers to
-    pub annotation_date: NaiveDate,
-    /// Value the annotation refers to (optional)
-    pub annotation_value: Option<BigDecimal>,
-    /// Annotation title
-    pub title: String,
-    /// Annotation content/description
-    pub content: String,
-    /// Type of annotation (note, highlight, warning, etc.)
-    pub annotation_type: String,
-    /// Color for display (optional)
-    pub color: Option<String>,
-    /// Whether the annotation is public (visible to others)
-    pub is_public: Option<bool>,
-}
-
-/// Input for adding a comment to an annotation
+/// Input for assigning an annotation review
 #[derive(InputObject)]
-pub struct AddCommentInput {
-    /// User ID adding the comment
-    pub user_id: ID,
-    /// Annotation ID to comment on
-    pub annotation_id: ID,
-    /// Comment content
-    pub content: String,
+pub struct AssignAnnotationReviewInput {
+    /// Financial statement whose annotations are reviewed
+    pub statement_id: ID,
+    /// Limit the assignment to one line item
+    pub line_item_id: Option<ID>,
+    /// User asked to do the review
+    pub assignee_id: ID,
+    #[graphql(default_with = "AssignmentKindType::Review")]
+    pub assignment_type: AssignmentKindType,
+    /// When the review is due; must be in the future
+    pub due_date: Option<DateTime<Utc>>,
+    /// Instructions for the assignee
+    pub notes: Option<String>,
 }
 
-/// Input for sharing a chart with another user
+/// Input for manually correcting a data point (admin only)
 #[derive(InputObject)]
-pub struct ShareChartInput {
-    /// Owner user ID (who is sharing)
-    pub owner_user_id: ID,
-    /// Target user ID (who to share with)
-    pub target_user_id: ID,
-    /// Chart ID to share
-    pub chart_id: ID,
-    /// Permission level (view, comment, edit, admin)
-    pub permission_level: String,
+pub struct CorrectDataPointInput {
+    /// Data point whose value is wrong
+    pub data_point_id: ID,
+    /// Corrected value (null marks the observation as missing)
+    pub value: Option<BigDecimal>,
+    /// Why the correction is needed; recorded in the audit log
+    pub reason: String,
 }
 
 /// Input for deleting an annotation
@@ -1036,6 +4397,83 @@ pub struct UpdateUserInput {
     pub email_verified: Option<bool>,
 }
 
+/// Input for creating a data source (admin only)
+#[derive(InputObject)]
+pub struct CreateDataSourceInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub base_url: String,
+    pub api_documentation_url: Option<String>,
+    /// Requests per minute allowed by the upstream API
+    pub rate_limit_per_minute: i32,
+    /// Hours between scheduled crawls (default 24)
+    pub crawl_frequency_hours: Option<i32>,
+    pub api_key_required: Option<bool>,
+    /// Environment variable holding the API key, e.g. FRED_API_KEY
+    pub api_key_name: Option<String>,
+    pub is_visible: Option<bool>,
+    pub is_enabled: Option<bool>,
+    pub requires_admin_approval: Option<bool>,
+}
+
+impl From<CreateDataSourceInput> for NewDataSource {
+    fn from(input: CreateDataSourceInput) -> Self {
+        Self {
+            name: input.name,
+            description: input.description,
+            base_url: input.base_url,
+            api_key_required: input
+                .api_key_required
+                .unwrap_or(input.api_key_name.is_some()),
+            rate_limit_per_minute: input.rate_limit_per_minute,
+            is_visible: input.is_visible.unwrap_or(true),
+            is_enabled: input.is_enabled.unwrap_or(true),
+            requires_admin_approval: input.requires_admin_approval.unwrap_or(false),
+            crawl_frequency_hours: input.crawl_frequency_hours.unwrap_or(24),
+            api_documentation_url: input.api_documentation_url,
+            api_key_name: input.api_key_name,
+        }
+    }
+}
+
+/// Input for updating a data source (admin only)
+///
+/// Omitted fields are left unchanged. Enabling, crawl frequency and credentials
+/// have dedicated mutations.
+#[derive(InputObject)]
+pub struct UpdateDataSourceInput {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub base_url: Option<String>,
+    pub api_documentation_url: Option<String>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub is_visible: Option<bool>,
+    pub requires_admin_approval: Option<bool>,
+}
+
+impl From<UpdateDataSourceInput> for UpdateDataSource {
+    fn from(input: UpdateDataSourceInput) -> Self {
+        Self {
+            name: input.name,
+            description: input.description,
+            base_url: input.base_url,
+            api_key_required: None,
+            rate_limit_per_minute: input.rate_limit_per_minute,
+            api_documentation_url: input.api_documentation_url,
+            is_visible: input.is_visible,
+            is_enabled: None,
+            requires_admin_approval: input.requires_admin_approval,
+            crawl_frequency_hours: None,
+            api_key_name: None,
+            daily_byte_quota: None,
+            daily_request_quota: None,
+            access_policy: None,
+            fallback_urls: None,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
 /// Input for filtering users (admin only)
 #[derive(InputObject)]
 pub struct UserFilterInput {
@@ -1052,7 +4490,7 @@ pub struct UserFilterInput {
 }
 
 /// Input for filtering audit logs (admin only)
-#[derive(InputObject)]
+#[derive(InputObject, Default)]
 pub struct AuditLogFilterInput {
     /// Filter by user ID
     pub user_id: Option<ID>,
@@ -1066,6 +4504,50 @@ pub struct AuditLogFilterInput {
     pub created_before: Option<DateTime<Utc>>,
 }
 
+impl TryFrom<AuditLogFilterInput> for models::admin::AuditLogFilter {
+    type Error = GraphQLError;
+
+    fn try_from(filter: AuditLogFilterInput) -> Result<Self> {
+        Ok(Self {
+            user_id: filter.user_id.map(|id| Uuid::parse_str(&id)).transpose()?,
+            action: filter.action,
+            resource_type: filter.resource_type,
+            created_after: filter.created_after,
+            created_before: filter.created_before,
+        })
+    }
+}
+
+/// Input for filtering security events (admin only)
+#[derive(InputObject, Default)]
+pub struct SecurityEventFilterInput {
+    /// Filter by event type, e.g. "rate_limit_exceeded"
+    pub event_type: Option<String>,
+    /// Filter by severity: low, medium, high or critical
+    pub severity: Option<String>,
+    /// Filter by resolution status
+    pub resolved: Option<bool>,
+    /// Filter by client IP address
+    pub ip_address: Option<String>,
+    /// Created after date
+    pub created_after: Option<DateTime<Utc>>,
+    /// Created before date
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl From<SecurityEventFilterInput> for models::admin::SecurityEventFilter {
+    fn from(filter: SecurityEventFilterInput) -> Self {
+        Self {
+            event_type: filter.event_type,
+            severity: filter.severity,
+            resolved: filter.resolved,
+            ip_address: filter.ip_address,
+            created_after: filter.created_after,
+            created_before: filter.created_before,
+        }
+    }
+}
+
 /// GraphQL connection for users
 #[derive(SimpleObject)]
 pub struct UserConnection {
@@ -1098,6 +4580,21 @@ pub struct UserSessionType {
     pub is_active: bool,
 }
 
+impl From<models::UserSession> for UserSessionType {
+    fn from(session: models::UserSession) -> Self {
+        Self {
+            id: ID::from(session.id),
+            user_id: ID::from(session.user_id),
+            created_at: session.created_at,
+            last_activity: session.last_used_at,
+            expires_at: session.expires_at,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            is_active: session.expires_at > Utc::now(),
+        }
+    }
+}
+
 /// GraphQL representation of system health
 #[derive(Clone, SimpleObject)]
 pub struct SystemHealthType {
@@ -1135,7 +4632,7 @@ pub struct SystemMetricsType {
 pub struct SecurityEventType {
     /// Event ID
     pub id: ID,
-    /// Event type
+    /// Event type, e.g. "rate_limit_exceeded"
     pub event_type: String,
     /// User ID (if applicable)
     pub user_id: Option<ID>,
@@ -1147,12 +4644,40 @@ pub struct SecurityEventType {
     pub user_agent: Option<String>,
     /// Event description
     pub description: String,
-    /// Event severity
+    /// Event severity: low, medium, high or critical
     pub severity: String,
+    /// Event details as JSON
+    pub metadata: Option<String>,
+    /// Whether an admin has resolved the event
+    pub resolved: bool,
+    /// Admin who resolved the event
+    pub resolved_by: Option<ID>,
+    /// When the event was resolved
+    pub resolved_at: Option<DateTime<Utc>>,
     /// Event timestamp
     pub created_at: DateTime<Utc>,
 }
 
+impl From<models::admin::SecurityEvent> for SecurityEventType {
+    fn from(event: models::admin::SecurityEvent) -> Self {
+        Self {
+            id: ID::from(event.id.to_string()),
+            event_type: event.event_type,
+            user_id: event.user_id.map(|id| ID::from(id.to_string())),
+            user_email: event.user_email,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            description: event.description,
+            severity: event.severity,
+            metadata: event.metadata.map(|metadata| metadata.to_string()),
+            resolved: event.resolved.unwrap_or(false),
+            resolved_by: event.resolved_by.map(|id| ID::from(id.to_string())),
+            resolved_at: event.resolved_at,
+            created_at: event.created_at,
+        }
+    }
+}
+
 /// GraphQL representation of an audit log entry
 #[derive(Clone, SimpleObject)]
 pub struct AuditLogType {
@@ -1178,6 +4703,23 @@ pub struct AuditLogType {
     pub created_at: DateTime<Utc>,
 }
 
+impl From<models::admin::AuditLog> for AuditLogType {
+    fn from(log: models::admin::AuditLog) -> Self {
+        Self {
+            id: ID::from(log.id.to_string()),
+            user_id: ID::from(log.user_id.to_string()),
+            user_name: log.user_name,
+            action: log.action,
+            resource_type: log.resource_type,
+            resource_id: log.resource_id,
+            ip_address: log.ip_address,
+            user_agent: log.user_agent,
+            details: log.details.map(|details| details.to_string()),
+            created_at: log.created_at,
+        }
+    }
+}
+
 /// GraphQL connection for audit logs
 #[derive(SimpleObject)]
 pub struct AuditLogConnection {
@@ -1188,3 +4730,53 @@ pub struct AuditLogConnection {
     /// Pagination info
     pub page_info: PageInfo,
 }
+
+impl AuditLogConnection {
+    /// Connection over one page of audit log entries, newest first
+    pub fn from_page(
+        page: Page<models::admin::AuditLog>,
+        has_previous_page: bool,
+        total_count: i64,
+    ) -> Self {
+        let start_cursor = page
+            .items
+            .first()
+            .map(|log| encode_cursor(AuditLogPosition::from(log)));
+        let end_cursor = page
+            .items
+            .last()
+            .map(|log| encode_cursor(AuditLogPosition::from(log)));
+
+        Self {
+            nodes: page.items.into_iter().map(AuditLogType::from).collect(),
+            total_count: total_count.min(i32::MAX as i64) as i32,
+            page_info: PageInfo {
+                has_next_page: page.has_next_page,
+                has_previous_page,
+                start_cursor,
+                end_cursor,
+            },
+        }
+    }
+}
+
+/// Audit log entries rendered as CSV
+#[derive(SimpleObject)]
+#[graphql(name = "AuditLogExport")]
+pub struct AuditLogExportType {
+    /// CSV with a header row, newest entry first
+    pub csv: String,
+    pub row_count: i32,
+    /// Whether more entries matched than were exported; narrow the filter to get the rest
+    pub truncated: bool,
+}
+
+impl From<AuditLogExport> for AuditLogExportType {
+    fn from(export: AuditLogExport) -> Self {
+        Self {
+            csv: export.csv,
+            row_count: export.row_count as i32,
+            truncated: export.truncated,
+        }
+    }
+}
@@ -16,6 +16,7 @@
 //! - **Protocol Compliance**: Full compliance with MCP specification
 //! - **Authentication**: Secure authentication for AI model access
 //! - **Data Filtering**: Intelligent data filtering and access control
+//! - **Chunked Series Data**: Large series are read in bounded chunks with continuation tokens
 //!
 //! ## Architecture
 //!
@@ -43,6 +44,7 @@
 //! ```
 
 pub mod mcp_server;
+pub mod series_stream;
 
 // Re-export commonly used MCP types
 pub use mcp_server::*;
@@ -13,7 +13,12 @@ use warp::http::StatusCode;
 use warp::Reply;
 
 use econ_graph_core::database::DatabasePool;
+use econ_graph_graphql::graphql::context::GraphQLContext;
 use econ_graph_graphql::graphql::schema::create_schema_with_data;
+use econ_graph_metrics::telemetry;
+
+use crate::series_stream::{self, ChunkRequest, SERIES_DATA_URI_TEMPLATE};
+use tracing::Instrument;
 
 /// MCP Server implementation for EconGraph
 #[derive(Clone)]
@@ -24,7 +29,7 @@ pub struct EconGraphMcpServer {
     schema: async_graphql::Schema<
         econ_graph_graphql::graphql::query::Query,
         econ_graph_graphql::graphql::mutation::Mutation,
-        async_graphql::EmptySubscription,
+        econ_graph_graphql::graphql::Subscription,
     >,
     /// HTTP client for calling private frontend chart API
     http_client: Client,
@@ -50,6 +55,9 @@ impl EconGraphMcpServer {
     }
 
     /// Execute a GraphQL query
+    ///
+    /// MCP clients are not authenticated, so queries run as an anonymous user:
+    /// restricted sources are refused and values are rounded for the public tier.
     async fn execute_graphql_query(&self, query: &str, variables: Option<Value>) -> Result<Value> {
         let request = if let Some(vars) = variables {
             let graphql_vars: Variables = serde_json::from_value(vars)?;
@@ -57,6 +65,7 @@ impl EconGraphMcpServer {
         } else {
             Request::new(query)
         };
+        let request = request.data(Arc::new(GraphQLContext::new(&self.pool, None)));
 
         let response = self.schema.execute(request).await;
 
@@ -199,6 +208,34 @@ impl EconGraphMcpServer {
         ]
     }
 
+    /// Get available resource templates
+    pub fn get_available_resource_templates() -> Vec<Value> {
+        vec![json!({
+            "uri_template": SERIES_DATA_URI_TEMPLATE,
+            "name": "Series Data",
+            "description": format!(
+                "Data points of an economic series, read in chunks of at most {} points. \
+                 Optional query parameters: start_date and end_date (YYYY-MM-DD), \
+                 page_size, format (json or csv) and cursor. When has_more is true, \
+                 read next_uri to get the following chunk.",
+                series_stream::MAX_CHUNK_POINTS
+            ),
+            "mime_type": "application/json"
+        })]
+    }
+
+    /// Read a resource by URI
+    pub async fn read_resource(&self, uri: &str) -> Result<Value> {
+        match uri {
+            "econ-graph://data-sources" => self.get_data_sources().await,
+            "econ-graph://series-catalog" => self.get_series_catalog().await,
+            _ => match ChunkRequest::from_uri(uri) {
+                Some(request) => series_stream::read_series_chunk(&self.pool, uri, request?).await,
+                None => Err(anyhow::anyhow!("Unknown resource: {}", uri)),
+            },
+        }
+    }
+
     /// Search for economic series
     pub async fn search_economic_series(&self, arguments: Value) -> Result<Value> {
         let query = arguments
@@ -440,16 +477,29 @@ impl EconGraphMcpServer {
     /// Call private frontend chart API to generate chart configuration
     pub async fn call_private_chart_api(&self, chart_request: &Value) -> Result<Value> {
         let url = format!("{}/generate", self.frontend_chart_api_url);
+        let span = tracing::info_span!(
+            "chart_api.generate",
+            otel.kind = "client",
+            http.request.method = "POST",
+            url.full = %url,
+        );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("X-MCP-Server-Request", "true")
-            .header("X-Internal-Request", "true")
-            .json(chart_request)
-            .send()
-            .await?;
+        let response = async {
+            // Propagate the trace so the chart API's spans join this request
+            let mut request = self
+                .http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("X-MCP-Server-Request", "true")
+                .header("X-Internal-Request", "true");
+            for (name, value) in telemetry::trace_context_headers() {
+                request = request.header(name, value);
+            }
+
+            request.json(chart_request).send().await
+        }
+        .instrument(span)
+        .await?;
 
         if response.status().is_success() {
             let chart_response: Value = response.json().await?;
@@ -678,17 +728,20 @@ pub async fn mcp_handler(
                 }
             })
         }
+        Some("resources/templates/list") => {
+            json!({
+                "jsonrpc": "2.0",
+                "id": request.get("id"),
+                "result": {
+                    "resource_templates": EconGraphMcpServer::get_available_resource_templates()
+                }
+            })
+        }
         Some("resources/read") => {
             let params = request.get("params").cloned().unwrap_or(json!({}));
             let uri = params.get("uri").and_then(|v| v.as_str()).unwrap_or("");
 
-            let result = match uri {
-                "econ-graph://data-sources" => server.get_data_sources().await,
-                "econ-graph://series-catalog" => server.get_series_catalog().await,
-                _ => Err(anyhow::anyhow!("Unknown resource: {}", uri)),
-            };
-
-            match result {
+            match server.read_resource(uri).await {
                 Ok(data) => json!({
                     "jsonrpc": "2.0",
                     "id": request.get("id"),
@@ -787,6 +840,21 @@ mod tests {
         assert!(resource_uris.contains(&"econ-graph://series-catalog".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_get_available_resource_templates() {
+        // REQUIREMENT: AI clients can discover how to read large series in chunks
+        // PURPOSE: Verify the series data template is advertised with its URI pattern
+        // This ensures clients use chunked reads instead of full JSON payloads
+
+        let templates = EconGraphMcpServer::get_available_resource_templates();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0]["uri_template"], SERIES_DATA_URI_TEMPLATE);
+        assert!(templates[0]["description"]
+            .as_str()
+            .unwrap()
+            .contains("next_uri"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_mcp_server_with_database_operations() {
@@ -1526,6 +1594,163 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_mcp_cannot_read_premium_series() {
+        // REQUIREMENT: Licensed data sources are only readable by the users they are licensed to
+        // PURPOSE: Verify MCP queries and series data resources are refused for a premium source
+        // This ensures the unauthenticated MCP endpoint is not a way around access policies
+
+        use bigdecimal::BigDecimal;
+        use chrono::NaiveDate;
+        use econ_graph_core::enums::DataAccessPolicy;
+        use econ_graph_core::models::{
+            DataPoint, DataSource, EconomicSeries, NewDataPoint, NewDataSource, NewEconomicSeries,
+            UpdateDataSource,
+        };
+        use std::str::FromStr;
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let server = EconGraphMcpServer::new(Arc::new(pool.clone()));
+
+        let source = DataSource::create(
+            pool,
+            NewDataSource {
+                name: format!("MCP Premium Source {}", uuid::Uuid::new_v4()),
+                base_url: "https://premium.example.com/api".to_string(),
+                ..NewDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        DataSource::update(
+            pool,
+            source.id,
+            UpdateDataSource {
+                access_policy: Some(DataAccessPolicy::Premium),
+                api_documentation_url: None,
+                ..UpdateDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = EconomicSeries::create(
+            pool,
+            &NewEconomicSeries {
+                source_id: source.id,
+                external_id: "MCP_PREMIUM_001".to_string(),
+                title: "MCP Premium Series".to_string(),
+                frequency: "Monthly".to_string(),
+                is_active: true,
+                ..NewEconomicSeries::default()
+            },
+        )
+        .await
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        DataPoint::create_batch(
+            pool,
+            &[NewDataPoint {
+                series_id: series.id,
+                date,
+                value: Some(BigDecimal::from_str("4321.987").unwrap()),
+                revision_date: date,
+                is_original_release: true,
+            }],
+        )
+        .await
+        .unwrap();
+
+        let result = server
+            .execute_graphql_query(
+                "query($id: ID!) { series(id: $id) { id dataPoints { value } } }",
+                Some(json!({ "id": series.id.to_string() })),
+            )
+            .await
+            .unwrap();
+        assert!(result["data"]["series"].is_null());
+        assert_eq!(result["errors"][0]["extensions"]["code"], "UNAUTHENTICATED");
+        assert!(!result.to_string().contains("4321"));
+
+        let uri = format!("econ-graph://series/{}/data", series.id);
+        assert!(server.read_resource(&uri).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mcp_serves_public_tier_values() {
+        // REQUIREMENT: Anonymous users see trends without full precision
+        // PURPOSE: Verify MCP queries and series data resources return rounded values
+        // This ensures the unauthenticated MCP endpoint does not serve exact figures
+
+        use bigdecimal::BigDecimal;
+        use chrono::NaiveDate;
+        use econ_graph_core::models::{
+            DataPoint, DataSource, EconomicSeries, NewDataPoint, NewDataSource, NewEconomicSeries,
+        };
+        use std::str::FromStr;
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let server = EconGraphMcpServer::new(Arc::new(pool.clone()));
+
+        let source = DataSource::create(
+            pool,
+            NewDataSource {
+                name: format!("MCP Public Source {}", uuid::Uuid::new_v4()),
+                base_url: "https://public.example.com/api".to_string(),
+                ..NewDataSource::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = EconomicSeries::create(
+            pool,
+            &NewEconomicSeries {
+                source_id: source.id,
+                external_id: "MCP_PUBLIC_001".to_string(),
+                title: "MCP Public Series".to_string(),
+                frequency: "Monthly".to_string(),
+                is_active: true,
+                ..NewEconomicSeries::default()
+            },
+        )
+        .await
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        DataPoint::create_batch(
+            pool,
+            &[NewDataPoint {
+                series_id: series.id,
+                date,
+                value: Some(BigDecimal::from_str("4321.987").unwrap()),
+                revision_date: date,
+                is_original_release: true,
+            }],
+        )
+        .await
+        .unwrap();
+
+        let result = server
+            .execute_graphql_query(
+                "query($id: ID!) { series(id: $id) { dataPoints { value } } }",
+                Some(json!({ "id": series.id.to_string() })),
+            )
+            .await
+            .unwrap();
+        assert!(result
+            .get("errors")
+            .map_or(true, |errors| errors.as_array().map_or(true, Vec::is_empty)));
+        assert!(!result.to_string().contains("4321.987"));
+
+        let uri = format!("econ-graph://series/{}/data?format=csv", series.id);
+        let chunk = server.read_resource(&uri).await.unwrap();
+        let text = chunk["contents"][0]["text"].as_str().unwrap();
+        let value = text.lines().nth(1).unwrap().split(',').nth(1).unwrap();
+        assert_eq!(BigDecimal::from_str(value).unwrap(), BigDecimal::from(4320));
+    }
+
     // test_call_private_chart_api_* tests moved to integration tests
     // because they require the chart API service to be running
 }
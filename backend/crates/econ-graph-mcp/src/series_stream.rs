@@ -0,0 +1,454 @@
+//! Chunked series data resources
+//!
+//! A decade of daily observations does not fit in an AI client's context, so
+//! series data is served as the resource template
+//! `econ-graph://series/{series_id}/data` and read one chunk at a time:
+//!
+//! ```text
+//! econ-graph://series/<uuid>/data?start_date=2015-01-01&format=csv&page_size=500
+//! ```
+//!
+//! Each chunk is capped both in data points ([`MAX_CHUNK_POINTS`]) and in
+//! encoded size ([`MAX_CHUNK_BYTES`]). When more data follows, the response
+//! carries a `next_cursor` continuation token and a ready-to-read `next_uri`.
+//! The token pins the series and date range it was issued for, so it cannot
+//! be replayed against another query. The `csv` format (`date,value` rows)
+//! is about a third of the size of the JSON format for the same points.
+//!
+//! MCP clients are anonymous, so series from sources that are not public are
+//! refused and values are rounded like public tier API responses.
+
+use anyhow::{anyhow, bail, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64;
+use base64::Engine;
+use chrono::NaiveDate;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::models::{DataPoint, DataQueryParams, DataSource};
+use econ_graph_graphql::graphql::public_tier::shared_public_tier_policy;
+use econ_graph_services::services::series_service::{self, DataPointPosition};
+
+/// URI template of chunked series data
+pub const SERIES_DATA_URI_TEMPLATE: &str = "econ-graph://series/{series_id}/data";
+/// Data points per chunk when `page_size` is not given
+pub const DEFAULT_CHUNK_POINTS: usize = 500;
+/// Largest number of data points in a chunk; larger `page_size` values are clamped
+pub const MAX_CHUNK_POINTS: usize = 2_000;
+/// Largest encoded chunk; chunks stop early rather than grow past it
+pub const MAX_CHUNK_BYTES: usize = 64 * 1024;
+
+/// How a chunk's data points are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkFormat {
+    /// JSON array of `{"date", "value"}` objects
+    Json,
+    /// `date,value` rows under a header line
+    Csv,
+}
+
+impl ChunkFormat {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(ChunkFormat::Json),
+            "csv" => Ok(ChunkFormat::Csv),
+            other => bail!("Unknown format '{}', expected 'json' or 'csv'", other),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChunkFormat::Json => "json",
+            ChunkFormat::Csv => "csv",
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match self {
+            ChunkFormat::Json => "application/json",
+            ChunkFormat::Csv => "text/csv",
+        }
+    }
+
+    /// Encoded form of one data point, without separators
+    fn encode_row(&self, point: &DataPoint) -> String {
+        let value = point
+            .value
+            .as_ref()
+            .map(|value| value.normalized().to_string());
+        match self {
+            ChunkFormat::Json => json!({ "date": point.date, "value": value }).to_string(),
+            ChunkFormat::Csv => format!("{},{}", point.date, value.unwrap_or_default()),
+        }
+    }
+
+    /// Join encoded rows into a chunk body
+    fn finish(&self, rows: &[String]) -> String {
+        match self {
+            ChunkFormat::Json => format!("[{}]", rows.join(",")),
+            ChunkFormat::Csv => {
+                let mut body = String::from("date,value\n");
+                for row in rows {
+                    body.push_str(row);
+                    body.push('\n');
+                }
+                body
+            }
+        }
+    }
+}
+
+/// Query a chunk belongs to; continuation tokens must match it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ChunkQuery {
+    series_id: Uuid,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+/// Contents of a continuation token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ContinuationToken {
+    query: ChunkQuery,
+    after: DataPointPosition,
+}
+
+impl ContinuationToken {
+    fn encode(&self) -> String {
+        // Serializing plain data to JSON cannot fail
+        BASE64.encode(serde_json::to_vec(self).expect("token serializes"))
+    }
+
+    fn decode(token: &str) -> Result<Self> {
+        let bytes = BASE64
+            .decode(token)
+            .map_err(|_| anyhow!("Invalid continuation token"))?;
+        serde_json::from_slice(&bytes).map_err(|_| anyhow!("Invalid continuation token"))
+    }
+}
+
+/// Parsed request for a chunk of series data
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkRequest {
+    query: ChunkQuery,
+    after: Option<DataPointPosition>,
+    page_size: usize,
+    format: ChunkFormat,
+}
+
+impl ChunkRequest {
+    /// Parse a series data resource URI; returns `None` for other resources
+    pub fn from_uri(uri: &str) -> Option<Result<Self>> {
+        let url = Url::parse(uri).ok()?;
+        if url.scheme() != "econ-graph" || url.host_str() != Some("series") {
+            return None;
+        }
+        let segments: Vec<&str> = url.path_segments()?.collect();
+        let [series_id, "data"] = segments.as_slice() else {
+            return None;
+        };
+
+        Some(Self::parse(series_id, &url))
+    }
+
+    fn parse(series_id: &str, url: &Url) -> Result<Self> {
+        let mut query = ChunkQuery {
+            series_id: Uuid::parse_str(series_id)
+                .map_err(|_| anyhow!("Invalid series ID '{}'", series_id))?,
+            start_date: None,
+            end_date: None,
+        };
+        let mut cursor = None;
+        let mut page_size = DEFAULT_CHUNK_POINTS;
+        let mut format = ChunkFormat::Json;
+
+        for (name, value) in url.query_pairs() {
+            match name.as_ref() {
+                "start_date" => query.start_date = Some(parse_date(&value)?),
+                "end_date" => query.end_date = Some(parse_date(&value)?),
+                "cursor" => cursor = Some(ContinuationToken::decode(&value)?),
+                "page_size" => {
+                    let size: usize = value
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid page_size '{}'", value))?;
+                    if size == 0 {
+                        bail!("page_size must be at least 1");
+                    }
+                    page_size = size.min(MAX_CHUNK_POINTS);
+                }
+                "format" => format = ChunkFormat::parse(&value)?,
+                other => bail!("Unknown parameter '{}'", other),
+            }
+        }
+
+        let after = match cursor {
+            Some(token) if token.query != query => {
+                bail!("Continuation token was issued for a different series or date range")
+            }
+            Some(token) => Some(token.after),
+            None => None,
+        };
+
+        Ok(Self {
+            query,
+            after,
+            page_size,
+            format,
+        })
+    }
+
+    /// URI of this resource with the given continuation token
+    fn uri_with_cursor(&self, cursor: &str) -> String {
+        let mut url = Url::parse(&format!(
+            "econ-graph://series/{}/data",
+            self.query.series_id
+        ))
+        .expect("series data URI is valid");
+        {
+            let mut pairs = url.query_pairs_mut();
+            if let Some(start_date) = self.query.start_date {
+                pairs.append_pair("start_date", &start_date.to_string());
+            }
+            if let Some(end_date) = self.query.end_date {
+                pairs.append_pair("end_date", &end_date.to_string());
+            }
+            pairs.append_pair("page_size", &self.page_size.to_string());
+            pairs.append_pair("format", self.format.as_str());
+            pairs.append_pair("cursor", cursor);
+        }
+        url.to_string()
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow!("Invalid date '{}', expected YYYY-MM-DD", value))
+}
+
+/// A chunk of encoded data points
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub text: String,
+    pub points: usize,
+    /// Position of the last point in the chunk, when more data follows it
+    pub continue_after: Option<DataPointPosition>,
+}
+
+/// Encode as many of `points` as fit in [`MAX_CHUNK_BYTES`]
+///
+/// `has_more` says whether data follows `points` in the series. A chunk
+/// always holds at least one point so that reading can make progress.
+pub fn encode_chunk(points: &[DataPoint], has_more: bool, format: ChunkFormat) -> Chunk {
+    let mut rows = Vec::with_capacity(points.len());
+    // Header or brackets plus one separator per row
+    let mut size = format.finish(&[]).len();
+
+    for point in points {
+        let row = format.encode_row(point);
+        if !rows.is_empty() && size + row.len() + 1 > MAX_CHUNK_BYTES {
+            break;
+        }
+        size += row.len() + 1;
+        rows.push(row);
+    }
+
+    let truncated = rows.len() < points.len();
+    let continue_after = if has_more || truncated {
+        rows.len()
+            .checked_sub(1)
+            .map(|last| DataPointPosition::from(&points[last]))
+    } else {
+        None
+    };
+
+    Chunk {
+        text: format.finish(&rows),
+        points: rows.len(),
+        continue_after,
+    }
+}
+
+/// Fail unless anonymous users may read the series' data
+///
+/// An unknown series is left to the read to report as empty.
+async fn require_public_series(pool: &DatabasePool, series_id: Uuid) -> Result<()> {
+    let Some(series) = series_service::get_series_by_id(pool, series_id).await? else {
+        return Ok(());
+    };
+    match DataSource::find_by_id(pool, series.source_id).await? {
+        Some(source) if !source.access_policy.permits(None) => {
+            bail!("Sign in to read data from {}", source.name)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Read one chunk of series data
+///
+/// Only the latest revision of each observation is served; revision history
+/// is available through the GraphQL API.
+pub async fn read_series_chunk(
+    pool: &DatabasePool,
+    uri: &str,
+    request: ChunkRequest,
+) -> Result<Value> {
+    require_public_series(pool, request.query.series_id).await?;
+
+    let params = DataQueryParams {
+        series_id: request.query.series_id,
+        start_date: request.query.start_date,
+        end_date: request.query.end_date,
+        original_only: None,
+        latest_revision_only: Some(true),
+        exclude_corrections: None,
+        limit: None,
+        offset: None,
+    };
+
+    let mut page =
+        series_service::get_series_data_page(pool, &params, request.after, request.page_size)
+            .await?;
+    let policy = shared_public_tier_policy();
+    for point in &mut page.items {
+        point.value = policy.optional_value(&point.value);
+    }
+    let total = series_service::count_series_data(pool, &params).await?;
+    let chunk = encode_chunk(&page.items, page.has_next_page, request.format);
+
+    let next_cursor = chunk.continue_after.map(|after| {
+        ContinuationToken {
+            query: request.query.clone(),
+            after,
+        }
+        .encode()
+    });
+    let next_uri = next_cursor
+        .as_deref()
+        .map(|cursor| request.uri_with_cursor(cursor));
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mime_type": request.format.mime_type(),
+            "text": chunk.text
+        }],
+        "points": chunk.points,
+        "total_points": total.count,
+        "total_points_is_estimate": total.is_estimate,
+        "has_more": next_cursor.is_some(),
+        "next_cursor": next_cursor,
+        "next_uri": next_uri
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use chrono::Utc;
+
+    fn points(count: usize) -> Vec<DataPoint> {
+        let series_id = Uuid::new_v4();
+        let start = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+        (0..count)
+            .map(|day| DataPoint {
+                id: Uuid::new_v4(),
+                series_id,
+                date: start + chrono::Days::new(day as u64),
+                value: Some(BigDecimal::from(day as i64)),
+                revision_date: start,
+                is_original_release: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_series_data_uri() {
+        // REQUIREMENT: AI clients read series data through a resource URI template
+        // PURPOSE: Verify URI parameters are parsed and page sizes are clamped to the server limit
+        // This ensures a client cannot ask for an unbounded chunk
+
+        let series_id = Uuid::new_v4();
+        let uri = format!(
+            "econ-graph://series/{}/data?start_date=2015-01-01&page_size=100000&format=csv",
+            series_id
+        );
+        let request = ChunkRequest::from_uri(&uri).unwrap().unwrap();
+        assert_eq!(request.query.series_id, series_id);
+        assert_eq!(
+            request.query.start_date,
+            NaiveDate::from_ymd_opt(2015, 1, 1)
+        );
+        assert_eq!(request.page_size, MAX_CHUNK_POINTS);
+        assert_eq!(request.format, ChunkFormat::Csv);
+
+        assert!(ChunkRequest::from_uri("econ-graph://data-sources").is_none());
+        assert!(ChunkRequest::from_uri(&format!(
+            "econ-graph://series/{}/data?page_size=0",
+            series_id
+        ))
+        .unwrap()
+        .is_err());
+    }
+
+    #[test]
+    fn test_continuation_token_is_bound_to_query() {
+        // REQUIREMENT: Continuation tokens resume the query they were issued for
+        // PURPOSE: Verify next_uri round-trips and tokens are rejected for another date range
+        // This ensures a stale token cannot silently skip or repeat data
+
+        let data = points(3);
+        let series_id = Uuid::new_v4();
+        let request = ChunkRequest::from_uri(&format!(
+            "econ-graph://series/{}/data?end_date=2020-12-31",
+            series_id
+        ))
+        .unwrap()
+        .unwrap();
+        let token = ContinuationToken {
+            query: request.query.clone(),
+            after: DataPointPosition::from(&data[1]),
+        }
+        .encode();
+
+        let next = ChunkRequest::from_uri(&request.uri_with_cursor(&token))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.after, Some(DataPointPosition::from(&data[1])));
+        assert_eq!(next.query, request.query);
+
+        let other_range = format!(
+            "econ-graph://series/{}/data?end_date=2021-12-31&cursor={}",
+            series_id, token
+        );
+        assert!(ChunkRequest::from_uri(&other_range).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_encode_chunk_respects_byte_limit() {
+        // REQUIREMENT: Responses must not blow an AI client's context
+        // PURPOSE: Verify chunks stop at the byte limit and continue after the last point sent
+        // This ensures decade-long daily series are delivered in bounded pieces without gaps
+
+        let data = points(MAX_CHUNK_POINTS);
+        let chunk = encode_chunk(&data, false, ChunkFormat::Json);
+        assert!(chunk.text.len() <= MAX_CHUNK_BYTES);
+        assert!(chunk.points < data.len());
+        assert_eq!(
+            chunk.continue_after,
+            Some(DataPointPosition::from(&data[chunk.points - 1]))
+        );
+
+        let small = encode_chunk(&data[..2], false, ChunkFormat::Csv);
+        assert_eq!(small.text, "date,value\n2015-01-01,0\n2015-01-02,1\n");
+        assert_eq!(small.continue_after, None);
+        assert!(encode_chunk(&data[..2], true, ChunkFormat::Csv)
+            .continue_after
+            .is_some());
+    }
+}
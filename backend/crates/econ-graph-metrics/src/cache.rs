@@ -0,0 +1,112 @@
+//! # Cache Metrics
+//!
+//! This module provides metrics for the in-memory caches used by the `EconGraph` services,
+//! such as the data point cache that serves hot series without a database round trip.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_metrics::cache::CACHE_METRICS;
+//!
+//! CACHE_METRICS.record_hit("data_points");
+//! CACHE_METRICS.record_miss("data_points");
+//! CACHE_METRICS.record_eviction("data_points", "capacity");
+//! ```
+
+use crate::DEFAULT_REGISTRY;
+use once_cell::sync::Lazy;
+use prometheus::{IntCounterVec, IntGaugeVec, Opts, Registry};
+
+/// Hit, miss and eviction metrics for in-memory caches
+pub struct CacheMetrics {
+    /// Total number of cache lookups that found a fresh entry, categorized by cache
+    pub cache_hits_total: IntCounterVec,
+    /// Total number of cache lookups that had to load from storage, categorized by cache
+    pub cache_misses_total: IntCounterVec,
+    /// Total number of entries removed from a cache, categorized by cache and reason
+    pub cache_evictions_total: IntCounterVec,
+    /// Current number of entries held by a cache
+    pub cache_entries: IntGaugeVec,
+}
+
+impl CacheMetrics {
+    /// Create a new `CacheMetrics` instance with all metrics registered to the provided registry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any metric fails to register with the provided registry
+    pub fn new(registry: &Registry) -> anyhow::Result<Self> {
+        let cache_hits_total = IntCounterVec::new(
+            Opts::new("econgraph_cache_hits_total", "Total number of cache hits"),
+            &["cache"],
+        )?;
+        registry.register(Box::new(cache_hits_total.clone()))?;
+
+        let cache_misses_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_cache_misses_total",
+                "Total number of cache misses",
+            ),
+            &["cache"],
+        )?;
+        registry.register(Box::new(cache_misses_total.clone()))?;
+
+        let cache_evictions_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_cache_evictions_total",
+                "Total number of cache entries evicted",
+            ),
+            &["cache", "reason"],
+        )?;
+        registry.register(Box::new(cache_evictions_total.clone()))?;
+
+        let cache_entries = IntGaugeVec::new(
+            Opts::new("econgraph_cache_entries", "Current number of cache entries"),
+            &["cache"],
+        )?;
+        registry.register(Box::new(cache_entries.clone()))?;
+
+        Ok(Self {
+            cache_hits_total,
+            cache_misses_total,
+            cache_evictions_total,
+            cache_entries,
+        })
+    }
+
+    /// Record a lookup served from the cache
+    pub fn record_hit(&self, cache: &str) {
+        self.cache_hits_total.with_label_values(&[cache]).inc();
+    }
+
+    /// Record a lookup that had to go to storage
+    pub fn record_miss(&self, cache: &str) {
+        self.cache_misses_total.with_label_values(&[cache]).inc();
+    }
+
+    /// Record an entry removed from the cache
+    ///
+    /// # Parameters
+    /// - `cache`: Cache name (e.g., "data_points")
+    /// - `reason`: Why the entry was removed (e.g., "capacity", "expired", "invalidated")
+    pub fn record_eviction(&self, cache: &str, reason: &str) {
+        self.cache_evictions_total
+            .with_label_values(&[cache, reason])
+            .inc();
+    }
+
+    /// Set the current number of entries in a cache
+    pub fn set_entries(&self, cache: &str, entries: usize) {
+        self.cache_entries
+            .with_label_values(&[cache])
+            .set(entries as i64);
+    }
+}
+
+/// Global cache metrics instance registered with the default registry
+///
+/// # Panics
+///
+/// Panics if the metrics fail to initialize during lazy initialization
+pub static CACHE_METRICS: Lazy<CacheMetrics> =
+    Lazy::new(|| CacheMetrics::new(&DEFAULT_REGISTRY).expect("Failed to initialize cache metrics"));
@@ -10,6 +10,10 @@
 //! - **Data Collection**: Track items collected and bytes downloaded
 //! - **Error Monitoring**: Categorize and count different types of errors
 //! - **Rate Limiting**: Monitor rate limit hits and retry attempts
+//! - **Quotas**: Track remaining daily byte and request quotas and deferred work
+//! - **API Keys**: Count requests per (hashed) API key when a source rotates between keys
+//! - **Data Quality**: Per-source quality scores and series with outliers, gaps or stale data
+//! - **Document Storage**: Sizes and compression ratios of stored filing and taxonomy documents
 //! - **Performance Analysis**: Histogram-based duration tracking for performance insights
 //!
 //! ## Usage
@@ -26,12 +30,36 @@
 //! // Record an error
 //! CRAWLER_METRICS.record_error("sec_edgar", "sec.gov", "network_timeout");
 //! ```
+//!
+//! ## Testing
+//!
+//! `CRAWLER_METRICS` records into the process-wide default registry, so tests
+//! asserting on counters would see each other's increments. A test can instead
+//! open a [`CrawlerMetricsTestScope`]: until the scope is dropped, everything the
+//! current thread records through `CRAWLER_METRICS` goes to a private registry.
+//!
+//! ```rust
+//! use econ_graph_metrics::crawler::{CrawlerMetrics, CRAWLER_METRICS};
+//!
+//! let scope = CrawlerMetrics::test_scope();
+//! CRAWLER_METRICS.record_error("sec_edgar", "sec.gov", "network_timeout");
+//!
+//! let errors = scope
+//!     .metrics()
+//!     .crawler_errors_total
+//!     .with_label_values(&["sec_edgar", "sec.gov", "network_timeout"])
+//!     .get();
+//! assert_eq!(errors, 1);
+//! ```
 
 use crate::DEFAULT_REGISTRY;
 use once_cell::sync::Lazy;
 use prometheus::{
-    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry,
 };
+use std::cell::Cell;
+use std::ops::Deref;
 
 /// Comprehensive metrics collection for web crawlers
 ///
@@ -55,6 +83,40 @@ pub struct CrawlerMetrics {
     pub crawler_retries_total: IntCounterVec,
     /// Total number of timeout errors, categorized by type and source
     pub crawler_timeouts_total: IntCounterVec,
+    /// Total number of queue items moved to the dead-letter queue, categorized by source
+    pub crawler_dead_lettered_total: IntCounterVec,
+    /// Current number of items in the dead-letter queue
+    pub crawler_dead_letter_queue_size: IntGauge,
+    /// Total number of observations passed through ingestion validation, categorized by source and outcome
+    pub crawler_validation_results_total: IntCounterVec,
+    /// Remaining daily quota per source, categorized by quota kind ("bytes" or "requests")
+    pub crawler_quota_remaining: IntGaugeVec,
+    /// Total number of crawl queue items deferred because a source's quota ran out
+    pub crawler_quota_deferrals_total: IntCounterVec,
+    /// Total number of crawl requests merged into an already pending queue item, categorized by source
+    pub crawler_queue_coalesced_total: IntCounterVec,
+    /// Total number of requests per API key, categorized by source, key hash and HTTP status
+    pub crawler_api_key_requests_total: IntCounterVec,
+    /// Total number of requests per endpoint of a source, categorized by outcome ("success" or "failure")
+    pub crawler_endpoint_requests_total: IntCounterVec,
+    /// Circuit breaker state per endpoint of a source: 0 closed, 1 half-open, 2 open
+    pub crawler_endpoint_circuit_state: IntGaugeVec,
+    /// File downloads currently in progress, categorized by type and source
+    pub crawler_downloads_in_flight: IntGaugeVec,
+    /// File downloads waiting for a download slot, categorized by type and source
+    pub crawler_downloads_queued: IntGaugeVec,
+    /// Total number of downloads resumed from a partial file, categorized by type and source
+    pub crawler_resumed_downloads_total: IntCounterVec,
+    /// Average data quality score (0 to 1) of a source's scored series
+    pub crawler_data_quality_score: GaugeVec,
+    /// Scored series with a data quality issue, categorized by source and issue ("outliers", "gaps" or "stale")
+    pub crawler_data_quality_issues: IntGaugeVec,
+    /// Total bytes of stored documents, categorized by document type and size ("original" or "stored")
+    pub crawler_document_bytes_total: IntCounterVec,
+    /// Stored size of each document as a fraction of its original size, categorized by document type
+    pub crawler_document_compression_ratio: HistogramVec,
+    /// Registry the metrics above are registered with
+    registry: Registry,
 }
 
 impl CrawlerMetrics {
@@ -74,6 +136,19 @@ impl CrawlerMetrics {
     ///
     /// Returns an error if any metric fails to register with the provided registry
     pub fn new(registry: &Registry) -> anyhow::Result<Self> {
+        Self::with_registry(registry.clone())
+    }
+
+    /// Create a new `CrawlerMetrics` instance that records into `registry`
+    ///
+    /// Unlike [`CrawlerMetrics::new`] the instance keeps the registry, so it can
+    /// be gathered later through [`CrawlerMetrics::registry`]. Pass a fresh
+    /// `Registry::new()` to get metrics that no other code records into.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any metric fails to register with the provided registry
+    pub fn with_registry(registry: Registry) -> anyhow::Result<Self> {
         let crawler_requests_total = IntCounterVec::new(
             Opts::new(
                 "econgraph_crawler_requests_total",
@@ -147,6 +222,148 @@ impl CrawlerMetrics {
         )?;
         registry.register(Box::new(crawler_timeouts_total.clone()))?;
 
+        let crawler_dead_lettered_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_dead_lettered_total",
+                "Total number of crawl queue items moved to the dead-letter queue",
+            ),
+            &["source"],
+        )?;
+        registry.register(Box::new(crawler_dead_lettered_total.clone()))?;
+
+        let crawler_dead_letter_queue_size = IntGauge::new(
+            "econgraph_crawler_dead_letter_queue_size",
+            "Current number of crawl queue items in the dead-letter queue",
+        )?;
+        registry.register(Box::new(crawler_dead_letter_queue_size.clone()))?;
+
+        let crawler_validation_results_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_validation_results_total",
+                "Total number of observations processed by the ingestion validation pipeline",
+            ),
+            &["source", "outcome"],
+        )?;
+        registry.register(Box::new(crawler_validation_results_total.clone()))?;
+
+        let crawler_quota_remaining = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_quota_remaining",
+                "Remaining daily crawl quota per data source",
+            ),
+            &["source", "quota"],
+        )?;
+        registry.register(Box::new(crawler_quota_remaining.clone()))?;
+
+        let crawler_quota_deferrals_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_quota_deferrals_total",
+                "Total number of crawl queue items deferred until a data source's quota resets",
+            ),
+            &["source"],
+        )?;
+        registry.register(Box::new(crawler_quota_deferrals_total.clone()))?;
+
+        let crawler_queue_coalesced_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_queue_coalesced_total",
+                "Total number of crawl requests merged into a pending queue item for the same series",
+            ),
+            &["source"],
+        )?;
+        registry.register(Box::new(crawler_queue_coalesced_total.clone()))?;
+
+        let crawler_api_key_requests_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_api_key_requests_total",
+                "Total number of requests sent with each API key of a data source",
+            ),
+            &["source", "api_key", "status"],
+        )?;
+        registry.register(Box::new(crawler_api_key_requests_total.clone()))?;
+
+        let crawler_endpoint_requests_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_endpoint_requests_total",
+                "Total number of requests served by each endpoint of a data source",
+            ),
+            &["source", "endpoint", "outcome"],
+        )?;
+        registry.register(Box::new(crawler_endpoint_requests_total.clone()))?;
+
+        let crawler_endpoint_circuit_state = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_endpoint_circuit_state",
+                "Circuit breaker state of each endpoint of a data source (0 closed, 1 half-open, 2 open)",
+            ),
+            &["source", "endpoint"],
+        )?;
+        registry.register(Box::new(crawler_endpoint_circuit_state.clone()))?;
+
+        let crawler_downloads_in_flight = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_downloads_in_flight",
+                "Current number of file downloads in progress",
+            ),
+            &["crawler_type", "source"],
+        )?;
+        registry.register(Box::new(crawler_downloads_in_flight.clone()))?;
+
+        let crawler_downloads_queued = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_downloads_queued",
+                "Current number of file downloads waiting for a download slot",
+            ),
+            &["crawler_type", "source"],
+        )?;
+        registry.register(Box::new(crawler_downloads_queued.clone()))?;
+
+        let crawler_resumed_downloads_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_resumed_downloads_total",
+                "Total number of file downloads resumed from a partial file",
+            ),
+            &["crawler_type", "source"],
+        )?;
+        registry.register(Box::new(crawler_resumed_downloads_total.clone()))?;
+
+        let crawler_data_quality_score = GaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_data_quality_score",
+                "Average data quality score of a data source's series, from 0 to 1",
+            ),
+            &["source"],
+        )?;
+        registry.register(Box::new(crawler_data_quality_score.clone()))?;
+
+        let crawler_data_quality_issues = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_crawler_data_quality_issues",
+                "Current number of series with a data quality issue",
+            ),
+            &["source", "issue"],
+        )?;
+        registry.register(Box::new(crawler_data_quality_issues.clone()))?;
+
+        let crawler_document_bytes_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_crawler_document_bytes_total",
+                "Total bytes of stored documents, before and after compression",
+            ),
+            &["document_type", "size"],
+        )?;
+        registry.register(Box::new(crawler_document_bytes_total.clone()))?;
+
+        let crawler_document_compression_ratio = HistogramVec::new(
+            HistogramOpts::new(
+                "econgraph_crawler_document_compression_ratio",
+                "Stored size of a document divided by its original size",
+            )
+            .buckets(vec![0.05, 0.1, 0.15, 0.2, 0.3, 0.4, 0.5, 0.75, 1.0]),
+            &["document_type"],
+        )?;
+        registry.register(Box::new(crawler_document_compression_ratio.clone()))?;
+
         Ok(Self {
             crawler_requests_total,
             crawler_request_duration_seconds,
@@ -156,9 +373,51 @@ impl CrawlerMetrics {
             crawler_rate_limit_hits_total,
             crawler_retries_total,
             crawler_timeouts_total,
+            crawler_dead_lettered_total,
+            crawler_dead_letter_queue_size,
+            crawler_validation_results_total,
+            crawler_quota_remaining,
+            crawler_quota_deferrals_total,
+            crawler_queue_coalesced_total,
+            crawler_api_key_requests_total,
+            crawler_endpoint_requests_total,
+            crawler_endpoint_circuit_state,
+            crawler_downloads_in_flight,
+            crawler_downloads_queued,
+            crawler_resumed_downloads_total,
+            crawler_data_quality_score,
+            crawler_data_quality_issues,
+            crawler_document_bytes_total,
+            crawler_document_compression_ratio,
+            registry,
         })
     }
 
+    /// Registry these metrics are registered with
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Route the current thread's `CRAWLER_METRICS` to a private registry
+    ///
+    /// Lets tests assert on exact counter values while running in parallel.
+    /// Only the calling thread is affected, so use a current-thread runtime
+    /// (the `#[tokio::test]` default) for async tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the metrics fail to register with the new registry
+    pub fn test_scope() -> CrawlerMetricsTestScope {
+        let metrics = CrawlerMetrics::with_registry(Registry::new())
+            .expect("Failed to initialize test crawler metrics");
+        // Leaked so `CRAWLER_METRICS` can hand out `&CrawlerMetrics` like the
+        // global instance; a handful of metric families per test is negligible
+        let metrics: &'static CrawlerMetrics = Box::leak(Box::new(metrics));
+        let previous = THREAD_METRICS.with(|current| current.replace(Some(metrics)));
+
+        CrawlerMetricsTestScope { metrics, previous }
+    }
+
     /// Record a crawler request with its duration and status
     ///
     /// This method updates both the request counter and duration histogram metrics
@@ -287,6 +546,250 @@ impl CrawlerMetrics {
             .with_label_values(&[crawler_type, source])
             .inc();
     }
+
+    /// Record a queue item moved to the dead-letter queue
+    ///
+    /// Called when an item exhausts its retries. The counter's growth rate is
+    /// what the dead-letter alerts watch.
+    ///
+    /// # Parameters
+    /// - `source`: Data source of the queue item (e.g., "FRED", "BLS")
+    pub fn record_dead_lettered(&self, source: &str) {
+        self.crawler_dead_lettered_total
+            .with_label_values(&[source])
+            .inc();
+        self.crawler_dead_letter_queue_size.inc();
+    }
+
+    /// Set the current dead-letter queue size
+    ///
+    /// # Parameters
+    /// - `size`: Number of items currently in the dead-letter queue
+    pub fn set_dead_letter_queue_size(&self, size: i64) {
+        self.crawler_dead_letter_queue_size.set(size);
+    }
+
+    /// Record observations handled by the ingestion validation pipeline
+    ///
+    /// # Parameters
+    /// - `source`: Data source of the batch (e.g., "FRED", "BLS")
+    /// - `outcome`: Validation outcome (e.g., "invalid", "outlier", "duplicate", "stored")
+    /// - `count`: Number of observations with this outcome
+    pub fn record_validation(&self, source: &str, outcome: &str, count: u64) {
+        if count > 0 {
+            self.crawler_validation_results_total
+                .with_label_values(&[source, outcome])
+                .inc_by(count);
+        }
+    }
+
+    /// Set the remaining daily quota of a data source
+    ///
+    /// # Parameters
+    /// - `source`: Data source the quota belongs to (e.g., "FRED", "BLS")
+    /// - `quota`: Quota kind, "bytes" or "requests"
+    /// - `remaining`: Amount left until the quota resets
+    pub fn set_quota_remaining(&self, source: &str, quota: &str, remaining: i64) {
+        self.crawler_quota_remaining
+            .with_label_values(&[source, quota])
+            .set(remaining);
+    }
+
+    /// Record crawl queue items deferred because a data source's quota ran out
+    ///
+    /// # Parameters
+    /// - `source`: Data source of the deferred items (e.g., "FRED", "BLS")
+    /// - `count`: Number of items deferred
+    pub fn record_quota_deferrals(&self, source: &str, count: u64) {
+        if count > 0 {
+            self.crawler_quota_deferrals_total
+                .with_label_values(&[source])
+                .inc_by(count);
+        }
+    }
+
+    /// Record crawl requests merged into a pending queue item for the same series
+    ///
+    /// # Parameters
+    /// - `source`: Data source of the series (e.g., "FRED", "BLS")
+    /// - `count`: Number of requests or duplicate items merged
+    pub fn record_queue_coalesced(&self, source: &str, count: u64) {
+        if count > 0 {
+            self.crawler_queue_coalesced_total
+                .with_label_values(&[source])
+                .inc_by(count);
+        }
+    }
+
+    /// Record a request sent with one of a data source's API keys
+    ///
+    /// # Parameters
+    /// - `source`: Data source the key belongs to (e.g., "FRED")
+    /// - `api_key`: Hash of the key, never the key itself
+    /// - `status`: HTTP status code of the response (e.g., "200", "429")
+    pub fn record_api_key_request(&self, source: &str, api_key: &str, status: &str) {
+        self.crawler_api_key_requests_total
+            .with_label_values(&[source, api_key, status])
+            .inc();
+    }
+
+    /// Record a request sent to one endpoint of a data source
+    ///
+    /// # Parameters
+    /// - `source`: Data source the endpoint belongs to (e.g., "BLS")
+    /// - `endpoint`: Base URL of the primary endpoint or a mirror
+    /// - `outcome`: "success", or "failure" for transport errors and server errors
+    pub fn record_endpoint_request(&self, source: &str, endpoint: &str, outcome: &str) {
+        self.crawler_endpoint_requests_total
+            .with_label_values(&[source, endpoint, outcome])
+            .inc();
+    }
+
+    /// Set the circuit breaker state of one endpoint of a data source
+    ///
+    /// # Parameters
+    /// - `source`: Data source the endpoint belongs to (e.g., "BLS")
+    /// - `endpoint`: Base URL of the primary endpoint or a mirror
+    /// - `state`: 0 closed, 1 half-open, 2 open
+    pub fn set_endpoint_circuit_state(&self, source: &str, endpoint: &str, state: i64) {
+        self.crawler_endpoint_circuit_state
+            .with_label_values(&[source, endpoint])
+            .set(state);
+    }
+
+    /// Adjust the number of file downloads in progress
+    ///
+    /// # Parameters
+    /// - `crawler_type`: Type of crawler (e.g., "sec")
+    /// - `source`: Data source being downloaded from (e.g., "edgar")
+    /// - `delta`: 1 when a download starts, -1 when it ends
+    pub fn add_downloads_in_flight(&self, crawler_type: &str, source: &str, delta: i64) {
+        self.crawler_downloads_in_flight
+            .with_label_values(&[crawler_type, source])
+            .add(delta);
+    }
+
+    /// Adjust the number of file downloads waiting for a slot
+    ///
+    /// # Parameters
+    /// - `crawler_type`: Type of crawler (e.g., "sec")
+    /// - `source`: Data source being downloaded from (e.g., "edgar")
+    /// - `delta`: Downloads queued (positive) or taken from the queue (negative)
+    pub fn add_queued_downloads(&self, crawler_type: &str, source: &str, delta: i64) {
+        self.crawler_downloads_queued
+            .with_label_values(&[crawler_type, source])
+            .add(delta);
+    }
+
+    /// Record a download resumed from a partial file
+    ///
+    /// # Parameters
+    /// - `crawler_type`: Type of crawler (e.g., "sec")
+    /// - `source`: Data source being downloaded from (e.g., "edgar")
+    pub fn record_resumed_download(&self, crawler_type: &str, source: &str) {
+        self.crawler_resumed_downloads_total
+            .with_label_values(&[crawler_type, source])
+            .inc();
+    }
+
+    /// Set the data quality gauges of a data source
+    ///
+    /// # Parameters
+    /// - `source`: Data source the series belong to (e.g., "FRED", "BLS")
+    /// - `average_score`: Average quality score of its scored series, from 0 to 1
+    /// - `outlier_series`: Series with at least one outlier
+    /// - `gap_series`: Series with missing periods
+    /// - `stale_series`: Series whose latest observation is overdue
+    pub fn set_data_quality(
+        &self,
+        source: &str,
+        average_score: f64,
+        outlier_series: i64,
+        gap_series: i64,
+        stale_series: i64,
+    ) {
+        self.crawler_data_quality_score
+            .with_label_values(&[source])
+            .set(average_score);
+        for (issue, count) in [
+            ("outliers", outlier_series),
+            ("gaps", gap_series),
+            ("stale", stale_series),
+        ] {
+            self.crawler_data_quality_issues
+                .with_label_values(&[source, issue])
+                .set(count);
+        }
+    }
+
+    /// Record a document written to storage
+    ///
+    /// # Parameters
+    /// - `document_type`: Kind of document (e.g., "filing", "taxonomy")
+    /// - `original_bytes`: Size of the document as downloaded
+    /// - `stored_bytes`: Size of the document as stored, after compression
+    pub fn record_document_stored(
+        &self,
+        document_type: &str,
+        original_bytes: u64,
+        stored_bytes: u64,
+    ) {
+        self.crawler_document_bytes_total
+            .with_label_values(&[document_type, "original"])
+            .inc_by(original_bytes);
+        self.crawler_document_bytes_total
+            .with_label_values(&[document_type, "stored"])
+            .inc_by(stored_bytes);
+        if original_bytes > 0 {
+            self.crawler_document_compression_ratio
+                .with_label_values(&[document_type])
+                .observe(stored_bytes as f64 / original_bytes as f64);
+        }
+    }
+}
+
+thread_local! {
+    /// Metrics installed by a [`CrawlerMetricsTestScope`] on this thread
+    static THREAD_METRICS: Cell<Option<&'static CrawlerMetrics>> = const { Cell::new(None) };
+}
+
+/// Handle behind [`CRAWLER_METRICS`]
+///
+/// Dereferences to the metrics registered with the default registry, or to the
+/// metrics of the innermost [`CrawlerMetricsTestScope`] open on this thread.
+pub struct CrawlerMetricsHandle {
+    global: Lazy<CrawlerMetrics>,
+}
+
+impl Deref for CrawlerMetricsHandle {
+    type Target = CrawlerMetrics;
+
+    fn deref(&self) -> &CrawlerMetrics {
+        THREAD_METRICS
+            .with(Cell::get)
+            .unwrap_or_else(|| &*self.global)
+    }
+}
+
+/// Thread-local override of [`CRAWLER_METRICS`], created by [`CrawlerMetrics::test_scope`]
+///
+/// Restores the previous metrics when dropped.
+pub struct CrawlerMetricsTestScope {
+    metrics: &'static CrawlerMetrics,
+    previous: Option<&'static CrawlerMetrics>,
+}
+
+impl CrawlerMetricsTestScope {
+    /// Metrics recorded on this thread while the scope is open
+    pub fn metrics(&self) -> &'static CrawlerMetrics {
+        self.metrics
+    }
+}
+
+impl Drop for CrawlerMetricsTestScope {
+    fn drop(&mut self) {
+        THREAD_METRICS.with(|current| current.set(self.previous));
+    }
 }
 
 /// Global crawler metrics instance
@@ -294,11 +797,103 @@ impl CrawlerMetrics {
 /// This static provides a lazily-initialized global instance of `CrawlerMetrics`
 /// that is registered with the default registry. It can be used throughout the
 /// application for consistent metrics collection without needing to pass metrics
-/// instances around.
+/// instances around. Tests can redirect it per thread with
+/// [`CrawlerMetrics::test_scope`].
 ///
 /// # Panics
 ///
 /// Panics if the metrics fail to initialize during lazy initialization
-pub static CRAWLER_METRICS: Lazy<CrawlerMetrics> = Lazy::new(|| {
-    CrawlerMetrics::new(&DEFAULT_REGISTRY).expect("Failed to initialize crawler metrics")
-});
+pub static CRAWLER_METRICS: CrawlerMetricsHandle = CrawlerMetricsHandle {
+    global: Lazy::new(|| {
+        CrawlerMetrics::new(&DEFAULT_REGISTRY).expect("Failed to initialize crawler metrics")
+    }),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn errors(metrics: &CrawlerMetrics) -> u64 {
+        metrics
+            .crawler_errors_total
+            .with_label_values(&["economic", "fred", "http_error"])
+            .get()
+    }
+
+    #[test]
+    fn test_scopes_isolate_parallel_threads() {
+        // REQUIREMENT: Metric-recording tests run in parallel without cross-talk
+        // PURPOSE: Verify each thread's test scope only sees its own increments
+
+        let handles: Vec<_> = (1..=4u64)
+            .map(|count| {
+                thread::spawn(move || {
+                    let scope = CrawlerMetrics::test_scope();
+                    for _ in 0..count {
+                        CRAWLER_METRICS.record_error("economic", "fred", "http_error");
+                    }
+                    errors(scope.metrics())
+                })
+            })
+            .collect();
+
+        let counts: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(counts, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_scope_restores_previous_metrics_on_drop() {
+        let outer = CrawlerMetrics::test_scope();
+        {
+            let inner = CrawlerMetrics::test_scope();
+            CRAWLER_METRICS.record_error("economic", "fred", "http_error");
+            assert_eq!(errors(inner.metrics()), 1);
+        }
+        CRAWLER_METRICS.record_error("economic", "fred", "http_error");
+
+        assert_eq!(errors(outer.metrics()), 1);
+        assert!(std::ptr::eq(&*CRAWLER_METRICS, outer.metrics()));
+    }
+
+    #[test]
+    fn test_with_registry_gathers_private_registry() {
+        let metrics = CrawlerMetrics::with_registry(Registry::new()).unwrap();
+        metrics.record_validation("FRED", "stored", 3);
+
+        let exported = prometheus::TextEncoder::new()
+            .encode_to_string(&metrics.registry().gather())
+            .unwrap();
+        assert!(exported.contains(
+            r#"econgraph_crawler_validation_results_total{outcome="stored",source="FRED"} 3"#
+        ));
+    }
+
+    #[test]
+    fn test_document_compression_is_recorded() {
+        // REQUIREMENT: Compression ratios of stored SEC documents are monitored
+        // PURPOSE: Verify original and stored sizes are counted and each document's ratio observed
+        // This ensures the effect of a compression level change shows up on dashboards
+
+        let scope = CrawlerMetrics::test_scope();
+        CRAWLER_METRICS.record_document_stored("filing", 1000, 200);
+        CRAWLER_METRICS.record_document_stored("filing", 1000, 400);
+        CRAWLER_METRICS.record_document_stored("filing", 0, 0);
+
+        let metrics = scope.metrics();
+        let bytes = |size: &str| {
+            metrics
+                .crawler_document_bytes_total
+                .with_label_values(&["filing", size])
+                .get()
+        };
+        assert_eq!(bytes("original"), 2000);
+        assert_eq!(bytes("stored"), 600);
+
+        let ratios = metrics
+            .crawler_document_compression_ratio
+            .with_label_values(&["filing"]);
+        assert_eq!(ratios.get_sample_count(), 2);
+        assert!((ratios.get_sample_sum() - 0.6).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,207 @@
+//! # Database Metrics
+//!
+//! This module provides metrics for the PostgreSQL connection pool and the
+//! statements run through it, for diagnosing database contention: how busy the
+//! pool is, how long callers wait for a connection, and which statements are slow.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_metrics::database::DATABASE_METRICS;
+//!
+//! DATABASE_METRICS.set_pool_state(20, 12, 3);
+//! DATABASE_METRICS.record_query("select", "data_points", 0.012, false);
+//! ```
+
+use crate::DEFAULT_REGISTRY;
+use once_cell::sync::Lazy;
+use prometheus::{
+    Gauge, Histogram, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry,
+};
+
+/// Connection pool and statement metrics
+pub struct DatabaseMetrics {
+    /// Open pool connections, categorized by state ("idle" or "in_use")
+    pub db_pool_connections: IntGaugeVec,
+    /// Most connections the pool will open
+    pub db_pool_max_connections: IntGauge,
+    /// Share of the pool's maximum size in use, from 0 to 1
+    pub db_pool_utilization: Gauge,
+    /// Time callers waited for a pool connection when none was free
+    pub db_pool_acquire_wait_seconds: Histogram,
+    /// Total number of connection requests, categorized by outcome ("direct", "waited", "timed_out")
+    pub db_pool_acquisitions_total: IntCounterVec,
+    /// Statement duration, categorized by operation and table
+    pub db_query_duration_seconds: HistogramVec,
+    /// Total number of statements slower than the slow-query threshold, categorized by operation and table
+    pub db_slow_queries_total: IntCounterVec,
+    /// Total number of failed statements, categorized by operation and table
+    pub db_query_errors_total: IntCounterVec,
+}
+
+impl DatabaseMetrics {
+    /// Create a new `DatabaseMetrics` instance with all metrics registered to the provided registry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any metric fails to register with the provided registry
+    pub fn new(registry: &Registry) -> anyhow::Result<Self> {
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "econgraph_db_pool_connections",
+                "Current number of open database pool connections",
+            ),
+            &["state"],
+        )?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+
+        let db_pool_max_connections = IntGauge::new(
+            "econgraph_db_pool_max_connections",
+            "Maximum number of database pool connections",
+        )?;
+        registry.register(Box::new(db_pool_max_connections.clone()))?;
+
+        let db_pool_utilization = Gauge::new(
+            "econgraph_db_pool_utilization",
+            "Share of the maximum database pool size in use",
+        )?;
+        registry.register(Box::new(db_pool_utilization.clone()))?;
+
+        let db_pool_acquire_wait_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "econgraph_db_pool_acquire_wait_seconds",
+                "Time spent waiting for a database pool connection when none was free",
+            )
+            .buckets(vec![
+                0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+            ]),
+        )?;
+        registry.register(Box::new(db_pool_acquire_wait_seconds.clone()))?;
+
+        let db_pool_acquisitions_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_db_pool_acquisitions_total",
+                "Total number of database pool connection requests",
+            ),
+            &["outcome"],
+        )?;
+        registry.register(Box::new(db_pool_acquisitions_total.clone()))?;
+
+        let db_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "econgraph_db_query_duration_seconds",
+                "Database statement duration in seconds",
+            )
+            .buckets(vec![
+                0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+            ]),
+            &["operation", "table"],
+        )?;
+        registry.register(Box::new(db_query_duration_seconds.clone()))?;
+
+        let db_slow_queries_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_db_slow_queries_total",
+                "Total number of database statements slower than the slow-query threshold",
+            ),
+            &["operation", "table"],
+        )?;
+        registry.register(Box::new(db_slow_queries_total.clone()))?;
+
+        let db_query_errors_total = IntCounterVec::new(
+            Opts::new(
+                "econgraph_db_query_errors_total",
+                "Total number of failed database statements",
+            ),
+            &["operation", "table"],
+        )?;
+        registry.register(Box::new(db_query_errors_total.clone()))?;
+
+        Ok(Self {
+            db_pool_connections,
+            db_pool_max_connections,
+            db_pool_utilization,
+            db_pool_acquire_wait_seconds,
+            db_pool_acquisitions_total,
+            db_query_duration_seconds,
+            db_slow_queries_total,
+            db_query_errors_total,
+        })
+    }
+
+    /// Set the current size and use of the connection pool
+    ///
+    /// # Parameters
+    /// - `max_connections`: Most connections the pool will open
+    /// - `connections`: Connections currently open
+    /// - `idle_connections`: Open connections not checked out
+    pub fn set_pool_state(&self, max_connections: u32, connections: u32, idle_connections: u32) {
+        let in_use = connections.saturating_sub(idle_connections);
+        self.db_pool_max_connections.set(i64::from(max_connections));
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(i64::from(idle_connections));
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set(i64::from(in_use));
+        if max_connections > 0 {
+            self.db_pool_utilization
+                .set(f64::from(in_use) / f64::from(max_connections));
+        }
+    }
+
+    /// Record connection requests by outcome
+    ///
+    /// # Parameters
+    /// - `outcome`: "direct" (a connection was free), "waited" or "timed_out"
+    /// - `count`: Number of requests with this outcome
+    pub fn record_acquisitions(&self, outcome: &str, count: u64) {
+        if count > 0 {
+            self.db_pool_acquisitions_total
+                .with_label_values(&[outcome])
+                .inc_by(count);
+        }
+    }
+
+    /// Record the wait of `count` connection requests that found no free connection
+    pub fn observe_acquire_wait(&self, wait_seconds: f64, count: u64) {
+        for _ in 0..count {
+            self.db_pool_acquire_wait_seconds.observe(wait_seconds);
+        }
+    }
+
+    /// Record a finished statement
+    ///
+    /// # Parameters
+    /// - `operation`: Statement kind (e.g., "select", "insert")
+    /// - `table`: Main table the statement touches (e.g., "data_points")
+    /// - `duration_seconds`: Time from sending the statement to its result
+    /// - `failed`: Whether the statement returned an error
+    pub fn record_query(&self, operation: &str, table: &str, duration_seconds: f64, failed: bool) {
+        self.db_query_duration_seconds
+            .with_label_values(&[operation, table])
+            .observe(duration_seconds);
+        if failed {
+            self.db_query_errors_total
+                .with_label_values(&[operation, table])
+                .inc();
+        }
+    }
+
+    /// Record a statement slower than the slow-query threshold
+    pub fn record_slow_query(&self, operation: &str, table: &str) {
+        self.db_slow_queries_total
+            .with_label_values(&[operation, table])
+            .inc();
+    }
+}
+
+/// Global database metrics instance registered with the default registry
+///
+/// # Panics
+///
+/// Panics if the metrics fail to initialize during lazy initialization
+pub static DATABASE_METRICS: Lazy<DatabaseMetrics> = Lazy::new(|| {
+    DatabaseMetrics::new(&DEFAULT_REGISTRY).expect("Failed to initialize database metrics")
+});
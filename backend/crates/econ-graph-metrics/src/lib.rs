@@ -10,6 +10,10 @@
 //! - **Error Tracking**: Comprehensive error categorization and counting
 //! - **Resource Usage**: Bandwidth and data collection metrics
 //! - **Rate Limiting**: Track rate limit hits and retry attempts
+//! - **Cache Metrics**: Hit, miss and eviction counts for in-memory caches
+//! - **Database Metrics**: Connection pool utilization, acquisition waits and statement durations
+//! - **Distributed Tracing**: OpenTelemetry export and trace context propagation
+//! - **Structured Logging**: JSON log output with request correlation fields
 //!
 //! ## Usage
 //!
@@ -29,7 +33,11 @@ pub use prometheus;
 use prometheus::Registry;
 use std::sync::Arc;
 
+pub mod cache;
 pub mod crawler;
+pub mod database;
+pub mod logging;
+pub mod telemetry;
 
 /// Shared default registry used across crates
 ///
@@ -0,0 +1,389 @@
+//! # Structured Logging
+//!
+//! Log output shared by the backend and crawler binaries. By default logs are
+//! human-readable text; with `LOG_FORMAT=json` every event is written as one
+//! JSON object per line, ready for Loki or ELK.
+//!
+//! Spans may carry correlation fields (`request_id`, `user_id`, `series_id`,
+//! `source`). [`CorrelationLayer`] remembers them, and JSON events include the
+//! nearest value of each field from the span they were emitted in or any of
+//! its ancestors. All logs of one HTTP request therefore share its
+//! `request_id`, including those of the services and crawlers it calls.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_metrics::logging::{self, CorrelationLayer, LogFormat};
+//! use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+//!
+//! let format = LogFormat::from_env();
+//! tracing_subscriber::registry()
+//!     .with(CorrelationLayer)
+//!     .with(format.is_text().then(tracing_subscriber::fmt::layer))
+//!     .with(format.is_json().then(logging::json_layer))
+//!     .init();
+//! ```
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Span, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::{FormatTime, SystemTime};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Span fields copied onto every JSON event logged inside the span
+pub const CORRELATION_FIELDS: [&str; 4] = ["request_id", "user_id", "series_id", "source"];
+
+/// Header carrying the request ID of an HTTP request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied request ID that is accepted
+pub const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// Log output format, chosen with `LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable text (default)
+    #[default]
+    Text,
+    /// One JSON object per event
+    Json,
+}
+
+impl LogFormat {
+    /// Read `LOG_FORMAT` (`text` or `json`); anything else means text
+    pub fn from_env() -> Self {
+        Self::parse(std::env::var("LOG_FORMAT").ok().as_deref())
+    }
+
+    fn parse(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            Some(value) if value.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+
+    pub fn is_text(self) -> bool {
+        self == Self::Text
+    }
+
+    pub fn is_json(self) -> bool {
+        self == Self::Json
+    }
+}
+
+/// Install a subscriber filtered by `RUST_LOG`, logging in the `LOG_FORMAT` format
+///
+/// For command line tools; servers that also export traces build their own
+/// subscriber from [`CorrelationLayer`] and [`json_layer`].
+pub fn init_from_env() {
+    init(EnvFilter::from_default_env());
+}
+
+/// Install a subscriber filtered by `directives` (`RUST_LOG` syntax), logging in the `LOG_FORMAT` format
+///
+/// For command line tools configured through `econ_graph_core::Config`, whose
+/// `logging.level` already takes `RUST_LOG` into account. Invalid directives
+/// fall back to `info`.
+pub fn init_with_filter(directives: &str) {
+    init(EnvFilter::try_new(directives).unwrap_or_else(|_| EnvFilter::new("info")));
+}
+
+fn init(filter: EnvFilter) {
+    let format = LogFormat::from_env();
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(CorrelationLayer)
+        .with(format.is_text().then(tracing_subscriber::fmt::layer))
+        .with(format.is_json().then(json_layer))
+        .init();
+}
+
+/// `fmt` layer writing one JSON object per event to stdout
+pub fn json_layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_subscriber::fmt::layer().event_format(JsonFormat)
+}
+
+/// Request ID for a request carrying `header` as its `x-request-id`
+///
+/// The caller's ID is kept when it is safe to log; otherwise a new one is generated.
+pub fn request_id_from_header(header: Option<&str>) -> String {
+    header
+        .and_then(usable_request_id)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// A caller-supplied request ID, if it is safe to log
+pub fn usable_request_id(request_id: &str) -> Option<&str> {
+    let request_id = request_id.trim();
+    let usable = !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LENGTH
+        && request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    usable.then_some(request_id)
+}
+
+/// `request_id` of the current span or its nearest ancestor that has one
+///
+/// Needs [`CorrelationLayer`] to be installed.
+pub fn current_request_id() -> Option<String> {
+    current_correlation_field("request_id")
+}
+
+/// Nearest value of a correlation field in the current span's scope
+pub fn current_correlation_field(name: &str) -> Option<String> {
+    Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            let span = registry.span(id)?;
+            span.scope()
+                .find_map(|span| span.extensions().get::<CorrelationFields>()?.get(name))
+        })
+        .flatten()
+}
+
+/// Correlation fields recorded on one span
+#[derive(Debug, Default)]
+struct CorrelationFields(BTreeMap<&'static str, String>);
+
+impl CorrelationFields {
+    fn get(&self, name: &str) -> Option<String> {
+        self.0.get(name).cloned()
+    }
+}
+
+impl Visit for CorrelationFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if CORRELATION_FIELDS.contains(&field.name()) {
+            self.0.insert(field.name(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if CORRELATION_FIELDS.contains(&field.name()) {
+            // Strings recorded with `?value` arrive quoted
+            let value = format!("{:?}", value);
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(str::to_string)
+                .unwrap_or(value);
+            self.0.insert(field.name(), value);
+        }
+    }
+}
+
+/// Remembers the correlation fields of each span, including fields recorded later
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CorrelationLayer;
+
+impl<S> Layer<S> for CorrelationLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = CorrelationFields::default();
+        attrs.record(&mut fields);
+        if fields.0.is_empty() {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        match extensions.get_mut::<CorrelationFields>() {
+            Some(fields) => values.record(fields),
+            None => {
+                let mut fields = CorrelationFields::default();
+                values.record(&mut fields);
+                if !fields.0.is_empty() {
+                    extensions.insert(fields);
+                }
+            }
+        }
+    }
+}
+
+/// Writes events as single-line JSON with the correlation fields of their spans
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut timestamp = String::new();
+        SystemTime.format_time(&mut Writer::new(&mut timestamp))?;
+
+        let mut object = Map::new();
+        object.insert("timestamp".into(), Value::String(timestamp));
+        object.insert("level".into(), Value::String(metadata.level().to_string()));
+        object.insert(
+            "target".into(),
+            Value::String(metadata.target().to_string()),
+        );
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut spans = scope.peekable();
+            if let Some(span) = spans.peek() {
+                object.insert("span".into(), Value::String(span.name().to_string()));
+            }
+            for span in spans {
+                if let Some(fields) = span.extensions().get::<CorrelationFields>() {
+                    for (name, value) in &fields.0 {
+                        object
+                            .entry(name.to_string())
+                            .or_insert_with(|| Value::String(value.clone()));
+                    }
+                }
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut object));
+
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
+/// Collects event fields into a JSON object
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::String(format!("{:?}", value)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// Captures everything written by the fmt layer
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_log_format_from_env_value() {
+        assert_eq!(LogFormat::parse(None), LogFormat::Text);
+        assert_eq!(LogFormat::parse(Some("JSON ")), LogFormat::Json);
+        assert_eq!(LogFormat::parse(Some("pretty")), LogFormat::Text);
+    }
+
+    #[test]
+    fn test_request_id_from_header() {
+        // REQUIREMENT: Every HTTP request has a request id for log correlation
+        // PURPOSE: Verify usable caller ids are kept and missing or unsafe ones are replaced
+
+        assert_eq!(request_id_from_header(Some(" req-1 ")), "req-1");
+        assert!(uuid::Uuid::parse_str(&request_id_from_header(None)).is_ok());
+        assert!(
+            uuid::Uuid::parse_str(&request_id_from_header(Some("bad id\nUser: admin"))).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_json_events_carry_correlation_fields_of_enclosing_spans() {
+        // REQUIREMENT: Logs of one request can be correlated in Loki/ELK
+        // PURPOSE: Verify JSON events include request, user, series and source fields
+        // recorded on their spans and ancestors, including fields recorded after creation
+        // This ensures service and crawler logs inherit the request id of the HTTP request
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::registry().with(CorrelationLayer).with(
+            tracing_subscriber::fmt::layer()
+                .event_format(JsonFormat)
+                .with_writer(move || writer.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!(
+                "http.request",
+                request_id = "req-42",
+                user_id = tracing::field::Empty
+            );
+            let _request = request.enter();
+            request.record("user_id", tracing::field::display("user-7"));
+
+            let crawl =
+                tracing::info_span!("crawler.queue_item", source = "FRED", series_id = %"GDP");
+            let _crawl = crawl.enter();
+            assert_eq!(current_request_id(), Some("req-42".to_string()));
+
+            tracing::info!(observations = 3, "stored observations");
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let event: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(event["request_id"], "req-42");
+        assert_eq!(event["user_id"], "user-7");
+        assert_eq!(event["source"], "FRED");
+        assert_eq!(event["series_id"], "GDP");
+        assert_eq!(event["span"], "crawler.queue_item");
+        assert_eq!(event["message"], "stored observations");
+        assert_eq!(event["observations"], 3);
+        assert_eq!(event["level"], "INFO");
+    }
+}
@@ -0,0 +1,201 @@
+//! # Distributed Tracing
+//!
+//! OpenTelemetry setup shared by the backend and crawler binaries. Spans
+//! recorded through `tracing` are exported over OTLP/gRPC when an OTLP
+//! endpoint is configured, and W3C trace context (`traceparent`) is used to
+//! continue traces across service boundaries.
+//!
+//! ## Configuration
+//!
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`):
+//!   collector address, e.g. `http://tempo:4317`. Export is disabled when unset.
+//! - `OTEL_SDK_DISABLED=true`: disable export even when an endpoint is set
+//! - `OTEL_TRACES_SAMPLER` / `OTEL_TRACES_SAMPLER_ARG`: standard sampler settings
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use econ_graph_metrics::telemetry::Telemetry;
+//! use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let telemetry = Telemetry::from_env("econ-graph-backend")?;
+//! tracing_subscriber::registry()
+//!     .with(tracing_subscriber::fmt::layer())
+//!     .with(telemetry.layer())
+//!     .init();
+//! # Ok(())
+//! # }
+//! ```
+
+use opentelemetry::propagation::TextMapCompositePropagator;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::{global, Context};
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+use tracing::Span;
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+
+/// Environment variables that enable OTLP export
+const ENDPOINT_VARS: [&str; 2] = [
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+];
+
+/// OpenTelemetry tracer provider for one service
+///
+/// Dropping it flushes and shuts down the exporter, so keep it alive for the
+/// lifetime of `main`.
+pub struct Telemetry {
+    service_name: &'static str,
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    /// Configure trace propagation and, when an endpoint is set, OTLP export
+    pub fn from_env(service_name: &'static str) -> anyhow::Result<Self> {
+        global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+            Box::new(TraceContextPropagator::new()),
+            Box::new(BaggagePropagator::new()),
+        ]));
+
+        if !export_enabled(|name| std::env::var(name).ok()) {
+            return Ok(Self {
+                service_name,
+                provider: None,
+            });
+        }
+
+        // The exporter reads the endpoint, headers and timeout from the
+        // standard OTEL_EXPORTER_OTLP_* variables.
+        let exporter = SpanExporter::builder().with_tonic().build()?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(service_name)
+                    .with_attribute(opentelemetry::KeyValue::new(
+                        "service.version",
+                        env!("CARGO_PKG_VERSION"),
+                    ))
+                    .build(),
+            )
+            .build();
+        global::set_tracer_provider(provider.clone());
+
+        Ok(Self {
+            service_name,
+            provider: Some(provider),
+        })
+    }
+
+    /// Whether spans are being exported
+    pub fn is_exporting(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// `tracing` layer forwarding spans to OpenTelemetry, if export is enabled
+    pub fn layer<S>(&self) -> Option<OpenTelemetryLayer<S, SdkTracer>>
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        self.provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer().with_tracer(provider.tracer(self.service_name))
+        })
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush traces on shutdown: {}", e);
+            }
+        }
+    }
+}
+
+/// Whether OTLP export is configured, given an environment lookup
+fn export_enabled(var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("OTEL_SDK_DISABLED").is_some_and(|value| value.eq_ignore_ascii_case("true")) {
+        return false;
+    }
+
+    ENDPOINT_VARS
+        .iter()
+        .any(|name| var(name).is_some_and(|value| !value.trim().is_empty()))
+}
+
+/// Headers carrying the current span's trace context for an outgoing request
+///
+/// Returns W3C `traceparent` (and `tracestate`/`baggage` when present); empty
+/// when there is no active trace.
+pub fn trace_context_headers() -> HashMap<String, String> {
+    let context = Span::current().context();
+    let mut headers = HashMap::new();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut headers);
+    });
+    headers
+}
+
+/// Continue the trace carried by incoming request headers in `span`
+///
+/// Header names are matched case-insensitively. Requests without a
+/// `traceparent` start a new trace.
+pub fn set_parent_from_headers<'a>(
+    span: &Span,
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    let carrier: HashMap<String, String> = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+        .collect();
+
+    let parent: Context =
+        global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+    // Only fails when no OpenTelemetry layer is installed, i.e. export is disabled
+    let _ = span.set_parent(parent);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_enabled() {
+        // REQUIREMENT: Tracing export is opt-in per deployment
+        // PURPOSE: Verify OTLP export only starts when an endpoint is configured and not disabled
+        // This ensures local runs and tests never try to reach a collector
+
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert!(!export_enabled(env(&[])));
+        assert!(export_enabled(env(&[(
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "http://tempo:4317"
+        )])));
+        assert!(export_enabled(env(&[(
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+            "http://jaeger:4317"
+        )])));
+        assert!(!export_enabled(env(&[(
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            " "
+        )])));
+        assert!(!export_enabled(env(&[
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://tempo:4317"),
+            ("OTEL_SDK_DISABLED", "TRUE"),
+        ])));
+    }
+}
@@ -1,9 +1,23 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use econ_graph_core::database::DatabasePool;
-use econ_graph_sec_crawler::{CrawlConfig, SecEdgarCrawler};
+use econ_graph_core::models::DocumentKind;
+use econ_graph_core::shutdown::{
+    shutdown_coordinator, shutdown_deadline_from_env, shutdown_signal,
+};
+use econ_graph_metrics::telemetry::Telemetry;
+use econ_graph_sec_crawler::blob_store::{DEFAULT_RECOMPRESSION_LIMIT, DEFAULT_VERIFICATION_LIMIT};
+use econ_graph_sec_crawler::checkpoint::{DEFAULT_BATCH_NAME, DEFAULT_PAGE_SIZE};
+use econ_graph_sec_crawler::company_sync::DEFAULT_COMPANY_SYNC_SCHEDULE;
+use econ_graph_sec_crawler::insider_transactions::DEFAULT_INSIDER_CRAWL_SCHEDULE;
+use econ_graph_sec_crawler::institutional_holdings::DEFAULT_INSTITUTIONAL_CRAWL_SCHEDULE;
+use econ_graph_sec_crawler::{
+    schedule_company_sync, schedule_insider_crawl, schedule_institutional_crawl, CrawlConfig,
+    InsiderDayReport, InstitutionalDayReport, SecEdgarCrawler,
+};
 use std::path::PathBuf;
-use tracing::{error, info};
+use tokio_cron_scheduler::JobScheduler;
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// **SEC EDGAR Crawler CLI**
@@ -56,9 +70,64 @@ enum Commands {
         exclude_restated: bool,
     },
 
+    /// Sync the companies table with SEC's company_tickers.json
+    SyncCompanies {
+        /// Keep running and re-sync on this cron schedule (with seconds)
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_COMPANY_SYNC_SCHEDULE)]
+        schedule: Option<String>,
+    },
+
+    /// Crawl Form 4 insider transactions from the EDGAR daily index
+    CrawlInsiders {
+        /// Crawl only this day (YYYY-MM-DD) instead of the days since the last crawl
+        #[arg(short, long)]
+        date: Option<String>,
+
+        /// Keep running and crawl again on this cron schedule (with seconds)
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_INSIDER_CRAWL_SCHEDULE)]
+        schedule: Option<String>,
+    },
+
+    /// Crawl 13F-HR institutional holdings from the EDGAR daily index
+    #[command(name = "crawl-13f")]
+    Crawl13f {
+        /// Crawl only this day (YYYY-MM-DD) instead of the days since the last crawl
+        #[arg(short, long)]
+        date: Option<String>,
+
+        /// Keep running and crawl again on this cron schedule (with seconds)
+        #[arg(long, num_args = 0..=1, default_missing_value = DEFAULT_INSTITUTIONAL_CRAWL_SCHEDULE)]
+        schedule: Option<String>,
+    },
+
+    /// Parse stored filings into line items, resuming an interrupted batch
+    ProcessXbrl {
+        /// Batch name; progress is checkpointed under this name
+        #[arg(short, long, default_value = DEFAULT_BATCH_NAME)]
+        batch: String,
+
+        /// Filings loaded per page
+        #[arg(short, long, default_value_t = DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+    },
+
     /// Get storage statistics
     Stats,
 
+    /// Check stored documents against their content hashes, least recently checked first
+    VerifyDocuments {
+        /// Maximum documents to check
+        #[arg(short, long, default_value_t = DEFAULT_VERIFICATION_LIMIT)]
+        limit: i64,
+    },
+
+    /// Re-encode stored filings and taxonomy files at the configured zstd levels
+    RecompressDocuments {
+        /// Maximum documents to re-encode of each kind
+        #[arg(short, long, default_value_t = DEFAULT_RECOMPRESSION_LIMIT)]
+        limit: i64,
+    },
+
     /// Validate XBRL file
     Validate {
         /// Path to XBRL file
@@ -80,13 +149,15 @@ enum Commands {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging
+    // Initialize logging, exporting crawl spans over OTLP when configured
+    let telemetry = Telemetry::from_env("econ-graph-sec-crawler")?;
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "sec_crawler=info".into()),
+                .unwrap_or_else(|_| "sec_crawler=info,econ_graph_sec_crawler=info".into()),
         )
         .with(tracing_subscriber::fmt::layer())
+        .with(telemetry.layer())
         .init();
 
     let cli = Cli::parse();
@@ -123,10 +194,34 @@ async fn main() -> Result<()> {
             .await?;
         }
 
+        Commands::SyncCompanies { schedule } => {
+            sync_companies_command(crawler, schedule).await?;
+        }
+
+        Commands::CrawlInsiders { date, schedule } => {
+            crawl_insiders_command(crawler, date, schedule).await?;
+        }
+
+        Commands::Crawl13f { date, schedule } => {
+            crawl_13f_command(crawler, date, schedule).await?;
+        }
+
+        Commands::ProcessXbrl { batch, page_size } => {
+            process_xbrl_command(crawler, batch, page_size).await?;
+        }
+
         Commands::Stats => {
             stats_command(crawler).await?;
         }
 
+        Commands::VerifyDocuments { limit } => {
+            verify_documents_command(crawler, limit).await?;
+        }
+
+        Commands::RecompressDocuments { limit } => {
+            recompress_documents_command(crawler, limit).await?;
+        }
+
         Commands::Validate { file } => {
             validate_command(file).await?;
         }
@@ -225,6 +320,172 @@ async fn crawl_company_command(
     Ok(())
 }
 
+async fn sync_companies_command(crawler: SecEdgarCrawler, schedule: Option<String>) -> Result<()> {
+    let report = crawler.sync_companies().await?;
+
+    println!("Company Sync Results:");
+    println!("  Listed by SEC: {}", report.listed);
+    println!("  Added: {}", report.added.len());
+    println!("  Updated: {}", report.updated.len());
+    println!("  Reactivated: {}", report.reactivated.len());
+    println!("  Deactivated: {}", report.deactivated.len());
+    println!("  Unchanged: {}", report.unchanged);
+
+    if let Some(schedule) = schedule {
+        let scheduler = schedule_company_sync(crawler, &schedule).await?;
+        info!("Waiting for scheduled company syncs, press Ctrl+C to stop");
+        drain_scheduler(scheduler).await?;
+    }
+
+    Ok(())
+}
+
+async fn crawl_insiders_command(
+    crawler: SecEdgarCrawler,
+    date: Option<String>,
+    schedule: Option<String>,
+) -> Result<()> {
+    let days = match date {
+        Some(date) => {
+            let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+            vec![crawler.crawl_insider_day(date).await?]
+        }
+        None => crawler.crawl_insider_transactions().await?.days,
+    };
+
+    println!("Insider Transaction Crawl Results:");
+    if days.is_empty() {
+        println!("  Already up to date");
+    }
+    for day in &days {
+        print_insider_day(day);
+    }
+
+    if let Some(schedule) = schedule {
+        let scheduler = schedule_insider_crawl(crawler, &schedule).await?;
+        info!("Waiting for scheduled insider crawls, press Ctrl+C to stop");
+        drain_scheduler(scheduler).await?;
+    }
+
+    Ok(())
+}
+
+fn print_insider_day(day: &InsiderDayReport) {
+    println!(
+        "  {}: {} filings, {} stored, {} already stored, {} failed, {} transactions",
+        day.index_date,
+        day.filings_found,
+        day.filings_stored,
+        day.filings_skipped,
+        day.filings_failed,
+        day.transactions_stored
+    );
+    for error in &day.errors {
+        println!("    - {}", error);
+    }
+}
+
+async fn crawl_13f_command(
+    crawler: SecEdgarCrawler,
+    date: Option<String>,
+    schedule: Option<String>,
+) -> Result<()> {
+    let days = match date {
+        Some(date) => {
+            let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+            vec![crawler.crawl_institutional_day(date).await?]
+        }
+        None => crawler.crawl_institutional_holdings().await?.days,
+    };
+
+    println!("13F Holdings Crawl Results:");
+    if days.is_empty() {
+        println!("  Already up to date");
+    }
+    for day in &days {
+        print_institutional_day(day);
+    }
+
+    if let Some(schedule) = schedule {
+        let scheduler = schedule_institutional_crawl(crawler, &schedule).await?;
+        info!("Waiting for scheduled 13F crawls, press Ctrl+C to stop");
+        drain_scheduler(scheduler).await?;
+    }
+
+    Ok(())
+}
+
+fn print_institutional_day(day: &InstitutionalDayReport) {
+    println!(
+        "  {}: {} filings, {} stored, {} already stored, {} failed, {} holdings, {} CUSIPs mapped",
+        day.index_date,
+        day.filings_found,
+        day.filings_stored,
+        day.filings_skipped,
+        day.filings_failed,
+        day.holdings_stored,
+        day.cusips_mapped
+    );
+    for error in &day.errors {
+        println!("    - {}", error);
+    }
+}
+
+/// Wait for SIGTERM or Ctrl+C, then stop scheduling and let running crawls finish
+async fn drain_scheduler(mut scheduler: JobScheduler) -> Result<()> {
+    shutdown_signal().await;
+    info!("Shutdown requested, waiting for running crawls to finish");
+    let coordinator = shutdown_coordinator();
+    coordinator.drain();
+    scheduler.shutdown().await?;
+
+    if !coordinator
+        .wait_for_in_flight(shutdown_deadline_from_env())
+        .await
+    {
+        warn!(
+            "Stopping with {} crawls still running",
+            coordinator.in_flight()
+        );
+    }
+    Ok(())
+}
+
+async fn process_xbrl_command(
+    crawler: SecEdgarCrawler,
+    batch: String,
+    page_size: i64,
+) -> Result<()> {
+    // The batch stops after the filing in flight; its checkpoint resumes it on the next run
+    tokio::spawn(async {
+        shutdown_signal().await;
+        info!("Shutdown requested, stopping the XBRL batch after the current filing");
+        shutdown_coordinator().drain();
+    });
+    let report = crawler.process_xbrl_batch(&batch, page_size).await?;
+
+    println!("XBRL Batch Results ({}):", report.batch_name);
+    if report.interrupted {
+        println!("  Stopped for shutdown; run again to resume");
+    }
+    if let Some(cursor) = report.resumed_after {
+        println!("  Resumed after filing: {}", cursor);
+    }
+    println!("  Completed: {}", report.filings_completed);
+    println!("  Failed: {}", report.filings_failed);
+    println!("  Skipped: {}", report.filings_skipped);
+    println!("  Line items stored: {}", report.line_items_stored);
+
+    if !report.errors.is_empty() {
+        println!("  Errors:");
+        for error in &report.errors {
+            println!("    - {}", error);
+        }
+    }
+
+    Ok(())
+}
+
 async fn stats_command(crawler: SecEdgarCrawler) -> Result<()> {
     info!("Getting storage statistics");
 
@@ -235,9 +496,75 @@ async fn stats_command(crawler: SecEdgarCrawler) -> Result<()> {
     println!("  Total size: {} bytes", stats.total_size_bytes);
     println!("  Large object files: {}", stats.large_object_files);
     println!("  Bytea files: {}", stats.bytea_files);
+    println!("  Blob files: {}", stats.blob_files);
     println!("  Compressed files: {}", stats.compressed_files);
     println!("  Uncompressed files: {}", stats.uncompressed_files);
 
+    let blobs = crawler.get_document_blob_stats().await?;
+    println!("Document Blobs:");
+    println!("  Distinct documents: {}", blobs.blob_count);
+    println!("  References: {}", blobs.reference_count);
+    println!("  Referenced size: {} bytes", blobs.referenced_size_bytes);
+    println!("  Uncompressed size: {} bytes", blobs.size_bytes);
+    println!("  Stored size: {} bytes", blobs.stored_size_bytes);
+    if blobs.size_bytes > 0 {
+        println!(
+            "  Compression ratio: {:.3}",
+            blobs.stored_size_bytes as f64 / blobs.size_bytes as f64
+        );
+    }
+
+    Ok(())
+}
+
+async fn verify_documents_command(crawler: SecEdgarCrawler, limit: i64) -> Result<()> {
+    info!("Verifying up to {} stored documents", limit);
+
+    let report = crawler.verify_documents(limit).await?;
+
+    println!("Document Verification Results:");
+    println!("  Checked: {}", report.checked);
+    println!("  Corrupted: {}", report.corrupted.len());
+    for hash in &report.corrupted {
+        println!("    - {}", hash);
+    }
+
+    if !report.corrupted.is_empty() {
+        anyhow::bail!("{} stored documents are corrupted", report.corrupted.len());
+    }
+
+    Ok(())
+}
+
+async fn recompress_documents_command(crawler: SecEdgarCrawler, limit: i64) -> Result<()> {
+    let mut failed = 0;
+    for kind in [DocumentKind::Filing, DocumentKind::Taxonomy] {
+        info!(
+            "Recompressing up to {} stored {} documents",
+            limit,
+            kind.as_str()
+        );
+
+        let report = crawler.recompress_documents(kind, limit).await?;
+
+        println!("Recompression Results ({}):", kind.as_str());
+        println!("  Checked: {}", report.checked);
+        println!("  Recompressed: {}", report.recompressed);
+        println!(
+            "  Stored size: {} -> {} bytes",
+            report.stored_bytes_before, report.stored_bytes_after
+        );
+        println!("  Failed: {}", report.failed.len());
+        for hash in &report.failed {
+            println!("    - {}", hash);
+        }
+        failed += report.failed.len();
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} stored documents could not be recompressed", failed);
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,383 @@
+//! Content-addressed store of filing and taxonomy documents
+//!
+//! Documents are stored once per distinct content in `document_blobs`, keyed
+//! by the SHA-256 of the uncompressed bytes, so the taxonomy schemas that
+//! thousands of filings reference take the space of one copy. Rows point at a
+//! blob by hash and the database counts the references. Contents are checked
+//! against their hash whenever they are read, and [`BlobStore::verify`] goes
+//! through the stored blobs to find corruption before anything reads them.
+//!
+//! Filings and taxonomy files are compressed with zstd at levels set by
+//! `SEC_FILING_ZSTD_LEVEL` and `SEC_TAXONOMY_ZSTD_LEVEL`. Each blob records the
+//! level it was encoded at, so [`BlobStore::recompress`] can bring existing
+//! blobs to a new level.
+
+use anyhow::{Context, Result};
+use diesel_async::AsyncPgConnection;
+use sha2::{Digest, Sha256};
+use zstd::stream::{decode_all, encode_all};
+
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::enums::CompressionType;
+use econ_graph_core::models::{
+    DocumentBlob, DocumentBlobStatistics, DocumentKind, NewDocumentBlob,
+};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Zstandard level used when nothing else is configured
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Environment variable with the zstd level of filings, or "none"
+pub const FILING_COMPRESSION_LEVEL_VAR: &str = "SEC_FILING_ZSTD_LEVEL";
+
+/// Environment variable with the zstd level of taxonomy files, or "none"
+pub const TAXONOMY_COMPRESSION_LEVEL_VAR: &str = "SEC_TAXONOMY_ZSTD_LEVEL";
+
+/// Levels accepted in configuration; higher compresses better but slower
+const COMPRESSION_LEVELS: std::ops::RangeInclusive<i32> = 1..=22;
+
+/// Blobs checked per verification run by default
+pub const DEFAULT_VERIFICATION_LIMIT: i64 = 1000;
+
+/// Blobs recompressed per document kind and run by default
+pub const DEFAULT_RECOMPRESSION_LIMIT: i64 = 1000;
+
+/// Blobs loaded at once while recompressing
+const RECOMPRESSION_PAGE_SIZE: i64 = 100;
+
+/// Documents smaller than this are stored uncompressed
+const MIN_COMPRESSED_SIZE: usize = 512;
+
+/// Lowercase hex SHA-256 of a document, the key of its blob
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Zstandard level configured for a kind of document
+///
+/// Unset means [`DEFAULT_COMPRESSION_LEVEL`]; "none" stores documents uncompressed.
+pub fn compression_level_from_env(kind: DocumentKind) -> Result<Option<i32>> {
+    let var = match kind {
+        DocumentKind::Filing => FILING_COMPRESSION_LEVEL_VAR,
+        DocumentKind::Taxonomy => TAXONOMY_COMPRESSION_LEVEL_VAR,
+    };
+    match std::env::var(var) {
+        Ok(value) => parse_compression_level(&value).with_context(|| format!("Invalid {}", var)),
+        Err(_) => Ok(Some(DEFAULT_COMPRESSION_LEVEL)),
+    }
+}
+
+/// Zstandard level from configuration: 1 to 22, or "none" for no compression
+pub fn parse_compression_level(value: &str) -> Result<Option<i32>> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+
+    let level: i32 = value
+        .parse()
+        .with_context(|| format!("Compression level is not a number: {}", value))?;
+    if !COMPRESSION_LEVELS.contains(&level) {
+        return Err(anyhow::anyhow!(
+            "Compression level {} is outside {} to {}",
+            level,
+            COMPRESSION_LEVELS.start(),
+            COMPRESSION_LEVELS.end()
+        ));
+    }
+
+    Ok(Some(level))
+}
+
+/// Blobs checked by one verification run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlobVerificationReport {
+    pub checked: usize,
+    /// Hashes of blobs whose content no longer matches
+    pub corrupted: Vec<String>,
+}
+
+/// Blobs rewritten by one recompression run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecompressionReport {
+    pub checked: usize,
+    pub recompressed: usize,
+    /// Stored size of the recompressed blobs before and after
+    pub stored_bytes_before: i64,
+    pub stored_bytes_after: i64,
+    /// Hashes of blobs that could not be recompressed
+    pub failed: Vec<String>,
+}
+
+/// Store of one kind of document; blobs are shared with the other kinds
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    pool: DatabasePool,
+    kind: DocumentKind,
+    /// Zstandard level, or `None` to store documents uncompressed
+    compression_level: Option<i32>,
+}
+
+impl BlobStore {
+    pub fn new(pool: DatabasePool, kind: DocumentKind, compression_level: Option<i32>) -> Self {
+        Self {
+            pool,
+            kind,
+            compression_level,
+        }
+    }
+
+    /// Blob of a document, compressed at the store's level when that makes it smaller
+    pub fn encode(&self, content: &[u8]) -> Result<NewDocumentBlob> {
+        let blob = encode(content, self.compression_level)?;
+        CRAWLER_METRICS.record_document_stored(
+            self.kind.as_str(),
+            blob.size_bytes as u64,
+            blob.stored_size_bytes as u64,
+        );
+        Ok(blob)
+    }
+
+    /// Store a blob unless one with its hash is stored already
+    ///
+    /// Call within the transaction that inserts the row referencing the blob.
+    pub async fn put(&self, conn: &mut AsyncPgConnection, blob: &NewDocumentBlob) -> Result<()> {
+        DocumentBlob::put(conn, blob)
+            .await
+            .context("Failed to store document blob")
+    }
+
+    /// Content of a document, checked against its hash
+    pub async fn get(&self, hash: &str) -> Result<Vec<u8>> {
+        let blob = DocumentBlob::find(&self.pool, hash)
+            .await
+            .context("Failed to load document blob")?
+            .ok_or_else(|| anyhow::anyhow!("Document blob not found: {}", hash))?;
+
+        decode(&blob)
+    }
+
+    /// Check the `limit` blobs verified longest ago against their hashes
+    pub async fn verify(&self, limit: i64) -> Result<BlobVerificationReport> {
+        let blobs = DocumentBlob::least_recently_verified(&self.pool, limit)
+            .await
+            .context("Failed to load document blobs")?;
+
+        let mut report = BlobVerificationReport::default();
+        for blob in blobs {
+            report.checked += 1;
+            match decode(&blob) {
+                Ok(_) => DocumentBlob::mark_verified(&self.pool, &blob.content_hash)
+                    .await
+                    .context("Failed to record blob verification")?,
+                Err(e) => {
+                    tracing::error!("Document blob {} is corrupted: {}", blob.content_hash, e);
+                    report.corrupted.push(blob.content_hash);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Re-encode up to `limit` blobs of the store's kind that were encoded at
+    /// another level
+    ///
+    /// Contents are checked against their hash before being rewritten; blobs
+    /// that fail are reported and left as they are.
+    pub async fn recompress(&self, limit: i64) -> Result<RecompressionReport> {
+        let target_level = self.compression_level.map(|level| level as i16);
+        let mut report = RecompressionReport::default();
+        let mut after: Option<String> = None;
+
+        while (report.checked as i64) < limit {
+            let page_size = RECOMPRESSION_PAGE_SIZE.min(limit - report.checked as i64);
+            let blobs = DocumentBlob::encoded_at_other_level(
+                &self.pool,
+                self.kind,
+                target_level,
+                after.as_deref(),
+                page_size,
+            )
+            .await
+            .context("Failed to load document blobs to recompress")?;
+            let Some(last) = blobs.last() else {
+                break;
+            };
+            after = Some(last.content_hash.clone());
+
+            for blob in blobs {
+                report.checked += 1;
+                let recompressed = match decode(&blob)
+                    .and_then(|content| encode(&content, self.compression_level))
+                {
+                    Ok(recompressed) => recompressed,
+                    Err(e) => {
+                        tracing::error!(
+                            "Document blob {} cannot be recompressed: {}",
+                            blob.content_hash,
+                            e
+                        );
+                        report.failed.push(blob.content_hash);
+                        continue;
+                    }
+                };
+
+                if let Err(e) = DocumentBlob::replace_content(&self.pool, &recompressed).await {
+                    tracing::warn!(
+                        "Failed to rewrite document blob {}: {}",
+                        blob.content_hash,
+                        e
+                    );
+                    report.failed.push(blob.content_hash);
+                    continue;
+                }
+                report.recompressed += 1;
+                report.stored_bytes_before += blob.stored_size_bytes;
+                report.stored_bytes_after += recompressed.stored_size_bytes;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Space used by stored documents, before and after deduplication
+    pub async fn statistics(&self) -> Result<DocumentBlobStatistics> {
+        DocumentBlob::statistics(&self.pool)
+            .await
+            .context("Failed to compute document blob statistics")
+    }
+
+    /// Delete blobs no filing or taxonomy references any more
+    pub async fn collect_garbage(&self) -> Result<usize> {
+        DocumentBlob::collect_garbage(&self.pool)
+            .await
+            .context("Failed to delete unreferenced document blobs")
+    }
+}
+
+/// Blob of a document, compressed with zstd at `compression_level` when that
+/// makes it smaller
+///
+/// The level is recorded either way, so the document is not tried again at it.
+pub fn encode(content: &[u8], compression_level: Option<i32>) -> Result<NewDocumentBlob> {
+    let compressed = match compression_level {
+        Some(level) if content.len() >= MIN_COMPRESSED_SIZE => {
+            Some(encode_all(content, level).context("Failed to compress document")?)
+        }
+        _ => None,
+    };
+    let (stored, compression_type) = match compressed {
+        Some(compressed) if compressed.len() < content.len() => (compressed, CompressionType::Zstd),
+        _ => (content.to_vec(), CompressionType::None),
+    };
+
+    Ok(NewDocumentBlob {
+        content_hash: content_hash(content),
+        size_bytes: content.len() as i64,
+        stored_size_bytes: stored.len() as i64,
+        content: stored,
+        compression_type,
+        compression_level: compression_level.map(|level| level as i16),
+    })
+}
+
+/// Uncompressed content of a blob, failing when it does not match the hash
+pub fn decode(blob: &DocumentBlob) -> Result<Vec<u8>> {
+    let content = match blob.compression_type {
+        CompressionType::Zstd => {
+            decode_all(&blob.content[..]).context("Failed to decompress document blob")?
+        }
+        CompressionType::None => blob.content.clone(),
+        other => {
+            return Err(anyhow::anyhow!(
+                "Unsupported blob compression: {}",
+                other.as_str()
+            ))
+        }
+    };
+
+    let actual = content_hash(&content);
+    if actual != blob.content_hash {
+        return Err(anyhow::anyhow!(
+            "Document blob {} has content hashing to {}",
+            blob.content_hash,
+            actual
+        ));
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn stored(blob: NewDocumentBlob) -> DocumentBlob {
+        DocumentBlob {
+            content_hash: blob.content_hash,
+            content: blob.content,
+            size_bytes: blob.size_bytes,
+            stored_size_bytes: blob.stored_size_bytes,
+            compression_type: blob.compression_type,
+            compression_level: blob.compression_level,
+            reference_count: 1,
+            created_at: Utc::now(),
+            verified_at: None,
+        }
+    }
+
+    #[test]
+    fn test_blobs_round_trip_and_detect_corruption() {
+        // REQUIREMENT: SEC documents are stored once by content hash and can be verified
+        // PURPOSE: Verify documents are keyed by the hash of their uncompressed content, compressed when worthwhile, and rejected when altered
+        // This ensures deduplication works across compression settings and corrupted documents are never parsed
+
+        let schema = "<xs:element name=\"Assets\"/>\n".repeat(200).into_bytes();
+        let compressed = encode(&schema, Some(3)).unwrap();
+        let uncompressed = encode(&schema, None).unwrap();
+        assert_eq!(compressed.content_hash, uncompressed.content_hash);
+        assert_eq!(compressed.content_hash, content_hash(&schema));
+        assert_eq!(compressed.compression_type, CompressionType::Zstd);
+        assert!(compressed.stored_size_bytes < compressed.size_bytes);
+        assert_eq!(uncompressed.compression_type, CompressionType::None);
+        assert_eq!(decode(&stored(compressed)).unwrap(), schema);
+
+        // Small documents are not worth compressing
+        assert_eq!(
+            encode(b"<xbrl/>", Some(3)).unwrap().compression_type,
+            CompressionType::None
+        );
+
+        let mut altered = stored(uncompressed);
+        altered.content[0] = b'!';
+        assert!(decode(&altered).is_err());
+    }
+
+    #[test]
+    fn test_compression_levels() {
+        // REQUIREMENT: Filings and taxonomy files are compressed at configurable zstd levels
+        // PURPOSE: Verify levels are parsed from configuration, recorded on blobs, and that re-encoding keeps the content and hash
+        // This ensures a level change can be applied to stored documents without breaking their references
+
+        assert_eq!(parse_compression_level("19").unwrap(), Some(19));
+        assert_eq!(parse_compression_level(" none ").unwrap(), None);
+        assert!(parse_compression_level("0").is_err());
+        assert!(parse_compression_level("23").is_err());
+        assert!(parse_compression_level("fast").is_err());
+
+        let filing = "<us-gaap:Assets contextRef=\"FY2024\">1000</us-gaap:Assets>\n"
+            .repeat(500)
+            .into_bytes();
+        let fast = encode(&filing, Some(1)).unwrap();
+        assert_eq!(fast.compression_level, Some(1));
+        assert_eq!(encode(&filing, None).unwrap().compression_level, None);
+
+        let recompressed = encode(&decode(&stored(fast.clone())).unwrap(), Some(19)).unwrap();
+        assert_eq!(recompressed.content_hash, fast.content_hash);
+        assert_eq!(recompressed.compression_level, Some(19));
+        assert!(recompressed.stored_size_bytes <= fast.stored_size_bytes);
+        assert_eq!(decode(&stored(recompressed)).unwrap(), filing);
+    }
+}
@@ -0,0 +1,469 @@
+//! **Calculation Linkbase Validation**
+//!
+//! Parses XBRL calculation linkbases and checks reported facts against their
+//! summation-item relationships, e.g. `Assets = AssetsCurrent + AssetsNoncurrent`.
+//! Filers are expected to keep these consistent; a mismatch usually points to a
+//! tagging error in the filing rather than in our parser.
+
+use anyhow::Result;
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, Zero};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use crate::xbrl_parser::{ValidationReport, XbrlFact};
+
+/// One summation-item arc: `parent` includes `weight * child`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalculationArc {
+    pub parent: String,
+    pub child: String,
+    pub weight: BigDecimal,
+    pub order: Option<f64>,
+}
+
+/// Calculation relationships of one extended link role (usually one statement)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalculationNetwork {
+    pub role: String,
+    pub arcs: Vec<CalculationArc>,
+}
+
+/// Parsed calculation linkbase
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalculationLinkbase {
+    pub networks: Vec<CalculationNetwork>,
+}
+
+/// A total whose reported value does not match the weighted sum of its children
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalculationDiscrepancy {
+    pub link_role: String,
+    pub parent_concept: String,
+    /// Children that were reported in the same context and unit
+    pub child_concepts: Vec<String>,
+    pub context_ref: String,
+    pub unit_ref: Option<String>,
+    pub reported_value: BigDecimal,
+    pub calculated_value: BigDecimal,
+    pub tolerance: BigDecimal,
+}
+
+impl CalculationDiscrepancy {
+    /// Reported minus calculated value
+    pub fn difference(&self) -> BigDecimal {
+        &self.reported_value - &self.calculated_value
+    }
+}
+
+/// Outcome of checking an instance against its calculation linkbases
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalculationValidation {
+    /// Totals that had a reported value and at least one reported child
+    pub checks_performed: usize,
+    pub discrepancies: Vec<CalculationDiscrepancy>,
+}
+
+impl CalculationValidation {
+    pub fn is_consistent(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// Summarize the discrepancies as validation errors
+    pub fn to_validation_report(&self) -> ValidationReport {
+        let errors = self
+            .discrepancies
+            .iter()
+            .map(|d| {
+                format!(
+                    "Calculation inconsistency for {} in context {}: reported {}, calculated {} ({})",
+                    d.parent_concept, d.context_ref, d.reported_value, d.calculated_value, d.link_role
+                )
+            })
+            .collect::<Vec<_>>();
+
+        ValidationReport {
+            is_valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl CalculationLinkbase {
+    /// Parse a calculation linkbase document
+    ///
+    /// Locator labels are resolved to concept names taken from the `href`
+    /// fragment, so `us-gaap-2024.xsd#us-gaap_Assets` becomes `us-gaap:Assets`.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut networks = Vec::new();
+        let mut current: Option<CalculationNetwork> = None;
+        let mut locators: HashMap<String, String> = HashMap::new();
+        // Arcs refer to locator labels, which are only resolved once the link is complete
+        let mut pending_arcs: Vec<(String, String, BigDecimal, Option<f64>)> = Vec::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"calculationLink" => {
+                    current = Some(CalculationNetwork {
+                        role: attribute(e, b"role").unwrap_or_default(),
+                        arcs: Vec::new(),
+                    });
+                    locators.clear();
+                    pending_arcs.clear();
+                }
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) if current.is_some() => {
+                    match e.local_name().as_ref() {
+                        b"loc" => {
+                            if let (Some(label), Some(href)) =
+                                (attribute(e, b"label"), attribute(e, b"href"))
+                            {
+                                locators.insert(label, concept_from_href(&href));
+                            }
+                        }
+                        b"calculationArc" => {
+                            if let (Some(from), Some(to)) =
+                                (attribute(e, b"from"), attribute(e, b"to"))
+                            {
+                                let weight = attribute(e, b"weight")
+                                    .and_then(|w| BigDecimal::from_str(&w).ok())
+                                    .unwrap_or_else(|| BigDecimal::from(1));
+                                let order = attribute(e, b"order").and_then(|o| o.parse().ok());
+                                pending_arcs.push((from, to, weight, order));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == b"calculationLink" => {
+                    if let Some(mut network) = current.take() {
+                        for (from, to, weight, order) in pending_arcs.drain(..) {
+                            if let (Some(parent), Some(child)) =
+                                (locators.get(&from), locators.get(&to))
+                            {
+                                network.arcs.push(CalculationArc {
+                                    parent: parent.clone(),
+                                    child: child.clone(),
+                                    weight,
+                                    order,
+                                });
+                            }
+                        }
+                        if !network.arcs.is_empty() {
+                            networks.push(network);
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(anyhow::anyhow!(
+                        "Failed to parse calculation linkbase: {}",
+                        e
+                    ))
+                }
+            }
+        }
+
+        Ok(Self { networks })
+    }
+
+    /// Merge the networks of several linkbases, e.g. company and standard taxonomy files
+    pub fn merge(linkbases: impl IntoIterator<Item = CalculationLinkbase>) -> Self {
+        Self {
+            networks: linkbases
+                .into_iter()
+                .flat_map(|linkbase| linkbase.networks)
+                .collect(),
+        }
+    }
+
+    /// Check every summation in the linkbase against the reported facts
+    ///
+    /// A total is compared only in contexts where it and at least one child are
+    /// reported with the same unit. The tolerance follows the least precise
+    /// `decimals` of the facts involved, so values rounded to millions are
+    /// allowed to be off by half a million.
+    pub fn validate(&self, facts: &[XbrlFact]) -> CalculationValidation {
+        let values = NumericFacts::from_facts(facts);
+        let mut validation = CalculationValidation::default();
+
+        for network in &self.networks {
+            let mut children: BTreeMap<&str, Vec<&CalculationArc>> = BTreeMap::new();
+            for arc in &network.arcs {
+                children.entry(arc.parent.as_str()).or_default().push(arc);
+            }
+
+            for (parent, arcs) in children {
+                for (key, reported) in values.for_concept(parent) {
+                    let mut calculated = BigDecimal::zero();
+                    let mut contributing = Vec::new();
+                    let mut decimals = reported.decimals;
+
+                    for arc in &arcs {
+                        if let Some(child) = values.get(&arc.child, key) {
+                            calculated += &child.value * &arc.weight;
+                            contributing.push(arc.child.clone());
+                            decimals = min_decimals(decimals, child.decimals);
+                        }
+                    }
+
+                    if contributing.is_empty() {
+                        continue;
+                    }
+                    validation.checks_performed += 1;
+
+                    let tolerance = tolerance_for(decimals);
+                    if (&reported.value - &calculated).abs() > tolerance {
+                        validation.discrepancies.push(CalculationDiscrepancy {
+                            link_role: network.role.clone(),
+                            parent_concept: parent.to_string(),
+                            child_concepts: contributing,
+                            context_ref: key.0.clone(),
+                            unit_ref: key.1.clone(),
+                            reported_value: reported.value.clone(),
+                            calculated_value: calculated,
+                            tolerance,
+                        });
+                    }
+                }
+            }
+        }
+
+        validation
+    }
+}
+
+/// Context and unit a numeric fact was reported in
+type FactKey = (String, Option<String>);
+
+struct NumericValue {
+    value: BigDecimal,
+    /// None means exact (`decimals="INF"` or no decimals attribute)
+    decimals: Option<i32>,
+}
+
+/// Numeric facts indexed by local concept name, then context and unit
+struct NumericFacts {
+    by_concept: HashMap<String, BTreeMap<FactKey, NumericValue>>,
+}
+
+impl NumericFacts {
+    fn from_facts(facts: &[XbrlFact]) -> Self {
+        let mut by_concept: HashMap<String, BTreeMap<FactKey, NumericValue>> = HashMap::new();
+
+        for fact in facts {
+            let value = match fact
+                .value
+                .as_deref()
+                .and_then(|v| BigDecimal::from_str(&v.replace(',', "")).ok())
+            {
+                Some(value) => value,
+                None => continue,
+            };
+
+            // Duplicate facts keep the first value; they are a separate consistency issue
+            by_concept
+                .entry(local_name(&fact.concept).to_string())
+                .or_default()
+                .entry((fact.context_ref.clone(), fact.unit_ref.clone()))
+                .or_insert(NumericValue {
+                    value,
+                    decimals: fact.decimals,
+                });
+        }
+
+        Self { by_concept }
+    }
+
+    fn for_concept<'a>(
+        &'a self,
+        concept: &str,
+    ) -> impl Iterator<Item = (&'a FactKey, &'a NumericValue)> + 'a {
+        self.by_concept
+            .get(local_name(concept))
+            .into_iter()
+            .flat_map(|facts| facts.iter())
+    }
+
+    fn get(&self, concept: &str, key: &FactKey) -> Option<&NumericValue> {
+        self.by_concept.get(local_name(concept))?.get(key)
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+}
+
+/// Concept name from a locator href such as `us-gaap-2024.xsd#us-gaap_Assets`
+fn concept_from_href(href: &str) -> String {
+    let fragment = href.rsplit('#').next().unwrap_or(href);
+    match fragment.split_once('_') {
+        Some((prefix, name)) => format!("{}:{}", prefix, name),
+        None => fragment.to_string(),
+    }
+}
+
+/// Concept name without its namespace prefix
+///
+/// Facts and locators are matched on local names, since instance prefixes are
+/// chosen by the filer and need not match the taxonomy's.
+fn local_name(concept: &str) -> &str {
+    concept.rsplit(':').next().unwrap_or(concept)
+}
+
+fn min_decimals(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Half a unit in the last reported digit, e.g. 500000 for `decimals="-6"`
+fn tolerance_for(decimals: Option<i32>) -> BigDecimal {
+    match decimals {
+        Some(decimals) => BigDecimal::new(BigInt::from(5), i64::from(decimals) + 1),
+        None => BigDecimal::zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BALANCE_SHEET_CAL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<link:linkbase xmlns:link="http://www.xbrl.org/2003/linkbase" xmlns:xlink="http://www.w3.org/1999/xlink">
+  <link:calculationLink xlink:type="extended" xlink:role="http://example.com/role/BalanceSheet">
+    <link:loc xlink:type="locator" xlink:href="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd#us-gaap_Assets" xlink:label="loc_Assets"/>
+    <link:loc xlink:type="locator" xlink:href="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd#us-gaap_AssetsCurrent" xlink:label="loc_AssetsCurrent"/>
+    <link:loc xlink:type="locator" xlink:href="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd#us-gaap_AssetsNoncurrent" xlink:label="loc_AssetsNoncurrent"/>
+    <link:calculationArc xlink:type="arc" xlink:arcrole="http://www.xbrl.org/2003/arcrole/summation-item" xlink:from="loc_Assets" xlink:to="loc_AssetsCurrent" order="1" weight="1.0"/>
+    <link:calculationArc xlink:type="arc" xlink:arcrole="http://www.xbrl.org/2003/arcrole/summation-item" xlink:from="loc_Assets" xlink:to="loc_AssetsNoncurrent" order="2" weight="1.0"/>
+  </link:calculationLink>
+  <link:calculationLink xlink:type="extended" xlink:role="http://example.com/role/IncomeStatement">
+    <link:loc xlink:type="locator" xlink:href="aapl-20240928.xsd#us-gaap_GrossProfit" xlink:label="loc_GrossProfit"/>
+    <link:loc xlink:type="locator" xlink:href="aapl-20240928.xsd#us-gaap_Revenues" xlink:label="loc_Revenues"/>
+    <link:loc xlink:type="locator" xlink:href="aapl-20240928.xsd#us-gaap_CostOfRevenue" xlink:label="loc_CostOfRevenue"/>
+    <link:calculationArc xlink:type="arc" xlink:arcrole="http://www.xbrl.org/2003/arcrole/summation-item" xlink:from="loc_GrossProfit" xlink:to="loc_Revenues" order="1" weight="1.0"/>
+    <link:calculationArc xlink:type="arc" xlink:arcrole="http://www.xbrl.org/2003/arcrole/summation-item" xlink:from="loc_GrossProfit" xlink:to="loc_CostOfRevenue" order="2" weight="-1.0"/>
+  </link:calculationLink>
+</link:linkbase>"#;
+
+    fn fact(concept: &str, context: &str, value: &str, decimals: Option<i32>) -> XbrlFact {
+        XbrlFact {
+            concept: concept.to_string(),
+            namespace: None,
+            local_name: None,
+            value: Some(value.to_string()),
+            context_ref: context.to_string(),
+            unit_ref: Some("usd".to_string()),
+            decimals,
+            precision: None,
+            fact_type: None,
+            scale: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_calculation_networks() {
+        let linkbase = CalculationLinkbase::parse(BALANCE_SHEET_CAL).unwrap();
+
+        assert_eq!(linkbase.networks.len(), 2);
+        let balance_sheet = &linkbase.networks[0];
+        assert_eq!(balance_sheet.role, "http://example.com/role/BalanceSheet");
+        assert_eq!(balance_sheet.arcs.len(), 2);
+        assert_eq!(balance_sheet.arcs[0].parent, "us-gaap:Assets");
+        assert_eq!(balance_sheet.arcs[0].child, "us-gaap:AssetsCurrent");
+        assert_eq!(
+            linkbase.networks[1].arcs[1].weight,
+            BigDecimal::from_str("-1.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_consistent_facts_pass_within_rounding_tolerance() {
+        let linkbase = CalculationLinkbase::parse(BALANCE_SHEET_CAL).unwrap();
+        let facts = vec![
+            fact("us-gaap:Assets", "FY2024", "364980000000", Some(-6)),
+            fact("us-gaap:AssetsCurrent", "FY2024", "152987000000", Some(-6)),
+            // Off by less than half a million after rounding
+            fact(
+                "us-gaap:AssetsNoncurrent",
+                "FY2024",
+                "211993400000",
+                Some(-6),
+            ),
+            fact("us-gaap:GrossProfit", "FY2024", "180683000000", Some(-6)),
+            fact("us-gaap:Revenues", "FY2024", "391035000000", Some(-6)),
+            fact("us-gaap:CostOfRevenue", "FY2024", "210352000000", Some(-6)),
+        ];
+
+        let validation = linkbase.validate(&facts);
+
+        assert_eq!(validation.checks_performed, 2);
+        assert!(validation.is_consistent());
+        assert!(validation.to_validation_report().is_valid);
+    }
+
+    #[test]
+    fn test_inconsistent_total_is_reported_per_context() {
+        let linkbase = CalculationLinkbase::parse(BALANCE_SHEET_CAL).unwrap();
+        let facts = vec![
+            fact("us-gaap:Assets", "FY2024", "364980000000", Some(-6)),
+            fact("us-gaap:AssetsCurrent", "FY2024", "152987000000", Some(-6)),
+            fact(
+                "us-gaap:AssetsNoncurrent",
+                "FY2024",
+                "201993000000",
+                Some(-6),
+            ),
+            fact("us-gaap:Assets", "FY2023", "352583000000", Some(-6)),
+            fact("us-gaap:AssetsCurrent", "FY2023", "143566000000", Some(-6)),
+            fact(
+                "us-gaap:AssetsNoncurrent",
+                "FY2023",
+                "209017000000",
+                Some(-6),
+            ),
+        ];
+
+        let validation = linkbase.validate(&facts);
+
+        assert_eq!(validation.checks_performed, 2);
+        assert_eq!(validation.discrepancies.len(), 1);
+        let discrepancy = &validation.discrepancies[0];
+        assert_eq!(discrepancy.parent_concept, "us-gaap:Assets");
+        assert_eq!(discrepancy.context_ref, "FY2024");
+        assert_eq!(
+            discrepancy.difference(),
+            BigDecimal::from_str("10000000000").unwrap()
+        );
+        assert_eq!(discrepancy.tolerance, BigDecimal::from(500000));
+        assert!(!validation.to_validation_report().is_valid);
+    }
+
+    #[test]
+    fn test_totals_without_reported_children_are_skipped() {
+        let linkbase = CalculationLinkbase::parse(BALANCE_SHEET_CAL).unwrap();
+        let facts = vec![
+            fact("us-gaap:Assets", "FY2024", "364980000000", Some(-6)),
+            fact("us-gaap:AssetsCurrent", "FY2023", "143566000000", Some(-6)),
+        ];
+
+        let validation = linkbase.validate(&facts);
+
+        assert_eq!(validation.checks_performed, 0);
+        assert!(validation.is_consistent());
+    }
+}
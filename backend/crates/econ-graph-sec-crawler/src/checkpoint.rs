@@ -0,0 +1,404 @@
+//! Crash-safe checkpoints for XBRL batch processing
+//!
+//! Parsing thousands of stored filings takes hours, so a batch records its
+//! progress as it goes:
+//!
+//! - each filing's `xbrl_processing_status` moves from `pending` (or
+//!   `downloaded`) to `processing` when its parse starts, and to `completed`
+//!   or `failed` when it ends;
+//! - the batch's cursor in `xbrl_batch_checkpoints` advances past every filing
+//!   that reached a final status.
+//!
+//! A restarted batch resumes after its cursor. The filing that was in flight
+//! when the job stopped is still `processing` and is parsed again; facts are
+//! upserted (see [`XbrlStorage::store_line_items`](crate::storage::XbrlStorage::store_line_items)),
+//! so this does not duplicate line items. Filings are walked in statement id
+//! order; filings stored behind the cursor while a batch runs are picked up by
+//! its next pass.
+//!
+//! When the process starts draining for shutdown the batch stops after the
+//! filing in flight, leaving its cursor on the last finished filing.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::crawler::SecEdgarCrawler;
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::enums::ProcessingStatus;
+use econ_graph_core::schema::{financial_statements, xbrl_batch_checkpoints};
+use econ_graph_core::shutdown::shutdown_coordinator;
+
+/// Batch name used when none is given
+pub const DEFAULT_BATCH_NAME: &str = "xbrl-facts";
+
+/// Filings loaded per page of a batch
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+
+/// Statuses a filing may move to `status` from
+///
+/// `processing` may be entered again so a filing interrupted by a crash is
+/// resumed; `failed` filings are left alone until reset.
+pub fn allowed_transitions_to(status: ProcessingStatus) -> &'static [ProcessingStatus] {
+    match status {
+        ProcessingStatus::Processing => &[
+            ProcessingStatus::Pending,
+            ProcessingStatus::Downloaded,
+            ProcessingStatus::Processing,
+        ],
+        ProcessingStatus::Completed | ProcessingStatus::Failed => &[ProcessingStatus::Processing],
+        ProcessingStatus::Pending | ProcessingStatus::Downloaded => &[],
+    }
+}
+
+/// Progress of a named XBRL batch
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Serialize)]
+#[diesel(table_name = xbrl_batch_checkpoints)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct XbrlBatchCheckpoint {
+    pub batch_name: String,
+    /// Last filing that reached a final status; the batch resumes after it
+    pub last_statement_id: Option<Uuid>,
+    pub filings_completed: i32,
+    pub filings_failed: i32,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set when the pass ran out of filings; the next run starts a new pass
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Filing waiting to be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Queryable)]
+pub struct PendingFiling {
+    pub statement_id: Uuid,
+    pub accession_number: String,
+}
+
+/// How processing a filing ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilingOutcome {
+    Completed,
+    Failed,
+    /// Another worker changed the filing's status first
+    Skipped,
+}
+
+/// Outcome of an XBRL batch run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct XbrlBatchReport {
+    pub batch_name: String,
+    /// Cursor the run resumed after, if it continued an interrupted pass
+    pub resumed_after: Option<Uuid>,
+    pub filings_completed: usize,
+    pub filings_failed: usize,
+    pub filings_skipped: usize,
+    /// Line items stored across completed filings
+    pub line_items_stored: usize,
+    pub errors: Vec<String>,
+    /// Whether the run stopped for shutdown before the pass finished
+    pub interrupted: bool,
+}
+
+/// Checkpoint store for XBRL batches and the processing status of filings
+#[derive(Clone)]
+pub struct XbrlCheckpointStore {
+    pool: DatabasePool,
+}
+
+impl XbrlCheckpointStore {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Load a batch's checkpoint, creating it or starting a new pass when the
+    /// previous one finished
+    pub async fn resume(&self, batch_name: &str) -> Result<XbrlBatchCheckpoint> {
+        let mut conn = self.pool.get().await?;
+
+        let existing = xbrl_batch_checkpoints::table
+            .find(batch_name)
+            .select(XbrlBatchCheckpoint::as_select())
+            .first(&mut conn)
+            .await
+            .optional()
+            .context("Failed to load batch checkpoint")?;
+
+        let checkpoint = match existing {
+            Some(checkpoint) if checkpoint.finished_at.is_none() => checkpoint,
+            Some(_) => diesel::update(xbrl_batch_checkpoints::table.find(batch_name))
+                .set((
+                    xbrl_batch_checkpoints::last_statement_id.eq(None::<Uuid>),
+                    xbrl_batch_checkpoints::filings_completed.eq(0),
+                    xbrl_batch_checkpoints::filings_failed.eq(0),
+                    xbrl_batch_checkpoints::started_at.eq(Utc::now()),
+                    xbrl_batch_checkpoints::updated_at.eq(Utc::now()),
+                    xbrl_batch_checkpoints::finished_at.eq(None::<DateTime<Utc>>),
+                ))
+                .returning(XbrlBatchCheckpoint::as_returning())
+                .get_result(&mut conn)
+                .await
+                .context("Failed to restart batch checkpoint")?,
+            None => diesel::insert_into(xbrl_batch_checkpoints::table)
+                .values(xbrl_batch_checkpoints::batch_name.eq(batch_name))
+                .returning(XbrlBatchCheckpoint::as_returning())
+                .get_result(&mut conn)
+                .await
+                .context("Failed to create batch checkpoint")?,
+        };
+
+        Ok(checkpoint)
+    }
+
+    /// Next filings after the batch's cursor that still need parsing
+    pub async fn next_filings(
+        &self,
+        checkpoint: &XbrlBatchCheckpoint,
+        limit: i64,
+    ) -> Result<Vec<PendingFiling>> {
+        let mut conn = self.pool.get().await?;
+
+        let mut query = financial_statements::table
+            .filter(
+                financial_statements::xbrl_processing_status
+                    .eq_any(allowed_transitions_to(ProcessingStatus::Processing).to_vec()),
+            )
+            .select((
+                financial_statements::id,
+                financial_statements::accession_number,
+            ))
+            .order(financial_statements::id.asc())
+            .limit(limit)
+            .into_boxed();
+        if let Some(cursor) = checkpoint.last_statement_id {
+            query = query.filter(financial_statements::id.gt(cursor));
+        }
+
+        query
+            .load::<PendingFiling>(&mut conn)
+            .await
+            .context("Failed to load filings to process")
+    }
+
+    /// Move a filing to `processing`
+    ///
+    /// Returns false when the filing is no longer waiting to be processed.
+    pub async fn mark_processing(&self, statement_id: Uuid) -> Result<bool> {
+        let mut conn = self.pool.get().await?;
+
+        let updated = diesel::update(
+            financial_statements::table
+                .filter(financial_statements::id.eq(statement_id))
+                .filter(
+                    financial_statements::xbrl_processing_status
+                        .eq_any(allowed_transitions_to(ProcessingStatus::Processing).to_vec()),
+                ),
+        )
+        .set((
+            financial_statements::xbrl_processing_status.eq(ProcessingStatus::Processing),
+            financial_statements::xbrl_processing_error.eq(None::<String>),
+            financial_statements::xbrl_processing_started_at.eq(Some(Utc::now())),
+            financial_statements::xbrl_processing_completed_at.eq(None::<DateTime<Utc>>),
+        ))
+        .execute(&mut conn)
+        .await
+        .context("Failed to mark filing as processing")?;
+
+        Ok(updated > 0)
+    }
+
+    /// Move a processing filing to `completed`, or to `failed` with `error`
+    pub async fn mark_finished(&self, statement_id: Uuid, error: Option<&str>) -> Result<bool> {
+        let status = match error {
+            Some(_) => ProcessingStatus::Failed,
+            None => ProcessingStatus::Completed,
+        };
+        let mut conn = self.pool.get().await?;
+
+        let updated = diesel::update(
+            financial_statements::table
+                .filter(financial_statements::id.eq(statement_id))
+                .filter(
+                    financial_statements::xbrl_processing_status
+                        .eq_any(allowed_transitions_to(status).to_vec()),
+                ),
+        )
+        .set((
+            financial_statements::xbrl_processing_status.eq(status),
+            financial_statements::xbrl_processing_error.eq(error),
+            financial_statements::xbrl_processing_completed_at.eq(Some(Utc::now())),
+        ))
+        .execute(&mut conn)
+        .await
+        .context("Failed to record filing processing result")?;
+
+        Ok(updated > 0)
+    }
+
+    /// Move the batch's cursor past a filing
+    pub async fn advance(
+        &self,
+        batch_name: &str,
+        statement_id: Uuid,
+        outcome: FilingOutcome,
+    ) -> Result<XbrlBatchCheckpoint> {
+        let completed = i32::from(outcome == FilingOutcome::Completed);
+        let failed = i32::from(outcome == FilingOutcome::Failed);
+        let mut conn = self.pool.get().await?;
+
+        diesel::update(xbrl_batch_checkpoints::table.find(batch_name))
+            .set((
+                xbrl_batch_checkpoints::last_statement_id.eq(Some(statement_id)),
+                xbrl_batch_checkpoints::filings_completed
+                    .eq(xbrl_batch_checkpoints::filings_completed + completed),
+                xbrl_batch_checkpoints::filings_failed
+                    .eq(xbrl_batch_checkpoints::filings_failed + failed),
+                xbrl_batch_checkpoints::updated_at.eq(Utc::now()),
+            ))
+            .returning(XbrlBatchCheckpoint::as_returning())
+            .get_result(&mut conn)
+            .await
+            .context("Failed to advance batch checkpoint")
+    }
+
+    /// Mark the batch's pass as finished
+    pub async fn finish(&self, batch_name: &str) -> Result<XbrlBatchCheckpoint> {
+        let mut conn = self.pool.get().await?;
+
+        diesel::update(xbrl_batch_checkpoints::table.find(batch_name))
+            .set((
+                xbrl_batch_checkpoints::updated_at.eq(Utc::now()),
+                xbrl_batch_checkpoints::finished_at.eq(Some(Utc::now())),
+            ))
+            .returning(XbrlBatchCheckpoint::as_returning())
+            .get_result(&mut conn)
+            .await
+            .context("Failed to finish batch checkpoint")
+    }
+}
+
+impl SecEdgarCrawler {
+    /// Parse every stored filing that still needs it, resuming an interrupted batch
+    ///
+    /// Progress is checkpointed after each filing, so a restarted run continues
+    /// with the first filing that did not finish.
+    #[tracing::instrument(name = "sec.process_xbrl_batch", skip(self))]
+    pub async fn process_xbrl_batch(
+        &self,
+        batch_name: &str,
+        page_size: i64,
+    ) -> Result<XbrlBatchReport> {
+        let store = XbrlCheckpointStore::new(self.pool.clone());
+        let mut checkpoint = store.resume(batch_name).await?;
+
+        let mut report = XbrlBatchReport {
+            batch_name: batch_name.to_string(),
+            resumed_after: checkpoint.last_statement_id,
+            ..Default::default()
+        };
+        match checkpoint.last_statement_id {
+            Some(cursor) => info!("Resuming XBRL batch {} after filing {}", batch_name, cursor),
+            None => info!("Starting XBRL batch {}", batch_name),
+        }
+
+        let coordinator = shutdown_coordinator();
+        'pass: loop {
+            let filings = store.next_filings(&checkpoint, page_size).await?;
+            if filings.is_empty() {
+                break;
+            }
+
+            for filing in filings {
+                let Some(_in_flight) = coordinator.try_track() else {
+                    report.interrupted = true;
+                    break 'pass;
+                };
+                let outcome = if !store.mark_processing(filing.statement_id).await? {
+                    report.filings_skipped += 1;
+                    FilingOutcome::Skipped
+                } else {
+                    match self
+                        .parse_and_store_statement(filing.statement_id, &filing.accession_number)
+                        .await
+                    {
+                        Ok(stored) => {
+                            store.mark_finished(filing.statement_id, None).await?;
+                            report.filings_completed += 1;
+                            report.line_items_stored += stored;
+                            FilingOutcome::Completed
+                        }
+                        Err(e) => {
+                            let message = format!("{:#}", e);
+                            error!(
+                                "Failed to process filing {}: {}",
+                                filing.accession_number, message
+                            );
+                            store
+                                .mark_finished(filing.statement_id, Some(&message))
+                                .await?;
+                            report.filings_failed += 1;
+                            report
+                                .errors
+                                .push(format!("{}: {}", filing.accession_number, message));
+                            FilingOutcome::Failed
+                        }
+                    }
+                };
+
+                checkpoint = store
+                    .advance(batch_name, filing.statement_id, outcome)
+                    .await?;
+            }
+        }
+
+        if report.interrupted {
+            info!(
+                "XBRL batch {} stopped for shutdown after filing {:?}: {} completed, {} failed, {} skipped",
+                batch_name,
+                checkpoint.last_statement_id,
+                report.filings_completed,
+                report.filings_failed,
+                report.filings_skipped
+            );
+            return Ok(report);
+        }
+
+        store.finish(batch_name).await?;
+        info!(
+            "XBRL batch {} finished: {} completed, {} failed, {} skipped",
+            batch_name, report.filings_completed, report.filings_failed, report.filings_skipped
+        );
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processing_status_transitions() {
+        // REQUIREMENT: Crash-safe checkpointing for XBRL batch processing
+        // PURPOSE: Verify which processing status changes a filing may make
+        // This ensures interrupted filings are resumed while finished ones are never reprocessed
+
+        let to_processing = allowed_transitions_to(ProcessingStatus::Processing);
+        assert!(to_processing.contains(&ProcessingStatus::Pending));
+        assert!(to_processing.contains(&ProcessingStatus::Downloaded));
+        assert!(to_processing.contains(&ProcessingStatus::Processing));
+        assert!(!to_processing.contains(&ProcessingStatus::Completed));
+        assert!(!to_processing.contains(&ProcessingStatus::Failed));
+
+        for status in [ProcessingStatus::Completed, ProcessingStatus::Failed] {
+            assert_eq!(
+                allowed_transitions_to(status),
+                &[ProcessingStatus::Processing]
+            );
+        }
+        assert!(allowed_transitions_to(ProcessingStatus::Pending).is_empty());
+    }
+}
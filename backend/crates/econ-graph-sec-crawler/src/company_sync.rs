@@ -0,0 +1,389 @@
+//! Company list sync from SEC `company_tickers.json`
+//!
+//! SEC publishes every registrant with a traded ticker in a single file. Syncing
+//! it keeps the `companies` table complete without manual seeding: new CIKs
+//! are inserted, ticker and name changes are applied, and companies that drop
+//! off the list are marked inactive rather than deleted, since filings and
+//! ratios still reference them.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use tokio_cron_scheduler::{Job, JobScheduler};
+use tracing::{error, info};
+
+use crate::crawler::SecEdgarCrawler;
+use crate::models::CompanyTickersResponse;
+use crate::utils::{build_company_tickers_url, pad_cik};
+use econ_graph_core::schema::companies;
+use econ_graph_core::shutdown::shutdown_coordinator;
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Default sync schedule: daily at 06:00 UTC, after SEC's overnight update
+pub const DEFAULT_COMPANY_SYNC_SCHEDULE: &str = "0 0 6 * * *";
+
+/// Rows per upsert statement
+const UPSERT_BATCH_SIZE: usize = 1000;
+
+/// Company as listed in `company_tickers.json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedCompany {
+    /// CIK padded to 10 digits
+    pub cik: String,
+    pub ticker: Option<String>,
+    pub name: String,
+}
+
+/// Subset of a stored company compared during a sync
+#[derive(Debug, Clone, PartialEq, Eq, Queryable)]
+pub struct ExistingCompany {
+    pub cik: String,
+    pub ticker: Option<String>,
+    pub name: String,
+    pub is_active: bool,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = companies)]
+struct CompanyListing<'a> {
+    cik: &'a str,
+    ticker: Option<&'a str>,
+    name: &'a str,
+    is_active: bool,
+}
+
+/// Outcome of a company sync
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CompanySyncReport {
+    /// Companies in `company_tickers.json`
+    pub listed: usize,
+    /// CIKs inserted
+    pub added: Vec<String>,
+    /// CIKs whose ticker or name changed
+    pub updated: Vec<String>,
+    /// Inactive CIKs that reappeared in the list
+    pub reactivated: Vec<String>,
+    /// CIKs that dropped off the list and were marked inactive
+    pub deactivated: Vec<String>,
+    pub unchanged: usize,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Convert the ticker file into one entry per CIK
+///
+/// Companies with several share classes appear once per ticker; the first
+/// listed ticker is kept as the primary one. Values that do not fit the
+/// `companies` columns are trimmed (names) or dropped (tickers).
+pub fn parse_company_tickers(response: CompanyTickersResponse) -> Vec<ListedCompany> {
+    let mut entries: Vec<(usize, _)> = response
+        .tickers
+        .into_iter()
+        .map(|(position, ticker)| (position.parse().unwrap_or(usize::MAX), ticker))
+        .collect();
+    entries.sort_by_key(|(position, _)| *position);
+
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter_map(|(_, entry)| {
+            let cik = pad_cik(&entry.cik_str.to_string());
+            if cik.len() > 10 || !seen.insert(cik.clone()) {
+                return None;
+            }
+
+            let ticker = entry.ticker.trim().to_uppercase();
+            let name: String = entry.title.trim().chars().take(255).collect();
+            Some(ListedCompany {
+                cik,
+                ticker: (!ticker.is_empty() && ticker.len() <= 10).then_some(ticker),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Work out which listed companies need writing and which stored ones dropped off
+///
+/// Returns the companies to upsert and a report with the additions, changes
+/// and removals filled in. Only active companies with a ticker can be removed,
+/// so companies added by other means are left alone.
+pub fn plan_company_sync<'a>(
+    listed: &'a [ListedCompany],
+    existing: &[ExistingCompany],
+) -> (Vec<&'a ListedCompany>, CompanySyncReport) {
+    let stored: HashMap<&str, &ExistingCompany> =
+        existing.iter().map(|c| (c.cik.as_str(), c)).collect();
+    let listed_ciks: HashSet<&str> = listed.iter().map(|c| c.cik.as_str()).collect();
+
+    let mut report = CompanySyncReport {
+        listed: listed.len(),
+        ..Default::default()
+    };
+    let mut upserts = Vec::new();
+
+    for company in listed {
+        match stored.get(company.cik.as_str()) {
+            None => report.added.push(company.cik.clone()),
+            Some(current) if !current.is_active => report.reactivated.push(company.cik.clone()),
+            Some(current) if current.ticker != company.ticker || current.name != company.name => {
+                report.updated.push(company.cik.clone())
+            }
+            Some(_) => {
+                report.unchanged += 1;
+                continue;
+            }
+        }
+        upserts.push(company);
+    }
+
+    report.deactivated = existing
+        .iter()
+        .filter(|c| c.is_active && c.ticker.is_some() && !listed_ciks.contains(c.cik.as_str()))
+        .map(|c| c.cik.clone())
+        .collect();
+
+    (upserts, report)
+}
+
+impl SecEdgarCrawler {
+    /// Sync the `companies` table with SEC's `company_tickers.json`
+    #[tracing::instrument(name = "sec.sync_companies", skip(self))]
+    pub async fn sync_companies(&self) -> Result<CompanySyncReport> {
+        let started_at = Utc::now();
+        info!("Starting SEC company sync");
+
+        let listed = parse_company_tickers(self.fetch_company_tickers().await?);
+
+        let mut conn = self.pool.get().await?;
+        let existing: Vec<ExistingCompany> = companies::table
+            .select((
+                companies::cik,
+                companies::ticker,
+                companies::name,
+                companies::is_active,
+            ))
+            .load(&mut conn)
+            .await
+            .context("Failed to load existing companies")?;
+
+        let (upserts, mut report) = plan_company_sync(&listed, &existing);
+
+        for batch in upserts.chunks(UPSERT_BATCH_SIZE) {
+            let rows: Vec<CompanyListing> = batch
+                .iter()
+                .map(|company| CompanyListing {
+                    cik: &company.cik,
+                    ticker: company.ticker.as_deref(),
+                    name: &company.name,
+                    is_active: true,
+                })
+                .collect();
+
+            diesel::insert_into(companies::table)
+                .values(&rows)
+                .on_conflict(companies::cik)
+                .do_update()
+                .set((
+                    companies::ticker.eq(excluded(companies::ticker)),
+                    companies::name.eq(excluded(companies::name)),
+                    companies::is_active.eq(true),
+                    companies::updated_at.eq(diesel::dsl::now),
+                ))
+                .execute(&mut conn)
+                .await
+                .context("Failed to upsert companies")?;
+        }
+
+        if !report.deactivated.is_empty() {
+            diesel::update(companies::table.filter(companies::cik.eq_any(&report.deactivated)))
+                .set((
+                    companies::is_active.eq(false),
+                    companies::updated_at.eq(diesel::dsl::now),
+                ))
+                .execute(&mut conn)
+                .await
+                .context("Failed to deactivate delisted companies")?;
+        }
+
+        CRAWLER_METRICS.record_items_collected(
+            "sec",
+            "edgar",
+            "company",
+            report.added.len() as u64,
+        );
+
+        report.started_at = Some(started_at);
+        report.finished_at = Some(Utc::now());
+        info!(
+            "SEC company sync complete: {} listed, {} added, {} updated, {} reactivated, {} deactivated",
+            report.listed,
+            report.added.len(),
+            report.updated.len(),
+            report.reactivated.len(),
+            report.deactivated.len()
+        );
+
+        Ok(report)
+    }
+
+    /// Download `company_tickers.json`
+    #[tracing::instrument(name = "sec.company_tickers", skip(self), fields(otel.kind = "client"))]
+    async fn fetch_company_tickers(&self) -> Result<CompanyTickersResponse> {
+        let url = build_company_tickers_url();
+
+        self.rate_limiter.wait_for_permit().await?;
+
+        let start = std::time::Instant::now();
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to fetch company tickers")?;
+
+        let duration = start.elapsed().as_secs_f64();
+        let status = response.status();
+        CRAWLER_METRICS.record_request(
+            "sec",
+            "edgar",
+            "/files/company_tickers.json",
+            status.as_str(),
+            duration,
+        );
+        if status.as_u16() == 429 {
+            CRAWLER_METRICS.record_rate_limit_hit("sec", "edgar");
+        }
+        if !status.is_success() {
+            CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
+            return Err(anyhow::anyhow!(
+                "HTTP error fetching company tickers: {}",
+                status
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse company tickers")
+    }
+}
+
+/// Run [`SecEdgarCrawler::sync_companies`] on a cron schedule
+///
+/// `schedule` uses the six-field cron format with seconds, e.g.
+/// [`DEFAULT_COMPANY_SYNC_SCHEDULE`]. The returned scheduler is already
+/// started; keep it alive for as long as syncs should run.
+pub async fn schedule_company_sync(
+    crawler: SecEdgarCrawler,
+    schedule: &str,
+) -> Result<JobScheduler> {
+    let scheduler = JobScheduler::new().await?;
+
+    let job = Job::new_async(schedule, move |_id, _scheduler| {
+        let crawler = crawler.clone();
+        Box::pin(async move {
+            // Runs that start while draining for shutdown are skipped
+            let Some(_in_flight) = shutdown_coordinator().try_track() else {
+                return;
+            };
+            if let Err(e) = crawler.sync_companies().await {
+                error!("Scheduled SEC company sync failed: {}", e);
+            }
+        })
+    })
+    .with_context(|| format!("Invalid company sync schedule: {}", schedule))?;
+
+    scheduler.add(job).await?;
+    scheduler.start().await?;
+    info!("SEC company sync scheduled: {}", schedule);
+
+    Ok(scheduler)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(cik: &str, ticker: Option<&str>, name: &str, is_active: bool) -> ExistingCompany {
+        ExistingCompany {
+            cik: cik.to_string(),
+            ticker: ticker.map(str::to_string),
+            name: name.to_string(),
+            is_active,
+        }
+    }
+
+    #[test]
+    fn test_parse_company_tickers() {
+        // REQUIREMENT: Import companies from SEC company_tickers.json
+        // PURPOSE: Verify the keyed JSON is parsed in SEC order with one entry per padded CIK
+        // This ensures share classes like GOOGL/GOOG do not create duplicate companies
+
+        let json = r#"{
+            "0": {"cik_str": 320193, "ticker": "AAPL", "title": "Apple Inc."},
+            "2": {"cik_str": 1652044, "ticker": "GOOG", "title": "Alphabet Inc."},
+            "1": {"cik_str": 1652044, "ticker": "GOOGL", "title": "Alphabet Inc."},
+            "3": {"cik_str": 1000045, "ticker": "ABCDEFGHIJK", "title": "Long Ticker Corp"}
+        }"#;
+        let response: CompanyTickersResponse = serde_json::from_str(json).unwrap();
+
+        let listed = parse_company_tickers(response);
+
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0].cik, "0000320193");
+        assert_eq!(listed[1].ticker.as_deref(), Some("GOOGL"));
+        assert_eq!(listed[2].ticker, None);
+    }
+
+    #[test]
+    fn test_plan_company_sync_tracks_changes() {
+        // REQUIREMENT: Track company additions and removals between syncs
+        // PURPOSE: Verify new, changed, reactivated, unchanged and delisted companies are classified
+        // This ensures only changed rows are written and delisted companies are deactivated
+
+        let listed = vec![
+            ListedCompany {
+                cik: "0000000001".to_string(),
+                ticker: Some("NEW".to_string()),
+                name: "New Co".to_string(),
+            },
+            ListedCompany {
+                cik: "0000000002".to_string(),
+                ticker: Some("RNMD".to_string()),
+                name: "Renamed Co".to_string(),
+            },
+            ListedCompany {
+                cik: "0000000003".to_string(),
+                ticker: Some("SAME".to_string()),
+                name: "Same Co".to_string(),
+            },
+            ListedCompany {
+                cik: "0000000004".to_string(),
+                ticker: Some("BACK".to_string()),
+                name: "Back Co".to_string(),
+            },
+        ];
+        let existing = vec![
+            stored("0000000002", Some("OLD"), "Old Co", true),
+            stored("0000000003", Some("SAME"), "Same Co", true),
+            stored("0000000004", Some("BACK"), "Back Co", false),
+            stored("0000000005", Some("GONE"), "Delisted Co", true),
+            stored("0000000006", None, "Private Co", true),
+        ];
+
+        let (upserts, report) = plan_company_sync(&listed, &existing);
+
+        assert_eq!(upserts.len(), 3);
+        assert_eq!(report.listed, 4);
+        assert_eq!(report.added, vec!["0000000001"]);
+        assert_eq!(report.updated, vec!["0000000002"]);
+        assert_eq!(report.reactivated, vec!["0000000004"]);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.deactivated, vec!["0000000005"]);
+    }
+}
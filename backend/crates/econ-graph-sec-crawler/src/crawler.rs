@@ -10,13 +10,20 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::calculation_linkbase::CalculationLinkbase;
+use crate::download_manager::{DownloadManager, DownloadManagerConfig, DownloadRequest};
+use crate::filing_sections::FilingSectionExtractor;
 use crate::models::{
     CompanySubmissionsResponse, CrawlConfig, CrawlProgress, CrawlResult, DtsReference, FilingInfo,
     SecCompany, SecFiling, StoredXbrlDocument,
 };
 use crate::rate_limiter::SecRateLimiter;
 use crate::storage::{XbrlStorage, XbrlStorageConfig};
-use crate::utils::{build_submissions_url, build_xbrl_url, get_fiscal_quarter, parse_sec_date};
+use crate::utils::{
+    build_filing_document_url, build_submissions_url, build_xbrl_url, get_fiscal_quarter,
+    parse_sec_date,
+};
+use crate::xbrl_parser::parse_instance_facts;
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::models::{Company, FinancialStatement};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
@@ -51,11 +58,12 @@ use econ_graph_metrics::crawler::CRAWLER_METRICS;
 /// ```
 #[derive(Clone)]
 pub struct SecEdgarCrawler {
-    client: Client,
-    rate_limiter: SecRateLimiter,
+    pub(crate) client: Client,
+    pub(crate) rate_limiter: SecRateLimiter,
+    downloads: DownloadManager,
     storage: XbrlStorage,
     config: CrawlConfig,
-    pool: DatabasePool,
+    pub(crate) pool: DatabasePool,
 }
 
 impl SecEdgarCrawler {
@@ -80,13 +88,21 @@ impl SecEdgarCrawler {
         let rate_limiter =
             SecRateLimiter::new(config.max_requests_per_second, Duration::from_secs(1));
 
-        // Create XBRL storage with default configuration
-        let storage_config = XbrlStorageConfig::default();
+        // Filing downloads share the client and rate limiter with every other request
+        let downloads = DownloadManager::new(
+            client.clone(),
+            rate_limiter.clone(),
+            DownloadManagerConfig::from(&config),
+        );
+
+        // Create XBRL storage, compressing filings at the configured level
+        let storage_config = XbrlStorageConfig::from_env()?;
         let storage = XbrlStorage::new(pool.clone(), storage_config);
 
         Ok(Self {
             client,
             rate_limiter,
+            downloads,
             storage,
             config,
             pool,
@@ -94,6 +110,7 @@ impl SecEdgarCrawler {
     }
 
     /// Crawl all filings for a specific company
+    #[tracing::instrument(name = "sec.crawl_company_filings", skip(self))]
     pub async fn crawl_company_filings(&self, cik: &str) -> Result<CrawlResult> {
         let operation_id = Uuid::new_v4();
         let start_time = Utc::now();
@@ -123,23 +140,56 @@ impl SecEdgarCrawler {
             success: false,
         };
 
-        // Download XBRL files
+        // Download XBRL files concurrently, storing each one as it arrives
+        let mut jobs = Vec::with_capacity(filings.len());
         for filing_info in filings {
-            match self.download_filing_xbrl(&company, &filing_info).await {
+            let accession_number = &filing_info.accession_number[0];
+            match build_xbrl_url(accession_number) {
+                Ok(url) => jobs.push((
+                    DownloadRequest {
+                        cik: cik.to_string(),
+                        url,
+                        endpoint: "/xbrl",
+                        resume_key: accession_number.clone(),
+                    },
+                    filing_info,
+                )),
+                Err(e) => {
+                    result.filings_failed += 1;
+                    let error_msg =
+                        format!("Failed to download filing {}: {}", accession_number, e);
+                    error!("{}", error_msg);
+                    result.errors.push(error_msg);
+                }
+            }
+        }
+
+        let company = &company;
+        let outcomes = self
+            .downloads
+            .download_all(jobs, |request, filing_info, content| async move {
+                let stored = match content {
+                    Ok(content) => {
+                        self.store_filing_xbrl(company, filing_info, &request.url, content)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                (request.resume_key, stored)
+            })
+            .await;
+
+        for (accession_number, outcome) in outcomes {
+            match outcome {
                 Ok(bytes_downloaded) => {
                     result.filings_downloaded += 1;
                     result.total_bytes_downloaded += bytes_downloaded;
-                    debug!(
-                        "Successfully downloaded filing: {}",
-                        filing_info.accession_number[0]
-                    );
+                    debug!("Successfully downloaded filing: {}", accession_number);
                 }
                 Err(e) => {
                     result.filings_failed += 1;
-                    let error_msg = format!(
-                        "Failed to download filing {}: {}",
-                        filing_info.accession_number[0], e
-                    );
+                    let error_msg =
+                        format!("Failed to download filing {}: {}", accession_number, e);
                     error!("{}", error_msg);
                     result.errors.push(error_msg);
                 }
@@ -158,6 +208,7 @@ impl SecEdgarCrawler {
     }
 
     /// Get company information from SEC EDGAR
+    #[tracing::instrument(name = "sec.company_info", skip(self), fields(otel.kind = "client"))]
     async fn get_company_info(&self, cik: &str) -> Result<SecCompany> {
         let url = build_submissions_url(cik);
 
@@ -210,6 +261,11 @@ impl SecEdgarCrawler {
     }
 
     /// Get company submissions (filings) from SEC EDGAR
+    #[tracing::instrument(
+        name = "sec.company_submissions",
+        skip(self),
+        fields(otel.kind = "client")
+    )]
     async fn get_company_submissions(&self, cik: &str) -> Result<CompanySubmissionsResponse> {
         let url = build_submissions_url(cik);
 
@@ -289,51 +345,29 @@ impl SecEdgarCrawler {
         Ok(filtered)
     }
 
-    /// Download XBRL file for a specific filing
-    async fn download_filing_xbrl(
+    /// Store a downloaded XBRL file with its DTS components and narrative sections
+    ///
+    /// Returns the size of the XBRL file in bytes.
+    #[tracing::instrument(
+        name = "sec.store_filing_xbrl",
+        skip_all,
+        fields(
+            cik = %company.cik,
+            accession_number = ?filing_info.accession_number.first(),
+        )
+    )]
+    async fn store_filing_xbrl(
         &self,
         company: &SecCompany,
         filing_info: &FilingInfo,
+        xbrl_url: &str,
+        content: Vec<u8>,
     ) -> Result<u64> {
         let accession_number = &filing_info.accession_number[0];
         let filing_date = parse_sec_date(&filing_info.filing_date[0])?;
         let report_date = parse_sec_date(&filing_info.report_date[0])?;
-
-        // Construct XBRL URL
-        let xbrl_url = build_xbrl_url(accession_number)?;
-
-        debug!("Downloading XBRL from: {}", xbrl_url);
-
-        self.rate_limiter.wait_for_permit().await;
-
-        let start = std::time::Instant::now();
-        let response = self
-            .client
-            .get(&xbrl_url)
-            .send()
-            .await
-            .context("Failed to download XBRL file")?;
-
-        let duration = start.elapsed().as_secs_f64();
-        let status = response.status();
-        CRAWLER_METRICS.record_request("sec", "edgar", "/xbrl", status.as_str(), duration);
-        if status.as_u16() == 429 {
-            CRAWLER_METRICS.record_rate_limit_hit("sec", "edgar");
-        }
-        if !status.is_success() {
-            CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
-            return Err(anyhow::anyhow!("HTTP error downloading XBRL: {}", status));
-        }
-
-        let content = response
-            .bytes()
-            .await
-            .context("Failed to read response body")?;
         let file_size = content.len() as u64;
 
-        // Record bytes downloaded metric
-        CRAWLER_METRICS.record_bytes_downloaded("sec", "edgar", file_size);
-
         // Store the XBRL file in the database
         let stored_doc = self
             .storage
@@ -346,7 +380,7 @@ impl SecEdgarCrawler {
                 report_date.year(),
                 Some(get_fiscal_quarter(&report_date)),
                 Some(&filing_info.form[0]),
-                Some(&xbrl_url),
+                Some(xbrl_url),
             )
             .await
             .context("Failed to store XBRL file")?;
@@ -357,48 +391,204 @@ impl SecEdgarCrawler {
         );
 
         // Discover and download DTS components
+        match self
+            .download_dts_components(&content, xbrl_url, &stored_doc.id)
+            .await
+        {
+            Ok(calculation_linkbases) if !calculation_linkbases.is_empty() => {
+                if let Err(e) = self
+                    .validate_calculations(&content, &calculation_linkbases, &stored_doc.id)
+                    .await
+                {
+                    warn!(
+                        "Failed to validate calculations for {}: {}",
+                        accession_number, e
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(
+                    "Failed to download DTS components for {}: {}",
+                    accession_number, e
+                );
+                // Don't fail the entire process if DTS download fails
+            }
+        }
+
+        // Extract narrative sections (Risk Factors, MD&A) from the primary document
         if let Err(e) = self
-            .download_dts_components(&content, &xbrl_url, &stored_doc.id)
+            .download_filing_sections(filing_info, &stored_doc.id)
             .await
         {
             warn!(
-                "Failed to download DTS components for {}: {}",
+                "Failed to extract filing sections for {}: {}",
                 accession_number, e
             );
-            // Don't fail the entire process if DTS download fails
+            // Section text is supplementary to the XBRL data
         }
 
         Ok(file_size)
     }
 
+    /// Download the HTML primary document of a 10-K/10-Q and store its narrative sections
+    #[tracing::instrument(
+        name = "sec.download_filing_sections",
+        skip_all,
+        fields(otel.kind = "client", accession_number = ?filing_info.accession_number.first())
+    )]
+    async fn download_filing_sections(
+        &self,
+        filing_info: &FilingInfo,
+        statement_id: &Uuid,
+    ) -> Result<()> {
+        let accession_number = &filing_info.accession_number[0];
+        let form_type = filing_info.form.first().map(String::as_str).unwrap_or("");
+        let primary_document = match filing_info.primary_document.first() {
+            Some(document) if !document.is_empty() => document,
+            _ => return Ok(()),
+        };
+
+        if !FilingSectionExtractor::supports_form(form_type) {
+            return Ok(());
+        }
+
+        let document_url = build_filing_document_url(accession_number, primary_document)?;
+
+        debug!("Downloading primary document from: {}", document_url);
+
+        self.rate_limiter.wait_for_permit().await?;
+
+        let start = std::time::Instant::now();
+        let response = self
+            .client
+            .get(&document_url)
+            .send()
+            .await
+            .context("Failed to download primary document")?;
+
+        let duration = start.elapsed().as_secs_f64();
+        let status = response.status();
+        CRAWLER_METRICS.record_request("sec", "edgar", "/document", status.as_str(), duration);
+        if status.as_u16() == 429 {
+            CRAWLER_METRICS.record_rate_limit_hit("sec", "edgar");
+        }
+        if !status.is_success() {
+            CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
+            return Err(anyhow::anyhow!(
+                "HTTP error downloading primary document: {}",
+                status
+            ));
+        }
+
+        let html = response
+            .text()
+            .await
+            .context("Failed to read primary document response")?;
+        CRAWLER_METRICS.record_bytes_downloaded("sec", "edgar", html.len() as u64);
+
+        let sections = FilingSectionExtractor::new().extract(&html, form_type);
+        let stored = self
+            .storage
+            .store_filing_sections(*statement_id, &sections, Some(&document_url))
+            .await?;
+
+        info!(
+            "Stored {} filing sections for {} ({})",
+            stored, accession_number, form_type
+        );
+
+        Ok(())
+    }
+
     /// Download DTS (Discoverable Taxonomy Set) components for an XBRL instance
+    ///
+    /// Returns the content of the calculation linkbases found, including those
+    /// referenced from the filer's extension schema rather than the instance itself.
     async fn download_dts_components(
         &self,
         xbrl_content: &[u8],
         xbrl_url: &str,
         statement_id: &Uuid,
-    ) -> Result<()> {
+    ) -> Result<Vec<String>> {
         debug!("Discovering DTS components for XBRL file");
 
         // Parse XBRL content to find schema references
-        let dts_references = self.discover_dts_references(xbrl_content)?;
+        let mut dts_references = self.discover_dts_references(xbrl_content)?;
 
         info!("Found {} DTS references in XBRL file", dts_references.len());
 
+        let mut calculation_linkbases = Vec::new();
+
         // Download each referenced taxonomy component
-        for reference in dts_references {
-            if let Err(e) = self
+        while let Some(reference) = dts_references.pop() {
+            let component = match self
                 .download_taxonomy_component(&reference, xbrl_url, statement_id)
                 .await
             {
-                warn!(
-                    "Failed to download taxonomy component {}: {}",
-                    reference.reference_href, e
+                Ok(component) => component,
+                Err(e) => {
+                    warn!(
+                        "Failed to download taxonomy component {}: {}",
+                        reference.reference_href, e
+                    );
+                    // Continue with other components
+                    continue;
+                }
+            };
+
+            if reference.is_calculation_linkbase() {
+                calculation_linkbases.push(String::from_utf8_lossy(&component).into_owned());
+            } else if reference.reference_type == "schemaRef"
+                && !reference.reference_href.starts_with("http")
+            {
+                // Extension schemas sit next to the instance and link the filer's own linkbases
+                dts_references.extend(
+                    self.discover_dts_references(&component)?
+                        .into_iter()
+                        .filter(|linked| {
+                            linked.is_calculation_linkbase()
+                                && !linked.reference_href.starts_with("http")
+                        }),
                 );
-                // Continue with other components
             }
         }
 
+        Ok(calculation_linkbases)
+    }
+
+    /// Check the instance's facts against its calculation linkbases and store the discrepancies
+    async fn validate_calculations(
+        &self,
+        xbrl_content: &[u8],
+        calculation_linkbases: &[String],
+        statement_id: &Uuid,
+    ) -> Result<()> {
+        let facts = parse_instance_facts(&String::from_utf8_lossy(xbrl_content))?;
+
+        let linkbases = calculation_linkbases
+            .iter()
+            .map(|content| CalculationLinkbase::parse(content))
+            .collect::<Result<Vec<_>>>()?;
+        let validation = CalculationLinkbase::merge(linkbases).validate(&facts);
+
+        let stored = self
+            .storage
+            .store_calculation_discrepancies(*statement_id, &validation)
+            .await?;
+
+        if stored > 0 {
+            warn!(
+                "Found {} calculation inconsistencies in {} checks for statement {}",
+                stored, validation.checks_performed, statement_id
+            );
+        } else {
+            debug!(
+                "Calculations consistent for statement {} ({} checks)",
+                statement_id, validation.checks_performed
+            );
+        }
+
         Ok(())
     }
 
@@ -416,14 +606,17 @@ impl SecEdgarCrawler {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => {
-                    if e.name().as_ref() == b"schemaRef" || e.name().as_ref() == b"linkbaseRef" {
+                // References are usually empty elements with prefixed names (link:schemaRef)
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    if e.local_name().as_ref() == b"schemaRef"
+                        || e.local_name().as_ref() == b"linkbaseRef"
+                    {
                         let mut href = None;
                         let mut role = None;
                         let mut arcrole = None;
 
                         for attr in e.attributes().flatten() {
-                            match attr.key.as_ref() {
+                            match attr.key.local_name().as_ref() {
                                 b"href" => {
                                     if let Ok(value) = std::str::from_utf8(&attr.value) {
                                         href = Some(value.to_string());
@@ -444,7 +637,7 @@ impl SecEdgarCrawler {
                         }
 
                         if let Some(href) = href {
-                            let reference_type = if e.name().as_ref() == b"schemaRef" {
+                            let reference_type = if e.local_name().as_ref() == b"schemaRef" {
                                 "schemaRef"
                             } else {
                                 "linkbaseRef"
@@ -475,12 +668,17 @@ impl SecEdgarCrawler {
     }
 
     /// Download a single taxonomy component
+    #[tracing::instrument(
+        name = "sec.download_taxonomy_component",
+        skip_all,
+        fields(otel.kind = "client", base_url)
+    )]
     async fn download_taxonomy_component(
         &self,
         reference: &DtsReference,
         base_url: &str,
         statement_id: &Uuid,
-    ) -> Result<()> {
+    ) -> Result<Vec<u8>> {
         // Construct the full URL for the taxonomy component
         let taxonomy_url = if reference.reference_href.starts_with("http") {
             reference.reference_href.clone()
@@ -536,7 +734,7 @@ impl SecEdgarCrawler {
             reference.reference_href
         );
 
-        Ok(())
+        Ok(content.to_vec())
     }
 
     /// Get crawl progress for a running operation
@@ -562,6 +760,41 @@ impl SecEdgarCrawler {
         self.storage.get_storage_stats().await
     }
 
+    /// Space used by the document blob store
+    pub async fn get_document_blob_stats(
+        &self,
+    ) -> Result<econ_graph_core::models::DocumentBlobStatistics> {
+        self.storage.blobs().statistics().await
+    }
+
+    /// Check up to `limit` stored documents against their content hashes
+    pub async fn verify_documents(
+        &self,
+        limit: i64,
+    ) -> Result<crate::blob_store::BlobVerificationReport> {
+        self.storage.blobs().verify(limit).await
+    }
+
+    /// Re-encode up to `limit` stored documents of `kind` at the configured zstd level
+    pub async fn recompress_documents(
+        &self,
+        kind: econ_graph_core::models::DocumentKind,
+        limit: i64,
+    ) -> Result<crate::blob_store::RecompressionReport> {
+        use crate::blob_store::{compression_level_from_env, BlobStore};
+        use econ_graph_core::models::DocumentKind;
+
+        match kind {
+            DocumentKind::Filing => self.storage.blobs().recompress(limit).await,
+            DocumentKind::Taxonomy => {
+                let level = compression_level_from_env(kind)?;
+                BlobStore::new(self.pool.clone(), kind, level)
+                    .recompress(limit)
+                    .await
+            }
+        }
+    }
+
     /// Crawl multiple companies concurrently
     pub async fn crawl_multiple_companies(&self, ciks: Vec<String>) -> Result<Vec<CrawlResult>> {
         let mut results = Vec::new();
@@ -716,7 +949,21 @@ impl SecEdgarCrawler {
     }
 
     /// Parse and store XBRL data after downloading
-    pub async fn parse_and_store_xbrl(&self, accession_number: &str) -> Result<()> {
+    ///
+    /// Returns the number of line items stored. Facts are upserted, so parsing
+    /// a filing again does not duplicate its line items.
+    pub async fn parse_and_store_xbrl(&self, accession_number: &str) -> Result<usize> {
+        let statement_id = self.storage.statement_id(accession_number).await?;
+        self.parse_and_store_statement(statement_id, accession_number)
+            .await
+    }
+
+    /// Parse a stored filing and upsert its facts as line items of `statement_id`
+    pub(crate) async fn parse_and_store_statement(
+        &self,
+        statement_id: Uuid,
+        accession_number: &str,
+    ) -> Result<usize> {
         info!("Parsing and storing XBRL data for: {}", accession_number);
 
         // Retrieve the XBRL file from storage
@@ -736,21 +983,25 @@ impl SecEdgarCrawler {
         let temp_file = std::env::temp_dir().join(format!("{}.xml", accession_number));
         tokio::fs::write(&temp_file, &xbrl_content).await?;
 
-        // Parse the XBRL file
-        let parse_result = parser.parse_xbrl_document(&temp_file).await?;
+        // Parse the XBRL file, cleaning up the temporary file either way
+        let parse_result = parser.parse_xbrl_document(&temp_file).await;
+        let _ = tokio::fs::remove_file(&temp_file).await;
+        let parse_result = parse_result?;
+
+        let statements = parse_result.statements.len();
+        let stored = self
+            .storage
+            .store_line_items(statement_id, parse_result.line_items)
+            .await?;
 
-        // Store the parsed financial statements in the database
-        // This would require implementing the storage logic for parsed data
         info!(
-            "Successfully parsed XBRL file: {} statements, {} facts",
-            parse_result.statements.len(),
-            parse_result.facts.len()
+            "Successfully parsed XBRL file: {} statements, {} facts, {} line items stored",
+            statements,
+            parse_result.facts.len(),
+            stored
         );
 
-        // Clean up temporary file
-        let _ = tokio::fs::remove_file(&temp_file).await;
-
-        Ok(())
+        Ok(stored)
     }
 }
 
@@ -0,0 +1,188 @@
+//! EDGAR daily index
+//!
+//! EDGAR publishes a master index of each business day's filings. Crawlers
+//! for a form type (Form 4, 13F-HR, ...) walk it day by day, continuing after
+//! the last day they crawled, instead of polling every company's submissions.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashSet;
+
+use crate::crawler::SecEdgarCrawler;
+use crate::utils::{build_daily_index_url, parse_sec_date};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Days crawled by the first run, before any day has been recorded
+pub const DEFAULT_LOOKBACK_DAYS: i64 = 7;
+
+/// Most days one run crawls; a longer gap is caught up over several runs
+pub const MAX_DAYS_PER_RUN: i64 = 31;
+
+/// Filing listed in an EDGAR daily index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedFiling {
+    pub form_type: String,
+    pub filed_date: NaiveDate,
+    pub accession_number: String,
+    /// Full submission text file, relative to `Archives/`
+    pub file_name: String,
+}
+
+/// Days an incremental crawl covers, oldest first
+///
+/// Starts the day after `last_crawled`, or [`DEFAULT_LOOKBACK_DAYS`] before
+/// `until` on the first run, and covers at most [`MAX_DAYS_PER_RUN`] days.
+pub fn days_to_crawl(last_crawled: Option<NaiveDate>, until: NaiveDate) -> Vec<NaiveDate> {
+    let first = match last_crawled {
+        Some(day) => day + Duration::days(1),
+        None => until - Duration::days(DEFAULT_LOOKBACK_DAYS - 1),
+    };
+
+    first
+        .iter_days()
+        .take_while(|day| *day <= until)
+        .take(MAX_DAYS_PER_RUN as usize)
+        .collect()
+}
+
+/// Filings of the given form types in an EDGAR `master.YYYYMMDD.idx` file
+///
+/// A filing with several filers, such as a Form 4 listed under both the
+/// issuer and the reporting owner, is returned once.
+pub fn parse_daily_index(index: &str, form_types: &[&str]) -> Vec<IndexedFiling> {
+    let mut seen = HashSet::new();
+
+    index
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [_cik, _name, form_type, date_filed, file_name] = fields[..] else {
+                return None;
+            };
+            if !form_types.contains(&form_type) {
+                return None;
+            }
+
+            let accession_number = file_name.rsplit('/').next()?.strip_suffix(".txt")?;
+            if !seen.insert(accession_number.to_string()) {
+                return None;
+            }
+
+            Some(IndexedFiling {
+                form_type: form_type.to_string(),
+                filed_date: parse_sec_date(date_filed).ok()?,
+                accession_number: accession_number.to_string(),
+                file_name: file_name.to_string(),
+            })
+        })
+        .collect()
+}
+
+impl SecEdgarCrawler {
+    /// Filings of the given form types made on `date`
+    ///
+    /// No index is published for weekends and holidays; those days have no
+    /// filings.
+    pub(crate) async fn fetch_daily_index(
+        &self,
+        date: NaiveDate,
+        form_types: &[&str],
+    ) -> Result<Vec<IndexedFiling>> {
+        let index = self
+            .fetch_archive_text(&build_daily_index_url(&date), "/daily-index")
+            .await?;
+
+        Ok(index
+            .map(|index| parse_daily_index(&index, form_types))
+            .unwrap_or_default())
+    }
+
+    /// Download a text file from the EDGAR archive; `None` when it does not exist
+    pub(crate) async fn fetch_archive_text(
+        &self,
+        url: &str,
+        endpoint: &str,
+    ) -> Result<Option<String>> {
+        self.rate_limiter.wait_for_permit().await?;
+
+        let start = std::time::Instant::now();
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to fetch {}", url))?;
+
+        let duration = start.elapsed().as_secs_f64();
+        let status = response.status();
+        CRAWLER_METRICS.record_request("sec", "edgar", endpoint, status.as_str(), duration);
+        if status.as_u16() == 404 {
+            return Ok(None);
+        }
+        if status.as_u16() == 429 {
+            CRAWLER_METRICS.record_rate_limit_hit("sec", "edgar");
+        }
+        if !status.is_success() {
+            CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
+            return Err(anyhow::anyhow!("HTTP error fetching {}: {}", url, status));
+        }
+
+        let text = response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read {}", url))?;
+        CRAWLER_METRICS.record_bytes_downloaded("sec", "edgar", text.len() as u64);
+
+        Ok(Some(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_daily_index_and_crawl_days() {
+        // REQUIREMENT: Crawl filings incrementally from the EDGAR daily index
+        // PURPOSE: Verify filings of the requested forms are listed once and each run resumes after the last crawled day
+        // This ensures filings listed under several filers are fetched once and no day is skipped
+
+        let index = "Description:           Daily Index of EDGAR Dissemination Feed by Company Name
+Last Data Received:    Jan 4, 2024
+
+CIK|Company Name|Form Type|Date Filed|File Name
+--------------------------------------------------------------------------------
+1214156|Cook Timothy D|4|20240104|edgar/data/1214156/0000320193-24-000006.txt
+320193|Apple Inc.|4|20240104|edgar/data/320193/0000320193-24-000006.txt
+320193|Apple Inc.|8-K|20240104|edgar/data/320193/0000320193-24-000007.txt
+789019|Microsoft Corp|4/A|20240104|edgar/data/789019/0001062993-24-000100.txt";
+
+        let filings = parse_daily_index(index, &["4", "4/A"]);
+
+        assert_eq!(filings.len(), 2);
+        assert_eq!(filings[0].accession_number, "0000320193-24-000006");
+        assert_eq!(
+            filings[0].filed_date,
+            NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()
+        );
+        assert_eq!(filings[1].form_type, "4/A");
+        assert_eq!(parse_daily_index(index, &["8-K"]).len(), 1);
+
+        let until = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        assert_eq!(days_to_crawl(None, until).len(), 7);
+        assert_eq!(
+            days_to_crawl(Some(NaiveDate::from_ymd_opt(2024, 1, 8).unwrap()), until),
+            vec![
+                NaiveDate::from_ymd_opt(2024, 1, 9).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 10).unwrap()
+            ]
+        );
+        assert!(days_to_crawl(Some(until), until).is_empty());
+        assert_eq!(
+            days_to_crawl(Some(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()), until).len(),
+            MAX_DAYS_PER_RUN as usize
+        );
+    }
+}
@@ -0,0 +1,587 @@
+//! Concurrent filing downloads
+//!
+//! [`DownloadManager`] downloads many SEC files at once while staying inside
+//! SEC's 10 requests per second guidance:
+//!
+//! - A semaphore shared by every clone of the manager bounds the downloads in
+//!   flight, however many crawls use it.
+//! - Every request, retries included, waits for the [`SecRateLimiter`].
+//! - Queued downloads are taken round-robin across companies, so one company
+//!   with hundreds of filings cannot starve the others.
+//! - Bodies are written to a partial file as they arrive. A download that
+//!   fails midway resumes with an HTTP `Range` request instead of starting over.
+//! - A worker takes its next download only after the previous one has been
+//!   handled, so slow storage slows downloading instead of buffering files in
+//!   memory.
+
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use reqwest::header::{CONTENT_RANGE, RANGE, RETRY_AFTER};
+use reqwest::{Client, StatusCode};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tracing::{debug, info, warn};
+
+use crate::models::CrawlConfig;
+use crate::rate_limiter::SecRateLimiter;
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Longest a `Retry-After` header may make a download wait
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// A file to download on behalf of a company
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadRequest {
+    /// CIK of the company the file belongs to, used for fair scheduling
+    pub cik: String,
+    pub url: String,
+    /// Endpoint label for request metrics, e.g. "/xbrl"
+    pub endpoint: &'static str,
+    /// Stable name for the partial file, e.g. the accession number
+    pub resume_key: String,
+}
+
+/// Settings for a [`DownloadManager`]
+#[derive(Debug, Clone)]
+pub struct DownloadManagerConfig {
+    /// Most downloads in flight at once, across all users of the manager
+    pub max_concurrent_downloads: usize,
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub retry_delay: Duration,
+    /// Downloads growing past this size are abandoned
+    pub max_file_size_bytes: u64,
+    /// Directory holding partial downloads between attempts
+    pub partial_dir: PathBuf,
+}
+
+impl Default for DownloadManagerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_downloads: 3,
+            max_retries: 3,
+            retry_delay: Duration::from_secs(5),
+            max_file_size_bytes: 50 * 1024 * 1024,
+            partial_dir: std::env::temp_dir().join("econ-graph-sec-downloads"),
+        }
+    }
+}
+
+impl From<&CrawlConfig> for DownloadManagerConfig {
+    fn from(config: &CrawlConfig) -> Self {
+        Self {
+            max_concurrent_downloads: config.max_concurrent_requests.unwrap_or(3).max(1),
+            max_retries: config.max_retries,
+            retry_delay: Duration::from_secs(config.retry_delay_seconds),
+            max_file_size_bytes: config.max_file_size_bytes,
+            ..Self::default()
+        }
+    }
+}
+
+/// Downloads waiting for a worker, handed out round-robin across companies
+#[derive(Debug)]
+pub struct FairQueue<T> {
+    order: VecDeque<String>,
+    pending: HashMap<String, VecDeque<T>>,
+    len: usize,
+}
+
+impl<T> Default for FairQueue<T> {
+    fn default() -> Self {
+        Self {
+            order: VecDeque::new(),
+            pending: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> FairQueue<T> {
+    /// Queue `item` behind the other downloads of company `cik`
+    pub fn push(&mut self, cik: &str, item: T) {
+        let pending = self.pending.entry(cik.to_string()).or_default();
+        if pending.is_empty() {
+            self.order.push_back(cik.to_string());
+        }
+        pending.push_back(item);
+        self.len += 1;
+    }
+
+    /// The oldest download of the company whose turn it is
+    pub fn pop(&mut self) -> Option<T> {
+        let cik = self.order.pop_front()?;
+        let pending = self.pending.get_mut(&cik)?;
+        let item = pending.pop_front();
+        if pending.is_empty() {
+            self.pending.remove(&cik);
+        } else {
+            self.order.push_back(cik);
+        }
+        self.len -= 1;
+        item
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Rate-limited, resumable downloads with bounded concurrency
+#[derive(Debug, Clone)]
+pub struct DownloadManager {
+    client: Client,
+    rate_limiter: SecRateLimiter,
+    slots: Arc<Semaphore>,
+    config: DownloadManagerConfig,
+}
+
+/// Why an attempt failed, and whether another attempt may succeed
+struct AttemptError {
+    error: anyhow::Error,
+    retry_reason: Option<&'static str>,
+    retry_after: Option<Duration>,
+}
+
+impl AttemptError {
+    fn fatal(error: anyhow::Error) -> Self {
+        Self {
+            error,
+            retry_reason: None,
+            retry_after: None,
+        }
+    }
+
+    fn retryable(error: anyhow::Error, reason: &'static str) -> Self {
+        Self {
+            error,
+            retry_reason: Some(reason),
+            retry_after: None,
+        }
+    }
+}
+
+impl DownloadManager {
+    pub fn new(
+        client: Client,
+        rate_limiter: SecRateLimiter,
+        config: DownloadManagerConfig,
+    ) -> Self {
+        Self {
+            client,
+            rate_limiter,
+            slots: Arc::new(Semaphore::new(config.max_concurrent_downloads.max(1))),
+            config,
+        }
+    }
+
+    /// Download every job's file, calling `handle` with each result as it completes
+    ///
+    /// Downloads run on up to `max_concurrent_downloads` workers, taken fairly
+    /// across companies. Returns what `handle` returned, in completion order.
+    pub async fn download_all<T, F, Fut, O>(
+        &self,
+        jobs: Vec<(DownloadRequest, T)>,
+        handle: F,
+    ) -> Vec<O>
+    where
+        F: Fn(DownloadRequest, T, Result<Vec<u8>>) -> Fut,
+        Fut: Future<Output = O>,
+    {
+        let mut queue = FairQueue::default();
+        for (request, context) in jobs {
+            let cik = request.cik.clone();
+            queue.push(&cik, (request, context));
+        }
+        let workers = self.config.max_concurrent_downloads.max(1).min(queue.len());
+        CRAWLER_METRICS.add_queued_downloads("sec", "edgar", queue.len() as i64);
+        let queue = Mutex::new(queue);
+
+        let next = || {
+            let job = queue.lock().unwrap_or_else(|e| e.into_inner()).pop();
+            if job.is_some() {
+                CRAWLER_METRICS.add_queued_downloads("sec", "edgar", -1);
+            }
+            job
+        };
+        let next = &next;
+        let handle = &handle;
+
+        let outputs = join_all((0..workers).map(move |_| async move {
+            let mut outputs = Vec::new();
+            while let Some((request, context)) = next() {
+                let result = self.download(&request).await;
+                outputs.push(handle(request, context, result).await);
+            }
+            outputs
+        }))
+        .await;
+
+        outputs.into_iter().flatten().collect()
+    }
+
+    /// Download one file, retrying failures and resuming partial bodies
+    pub async fn download(&self, request: &DownloadRequest) -> Result<Vec<u8>> {
+        let _slot = self
+            .slots
+            .acquire()
+            .await
+            .context("Download manager closed")?;
+        let _in_flight = InFlight::start();
+
+        fs::create_dir_all(&self.config.partial_dir)
+            .await
+            .context("Failed to create partial download directory")?;
+        let partial = self.partial_path(&request.resume_key);
+
+        let mut attempt = 0;
+        loop {
+            match self.attempt(request, &partial).await {
+                Ok(()) => {
+                    let content = fs::read(&partial)
+                        .await
+                        .context("Failed to read completed download")?;
+                    fs::remove_file(&partial).await.ok();
+                    return Ok(content);
+                }
+                Err(failure) => {
+                    let Some(reason) = failure.retry_reason else {
+                        fs::remove_file(&partial).await.ok();
+                        return Err(failure.error);
+                    };
+                    if attempt >= self.config.max_retries {
+                        // Keep the partial file so a later crawl can resume it
+                        return Err(failure.error.context(format!(
+                            "Giving up on {} after {} attempts",
+                            request.url,
+                            attempt + 1
+                        )));
+                    }
+
+                    let delay = failure
+                        .retry_after
+                        .unwrap_or(self.config.retry_delay * 2u32.saturating_pow(attempt));
+                    CRAWLER_METRICS.record_retry("sec", "edgar", reason);
+                    warn!(
+                        "Retrying {} in {:?} ({}): {}",
+                        request.url, delay, reason, failure.error
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// One request for the rest of the file, appending to the partial file
+    async fn attempt(
+        &self,
+        request: &DownloadRequest,
+        partial: &Path,
+    ) -> std::result::Result<(), AttemptError> {
+        let resume_from = fs::metadata(partial).await.map(|m| m.len()).unwrap_or(0);
+
+        let _ = self.rate_limiter.wait_for_permit().await;
+
+        let mut http_request = self.client.get(&request.url);
+        if resume_from > 0 {
+            http_request = http_request.header(RANGE, format!("bytes={}-", resume_from));
+        }
+
+        let start = Instant::now();
+        let response = http_request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                CRAWLER_METRICS.record_timeout("sec", "edgar");
+            }
+            AttemptError::retryable(anyhow::Error::new(e).context("Request failed"), "network")
+        })?;
+        let status = response.status();
+        CRAWLER_METRICS.record_request(
+            "sec",
+            "edgar",
+            request.endpoint,
+            status.as_str(),
+            start.elapsed().as_secs_f64(),
+        );
+
+        let append = match status {
+            StatusCode::PARTIAL_CONTENT
+                if content_range_start(response.headers().get(CONTENT_RANGE))
+                    == Some(resume_from) =>
+            {
+                info!("Resuming {} at byte {}", request.url, resume_from);
+                CRAWLER_METRICS.record_resumed_download("sec", "edgar");
+                true
+            }
+            // The server ignored or mangled the range; start over
+            StatusCode::PARTIAL_CONTENT => {
+                fs::remove_file(partial).await.ok();
+                return Err(AttemptError::retryable(
+                    anyhow::anyhow!("Unexpected Content-Range resuming {}", request.url),
+                    "bad_range",
+                ));
+            }
+            // Everything was already downloaded
+            StatusCode::RANGE_NOT_SATISFIABLE if resume_from > 0 => return Ok(()),
+            status if status.is_success() => false,
+            StatusCode::TOO_MANY_REQUESTS => {
+                CRAWLER_METRICS.record_rate_limit_hit("sec", "edgar");
+                return Err(AttemptError {
+                    error: anyhow::anyhow!("Rate limited downloading {}", request.url),
+                    retry_reason: Some("rate_limited"),
+                    retry_after: retry_after(response.headers().get(RETRY_AFTER)),
+                });
+            }
+            status if status.is_server_error() => {
+                CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
+                return Err(AttemptError::retryable(
+                    anyhow::anyhow!("HTTP error downloading {}: {}", request.url, status),
+                    "server_error",
+                ));
+            }
+            status => {
+                CRAWLER_METRICS.record_error("sec", "edgar", "http_error");
+                return Err(AttemptError::fatal(anyhow::anyhow!(
+                    "HTTP error downloading {}: {}",
+                    request.url,
+                    status
+                )));
+            }
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(partial)
+            .await
+            .map_err(|e| AttemptError::fatal(e.into()))?;
+        let mut size = if append { resume_from } else { 0 };
+
+        let mut response = response;
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    file.flush().await.ok();
+                    return Err(AttemptError::retryable(
+                        anyhow::Error::new(e).context("Download interrupted"),
+                        "interrupted",
+                    ));
+                }
+            };
+            size += chunk.len() as u64;
+            if size > self.config.max_file_size_bytes {
+                drop(file);
+                fs::remove_file(partial).await.ok();
+                return Err(AttemptError::fatal(anyhow::anyhow!(
+                    "{} exceeds the {} byte limit",
+                    request.url,
+                    self.config.max_file_size_bytes
+                )));
+            }
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| AttemptError::fatal(e.into()))?;
+            CRAWLER_METRICS.record_bytes_downloaded("sec", "edgar", chunk.len() as u64);
+        }
+        file.flush()
+            .await
+            .map_err(|e| AttemptError::fatal(e.into()))?;
+
+        debug!("Downloaded {} ({} bytes)", request.url, size);
+        Ok(())
+    }
+
+    fn partial_path(&self, resume_key: &str) -> PathBuf {
+        let name: String = resume_key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.config.partial_dir.join(format!("{}.part", name))
+    }
+}
+
+/// Tracks a download in the in-flight gauge for as long as it lives
+struct InFlight;
+
+impl InFlight {
+    fn start() -> Self {
+        CRAWLER_METRICS.add_downloads_in_flight("sec", "edgar", 1);
+        Self
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        CRAWLER_METRICS.add_downloads_in_flight("sec", "edgar", -1);
+    }
+}
+
+/// First byte position of a `Content-Range: bytes start-end/total` header
+fn content_range_start(header: Option<&reqwest::header::HeaderValue>) -> Option<u64> {
+    let range = header?.to_str().ok()?.strip_prefix("bytes ")?;
+    range.split('-').next()?.trim().parse().ok()
+}
+
+/// Delay requested by a `Retry-After` header in seconds, capped
+fn retry_after(header: Option<&reqwest::header::HeaderValue>) -> Option<Duration> {
+    let seconds: u64 = header?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(partial_dir: &Path) -> DownloadManager {
+        DownloadManager::new(
+            Client::new(),
+            SecRateLimiter::aggressive(),
+            DownloadManagerConfig {
+                max_concurrent_downloads: 2,
+                max_retries: 1,
+                retry_delay: Duration::from_millis(10),
+                max_file_size_bytes: 1024,
+                partial_dir: partial_dir.to_path_buf(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_fair_queue_round_robin() {
+        // REQUIREMENT: Concurrent downloads are shared fairly across companies
+        // PURPOSE: Verify queued downloads alternate between companies, oldest first within each
+        // This ensures a company with many filings cannot starve the others
+
+        let mut queue = FairQueue::default();
+        for filing in ["a1", "a2", "a3"] {
+            queue.push("A", filing);
+        }
+        queue.push("B", "b1");
+        queue.push("C", "c1");
+        queue.push("B", "b2");
+        assert_eq!(queue.len(), 6);
+
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(order, vec!["a1", "b1", "c1", "a2", "b2", "a3"]);
+        assert!(queue.is_empty());
+
+        assert_eq!(
+            content_range_start(Some(&"bytes 5-9/10".parse().unwrap())),
+            Some(5)
+        );
+        assert_eq!(
+            retry_after(Some(&"600".parse().unwrap())),
+            Some(MAX_RETRY_AFTER)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_partial_file() {
+        // REQUIREMENT: Interrupted filing downloads resume instead of starting over
+        // PURPOSE: Verify a partial file is continued with a Range request and removed once complete
+        // This ensures large filings are not downloaded twice after a dropped connection
+
+        let mut server = mockito::Server::new_async().await;
+        let resumed = server
+            .mock("GET", "/filing.xml")
+            .match_header("range", "bytes=6-")
+            .with_status(206)
+            .with_header("content-range", "bytes 6-10/11")
+            .with_body("world")
+            .create_async()
+            .await;
+
+        let partial_dir = tempfile::tempdir().unwrap();
+        let manager = manager(partial_dir.path());
+        let request = DownloadRequest {
+            cik: "320193".to_string(),
+            url: format!("{}/filing.xml", server.url()),
+            endpoint: "/xbrl",
+            resume_key: "0000320193-24-000001".to_string(),
+        };
+        let partial = manager.partial_path(&request.resume_key);
+        std::fs::write(&partial, "hello ").unwrap();
+
+        let content = manager.download(&request).await.unwrap();
+
+        assert_eq!(&content[..], b"hello world");
+        assert!(!partial.exists());
+        resumed.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_download_all_hands_every_result_to_handler() {
+        // REQUIREMENT: Batches of filings download concurrently and each result is processed
+        // PURPOSE: Verify successes and failures all reach the handler and client errors are not retried
+        // This ensures one missing filing does not stop the rest of a company's crawl
+
+        let mut server = mockito::Server::new_async().await;
+        let found = server
+            .mock("GET", "/found.xml")
+            .with_status(200)
+            .with_body("<xbrl/>")
+            .expect(2)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/missing.xml")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let partial_dir = tempfile::tempdir().unwrap();
+        let manager = manager(partial_dir.path());
+        let job = |cik: &str, path: &str, key: &str| {
+            (
+                DownloadRequest {
+                    cik: cik.to_string(),
+                    url: format!("{}{}", server.url(), path),
+                    endpoint: "/xbrl",
+                    resume_key: key.to_string(),
+                },
+                key.to_string(),
+            )
+        };
+
+        let mut outcomes = manager
+            .download_all(
+                vec![
+                    job("1", "/found.xml", "a"),
+                    job("1", "/missing.xml", "b"),
+                    job("2", "/found.xml", "c"),
+                ],
+                |_, key, result| async move { (key, result.map(|body| body.len())) },
+            )
+            .await;
+        outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0].1.as_ref().unwrap(), &7);
+        assert!(outcomes[1].1.is_err());
+        assert_eq!(outcomes[2].1.as_ref().unwrap(), &7);
+        found.assert_async().await;
+        missing.assert_async().await;
+    }
+}
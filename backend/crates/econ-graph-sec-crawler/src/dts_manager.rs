@@ -1,17 +1,33 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, RunQueryDsl};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::enums::{TaxonomyFileType, TaxonomySourceType};
-use econ_graph_core::models::XbrlTaxonomySchema;
+use econ_graph_core::models::{
+    DocumentKind, NewXbrlDtsDependency, NewXbrlInstanceDtsReference, XbrlDtsDependency,
+    XbrlInstanceDtsReference, XbrlTaxonomySchema,
+};
 use econ_graph_core::schema::{xbrl_taxonomy_linkbases, xbrl_taxonomy_schemas};
-use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use reqwest::Client;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::fs;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
+use crate::blob_store::BlobStore;
+use crate::dts_resolver::{
+    resolve_location, DtsDocument, DtsGraph, DtsNode, DtsResolutionReport, SchemaDependency,
+};
+use crate::models::CrawlConfig;
+
+/// Upper bound on documents walked for one filing, in case a taxonomy keeps
+/// referring to new locations
+const MAX_DTS_DOCUMENTS: usize = 5_000;
+
 /// **DTS Manager**
 ///
 /// Manages Discoverable Taxonomy Set (DTS) files for XBRL parsing.
@@ -20,12 +36,25 @@ use uuid::Uuid;
 pub struct DtsManager {
     pool: DatabasePool,
     cache_dir: PathBuf,
+    client: Client,
+    blobs: BlobStore,
 }
 
 impl DtsManager {
-    /// Create a new DTS manager
-    pub fn new(pool: DatabasePool, cache_dir: PathBuf) -> Self {
-        Self { pool, cache_dir }
+    /// Create a new DTS manager storing taxonomy files at a zstd `compression_level`
+    pub fn new(pool: DatabasePool, cache_dir: PathBuf, compression_level: Option<i32>) -> Self {
+        let client = Client::builder()
+            .user_agent(CrawlConfig::default().user_agent)
+            .timeout(Duration::from_secs(60))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            blobs: BlobStore::new(pool.clone(), DocumentKind::Taxonomy, compression_level),
+            pool,
+            cache_dir,
+            client,
+        }
     }
 
     /// Get the taxonomy cache directory path
@@ -54,6 +83,293 @@ impl DtsManager {
         Ok(resolutions)
     }
 
+    /// Resolve the full DTS of an XBRL instance
+    ///
+    /// Walks the instance's references and everything they import, include
+    /// or refer to, downloading documents missing from the cache. Dependency
+    /// edges are stored in `xbrl_dts_dependencies` and the outcome of each
+    /// instance reference in `xbrl_instance_dts_references`.
+    pub async fn resolve_dts_graph(
+        &self,
+        instance_file_path: &Path,
+        statement_id: Uuid,
+    ) -> Result<DtsResolutionReport> {
+        let references = self
+            .extract_dts_references_from_instance(instance_file_path)
+            .await?;
+
+        let mut roots: Vec<String> = Vec::new();
+        for reference in &references {
+            let location = resolve_location("", &reference.reference_href);
+            if !roots.contains(&location) {
+                roots.push(location);
+            }
+        }
+
+        let mut graph = DtsGraph::new();
+        let mut queue: VecDeque<String> = roots.iter().cloned().collect();
+        let mut dependencies: Vec<(String, SchemaDependency, String)> = Vec::new();
+        let mut prefetched_count = 0;
+
+        while let Some(location) = queue.pop_front() {
+            if graph.contains(&location) {
+                continue;
+            }
+            if graph.len() >= MAX_DTS_DOCUMENTS {
+                warn!(
+                    "DTS of statement {} exceeds {} documents, stopping discovery",
+                    statement_id, MAX_DTS_DOCUMENTS
+                );
+                break;
+            }
+
+            let loaded = match self.load_dts_document(&location).await {
+                Ok(loaded) => loaded,
+                Err(e) => {
+                    graph.add_node(DtsNode {
+                        location: location.clone(),
+                        schema_id: None,
+                        target_namespace: None,
+                        error: Some(e.to_string()),
+                    });
+                    continue;
+                }
+            };
+            if loaded.prefetched {
+                prefetched_count += 1;
+            }
+
+            let document = DtsDocument::parse(&loaded.content).unwrap_or_else(|e| {
+                warn!("Failed to discover dependencies of {}: {}", location, e);
+                DtsDocument::default()
+            });
+
+            graph.add_node(DtsNode {
+                location: location.clone(),
+                schema_id: Some(loaded.schema_id),
+                target_namespace: document.target_namespace.clone(),
+                error: None,
+            });
+
+            for dependency in document.dependencies {
+                let child = resolve_location(&location, &dependency.location);
+                graph.add_edge(&location, &child, dependency.kind);
+                if !graph.contains(&child) {
+                    queue.push_back(child.clone());
+                }
+                dependencies.push((location.clone(), dependency, child));
+            }
+        }
+
+        for location in queue {
+            if !graph.contains(&location) {
+                graph.add_node(DtsNode {
+                    location,
+                    schema_id: None,
+                    target_namespace: None,
+                    error: Some("Not visited: DTS document limit reached".to_string()),
+                });
+            }
+        }
+
+        self.store_dts_dependencies(&graph, &dependencies).await?;
+        self.store_instance_references(&graph, &references, statement_id)
+            .await?;
+
+        let report =
+            DtsResolutionReport::from_graph(statement_id, &graph, &roots, prefetched_count);
+        debug!(
+            "Resolved DTS of statement {}: {} documents, {} missing, {} cycles",
+            statement_id,
+            report.resolution_order.len(),
+            report.missing.len(),
+            report.cycles.len()
+        );
+
+        Ok(report)
+    }
+
+    /// Download and store dependencies recorded as unresolved by earlier runs
+    ///
+    /// Returns the number of documents stored.
+    pub async fn prefetch_unresolved_dependencies(&self, limit: i64) -> Result<usize> {
+        let unresolved = XbrlDtsDependency::find_unresolved(&self.pool, limit).await?;
+
+        let mut locations: Vec<String> = unresolved
+            .into_iter()
+            .filter_map(|dependency| dependency.dependency_location)
+            .collect();
+        locations.sort();
+        locations.dedup();
+
+        let mut stored = 0;
+        for location in locations {
+            match self.load_dts_document(&location).await {
+                Ok(loaded) => {
+                    XbrlDtsDependency::mark_resolved(&self.pool, &location, loaded.schema_id)
+                        .await?;
+                    stored += 1;
+                }
+                Err(e) => warn!("Failed to prefetch DTS document {}: {}", location, e),
+            }
+        }
+
+        Ok(stored)
+    }
+
+    /// Stored schema and content of a DTS document, downloading it if needed
+    async fn load_dts_document(&self, location: &str) -> Result<LoadedDtsDocument> {
+        if let Some(schema) = self.find_existing_taxonomy(location).await? {
+            let content = match (&schema.blob_hash, schema.file_content.clone()) {
+                (Some(hash), _) => self.blobs.get(hash).await?,
+                (None, Some(content)) => content,
+                (None, None) => fs::read(self.get_local_file_path(&schema))
+                    .await
+                    .with_context(|| format!("Stored taxonomy {} has no content", location))?,
+            };
+            return Ok(LoadedDtsDocument {
+                schema_id: schema.id,
+                content,
+                prefetched: false,
+            });
+        }
+
+        let reference = DtsReference {
+            reference_type: if location.ends_with(".xsd") {
+                "schemaRef".to_string()
+            } else {
+                "linkbaseRef".to_string()
+            },
+            reference_role: None,
+            reference_href: location.to_string(),
+            reference_arcrole: None,
+        };
+
+        let (file_path, prefetched) = match self.find_local_taxonomy_file(location).await? {
+            Some(file_path) => (file_path, false),
+            None if location.starts_with("http://") || location.starts_with("https://") => {
+                (self.download_taxonomy_file(location).await?, true)
+            }
+            None => {
+                return Err(anyhow::anyhow!(
+                    "No local taxonomy file found for: {}",
+                    location
+                ))
+            }
+        };
+
+        let schema_id = self
+            .store_local_taxonomy_file(&file_path, &reference, Uuid::nil())
+            .await?;
+        let content = fs::read(&file_path).await?;
+
+        Ok(LoadedDtsDocument {
+            schema_id,
+            content,
+            prefetched,
+        })
+    }
+
+    /// Download a taxonomy document into the cache directory
+    async fn download_taxonomy_file(&self, url: &str) -> Result<PathBuf> {
+        debug!("Downloading DTS document {}", url);
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Failed to download {}", url))?;
+        let content = response.bytes().await?;
+
+        let file_path = self.cache_dir.join(self.extract_filename_from_href(url));
+        fs::write(&file_path, &content)
+            .await
+            .with_context(|| format!("Failed to cache {}", url))?;
+
+        Ok(file_path)
+    }
+
+    /// Store the dependency edges found while walking a DTS
+    async fn store_dts_dependencies(
+        &self,
+        graph: &DtsGraph,
+        dependencies: &[(String, SchemaDependency, String)],
+    ) -> Result<()> {
+        let mut rows: HashMap<(Uuid, String), NewXbrlDtsDependency> = HashMap::new();
+
+        for (parent, dependency, child) in dependencies {
+            let Some(parent_schema_id) = graph.node(parent).and_then(|node| node.schema_id) else {
+                continue;
+            };
+            let child_schema_id = graph.node(child).and_then(|node| node.schema_id);
+            // Edges are unique per namespace, so includes and references,
+            // which do not name one, are keyed by location
+            let child_namespace = dependency
+                .namespace
+                .clone()
+                .unwrap_or_else(|| child.clone());
+
+            rows.insert(
+                (parent_schema_id, child_namespace.clone()),
+                NewXbrlDtsDependency {
+                    parent_schema_id,
+                    child_schema_id,
+                    child_namespace,
+                    dependency_type: dependency.kind.as_str().to_string(),
+                    dependency_location: Some(child.clone()),
+                    is_resolved: child_schema_id.is_some(),
+                },
+            );
+        }
+
+        let rows: Vec<NewXbrlDtsDependency> = rows.into_values().collect();
+        XbrlDtsDependency::upsert_many(&self.pool, &rows)
+            .await
+            .context("Failed to store DTS dependencies")?;
+
+        Ok(())
+    }
+
+    /// Record how each reference of an instance was resolved
+    async fn store_instance_references(
+        &self,
+        graph: &DtsGraph,
+        references: &[DtsReference],
+        statement_id: Uuid,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let rows: Vec<NewXbrlInstanceDtsReference> = references
+            .iter()
+            .map(|reference| {
+                let location = resolve_location("", &reference.reference_href);
+                let node = graph.node(&location);
+                let resolved_schema_id = node.and_then(|node| node.schema_id);
+
+                NewXbrlInstanceDtsReference {
+                    statement_id,
+                    reference_type: reference.reference_type.clone(),
+                    reference_role: reference.reference_role.clone(),
+                    reference_href: reference.reference_href.clone(),
+                    reference_arcrole: reference.reference_arcrole.clone(),
+                    resolved_schema_id,
+                    resolved_linkbase_id: None,
+                    is_resolved: resolved_schema_id.is_some(),
+                    resolution_error: node.and_then(|node| node.error.clone()),
+                    resolved_at: resolved_schema_id.map(|_| now),
+                }
+            })
+            .collect();
+
+        XbrlInstanceDtsReference::replace_for_statement(&self.pool, statement_id, &rows)
+            .await
+            .context("Failed to store instance DTS references")?;
+
+        Ok(())
+    }
+
     /// Extract DTS references from an XBRL instance file
     async fn extract_dts_references_from_instance(
         &self,
@@ -239,12 +555,10 @@ impl DtsManager {
         statement_id: Uuid,
     ) -> Result<Uuid> {
         let content = fs::read(file_path).await?;
-        let file_size = content.len() as i64;
 
-        // Calculate file hash
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        let file_hash = format!("sha256:{:x}", hasher.finalize());
+        // Stored once however many filings reference it
+        let blob = self.blobs.encode(&content)?;
+        let file_hash = format!("sha256:{}", blob.content_hash);
 
         // Determine file type and source type
         let file_type = if reference.reference_type == "schemaRef" {
@@ -281,12 +595,12 @@ impl DtsManager {
             schema_date: None,
             file_type,
             source_type,
-            file_content: Some(content),
+            file_content: None,
             file_oid: None,
-            file_size_bytes: file_size,
+            file_size_bytes: blob.size_bytes,
             file_hash,
-            is_compressed: false,
-            compression_type: econ_graph_core::enums::CompressionType::None,
+            is_compressed: blob.compression_type != econ_graph_core::enums::CompressionType::None,
+            compression_type: blob.compression_type,
             source_url: Some(reference.reference_href.clone()),
             download_url: Some(reference.reference_href.clone()),
             original_filename: Some(file_path.file_name().unwrap().to_string_lossy().to_string()),
@@ -298,16 +612,26 @@ impl DtsManager {
             relationships_extracted: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            blob_hash: Some(blob.content_hash.clone()),
         };
 
-        // Insert into database
+        // Insert into database, together with the blob it references
         let mut conn = self.pool.get().await?;
-        diesel_async::RunQueryDsl::execute(
-            diesel::insert_into(xbrl_taxonomy_schemas::table).values(&taxonomy_schema),
-            &mut conn,
-        )
-        .await
-        .context("Failed to insert taxonomy schema")?;
+        let schema = &taxonomy_schema;
+        let blob = &blob;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                self.blobs.put(conn, blob).await?;
+                diesel::insert_into(xbrl_taxonomy_schemas::table)
+                    .values(schema)
+                    .execute(conn)
+                    .await
+                    .context("Failed to insert taxonomy schema")?;
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
 
         Ok(schema_id)
     }
@@ -419,6 +743,14 @@ pub struct DtsReference {
     pub reference_arcrole: Option<String>,
 }
 
+/// A DTS document available in the database
+struct LoadedDtsDocument {
+    schema_id: Uuid,
+    content: Vec<u8>,
+    /// Whether it was downloaded to resolve this DTS
+    prefetched: bool,
+}
+
 /// **DTS Resolution**
 ///
 /// Represents the resolution of a DTS reference.
@@ -0,0 +1,619 @@
+//! **DTS Dependency Graph**
+//!
+//! The Discoverable Taxonomy Set of a filing is everything reachable from its
+//! `schemaRef`/`linkbaseRef`s: schemas pull in other schemas through
+//! `xs:import`/`xs:include`, schemas pull in linkbases through `linkbaseRef`,
+//! and linkbase locators point at the schemas defining their concepts.
+//! [`DtsGraph`] holds the documents found while walking those references so
+//! the walk can report what is missing and where taxonomies refer back to
+//! themselves. Cycles are legal in XBRL (us-gaap schemas import each other),
+//! so they are reported but do not stop resolution.
+
+use anyhow::Result;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use uuid::Uuid;
+
+/// How a document refers to one of its dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DependencyKind {
+    /// `xs:import` of another namespace
+    Import,
+    /// `xs:include` of a schema with the same target namespace
+    Include,
+    /// `linkbaseRef` in a schema or `loc` in a linkbase
+    Reference,
+}
+
+impl DependencyKind {
+    /// Value stored in `xbrl_dts_dependencies.dependency_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Import => "import",
+            DependencyKind::Include => "include",
+            DependencyKind::Reference => "reference",
+        }
+    }
+}
+
+/// A dependency declared by a taxonomy document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDependency {
+    pub kind: DependencyKind,
+    /// Imported namespace; `None` for includes and references
+    pub namespace: Option<String>,
+    /// `schemaLocation` or `href` as written in the document, without fragment
+    pub location: String,
+}
+
+/// Target namespace and dependencies of a schema or linkbase
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DtsDocument {
+    pub target_namespace: Option<String>,
+    pub dependencies: Vec<SchemaDependency>,
+}
+
+impl DtsDocument {
+    /// Parse the DTS discovery references out of a schema or linkbase
+    ///
+    /// Imports without a `schemaLocation` are skipped: XBRL only discovers
+    /// documents by location.
+    pub fn parse(content: &[u8]) -> Result<Self> {
+        let mut reader = Reader::from_reader(content);
+        reader.config_mut().trim_text(true);
+
+        let mut document = DtsDocument::default();
+        let mut seen = HashSet::new();
+        let mut buf = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let dependency = match e.local_name().as_ref() {
+                        b"schema" => {
+                            document.target_namespace = attribute(e, b"targetNamespace");
+                            None
+                        }
+                        b"import" => attribute(e, b"schemaLocation").map(|location| {
+                            (DependencyKind::Import, attribute(e, b"namespace"), location)
+                        }),
+                        b"include" => attribute(e, b"schemaLocation")
+                            .map(|location| (DependencyKind::Include, None, location)),
+                        b"linkbaseRef" | b"loc" => attribute(e, b"href")
+                            .map(|location| (DependencyKind::Reference, None, location)),
+                        _ => None,
+                    };
+
+                    if let Some((kind, namespace, location)) = dependency {
+                        let location = strip_fragment(&location).to_string();
+                        if !location.is_empty() && seen.insert(location.clone()) {
+                            document.dependencies.push(SchemaDependency {
+                                kind,
+                                namespace,
+                                location,
+                            });
+                        }
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(anyhow::anyhow!(
+                        "Failed to parse taxonomy document for DTS discovery: {}",
+                        e
+                    ))
+                }
+            }
+            buf.clear();
+        }
+
+        Ok(document)
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .map(|attr| String::from_utf8_lossy(&attr.value).to_string())
+}
+
+fn strip_fragment(href: &str) -> &str {
+    href.split('#').next().unwrap_or(href)
+}
+
+/// Location of a dependency relative to the document declaring it
+///
+/// Relative references from a downloaded taxonomy are resolved against its
+/// URL, so `../dei-2024.xsd` in a FASB schema points back at the FASB site.
+/// Relative references from local filing documents stay as written; filing
+/// documents live side by side in the taxonomy cache.
+pub fn resolve_location(base: &str, location: &str) -> String {
+    let location = strip_fragment(location);
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return location.to_string();
+    }
+
+    match url::Url::parse(base).and_then(|base| base.join(location)) {
+        Ok(resolved) => resolved.to_string(),
+        Err(_) => location.to_string(),
+    }
+}
+
+/// A taxonomy document in the graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DtsNode {
+    pub location: String,
+    /// Stored `xbrl_taxonomy_schemas` row, once the document is available
+    pub schema_id: Option<Uuid>,
+    pub target_namespace: Option<String>,
+    /// Why the document could not be resolved
+    pub error: Option<String>,
+}
+
+impl DtsNode {
+    pub fn is_resolved(&self) -> bool {
+        self.schema_id.is_some()
+    }
+}
+
+/// A document of the DTS with the documents it depends on
+///
+/// Documents reachable along several paths are expanded once; later
+/// occurrences (and the node closing a cycle) have `expanded` unset and no
+/// children.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DtsTreeNode {
+    pub location: String,
+    pub kind: Option<DependencyKind>,
+    pub is_resolved: bool,
+    pub expanded: bool,
+    pub children: Vec<DtsTreeNode>,
+}
+
+/// A document of the DTS that could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingDependency {
+    pub location: String,
+    /// Documents that refer to the missing one
+    pub referenced_by: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Dependency graph of taxonomy documents keyed by location
+#[derive(Debug, Clone, Default)]
+pub struct DtsGraph {
+    nodes: BTreeMap<String, DtsNode>,
+    edges: BTreeMap<String, Vec<(String, DependencyKind)>>,
+}
+
+impl DtsGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, location: &str) -> bool {
+        self.nodes.contains_key(location)
+    }
+
+    pub fn node(&self, location: &str) -> Option<&DtsNode> {
+        self.nodes.get(location)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Add a document, replacing what was known about it
+    pub fn add_node(&mut self, node: DtsNode) {
+        self.nodes.insert(node.location.clone(), node);
+    }
+
+    /// Record that `parent` depends on the document at `child`
+    pub fn add_edge(&mut self, parent: &str, child: &str, kind: DependencyKind) {
+        let children = self.edges.entry(parent.to_string()).or_default();
+        if !children.iter().any(|(location, _)| location == child) {
+            children.push((child.to_string(), kind));
+        }
+    }
+
+    /// Dependencies of a document in declaration order
+    pub fn children(&self, location: &str) -> &[(String, DependencyKind)] {
+        self.edges.get(location).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Locations reachable from `roots`, dependencies before their dependents
+    pub fn resolution_order(&self, roots: &[String]) -> Vec<String> {
+        fn visit(
+            graph: &DtsGraph,
+            location: &str,
+            visited: &mut HashSet<String>,
+            order: &mut Vec<String>,
+        ) {
+            if !visited.insert(location.to_string()) {
+                return;
+            }
+            for (child, _) in graph.children(location) {
+                visit(graph, child, visited, order);
+            }
+            order.push(location.to_string());
+        }
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        for root in roots {
+            visit(self, root, &mut visited, &mut order);
+        }
+        order
+    }
+
+    /// Cycles reachable from `roots`, each listed from its first document
+    /// back to the one that refers to it again
+    pub fn cycles(&self, roots: &[String]) -> Vec<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            InProgress,
+            Done,
+        }
+
+        fn visit(
+            graph: &DtsGraph,
+            location: &str,
+            marks: &mut BTreeMap<String, Mark>,
+            path: &mut Vec<String>,
+            cycles: &mut Vec<Vec<String>>,
+        ) {
+            marks.insert(location.to_string(), Mark::InProgress);
+            path.push(location.to_string());
+
+            for (child, _) in graph.children(location) {
+                match marks.get(child.as_str()) {
+                    None => visit(graph, child, marks, path, cycles),
+                    Some(Mark::InProgress) => {
+                        let start = path.iter().position(|p| p == child).unwrap_or(0);
+                        cycles.push(path[start..].to_vec());
+                    }
+                    Some(Mark::Done) => {}
+                }
+            }
+
+            path.pop();
+            marks.insert(location.to_string(), Mark::Done);
+        }
+
+        let mut marks = BTreeMap::new();
+        let mut cycles = Vec::new();
+        for root in roots {
+            if !marks.contains_key(root.as_str()) {
+                visit(self, root, &mut marks, &mut Vec::new(), &mut cycles);
+            }
+        }
+        cycles
+    }
+
+    /// Unresolved documents reachable from `roots`
+    pub fn missing(&self, roots: &[String]) -> Vec<MissingDependency> {
+        let reachable: BTreeSet<String> = self.resolution_order(roots).into_iter().collect();
+
+        reachable
+            .iter()
+            .filter(|location| {
+                self.nodes
+                    .get(location.as_str())
+                    .is_none_or(|node| !node.is_resolved())
+            })
+            .map(|location| MissingDependency {
+                location: location.clone(),
+                referenced_by: reachable
+                    .iter()
+                    .filter(|parent| {
+                        self.children(parent)
+                            .iter()
+                            .any(|(child, _)| child == location)
+                    })
+                    .cloned()
+                    .collect(),
+                error: self
+                    .nodes
+                    .get(location.as_str())
+                    .and_then(|node| node.error.clone()),
+            })
+            .collect()
+    }
+
+    /// Dependency tree below a document
+    pub fn tree(&self, root: &str) -> DtsTreeNode {
+        fn build(
+            graph: &DtsGraph,
+            location: &str,
+            kind: Option<DependencyKind>,
+            expanded: &mut HashSet<String>,
+        ) -> DtsTreeNode {
+            let is_resolved = graph.node(location).is_some_and(DtsNode::is_resolved);
+            if !expanded.insert(location.to_string()) {
+                return DtsTreeNode {
+                    location: location.to_string(),
+                    kind,
+                    is_resolved,
+                    expanded: false,
+                    children: Vec::new(),
+                };
+            }
+
+            DtsTreeNode {
+                location: location.to_string(),
+                kind,
+                is_resolved,
+                expanded: true,
+                children: graph
+                    .children(location)
+                    .iter()
+                    .map(|(child, kind)| build(graph, child, Some(*kind), expanded))
+                    .collect(),
+            }
+        }
+
+        build(self, root, None, &mut HashSet::new())
+    }
+}
+
+/// Whether every document of a filing's DTS is available
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DtsResolutionStatus {
+    Complete,
+    MissingDependencies,
+}
+
+/// Outcome of resolving the full DTS of a filing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DtsResolutionReport {
+    pub statement_id: Uuid,
+    /// One tree per `schemaRef`/`linkbaseRef` of the instance
+    pub trees: Vec<DtsTreeNode>,
+    /// Every document of the DTS, dependencies first
+    pub resolution_order: Vec<String>,
+    pub resolved_count: usize,
+    /// Documents downloaded during this resolution
+    pub prefetched_count: usize,
+    pub missing: Vec<MissingDependency>,
+    pub cycles: Vec<Vec<String>>,
+    pub status: DtsResolutionStatus,
+}
+
+impl DtsResolutionReport {
+    /// Summarize the graph reachable from an instance's references
+    pub fn from_graph(
+        statement_id: Uuid,
+        graph: &DtsGraph,
+        roots: &[String],
+        prefetched_count: usize,
+    ) -> Self {
+        let resolution_order = graph.resolution_order(roots);
+        let missing = graph.missing(roots);
+        let resolved_count = resolution_order.len() - missing.len();
+
+        Self {
+            statement_id,
+            trees: roots.iter().map(|root| graph.tree(root)).collect(),
+            resolution_order,
+            resolved_count,
+            prefetched_count,
+            status: if missing.is_empty() {
+                DtsResolutionStatus::Complete
+            } else {
+                DtsResolutionStatus::MissingDependencies
+            },
+            missing,
+            cycles: graph.cycles(roots),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPANY_SCHEMA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:link="http://www.xbrl.org/2003/linkbase" xmlns:xlink="http://www.w3.org/1999/xlink" targetNamespace="http://www.apple.com/20240928">
+  <xs:annotation>
+    <xs:appinfo>
+      <link:linkbaseRef xlink:type="simple" xlink:href="aapl-20240928_cal.xml" xlink:role="http://www.xbrl.org/2003/role/calculationLinkbaseRef"/>
+      <link:linkbaseRef xlink:type="simple" xlink:href="aapl-20240928_lab.xml"/>
+    </xs:appinfo>
+  </xs:annotation>
+  <xs:import namespace="http://fasb.org/us-gaap/2024" schemaLocation="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd"/>
+  <xs:import namespace="http://xbrl.sec.gov/dei/2024" schemaLocation="https://xbrl.sec.gov/dei/2024/dei-2024.xsd"/>
+  <xs:import namespace="http://www.xbrl.org/2003/instance"/>
+  <xs:include schemaLocation="aapl-20240928-types.xsd"/>
+</xs:schema>"#;
+
+    fn resolved(graph: &mut DtsGraph, location: &str) {
+        graph.add_node(DtsNode {
+            location: location.to_string(),
+            schema_id: Some(Uuid::new_v4()),
+            target_namespace: None,
+            error: None,
+        });
+    }
+
+    fn unresolved(graph: &mut DtsGraph, location: &str) {
+        graph.add_node(DtsNode {
+            location: location.to_string(),
+            schema_id: None,
+            target_namespace: None,
+            error: Some("Not found".to_string()),
+        });
+    }
+
+    #[test]
+    fn test_parse_schema_dependencies() {
+        // REQUIREMENT: Resolve the full taxonomy dependency tree of a filing
+        // PURPOSE: Verify imports, includes and linkbaseRefs are discovered from a schema
+
+        let document = DtsDocument::parse(COMPANY_SCHEMA.as_bytes()).unwrap();
+
+        assert_eq!(
+            document.target_namespace.as_deref(),
+            Some("http://www.apple.com/20240928")
+        );
+        let found: Vec<(DependencyKind, &str)> = document
+            .dependencies
+            .iter()
+            .map(|d| (d.kind, d.location.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DependencyKind::Reference, "aapl-20240928_cal.xml"),
+                (DependencyKind::Reference, "aapl-20240928_lab.xml"),
+                (
+                    DependencyKind::Import,
+                    "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd"
+                ),
+                (
+                    DependencyKind::Import,
+                    "https://xbrl.sec.gov/dei/2024/dei-2024.xsd"
+                ),
+                (DependencyKind::Include, "aapl-20240928-types.xsd"),
+            ]
+        );
+        assert_eq!(
+            document.dependencies[2].namespace.as_deref(),
+            Some("http://fasb.org/us-gaap/2024")
+        );
+    }
+
+    #[test]
+    fn test_parse_linkbase_locators_once_per_document() {
+        let linkbase = r#"<link:linkbase xmlns:link="http://www.xbrl.org/2003/linkbase" xmlns:xlink="http://www.w3.org/1999/xlink">
+  <link:calculationLink xlink:type="extended">
+    <link:loc xlink:type="locator" xlink:href="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd#us-gaap_Assets" xlink:label="a"/>
+    <link:loc xlink:type="locator" xlink:href="https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd#us-gaap_Liabilities" xlink:label="b"/>
+    <link:loc xlink:type="locator" xlink:href="aapl-20240928.xsd#aapl_Custom" xlink:label="c"/>
+  </link:calculationLink>
+</link:linkbase>"#;
+
+        let document = DtsDocument::parse(linkbase.as_bytes()).unwrap();
+        let locations: Vec<&str> = document
+            .dependencies
+            .iter()
+            .map(|d| d.location.as_str())
+            .collect();
+
+        assert_eq!(
+            locations,
+            vec![
+                "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd",
+                "aapl-20240928.xsd"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_location() {
+        assert_eq!(
+            resolve_location(
+                "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd",
+                "../../../srt/2024/elts/srt-2024.xsd"
+            ),
+            "https://xbrl.fasb.org/srt/2024/elts/srt-2024.xsd"
+        );
+        assert_eq!(
+            resolve_location("aapl-20240928.xsd", "aapl-20240928_lab.xml"),
+            "aapl-20240928_lab.xml"
+        );
+        assert_eq!(
+            resolve_location(
+                "aapl-20240928.xsd",
+                "https://xbrl.sec.gov/dei/2024/dei-2024.xsd#dei_EntityRegistrantName"
+            ),
+            "https://xbrl.sec.gov/dei/2024/dei-2024.xsd"
+        );
+    }
+
+    #[test]
+    fn test_cycles_are_reported_without_breaking_order() {
+        // REQUIREMENT: Detect cycles in the taxonomy dependency graph
+        // PURPOSE: Verify mutually importing schemas are reported and still resolved once each
+
+        let mut graph = DtsGraph::new();
+        for location in ["filing.xsd", "us-gaap.xsd", "us-gaap-types.xsd", "dei.xsd"] {
+            resolved(&mut graph, location);
+        }
+        graph.add_edge("filing.xsd", "us-gaap.xsd", DependencyKind::Import);
+        graph.add_edge("filing.xsd", "dei.xsd", DependencyKind::Import);
+        graph.add_edge("us-gaap.xsd", "us-gaap-types.xsd", DependencyKind::Import);
+        graph.add_edge("us-gaap-types.xsd", "us-gaap.xsd", DependencyKind::Import);
+
+        let roots = vec!["filing.xsd".to_string()];
+
+        assert_eq!(
+            graph.cycles(&roots),
+            vec![vec![
+                "us-gaap.xsd".to_string(),
+                "us-gaap-types.xsd".to_string()
+            ]]
+        );
+        assert_eq!(
+            graph.resolution_order(&roots),
+            vec!["us-gaap-types.xsd", "us-gaap.xsd", "dei.xsd", "filing.xsd"]
+        );
+
+        let tree = graph.tree("filing.xsd");
+        let us_gaap = &tree.children[0];
+        assert!(us_gaap.expanded);
+        assert!(!us_gaap.children[0].children[0].expanded);
+    }
+
+    #[test]
+    fn test_report_lists_missing_dependencies() {
+        // REQUIREMENT: Report DTS resolution status per filing
+        // PURPOSE: Verify unresolved documents are reported with the documents referring to them
+
+        let mut graph = DtsGraph::new();
+        resolved(&mut graph, "filing.xsd");
+        resolved(&mut graph, "filing_lab.xml");
+        unresolved(
+            &mut graph,
+            "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd",
+        );
+        graph.add_edge("filing.xsd", "filing_lab.xml", DependencyKind::Reference);
+        for parent in ["filing.xsd", "filing_lab.xml"] {
+            graph.add_edge(
+                parent,
+                "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd",
+                DependencyKind::Import,
+            );
+        }
+
+        let statement_id = Uuid::new_v4();
+        let report =
+            DtsResolutionReport::from_graph(statement_id, &graph, &["filing.xsd".to_string()], 0);
+
+        assert_eq!(report.status, DtsResolutionStatus::MissingDependencies);
+        assert_eq!(report.resolved_count, 2);
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(
+            report.missing[0].referenced_by,
+            vec!["filing.xsd", "filing_lab.xml"]
+        );
+        assert_eq!(report.missing[0].error.as_deref(), Some("Not found"));
+        assert!(report.cycles.is_empty());
+
+        resolved(
+            &mut graph,
+            "https://xbrl.fasb.org/us-gaap/2024/elts/us-gaap-2024.xsd",
+        );
+        let report =
+            DtsResolutionReport::from_graph(statement_id, &graph, &["filing.xsd".to_string()], 1);
+        assert_eq!(report.status, DtsResolutionStatus::Complete);
+        assert_eq!(report.resolved_count, 3);
+    }
+}
@@ -0,0 +1,445 @@
+//! **Filing Section Extraction**
+//!
+//! Extracts narrative sections (Risk Factors, MD&A, ...) from the HTML primary
+//! document of 10-K and 10-Q filings. The XBRL instance only carries numeric facts;
+//! these sections hold the management commentary that text search and
+//! summarization work against.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+static SCRIPT_STYLE_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<(script|style|head)[^>]*>.*?</(script|style|head)>").unwrap());
+static HIDDEN_XBRL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?is)<ix:header>.*?</ix:header>").unwrap());
+static BLOCK_TAG_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<(br|/p|/div|/tr|/li|/h[1-6]|/table)[^>]*>").unwrap());
+static TAG_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+static NUMERIC_ENTITY_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&#(x[0-9a-fA-F]+|[0-9]+);").unwrap());
+static ITEM_HEADING_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^item\s*(\d{1,2}[a-c]?)\s*[\.:\-\u{2013}\u{2014}]?\s*(.*)$").unwrap()
+});
+static PART_HEADING_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^part\s+(iv|iii|ii|i)\b").unwrap());
+
+/// Narrative sections extracted from periodic filings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FilingSectionKind {
+    /// Item 1 (10-K)
+    Business,
+    /// Item 1A (10-K) / Part II Item 1A (10-Q)
+    RiskFactors,
+    /// Item 3 (10-K) / Part II Item 1 (10-Q)
+    LegalProceedings,
+    /// Item 7 (10-K) / Part I Item 2 (10-Q)
+    ManagementDiscussion,
+    /// Item 7A (10-K) / Part I Item 3 (10-Q)
+    MarketRisk,
+}
+
+impl FilingSectionKind {
+    /// Stable identifier stored in `filing_sections.section_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilingSectionKind::Business => "business",
+            FilingSectionKind::RiskFactors => "risk_factors",
+            FilingSectionKind::LegalProceedings => "legal_proceedings",
+            FilingSectionKind::ManagementDiscussion => "mdna",
+            FilingSectionKind::MarketRisk => "market_risk",
+        }
+    }
+
+    /// Title used when the heading in the document has no text after the item number
+    pub fn default_title(&self) -> &'static str {
+        match self {
+            FilingSectionKind::Business => "Business",
+            FilingSectionKind::RiskFactors => "Risk Factors",
+            FilingSectionKind::LegalProceedings => "Legal Proceedings",
+            FilingSectionKind::ManagementDiscussion => {
+                "Management's Discussion and Analysis of Financial Condition and Results of Operations"
+            }
+            FilingSectionKind::MarketRisk => {
+                "Quantitative and Qualitative Disclosures About Market Risk"
+            }
+        }
+    }
+}
+
+impl fmt::Display for FilingSectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A section located in a filing document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractedFilingSection {
+    pub kind: FilingSectionKind,
+    pub item_number: String,
+    pub part: Option<String>,
+    pub title: String,
+    pub content: String,
+    pub word_count: usize,
+}
+
+impl ExtractedFilingSection {
+    /// SHA-256 of the section text, used to detect changes between crawls
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.content.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Item headings to look for, as (part, item number, kind)
+type SectionTarget = (Option<&'static str>, &'static str, FilingSectionKind);
+
+const ANNUAL_REPORT_TARGETS: &[SectionTarget] = &[
+    (None, "1", FilingSectionKind::Business),
+    (None, "1A", FilingSectionKind::RiskFactors),
+    (None, "3", FilingSectionKind::LegalProceedings),
+    (None, "7", FilingSectionKind::ManagementDiscussion),
+    (None, "7A", FilingSectionKind::MarketRisk),
+];
+
+const QUARTERLY_REPORT_TARGETS: &[SectionTarget] = &[
+    (Some("I"), "2", FilingSectionKind::ManagementDiscussion),
+    (Some("I"), "3", FilingSectionKind::MarketRisk),
+    (Some("II"), "1", FilingSectionKind::LegalProceedings),
+    (Some("II"), "1A", FilingSectionKind::RiskFactors),
+];
+
+/// Item heading found in the plain text of a filing
+#[derive(Debug, Clone)]
+struct ItemHeading {
+    part: Option<String>,
+    item_number: String,
+    title: String,
+    line_index: usize,
+}
+
+/// **Filing Section Extractor**
+///
+/// Converts a filing's HTML primary document to plain text and slices out the
+/// sections between consecutive `Item` headings. The table of contents repeats
+/// every heading, so when an item appears more than once the longest span wins.
+#[derive(Debug, Clone)]
+pub struct FilingSectionExtractor {
+    /// Sections shorter than this many words are treated as cross-references and dropped
+    pub min_word_count: usize,
+}
+
+impl Default for FilingSectionExtractor {
+    fn default() -> Self {
+        Self { min_word_count: 20 }
+    }
+}
+
+impl FilingSectionExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether sections can be extracted for this form type
+    pub fn supports_form(form_type: &str) -> bool {
+        Self::targets_for_form(form_type).is_some()
+    }
+
+    fn targets_for_form(form_type: &str) -> Option<&'static [SectionTarget]> {
+        let base = form_type.trim().to_uppercase();
+        let base = base.split('/').next().unwrap_or_default();
+        match base {
+            "10-K" | "10-K405" | "10-KT" => Some(ANNUAL_REPORT_TARGETS),
+            "10-Q" | "10-QT" => Some(QUARTERLY_REPORT_TARGETS),
+            _ => None,
+        }
+    }
+
+    /// Extract the known sections from an HTML filing document
+    pub fn extract(&self, html: &str, form_type: &str) -> Vec<ExtractedFilingSection> {
+        let Some(targets) = Self::targets_for_form(form_type) else {
+            return Vec::new();
+        };
+
+        let text = html_to_text(html);
+        let lines: Vec<&str> = text.lines().collect();
+        let headings = find_item_headings(&lines);
+        let match_part = targets.iter().any(|(part, _, _)| part.is_some());
+
+        let mut sections = Vec::new();
+        for (part, item_number, kind) in targets {
+            let best = headings
+                .iter()
+                .enumerate()
+                .filter(|(_, heading)| {
+                    heading.item_number.eq_ignore_ascii_case(item_number)
+                        && (!match_part || heading.part.as_deref() == *part)
+                })
+                .map(|(index, heading)| {
+                    let end = headings
+                        .get(index + 1)
+                        .map(|next| next.line_index)
+                        .unwrap_or(lines.len());
+                    let content = lines[heading.line_index + 1..end]
+                        .iter()
+                        .filter(|line| !line.is_empty())
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    (heading, content)
+                })
+                .max_by_key(|(_, content)| content.len());
+
+            if let Some((heading, content)) = best {
+                let word_count = content.split_whitespace().count();
+                if word_count < self.min_word_count {
+                    continue;
+                }
+                let title = if heading.title.is_empty() {
+                    kind.default_title().to_string()
+                } else {
+                    heading.title.clone()
+                };
+                sections.push(ExtractedFilingSection {
+                    kind: *kind,
+                    item_number: item_number.to_string(),
+                    part: part.map(str::to_string),
+                    title,
+                    content,
+                    word_count,
+                });
+            }
+        }
+
+        sections
+    }
+}
+
+/// Locate `Item N.` headings, tracking the enclosing `Part` for 10-Q documents
+fn find_item_headings(lines: &[&str]) -> Vec<ItemHeading> {
+    let mut headings = Vec::new();
+    let mut current_part: Option<String> = None;
+
+    for (line_index, line) in lines.iter().enumerate() {
+        if let Some(captures) = PART_HEADING_PATTERN.captures(line) {
+            current_part = Some(captures[1].to_uppercase());
+            // "PART I - ITEM 2. ..." may share a line with the item heading
+            let rest = line[captures.get(0).unwrap().end()..]
+                .trim_start_matches(|c: char| !c.is_alphanumeric());
+            if let Some(heading) = parse_item_heading(rest, &current_part, line_index) {
+                headings.push(heading);
+            }
+            continue;
+        }
+        if let Some(heading) = parse_item_heading(line, &current_part, line_index) {
+            headings.push(heading);
+        }
+    }
+
+    headings
+}
+
+fn parse_item_heading(line: &str, part: &Option<String>, line_index: usize) -> Option<ItemHeading> {
+    let captures = ITEM_HEADING_PATTERN.captures(line)?;
+    // Headings are short; long lines starting with "Item" are body text
+    if line.len() > 200 {
+        return None;
+    }
+    let title = captures[2]
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c.is_whitespace())
+        .trim()
+        .to_string();
+    Some(ItemHeading {
+        part: part.clone(),
+        item_number: captures[1].to_uppercase(),
+        title,
+        line_index,
+    })
+}
+
+/// Convert an HTML filing document to plain text, one block element per line
+pub fn html_to_text(html: &str) -> String {
+    let text = SCRIPT_STYLE_PATTERN.replace_all(html, " ");
+    let text = HIDDEN_XBRL_PATTERN.replace_all(&text, " ");
+    let text = BLOCK_TAG_PATTERN.replace_all(&text, "\n");
+    let text = TAG_PATTERN.replace_all(&text, "");
+    let text = decode_entities(&text);
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn decode_entities(text: &str) -> String {
+    let text = NUMERIC_ENTITY_PATTERN.replace_all(text, |captures: &regex::Captures| {
+        let value = &captures[1];
+        let code = if let Some(hex) = value.strip_prefix('x') {
+            u32::from_str_radix(hex, 16).ok()
+        } else {
+            value.parse::<u32>().ok()
+        };
+        match code.and_then(char::from_u32) {
+            Some('\u{a0}') => " ".to_string(),
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    });
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&rsquo;", "\u{2019}")
+        .replace("&lsquo;", "\u{2018}")
+        .replace("&ldquo;", "\u{201c}")
+        .replace("&rdquo;", "\u{201d}")
+        .replace("&mdash;", "\u{2014}")
+        .replace("&ndash;", "\u{2013}")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(words: usize, word: &str) -> String {
+        format!("<p>{}</p>", vec![word; words].join(" "))
+    }
+
+    fn sample_10k() -> String {
+        format!(
+            r#"<html><head><style>p {{ color: black; }}</style></head><body>
+            <table>
+              <tr><td>Item 1.</td></tr><tr><td>Business</td></tr>
+              <tr><td>Item 1A. Risk Factors 12</td></tr>
+              <tr><td>Item 7. Management&#8217;s Discussion and Analysis 30</td></tr>
+              <tr><td>Item 7A. Quantitative and Qualitative Disclosures About Market Risk 45</td></tr>
+              <tr><td>Item 8. Financial Statements 47</td></tr>
+            </table>
+            <p>PART I</p>
+            <p>Item 1. Business</p>
+            {business}
+            <p>Item 1A.&nbsp;Risk Factors</p>
+            {risks}
+            <p>Item 7. Management&#8217;s Discussion and Analysis</p>
+            {mdna}
+            <p>Item 7A. Quantitative and Qualitative Disclosures About Market Risk</p>
+            {market}
+            <p>Item 8. Financial Statements</p>
+            {financials}
+            </body></html>"#,
+            business = paragraph(40, "business"),
+            risks = paragraph(60, "risk"),
+            mdna = paragraph(80, "revenue"),
+            market = paragraph(30, "rates"),
+            financials = paragraph(50, "balance"),
+        )
+    }
+
+    #[test]
+    fn test_extract_10k_sections_skips_table_of_contents() {
+        // REQUIREMENT: Risk Factors and MD&A must be extracted from 10-K primary documents
+        // PURPOSE: Verify that the body section is chosen over the table of contents entry
+        // This ensures stored sections contain narrative text rather than page references
+
+        let sections = FilingSectionExtractor::new().extract(&sample_10k(), "10-K");
+
+        let risks = sections
+            .iter()
+            .find(|s| s.kind == FilingSectionKind::RiskFactors)
+            .expect("risk factors section");
+        assert_eq!(risks.item_number, "1A");
+        assert_eq!(risks.title, "Risk Factors");
+        assert_eq!(risks.word_count, 60);
+        assert!(risks.content.split_whitespace().all(|w| w == "risk"));
+
+        let mdna = sections
+            .iter()
+            .find(|s| s.kind == FilingSectionKind::ManagementDiscussion)
+            .expect("MD&A section");
+        assert_eq!(mdna.title, "Management\u{2019}s Discussion and Analysis");
+        assert_eq!(mdna.word_count, 80);
+
+        let market = sections
+            .iter()
+            .find(|s| s.kind == FilingSectionKind::MarketRisk)
+            .expect("market risk section");
+        assert_eq!(market.item_number, "7A");
+        assert_eq!(market.word_count, 30);
+    }
+
+    #[test]
+    fn test_extract_10q_sections_uses_parts() {
+        // REQUIREMENT: 10-Q filings number MD&A and Risk Factors differently than 10-Ks
+        // PURPOSE: Verify that Part I Item 2 and Part II Item 1A are mapped correctly
+        // This ensures quarterly filings produce the same section types as annual filings
+
+        let html = format!(
+            r#"<div>PART I &#8212; FINANCIAL INFORMATION</div>
+            <div>Item 1. Financial Statements</div>{}
+            <div>Item 2. Management's Discussion and Analysis</div>{}
+            <div>Item 4. Controls and Procedures</div>{}
+            <div>PART II &#8212; OTHER INFORMATION</div>
+            <div>Item 1. Legal Proceedings</div>{}
+            <div>Item 1A. Risk Factors</div>{}
+            <div>Item 6. Exhibits</div>"#,
+            paragraph(30, "assets"),
+            paragraph(45, "sales"),
+            paragraph(25, "controls"),
+            paragraph(22, "lawsuit"),
+            paragraph(35, "risk"),
+        );
+
+        let sections = FilingSectionExtractor::new().extract(&html, "10-Q");
+        let kinds: Vec<_> = sections.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FilingSectionKind::ManagementDiscussion,
+                FilingSectionKind::LegalProceedings,
+                FilingSectionKind::RiskFactors,
+            ]
+        );
+        assert_eq!(sections[0].part.as_deref(), Some("I"));
+        assert_eq!(sections[0].word_count, 45);
+        assert_eq!(sections[2].part.as_deref(), Some("II"));
+        assert_eq!(sections[2].word_count, 35);
+    }
+
+    #[test]
+    fn test_unsupported_forms_and_short_sections() {
+        // REQUIREMENT: Only periodic reports carry the itemized narrative sections
+        // PURPOSE: Verify other forms are ignored and cross-reference stubs are dropped
+        // This prevents storing empty "see Item 7" placeholders as sections
+
+        assert!(FilingSectionExtractor::supports_form("10-K/A"));
+        assert!(FilingSectionExtractor::supports_form("10-q"));
+        assert!(!FilingSectionExtractor::supports_form("8-K"));
+        assert!(FilingSectionExtractor::new()
+            .extract(&sample_10k(), "8-K")
+            .is_empty());
+
+        let html = "<p>Item 1A. Risk Factors</p><p>See Item 7.</p><p>Item 2. Properties</p>";
+        assert!(FilingSectionExtractor::new()
+            .extract(html, "10-K")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        // REQUIREMENT: Section content must be stored as searchable plain text
+        // PURPOSE: Verify markup, scripts and entities are removed from filing HTML
+        // This ensures full-text indexes are built over readable words only
+
+        let text = html_to_text(
+            "<script>var x = 1;</script><p>Cash&nbsp;&amp; equivalents</p><div>Q&#x2019;s <b>net</b>   income</div>",
+        );
+        assert_eq!(text.trim(), "Cash & equivalents\nQ\u{2019}s net income");
+    }
+}
@@ -990,6 +990,7 @@ mod tests {
                 restatement_reason: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                xbrl_file_blob_hash: None,
             };
 
             let line_items = vec![
@@ -1017,6 +1018,12 @@ mod tests {
                     calculation_formula: None,
                     created_at: Utc::now(),
                     updated_at: Utc::now(),
+                    normalized_value: None,
+                    normalized_unit: None,
+                    fx_rate: None,
+                    dimensions: serde_json::json!({}),
+                    period_start_date: None,
+                    period_end_date: None,
                 },
                 FinancialLineItem {
                     id: Uuid::new_v4(),
@@ -1042,6 +1049,12 @@ mod tests {
                     calculation_formula: None,
                     created_at: Utc::now(),
                     updated_at: Utc::now(),
+                    normalized_value: None,
+                    normalized_unit: None,
+                    fx_rate: None,
+                    dimensions: serde_json::json!({}),
+                    period_start_date: None,
+                    period_end_date: None,
                 },
             ];
 
@@ -0,0 +1,553 @@
+//! Insider transactions from SEC Form 4
+//!
+//! Officers, directors and 10% owners report trades in their company's
+//! securities on Form 4 within two business days. The crawl walks EDGAR's
+//! daily master index: each Form 4 and 4/A filed on a day is downloaded, its
+//! ownership XML is parsed, and every transaction line is stored in
+//! `insider_transactions`. Crawled days are recorded in `insider_crawl_days`,
+//! so each run continues after the last day crawled.
+//!
+//! A filing with several reporting owners (a joint filing) is stored once,
+//! under the first owner, so its shares are not counted twice. Holdings
+//! reported without a transaction are not stored.
+
+use anyhow::{Context, Result};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use roxmltree::{Document, Node};
+use serde::Serialize;
+use std::str::FromStr;
+use tokio_cron_scheduler::{Job, JobScheduler};
+use tracing::{error, info, warn};
+
+use crate::crawler::SecEdgarCrawler;
+use crate::daily_index::{days_to_crawl, IndexedFiling};
+use crate::utils::{build_archive_url, pad_cik, xml_child, xml_text};
+use econ_graph_core::models::{InsiderTransaction, NewInsiderTransaction};
+use econ_graph_core::schema::insider_crawl_days;
+use econ_graph_core::shutdown::shutdown_coordinator;
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Default crawl schedule: daily at 07:00 UTC, after EDGAR publishes the
+/// previous day's index
+pub const DEFAULT_INSIDER_CRAWL_SCHEDULE: &str = "0 0 7 * * *";
+
+/// Form types parsed as Form 4
+const FORM_4_TYPES: [&str; 2] = ["4", "4/A"];
+
+/// Longest name or title stored, matching the column sizes
+const MAX_TEXT_LENGTH: usize = 255;
+
+/// Outcome of crawling one day of the index
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsiderDayReport {
+    pub index_date: NaiveDate,
+    /// Form 4 filings in the index
+    pub filings_found: usize,
+    /// Filings whose transactions were stored by an earlier crawl
+    pub filings_skipped: usize,
+    pub filings_stored: usize,
+    pub filings_failed: usize,
+    pub transactions_stored: usize,
+    pub errors: Vec<String>,
+}
+
+impl InsiderDayReport {
+    fn new(index_date: NaiveDate) -> Self {
+        Self {
+            index_date,
+            filings_found: 0,
+            filings_skipped: 0,
+            filings_stored: 0,
+            filings_failed: 0,
+            transactions_stored: 0,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of an incremental insider crawl
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InsiderCrawlReport {
+    /// One entry per day crawled, oldest first
+    pub days: Vec<InsiderDayReport>,
+}
+
+impl InsiderCrawlReport {
+    pub fn filings_stored(&self) -> usize {
+        self.days.iter().map(|day| day.filings_stored).sum()
+    }
+
+    pub fn filings_failed(&self) -> usize {
+        self.days.iter().map(|day| day.filings_failed).sum()
+    }
+
+    pub fn transactions_stored(&self) -> usize {
+        self.days.iter().map(|day| day.transactions_stored).sum()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = insider_crawl_days)]
+struct CrawledDay {
+    index_date: NaiveDate,
+    filings_found: i32,
+    filings_stored: i32,
+    filings_failed: i32,
+    transactions_stored: i32,
+}
+
+/// The `<ownershipDocument>` XML embedded in a full submission text file
+pub fn extract_ownership_xml(submission: &str) -> Option<&str> {
+    const END_TAG: &str = "</ownershipDocument>";
+
+    let start = submission.find("<ownershipDocument")?;
+    let end = start + submission[start..].find(END_TAG)? + END_TAG.len();
+    Some(&submission[start..end])
+}
+
+/// Text of a `<value>` element below `path`, the form of most Form 4 fields
+fn value<'a>(node: Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    let mut current = node;
+    for tag in path {
+        current = xml_child(current, tag)?;
+    }
+    xml_text(current, &["value"])
+}
+
+/// Form 4 flags are written as 1/0 or true/false
+fn flag(node: Option<Node<'_, '_>>, tag: &str) -> bool {
+    node.and_then(|n| xml_text(n, &[tag]))
+        .is_some_and(|t| t == "1" || t.eq_ignore_ascii_case("true"))
+}
+
+fn truncate(text: &str) -> String {
+    text.chars().take(MAX_TEXT_LENGTH).collect()
+}
+
+/// Dates may carry a time zone offset, e.g. `2024-01-02-05:00`
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Issuer and reporting owner shared by every line of a filing
+struct Form4Parties<'a> {
+    filing: &'a IndexedFiling,
+    issuer_cik: String,
+    issuer_name: String,
+    issuer_ticker: Option<String>,
+    insider_cik: String,
+    insider_name: String,
+    is_director: bool,
+    is_officer: bool,
+    is_ten_percent_owner: bool,
+    is_other: bool,
+    officer_title: Option<String>,
+}
+
+/// Parse the transactions of a Form 4 ownership document
+///
+/// Lines are numbered across the non-derivative and derivative tables in
+/// document order. A line missing its date, code, share count or
+/// acquired/disposed code is skipped but keeps its number, so the numbering
+/// of a filing does not change between parses.
+pub fn parse_form4(xml: &str, filing: &IndexedFiling) -> Result<Vec<NewInsiderTransaction>> {
+    let document = Document::parse(xml).context("Invalid Form 4 XML")?;
+    let root = document.root_element();
+
+    let issuer = xml_child(root, "issuer").context("Form 4 has no issuer")?;
+    let owner = xml_child(root, "reportingOwner").context("Form 4 has no reporting owner")?;
+    let relationship = xml_child(owner, "reportingOwnerRelationship");
+    let parties = Form4Parties {
+        filing,
+        issuer_cik: pad_cik(xml_text(issuer, &["issuerCik"]).context("Form 4 has no issuer CIK")?),
+        issuer_name: truncate(xml_text(issuer, &["issuerName"]).unwrap_or_default()),
+        issuer_ticker: xml_text(issuer, &["issuerTradingSymbol"])
+            .map(str::to_uppercase)
+            .filter(|ticker| ticker.len() <= 10 && ticker != "NONE" && ticker != "N/A"),
+        insider_cik: pad_cik(
+            xml_text(owner, &["reportingOwnerId", "rptOwnerCik"])
+                .context("Form 4 has no reporting owner CIK")?,
+        ),
+        insider_name: truncate(
+            xml_text(owner, &["reportingOwnerId", "rptOwnerName"]).unwrap_or_default(),
+        ),
+        is_director: flag(relationship, "isDirector"),
+        is_officer: flag(relationship, "isOfficer"),
+        is_ten_percent_owner: flag(relationship, "isTenPercentOwner"),
+        is_other: flag(relationship, "isOther"),
+        officer_title: relationship
+            .and_then(|r| xml_text(r, &["officerTitle"]))
+            .map(truncate),
+    };
+
+    let tables = [
+        ("nonDerivativeTable", "nonDerivativeTransaction", false),
+        ("derivativeTable", "derivativeTransaction", true),
+    ];
+    let lines = tables.iter().flat_map(|(table, row, is_derivative)| {
+        xml_child(root, table)
+            .into_iter()
+            .flat_map(move |t| t.children().filter(move |n| n.has_tag_name(*row)))
+            .map(move |line| (line, *is_derivative))
+    });
+
+    let mut transactions = Vec::new();
+    for (index, (line, is_derivative)) in lines.enumerate() {
+        let line_number = index as i32 + 1;
+        match parse_transaction_line(line, is_derivative, line_number, &parties) {
+            Some(transaction) => transactions.push(transaction),
+            None => warn!(
+                "Skipping incomplete transaction line {} of {}",
+                line_number, filing.accession_number
+            ),
+        }
+    }
+
+    Ok(transactions)
+}
+
+fn parse_transaction_line(
+    line: Node<'_, '_>,
+    is_derivative: bool,
+    line_number: i32,
+    parties: &Form4Parties<'_>,
+) -> Option<NewInsiderTransaction> {
+    let transaction_date = parse_date(value(line, &["transactionDate"])?)?;
+    let transaction_code =
+        xml_text(line, &["transactionCoding", "transactionCode"]).filter(|code| code.len() == 1)?;
+    let shares =
+        BigDecimal::from_str(value(line, &["transactionAmounts", "transactionShares"])?).ok()?;
+    let acquired_disposed = value(
+        line,
+        &["transactionAmounts", "transactionAcquiredDisposedCode"],
+    )
+    .filter(|code| matches!(*code, "A" | "D"))?;
+    let ownership_nature = match value(line, &["ownershipNature", "directOrIndirectOwnership"]) {
+        Some("I") => "I",
+        _ => "D",
+    };
+
+    Some(NewInsiderTransaction {
+        accession_number: parties.filing.accession_number.clone(),
+        form_type: parties.filing.form_type.clone(),
+        filed_date: parties.filing.filed_date,
+        line_number,
+        issuer_cik: parties.issuer_cik.clone(),
+        issuer_name: parties.issuer_name.clone(),
+        issuer_ticker: parties.issuer_ticker.clone(),
+        insider_cik: parties.insider_cik.clone(),
+        insider_name: parties.insider_name.clone(),
+        is_director: parties.is_director,
+        is_officer: parties.is_officer,
+        is_ten_percent_owner: parties.is_ten_percent_owner,
+        is_other: parties.is_other,
+        officer_title: parties.officer_title.clone(),
+        security_title: truncate(value(line, &["securityTitle"]).unwrap_or_default()),
+        is_derivative,
+        transaction_date,
+        transaction_code: transaction_code.to_string(),
+        shares,
+        price_per_share: value(line, &["transactionAmounts", "transactionPricePerShare"])
+            .and_then(|price| BigDecimal::from_str(price).ok()),
+        acquired_disposed: acquired_disposed.to_string(),
+        shares_owned_after: value(
+            line,
+            &["postTransactionAmounts", "sharesOwnedFollowingTransaction"],
+        )
+        .and_then(|shares| BigDecimal::from_str(shares).ok()),
+        ownership_nature: ownership_nature.to_string(),
+    })
+}
+
+impl SecEdgarCrawler {
+    /// Crawl Form 4 filings for the days since the last crawl, up to yesterday
+    #[tracing::instrument(name = "sec.crawl_insider_transactions", skip(self))]
+    pub async fn crawl_insider_transactions(&self) -> Result<InsiderCrawlReport> {
+        let mut conn = self.pool.get().await?;
+        let last_crawled: Option<NaiveDate> = insider_crawl_days::table
+            .select(diesel::dsl::max(insider_crawl_days::index_date))
+            .first(&mut conn)
+            .await
+            .context("Failed to load last insider crawl day")?;
+        drop(conn);
+
+        let yesterday = Utc::now().date_naive() - Duration::days(1);
+        let mut report = InsiderCrawlReport::default();
+        for day in days_to_crawl(last_crawled, yesterday) {
+            report.days.push(self.crawl_insider_day(day).await?);
+        }
+
+        info!(
+            "Insider crawl complete: {} days, {} filings stored, {} failed, {} transactions",
+            report.days.len(),
+            report.filings_stored(),
+            report.filings_failed(),
+            report.transactions_stored()
+        );
+
+        Ok(report)
+    }
+
+    /// Crawl the Form 4 filings of one day of the EDGAR index
+    ///
+    /// Filings with stored transactions are skipped, so a day can be crawled
+    /// again to retry the filings that failed.
+    #[tracing::instrument(name = "sec.crawl_insider_day", skip(self))]
+    pub async fn crawl_insider_day(&self, date: NaiveDate) -> Result<InsiderDayReport> {
+        let mut report = InsiderDayReport::new(date);
+
+        let filings = self.fetch_daily_index(date, &FORM_4_TYPES).await?;
+        report.filings_found = filings.len();
+
+        let accession_numbers: Vec<String> = filings
+            .iter()
+            .map(|filing| filing.accession_number.clone())
+            .collect();
+        let stored = InsiderTransaction::stored_accessions(&self.pool, &accession_numbers)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load stored Form 4 filings: {}", e))?;
+
+        for filing in &filings {
+            if stored.contains(&filing.accession_number) {
+                report.filings_skipped += 1;
+                continue;
+            }
+
+            match self.store_form4(filing).await {
+                Ok(count) => {
+                    report.filings_stored += 1;
+                    report.transactions_stored += count;
+                }
+                Err(e) => {
+                    warn!("Failed to store Form 4 {}: {}", filing.accession_number, e);
+                    CRAWLER_METRICS.record_error("sec", "edgar", "form4_parse");
+                    report.filings_failed += 1;
+                    report
+                        .errors
+                        .push(format!("{}: {}", filing.accession_number, e));
+                }
+            }
+        }
+
+        self.record_insider_crawl_day(&report).await?;
+        CRAWLER_METRICS.record_items_collected(
+            "sec",
+            "edgar",
+            "insider_transaction",
+            report.transactions_stored as u64,
+        );
+        info!(
+            "Crawled Form 4 filings for {}: {} found, {} stored, {} failed",
+            date, report.filings_found, report.filings_stored, report.filings_failed
+        );
+
+        Ok(report)
+    }
+
+    /// Download, parse and store one Form 4
+    async fn store_form4(&self, filing: &IndexedFiling) -> Result<usize> {
+        let submission = self
+            .fetch_archive_text(&build_archive_url(&filing.file_name), "/form4")
+            .await?
+            .context("Filing not found")?;
+        let xml = extract_ownership_xml(&submission).context("Filing has no ownership XML")?;
+        let transactions = parse_form4(xml, filing)?;
+
+        InsiderTransaction::insert_many(&self.pool, &transactions)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store insider transactions: {}", e))
+    }
+
+    async fn record_insider_crawl_day(&self, report: &InsiderDayReport) -> Result<()> {
+        let day = CrawledDay {
+            index_date: report.index_date,
+            filings_found: report.filings_found as i32,
+            filings_stored: report.filings_stored as i32,
+            filings_failed: report.filings_failed as i32,
+            transactions_stored: report.transactions_stored as i32,
+        };
+
+        let mut conn = self.pool.get().await?;
+        diesel::insert_into(insider_crawl_days::table)
+            .values(&day)
+            .on_conflict(insider_crawl_days::index_date)
+            .do_update()
+            .set((
+                insider_crawl_days::filings_found.eq(excluded(insider_crawl_days::filings_found)),
+                insider_crawl_days::filings_stored.eq(insider_crawl_days::filings_stored
+                    + excluded(insider_crawl_days::filings_stored)),
+                insider_crawl_days::filings_failed.eq(excluded(insider_crawl_days::filings_failed)),
+                insider_crawl_days::transactions_stored.eq(insider_crawl_days::transactions_stored
+                    + excluded(insider_crawl_days::transactions_stored)),
+                insider_crawl_days::crawled_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await
+            .context("Failed to record insider crawl day")?;
+
+        Ok(())
+    }
+}
+
+/// Run [`SecEdgarCrawler::crawl_insider_transactions`] on a cron schedule
+///
+/// `schedule` uses the six-field cron format with seconds, e.g.
+/// [`DEFAULT_INSIDER_CRAWL_SCHEDULE`]. The returned scheduler is already
+/// started; keep it alive for as long as crawls should run.
+pub async fn schedule_insider_crawl(
+    crawler: SecEdgarCrawler,
+    schedule: &str,
+) -> Result<JobScheduler> {
+    let scheduler = JobScheduler::new().await?;
+
+    let job = Job::new_async(schedule, move |_id, _scheduler| {
+        let crawler = crawler.clone();
+        Box::pin(async move {
+            // Runs that start while draining for shutdown are skipped
+            let Some(_in_flight) = shutdown_coordinator().try_track() else {
+                return;
+            };
+            if let Err(e) = crawler.crawl_insider_transactions().await {
+                error!("Scheduled insider transaction crawl failed: {}", e);
+            }
+        })
+    })
+    .with_context(|| format!("Invalid insider crawl schedule: {}", schedule))?;
+
+    scheduler.add(job).await?;
+    scheduler.start().await?;
+    info!("Insider transaction crawl scheduled: {}", schedule);
+
+    Ok(scheduler)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORM_4: &str = r#"<SEC-DOCUMENT>0000320193-24-000006.txt : 20240104
+<DOCUMENT>
+<TYPE>4
+<TEXT>
+<XML>
+<?xml version="1.0"?>
+<ownershipDocument>
+    <schemaVersion>X0508</schemaVersion>
+    <documentType>4</documentType>
+    <issuer>
+        <issuerCik>0000320193</issuerCik>
+        <issuerName>Apple Inc.</issuerName>
+        <issuerTradingSymbol>aapl</issuerTradingSymbol>
+    </issuer>
+    <reportingOwner>
+        <reportingOwnerId>
+            <rptOwnerCik>0001214156</rptOwnerCik>
+            <rptOwnerName>Cook Timothy D</rptOwnerName>
+        </reportingOwnerId>
+        <reportingOwnerRelationship>
+            <isDirector>1</isDirector>
+            <isOfficer>true</isOfficer>
+            <isTenPercentOwner>0</isTenPercentOwner>
+            <officerTitle>Chief Executive Officer</officerTitle>
+        </reportingOwnerRelationship>
+    </reportingOwner>
+    <nonDerivativeTable>
+        <nonDerivativeTransaction>
+            <securityTitle><value>Common Stock</value></securityTitle>
+            <transactionDate><value>2024-01-02</value></transactionDate>
+            <transactionCoding>
+                <transactionFormType>4</transactionFormType>
+                <transactionCode>S</transactionCode>
+            </transactionCoding>
+            <transactionAmounts>
+                <transactionShares><value>1000</value></transactionShares>
+                <transactionPricePerShare><value>185.50</value></transactionPricePerShare>
+                <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+            </transactionAmounts>
+            <postTransactionAmounts>
+                <sharesOwnedFollowingTransaction><value>3280000</value></sharesOwnedFollowingTransaction>
+            </postTransactionAmounts>
+            <ownershipNature>
+                <directOrIndirectOwnership><value>D</value></directOrIndirectOwnership>
+            </ownershipNature>
+        </nonDerivativeTransaction>
+        <nonDerivativeTransaction>
+            <securityTitle><value>Common Stock</value></securityTitle>
+            <transactionCoding><transactionCode>S</transactionCode></transactionCoding>
+        </nonDerivativeTransaction>
+        <nonDerivativeHolding>
+            <securityTitle><value>Common Stock</value></securityTitle>
+        </nonDerivativeHolding>
+    </nonDerivativeTable>
+    <derivativeTable>
+        <derivativeTransaction>
+            <securityTitle><value>Restricted Stock Unit</value></securityTitle>
+            <transactionDate><value>2024-01-02-05:00</value></transactionDate>
+            <transactionCoding><transactionCode>M</transactionCode></transactionCoding>
+            <transactionAmounts>
+                <transactionShares><value>500</value></transactionShares>
+                <transactionPricePerShare><footnoteId id="F1"/></transactionPricePerShare>
+                <transactionAcquiredDisposedCode><value>D</value></transactionAcquiredDisposedCode>
+            </transactionAmounts>
+            <ownershipNature>
+                <directOrIndirectOwnership><value>I</value></directOrIndirectOwnership>
+            </ownershipNature>
+        </derivativeTransaction>
+    </derivativeTable>
+</ownershipDocument>
+</XML>
+</TEXT>
+</DOCUMENT>
+</SEC-DOCUMENT>"#;
+
+    #[test]
+    fn test_parse_form4_transactions() {
+        // REQUIREMENT: Store insider, relationship, transaction code, shares and price from Form 4
+        // PURPOSE: Verify both transaction tables are parsed and incomplete lines are skipped
+        // This ensures insider activity queries see every reported trade with stable line numbers
+
+        let filing = IndexedFiling {
+            form_type: "4".to_string(),
+            filed_date: NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(),
+            accession_number: "0000320193-24-000006".to_string(),
+            file_name: "edgar/data/320193/0000320193-24-000006.txt".to_string(),
+        };
+        let xml = extract_ownership_xml(FORM_4).unwrap();
+
+        let transactions = parse_form4(xml, &filing).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        let sale = &transactions[0];
+        assert_eq!(sale.line_number, 1);
+        assert_eq!(sale.issuer_ticker.as_deref(), Some("AAPL"));
+        assert_eq!(sale.insider_cik, "0001214156");
+        assert!(sale.is_director && sale.is_officer && !sale.is_ten_percent_owner);
+        assert_eq!(
+            sale.officer_title.as_deref(),
+            Some("Chief Executive Officer")
+        );
+        assert_eq!(sale.transaction_code, "S");
+        assert_eq!(sale.shares, BigDecimal::from(1000));
+        assert_eq!(
+            sale.price_per_share,
+            Some(BigDecimal::from_str("185.50").unwrap())
+        );
+        assert_eq!(sale.acquired_disposed, "D");
+        assert!(!sale.is_derivative);
+
+        let exercise = &transactions[1];
+        assert_eq!(exercise.line_number, 3);
+        assert!(exercise.is_derivative);
+        assert_eq!(exercise.transaction_code, "M");
+        assert_eq!(
+            exercise.transaction_date,
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()
+        );
+        assert_eq!(exercise.price_per_share, None);
+        assert_eq!(exercise.ownership_nature, "I");
+    }
+}
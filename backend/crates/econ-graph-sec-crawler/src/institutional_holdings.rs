@@ -0,0 +1,704 @@
+//! Institutional holdings from SEC Form 13F-HR
+//!
+//! Investment managers with over $100M in US equities report their
+//! positions within 45 days of each quarter end. The crawl walks EDGAR's
+//! daily master index for 13F-HR and 13F-HR/A filings, parses the cover page
+//! and information table XML of each, and stores the rows as the manager's
+//! holdings snapshot for the quarter. Crawled days are recorded in
+//! `institutional_crawl_days`, so each run continues after the last day
+//! crawled.
+//!
+//! 13F rows name securities by CUSIP. A CUSIP is mapped to a company the
+//! first time it is seen, by matching the issuer name against company names;
+//! names matching no company, or several, are left unmapped.
+
+use anyhow::{Context, Result};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use roxmltree::{Document, Node};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use tokio_cron_scheduler::{Job, JobScheduler};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::crawler::SecEdgarCrawler;
+use crate::daily_index::{days_to_crawl, IndexedFiling};
+use crate::utils::{build_archive_url, pad_cik, parse_sec_date, xml_child, xml_text};
+use econ_graph_core::models::{
+    CusipMapping, InstitutionalFiling, NewCusipMapping, NewInstitutionalFiling,
+    NewInstitutionalHolding, AMENDMENT_NEW_HOLDINGS, AMENDMENT_RESTATEMENT,
+};
+use econ_graph_core::schema::{companies, institutional_crawl_days};
+use econ_graph_core::shutdown::shutdown_coordinator;
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Default crawl schedule: daily at 07:30 UTC, after the Form 4 crawl
+pub const DEFAULT_INSTITUTIONAL_CRAWL_SCHEDULE: &str = "0 30 7 * * *";
+
+/// Form types parsed as 13F holdings reports
+const FORM_13F_TYPES: [&str; 2] = ["13F-HR", "13F-HR/A"];
+
+/// First filing day on which information table values are in dollars;
+/// earlier filings report thousands of dollars
+const DOLLAR_VALUES_SINCE: (i32, u32, u32) = (2023, 1, 3);
+
+/// Words dropped from the end of issuer and company names before matching
+const NAME_SUFFIXES: [&str; 19] = [
+    "INC",
+    "INCORPORATED",
+    "CORP",
+    "CORPORATION",
+    "CO",
+    "COMPANY",
+    "LTD",
+    "LIMITED",
+    "PLC",
+    "LLC",
+    "LP",
+    "SA",
+    "NV",
+    "AG",
+    "THE",
+    "DE",
+    "DEL",
+    "NEW",
+    "COM",
+];
+
+/// Outcome of crawling one day of the index
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InstitutionalDayReport {
+    pub index_date: NaiveDate,
+    /// 13F-HR filings in the index
+    pub filings_found: usize,
+    /// Filings stored by an earlier crawl
+    pub filings_skipped: usize,
+    pub filings_stored: usize,
+    pub filings_failed: usize,
+    pub holdings_stored: usize,
+    /// CUSIPs mapped to a company for the first time
+    pub cusips_mapped: usize,
+    pub errors: Vec<String>,
+}
+
+impl InstitutionalDayReport {
+    fn new(index_date: NaiveDate) -> Self {
+        Self {
+            index_date,
+            filings_found: 0,
+            filings_skipped: 0,
+            filings_stored: 0,
+            filings_failed: 0,
+            holdings_stored: 0,
+            cusips_mapped: 0,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of an incremental 13F crawl
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct InstitutionalCrawlReport {
+    /// One entry per day crawled, oldest first
+    pub days: Vec<InstitutionalDayReport>,
+}
+
+impl InstitutionalCrawlReport {
+    pub fn filings_stored(&self) -> usize {
+        self.days.iter().map(|day| day.filings_stored).sum()
+    }
+
+    pub fn filings_failed(&self) -> usize {
+        self.days.iter().map(|day| day.filings_failed).sum()
+    }
+
+    pub fn holdings_stored(&self) -> usize {
+        self.days.iter().map(|day| day.holdings_stored).sum()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = institutional_crawl_days)]
+struct CrawledDay {
+    index_date: NaiveDate,
+    filings_found: i32,
+    filings_stored: i32,
+    filings_failed: i32,
+    holdings_stored: i32,
+}
+
+/// Cover page and information table of a 13F filing
+#[derive(Debug, Clone, PartialEq)]
+pub struct Form13F {
+    pub filing: NewInstitutionalFiling,
+    pub holdings: Vec<NewInstitutionalHolding>,
+}
+
+/// The `<XML>` documents embedded in a full submission text file
+pub fn extract_xml_documents(submission: &str) -> Vec<&str> {
+    const START_TAG: &str = "<XML>";
+    const END_TAG: &str = "</XML>";
+
+    let mut documents = Vec::new();
+    let mut rest = submission;
+    while let Some(start) = rest.find(START_TAG) {
+        let body = &rest[start + START_TAG.len()..];
+        let Some(end) = body.find(END_TAG) else {
+            break;
+        };
+        documents.push(body[..end].trim());
+        rest = &body[end + END_TAG.len()..];
+    }
+    documents
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    text.chars().take(max_length).collect()
+}
+
+fn number(node: Node<'_, '_>, path: &[&str]) -> Option<BigDecimal> {
+    BigDecimal::from_str(&xml_text(node, path)?.replace(',', "")).ok()
+}
+
+/// Parse a 13F-HR submission
+///
+/// The cover page gives the manager, quarter and amendment type; each
+/// `infoTable` entry becomes a holding. Entries missing their CUSIP, value
+/// or share count are skipped. Values are converted to dollars.
+pub fn parse_13f(submission: &str, filing: &IndexedFiling) -> Result<Form13F> {
+    let documents = extract_xml_documents(submission)
+        .into_iter()
+        .map(|xml| Document::parse(xml).context("Invalid 13F XML"))
+        .collect::<Result<Vec<_>>>()?;
+    let cover = documents
+        .iter()
+        .map(|document| document.root_element())
+        .find(|root| root.has_tag_name("edgarSubmission"))
+        .context("13F has no cover page")?;
+
+    let form_data = xml_child(cover, "formData").context("13F has no form data")?;
+    let cover_page = xml_child(form_data, "coverPage").context("13F has no cover page")?;
+    let report_period = parse_sec_date(
+        xml_text(cover_page, &["reportCalendarOrQuarter"]).context("13F has no report period")?,
+    )?;
+    let manager_cik = xml_text(
+        cover,
+        &["headerData", "filerInfo", "filer", "credentials", "cik"],
+    )
+    .context("13F has no filer CIK")?;
+    let manager_name = xml_text(cover_page, &["filingManager", "name"]).unwrap_or_default();
+
+    let amendment_type = if filing.form_type.ends_with("/A") {
+        match xml_text(cover_page, &["amendmentInfo", "amendmentType"]) {
+            Some(kind) if kind.eq_ignore_ascii_case(AMENDMENT_NEW_HOLDINGS) => {
+                Some(AMENDMENT_NEW_HOLDINGS)
+            }
+            _ => Some(AMENDMENT_RESTATEMENT),
+        }
+    } else {
+        None
+    };
+
+    let dollar_values_since = NaiveDate::from_ymd_opt(
+        DOLLAR_VALUES_SINCE.0,
+        DOLLAR_VALUES_SINCE.1,
+        DOLLAR_VALUES_SINCE.2,
+    )
+    .context("Invalid 13F value cutover date")?;
+    let value_multiplier = if filing.filed_date < dollar_values_since {
+        BigDecimal::from(1000)
+    } else {
+        BigDecimal::from(1)
+    };
+
+    let entries = documents
+        .iter()
+        .map(|document| document.root_element())
+        .filter(|root| root.has_tag_name("informationTable"))
+        .flat_map(|table| table.children().filter(|n| n.has_tag_name("infoTable")));
+
+    let mut holdings = Vec::new();
+    for (index, entry) in entries.enumerate() {
+        match parse_info_table_entry(entry, &value_multiplier) {
+            Some(holding) => holdings.push(holding),
+            None => warn!(
+                "Skipping incomplete 13F entry {} of {}",
+                index + 1,
+                filing.accession_number
+            ),
+        }
+    }
+
+    let total_value_usd = holdings.iter().fold(BigDecimal::zero(), |total, holding| {
+        total + &holding.value_usd
+    });
+
+    Ok(Form13F {
+        filing: NewInstitutionalFiling {
+            accession_number: filing.accession_number.clone(),
+            form_type: filing.form_type.clone(),
+            amendment_type: amendment_type.map(str::to_string),
+            manager_cik: pad_cik(manager_cik),
+            manager_name: truncate(manager_name, 255),
+            report_period,
+            filed_date: filing.filed_date,
+            holdings_count: holdings.len() as i32,
+            total_value_usd,
+        },
+        holdings,
+    })
+}
+
+fn parse_info_table_entry(
+    entry: Node<'_, '_>,
+    value_multiplier: &BigDecimal,
+) -> Option<NewInstitutionalHolding> {
+    let cusip = xml_text(entry, &["cusip"])
+        .map(str::to_uppercase)
+        .filter(|cusip| cusip.len() == 9)?;
+    let value_usd = number(entry, &["value"])? * value_multiplier;
+    let shares = number(entry, &["shrsOrPrnAmt", "sshPrnamt"])?;
+    let share_type = xml_text(entry, &["shrsOrPrnAmt", "sshPrnamtType"])
+        .map(str::to_uppercase)
+        .filter(|kind| kind == "SH" || kind == "PRN")?;
+    let put_call = xml_text(entry, &["putCall"]).and_then(|kind| {
+        ["Put", "Call"]
+            .into_iter()
+            .find(|option| kind.eq_ignore_ascii_case(option))
+    });
+    let voting = |tag: &str| {
+        xml_text(entry, &["votingAuthority", tag])
+            .and_then(|count| count.replace(',', "").parse::<i64>().ok())
+            .unwrap_or(0)
+    };
+
+    Some(NewInstitutionalHolding {
+        cusip,
+        issuer_name: truncate(xml_text(entry, &["nameOfIssuer"]).unwrap_or_default(), 255),
+        title_of_class: truncate(xml_text(entry, &["titleOfClass"]).unwrap_or_default(), 150),
+        value_usd,
+        shares,
+        share_type,
+        put_call: put_call.map(str::to_string),
+        investment_discretion: truncate(
+            xml_text(entry, &["investmentDiscretion"]).unwrap_or_default(),
+            10,
+        ),
+        voting_sole: voting("Sole"),
+        voting_shared: voting("Shared"),
+        voting_none: voting("None"),
+    })
+}
+
+/// Issuer or company name reduced to the words that identify it
+///
+/// Punctuation and trailing legal-form words are dropped, so `Apple Inc.`
+/// and `APPLE INC` both become `APPLE`.
+pub fn normalize_issuer_name(name: &str) -> String {
+    let cleaned: String = name
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| NAME_SUFFIXES.contains(word))
+    {
+        words.pop();
+    }
+    words.join(" ")
+}
+
+/// Companies by normalized name, for resolving 13F issuer names
+#[derive(Debug, Default)]
+pub struct CompanyNameIndex {
+    /// `None` where several companies share the name
+    companies: HashMap<String, Option<Uuid>>,
+}
+
+impl CompanyNameIndex {
+    pub fn new(companies: impl IntoIterator<Item = (Uuid, String)>) -> Self {
+        let mut index = HashMap::new();
+        for (id, name) in companies {
+            let key = normalize_issuer_name(&name);
+            if key.is_empty() {
+                continue;
+            }
+            index
+                .entry(key)
+                .and_modify(|existing: &mut Option<Uuid>| {
+                    if *existing != Some(id) {
+                        *existing = None;
+                    }
+                })
+                .or_insert(Some(id));
+        }
+        Self { companies: index }
+    }
+
+    /// The one company whose name matches `issuer_name`
+    pub fn resolve(&self, issuer_name: &str) -> Option<Uuid> {
+        self.companies
+            .get(&normalize_issuer_name(issuer_name))
+            .copied()
+            .flatten()
+    }
+}
+
+impl SecEdgarCrawler {
+    /// Crawl 13F-HR filings for the days since the last crawl, up to yesterday
+    #[tracing::instrument(name = "sec.crawl_institutional_holdings", skip(self))]
+    pub async fn crawl_institutional_holdings(&self) -> Result<InstitutionalCrawlReport> {
+        let mut conn = self.pool.get().await?;
+        let last_crawled: Option<NaiveDate> = institutional_crawl_days::table
+            .select(diesel::dsl::max(institutional_crawl_days::index_date))
+            .first(&mut conn)
+            .await
+            .context("Failed to load last 13F crawl day")?;
+        drop(conn);
+
+        let yesterday = Utc::now().date_naive() - Duration::days(1);
+        let mut report = InstitutionalCrawlReport::default();
+        for day in days_to_crawl(last_crawled, yesterday) {
+            report.days.push(self.crawl_institutional_day(day).await?);
+        }
+
+        info!(
+            "13F crawl complete: {} days, {} filings stored, {} failed, {} holdings",
+            report.days.len(),
+            report.filings_stored(),
+            report.filings_failed(),
+            report.holdings_stored()
+        );
+
+        Ok(report)
+    }
+
+    /// Crawl the 13F-HR filings of one day of the EDGAR index
+    ///
+    /// Stored filings are skipped, so a day can be crawled again to retry
+    /// the filings that failed.
+    #[tracing::instrument(name = "sec.crawl_institutional_day", skip(self))]
+    pub async fn crawl_institutional_day(&self, date: NaiveDate) -> Result<InstitutionalDayReport> {
+        let mut report = InstitutionalDayReport::new(date);
+
+        let filings = self.fetch_daily_index(date, &FORM_13F_TYPES).await?;
+        report.filings_found = filings.len();
+
+        let accession_numbers: Vec<String> = filings
+            .iter()
+            .map(|filing| filing.accession_number.clone())
+            .collect();
+        let stored = InstitutionalFiling::stored_accessions(&self.pool, &accession_numbers)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load stored 13F filings: {}", e))?;
+
+        let pending: Vec<&IndexedFiling> = filings
+            .iter()
+            .filter(|filing| !stored.contains(&filing.accession_number))
+            .collect();
+        report.filings_skipped = filings.len() - pending.len();
+
+        if !pending.is_empty() {
+            let names = self.company_name_index().await?;
+            for filing in pending {
+                match self.store_13f(filing, &names).await {
+                    Ok((holdings, mapped)) => {
+                        report.filings_stored += 1;
+                        report.holdings_stored += holdings;
+                        report.cusips_mapped += mapped;
+                    }
+                    Err(e) => {
+                        warn!("Failed to store 13F {}: {}", filing.accession_number, e);
+                        CRAWLER_METRICS.record_error("sec", "edgar", "form13f_parse");
+                        report.filings_failed += 1;
+                        report
+                            .errors
+                            .push(format!("{}: {}", filing.accession_number, e));
+                    }
+                }
+            }
+        }
+
+        self.record_institutional_crawl_day(&report).await?;
+        CRAWLER_METRICS.record_items_collected(
+            "sec",
+            "edgar",
+            "institutional_holding",
+            report.holdings_stored as u64,
+        );
+        info!(
+            "Crawled 13F filings for {}: {} found, {} stored, {} failed",
+            date, report.filings_found, report.filings_stored, report.filings_failed
+        );
+
+        Ok(report)
+    }
+
+    async fn company_name_index(&self) -> Result<CompanyNameIndex> {
+        let mut conn = self.pool.get().await?;
+        let companies = companies::table
+            .select((companies::id, companies::name))
+            .load::<(Uuid, String)>(&mut conn)
+            .await
+            .context("Failed to load company names")?;
+
+        Ok(CompanyNameIndex::new(companies))
+    }
+
+    /// Download, parse and store one 13F, then map its new CUSIPs
+    ///
+    /// Returns the holdings stored and the CUSIPs mapped.
+    async fn store_13f(
+        &self,
+        filing: &IndexedFiling,
+        names: &CompanyNameIndex,
+    ) -> Result<(usize, usize)> {
+        let submission = self
+            .fetch_archive_text(&build_archive_url(&filing.file_name), "/form13f")
+            .await?
+            .context("Filing not found")?;
+        let form = parse_13f(&submission, filing)?;
+
+        let holdings_stored = InstitutionalFiling::store(&self.pool, &form.filing, &form.holdings)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store 13F holdings: {}", e))?
+            .unwrap_or(0);
+
+        let issuers: BTreeMap<&str, &str> = form
+            .holdings
+            .iter()
+            .map(|holding| (holding.cusip.as_str(), holding.issuer_name.as_str()))
+            .collect();
+        let cusips: Vec<String> = issuers.keys().map(|cusip| cusip.to_string()).collect();
+        let mapped = CusipMapping::mapped(&self.pool, &cusips)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load CUSIP mappings: {}", e))?;
+
+        let mappings: Vec<NewCusipMapping> = issuers
+            .into_iter()
+            .filter(|(cusip, _)| !mapped.contains(*cusip))
+            .filter_map(|(cusip, issuer_name)| {
+                Some(NewCusipMapping {
+                    cusip: cusip.to_string(),
+                    company_id: names.resolve(issuer_name)?,
+                    issuer_name: issuer_name.to_string(),
+                    source: "name_match".to_string(),
+                })
+            })
+            .collect();
+        let cusips_mapped = CusipMapping::insert_missing(&self.pool, &mappings)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store CUSIP mappings: {}", e))?;
+
+        Ok((holdings_stored, cusips_mapped))
+    }
+
+    async fn record_institutional_crawl_day(&self, report: &InstitutionalDayReport) -> Result<()> {
+        let day = CrawledDay {
+            index_date: report.index_date,
+            filings_found: report.filings_found as i32,
+            filings_stored: report.filings_stored as i32,
+            filings_failed: report.filings_failed as i32,
+            holdings_stored: report.holdings_stored as i32,
+        };
+
+        let mut conn = self.pool.get().await?;
+        diesel::insert_into(institutional_crawl_days::table)
+            .values(&day)
+            .on_conflict(institutional_crawl_days::index_date)
+            .do_update()
+            .set((
+                institutional_crawl_days::filings_found
+                    .eq(excluded(institutional_crawl_days::filings_found)),
+                institutional_crawl_days::filings_stored
+                    .eq(institutional_crawl_days::filings_stored
+                        + excluded(institutional_crawl_days::filings_stored)),
+                institutional_crawl_days::filings_failed
+                    .eq(excluded(institutional_crawl_days::filings_failed)),
+                institutional_crawl_days::holdings_stored
+                    .eq(institutional_crawl_days::holdings_stored
+                        + excluded(institutional_crawl_days::holdings_stored)),
+                institutional_crawl_days::crawled_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await
+            .context("Failed to record 13F crawl day")?;
+
+        Ok(())
+    }
+}
+
+/// Run [`SecEdgarCrawler::crawl_institutional_holdings`] on a cron schedule
+///
+/// `schedule` uses the six-field cron format with seconds, e.g.
+/// [`DEFAULT_INSTITUTIONAL_CRAWL_SCHEDULE`]. The returned scheduler is
+/// already started; keep it alive for as long as crawls should run.
+pub async fn schedule_institutional_crawl(
+    crawler: SecEdgarCrawler,
+    schedule: &str,
+) -> Result<JobScheduler> {
+    let scheduler = JobScheduler::new().await?;
+
+    let job = Job::new_async(schedule, move |_id, _scheduler| {
+        let crawler = crawler.clone();
+        Box::pin(async move {
+            // Runs that start while draining for shutdown are skipped
+            let Some(_in_flight) = shutdown_coordinator().try_track() else {
+                return;
+            };
+            if let Err(e) = crawler.crawl_institutional_holdings().await {
+                error!("Scheduled 13F crawl failed: {}", e);
+            }
+        })
+    })
+    .with_context(|| format!("Invalid 13F crawl schedule: {}", schedule))?;
+
+    scheduler.add(job).await?;
+    scheduler.start().await?;
+    info!("13F holdings crawl scheduled: {}", schedule);
+
+    Ok(scheduler)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORM_13F: &str = r#"<SEC-DOCUMENT>0000950123-22-012345.txt : 20221114
+<DOCUMENT>
+<TYPE>13F-HR/A
+<TEXT>
+<XML>
+<?xml version="1.0" encoding="UTF-8"?>
+<edgarSubmission xmlns="http://www.sec.gov/edgar/thirteenffiler">
+  <headerData>
+    <submissionType>13F-HR/A</submissionType>
+    <filerInfo>
+      <filer><credentials><cik>0001067983</cik></credentials></filer>
+      <periodOfReport>09-30-2022</periodOfReport>
+    </filerInfo>
+  </headerData>
+  <formData>
+    <coverPage>
+      <reportCalendarOrQuarter>09-30-2022</reportCalendarOrQuarter>
+      <isAmendment>true</isAmendment>
+      <amendmentInfo><amendmentType>NEW HOLDINGS</amendmentType></amendmentInfo>
+      <filingManager><name>Berkshire Hathaway Inc</name></filingManager>
+    </coverPage>
+  </formData>
+</edgarSubmission>
+</XML>
+</TEXT>
+</DOCUMENT>
+<DOCUMENT>
+<TYPE>INFORMATION TABLE
+<TEXT>
+<XML>
+<ns1:informationTable xmlns:ns1="http://www.sec.gov/edgar/document/thirteenf/informationtable">
+  <ns1:infoTable>
+    <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+    <ns1:titleOfClass>COM</ns1:titleOfClass>
+    <ns1:cusip>037833100</ns1:cusip>
+    <ns1:value>1234</ns1:value>
+    <ns1:shrsOrPrnAmt><ns1:sshPrnamt>9000</ns1:sshPrnamt><ns1:sshPrnamtType>SH</ns1:sshPrnamtType></ns1:shrsOrPrnAmt>
+    <ns1:investmentDiscretion>DFND</ns1:investmentDiscretion>
+    <ns1:votingAuthority><ns1:Sole>9000</ns1:Sole><ns1:Shared>0</ns1:Shared><ns1:None>0</ns1:None></ns1:votingAuthority>
+  </ns1:infoTable>
+  <ns1:infoTable>
+    <ns1:nameOfIssuer>APPLE INC</ns1:nameOfIssuer>
+    <ns1:titleOfClass>COM</ns1:titleOfClass>
+    <ns1:cusip>037833100</ns1:cusip>
+    <ns1:value>10</ns1:value>
+    <ns1:shrsOrPrnAmt><ns1:sshPrnamt>100</ns1:sshPrnamt><ns1:sshPrnamtType>SH</ns1:sshPrnamtType></ns1:shrsOrPrnAmt>
+    <ns1:putCall>PUT</ns1:putCall>
+    <ns1:investmentDiscretion>SOLE</ns1:investmentDiscretion>
+  </ns1:infoTable>
+  <ns1:infoTable>
+    <ns1:nameOfIssuer>NO CUSIP CORP</ns1:nameOfIssuer>
+    <ns1:value>5</ns1:value>
+  </ns1:infoTable>
+</ns1:informationTable>
+</XML>
+</TEXT>
+</DOCUMENT>
+</SEC-DOCUMENT>"#;
+
+    #[test]
+    fn test_parse_13f_cover_page_and_information_table() {
+        // REQUIREMENT: Store each manager's quarterly 13F holdings with values in dollars
+        // PURPOSE: Verify the cover page and namespaced information table are parsed and pre-2023 values scaled
+        // This ensures holdings from old and new filings are comparable and amendments are classified
+
+        let filing = IndexedFiling {
+            form_type: "13F-HR/A".to_string(),
+            filed_date: NaiveDate::from_ymd_opt(2022, 11, 14).unwrap(),
+            accession_number: "0000950123-22-012345".to_string(),
+            file_name: "edgar/data/1067983/0000950123-22-012345.txt".to_string(),
+        };
+
+        let form = parse_13f(FORM_13F, &filing).unwrap();
+
+        assert_eq!(form.filing.manager_cik, "0001067983");
+        assert_eq!(form.filing.manager_name, "Berkshire Hathaway Inc");
+        assert_eq!(
+            form.filing.report_period,
+            NaiveDate::from_ymd_opt(2022, 9, 30).unwrap()
+        );
+        assert_eq!(
+            form.filing.amendment_type.as_deref(),
+            Some(AMENDMENT_NEW_HOLDINGS)
+        );
+        assert!(!form.filing.replaces_holdings());
+        assert_eq!(form.filing.holdings_count, 2);
+        assert_eq!(form.filing.total_value_usd, BigDecimal::from(1_244_000));
+
+        let stock = &form.holdings[0];
+        assert_eq!(stock.cusip, "037833100");
+        assert_eq!(stock.value_usd, BigDecimal::from(1_234_000));
+        assert_eq!(stock.shares, BigDecimal::from(9000));
+        assert_eq!(stock.put_call, None);
+        assert_eq!(stock.investment_discretion, "DFND");
+        assert_eq!(stock.voting_sole, 9000);
+        assert_eq!(form.holdings[1].put_call.as_deref(), Some("Put"));
+
+        let recent = IndexedFiling {
+            filed_date: NaiveDate::from_ymd_opt(2023, 2, 14).unwrap(),
+            ..filing
+        };
+        let form = parse_13f(FORM_13F, &recent).unwrap();
+        assert_eq!(form.holdings[0].value_usd, BigDecimal::from(1234));
+    }
+
+    #[test]
+    fn test_resolve_issuer_names_to_companies() {
+        // REQUIREMENT: Resolve 13F CUSIPs to companies
+        // PURPOSE: Verify issuer names match company names despite case, punctuation and legal suffixes
+        // This ensures holdings are attributed to a company only when exactly one company matches
+
+        assert_eq!(normalize_issuer_name("Apple Inc."), "APPLE");
+        assert_eq!(
+            normalize_issuer_name("JPMORGAN CHASE & CO"),
+            normalize_issuer_name("JPMorgan Chase & Co.")
+        );
+        assert_eq!(normalize_issuer_name("CO"), "CO");
+
+        let apple = Uuid::new_v4();
+        let index = CompanyNameIndex::new(vec![
+            (apple, "Apple Inc.".to_string()),
+            (Uuid::new_v4(), "Acme Corp".to_string()),
+            (Uuid::new_v4(), "ACME INC /DE/".to_string()),
+        ]);
+
+        assert_eq!(index.resolve("APPLE INC"), Some(apple));
+        assert_eq!(index.resolve("ACME CORPORATION"), None);
+        assert_eq!(index.resolve("MICROSOFT CORP"), None);
+    }
+}
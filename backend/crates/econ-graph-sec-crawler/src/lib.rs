@@ -4,29 +4,57 @@
 //! XBRL financial data. It includes comprehensive error handling, rate limiting,
 //! retry logic, and progress tracking for reliable data acquisition.
 
+pub mod blob_store;
+pub mod calculation_linkbase;
+pub mod checkpoint;
+pub mod company_sync;
 pub mod config_loader;
 pub mod crawler;
+pub mod daily_index;
+pub mod download_manager;
 pub mod dts_manager;
+pub mod dts_resolver;
+pub mod filing_sections;
 pub mod financial_ratio_calculator;
+pub mod insider_transactions;
+pub mod institutional_holdings;
 pub mod models;
 pub mod rate_limiter;
 pub mod storage;
+pub mod unit_normalization;
 pub mod utils;
 pub mod xbrl_parser;
 pub mod xbrl_parser_tests;
 
+pub use blob_store::{BlobStore, BlobVerificationReport, RecompressionReport};
+pub use calculation_linkbase::{
+    CalculationDiscrepancy, CalculationLinkbase, CalculationNetwork, CalculationValidation,
+};
+pub use checkpoint::{XbrlBatchCheckpoint, XbrlBatchReport, XbrlCheckpointStore};
+pub use company_sync::{schedule_company_sync, CompanySyncReport};
 pub use config_loader::{
     ConceptMappingsConfig, FinancialAnalysisConfig, RatioBenchmarksConfig, RatioFormulasConfig,
     RatioInterpretationsConfig,
 };
 pub use crawler::SecEdgarCrawler;
+pub use daily_index::IndexedFiling;
+pub use download_manager::{DownloadManager, DownloadManagerConfig, DownloadRequest};
 pub use dts_manager::DtsManager;
+pub use dts_resolver::{DtsGraph, DtsResolutionReport, DtsResolutionStatus};
+pub use filing_sections::{ExtractedFilingSection, FilingSectionExtractor, FilingSectionKind};
 pub use financial_ratio_calculator::{
     CalculatedRatio, FinancialRatioCalculator, RatioCalculationConfig,
 };
+pub use insider_transactions::{schedule_insider_crawl, InsiderCrawlReport, InsiderDayReport};
+pub use institutional_holdings::{
+    schedule_institutional_crawl, InstitutionalCrawlReport, InstitutionalDayReport,
+};
 pub use models::*;
 pub use rate_limiter::SecRateLimiter;
 pub use storage::XbrlStorage;
+pub use unit_normalization::{
+    FxRateProvider, NormalizedValue, StaticFxRateProvider, UnitMeasure, UnitNormalizer,
+};
 pub use xbrl_parser::{
     DocumentType, FinancialRatio, TaxonomyConcept, ValidationReport, XbrlParseResult, XbrlParser,
     XbrlParserConfig,
@@ -471,11 +471,15 @@ pub struct CrawlProgress {
 
 /// **CompanyTickersResponse Model**
 ///
-/// Response from SEC EDGAR company tickers API.
+/// Response from SEC EDGAR company tickers API (`company_tickers.json`).
+///
+/// The file is a JSON object keyed by position ("0", "1", ...), ordered by
+/// SEC's ranking of the companies.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct CompanyTickersResponse {
-    /// Array of company ticker information
-    pub tickers: Vec<CompanyTicker>,
+    /// Company ticker information keyed by position
+    pub tickers: std::collections::HashMap<String, CompanyTicker>,
 }
 
 /// **CompanyTicker Model**
@@ -483,8 +487,8 @@ pub struct CompanyTickersResponse {
 /// Individual company ticker information from SEC EDGAR.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompanyTicker {
-    /// Company CIK
-    pub cik_str: String,
+    /// Company CIK, without leading zeros
+    pub cik_str: u64,
 
     /// Company name
     pub title: String,
@@ -492,8 +496,9 @@ pub struct CompanyTicker {
     /// Stock ticker symbol
     pub ticker: String,
 
-    /// Exchange where the stock is traded
-    pub exchange: String,
+    /// Exchange where the stock is traded (not included in `company_tickers.json`)
+    #[serde(default)]
+    pub exchange: Option<String>,
 }
 
 /// **CompanyFactsResponse Model**
@@ -719,7 +724,7 @@ pub struct StoredXbrlDocument {
     /// SHA-256 hash of the original file
     pub file_hash: String,
 
-    /// Storage method used ("large_object" or "bytea")
+    /// Storage method used ("large_object", "bytea" or "blob")
     pub storage_method: String,
 
     /// When this record was created
@@ -743,6 +748,9 @@ pub struct XbrlStorageStats {
     /// Number of files stored as bytea
     pub bytea_files: u64,
 
+    /// Number of files stored in the content-addressed blob store
+    pub blob_files: u64,
+
     /// Number of compressed files
     pub compressed_files: u64,
 
@@ -795,3 +803,15 @@ pub struct DtsReference {
     /// Optional arcrole attribute (for linkbase references)
     pub reference_arcrole: Option<String>,
 }
+
+impl DtsReference {
+    /// Whether this reference points at a calculation linkbase
+    pub fn is_calculation_linkbase(&self) -> bool {
+        self.reference_type == "linkbaseRef"
+            && (self
+                .reference_role
+                .as_deref()
+                .is_some_and(|role| role.ends_with("/calculationLinkbaseRef"))
+                || self.reference_href.contains("_cal"))
+    }
+}
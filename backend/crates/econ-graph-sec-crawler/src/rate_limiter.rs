@@ -1,16 +1,14 @@
 use anyhow::Result;
-use governor::{Quota, RateLimiter as GovernorRateLimiter};
-use std::num::NonZeroU32;
+use econ_graph_core::rate_limiter::{RateLimit, RateLimiter, SEC_HOST};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
-use tracing::{debug, warn};
+use tracing::debug;
 
 /// **Rate Limiter for SEC EDGAR API**
 ///
 /// Implements rate limiting for SEC EDGAR API requests to comply with
-/// SEC's guidelines and avoid being blocked. Uses the `governor` crate
-/// for efficient rate limiting with minimal overhead.
+/// SEC's guidelines and avoid being blocked. Backed by the shared token-bucket
+/// [`RateLimiter`] from `econ-graph-core`.
 ///
 /// # Rate Limiting Strategy
 /// - Default: 10 requests per second (SEC recommended)
@@ -33,14 +31,8 @@ use tracing::{debug, warn};
 /// ```
 #[derive(Debug, Clone)]
 pub struct SecRateLimiter {
-    /// Internal rate limiter from governor crate
-    limiter: Arc<
-        GovernorRateLimiter<
-            governor::state::direct::NotKeyed,
-            governor::state::InMemoryState,
-            governor::clock::DefaultClock,
-        >,
-    >,
+    /// Token bucket holding the SEC budget
+    limiter: Arc<RateLimiter>,
 
     /// Maximum requests per second
     max_requests_per_second: u32,
@@ -65,8 +57,10 @@ impl SecRateLimiter {
     /// let rate_limiter = RateLimiter::new(10, Duration::from_secs(1));
     /// ```
     pub fn new(max_requests_per_second: u32, time_window: Duration) -> Self {
-        let quota = Quota::per_second(NonZeroU32::new(max_requests_per_second).unwrap());
-        let limiter = Arc::new(GovernorRateLimiter::direct(quota));
+        let limiter = Arc::new(RateLimiter::new(RateLimit::new(
+            max_requests_per_second,
+            time_window,
+        )));
 
         Self {
             limiter,
@@ -146,19 +140,9 @@ impl SecRateLimiter {
     /// # }
     /// ```
     pub async fn wait_for_permit(&self) -> Result<()> {
-        loop {
-            match self.limiter.check() {
-                Ok(_) => {
-                    debug!("Rate limit permit granted");
-                    return Ok(());
-                }
-                Err(e) => {
-                    debug!("Rate limit exceeded, waiting 100ms");
-                    sleep(Duration::from_millis(100)).await;
-                    warn!("Rate limiter error: {:?}", e);
-                }
-            }
-        }
+        self.limiter.acquire(SEC_HOST, None).await;
+        debug!("Rate limit permit granted");
+        Ok(())
     }
 
     /// Try to get a permit without waiting
@@ -187,14 +171,16 @@ impl SecRateLimiter {
     /// }
     /// ```
     pub fn try_permit(&self) -> Result<()> {
-        match self.limiter.check() {
-            Ok(_) => {
+        match self.limiter.try_acquire(SEC_HOST, None) {
+            Ok(()) => {
                 debug!("Rate limit permit granted (non-blocking)");
                 Ok(())
             }
-            Err(e) => {
-                debug!("Rate limit exceeded (non-blocking)");
-                warn!("Rate limiter error: {:?}", e);
+            Err(wait) => {
+                debug!(
+                    "Rate limit exceeded (non-blocking), next permit in {:?}",
+                    wait
+                );
                 Err(anyhow::anyhow!("Rate limit exceeded"))
             }
         }
@@ -236,7 +222,7 @@ impl SecRateLimiter {
     /// }
     /// ```
     pub fn is_rate_limited(&self) -> bool {
-        self.limiter.check().is_err()
+        !self.limiter.time_until_available(SEC_HOST, None).is_zero()
     }
 
     /// Get the time until the next permit is available
@@ -257,10 +243,8 @@ impl SecRateLimiter {
     /// }
     /// ```
     pub fn time_until_next_permit(&self) -> Option<Duration> {
-        match self.limiter.check() {
-            Ok(_) => None,
-            Err(_) => Some(Duration::from_millis(100)),
-        }
+        let wait = self.limiter.time_until_available(SEC_HOST, None);
+        (!wait.is_zero()).then_some(wait)
     }
 
     /// Reset the rate limiter (for testing purposes)
@@ -279,10 +263,9 @@ impl SecRateLimiter {
     /// rate_limiter.reset();
     /// ```
     pub fn reset(&self) {
-        // Note: The governor crate doesn't provide a direct reset method
-        // This is a limitation of the current implementation
-        // In practice, you would create a new rate limiter instance
-        debug!("Rate limiter reset requested (note: governor crate doesn't support reset)");
+        // Note: Buckets are not reset in place; create a new rate limiter
+        // instance to start from a full budget
+        debug!("Rate limiter reset requested (not supported, create a new limiter instead)");
     }
 }
 
@@ -5,17 +5,26 @@ use chrono::{DateTime, Utc};
 use diesel::expression_methods::ExpressionMethods;
 use diesel::prelude::*;
 use diesel::query_dsl::QueryDsl;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
-use sha2::{Digest, Sha256};
+use diesel::upsert::excluded;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::HashSet;
 use std::io::{Cursor, Read};
 use tokio::io::{AsyncRead, AsyncReadExt};
 use uuid::Uuid;
-use zstd::stream::{decode_all, encode_all};
+use zstd::stream::decode_all;
 
+use crate::blob_store::{compression_level_from_env, BlobStore};
 use crate::models::{StoredXbrlDocument, XbrlStorageStats};
+use econ_graph_core::bulk_copy::{BulkCopy, BULK_COPY_MIN_ROWS};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::enums::{CompressionType, ProcessingStatus};
-use econ_graph_core::models::{Company, FinancialStatement};
+use econ_graph_core::models::{
+    Company, DocumentKind, FinancialLineItem, FinancialStatement, NewDocumentBlob,
+};
+
+/// Line items per upsert statement, well below PostgreSQL's bind parameter limit
+const LINE_ITEM_BATCH_SIZE: usize = 1000;
 
 /// Configuration for XBRL file storage
 #[derive(Debug, Clone)]
@@ -41,17 +50,42 @@ impl Default for XbrlStorageConfig {
     }
 }
 
+impl XbrlStorageConfig {
+    /// Default configuration with the compression level of `SEC_FILING_ZSTD_LEVEL`
+    pub fn from_env() -> Result<Self> {
+        let mut config = Self::default();
+        match compression_level_from_env(DocumentKind::Filing)? {
+            Some(level) => config.zstd_compression_level = level,
+            None => config.compression_enabled = false,
+        }
+        Ok(config)
+    }
+}
+
 /// XBRL file storage implementation using PostgreSQL
 #[derive(Clone)]
 pub struct XbrlStorage {
     pool: DatabasePool,
     config: XbrlStorageConfig,
+    blobs: BlobStore,
 }
 
 impl XbrlStorage {
     /// Create a new XBRL storage instance
     pub fn new(pool: DatabasePool, config: XbrlStorageConfig) -> Self {
-        Self { pool, config }
+        let compression_level = config
+            .compression_enabled
+            .then_some(config.zstd_compression_level);
+        Self {
+            blobs: BlobStore::new(pool.clone(), DocumentKind::Filing, compression_level),
+            pool,
+            config,
+        }
+    }
+
+    /// Store holding filing and taxonomy documents
+    pub fn blobs(&self) -> &BlobStore {
+        &self.blobs
     }
 
     /// Store an XBRL file in the database
@@ -73,39 +107,19 @@ impl XbrlStorage {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get database connection: {}", e))?;
 
-        // Calculate file hash for integrity verification
-        let mut hasher = Sha256::new();
-        hasher.update(content);
-        let file_hash = hex::encode(hasher.finalize());
-
-        // Compress content if enabled
-        let (compressed_content, compression_type): (Vec<u8>, CompressionType) =
-            if self.config.compression_enabled {
-                let compressed = encode_all(content, self.config.zstd_compression_level)
-                    .context("Failed to compress XBRL file")?;
-                (compressed, CompressionType::Zstd)
-            } else {
-                (content.to_vec(), CompressionType::None)
-            };
-
+        // Hash and compress once; the hash also keys the document's blob
+        let blob = self.blobs.encode(content)?;
         let file_size = content.len();
-        let compressed_size = compressed_content.len();
 
         // Determine storage method based on file size and configuration
-        let use_lob = self.config.use_large_objects && compressed_size > self.config.max_bytea_size;
-
-        let compression_type_str = match compression_type {
-            CompressionType::Zstd => "zstd",
-            CompressionType::Lz4 => "lz4",
-            CompressionType::Gzip => "gzip",
-            CompressionType::None => "none",
-        };
+        let use_lob = self.config.use_large_objects
+            && blob.stored_size_bytes as usize > self.config.max_bytea_size;
 
         if use_lob {
             self.store_as_large_object(
                 &mut *conn,
                 acc_num,
-                &compressed_content,
+                &blob.content,
                 comp_id,
                 filing_dt,
                 period_end_dt,
@@ -114,15 +128,15 @@ impl XbrlStorage {
                 form_typ,
                 doc_url,
                 file_size,
-                &file_hash,
-                compression_type,
+                &blob.content_hash,
+                blob.compression_type,
             )
             .await
         } else {
-            self.store_as_bytea(
+            self.store_as_blob(
                 &mut *conn,
                 acc_num,
-                &compressed_content,
+                &blob,
                 comp_id,
                 filing_dt,
                 period_end_dt,
@@ -130,9 +144,6 @@ impl XbrlStorage {
                 fiscal_qtr,
                 form_typ,
                 doc_url,
-                file_size,
-                &file_hash,
-                compression_type,
             )
             .await
         }
@@ -180,7 +191,7 @@ impl XbrlStorage {
             xbrl_file_oid: Some(lob_oid.0 as u32),
             xbrl_file_content: None,
             xbrl_file_size_bytes: Some(original_size as i64),
-            xbrl_file_compressed: self.config.compression_enabled,
+            xbrl_file_compressed: compression_type != CompressionType::None,
             xbrl_file_compression_type: compression_type,
             xbrl_file_hash: Some(file_hash.to_string()),
             xbrl_processing_status: ProcessingStatus::Pending,
@@ -194,6 +205,7 @@ impl XbrlStorage {
             restatement_reason: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            xbrl_file_blob_hash: None,
         };
 
         diesel::insert_into(financial_statements)
@@ -224,12 +236,12 @@ impl XbrlStorage {
         })
     }
 
-    /// Store XBRL file as bytea column
-    async fn store_as_bytea(
+    /// Store XBRL file in the document blob store, shared with identical filings
+    async fn store_as_blob(
         &self,
         conn: &mut AsyncPgConnection,
         acc_num: &str,
-        content: &[u8],
+        blob: &NewDocumentBlob,
         comp_id: Uuid,
         filing_dt: DateTime<Utc>,
         period_end_dt: DateTime<Utc>,
@@ -237,13 +249,11 @@ impl XbrlStorage {
         fiscal_qtr: Option<i32>,
         form_typ: Option<&str>,
         doc_url: Option<&str>,
-        original_size: usize,
-        file_hash: &str,
-        compression_type: CompressionType,
     ) -> Result<StoredXbrlDocument> {
         use econ_graph_core::schema::financial_statements::dsl::*;
 
-        // Insert financial statement record with bytea content
+        // The statement references the blob by hash; its compression fields
+        // describe the blob
         let new_statement = FinancialStatement {
             id: Uuid::new_v4(),
             company_id: comp_id,
@@ -257,11 +267,11 @@ impl XbrlStorage {
             document_type: "XBRL".to_string(),
             document_url: doc_url.unwrap_or("").to_string(),
             xbrl_file_oid: None,
-            xbrl_file_content: Some(content.to_vec()),
-            xbrl_file_size_bytes: Some(original_size as i64),
-            xbrl_file_compressed: self.config.compression_enabled,
-            xbrl_file_compression_type: compression_type,
-            xbrl_file_hash: Some(file_hash.to_string()),
+            xbrl_file_content: None,
+            xbrl_file_size_bytes: Some(blob.size_bytes),
+            xbrl_file_compressed: blob.compression_type != CompressionType::None,
+            xbrl_file_compression_type: blob.compression_type,
+            xbrl_file_hash: Some(blob.content_hash.clone()),
             xbrl_processing_status: ProcessingStatus::Pending,
             xbrl_processing_error: None,
             xbrl_processing_started_at: None,
@@ -273,13 +283,25 @@ impl XbrlStorage {
             restatement_reason: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            xbrl_file_blob_hash: Some(blob.content_hash.clone()),
         };
 
-        diesel::insert_into(financial_statements)
-            .values(&new_statement)
-            .execute(conn)
-            .await
-            .context("Failed to insert financial statement")?;
+        // Storing the blob locks it until the statement references it, so
+        // garbage collection cannot delete it in between
+        let statement = &new_statement;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                self.blobs.put(conn, blob).await?;
+                diesel::insert_into(financial_statements)
+                    .values(statement)
+                    .execute(conn)
+                    .await
+                    .context("Failed to insert financial statement")?;
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
 
         Ok(StoredXbrlDocument {
             id: new_statement.id,
@@ -289,20 +311,31 @@ impl XbrlStorage {
             period_end_date: period_end_dt,
             fiscal_year: fiscal_yr,
             fiscal_quarter: fiscal_qtr,
-            file_size: original_size,
-            compressed_size: content.len(),
-            compression_type: match compression_type {
-                CompressionType::Zstd => "zstd".to_string(),
-                CompressionType::Lz4 => "lz4".to_string(),
-                CompressionType::Gzip => "gzip".to_string(),
-                CompressionType::None => "none".to_string(),
-            },
-            file_hash: file_hash.to_string(),
-            storage_method: "bytea".to_string(),
+            file_size: blob.size_bytes as usize,
+            compressed_size: blob.stored_size_bytes as usize,
+            compression_type: blob.compression_type.as_str().to_string(),
+            file_hash: blob.content_hash.clone(),
+            storage_method: "blob".to_string(),
             created_at: new_statement.created_at,
         })
     }
 
+    /// Id of the statement stored for a filing
+    pub async fn statement_id(&self, acc_num: &str) -> Result<Uuid> {
+        use econ_graph_core::schema::financial_statements::dsl::*;
+
+        let mut conn = self.pool.get().await?;
+
+        financial_statements
+            .filter(accession_number.eq(acc_num))
+            .select(id)
+            .first::<Uuid>(&mut conn)
+            .await
+            .optional()
+            .context("Failed to query financial statement")?
+            .ok_or_else(|| anyhow::anyhow!("Filing not found: {}", acc_num))
+    }
+
     /// Retrieve an XBRL file from the database
     pub async fn retrieve_xbrl_file(&self, acc_num: &str) -> Result<Vec<u8>> {
         use econ_graph_core::schema::financial_statements::dsl::*;
@@ -317,6 +350,11 @@ impl XbrlStorage {
             .context("Failed to query financial statement")?
             .ok_or_else(|| anyhow::anyhow!("XBRL file not found: {}", acc_num))?;
 
+        // Blobs are decompressed and checked against their hash by the store
+        if let Some(hash) = &statement.xbrl_file_blob_hash {
+            return self.blobs.get(hash).await;
+        }
+
         let content = if let Some(oid) = statement.xbrl_file_oid {
             // Retrieve from Large Object
             self.retrieve_from_large_object(&mut conn, oid as i32)
@@ -387,6 +425,13 @@ impl XbrlStorage {
             .await
             .context("Failed to count bytea files")?;
 
+        let blob_count: i64 = financial_statements
+            .filter(xbrl_file_blob_hash.is_not_null())
+            .count()
+            .get_result(&mut conn)
+            .await
+            .context("Failed to count blob files")?;
+
         // Count by compression type
         let compressed_count: i64 = financial_statements
             .filter(xbrl_file_compressed.eq(true))
@@ -404,6 +449,7 @@ impl XbrlStorage {
                 .unwrap_or(0),
             large_object_files: lob_count as u64,
             bytea_files: bytea_count as u64,
+            blob_files: blob_count as u64,
             compressed_files: compressed_count as u64,
             uncompressed_files: total_files as u64 - compressed_count as u64,
         })
@@ -438,9 +484,141 @@ impl XbrlStorage {
             .await
             .context("Failed to delete financial statement")?;
 
+        // The filing's blob goes unless another filing has the same document
+        self.blobs.collect_garbage().await?;
+
         Ok(())
     }
 
+    /// Store the facts parsed from a filing as line items of its statement
+    ///
+    /// Facts are upserted by concept, context and unit, so parsing a filing
+    /// again (for example after a batch was interrupted) updates the stored
+    /// line items, keeping their ids and annotations, instead of duplicating them.
+    /// Filings with at least [`BULK_COPY_MIN_ROWS`] facts are loaded with binary COPY.
+    pub async fn store_line_items(
+        &self,
+        statement_id: Uuid,
+        line_items: Vec<FinancialLineItem>,
+    ) -> Result<usize> {
+        use econ_graph_core::schema::financial_line_items::dsl;
+
+        let line_items = unique_facts(statement_id, line_items);
+        if line_items.is_empty() {
+            return Ok(0);
+        }
+
+        if line_items.len() >= BULK_COPY_MIN_ROWS {
+            if let Some(mut copy) = BulkCopy::connect_to_pool_database().await? {
+                let stored = copy
+                    .upsert_line_items(&line_items)
+                    .await
+                    .context("Failed to copy line items")?;
+                return Ok(stored as usize);
+            }
+        }
+
+        let mut conn = self.pool.get().await?;
+        let mut stored = 0;
+        for batch in line_items.chunks(LINE_ITEM_BATCH_SIZE) {
+            stored += diesel::insert_into(dsl::financial_line_items)
+                .values(batch)
+                .on_conflict((
+                    dsl::statement_id,
+                    dsl::taxonomy_concept,
+                    dsl::context_ref,
+                    dsl::unit,
+                ))
+                .do_update()
+                .set((
+                    dsl::standard_label.eq(excluded(dsl::standard_label)),
+                    dsl::value.eq(excluded(dsl::value)),
+                    dsl::precision.eq(excluded(dsl::precision)),
+                    dsl::decimals.eq(excluded(dsl::decimals)),
+                    dsl::statement_type.eq(excluded(dsl::statement_type)),
+                    dsl::statement_section.eq(excluded(dsl::statement_section)),
+                    dsl::normalized_value.eq(excluded(dsl::normalized_value)),
+                    dsl::normalized_unit.eq(excluded(dsl::normalized_unit)),
+                    dsl::fx_rate.eq(excluded(dsl::fx_rate)),
+                    dsl::dimensions.eq(excluded(dsl::dimensions)),
+                    dsl::period_start_date.eq(excluded(dsl::period_start_date)),
+                    dsl::period_end_date.eq(excluded(dsl::period_end_date)),
+                    dsl::updated_at.eq(excluded(dsl::updated_at)),
+                ))
+                .execute(&mut conn)
+                .await
+                .context("Failed to store line items")?;
+        }
+
+        Ok(stored)
+    }
+
+    /// Store narrative sections extracted from a filing's primary document
+    ///
+    /// Sections are keyed by statement and section type, so re-crawling a filing
+    /// replaces the previously stored text.
+    pub async fn store_filing_sections(
+        &self,
+        statement_id: Uuid,
+        sections: &[crate::filing_sections::ExtractedFilingSection],
+        source_url: Option<&str>,
+    ) -> Result<usize> {
+        use econ_graph_core::models::{FilingSection, NewFilingSection};
+
+        let new_sections: Vec<NewFilingSection> = sections
+            .iter()
+            .map(|section| NewFilingSection {
+                statement_id,
+                section_type: section.kind.as_str().to_string(),
+                item_number: section.item_number.clone(),
+                part: section.part.clone(),
+                title: section.title.chars().take(500).collect(),
+                content: section.content.clone(),
+                word_count: section.word_count as i32,
+                content_hash: section.content_hash(),
+                source_url: source_url.map(str::to_string),
+            })
+            .collect();
+
+        let stored = FilingSection::upsert_many(&self.pool, &new_sections)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store filing sections: {}", e))?;
+
+        Ok(stored.len())
+    }
+
+    /// Store the calculation inconsistencies found in a filing
+    ///
+    /// Replaces the discrepancies of any earlier validation run for the statement.
+    pub async fn store_calculation_discrepancies(
+        &self,
+        statement_id: Uuid,
+        validation: &crate::calculation_linkbase::CalculationValidation,
+    ) -> Result<usize> {
+        use econ_graph_core::models::{NewXbrlCalculationDiscrepancy, XbrlCalculationDiscrepancy};
+
+        let discrepancies: Vec<NewXbrlCalculationDiscrepancy> = validation
+            .discrepancies
+            .iter()
+            .map(|discrepancy| NewXbrlCalculationDiscrepancy {
+                statement_id,
+                link_role: discrepancy.link_role.clone(),
+                parent_concept: discrepancy.parent_concept.clone(),
+                child_concepts: discrepancy.child_concepts.clone(),
+                context_ref: discrepancy.context_ref.clone(),
+                unit_ref: discrepancy.unit_ref.clone(),
+                reported_value: discrepancy.reported_value.clone(),
+                calculated_value: discrepancy.calculated_value.clone(),
+                difference: discrepancy.difference(),
+                tolerance: discrepancy.tolerance.clone(),
+            })
+            .collect();
+
+        XbrlCalculationDiscrepancy::replace_for_statement(&self.pool, statement_id, &discrepancies)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store calculation discrepancies: {}", e))
+    }
+
     /// Store a taxonomy component (schema or linkbase) in the database
     pub async fn store_taxonomy_component(
         &self,
@@ -451,18 +629,18 @@ impl XbrlStorage {
     ) -> Result<()> {
         use econ_graph_core::enums::{TaxonomyFileType, TaxonomySourceType};
         use econ_graph_core::models::XbrlTaxonomySchema;
-        use sha2::{Digest, Sha256};
 
         let mut conn = self.pool.get().await?;
 
-        // Calculate file hash
-        let mut hasher = Sha256::new();
-        hasher.update(content);
-        let file_hash = format!("sha256:{:x}", hasher.finalize());
+        // Taxonomy files are shared by many filings, so each is stored once
+        let blob = self.blobs.encode(content)?;
+        let file_hash = format!("sha256:{}", blob.content_hash);
 
         // Determine file type and source type
         let file_type = if reference.reference_type == "schemaRef" {
             TaxonomyFileType::Schema
+        } else if reference.is_calculation_linkbase() {
+            TaxonomyFileType::CalculationLinkbase
         } else {
             // Determine linkbase type from role or filename
             TaxonomyFileType::LabelLinkbase // Default, could be enhanced
@@ -483,12 +661,12 @@ impl XbrlStorage {
             schema_date: None,
             file_type,
             source_type,
-            file_content: Some(content.to_vec()),
+            file_content: None,
             file_oid: None,
-            file_size_bytes: content.len() as i64,
+            file_size_bytes: blob.size_bytes,
             file_hash,
-            is_compressed: false, // Store uncompressed for taxonomy files
-            compression_type: econ_graph_core::enums::CompressionType::None,
+            is_compressed: blob.compression_type != CompressionType::None,
+            compression_type: blob.compression_type,
             source_url: Some(download_url.to_string()),
             download_url: Some(download_url.to_string()),
             original_filename: None,
@@ -500,14 +678,25 @@ impl XbrlStorage {
             relationships_extracted: 0,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            blob_hash: Some(blob.content_hash.clone()),
         };
 
-        // Insert the taxonomy schema
-        diesel::insert_into(econ_graph_core::schema::xbrl_taxonomy_schemas::table)
-            .values(&taxonomy_schema)
-            .execute(&mut conn)
-            .await
-            .context("Failed to insert taxonomy schema")?;
+        // Insert the taxonomy schema together with its blob
+        let schema = &taxonomy_schema;
+        let blob = &blob;
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                self.blobs.put(conn, blob).await?;
+                diesel::insert_into(econ_graph_core::schema::xbrl_taxonomy_schemas::table)
+                    .values(schema)
+                    .execute(conn)
+                    .await
+                    .context("Failed to insert taxonomy schema")?;
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await?;
 
         // Store DTS reference
         self.store_dts_reference(reference, statement_id, &taxonomy_schema.id, download_url)
@@ -588,9 +777,89 @@ impl XbrlStorage {
     }
 }
 
+/// Attach parsed line items to their statement, keeping the first of any
+/// facts reported twice for the same concept, context and unit
+///
+/// One upsert statement cannot touch the same row twice, and filings do repeat
+/// facts (e.g. a total shown on two statements).
+fn unique_facts(statement_id: Uuid, line_items: Vec<FinancialLineItem>) -> Vec<FinancialLineItem> {
+    let mut seen = HashSet::new();
+    line_items
+        .into_iter()
+        .filter(|item| {
+            seen.insert((
+                item.taxonomy_concept.clone(),
+                item.context_ref.clone(),
+                item.unit.clone(),
+            ))
+        })
+        .map(|item| FinancialLineItem {
+            statement_id,
+            ..item
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use econ_graph_core::enums::{StatementSection, StatementType};
+
+    fn line_item(concept: &str, context_ref: &str, value: i64) -> FinancialLineItem {
+        FinancialLineItem {
+            id: Uuid::new_v4(),
+            statement_id: Uuid::new_v4(),
+            taxonomy_concept: concept.to_string(),
+            standard_label: None,
+            custom_label: None,
+            value: Some(BigDecimal::from(value)),
+            unit: "USD".to_string(),
+            context_ref: context_ref.to_string(),
+            segment_ref: None,
+            scenario_ref: None,
+            precision: None,
+            decimals: Some(-6),
+            is_credit: None,
+            is_debit: None,
+            statement_type: StatementType::BalanceSheet,
+            statement_section: StatementSection::Assets,
+            parent_concept: None,
+            level: 0,
+            order_index: None,
+            is_calculated: false,
+            calculation_formula: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            normalized_value: None,
+            normalized_unit: None,
+            fx_rate: None,
+            dimensions: serde_json::json!({}),
+            period_start_date: None,
+            period_end_date: None,
+        }
+    }
+
+    #[test]
+    fn test_unique_facts_for_statement() {
+        // REQUIREMENT: Idempotent fact insertion for XBRL batch processing
+        // PURPOSE: Verify parsed facts are attached to the filing's statement and repeated facts are dropped
+        // This ensures the fact upsert never conflicts with itself and re-runs map onto the same rows
+
+        let statement_id = Uuid::new_v4();
+        let facts = unique_facts(
+            statement_id,
+            vec![
+                line_item("us-gaap:Assets", "FY2024", 100),
+                line_item("us-gaap:Assets", "FY2023", 90),
+                line_item("us-gaap:Assets", "FY2024", 100),
+            ],
+        );
+
+        assert_eq!(facts.len(), 2);
+        assert!(facts.iter().all(|fact| fact.statement_id == statement_id));
+        assert_eq!(facts[0].context_ref, "FY2024");
+        assert_eq!(facts[1].context_ref, "FY2023");
+    }
 
     #[tokio::test]
     async fn test_store_and_retrieve_xbrl_file() {
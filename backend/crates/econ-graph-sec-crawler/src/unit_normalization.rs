@@ -0,0 +1,528 @@
+//! **XBRL Unit Normalization**
+//!
+//! Converts reported facts into canonical units so values from different
+//! filers can be compared directly: monetary amounts become absolute USD,
+//! share counts stay in shares and per-share amounts become USD per share.
+//!
+//! Two things get in the way. Inline XBRL displays amounts the way the
+//! filing prints them ("394,328" with `scale="6"` for $394.3B), and foreign
+//! private issuers report in their own currency. Note that `decimals` only
+//! states the precision of a value (`-6` means accurate to the million); it
+//! never changes the magnitude, so it is kept as reported.
+//!
+//! Currency conversion goes through an [`FxRateProvider`], so callers can plug
+//! in whatever rate source they have. Facts whose currency has no rate keep
+//! their reported value and are left without a normalized value.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, One, Zero};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::xbrl_parser::{XbrlContext, XbrlUnit};
+
+/// Currency every monetary fact is normalized into
+pub const CANONICAL_CURRENCY: &str = "USD";
+
+/// Largest `scale` accepted on an inline XBRL fact
+///
+/// Filings use -2 (percentages) through 9 (billions); anything far outside
+/// that is a tagging error and would overflow the numeric column.
+const MAX_SCALE: i32 = 12;
+
+/// What a fact's unit measures
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UnitMeasure {
+    /// ISO 4217 currency code, e.g. `USD`
+    Currency(String),
+    /// Number of shares
+    Shares,
+    /// Currency amount per share, e.g. earnings per share
+    CurrencyPerShare(String),
+    /// Dimensionless ratio or percentage
+    Pure,
+    /// Any other unit, kept as reported
+    Other(String),
+}
+
+impl UnitMeasure {
+    /// Parse a measure such as `iso4217:USD`, `xbrli:shares` or
+    /// `iso4217:USD/xbrli:shares`
+    pub fn parse(measure: &str) -> Self {
+        let measure = measure.trim();
+        if let Some((numerator, denominator)) = measure.split_once('/') {
+            return match (Self::parse(numerator), Self::parse(denominator)) {
+                (Self::Currency(currency), Self::Shares) => Self::CurrencyPerShare(currency),
+                _ => Self::Other(measure.to_string()),
+            };
+        }
+
+        let (prefix, local_name) = match measure.split_once(':') {
+            Some((prefix, local_name)) => (Some(prefix), local_name),
+            None => (None, measure),
+        };
+
+        if local_name.eq_ignore_ascii_case("shares") {
+            Self::Shares
+        } else if local_name.eq_ignore_ascii_case("pure") {
+            Self::Pure
+        } else if prefix.map_or(is_currency_code(local_name), |prefix| {
+            prefix.eq_ignore_ascii_case("iso4217")
+        }) {
+            Self::Currency(local_name.to_ascii_uppercase())
+        } else {
+            Self::Other(measure.to_string())
+        }
+    }
+
+    /// Guess the measure from a unit id such as `usd`, `U_USD` or
+    /// `USD_per_share`
+    ///
+    /// Used when the unit declaration is missing; filers usually name units
+    /// after what they measure.
+    pub fn from_unit_id(id: &str) -> Self {
+        let lower = id.to_ascii_lowercase();
+        let currency = unit_id_words(id)
+            .into_iter()
+            .find(|word| {
+                is_currency_code(word)
+                    && !word.eq_ignore_ascii_case("per")
+                    && !word.eq_ignore_ascii_case("iso")
+            })
+            .map(|word| word.to_ascii_uppercase());
+
+        match currency {
+            Some(currency) if lower.contains("share") => Self::CurrencyPerShare(currency),
+            Some(currency) => Self::Currency(currency),
+            None if lower.contains("share") => Self::Shares,
+            None if lower.contains("pure") => Self::Pure,
+            None => Self::Other(id.to_string()),
+        }
+    }
+
+    /// Resolve the measure of a fact's `unitRef` against the declared units
+    pub fn resolve(unit_ref: Option<&str>, units: &[XbrlUnit]) -> Self {
+        let Some(unit_ref) = unit_ref else {
+            return Self::Pure;
+        };
+
+        let from_id = Self::from_unit_id(unit_ref);
+        // Divide units are declared with two measures and only the last one
+        // survives parsing, so a per-share id is more reliable than the measure
+        if matches!(from_id, Self::CurrencyPerShare(_)) {
+            return from_id;
+        }
+
+        units
+            .iter()
+            .find(|unit| unit.id == unit_ref)
+            .and_then(|unit| unit.measure.as_deref())
+            .filter(|measure| !measure.trim().is_empty())
+            .map(Self::parse)
+            .unwrap_or(from_id)
+    }
+
+    /// Currency of a monetary measure
+    pub fn currency(&self) -> Option<&str> {
+        match self {
+            Self::Currency(currency) | Self::CurrencyPerShare(currency) => Some(currency),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UnitMeasure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Currency(currency) => write!(f, "{}", currency),
+            Self::Shares => write!(f, "shares"),
+            Self::CurrencyPerShare(currency) => write!(f, "{}/shares", currency),
+            Self::Pure => write!(f, "pure"),
+            Self::Other(unit) => write!(f, "{}", unit),
+        }
+    }
+}
+
+fn is_currency_code(word: &str) -> bool {
+    word.len() == 3 && word.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Split a unit id into words at separators and camelCase boundaries
+/// (`USDPerShare` -> `USD`, `Per`, `Share`)
+fn unit_id_words(id: &str) -> Vec<String> {
+    let chars: Vec<char> = id.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphabetic() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let boundary = i > 0 && c.is_ascii_uppercase() && {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            previous.is_ascii_lowercase() || (previous.is_ascii_uppercase() && next_is_lower)
+        };
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Source of exchange rates for currency conversion
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    /// Units of `to` one unit of `from` buys on `date` (the latest rate when
+    /// `date` is `None`), or `None` when the provider has no rate
+    async fn rate(
+        &self,
+        from: &str,
+        to: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Option<BigDecimal>>;
+}
+
+/// Exchange rates held in memory, quoted as USD per unit of currency
+///
+/// Looks up the most recent rate on or before the requested date. Useful for
+/// tests and for seeding from a rate series loaded elsewhere.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFxRateProvider {
+    usd_rates: HashMap<String, BTreeMap<NaiveDate, BigDecimal>>,
+}
+
+impl StaticFxRateProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the USD value of one unit of `currency` on `date`
+    pub fn with_rate(mut self, currency: &str, date: NaiveDate, usd_per_unit: BigDecimal) -> Self {
+        self.usd_rates
+            .entry(currency.to_ascii_uppercase())
+            .or_default()
+            .insert(date, usd_per_unit);
+        self
+    }
+
+    fn usd_rate(&self, currency: &str, date: Option<NaiveDate>) -> Option<BigDecimal> {
+        if currency.eq_ignore_ascii_case(CANONICAL_CURRENCY) {
+            return Some(BigDecimal::one());
+        }
+
+        let rates = self.usd_rates.get(&currency.to_ascii_uppercase())?;
+        let rate = match date {
+            Some(date) => rates.range(..=date).next_back(),
+            None => rates.iter().next_back(),
+        };
+        rate.map(|(_, rate)| rate.clone())
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for StaticFxRateProvider {
+    async fn rate(
+        &self,
+        from: &str,
+        to: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Option<BigDecimal>> {
+        if from.eq_ignore_ascii_case(to) {
+            return Ok(Some(BigDecimal::one()));
+        }
+
+        let (Some(from_usd), Some(to_usd)) = (self.usd_rate(from, date), self.usd_rate(to, date))
+        else {
+            return Ok(None);
+        };
+
+        if to_usd.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(from_usd / to_usd))
+    }
+}
+
+/// A fact value in canonical units
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedValue {
+    pub value: BigDecimal,
+    /// Canonical unit, e.g. `USD`, `shares` or `USD/shares`
+    pub unit: String,
+    /// Exchange rate applied, when the fact was reported in another currency
+    pub fx_rate: Option<BigDecimal>,
+}
+
+/// Converts fact values into canonical units
+#[derive(Clone)]
+pub struct UnitNormalizer {
+    fx_rates: Arc<dyn FxRateProvider>,
+}
+
+impl Default for UnitNormalizer {
+    /// Normalizer without exchange rates; only USD facts get a monetary value
+    fn default() -> Self {
+        Self::new(Arc::new(StaticFxRateProvider::new()))
+    }
+}
+
+impl UnitNormalizer {
+    pub fn new(fx_rates: Arc<dyn FxRateProvider>) -> Self {
+        Self { fx_rates }
+    }
+
+    /// Parse a reported value and apply its inline XBRL `scale`
+    ///
+    /// Accepts display formatting ("1,234.5", "(12)" for negatives).
+    pub fn parse_reported_value(raw: &str, scale: Option<i32>) -> Option<BigDecimal> {
+        let trimmed = raw.trim();
+        let (negative, digits) = match trimmed
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+        {
+            Some(inner) => (true, inner),
+            None => (false, trimmed),
+        };
+        let digits: String = digits
+            .chars()
+            .filter(|c| *c != ',' && !c.is_whitespace())
+            .collect();
+
+        let value = BigDecimal::from_str(&digits).ok()?;
+        let value = if negative { -value } else { value };
+
+        match scale {
+            None | Some(0) => Some(value),
+            Some(scale) if scale.abs() <= MAX_SCALE => {
+                // value * 10^scale
+                let (mantissa, exponent) = value.as_bigint_and_exponent();
+                Some(BigDecimal::new(mantissa, exponent - i64::from(scale)))
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Convert a value in `measure` into canonical units
+    ///
+    /// `date` picks the exchange rate, normally the end of the fact's period.
+    /// Returns `None` for monetary facts whose currency has no rate.
+    pub async fn normalize(
+        &self,
+        value: &BigDecimal,
+        measure: &UnitMeasure,
+        date: Option<NaiveDate>,
+    ) -> Result<Option<NormalizedValue>> {
+        let Some(currency) = measure.currency() else {
+            return Ok(Some(NormalizedValue {
+                value: value.clone(),
+                unit: measure.to_string(),
+                fx_rate: None,
+            }));
+        };
+
+        let canonical = match measure {
+            UnitMeasure::CurrencyPerShare(_) => {
+                UnitMeasure::CurrencyPerShare(CANONICAL_CURRENCY.to_string())
+            }
+            _ => UnitMeasure::Currency(CANONICAL_CURRENCY.to_string()),
+        };
+
+        if currency == CANONICAL_CURRENCY {
+            return Ok(Some(NormalizedValue {
+                value: value.clone(),
+                unit: canonical.to_string(),
+                fx_rate: None,
+            }));
+        }
+
+        let Some(rate) = self
+            .fx_rates
+            .rate(currency, CANONICAL_CURRENCY, date)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(NormalizedValue {
+            value: value * &rate,
+            unit: canonical.to_string(),
+            fx_rate: Some(rate),
+        }))
+    }
+}
+
+/// Date whose exchange rate applies to facts in a context
+///
+/// The instant for balance sheet facts, the period end for duration facts.
+pub fn context_rate_date(context_ref: &str, contexts: &[XbrlContext]) -> Option<NaiveDate> {
+    let context = contexts.iter().find(|context| context.id == context_ref)?;
+    context
+        .period
+        .instant
+        .as_deref()
+        .or(context.period.end_date.as_deref())
+        .and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn unit(id: &str, measure: &str) -> XbrlUnit {
+        XbrlUnit {
+            id: id.to_string(),
+            measure: Some(measure.to_string()),
+            measure_namespace: None,
+            measure_local_name: None,
+            unit_type: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_reported_value_applies_scale() {
+        // REQUIREMENT: Facts are stored in absolute units regardless of display scale
+        // PURPOSE: Verify inline XBRL display values are scaled and decimals never are
+
+        assert_eq!(
+            UnitNormalizer::parse_reported_value("394,328", Some(6)),
+            Some(decimal("394328000000"))
+        );
+        assert_eq!(
+            UnitNormalizer::parse_reported_value("(1,250.5)", Some(3)),
+            Some(decimal("-1250500"))
+        );
+        assert_eq!(
+            UnitNormalizer::parse_reported_value("12.5", Some(-2)),
+            Some(decimal("0.125"))
+        );
+        assert_eq!(
+            UnitNormalizer::parse_reported_value("394328000000", None),
+            Some(decimal("394328000000"))
+        );
+        assert_eq!(UnitNormalizer::parse_reported_value("n/a", None), None);
+        assert_eq!(UnitNormalizer::parse_reported_value("1", Some(40)), None);
+    }
+
+    #[test]
+    fn test_resolve_unit_measures() {
+        let units = vec![
+            unit("usd", "iso4217:USD"),
+            unit("eur", "iso4217:EUR"),
+            unit("shares", "xbrli:shares"),
+            // Divide unit: only the denominator survives parsing
+            unit("usdPerShare", "xbrli:shares"),
+        ];
+
+        assert_eq!(
+            UnitMeasure::resolve(Some("usd"), &units),
+            UnitMeasure::Currency("USD".to_string())
+        );
+        assert_eq!(
+            UnitMeasure::resolve(Some("eur"), &units),
+            UnitMeasure::Currency("EUR".to_string())
+        );
+        assert_eq!(
+            UnitMeasure::resolve(Some("shares"), &units),
+            UnitMeasure::Shares
+        );
+        assert_eq!(
+            UnitMeasure::resolve(Some("usdPerShare"), &units),
+            UnitMeasure::CurrencyPerShare("USD".to_string())
+        );
+        assert_eq!(
+            UnitMeasure::resolve(Some("U_JPY"), &units),
+            UnitMeasure::Currency("JPY".to_string())
+        );
+        assert_eq!(
+            UnitMeasure::from_unit_id("EURPerShare"),
+            UnitMeasure::CurrencyPerShare("EUR".to_string())
+        );
+        assert_eq!(UnitMeasure::resolve(None, &units), UnitMeasure::Pure);
+        assert_eq!(
+            UnitMeasure::parse("iso4217:EUR/xbrli:shares"),
+            UnitMeasure::CurrencyPerShare("EUR".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_normalize_converts_currency_with_dated_rate() {
+        // REQUIREMENT: Facts in other currencies are converted to USD
+        // PURPOSE: Verify the rate in effect on the period end is applied and recorded
+
+        let provider = StaticFxRateProvider::new()
+            .with_rate(
+                "EUR",
+                NaiveDate::from_ymd_opt(2024, 6, 28).unwrap(),
+                decimal("1.07"),
+            )
+            .with_rate(
+                "EUR",
+                NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+                decimal("1.04"),
+            );
+        let normalizer = UnitNormalizer::new(Arc::new(provider));
+        let year_end = NaiveDate::from_ymd_opt(2024, 12, 31);
+
+        let revenue = normalizer
+            .normalize(
+                &decimal("1000000"),
+                &UnitMeasure::Currency("EUR".to_string()),
+                year_end,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(revenue.value, decimal("1040000"));
+        assert_eq!(revenue.unit, "USD");
+        assert_eq!(revenue.fx_rate, Some(decimal("1.04")));
+
+        let eps = normalizer
+            .normalize(
+                &decimal("2.00"),
+                &UnitMeasure::CurrencyPerShare("EUR".to_string()),
+                NaiveDate::from_ymd_opt(2024, 9, 30),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(eps.value, decimal("2.14"));
+        assert_eq!(eps.unit, "USD/shares");
+
+        let shares = normalizer
+            .normalize(&decimal("15000000"), &UnitMeasure::Shares, year_end)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(shares.unit, "shares");
+        assert_eq!(shares.fx_rate, None);
+
+        // No rate for yen: nothing to normalize into
+        let missing = normalizer
+            .normalize(
+                &decimal("100"),
+                &UnitMeasure::Currency("JPY".to_string()),
+                year_end,
+            )
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+}
@@ -97,11 +97,47 @@ pub fn build_xbrl_url(accession: &str) -> Result<String> {
     ))
 }
 
+/// Build the URL of a filing's primary document (the HTML 10-K/10-Q body)
+pub fn build_filing_document_url(accession: &str, primary_document: &str) -> Result<String> {
+    let components = parse_accession_number(accession)?;
+    let cik_unpadded = unpad_cik(&components.cik);
+    let accession_clean = accession.replace("-", "");
+
+    Ok(format!(
+        "https://www.sec.gov/Archives/edgar/data/{}/{}/{}",
+        cik_unpadded, accession_clean, primary_document
+    ))
+}
+
 /// Build company submissions URL from CIK
 pub fn build_submissions_url(cik: &str) -> String {
     format!("https://data.sec.gov/submissions/CIK{}.json", pad_cik(cik))
 }
 
+/// Build the URL of SEC's full company ticker list
+pub fn build_company_tickers_url() -> String {
+    "https://www.sec.gov/files/company_tickers.json".to_string()
+}
+
+/// Build the URL of EDGAR's master index of the filings made on `date`
+pub fn build_daily_index_url(date: &NaiveDate) -> String {
+    format!(
+        "https://www.sec.gov/Archives/edgar/daily-index/{}/QTR{}/master.{}.idx",
+        date.year(),
+        get_fiscal_quarter(date),
+        format_sec_date(date)
+    )
+}
+
+/// Build the URL of a file named in an EDGAR index,
+/// e.g. `edgar/data/320193/0000320193-24-000001.txt`
+pub fn build_archive_url(file_name: &str) -> String {
+    format!(
+        "https://www.sec.gov/Archives/{}",
+        file_name.trim_start_matches('/')
+    )
+}
+
 /// Build company facts URL from CIK
 pub fn build_company_facts_url(cik: &str) -> String {
     format!(
@@ -110,6 +146,29 @@ pub fn build_company_facts_url(cik: &str) -> String {
     )
 }
 
+/// **XML Utilities**
+///
+/// Helpers for reading EDGAR XML documents with `roxmltree`. Tag names are
+/// matched without their namespace, since filers use both prefixed and
+/// default namespaces.
+
+/// First child element named `tag`
+pub fn xml_child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    tag: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+/// Trimmed, non-empty text of the element at `path` below `node`
+pub fn xml_text<'a>(node: roxmltree::Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    let mut current = node;
+    for tag in path {
+        current = xml_child(current, tag)?;
+    }
+    current.text().map(str::trim).filter(|t| !t.is_empty())
+}
+
 /// **File Size Utilities**
 ///
 /// Utility functions for file size formatting and validation.
@@ -309,6 +368,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_filing_document_url() {
+        let url = build_filing_document_url("0000320193-23-000106", "aapl-20230930.htm").unwrap();
+        assert_eq!(
+            url,
+            "https://www.sec.gov/Archives/edgar/data/320193/000032019323000106/aapl-20230930.htm"
+        );
+    }
+
+    #[test]
+    fn test_build_daily_index_url() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(
+            build_daily_index_url(&date),
+            "https://www.sec.gov/Archives/edgar/daily-index/2024/QTR2/master.20240501.idx"
+        );
+    }
+
     #[test]
     fn test_format_file_size() {
         assert_eq!(format_file_size(0), "0 B");
@@ -9,17 +9,19 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::sync::Arc;
 use tokio::fs;
 use tokio::process::Command as AsyncCommand;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use xml::reader::{EventReader, XmlEvent};
 
+use crate::calculation_linkbase::{CalculationLinkbase, CalculationValidation};
 use crate::models::{StoredXbrlDocument, XbrlStorageStats};
-use bigdecimal::BigDecimal;
+use crate::unit_normalization::{context_rate_date, FxRateProvider, UnitMeasure, UnitNormalizer};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::enums::{CompressionType, ProcessingStatus, StatementSection, StatementType};
-use econ_graph_core::models::{Company, FinancialLineItem, FinancialStatement};
+use econ_graph_core::models::{Company, DocumentKind, FinancialLineItem, FinancialStatement};
 
 /// **XBRL Parser Configuration**
 ///
@@ -109,6 +111,7 @@ pub struct XbrlParser {
     statement_mapper: StatementMapper,
     fact_validator: FactValidator,
     dts_manager: Option<crate::dts_manager::DtsManager>,
+    unit_normalizer: UnitNormalizer,
 }
 
 impl XbrlParser {
@@ -148,9 +151,12 @@ impl XbrlParser {
             fs::create_dir_all(&taxonomy_cache_dir)
                 .await
                 .context("Failed to create taxonomy cache directory")?;
+            let compression_level =
+                crate::blob_store::compression_level_from_env(DocumentKind::Taxonomy)?;
             Some(crate::dts_manager::DtsManager::new(
                 pool,
                 taxonomy_cache_dir,
+                compression_level,
             ))
         } else {
             None
@@ -163,9 +169,18 @@ impl XbrlParser {
             statement_mapper,
             fact_validator,
             dts_manager,
+            unit_normalizer: UnitNormalizer::default(),
         })
     }
 
+    /// Use `provider` to convert facts reported in other currencies into USD
+    ///
+    /// Without one, only USD-denominated facts get a normalized monetary value.
+    pub fn with_fx_rates(mut self, provider: Arc<dyn FxRateProvider>) -> Self {
+        self.unit_normalizer = UnitNormalizer::new(provider);
+        self
+    }
+
     /// Verify that Arelle is properly installed and accessible
     async fn verify_arelle_installation(config: &XbrlParserConfig) -> Result<()> {
         let mut cmd = if let Some(ref python_env) = config.python_env {
@@ -328,7 +343,9 @@ impl XbrlParser {
         let taxonomy_concepts = self.extract_taxonomy_concepts_from_content(&content)?;
 
         let validation_report = self.fact_validator.validate_facts(&facts)?;
-        let line_items = self.extract_line_items_from_facts(&facts, &contexts)?;
+        let line_items = self
+            .extract_line_items_from_facts(&facts, &contexts, &units)
+            .await?;
 
         Ok(XbrlParseResult {
             statements,
@@ -386,7 +403,9 @@ impl XbrlParser {
         let statements = self
             .statement_mapper
             .map_facts_to_statements(&facts, &contexts)?;
-        let line_items = self.extract_line_items_from_facts(&facts, &contexts)?;
+        let line_items = self
+            .extract_line_items_from_facts(&facts, &contexts, &units)
+            .await?;
 
         // Extract taxonomy information
         let taxonomy_concepts = self.extract_taxonomy_concepts_from_xml(&xml_result)?;
@@ -557,6 +576,7 @@ impl XbrlParser {
             restatement_reason: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            xbrl_file_blob_hash: None,
         };
 
         Ok(Some(statement))
@@ -626,6 +646,28 @@ impl XbrlParser {
         })
     }
 
+    /// Check parsed facts against the filing's calculation linkbases
+    ///
+    /// Linkbases that fail to parse are skipped with a warning so one malformed
+    /// taxonomy file does not hide inconsistencies found by the others.
+    pub fn validate_calculations(
+        &self,
+        facts: &[XbrlFact],
+        calculation_linkbases: &[String],
+    ) -> CalculationValidation {
+        let linkbases = calculation_linkbases.iter().filter_map(|content| {
+            match CalculationLinkbase::parse(content) {
+                Ok(linkbase) => Some(linkbase),
+                Err(e) => {
+                    warn!("Skipping unparseable calculation linkbase: {}", e);
+                    None
+                }
+            }
+        });
+
+        CalculationLinkbase::merge(linkbases).validate(facts)
+    }
+
     /// Extract taxonomy concepts from XBRL document
     pub async fn extract_taxonomy_concepts(
         &self,
@@ -647,6 +689,80 @@ impl XbrlParser {
     }
 }
 
+/// Extract the facts of an XBRL instance document without Arelle
+///
+/// Used where only the facts are needed, e.g. calculation validation during a crawl.
+pub fn parse_instance_facts(content: &str) -> Result<Vec<XbrlFact>> {
+    Ok(XbrlXmlParser::new().parse(content)?.facts)
+}
+
+/// Element name without its namespace prefix
+fn local_name(name: &[u8]) -> &[u8] {
+    match name.iter().position(|byte| *byte == b':') {
+        Some(colon) => &name[colon + 1..],
+        None => name,
+    }
+}
+
+/// Read the explicit members of a `segment` or `scenario` up to its end tag
+///
+/// Typed members are skipped: their values are arbitrary XML, not members of
+/// a domain, and SEC filings rarely use them.
+fn parse_explicit_members(
+    reader: &mut Reader<&[u8]>,
+    container: &[u8],
+    dimensions: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut dimension: Option<String> = None;
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(quick_xml::events::Event::Start(ref e))
+                if local_name(e.name().as_ref()) == b"explicitMember" =>
+            {
+                dimension = None;
+                for attr in e.attributes() {
+                    let attr = attr?;
+                    if attr.key.as_ref() == b"dimension" {
+                        dimension = Some(String::from_utf8_lossy(&attr.value).trim().to_string());
+                    }
+                }
+            }
+            Ok(quick_xml::events::Event::Text(e)) => {
+                if let Some(dimension) = dimension.take() {
+                    let member = String::from_utf8_lossy(e.as_ref()).trim().to_string();
+                    if !dimension.is_empty() && !member.is_empty() {
+                        dimensions.insert(dimension, member);
+                    }
+                }
+            }
+            Ok(quick_xml::events::Event::End(ref e)) => {
+                if local_name(e.name().as_ref()) == container {
+                    break;
+                }
+            }
+            Ok(quick_xml::events::Event::Eof) => break,
+            Err(e) => return Err(anyhow::anyhow!("Error parsing dimensions: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+/// Explicit dimension members of a context as a JSON object, `{}` without any
+fn dimensions_json(dimensions: &BTreeMap<String, String>) -> serde_json::Value {
+    serde_json::Value::Object(
+        dimensions
+            .iter()
+            .map(|(dimension, member)| {
+                (dimension.clone(), serde_json::Value::String(member.clone()))
+            })
+            .collect(),
+    )
+}
+
 /// **XBRL Cache**
 ///
 /// Cache for parsed XBRL results to avoid re-parsing.
@@ -746,6 +862,9 @@ pub struct XbrlFact {
     pub decimals: Option<i32>,
     pub precision: Option<i32>,
     pub fact_type: Option<String>,
+    /// Inline XBRL `scale`: the displayed value is in units of 10^scale
+    #[serde(default)]
+    pub scale: Option<i32>,
 }
 
 /// **XBRL Context**
@@ -759,6 +878,12 @@ pub struct XbrlContext {
     pub scenario: Option<XbrlScenario>,
     pub entity_identifier: Option<String>,
     pub segment: Option<serde_json::Value>,
+    /// Explicit members of the segment and scenario, by dimension (axis)
+    ///
+    /// E.g. `us-gaap:StatementBusinessSegmentsAxis` → `aapl:IPhoneMember`.
+    /// Empty for facts about the entity as a whole.
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, String>,
 }
 
 /// **XBRL Entity**
@@ -1131,6 +1256,7 @@ impl XbrlXmlParser {
             decimals: None,
             precision: None,
             fact_type: None,
+            scale: None,
         };
 
         // Get element name as concept
@@ -1204,6 +1330,7 @@ impl XbrlXmlParser {
             scenario: None,
             entity_identifier: None,
             segment: None,
+            dimensions: BTreeMap::new(),
         };
 
         // Get context ID
@@ -1225,10 +1352,15 @@ impl XbrlXmlParser {
             match reader.read_event_into(&mut buf) {
                 Ok(quick_xml::events::Event::Start(ref e)) => match e.name().as_ref() {
                     b"entity" => {
-                        if let Some(entity) = self.parse_entity_element(e, reader)? {
+                        if let Some(entity) =
+                            self.parse_entity_element(e, reader, &mut context.dimensions)?
+                        {
                             context.entity = entity;
                         }
                     }
+                    name if local_name(name) == b"scenario" => {
+                        parse_explicit_members(reader, b"scenario", &mut context.dimensions)?;
+                    }
                     b"period" => {
                         if let Some(period) = self.parse_period_element(e, reader)? {
                             context.period = period;
@@ -1256,6 +1388,7 @@ impl XbrlXmlParser {
         &self,
         _element: &quick_xml::events::BytesStart,
         reader: &mut Reader<&[u8]>,
+        dimensions: &mut BTreeMap<String, String>,
     ) -> Result<Option<XbrlEntity>> {
         let mut entity = XbrlEntity {
             identifier: String::new(),
@@ -1266,7 +1399,9 @@ impl XbrlXmlParser {
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(quick_xml::events::Event::Start(ref e)) => {
-                    if e.name().as_ref() == b"identifier" {
+                    if local_name(e.name().as_ref()) == b"segment" {
+                        parse_explicit_members(reader, b"segment", dimensions)?;
+                    } else if e.name().as_ref() == b"identifier" {
                         for attr in e.attributes() {
                             let attr = attr?;
                             if attr.key.as_ref() == b"scheme" {
@@ -1530,8 +1665,11 @@ impl XbrlParser {
             decimals: None,
             precision: None,
             fact_type: None,
+            scale: None,
         };
 
+        let mut negative = false;
+
         // Parse attributes
         for attr in element.attributes() {
             let attr = attr?;
@@ -1553,6 +1691,12 @@ impl XbrlParser {
                         fact.precision = precision_str.parse().ok();
                     }
                 }
+                b"scale" => {
+                    if let Ok(scale_str) = String::from_utf8(attr.value.to_vec()) {
+                        fact.scale = scale_str.parse().ok();
+                    }
+                }
+                b"sign" => negative = attr.value.as_ref() == b"-",
                 _ => {}
             }
         }
@@ -1574,7 +1718,13 @@ impl XbrlParser {
         }
 
         if !content.trim().is_empty() {
-            fact.value = Some(content.trim().to_string());
+            // Inline XBRL displays magnitudes; `sign="-"` carries the sign
+            let content = content.trim();
+            fact.value = Some(if negative {
+                format!("-{}", content)
+            } else {
+                content.to_string()
+            });
         }
 
         Ok(Some(fact))
@@ -1627,6 +1777,7 @@ impl XbrlParser {
             scenario: None,
             entity_identifier: None,
             segment: None,
+            dimensions: BTreeMap::new(),
         };
 
         // Get context ID
@@ -1648,10 +1799,15 @@ impl XbrlParser {
             match reader.read_event_into(&mut buf) {
                 Ok(quick_xml::events::Event::Start(ref e)) => match e.name().as_ref() {
                     b"entity" => {
-                        if let Some(entity) = self.parse_entity_element(e, reader)? {
+                        if let Some(entity) =
+                            self.parse_entity_element(e, reader, &mut context.dimensions)?
+                        {
                             context.entity = entity;
                         }
                     }
+                    name if local_name(name) == b"scenario" => {
+                        parse_explicit_members(reader, b"scenario", &mut context.dimensions)?;
+                    }
                     b"period" => {
                         if let Some(period) = self.parse_period_element(e, reader)? {
                             context.period = period;
@@ -1679,6 +1835,7 @@ impl XbrlParser {
         &self,
         _element: &quick_xml::events::BytesStart,
         reader: &mut Reader<&[u8]>,
+        dimensions: &mut BTreeMap<String, String>,
     ) -> Result<Option<XbrlEntity>> {
         let mut entity = XbrlEntity {
             identifier: String::new(),
@@ -1689,7 +1846,9 @@ impl XbrlParser {
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(quick_xml::events::Event::Start(ref e)) => {
-                    if e.name().as_ref() == b"identifier" {
+                    if local_name(e.name().as_ref()) == b"segment" {
+                        parse_explicit_members(reader, b"segment", dimensions)?;
+                    } else if e.name().as_ref() == b"identifier" {
                         for attr in e.attributes() {
                             let attr = attr?;
                             if attr.key.as_ref() == b"scheme" {
@@ -1893,16 +2052,39 @@ impl XbrlParser {
     }
 
     /// Extract line items from facts
-    fn extract_line_items_from_facts(
+    ///
+    /// Each item keeps the value and unit as reported alongside the value in
+    /// canonical units (see [`UnitNormalizer`]).
+    async fn extract_line_items_from_facts(
         &self,
         facts: &[XbrlFact],
         contexts: &[XbrlContext],
+        units: &[XbrlUnit],
     ) -> Result<Vec<FinancialLineItem>> {
         let mut line_items = Vec::new();
 
         for fact in facts {
             if let Some(value_str) = &fact.value {
-                if let Ok(value) = value_str.parse::<BigDecimal>() {
+                if let Some(value) = UnitNormalizer::parse_reported_value(value_str, None) {
+                    let measure = UnitMeasure::resolve(fact.unit_ref.as_deref(), units);
+                    let context = contexts.iter().find(|c| c.id == fact.context_ref);
+                    let period_end_date = context_rate_date(&fact.context_ref, contexts);
+                    let normalized =
+                        match UnitNormalizer::parse_reported_value(value_str, fact.scale) {
+                            Some(scaled) => {
+                                self.unit_normalizer
+                                    .normalize(&scaled, &measure, period_end_date)
+                                    .await?
+                            }
+                            None => None,
+                        };
+                    if normalized.is_none() {
+                        debug!(
+                            "No normalized value for {} in {} ({})",
+                            fact.concept, measure, fact.context_ref
+                        );
+                    }
+
                     let line_item = FinancialLineItem {
                         id: Uuid::new_v4(),
                         statement_id: Uuid::new_v4(), // This should be determined from context
@@ -1910,12 +2092,12 @@ impl XbrlParser {
                         standard_label: Some(self.map_concept_to_label(&fact.concept)),
                         custom_label: None,
                         value: Some(value),
-                        unit: self.extract_unit_from_fact(fact, contexts),
+                        unit: measure.to_string(),
                         context_ref: fact.context_ref.clone(),
                         segment_ref: None,
                         scenario_ref: None,
-                        precision: None,
-                        decimals: None,
+                        precision: fact.precision,
+                        decimals: fact.decimals,
                         is_credit: None,
                         is_debit: None,
                         statement_type: match self.determine_statement_type(&fact.concept).as_str()
@@ -1944,6 +2126,18 @@ impl XbrlParser {
                         calculation_formula: None,
                         created_at: Utc::now(),
                         updated_at: Utc::now(),
+                        normalized_value: normalized.as_ref().map(|n| n.value.clone()),
+                        normalized_unit: normalized.as_ref().map(|n| n.unit.clone()),
+                        fx_rate: normalized.and_then(|n| n.fx_rate),
+                        dimensions: context
+                            .map(|c| dimensions_json(&c.dimensions))
+                            .unwrap_or_else(|| serde_json::json!({})),
+                        period_start_date: context
+                            .and_then(|c| c.period.start_date.as_deref())
+                            .and_then(|date| {
+                                NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()
+                            }),
+                        period_end_date,
                     };
                     line_items.push(line_item);
                 }
@@ -1968,18 +2162,6 @@ impl XbrlParser {
         }
     }
 
-    /// Extract unit from fact
-    fn extract_unit_from_fact(&self, fact: &XbrlFact, contexts: &[XbrlContext]) -> String {
-        // Default to USD for monetary items
-        if let Some(unit_ref) = &fact.unit_ref {
-            // In practice, would look up unit in units collection
-            if unit_ref.contains("USD") {
-                return "USD".to_string();
-            }
-        }
-        "USD".to_string()
-    }
-
     /// Determine statement type from concept
     fn determine_statement_type(&self, concept: &str) -> String {
         if concept.contains("Assets")
@@ -2159,4 +2341,48 @@ mod tests {
         cache.add_concept(concept.clone());
         assert_eq!(cache.get_concept("Assets"), Some(&concept));
     }
+
+    #[test]
+    fn test_context_dimensions() {
+        // REQUIREMENT: Dimensional XBRL facts keep their segment and scenario members
+        // PURPOSE: Verify explicit members of a context's segment and scenario are captured by axis
+        // This ensures segment revenue is not flattened into the company total
+
+        let content = r#"<xbrl>
+            <context id="FY2024">
+                <entity><identifier scheme="http://www.sec.gov/CIK">0000320193</identifier></entity>
+                <period><startDate>2024-01-01</startDate><endDate>2024-12-31</endDate></period>
+            </context>
+            <context id="FY2024_Services">
+                <entity>
+                    <identifier scheme="http://www.sec.gov/CIK">0000320193</identifier>
+                    <segment>
+                        <xbrldi:explicitMember dimension="us-gaap:StatementBusinessSegmentsAxis">aapl:ServicesMember</xbrldi:explicitMember>
+                    </segment>
+                </entity>
+                <period><startDate>2024-01-01</startDate><endDate>2024-12-31</endDate></period>
+                <scenario>
+                    <xbrldi:explicitMember dimension="srt:StatementScenarioAxis">srt:RestatementAdjustmentMember</xbrldi:explicitMember>
+                </scenario>
+            </context>
+            <us-gaap:Revenues contextRef="FY2024_Services" unitRef="usd" decimals="-6">96169000000</us-gaap:Revenues>
+        </xbrl>"#;
+
+        let result = XbrlXmlParser::new().parse(content).unwrap();
+
+        assert_eq!(result.contexts.len(), 2);
+        assert!(result.contexts[0].dimensions.is_empty());
+        let services = &result.contexts[1];
+        assert_eq!(services.entity.identifier, "0000320193");
+        assert_eq!(services.period.end_date.as_deref(), Some("2024-12-31"));
+        assert_eq!(
+            dimensions_json(&services.dimensions),
+            serde_json::json!({
+                "us-gaap:StatementBusinessSegmentsAxis": "aapl:ServicesMember",
+                "srt:StatementScenarioAxis": "srt:RestatementAdjustmentMember",
+            })
+        );
+        assert_eq!(result.facts.len(), 1);
+        assert_eq!(result.facts[0].context_ref, "FY2024_Services");
+    }
 }
@@ -121,6 +121,7 @@ fn test_xbrl_fact_creation() {
         decimals: Some(0),
         precision: None,
         fact_type: Some("monetaryItemType".to_string()),
+        scale: None,
     };
 
     assert_eq!(fact.concept, "us-gaap:Assets");
@@ -148,6 +149,7 @@ fn test_xbrl_context_creation() {
         scenario: None,
         entity_identifier: Some("0001234567".to_string()),
         segment: None,
+        dimensions: Default::default(),
     };
 
     assert_eq!(context.id, "c1");
@@ -305,6 +307,7 @@ async fn test_xbrl_cache_operations() {
         restatement_reason: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        xbrl_file_blob_hash: None,
     }];
 
     // Create a test file
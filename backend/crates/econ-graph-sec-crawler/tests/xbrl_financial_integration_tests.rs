@@ -162,6 +162,12 @@ mod tests {
                 statement_section: StatementSection::Assets,
                 parent_concept: None,
                 level: 1,
+                normalized_value: None,
+                normalized_unit: None,
+                fx_rate: None,
+                dimensions: serde_json::json!({}),
+                period_start_date: None,
+                period_end_date: None,
             },
             NewFinancialLineItem {
                 statement_id: statement.id,
@@ -184,6 +190,12 @@ mod tests {
                 statement_section: StatementSection::Liabilities,
                 parent_concept: None,
                 level: 1,
+                normalized_value: None,
+                normalized_unit: None,
+                fx_rate: None,
+                dimensions: serde_json::json!({}),
+                period_start_date: None,
+                period_end_date: None,
             },
         ];
 
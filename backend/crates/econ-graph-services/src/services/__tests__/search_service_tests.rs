@@ -40,6 +40,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(10),
@@ -84,6 +86,8 @@ mod tests {
             source_id: Some(source_id),
             category: None,
             frequency: Some("Monthly".to_string()),
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Title,
             limit: Some(5),
@@ -103,6 +107,8 @@ mod tests {
             source_id: None,
             category: Some("Employment".to_string()),
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::LastUpdated,
             limit: Some(10),
@@ -180,6 +186,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(10),
@@ -231,6 +239,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(50),
@@ -270,6 +280,8 @@ mod tests {
             source_id: Some(Uuid::new_v4()), // Non-existent source
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(0), // Invalid limit
@@ -289,6 +301,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(10),
@@ -326,6 +340,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Relevance,
             limit: Some(10),
@@ -346,6 +362,8 @@ mod tests {
             source_id: None,
             category: None,
             frequency: None,
+            geography: None,
+            topic: None,
             is_active: Some(true),
             sort_by: SearchSortOrder::Title,
             limit: Some(10),
@@ -0,0 +1,274 @@
+//! # Aligned Series
+//!
+//! "As reported" views of several series: each series as it was known on a
+//! vintage date, aligned on one date index. A value was known on `as_of` when
+//! its revision date is on or before it; later revisions and corrections are
+//! ignored, and of several revisions the latest known one is used.
+//!
+//! With a frequency, series observed more often are first resampled (see
+//! [`resample`]) so that, e.g., monthly and quarterly series share quarters.
+//! Only periods their observations fully cover are kept. Series already at the
+//! frequency are aligned on the start of their periods.
+
+use std::collections::BTreeMap;
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{DataPoint, EconomicSeries},
+    schema::data_points,
+};
+
+use crate::services::series_service::{
+    get_series_by_id, resample, ResampleFrequency, ResampleMethod, SourceFrequency,
+};
+
+/// Most series one aligned view may combine
+pub const MAX_ALIGNED_SERIES: usize = 20;
+
+/// Several series as known on a vintage date, on a common date index
+#[derive(Debug, Clone)]
+pub struct AlignedSeries {
+    pub as_of: NaiveDate,
+    pub frequency: Option<ResampleFrequency>,
+    /// Columns of the view, in the order they were requested
+    pub series: Vec<EconomicSeries>,
+    /// Dates any series has a value for, oldest first
+    pub rows: Vec<AlignedRow>,
+}
+
+/// One date of an aligned view
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedRow {
+    /// Observation date, or the first day of the period when resampled
+    pub date: NaiveDate,
+    /// One value per series, `None` where a series has no value for the date
+    pub values: Vec<Option<BigDecimal>>,
+}
+
+/// Align `series_ids` as they were known on `as_of`
+///
+/// Duplicate IDs are combined. Unknown series fail with `SeriesNotFound`, and
+/// series observed less often than `frequency` cannot be aligned to it.
+pub async fn get_aligned_series(
+    pool: &DatabasePool,
+    series_ids: &[Uuid],
+    as_of: NaiveDate,
+    frequency: Option<ResampleFrequency>,
+    method: ResampleMethod,
+) -> AppResult<AlignedSeries> {
+    let mut ids: Vec<Uuid> = Vec::with_capacity(series_ids.len());
+    for id in series_ids {
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+    if ids.is_empty() || ids.len() > MAX_ALIGNED_SERIES {
+        return Err(AppError::ValidationError(format!(
+            "Align between 1 and {} series",
+            MAX_ALIGNED_SERIES
+        )));
+    }
+
+    let mut series = Vec::with_capacity(ids.len());
+    for id in &ids {
+        series.push(
+            get_series_by_id(pool, *id)
+                .await?
+                .ok_or_else(|| AppError::SeriesNotFound(id.to_string()))?,
+        );
+    }
+
+    let mut points_by_series: BTreeMap<Uuid, Vec<DataPoint>> = BTreeMap::new();
+    for point in vintage_points(pool, &ids, as_of).await? {
+        points_by_series
+            .entry(point.series_id)
+            .or_default()
+            .push(point);
+    }
+
+    let columns = series
+        .iter()
+        .map(|s| {
+            let points = points_by_series.remove(&s.id).unwrap_or_default();
+            column(&points, &s.frequency, frequency, method)
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    Ok(AlignedSeries {
+        as_of,
+        frequency,
+        series,
+        rows: align(&columns),
+    })
+}
+
+/// Latest revision known on `as_of` of each observation of the series, by date
+async fn vintage_points(
+    pool: &DatabasePool,
+    series_ids: &[Uuid],
+    as_of: NaiveDate,
+) -> AppResult<Vec<DataPoint>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let points = data_points::table
+        .filter(data_points::series_id.eq_any(series_ids))
+        .filter(data_points::revision_date.le(as_of))
+        .distinct_on((data_points::series_id, data_points::date))
+        .order_by((
+            data_points::series_id,
+            data_points::date,
+            data_points::revision_date.desc(),
+            data_points::created_at.desc(),
+        ))
+        .select(DataPoint::as_select())
+        .load::<DataPoint>(&mut conn)
+        .await?;
+
+    Ok(points)
+}
+
+/// Dated values of one series at the view's frequency
+fn column(
+    points: &[DataPoint],
+    series_frequency: &str,
+    frequency: Option<ResampleFrequency>,
+    method: ResampleMethod,
+) -> AppResult<Vec<(NaiveDate, BigDecimal)>> {
+    let Some(frequency) = frequency else {
+        return Ok(points
+            .iter()
+            .filter_map(|point| Some((point.date, point.value.clone()?)))
+            .collect());
+    };
+
+    match SourceFrequency::parse(series_frequency) {
+        Some(source) if source.rank() == frequency.rank() => Ok(points
+            .iter()
+            .filter_map(|point| Some((frequency.period_start(point.date), point.value.clone()?)))
+            .collect()),
+        _ => Ok(resample(points, series_frequency, frequency, method)?
+            .into_iter()
+            .filter(|period| period.is_complete)
+            .map(|period| (period.period_start, period.value))
+            .collect()),
+    }
+}
+
+/// Rows of every date in `columns`, with one value per column
+fn align(columns: &[Vec<(NaiveDate, BigDecimal)>]) -> Vec<AlignedRow> {
+    let mut rows: BTreeMap<NaiveDate, Vec<Option<BigDecimal>>> = BTreeMap::new();
+
+    for (index, column) in columns.iter().enumerate() {
+        for (date, value) in column {
+            rows.entry(*date)
+                .or_insert_with(|| vec![None; columns.len()])[index] = Some(value.clone());
+        }
+    }
+
+    rows.into_iter()
+        .map(|(date, values)| AlignedRow { date, values })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn point(on: NaiveDate, value: i64) -> DataPoint {
+        DataPoint {
+            id: Uuid::new_v4(),
+            series_id: Uuid::new_v4(),
+            date: on,
+            value: Some(BigDecimal::from(value)),
+            revision_date: on,
+            is_original_release: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_align_leaves_gaps_empty() {
+        // REQUIREMENT: Multi-series views share one date index
+        // PURPOSE: Verify rows cover every date of any series, oldest first, with None where a series has no value
+        // This ensures series with different release calendars line up without dropping observations
+
+        let rows = align(&[
+            vec![
+                (date(2024, 1, 1), BigDecimal::from(1)),
+                (date(2024, 3, 1), BigDecimal::from(3)),
+            ],
+            vec![(date(2024, 2, 1), BigDecimal::from(20))],
+        ]);
+
+        assert_eq!(
+            rows,
+            vec![
+                AlignedRow {
+                    date: date(2024, 1, 1),
+                    values: vec![Some(BigDecimal::from(1)), None],
+                },
+                AlignedRow {
+                    date: date(2024, 2, 1),
+                    values: vec![None, Some(BigDecimal::from(20))],
+                },
+                AlignedRow {
+                    date: date(2024, 3, 1),
+                    values: vec![Some(BigDecimal::from(3)), None],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_columns_share_quarters() {
+        // REQUIREMENT: Series of different frequencies can be aligned on a common frequency
+        // PURPOSE: Verify monthly series are resampled to complete quarters and quarterly series keyed by quarter start
+        // This ensures monthly and quarterly series land on the same rows
+
+        let monthly: Vec<DataPoint> = (1..=4)
+            .map(|month| point(date(2024, month, 1), month as i64))
+            .collect();
+        let quarterly = vec![point(date(2024, 1, 1), 100)];
+
+        let monthly = column(
+            &monthly,
+            "Monthly",
+            Some(ResampleFrequency::Quarterly),
+            ResampleMethod::Sum,
+        )
+        .unwrap();
+        assert_eq!(monthly, vec![(date(2024, 1, 1), BigDecimal::from(6))]);
+
+        let quarterly = column(
+            &quarterly,
+            "Quarterly",
+            Some(ResampleFrequency::Quarterly),
+            ResampleMethod::Sum,
+        )
+        .unwrap();
+        assert_eq!(quarterly, vec![(date(2024, 1, 1), BigDecimal::from(100))]);
+
+        assert!(column(
+            &[point(date(2024, 1, 1), 1)],
+            "Annual",
+            Some(ResampleFrequency::Quarterly),
+            ResampleMethod::Sum,
+        )
+        .is_err());
+    }
+}
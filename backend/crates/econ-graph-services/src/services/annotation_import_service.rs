@@ -0,0 +1,522 @@
+/**
+ * REQUIREMENT: Import chart annotations teams keep in spreadsheets
+ * PURPOSE: Parse an uploaded CSV of annotations, check every row, and create
+ * the valid ones in one statement, reporting the outcome of each row so the
+ * spreadsheet can be fixed and the failed rows uploaded again
+ */
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    enums::DataAccessPolicy,
+    error::{AppError, AppResult},
+    models::organization::OrganizationMember,
+    models::user::NewChartAnnotation,
+    schema::{chart_annotations, data_sources, economic_series},
+};
+
+/// Most rows one file may hold
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+/// Largest file accepted, ample for [`MAX_IMPORT_ROWS`] rows with long descriptions
+pub const MAX_IMPORT_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Annotation type of rows that do not name one
+const DEFAULT_ANNOTATION_TYPE: &str = "line";
+
+/// An annotation read from one row, before its series is resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationRow {
+    /// Series UUID or external ID, e.g. UNRATE
+    pub series: String,
+    pub annotation_date: NaiveDate,
+    pub annotation_value: Option<BigDecimal>,
+    pub title: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub annotation_type: String,
+    pub tags: Vec<String>,
+    pub is_public: bool,
+}
+
+/// Outcome of one row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationImportRow {
+    /// Line of the file, counting the header as line 1
+    pub line: usize,
+    /// Created annotation, if the row was imported
+    pub annotation_id: Option<Uuid>,
+    /// Why the row was not imported
+    pub error: Option<String>,
+}
+
+/// Outcome of importing a file, one entry per row in file order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnnotationImportReport {
+    pub rows: Vec<AnnotationImportRow>,
+}
+
+impl AnnotationImportReport {
+    pub fn created(&self) -> usize {
+        self.rows.iter().filter(|row| row.error.is_none()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.rows.len() - self.created()
+    }
+}
+
+/// Parse a CSV of annotations
+///
+/// The header names the columns, in any order and case: `series`, `date` and
+/// `title` are required; `value`, `description`, `color`, `type`, `tags`
+/// (separated by `;`) and `public` are optional. Returns each data row's line
+/// with the annotation or the reason it is invalid. A file without the
+/// required columns, or with more than [`MAX_IMPORT_ROWS`] rows, is rejected.
+pub fn parse_annotations_csv(
+    content: &[u8],
+) -> AppResult<Vec<(usize, Result<AnnotationRow, String>)>> {
+    let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::ParserError(format!("Invalid annotations CSV: {}", e)))?
+        .clone();
+    let column = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|header| names.iter().any(|name| header.eq_ignore_ascii_case(name)))
+    };
+    let required = |names: &[&str]| {
+        column(names).ok_or_else(|| {
+            AppError::ValidationError(format!("Annotations CSV has no {} column", names[0]))
+        })
+    };
+    let columns = Columns {
+        series: required(&["series", "series_id"])?,
+        date: required(&["date", "annotation_date"])?,
+        title: required(&["title"])?,
+        value: column(&["value", "annotation_value"]),
+        description: column(&["description", "content"]),
+        color: column(&["color"]),
+        annotation_type: column(&["type", "annotation_type"]),
+        tags: column(&["tags"]),
+        is_public: column(&["public", "is_public"]),
+    };
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        if rows.len() == MAX_IMPORT_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "Annotations CSV has more than {} rows",
+                MAX_IMPORT_ROWS
+            )));
+        }
+        // Quoted fields may span lines and blank lines are skipped, so the
+        // line is where the record starts rather than the row count
+        let (position, row) = match record {
+            Ok(record) if record.iter().all(str::is_empty) => continue,
+            Ok(record) => (record.position().cloned(), parse_row(&columns, &record)),
+            Err(e) => (e.position().cloned(), Err(format!("Unreadable row: {}", e))),
+        };
+        let line = position
+            .and_then(|position| usize::try_from(position.line()).ok())
+            .unwrap_or(rows.len() + 2);
+        rows.push((line, row));
+    }
+
+    Ok(rows)
+}
+
+/// Positions of the columns in the header
+struct Columns {
+    series: usize,
+    date: usize,
+    title: usize,
+    value: Option<usize>,
+    description: Option<usize>,
+    color: Option<usize>,
+    annotation_type: Option<usize>,
+    tags: Option<usize>,
+    is_public: Option<usize>,
+}
+
+fn parse_row(columns: &Columns, record: &csv::StringRecord) -> Result<AnnotationRow, String> {
+    let field = |column: Option<usize>| {
+        column
+            .and_then(|column| record.get(column))
+            .filter(|value| !value.is_empty())
+    };
+
+    let series = field(Some(columns.series)).ok_or("series is required")?;
+    let date = field(Some(columns.date)).ok_or("date is required")?;
+    let annotation_date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("date '{}' is not a YYYY-MM-DD date", date))?;
+    let title = field(Some(columns.title)).ok_or("title is required")?;
+    if title.chars().count() > 255 {
+        return Err("title is longer than 255 characters".to_string());
+    }
+
+    let annotation_value = field(columns.value)
+        .map(|value| {
+            BigDecimal::from_str(value).map_err(|_| format!("value '{}' is not a number", value))
+        })
+        .transpose()?;
+    let color = field(columns.color)
+        .map(|color| {
+            let valid = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            valid
+                .then(|| color.to_lowercase())
+                .ok_or_else(|| format!("color '{}' is not a #rrggbb hex color", color))
+        })
+        .transpose()?;
+    let annotation_type = field(columns.annotation_type)
+        .unwrap_or(DEFAULT_ANNOTATION_TYPE)
+        .to_lowercase();
+    if annotation_type.len() > 20 {
+        return Err("type is longer than 20 characters".to_string());
+    }
+    let tags = field(columns.tags)
+        .map(|tags| {
+            tags.split(';')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let is_public = match field(columns.is_public).map(str::to_lowercase).as_deref() {
+        None | Some("false" | "no" | "0") => false,
+        Some("true" | "yes" | "1") => true,
+        Some(other) => return Err(format!("public '{}' is not true or false", other)),
+    };
+
+    Ok(AnnotationRow {
+        series: series.to_string(),
+        annotation_date,
+        annotation_value,
+        title: title.to_string(),
+        description: field(columns.description).map(str::to_string),
+        color,
+        annotation_type,
+        tags,
+        is_public,
+    })
+}
+
+/// Series ID and the access policy of its data source
+type ResolvedSeries = (Uuid, DataAccessPolicy);
+
+/// Resolve series references to series IDs and their sources' access policies
+///
+/// A reference is a series UUID or an external ID; an external ID used by
+/// series of several sources is ambiguous and must be given as a UUID.
+async fn resolve_series(
+    pool: &DatabasePool,
+    references: Vec<&str>,
+) -> AppResult<HashMap<String, Result<ResolvedSeries, String>>> {
+    let mut conn = pool
+        .get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let (ids, external_ids): (Vec<&str>, Vec<&str>) = references
+        .into_iter()
+        .partition(|reference| Uuid::parse_str(reference).is_ok());
+    let ids: Vec<Uuid> = ids
+        .iter()
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect();
+
+    let existing: HashMap<Uuid, DataAccessPolicy> = economic_series::table
+        .inner_join(data_sources::table)
+        .filter(economic_series::id.eq_any(&ids))
+        .select((economic_series::id, data_sources::access_policy))
+        .load::<ResolvedSeries>(&mut conn)
+        .await?
+        .into_iter()
+        .collect();
+    let by_external_id: Vec<(String, Uuid, DataAccessPolicy)> = economic_series::table
+        .inner_join(data_sources::table)
+        .filter(economic_series::external_id.eq_any(&external_ids))
+        .select((
+            economic_series::external_id,
+            economic_series::id,
+            data_sources::access_policy,
+        ))
+        .load(&mut conn)
+        .await?;
+
+    let mut resolved = HashMap::new();
+    for id in ids {
+        let series = match existing.get(&id) {
+            Some(policy) => Ok((id, *policy)),
+            None => Err(format!("series {} does not exist", id)),
+        };
+        resolved.insert(id.to_string(), series);
+    }
+    for external_id in external_ids {
+        let mut matches = by_external_id
+            .iter()
+            .filter(|(candidate, _, _)| candidate == external_id);
+        let series = match (matches.next(), matches.next()) {
+            (Some((_, id, policy)), None) => Ok((*id, *policy)),
+            (None, _) => Err(format!("series {} does not exist", external_id)),
+            (Some(_), Some(_)) => Err(format!(
+                "series {} exists in several sources; use its ID",
+                external_id
+            )),
+        };
+        resolved.insert(external_id.to_string(), series);
+    }
+
+    Ok(resolved)
+}
+
+/// Import a CSV of annotations for a user
+///
+/// Rows are checked independently; the valid ones are created together and
+/// the report tells which rows failed and why. A row fails if `may_read`
+/// rejects the access policy of its series' data source, so users only
+/// annotate series they may read. With an `organization_id` every imported
+/// annotation is shared with that organization, which needs a membership that
+/// allows sharing.
+pub async fn import_annotations(
+    pool: &DatabasePool,
+    user_id: Uuid,
+    organization_id: Option<Uuid>,
+    content: &[u8],
+    may_read: impl Fn(DataAccessPolicy) -> bool,
+) -> AppResult<AnnotationImportReport> {
+    if let Some(org_id) = organization_id {
+        let member = OrganizationMember::find(pool, org_id, user_id).await?;
+        if !member.is_some_and(|m| m.organization_role().can_share()) {
+            return Err(AppError::Forbidden(
+                "Not allowed to share annotations with this organization".to_string(),
+            ));
+        }
+    }
+
+    let parsed = parse_annotations_csv(content)?;
+    let references = parsed
+        .iter()
+        .filter_map(|(_, row)| row.as_ref().ok())
+        .map(|row| row.series.as_str())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let series = resolve_series(pool, references).await?;
+
+    let mut rows = Vec::with_capacity(parsed.len());
+    let mut new_annotations = Vec::new();
+    for (line, row) in parsed {
+        let annotation = row.and_then(|row| {
+            let (series_id, policy) = series
+                .get(&row.series)
+                .cloned()
+                .unwrap_or_else(|| Err(format!("series {} does not exist", row.series)))?;
+            if !may_read(policy) {
+                return Err(format!("not allowed to read series {}", row.series));
+            }
+            Ok(NewChartAnnotation {
+                user_id,
+                series_id: Some(series_id.to_string()),
+                chart_id: None,
+                annotation_date: row.annotation_date,
+                annotation_value: row.annotation_value,
+                title: row.title,
+                description: row.description,
+                color: row.color,
+                annotation_type: Some(row.annotation_type),
+                is_visible: Some(row.is_public),
+                is_pinned: Some(false),
+                tags: (!row.tags.is_empty()).then(|| row.tags.into_iter().map(Some).collect()),
+                organization_id,
+            })
+        });
+
+        let error = match annotation {
+            Ok(annotation) => {
+                new_annotations.push(annotation);
+                None
+            }
+            Err(error) => Some(error),
+        };
+        rows.push(AnnotationImportRow {
+            line,
+            annotation_id: None,
+            error,
+        });
+    }
+
+    if !new_annotations.is_empty() {
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // PostgreSQL returns the inserted rows in the order of VALUES
+        let ids: Vec<Uuid> = diesel::insert_into(chart_annotations::table)
+            .values(&new_annotations)
+            .returning(chart_annotations::id)
+            .get_results(&mut conn)
+            .await?;
+
+        let mut ids = ids.into_iter();
+        for row in rows.iter_mut().filter(|row| row.error.is_none()) {
+            row.annotation_id = ids.next();
+        }
+    }
+
+    Ok(AnnotationImportReport { rows })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_rows_are_checked_individually() {
+        // REQUIREMENT: Teams import annotation spreadsheets with a per-row report
+        // PURPOSE: Verify columns are found by name, optional fields are parsed, and each invalid row gets its own error and line
+        // This ensures one bad row does not block the rest and users can find what to fix
+
+        let csv = "\u{feff}Title,Date,Series,Value,Color,Tags,Public\n\
+                   Recession begins,2020-02-01,UNRATE,3.5,#FF0000,recession; covid,yes\n\
+                   \n\
+                   Bad date,02/01/2020,UNRATE,,,,\n\
+                   \"Multi\nline\",2021-01-01,GDP,abc,,,\n\
+                   No series,2021-01-01,,,,,\n\
+                   Odd color,2021-01-01,GDP,,red,,maybe\n";
+        let rows = parse_annotations_csv(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 5);
+
+        let (line, first) = &rows[0];
+        assert_eq!(*line, 2);
+        let first = first.as_ref().unwrap();
+        assert_eq!(first.series, "UNRATE");
+        assert_eq!(
+            first.annotation_date,
+            NaiveDate::from_ymd_opt(2020, 2, 1).unwrap()
+        );
+        assert_eq!(
+            first.annotation_value,
+            Some(BigDecimal::from_str("3.5").unwrap())
+        );
+        assert_eq!(first.color.as_deref(), Some("#ff0000"));
+        assert_eq!(first.annotation_type, DEFAULT_ANNOTATION_TYPE);
+        assert_eq!(first.tags, vec!["recession", "covid"]);
+        assert!(first.is_public);
+
+        let errors: Vec<(usize, String)> = rows[1..]
+            .iter()
+            .map(|(line, row)| (*line, row.clone().unwrap_err()))
+            .collect();
+        assert_eq!(errors[0].0, 4);
+        assert!(errors[0].1.contains("YYYY-MM-DD"));
+        assert_eq!(errors[1].0, 5);
+        assert!(errors[1].1.contains("not a number"));
+        assert_eq!(errors[2], (7, "series is required".to_string()));
+        assert_eq!(errors[3].0, 8);
+        assert!(errors[3].1.contains("hex color"));
+
+        // Files without the required columns are rejected as a whole
+        assert!(parse_annotations_csv(b"title,date\nA,2020-01-01\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_series_the_user_may_not_read() {
+        // REQUIREMENT: Users only annotate series whose data they may read
+        // PURPOSE: Verify rows referencing a series of a restricted source fail while the others are imported
+        // This ensures imports cannot attach annotations to licensed series outside the user's subscription
+
+        use econ_graph_core::models::{
+            DataSource, EconomicSeries, NewDataSource, NewEconomicSeries, UpdateDataSource, User,
+        };
+        use econ_graph_core::test_utils::TestContainer;
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+
+        let mut series = Vec::new();
+        for policy in [DataAccessPolicy::Public, DataAccessPolicy::Premium] {
+            let source = DataSource::create(
+                pool,
+                NewDataSource {
+                    name: format!("Import Source {}", Uuid::new_v4()),
+                    base_url: "https://import.example.com/api".to_string(),
+                    ..NewDataSource::default()
+                },
+            )
+            .await
+            .unwrap();
+            DataSource::update(
+                pool,
+                source.id,
+                UpdateDataSource {
+                    access_policy: Some(policy),
+                    api_documentation_url: None,
+                    ..UpdateDataSource::default()
+                },
+            )
+            .await
+            .unwrap();
+            series.push(
+                EconomicSeries::create(
+                    pool,
+                    &NewEconomicSeries {
+                        source_id: source.id,
+                        external_id: format!("IMPORT_{}", Uuid::new_v4()),
+                        title: "Import Series".to_string(),
+                        frequency: "Monthly".to_string(),
+                        is_active: true,
+                        ..NewEconomicSeries::default()
+                    },
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        let user = User::create_with_email(
+            pool,
+            format!("importer-{}@example.com", Uuid::new_v4()),
+            "password123".to_string(),
+            "Importer".to_string(),
+        )
+        .await
+        .unwrap();
+
+        let csv = format!(
+            "series,date,title\n{},2024-01-01,Open\n{},2024-01-01,Licensed\n",
+            series[0].external_id, series[1].id
+        );
+        let report = import_annotations(pool, user.id, None, csv.as_bytes(), |policy| {
+            policy == DataAccessPolicy::Public
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(report.created(), 1);
+        assert!(report.rows[0].annotation_id.is_some());
+        assert_eq!(
+            report.rows[1].error.as_deref(),
+            Some(format!("not allowed to read series {}", series[1].id).as_str())
+        );
+
+        let report = import_annotations(pool, user.id, None, csv.as_bytes(), |_| true)
+            .await
+            .unwrap();
+        assert_eq!(report.created(), 2);
+    }
+}
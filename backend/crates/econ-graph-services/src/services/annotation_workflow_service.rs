@@ -0,0 +1,324 @@
+//! Assignment, reply and resolution workflow for financial statement annotations
+//!
+//! Each operation stores the change and then notifies the users involved
+//! through [`NotificationService`]. A failed notification is logged but does
+//! not undo the change.
+//!
+//! An assignment asks a user to review (or analyze, verify, ...) a statement's
+//! annotations. The assignee accepts it and later completes it, or rejects it;
+//! the assigner may also reject it to withdraw it. Each step notifies the
+//! other party.
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::services::notification_service::NotificationService;
+use econ_graph_core::{
+    database::{DatabasePool, PooledConn},
+    enums::{AnnotationStatus, AssignmentStatus},
+    error::{AppError, AppResult},
+    models::{
+        AnnotationAssignment, AnnotationReply, AssignmentAction, NewAnnotationAssignment,
+        NewAnnotationReply, MAX_PENDING_ASSIGNMENTS,
+    },
+    schema::{
+        annotation_assignments, annotation_replies, financial_annotations, financial_statements,
+        users,
+    },
+};
+
+/// Financial annotation workflow with notifications
+pub struct AnnotationWorkflowService {
+    pool: DatabasePool,
+    notifications: NotificationService,
+}
+
+impl AnnotationWorkflowService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self {
+            notifications: NotificationService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    async fn connection(&self) -> AppResult<PooledConn<'_>> {
+        self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })
+    }
+
+    /// Assign an annotation task and notify the assignee
+    ///
+    /// The statement must exist, the assignee must be an active user, and a
+    /// due date must be in the future.
+    pub async fn assign(
+        &self,
+        new_assignment: &NewAnnotationAssignment,
+    ) -> AppResult<AnnotationAssignment> {
+        new_assignment.validate(Utc::now())?;
+
+        let mut conn = self.connection().await?;
+
+        let assignee_active = users::table
+            .find(new_assignment.assignee_id)
+            .select(users::is_active)
+            .first::<bool>(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound("Assignee not found".to_string()))?;
+        if !assignee_active {
+            return Err(AppError::ValidationError(
+                "Cannot assign an annotation review to an inactive user".to_string(),
+            ));
+        }
+
+        let statement_exists = diesel::select(diesel::dsl::exists(
+            financial_statements::table.find(new_assignment.statement_id),
+        ))
+        .get_result::<bool>(&mut conn)
+        .await?;
+        if !statement_exists {
+            return Err(AppError::NotFound(
+                "Financial statement not found".to_string(),
+            ));
+        }
+
+        let assignment = diesel::insert_into(annotation_assignments::table)
+            .values(new_assignment)
+            .get_result::<AnnotationAssignment>(&mut conn)
+            .await?;
+
+        if let Err(e) = self.notifications.notify_assignment(&assignment).await {
+            warn!("Failed to notify assignment {}: {}", assignment.id, e);
+        }
+
+        Ok(assignment)
+    }
+
+    /// Accept an assignment; only its assignee can
+    pub async fn accept(
+        &self,
+        assignment_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<AnnotationAssignment> {
+        self.transition(assignment_id, user_id, AssignmentAction::Accept, None)
+            .await
+    }
+
+    /// Complete an accepted assignment; only its assignee can
+    pub async fn complete(
+        &self,
+        assignment_id: Uuid,
+        user_id: Uuid,
+    ) -> AppResult<AnnotationAssignment> {
+        self.transition(assignment_id, user_id, AssignmentAction::Complete, None)
+            .await
+    }
+
+    /// Reject an open assignment as its assignee, or withdraw it as its assigner
+    ///
+    /// The reason, if given, is added to the assignment's notes.
+    pub async fn reject(
+        &self,
+        assignment_id: Uuid,
+        user_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<AnnotationAssignment> {
+        self.transition(assignment_id, user_id, AssignmentAction::Reject, reason)
+            .await
+    }
+
+    /// Open assignments of a user, earliest due first
+    ///
+    /// Assignments without a due date come after dated ones, oldest first.
+    pub async fn pending_reviews(
+        &self,
+        assignee_id: Uuid,
+        limit: i64,
+    ) -> AppResult<Vec<AnnotationAssignment>> {
+        let mut conn = self.connection().await?;
+
+        let assignments = annotation_assignments::table
+            .filter(annotation_assignments::assignee_id.eq(assignee_id))
+            .filter(annotation_assignments::status.eq_any(vec![
+                AssignmentStatus::Pending,
+                AssignmentStatus::InProgress,
+                AssignmentStatus::Overdue,
+            ]))
+            .order((
+                annotation_assignments::due_date.asc().nulls_last(),
+                annotation_assignments::created_at.asc(),
+            ))
+            .limit(limit.clamp(1, MAX_PENDING_ASSIGNMENTS))
+            .load::<AnnotationAssignment>(&mut conn)
+            .await?;
+
+        Ok(assignments)
+    }
+
+    /// Move an assignment to its next status and notify the other party
+    ///
+    /// The update only applies while the assignment still has the status it
+    /// was checked against, so two concurrent steps cannot both succeed.
+    async fn transition(
+        &self,
+        assignment_id: Uuid,
+        user_id: Uuid,
+        action: AssignmentAction,
+        reason: Option<&str>,
+    ) -> AppResult<AnnotationAssignment> {
+        let mut conn = self.connection().await?;
+
+        let assignment = annotation_assignments::table
+            .find(assignment_id)
+            .first::<AnnotationAssignment>(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound("Assignment not found".to_string()))?;
+        let status = assignment.transition(action, user_id)?;
+
+        let notes = match reason.map(str::trim).filter(|reason| !reason.is_empty()) {
+            Some(reason) => Some(match &assignment.notes {
+                Some(notes) => format!("{}\n\nRejected: {}", notes, reason),
+                None => format!("Rejected: {}", reason),
+            }),
+            None => assignment.notes.clone(),
+        };
+
+        let updated = diesel::update(
+            annotation_assignments::table
+                .filter(annotation_assignments::id.eq(assignment_id))
+                .filter(annotation_assignments::status.eq(assignment.status)),
+        )
+        .set((
+            annotation_assignments::status.eq(status),
+            annotation_assignments::notes.eq(notes),
+            annotation_assignments::updated_at.eq(diesel::dsl::now),
+        ))
+        .get_result::<AnnotationAssignment>(&mut conn)
+        .await
+        .optional()?
+        .ok_or_else(|| {
+            AppError::Conflict("The assignment was changed by someone else".to_string())
+        })?;
+
+        if let Err(e) = self
+            .notifications
+            .notify_assignment_updated(&updated, action, user_id)
+            .await
+        {
+            warn!(
+                "Failed to notify update of assignment {}: {}",
+                updated.id, e
+            );
+        }
+
+        Ok(updated)
+    }
+
+    /// Reply to an annotation and notify its author and mentioned users
+    pub async fn reply(&self, new_reply: &NewAnnotationReply) -> AppResult<AnnotationReply> {
+        let mut conn = self.connection().await?;
+
+        let annotation_author_id = financial_annotations::table
+            .find(new_reply.annotation_id)
+            .select(financial_annotations::author_id)
+            .first::<Uuid>(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound("Annotation not found".to_string()))?;
+
+        let mentions: Vec<Option<Uuid>> = new_reply.mentions.iter().copied().map(Some).collect();
+        let (id, annotation_id, author_id, content, stored_mentions, created_at, updated_at) =
+            diesel::insert_into(annotation_replies::table)
+                .values((
+                    annotation_replies::annotation_id.eq(new_reply.annotation_id),
+                    annotation_replies::author_id.eq(new_reply.author_id),
+                    annotation_replies::content.eq(&new_reply.content),
+                    annotation_replies::mentions.eq(mentions),
+                ))
+                .returning((
+                    annotation_replies::id,
+                    annotation_replies::annotation_id,
+                    annotation_replies::author_id,
+                    annotation_replies::content,
+                    annotation_replies::mentions,
+                    annotation_replies::created_at,
+                    annotation_replies::updated_at,
+                ))
+                .get_result::<(
+                    Uuid,
+                    Uuid,
+                    Uuid,
+                    String,
+                    Option<Vec<Option<Uuid>>>,
+                    DateTime<Utc>,
+                    DateTime<Utc>,
+                )>(&mut conn)
+                .await?;
+
+        let reply = AnnotationReply {
+            id,
+            annotation_id,
+            author_id,
+            content,
+            mentions: stored_mentions.into_iter().flatten().flatten().collect(),
+            created_at,
+            updated_at,
+        };
+
+        if let Err(e) = self
+            .notifications
+            .notify_reply(&reply, annotation_author_id)
+            .await
+        {
+            warn!("Failed to notify reply {}: {}", reply.id, e);
+        }
+
+        Ok(reply)
+    }
+
+    /// Mark an annotation resolved and notify its author and open assignees
+    ///
+    /// Returns `false` when the annotation does not exist or was already resolved.
+    pub async fn resolve(&self, annotation_id: Uuid, resolved_by: Uuid) -> AppResult<bool> {
+        let mut conn = self.connection().await?;
+
+        let resolved = diesel::update(
+            financial_annotations::table
+                .filter(financial_annotations::id.eq(annotation_id))
+                .filter(financial_annotations::status.ne(AnnotationStatus::Resolved)),
+        )
+        .set((
+            financial_annotations::status.eq(AnnotationStatus::Resolved),
+            financial_annotations::updated_at.eq(diesel::dsl::now),
+        ))
+        .returning((
+            financial_annotations::statement_id,
+            financial_annotations::author_id,
+        ))
+        .get_result::<(Uuid, Uuid)>(&mut conn)
+        .await
+        .optional()?;
+
+        let Some((statement_id, author_id)) = resolved else {
+            return Ok(false);
+        };
+
+        if let Err(e) = self
+            .notifications
+            .notify_annotation_resolved(annotation_id, statement_id, author_id, resolved_by)
+            .await
+        {
+            warn!(
+                "Failed to notify resolution of annotation {}: {}",
+                annotation_id, e
+            );
+        }
+
+        Ok(true)
+    }
+}
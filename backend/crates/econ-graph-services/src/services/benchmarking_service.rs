@@ -0,0 +1,661 @@
+/**
+ * REQUIREMENT: Compare a company's financial ratios against its peers
+ * PURPOSE: Group companies by SIC code and sector, compute ratio distributions per
+ * fiscal period, and persist them alongside the company ratios
+ * This fills the industry/sector benchmark columns that ratio calculation leaves empty
+ */
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{Date, Double, Int4, Nullable, Text};
+use diesel_async::RunQueryDsl;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{BenchmarkGroup, IndustryBenchmark, NewIndustryBenchmark},
+    schema::companies,
+};
+
+/// Smallest peer group for which a benchmark is published
+///
+/// Smaller groups would mostly reflect (and reveal) individual companies.
+pub const MIN_PEER_GROUP_SIZE: usize = 3;
+
+/// Largest magnitude the `financial_ratios` benchmark columns (NUMERIC(10,6)) can hold
+const MAX_STORED_RATIO: f64 = 10_000.0;
+
+/// Latest ratio value for one company and fiscal period
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct RatioObservation {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub company_id: Uuid,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub sic_code: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub sector: Option<String>,
+    #[diesel(sql_type = Text)]
+    pub ratio_name: String,
+    #[diesel(sql_type = Int4)]
+    pub fiscal_year: i32,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub fiscal_quarter: Option<i32>,
+    #[diesel(sql_type = Double)]
+    pub ratio_value: f64,
+}
+
+impl RatioObservation {
+    /// Peer group code for a grouping, if the company has one
+    fn group_code(&self, group: BenchmarkGroup) -> Option<&str> {
+        match group {
+            BenchmarkGroup::Sic => self.sic_code.as_deref(),
+            BenchmarkGroup::Sector => self.sector.as_deref(),
+        }
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+    }
+}
+
+/// Summary statistics of a ratio across a peer group
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatioDistribution {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+impl RatioDistribution {
+    /// Distribution of the finite values, or `None` when there are none
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        Some(Self {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            median: percentile(&sorted, 50.0),
+            p10: percentile(&sorted, 10.0),
+            p25: percentile(&sorted, 25.0),
+            p75: percentile(&sorted, 75.0),
+            p90: percentile(&sorted, 90.0),
+        })
+    }
+}
+
+/// Outcome of a benchmark refresh
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkRefreshSummary {
+    pub observations: usize,
+    pub benchmarks_stored: usize,
+    pub ratios_updated: usize,
+}
+
+/// A company's ratio in one period next to its peer benchmarks
+#[derive(Debug, Clone)]
+pub struct CompanyBenchmarkPeriod {
+    pub fiscal_year: i32,
+    pub fiscal_quarter: Option<i32>,
+    pub value: f64,
+    /// Share of SIC peers (0-100) with a lower value
+    pub industry_percentile_rank: Option<f64>,
+    pub industry: Option<IndustryBenchmark>,
+    pub sector: Option<IndustryBenchmark>,
+}
+
+/// Benchmark history of one ratio for one company
+#[derive(Debug, Clone)]
+pub struct CompanyBenchmark {
+    pub company_id: Uuid,
+    pub ratio_name: String,
+    pub sic_code: Option<String>,
+    pub sector: Option<String>,
+    pub periods: Vec<CompanyBenchmarkPeriod>,
+}
+
+/// A company's ratio in one fiscal period
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct RatioHistoryPoint {
+    #[diesel(sql_type = Int4)]
+    pub fiscal_year: i32,
+    #[diesel(sql_type = Nullable<Int4>)]
+    pub fiscal_quarter: Option<i32>,
+    #[diesel(sql_type = Date)]
+    pub period_end_date: NaiveDate,
+    #[diesel(sql_type = Double)]
+    pub value: f64,
+    /// Statement the value was calculated from
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub statement_id: Uuid,
+}
+
+/// A company's ratio in one period ranked within its sector
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatioPercentilePeriod {
+    pub fiscal_year: i32,
+    pub fiscal_quarter: Option<i32>,
+    pub value: f64,
+    /// Share of sector peers (0-100) with a lower value, `None` below [`MIN_PEER_GROUP_SIZE`] peers
+    pub percentile: Option<f64>,
+    /// Companies in the sector with a value for the period, including this one
+    pub peer_count: usize,
+}
+
+/// Sector percentile of one ratio for one company over time
+#[derive(Debug, Clone)]
+pub struct RatioPeerPercentile {
+    pub company_id: Uuid,
+    pub ratio_name: String,
+    pub sector: Option<String>,
+    pub periods: Vec<RatioPercentilePeriod>,
+}
+
+type PeriodKey = (i32, Option<i32>);
+
+/// Computes and serves peer-group benchmarks for financial ratios
+pub struct BenchmarkingService;
+
+impl BenchmarkingService {
+    /// Recompute every benchmark and copy the results onto the company ratios
+    ///
+    /// SIC benchmarks fill `industry_average` and `peer_median`; sector
+    /// benchmarks fill `sector_average`.
+    pub async fn refresh_benchmarks(pool: &DatabasePool) -> AppResult<BenchmarkRefreshSummary> {
+        let observations = load_ratio_observations(pool, None).await?;
+        let benchmarks = compute_benchmarks(&observations, MIN_PEER_GROUP_SIZE, Utc::now());
+        let benchmarks_stored = IndustryBenchmark::upsert_many(pool, &benchmarks).await?;
+
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let industry_updated = diesel::sql_query(
+            "UPDATE financial_ratios fr
+             SET industry_average = CASE WHEN abs(ib.mean_value) < $1 THEN ib.mean_value END,
+                 peer_median = CASE WHEN abs(ib.median_value) < $1 THEN ib.median_value END,
+                 updated_at = NOW()
+             FROM financial_statements fs
+             JOIN companies c ON fs.company_id = c.id
+             JOIN industry_benchmarks ib
+               ON ib.group_type = 'sic'
+              AND ib.group_code = c.sic_code
+              AND ib.fiscal_year = fs.fiscal_year
+              AND ib.fiscal_quarter IS NOT DISTINCT FROM fs.fiscal_quarter
+             WHERE fr.statement_id = fs.id AND ib.ratio_name = fr.ratio_name",
+        )
+        .bind::<Double, _>(MAX_STORED_RATIO)
+        .execute(&mut conn)
+        .await?;
+
+        let sector_updated = diesel::sql_query(
+            "UPDATE financial_ratios fr
+             SET sector_average = CASE WHEN abs(ib.mean_value) < $1 THEN ib.mean_value END,
+                 updated_at = NOW()
+             FROM financial_statements fs
+             JOIN companies c ON fs.company_id = c.id
+             JOIN industry_benchmarks ib
+               ON ib.group_type = 'sector'
+              AND ib.group_code = c.sector
+              AND ib.fiscal_year = fs.fiscal_year
+              AND ib.fiscal_quarter IS NOT DISTINCT FROM fs.fiscal_quarter
+             WHERE fr.statement_id = fs.id AND ib.ratio_name = fr.ratio_name",
+        )
+        .bind::<Double, _>(MAX_STORED_RATIO)
+        .execute(&mut conn)
+        .await?;
+
+        Ok(BenchmarkRefreshSummary {
+            observations: observations.len(),
+            benchmarks_stored,
+            ratios_updated: industry_updated.max(sector_updated),
+        })
+    }
+
+    /// A company's ratio history with its SIC and sector benchmarks
+    pub async fn company_benchmark(
+        pool: &DatabasePool,
+        company_id: Uuid,
+        ratio_name: &str,
+    ) -> AppResult<CompanyBenchmark> {
+        let (sic_code, sector) = {
+            let mut conn = pool.get().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+            })?;
+            companies::table
+                .find(company_id)
+                .select((companies::sic_code, companies::sector))
+                .get_result::<(Option<String>, Option<String>)>(&mut conn)
+                .await
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Company {} not found", company_id)))?
+        };
+
+        let observations = load_ratio_observations(pool, Some(ratio_name)).await?;
+        let company_rows: Vec<&RatioObservation> = observations
+            .iter()
+            .filter(|row| row.company_id == company_id)
+            .collect();
+
+        let industry = match &sic_code {
+            Some(code) => benchmarks_by_period(pool, BenchmarkGroup::Sic, code, ratio_name).await?,
+            None => HashMap::new(),
+        };
+        let sector_benchmarks = match &sector {
+            Some(name) => {
+                benchmarks_by_period(pool, BenchmarkGroup::Sector, name, ratio_name).await?
+            }
+            None => HashMap::new(),
+        };
+
+        let periods = company_rows
+            .iter()
+            .map(|row| {
+                let key = (row.fiscal_year, row.fiscal_quarter);
+                let peers: Vec<f64> = observations
+                    .iter()
+                    .filter(|peer| {
+                        (peer.fiscal_year, peer.fiscal_quarter) == key
+                            && peer.group_code(BenchmarkGroup::Sic).is_some()
+                            && peer.group_code(BenchmarkGroup::Sic)
+                                == row.group_code(BenchmarkGroup::Sic)
+                    })
+                    .map(|peer| peer.ratio_value)
+                    .collect();
+
+                CompanyBenchmarkPeriod {
+                    fiscal_year: row.fiscal_year,
+                    fiscal_quarter: row.fiscal_quarter,
+                    value: row.ratio_value,
+                    industry_percentile_rank: (peers.len() >= MIN_PEER_GROUP_SIZE)
+                        .then(|| percentile_rank(&peers, row.ratio_value)),
+                    industry: industry.get(&key).cloned(),
+                    sector: sector_benchmarks.get(&key).cloned(),
+                }
+            })
+            .collect();
+
+        Ok(CompanyBenchmark {
+            company_id,
+            ratio_name: ratio_name.to_string(),
+            sic_code,
+            sector,
+            periods,
+        })
+    }
+
+    /// A company's ratio across fiscal periods, oldest first
+    ///
+    /// As with benchmarks, an amended filing replaces the original for its period.
+    pub async fn ratio_history(
+        pool: &DatabasePool,
+        company_id: Uuid,
+        ratio_name: &str,
+    ) -> AppResult<Vec<RatioHistoryPoint>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+        companies::table
+            .find(company_id)
+            .select(companies::id)
+            .get_result::<Uuid>(&mut conn)
+            .await
+            .optional()?
+            .ok_or_else(|| AppError::NotFound(format!("Company {} not found", company_id)))?;
+
+        let history = diesel::sql_query(
+            "SELECT * FROM (
+                 SELECT DISTINCT ON (fs.fiscal_year, fs.fiscal_quarter)
+                        fs.fiscal_year, fs.fiscal_quarter, fs.period_end_date,
+                        fr.ratio_value::float8 AS value, fs.id AS statement_id
+                 FROM financial_ratios fr
+                 JOIN financial_statements fs ON fr.statement_id = fs.id
+                 WHERE fs.company_id = $1
+                   AND fr.ratio_name = $2
+                   AND fr.ratio_value IS NOT NULL
+                 ORDER BY fs.fiscal_year, fs.fiscal_quarter, fs.filing_date DESC
+             ) latest
+             ORDER BY period_end_date, fiscal_quarter NULLS LAST",
+        )
+        .bind::<diesel::sql_types::Uuid, _>(company_id)
+        .bind::<Text, _>(ratio_name)
+        .load::<RatioHistoryPoint>(&mut conn)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Where a company's ratio ranks within its sector in each fiscal period
+    pub async fn ratio_peer_percentile(
+        pool: &DatabasePool,
+        company_id: Uuid,
+        ratio_name: &str,
+    ) -> AppResult<RatioPeerPercentile> {
+        let sector = {
+            let mut conn = pool.get().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+            })?;
+            companies::table
+                .find(company_id)
+                .select(companies::sector)
+                .get_result::<Option<String>>(&mut conn)
+                .await
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("Company {} not found", company_id)))?
+        };
+
+        let observations = load_ratio_observations(pool, Some(ratio_name)).await?;
+
+        Ok(RatioPeerPercentile {
+            company_id,
+            ratio_name: ratio_name.to_string(),
+            sector,
+            periods: sector_percentiles(&observations, company_id),
+        })
+    }
+}
+
+/// Percentile rank of a company's values within its sector, per period, oldest first
+///
+/// Periods in which the company has no sector, or fewer than
+/// [`MIN_PEER_GROUP_SIZE`] sector companies reported the ratio, have no percentile.
+pub fn sector_percentiles(
+    observations: &[RatioObservation],
+    company_id: Uuid,
+) -> Vec<RatioPercentilePeriod> {
+    let mut periods: Vec<RatioPercentilePeriod> = observations
+        .iter()
+        .filter(|row| row.company_id == company_id)
+        .map(|row| {
+            let sector = row.group_code(BenchmarkGroup::Sector);
+            let peers: Vec<f64> = observations
+                .iter()
+                .filter(|peer| {
+                    sector.is_some()
+                        && (peer.fiscal_year, peer.fiscal_quarter)
+                            == (row.fiscal_year, row.fiscal_quarter)
+                        && peer.group_code(BenchmarkGroup::Sector) == sector
+                })
+                .map(|peer| peer.ratio_value)
+                .collect();
+
+            RatioPercentilePeriod {
+                fiscal_year: row.fiscal_year,
+                fiscal_quarter: row.fiscal_quarter,
+                value: row.ratio_value,
+                percentile: (peers.len() >= MIN_PEER_GROUP_SIZE)
+                    .then(|| percentile_rank(&peers, row.ratio_value)),
+                peer_count: peers.len(),
+            }
+        })
+        .collect();
+    // Quarters of a fiscal year come before the full year
+    periods.sort_by_key(|period| {
+        (
+            period.fiscal_year,
+            period.fiscal_quarter.is_none(),
+            period.fiscal_quarter,
+        )
+    });
+
+    periods
+}
+
+/// Load the latest value of each ratio per company and fiscal period
+///
+/// Amended filings replace the original: only the most recently filed
+/// statement for a period is used.
+async fn load_ratio_observations(
+    pool: &DatabasePool,
+    ratio_name: Option<&str>,
+) -> AppResult<Vec<RatioObservation>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let observations = diesel::sql_query(
+        "SELECT DISTINCT ON (fs.company_id, fr.ratio_name, fs.fiscal_year, fs.fiscal_quarter)
+                fs.company_id, c.sic_code, c.sector, fr.ratio_name,
+                fs.fiscal_year, fs.fiscal_quarter, fr.ratio_value::float8 AS ratio_value
+         FROM financial_ratios fr
+         JOIN financial_statements fs ON fr.statement_id = fs.id
+         JOIN companies c ON fs.company_id = c.id
+         WHERE c.is_active = TRUE
+           AND fr.ratio_value IS NOT NULL
+           AND ($1::text IS NULL OR fr.ratio_name = $1)
+         ORDER BY fs.company_id, fr.ratio_name, fs.fiscal_year, fs.fiscal_quarter,
+                  fs.filing_date DESC",
+    )
+    .bind::<Nullable<Text>, _>(ratio_name)
+    .load::<RatioObservation>(&mut conn)
+    .await?;
+
+    Ok(observations)
+}
+
+async fn benchmarks_by_period(
+    pool: &DatabasePool,
+    group: BenchmarkGroup,
+    group_code: &str,
+    ratio_name: &str,
+) -> AppResult<HashMap<PeriodKey, IndustryBenchmark>> {
+    Ok(
+        IndustryBenchmark::find_for_group(pool, group, group_code.trim(), ratio_name)
+            .await?
+            .into_iter()
+            .map(|benchmark| ((benchmark.fiscal_year, benchmark.fiscal_quarter), benchmark))
+            .collect(),
+    )
+}
+
+/// Group observations by SIC code and sector and summarize each group per period
+///
+/// Groups with fewer than `min_peers` companies are skipped.
+pub fn compute_benchmarks(
+    observations: &[RatioObservation],
+    min_peers: usize,
+    calculated_at: DateTime<Utc>,
+) -> Vec<NewIndustryBenchmark> {
+    let mut groups: BTreeMap<(BenchmarkGroup, &str, &str, PeriodKey), Vec<f64>> = BTreeMap::new();
+    for row in observations {
+        for group in [BenchmarkGroup::Sic, BenchmarkGroup::Sector] {
+            if let Some(code) = row.group_code(group) {
+                groups
+                    .entry((
+                        group,
+                        code,
+                        row.ratio_name.as_str(),
+                        (row.fiscal_year, row.fiscal_quarter),
+                    ))
+                    .or_default()
+                    .push(row.ratio_value);
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|((group, code, ratio_name, (year, quarter)), values)| {
+            let distribution = RatioDistribution::from_values(&values)?;
+            if distribution.count < min_peers.max(1) {
+                return None;
+            }
+
+            Some(NewIndustryBenchmark {
+                group_type: group.as_str().to_string(),
+                group_code: code.to_string(),
+                ratio_name: ratio_name.to_string(),
+                fiscal_year: year,
+                fiscal_quarter: quarter,
+                company_count: distribution.count as i32,
+                mean_value: decimal_from_f64(distribution.mean),
+                median_value: decimal_from_f64(distribution.median),
+                p10_value: decimal_from_f64(distribution.p10),
+                p25_value: decimal_from_f64(distribution.p25),
+                p75_value: decimal_from_f64(distribution.p75),
+                p90_value: decimal_from_f64(distribution.p90),
+                calculated_at,
+            })
+        })
+        .collect()
+}
+
+/// Percentile of sorted values using linear interpolation between closest ranks
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Percentile rank (0-100) of a value among peers, counting ties as half
+pub fn percentile_rank(peers: &[f64], value: f64) -> f64 {
+    if peers.is_empty() {
+        return 0.0;
+    }
+
+    let below = peers.iter().filter(|peer| **peer < value).count() as f64;
+    let equal = peers.iter().filter(|peer| **peer == value).count() as f64;
+    (below + 0.5 * equal) / peers.len() as f64 * 100.0
+}
+
+/// Convert a ratio statistic to the stored NUMERIC(20,6) precision
+fn decimal_from_f64(value: f64) -> BigDecimal {
+    BigDecimal::from_str(&format!("{:.6}", value)).unwrap_or_default()
+}
+
+/// Convert a stored benchmark value back to a float
+pub fn decimal_to_f64(value: &BigDecimal) -> f64 {
+    value.to_string().parse().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(company: u128, sic: &str, sector: &str, value: f64) -> RatioObservation {
+        RatioObservation {
+            company_id: Uuid::from_u128(company),
+            sic_code: Some(sic.to_string()),
+            sector: Some(sector.to_string()),
+            ratio_name: "return_on_equity".to_string(),
+            fiscal_year: 2024,
+            fiscal_quarter: None,
+            ratio_value: value,
+        }
+    }
+
+    #[test]
+    fn test_ratio_distribution_percentiles() {
+        // REQUIREMENT: Median and percentile ratios per peer group
+        // PURPOSE: Verify percentiles interpolate between ranks and ignore non-finite values
+        // This ensures a division-by-zero ratio cannot distort the industry benchmark
+
+        let distribution =
+            RatioDistribution::from_values(&[0.4, 0.1, f64::NAN, 0.3, 0.2, 0.5]).unwrap();
+
+        assert_eq!(distribution.count, 5);
+        assert!((distribution.mean - 0.3).abs() < 1e-12);
+        assert!((distribution.median - 0.3).abs() < 1e-12);
+        assert!((distribution.p25 - 0.2).abs() < 1e-12);
+        assert!((distribution.p10 - 0.14).abs() < 1e-12);
+        assert!((distribution.p90 - 0.46).abs() < 1e-12);
+        assert!(RatioDistribution::from_values(&[f64::INFINITY]).is_none());
+    }
+
+    #[test]
+    fn test_compute_benchmarks_groups_by_sic_and_sector() {
+        // REQUIREMENT: Benchmarks grouped by SIC code and by sector
+        // PURPOSE: Verify each peer group gets its own benchmark and small groups are skipped
+        // This ensures a lone company in its SIC code is not published as an industry average
+
+        let observations = vec![
+            observation(1, "3571", "Technology", 0.10),
+            observation(2, "3571", "Technology", 0.20),
+            observation(3, "3571", "Technology", 0.30),
+            observation(4, "7372", "Technology", 0.40),
+        ];
+
+        let benchmarks = compute_benchmarks(&observations, MIN_PEER_GROUP_SIZE, Utc::now());
+
+        assert_eq!(benchmarks.len(), 2);
+        let sic = benchmarks
+            .iter()
+            .find(|b| b.group_type == "sic")
+            .expect("SIC 3571 has three peers");
+        assert_eq!(sic.group_code, "3571");
+        assert_eq!(sic.company_count, 3);
+        assert_eq!(decimal_to_f64(&sic.median_value), 0.2);
+
+        let sector = benchmarks
+            .iter()
+            .find(|b| b.group_type == "sector")
+            .expect("Technology has four peers");
+        assert_eq!(sector.company_count, 4);
+        assert_eq!(decimal_to_f64(&sector.mean_value), 0.25);
+    }
+
+    #[test]
+    fn test_percentile_rank() {
+        // REQUIREMENT: Show where a company sits within its peer group
+        // PURPOSE: Verify percentile rank counts lower peers and splits ties
+        // This ensures the best and worst performers land at the ends of the range
+
+        let peers = [0.1, 0.2, 0.3, 0.4];
+        assert_eq!(percentile_rank(&peers, 0.05), 0.0);
+        assert_eq!(percentile_rank(&peers, 0.3), 62.5);
+        assert_eq!(percentile_rank(&peers, 0.5), 100.0);
+    }
+
+    #[test]
+    fn test_sector_percentiles_per_period() {
+        // REQUIREMENT: Chart a company's percentile within its sector across fiscal periods
+        // PURPOSE: Verify each period ranks the company against that period's sector peers only
+        // This ensures the percentile series moves with the company's standing, not with peers from other periods or sectors
+
+        let period = |row: RatioObservation, year: i32, quarter: Option<i32>| RatioObservation {
+            fiscal_year: year,
+            fiscal_quarter: quarter,
+            ..row
+        };
+        let observations = vec![
+            period(observation(1, "3571", "Technology", 0.30), 2024, None),
+            period(observation(2, "3571", "Technology", 0.10), 2024, None),
+            period(observation(3, "7372", "Technology", 0.20), 2024, None),
+            period(observation(4, "6021", "Financials", 0.90), 2024, None),
+            period(observation(1, "3571", "Technology", 0.05), 2024, Some(2)),
+            period(observation(2, "3571", "Technology", 0.10), 2024, Some(2)),
+            period(observation(3, "7372", "Technology", 0.20), 2024, Some(2)),
+            period(observation(1, "3571", "Technology", 0.40), 2023, None),
+        ];
+
+        let periods = sector_percentiles(&observations, Uuid::from_u128(1));
+
+        let keys: Vec<(i32, Option<i32>)> = periods
+            .iter()
+            .map(|p| (p.fiscal_year, p.fiscal_quarter))
+            .collect();
+        assert_eq!(keys, vec![(2023, None), (2024, Some(2)), (2024, None)]);
+
+        // A lone reporter has no percentile
+        assert_eq!(periods[0].peer_count, 1);
+        assert_eq!(periods[0].percentile, None);
+
+        // Bottom of the sector in Q2, top for the full year; Financials is not a peer
+        assert!((periods[1].percentile.unwrap() - 100.0 / 6.0).abs() < 1e-9);
+        assert_eq!(periods[2].peer_count, 3);
+        assert!((periods[2].percentile.unwrap() - 250.0 / 3.0).abs() < 1e-9);
+    }
+}
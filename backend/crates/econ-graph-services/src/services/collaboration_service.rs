@@ -11,14 +11,20 @@ use diesel_async::RunQueryDsl;
 use std::fmt;
 use uuid::Uuid;
 
+use crate::services::email_service::queue_chart_invite;
+use crate::services::notification_service::NotificationService;
 use econ_graph_core::{
     database::DatabasePool,
     error::{AppError, AppResult},
+    models::organization::{highest_chart_permission, OrganizationChartShare, OrganizationMember},
     models::user::{
         AnnotationComment, ChartAnnotation, ChartCollaborator, NewAnnotationComment,
         NewChartAnnotation, NewChartCollaborator, User,
     },
-    schema::{annotation_comments, chart_annotations, chart_collaborators, users},
+    schema::{
+        annotation_comments, chart_annotations, chart_collaborators, organization_members,
+        saved_charts, users,
+    },
 };
 
 /// Permission levels for collaboration
@@ -94,6 +100,7 @@ impl CollaborationService {
         annotation_type: String,
         color: Option<String>,
         is_public: bool,
+        organization_id: Option<Uuid>,
     ) -> AppResult<ChartAnnotation> {
         let mut conn = self.pool.get().await.map_err(|e| {
             econ_graph_core::error::AppError::DatabaseError(format!(
@@ -107,6 +114,16 @@ impl CollaborationService {
             return Err(AppError::Unauthorized("Unauthorized".to_string()));
         }
 
+        // Organization-scoped annotations require a membership that allows sharing
+        if let Some(org_id) = organization_id {
+            let member = OrganizationMember::find(&self.pool, org_id, user_id).await?;
+            if !member.is_some_and(|m| m.organization_role().can_share()) {
+                return Err(AppError::Forbidden(
+                    "Not allowed to share annotations with this organization".to_string(),
+                ));
+            }
+        }
+
         let new_annotation = NewChartAnnotation {
             user_id,
             series_id: Some(series_id.to_string()),
@@ -120,6 +137,7 @@ impl CollaborationService {
             is_visible: Some(is_public),
             is_pinned: Some(false),
             tags: None,
+            organization_id,
         };
 
         let annotation = diesel::insert_into(chart_annotations::table)
@@ -146,12 +164,18 @@ impl CollaborationService {
         })?;
 
         let annotations = if let Some(uid) = user_id {
+            // Annotations scoped to any organization the user belongs to are visible too
+            let member_organizations = organization_members::table
+                .filter(organization_members::user_id.eq(uid))
+                .select(organization_members::organization_id.nullable());
+
             chart_annotations::table
                 .filter(chart_annotations::series_id.eq(series_id))
                 .filter(
                     chart_annotations::is_visible
                         .eq(true)
-                        .or(chart_annotations::user_id.eq(uid)),
+                        .or(chart_annotations::user_id.eq(uid))
+                        .or(chart_annotations::organization_id.eq_any(member_organizations)),
                 )
                 .order_by(chart_annotations::created_at.desc())
                 .select(ChartAnnotation::as_select())
@@ -220,9 +244,62 @@ impl CollaborationService {
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
+        if let Err(e) = NotificationService::new(self.pool.clone())
+            .notify_comment(&annotation, &comment)
+            .await
+        {
+            tracing::warn!("Failed to send comment notifications: {}", e);
+        }
+
         Ok(comment)
     }
 
+    /// Resolve a comment (only by the comment author or annotation owner)
+    pub async fn resolve_comment(
+        &self,
+        user_id: Uuid,
+        comment_id: Uuid,
+    ) -> AppResult<AnnotationComment> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            econ_graph_core::error::AppError::DatabaseError(format!(
+                "Failed to get database connection: {}",
+                e
+            ))
+        })?;
+
+        let (comment, annotation) = annotation_comments::table
+            .inner_join(chart_annotations::table)
+            .filter(annotation_comments::id.eq(comment_id))
+            .select((AnnotationComment::as_select(), ChartAnnotation::as_select()))
+            .first::<(AnnotationComment, ChartAnnotation)>(&mut conn)
+            .await
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+
+        if comment.user_id != user_id && annotation.user_id != user_id {
+            return Err(AppError::Unauthorized("Unauthorized".to_string()));
+        }
+
+        let resolved = diesel::update(
+            annotation_comments::table.filter(annotation_comments::id.eq(comment_id)),
+        )
+        .set(annotation_comments::is_resolved.eq(true))
+        .returning(AnnotationComment::as_select())
+        .get_result::<AnnotationComment>(&mut conn)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let Err(e) = NotificationService::new(self.pool.clone())
+            .notify_comment_resolved(&annotation, &resolved, user_id)
+            .await
+        {
+            tracing::warn!("Failed to send comment resolution notifications: {}", e);
+        }
+
+        Ok(resolved)
+    }
+
     /// Get comments for an annotation
     pub async fn get_comments_for_annotation(
         &self,
@@ -305,6 +382,16 @@ impl CollaborationService {
             .get_result::<ChartCollaborator>(&mut conn)
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        drop(conn);
+
+        queue_chart_invite(
+            &self.pool,
+            chart_id,
+            owner_user_id,
+            target_user_id,
+            &permission_level.to_string(),
+        )
+        .await;
 
         Ok(collaborator)
     }
@@ -350,8 +437,13 @@ impl CollaborationService {
         Ok(true)
     }
 
-    /// Check if user has admin permission on a chart
-    async fn check_admin_permission(&self, user_id: Uuid, chart_id: Uuid) -> AppResult<bool> {
+    /// Permission a user holds on a chart: admin for its owner, otherwise the
+    /// highest of their collaborator role and their organizations' shares
+    pub async fn chart_permission(
+        &self,
+        user_id: Uuid,
+        chart_id: Uuid,
+    ) -> AppResult<Option<PermissionLevel>> {
         let mut conn = self.pool.get().await.map_err(|e| {
             econ_graph_core::error::AppError::DatabaseError(format!(
                 "Failed to get database connection: {}",
@@ -359,23 +451,46 @@ impl CollaborationService {
             ))
         })?;
 
-        let permission = chart_collaborators::table
+        let owned = saved_charts::table
+            .filter(saved_charts::id.eq(chart_id))
+            .filter(saved_charts::user_id.eq(user_id))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if owned > 0 {
+            return Ok(Some(PermissionLevel::Admin));
+        }
+
+        let collaborator_role = chart_collaborators::table
             .filter(chart_collaborators::chart_id.eq(chart_id))
             .filter(chart_collaborators::user_id.eq(user_id))
             .select(chart_collaborators::role)
             .first::<Option<String>>(&mut conn)
             .await
             .optional()
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .flatten();
+        drop(conn);
 
-        if let Some(Some(perm_str)) = permission {
-            let perm = PermissionLevel::from_string(&perm_str);
-            Ok(perm.can_admin())
-        } else {
-            // If no explicit permission, check if user is the owner
-            // For now, return true to allow sharing (this could be enhanced)
-            Ok(true)
-        }
+        let organization_share =
+            OrganizationChartShare::permission_for_user(&self.pool, user_id, chart_id).await?;
+
+        Ok(highest_chart_permission(
+            collaborator_role
+                .as_deref()
+                .into_iter()
+                .chain(organization_share),
+        )
+        .map(PermissionLevel::from_string))
+    }
+
+    /// Check if user has admin permission on a chart, directly or through an organization share
+    pub async fn check_admin_permission(&self, user_id: Uuid, chart_id: Uuid) -> AppResult<bool> {
+        Ok(self
+            .chart_permission(user_id, chart_id)
+            .await?
+            .is_some_and(|permission| permission.can_admin()))
     }
 
     /// Delete an annotation (only by owner or admin)
@@ -432,6 +547,10 @@ impl CollaborationService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use econ_graph_core::models::{
+        NewOrganization, NewOrganizationChartShare, NewOrganizationMember, NewSavedChart,
+        Organization, OrganizationRole, SavedChart,
+    };
     use econ_graph_core::test_utils::TestContainer;
     use serial_test::serial;
 
@@ -447,6 +566,192 @@ mod tests {
         assert!(true);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_only_chart_admins_can_share() {
+        // REQUIREMENT: Only a chart's owner or admins may share it with users or organizations
+        // PURPOSE: Verify chart admin permission comes from ownership or an admin collaborator role
+        // This ensures another user cannot grant themselves or their organization access to a chart
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let service = CollaborationService::new(pool.clone());
+
+        let owner = User::create_with_email(
+            pool,
+            format!("owner-{}@example.com", Uuid::new_v4()),
+            "password123".to_string(),
+            "Chart Owner".to_string(),
+        )
+        .await
+        .unwrap();
+        let other = User::create_with_email(
+            pool,
+            format!("other-{}@example.com", Uuid::new_v4()),
+            "password123".to_string(),
+            "Other User".to_string(),
+        )
+        .await
+        .unwrap();
+        let chart = SavedChart::create(
+            pool,
+            &NewSavedChart {
+                user_id: owner.id,
+                title: "GDP vs Unemployment".to_string(),
+                description: None,
+                chart_config: serde_json::json!({}),
+                series_ids: Vec::new(),
+                transformations: serde_json::json!({}),
+                start_date: None,
+                end_date: None,
+                is_public: false,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(service
+            .check_admin_permission(owner.id, chart.id)
+            .await
+            .unwrap());
+        assert!(!service
+            .check_admin_permission(other.id, chart.id)
+            .await
+            .unwrap());
+        assert!(matches!(
+            service
+                .share_chart(chart.id, other.id, other.id, PermissionLevel::Admin)
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        // A view collaborator still cannot share; an admin collaborator can
+        service
+            .share_chart(chart.id, owner.id, other.id, PermissionLevel::View)
+            .await
+            .unwrap();
+        assert!(!service
+            .check_admin_permission(other.id, chart.id)
+            .await
+            .unwrap());
+        service
+            .share_chart(chart.id, owner.id, other.id, PermissionLevel::Admin)
+            .await
+            .unwrap();
+        assert!(service
+            .check_admin_permission(other.id, chart.id)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_organization_shares_grant_chart_permission() {
+        // REQUIREMENT: Charts shared with an organization are accessible to its members
+        // PURPOSE: Verify organization shares count in chart permission checks, capped by the member's role
+        // This ensures org sharing grants access without per-user invites, and viewers cannot administer shared charts
+
+        let container = TestContainer::new().await;
+        let pool = container.pool();
+        let service = CollaborationService::new(pool.clone());
+
+        let mut users = Vec::new();
+        for name in ["Owner", "Org Admin", "Org Viewer"] {
+            users.push(
+                User::create_with_email(
+                    pool,
+                    format!("{}-{}@example.com", name.replace(' ', "-"), Uuid::new_v4()),
+                    "password123".to_string(),
+                    name.to_string(),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        let (owner, org_admin, org_viewer) = (&users[0], &users[1], &users[2]);
+
+        let chart = SavedChart::create(
+            pool,
+            &NewSavedChart {
+                user_id: owner.id,
+                title: "Shared Inflation Chart".to_string(),
+                description: None,
+                chart_config: serde_json::json!({}),
+                series_ids: Vec::new(),
+                transformations: serde_json::json!({}),
+                start_date: None,
+                end_date: None,
+                is_public: false,
+            },
+        )
+        .await
+        .unwrap();
+        let organization = Organization::create(
+            pool,
+            &NewOrganization {
+                name: "Research Team".to_string(),
+                slug: format!("research-{}", Uuid::new_v4()),
+                description: None,
+                created_by: Some(owner.id),
+            },
+            owner.id,
+        )
+        .await
+        .unwrap();
+        for (user, role) in [
+            (org_admin, OrganizationRole::Admin),
+            (org_viewer, OrganizationRole::Viewer),
+        ] {
+            OrganizationMember::upsert(
+                pool,
+                &NewOrganizationMember {
+                    organization_id: organization.id,
+                    user_id: user.id,
+                    role: role.to_string(),
+                    invited_by: Some(owner.id),
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            service
+                .chart_permission(org_admin.id, chart.id)
+                .await
+                .unwrap(),
+            None
+        );
+
+        OrganizationChartShare::upsert(
+            pool,
+            &NewOrganizationChartShare {
+                organization_id: organization.id,
+                chart_id: chart.id,
+                shared_by: Some(owner.id),
+                permission_level: "admin".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(service
+            .check_admin_permission(org_admin.id, chart.id)
+            .await
+            .unwrap());
+        assert_eq!(
+            service
+                .chart_permission(org_viewer.id, chart.id)
+                .await
+                .unwrap(),
+            Some(PermissionLevel::View)
+        );
+        assert!(!service
+            .check_admin_permission(org_viewer.id, chart.id)
+            .await
+            .unwrap());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_permission_levels() {
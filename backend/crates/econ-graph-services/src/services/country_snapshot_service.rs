@@ -0,0 +1,391 @@
+/**
+ * REQUIREMENT: Global analysis maps color countries by the value of an indicator
+ * PURPOSE: Snapshot one indicator across every active country on a date, with the
+ * min, max and quantile breaks a choropleth needs for its color scale
+ * Values and scale come from a single aggregation and are cached briefly, since
+ * every pan of a map asks for the same snapshot
+ */
+use chrono::{Duration, NaiveDate};
+use diesel::sql_types::{Array, Date, Double, Nullable, Text, Varchar};
+use diesel::QueryableByName;
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use uuid::Uuid;
+
+use crate::services::benchmarking_service::percentile;
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+};
+
+/// Quantile levels of the color scale breaks (quintiles)
+pub const SNAPSHOT_QUANTILE_LEVELS: [f64; 4] = [0.2, 0.4, 0.6, 0.8];
+
+/// Oldest observation, relative to the snapshot date, a country is shown with
+pub const MAX_OBSERVATION_AGE_DAYS: i64 = 2 * 365;
+
+/// How long computed snapshots are served from the cache
+const SNAPSHOT_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Latest value of the indicator for one country, with the scale of all countries
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct CountrySnapshotRow {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub country_id: Uuid,
+    #[diesel(sql_type = Varchar)]
+    pub iso_code: String,
+    #[diesel(sql_type = Varchar)]
+    pub iso_code_2: String,
+    #[diesel(sql_type = Varchar)]
+    pub country_name: String,
+    #[diesel(sql_type = Varchar)]
+    pub region: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub unit: Option<String>,
+    #[diesel(sql_type = Date)]
+    pub date: NaiveDate,
+    #[diesel(sql_type = Double)]
+    pub value: f64,
+    #[diesel(sql_type = Varchar)]
+    pub data_source: String,
+    #[diesel(sql_type = Double)]
+    pub min_value: f64,
+    #[diesel(sql_type = Double)]
+    pub max_value: f64,
+    #[diesel(sql_type = Array<Double>)]
+    pub quantile_values: Vec<f64>,
+}
+
+/// Value of an indicator for one country
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountryIndicatorValue {
+    pub country_id: Uuid,
+    pub iso_code: String,
+    pub iso_code_2: String,
+    pub country_name: String,
+    pub region: String,
+    pub unit: Option<String>,
+    /// Date of the observation, on or before the snapshot date
+    pub date: NaiveDate,
+    pub value: f64,
+    /// Name of the data source the observation came from
+    pub data_source: String,
+}
+
+/// Value below which a share of the countries fall
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantileBreak {
+    /// Share of countries, between 0 and 1
+    pub level: f64,
+    pub value: f64,
+}
+
+/// One indicator across all countries on a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountryIndicatorSnapshot {
+    pub indicator_code: String,
+    pub date: NaiveDate,
+    /// Countries with a recent enough value, by ISO code
+    pub countries: Vec<CountryIndicatorValue>,
+    /// `None` when no country has a value
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    /// Breaks at [`SNAPSHOT_QUANTILE_LEVELS`]; empty when no country has a value
+    pub quantiles: Vec<QuantileBreak>,
+}
+
+/// Split the rows of the aggregation into country values and the shared scale
+pub fn build_snapshot(
+    indicator_code: String,
+    date: NaiveDate,
+    rows: Vec<CountrySnapshotRow>,
+) -> CountryIndicatorSnapshot {
+    let (min_value, max_value, quantiles) = match rows.first() {
+        Some(row) => (
+            Some(row.min_value),
+            Some(row.max_value),
+            SNAPSHOT_QUANTILE_LEVELS
+                .iter()
+                .zip(&row.quantile_values)
+                .map(|(level, value)| QuantileBreak {
+                    level: *level,
+                    value: *value,
+                })
+                .collect(),
+        ),
+        None => (None, None, Vec::new()),
+    };
+
+    let countries = rows
+        .into_iter()
+        .map(|row| CountryIndicatorValue {
+            country_id: row.country_id,
+            iso_code: row.iso_code,
+            iso_code_2: row.iso_code_2,
+            country_name: row.country_name,
+            region: row.region,
+            unit: row.unit,
+            date: row.date,
+            value: row.value,
+            data_source: row.data_source,
+        })
+        .collect();
+
+    CountryIndicatorSnapshot {
+        indicator_code,
+        date,
+        countries,
+        min_value,
+        max_value,
+        quantiles,
+    }
+}
+
+impl CountryIndicatorSnapshot {
+    /// The snapshot with only the countries `keep` accepts
+    ///
+    /// The min, max and quantile breaks are recomputed from the countries
+    /// kept, so the color scale does not reveal the values left out.
+    pub fn retain_countries(&self, keep: impl Fn(&CountryIndicatorValue) -> bool) -> Self {
+        let countries: Vec<_> = self
+            .countries
+            .iter()
+            .filter(|country| keep(country))
+            .cloned()
+            .collect();
+        if countries.len() == self.countries.len() {
+            return self.clone();
+        }
+
+        let mut values: Vec<f64> = countries.iter().map(|country| country.value).collect();
+        values.sort_by(f64::total_cmp);
+        let quantiles = if values.is_empty() {
+            Vec::new()
+        } else {
+            SNAPSHOT_QUANTILE_LEVELS
+                .iter()
+                .map(|level| QuantileBreak {
+                    level: *level,
+                    value: percentile(&values, level * 100.0),
+                })
+                .collect()
+        };
+
+        Self {
+            indicator_code: self.indicator_code.clone(),
+            date: self.date,
+            min_value: values.first().copied(),
+            max_value: values.last().copied(),
+            countries,
+            quantiles,
+        }
+    }
+}
+
+type CacheKey = (String, NaiveDate);
+
+/// Short-lived cache of computed snapshots
+#[derive(Default)]
+pub struct CountrySnapshotCache {
+    entries: Mutex<HashMap<CacheKey, (Instant, Arc<CountryIndicatorSnapshot>)>>,
+}
+
+impl CountrySnapshotCache {
+    fn get(&self, key: &CacheKey) -> Option<Arc<CountryIndicatorSnapshot>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(cached_at, _)| cached_at.elapsed() < SNAPSHOT_CACHE_TTL)
+            .map(|(_, snapshot)| snapshot.clone())
+    }
+
+    fn insert(
+        &self,
+        key: CacheKey,
+        snapshot: CountryIndicatorSnapshot,
+    ) -> Arc<CountryIndicatorSnapshot> {
+        let snapshot = Arc::new(snapshot);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (cached_at, _)| cached_at.elapsed() < SNAPSHOT_CACHE_TTL);
+        entries.insert(key, (Instant::now(), snapshot.clone()));
+        snapshot
+    }
+}
+
+static COUNTRY_SNAPSHOT_CACHE: OnceLock<CountrySnapshotCache> = OnceLock::new();
+
+fn shared_cache() -> &'static CountrySnapshotCache {
+    COUNTRY_SNAPSHOT_CACHE.get_or_init(CountrySnapshotCache::default)
+}
+
+/// Latest value of `indicator_code` on or before `date` for every active country
+///
+/// Countries whose latest value is older than [`MAX_OBSERVATION_AGE_DAYS`] are
+/// left out. Served from a cache for up to five minutes after being computed.
+pub async fn get_country_indicator_snapshot(
+    pool: &DatabasePool,
+    indicator_code: &str,
+    date: NaiveDate,
+) -> AppResult<Arc<CountryIndicatorSnapshot>> {
+    let indicator_code = indicator_code.trim();
+    if indicator_code.is_empty() {
+        return Err(AppError::ValidationError(
+            "Indicator code must not be empty".to_string(),
+        ));
+    }
+
+    let key = (indicator_code.to_string(), date);
+    if let Some(snapshot) = shared_cache().get(&key) {
+        return Ok(snapshot);
+    }
+
+    let rows = load_snapshot_rows(pool, indicator_code, date).await?;
+
+    Ok(shared_cache().insert(key, build_snapshot(indicator_code.to_string(), date, rows)))
+}
+
+async fn load_snapshot_rows(
+    pool: &DatabasePool,
+    indicator_code: &str,
+    date: NaiveDate,
+) -> AppResult<Vec<CountrySnapshotRow>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let rows = diesel::sql_query(
+        "WITH latest AS (
+             SELECT DISTINCT ON (c.id)
+                    c.id AS country_id, c.iso_code, c.iso_code_2,
+                    c.name AS country_name, c.region, gei.unit,
+                    gid.date, gid.value::float8 AS value, gid.data_source
+             FROM global_indicator_data gid
+             JOIN global_economic_indicators gei ON gid.indicator_id = gei.id
+             JOIN countries c ON gei.country_id = c.id
+             WHERE gei.indicator_code = $1
+               AND gid.date <= $2
+               AND gid.date > $3
+               AND gid.value IS NOT NULL
+               AND c.is_active
+             ORDER BY c.id, gid.date DESC, gid.is_preliminary, gid.created_at DESC
+         )
+         SELECT latest.*, scale.min_value, scale.max_value, scale.quantile_values
+         FROM latest
+         CROSS JOIN (
+             SELECT MIN(value) AS min_value, MAX(value) AS max_value,
+                    percentile_cont($4) WITHIN GROUP (ORDER BY value) AS quantile_values
+             FROM latest
+         ) scale
+         ORDER BY latest.iso_code",
+    )
+    .bind::<Text, _>(indicator_code)
+    .bind::<Date, _>(date)
+    .bind::<Date, _>(date - Duration::days(MAX_OBSERVATION_AGE_DAYS))
+    .bind::<Array<Double>, _>(SNAPSHOT_QUANTILE_LEVELS.to_vec())
+    .load::<CountrySnapshotRow>(&mut conn)
+    .await?;
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(iso_code: &str, value: f64) -> CountrySnapshotRow {
+        CountrySnapshotRow {
+            country_id: Uuid::new_v4(),
+            iso_code: iso_code.to_string(),
+            iso_code_2: iso_code[..2].to_string(),
+            country_name: iso_code.to_string(),
+            region: "Europe".to_string(),
+            unit: Some("Percent".to_string()),
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            value,
+            data_source: "World Bank Open Data".to_string(),
+            min_value: 1.0,
+            max_value: 5.0,
+            quantile_values: vec![1.8, 2.6, 3.4, 4.2],
+        }
+    }
+
+    #[test]
+    fn test_build_snapshot_shares_scale() {
+        // REQUIREMENT: Choropleth maps need one color scale for all countries
+        // PURPOSE: Verify the min, max and quantile breaks of the aggregation are paired with their levels
+        // This ensures every country is colored against the same scale
+
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let snapshot = build_snapshot(
+            "GDP_GROWTH".to_string(),
+            date,
+            vec![row("DEU", 1.0), row("FRA", 5.0)],
+        );
+
+        assert_eq!(snapshot.countries.len(), 2);
+        assert_eq!(snapshot.countries[1].iso_code, "FRA");
+        assert_eq!(snapshot.min_value, Some(1.0));
+        assert_eq!(snapshot.max_value, Some(5.0));
+        assert_eq!(
+            snapshot.quantiles,
+            vec![
+                QuantileBreak {
+                    level: 0.2,
+                    value: 1.8
+                },
+                QuantileBreak {
+                    level: 0.4,
+                    value: 2.6
+                },
+                QuantileBreak {
+                    level: 0.6,
+                    value: 3.4
+                },
+                QuantileBreak {
+                    level: 0.8,
+                    value: 4.2
+                },
+            ]
+        );
+
+        let empty = build_snapshot("GDP_GROWTH".to_string(), date, Vec::new());
+        assert!(empty.countries.is_empty());
+        assert_eq!(empty.min_value, None);
+        assert!(empty.quantiles.is_empty());
+    }
+
+    #[test]
+    fn test_retain_countries_rescales() {
+        // REQUIREMENT: Readers only see countries whose data source they may read
+        // PURPOSE: Verify dropping countries recomputes the min, max and quantile breaks from those kept
+        // This ensures the color scale of a filtered map does not leak the values left out
+
+        let date = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let mut premium = row("JPN", 9.0);
+        premium.data_source = "Licensed Vendor".to_string();
+        let snapshot = build_snapshot(
+            "GDP_GROWTH".to_string(),
+            date,
+            vec![row("DEU", 1.0), row("FRA", 2.0), row("ITA", 3.0), premium],
+        );
+
+        assert_eq!(snapshot.retain_countries(|_| true), snapshot);
+
+        let public = snapshot.retain_countries(|country| country.data_source != "Licensed Vendor");
+        assert_eq!(public.countries.len(), 3);
+        assert_eq!(public.min_value, Some(1.0));
+        assert_eq!(public.max_value, Some(3.0));
+        let breaks: Vec<f64> = public.quantiles.iter().map(|q| q.value).collect();
+        assert_eq!(breaks.len(), SNAPSHOT_QUANTILE_LEVELS.len());
+        for (actual, expected) in breaks.iter().zip([1.4, 1.8, 2.2, 2.6]) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+
+        let none = snapshot.retain_countries(|_| false);
+        assert!(none.countries.is_empty());
+        assert_eq!(none.min_value, None);
+        assert!(none.quantiles.is_empty());
+    }
+}
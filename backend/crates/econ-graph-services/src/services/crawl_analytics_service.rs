@@ -0,0 +1,387 @@
+/**
+ * REQUIREMENT: Operators can see how each data source's crawls are doing
+ * PURPOSE: Aggregate crawl_attempts per data source over a time window (success rate,
+ * response times, data freshness, error breakdown) for the ops dashboard
+ * Results are cached briefly so a dashboard polling every few seconds costs one query
+ */
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use diesel::sql_types::{BigInt, Nullable, Text, Timestamptz};
+use diesel::QueryableByName;
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+};
+
+/// Longest window that can be requested, to keep the aggregation bounded
+pub const MAX_ANALYTICS_WINDOW_HOURS: i64 = 24 * 90;
+
+/// How long computed analytics are served from the cache
+const ANALYTICS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Aggregated crawl attempts of one data source
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct SourceCrawlTotals {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub source_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub source_name: String,
+    #[diesel(sql_type = BigInt)]
+    pub total_attempts: i64,
+    #[diesel(sql_type = BigInt)]
+    pub successful_attempts: i64,
+    #[diesel(sql_type = BigInt)]
+    pub data_found_attempts: i64,
+    #[diesel(sql_type = BigInt)]
+    pub new_data_points: i64,
+    #[diesel(sql_type = Nullable<diesel::sql_types::Double>)]
+    pub avg_response_time_ms: Option<f64>,
+    #[diesel(sql_type = Nullable<diesel::sql_types::Double>)]
+    pub p95_response_time_ms: Option<f64>,
+    #[diesel(sql_type = Nullable<diesel::sql_types::Double>)]
+    pub avg_freshness_hours: Option<f64>,
+    #[diesel(sql_type = Nullable<Timestamptz>)]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    #[diesel(sql_type = Nullable<Timestamptz>)]
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+/// Failed attempts of one data source with the same error type
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct CrawlErrorCount {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub source_id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub error_type: String,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = Timestamptz)]
+    pub last_seen_at: DateTime<Utc>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub last_error_message: Option<String>,
+}
+
+/// Crawl analytics of one data source over a window
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceCrawlAnalytics {
+    pub totals: SourceCrawlTotals,
+    /// Most frequent errors first
+    pub errors: Vec<CrawlErrorCount>,
+}
+
+impl SourceCrawlAnalytics {
+    /// Share of attempts that succeeded, between 0 and 1
+    pub fn success_rate(&self) -> f64 {
+        ratio(self.totals.successful_attempts, self.totals.total_attempts)
+    }
+
+    /// Share of attempts that found new data, between 0 and 1
+    pub fn data_found_rate(&self) -> f64 {
+        ratio(self.totals.data_found_attempts, self.totals.total_attempts)
+    }
+
+    /// Hours between the last successful crawl and `now`
+    pub fn hours_since_last_success(&self, now: DateTime<Utc>) -> Option<f64> {
+        self.totals
+            .last_success_at
+            .map(|at| (now - at).num_seconds().max(0) as f64 / 3600.0)
+    }
+}
+
+fn ratio(part: i64, total: i64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Crawl analytics for every data source with attempts in a window
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrawlAnalyticsReport {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub sources: Vec<SourceCrawlAnalytics>,
+}
+
+/// Window ending now, aligned to the minute so that concurrent requests share
+/// a cache entry
+pub fn analytics_window(window_hours: i64) -> AppResult<(DateTime<Utc>, DateTime<Utc>)> {
+    if !(1..=MAX_ANALYTICS_WINDOW_HOURS).contains(&window_hours) {
+        return Err(AppError::ValidationError(format!(
+            "Window must be between 1 and {} hours",
+            MAX_ANALYTICS_WINDOW_HOURS
+        )));
+    }
+
+    let until = Utc::now()
+        .duration_trunc(Duration::minutes(1))
+        .map_err(|e| AppError::InternalError(format!("Failed to align window: {}", e)))?
+        + Duration::minutes(1);
+    Ok((until - Duration::hours(window_hours), until))
+}
+
+type CacheKey = (DateTime<Utc>, DateTime<Utc>, Option<Uuid>);
+
+/// Short-lived cache of computed reports
+#[derive(Default)]
+pub struct CrawlAnalyticsCache {
+    entries: Mutex<HashMap<CacheKey, (Instant, Arc<CrawlAnalyticsReport>)>>,
+}
+
+impl CrawlAnalyticsCache {
+    fn get(&self, key: &CacheKey) -> Option<Arc<CrawlAnalyticsReport>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|(cached_at, _)| cached_at.elapsed() < ANALYTICS_CACHE_TTL)
+            .map(|(_, report)| report.clone())
+    }
+
+    fn insert(&self, key: CacheKey, report: CrawlAnalyticsReport) -> Arc<CrawlAnalyticsReport> {
+        let report = Arc::new(report);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (cached_at, _)| cached_at.elapsed() < ANALYTICS_CACHE_TTL);
+        entries.insert(key, (Instant::now(), report.clone()));
+        report
+    }
+}
+
+static CRAWL_ANALYTICS_CACHE: OnceLock<CrawlAnalyticsCache> = OnceLock::new();
+
+fn shared_cache() -> &'static CrawlAnalyticsCache {
+    CRAWL_ANALYTICS_CACHE.get_or_init(CrawlAnalyticsCache::default)
+}
+
+/// Crawl analytics per data source for attempts made in `[since, until)`
+///
+/// Served from a cache for up to a minute after being computed.
+pub async fn get_crawl_analytics(
+    pool: &DatabasePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    source_id: Option<Uuid>,
+) -> AppResult<Arc<CrawlAnalyticsReport>> {
+    let key = (since, until, source_id);
+    if let Some(report) = shared_cache().get(&key) {
+        return Ok(report);
+    }
+
+    let totals = load_source_totals(pool, since, until, source_id).await?;
+    let errors = load_error_counts(pool, since, until, source_id).await?;
+
+    Ok(shared_cache().insert(key, build_report(since, until, totals, errors)))
+}
+
+/// Attach each source's error counts to its totals
+pub fn build_report(
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    totals: Vec<SourceCrawlTotals>,
+    errors: Vec<CrawlErrorCount>,
+) -> CrawlAnalyticsReport {
+    let mut errors_by_source: HashMap<Uuid, Vec<CrawlErrorCount>> = HashMap::new();
+    for error in errors {
+        errors_by_source
+            .entry(error.source_id)
+            .or_default()
+            .push(error);
+    }
+
+    let sources = totals
+        .into_iter()
+        .map(|totals| {
+            let mut errors = errors_by_source
+                .remove(&totals.source_id)
+                .unwrap_or_default();
+            errors.sort_by(|a, b| {
+                b.count
+                    .cmp(&a.count)
+                    .then_with(|| a.error_type.cmp(&b.error_type))
+            });
+            SourceCrawlAnalytics { totals, errors }
+        })
+        .collect();
+
+    CrawlAnalyticsReport {
+        since,
+        until,
+        sources,
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+async fn load_source_totals(
+    pool: &DatabasePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    source_id: Option<Uuid>,
+) -> AppResult<Vec<SourceCrawlTotals>> {
+    let mut conn = pool.get().await.map_err(connection_error)?;
+
+    let totals = diesel::sql_query(
+        "SELECT ds.id AS source_id, ds.name AS source_name,
+                COUNT(*) AS total_attempts,
+                COUNT(*) FILTER (WHERE ca.success) AS successful_attempts,
+                COUNT(*) FILTER (WHERE ca.data_found) AS data_found_attempts,
+                COALESCE(SUM(ca.new_data_points), 0)::int8 AS new_data_points,
+                AVG(ca.response_time_ms)::float8 AS avg_response_time_ms,
+                percentile_cont(0.95) WITHIN GROUP (ORDER BY ca.response_time_ms)
+                    AS p95_response_time_ms,
+                (AVG(ca.data_freshness_hours) FILTER (WHERE ca.data_found))::float8
+                    AS avg_freshness_hours,
+                MAX(ca.attempted_at) AS last_attempt_at,
+                MAX(ca.attempted_at) FILTER (WHERE ca.success) AS last_success_at
+         FROM crawl_attempts ca
+         JOIN economic_series es ON ca.series_id = es.id
+         JOIN data_sources ds ON es.source_id = ds.id
+         WHERE ca.attempted_at >= $1
+           AND ca.attempted_at < $2
+           AND ($3::uuid IS NULL OR ds.id = $3)
+         GROUP BY ds.id, ds.name
+         ORDER BY ds.name",
+    )
+    .bind::<Timestamptz, _>(since)
+    .bind::<Timestamptz, _>(until)
+    .bind::<Nullable<diesel::sql_types::Uuid>, _>(source_id)
+    .load::<SourceCrawlTotals>(&mut conn)
+    .await?;
+
+    Ok(totals)
+}
+
+async fn load_error_counts(
+    pool: &DatabasePool,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    source_id: Option<Uuid>,
+) -> AppResult<Vec<CrawlErrorCount>> {
+    let mut conn = pool.get().await.map_err(connection_error)?;
+
+    let errors = diesel::sql_query(
+        "SELECT ds.id AS source_id,
+                COALESCE(ca.error_type, 'unknown') AS error_type,
+                COUNT(*) AS count,
+                MAX(ca.attempted_at) AS last_seen_at,
+                (ARRAY_AGG(ca.error_message ORDER BY ca.attempted_at DESC))[1]
+                    AS last_error_message
+         FROM crawl_attempts ca
+         JOIN economic_series es ON ca.series_id = es.id
+         JOIN data_sources ds ON es.source_id = ds.id
+         WHERE ca.attempted_at >= $1
+           AND ca.attempted_at < $2
+           AND ($3::uuid IS NULL OR ds.id = $3)
+           AND NOT ca.success
+         GROUP BY ds.id, COALESCE(ca.error_type, 'unknown')",
+    )
+    .bind::<Timestamptz, _>(since)
+    .bind::<Timestamptz, _>(until)
+    .bind::<Nullable<diesel::sql_types::Uuid>, _>(source_id)
+    .load::<CrawlErrorCount>(&mut conn)
+    .await?;
+
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totals(source_id: Uuid, total: i64, successful: i64) -> SourceCrawlTotals {
+        SourceCrawlTotals {
+            source_id,
+            source_name: "FRED".to_string(),
+            total_attempts: total,
+            successful_attempts: successful,
+            data_found_attempts: successful / 2,
+            new_data_points: 0,
+            avg_response_time_ms: None,
+            p95_response_time_ms: None,
+            avg_freshness_hours: None,
+            last_attempt_at: None,
+            last_success_at: None,
+        }
+    }
+
+    fn error(source_id: Uuid, error_type: &str, count: i64) -> CrawlErrorCount {
+        CrawlErrorCount {
+            source_id,
+            error_type: error_type.to_string(),
+            count,
+            last_seen_at: Utc::now(),
+            last_error_message: None,
+        }
+    }
+
+    #[test]
+    fn test_build_report_groups_errors_by_source() {
+        // REQUIREMENT: Per-source crawl analytics for the ops dashboard
+        // PURPOSE: Verify error counts are attached to their source, most frequent first
+
+        let fred = Uuid::new_v4();
+        let bls = Uuid::new_v4();
+        let now = Utc::now();
+
+        let report = build_report(
+            now - Duration::hours(24),
+            now,
+            vec![totals(fred, 10, 8), totals(bls, 4, 4)],
+            vec![
+                error(fred, "timeout", 1),
+                error(fred, "rate_limit", 3),
+                error(Uuid::new_v4(), "timeout", 7),
+            ],
+        );
+
+        assert_eq!(report.sources.len(), 2);
+        let fred_errors: Vec<(&str, i64)> = report.sources[0]
+            .errors
+            .iter()
+            .map(|e| (e.error_type.as_str(), e.count))
+            .collect();
+        assert_eq!(fred_errors, vec![("rate_limit", 3), ("timeout", 1)]);
+        assert!(report.sources[1].errors.is_empty());
+
+        assert!((report.sources[0].success_rate() - 0.8).abs() < f64::EPSILON);
+        assert!((report.sources[0].data_found_rate() - 0.4).abs() < f64::EPSILON);
+        assert_eq!(
+            SourceCrawlAnalytics {
+                totals: totals(bls, 0, 0),
+                errors: Vec::new()
+            }
+            .success_rate(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_analytics_window_is_minute_aligned_and_bounded() {
+        let (since, until) = analytics_window(24).unwrap();
+
+        assert_eq!(until - since, Duration::hours(24));
+        assert_eq!(until.timestamp() % 60, 0);
+        assert!(until > Utc::now());
+        assert!(analytics_window(0).is_err());
+        assert!(analytics_window(MAX_ANALYTICS_WINDOW_HOURS + 1).is_err());
+    }
+
+    #[test]
+    fn test_cache_serves_fresh_entries() {
+        let cache = CrawlAnalyticsCache::default();
+        let now = Utc::now();
+        let key = (now - Duration::hours(1), now, None);
+
+        assert!(cache.get(&key).is_none());
+        cache.insert(key, build_report(key.0, key.1, Vec::new(), Vec::new()));
+        assert!(cache.get(&key).is_some());
+        assert!(cache.get(&(key.0, key.1, Some(Uuid::new_v4()))).is_none());
+    }
+}
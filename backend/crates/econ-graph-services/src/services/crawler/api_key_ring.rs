@@ -0,0 +1,175 @@
+//! Rotation between several API keys of one data source
+//!
+//! Some APIs, FRED among them, throttle each API key separately. A data
+//! source may therefore be configured with several comma separated keys,
+//! either in its stored credential or in the environment variable named by
+//! `api_key_name`. The crawler keeps using one key until a response is
+//! HTTP 429, rests that key and moves on to the next one; see
+//! [`QuotaClient::send_with_api_key`](super::QuotaClient::send_with_api_key).
+//!
+//! Metrics and `data_source_key_usage` identify a key by [`api_key_hash`],
+//! never by the key itself.
+
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use econ_graph_core::secrets::SecretString;
+
+/// How long a throttled key rests when the response has no Retry-After header
+///
+/// FRED limits requests per key per minute.
+pub const DEFAULT_THROTTLE_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Hex digits of the SHA-256 hash that identify a key
+const KEY_HASH_LENGTH: usize = 12;
+
+/// Short hash identifying an API key in metrics and usage records
+pub fn api_key_hash(key: &SecretString) -> String {
+    let mut hash = format!("{:x}", Sha256::digest(key.expose().as_bytes()));
+    hash.truncate(KEY_HASH_LENGTH);
+    hash
+}
+
+/// Keys of an API key setting, which may list several separated by commas
+pub fn parse_api_keys(setting: &str) -> Vec<SecretString> {
+    setting
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(SecretString::new)
+        .collect()
+}
+
+/// An API key together with its hash
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub secret: SecretString,
+    pub hash: String,
+}
+
+/// API keys of a data source, used one at a time
+#[derive(Debug)]
+pub struct ApiKeyRing {
+    keys: Vec<ApiKey>,
+    state: Mutex<RingState>,
+}
+
+#[derive(Debug)]
+struct RingState {
+    /// Index of the key in use
+    current: usize,
+    /// Until when each key rests after being throttled
+    throttled_until: Vec<Option<Instant>>,
+}
+
+impl ApiKeyRing {
+    /// Ring of `keys` in order; a key listed twice is used once
+    pub fn new(keys: Vec<SecretString>) -> Self {
+        let mut ring_keys: Vec<ApiKey> = Vec::with_capacity(keys.len());
+        for secret in keys {
+            let hash = api_key_hash(&secret);
+            if ring_keys.iter().all(|key| key.hash != hash) {
+                ring_keys.push(ApiKey { secret, hash });
+            }
+        }
+
+        Self {
+            state: Mutex::new(RingState {
+                current: 0,
+                throttled_until: vec![None; ring_keys.len()],
+            }),
+            keys: ring_keys,
+        }
+    }
+
+    /// Ring of the keys in a setting such as `FRED_API_KEY=key1,key2`
+    pub fn from_setting(setting: Option<&str>) -> Self {
+        Self::new(setting.map(parse_api_keys).unwrap_or_default())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Key to send the next request with
+    ///
+    /// The key in use unless it is resting, otherwise the next key that is
+    /// not, which then stays in use. `None` when every key is resting.
+    pub fn acquire(&self, now: Instant) -> Option<ApiKey> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        for offset in 0..self.keys.len() {
+            let index = (state.current + offset) % self.keys.len();
+            match state.throttled_until[index] {
+                Some(until) if until > now => continue,
+                _ => {
+                    state.throttled_until[index] = None;
+                    state.current = index;
+                    return Some(self.keys[index].clone());
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Rest a key until `until` after it was throttled
+    pub fn mark_throttled(&self, hash: &str, until: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(index) = self.keys.iter().position(|key| key.hash == hash) {
+            state.throttled_until[index] = Some(until);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_ring_rotates_past_throttled_keys() {
+        // REQUIREMENT: Rotate to another API key when FRED throttles the one in use
+        // PURPOSE: Verify a key stays in use until throttled, throttled keys rest until their cooldown ends, and no key is returned while all rest
+        // This ensures crawls keep going on the remaining keys without hammering a throttled one
+
+        let ring = ApiKeyRing::from_setting(Some(" key-a, key-b ,,key-a"));
+        assert_eq!(ring.len(), 2);
+
+        let now = Instant::now();
+        let first = ring.acquire(now).unwrap();
+        assert_eq!(first.secret.expose(), "key-a");
+        assert_eq!(ring.acquire(now).unwrap().hash, first.hash);
+
+        ring.mark_throttled(&first.hash, now + DEFAULT_THROTTLE_COOLDOWN);
+        let second = ring.acquire(now).unwrap();
+        assert_eq!(second.secret.expose(), "key-b");
+
+        ring.mark_throttled(&second.hash, now + Duration::from_secs(5));
+        assert!(ring.acquire(now).is_none());
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(ring.acquire(later).unwrap().secret.expose(), "key-b");
+        assert!(ApiKeyRing::from_setting(None).acquire(now).is_none());
+    }
+
+    #[test]
+    fn test_api_key_hash_hides_key() {
+        // REQUIREMENT: Metrics label which API key served a request without exposing it
+        // PURPOSE: Verify the hash is short, stable and does not contain the key
+        // This ensures dashboards can tell keys apart while the keys stay secret
+
+        let key = SecretString::new("abcdef0123456789abcdef0123456789");
+        let hash = api_key_hash(&key);
+
+        assert_eq!(hash.len(), KEY_HASH_LENGTH);
+        assert_eq!(hash, api_key_hash(&key.clone()));
+        assert_ne!(hash, api_key_hash(&SecretString::new("another-key")));
+        assert!(!key.expose().contains(&hash));
+    }
+}
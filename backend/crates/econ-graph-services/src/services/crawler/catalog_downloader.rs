@@ -64,6 +64,13 @@ impl CatalogDownloader {
                 let series_ids = self.discovery_service.discover_ecb_series(pool).await?;
                 series_ids.len()
             }
+            "EUROSTAT" => {
+                let series_ids = self
+                    .discovery_service
+                    .discover_eurostat_series(pool)
+                    .await?;
+                series_ids.len()
+            }
             "OECD" => {
                 let series_ids = self.discovery_service.discover_oecd_series(pool).await?;
                 series_ids.len()
@@ -102,7 +109,7 @@ impl CatalogDownloader {
             }
             _ => {
                 return Err(econ_graph_core::error::AppError::ValidationError(format!(
-                    "Unknown data source: {}. Available sources: FRED, BLS, Census, BEA, World Bank, IMF, FHFA, ECB, OECD, BoE, WTO, BoJ, RBA, BoC, SNB, UN Stats, ILO",
+                    "Unknown data source: {}. Available sources: FRED, BLS, Census, BEA, World Bank, IMF, FHFA, ECB, Eurostat, OECD, BoE, WTO, BoJ, RBA, BoC, SNB, UN Stats, ILO",
                     source_name
                 )));
             }
@@ -128,6 +135,7 @@ impl CatalogDownloader {
             ("IMF", "International Monetary Fund - Global economic data"),
             ("FHFA", "Federal Housing Finance Agency - US housing data"),
             ("ECB", "European Central Bank - Euro area economic data"),
+            ("Eurostat", "Eurostat - European Union statistics"),
             (
                 "OECD",
                 "Organisation for Economic Co-operation and Development",
@@ -3,10 +3,11 @@
 // This replaces the hardcoded series list with dynamic discovery
 
 use crate::services::crawler::legacy_crawler_service::CrawlerService;
+use crate::services::queue_service::enqueue_item;
 use crate::services::series_discovery::SeriesDiscoveryService;
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::AppResult;
-use econ_graph_core::models::{CrawlQueueItem, NewCrawlQueueItem, QueuePriority};
+use econ_graph_core::models::{NewCrawlQueueItem, QueuePriority};
 
 /// Comprehensive crawler that can discover and catalog all available series
 pub struct ComprehensiveCrawler {
@@ -163,7 +164,7 @@ impl ComprehensiveCrawler {
                 max_retries: 3,
             };
 
-            CrawlQueueItem::create(pool, &queue_item).await?;
+            enqueue_item(pool, &queue_item).await?;
             queued_count += 1;
         }
 
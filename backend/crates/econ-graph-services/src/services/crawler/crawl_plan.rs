@@ -0,0 +1,460 @@
+//! Crawl plans for dry runs
+//!
+//! Resolves what a crawl would fetch (series, endpoints, and requests and bytes
+//! estimated from recent crawl attempts) using reads only, so operators can
+//! check a configuration change before anything is downloaded or written.
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{Duration, Utc};
+use diesel::dsl::{avg, count};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::HashMap;
+use std::fmt::Write;
+use uuid::Uuid;
+
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{DataSource, SeriesMetadata};
+use econ_graph_core::schema::{crawl_attempts, economic_series};
+use econ_graph_core::secrets::redact_credentials;
+
+/// How far back crawl attempts are used for estimates
+const ESTIMATE_WINDOW_DAYS: i64 = 30;
+
+/// Average cost of crawling a series, from successful recent attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrawlCost {
+    pub attempts: i64,
+    pub average_bytes: Option<f64>,
+    pub average_response_ms: Option<f64>,
+}
+
+impl CrawlCost {
+    /// Attempt-weighted average of several costs
+    fn combined<'a>(costs: impl Iterator<Item = &'a CrawlCost>) -> Option<CrawlCost> {
+        let mut attempts = 0;
+        let (mut bytes, mut bytes_attempts) = (0.0, 0);
+        let (mut response_ms, mut response_attempts) = (0.0, 0);
+        for cost in costs {
+            attempts += cost.attempts;
+            if let Some(average) = cost.average_bytes {
+                bytes += average * cost.attempts as f64;
+                bytes_attempts += cost.attempts;
+            }
+            if let Some(average) = cost.average_response_ms {
+                response_ms += average * cost.attempts as f64;
+                response_attempts += cost.attempts;
+            }
+        }
+
+        (attempts > 0).then(|| CrawlCost {
+            attempts,
+            average_bytes: (bytes_attempts > 0).then_some(bytes / bytes_attempts as f64),
+            average_response_ms: (response_attempts > 0)
+                .then_some(response_ms / response_attempts as f64),
+        })
+    }
+}
+
+/// What an estimate is based on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EstimateBasis {
+    /// Recent attempts for this series
+    SeriesHistory(CrawlCost),
+    /// Recent attempts for other series of the same source
+    SourceAverage(CrawlCost),
+    /// Nothing crawled recently to estimate from
+    NoHistory,
+}
+
+impl EstimateBasis {
+    fn cost(&self) -> Option<&CrawlCost> {
+        match self {
+            EstimateBasis::SeriesHistory(cost) | EstimateBasis::SourceAverage(cost) => Some(cost),
+            EstimateBasis::NoHistory => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            EstimateBasis::SeriesHistory(cost) => format!("{} recent crawls", cost.attempts),
+            EstimateBasis::SourceAverage(cost) => {
+                format!("source average of {} crawls", cost.attempts)
+            }
+            EstimateBasis::NoHistory => "no crawl history".to_string(),
+        }
+    }
+}
+
+/// One series a crawl would fetch
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedCrawl {
+    pub source: String,
+    pub external_id: String,
+    pub title: String,
+    /// URL the crawl would request, with credentials redacted
+    pub endpoint: Option<String>,
+    pub requests: u32,
+    pub basis: EstimateBasis,
+}
+
+impl PlannedCrawl {
+    pub fn estimated_bytes(&self) -> Option<f64> {
+        self.basis
+            .cost()
+            .and_then(|cost| cost.average_bytes)
+            .map(|bytes| bytes * self.requests as f64)
+    }
+
+    pub fn estimated_seconds(&self) -> Option<f64> {
+        self.basis
+            .cost()
+            .and_then(|cost| cost.average_response_ms)
+            .map(|ms| ms * self.requests as f64 / 1000.0)
+    }
+}
+
+/// Everything a crawl command would do, resolved without side effects
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrawlPlan {
+    /// Steps other than series downloads, e.g. catalog discovery
+    pub steps: Vec<String>,
+    pub crawls: Vec<PlannedCrawl>,
+    /// Things the operator should know, e.g. series missing from the catalog
+    pub warnings: Vec<String>,
+}
+
+impl CrawlPlan {
+    pub fn total_requests(&self) -> u32 {
+        self.crawls.iter().map(|crawl| crawl.requests).sum()
+    }
+
+    /// Estimated download size, counting only crawls with history
+    pub fn estimated_bytes(&self) -> f64 {
+        self.crawls
+            .iter()
+            .filter_map(PlannedCrawl::estimated_bytes)
+            .sum()
+    }
+
+    /// Crawls with no history to estimate from
+    pub fn unestimated_crawls(&self) -> usize {
+        self.crawls
+            .iter()
+            .filter(|crawl| crawl.basis == EstimateBasis::NoHistory)
+            .count()
+    }
+
+    /// Plain-text report for the terminal
+    pub fn render(&self) -> String {
+        let mut report = String::from("Crawl plan (dry run, nothing was downloaded or written)\n");
+
+        for step in &self.steps {
+            let _ = writeln!(report, "  • {}", step);
+        }
+        for crawl in &self.crawls {
+            let _ = writeln!(
+                report,
+                "  • {} {} - {}\n      endpoint: {}\n      requests: {}, estimated size: {}, estimated time: {} ({})",
+                crawl.source,
+                crawl.external_id,
+                crawl.title,
+                crawl.endpoint.as_deref().unwrap_or("unknown"),
+                crawl.requests,
+                crawl
+                    .estimated_bytes()
+                    .map_or_else(|| "unknown".to_string(), format_bytes),
+                crawl
+                    .estimated_seconds()
+                    .map_or_else(|| "unknown".to_string(), |secs| format!("{:.1}s", secs)),
+                crawl.basis.describe(),
+            );
+        }
+        for warning in &self.warnings {
+            let _ = writeln!(report, "  ⚠️  {}", warning);
+        }
+
+        let _ = write!(
+            report,
+            "Total: {} series, {} requests, ~{} estimated",
+            self.crawls.len(),
+            self.total_requests(),
+            format_bytes(self.estimated_bytes())
+        );
+        if self.unestimated_crawls() > 0 {
+            let _ = write!(
+                report,
+                " ({} series without history not included)",
+                self.unestimated_crawls()
+            );
+        }
+        report.push('\n');
+        report
+    }
+}
+
+/// Human-readable byte count
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0} {}", value, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Recent crawl costs of a source's series, by external id
+pub async fn source_crawl_costs(
+    pool: &DatabasePool,
+    source_id: Uuid,
+) -> AppResult<HashMap<String, CrawlCost>> {
+    let mut conn = pool
+        .get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let cutoff = Utc::now() - Duration::days(ESTIMATE_WINDOW_DAYS);
+    let rows: Vec<(String, i64, Option<BigDecimal>, Option<BigDecimal>)> = crawl_attempts::table
+        .inner_join(economic_series::table)
+        .filter(economic_series::source_id.eq(source_id))
+        .filter(crawl_attempts::success.eq(true))
+        .filter(crawl_attempts::attempted_at.ge(cutoff))
+        .group_by(economic_series::external_id)
+        .select((
+            economic_series::external_id,
+            count(crawl_attempts::id),
+            avg(crawl_attempts::data_size_bytes),
+            avg(crawl_attempts::response_time_ms),
+        ))
+        .load(&mut conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(external_id, attempts, bytes, response_ms)| {
+            let cost = CrawlCost {
+                attempts,
+                average_bytes: bytes.and_then(|bytes| bytes.to_f64()),
+                average_response_ms: response_ms.and_then(|ms| ms.to_f64()),
+            };
+            (external_id, cost)
+        })
+        .collect())
+}
+
+/// Estimate basis for one series from its source's recent costs
+pub fn estimate_basis(costs: &HashMap<String, CrawlCost>, external_id: &str) -> EstimateBasis {
+    if let Some(cost) = costs.get(external_id) {
+        return EstimateBasis::SeriesHistory(*cost);
+    }
+    match CrawlCost::combined(costs.values()) {
+        Some(cost) => EstimateBasis::SourceAverage(cost),
+        None => EstimateBasis::NoHistory,
+    }
+}
+
+/// Builds crawl plans from the catalog and crawl history
+pub struct CrawlPlanner<'a> {
+    pool: &'a DatabasePool,
+}
+
+impl<'a> CrawlPlanner<'a> {
+    pub fn new(pool: &'a DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    async fn data_source(&self, source_name: &str) -> AppResult<DataSource> {
+        DataSource::find_by_name(self.pool, source_name)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError(format!("Data source not found: {}", source_name))
+            })
+    }
+
+    fn planned(
+        data_source: &DataSource,
+        series: &SeriesMetadata,
+        costs: &HashMap<String, CrawlCost>,
+    ) -> PlannedCrawl {
+        PlannedCrawl {
+            source: data_source.name.clone(),
+            external_id: series.external_id.clone(),
+            title: series.title.clone(),
+            endpoint: series
+                .api_endpoint
+                .as_deref()
+                .or(series.data_url.as_deref())
+                .map(redact_credentials),
+            requests: 1,
+            basis: estimate_basis(costs, &series.external_id),
+        }
+    }
+
+    /// Plan for discovering a source's catalog and downloading `series_count` series
+    pub async fn plan_source(
+        &self,
+        source_name: &str,
+        series_count: usize,
+    ) -> AppResult<CrawlPlan> {
+        let data_source = self.data_source(source_name).await?;
+        let catalog = SeriesMetadata::find_by_source(self.pool, data_source.id).await?;
+        let costs = source_crawl_costs(self.pool, data_source.id).await?;
+
+        let mut plan = CrawlPlan {
+            steps: vec![format!(
+                "Discover {} catalog at {} ({} series known)",
+                data_source.name,
+                data_source.base_url,
+                catalog.len()
+            )],
+            ..Default::default()
+        };
+        if !data_source.is_enabled {
+            plan.warnings.push(format!(
+                "{} is disabled and would be skipped",
+                data_source.name
+            ));
+        }
+        plan.crawls = catalog
+            .iter()
+            .filter(|series| series.is_active)
+            .take(series_count)
+            .map(|series| Self::planned(&data_source, series, &costs))
+            .collect();
+        if catalog.is_empty() {
+            plan.warnings.push(format!(
+                "{} has no catalog yet; the series to download are only known after discovery",
+                data_source.name
+            ));
+        }
+
+        Ok(plan)
+    }
+
+    /// Plan for downloading specific series of a source
+    pub async fn plan_series(
+        &self,
+        source_name: &str,
+        external_ids: &[&str],
+    ) -> AppResult<CrawlPlan> {
+        let data_source = self.data_source(source_name).await?;
+        let costs = source_crawl_costs(self.pool, data_source.id).await?;
+
+        let mut plan = CrawlPlan::default();
+        for external_id in external_ids {
+            match SeriesMetadata::find_by_external_id(self.pool, data_source.id, external_id)
+                .await?
+            {
+                Some(series) => plan
+                    .crawls
+                    .push(Self::planned(&data_source, &series, &costs)),
+                None => plan.warnings.push(format!(
+                    "{} is not in the {} catalog; the catalog would be downloaded first",
+                    external_id, data_source.name
+                )),
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Plan for downloading one randomly chosen series of a source
+    pub async fn plan_random(&self, source_name: &str) -> AppResult<CrawlPlan> {
+        let data_source = self.data_source(source_name).await?;
+        let catalog = SeriesMetadata::find_by_source(self.pool, data_source.id).await?;
+        let costs = source_crawl_costs(self.pool, data_source.id).await?;
+
+        let mut plan = CrawlPlan::default();
+        if catalog.is_empty() {
+            plan.warnings.push(format!(
+                "{} has no catalog yet; the catalog would be downloaded first",
+                data_source.name
+            ));
+        } else {
+            plan.crawls.push(PlannedCrawl {
+                source: data_source.name.clone(),
+                external_id: "(random)".to_string(),
+                title: format!("one of {} catalog series", catalog.len()),
+                endpoint: None,
+                requests: 1,
+                basis: CrawlCost::combined(costs.values())
+                    .map_or(EstimateBasis::NoHistory, EstimateBasis::SourceAverage),
+            });
+        }
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crawl_plan_estimates() {
+        // REQUIREMENT: Dry runs estimate requests and bytes from historical crawl metrics
+        // PURPOSE: Verify series history is preferred, the source average fills gaps, and totals add up
+        // This ensures operators see a realistic size for a crawl before running it
+
+        let costs = HashMap::from([
+            (
+                "GDP".to_string(),
+                CrawlCost {
+                    attempts: 3,
+                    average_bytes: Some(2048.0),
+                    average_response_ms: Some(500.0),
+                },
+            ),
+            (
+                "UNRATE".to_string(),
+                CrawlCost {
+                    attempts: 1,
+                    average_bytes: Some(1024.0),
+                    average_response_ms: None,
+                },
+            ),
+        ]);
+
+        let gdp = estimate_basis(&costs, "GDP");
+        assert!(matches!(gdp, EstimateBasis::SeriesHistory(cost) if cost.attempts == 3));
+
+        let EstimateBasis::SourceAverage(average) = estimate_basis(&costs, "CPIAUCSL") else {
+            panic!("Unknown series should use the source average");
+        };
+        assert_eq!(average.attempts, 4);
+        assert_eq!(average.average_bytes, Some(1792.0));
+        assert_eq!(average.average_response_ms, Some(500.0));
+        assert_eq!(
+            estimate_basis(&HashMap::new(), "GDP"),
+            EstimateBasis::NoHistory
+        );
+
+        let planned = |external_id: &str, basis| PlannedCrawl {
+            source: "FRED".to_string(),
+            external_id: external_id.to_string(),
+            title: external_id.to_string(),
+            endpoint: None,
+            requests: 1,
+            basis,
+        };
+        let plan = CrawlPlan {
+            crawls: vec![
+                planned("GDP", gdp),
+                planned("NEW", EstimateBasis::NoHistory),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(plan.total_requests(), 2);
+        assert_eq!(plan.estimated_bytes(), 2048.0);
+        assert_eq!(plan.unestimated_crawls(), 1);
+        assert!(plan.render().contains("2 series, 2 requests, ~2.0 KB"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Failover between the mirrors of one data source
+//!
+//! Some data sources publish mirrors of their API. A source lists them in
+//! `data_sources.fallback_urls`; requests go to its primary endpoint while it
+//! works and to the mirrors, in order, when it does not. See
+//! [`QuotaClient::send_with_failover`](super::QuotaClient::send_with_failover).
+//!
+//! Every endpoint has a circuit breaker. Transport errors and HTTP 5xx
+//! responses count as failures; a request that fails on one endpoint is
+//! repeated on the next. After [`FailoverPolicy::failure_threshold`]
+//! consecutive failures the circuit opens and the endpoint is skipped for
+//! [`FailoverPolicy::open_duration`]. It then half-opens: requests reach it
+//! again, and the next failure reopens the circuit while the next success
+//! closes it. Since the primary endpoint is always tried first when its
+//! circuit is not open, crawls move back to it once it recovers.
+//!
+//! Circuit state is kept per process, like [`ApiKeyRing`](super::ApiKeyRing).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use econ_graph_core::models::DataSource;
+use uuid::Uuid;
+
+/// Consecutive failures that open an endpoint's circuit
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open circuit skips its endpoint
+pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(60);
+
+/// When circuits open and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            open_duration: DEFAULT_OPEN_DURATION,
+        }
+    }
+}
+
+impl FailoverPolicy {
+    /// Policy from `CRAWLER_ENDPOINT_FAILURE_THRESHOLD` and
+    /// `CRAWLER_ENDPOINT_OPEN_SECONDS`, falling back to the defaults
+    pub fn from_env() -> Self {
+        let failure_threshold = std::env::var("CRAWLER_ENDPOINT_FAILURE_THRESHOLD")
+            .ok()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .filter(|threshold| *threshold > 0)
+            .unwrap_or(DEFAULT_FAILURE_THRESHOLD);
+        let open_duration = std::env::var("CRAWLER_ENDPOINT_OPEN_SECONDS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_OPEN_DURATION);
+
+        Self {
+            failure_threshold,
+            open_duration,
+        }
+    }
+}
+
+/// State of an endpoint's circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests reach the endpoint
+    Closed,
+    /// The open period is over; the next request decides
+    HalfOpen,
+    /// The endpoint is skipped
+    Open,
+}
+
+impl CircuitState {
+    /// Value of the `econgraph_crawler_endpoint_circuit_state` gauge
+    pub fn metric_value(&self) -> i64 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::HalfOpen => 1,
+            CircuitState::Open => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    /// Set when the circuit opens; cleared by a success
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.open_until {
+            Some(until) if until > now => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+}
+
+/// Endpoints of a data source, primary first, with their circuit breakers
+#[derive(Debug)]
+pub struct EndpointRing {
+    endpoints: Vec<String>,
+    policy: FailoverPolicy,
+    breakers: Mutex<Vec<Breaker>>,
+}
+
+impl EndpointRing {
+    /// Ring of `endpoints` in order of preference; an endpoint listed twice is used once
+    pub fn new(endpoints: Vec<String>, policy: FailoverPolicy) -> Self {
+        let ring_endpoints = distinct(endpoints);
+
+        Self {
+            breakers: Mutex::new(vec![Breaker::default(); ring_endpoints.len()]),
+            endpoints: ring_endpoints,
+            policy,
+        }
+    }
+
+    /// Endpoints in order of preference
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Endpoints to try a request on, in order; empty when every circuit is open
+    pub fn candidates(&self, now: Instant) -> Vec<String> {
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+
+        self.endpoints
+            .iter()
+            .zip(breakers.iter())
+            .filter(|(_, breaker)| breaker.state(now) != CircuitState::Open)
+            .map(|(endpoint, _)| endpoint.clone())
+            .collect()
+    }
+
+    /// Close the endpoint's circuit after a request succeeded
+    pub fn record_success(&self, endpoint: &str) -> CircuitState {
+        self.update(endpoint, |breaker| {
+            *breaker = Breaker::default();
+            CircuitState::Closed
+        })
+    }
+
+    /// Count a failed request, opening the circuit at the failure threshold
+    pub fn record_failure(&self, endpoint: &str, now: Instant) -> CircuitState {
+        let policy = self.policy;
+        self.update(endpoint, |breaker| {
+            breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+            if breaker.consecutive_failures >= policy.failure_threshold {
+                breaker.open_until = Some(now + policy.open_duration);
+            }
+            breaker.state(now)
+        })
+    }
+
+    /// Current state of an endpoint's circuit; closed for unknown endpoints
+    pub fn state(&self, endpoint: &str, now: Instant) -> CircuitState {
+        self.update(endpoint, |breaker| breaker.state(now))
+    }
+
+    fn update(&self, endpoint: &str, f: impl FnOnce(&mut Breaker) -> CircuitState) -> CircuitState {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+
+        match self.endpoints.iter().position(|known| known == endpoint) {
+            Some(index) => f(&mut breakers[index]),
+            None => CircuitState::Closed,
+        }
+    }
+}
+
+/// `endpoints` without repetitions, in their first order
+fn distinct(endpoints: Vec<String>) -> Vec<String> {
+    let mut distinct: Vec<String> = Vec::with_capacity(endpoints.len());
+    for endpoint in endpoints {
+        if !distinct.contains(&endpoint) {
+            distinct.push(endpoint);
+        }
+    }
+    distinct
+}
+
+/// Endpoint rings of every data source a client sends requests for
+#[derive(Debug)]
+pub struct EndpointHealth {
+    policy: FailoverPolicy,
+    rings: Mutex<HashMap<Uuid, Arc<EndpointRing>>>,
+}
+
+impl Default for EndpointHealth {
+    fn default() -> Self {
+        Self::new(FailoverPolicy::from_env())
+    }
+}
+
+impl EndpointHealth {
+    pub fn new(policy: FailoverPolicy) -> Self {
+        Self {
+            policy,
+            rings: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Ring of `primary` followed by the source's fallback URLs
+    ///
+    /// Circuit state is kept as long as the list stays the same; a changed
+    /// list starts over with every circuit closed.
+    pub fn ring(&self, source: &DataSource, primary: &str) -> Arc<EndpointRing> {
+        let endpoints = distinct(
+            std::iter::once(primary.to_string())
+                .chain(source.fallback_urls.iter().cloned())
+                .collect(),
+        );
+        let mut rings = self.rings.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(ring) = rings.get(&source.id) {
+            if ring.endpoints() == endpoints.as_slice() {
+                return ring.clone();
+            }
+        }
+
+        let ring = Arc::new(EndpointRing::new(endpoints, self.policy));
+        rings.insert(source.id, ring.clone());
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures_and_half_opens() {
+        // REQUIREMENT: Crawlers switch to a data source's mirror when its primary endpoint keeps failing
+        // PURPOSE: Verify circuits open at the failure threshold, skip the endpoint while open, and half-open afterwards
+        // This ensures crawls move to a mirror during an outage and back to the primary once it recovers
+
+        let policy = FailoverPolicy {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(30),
+        };
+        let primary = "https://api.bls.gov/publicAPI/v2";
+        let mirror = "https://mirror.example.com/publicAPI/v2";
+        let ring = EndpointRing::new(
+            vec![primary.to_string(), mirror.to_string(), primary.to_string()],
+            policy,
+        );
+        assert_eq!(ring.endpoints().len(), 2);
+
+        let now = Instant::now();
+        assert_eq!(ring.candidates(now), vec![primary, mirror]);
+
+        // A success in between resets the count of consecutive failures
+        assert_eq!(ring.record_failure(primary, now), CircuitState::Closed);
+        ring.record_success(primary);
+        assert_eq!(ring.record_failure(primary, now), CircuitState::Closed);
+        assert_eq!(ring.record_failure(primary, now), CircuitState::Open);
+        assert_eq!(ring.candidates(now), vec![mirror]);
+
+        ring.record_failure(mirror, now);
+        ring.record_failure(mirror, now);
+        assert!(ring.candidates(now).is_empty());
+
+        let later = now + Duration::from_secs(31);
+        assert_eq!(ring.state(primary, later), CircuitState::HalfOpen);
+        assert_eq!(ring.candidates(later), vec![primary, mirror]);
+
+        // One failure while half-open reopens the circuit
+        assert_eq!(ring.record_failure(primary, later), CircuitState::Open);
+        assert_eq!(ring.candidates(later), vec![mirror]);
+        assert_eq!(ring.record_success(mirror), CircuitState::Closed);
+        assert_eq!(ring.state(mirror, later), CircuitState::Closed);
+    }
+}
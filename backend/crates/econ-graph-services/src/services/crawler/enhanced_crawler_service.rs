@@ -1,6 +1,5 @@
 //! Enhanced crawler service with crawl attempts tracking and data source visibility controls
 
-use bigdecimal::BigDecimal;
 use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
 use diesel::ExpressionMethods;
@@ -12,7 +11,13 @@ use uuid::Uuid;
 
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
-use econ_graph_core::models::{CrawlAttempt, DataPoint, NewCrawlAttempt, NewDataPoint};
+use econ_graph_core::models::{CrawlAttempt, NewCrawlAttempt};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, FRED_HOST};
+
+use super::crawl_plan::{estimate_basis, source_crawl_costs, PlannedCrawl};
+use super::ingestion_pipeline::{CrawlOrigin, IngestionPipeline, RawObservation};
+use crate::services::series_service;
+use crate::services::webhook_service::publish_crawl_failed;
 
 /// Enhanced crawler service with comprehensive tracking
 pub struct EnhancedCrawlerService {
@@ -31,6 +36,7 @@ impl EnhancedCrawlerService {
     }
 
     /// Crawl a series with comprehensive tracking
+    #[tracing::instrument(name = "crawler.crawl_series", skip(self, pool))]
     pub async fn crawl_series_with_tracking(
         &self,
         pool: &DatabasePool,
@@ -55,10 +61,23 @@ impl EnhancedCrawlerService {
 
         let attempt = CrawlAttempt::create(pool, &new_attempt).await?;
 
+        // Stored points are traced back to this attempt
+        let origin = CrawlOrigin {
+            crawl_attempt_id: Some(attempt.id),
+            source_url: Some(self.public_crawl_url(source_name, external_id)),
+            crawled_at: Some(start_time),
+        };
+
         // Perform the actual crawl
         let crawl_result = match source_name {
-            "FRED" => self.crawl_fred_series(pool, series_id, external_id).await,
-            "BLS" => self.crawl_bls_series(pool, series_id, external_id).await,
+            "FRED" => {
+                self.crawl_fred_series(pool, series_id, external_id, origin)
+                    .await
+            }
+            "BLS" => {
+                self.crawl_bls_series(pool, series_id, external_id, origin)
+                    .await
+            }
             _ => Err(AppError::ExternalApiError(format!(
                 "Unsupported source: {}",
                 source_name
@@ -87,6 +106,7 @@ impl EnhancedCrawlerService {
                 .await?
             }
             Err(e) => {
+                publish_crawl_failed(pool, source_name, external_id, &e.to_string()).await;
                 CrawlAttempt::update_completion(
                     pool,
                     &attempt.id,
@@ -131,12 +151,55 @@ impl EnhancedCrawlerService {
         }
     }
 
+    /// What [`Self::crawl_series_with_tracking`] would request, without
+    /// fetching or writing anything
+    pub async fn plan_series_crawl(
+        &self,
+        pool: &DatabasePool,
+        series_id: &Uuid,
+        external_id: &str,
+        source_name: &str,
+    ) -> AppResult<PlannedCrawl> {
+        if !matches!(source_name, "FRED" | "BLS") {
+            return Err(AppError::ExternalApiError(format!(
+                "Unsupported source: {}",
+                source_name
+            )));
+        }
+
+        let series = series_service::get_series_by_id(pool, *series_id)
+            .await?
+            .ok_or_else(|| AppError::SeriesNotFound(series_id.to_string()))?;
+        let costs = source_crawl_costs(pool, series.source_id).await?;
+
+        Ok(PlannedCrawl {
+            source: source_name.to_string(),
+            external_id: external_id.to_string(),
+            title: series.title,
+            endpoint: Some(self.public_crawl_url(source_name, external_id)),
+            requests: 1,
+            basis: estimate_basis(&costs, external_id),
+        })
+    }
+
+    /// Crawl URL without credentials, as shown in data point provenance
+    fn public_crawl_url(&self, source_name: &str, external_id: &str) -> String {
+        match source_name {
+            "FRED" => format!(
+                "https://api.stlouisfed.org/fred/series/observations?series_id={}&file_type=json",
+                external_id
+            ),
+            _ => self.build_crawl_url(source_name, external_id),
+        }
+    }
+
     /// Crawl FRED series with tracking
     async fn crawl_fred_series(
         &self,
         pool: &DatabasePool,
         series_id: &Uuid,
         external_id: &str,
+        origin: CrawlOrigin,
     ) -> AppResult<CrawlResult> {
         let api_key = self
             .fred_api_key
@@ -148,6 +211,9 @@ impl EnhancedCrawlerService {
             external_id, api_key
         );
 
+        shared_rate_limiter()
+            .acquire(FRED_HOST, Some(api_key.as_str()))
+            .await;
         let response = self
             .client
             .get(&url)
@@ -167,52 +233,24 @@ impl EnhancedCrawlerService {
             AppError::ExternalApiError(format!("Failed to parse FRED response: {}", e))
         })?;
 
-        let mut new_data_points = 0;
-        let mut latest_data_date = None;
-        let mut data_freshness_hours = None;
-
-        for observation in &fred_response.observations {
-            if observation.value != "." && !observation.value.is_empty() {
-                let date =
-                    NaiveDate::parse_from_str(&observation.date, "%Y-%m-%d").map_err(|e| {
-                        AppError::ExternalApiError(format!("Invalid date format: {}", e))
-                    })?;
-
-                let value =
-                    BigDecimal::parse_bytes(observation.value.as_bytes(), 10).ok_or_else(|| {
-                        AppError::ExternalApiError("Invalid value format".to_string())
-                    })?;
-
-                // Check if this data point already exists
-                let existing_point =
-                    DataPoint::get_by_series_and_date(pool, *series_id, &date).await?;
-
-                if existing_point.is_none() {
-                    let new_point = NewDataPoint {
-                        series_id: *series_id,
-                        date,
-                        value: Some(value),
-                        revision_date: Utc::now().date_naive(),
-                        is_original_release: true,
-                    };
-
-                    DataPoint::create(pool, &new_point).await?;
-                    new_data_points += 1;
-                }
+        let batch: Vec<RawObservation> = fred_response
+            .observations
+            .iter()
+            .map(|observation| RawObservation::new(&observation.date, &observation.value))
+            .collect();
+        let report = IngestionPipeline::default()
+            .with_origin(origin)
+            .ingest(pool, "FRED", *series_id, &batch)
+            .await?;
 
-                // Track latest data date
-                if latest_data_date.is_none() || date > latest_data_date.unwrap() {
-                    latest_data_date = Some(date);
+        let new_data_points = report.stored as i32;
+        let latest_data_date = report.latest_date;
 
-                    // Calculate data freshness (hours since publication)
-                    let now = Utc::now().date_naive();
-                    if date <= now {
-                        let days_diff = (now - date).num_days();
-                        data_freshness_hours = Some((days_diff * 24) as i32);
-                    }
-                }
-            }
-        }
+        // Data freshness: hours since the latest observation date
+        let now = Utc::now().date_naive();
+        let data_freshness_hours = latest_data_date
+            .filter(|date| *date <= now)
+            .map(|date| ((now - date).num_days() * 24) as i32);
 
         Ok(CrawlResult {
             data_found: new_data_points > 0 || !fred_response.observations.is_empty(),
@@ -229,6 +267,7 @@ impl EnhancedCrawlerService {
         _pool: &DatabasePool,
         _series_id: &Uuid,
         _external_id: &str,
+        _origin: CrawlOrigin,
     ) -> AppResult<CrawlResult> {
         // BLS crawling implementation would go here
         // For now, return a placeholder result
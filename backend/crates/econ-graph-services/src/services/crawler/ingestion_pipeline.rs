@@ -0,0 +1,703 @@
+//! Validation pipeline for crawled observations
+//!
+//! Every batch goes through the same steps before anything is written:
+//! 1. Schema validation (parseable date and value, no future dates)
+//! 2. Unit normalization into the series' unit
+//! 3. Outlier flagging by z-score (flagged points are still stored)
+//! 4. Duplicate detection within the batch and against stored data
+//! 5. Storage write, with a lineage record per stored point naming the crawl
+//!    and the steps above that touched it
+//! 6. Evaluation of the series' alert rules
+//! 7. Queueing of `series_updated` webhook deliveries
+//!
+//! The returned [`IngestionReport`] describes what happened to each batch, and
+//! the outcome counts are exported as `econgraph_crawler_validation_results_total`.
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::data_point_cache::shared_data_point_cache;
+use crate::services::derived_series_service::recompute_derived_after_update;
+use crate::services::response_cache::shared_response_cache;
+use crate::services::series_alert_service::evaluate_alerts_after_update;
+use crate::services::webhook_service::publish_series_updated;
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::AppResult;
+use econ_graph_core::models::{
+    DataLineage, DataPoint, NewDataLineage, NewDataPoint, ProcessingStep,
+};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Observation as received from a data source, before validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawObservation {
+    /// Observation date in `YYYY-MM-DD` format
+    pub date: String,
+    /// Reported value; `None`, empty or `"."` mean the value is missing
+    pub value: Option<String>,
+    /// Unit the value is reported in, when it differs from the series unit
+    pub unit: Option<String>,
+}
+
+impl RawObservation {
+    pub fn new(date: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            date: date.into(),
+            value: Some(value.into()),
+            unit: None,
+        }
+    }
+
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+}
+
+/// Pipeline settings
+#[derive(Debug, Clone)]
+pub struct IngestionConfig {
+    /// Unit values are stored in; observations without a unit are assumed to use it
+    pub target_unit: Option<String>,
+    /// Absolute z-score above which a value is flagged as an outlier
+    pub outlier_z_threshold: f64,
+    /// Minimum number of values in a batch before outliers are flagged
+    pub min_points_for_outliers: usize,
+    /// Accept observations dated after today (forecast series)
+    pub allow_future_dates: bool,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            target_unit: None,
+            outlier_z_threshold: 3.0,
+            // |z| cannot exceed (n - 1) / sqrt(n), so smaller batches never reach 3.0
+            min_points_for_outliers: 12,
+            allow_future_dates: false,
+        }
+    }
+}
+
+/// Crawl a batch came from, recorded as the lineage of every point it stores
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrawlOrigin {
+    pub crawl_attempt_id: Option<Uuid>,
+    /// URL the batch was fetched from, without credentials
+    pub source_url: Option<String>,
+    /// When the batch was fetched; defaults to the time of ingestion
+    pub crawled_at: Option<DateTime<Utc>>,
+}
+
+/// Observation rejected by schema validation or unit normalization
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RejectedObservation {
+    /// Position in the submitted batch
+    pub index: usize,
+    pub date: String,
+    pub reason: String,
+}
+
+/// Value flagged as a statistical outlier within its batch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutlierFlag {
+    pub date: NaiveDate,
+    pub value: f64,
+    pub z_score: f64,
+}
+
+/// Outcome of ingesting one batch
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IngestionReport {
+    pub series_id: Uuid,
+    pub source: String,
+    /// Observations submitted
+    pub received: usize,
+    /// Observations without a value, skipped
+    pub missing: usize,
+    pub rejected: Vec<RejectedObservation>,
+    /// Values converted from another unit
+    pub normalized: usize,
+    pub outliers: Vec<OutlierFlag>,
+    /// Observations skipped because the date was repeated or already stored
+    pub duplicates: usize,
+    /// Data points written
+    pub stored: usize,
+    /// Latest valid observation date in the batch
+    pub latest_date: Option<NaiveDate>,
+}
+
+impl IngestionReport {
+    /// Whether every submitted observation was usable
+    pub fn is_clean(&self) -> bool {
+        self.rejected.is_empty() && self.outliers.is_empty()
+    }
+
+    fn record_metrics(&self) {
+        let source = self.source.as_str();
+        CRAWLER_METRICS.record_validation(source, "received", self.received as u64);
+        CRAWLER_METRICS.record_validation(source, "missing", self.missing as u64);
+        CRAWLER_METRICS.record_validation(source, "invalid", self.rejected.len() as u64);
+        CRAWLER_METRICS.record_validation(source, "normalized", self.normalized as u64);
+        CRAWLER_METRICS.record_validation(source, "outlier", self.outliers.len() as u64);
+        CRAWLER_METRICS.record_validation(source, "duplicate", self.duplicates as u64);
+        CRAWLER_METRICS.record_validation(source, "stored", self.stored as u64);
+    }
+}
+
+/// Observation that passed validation and normalization
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidObservation {
+    pub date: NaiveDate,
+    pub value: BigDecimal,
+    /// Unit the value was converted from, if unit normalization changed it
+    pub normalized_from: Option<String>,
+}
+
+/// Runs crawled batches through validation before storing them
+#[derive(Debug, Clone, Default)]
+pub struct IngestionPipeline {
+    config: IngestionConfig,
+    origin: CrawlOrigin,
+}
+
+impl IngestionPipeline {
+    pub fn new(config: IngestionConfig) -> Self {
+        Self {
+            config,
+            origin: CrawlOrigin::default(),
+        }
+    }
+
+    /// Record `origin` as the lineage of the points this pipeline stores
+    pub fn with_origin(mut self, origin: CrawlOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Validate a batch and store the new observations for a series
+    #[tracing::instrument(name = "crawler.ingest", skip(self, pool, batch), fields(batch_size = batch.len()))]
+    pub async fn ingest(
+        &self,
+        pool: &DatabasePool,
+        source: &str,
+        series_id: Uuid,
+        batch: &[RawObservation],
+    ) -> AppResult<IngestionReport> {
+        let (observations, mut report) = self.validate(source, series_id, batch);
+
+        let existing_dates = match (
+            observations.iter().map(|o| o.date).min(),
+            observations.iter().map(|o| o.date).max(),
+        ) {
+            (Some(start), Some(end)) => {
+                DataPoint::find_by_series_and_date_range(pool, series_id, start, end)
+                    .await?
+                    .into_iter()
+                    .map(|point| point.date)
+                    .collect()
+            }
+            _ => HashSet::new(),
+        };
+
+        let new_observations = remove_duplicates(observations, &existing_dates, &mut report);
+
+        if !new_observations.is_empty() {
+            let steps: HashMap<NaiveDate, Vec<ProcessingStep>> = new_observations
+                .iter()
+                .map(|observation| {
+                    (
+                        observation.date,
+                        self.processing_steps(observation, &report.outliers),
+                    )
+                })
+                .collect();
+
+            let revision_date = Utc::now().date_naive();
+            let data_points: Vec<NewDataPoint> = new_observations
+                .into_iter()
+                .map(|observation| NewDataPoint {
+                    series_id,
+                    date: observation.date,
+                    value: Some(observation.value),
+                    revision_date,
+                    is_original_release: true,
+                })
+                .collect();
+
+            let stored = DataPoint::insert_bulk(pool, &data_points).await?;
+            report.stored = stored.len();
+            self.record_lineage(pool, source, series_id, &stored, &steps)
+                .await;
+            shared_data_point_cache().invalidate_series(series_id);
+            shared_response_cache().invalidate_series(series_id);
+            evaluate_alerts_after_update(pool, series_id).await;
+            publish_series_updated(pool, series_id, report.stored).await;
+            recompute_derived_after_update(pool, series_id).await;
+        }
+
+        report.record_metrics();
+        if !report.is_clean() {
+            warn!(
+                "Ingested {} batch for series {} with {} rejected and {} outlier observations",
+                source,
+                series_id,
+                report.rejected.len(),
+                report.outliers.len()
+            );
+        }
+        info!(
+            "Stored {} of {} {} observations for series {}",
+            report.stored, report.received, source, series_id
+        );
+
+        Ok(report)
+    }
+
+    /// Steps applied to an observation on its way to storage
+    pub fn processing_steps(
+        &self,
+        observation: &ValidObservation,
+        outliers: &[OutlierFlag],
+    ) -> Vec<ProcessingStep> {
+        let mut steps = vec![ProcessingStep::new("schema_validation")];
+        if let Some(from) = &observation.normalized_from {
+            let to = self.config.target_unit.as_deref().unwrap_or("series unit");
+            steps.push(
+                ProcessingStep::new("unit_normalization")
+                    .with_detail(format!("converted from {} to {}", from, to)),
+            );
+        }
+        let outlier_check = ProcessingStep::new("outlier_check");
+        steps.push(
+            match outliers.iter().find(|flag| flag.date == observation.date) {
+                Some(flag) => outlier_check.with_detail(format!(
+                    "flagged as an outlier (z-score {:.2})",
+                    flag.z_score
+                )),
+                None => outlier_check,
+            },
+        );
+        steps.push(ProcessingStep::new("duplicate_check"));
+        steps.push(ProcessingStep::new("storage"));
+        steps
+    }
+
+    /// Link stored points to the crawl they came from
+    ///
+    /// Lineage is descriptive, so a failed write is logged rather than undoing
+    /// the stored batch.
+    async fn record_lineage(
+        &self,
+        pool: &DatabasePool,
+        source: &str,
+        series_id: Uuid,
+        stored: &[DataPoint],
+        steps: &HashMap<NaiveDate, Vec<ProcessingStep>>,
+    ) {
+        let crawled_at = self.origin.crawled_at.unwrap_or_else(Utc::now);
+        let records: Vec<NewDataLineage> = stored
+            .iter()
+            .map(|point| {
+                NewDataLineage::for_data_point(
+                    point.id,
+                    source,
+                    self.origin.source_url.clone(),
+                    self.origin.crawl_attempt_id,
+                    crawled_at,
+                    steps
+                        .get(&point.date)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                )
+            })
+            .collect();
+
+        if let Err(e) = DataLineage::create_batch(pool, &records).await {
+            warn!(
+                "Failed to record lineage of {} data points for series {}: {}",
+                records.len(),
+                series_id,
+                e
+            );
+        }
+    }
+
+    /// Schema validation, unit normalization and outlier flagging
+    ///
+    /// Duplicate detection needs the stored dates and happens in [`Self::ingest`].
+    pub fn validate(
+        &self,
+        source: &str,
+        series_id: Uuid,
+        batch: &[RawObservation],
+    ) -> (Vec<ValidObservation>, IngestionReport) {
+        let mut report = IngestionReport {
+            series_id,
+            source: source.to_string(),
+            received: batch.len(),
+            ..Default::default()
+        };
+        let today = Utc::now().date_naive();
+        let target_unit = self.config.target_unit.as_deref();
+
+        let mut observations = Vec::with_capacity(batch.len());
+        for (index, raw) in batch.iter().enumerate() {
+            let reject = |reason: String| RejectedObservation {
+                index,
+                date: raw.date.clone(),
+                reason,
+            };
+
+            let date = match NaiveDate::parse_from_str(raw.date.trim(), "%Y-%m-%d") {
+                Ok(date) => date,
+                Err(_) => {
+                    report
+                        .rejected
+                        .push(reject(format!("invalid date '{}'", raw.date)));
+                    continue;
+                }
+            };
+            if date > today && !self.config.allow_future_dates {
+                report
+                    .rejected
+                    .push(reject(format!("date {} is in the future", date)));
+                continue;
+            }
+
+            let value = match raw.value.as_deref().map(str::trim) {
+                None | Some("") | Some(".") => {
+                    report.missing += 1;
+                    continue;
+                }
+                Some(value) => match BigDecimal::from_str(value) {
+                    Ok(value) => value,
+                    Err(_) => {
+                        report
+                            .rejected
+                            .push(reject(format!("invalid value '{}'", value)));
+                        continue;
+                    }
+                },
+            };
+
+            let (value, normalized_from) =
+                match normalize_unit(value, raw.unit.as_deref(), target_unit) {
+                    Ok((value, converted)) => {
+                        if converted {
+                            report.normalized += 1;
+                        }
+                        (value, raw.unit.clone().filter(|_| converted))
+                    }
+                    Err(reason) => {
+                        report.rejected.push(reject(reason));
+                        continue;
+                    }
+                };
+
+            observations.push(ValidObservation {
+                date,
+                value,
+                normalized_from,
+            });
+        }
+
+        report.outliers = flag_outliers(
+            &observations,
+            self.config.outlier_z_threshold,
+            self.config.min_points_for_outliers,
+        );
+        report.latest_date = observations.iter().map(|o| o.date).max();
+
+        (observations, report)
+    }
+}
+
+/// Drop observations whose date repeats within the batch or is already stored
+pub fn remove_duplicates(
+    observations: Vec<ValidObservation>,
+    existing_dates: &HashSet<NaiveDate>,
+    report: &mut IngestionReport,
+) -> Vec<ValidObservation> {
+    let mut seen = HashSet::new();
+    observations
+        .into_iter()
+        .filter(|observation| {
+            let is_new =
+                !existing_dates.contains(&observation.date) && seen.insert(observation.date);
+            if !is_new {
+                report.duplicates += 1;
+            }
+            is_new
+        })
+        .collect()
+}
+
+/// Flag values whose z-score within the batch exceeds `threshold`
+pub fn flag_outliers(
+    observations: &[ValidObservation],
+    threshold: f64,
+    min_points: usize,
+) -> Vec<OutlierFlag> {
+    if observations.len() < min_points.max(2) {
+        return Vec::new();
+    }
+
+    let values: Vec<f64> = observations
+        .iter()
+        .map(|o| o.value.to_string().parse().unwrap_or(f64::NAN))
+        .collect();
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if !std_dev.is_finite() || std_dev == 0.0 {
+        return Vec::new();
+    }
+
+    observations
+        .iter()
+        .zip(values)
+        .filter_map(|(observation, value)| {
+            let z_score = (value - mean) / std_dev;
+            (z_score.abs() > threshold).then_some(OutlierFlag {
+                date: observation.date,
+                value,
+                z_score,
+            })
+        })
+        .collect()
+}
+
+/// Units that can be converted into each other by a power of ten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnitFamily {
+    Count,
+    Rate,
+}
+
+/// Family and power of ten of a unit label such as "Billions of Dollars"
+fn unit_scale(unit: &str) -> Option<(UnitFamily, i64)> {
+    let unit = unit.to_ascii_lowercase();
+    let scale = if unit.contains("basis point") {
+        (UnitFamily::Rate, -2)
+    } else if unit.contains("percent") || unit == "%" {
+        (UnitFamily::Rate, 0)
+    } else if unit.contains("trillion") {
+        (UnitFamily::Count, 12)
+    } else if unit.contains("billion") {
+        (UnitFamily::Count, 9)
+    } else if unit.contains("million") {
+        (UnitFamily::Count, 6)
+    } else if unit.contains("thousand") {
+        (UnitFamily::Count, 3)
+    } else {
+        return None;
+    };
+    Some(scale)
+}
+
+/// Convert `value` from `from` into `to`, returning whether it was converted
+///
+/// Values are left untouched when either unit is unknown or both are the same.
+pub fn normalize_unit(
+    value: BigDecimal,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<(BigDecimal, bool), String> {
+    let (Some(from), Some(to)) = (from, to) else {
+        return Ok((value, false));
+    };
+    if from.trim().eq_ignore_ascii_case(to.trim()) {
+        return Ok((value, false));
+    }
+
+    match (unit_scale(from), unit_scale(to)) {
+        (Some((from_family, from_exp)), Some((to_family, to_exp))) if from_family == to_family => {
+            if from_exp == to_exp {
+                return Ok((value, false));
+            }
+            let factor = BigDecimal::from_str(&format!("1e{}", from_exp - to_exp))
+                .map_err(|e| format!("invalid unit scale: {}", e))?;
+            Ok(((value * factor).normalized(), true))
+        }
+        _ => Err(format!("cannot convert unit '{}' to '{}'", from, to)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline(target_unit: Option<&str>) -> IngestionPipeline {
+        IngestionPipeline::new(IngestionConfig {
+            target_unit: target_unit.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_observations() {
+        // REQUIREMENT: Crawled data is validated before it is stored
+        // PURPOSE: Verify bad dates, bad values and future dates are rejected and missing values skipped
+        // This ensures malformed API responses never reach the data_points table
+
+        let batch = vec![
+            RawObservation::new("2024-01-01", "1.5"),
+            RawObservation::new("2024-13-01", "2.0"),
+            RawObservation::new("2024-02-01", "abc"),
+            RawObservation::new("2024-03-01", "."),
+            RawObservation::new("2999-01-01", "3.0"),
+        ];
+
+        let (valid, report) = pipeline(None).validate("FRED", Uuid::nil(), &batch);
+
+        assert_eq!(valid.len(), 1);
+        assert_eq!(report.received, 5);
+        assert_eq!(report.missing, 1);
+        assert_eq!(
+            report.rejected.iter().map(|r| r.index).collect::<Vec<_>>(),
+            vec![1, 2, 4]
+        );
+        assert_eq!(report.latest_date, NaiveDate::from_ymd_opt(2024, 1, 1));
+    }
+
+    #[test]
+    fn test_unit_normalization() {
+        // REQUIREMENT: Values are stored in the series' unit
+        // PURPOSE: Verify magnitude conversions and rejection of incompatible units
+        // This ensures a source switching from millions to billions does not corrupt a series
+
+        let batch = vec![
+            RawObservation::new("2024-01-01", "2500").with_unit("Millions of Dollars"),
+            RawObservation::new("2024-02-01", "2.6").with_unit("Billions of Dollars"),
+            RawObservation::new("2024-03-01", "4.1").with_unit("Percent"),
+        ];
+
+        let (valid, report) =
+            pipeline(Some("Billions of Dollars")).validate("BEA", Uuid::nil(), &batch);
+
+        assert_eq!(valid[0].value, BigDecimal::from_str("2.5").unwrap());
+        assert_eq!(valid[1].value, BigDecimal::from_str("2.6").unwrap());
+        assert_eq!(report.normalized, 1);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].index, 2);
+
+        let (bps, converted) =
+            normalize_unit(BigDecimal::from(25), Some("Basis Points"), Some("Percent")).unwrap();
+        assert!(converted);
+        assert_eq!(bps, BigDecimal::from_str("0.25").unwrap());
+    }
+
+    #[test]
+    fn test_outliers_flagged_and_duplicates_removed() {
+        // REQUIREMENT: Suspicious values are flagged and duplicates are not re-inserted
+        // PURPOSE: Verify z-score flagging and duplicate detection within a batch and against stored dates
+        // This ensures recrawls are idempotent and data errors are visible in the batch report
+
+        let mut batch: Vec<RawObservation> = (1..=20)
+            .map(|month| {
+                let date = NaiveDate::from_ymd_opt(
+                    2020 + (month - 1) / 12,
+                    ((month - 1) % 12 + 1) as u32,
+                    1,
+                )
+                .unwrap();
+                RawObservation::new(date.to_string(), format!("{}", 100 + month % 3))
+            })
+            .collect();
+        batch.push(RawObservation::new("2022-06-01", "1000"));
+        batch.push(RawObservation::new("2020-01-01", "101"));
+
+        let (valid, mut report) = pipeline(None).validate("FRED", Uuid::nil(), &batch);
+
+        assert_eq!(report.outliers.len(), 1);
+        assert_eq!(
+            report.outliers[0].date,
+            NaiveDate::from_ymd_opt(2022, 6, 1).unwrap()
+        );
+        assert!(report.outliers[0].z_score > 3.0);
+
+        let existing = HashSet::from([NaiveDate::from_ymd_opt(2020, 2, 1).unwrap()]);
+        let new_observations = remove_duplicates(valid, &existing, &mut report);
+
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(new_observations.len(), 20);
+    }
+
+    #[test]
+    fn test_processing_steps_describe_each_observation() {
+        // REQUIREMENT: Stored data points record how they were processed
+        // PURPOSE: Verify lineage steps name the unit conversion and outlier flag of each value
+        // This ensures provenance queries explain why a stored value differs from the source
+
+        let batch = vec![
+            RawObservation::new("2024-01-01", "2500").with_unit("Millions of Dollars"),
+            RawObservation::new("2024-02-01", "2.6"),
+        ];
+        let pipeline = pipeline(Some("Billions of Dollars"));
+        let (valid, _) = pipeline.validate("BEA", Uuid::nil(), &batch);
+        let outliers = vec![OutlierFlag {
+            date: valid[1].date,
+            value: 2.6,
+            z_score: 4.25,
+        }];
+
+        let converted = pipeline.processing_steps(&valid[0], &outliers);
+        let names: Vec<&str> = converted.iter().map(|step| step.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "schema_validation",
+                "unit_normalization",
+                "outlier_check",
+                "duplicate_check",
+                "storage"
+            ]
+        );
+        assert_eq!(
+            converted[1].detail.as_deref(),
+            Some("converted from Millions of Dollars to Billions of Dollars")
+        );
+        assert_eq!(converted[2].detail, None);
+
+        let flagged = pipeline.processing_steps(&valid[1], &outliers);
+        assert_eq!(flagged.len(), 4);
+        assert_eq!(
+            flagged[1].detail.as_deref(),
+            Some("flagged as an outlier (z-score 4.25)")
+        );
+    }
+
+    #[test]
+    fn test_report_records_validation_metrics() {
+        // REQUIREMENT: Validation outcomes are visible in crawler metrics
+        // PURPOSE: Verify each outcome of a batch report is counted under its source
+
+        let metrics = econ_graph_metrics::crawler::CrawlerMetrics::test_scope();
+        let batch = vec![
+            RawObservation::new("2024-01-01", "1.5"),
+            RawObservation::new("2024-02-01", "abc"),
+            RawObservation::new("2024-03-01", "."),
+        ];
+
+        let (_, report) = pipeline(None).validate("FRED", Uuid::nil(), &batch);
+        report.record_metrics();
+
+        let count = |outcome: &str| {
+            metrics
+                .metrics()
+                .crawler_validation_results_total
+                .with_label_values(&["FRED", outcome])
+                .get()
+        };
+        assert_eq!(count("received"), 3);
+        assert_eq!(count("invalid"), 1);
+        assert_eq!(count("missing"), 1);
+        assert_eq!(count("stored"), 0);
+    }
+}
@@ -1,7 +1,7 @@
 use bigdecimal::{BigDecimal, FromPrimitive};
 use chrono::{DateTime, Datelike, NaiveDate, Utc};
-use reqwest::Client;
 use serde::Deserialize;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use econ_graph_core::database::DatabasePool;
@@ -10,6 +10,22 @@ use econ_graph_core::models::{
     CrawlQueueItem, DataPoint, DataSource, EconomicSeries, NewCrawlQueueItem, NewDataPoint,
     NewEconomicSeries, QueuePriority,
 };
+use econ_graph_core::shutdown::shutdown_coordinator;
+
+use crate::services::crawler::api_key_ring::ApiKeyRing;
+use crate::services::crawler::quota_client::{next_quota_reset, QuotaClient};
+use crate::services::data_point_cache::shared_data_point_cache;
+use crate::services::derived_series_service::recompute_derived_after_update;
+use crate::services::response_cache::shared_response_cache;
+use crate::services::queue_service::{defer_source_items, enqueue_item};
+use crate::services::series_alert_service::evaluate_alerts_after_update;
+use crate::services::webhook_service::{publish_crawl_failed, publish_series_updated};
+
+/// FRED API root; endpoints such as `/series` are appended
+pub const FRED_BASE_URL: &str = "https://api.stlouisfed.org/fred";
+
+/// BLS public API root; endpoints such as `/timeseries/data/` are appended
+pub const BLS_BASE_URL: &str = "https://api.bls.gov/publicAPI/v2";
 
 /// FRED API response for series metadata
 #[derive(Debug, Deserialize)]
@@ -87,21 +103,40 @@ pub struct BlsFootnote {
 
 /// Crawler service for fetching economic data from external APIs
 pub struct CrawlerService {
-    client: Client,
-    fred_api_key: Option<String>,
+    client: QuotaClient,
+    /// FRED keys, rotated when one is throttled
+    fred_keys: ApiKeyRing,
     bls_api_key: Option<String>,
+    fred_base_url: String,
+    bls_base_url: String,
 }
 
 impl CrawlerService {
     /// Create new crawler service
+    ///
+    /// `fred_api_key` may list several keys separated by commas.
     pub fn new(fred_api_key: Option<String>, bls_api_key: Option<String>) -> Self {
         Self {
-            client: Client::new(),
-            fred_api_key,
+            client: QuotaClient::default(),
+            fred_keys: ApiKeyRing::from_setting(fred_api_key.as_deref()),
             bls_api_key,
+            fred_base_url: FRED_BASE_URL.to_string(),
+            bls_base_url: BLS_BASE_URL.to_string(),
         }
     }
 
+    /// Send FRED requests to `base_url` instead of [`FRED_BASE_URL`], e.g. a mock server
+    pub fn with_fred_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.fred_base_url = base_url.into();
+        self
+    }
+
+    /// Send BLS requests to `base_url` instead of [`BLS_BASE_URL`], e.g. a mock server
+    pub fn with_bls_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.bls_base_url = base_url.into();
+        self
+    }
+
     /// Get or create FRED data source
     async fn get_or_create_fred_source(&self, pool: &DatabasePool) -> AppResult<DataSource> {
         let fred_source = DataSource::fred();
@@ -115,30 +150,38 @@ impl CrawlerService {
     }
 
     /// Fetch FRED series metadata
-    async fn fetch_fred_series_metadata(&self, series_id: &str) -> AppResult<FredSeries> {
-        let api_key = self
-            .fred_api_key
-            .as_ref()
-            .ok_or_else(|| AppError::ExternalApiError("FRED API key not configured".to_string()))?;
-
-        let url = format!(
-            "https://api.stlouisfed.org/fred/series?series_id={}&api_key={}&file_type=json",
-            series_id, api_key
-        );
+    async fn fetch_fred_series_metadata(
+        &self,
+        pool: &DatabasePool,
+        source: &DataSource,
+        series_id: &str,
+    ) -> AppResult<FredSeries> {
+        if self.fred_keys.is_empty() {
+            return Err(AppError::ExternalApiError(
+                "FRED API key not configured".to_string(),
+            ));
+        }
 
-        let response =
-            self.client.get(&url).send().await.map_err(|e| {
-                AppError::ExternalApiError(format!("FRED API request failed: {}", e))
-            })?;
+        let response = self
+            .client
+            .send_with_api_key(pool, source, &self.fred_keys, |api_key| {
+                self.client.get(&format!(
+                    "{}/series?series_id={}&api_key={}&file_type=json",
+                    self.fred_base_url,
+                    series_id,
+                    api_key.expose()
+                ))
+            })
+            .await?;
 
-        if !response.status().is_success() {
+        if !response.status.is_success() {
             return Err(AppError::ExternalApiError(format!(
                 "FRED API returned status: {}",
-                response.status()
+                response.status
             )));
         }
 
-        let fred_response: FredSeriesResponse = response.json().await.map_err(|e| {
+        let fred_response: FredSeriesResponse = response.json().map_err(|e| {
             AppError::ExternalApiError(format!("Failed to parse FRED response: {}", e))
         })?;
 
@@ -148,29 +191,38 @@ impl CrawlerService {
     }
 
     /// Fetch FRED observations
-    async fn fetch_fred_observations(&self, series_id: &str) -> AppResult<Vec<FredObservation>> {
-        let api_key = self
-            .fred_api_key
-            .as_ref()
-            .ok_or_else(|| AppError::ExternalApiError("FRED API key not configured".to_string()))?;
-
-        let url = format!(
-            "https://api.stlouisfed.org/fred/series/observations?series_id={}&api_key={}&file_type=json&realtime_start=1776-07-04&realtime_end=9999-12-31",
-            series_id, api_key
-        );
+    async fn fetch_fred_observations(
+        &self,
+        pool: &DatabasePool,
+        source: &DataSource,
+        series_id: &str,
+    ) -> AppResult<Vec<FredObservation>> {
+        if self.fred_keys.is_empty() {
+            return Err(AppError::ExternalApiError(
+                "FRED API key not configured".to_string(),
+            ));
+        }
 
-        let response = self.client.get(&url).send().await.map_err(|e| {
-            AppError::ExternalApiError(format!("FRED observations request failed: {}", e))
-        })?;
+        let response = self
+            .client
+            .send_with_api_key(pool, source, &self.fred_keys, |api_key| {
+                self.client.get(&format!(
+                    "{}/series/observations?series_id={}&api_key={}&file_type=json&realtime_start=1776-07-04&realtime_end=9999-12-31",
+                    self.fred_base_url,
+                    series_id,
+                    api_key.expose()
+                ))
+            })
+            .await?;
 
-        if !response.status().is_success() {
+        if !response.status.is_success() {
             return Err(AppError::ExternalApiError(format!(
                 "FRED observations API returned status: {}",
-                response.status()
+                response.status
             )));
         }
 
-        let fred_response: FredObservationsResponse = response.json().await.map_err(|e| {
+        let fred_response: FredObservationsResponse = response.json().map_err(|e| {
             AppError::ExternalApiError(format!("Failed to parse FRED observations: {}", e))
         })?;
 
@@ -180,12 +232,12 @@ impl CrawlerService {
     /// Fetch BLS data
     async fn fetch_bls_data(
         &self,
+        pool: &DatabasePool,
+        source: &DataSource,
         series_ids: &[String],
         start_year: i32,
         end_year: i32,
     ) -> AppResult<Vec<BlsSeries>> {
-        let url = "https://api.bls.gov/publicAPI/v2/timeseries/data/";
-
         let request_body = serde_json::json!({
             "seriesid": series_ids,
             "startyear": start_year.to_string(),
@@ -195,20 +247,21 @@ impl CrawlerService {
 
         let response = self
             .client
-            .post(url)
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApiError(format!("BLS API request failed: {}", e)))?;
+            .send_with_failover(pool, source, &self.bls_base_url, |base_url| {
+                self.client
+                    .post(&format!("{}/timeseries/data/", base_url))
+                    .json(&request_body)
+            })
+            .await?;
 
-        if !response.status().is_success() {
+        if !response.status.is_success() {
             return Err(AppError::ExternalApiError(format!(
                 "BLS API returned status: {}",
-                response.status()
+                response.status
             )));
         }
 
-        let bls_response: BlsResponse = response.json().await.map_err(|e| {
+        let bls_response: BlsResponse = response.json().map_err(|e| {
             AppError::ExternalApiError(format!("Failed to parse BLS response: {}", e))
         })?;
 
@@ -340,7 +393,9 @@ impl CrawlerService {
         let data_source = self.get_or_create_fred_source(pool).await?;
 
         // Fetch series metadata
-        let fred_series = self.fetch_fred_series_metadata(series_id).await?;
+        let fred_series = self
+            .fetch_fred_series_metadata(pool, &data_source, series_id)
+            .await?;
 
         // Upsert economic series
         let economic_series = self
@@ -348,7 +403,9 @@ impl CrawlerService {
             .await?;
 
         // Fetch observations
-        let observations = self.fetch_fred_observations(series_id).await?;
+        let observations = self
+            .fetch_fred_observations(pool, &data_source, series_id)
+            .await?;
 
         // Process observations in batches
         let mut data_points = Vec::new();
@@ -392,6 +449,11 @@ impl CrawlerService {
         // Batch insert data points
         if !data_points.is_empty() {
             DataPoint::create_batch(pool, &data_points).await?;
+            shared_data_point_cache().invalidate_series(economic_series.id);
+            shared_response_cache().invalidate_series(economic_series.id);
+            evaluate_alerts_after_update(pool, economic_series.id).await;
+            publish_series_updated(pool, economic_series.id, data_points.len()).await;
+            recompute_derived_after_update(pool, economic_series.id).await;
             println!(
                 "Inserted {} data points for FRED series {}",
                 data_points.len(),
@@ -418,7 +480,13 @@ impl CrawlerService {
         let current_year = Utc::now().year();
         let start_year = current_year - 10;
         let bls_series_list = self
-            .fetch_bls_data(&[series_id.to_string()], start_year, current_year)
+            .fetch_bls_data(
+                pool,
+                &data_source,
+                &[series_id.to_string()],
+                start_year,
+                current_year,
+            )
             .await?;
 
         // Process BLS data
@@ -450,6 +518,11 @@ impl CrawlerService {
             // Batch insert data points
             if !data_points.is_empty() {
                 DataPoint::create_batch(pool, &data_points).await?;
+                shared_data_point_cache().invalidate_series(economic_series.id);
+                shared_response_cache().invalidate_series(economic_series.id);
+                evaluate_alerts_after_update(pool, economic_series.id).await;
+                publish_series_updated(pool, economic_series.id, data_points.len()).await;
+                recompute_derived_after_update(pool, economic_series.id).await;
                 println!(
                     "Inserted {} data points for BLS series {}",
                     data_points.len(),
@@ -510,7 +583,7 @@ impl CrawlerService {
             scheduled_for: None,
         };
 
-        CrawlQueueItem::create(pool, &queue_item).await?;
+        enqueue_item(pool, &queue_item).await?;
         println!("Scheduled FRED crawl for series: {}", series_id);
         Ok(())
     }
@@ -530,14 +603,26 @@ impl CrawlerService {
             scheduled_for: None,
         };
 
-        CrawlQueueItem::create(pool, &queue_item).await?;
+        enqueue_item(pool, &queue_item).await?;
         println!("Scheduled BLS crawl for series: {}", series_id);
         Ok(())
     }
 
     /// Process queue items (worker function)
+    ///
+    /// Returns once the process starts draining for shutdown; the item being
+    /// crawled at that point is finished first.
     pub async fn process_queue(&self, pool: &DatabasePool, worker_id: &str) -> AppResult<()> {
+        let coordinator = shutdown_coordinator();
         loop {
+            let Some(in_flight) = coordinator.try_track() else {
+                println!(
+                    "Worker {} stopped claiming queue items for shutdown",
+                    worker_id
+                );
+                return Ok(());
+            };
+
             // Get next item from queue using SKIP LOCKED
             if let Some(item) = CrawlQueueItem::get_next_for_processing(pool, worker_id).await? {
                 println!(
@@ -545,29 +630,61 @@ impl CrawlerService {
                     item.id, item.series_id
                 );
 
-                let result = match item.source.as_str() {
-                    "FRED" => self.crawl_fred_series(pool, &item.series_id).await,
-                    "BLS" => self.crawl_bls_series(pool, &item.series_id).await,
-                    _ => Err(AppError::ExternalApiError(format!(
-                        "Unknown source: {}",
-                        item.source
-                    ))),
-                };
+                // Logs of the crawl carry the queue item's source and series
+                let item_span = tracing::info_span!(
+                    "crawler.queue_item",
+                    queue_item_id = %item.id,
+                    source = %item.source,
+                    series_id = %item.series_id,
+                    worker_id,
+                );
+                let result = async {
+                    match item.source.as_str() {
+                        "FRED" => self.crawl_fred_series(pool, &item.series_id).await,
+                        "BLS" => self.crawl_bls_series(pool, &item.series_id).await,
+                        _ => Err(AppError::ExternalApiError(format!(
+                            "Unknown source: {}",
+                            item.source
+                        ))),
+                    }
+                }
+                .instrument(item_span)
+                .await;
 
                 match result {
                     Ok(_) => {
                         CrawlQueueItem::mark_completed(pool, item.id).await?;
                         println!("Successfully completed queue item: {}", item.id);
                     }
+                    Err(AppError::QuotaExceeded(reason)) => {
+                        // Not a failure: the source's work waits for the quota to reset
+                        let deferred = defer_source_items(
+                            pool,
+                            &item.source,
+                            item.id,
+                            next_quota_reset(),
+                            &reason,
+                        )
+                        .await?;
+                        println!(
+                            "Deferred {} {} queue items: {}",
+                            deferred, item.source, reason
+                        );
+                    }
                     Err(e) => {
                         let error_msg = format!("Crawl failed: {}", e);
+                        publish_crawl_failed(pool, &item.source, &item.series_id, &error_msg).await;
                         CrawlQueueItem::mark_failed(pool, item.id, error_msg).await?;
                         println!("Failed queue item {}: {}", item.id, e);
                     }
                 }
             } else {
-                // No items available, wait a bit
-                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                // No items available, wait a bit (or until draining starts)
+                drop(in_flight);
+                tokio::select! {
+                    _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)) => {}
+                    _ = coordinator.draining() => {}
+                }
             }
         }
     }
@@ -4,19 +4,37 @@
 //! 1. Downloading data source catalogs into the database
 //! 2. Downloading specific series or random series into the database
 
+pub mod api_key_ring;
 pub mod catalog_downloader;
 pub mod cli;
 pub mod comprehensive_crawler;
 pub mod comprehensive_crawler_scheduler;
+pub mod crawl_plan;
 pub mod crawler_service;
+pub mod endpoint_failover;
 pub mod enhanced_crawler_scheduler;
 pub mod enhanced_crawler_service;
+pub mod ingestion_pipeline;
 pub mod legacy_crawler_service;
+pub mod quota_client;
+pub mod release_scheduler;
 pub mod series_downloader;
 pub mod simple_crawler_service;
+pub mod stream_ingestion;
 
 #[cfg(test)]
 mod tests;
 
+pub use api_key_ring::{ApiKey, ApiKeyRing};
 pub use catalog_downloader::CatalogDownloader;
+pub use crawl_plan::{CrawlPlan, CrawlPlanner, PlannedCrawl};
+pub use endpoint_failover::{CircuitState, EndpointHealth, EndpointRing, FailoverPolicy};
+pub use ingestion_pipeline::{
+    CrawlOrigin, IngestionConfig, IngestionPipeline, IngestionReport, RawObservation,
+};
+pub use quota_client::{MeteredResponse, QuotaClient};
+pub use release_scheduler::{
+    learn_release_calendars, queue_upcoming_releases, CalendarLearningReport, ReleaseQueueReport,
+};
 pub use series_downloader::SeriesDownloader;
+pub use stream_ingestion::{StreamFormat, StreamIngestionReport, StreamIngestor};
@@ -0,0 +1,330 @@
+//! HTTP client that enforces per data source bandwidth and request quotas
+//!
+//! Data sources may set a daily byte quota and a daily request quota
+//! (`data_sources.daily_byte_quota` / `daily_request_quota`). Every request
+//! sent through [`QuotaClient`] is counted in `data_source_usage` for the
+//! current UTC day, so all crawler instances share one budget. Once either
+//! quota is used up, further requests fail with [`AppError::QuotaExceeded`]
+//! until midnight UTC; queue workers respond by deferring the source's
+//! remaining work (see [`crate::services::queue_service::defer_source_items`]).
+//!
+//! The check happens before a request is sent, so concurrent workers can
+//! overshoot a quota by the requests they had in flight.
+//!
+//! Requests that need an API key can go through
+//! [`QuotaClient::send_with_api_key`], which rotates between the source's
+//! keys when one is throttled and also counts usage per key in
+//! `data_source_key_usage`. Requests to a source with mirrors can go through
+//! [`QuotaClient::send_with_failover`], which moves to a mirror when an
+//! endpoint fails (see [`crate::services::crawler::endpoint_failover`]).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::services::crawler::api_key_ring::{ApiKey, ApiKeyRing, DEFAULT_THROTTLE_COOLDOWN};
+use crate::services::crawler::endpoint_failover::EndpointHealth;
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{
+    DataSource, DataSourceKeyUsage, DataSourceQuotaStatus, DataSourceUsage,
+};
+use econ_graph_core::secrets::SecretString;
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+
+/// Value of the `crawler_type` metric label for requests sent through [`QuotaClient`]
+const CRAWLER_TYPE: &str = "queue";
+
+/// Response of a request sent through [`QuotaClient`], with the body already read
+#[derive(Debug, Clone)]
+pub struct MeteredResponse {
+    pub status: StatusCode,
+    pub body: Vec<u8>,
+    /// Wait requested by a Retry-After header given in seconds
+    pub retry_after: Option<Duration>,
+}
+
+impl MeteredResponse {
+    /// Parse the body as JSON
+    pub fn json<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// Crawler HTTP client that charges requests against data source quotas
+///
+/// Clones share the circuit breakers of data source endpoints.
+#[derive(Debug, Clone, Default)]
+pub struct QuotaClient {
+    client: Client,
+    endpoints: Arc<EndpointHealth>,
+}
+
+impl QuotaClient {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            endpoints: Arc::default(),
+        }
+    }
+
+    /// Start a GET request; send it with [`QuotaClient::send`]
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    /// Start a POST request; send it with [`QuotaClient::send`]
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Send a request on behalf of `source` and read the whole body
+    ///
+    /// Fails with [`AppError::QuotaExceeded`] without sending anything when
+    /// the source has used up a daily quota. Requests that fail in transit
+    /// still count against the request quota.
+    pub async fn send(
+        &self,
+        pool: &DatabasePool,
+        source: &DataSource,
+        request: RequestBuilder,
+    ) -> AppResult<MeteredResponse> {
+        let status = quota_status(pool, source).await?;
+        if status.is_exhausted() {
+            publish_remaining(source, &status);
+            return Err(quota_exceeded(source, &status));
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                record_usage(pool, source, 0).await?;
+                return Err(AppError::ExternalApiError(format!(
+                    "{} request failed: {}",
+                    source.name, e
+                )));
+            }
+        };
+
+        let http_status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+        let body = response.bytes().await.map(|body| body.to_vec());
+        let bytes = body.as_ref().map_or(0, Vec::len);
+        record_usage(pool, source, bytes as i64).await?;
+
+        let body = body.map_err(|e| {
+            AppError::ExternalApiError(format!("Failed to read {} response: {}", source.name, e))
+        })?;
+
+        Ok(MeteredResponse {
+            status: http_status,
+            body,
+            retry_after,
+        })
+    }
+
+    /// Send a request that needs one of `source`'s API keys
+    ///
+    /// `build` creates the request for a key. When the response is HTTP 429
+    /// the key rests for the Retry-After time, or
+    /// [`DEFAULT_THROTTLE_COOLDOWN`], and the request is repeated with the
+    /// next key. If every key is throttled the last 429 response is returned;
+    /// if every key is still resting from earlier requests, nothing is sent
+    /// and the call fails with [`AppError::RateLimitExceeded`].
+    pub async fn send_with_api_key(
+        &self,
+        pool: &DatabasePool,
+        source: &DataSource,
+        keys: &ApiKeyRing,
+        build: impl Fn(&SecretString) -> RequestBuilder,
+    ) -> AppResult<MeteredResponse> {
+        if keys.is_empty() {
+            return Err(AppError::ConfigError(format!(
+                "No API key configured for {}",
+                source.name
+            )));
+        }
+
+        let mut throttled = None;
+        for _ in 0..keys.len() {
+            let Some(key) = keys.acquire(Instant::now()) else {
+                break;
+            };
+
+            let response = self.send(pool, source, build(&key.secret)).await?;
+            record_key_usage(pool, source, &key, &response).await?;
+            if response.status != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            CRAWLER_METRICS.record_rate_limit_hit(CRAWLER_TYPE, &source.name);
+            let cooldown = response.retry_after.unwrap_or(DEFAULT_THROTTLE_COOLDOWN);
+            keys.mark_throttled(&key.hash, Instant::now() + cooldown);
+            throttled = Some(response);
+        }
+
+        throttled.ok_or(AppError::RateLimitExceeded)
+    }
+
+    /// Send a request to the first healthy endpoint of `source`
+    ///
+    /// `build` creates the request for an endpoint's base URL. The endpoints
+    /// are `primary`, usually the source's `base_url`, followed by its
+    /// `fallback_urls`, skipping those whose circuit is open. When a request
+    /// fails in transit or the response is HTTP 5xx, it is repeated on the
+    /// next endpoint and the last failure is returned if none succeeds. Other
+    /// errors, such as [`AppError::QuotaExceeded`], are returned at once.
+    pub async fn send_with_failover(
+        &self,
+        pool: &DatabasePool,
+        source: &DataSource,
+        primary: &str,
+        build: impl Fn(&str) -> RequestBuilder,
+    ) -> AppResult<MeteredResponse> {
+        let ring = self.endpoints.ring(source, primary);
+
+        let mut last = Err(AppError::ExternalApiError(format!(
+            "Every endpoint of {} is failing; retry once their circuits half-open",
+            source.name
+        )));
+        for endpoint in ring.candidates(Instant::now()) {
+            let result = self.send(pool, source, build(&endpoint)).await;
+            let failed = match &result {
+                Ok(response) => response.status.is_server_error(),
+                Err(error) => matches!(error, AppError::ExternalApiError(_)),
+            };
+            if result.is_err() && !failed {
+                return result;
+            }
+
+            let (state, outcome) = if failed {
+                (ring.record_failure(&endpoint, Instant::now()), "failure")
+            } else {
+                (ring.record_success(&endpoint), "success")
+            };
+            CRAWLER_METRICS.record_endpoint_request(&source.name, &endpoint, outcome);
+            CRAWLER_METRICS.set_endpoint_circuit_state(
+                &source.name,
+                &endpoint,
+                state.metric_value(),
+            );
+
+            if !failed {
+                return result;
+            }
+            last = result;
+        }
+
+        last
+    }
+}
+
+/// Today's quotas and usage of a data source
+pub async fn quota_status(
+    pool: &DatabasePool,
+    source: &DataSource,
+) -> AppResult<DataSourceQuotaStatus> {
+    let today = quota_date();
+    let usage = DataSourceUsage::find(pool, source.id, today).await?;
+
+    Ok(DataSourceQuotaStatus::new(source, today, usage.as_ref()))
+}
+
+/// Today's quotas and usage of every data source
+///
+/// Also refreshes the remaining quota gauges, so the admin API and the
+/// metrics agree.
+pub async fn quota_statuses(
+    pool: &DatabasePool,
+) -> AppResult<Vec<(DataSource, DataSourceQuotaStatus)>> {
+    let today = quota_date();
+    let usage = DataSourceUsage::for_date(pool, today).await?;
+
+    Ok(DataSource::find_all(pool)
+        .await?
+        .into_iter()
+        .map(|source| {
+            let source_usage = usage.iter().find(|usage| usage.data_source_id == source.id);
+            let status = DataSourceQuotaStatus::new(&source, today, source_usage);
+            publish_remaining(&source, &status);
+            (source, status)
+        })
+        .collect())
+}
+
+/// Count one request and `bytes` downloaded against today's usage of `source`
+async fn record_usage(
+    pool: &DatabasePool,
+    source: &DataSource,
+    bytes: i64,
+) -> AppResult<DataSourceQuotaStatus> {
+    let today = quota_date();
+    let usage = DataSourceUsage::record(pool, source.id, today, bytes, 1).await?;
+    let status = DataSourceQuotaStatus::new(source, today, Some(&usage));
+
+    CRAWLER_METRICS.record_bytes_downloaded(CRAWLER_TYPE, &source.name, bytes as u64);
+    publish_remaining(source, &status);
+
+    Ok(status)
+}
+
+/// Count a response to a request sent with `key` against the key's usage today
+async fn record_key_usage(
+    pool: &DatabasePool,
+    source: &DataSource,
+    key: &ApiKey,
+    response: &MeteredResponse,
+) -> AppResult<DataSourceKeyUsage> {
+    CRAWLER_METRICS.record_api_key_request(&source.name, &key.hash, response.status.as_str());
+
+    DataSourceKeyUsage::record(
+        pool,
+        source.id,
+        &key.hash,
+        quota_date(),
+        response.body.len() as i64,
+        response.status == StatusCode::TOO_MANY_REQUESTS,
+    )
+    .await
+}
+
+/// Export the remaining quotas of a source; unlimited quotas are not exported
+fn publish_remaining(source: &DataSource, status: &DataSourceQuotaStatus) {
+    if let Some(remaining) = status.remaining_bytes() {
+        CRAWLER_METRICS.set_quota_remaining(&source.name, "bytes", remaining);
+    }
+    if let Some(remaining) = status.remaining_requests() {
+        CRAWLER_METRICS.set_quota_remaining(&source.name, "requests", i64::from(remaining));
+    }
+}
+
+fn quota_exceeded(source: &DataSource, status: &DataSourceQuotaStatus) -> AppError {
+    AppError::QuotaExceeded(format!(
+        "Daily quota of {} is used up until {}",
+        source.name,
+        status.resets_at().to_rfc3339()
+    ))
+}
+
+/// When quotas used up today become available again
+pub fn next_quota_reset() -> DateTime<Utc> {
+    DataSourceQuotaStatus::reset_time(quota_date())
+}
+
+/// Today's usage of every API key, by source and key hash
+pub async fn key_usage(pool: &DatabasePool) -> AppResult<Vec<DataSourceKeyUsage>> {
+    DataSourceKeyUsage::for_date(pool, quota_date()).await
+}
+
+/// Quotas are counted per UTC day
+fn quota_date() -> NaiveDate {
+    Utc::now().date_naive()
+}
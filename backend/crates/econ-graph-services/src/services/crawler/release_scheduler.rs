@@ -0,0 +1,414 @@
+//! Crawls scheduled from release calendars
+//!
+//! [`queue_upcoming_releases`] puts a high priority crawl of every series a
+//! calendar covers on the crawl queue, scheduled `crawl_delay_minutes` after
+//! the expected release, so new CPI or payroll figures are picked up within
+//! minutes instead of at the next periodic crawl. [`learn_release_calendars`]
+//! infers calendars for series without one from when their observations first
+//! arrived.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday};
+use diesel::prelude::*;
+use diesel::sql_types::{Text, Timestamp, Uuid as SqlUuid, Varchar};
+use diesel_async::RunQueryDsl;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{
+    LearnedRelease, NewCrawlQueueItem, QueuePriority, ReleaseCalendar, ReleaseFrequency,
+    ReleaseRule, DEFAULT_RELEASE_TIME_ZONE,
+};
+use econ_graph_core::schema::{data_sources, economic_series, release_calendars};
+
+use crate::services::queue_service::enqueue_item;
+
+/// Seconds between runs of the release scheduler
+pub const DEFAULT_RELEASE_SCHEDULER_INTERVAL_SECONDS: u64 = 300;
+
+/// How far ahead releases are queued
+pub const DEFAULT_RELEASE_QUEUE_HORIZON_MINUTES: i64 = 60;
+
+/// Seconds between calendar learning runs
+pub const DEFAULT_RELEASE_CALENDAR_LEARNING_INTERVAL_SECONDS: u64 = 86_400;
+
+/// Releases needed before a calendar is learned
+const MIN_LEARNING_SAMPLES: usize = 4;
+
+/// Share of releases a learned calendar must fit
+const MIN_LEARNING_CONFIDENCE: f64 = 0.6;
+
+/// Days on which more observations arrived were backfills, not releases
+const MAX_OBSERVATIONS_PER_RELEASE: usize = 3;
+
+/// Days a monthly release may drift from its usual day of the month
+const DAY_OF_MONTH_TOLERANCE: u32 = 2;
+
+/// Crawls queued by one scheduler run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReleaseQueueReport {
+    pub releases: usize,
+    pub queued: usize,
+    /// Crawls already on the queue for the release
+    pub skipped: usize,
+}
+
+/// Calendars stored by one learning run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CalendarLearningReport {
+    pub series_examined: usize,
+    pub learned: usize,
+}
+
+/// Crawl queue name of a data source, e.g. `FRED` for
+/// "Federal Reserve Economic Data (FRED)"
+pub fn queue_source_name(data_source_name: &str) -> String {
+    data_source_name
+        .rsplit_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(abbreviation, _)| abbreviation.trim())
+        .filter(|abbreviation| !abbreviation.is_empty())
+        .unwrap_or(data_source_name.trim())
+        .to_uppercase()
+}
+
+/// Series a calendar releases, as crawl queue source and external id
+async fn release_targets(
+    pool: &DatabasePool,
+    calendar: &ReleaseCalendar,
+) -> AppResult<Vec<(String, String)>> {
+    let mut conn = pool
+        .get()
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    let mut query = economic_series::table
+        .inner_join(data_sources::table)
+        .filter(economic_series::is_active.eq(true))
+        .select((data_sources::name, economic_series::external_id))
+        .into_boxed();
+    query = match (calendar.series_id, calendar.source_id) {
+        (Some(series_id), _) => query.filter(economic_series::id.eq(series_id)),
+        (None, Some(source_id)) => {
+            // Series with a calendar of their own follow that one
+            let own_calendars = release_calendars::table
+                .filter(release_calendars::series_id.is_not_null())
+                .select(release_calendars::series_id.assume_not_null())
+                .load::<Uuid>(&mut conn)
+                .await?;
+            query
+                .filter(economic_series::source_id.eq(source_id))
+                .filter(economic_series::frequency.ilike(format!("{}%", calendar.frequency)))
+                .filter(economic_series::id.ne_all(own_calendars))
+        }
+        (None, None) => return Ok(Vec::new()),
+    };
+
+    let targets = query
+        .load::<(String, String)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(source, external_id)| (queue_source_name(&source), external_id))
+        .collect();
+
+    Ok(targets)
+}
+
+/// Queue crawls of the releases expected within `horizon`
+pub async fn queue_upcoming_releases(
+    pool: &DatabasePool,
+    horizon: Duration,
+) -> AppResult<ReleaseQueueReport> {
+    let mut report = ReleaseQueueReport::default();
+
+    for calendar in ReleaseCalendar::due(pool, Utc::now() + horizon).await? {
+        let Some(release_at) = calendar.next_release_at else {
+            continue;
+        };
+        let scheduled_for = calendar.crawl_at(release_at);
+
+        for (source, series_id) in release_targets(pool, &calendar).await? {
+            let item = NewCrawlQueueItem {
+                source,
+                series_id,
+                priority: QueuePriority::High as i32,
+                max_retries: 3,
+                scheduled_for: Some(scheduled_for),
+            };
+            // A crawl already waiting for the release absorbs this one
+            if enqueue_item(pool, &item).await?.coalesced {
+                report.skipped += 1;
+            } else {
+                report.queued += 1;
+            }
+        }
+
+        calendar.advance(pool).await?;
+        report.releases += 1;
+    }
+
+    Ok(report)
+}
+
+#[derive(QueryableByName)]
+struct Arrival {
+    #[diesel(sql_type = SqlUuid)]
+    series_id: Uuid,
+    #[diesel(sql_type = Varchar)]
+    frequency: String,
+    /// When the observation was first stored, in the series' calendar time zone
+    #[diesel(sql_type = Timestamp)]
+    first_seen: NaiveDateTime,
+}
+
+/// Learn calendars of active series from when their observations arrived
+///
+/// Series with a calendar an admin entered, of their own or of their source,
+/// are left alone; learned calendars are replaced by what the latest two years
+/// of arrivals show.
+pub async fn learn_release_calendars(pool: &DatabasePool) -> AppResult<CalendarLearningReport> {
+    let arrivals = {
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::sql_query(
+            "SELECT dp.series_id, s.frequency, \
+                    MIN(dp.created_at) AT TIME ZONE COALESCE(rc.time_zone, $1) AS first_seen \
+             FROM data_points dp \
+             JOIN economic_series s ON s.id = dp.series_id \
+             LEFT JOIN release_calendars rc ON rc.series_id = s.id \
+             WHERE dp.is_original_release \
+               AND s.is_active \
+               AND dp.created_at >= NOW() - INTERVAL '2 years' \
+               AND (rc.id IS NULL OR rc.is_learned) \
+               AND NOT EXISTS ( \
+                   SELECT 1 FROM release_calendars sc \
+                   WHERE sc.source_id = s.source_id AND s.frequency ILIKE sc.frequency || '%') \
+             GROUP BY dp.series_id, s.frequency, rc.time_zone, dp.date \
+             ORDER BY dp.series_id",
+        )
+        .bind::<Text, _>(DEFAULT_RELEASE_TIME_ZONE)
+        .load::<Arrival>(&mut conn)
+        .await?
+    };
+
+    let mut by_series: HashMap<Uuid, (String, Vec<NaiveDateTime>)> = HashMap::new();
+    for arrival in arrivals {
+        by_series
+            .entry(arrival.series_id)
+            .or_insert_with(|| (arrival.frequency, Vec::new()))
+            .1
+            .push(arrival.first_seen);
+    }
+
+    let mut report = CalendarLearningReport::default();
+    for (series_id, (frequency, first_seen)) in by_series {
+        report.series_examined += 1;
+        let Some(frequency) = ReleaseFrequency::from_series_frequency(&frequency) else {
+            continue;
+        };
+        let Some(learned) = learn_rule(frequency, &first_seen) else {
+            continue;
+        };
+        if ReleaseCalendar::record_learned(pool, series_id, &learned)
+            .await?
+            .is_some()
+        {
+            report.learned += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Most common value and how often it occurs
+fn mode<T: Ord + Copy>(values: impl Iterator<Item = T>) -> Option<(T, usize)> {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts.into_iter().max_by_key(|(_, count)| *count)
+}
+
+/// Infer the schedule of a series from the local times its observations first arrived
+///
+/// Arrivals are grouped by day; days with many observations are backfills and
+/// ignored. The earliest arrival gives the release time, since data cannot
+/// arrive before it is released.
+pub fn learn_rule(
+    frequency: ReleaseFrequency,
+    first_seen: &[NaiveDateTime],
+) -> Option<LearnedRelease> {
+    let mut days: BTreeMap<NaiveDate, (usize, NaiveTime)> = BTreeMap::new();
+    for arrival in first_seen {
+        let day = days.entry(arrival.date()).or_insert((0, arrival.time()));
+        day.0 += 1;
+        day.1 = day.1.min(arrival.time());
+    }
+    let releases: Vec<(NaiveDate, NaiveTime)> = days
+        .into_iter()
+        .filter(|(_, (count, _))| *count <= MAX_OBSERVATIONS_PER_RELEASE)
+        .map(|(date, (_, time))| (date, time))
+        .collect();
+    if releases.len() < MIN_LEARNING_SAMPLES {
+        return None;
+    }
+
+    let earliest = releases.iter().map(|(_, time)| *time).min()?;
+    let release_time = NaiveTime::from_hms_opt(earliest.hour(), earliest.minute(), 0)?;
+    let dates: Vec<NaiveDate> = releases.iter().map(|(date, _)| *date).collect();
+    let total = dates.len();
+    let rule = ReleaseRule {
+        frequency,
+        day_of_week: None,
+        week_of_month: None,
+        day_of_month: None,
+        month_of_period: 1,
+        release_time,
+    };
+
+    let (rule, fitting) = match frequency {
+        ReleaseFrequency::Daily => {
+            let weekdays = dates
+                .iter()
+                .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+                .count();
+            (rule, weekdays)
+        }
+        ReleaseFrequency::Weekly => {
+            let (weekday, count) = mode(
+                dates
+                    .iter()
+                    .map(|date| date.weekday().num_days_from_monday() as u8),
+            )?;
+            let rule = ReleaseRule {
+                day_of_week: Weekday::try_from(weekday).ok(),
+                ..rule
+            };
+            (rule, count)
+        }
+        ReleaseFrequency::Monthly | ReleaseFrequency::Quarterly | ReleaseFrequency::Annual => {
+            let month_of_period = match frequency {
+                ReleaseFrequency::Quarterly => {
+                    mode(dates.iter().map(|date| date.month0() % 3 + 1))?.0
+                }
+                ReleaseFrequency::Annual => mode(dates.iter().map(|date| date.month()))?.0,
+                _ => 1,
+            };
+
+            let mut days_of_month: Vec<u32> = dates.iter().map(|date| date.day()).collect();
+            days_of_month.sort_unstable();
+            let median = days_of_month[days_of_month.len() / 2];
+            let near_median = days_of_month
+                .iter()
+                .filter(|day| day.abs_diff(median) <= DAY_OF_MONTH_TOLERANCE)
+                .count();
+
+            let ((weekday, week), nth_weekday) = mode(dates.iter().map(|date| {
+                (
+                    date.weekday().num_days_from_monday() as u8,
+                    (date.day() - 1) / 7 + 1,
+                )
+            }))?;
+
+            let rule = ReleaseRule {
+                month_of_period,
+                ..rule
+            };
+            if nth_weekday > near_median {
+                let rule = ReleaseRule {
+                    day_of_week: Weekday::try_from(weekday).ok(),
+                    week_of_month: Some(week),
+                    ..rule
+                };
+                (rule, nth_weekday)
+            } else {
+                let rule = ReleaseRule {
+                    day_of_month: Some(median),
+                    ..rule
+                };
+                (rule, near_median)
+            }
+        }
+    };
+
+    let confidence = fitting as f64 / total as f64;
+    (confidence >= MIN_LEARNING_CONFIDENCE && rule.check().is_ok()).then_some(LearnedRelease {
+        rule,
+        confidence,
+        sample_count: total as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrival(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_calendars_are_learned_from_arrivals() {
+        // REQUIREMENT: Release calendars are learned from historical data arrival patterns
+        // PURPOSE: Verify the release day and time are inferred from first arrivals, backfills are ignored, and irregular series get no calendar
+        // This ensures learned calendars schedule crawls at real release times rather than at noise
+
+        // The employment report: first Friday of the month, crawled shortly after 8:30
+        let mut payrolls = vec![
+            arrival("2024-01-05", "08:41"),
+            arrival("2024-02-02", "08:36"),
+            arrival("2024-06-07", "08:52"),
+            arrival("2024-04-05", "08:38"),
+            arrival("2024-05-03", "08:44"),
+        ];
+        // A backfill of history on one day is not a release
+        payrolls.extend((0..24).map(|_| arrival("2024-02-20", "03:12")));
+
+        let learned = learn_rule(ReleaseFrequency::Monthly, &payrolls).unwrap();
+        assert_eq!(learned.rule.day_of_week, Some(Weekday::Fri));
+        assert_eq!(learned.rule.week_of_month, Some(1));
+        assert_eq!(learned.rule.day_of_month, None);
+        assert_eq!(
+            learned.rule.release_time,
+            NaiveTime::from_hms_opt(8, 36, 0).unwrap()
+        );
+        assert_eq!(learned.sample_count, 5);
+        assert!((learned.confidence - 1.0).abs() < f64::EPSILON);
+
+        // CPI: around the 12th
+        let cpi = [
+            arrival("2024-01-11", "08:35"),
+            arrival("2024-02-13", "08:33"),
+            arrival("2024-03-12", "08:31"),
+            arrival("2024-04-10", "08:34"),
+            arrival("2024-05-15", "08:32"),
+        ];
+        let learned = learn_rule(ReleaseFrequency::Monthly, &cpi).unwrap();
+        assert_eq!(learned.rule.day_of_month, Some(12));
+        assert_eq!(
+            learned.rule.release_time,
+            NaiveTime::from_hms_opt(8, 31, 0).unwrap()
+        );
+
+        // Too few releases, or no pattern
+        assert!(learn_rule(ReleaseFrequency::Monthly, &cpi[..3]).is_none());
+        let irregular = [
+            arrival("2024-01-02", "10:00"),
+            arrival("2024-02-27", "10:00"),
+            arrival("2024-03-16", "10:00"),
+            arrival("2024-04-09", "10:00"),
+            arrival("2024-05-22", "10:00"),
+        ];
+        assert!(learn_rule(ReleaseFrequency::Monthly, &irregular).is_none());
+
+        assert_eq!(
+            queue_source_name("Federal Reserve Economic Data (FRED)"),
+            "FRED"
+        );
+        assert_eq!(queue_source_name("bls"), "BLS");
+    }
+}
@@ -10,10 +10,17 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use tracing::{error, info, warn};
 
+use crate::services::data_point_cache::shared_data_point_cache;
+use crate::services::derived_series_service::recompute_derived_after_update;
+use crate::services::response_cache::shared_response_cache;
+use crate::services::series_alert_service::evaluate_alerts_after_update;
+use crate::services::webhook_service::publish_series_updated;
+
 use econ_graph_core::{
     database::DatabasePool,
     error::{AppError, AppResult},
     models::{DataPoint, DataSource, EconomicSeries, NewDataPoint, NewEconomicSeries},
+    rate_limiter::{shared_rate_limiter, BLS_HOST, FRED_HOST},
 };
 
 /// Crawler status information
@@ -109,6 +116,11 @@ struct FredObservation {
 }
 
 /// Crawl a specific FRED series
+#[tracing::instrument(
+    name = "crawler.fred_series",
+    skip(pool),
+    fields(otel.kind = "client", source = "FRED")
+)]
 pub async fn crawl_fred_series(pool: &DatabasePool, series_id: &str) -> AppResult<()> {
     // REQUIREMENT: Crawl Federal Reserve economic time series data
     // PURPOSE: Fetch and store FRED series data with revision tracking
@@ -127,6 +139,9 @@ pub async fn crawl_fred_series(pool: &DatabasePool, series_id: &str) -> AppResul
         series_id, api_key
     );
 
+    shared_rate_limiter()
+        .acquire(FRED_HOST, Some(api_key.as_str()))
+        .await;
     let series_response = client
         .get(&series_url)
         .send()
@@ -178,6 +193,9 @@ pub async fn crawl_fred_series(pool: &DatabasePool, series_id: &str) -> AppResul
         series_id, api_key
     );
 
+    shared_rate_limiter()
+        .acquire(FRED_HOST, Some(api_key.as_str()))
+        .await;
     let obs_response = client.get(&observations_url).send().await.map_err(|e| {
         AppError::ExternalApiError(format!("FRED observations request failed: {}", e))
     })?;
@@ -291,6 +309,13 @@ pub async fn crawl_fred_series(pool: &DatabasePool, series_id: &str) -> AppResul
             }
         }
     }
+    shared_data_point_cache().invalidate_series(economic_series.id);
+    shared_response_cache().invalidate_series(economic_series.id);
+    evaluate_alerts_after_update(pool, economic_series.id).await;
+    if processed_count > 0 {
+        publish_series_updated(pool, economic_series.id, processed_count).await;
+        recompute_derived_after_update(pool, economic_series.id).await;
+    }
 
     // Update series metadata with date range
     if let (Some(start_date), Some(end_date)) = (min_date, max_date) {
@@ -351,6 +376,11 @@ struct BlsFootnote {
 }
 
 /// Crawl a specific BLS series
+#[tracing::instrument(
+    name = "crawler.bls_series",
+    skip(pool),
+    fields(otel.kind = "client", source = "BLS")
+)]
 pub async fn crawl_bls_series(pool: &DatabasePool, series_id: &str) -> AppResult<()> {
     // REQUIREMENT: Crawl Bureau of Labor Statistics economic time series data
     // PURPOSE: Fetch and store BLS series data with proper date handling
@@ -372,6 +402,12 @@ pub async fn crawl_bls_series(pool: &DatabasePool, series_id: &str) -> AppResult
 
     let bls_url = "https://api.bls.gov/publicAPI/v2/timeseries/data/";
 
+    shared_rate_limiter()
+        .acquire(
+            BLS_HOST,
+            Some(api_key.as_str()).filter(|key| !key.is_empty()),
+        )
+        .await;
     let response = client
         .post(bls_url)
         .json(&request_data)
@@ -515,6 +551,13 @@ pub async fn crawl_bls_series(pool: &DatabasePool, series_id: &str) -> AppResult
             }
         }
     }
+    shared_data_point_cache().invalidate_series(economic_series.id);
+    shared_response_cache().invalidate_series(economic_series.id);
+    evaluate_alerts_after_update(pool, economic_series.id).await;
+    if processed_count > 0 {
+        publish_series_updated(pool, economic_series.id, processed_count).await;
+        recompute_derived_after_update(pool, economic_series.id).await;
+    }
 
     // Update series metadata with date range
     if let (Some(start_date), Some(end_date)) = (min_date, max_date) {
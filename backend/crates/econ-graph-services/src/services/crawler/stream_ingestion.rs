@@ -0,0 +1,600 @@
+//! Streaming ingestion of observations pushed by external crawlers
+//!
+//! Crawlers that run outside this process (and outside Rust) push data points
+//! as a stream of records, either newline-delimited JSON:
+//!
+//! ```text
+//! {"series_id":"0191...","date":"2024-01-01","value":1.5}
+//! {"series_id":"0191...","date":"2024-02-01","value":"1.7","unit":"Percent"}
+//! ```
+//!
+//! or an Arrow IPC stream whose batches have `series_id` and `date` columns, a
+//! `value` column and an optional `unit` column.
+//!
+//! Records are decoded as the body arrives and collected into batches of
+//! [`StreamIngestionConfig::batch_size`]. Each batch is validated through the
+//! [`IngestionPipeline`] and committed before more of the body is read, so a slow
+//! database slows the sender down instead of buffering the whole upload, and
+//! an interrupted stream keeps every batch that was already committed.
+
+use arrow::array::Array;
+use arrow::buffer::Buffer;
+use arrow::ipc::reader::StreamDecoder;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use futures::{Stream, StreamExt};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::ingestion_pipeline::{IngestionConfig, IngestionPipeline, RawObservation};
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::schema::economic_series;
+
+/// Wire format of an ingestion stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamFormat {
+    Ndjson,
+    ArrowIpc,
+}
+
+impl StreamFormat {
+    /// Format for a request `Content-Type`, ignoring parameters such as `charset`
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/x-ndjson" | "application/ndjson" | "application/jsonl" => {
+                Some(Self::Ndjson)
+            }
+            "application/vnd.apache.arrow.stream" => Some(Self::ArrowIpc),
+            _ => None,
+        }
+    }
+}
+
+/// One observation as sent by an external crawler
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StreamRecord {
+    pub series_id: Uuid,
+    /// Observation date in `YYYY-MM-DD` format
+    pub date: String,
+    /// Value as a JSON number or string; null or missing means no value
+    #[serde(default, deserialize_with = "number_or_string")]
+    pub value: Option<String>,
+    /// Unit the value is reported in, when it differs from the series unit
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+fn number_or_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(value)) => Ok(Some(value)),
+        Some(serde_json::Value::Number(value)) => Ok(Some(value.to_string())),
+        Some(other) => Err(D::Error::custom(format!(
+            "value must be a number or string, got {}",
+            other
+        ))),
+    }
+}
+
+/// Streaming ingestion settings
+#[derive(Debug, Clone)]
+pub struct StreamIngestionConfig {
+    /// Records validated and committed together
+    pub batch_size: usize,
+    /// Longest NDJSON line accepted, in bytes
+    pub max_record_bytes: usize,
+    /// Record errors listed in the report before the list is truncated
+    pub max_reported_errors: usize,
+}
+
+impl Default for StreamIngestionConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 5_000,
+            max_record_bytes: 64 * 1024,
+            max_reported_errors: 100,
+        }
+    }
+}
+
+/// Record that could not be decoded or was rejected by validation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordError {
+    /// 1-based position of the record in the stream
+    pub record: usize,
+    pub reason: String,
+}
+
+/// Outcome of one committed batch
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BatchSummary {
+    pub batch: usize,
+    pub records: usize,
+    pub series: usize,
+    pub stored: usize,
+    pub duplicates: usize,
+    pub missing: usize,
+    pub rejected: usize,
+    pub outliers: usize,
+}
+
+/// Outcome of a whole ingestion stream
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamIngestionReport {
+    pub format: StreamFormat,
+    pub source: String,
+    pub records_received: usize,
+    pub records_rejected: usize,
+    pub stored: usize,
+    pub batches_committed: usize,
+    pub batches: Vec<BatchSummary>,
+    pub errors: Vec<RecordError>,
+    /// More errors occurred than are listed in `errors`
+    pub errors_truncated: bool,
+    /// Why the stream was abandoned; batches committed before this are kept
+    pub aborted: Option<String>,
+}
+
+impl StreamIngestionReport {
+    fn new(format: StreamFormat, source: &str) -> Self {
+        Self {
+            format,
+            source: source.to_string(),
+            records_received: 0,
+            records_rejected: 0,
+            stored: 0,
+            batches_committed: 0,
+            batches: Vec::new(),
+            errors: Vec::new(),
+            errors_truncated: false,
+            aborted: None,
+        }
+    }
+
+    fn reject(&mut self, record: usize, reason: String, max_reported: usize) {
+        self.records_rejected += 1;
+        if self.errors.len() < max_reported {
+            self.errors.push(RecordError { record, reason });
+        } else {
+            self.errors_truncated = true;
+        }
+    }
+}
+
+/// Decoded record, or the reason it could not be decoded
+type Decoded = Result<StreamRecord, String>;
+
+/// Incremental decoder turning body chunks into records
+trait RecordDecoder: Send {
+    /// Decode everything complete in `chunk`; `Err` means the stream is unreadable
+    fn push(&mut self, chunk: &[u8], out: &mut Vec<Decoded>) -> Result<(), String>;
+
+    /// Decode whatever is left once the body has ended
+    fn finish(&mut self, out: &mut Vec<Decoded>) -> Result<(), String>;
+}
+
+fn decoder_for(format: StreamFormat, config: &StreamIngestionConfig) -> Box<dyn RecordDecoder> {
+    match format {
+        StreamFormat::Ndjson => Box::new(NdjsonDecoder::new(config.max_record_bytes)),
+        StreamFormat::ArrowIpc => Box::new(ArrowIpcDecoder::default()),
+    }
+}
+
+/// Newline-delimited JSON, one [`StreamRecord`] per line
+struct NdjsonDecoder {
+    pending: Vec<u8>,
+    max_record_bytes: usize,
+}
+
+impl NdjsonDecoder {
+    fn new(max_record_bytes: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            max_record_bytes,
+        }
+    }
+
+    fn decode_line(line: &[u8], out: &mut Vec<Decoded>) {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+        out.push(serde_json::from_slice(line).map_err(|e| format!("invalid record: {}", e)));
+    }
+}
+
+impl RecordDecoder for NdjsonDecoder {
+    fn push(&mut self, chunk: &[u8], out: &mut Vec<Decoded>) -> Result<(), String> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut start = 0;
+        while let Some(offset) = self.pending[start..].iter().position(|&b| b == b'\n') {
+            Self::decode_line(&self.pending[start..start + offset], out);
+            start += offset + 1;
+        }
+        self.pending.drain(..start);
+
+        if self.pending.len() > self.max_record_bytes {
+            return Err(format!(
+                "record exceeds {} bytes without a newline",
+                self.max_record_bytes
+            ));
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut Vec<Decoded>) -> Result<(), String> {
+        let line = std::mem::take(&mut self.pending);
+        Self::decode_line(&line, out);
+        Ok(())
+    }
+}
+
+/// Arrow IPC streaming format
+#[derive(Default)]
+struct ArrowIpcDecoder {
+    decoder: StreamDecoder,
+}
+
+impl ArrowIpcDecoder {
+    fn decode_batch(batch: &RecordBatch, out: &mut Vec<Decoded>) -> Result<(), String> {
+        let column = |name: &str| batch.column_by_name(name).map(|array| array.as_ref());
+        let required = |name: &str| {
+            column(name).ok_or_else(|| format!("Arrow stream has no '{}' column", name))
+        };
+        let options = FormatOptions::default();
+
+        let series_ids = required("series_id")?;
+        let dates = required("date")?;
+        let values = required("value")?;
+        let units = column("unit");
+
+        let series_id_text = formatter(series_ids, &options)?;
+        let date_text = formatter(dates, &options)?;
+        let value_text = formatter(values, &options)?;
+        let unit_text = units.map(|units| formatter(units, &options)).transpose()?;
+
+        for row in 0..batch.num_rows() {
+            if series_ids.is_null(row) || dates.is_null(row) {
+                out.push(Err("series_id and date must not be null".to_string()));
+                continue;
+            }
+            let series_id = series_id_text.value(row).to_string();
+            let record = Uuid::parse_str(&series_id)
+                .map_err(|_| format!("invalid series_id '{}'", series_id))
+                .map(|series_id| StreamRecord {
+                    series_id,
+                    date: date_text.value(row).to_string(),
+                    value: (!values.is_null(row)).then(|| value_text.value(row).to_string()),
+                    unit: units.zip(unit_text.as_ref()).and_then(|(units, text)| {
+                        (!units.is_null(row)).then(|| text.value(row).to_string())
+                    }),
+                });
+            out.push(record);
+        }
+        Ok(())
+    }
+}
+
+/// Formats any Arrow column as text, e.g. `Date32` as `2024-01-01`
+fn formatter<'a>(
+    array: &'a dyn Array,
+    options: &'a FormatOptions<'a>,
+) -> Result<ArrayFormatter<'a>, String> {
+    ArrayFormatter::try_new(array, options)
+        .map_err(|e| format!("unsupported Arrow column type: {}", e))
+}
+
+impl RecordDecoder for ArrowIpcDecoder {
+    fn push(&mut self, chunk: &[u8], out: &mut Vec<Decoded>) -> Result<(), String> {
+        let mut buffer = Buffer::from_vec(chunk.to_vec());
+        while !buffer.is_empty() {
+            match self
+                .decoder
+                .decode(&mut buffer)
+                .map_err(|e| format!("invalid Arrow IPC stream: {}", e))?
+            {
+                Some(batch) => Self::decode_batch(&batch, out)?,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, _out: &mut Vec<Decoded>) -> Result<(), String> {
+        self.decoder
+            .finish()
+            .map_err(|e| format!("truncated Arrow IPC stream: {}", e))
+    }
+}
+
+/// Ingests record streams from external crawlers batch by batch
+#[derive(Debug, Clone, Default)]
+pub struct StreamIngestor {
+    config: StreamIngestionConfig,
+}
+
+impl StreamIngestor {
+    pub fn new(config: StreamIngestionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Decode, validate and store a record stream
+    ///
+    /// Decoding and validation problems are reported per record. Only an
+    /// unreadable stream or a failed body read aborts ingestion, and batches
+    /// committed up to that point are kept. Database errors are returned as errors.
+    #[tracing::instrument(name = "crawler.stream_ingest", skip(self, pool, body))]
+    pub async fn ingest<S, E>(
+        &self,
+        pool: &DatabasePool,
+        source: &str,
+        format: StreamFormat,
+        body: S,
+    ) -> AppResult<StreamIngestionReport>
+    where
+        S: Stream<Item = Result<Vec<u8>, E>> + Unpin,
+        E: std::fmt::Display,
+    {
+        let mut report = StreamIngestionReport::new(format, source);
+        let mut decoder = decoder_for(format, &self.config);
+        let mut body = body;
+        let mut decoded = Vec::new();
+        let mut pending = Vec::with_capacity(self.config.batch_size);
+
+        loop {
+            let (outcome, ended) = match body.next().await {
+                Some(Ok(chunk)) => (decoder.push(&chunk, &mut decoded), false),
+                Some(Err(e)) => (Err(format!("failed to read request body: {}", e)), true),
+                None => (decoder.finish(&mut decoded), true),
+            };
+
+            for record in decoded.drain(..) {
+                report.records_received += 1;
+                let position = report.records_received;
+                match record {
+                    Ok(record) => pending.push((position, record)),
+                    Err(reason) => report.reject(position, reason, self.config.max_reported_errors),
+                }
+                // Committing before reading further is what applies backpressure
+                if pending.len() >= self.config.batch_size {
+                    self.commit_batch(pool, &mut report, std::mem::take(&mut pending))
+                        .await?;
+                }
+            }
+
+            if let Err(reason) = outcome {
+                warn!("Abandoning {} ingestion stream: {}", source, reason);
+                report.aborted = Some(reason);
+            }
+            if ended || report.aborted.is_some() {
+                break;
+            }
+        }
+
+        if !pending.is_empty() {
+            self.commit_batch(pool, &mut report, pending).await?;
+        }
+
+        info!(
+            "Stream ingestion from {}: {} records, {} stored, {} rejected in {} batches",
+            source,
+            report.records_received,
+            report.stored,
+            report.records_rejected,
+            report.batches_committed
+        );
+
+        Ok(report)
+    }
+
+    /// Validate and store one batch, grouped by series
+    async fn commit_batch(
+        &self,
+        pool: &DatabasePool,
+        report: &mut StreamIngestionReport,
+        batch: Vec<(usize, StreamRecord)>,
+    ) -> AppResult<()> {
+        let mut summary = BatchSummary {
+            batch: report.batches_committed + 1,
+            records: batch.len(),
+            ..Default::default()
+        };
+
+        let mut by_series: BTreeMap<Uuid, (Vec<usize>, Vec<RawObservation>)> = BTreeMap::new();
+        for (record, row) in batch {
+            let (records, observations) = by_series.entry(row.series_id).or_default();
+            records.push(record);
+            observations.push(RawObservation {
+                date: row.date,
+                value: row.value,
+                unit: row.unit,
+            });
+        }
+
+        let series_ids: Vec<Uuid> = by_series.keys().copied().collect();
+        let units = series_units(pool, &series_ids).await?;
+        let max_errors = self.config.max_reported_errors;
+
+        for (series_id, (records, observations)) in by_series {
+            let Some(target_unit) = units.get(&series_id) else {
+                summary.rejected += records.len();
+                for record in records {
+                    report.reject(record, format!("unknown series {}", series_id), max_errors);
+                }
+                continue;
+            };
+
+            let pipeline = IngestionPipeline::new(IngestionConfig {
+                target_unit: target_unit.clone(),
+                ..Default::default()
+            });
+            let outcome = pipeline
+                .ingest(pool, &report.source, series_id, &observations)
+                .await?;
+
+            summary.series += 1;
+            summary.stored += outcome.stored;
+            summary.duplicates += outcome.duplicates;
+            summary.missing += outcome.missing;
+            summary.rejected += outcome.rejected.len();
+            summary.outliers += outcome.outliers.len();
+            for rejected in outcome.rejected {
+                report.reject(records[rejected.index], rejected.reason, max_errors);
+            }
+        }
+
+        report.stored += summary.stored;
+        report.batches_committed += 1;
+        report.batches.push(summary);
+        Ok(())
+    }
+}
+
+/// Units of the given series; series that do not exist are absent
+async fn series_units(
+    pool: &DatabasePool,
+    series_ids: &[Uuid],
+) -> AppResult<HashMap<Uuid, Option<String>>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let units = economic_series::table
+        .filter(economic_series::id.eq_any(series_ids))
+        .select((economic_series::id, economic_series::units))
+        .load::<(Uuid, Option<String>)>(&mut conn)
+        .await?;
+
+    Ok(units.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Date32Array, Float64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::writer::StreamWriter;
+    use std::sync::Arc;
+
+    const SERIES: &str = "01916a2c-7d1e-7b4f-9a0e-5c2d3e4f5a6b";
+
+    fn decode_chunks(decoder: &mut dyn RecordDecoder, chunks: &[&[u8]]) -> Vec<Decoded> {
+        let mut out = Vec::new();
+        for chunk in chunks {
+            decoder.push(chunk, &mut out).unwrap();
+        }
+        decoder.finish(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_ndjson_records_split_across_chunks() {
+        // REQUIREMENT: External crawlers can stream NDJSON data points over HTTP
+        // PURPOSE: Verify records are decoded regardless of how the body is chunked
+        // This ensures a record split between two network reads is not lost or rejected
+
+        let body = format!(
+            "{{\"series_id\":\"{s}\",\"date\":\"2024-01-01\",\"value\":1.5}}\r\n\
+             \n\
+             {{\"series_id\":\"{s}\",\"date\":\"2024-02-01\",\"value\":\"1.7\",\"unit\":\"Percent\"}}\n\
+             not json\n\
+             {{\"series_id\":\"{s}\",\"date\":\"2024-03-01\",\"value\":null}}",
+            s = SERIES
+        );
+        let (first, second) = body.as_bytes().split_at(30);
+
+        let records = decode_chunks(&mut NdjsonDecoder::new(1024), &[first, second]);
+
+        assert_eq!(records.len(), 4);
+        let first = records[0].as_ref().unwrap();
+        assert_eq!(first.series_id, Uuid::parse_str(SERIES).unwrap());
+        assert_eq!(first.value.as_deref(), Some("1.5"));
+        assert_eq!(
+            records[1].as_ref().unwrap().unit.as_deref(),
+            Some("Percent")
+        );
+        assert!(records[2].is_err());
+        assert_eq!(records[3].as_ref().unwrap().value, None);
+
+        let mut out = Vec::new();
+        let overlong = NdjsonDecoder::new(16).push(&[b'x'; 32], &mut out);
+        assert!(overlong.is_err());
+    }
+
+    #[test]
+    fn test_arrow_ipc_stream_decoded_into_records() {
+        // REQUIREMENT: External crawlers can stream Arrow IPC data points over HTTP
+        // PURPOSE: Verify typed Arrow columns are decoded into records, including nulls
+        // This ensures Arrow producers can send native date and float columns
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("series_id", DataType::Utf8, false),
+            Field::new("date", DataType::Date32, false),
+            Field::new("value", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec![SERIES, "not-a-uuid"])),
+                // 19723 days after the epoch is 2024-01-01
+                Arc::new(Date32Array::from(vec![19723, 19754])),
+                Arc::new(Float64Array::from(vec![Some(2.25), None])),
+            ],
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        {
+            let mut writer = StreamWriter::try_new(&mut bytes, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+        let chunks: Vec<&[u8]> = bytes.chunks(7).collect();
+
+        let records = decode_chunks(&mut ArrowIpcDecoder::default(), &chunks);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            Ok(StreamRecord {
+                series_id: Uuid::parse_str(SERIES).unwrap(),
+                date: "2024-01-01".to_string(),
+                value: Some("2.25".to_string()),
+                unit: None,
+            })
+        );
+        assert!(records[1].is_err());
+
+        let mut truncated = ArrowIpcDecoder::default();
+        let mut out = Vec::new();
+        truncated.push(&bytes[..bytes.len() / 2], &mut out).unwrap();
+        assert!(truncated.finish(&mut out).is_err());
+    }
+
+    #[test]
+    fn test_stream_format_from_content_type() {
+        // REQUIREMENT: The ingestion endpoint picks the decoder from the request
+        // PURPOSE: Verify supported content types are recognized with parameters
+        // This ensures clients sending charset parameters are not rejected
+
+        assert_eq!(
+            StreamFormat::from_content_type("application/x-ndjson; charset=utf-8"),
+            Some(StreamFormat::Ndjson)
+        );
+        assert_eq!(
+            StreamFormat::from_content_type("application/vnd.apache.arrow.stream"),
+            Some(StreamFormat::ArrowIpc)
+        );
+        assert_eq!(StreamFormat::from_content_type("application/json"), None);
+    }
+}
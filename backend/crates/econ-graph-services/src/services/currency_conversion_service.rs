@@ -0,0 +1,485 @@
+/**
+ * REQUIREMENT: Compare monetary series published in different currencies
+ * PURPOSE: Load ECB euro reference rates into fx_rates and convert a series into
+ * another currency using the rate of each observation's date. Days without a
+ * published rate (weekends, holidays, month starts) use the last rate before them
+ */
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use reqwest::Client;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{DataQueryParams, FxRate, NewFxRate},
+};
+
+use super::crawler::CrawlPlan;
+use super::series_service;
+
+/// Currency ECB reference rates are quoted against
+pub const RATE_BASE_CURRENCY: &str = "EUR";
+
+/// Source name stored with ECB rates
+pub const ECB_SOURCE: &str = "ECB";
+
+/// Daily euro reference rates for all currencies, as CSV
+const ECB_RATES_URL: &str = "https://data-api.ecb.europa.eu/service/data/EXR/D..EUR.SP00.A";
+
+/// Decimal places kept in cross rates and converted values
+const CONVERSION_SCALE: i64 = 10;
+
+/// Currencies the ECB publishes reference rates for, besides the euro
+pub const ECB_CURRENCIES: &[&str] = &[
+    "AUD", "BGN", "BRL", "CAD", "CHF", "CNY", "CZK", "DKK", "GBP", "HKD", "HUF", "IDR", "ILS",
+    "INR", "ISK", "JPY", "KRW", "MXN", "MYR", "NOK", "NZD", "PHP", "PLN", "RON", "SEK", "SGD",
+    "THB", "TRY", "USD", "ZAR",
+];
+
+/// Currency names as they appear in series units, most specific first
+const UNIT_CURRENCY_NAMES: &[(&str, &str)] = &[
+    ("canadian dollar", "CAD"),
+    ("australian dollar", "AUD"),
+    ("new zealand dollar", "NZD"),
+    ("hong kong dollar", "HKD"),
+    ("singapore dollar", "SGD"),
+    ("dollar", "USD"),
+    ("euro", "EUR"),
+    ("yen", "JPY"),
+    ("sterling", "GBP"),
+    ("swiss franc", "CHF"),
+    ("yuan", "CNY"),
+    ("renminbi", "CNY"),
+    ("rupee", "INR"),
+];
+
+/// First day the ECB published euro reference rates
+fn first_ecb_rate_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1999, 1, 4).expect("valid date")
+}
+
+/// Download ECB reference rates published on or after `since`
+pub async fn fetch_ecb_rates(client: &Client, since: NaiveDate) -> AppResult<Vec<NewFxRate>> {
+    let response = client
+        .get(ECB_RATES_URL)
+        .query(&[
+            ("startPeriod", since.format("%Y-%m-%d").to_string()),
+            ("format", "csvdata".to_string()),
+        ])
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApiError(format!("ECB rates request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApiError(format!(
+            "ECB rates request failed with status {}",
+            response.status()
+        )));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| AppError::ExternalApiError(format!("Failed to read ECB rates: {}", e)))?;
+
+    parse_ecb_rates_csv(&body)
+}
+
+/// Parse the ECB data API's CSV export of euro reference rates
+///
+/// Rows without a value (days a currency was not quoted) are skipped.
+pub fn parse_ecb_rates_csv(body: &str) -> AppResult<Vec<NewFxRate>> {
+    let mut reader = csv::Reader::from_reader(body.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::ParserError(format!("Invalid ECB rates CSV: {}", e)))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| AppError::ParserError(format!("ECB rates CSV has no {} column", name)))
+    };
+    let currency_column = column("CURRENCY")?;
+    let date_column = column("TIME_PERIOD")?;
+    let value_column = column("OBS_VALUE")?;
+
+    let mut rates = Vec::new();
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| AppError::ParserError(format!("Invalid ECB rates row: {}", e)))?;
+        let (Some(currency), Some(date), Some(value)) = (
+            record.get(currency_column),
+            record.get(date_column),
+            record.get(value_column),
+        ) else {
+            continue;
+        };
+        let Ok(rate) = BigDecimal::from_str(value.trim()) else {
+            continue;
+        };
+        if rate <= BigDecimal::from(0) {
+            continue;
+        }
+        let rate_date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|e| AppError::ParserError(format!("Invalid ECB rate date {}: {}", date, e)))?;
+
+        rates.push(NewFxRate {
+            base_currency: RATE_BASE_CURRENCY.to_string(),
+            quote_currency: currency.trim().to_string(),
+            rate_date,
+            rate,
+            source: ECB_SOURCE.to_string(),
+        });
+    }
+
+    Ok(rates)
+}
+
+/// Fetch ECB rates since the last stored day and store them
+///
+/// The last stored day is fetched again so late corrections replace it. An
+/// empty table is filled with the full history. Returns how many rates were written.
+pub async fn refresh_ecb_rates(pool: &DatabasePool, client: &Client) -> AppResult<usize> {
+    let since = FxRate::latest_date(pool, RATE_BASE_CURRENCY)
+        .await?
+        .unwrap_or_else(first_ecb_rate_date);
+
+    let rates = fetch_ecb_rates(client, since).await?;
+    FxRate::upsert_batch(pool, &rates).await
+}
+
+/// What [`refresh_ecb_rates`] would download, without fetching or writing anything
+pub async fn plan_ecb_refresh(pool: &DatabasePool) -> AppResult<CrawlPlan> {
+    let latest = FxRate::latest_date(pool, RATE_BASE_CURRENCY).await?;
+    let since = latest.unwrap_or_else(first_ecb_rate_date);
+
+    let mut plan = CrawlPlan {
+        steps: vec![format!(
+            "Download ECB reference rates from {} for {} onwards",
+            ECB_RATES_URL, since
+        )],
+        ..Default::default()
+    };
+    if latest.is_none() {
+        plan.warnings.push(
+            "No rates stored yet; the full history since 1999 would be downloaded".to_string(),
+        );
+    }
+
+    Ok(plan)
+}
+
+/// Currency of a series from its units, e.g. "Billions of Dollars" is USD
+///
+/// Returns `None` for units that do not name a currency, such as "Percent" or
+/// "Index 2015=100".
+pub fn currency_from_units(units: &str) -> Option<&'static str> {
+    let code = units
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .find_map(|token| {
+            std::iter::once(RATE_BASE_CURRENCY)
+                .chain(ECB_CURRENCIES.iter().copied())
+                .find(|code| *code == token)
+        });
+    if code.is_some() {
+        return code;
+    }
+
+    let units = units.to_lowercase();
+    UNIT_CURRENCY_NAMES
+        .iter()
+        .find(|(name, _)| units.contains(name))
+        .map(|(_, code)| *code)
+}
+
+/// Normalize a currency code, rejecting currencies without reference rates
+pub fn parse_currency(code: &str) -> AppResult<String> {
+    let code = code.trim().to_uppercase();
+    if code == RATE_BASE_CURRENCY || ECB_CURRENCIES.contains(&code.as_str()) {
+        Ok(code)
+    } else {
+        Err(AppError::ValidationError(format!(
+            "No exchange rates are available for currency {}",
+            code
+        )))
+    }
+}
+
+/// Units of one currency per euro over time
+#[derive(Debug, Clone)]
+pub enum EuroRates {
+    /// The euro itself, always 1
+    Euro,
+    /// Published rates, oldest first
+    Quoted(Vec<(NaiveDate, BigDecimal)>),
+}
+
+impl EuroRates {
+    /// Last rate published on or before `date`, with the day it was published
+    pub fn on(&self, date: NaiveDate) -> Option<(NaiveDate, BigDecimal)> {
+        match self {
+            EuroRates::Euro => Some((date, BigDecimal::from(1))),
+            EuroRates::Quoted(rates) => {
+                let published = rates.partition_point(|(rate_date, _)| *rate_date <= date);
+                rates.get(published.checked_sub(1)?).cloned()
+            }
+        }
+    }
+}
+
+/// One observation converted into the target currency
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvertedDataPoint {
+    pub date: NaiveDate,
+    pub original_value: Option<BigDecimal>,
+    /// `None` when the observation has no value or no rate was published yet
+    pub value: Option<BigDecimal>,
+    /// Units of target currency per unit of source currency
+    pub rate: Option<BigDecimal>,
+    /// Day the rate was published; earlier than `date` when carried forward
+    pub rate_date: Option<NaiveDate>,
+}
+
+/// A series converted into another currency
+#[derive(Debug, Clone)]
+pub struct CurrencyConversion {
+    pub series_id: Uuid,
+    pub source_currency: String,
+    pub target_currency: String,
+    pub points: Vec<ConvertedDataPoint>,
+    /// Observations dated before the first available rate
+    pub missing_rate_count: usize,
+}
+
+/// Convert observations from one currency to another through their euro rates
+pub fn convert_observations(
+    observations: &[(NaiveDate, Option<BigDecimal>)],
+    source: &EuroRates,
+    target: &EuroRates,
+) -> Vec<ConvertedDataPoint> {
+    observations
+        .iter()
+        .map(|(date, original_value)| {
+            let (Some((source_date, source_rate)), Some((target_date, target_rate))) =
+                (source.on(*date), target.on(*date))
+            else {
+                return ConvertedDataPoint {
+                    date: *date,
+                    original_value: original_value.clone(),
+                    value: None,
+                    rate: None,
+                    rate_date: None,
+                };
+            };
+
+            // Divide last so large values keep their precision
+            let value = original_value.as_ref().map(|value| {
+                (value * &target_rate / &source_rate)
+                    .round(CONVERSION_SCALE)
+                    .normalized()
+            });
+            let rate = (target_rate / source_rate)
+                .round(CONVERSION_SCALE)
+                .normalized();
+
+            ConvertedDataPoint {
+                date: *date,
+                original_value: original_value.clone(),
+                value,
+                rate: Some(rate),
+                rate_date: Some(source_date.min(target_date)),
+            }
+        })
+        .collect()
+}
+
+async fn load_euro_rates(
+    pool: &DatabasePool,
+    currency: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> AppResult<EuroRates> {
+    if currency == RATE_BASE_CURRENCY {
+        return Ok(EuroRates::Euro);
+    }
+
+    let rates =
+        FxRate::find_range_with_prior(pool, RATE_BASE_CURRENCY, currency, start, end).await?;
+    if rates.is_empty() {
+        return Err(AppError::InvalidTransformation(format!(
+            "No exchange rates stored for {}; run `crawler fx-rates` first",
+            currency
+        )));
+    }
+
+    Ok(EuroRates::Quoted(
+        rates
+            .into_iter()
+            .map(|rate| (rate.rate_date, rate.rate))
+            .collect(),
+    ))
+}
+
+/// Convert a monetary series into `target_currency`
+///
+/// The source currency is read from the series units unless given.
+pub async fn convert_series(
+    pool: &DatabasePool,
+    series_id: Uuid,
+    target_currency: &str,
+    source_currency: Option<&str>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> AppResult<CurrencyConversion> {
+    let series = series_service::get_series_by_id(pool, series_id)
+        .await?
+        .ok_or_else(|| AppError::SeriesNotFound(series_id.to_string()))?;
+
+    let target_currency = parse_currency(target_currency)?;
+    let source_currency = match source_currency {
+        Some(code) => parse_currency(code)?,
+        None => series
+            .units
+            .as_deref()
+            .and_then(currency_from_units)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                AppError::InvalidTransformation(format!(
+                    "Cannot tell the currency of series {} from its units ({}); pass the source currency",
+                    series.title,
+                    series.units.as_deref().unwrap_or("none")
+                ))
+            })?,
+    };
+
+    let data_points = series_service::get_series_data_window(
+        pool,
+        &DataQueryParams {
+            series_id,
+            start_date,
+            end_date,
+            original_only: None,
+            latest_revision_only: Some(true),
+            exclude_corrections: None,
+            limit: None,
+            offset: None,
+        },
+    )
+    .await?;
+
+    let observations: Vec<(NaiveDate, Option<BigDecimal>)> = data_points
+        .into_iter()
+        .map(|point| (point.date, point.value))
+        .collect();
+
+    let points = match (observations.first(), observations.last()) {
+        (Some((first, _)), Some((last, _))) if source_currency != target_currency => {
+            let (start, end) = ((*first).min(*last), (*first).max(*last));
+            let source = load_euro_rates(pool, &source_currency, start, end).await?;
+            let target = load_euro_rates(pool, &target_currency, start, end).await?;
+            convert_observations(&observations, &source, &target)
+        }
+        _ => convert_observations(&observations, &EuroRates::Euro, &EuroRates::Euro),
+    };
+    let missing_rate_count = points.iter().filter(|point| point.rate.is_none()).count();
+
+    Ok(CurrencyConversion {
+        series_id,
+        source_currency,
+        target_currency,
+        points,
+        missing_rate_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_ecb_rates_csv() {
+        // REQUIREMENT: Currency rates are loaded from ECB reference rates
+        // PURPOSE: Verify the ECB CSV export is read by column name and unquoted days are skipped
+        // This ensures column order changes or holidays do not corrupt stored rates
+
+        let body = "KEY,FREQ,CURRENCY,CURRENCY_DENOM,EXR_TYPE,EXR_SUFFIX,TIME_PERIOD,OBS_VALUE\n\
+            EXR.D.USD.EUR.SP00.A,D,USD,EUR,SP00,A,2024-01-02,1.0956\n\
+            EXR.D.JPY.EUR.SP00.A,D,JPY,EUR,SP00,A,2024-01-02,155.52\n\
+            EXR.D.ISK.EUR.SP00.A,D,ISK,EUR,SP00,A,2024-01-02,\n";
+
+        let rates = parse_ecb_rates_csv(body).unwrap();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].base_currency, "EUR");
+        assert_eq!(rates[0].quote_currency, "USD");
+        assert_eq!(rates[0].rate_date, date("2024-01-02"));
+        assert_eq!(rates[0].rate, decimal("1.0956"));
+        assert_eq!(rates[1].quote_currency, "JPY");
+    }
+
+    #[test]
+    fn test_currency_from_units() {
+        // REQUIREMENT: Monetary series convert without the caller naming their currency
+        // PURPOSE: Verify currencies are read from ISO codes and names in series units
+        // This ensures non-monetary series are not silently treated as dollars
+
+        assert_eq!(currency_from_units("Billions of Dollars"), Some("USD"));
+        assert_eq!(
+            currency_from_units("Millions of Canadian Dollars"),
+            Some("CAD")
+        );
+        assert_eq!(currency_from_units("Millions of Euros"), Some("EUR"));
+        assert_eq!(currency_from_units("Billions of Yen"), Some("JPY"));
+        assert_eq!(currency_from_units("GBP millions"), Some("GBP"));
+        assert_eq!(currency_from_units("Percent"), None);
+        assert_eq!(currency_from_units("Cents per Pound"), None);
+    }
+
+    #[test]
+    fn test_convert_observations_carries_rates_forward() {
+        // REQUIREMENT: Conversion uses date-matched rates with last observation carried forward
+        // PURPOSE: Verify cross rates go through the euro and days without a rate use the previous one
+        // This ensures weekend and month-start observations are converted instead of dropped
+
+        let usd = EuroRates::Quoted(vec![
+            (date("2024-01-02"), decimal("1.25")),
+            (date("2024-01-05"), decimal("1.0")),
+        ]);
+        let gbp = EuroRates::Quoted(vec![
+            (date("2024-01-02"), decimal("1.0")),
+            (date("2024-01-05"), decimal("0.8")),
+        ]);
+        let observations = vec![
+            (date("2024-01-01"), Some(decimal("100"))),
+            (date("2024-01-02"), Some(decimal("110"))),
+            (date("2024-01-06"), Some(decimal("100"))),
+            (date("2024-01-07"), None),
+        ];
+
+        let converted = convert_observations(&observations, &usd, &gbp);
+
+        assert_eq!(converted[0].value, None);
+        assert_eq!(converted[0].rate, None);
+        assert_eq!(converted[1].value, Some(decimal("88")));
+        assert_eq!(converted[1].rate, Some(decimal("0.8")));
+        assert_eq!(converted[2].value, Some(decimal("80")));
+        assert_eq!(converted[2].rate_date, Some(date("2024-01-05")));
+        assert_eq!(converted[3].value, None);
+        assert_eq!(converted[3].rate, Some(decimal("0.8")));
+
+        let to_euro = convert_observations(&observations[1..2], &usd, &EuroRates::Euro);
+        assert_eq!(to_euro[0].value, Some(decimal("88")));
+        assert_eq!(to_euro[0].rate_date, Some(date("2024-01-02")));
+    }
+}
@@ -0,0 +1,125 @@
+/**
+ * REQUIREMENT: Administrators can fix bad observations without losing the original values
+ * PURPOSE: Record a manual correction as a new revision of the data point, mark it so
+ * queries can filter corrections out, and write the reason to the audit log
+ * Existing data point rows are never modified
+ */
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        admin::AuditLog, DataPoint, DataPointCorrection, NewDataPoint, MAX_CORRECTION_REASON_LENGTH,
+    },
+};
+
+use crate::services::data_point_cache::shared_data_point_cache;
+use crate::services::response_cache::shared_response_cache;
+use crate::services::data_source_admin_service::AuditActor;
+
+/// Value stored in `audit_logs.resource_type` for data point corrections
+pub const DATA_POINT_RESOURCE_TYPE: &str = "data_point";
+
+pub struct DataCorrectionService;
+
+impl DataCorrectionService {
+    /// Correct the value of a data point by writing a new revision
+    ///
+    /// The revision keeps the series and observation date of the corrected
+    /// point and is dated today.
+    pub async fn correct_data_point(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        data_point_id: Uuid,
+        value: Option<BigDecimal>,
+        reason: &str,
+    ) -> AppResult<(DataPoint, DataPointCorrection)> {
+        let reason = validate_reason(reason)?;
+
+        let previous = DataPoint::find_by_id(pool, data_point_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Data point {} not found", data_point_id)))?;
+
+        let revision = NewDataPoint {
+            series_id: previous.series_id,
+            date: previous.date,
+            value,
+            revision_date: Utc::now().date_naive(),
+            is_original_release: false,
+        };
+
+        let (data_point, correction) =
+            DataPointCorrection::record(pool, &revision, previous.id, &reason, actor.user_id)
+                .await?;
+
+        AuditLog::create(
+            pool,
+            actor.user_id,
+            actor.user_name.clone(),
+            "correct_data_point".to_string(),
+            DATA_POINT_RESOURCE_TYPE.to_string(),
+            Some(data_point.id.to_string()),
+            actor.ip_address.clone(),
+            None,
+            Some(json!({
+                "series_id": data_point.series_id,
+                "date": data_point.date,
+                "corrected_data_point_id": previous.id,
+                "previous_value": previous.value,
+                "value": data_point.value,
+                "reason": reason,
+            })),
+        )
+        .await?;
+
+        shared_data_point_cache().invalidate_series(data_point.series_id);
+
+        shared_response_cache().invalidate_series(data_point.series_id);
+
+        Ok((data_point, correction))
+    }
+}
+
+/// Corrections must say why they were made
+pub fn validate_reason(reason: &str) -> AppResult<String> {
+    let reason = reason.trim();
+
+    if reason.is_empty() {
+        return Err(AppError::ValidationError(
+            "A reason is required for data corrections".to_string(),
+        ));
+    }
+    if reason.chars().count() > MAX_CORRECTION_REASON_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Correction reason must be at most {} characters",
+            MAX_CORRECTION_REASON_LENGTH
+        )));
+    }
+
+    Ok(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correction_reason_is_required() {
+        // REQUIREMENT: Every manual correction records why it was made
+        // PURPOSE: Verify blank and oversized reasons are rejected and others are trimmed
+        // This keeps the audit trail meaningful for later review
+
+        assert!(validate_reason("").is_err());
+        assert!(validate_reason("   \n").is_err());
+        assert!(validate_reason(&"x".repeat(MAX_CORRECTION_REASON_LENGTH + 1)).is_err());
+
+        assert_eq!(
+            validate_reason("  Source republished with a typo fix \n").unwrap(),
+            "Source republished with a typo fix"
+        );
+    }
+}
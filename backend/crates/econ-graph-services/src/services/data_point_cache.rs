@@ -0,0 +1,341 @@
+/**
+ * REQUIREMENT: Serve frequently requested series without a database round trip
+ * PURPOSE: Keep recently read data point windows in a bounded LRU cache with a TTL
+ * This takes repeated chart and dashboard reads of hot series off the data_points table
+ */
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use econ_graph_core::{
+    error::AppResult,
+    models::{DataPoint, DataQueryParams},
+    query_explain::record_cache_lookup,
+};
+use econ_graph_metrics::cache::CACHE_METRICS;
+
+/// Cache name used in metric labels
+pub const DATA_POINT_CACHE_NAME: &str = "data_points";
+
+/// Identifies one cached window of a series
+///
+/// Two requests share an entry only if they ask for the same date range,
+/// release filters, page and transformation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataPointCacheKey {
+    pub series_id: Uuid,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub original_only: bool,
+    pub latest_revision_only: bool,
+    pub exclude_corrections: bool,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Transformation applied to the points, e.g. "YearOverYear"
+    pub transformation: Option<String>,
+}
+
+impl DataPointCacheKey {
+    /// Untransformed window of a series
+    pub fn new(
+        series_id: Uuid,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            series_id,
+            start_date,
+            end_date,
+            original_only: false,
+            latest_revision_only: false,
+            exclude_corrections: false,
+            limit: None,
+            offset: None,
+            transformation: None,
+        }
+    }
+
+    /// Key for a [`DataQueryParams`] request
+    pub fn from_params(params: &DataQueryParams) -> Self {
+        Self {
+            original_only: params.original_only.unwrap_or(false),
+            latest_revision_only: params.latest_revision_only.unwrap_or(false),
+            exclude_corrections: params.exclude_corrections.unwrap_or(false),
+            limit: params.limit,
+            offset: params.offset,
+            ..Self::new(params.series_id, params.start_date, params.end_date)
+        }
+    }
+
+    pub fn with_original_only(mut self, original_only: bool) -> Self {
+        self.original_only = original_only;
+        self
+    }
+
+    pub fn with_transformation(mut self, transformation: impl Into<String>) -> Self {
+        self.transformation = Some(transformation.into());
+        self
+    }
+}
+
+struct CachedWindow {
+    points: Arc<Vec<DataPoint>>,
+    cached_at: Instant,
+    /// Value of the access counter when the entry was last read or written
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct LruEntries {
+    windows: HashMap<DataPointCacheKey, CachedWindow>,
+    access_counter: u64,
+}
+
+impl LruEntries {
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+}
+
+/// In-memory LRU cache of data point windows
+///
+/// Entries expire after the TTL and are dropped as soon as new data is written
+/// for their series. Writers in other processes (e.g. standalone crawlers) are
+/// only picked up once the TTL expires.
+pub struct DataPointCache {
+    entries: Mutex<LruEntries>,
+    capacity: usize,
+    ttl: Duration,
+    max_entry_points: usize,
+}
+
+impl Default for DataPointCache {
+    fn default() -> Self {
+        Self::new(256, Duration::from_secs(300))
+    }
+}
+
+impl DataPointCache {
+    /// Create a cache holding at most `capacity` windows for `ttl` each
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruEntries::default()),
+            capacity: capacity.max(1),
+            ttl,
+            max_entry_points: 50_000,
+        }
+    }
+
+    /// Largest window, in data points, that will be cached
+    ///
+    /// Bigger results are always read from storage so a few full-history
+    /// requests cannot hold most of the cache's memory.
+    pub fn max_entry_points(&self) -> usize {
+        self.max_entry_points
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached points for `key`, if present and not expired
+    pub fn get(&self, key: &DataPointCacheKey) -> Option<Arc<Vec<DataPoint>>> {
+        let mut entries = self.lock();
+        let access = entries.next_access();
+
+        let expired = match entries.windows.get_mut(key) {
+            Some(window) if window.cached_at.elapsed() < self.ttl => {
+                window.last_used = access;
+                CACHE_METRICS.record_hit(DATA_POINT_CACHE_NAME);
+                record_cache_lookup(DATA_POINT_CACHE_NAME, true);
+                return Some(window.points.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            entries.windows.remove(key);
+            CACHE_METRICS.record_eviction(DATA_POINT_CACHE_NAME, "expired");
+            CACHE_METRICS.set_entries(DATA_POINT_CACHE_NAME, entries.windows.len());
+        }
+        CACHE_METRICS.record_miss(DATA_POINT_CACHE_NAME);
+        record_cache_lookup(DATA_POINT_CACHE_NAME, false);
+        None
+    }
+
+    /// Store points for `key`, evicting the least recently used window if full
+    ///
+    /// Windows larger than [`Self::max_entry_points`] are returned without
+    /// being cached.
+    pub fn insert(&self, key: DataPointCacheKey, points: Vec<DataPoint>) -> Arc<Vec<DataPoint>> {
+        let points = Arc::new(points);
+        if points.len() > self.max_entry_points {
+            return points;
+        }
+
+        let mut entries = self.lock();
+        let access = entries.next_access();
+
+        if !entries.windows.contains_key(&key) && entries.windows.len() >= self.capacity {
+            let ttl = self.ttl;
+            let before = entries.windows.len();
+            entries
+                .windows
+                .retain(|_, window| window.cached_at.elapsed() < ttl);
+            for _ in entries.windows.len()..before {
+                CACHE_METRICS.record_eviction(DATA_POINT_CACHE_NAME, "expired");
+            }
+
+            if entries.windows.len() >= self.capacity {
+                let least_recent = entries
+                    .windows
+                    .iter()
+                    .min_by_key(|(_, window)| window.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(least_recent) = least_recent {
+                    entries.windows.remove(&least_recent);
+                    CACHE_METRICS.record_eviction(DATA_POINT_CACHE_NAME, "capacity");
+                }
+            }
+        }
+
+        entries.windows.insert(
+            key,
+            CachedWindow {
+                points: points.clone(),
+                cached_at: Instant::now(),
+                last_used: access,
+            },
+        );
+        CACHE_METRICS.set_entries(DATA_POINT_CACHE_NAME, entries.windows.len());
+
+        points
+    }
+
+    /// Cached points for `key`, loading and caching them on a miss
+    pub async fn get_or_load<F, Fut>(
+        &self,
+        key: DataPointCacheKey,
+        load: F,
+    ) -> AppResult<Arc<Vec<DataPoint>>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = AppResult<Vec<DataPoint>>>,
+    {
+        if let Some(points) = self.get(&key) {
+            return Ok(points);
+        }
+
+        let points = load().await?;
+        Ok(self.insert(key, points))
+    }
+
+    /// Drop every cached window of a series; call after writing its data points
+    pub fn invalidate_series(&self, series_id: Uuid) {
+        let mut entries = self.lock();
+        let before = entries.windows.len();
+        entries.windows.retain(|key, _| key.series_id != series_id);
+
+        for _ in entries.windows.len()..before {
+            CACHE_METRICS.record_eviction(DATA_POINT_CACHE_NAME, "invalidated");
+        }
+        CACHE_METRICS.set_entries(DATA_POINT_CACHE_NAME, entries.windows.len());
+    }
+
+    /// Number of cached windows, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.lock().windows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Process-wide cache instance
+///
+/// GraphQL schemas are built per request, so the cache has to live outside
+/// the schema to be shared between requests.
+pub fn shared_data_point_cache() -> &'static DataPointCache {
+    static CACHE: OnceLock<DataPointCache> = OnceLock::new();
+    CACHE.get_or_init(DataPointCache::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn point(series_id: Uuid, day: u32) -> DataPoint {
+        let date = NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        DataPoint {
+            id: Uuid::new_v4(),
+            series_id,
+            date,
+            value: Some(day.into()),
+            revision_date: date,
+            is_original_release: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used_window_is_evicted() {
+        // REQUIREMENT: Hot series are served from memory within a bounded cache
+        // PURPOSE: Verify a full cache evicts the window that was read least recently
+        // This ensures frequently charted series stay cached while one-off reads age out
+
+        let cache = DataPointCache::new(2, Duration::from_secs(60));
+        let series = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let keys: Vec<_> = series
+            .iter()
+            .map(|id| DataPointCacheKey::new(*id, None, None))
+            .collect();
+
+        cache.insert(keys[0].clone(), vec![point(series[0], 1)]);
+        cache.insert(keys[1].clone(), vec![point(series[1], 1)]);
+        assert!(cache.get(&keys[0]).is_some());
+
+        cache.insert(keys[2].clone(), vec![point(series[2], 1)]);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&keys[0]).is_some());
+        assert!(cache.get(&keys[1]).is_none());
+        assert!(cache.get(&keys[2]).is_some());
+    }
+
+    #[test]
+    fn test_windows_expire_and_are_invalidated_on_write() {
+        // REQUIREMENT: Cached data is never served after it goes stale
+        // PURPOSE: Verify TTL expiry and per-series invalidation across ranges and transformations
+        // This ensures newly crawled observations show up on the next read
+
+        let series_id = Uuid::new_v4();
+        let other_series = Uuid::new_v4();
+        let full = DataPointCacheKey::new(series_id, None, None);
+        let yoy = full.clone().with_transformation("YearOverYear");
+        let other = DataPointCacheKey::new(other_series, None, None);
+
+        let cache = DataPointCache::new(10, Duration::from_secs(60));
+        cache.insert(full.clone(), vec![point(series_id, 1)]);
+        cache.insert(yoy.clone(), vec![point(series_id, 2)]);
+        cache.insert(other.clone(), vec![point(other_series, 1)]);
+
+        cache.invalidate_series(series_id);
+
+        assert!(cache.get(&full).is_none());
+        assert!(cache.get(&yoy).is_none());
+        assert!(cache.get(&other).is_some());
+
+        let expiring = DataPointCache::new(10, Duration::ZERO);
+        expiring.insert(full.clone(), vec![point(series_id, 1)]);
+        assert!(expiring.get(&full).is_none());
+        assert!(expiring.is_empty());
+    }
+}
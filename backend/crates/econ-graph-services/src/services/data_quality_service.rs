@@ -0,0 +1,487 @@
+/**
+ * REQUIREMENT: Operators see which series have suspicious values, missing periods or stale data
+ * PURPOSE: Score series after new data arrives and export the scores as Prometheus gauges
+ * Each series is checked for outliers among its period-over-period changes (beyond the
+ * IQR fence and with a high robust z-score), periods missing for its frequency, and an
+ * overdue latest observation. The findings are combined into a score from 0 to 1.
+ */
+use std::collections::{BTreeMap, HashSet};
+
+use bigdecimal::ToPrimitive;
+use chrono::{Datelike, Duration, NaiveDate, Utc, Weekday};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{DataQueryParams, NewSeriesQualityScore, SeriesQualityScore},
+};
+
+use super::series_service::{self, SourceFrequency};
+
+/// How often the backend runs the data quality job
+pub const DEFAULT_QUALITY_CHECK_INTERVAL_SECONDS: u64 = 3600;
+
+/// Most series scored in one run
+pub const QUALITY_CHECK_BATCH_SIZE: i64 = 500;
+
+/// Scores older than this are recomputed so staleness stays current
+const RESCORE_AFTER_HOURS: i64 = 24;
+
+/// Weights of completeness, cleanliness and freshness in the score
+const SCORE_WEIGHTS: (f64, f64, f64) = (0.4, 0.3, 0.3);
+
+/// Cleanliness lost per share of observations flagged as outliers, so a
+/// series with a tenth of its observations flagged has none left
+const OUTLIER_PENALTY: f64 = 10.0;
+
+/// Robust z-score of 0.6745 * (x - median) / MAD, which matches the ordinary
+/// z-score for normally distributed values
+const MAD_SCALE: f64 = 0.6745;
+
+/// Thresholds for the outlier checks
+#[derive(Debug, Clone, Copy)]
+pub struct QualityConfig {
+    /// Changes further than this many IQRs outside the quartiles fail the fence check
+    pub iqr_multiplier: f64,
+    /// Robust z-score above which a change fails the z-score check
+    pub z_threshold: f64,
+    /// Minimum number of observations before outliers are flagged
+    pub min_points_for_outliers: usize,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            iqr_multiplier: 3.0,
+            z_threshold: 3.5,
+            min_points_for_outliers: 12,
+        }
+    }
+}
+
+/// What the quality checks found in a series
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityAssessment {
+    pub observation_count: usize,
+    /// Dates of observations whose change from the previous one is an outlier
+    pub outlier_dates: Vec<NaiveDate>,
+    /// Periods between the first and last observation without a value
+    pub missing_periods: usize,
+    pub last_observation_date: Option<NaiveDate>,
+    pub is_stale: bool,
+    /// 0 (unusable) to 1 (no issues found)
+    pub score: f64,
+}
+
+impl QualityAssessment {
+    fn to_new_score(
+        &self,
+        series_id: Uuid,
+        checked_through: chrono::DateTime<Utc>,
+    ) -> NewSeriesQualityScore {
+        NewSeriesQualityScore {
+            series_id,
+            score: self.score,
+            observation_count: self.observation_count as i32,
+            outlier_count: self.outlier_dates.len() as i32,
+            missing_periods: self.missing_periods as i32,
+            last_observation_date: self.last_observation_date,
+            is_stale: self.is_stale,
+            checked_through,
+        }
+    }
+}
+
+/// Result of one run of the data quality job
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QualityCheckSummary {
+    pub scored: usize,
+    pub failed: usize,
+    pub with_outliers: usize,
+    pub stale: usize,
+}
+
+/// Score series with new data points, or whose score is a day old, and
+/// refresh the data quality gauges
+pub async fn run_quality_check(
+    pool: &DatabasePool,
+    config: &QualityConfig,
+) -> AppResult<QualityCheckSummary> {
+    // Points created while this run reads are picked up by the next one
+    let checked_through = Utc::now();
+
+    let mut series_ids =
+        SeriesQualityScore::unscored_series(pool, QUALITY_CHECK_BATCH_SIZE).await?;
+    let remaining = QUALITY_CHECK_BATCH_SIZE - series_ids.len() as i64;
+    if remaining > 0 {
+        let rescore_before = checked_through - Duration::hours(RESCORE_AFTER_HOURS);
+        series_ids
+            .extend(SeriesQualityScore::due_for_rescore(pool, rescore_before, remaining).await?);
+    }
+
+    let mut summary = QualityCheckSummary::default();
+    for series_id in series_ids {
+        match score_series(pool, series_id, checked_through, config).await {
+            Ok(score) => {
+                summary.scored += 1;
+                summary.with_outliers += usize::from(score.outlier_count > 0);
+                summary.stale += usize::from(score.is_stale);
+            }
+            Err(e) => {
+                summary.failed += 1;
+                warn!(
+                    "Failed to score data quality of series {}: {}",
+                    series_id, e
+                );
+            }
+        }
+    }
+
+    update_quality_metrics(pool).await?;
+
+    info!(
+        "Data quality check scored {} series ({} with outliers, {} stale, {} failed)",
+        summary.scored, summary.with_outliers, summary.stale, summary.failed
+    );
+
+    Ok(summary)
+}
+
+/// Check the latest revision of a series and store its score
+pub async fn score_series(
+    pool: &DatabasePool,
+    series_id: Uuid,
+    checked_through: chrono::DateTime<Utc>,
+    config: &QualityConfig,
+) -> AppResult<SeriesQualityScore> {
+    let series = series_service::get_series_by_id(pool, series_id)
+        .await?
+        .ok_or_else(|| AppError::SeriesNotFound(series_id.to_string()))?;
+
+    // Read directly rather than through the data point cache, which may predate new points
+    let window = series_service::get_series_data_window(
+        pool,
+        &DataQueryParams {
+            series_id,
+            start_date: None,
+            end_date: None,
+            original_only: None,
+            latest_revision_only: Some(true),
+            exclude_corrections: None,
+            limit: None,
+            offset: None,
+        },
+    )
+    .await?;
+
+    let mut observations: Vec<(NaiveDate, f64)> = window
+        .iter()
+        .filter_map(|point| Some((point.date, point.value.as_ref()?.to_f64()?)))
+        .collect();
+    observations.sort_by_key(|(date, _)| *date);
+
+    let assessment = assess(
+        &observations,
+        &series.frequency,
+        Utc::now().date_naive(),
+        config,
+    );
+
+    SeriesQualityScore::upsert(pool, &assessment.to_new_score(series_id, checked_through)).await
+}
+
+/// Set the per-source data quality gauges from the stored scores
+pub async fn update_quality_metrics(pool: &DatabasePool) -> AppResult<()> {
+    #[derive(Default)]
+    struct SourceTotals {
+        score_sum: f64,
+        series: usize,
+        outliers: i64,
+        gaps: i64,
+        stale: i64,
+    }
+
+    let mut by_source: BTreeMap<String, SourceTotals> = BTreeMap::new();
+    for row in SeriesQualityScore::list_with_sources(pool).await? {
+        let totals = by_source.entry(row.source_name).or_default();
+        totals.score_sum += row.score;
+        totals.series += 1;
+        totals.outliers += i64::from(row.outlier_count > 0);
+        totals.gaps += i64::from(row.missing_periods > 0);
+        totals.stale += i64::from(row.is_stale);
+    }
+
+    for (source, totals) in by_source {
+        CRAWLER_METRICS.set_data_quality(
+            &source,
+            totals.score_sum / totals.series as f64,
+            totals.outliers,
+            totals.gaps,
+            totals.stale,
+        );
+    }
+
+    Ok(())
+}
+
+/// Check date-ordered observations of a series with the given frequency
+///
+/// Series of unknown or irregular frequency are only checked for outliers.
+pub fn assess(
+    observations: &[(NaiveDate, f64)],
+    frequency: &str,
+    today: NaiveDate,
+    config: &QualityConfig,
+) -> QualityAssessment {
+    let frequency = SourceFrequency::parse(frequency);
+    let last_observation_date = observations.last().map(|(date, _)| *date);
+    let outlier_dates = outlier_dates(observations, config);
+    let missing_periods = frequency.map_or(0, |f| missing_periods(observations, f));
+
+    let age_days = last_observation_date.map(|date| (today - date).num_days());
+    let max_age = frequency.map(max_age_days);
+    let is_stale = match (age_days, max_age) {
+        (Some(age), Some(max_age)) => age > max_age,
+        (None, _) => true,
+        _ => false,
+    };
+
+    let score = if observations.is_empty() {
+        0.0
+    } else {
+        let count = observations.len() as f64;
+        let completeness = count / (count + missing_periods as f64);
+        let cleanliness = 1.0 - (outlier_dates.len() as f64 / count * OUTLIER_PENALTY).min(1.0);
+        let freshness = match (age_days, max_age) {
+            (Some(age), Some(max_age)) if age > max_age => max_age as f64 / age as f64,
+            _ => 1.0,
+        };
+        let (w_complete, w_clean, w_fresh) = SCORE_WEIGHTS;
+        (w_complete * completeness + w_clean * cleanliness + w_fresh * freshness).clamp(0.0, 1.0)
+    };
+
+    QualityAssessment {
+        observation_count: observations.len(),
+        outlier_dates,
+        missing_periods,
+        last_observation_date,
+        is_stale,
+        score,
+    }
+}
+
+/// Observations whose change from the previous one fails both the IQR fence
+/// and the robust z-score check
+///
+/// Working on changes rather than levels keeps trending series from looking
+/// like outliers. A spike moves away and back, so only the first of two
+/// consecutive opposite flagged changes is reported.
+fn outlier_dates(observations: &[(NaiveDate, f64)], config: &QualityConfig) -> Vec<NaiveDate> {
+    if observations.len() < config.min_points_for_outliers.max(3) {
+        return Vec::new();
+    }
+
+    let changes: Vec<(NaiveDate, f64)> = observations
+        .windows(2)
+        .map(|pair| (pair[1].0, pair[1].1 - pair[0].1))
+        .collect();
+
+    let mut sorted: Vec<f64> = changes.iter().map(|(_, change)| *change).collect();
+    sorted.sort_by(f64::total_cmp);
+    let q1 = quantile(&sorted, 0.25);
+    let q3 = quantile(&sorted, 0.75);
+    let iqr = q3 - q1;
+    let median = quantile(&sorted, 0.5);
+
+    let mut deviations: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    let mad = quantile(&deviations, 0.5);
+    // Mostly unchanged series, such as policy rates, have no spread to measure against
+    if !mad.is_finite() || mad == 0.0 {
+        return Vec::new();
+    }
+
+    let lower_fence = q1 - config.iqr_multiplier * iqr;
+    let upper_fence = q3 + config.iqr_multiplier * iqr;
+
+    let mut dates = Vec::new();
+    let mut previous_flagged: Option<f64> = None;
+    for (date, change) in changes {
+        let outside_fence = change < lower_fence || change > upper_fence;
+        let robust_z = MAD_SCALE * (change - median) / mad;
+        let flagged = outside_fence && robust_z.abs() > config.z_threshold;
+
+        if flagged {
+            let reverts_spike =
+                previous_flagged.is_some_and(|prev| prev.signum() != change.signum());
+            if !reverts_spike {
+                dates.push(date);
+            }
+            previous_flagged = (!reverts_spike).then_some(change);
+        } else {
+            previous_flagged = None;
+        }
+    }
+
+    dates
+}
+
+/// Linear interpolation quantile of sorted values
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Periods between the first and last observation without an observation
+fn missing_periods(observations: &[(NaiveDate, f64)], frequency: SourceFrequency) -> usize {
+    let period_index = |date: NaiveDate| -> i64 {
+        let year = i64::from(date.year());
+        let month = i64::from(date.month0());
+        match frequency {
+            SourceFrequency::Monthly => year * 12 + month,
+            SourceFrequency::Quarterly => year * 4 + month / 3,
+            _ => year,
+        }
+    };
+
+    match frequency {
+        SourceFrequency::Monthly | SourceFrequency::Quarterly | SourceFrequency::Annual => {
+            let periods: HashSet<i64> = observations
+                .iter()
+                .map(|(date, _)| period_index(*date))
+                .collect();
+            match (periods.iter().min(), periods.iter().max()) {
+                (Some(first), Some(last)) => (last - first + 1) as usize - periods.len(),
+                _ => 0,
+            }
+        }
+        SourceFrequency::Weekly => observations
+            .windows(2)
+            .map(|pair| {
+                let weeks = ((pair[1].0 - pair[0].0).num_days() as f64 / 7.0).round() as usize;
+                weeks.saturating_sub(1)
+            })
+            .sum(),
+        SourceFrequency::Daily => observations
+            .windows(2)
+            .filter(|pair| (pair[1].0 - pair[0].0).num_days() > frequency.edge_tolerance_days())
+            .map(|pair| weekdays_between(pair[0].0, pair[1].0))
+            .sum(),
+    }
+}
+
+/// Weekdays strictly between two dates
+fn weekdays_between(start: NaiveDate, end: NaiveDate) -> usize {
+    start
+        .iter_days()
+        .skip(1)
+        .take_while(|date| *date < end)
+        .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+        .count()
+}
+
+/// Days after which the latest observation of a series is overdue, allowing
+/// for the usual publication lag
+fn max_age_days(frequency: SourceFrequency) -> i64 {
+    match frequency {
+        SourceFrequency::Daily => 14,
+        SourceFrequency::Weekly => 30,
+        SourceFrequency::Monthly => 90,
+        SourceFrequency::Quarterly => 200,
+        SourceFrequency::Annual => 550,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Two years of monthly values growing about 1 a month
+    fn monthly_series() -> Vec<(NaiveDate, f64)> {
+        (0..24)
+            .map(|i| {
+                let value = 100.0 + i as f64 + [0.2, -0.1, 0.0, 0.1, -0.2, 0.3][i % 6];
+                (date(2022 + i as i32 / 12, i as u32 % 12 + 1, 1), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_series_scores_full_marks() {
+        // REQUIREMENT: Series without issues are not flagged
+        // PURPOSE: Verify a steadily trending, complete and current series scores 1
+        // This ensures trends are not mistaken for outliers
+
+        let assessment = assess(
+            &monthly_series(),
+            "Monthly",
+            date(2024, 1, 15),
+            &QualityConfig::default(),
+        );
+
+        assert_eq!(assessment.observation_count, 24);
+        assert!(assessment.outlier_dates.is_empty());
+        assert_eq!(assessment.missing_periods, 0);
+        assert!(!assessment.is_stale);
+        assert_eq!(assessment.score, 1.0);
+    }
+
+    #[test]
+    fn test_outliers_gaps_and_staleness_lower_the_score() {
+        // REQUIREMENT: Outliers, gaps and stale data are detected and scored
+        // PURPOSE: Verify a spike is flagged once, missing months are counted and
+        // an overdue series is stale
+        // This ensures the quality score reflects each kind of problem
+
+        let mut observations = monthly_series();
+        observations[10].1 += 40.0;
+        observations.remove(20);
+
+        let assessment = assess(
+            &observations,
+            "Monthly",
+            date(2024, 12, 1),
+            &QualityConfig::default(),
+        );
+
+        assert_eq!(assessment.outlier_dates, vec![date(2022, 11, 1)]);
+        assert_eq!(assessment.missing_periods, 1);
+        assert!(assessment.is_stale);
+        assert!(assessment.score < 0.9);
+        assert!(assessment.score > 0.0);
+    }
+
+    #[test]
+    fn test_business_day_gaps() {
+        // REQUIREMENT: Daily series published on business days are not penalized for weekends
+        // PURPOSE: Verify weekends are ignored while a missing trading week is counted
+        // This ensures gap counts match the source's real publication schedule
+
+        // Mon 2024-01-01 to Fri 2024-01-12, without the second week's Monday to Thursday
+        let observations: Vec<(NaiveDate, f64)> = date(2024, 1, 1)
+            .iter_days()
+            .take(12)
+            .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+            .filter(|d| !(8..=11).contains(&d.day()))
+            .map(|d| (d, 1.0))
+            .collect();
+
+        assert_eq!(missing_periods(&observations, SourceFrequency::Daily), 4);
+        assert_eq!(
+            missing_periods(&observations[..5], SourceFrequency::Daily),
+            0
+        );
+    }
+}
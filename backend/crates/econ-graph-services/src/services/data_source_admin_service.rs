@@ -0,0 +1,635 @@
+/**
+ * REQUIREMENT: Administrators manage data sources without editing the database by hand
+ * PURPOSE: Create, update, enable/disable data sources, set crawl frequency, record
+ * which credential a source uses and store API keys encrypted, writing an audit log
+ * entry for every change
+ * API keys are either stored AES-GCM encrypted in data_source_credentials or kept in
+ * the environment variable named by api_key_name; they are decrypted only when used.
+ * Either may list several keys separated by commas, which the crawlers rotate between
+ */
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::services::crawler::api_key_ring::parse_api_keys;
+use econ_graph_core::{
+    database::DatabasePool,
+    enums::DataAccessPolicy,
+    error::{AppError, AppResult},
+    models::{
+        admin::AuditLog, DataSource, DataSourceCredential, NewDataSource, NewDataSourceCredential,
+        UpdateDataSource,
+    },
+    secrets::{shared_secret_cipher, SecretCipher, SecretString},
+};
+
+/// Longest API key accepted for storage
+pub const MAX_API_KEY_LENGTH: usize = 512;
+
+/// Most mirrors a data source may fail over to
+pub const MAX_FALLBACK_URLS: usize = 5;
+
+/// Value stored in `audit_logs.resource_type` for data source changes
+pub const DATA_SOURCE_RESOURCE_TYPE: &str = "data_source";
+
+/// Administrator performing a change, as recorded in the audit log
+#[derive(Debug, Clone)]
+pub struct AuditActor {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub ip_address: Option<String>,
+}
+
+pub struct DataSourceAdminService;
+
+impl DataSourceAdminService {
+    /// Create a data source
+    pub async fn create(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        new_source: NewDataSource,
+    ) -> AppResult<DataSource> {
+        if let Some(api_key_name) = &new_source.api_key_name {
+            validate_api_key_name(api_key_name)?;
+        }
+        if DataSource::find_by_name(pool, &new_source.name)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Data source '{}' already exists",
+                new_source.name
+            )));
+        }
+
+        let source = DataSource::create(pool, new_source).await?;
+
+        let details = serde_json::to_value(&source)?;
+        record_change(pool, actor, "create_data_source", &source, details).await?;
+
+        Ok(source)
+    }
+
+    /// Update a data source's settings
+    pub async fn update(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        changes: UpdateDataSource,
+    ) -> AppResult<DataSource> {
+        Self::apply(pool, actor, id, "update_data_source", changes).await
+    }
+
+    /// Enable or disable crawling and display of a data source
+    pub async fn set_enabled(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        enabled: bool,
+    ) -> AppResult<DataSource> {
+        let action = if enabled {
+            "enable_data_source"
+        } else {
+            "disable_data_source"
+        };
+        let changes = UpdateDataSource {
+            is_enabled: Some(enabled),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, action, changes).await
+    }
+
+    /// Set how often a data source is crawled
+    pub async fn set_crawl_frequency(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        crawl_frequency_hours: i32,
+    ) -> AppResult<DataSource> {
+        let changes = UpdateDataSource {
+            crawl_frequency_hours: Some(crawl_frequency_hours),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, "set_crawl_frequency", changes).await
+    }
+
+    /// Record which environment variable holds a data source's API key
+    ///
+    /// Passing `None` clears the reference. The key itself is never stored.
+    pub async fn set_api_credentials(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        api_key_required: bool,
+        api_key_name: Option<String>,
+    ) -> AppResult<DataSource> {
+        if let Some(api_key_name) = &api_key_name {
+            validate_api_key_name(api_key_name)?;
+        } else if api_key_required {
+            return Err(AppError::ValidationError(
+                "An API key name is required when the source requires an API key".to_string(),
+            ));
+        }
+
+        let changes = UpdateDataSource {
+            api_key_required: Some(api_key_required),
+            api_key_name: Some(api_key_name),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, "set_api_credentials", changes).await
+    }
+
+    /// Set a data source's daily byte and request quotas
+    ///
+    /// `None` makes the quota unlimited. Crawlers pick up the new quotas on
+    /// their next request.
+    pub async fn set_quotas(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        daily_byte_quota: Option<i64>,
+        daily_request_quota: Option<i32>,
+    ) -> AppResult<DataSource> {
+        validate_quota("Daily byte quota", daily_byte_quota)?;
+        validate_quota("Daily request quota", daily_request_quota)?;
+
+        let changes = UpdateDataSource {
+            daily_byte_quota: Some(daily_byte_quota),
+            daily_request_quota: Some(daily_request_quota),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, "set_data_source_quotas", changes).await
+    }
+
+    /// Set the mirrors a data source's crawlers fail over to
+    ///
+    /// Mirrors are tried in the order given when `base_url` keeps failing;
+    /// an empty list turns failover off. Crawlers pick up the new list the
+    /// next time they load the source.
+    pub async fn set_fallback_urls(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        fallback_urls: Vec<String>,
+    ) -> AppResult<DataSource> {
+        let fallback_urls = validate_fallback_urls(fallback_urls)?;
+
+        let changes = UpdateDataSource {
+            fallback_urls: Some(fallback_urls),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, "set_data_source_fallback_urls", changes).await
+    }
+
+    /// Set who may read a data source's observations
+    ///
+    /// Takes effect on the next request; users' subscription tiers come from
+    /// their access tokens.
+    pub async fn set_access_policy(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        access_policy: DataAccessPolicy,
+    ) -> AppResult<DataSource> {
+        let changes = UpdateDataSource {
+            access_policy: Some(access_policy),
+            ..empty_update()
+        };
+
+        Self::apply(pool, actor, id, "set_data_source_access_policy", changes).await
+    }
+
+    /// Store a data source's API key, encrypted with the current key
+    ///
+    /// Replaces any stored key. The audit log records which encryption key
+    /// was used, never the API key.
+    pub async fn store_api_key(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        cipher: &SecretCipher,
+        id: Uuid,
+        api_key: &SecretString,
+    ) -> AppResult<DataSource> {
+        let api_key = validate_api_key(api_key)?;
+        let source = DataSource::find_by_id(pool, id)
+            .await?
+            .ok_or_else(|| AppError::DataSourceNotFound(id.to_string()))?;
+
+        let encrypted = cipher.encrypt(&api_key, source.id.as_bytes())?;
+        let replaced = DataSourceCredential::find_by_data_source(pool, source.id)
+            .await?
+            .is_some();
+        let credential = DataSourceCredential::upsert(
+            pool,
+            &NewDataSourceCredential::new(source.id, encrypted, Some(actor.user_id)),
+        )
+        .await?;
+
+        let details = json!({ "key_id": credential.key_id, "replaced": replaced });
+        record_change(pool, actor, "store_api_key", &source, details).await?;
+
+        Ok(source)
+    }
+
+    /// Delete a data source's stored API key
+    ///
+    /// The source falls back to the environment variable named by
+    /// `api_key_name`, if any.
+    pub async fn remove_api_key(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+    ) -> AppResult<DataSource> {
+        let source = DataSource::find_by_id(pool, id)
+            .await?
+            .ok_or_else(|| AppError::DataSourceNotFound(id.to_string()))?;
+
+        let removed = DataSourceCredential::delete_for_data_source(pool, source.id).await?;
+
+        let details = json!({ "removed": removed });
+        record_change(pool, actor, "remove_api_key", &source, details).await?;
+
+        Ok(source)
+    }
+
+    /// Create the predefined data sources missing from the database
+    ///
+    /// Existing sources are left as they are, so seeding can be repeated.
+    /// Returns the sources created.
+    pub async fn seed_predefined(pool: &DatabasePool) -> AppResult<Vec<DataSource>> {
+        let mut created = Vec::new();
+        for new_source in DataSource::predefined() {
+            if DataSource::find_by_name(pool, &new_source.name)
+                .await?
+                .is_none()
+            {
+                created.push(DataSource::create(pool, new_source).await?);
+            }
+        }
+
+        Ok(created)
+    }
+
+    async fn apply(
+        pool: &DatabasePool,
+        actor: &AuditActor,
+        id: Uuid,
+        action: &str,
+        changes: UpdateDataSource,
+    ) -> AppResult<DataSource> {
+        if let Some(Some(api_key_name)) = &changes.api_key_name {
+            validate_api_key_name(api_key_name)?;
+        }
+
+        let before = DataSource::find_by_id(pool, id)
+            .await?
+            .ok_or_else(|| AppError::DataSourceNotFound(id.to_string()))?;
+        let after = DataSource::update(pool, id, changes)
+            .await?
+            .ok_or_else(|| AppError::DataSourceNotFound(id.to_string()))?;
+
+        let details = changed_fields(&before, &after)?;
+        record_change(pool, actor, action, &after, details).await?;
+
+        Ok(after)
+    }
+}
+
+/// Update that changes nothing but the timestamp
+///
+/// `UpdateDataSource::default()` is not used here because it sets a placeholder
+/// documentation URL.
+fn empty_update() -> UpdateDataSource {
+    UpdateDataSource {
+        api_documentation_url: None,
+        ..UpdateDataSource::default()
+    }
+}
+
+/// API key of a data source, decrypted for use
+///
+/// The first of the keys returned by [`resolve_api_keys`], for crawlers that
+/// do not rotate keys.
+pub async fn resolve_api_key(
+    pool: &DatabasePool,
+    source: &DataSource,
+) -> AppResult<Option<SecretString>> {
+    Ok(resolve_api_keys(pool, source).await?.into_iter().next())
+}
+
+/// API keys of a data source, decrypted for use
+///
+/// A key stored with [`DataSourceAdminService::store_api_key`] takes
+/// precedence over the environment variable named by `api_key_name`. Either
+/// may list several keys separated by commas. Returns no keys when neither
+/// is set.
+pub async fn resolve_api_keys(
+    pool: &DatabasePool,
+    source: &DataSource,
+) -> AppResult<Vec<SecretString>> {
+    if let Some(credential) = DataSourceCredential::find_by_data_source(pool, source.id).await? {
+        let cipher = shared_secret_cipher()?;
+        let keys = cipher.decrypt(&credential.encrypted(), source.id.as_bytes())?;
+        return Ok(parse_api_keys(keys.expose()));
+    }
+
+    Ok(source
+        .api_key_name
+        .as_deref()
+        .and_then(|name| std::env::var(name).ok())
+        .map(|keys| parse_api_keys(&keys))
+        .unwrap_or_default())
+}
+
+/// API keys are trimmed and must be single non-empty tokens
+///
+/// Several keys may be given separated by commas; each is validated and they
+/// are stored as one comma separated list.
+pub fn validate_api_key(api_key: &SecretString) -> AppResult<SecretString> {
+    let keys = api_key
+        .expose()
+        .split(',')
+        .map(validate_single_api_key)
+        .collect::<AppResult<Vec<_>>>()?;
+
+    Ok(SecretString::new(keys.join(",")))
+}
+
+fn validate_single_api_key(api_key: &str) -> AppResult<&str> {
+    let trimmed = api_key.trim();
+
+    if trimmed.is_empty() {
+        return Err(AppError::ValidationError(
+            "API key must not be empty".to_string(),
+        ));
+    }
+    if trimmed.len() > MAX_API_KEY_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "API key must be at most {} characters",
+            MAX_API_KEY_LENGTH
+        )));
+    }
+    if trimmed.chars().any(char::is_whitespace) {
+        return Err(AppError::ValidationError(
+            "API key must not contain whitespace".to_string(),
+        ));
+    }
+
+    Ok(trimmed)
+}
+
+/// Quotas must be positive; a zero quota would stop a source for good
+pub fn validate_quota<T>(name: &str, quota: Option<T>) -> AppResult<()>
+where
+    T: PartialOrd + Default,
+{
+    match quota {
+        Some(quota) if quota <= T::default() => Err(AppError::ValidationError(format!(
+            "{} must be positive; omit it for no limit",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Mirrors must be distinct absolute http(s) URLs, at most [`MAX_FALLBACK_URLS`]
+///
+/// Trailing slashes are removed, since request paths are appended to the URL.
+pub fn validate_fallback_urls(fallback_urls: Vec<String>) -> AppResult<Vec<String>> {
+    if fallback_urls.len() > MAX_FALLBACK_URLS {
+        return Err(AppError::ValidationError(format!(
+            "At most {} fallback URLs may be configured",
+            MAX_FALLBACK_URLS
+        )));
+    }
+
+    let mut validated: Vec<String> = Vec::with_capacity(fallback_urls.len());
+    for fallback_url in fallback_urls {
+        let trimmed = fallback_url.trim().trim_end_matches('/');
+        let valid = url::Url::parse(trimmed)
+            .is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https"))
+            && trimmed.len() <= 500;
+        if !valid {
+            return Err(AppError::ValidationError(format!(
+                "Fallback URL '{}' must be an http or https URL of at most 500 characters",
+                fallback_url
+            )));
+        }
+        if !validated.iter().any(|url| url == trimmed) {
+            validated.push(trimmed.to_string());
+        }
+    }
+
+    Ok(validated)
+}
+
+/// Credential references must be environment variable names such as `FRED_API_KEY`
+///
+/// This also keeps administrators from pasting the key itself into the field.
+pub fn validate_api_key_name(api_key_name: &str) -> AppResult<()> {
+    let valid = api_key_name.len() <= 255
+        && api_key_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_uppercase())
+        && api_key_name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        // The rejected value is not echoed: it may be the key itself
+        Err(AppError::ValidationError(
+            "API key name must be an environment variable name like FRED_API_KEY".to_string(),
+        ))
+    }
+}
+
+/// Fields that differ between two versions of a data source, as `{field: {from, to}}`
+pub fn changed_fields(before: &DataSource, after: &DataSource) -> AppResult<Value> {
+    let before = serde_json::to_value(before)?;
+    let after = serde_json::to_value(after)?;
+
+    let mut changes = Map::new();
+    if let (Value::Object(before), Value::Object(after)) = (before, after) {
+        for (field, new_value) in after {
+            if field == "updated_at" {
+                continue;
+            }
+            let old_value = before.get(&field).cloned().unwrap_or(Value::Null);
+            if old_value != new_value {
+                changes.insert(field, json!({ "from": old_value, "to": new_value }));
+            }
+        }
+    }
+
+    Ok(Value::Object(changes))
+}
+
+async fn record_change(
+    pool: &DatabasePool,
+    actor: &AuditActor,
+    action: &str,
+    source: &DataSource,
+    details: Value,
+) -> AppResult<()> {
+    AuditLog::create(
+        pool,
+        actor.user_id,
+        actor.user_name.clone(),
+        action.to_string(),
+        DATA_SOURCE_RESOURCE_TYPE.to_string(),
+        Some(source.id.to_string()),
+        actor.ip_address.clone(),
+        None,
+        Some(details),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn source() -> DataSource {
+        DataSource {
+            id: Uuid::new_v4(),
+            name: "Federal Reserve Economic Data (FRED)".to_string(),
+            description: None,
+            base_url: "https://api.stlouisfed.org/fred".to_string(),
+            api_key_required: true,
+            rate_limit_per_minute: 120,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_visible: true,
+            is_enabled: true,
+            requires_admin_approval: false,
+            crawl_frequency_hours: 6,
+            last_crawl_at: None,
+            crawl_status: None,
+            crawl_error_message: None,
+            api_documentation_url: None,
+            api_key_name: Some("FRED_API_KEY".to_string()),
+            daily_byte_quota: None,
+            daily_request_quota: None,
+            access_policy: DataAccessPolicy::Public,
+            fallback_urls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_changed_fields_lists_only_modified_settings() {
+        // REQUIREMENT: Every data source change leaves an audit trail
+        // PURPOSE: Verify audit details record old and new values of changed fields only
+        // This keeps audit entries readable when a single setting is toggled
+
+        let before = source();
+        let after = DataSource {
+            is_enabled: false,
+            crawl_frequency_hours: 24,
+            updated_at: Utc::now() + chrono::Duration::seconds(5),
+            ..before.clone()
+        };
+
+        let changes = changed_fields(&before, &after).unwrap();
+
+        assert_eq!(
+            changes,
+            json!({
+                "is_enabled": { "from": true, "to": false },
+                "crawl_frequency_hours": { "from": 6, "to": 24 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_api_key_name_must_be_environment_variable() {
+        // REQUIREMENT: Only references to API credentials are stored
+        // PURPOSE: Verify key names are accepted and raw key values are rejected
+        // This prevents secrets from ending up in data_sources and the audit log
+
+        assert!(validate_api_key_name("FRED_API_KEY").is_ok());
+        assert!(validate_api_key_name("BEA_API_KEY_2").is_ok());
+
+        assert!(validate_api_key_name("").is_err());
+        assert!(validate_api_key_name("fred_api_key").is_err());
+        assert!(validate_api_key_name("abcd1234ef567890abcd1234ef567890").is_err());
+        assert!(validate_api_key_name("2FRED").is_err());
+
+        let error = validate_api_key_name("abcd1234ef567890").unwrap_err();
+        assert!(!error.to_string().contains("abcd1234"));
+    }
+
+    #[test]
+    fn test_quota_validation() {
+        // REQUIREMENT: Administrators set per-source daily byte and request quotas
+        // PURPOSE: Verify positive and absent quotas are accepted and others rejected
+        // This keeps a typo from disabling a source indefinitely
+
+        assert!(validate_quota("Daily byte quota", Some(500_000_000_i64)).is_ok());
+        assert!(validate_quota::<i32>("Daily request quota", None).is_ok());
+
+        assert!(validate_quota("Daily byte quota", Some(0_i64)).is_err());
+        assert!(validate_quota("Daily request quota", Some(-1_i32)).is_err());
+    }
+
+    #[test]
+    fn test_fallback_url_validation() {
+        // REQUIREMENT: Administrators configure mirrors a data source fails over to
+        // PURPOSE: Verify mirrors are normalized and deduplicated, and non-http or excess URLs are rejected
+        // This keeps request paths appended to a mirror from producing broken URLs
+
+        assert_eq!(
+            validate_fallback_urls(vec![
+                " https://mirror.example.com/fred/ ".to_string(),
+                "https://mirror.example.com/fred".to_string(),
+                "http://backup.example.org/fred".to_string(),
+            ])
+            .unwrap(),
+            vec![
+                "https://mirror.example.com/fred".to_string(),
+                "http://backup.example.org/fred".to_string(),
+            ]
+        );
+        assert!(validate_fallback_urls(Vec::new()).unwrap().is_empty());
+
+        assert!(validate_fallback_urls(vec!["ftp://mirror.example.com".to_string()]).is_err());
+        assert!(validate_fallback_urls(vec!["mirror.example.com/fred".to_string()]).is_err());
+        let too_many = (0..=MAX_FALLBACK_URLS)
+            .map(|i| format!("https://mirror{}.example.com", i))
+            .collect();
+        assert!(validate_fallback_urls(too_many).is_err());
+    }
+
+    #[test]
+    fn test_api_key_validation() {
+        // REQUIREMENT: Stored API keys are usable as-is by the crawlers
+        // PURPOSE: Verify keys are trimmed, comma separated lists are validated key by key, and empty, oversized or multi-word values are rejected
+        // This catches paste mistakes before the key is encrypted and stored
+
+        assert_eq!(
+            validate_api_key(&SecretString::new("  abcd1234 \n"))
+                .unwrap()
+                .expose(),
+            "abcd1234"
+        );
+        assert_eq!(
+            validate_api_key(&SecretString::new("key-a, key-b"))
+                .unwrap()
+                .expose(),
+            "key-a,key-b"
+        );
+        assert!(validate_api_key(&SecretString::new("   ")).is_err());
+        assert!(validate_api_key(&SecretString::new("key-a,,key-b")).is_err());
+        assert!(validate_api_key(&SecretString::new("abc def")).is_err());
+        assert!(validate_api_key(&SecretString::new("k".repeat(MAX_API_KEY_LENGTH + 1))).is_err());
+    }
+}
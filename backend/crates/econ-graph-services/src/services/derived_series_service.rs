@@ -0,0 +1,658 @@
+/**
+ * REQUIREMENT: Users build composite indicators as formulas over existing series
+ * PURPOSE: Compute derived series such as real GDP = nominal / deflator * 100 and keep
+ * them current: when a series gets new observations, every derived series depending on
+ * it, directly or through other derived series, is recomputed
+ * Values live in an ordinary economic series, so charts, transformations, alerts and
+ * webhooks work on derived series unchanged. Formulas that would make a series depend
+ * on itself are rejected. A derived series is as restricted as its most restricted
+ * input, so a formula cannot republish licensed data to a wider audience.
+ */
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use tracing::{info, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::services::data_point_cache::shared_data_point_cache;
+use crate::services::response_cache::shared_response_cache;
+use crate::services::series_alert_service::{evaluate_alerts_after_update, latest_observations};
+use crate::services::series_service;
+use crate::services::webhook_service::publish_series_updated;
+use econ_graph_core::{
+    database::DatabasePool,
+    enums::DataAccessPolicy,
+    error::{AppError, AppResult},
+    models::{
+        parse_formula, DataPoint, DataSource, DerivedSeries, DerivedSeriesInput, EconomicSeries,
+        FormulaBinding, FormulaExpr, NewDataPoint, NewDataSource, NewDerivedSeries,
+        NewEconomicSeries, UpdateDataSource, UpdateEconomicSeries,
+    },
+    schema::{data_points, economic_series},
+};
+
+/// Data source of the economic series holding derived values from public inputs
+///
+/// Derived series with restricted inputs live in a source per access policy,
+/// named by [`derived_source_name`].
+pub const DERIVED_SOURCE_NAME: &str = "EconGraph Derived";
+
+/// Decimal places stored for computed values, matching `data_points.value`
+const DERIVED_VALUE_SCALE: i64 = 6;
+
+/// Most derived series recomputed after one update, as a guard against runaway chains
+const MAX_RECOMPUTES_PER_UPDATE: usize = 1000;
+
+/// A derived series as defined by a user
+#[derive(Debug, Clone)]
+pub struct DerivedSeriesDefinition {
+    pub title: String,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    pub formula: String,
+    pub inputs: Vec<FormulaBinding>,
+}
+
+/// Changes to a derived series; unset fields are kept
+#[derive(Debug, Clone, Default)]
+pub struct DerivedSeriesChanges {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    pub formula: Option<String>,
+    pub inputs: Option<Vec<FormulaBinding>>,
+}
+
+/// Formula values on every date all inputs have an observation, oldest first
+///
+/// `inputs` pairs each formula variable with its latest observations. Dates
+/// where the formula has no finite value, e.g. a division by zero, are left out.
+pub fn compute_values(
+    expr: &FormulaExpr,
+    inputs: &[(String, Vec<(NaiveDate, BigDecimal)>)],
+) -> Vec<(NaiveDate, BigDecimal)> {
+    let mut by_date: BTreeMap<NaiveDate, HashMap<&str, f64>> = BTreeMap::new();
+    for (variable, observations) in inputs {
+        for (date, value) in observations {
+            if let Some(value) = value.to_f64() {
+                by_date
+                    .entry(*date)
+                    .or_default()
+                    .insert(variable.as_str(), value);
+            }
+        }
+    }
+
+    by_date
+        .into_iter()
+        .filter(|(_, values)| values.len() == inputs.len())
+        .filter_map(|(date, values)| {
+            let value = BigDecimal::from_f64(expr.evaluate(&values)?)?;
+            Some((date, value.round(DERIVED_VALUE_SCALE)))
+        })
+        .collect()
+}
+
+/// Chain of series through which `series_id` would depend on itself
+///
+/// `graph` maps each derived series' value series to its input series, as
+/// returned by [`DerivedSeries::dependency_graph`]; `inputs` are the proposed
+/// inputs of `series_id`. A returned chain starts and ends with `series_id`.
+pub fn find_cycle(
+    graph: &HashMap<Uuid, Vec<Uuid>>,
+    series_id: Uuid,
+    inputs: &[Uuid],
+) -> Option<Vec<Uuid>> {
+    let mut visited = HashSet::new();
+    let mut path = vec![series_id];
+    inputs
+        .iter()
+        .any(|&input| reaches(graph, input, series_id, &mut visited, &mut path))
+        .then_some(path)
+}
+
+fn reaches(
+    graph: &HashMap<Uuid, Vec<Uuid>>,
+    from: Uuid,
+    target: Uuid,
+    visited: &mut HashSet<Uuid>,
+    path: &mut Vec<Uuid>,
+) -> bool {
+    path.push(from);
+    if from == target {
+        return true;
+    }
+    if visited.insert(from) {
+        for &next in graph.get(&from).into_iter().flatten() {
+            if reaches(graph, next, target, visited, path) {
+                return true;
+            }
+        }
+    }
+    path.pop();
+    false
+}
+
+/// Latest revision per observation date
+fn latest_revisions(points: Vec<DataPoint>) -> BTreeMap<NaiveDate, DataPoint> {
+    let mut latest: BTreeMap<NaiveDate, DataPoint> = BTreeMap::new();
+    for point in points {
+        match latest.get(&point.date) {
+            Some(existing) if existing.revision_date >= point.revision_date => {}
+            _ => {
+                latest.insert(point.date, point);
+            }
+        }
+    }
+    latest
+}
+
+/// Most restrictive of the given access policies; public when there are none
+pub fn strictest_access_policy(
+    policies: impl IntoIterator<Item = DataAccessPolicy>,
+) -> DataAccessPolicy {
+    policies.into_iter().max().unwrap_or_default()
+}
+
+/// Name of the data source holding derived series with `policy`
+pub fn derived_source_name(policy: DataAccessPolicy) -> String {
+    match policy {
+        DataAccessPolicy::Public => DERIVED_SOURCE_NAME.to_string(),
+        policy => format!("{} ({})", DERIVED_SOURCE_NAME, policy.as_str()),
+    }
+}
+
+fn derived_source(policy: DataAccessPolicy) -> NewDataSource {
+    NewDataSource {
+        name: derived_source_name(policy),
+        description: Some("Series computed from formulas over other series".to_string()),
+        base_url: "https://econgraph.com".to_string(),
+        is_visible: true,
+        // Derived series are recomputed from their inputs, never crawled
+        is_enabled: false,
+        api_documentation_url: None,
+        ..Default::default()
+    }
+}
+
+fn not_found(id: Uuid) -> AppError {
+    AppError::NotFound(format!("Derived series {} not found", id))
+}
+
+/// Creates derived series and keeps their values current
+pub struct DerivedSeriesService {
+    pool: DatabasePool,
+}
+
+impl DerivedSeriesService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Create a derived series and compute its values
+    ///
+    /// The new series takes the frequency of its first input. A new series has
+    /// no dependents yet, so it cannot close a cycle.
+    pub async fn create(
+        &self,
+        definition: DerivedSeriesDefinition,
+        created_by: Option<Uuid>,
+    ) -> AppResult<(DerivedSeries, Vec<DerivedSeriesInput>)> {
+        let (expr, input_series) = self
+            .check_definition(&definition.formula, &definition.inputs)
+            .await?;
+        let source = self.derived_source_for(&input_series).await?;
+
+        let series = NewEconomicSeries {
+            source_id: source.id,
+            external_id: format!("derived-{}", Uuid::new_v4()),
+            title: definition.title.trim().to_string(),
+            description: definition.description,
+            units: definition.units,
+            frequency: input_series[0].frequency.clone(),
+            ..Default::default()
+        };
+        let new_derived = NewDerivedSeries {
+            formula: definition.formula.trim().to_string(),
+            formula_ast: serde_json::to_value(&expr)?,
+            created_by,
+        };
+        let (derived, inputs) =
+            DerivedSeries::create(&self.pool, &series, &new_derived, &definition.inputs).await?;
+
+        info!(
+            "Created derived series {} = {}",
+            derived.series_id, derived.formula
+        );
+        self.refresh(&derived).await;
+
+        let derived = DerivedSeries::find_by_id(&self.pool, derived.id)
+            .await?
+            .ok_or_else(|| not_found(derived.id))?;
+        Ok((derived, inputs))
+    }
+
+    /// Update a derived series, recomputing it if its formula or inputs changed
+    ///
+    /// A new formula replaces every value computed from the old one.
+    pub async fn update(
+        &self,
+        id: Uuid,
+        changes: DerivedSeriesChanges,
+    ) -> AppResult<(DerivedSeries, Vec<DerivedSeriesInput>)> {
+        let derived = DerivedSeries::find_by_id(&self.pool, id)
+            .await?
+            .ok_or_else(|| not_found(id))?;
+
+        // Check a new formula before changing anything
+        let redefinition = if changes.formula.is_some() || changes.inputs.is_some() {
+            let formula = changes.formula.unwrap_or_else(|| derived.formula.clone());
+            let bindings = match changes.inputs {
+                Some(bindings) => bindings,
+                None => DerivedSeries::inputs(&self.pool, &[id])
+                    .await?
+                    .into_iter()
+                    .map(|input| FormulaBinding {
+                        variable: input.variable,
+                        series_id: input.input_series_id,
+                    })
+                    .collect(),
+            };
+            let (expr, input_series) = self.check_definition(&formula, &bindings).await?;
+
+            let graph = DerivedSeries::dependency_graph(&self.pool).await?;
+            let input_ids: Vec<Uuid> = bindings.iter().map(|binding| binding.series_id).collect();
+            if let Some(cycle) = find_cycle(&graph, derived.series_id, &input_ids) {
+                let chain: Vec<String> = cycle.iter().map(Uuid::to_string).collect();
+                return Err(AppError::ValidationError(format!(
+                    "Formula would make the series depend on itself: {}",
+                    chain.join(" -> ")
+                )));
+            }
+
+            Some((formula, bindings, expr, input_series))
+        } else {
+            None
+        };
+
+        if changes.title.is_some() || changes.description.is_some() || changes.units.is_some() {
+            let update = UpdateEconomicSeries {
+                title: changes.title.map(|title| title.trim().to_string()),
+                description: changes.description,
+                units: changes.units,
+                updated_at: Utc::now(),
+                ..Default::default()
+            };
+            update.validate()?;
+            EconomicSeries::update(&self.pool, derived.series_id, &update).await?;
+        }
+
+        let Some((formula, bindings, expr, input_series)) = redefinition else {
+            let inputs = DerivedSeries::inputs(&self.pool, &[id]).await?;
+            return Ok((derived, inputs));
+        };
+
+        let (derived, inputs) = DerivedSeries::redefine(
+            &self.pool,
+            id,
+            formula.trim(),
+            serde_json::to_value(&expr)?,
+            &bindings,
+        )
+        .await?;
+        self.move_to_source(derived.series_id, &input_series)
+            .await?;
+        // The old values are gone even if the new formula yields none
+        shared_data_point_cache().invalidate_series(derived.series_id);
+        shared_response_cache().invalidate_series(derived.series_id);
+        self.refresh(&derived).await;
+
+        let derived = DerivedSeries::find_by_id(&self.pool, id)
+            .await?
+            .ok_or_else(|| not_found(id))?;
+        Ok((derived, inputs))
+    }
+
+    /// Delete a derived series and its values
+    ///
+    /// Fails while other derived series use it as an input.
+    pub async fn delete(&self, id: Uuid) -> AppResult<bool> {
+        let Some(derived) = DerivedSeries::find_by_id(&self.pool, id).await? else {
+            return Ok(false);
+        };
+
+        let dependents = DerivedSeries::find_dependents(&self.pool, derived.series_id).await?;
+        if !dependents.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Derived series {} is an input of {} other derived series",
+                id,
+                dependents.len()
+            )));
+        }
+
+        DerivedSeries::delete(&self.pool, id).await
+    }
+
+    /// Recompute a derived series from the latest observations of its inputs
+    ///
+    /// Returns the number of values written. Unchanged values are left alone;
+    /// a changed value becomes a new revision. The outcome is recorded on the
+    /// derived series.
+    #[tracing::instrument(name = "derived_series.recompute", skip(self, derived), fields(id = %derived.id))]
+    pub async fn recompute(&self, derived: &DerivedSeries) -> AppResult<usize> {
+        let result = self.compute_and_store(derived).await;
+        let error = result.as_ref().err().map(|e| e.to_string());
+        DerivedSeries::record_computation(&self.pool, derived.id, error.as_deref()).await?;
+        result
+    }
+
+    /// Recompute every derived series depending on `series_id`
+    ///
+    /// Series that change are passed on to their own dependents, and their
+    /// alert rules and webhooks run as for crawled series. Returns the number
+    /// of recomputes.
+    pub async fn recompute_dependents(&self, series_id: Uuid) -> AppResult<usize> {
+        let mut updated = VecDeque::from([series_id]);
+        let mut recomputes = 0;
+
+        while let Some(series_id) = updated.pop_front() {
+            for derived in DerivedSeries::find_dependents(&self.pool, series_id).await? {
+                if recomputes == MAX_RECOMPUTES_PER_UPDATE {
+                    warn!(
+                        "Stopped recomputing derived series after {} recomputes",
+                        recomputes
+                    );
+                    return Ok(recomputes);
+                }
+                recomputes += 1;
+
+                match self.recompute(&derived).await {
+                    Ok(0) => {}
+                    Ok(written) => {
+                        self.after_update(derived.series_id, written).await;
+                        updated.push_back(derived.series_id);
+                    }
+                    Err(e) => warn!("Failed to recompute derived series {}: {}", derived.id, e),
+                }
+            }
+        }
+
+        Ok(recomputes)
+    }
+
+    /// Recompute a derived series and everything depending on it
+    ///
+    /// A failed recompute is recorded on the derived series rather than
+    /// failing the change that triggered it.
+    async fn refresh(&self, derived: &DerivedSeries) {
+        match self.recompute(derived).await {
+            Ok(0) => {}
+            Ok(written) => {
+                self.after_update(derived.series_id, written).await;
+                recompute_derived_after_update(&self.pool, derived.series_id).await;
+            }
+            Err(e) => warn!("Failed to recompute derived series {}: {}", derived.id, e),
+        }
+    }
+
+    /// Run what the crawlers run after storing observations, except recomputing dependents
+    async fn after_update(&self, series_id: Uuid, written: usize) {
+        shared_data_point_cache().invalidate_series(series_id);
+        shared_response_cache().invalidate_series(series_id);
+        evaluate_alerts_after_update(&self.pool, series_id).await;
+        publish_series_updated(&self.pool, series_id, written).await;
+    }
+
+    /// Data source for a derived series over `input_series`, with the strictest of their policies
+    ///
+    /// An input whose source is gone has no policy to inherit. A source an
+    /// admin has made stricter keeps its policy.
+    async fn derived_source_for(&self, input_series: &[EconomicSeries]) -> AppResult<DataSource> {
+        let mut policies = Vec::with_capacity(input_series.len());
+        for series in input_series {
+            if let Some(source) = DataSource::find_by_id(&self.pool, series.source_id).await? {
+                policies.push(source.access_policy);
+            }
+        }
+        let policy = strictest_access_policy(policies);
+
+        let source = DataSource::get_or_create(&self.pool, derived_source(policy)).await?;
+        if source.access_policy >= policy {
+            return Ok(source);
+        }
+
+        // New sources start public, and an admin may have loosened the policy since
+        let changes = UpdateDataSource {
+            access_policy: Some(policy),
+            api_documentation_url: None,
+            ..UpdateDataSource::default()
+        };
+        DataSource::update(&self.pool, source.id, changes)
+            .await?
+            .ok_or_else(|| AppError::DataSourceNotFound(source.id.to_string()))
+    }
+
+    /// Move a derived series to the source matching its new inputs' policies
+    async fn move_to_source(
+        &self,
+        series_id: Uuid,
+        input_series: &[EconomicSeries],
+    ) -> AppResult<()> {
+        let source = self.derived_source_for(input_series).await?;
+
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+        diesel::update(economic_series::table.find(series_id))
+            .filter(economic_series::source_id.ne(source.id))
+            .set(economic_series::source_id.eq(source.id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Parse a formula against its bindings and load the bound series, in binding order
+    async fn check_definition(
+        &self,
+        formula: &str,
+        bindings: &[FormulaBinding],
+    ) -> AppResult<(FormulaExpr, Vec<EconomicSeries>)> {
+        let variables: Vec<&str> = bindings
+            .iter()
+            .map(|binding| binding.variable.as_str())
+            .collect();
+        let expr = parse_formula(formula.trim(), &variables)?;
+
+        let mut input_series = Vec::with_capacity(bindings.len());
+        for binding in bindings {
+            let series = series_service::get_series_by_id(&self.pool, binding.series_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Input series {} not found", binding.series_id))
+                })?;
+            input_series.push(series);
+        }
+
+        Ok((expr, input_series))
+    }
+
+    async fn compute_and_store(&self, derived: &DerivedSeries) -> AppResult<usize> {
+        let expr = derived.expression()?;
+
+        let mut inputs = Vec::new();
+        for input in DerivedSeries::inputs(&self.pool, &[derived.id]).await? {
+            let points = self.series_points(input.input_series_id).await?;
+            inputs.push((input.variable, latest_observations(points)));
+        }
+        let values = compute_values(&expr, &inputs);
+
+        let existing = latest_revisions(self.series_points(derived.series_id).await?);
+        let today = Utc::now().date_naive();
+        let mut new_points = Vec::new();
+        let mut revised_today = Vec::new();
+        for (date, value) in &values {
+            match existing.get(date) {
+                Some(point) if point.value.as_ref() == Some(value) => {}
+                // A second recompute on one day revises that day's revision in place
+                Some(point) if point.revision_date == today => {
+                    revised_today.push((point.id, value.clone()));
+                }
+                point => new_points.push(NewDataPoint {
+                    series_id: derived.series_id,
+                    date: *date,
+                    value: Some(value.clone()),
+                    revision_date: today,
+                    is_original_release: point.is_none(),
+                }),
+            }
+        }
+
+        let written = new_points.len() + revised_today.len();
+        if written == 0 {
+            return Ok(0);
+        }
+
+        if !new_points.is_empty() {
+            DataPoint::create_batch(&self.pool, &new_points).await?;
+        }
+        if !revised_today.is_empty() {
+            let mut conn = self.pool.get().await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+            })?;
+            for (id, value) in revised_today {
+                diesel::update(data_points::table.find(id))
+                    .set(data_points::value.eq(Some(value)))
+                    .execute(&mut conn)
+                    .await?;
+            }
+        }
+
+        if let (Some((start, _)), Some((end, _))) = (values.first(), values.last()) {
+            EconomicSeries::update_date_range(&self.pool, derived.series_id, *start, *end).await?;
+        }
+
+        Ok(written)
+    }
+
+    async fn series_points(&self, series_id: Uuid) -> AppResult<Vec<DataPoint>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let points = data_points::table
+            .filter(data_points::series_id.eq(series_id))
+            .select(DataPoint::as_select())
+            .load::<DataPoint>(&mut conn)
+            .await?;
+
+        Ok(points)
+    }
+}
+
+/// Recompute derived series after new observations were stored for a series
+///
+/// Failures are logged rather than returned so derived series never fail a crawl.
+pub async fn recompute_derived_after_update(pool: &DatabasePool, series_id: Uuid) {
+    if let Err(e) = DerivedSeriesService::new(pool.clone())
+        .recompute_dependents(series_id)
+        .await
+    {
+        warn!(
+            "Failed to recompute derived series of series {}: {}",
+            series_id, e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn observations(values: &[(u32, &str)]) -> Vec<(NaiveDate, BigDecimal)> {
+        values
+            .iter()
+            .map(|(month, value)| {
+                (
+                    NaiveDate::from_ymd_opt(2024, *month, 1).unwrap(),
+                    BigDecimal::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_derived_values_align_on_common_dates() {
+        // REQUIREMENT: Composite indicators computed from existing series
+        // PURPOSE: Verify values are computed on dates all inputs share and undefined results are skipped
+        // This ensures real GDP lines up with both nominal GDP and the deflator
+
+        let expr = FormulaExpr::parse("nominal / deflator * 100").unwrap();
+        let inputs = vec![
+            (
+                "nominal".to_string(),
+                observations(&[(1, "27000"), (4, "27500"), (7, "28100")]),
+            ),
+            (
+                "deflator".to_string(),
+                observations(&[(1, "120"), (4, "0"), (7, "122.5"), (10, "123")]),
+            ),
+        ];
+
+        let values = compute_values(&expr, &inputs);
+        assert_eq!(values, observations(&[(1, "22500"), (7, "22938.775510")]));
+    }
+
+    #[test]
+    fn test_derived_series_take_strictest_input_policy() {
+        // REQUIREMENT: Derived series must not expose licensed inputs to a wider audience
+        // PURPOSE: Verify the strictest input policy wins and each policy has its own source
+        // This ensures a formula over premium data is itself premium
+
+        assert_eq!(
+            strictest_access_policy([DataAccessPolicy::Public, DataAccessPolicy::Premium]),
+            DataAccessPolicy::Premium
+        );
+        assert_eq!(
+            strictest_access_policy([DataAccessPolicy::Registered, DataAccessPolicy::Public]),
+            DataAccessPolicy::Registered
+        );
+        assert_eq!(strictest_access_policy([]), DataAccessPolicy::Public);
+
+        assert_eq!(
+            derived_source_name(DataAccessPolicy::Public),
+            DERIVED_SOURCE_NAME
+        );
+        assert_eq!(
+            derived_source_name(DataAccessPolicy::Premium),
+            "EconGraph Derived (premium)"
+        );
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        // REQUIREMENT: Derived series must not depend on themselves
+        // PURPOSE: Verify direct and indirect cycles are found and acyclic inputs pass
+        // This ensures recomputation always terminates
+
+        let [gdp, deflator, real_gdp, per_capita, population] = [(); 5].map(|_| Uuid::new_v4());
+        let graph = HashMap::from([
+            (real_gdp, vec![gdp, deflator]),
+            (per_capita, vec![real_gdp, population]),
+        ]);
+
+        assert_eq!(find_cycle(&graph, real_gdp, &[gdp, deflator]), None);
+        assert_eq!(
+            find_cycle(&graph, real_gdp, &[per_capita]),
+            Some(vec![real_gdp, per_capita, real_gdp])
+        );
+        assert_eq!(
+            find_cycle(&graph, real_gdp, &[real_gdp]),
+            Some(vec![real_gdp, real_gdp])
+        );
+    }
+}
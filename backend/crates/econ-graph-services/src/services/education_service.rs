@@ -0,0 +1,491 @@
+/**
+ * REQUIREMENT: Learners work through the educational content library and see their progress
+ * PURPOSE: Serve learning paths and modules, record progress through modules and award
+ * achievements when modules, quizzes and paths are completed
+ * Content is compiled into the backend; only progress and achievements are stored
+ */
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        educational_content::EducationalContentLibrary, AchievementType, EducationalModule,
+        LearningAchievement, LearningAchievementRecord, LearningPath, LearningProgress,
+        LearningProgressRecord, LearningStatus, NewLearningAchievement, NewLearningProgress,
+        QuizScore,
+    },
+};
+
+/// Longest study session a single progress update may report
+pub const MAX_MINUTES_PER_UPDATE: i32 = 24 * 60;
+
+/// Answer to an assessment question
+#[derive(Debug, Clone)]
+pub struct QuizAnswer {
+    pub question_id: Uuid,
+    pub answer: serde_json::Value,
+    pub time_taken_seconds: i32,
+}
+
+/// Progress a learner made in a module since the last update
+#[derive(Debug, Clone, Default)]
+pub struct ProgressUpdate {
+    pub module_id: Uuid,
+    pub completed_section_ids: Vec<Uuid>,
+    pub completed_exercise_ids: Vec<Uuid>,
+    pub quiz_answers: Vec<QuizAnswer>,
+    pub time_spent_minutes: i32,
+}
+
+/// Stored progress after an update and the achievements it earned
+#[derive(Debug, Clone)]
+pub struct ProgressOutcome {
+    pub progress: LearningProgress,
+    pub new_achievements: Vec<LearningAchievement>,
+}
+
+/// Apply an update to a learner's progress through `module`
+///
+/// Sections, exercises and answered questions accumulate across updates; a
+/// question's latest answer replaces earlier ones. The module is completed
+/// once every section and exercise is done and every question is answered.
+pub fn apply_update(
+    module: &EducationalModule,
+    user_id: Uuid,
+    existing: Option<LearningProgress>,
+    update: &ProgressUpdate,
+    now: DateTime<Utc>,
+) -> AppResult<LearningProgress> {
+    if !(0..=MAX_MINUTES_PER_UPDATE).contains(&update.time_spent_minutes) {
+        return Err(AppError::ValidationError(format!(
+            "Time spent must be between 0 and {} minutes",
+            MAX_MINUTES_PER_UPDATE
+        )));
+    }
+
+    let section_ids: HashSet<Uuid> = module.content_sections.iter().map(|s| s.id).collect();
+    let exercise_ids: HashSet<Uuid> = module.interactive_exercises.iter().map(|e| e.id).collect();
+    check_belongs(&update.completed_section_ids, &section_ids, "section")?;
+    check_belongs(&update.completed_exercise_ids, &exercise_ids, "exercise")?;
+
+    let mut progress = existing.unwrap_or_else(|| LearningProgress {
+        id: Uuid::nil(),
+        user_id,
+        module_id: Some(module.id),
+        learning_path_id: None,
+        status: LearningStatus::NotStarted,
+        progress_percentage: 0.0,
+        time_spent_minutes: 0,
+        completed_sections: Vec::new(),
+        completed_exercises: Vec::new(),
+        quiz_scores: Vec::new(),
+        started_at: now,
+        last_accessed_at: now,
+        completed_at: None,
+    });
+
+    for id in &update.completed_section_ids {
+        if !progress.completed_sections.contains(id) {
+            progress.completed_sections.push(*id);
+        }
+    }
+    for id in &update.completed_exercise_ids {
+        if !progress.completed_exercises.contains(id) {
+            progress.completed_exercises.push(*id);
+        }
+    }
+
+    for answer in &update.quiz_answers {
+        let question = module
+            .assessment_questions
+            .iter()
+            .find(|q| q.id == answer.question_id)
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "Question {} is not part of module {}",
+                    answer.question_id, module.id
+                ))
+            })?;
+        let is_correct = answer.answer == question.correct_answer;
+
+        progress
+            .quiz_scores
+            .retain(|score| score.question_id != question.id);
+        progress.quiz_scores.push(QuizScore {
+            question_id: question.id,
+            user_answer: answer.answer.clone(),
+            is_correct,
+            points_earned: if is_correct { question.points } else { 0 },
+            time_taken_seconds: answer.time_taken_seconds.max(0),
+            attempted_at: now,
+        });
+    }
+
+    let total = section_ids.len() + exercise_ids.len() + module.assessment_questions.len();
+    let done = progress.completed_sections.len()
+        + progress.completed_exercises.len()
+        + progress.quiz_scores.len();
+
+    progress.progress_percentage = if total == 0 {
+        100.0
+    } else {
+        (done as f64 / total as f64 * 100.0).min(100.0)
+    };
+    progress.time_spent_minutes = progress
+        .time_spent_minutes
+        .saturating_add(update.time_spent_minutes);
+    progress.last_accessed_at = now;
+
+    if done >= total {
+        progress.status = LearningStatus::Completed;
+        progress.completed_at.get_or_insert(now);
+    } else if progress.status != LearningStatus::Completed {
+        progress.status = LearningStatus::InProgress;
+    }
+
+    Ok(progress)
+}
+
+fn check_belongs(ids: &[Uuid], known: &HashSet<Uuid>, kind: &str) -> AppResult<()> {
+    match ids.iter().find(|id| !known.contains(id)) {
+        Some(id) => Err(AppError::ValidationError(format!(
+            "Unknown {} {} for this module",
+            kind, id
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Achievements a learner qualifies for after progressing through `module`
+///
+/// `all_progress` is the learner's progress through every module, including
+/// `module`. Achievements already earned are returned again; storing them is
+/// idempotent.
+pub fn earned_achievements(
+    user_id: Uuid,
+    module: &EducationalModule,
+    all_progress: &[LearningProgress],
+    paths: &[LearningPath],
+) -> Vec<NewLearningAchievement> {
+    let completed: HashSet<Uuid> = all_progress
+        .iter()
+        .filter(|progress| progress.status == LearningStatus::Completed)
+        .filter_map(|progress| progress.module_id)
+        .collect();
+    if !completed.contains(&module.id) {
+        return Vec::new();
+    }
+
+    let mut achievements = vec![NewLearningAchievement {
+        user_id,
+        achievement_key: format!("module_completion:{}", module.id),
+        achievement_type: AchievementType::ModuleCompletion.as_str().to_string(),
+        title: format!("Completed {}", module.title),
+        description: format!("Finished every section of \"{}\"", module.title),
+        criteria: "Complete all sections, exercises and assessment questions".to_string(),
+        module_id: Some(module.id),
+        learning_path_id: None,
+        badge_url: None,
+    }];
+
+    let all_correct = all_progress
+        .iter()
+        .find(|progress| progress.module_id == Some(module.id))
+        .is_some_and(|progress| progress.quiz_scores.iter().all(|score| score.is_correct));
+    if !module.assessment_questions.is_empty() && all_correct {
+        achievements.push(NewLearningAchievement {
+            user_id,
+            achievement_key: format!("perfect_score:{}", module.id),
+            achievement_type: AchievementType::PerfectScore.as_str().to_string(),
+            title: format!("Perfect score in {}", module.title),
+            description: format!("Answered every question of \"{}\" correctly", module.title),
+            criteria: "Complete the module with every question answered correctly".to_string(),
+            module_id: Some(module.id),
+            learning_path_id: None,
+            badge_url: None,
+        });
+    }
+
+    for path in paths {
+        let in_path = path.modules.iter().any(|m| m.module_id == module.id);
+        let path_done = path
+            .modules
+            .iter()
+            .filter(|m| m.is_required)
+            .all(|m| completed.contains(&m.module_id));
+        if in_path && path_done {
+            achievements.push(NewLearningAchievement {
+                user_id,
+                achievement_key: format!("path_completion:{}", path.id),
+                achievement_type: AchievementType::PathCompletion.as_str().to_string(),
+                title: format!("Completed {}", path.title),
+                description: format!("Finished every required module of \"{}\"", path.title),
+                criteria: "Complete all required modules of the learning path".to_string(),
+                module_id: None,
+                learning_path_id: Some(path.id),
+                badge_url: None,
+            });
+        }
+    }
+
+    achievements
+}
+
+/// Serves the educational content library and tracks learners' progress
+pub struct EducationService {
+    pool: DatabasePool,
+}
+
+impl EducationService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// All learning paths
+    pub fn learning_paths(&self) -> Vec<LearningPath> {
+        EducationalContentLibrary::learning_paths()
+    }
+
+    /// Learning path with the given ID
+    pub fn learning_path(&self, id: Uuid) -> Option<LearningPath> {
+        EducationalContentLibrary::find_learning_path(id)
+    }
+
+    /// Module with the given ID
+    pub fn module(&self, id: Uuid) -> Option<EducationalModule> {
+        EducationalContentLibrary::find_module(id)
+    }
+
+    /// Progress of a user through every module they started, most recent first
+    pub async fn progress_for_user(&self, user_id: Uuid) -> AppResult<Vec<LearningProgress>> {
+        LearningProgressRecord::list_for_user(&self.pool, user_id)
+            .await?
+            .into_iter()
+            .map(LearningProgressRecord::into_progress)
+            .collect()
+    }
+
+    /// Achievements of a user, most recent first
+    pub async fn achievements_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> AppResult<Vec<LearningAchievement>> {
+        LearningAchievementRecord::list_for_user(&self.pool, user_id)
+            .await?
+            .into_iter()
+            .map(LearningAchievementRecord::into_achievement)
+            .collect()
+    }
+
+    /// Record progress of a user through a module and award any achievements earned
+    #[tracing::instrument(name = "education.record_progress", skip(self, update), fields(module_id = %update.module_id))]
+    pub async fn record_progress(
+        &self,
+        user_id: Uuid,
+        update: ProgressUpdate,
+    ) -> AppResult<ProgressOutcome> {
+        let module = self.module(update.module_id).ok_or_else(|| {
+            AppError::NotFound(format!("Learning module {} not found", update.module_id))
+        })?;
+
+        let existing = LearningProgressRecord::find_for_user_module(&self.pool, user_id, module.id)
+            .await?
+            .map(LearningProgressRecord::into_progress)
+            .transpose()?;
+        let progress = apply_update(&module, user_id, existing, &update, Utc::now())?;
+
+        let stored = LearningProgressRecord::upsert(
+            &self.pool,
+            &NewLearningProgress::from_progress(&progress, module.id)?,
+        )
+        .await?
+        .into_progress()?;
+
+        let mut new_achievements = Vec::new();
+        if stored.status == LearningStatus::Completed {
+            let all_progress = self.progress_for_user(user_id).await?;
+            for achievement in
+                earned_achievements(user_id, &module, &all_progress, &self.learning_paths())
+            {
+                if let Some(awarded) =
+                    LearningAchievementRecord::award(&self.pool, &achievement).await?
+                {
+                    new_achievements.push(awarded.into_achievement()?);
+                }
+            }
+        }
+
+        Ok(ProgressOutcome {
+            progress: stored,
+            new_achievements,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module() -> EducationalModule {
+        EducationalContentLibrary::modules()
+            .into_iter()
+            .find(|module| module.title == "Enterprise Value Fundamentals")
+            .unwrap()
+    }
+
+    fn full_update(module: &EducationalModule, answer: serde_json::Value) -> ProgressUpdate {
+        ProgressUpdate {
+            module_id: module.id,
+            completed_section_ids: module.content_sections.iter().map(|s| s.id).collect(),
+            completed_exercise_ids: module.interactive_exercises.iter().map(|e| e.id).collect(),
+            quiz_answers: module
+                .assessment_questions
+                .iter()
+                .map(|q| QuizAnswer {
+                    question_id: q.id,
+                    answer: answer.clone(),
+                    time_taken_seconds: 30,
+                })
+                .collect(),
+            time_spent_minutes: 45,
+        }
+    }
+
+    #[test]
+    fn test_progress_accumulates_until_module_is_completed() {
+        // REQUIREMENT: Learners see how far they are through a module
+        // PURPOSE: Verify updates accumulate, completion needs every question answered
+        // and a later answer replaces an earlier one
+
+        let module = module();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let first = ProgressUpdate {
+            module_id: module.id,
+            completed_section_ids: vec![module.content_sections[0].id],
+            time_spent_minutes: 10,
+            ..Default::default()
+        };
+        let progress = apply_update(&module, user_id, None, &first, now).unwrap();
+        assert_eq!(progress.status, LearningStatus::InProgress);
+        assert!(progress.progress_percentage > 0.0 && progress.progress_percentage < 100.0);
+
+        let wrong = full_update(&module, serde_json::json!(0));
+        let progress = apply_update(&module, user_id, Some(progress), &wrong, now).unwrap();
+        assert_eq!(progress.status, LearningStatus::Completed);
+        assert_eq!(progress.progress_percentage, 100.0);
+        assert_eq!(
+            progress.completed_sections.len(),
+            module.content_sections.len()
+        );
+        assert_eq!(progress.time_spent_minutes, 55);
+        assert!(progress.quiz_scores.iter().all(|score| !score.is_correct));
+        assert!(progress.completed_at.is_some());
+
+        let right = full_update(&module, serde_json::json!(1));
+        let progress = apply_update(&module, user_id, Some(progress), &right, now).unwrap();
+        assert_eq!(progress.status, LearningStatus::Completed);
+        assert_eq!(
+            progress.quiz_scores.len(),
+            module.assessment_questions.len()
+        );
+        assert!(progress.quiz_scores.iter().all(|score| score.is_correct));
+    }
+
+    #[test]
+    fn test_progress_rejects_content_from_other_modules() {
+        // REQUIREMENT: Progress can only be recorded for content of the module being studied
+        // PURPOSE: Verify unknown section IDs and implausible durations are rejected
+
+        let module = module();
+        let update = ProgressUpdate {
+            module_id: module.id,
+            completed_section_ids: vec![Uuid::new_v4()],
+            ..Default::default()
+        };
+        assert!(apply_update(&module, Uuid::new_v4(), None, &update, Utc::now()).is_err());
+
+        let update = ProgressUpdate {
+            module_id: module.id,
+            time_spent_minutes: MAX_MINUTES_PER_UPDATE + 1,
+            ..Default::default()
+        };
+        assert!(apply_update(&module, Uuid::new_v4(), None, &update, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_achievements_for_module_and_path_completion() {
+        // REQUIREMENT: Learners earn achievements for completing modules, quizzes and paths
+        // PURPOSE: Verify which achievements a completed module earns
+        // This ensures path completion is only awarded once every required module is done
+
+        let user_id = Uuid::new_v4();
+        let paths = EducationalContentLibrary::learning_paths();
+        let modules = EducationalContentLibrary::modules();
+        let now = Utc::now();
+
+        let complete = |module: &EducationalModule| {
+            apply_update(
+                module,
+                user_id,
+                None,
+                &full_update(module, serde_json::json!(1)),
+                now,
+            )
+            .unwrap()
+        };
+
+        let first = &modules[0];
+        let missed = apply_update(
+            first,
+            user_id,
+            None,
+            &full_update(first, serde_json::json!(0)),
+            now,
+        )
+        .unwrap();
+        let keys: Vec<String> = earned_achievements(user_id, first, &[missed], &paths)
+            .into_iter()
+            .map(|a| a.achievement_key)
+            .collect();
+        assert_eq!(keys, vec![format!("module_completion:{}", first.id)]);
+
+        let progress = vec![complete(first)];
+        let keys: Vec<String> = earned_achievements(user_id, first, &progress, &paths)
+            .into_iter()
+            .map(|a| a.achievement_key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                format!("module_completion:{}", first.id),
+                format!("perfect_score:{}", first.id),
+            ]
+        );
+
+        let buffett = &paths[0];
+        let progress: Vec<LearningProgress> = buffett
+            .modules
+            .iter()
+            .map(|m| {
+                complete(
+                    modules
+                        .iter()
+                        .find(|module| module.id == m.module_id)
+                        .unwrap(),
+                )
+            })
+            .collect();
+        let achievements = earned_achievements(user_id, first, &progress, &paths);
+        assert!(achievements
+            .iter()
+            .any(|a| a.achievement_key == format!("path_completion:{}", buffett.id)));
+        assert!(!achievements
+            .iter()
+            .any(|a| a.achievement_key == format!("path_completion:{}", paths[1].id)));
+    }
+}
@@ -0,0 +1,241 @@
+//! Email templates
+//!
+//! Each template renders a subject, a plain-text body and an HTML body from
+//! plain values, so rendering never touches the database. Every value is
+//! escaped before it goes into the HTML body.
+
+use chrono::{DateTime, Utc};
+
+/// Notifications listed in a weekly digest before the rest are summarized
+pub const DIGEST_MAX_ITEMS: usize = 20;
+
+const FOOTER: &str =
+    "You receive this email because of your EconGraph email preferences. You can change them in your account settings.";
+
+/// A rendered email, ready to be queued
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+}
+
+/// Someone shared a chart with the recipient
+#[derive(Debug, Clone)]
+pub struct InviteEmail<'a> {
+    pub inviter_name: &'a str,
+    /// `None` when the chart is not a saved chart with a title
+    pub chart_title: Option<&'a str>,
+    /// Permission granted, e.g. `edit`
+    pub permission: &'a str,
+    pub chart_url: &'a str,
+}
+
+/// One of the recipient's series alert rules fired
+#[derive(Debug, Clone)]
+pub struct AlertTriggeredEmail<'a> {
+    pub rule_name: &'a str,
+    /// What the rule observed, as in the in-app notification
+    pub message: &'a str,
+    pub series_url: &'a str,
+}
+
+/// Notification listed in a weekly digest
+#[derive(Debug, Clone)]
+pub struct DigestItem {
+    pub title: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Weekly summary of the recipient's notifications
+#[derive(Debug, Clone)]
+pub struct WeeklyDigestEmail<'a> {
+    pub recipient_name: &'a str,
+    /// Newest first
+    pub items: &'a [DigestItem],
+    pub unread_count: i64,
+    pub app_url: &'a str,
+}
+
+/// Escape text for an HTML body or attribute
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// HTML document around already escaped content, with one call to action
+fn html_layout(heading: &str, content_html: &str, action_label: &str, action_url: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><body style=\"font-family: sans-serif; color: #1f2933;\">\n\
+         <h2>{}</h2>\n{}\n\
+         <p><a href=\"{}\" style=\"color: #1565c0;\">{}</a></p>\n\
+         <p style=\"font-size: 12px; color: #7b8794;\">{}</p>\n\
+         </body></html>\n",
+        escape_html(heading),
+        content_html,
+        escape_html(action_url),
+        escape_html(action_label),
+        escape_html(FOOTER)
+    )
+}
+
+/// Email telling a user a chart was shared with them
+pub fn render_invite(email: &InviteEmail<'_>) -> RenderedEmail {
+    let chart = email.chart_title.unwrap_or("a chart");
+    let subject = format!("{} shared {} with you", email.inviter_name, chart);
+    let text_body = format!(
+        "{} shared {} with you on EconGraph with {} access.\n\nOpen the chart: {}\n\n{}\n",
+        email.inviter_name, chart, email.permission, email.chart_url, FOOTER
+    );
+    let content = format!(
+        "<p>{} shared <strong>{}</strong> with you on EconGraph with {} access.</p>",
+        escape_html(email.inviter_name),
+        escape_html(chart),
+        escape_html(email.permission)
+    );
+
+    RenderedEmail {
+        html_body: html_layout(&subject, &content, "Open the chart", email.chart_url),
+        subject,
+        text_body,
+    }
+}
+
+/// Email telling a user one of their alert rules fired
+pub fn render_alert_triggered(email: &AlertTriggeredEmail<'_>) -> RenderedEmail {
+    let subject = format!("Alert triggered: {}", email.rule_name);
+    let text_body = format!(
+        "Your alert \"{}\" was triggered.\n\n{}\n\nView the series: {}\n\n{}\n",
+        email.rule_name, email.message, email.series_url, FOOTER
+    );
+    let content = format!(
+        "<p>Your alert <strong>{}</strong> was triggered.</p>\n<p>{}</p>",
+        escape_html(email.rule_name),
+        escape_html(email.message)
+    );
+
+    RenderedEmail {
+        html_body: html_layout(&subject, &content, "View the series", email.series_url),
+        subject,
+        text_body,
+    }
+}
+
+/// Weekly summary of a user's notifications
+///
+/// Lists the newest [`DIGEST_MAX_ITEMS`] notifications and counts the rest.
+pub fn render_weekly_digest(email: &WeeklyDigestEmail<'_>) -> RenderedEmail {
+    let subject = match email.items.len() {
+        1 => "Your EconGraph week: 1 notification".to_string(),
+        n => format!("Your EconGraph week: {} notifications", n),
+    };
+    let listed = &email.items[..email.items.len().min(DIGEST_MAX_ITEMS)];
+    let more = email.items.len() - listed.len();
+
+    let mut text_body = format!(
+        "Hi {},\n\nHere is what happened this week. You have {} unread notifications.\n\n",
+        email.recipient_name, email.unread_count
+    );
+    let mut content = format!(
+        "<p>Hi {},</p>\n<p>Here is what happened this week. You have {} unread notifications.</p>\n<ul>\n",
+        escape_html(email.recipient_name),
+        email.unread_count
+    );
+    for item in listed {
+        let date = item.created_at.format("%b %-d");
+        text_body.push_str(&format!("- {} ({}): {}\n", item.title, date, item.message));
+        content.push_str(&format!(
+            "<li><strong>{}</strong> ({}): {}</li>\n",
+            escape_html(&item.title),
+            date,
+            escape_html(&item.message)
+        ));
+    }
+    content.push_str("</ul>");
+    if more > 0 {
+        text_body.push_str(&format!("- and {} more\n", more));
+        content.push_str(&format!("\n<p>And {} more.</p>", more));
+    }
+    text_body.push_str(&format!(
+        "\nOpen EconGraph: {}\n\n{}\n",
+        email.app_url, FOOTER
+    ));
+
+    RenderedEmail {
+        html_body: html_layout(&subject, &content, "Open EconGraph", email.app_url),
+        subject,
+        text_body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_escape_html() {
+        // REQUIREMENT: Templated emails for invites, triggered alerts and the weekly digest
+        // PURPOSE: Verify user-provided values appear verbatim in text and escaped in HTML
+        // This ensures a chart title or alert name cannot inject markup into an email
+
+        let invite = render_invite(&InviteEmail {
+            inviter_name: "Ada",
+            chart_title: Some("GDP <script>"),
+            permission: "edit",
+            chart_url: "https://econgraph.example/analysis/1",
+        });
+        assert_eq!(invite.subject, "Ada shared GDP <script> with you");
+        assert!(invite.text_body.contains("GDP <script>"));
+        assert!(invite.html_body.contains("GDP &lt;script&gt;"));
+        assert!(!invite.html_body.contains("<script>"));
+
+        let alert = render_alert_triggered(&AlertTriggeredEmail {
+            rule_name: "CPI \"spike\"",
+            message: "CPI rose 0.6% > 0.5%",
+            series_url: "https://econgraph.example/series/2",
+        });
+        assert_eq!(alert.subject, "Alert triggered: CPI \"spike\"");
+        assert!(alert.html_body.contains("CPI rose 0.6% &gt; 0.5%"));
+        assert!(alert
+            .html_body
+            .contains("href=\"https://econgraph.example/series/2\""));
+    }
+
+    #[test]
+    fn test_weekly_digest_summarizes_overflow() {
+        // REQUIREMENT: Weekly digest email of a user's notifications
+        // PURPOSE: Verify the digest lists at most DIGEST_MAX_ITEMS notifications and counts the rest
+        // This ensures a busy week produces a readable email
+
+        let items: Vec<DigestItem> = (0..25)
+            .map(|i| DigestItem {
+                title: format!("Notification {}", i),
+                message: "Details".to_string(),
+                created_at: Utc::now(),
+            })
+            .collect();
+
+        let digest = render_weekly_digest(&WeeklyDigestEmail {
+            recipient_name: "Ada",
+            items: &items,
+            unread_count: 7,
+            app_url: "https://econgraph.example/",
+        });
+
+        assert_eq!(digest.subject, "Your EconGraph week: 25 notifications");
+        assert_eq!(digest.html_body.matches("<li>").count(), DIGEST_MAX_ITEMS);
+        assert!(digest.text_body.contains("- and 5 more"));
+        assert!(digest.text_body.contains("7 unread"));
+    }
+}
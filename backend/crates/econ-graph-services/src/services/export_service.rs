@@ -0,0 +1,762 @@
+/**
+ * REQUIREMENT: Large exports are produced in the background instead of timing out over HTTP
+ * PURPOSE: Queue export jobs for a set of series and a date range, have a worker write them
+ * to export storage as CSV, Parquet or XLSX, and hand out signed, time-limited download URLs
+ * Files are deleted once their retention period ends. Storage sits behind the ExportStorage
+ * trait; the filesystem implementation suits a local disk or a mounted bucket.
+ */
+use arrow::array::{ArrayRef, Date32Array, Float64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use futures::TryStreamExt;
+use parquet::arrow::ArrowWriter;
+use rand::Rng;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::webhook_service::{hmac_sha256_hex, verify_hmac_sha256_hex};
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        DataPoint, ExportArtifact, ExportFormat, ExportJob, NewExportJob,
+        DATA_POINT_STREAM_BATCH_SIZE,
+    },
+    schema::economic_series,
+};
+
+/// How often the export worker looks for new jobs by default
+pub const DEFAULT_EXPORT_WORKER_INTERVAL_SECONDS: u64 = 5;
+
+/// How long export files are kept by default
+pub const DEFAULT_EXPORT_RETENTION_HOURS: i64 = 24;
+
+/// How long a signed download URL stays valid
+pub const DOWNLOAD_URL_TTL_SECONDS: i64 = 15 * 60;
+
+/// Pending and running exports one user may have at a time
+pub const MAX_UNFINISHED_EXPORTS_PER_USER: i64 = 5;
+
+/// Most rows in one export
+pub const MAX_EXPORT_ROWS: usize = 5_000_000;
+
+/// Rows of one XLSX worksheet, including the header
+const XLSX_MAX_ROWS: usize = 1_048_576;
+
+/// Rows per Parquet row group
+const PARQUET_ROW_GROUP_ROWS: usize = 65_536;
+
+/// How long a worker may take on a job before another worker claims it again
+const EXPORT_JOB_LEASE_MINUTES: i64 = 30;
+
+/// Jobs one worker run produces, so that expiry still runs during a backlog
+const EXPORT_JOBS_PER_RUN: usize = 10;
+
+/// Expired files deleted per worker run
+const EXPIRED_EXPORTS_PER_RUN: i64 = 100;
+
+/// Column names, in file order
+const EXPORT_COLUMNS: [&str; 6] = [
+    "series_id",
+    "external_id",
+    "title",
+    "date",
+    "value",
+    "revision_date",
+];
+
+/// One observation in an export: the latest revision for a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRow {
+    pub series_id: Uuid,
+    pub external_id: String,
+    pub title: String,
+    pub date: NaiveDate,
+    pub value: Option<BigDecimal>,
+    pub revision_date: NaiveDate,
+}
+
+/// Write `rows` in `format`
+pub fn render_export(format: ExportFormat, rows: &[ExportRow]) -> AppResult<Vec<u8>> {
+    match format {
+        ExportFormat::Csv => render_csv(rows),
+        ExportFormat::Parquet => render_parquet(rows),
+        ExportFormat::Xlsx => render_xlsx(rows),
+    }
+}
+
+fn render_error(format: ExportFormat, e: impl std::fmt::Display) -> AppError {
+    AppError::InternalError(format!("Failed to write {} export: {}", format, e))
+}
+
+fn render_csv(rows: &[ExportRow]) -> AppResult<Vec<u8>> {
+    let error = |e: csv::Error| render_error(ExportFormat::Csv, e);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer.write_record(EXPORT_COLUMNS).map_err(error)?;
+    for row in rows {
+        writer
+            .write_record([
+                row.series_id.to_string(),
+                row.external_id.clone(),
+                row.title.clone(),
+                row.date.to_string(),
+                row.value
+                    .as_ref()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                row.revision_date.to_string(),
+            ])
+            .map_err(error)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| render_error(ExportFormat::Csv, e))
+}
+
+/// Parquet DATE value: days since 1970-01-01
+fn days_since_epoch(date: NaiveDate) -> i32 {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+    (date - epoch).num_days() as i32
+}
+
+fn render_parquet(rows: &[ExportRow]) -> AppResult<Vec<u8>> {
+    fn error(e: impl std::fmt::Display) -> AppError {
+        render_error(ExportFormat::Parquet, e)
+    }
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new(EXPORT_COLUMNS[0], DataType::Utf8, false),
+        Field::new(EXPORT_COLUMNS[1], DataType::Utf8, false),
+        Field::new(EXPORT_COLUMNS[2], DataType::Utf8, false),
+        Field::new(EXPORT_COLUMNS[3], DataType::Date32, false),
+        Field::new(EXPORT_COLUMNS[4], DataType::Float64, true),
+        Field::new(EXPORT_COLUMNS[5], DataType::Date32, false),
+    ]));
+
+    let mut writer = ArrowWriter::try_new(Vec::new(), schema.clone(), None).map_err(error)?;
+    for chunk in rows.chunks(PARQUET_ROW_GROUP_ROWS) {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                chunk.iter().map(|row| row.series_id.to_string()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                chunk.iter().map(|row| row.external_id.as_str()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                chunk.iter().map(|row| row.title.as_str()),
+            )),
+            Arc::new(Date32Array::from_iter_values(
+                chunk.iter().map(|row| days_since_epoch(row.date)),
+            )),
+            Arc::new(
+                chunk
+                    .iter()
+                    .map(|row| row.value.as_ref().and_then(|v| v.to_f64()))
+                    .collect::<Float64Array>(),
+            ),
+            Arc::new(Date32Array::from_iter_values(
+                chunk.iter().map(|row| days_since_epoch(row.revision_date)),
+            )),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(error)?;
+        writer.write(&batch).map_err(error)?;
+        writer.flush().map_err(error)?;
+    }
+
+    writer.into_inner().map_err(error)
+}
+
+fn render_xlsx(rows: &[ExportRow]) -> AppResult<Vec<u8>> {
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, Worksheet, XlsxError};
+
+    if rows.len() >= XLSX_MAX_ROWS {
+        return Err(AppError::ValidationError(format!(
+            "XLSX exports hold at most {} rows; this export has {}. Use CSV or Parquet",
+            XLSX_MAX_ROWS - 1,
+            rows.len()
+        )));
+    }
+
+    // Excel has no dates before 1900; those are written as text
+    fn write_date(
+        worksheet: &mut Worksheet,
+        row: u32,
+        col: u16,
+        date: NaiveDate,
+        format: &Format,
+    ) -> Result<(), XlsxError> {
+        use chrono::Datelike;
+
+        match ExcelDateTime::from_ymd(date.year() as u16, date.month() as u8, date.day() as u8) {
+            Ok(excel_date) if date.year() >= 1900 => {
+                worksheet.write_datetime_with_format(row, col, &excel_date, format)?;
+            }
+            _ => {
+                worksheet.write_string(row, col, date.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    let write = || -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let date_format = Format::new().set_num_format("yyyy-mm-dd");
+        let worksheet = workbook.add_worksheet();
+
+        for (col, name) in EXPORT_COLUMNS.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *name)?;
+        }
+        for (index, row) in rows.iter().enumerate() {
+            let line = index as u32 + 1;
+            worksheet.write_string(line, 0, row.series_id.to_string())?;
+            worksheet.write_string(line, 1, &row.external_id)?;
+            worksheet.write_string(line, 2, &row.title)?;
+            write_date(worksheet, line, 3, row.date, &date_format)?;
+            if let Some(value) = row.value.as_ref().and_then(|v| v.to_f64()) {
+                worksheet.write_number(line, 4, value)?;
+            }
+            write_date(worksheet, line, 5, row.revision_date, &date_format)?;
+        }
+
+        workbook.save_to_buffer()
+    };
+
+    write().map_err(|e| render_error(ExportFormat::Xlsx, e))
+}
+
+/// Where export files are kept
+#[async_trait]
+pub trait ExportStorage: Send + Sync {
+    /// Store a file under `key`, replacing any existing one
+    async fn put(&self, key: &str, contents: &[u8]) -> AppResult<()>;
+
+    /// Read the file under `key`
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>>;
+
+    /// Delete the file under `key`; deleting a missing file succeeds
+    async fn delete(&self, key: &str) -> AppResult<()>;
+}
+
+/// Export storage in a directory
+#[derive(Debug, Clone)]
+pub struct FilesystemExportStorage {
+    root: PathBuf,
+}
+
+impl FilesystemExportStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Storage in `EXPORT_STORAGE_DIR`, or a directory under the system temp dir
+    pub fn from_env() -> Self {
+        let root = std::env::var("EXPORT_STORAGE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir().join("econ-graph-exports"));
+        Self::new(root)
+    }
+
+    /// Path of a key; keys are relative paths without `..` components
+    fn path(&self, key: &str) -> AppResult<PathBuf> {
+        let valid = !key.is_empty()
+            && !key.starts_with('/')
+            && key
+                .split('/')
+                .all(|part| !part.is_empty() && part != "." && part != "..")
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+        if !valid {
+            return Err(AppError::ValidationError(format!(
+                "Invalid export storage key '{}'",
+                key
+            )));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl ExportStorage for FilesystemExportStorage {
+    async fn put(&self, key: &str, contents: &[u8]) -> AppResult<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Write beside the target and rename, so readers never see a partial file
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, contents).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(contents) => Ok(contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(AppError::NotFound(format!("Export file {} not found", key)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Signs export download URLs
+///
+/// A URL carries its expiry and an HMAC-SHA256 of `"{job_id}.{expires}"`, so
+/// the download endpoint can check it without a session.
+pub struct DownloadSigner {
+    secret: Vec<u8>,
+    /// Prefix for download URLs, e.g. `https://api.econgraph.com`; empty for relative URLs
+    base_url: String,
+}
+
+impl DownloadSigner {
+    pub fn new(secret: impl Into<Vec<u8>>, base_url: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Signer keyed by `EXPORT_DOWNLOAD_SECRET`, with URLs under `EXPORT_DOWNLOAD_BASE_URL`
+    ///
+    /// Without a configured secret a random one is used, so links stop working
+    /// when the server restarts and are only valid on the instance that made them.
+    pub fn from_env() -> Self {
+        let secret = match std::env::var("EXPORT_DOWNLOAD_SECRET") {
+            Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+            _ => {
+                warn!(
+                    "EXPORT_DOWNLOAD_SECRET not set; export download links use a per-process key"
+                );
+                rand::thread_rng().gen::<[u8; 32]>().to_vec()
+            }
+        };
+        let base_url = std::env::var("EXPORT_DOWNLOAD_BASE_URL").unwrap_or_default();
+        Self::new(secret, base_url)
+    }
+
+    /// Hex signature of a download link for `job_id` valid until `expires` (Unix seconds)
+    pub fn signature(&self, job_id: Uuid, expires: i64) -> String {
+        hmac_sha256_hex(&self.secret, format!("{}.{}", job_id, expires).as_bytes())
+    }
+
+    /// Whether `signature` is the signature of a link for `job_id` valid until `expires`
+    ///
+    /// Compared in constant time, so timing does not help forge a link.
+    pub fn verify(&self, job_id: Uuid, expires: i64, signature: &str) -> bool {
+        verify_hmac_sha256_hex(
+            &self.secret,
+            format!("{}.{}", job_id, expires).as_bytes(),
+            signature,
+        )
+    }
+
+    /// Download URL for a job's file, valid until `expires_at`
+    pub fn download_url(&self, job_id: Uuid, expires_at: DateTime<Utc>) -> String {
+        let expires = expires_at.timestamp();
+        format!(
+            "{}/exports/{}/download?expires={}&signature={}",
+            self.base_url,
+            job_id,
+            expires,
+            self.signature(job_id, expires)
+        )
+    }
+
+    /// Download URL and its expiry for a completed job, if its file is still kept
+    ///
+    /// The URL expires after [`DOWNLOAD_URL_TTL_SECONDS`] or with the file,
+    /// whichever comes first.
+    pub fn download_url_for(
+        &self,
+        job: &ExportJob,
+        now: DateTime<Utc>,
+    ) -> Option<(String, DateTime<Utc>)> {
+        if !job.is_downloadable(now) {
+            return None;
+        }
+        let expires_at = job
+            .expires_at?
+            .min(now + Duration::seconds(DOWNLOAD_URL_TTL_SECONDS));
+        Some((self.download_url(job.id, expires_at), expires_at))
+    }
+}
+
+/// Process-wide download signer
+pub fn shared_download_signer() -> &'static DownloadSigner {
+    static SIGNER: OnceLock<DownloadSigner> = OnceLock::new();
+    SIGNER.get_or_init(DownloadSigner::from_env)
+}
+
+/// Export requests from users
+pub struct ExportService {
+    pool: DatabasePool,
+}
+
+impl ExportService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue an export of `series_ids` between optional dates
+    pub async fn request(
+        &self,
+        user_id: Uuid,
+        series_ids: Vec<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        format: ExportFormat,
+    ) -> AppResult<ExportJob> {
+        let mut unique_ids = Vec::with_capacity(series_ids.len());
+        for id in series_ids {
+            if !unique_ids.contains(&id) {
+                unique_ids.push(id);
+            }
+        }
+
+        let new_job = NewExportJob {
+            user_id,
+            series_ids: unique_ids,
+            start_date,
+            end_date,
+            format: format.as_str().to_string(),
+        };
+        new_job.validate_request()?;
+
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+        let existing = economic_series::table
+            .filter(economic_series::id.eq_any(&new_job.series_ids))
+            .select(economic_series::id)
+            .load::<Uuid>(&mut conn)
+            .await?;
+        if let Some(missing) = new_job.series_ids.iter().find(|id| !existing.contains(id)) {
+            return Err(AppError::SeriesNotFound(missing.to_string()));
+        }
+        drop(conn);
+
+        if ExportJob::count_unfinished(&self.pool, user_id).await?
+            >= MAX_UNFINISHED_EXPORTS_PER_USER
+        {
+            return Err(AppError::ValidationError(format!(
+                "At most {} exports can be in progress at once",
+                MAX_UNFINISHED_EXPORTS_PER_USER
+            )));
+        }
+
+        let job = ExportJob::create(&self.pool, &new_job).await?;
+        info!(
+            "Queued {} export {} of {} series for user {}",
+            format,
+            job.id,
+            job.series_ids.len(),
+            user_id
+        );
+        Ok(job)
+    }
+}
+
+/// Outcome of one worker run
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExportRunSummary {
+    pub completed: usize,
+    pub failed: usize,
+    pub expired: usize,
+}
+
+/// Produces queued exports and deletes expired files
+pub struct ExportWorker {
+    pool: DatabasePool,
+    storage: Arc<dyn ExportStorage>,
+    retention: Duration,
+}
+
+impl ExportWorker {
+    pub fn new(pool: DatabasePool, storage: Arc<dyn ExportStorage>, retention: Duration) -> Self {
+        Self {
+            pool,
+            storage,
+            retention,
+        }
+    }
+
+    /// Produce pending exports, then delete files past their retention
+    pub async fn run(&self) -> AppResult<ExportRunSummary> {
+        let lease = Duration::minutes(EXPORT_JOB_LEASE_MINUTES);
+        let mut summary = ExportRunSummary::default();
+
+        let abandoned = ExportJob::fail_abandoned(&self.pool, lease).await?;
+        summary.failed += abandoned;
+
+        for _ in 0..EXPORT_JOBS_PER_RUN {
+            let Some(job) = ExportJob::claim_next(&self.pool, lease).await? else {
+                break;
+            };
+
+            match self.produce(&job).await {
+                Ok(artifact) => {
+                    ExportJob::mark_completed(&self.pool, job.id, &artifact).await?;
+                    info!(
+                        "Export {} completed: {} rows, {} bytes",
+                        job.id, artifact.row_count, artifact.file_size_bytes
+                    );
+                    summary.completed += 1;
+                }
+                Err(e) => {
+                    warn!("Export {} failed: {}", job.id, e);
+                    ExportJob::mark_failed(&self.pool, job.id, &e.to_string()).await?;
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        summary.expired = self.remove_expired().await?;
+        Ok(summary)
+    }
+
+    /// Write a job's file to storage
+    async fn produce(&self, job: &ExportJob) -> AppResult<ExportArtifact> {
+        let format = job.export_format()?;
+        let rows =
+            load_export_rows(&self.pool, &job.series_ids, job.start_date, job.end_date).await?;
+
+        let contents = tokio::task::spawn_blocking(move || {
+            render_export(format, &rows).map(|c| (c, rows.len()))
+        })
+        .await
+        .map_err(|e| AppError::InternalError(format!("Export rendering panicked: {}", e)))?;
+        let (contents, row_count) = contents?;
+
+        let storage_key = format!("exports/{}.{}", job.id, format.as_str());
+        self.storage.put(&storage_key, &contents).await?;
+
+        Ok(ExportArtifact {
+            storage_key,
+            file_size_bytes: contents.len() as i64,
+            row_count: row_count as i64,
+            expires_at: Utc::now() + self.retention,
+        })
+    }
+
+    /// Delete files of expired exports
+    pub async fn remove_expired(&self) -> AppResult<usize> {
+        let jobs = ExportJob::find_expired(&self.pool, EXPIRED_EXPORTS_PER_RUN).await?;
+        let mut removed = 0;
+
+        for job in jobs {
+            if let Some(key) = &job.storage_key {
+                if let Err(e) = self.storage.delete(key).await {
+                    warn!("Failed to delete expired export {}: {}", job.id, e);
+                    continue;
+                }
+            }
+            ExportJob::mark_expired(&self.pool, job.id).await?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+}
+
+/// Latest revision of each observation, series in the given order, dates ascending
+///
+/// Fails once the export passes [`MAX_EXPORT_ROWS`].
+pub async fn load_export_rows(
+    pool: &DatabasePool,
+    series_ids: &[Uuid],
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> AppResult<Vec<ExportRow>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+    let series: HashMap<Uuid, (String, String)> = economic_series::table
+        .filter(economic_series::id.eq_any(series_ids))
+        .select((
+            economic_series::id,
+            economic_series::external_id,
+            economic_series::title,
+        ))
+        .load::<(Uuid, String, String)>(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(id, external_id, title)| (id, (external_id, title)))
+        .collect();
+    drop(conn);
+
+    let mut rows = Vec::new();
+    for series_id in series_ids {
+        // Unknown series, such as ones deleted since an export was requested, are left out
+        let Some((external_id, title)) = series.get(series_id) else {
+            continue;
+        };
+        let to_row = |point: DataPoint| ExportRow {
+            series_id: *series_id,
+            external_id: external_id.clone(),
+            title: title.clone(),
+            date: point.date,
+            value: point.value,
+            revision_date: point.revision_date,
+        };
+
+        let mut batches = std::pin::pin!(DataPoint::stream_by_series(
+            pool,
+            *series_id,
+            start_date,
+            end_date,
+            false,
+            DATA_POINT_STREAM_BATCH_SIZE,
+        ));
+        // Batches are ordered by date, so the revisions of a date are adjacent
+        let mut latest: Option<DataPoint> = None;
+        while let Some(batch) = batches.try_next().await? {
+            for point in batch {
+                match &latest {
+                    Some(current) if current.date == point.date => {
+                        if point.revision_date >= current.revision_date {
+                            latest = Some(point);
+                        }
+                    }
+                    _ => {
+                        if let Some(done) = latest.replace(point) {
+                            rows.push(to_row(done));
+                        }
+                    }
+                }
+            }
+            if rows.len() > MAX_EXPORT_ROWS {
+                return Err(AppError::ValidationError(format!(
+                    "Export exceeds {} rows; narrow the date range or series",
+                    MAX_EXPORT_ROWS
+                )));
+            }
+        }
+        rows.extend(latest.map(to_row));
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn rows() -> Vec<ExportRow> {
+        let series_id = Uuid::new_v4();
+        vec![
+            ExportRow {
+                series_id,
+                external_id: "GDPC1".to_string(),
+                title: "Real GDP, \"chained\"".to_string(),
+                date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                value: Some(BigDecimal::from_str("22768.866").unwrap()),
+                revision_date: NaiveDate::from_ymd_opt(2024, 4, 25).unwrap(),
+            },
+            ExportRow {
+                series_id,
+                external_id: "GDPC1".to_string(),
+                title: "Real GDP, \"chained\"".to_string(),
+                date: NaiveDate::from_ymd_opt(1890, 4, 1).unwrap(),
+                value: None,
+                revision_date: NaiveDate::from_ymd_opt(2024, 7, 25).unwrap(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_export_formats() {
+        // REQUIREMENT: Exports are downloadable as CSV, Parquet or XLSX
+        // PURPOSE: Verify each format is written with the expected structure
+        // This ensures downstream tools can open every file the worker produces
+
+        let rows = rows();
+
+        let csv = String::from_utf8(render_export(ExportFormat::Csv, &rows).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "series_id,external_id,title,date,value,revision_date"
+        );
+        assert!(lines[1]
+            .ends_with(",GDPC1,\"Real GDP, \"\"chained\"\"\",2024-01-01,22768.866,2024-04-25"));
+        assert!(lines[2].ends_with(",1890-04-01,,2024-07-25"));
+
+        let parquet = render_export(ExportFormat::Parquet, &rows).unwrap();
+        assert_eq!(&parquet[..4], b"PAR1");
+        assert_eq!(&parquet[parquet.len() - 4..], b"PAR1");
+
+        // XLSX files are zip archives
+        let xlsx = render_export(ExportFormat::Xlsx, &rows).unwrap();
+        assert_eq!(&xlsx[..2], b"PK");
+    }
+
+    #[tokio::test]
+    async fn test_filesystem_storage_and_signed_urls() {
+        // REQUIREMENT: Export files are stored and downloaded through signed URLs
+        // PURPOSE: Verify storage round trips, rejects unsafe keys, and URLs carry a verifiable signature
+        // This ensures a download link only works for its own job until it expires
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FilesystemExportStorage::new(dir.path());
+        storage.put("exports/a.csv", b"x,y\n").await.unwrap();
+        assert_eq!(storage.get("exports/a.csv").await.unwrap(), b"x,y\n");
+        storage.delete("exports/a.csv").await.unwrap();
+        storage.delete("exports/a.csv").await.unwrap();
+        assert!(matches!(
+            storage.get("exports/a.csv").await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(storage.put("../escape.csv", b"").await.is_err());
+        assert!(storage.put("/etc/passwd", b"").await.is_err());
+
+        let signer = DownloadSigner::new("export-secret", "https://api.example.com/");
+        let job_id = Uuid::new_v4();
+        let expires_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let url = signer.download_url(job_id, expires_at);
+        assert_eq!(
+            url,
+            format!(
+                "https://api.example.com/exports/{}/download?expires=1700000000&signature={}",
+                job_id,
+                signer.signature(job_id, 1_700_000_000)
+            )
+        );
+        assert_ne!(
+            signer.signature(job_id, 1_700_000_000),
+            signer.signature(Uuid::new_v4(), 1_700_000_000)
+        );
+        assert_ne!(
+            signer.signature(job_id, 1_700_000_000),
+            DownloadSigner::new("other-secret", "").signature(job_id, 1_700_000_000)
+        );
+
+        let signature = signer.signature(job_id, 1_700_000_000);
+        assert!(signer.verify(job_id, 1_700_000_000, &signature));
+        assert!(!signer.verify(job_id, 1_700_000_001, &signature));
+        assert!(!DownloadSigner::new("other-secret", "").verify(job_id, 1_700_000_000, &signature));
+        assert!(!signer.verify(job_id, 1_700_000_000, &signature[..62]));
+        assert!(!signer.verify(job_id, 1_700_000_000, "not-a-signature"));
+    }
+}
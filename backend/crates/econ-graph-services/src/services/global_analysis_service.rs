@@ -1,19 +1,96 @@
 use bigdecimal::{BigDecimal, ToPrimitive};
-use chrono::{NaiveDate, Utc};
+use chrono::{Datelike, NaiveDate, Utc};
 use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
 use econ_graph_core::models::{
     CorrelationConnection, CorrelationNetworkNode, Country, CountryCorrelation,
-    CountryWithEconomicData, GlobalEconomicEvent, GlobalEventWithImpacts, NewCountryCorrelation,
-    TradePartner,
+    CountryWithEconomicData, GlobalEconomicEvent, GlobalEventWithImpacts, LeadingIndicator,
+    NewCountryCorrelation, NewLeadingIndicator, TradePartner,
+};
+use econ_graph_core::schema::{
+    countries, country_correlations, global_economic_indicators, global_indicator_data,
+    leading_indicators,
 };
-use econ_graph_core::schema::{countries, country_correlations};
 use rust_decimal::prelude::{Decimal, FromStr};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 // Removed duplicate Decimal import - already imported from prelude
 
+/// Minimum number of paired observations before a correlation is reported
+pub const MIN_CORRELATION_SAMPLES: usize = 6;
+
+/// Two-sided p-value below which a correlation is flagged significant
+pub const CORRELATION_SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+/// Longest lead time the `leading_indicators` table accepts
+pub const MAX_LEAD_TIME_MONTHS: usize = 24;
+
+/// Parameters for a cross-series correlation and lead/lag run
+#[derive(Debug, Clone)]
+pub struct CrossSeriesAnalysisConfig {
+    pub indicator_category: String,
+    /// Restrict each country to this indicator code; otherwise its longest series in the category is used
+    pub indicator_code: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Country pairs to analyze; empty means every pair of active countries
+    pub pairs: Vec<(uuid::Uuid, uuid::Uuid)>,
+    /// Length of each rolling correlation window
+    pub window_months: usize,
+    /// Distance between the starts of consecutive rolling windows
+    pub step_months: usize,
+    /// Largest lead or lag searched in either direction
+    pub max_lag_months: usize,
+}
+
+impl CrossSeriesAnalysisConfig {
+    /// Config with the default 36-month rolling window stepped yearly and a 12-month lag search
+    pub fn new(indicator_category: String, start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        Self {
+            indicator_category,
+            indicator_code: None,
+            start_date,
+            end_date,
+            pairs: Vec::new(),
+            window_months: 36,
+            step_months: 12,
+            max_lag_months: 12,
+        }
+    }
+}
+
+/// Results persisted by a cross-series analysis run
+#[derive(Debug, Clone, Default)]
+pub struct CrossSeriesAnalysisSummary {
+    pub pairs_analyzed: usize,
+    /// Pairs without enough overlapping observations
+    pub pairs_skipped: usize,
+    /// Full-period and rolling-window correlations
+    pub correlations: Vec<CountryCorrelation>,
+    pub leading_indicators: Vec<LeadingIndicator>,
+}
+
+/// Correlation over one rolling window
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingCorrelation {
+    pub window_start: NaiveDate,
+    pub window_end: NaiveDate,
+    pub coefficient: f64,
+    pub sample_size: usize,
+}
+
+/// Strongest lead/lag relationship found between two series
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeadLagRelationship {
+    /// Months the first series leads the second; negative when it lags
+    pub lead_months: i32,
+    pub coefficient: f64,
+    pub sample_size: usize,
+    /// Share of period-over-period moves the leader called correctly
+    pub directional_accuracy: Option<f64>,
+}
+
 /// Service for global economic network analysis
 pub struct GlobalAnalysisService;
 
@@ -120,6 +197,7 @@ impl GlobalAnalysisService {
         }
 
         // Store correlations in database
+        let mut stored = Vec::with_capacity(correlations.len());
         for correlation in &correlations {
             let new_correlation = NewCountryCorrelation {
                 country_a_id: correlation.country_a_id,
@@ -133,36 +211,427 @@ impl GlobalAnalysisService {
                 is_significant: Some(correlation.is_significant),
             };
 
-            diesel::insert_into(country_correlations::table)
-                .values(&new_correlation.clone())
-                .on_conflict((
-                    country_correlations::country_a_id,
-                    country_correlations::country_b_id,
-                    country_correlations::indicator_category,
-                    country_correlations::time_period_start,
-                    country_correlations::time_period_end,
-                ))
-                .do_update()
-                .set((
-                    country_correlations::correlation_coefficient
-                        .eq(new_correlation.correlation_coefficient.clone()),
-                    country_correlations::sample_size.eq(new_correlation.sample_size),
-                    country_correlations::p_value.eq(new_correlation.p_value.clone()),
-                    country_correlations::is_significant
-                        .eq(new_correlation.is_significant.unwrap_or(false)),
-                    country_correlations::calculated_at.eq(Utc::now()),
-                ))
-                .execute(&mut conn)
+            stored.push(Self::store_country_correlation(&mut conn, &new_correlation).await?);
+        }
+
+        Ok(stored)
+    }
+
+    /// Compute rolling correlations and lead/lag relationships for selected country pairs
+    ///
+    /// For every pair this stores the full-period correlation and one
+    /// correlation per rolling window in `country_correlations`, and replaces
+    /// the pair's `leading_indicators` row with the strongest non-zero lead
+    /// found within `max_lag_months`. Pairs without enough overlapping
+    /// observations are skipped.
+    pub async fn run_cross_series_analysis(
+        pool: &DatabasePool,
+        config: &CrossSeriesAnalysisConfig,
+    ) -> AppResult<CrossSeriesAnalysisSummary> {
+        if config.start_date > config.end_date {
+            return Err(AppError::ValidationError(
+                "Analysis start date must not be after end date".to_string(),
+            ));
+        }
+        if config.window_months < MIN_CORRELATION_SAMPLES || config.step_months == 0 {
+            return Err(AppError::ValidationError(format!(
+                "Rolling windows must span at least {} months and advance by at least one",
+                MIN_CORRELATION_SAMPLES
+            )));
+        }
+        if config.max_lag_months > MAX_LEAD_TIME_MONTHS {
+            return Err(AppError::ValidationError(format!(
+                "Lead/lag search is limited to {} months",
+                MAX_LEAD_TIME_MONTHS
+            )));
+        }
+
+        let mut conn = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get database connection: {}", e);
+            AppError::database_error("Database connection failed".to_string())
+        })?;
+
+        let pairs = if config.pairs.is_empty() {
+            let country_ids = countries::table
+                .filter(countries::is_active.eq(true))
+                .select(countries::id)
+                .load::<uuid::Uuid>(&mut conn)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to load countries: {}", e);
+                    AppError::database_error(e.to_string())
+                })?;
+
+            country_ids
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| country_ids[i + 1..].iter().map(move |b| (*a, *b)))
+                .collect()
+        } else {
+            config.pairs.clone()
+        };
+
+        let mut series_cache: HashMap<uuid::Uuid, BTreeMap<i32, f64>> = HashMap::new();
+        let mut summary = CrossSeriesAnalysisSummary::default();
+
+        for (first, second) in pairs {
+            if first == second {
+                continue;
+            }
+            // Correlations are symmetric, so store each pair in one canonical order
+            let (country_a_id, country_b_id) = if first < second {
+                (first, second)
+            } else {
+                (second, first)
+            };
+
+            for country_id in [country_a_id, country_b_id] {
+                if let std::collections::hash_map::Entry::Vacant(entry) =
+                    series_cache.entry(country_id)
+                {
+                    let series = Self::load_monthly_indicator(
+                        &mut conn,
+                        country_id,
+                        &config.indicator_category,
+                        config.indicator_code.as_deref(),
+                        config.start_date,
+                        config.end_date,
+                    )
+                    .await?;
+                    entry.insert(series);
+                }
+            }
+            let series_a = &series_cache[&country_a_id];
+            let series_b = &series_cache[&country_b_id];
+
+            let Some(full_period) = Self::correlation_record(
+                country_a_id,
+                country_b_id,
+                &config.indicator_category,
+                config.start_date,
+                config.end_date,
+                &aligned_pairs(series_a, series_b, 0),
+            ) else {
+                summary.pairs_skipped += 1;
+                continue;
+            };
+            summary.pairs_analyzed += 1;
+            summary
+                .correlations
+                .push(Self::store_country_correlation(&mut conn, &full_period).await?);
+
+            for window in
+                rolling_correlations(series_a, series_b, config.window_months, config.step_months)
+            {
+                let p_value = correlation_p_value(window.coefficient, window.sample_size);
+                let record = NewCountryCorrelation {
+                    country_a_id,
+                    country_b_id,
+                    indicator_category: config.indicator_category.clone(),
+                    correlation_coefficient: decimal_from_f64(window.coefficient, 4),
+                    time_period_start: window.window_start,
+                    time_period_end: window.window_end,
+                    sample_size: window.sample_size as i32,
+                    p_value: p_value.map(|p| decimal_from_f64(p, 8)),
+                    is_significant: Some(
+                        p_value.is_some_and(|p| p < CORRELATION_SIGNIFICANCE_LEVEL),
+                    ),
+                };
+                summary
+                    .correlations
+                    .push(Self::store_country_correlation(&mut conn, &record).await?);
+            }
+
+            // Drop the previous relationship in either direction before recording the new one
+            diesel::delete(
+                leading_indicators::table
+                    .filter(leading_indicators::indicator_category.eq(&config.indicator_category))
+                    .filter(
+                        leading_indicators::leading_country_id
+                            .eq(country_a_id)
+                            .and(leading_indicators::following_country_id.eq(country_b_id))
+                            .or(leading_indicators::leading_country_id
+                                .eq(country_b_id)
+                                .and(leading_indicators::following_country_id.eq(country_a_id))),
+                    ),
+            )
+            .execute(&mut conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to clear leading indicator: {}", e);
+                AppError::database_error(e.to_string())
+            })?;
+
+            let Some(relationship) = best_lead_lag(series_a, series_b, config.max_lag_months)
+            else {
+                continue;
+            };
+            if relationship.lead_months == 0 {
+                continue;
+            }
+
+            let (leading_country_id, following_country_id) = if relationship.lead_months > 0 {
+                (country_a_id, country_b_id)
+            } else {
+                (country_b_id, country_a_id)
+            };
+            let new_leading_indicator = NewLeadingIndicator {
+                leading_country_id,
+                following_country_id,
+                indicator_category: config.indicator_category.clone(),
+                lead_time_months: relationship.lead_months.abs(),
+                correlation_strength: decimal_from_f64(relationship.coefficient, 4),
+                predictive_accuracy: relationship
+                    .directional_accuracy
+                    .map(|accuracy| decimal_from_f64(accuracy, 4)),
+                time_period_start: config.start_date,
+                time_period_end: config.end_date,
+            };
+
+            let leading_indicator = diesel::insert_into(leading_indicators::table)
+                .values(&new_leading_indicator)
+                .returning(LeadingIndicator::as_returning())
+                .get_result::<LeadingIndicator>(&mut conn)
                 .await
                 .map_err(|e| {
-                    tracing::error!("Failed to store correlation: {}", e);
+                    tracing::error!("Failed to store leading indicator: {}", e);
                     AppError::database_error(e.to_string())
                 })?;
+            summary.leading_indicators.push(leading_indicator);
+        }
+
+        tracing::info!(
+            "Cross-series analysis for {}: {} pairs analyzed, {} skipped, {} leading relationships",
+            config.indicator_category,
+            summary.pairs_analyzed,
+            summary.pairs_skipped,
+            summary.leading_indicators.len()
+        );
+
+        Ok(summary)
+    }
+
+    /// Stored correlations for a category, strongest first
+    pub async fn get_country_correlations(
+        pool: &DatabasePool,
+        indicator_category: &str,
+        country_id: Option<uuid::Uuid>,
+        min_correlation: f64,
+    ) -> AppResult<Vec<CountryCorrelation>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get database connection: {}", e);
+            AppError::database_error("Database connection failed".to_string())
+        })?;
+
+        let mut query = country_correlations::table
+            .filter(country_correlations::indicator_category.eq(indicator_category))
+            .into_boxed();
+        if let Some(country_id) = country_id {
+            query = query.filter(
+                country_correlations::country_a_id
+                    .eq(country_id)
+                    .or(country_correlations::country_b_id.eq(country_id)),
+            );
         }
 
+        let mut correlations = query
+            .order((
+                country_correlations::country_a_id,
+                country_correlations::country_b_id,
+                country_correlations::time_period_start,
+            ))
+            .select(CountryCorrelation::as_select())
+            .load::<CountryCorrelation>(&mut conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load correlations: {}", e);
+                AppError::database_error(e.to_string())
+            })?;
+
+        correlations.retain(|correlation| {
+            correlation
+                .correlation_coefficient
+                .to_f64()
+                .unwrap_or(0.0)
+                .abs()
+                >= min_correlation
+        });
+
         Ok(correlations)
     }
 
+    /// Stored lead/lag relationships, strongest first
+    pub async fn get_leading_indicators(
+        pool: &DatabasePool,
+        indicator_category: Option<&str>,
+        country_id: Option<uuid::Uuid>,
+    ) -> AppResult<Vec<LeadingIndicator>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get database connection: {}", e);
+            AppError::database_error("Database connection failed".to_string())
+        })?;
+
+        let mut query = leading_indicators::table.into_boxed();
+        if let Some(indicator_category) = indicator_category {
+            query = query.filter(leading_indicators::indicator_category.eq(indicator_category));
+        }
+        if let Some(country_id) = country_id {
+            query = query.filter(
+                leading_indicators::leading_country_id
+                    .eq(country_id)
+                    .or(leading_indicators::following_country_id.eq(country_id)),
+            );
+        }
+
+        query
+            .order(leading_indicators::correlation_strength.desc())
+            .select(LeadingIndicator::as_select())
+            .load::<LeadingIndicator>(&mut conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load leading indicators: {}", e);
+                AppError::database_error(e.to_string())
+            })
+    }
+
+    /// Names of the data sources of the indicator data in a category, or in all categories
+    ///
+    /// Correlations and leading indicators are computed from this data, so
+    /// they are subject to the access policies of these sources.
+    pub async fn indicator_data_sources(
+        pool: &DatabasePool,
+        indicator_category: Option<&str>,
+    ) -> AppResult<Vec<String>> {
+        let mut conn = pool.get().await.map_err(|e| {
+            tracing::error!("Failed to get database connection: {}", e);
+            AppError::database_error("Database connection failed".to_string())
+        })?;
+
+        let mut query = global_indicator_data::table
+            .inner_join(global_economic_indicators::table)
+            .select(global_indicator_data::data_source)
+            .distinct()
+            .into_boxed();
+        if let Some(indicator_category) = indicator_category {
+            query = query.filter(global_economic_indicators::category.eq(indicator_category));
+        }
+
+        query.load::<String>(&mut conn).await.map_err(|e| {
+            tracing::error!("Failed to load indicator data sources: {}", e);
+            AppError::database_error(e.to_string())
+        })
+    }
+
+    /// Insert a correlation, replacing any stored for the same pair, category and period
+    async fn store_country_correlation(
+        conn: &mut AsyncPgConnection,
+        new_correlation: &NewCountryCorrelation,
+    ) -> AppResult<CountryCorrelation> {
+        diesel::insert_into(country_correlations::table)
+            .values(new_correlation)
+            .on_conflict((
+                country_correlations::country_a_id,
+                country_correlations::country_b_id,
+                country_correlations::indicator_category,
+                country_correlations::time_period_start,
+                country_correlations::time_period_end,
+            ))
+            .do_update()
+            .set((
+                country_correlations::correlation_coefficient
+                    .eq(new_correlation.correlation_coefficient.clone()),
+                country_correlations::sample_size.eq(new_correlation.sample_size),
+                country_correlations::p_value.eq(new_correlation.p_value.clone()),
+                country_correlations::is_significant
+                    .eq(new_correlation.is_significant.unwrap_or(false)),
+                country_correlations::calculated_at.eq(Utc::now()),
+            ))
+            .returning(CountryCorrelation::as_returning())
+            .get_result::<CountryCorrelation>(conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to store correlation: {}", e);
+                AppError::database_error(e.to_string())
+            })
+    }
+
+    /// Load one country's indicator in a category as monthly observations
+    ///
+    /// A category can hold several indicators per country (e.g. GDP in
+    /// current and constant prices), so only one is used: `indicator_code`
+    /// when given, otherwise the one with the most observations in the period.
+    async fn load_monthly_indicator(
+        conn: &mut AsyncPgConnection,
+        country_id: uuid::Uuid,
+        indicator_category: &str,
+        indicator_code: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> AppResult<BTreeMap<i32, f64>> {
+        let mut query = global_indicator_data::table
+            .inner_join(global_economic_indicators::table)
+            .filter(global_economic_indicators::country_id.eq(country_id))
+            .filter(global_economic_indicators::category.eq(indicator_category))
+            .filter(global_indicator_data::date.between(start_date, end_date))
+            .filter(global_indicator_data::value.is_not_null())
+            .into_boxed();
+        if let Some(indicator_code) = indicator_code {
+            query = query.filter(global_economic_indicators::indicator_code.eq(indicator_code));
+        }
+
+        let rows = query
+            .select((
+                global_economic_indicators::indicator_code,
+                global_indicator_data::date,
+                global_indicator_data::value,
+            ))
+            .load::<(String, NaiveDate, Option<BigDecimal>)>(conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load indicator data: {}", e);
+                AppError::database_error(e.to_string())
+            })?;
+
+        let mut by_code: HashMap<String, Vec<(NaiveDate, f64)>> = HashMap::new();
+        for (code, date, value) in rows {
+            if let Some(value) = value.and_then(|value| value.to_f64()) {
+                by_code.entry(code).or_default().push((date, value));
+            }
+        }
+
+        Ok(by_code
+            .into_iter()
+            .max_by(|(code_a, a), (code_b, b)| a.len().cmp(&b.len()).then(code_b.cmp(code_a)))
+            .map(|(_, observations)| monthly_observations(&observations))
+            .unwrap_or_default())
+    }
+
+    /// Build a correlation record from aligned observations, if there are enough of them
+    fn correlation_record(
+        country_a_id: uuid::Uuid,
+        country_b_id: uuid::Uuid,
+        indicator_category: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        aligned: &[(i32, f64, f64)],
+    ) -> Option<NewCountryCorrelation> {
+        let samples: Vec<(f64, f64)> = aligned.iter().map(|(_, a, b)| (*a, *b)).collect();
+        let coefficient = pearson_correlation(&samples)?;
+        let p_value = correlation_p_value(coefficient, samples.len());
+
+        Some(NewCountryCorrelation {
+            country_a_id,
+            country_b_id,
+            indicator_category: indicator_category.to_string(),
+            correlation_coefficient: decimal_from_f64(coefficient, 4),
+            time_period_start: start_date,
+            time_period_end: end_date,
+            sample_size: samples.len() as i32,
+            p_value: p_value.map(|p| decimal_from_f64(p, 8)),
+            is_significant: Some(p_value.is_some_and(|p| p < CORRELATION_SIGNIFICANCE_LEVEL)),
+        })
+    }
+
     /// Get correlation network for visualization
     pub async fn get_correlation_network(
         pool: &DatabasePool,
@@ -188,6 +657,24 @@ impl GlobalAnalysisService {
                 AppError::database_error(e.to_string())
             })?;
 
+        // Rolling-window rows share a pair with the full-period row; keep the widest period
+        let mut widest: HashMap<(uuid::Uuid, uuid::Uuid), CountryCorrelation> = HashMap::new();
+        for correlation in correlations {
+            let span = correlation.time_period_end - correlation.time_period_start;
+            match widest.entry((correlation.country_a_id, correlation.country_b_id)) {
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    let current = entry.get();
+                    if span > current.time_period_end - current.time_period_start {
+                        entry.insert(correlation);
+                    }
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(correlation);
+                }
+            }
+        }
+        let correlations: Vec<CountryCorrelation> = widest.into_values().collect();
+
         // Build network nodes
         let mut country_connections: HashMap<uuid::Uuid, Vec<CorrelationConnection>> =
             HashMap::new();
@@ -485,21 +972,51 @@ impl GlobalAnalysisService {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> AppResult<CountryCorrelation> {
-        // This is a simplified implementation
-        // In a real system, you'd fetch the actual time series data and calculate Pearson correlation
+        let series_a = Self::load_monthly_indicator(
+            conn,
+            country_a_id,
+            indicator_category,
+            None,
+            start_date,
+            end_date,
+        )
+        .await?;
+        let series_b = Self::load_monthly_indicator(
+            conn,
+            country_b_id,
+            indicator_category,
+            None,
+            start_date,
+            end_date,
+        )
+        .await?;
 
-        // For now, return a mock correlation
-        Ok(CountryCorrelation {
-            id: uuid::Uuid::new_v4(),
+        let record = Self::correlation_record(
             country_a_id,
             country_b_id,
-            indicator_category: indicator_category.to_string(),
-            correlation_coefficient: BigDecimal::from(75) / BigDecimal::from(100), // Mock correlation 0.75
-            time_period_start: start_date,
-            time_period_end: end_date,
-            sample_size: 100,
-            p_value: Some(BigDecimal::from(1) / BigDecimal::from(100)), // 0.01
-            is_significant: true,
+            indicator_category,
+            start_date,
+            end_date,
+            &aligned_pairs(&series_a, &series_b, 0),
+        )
+        .ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Not enough overlapping {} observations to correlate",
+                indicator_category
+            ))
+        })?;
+
+        Ok(CountryCorrelation {
+            id: uuid::Uuid::new_v4(),
+            country_a_id: record.country_a_id,
+            country_b_id: record.country_b_id,
+            indicator_category: record.indicator_category,
+            correlation_coefficient: record.correlation_coefficient,
+            time_period_start: record.time_period_start,
+            time_period_end: record.time_period_end,
+            sample_size: record.sample_size,
+            p_value: record.p_value,
+            is_significant: record.is_significant.unwrap_or(false),
             calculated_at: Utc::now(),
         })
     }
@@ -582,6 +1099,216 @@ impl GlobalAnalysisService {
     }
 }
 
+/// Index of the calendar month containing a date
+fn month_key(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// First day of a month index
+fn month_start(key: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(key.div_euclid(12), key.rem_euclid(12) as u32 + 1, 1)
+        .unwrap_or_default()
+}
+
+/// Last day of a month index
+fn month_end(key: i32) -> NaiveDate {
+    month_start(key + 1).pred_opt().unwrap_or_default()
+}
+
+/// Round a float into a `BigDecimal` with the given number of decimal places
+fn decimal_from_f64(value: f64, scale: usize) -> BigDecimal {
+    BigDecimal::from_str(&format!("{:.*}", scale, value)).unwrap_or_default()
+}
+
+/// Collapse observations to one value per calendar month, averaging within a month
+///
+/// Keys are month indices (`year * 12 + month0`), so annual and quarterly
+/// series line up with monthly ones on the months they were observed.
+pub fn monthly_observations(observations: &[(NaiveDate, f64)]) -> BTreeMap<i32, f64> {
+    let mut sums: BTreeMap<i32, (f64, usize)> = BTreeMap::new();
+    for (date, value) in observations {
+        let entry = sums.entry(month_key(*date)).or_insert((0.0, 0));
+        entry.0 += value;
+        entry.1 += 1;
+    }
+
+    sums.into_iter()
+        .map(|(key, (sum, count))| (key, sum / count as f64))
+        .collect()
+}
+
+/// Pair each month of `b` with the value `a` had `lag` months earlier
+///
+/// A positive lag tests whether `a` leads `b`. Returns `(month, a, b)`.
+pub fn aligned_pairs(
+    a: &BTreeMap<i32, f64>,
+    b: &BTreeMap<i32, f64>,
+    lag: i32,
+) -> Vec<(i32, f64, f64)> {
+    b.iter()
+        .filter_map(|(key, value_b)| {
+            a.get(&(key - lag))
+                .map(|value_a| (*key, *value_a, *value_b))
+        })
+        .collect()
+}
+
+/// Pearson correlation coefficient of paired samples
+///
+/// Returns `None` with fewer than [`MIN_CORRELATION_SAMPLES`] pairs or when
+/// either side is constant.
+pub fn pearson_correlation(samples: &[(f64, f64)]) -> Option<f64> {
+    if samples.len() < MIN_CORRELATION_SAMPLES {
+        return None;
+    }
+
+    let n = samples.len() as f64;
+    let mean_a = samples.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_b = samples.iter().map(|(_, b)| b).sum::<f64>() / n;
+
+    let (mut covariance, mut variance_a, mut variance_b) = (0.0, 0.0, 0.0);
+    for (a, b) in samples {
+        covariance += (a - mean_a) * (b - mean_b);
+        variance_a += (a - mean_a).powi(2);
+        variance_b += (b - mean_b).powi(2);
+    }
+
+    if variance_a <= f64::EPSILON || variance_b <= f64::EPSILON {
+        return None;
+    }
+
+    Some((covariance / (variance_a * variance_b).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Two-sided p-value for a correlation using the Fisher z-transformation
+pub fn correlation_p_value(coefficient: f64, sample_size: usize) -> Option<f64> {
+    if sample_size <= 3 {
+        return None;
+    }
+    if coefficient.abs() >= 1.0 {
+        return Some(0.0);
+    }
+
+    let z = coefficient.atanh() * ((sample_size - 3) as f64).sqrt();
+    Some((2.0 * (1.0 - standard_normal_cdf(z.abs()))).clamp(0.0, 1.0))
+}
+
+/// Standard normal CDF via the Abramowitz and Stegun erf approximation (error < 1.5e-7)
+fn standard_normal_cdf(x: f64) -> f64 {
+    let t = x.abs() / std::f64::consts::SQRT_2;
+    let k = 1.0 / (1.0 + 0.3275911 * t);
+    let poly = k
+        * (0.254829592
+            + k * (-0.284496736 + k * (1.421413741 + k * (-1.453152027 + k * 1.061405429))));
+    let erf = 1.0 - poly * (-t * t).exp();
+
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Correlations over full rolling windows of `window_months`, advancing by `step_months`
+pub fn rolling_correlations(
+    a: &BTreeMap<i32, f64>,
+    b: &BTreeMap<i32, f64>,
+    window_months: usize,
+    step_months: usize,
+) -> Vec<RollingCorrelation> {
+    let aligned = aligned_pairs(a, b, 0);
+    if aligned.is_empty() {
+        return Vec::new();
+    }
+    let (first, last) = (aligned[0].0, aligned[aligned.len() - 1].0);
+    let window = window_months as i32;
+
+    (first..=last - window + 1)
+        .step_by(step_months.max(1))
+        .filter_map(|window_start| {
+            let samples: Vec<(f64, f64)> = aligned
+                .iter()
+                .filter(|(key, _, _)| (window_start..window_start + window).contains(key))
+                .map(|(_, a, b)| (*a, *b))
+                .collect();
+
+            Some(RollingCorrelation {
+                window_start: month_start(window_start),
+                window_end: month_end(window_start + window - 1),
+                coefficient: pearson_correlation(&samples)?,
+                sample_size: samples.len(),
+            })
+        })
+        .collect()
+}
+
+/// Find the lead or lag up to `max_lag_months` at which two series correlate most strongly
+///
+/// Ties go to the shorter lag, so a contemporaneous relationship is never
+/// reported as a lead.
+pub fn best_lead_lag(
+    a: &BTreeMap<i32, f64>,
+    b: &BTreeMap<i32, f64>,
+    max_lag_months: usize,
+) -> Option<LeadLagRelationship> {
+    let max_lag = max_lag_months as i32;
+    let lags = (0..=max_lag).flat_map(|lag| if lag == 0 { vec![0] } else { vec![lag, -lag] });
+
+    let mut best: Option<LeadLagRelationship> = None;
+    for lag in lags {
+        let samples: Vec<(f64, f64)> = aligned_pairs(a, b, lag)
+            .iter()
+            .map(|(_, a, b)| (*a, *b))
+            .collect();
+        let Some(coefficient) = pearson_correlation(&samples) else {
+            continue;
+        };
+
+        if best
+            .as_ref()
+            .is_none_or(|current| coefficient.abs() > current.coefficient.abs() + 1e-12)
+        {
+            best = Some(LeadLagRelationship {
+                lead_months: lag,
+                coefficient,
+                sample_size: samples.len(),
+                directional_accuracy: None,
+            });
+        }
+    }
+
+    best.map(|mut relationship| {
+        relationship.directional_accuracy = directional_accuracy(
+            &aligned_pairs(a, b, relationship.lead_months),
+            relationship.coefficient,
+        );
+        relationship
+    })
+}
+
+/// Share of consecutive moves where the leader's direction predicted the follower's
+///
+/// For negative correlations an opposite move counts as a correct call.
+fn directional_accuracy(aligned: &[(i32, f64, f64)], coefficient: f64) -> Option<f64> {
+    let expected_sign = coefficient.signum();
+    let (mut correct, mut total) = (0usize, 0usize);
+
+    for pair in aligned.windows(2) {
+        let change_a = pair[1].1 - pair[0].1;
+        let change_b = pair[1].2 - pair[0].2;
+        if change_a == 0.0 || change_b == 0.0 {
+            continue;
+        }
+
+        total += 1;
+        if change_a.signum() * change_b.signum() == expected_sign {
+            correct += 1;
+        }
+    }
+
+    (total > 0).then(|| correct as f64 / total as f64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -755,6 +1482,53 @@ mod tests {
                 AppError::database_error(format!("Failed to create GDP growth data: {}", e))
             })?;
 
+        // Monthly GDP history for countries A and B so correlations have data to work with
+        let gdp_indicator_b =
+            diesel::insert_into(econ_graph_core::schema::global_economic_indicators::table)
+                .values(&NewGlobalEconomicIndicator {
+                    country_id: country_b_result.id,
+                    ..gdp_indicator.clone()
+                })
+                .returning(econ_graph_core::models::GlobalEconomicIndicator::as_returning())
+                .get_result::<econ_graph_core::models::GlobalEconomicIndicator>(&mut conn)
+                .await
+                .map_err(|e| {
+                    AppError::database_error(format!("Failed to create GDP indicator B: {}", e))
+                })?;
+
+        let history: Vec<NewGlobalIndicatorData> = (0..47)
+            .flat_map(|month| {
+                let date =
+                    NaiveDate::from_ymd_opt(2020 + month / 12, month as u32 % 12 + 1, 1).unwrap();
+                let cycle = (month as f64 / 6.0).sin();
+                [
+                    (
+                        gdp_indicator_result.id,
+                        20_000.0 + month as f64 * 50.0 + cycle * 400.0,
+                    ),
+                    (
+                        gdp_indicator_b.id,
+                        14_000.0 + month as f64 * 30.0 + cycle * 250.0,
+                    ),
+                ]
+                .map(|(indicator_id, value)| NewGlobalIndicatorData {
+                    indicator_id,
+                    date,
+                    value: Some(BigDecimal::from_str(&format!("{:.2}", value)).unwrap()),
+                    is_preliminary: Some(false),
+                    data_source: "Test Data".to_string(),
+                })
+            })
+            .collect();
+
+        diesel::insert_into(econ_graph_core::schema::global_indicator_data::table)
+            .values(&history)
+            .execute(&mut conn)
+            .await
+            .map_err(|e| {
+                AppError::database_error(format!("Failed to create GDP history: {}", e))
+            })?;
+
         // Create trade relationship
         let trade_relationship = NewTradeRelationship {
             exporter_country_id: country_a_result.id,
@@ -905,6 +1679,36 @@ mod tests {
         assert!(correlation.sample_size > 0);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_indicator_data_sources() {
+        // REQUIREMENT: Correlations are subject to the access policies of the data they come from
+        // PURPOSE: Verify the data sources behind a category's indicator data are listed once each
+        // This ensures resolvers can check the reader may read every source a correlation was computed from
+
+        let container = TestContainer::new().await;
+        let _ = container.clean_database().await;
+        let pool = container.pool();
+        setup_test_data(&container)
+            .await
+            .expect("Failed to setup test data");
+
+        let sources = GlobalAnalysisService::indicator_data_sources(pool, Some("GDP"))
+            .await
+            .expect("Failed to load data sources");
+        assert_eq!(sources, vec!["Test Data".to_string()]);
+
+        let all = GlobalAnalysisService::indicator_data_sources(pool, None)
+            .await
+            .expect("Failed to load data sources");
+        assert_eq!(all, vec!["Test Data".to_string()]);
+
+        let unknown = GlobalAnalysisService::indicator_data_sources(pool, Some("NO_SUCH_CATEGORY"))
+            .await
+            .expect("Failed to load data sources");
+        assert!(unknown.is_empty());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_get_correlation_network() {
@@ -1298,4 +2102,94 @@ mod tests {
             );
         }
     }
+
+    fn monthly(values: &[f64]) -> BTreeMap<i32, f64> {
+        let observations: Vec<(NaiveDate, f64)> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let date = NaiveDate::from_ymd_opt(2020 + (i / 12) as i32, (i % 12) as u32 + 1, 15)
+                    .unwrap();
+                (date, *value)
+            })
+            .collect();
+        monthly_observations(&observations)
+    }
+
+    #[test]
+    fn test_pearson_correlation_and_p_value() {
+        // REQUIREMENT: Correlations between countries must reflect real indicator data
+        // PURPOSE: Verify Pearson coefficients and Fisher p-values on known inputs
+        // This ensures stored correlations and significance flags are mathematically sound
+
+        let rising: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, 2.0 * i as f64 + 1.0)).collect();
+        assert!((pearson_correlation(&rising).unwrap() - 1.0).abs() < 1e-12);
+
+        let falling: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, -(i as f64))).collect();
+        assert!((pearson_correlation(&falling).unwrap() + 1.0).abs() < 1e-12);
+
+        let constant: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, 5.0)).collect();
+        assert_eq!(pearson_correlation(&constant), None);
+        assert_eq!(pearson_correlation(&rising[..3]), None);
+
+        // r = 0.5 with n = 30 has p ≈ 0.0045
+        let p = correlation_p_value(0.5, 30).unwrap();
+        assert!((p - 0.0045).abs() < 0.0005, "p = {}", p);
+        assert!(correlation_p_value(0.05, 10).unwrap() > CORRELATION_SIGNIFICANCE_LEVEL);
+        assert_eq!(correlation_p_value(0.9, 3), None);
+    }
+
+    #[test]
+    fn test_best_lead_lag_finds_shifted_series() {
+        // REQUIREMENT: Identify countries whose indicators lead others
+        // PURPOSE: Verify a series shifted by three months is detected as a three-month lead
+        // This ensures leading_indicators records the right direction and lead time
+
+        let signal: Vec<f64> = (0..60)
+            .map(|i| (i as f64 / 4.0).sin() * 10.0 + (i % 7) as f64)
+            .collect();
+        let leader = monthly(&signal[3..]);
+        let follower = monthly(&signal[..57]);
+
+        let relationship = best_lead_lag(&leader, &follower, 12).unwrap();
+        assert_eq!(relationship.lead_months, 3);
+        assert!(relationship.coefficient > 0.99);
+        assert_eq!(relationship.directional_accuracy, Some(1.0));
+
+        let reversed = best_lead_lag(&follower, &leader, 12).unwrap();
+        assert_eq!(reversed.lead_months, -3);
+
+        let same = best_lead_lag(&leader, &leader, 12).unwrap();
+        assert_eq!(same.lead_months, 0);
+    }
+
+    #[test]
+    fn test_rolling_correlations_windows() {
+        // REQUIREMENT: Track how co-movement between countries changes over time
+        // PURPOSE: Verify rolling windows cover full periods and pick up a regime change
+        // This ensures each stored window has accurate bounds and its own coefficient
+
+        let a: Vec<f64> = (0..48).map(|i| (i as f64 / 3.0).sin()).collect();
+        let b: Vec<f64> = a
+            .iter()
+            .enumerate()
+            .map(|(i, value)| if i < 24 { *value } else { -*value })
+            .collect();
+
+        let windows = rolling_correlations(&monthly(&a), &monthly(&b), 24, 12);
+
+        assert_eq!(windows.len(), 3);
+        assert_eq!(
+            windows[0].window_start,
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+        );
+        assert_eq!(
+            windows[0].window_end,
+            NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()
+        );
+        assert_eq!(windows[0].sample_size, 24);
+        assert!((windows[0].coefficient - 1.0).abs() < 1e-9);
+        assert!((windows[2].coefficient + 1.0).abs() < 1e-9);
+        assert!(rolling_correlations(&monthly(&a[..12]), &monthly(&b[..12]), 24, 12).is_empty());
+    }
 }
@@ -1,11 +1,37 @@
+pub mod aligned_series_service;
+pub mod annotation_import_service;
+pub mod annotation_workflow_service;
+pub mod audit_log_service;
+pub mod benchmarking_service;
 pub mod collaboration_service;
 pub mod comprehensive_series_catalog;
+pub mod country_snapshot_service;
+pub mod crawl_analytics_service;
 pub mod crawler;
+pub mod currency_conversion_service;
+pub mod data_correction_service;
+pub mod data_point_cache;
+pub mod data_quality_service;
+pub mod data_source_admin_service;
+pub mod derived_series_service;
+pub mod education_service;
+pub mod email_service;
+pub mod email_templates;
+pub mod export_service;
 pub mod global_analysis_service;
+pub mod notification_service;
+pub mod provenance_service;
 pub mod queue_service;
+pub mod response_cache;
+pub mod revision_retention_service;
 pub mod search_service;
+pub mod seasonal_adjustment_service;
+pub mod series_alert_service;
+pub mod series_link_service;
 pub mod series_discovery;
 pub mod series_service;
+pub mod user_profile_service;
+pub mod webhook_service;
 
 // #[cfg(test)]
 // mod __tests__;
@@ -0,0 +1,495 @@
+//! Notifications for annotation collaboration and series alerts
+//!
+//! Annotation workflows call [`NotificationService`] when something happens
+//! that another user should hear about: an assignment, a reply, a comment or a
+//! resolution. Series alert rules use it when they fire. Each notification is stored for the in-app feed, published to
+//! live subscribers through the process-wide [`NotificationHub`], and handed
+//! to the email hook when one is registered.
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio::sync::broadcast;
+use tracing::warn;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        AnnotationAssignment, AnnotationComment, AnnotationReply, AssignmentAction,
+        ChartAnnotation, NewNotification, Notification, NotificationKind, NotificationSubject,
+        SeriesAlertRule, User,
+    },
+    schema::{annotation_assignments, annotation_comments},
+};
+
+/// Buffered notifications per live subscriber before the oldest are dropped
+const SUBSCRIBER_BUFFER: usize = 256;
+
+/// Hook for delivering notifications by email
+///
+/// Only called for recipients with `notifications_enabled`. Failures are
+/// logged and do not affect the in-app notification.
+#[async_trait]
+pub trait NotificationEmailHook: Send + Sync {
+    async fn send(&self, recipient: &User, notification: &Notification) -> AppResult<()>;
+}
+
+/// Process-wide fan-out of new notifications
+///
+/// The GraphQL schema is built per request, so live subscriptions and the
+/// email hook are kept here rather than in the schema context.
+pub struct NotificationHub {
+    sender: broadcast::Sender<Notification>,
+    email_hook: RwLock<Option<Arc<dyn NotificationEmailHook>>>,
+}
+
+impl NotificationHub {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            sender,
+            email_hook: RwLock::new(None),
+        }
+    }
+
+    /// Receive every notification created from now on
+    ///
+    /// Subscribers filter by recipient themselves.
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.sender.subscribe()
+    }
+
+    /// Register the email hook, replacing any previous one
+    pub fn set_email_hook(&self, hook: Arc<dyn NotificationEmailHook>) {
+        *self.email_hook.write().unwrap_or_else(|e| e.into_inner()) = Some(hook);
+    }
+
+    fn email_hook(&self) -> Option<Arc<dyn NotificationEmailHook>> {
+        self.email_hook
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn publish(&self, notification: &Notification) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.sender.send(notification.clone());
+    }
+}
+
+/// Shared notification hub
+pub fn notification_hub() -> &'static NotificationHub {
+    static HUB: OnceLock<NotificationHub> = OnceLock::new();
+    HUB.get_or_init(NotificationHub::new)
+}
+
+/// Recipients of an event, without the user who caused it or duplicates
+pub fn notification_recipients(
+    candidates: impl IntoIterator<Item = Uuid>,
+    actor_id: Uuid,
+) -> Vec<Uuid> {
+    let mut seen = HashSet::from([actor_id]);
+    candidates
+        .into_iter()
+        .filter(|user_id| seen.insert(*user_id))
+        .collect()
+}
+
+/// Shorten text for a notification message
+fn excerpt(text: &str) -> String {
+    const MAX_CHARS: usize = 140;
+    let text = text.trim();
+    if text.chars().count() <= MAX_CHARS {
+        text.to_string()
+    } else {
+        let mut excerpt: String = text.chars().take(MAX_CHARS - 1).collect();
+        excerpt.push('…');
+        excerpt
+    }
+}
+
+/// Creates and delivers annotation notifications
+pub struct NotificationService {
+    pool: DatabasePool,
+}
+
+impl NotificationService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Notify the assignee of a new annotation assignment
+    pub async fn notify_assignment(
+        &self,
+        assignment: &AnnotationAssignment,
+    ) -> AppResult<Vec<Notification>> {
+        let recipients = notification_recipients([assignment.assignee_id], assignment.assigner_id);
+        let action = format!("{:?}", assignment.assignment_type).to_lowercase();
+        let message = match &assignment.notes {
+            Some(notes) => format!(
+                "You were asked to {} an annotation: {}",
+                action,
+                excerpt(notes)
+            ),
+            None => format!("You were asked to {} an annotation", action),
+        };
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AnnotationAssigned,
+                        NotificationSubject::AnnotationAssignment,
+                        assignment.id,
+                        "New annotation assignment",
+                        message.clone(),
+                    )
+                    .with_actor(assignment.assigner_id)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify the other party that an assignment was accepted, completed or rejected
+    ///
+    /// The assigner hears about the assignee's steps; the assignee hears when
+    /// the assigner withdraws the assignment.
+    pub async fn notify_assignment_updated(
+        &self,
+        assignment: &AnnotationAssignment,
+        action: AssignmentAction,
+        actor_id: Uuid,
+    ) -> AppResult<Vec<Notification>> {
+        let recipients =
+            notification_recipients([assignment.assigner_id, assignment.assignee_id], actor_id);
+        let (title, message) = match action {
+            AssignmentAction::Accept => (
+                "Assignment accepted",
+                "An annotation assignment you made was accepted",
+            ),
+            AssignmentAction::Complete => (
+                "Assignment completed",
+                "An annotation assignment you made was completed",
+            ),
+            AssignmentAction::Reject if actor_id == assignment.assigner_id => (
+                "Assignment withdrawn",
+                "An annotation assignment you received was withdrawn",
+            ),
+            AssignmentAction::Reject => (
+                "Assignment rejected",
+                "An annotation assignment you made was rejected",
+            ),
+        };
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AssignmentUpdated,
+                        NotificationSubject::AnnotationAssignment,
+                        assignment.id,
+                        title,
+                        message,
+                    )
+                    .with_actor(actor_id)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify the annotation author and mentioned users of a reply
+    pub async fn notify_reply(
+        &self,
+        reply: &AnnotationReply,
+        annotation_author_id: Uuid,
+    ) -> AppResult<Vec<Notification>> {
+        let recipients = notification_recipients(
+            std::iter::once(annotation_author_id).chain(reply.mentions.iter().copied()),
+            reply.author_id,
+        );
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    let title = if user_id == annotation_author_id {
+                        "New reply to your annotation"
+                    } else {
+                        "You were mentioned in a reply"
+                    };
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AnnotationReply,
+                        NotificationSubject::FinancialAnnotation,
+                        reply.annotation_id,
+                        title,
+                        excerpt(&reply.content),
+                    )
+                    .with_actor(reply.author_id)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify the annotation owner and earlier commenters of a new comment
+    pub async fn notify_comment(
+        &self,
+        annotation: &ChartAnnotation,
+        comment: &AnnotationComment,
+    ) -> AppResult<Vec<Notification>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let earlier_commenters: Vec<Uuid> = annotation_comments::table
+            .filter(annotation_comments::annotation_id.eq(annotation.id))
+            .filter(annotation_comments::id.ne(comment.id))
+            .select(annotation_comments::user_id)
+            .distinct()
+            .load(&mut conn)
+            .await?;
+
+        let recipients = notification_recipients(
+            std::iter::once(annotation.user_id).chain(earlier_commenters),
+            comment.user_id,
+        );
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AnnotationComment,
+                        NotificationSubject::ChartAnnotation,
+                        annotation.id,
+                        format!("New comment on \"{}\"", excerpt(&annotation.title)),
+                        excerpt(&comment.content),
+                    )
+                    .with_actor(comment.user_id)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify a comment's author that it was resolved
+    pub async fn notify_comment_resolved(
+        &self,
+        annotation: &ChartAnnotation,
+        comment: &AnnotationComment,
+        resolved_by: Uuid,
+    ) -> AppResult<Vec<Notification>> {
+        let recipients = notification_recipients([comment.user_id], resolved_by);
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AnnotationResolved,
+                        NotificationSubject::ChartAnnotation,
+                        annotation.id,
+                        format!(
+                            "Your comment on \"{}\" was resolved",
+                            excerpt(&annotation.title)
+                        ),
+                        excerpt(&comment.content),
+                    )
+                    .with_actor(resolved_by)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify the author and open assignees that a financial annotation was resolved
+    pub async fn notify_annotation_resolved(
+        &self,
+        annotation_id: Uuid,
+        statement_id: Uuid,
+        author_id: Uuid,
+        resolved_by: Uuid,
+    ) -> AppResult<Vec<Notification>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let assignees: Vec<Uuid> = annotation_assignments::table
+            .filter(annotation_assignments::statement_id.eq(statement_id))
+            .filter(annotation_assignments::status.eq_any(["pending", "in_progress"]))
+            .select(annotation_assignments::assignee_id)
+            .distinct()
+            .load(&mut conn)
+            .await?;
+
+        let recipients =
+            notification_recipients(std::iter::once(author_id).chain(assignees), resolved_by);
+
+        self.deliver(
+            recipients
+                .into_iter()
+                .map(|user_id| {
+                    NewNotification::new(
+                        user_id,
+                        NotificationKind::AnnotationResolved,
+                        NotificationSubject::FinancialAnnotation,
+                        annotation_id,
+                        "Annotation resolved",
+                        "An annotation you are involved in was marked as resolved",
+                    )
+                    .with_actor(resolved_by)
+                })
+                .collect(),
+        )
+        .await
+    }
+
+    /// Notify the owner of an alert rule that it fired
+    pub async fn notify_series_alert(
+        &self,
+        rule: &SeriesAlertRule,
+        message: String,
+    ) -> AppResult<Vec<Notification>> {
+        self.deliver(vec![NewNotification::new(
+            rule.user_id,
+            NotificationKind::SeriesAlertTriggered,
+            NotificationSubject::SeriesAlertRule,
+            rule.id,
+            excerpt(&format!("Alert: {}", rule.name)),
+            message,
+        )])
+        .await
+    }
+
+    /// A user's notification feed, newest first
+    pub async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<Notification>> {
+        Notification::list_for_user(&self.pool, user_id, unread_only, limit, offset).await
+    }
+
+    pub async fn unread_count(&self, user_id: Uuid) -> AppResult<i64> {
+        Notification::unread_count(&self.pool, user_id).await
+    }
+
+    /// Mark notifications as read; `None` marks the whole feed
+    pub async fn mark_read(&self, user_id: Uuid, ids: Option<&[Uuid]>) -> AppResult<usize> {
+        Notification::mark_read(&self.pool, user_id, ids).await
+    }
+
+    /// Store notifications, publish them to subscribers and run the email hook
+    async fn deliver(
+        &self,
+        new_notifications: Vec<NewNotification>,
+    ) -> AppResult<Vec<Notification>> {
+        let notifications = Notification::create_many(&self.pool, &new_notifications).await?;
+
+        let hub = notification_hub();
+        for notification in &notifications {
+            hub.publish(notification);
+        }
+
+        if let Some(hook) = hub.email_hook() {
+            for notification in &notifications {
+                let recipient = match User::get_by_id(&self.pool, notification.user_id).await {
+                    Ok(user) if user.notifications_enabled && user.is_active => user,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        warn!(
+                            "Failed to load recipient {} for notification email: {}",
+                            notification.user_id, e
+                        );
+                        continue;
+                    }
+                };
+                if let Err(e) = hook.send(&recipient, notification).await {
+                    warn!(
+                        "Failed to email notification {} to {}: {}",
+                        notification.id, recipient.id, e
+                    );
+                }
+            }
+        }
+
+        Ok(notifications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_recipients() {
+        // REQUIREMENT: Notify everyone involved in an annotation except the person acting
+        // PURPOSE: Verify the actor is excluded and repeated users are notified once
+        // This ensures users are not notified about their own comments or spammed twice
+
+        let actor = Uuid::from_u128(1);
+        let author = Uuid::from_u128(2);
+        let mentioned = Uuid::from_u128(3);
+
+        let recipients = notification_recipients([author, actor, mentioned, author], actor);
+
+        assert_eq!(recipients, vec![author, mentioned]);
+        assert!(notification_recipients([actor], actor).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hub_publishes_to_subscribers() {
+        // REQUIREMENT: Live notification feed via GraphQL subscription
+        // PURPOSE: Verify notifications published to the hub reach every subscriber
+        // This ensures open browser sessions see new notifications without polling
+
+        let hub = NotificationHub::new();
+        let mut receiver = hub.subscribe();
+        let notification = Notification {
+            id: Uuid::from_u128(10),
+            user_id: Uuid::from_u128(2),
+            actor_id: Some(Uuid::from_u128(1)),
+            notification_type: NotificationKind::AnnotationComment.to_string(),
+            subject_type: NotificationSubject::ChartAnnotation.as_str().to_string(),
+            subject_id: Uuid::from_u128(20),
+            title: "New comment".to_string(),
+            message: "Looks like a seasonal dip".to_string(),
+            read_at: None,
+            created_at: chrono::Utc::now(),
+        };
+
+        hub.publish(&notification);
+
+        let received = receiver.recv().await.unwrap();
+        assert_eq!(received.id, notification.id);
+        assert_eq!(received.user_id, notification.user_id);
+    }
+
+    #[test]
+    fn test_excerpt_truncates_long_text() {
+        // REQUIREMENT: Notification messages stay short enough for the feed
+        // PURPOSE: Verify long comments are cut at a character boundary with an ellipsis
+        // This ensures multi-byte text never panics when shortened
+
+        assert_eq!(excerpt("  short  "), "short");
+        let long = "é".repeat(200);
+        let shortened = excerpt(&long);
+        assert_eq!(shortened.chars().count(), 140);
+        assert!(shortened.ends_with('…'));
+    }
+}
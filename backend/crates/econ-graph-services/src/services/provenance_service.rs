@@ -0,0 +1,210 @@
+/**
+ * REQUIREMENT: Users can find out where any stored number came from
+ * PURPOSE: Resolve the source URL, crawl time and processing steps of a data point
+ * or SEC fact from its lineage record. Values stored before lineage was recorded
+ * are traced to the closest earlier successful crawl of their series, or to the
+ * filing of the fact, and marked as inferred
+ */
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use url::Url;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{CrawlAttempt, DataLineage, ProcessingStep},
+    schema::{
+        crawl_attempts, data_points, data_sources, economic_series, financial_line_items,
+        financial_statements,
+    },
+};
+
+/// Query parameters that carry credentials and never leave the server
+const CREDENTIAL_PARAMS: &[&str] = &["api_key", "apikey", "registrationkey", "token"];
+
+/// Where a stored value came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    /// Data source name, e.g. "FRED"
+    pub source: String,
+    pub source_url: Option<String>,
+    pub crawled_at: Option<DateTime<Utc>>,
+    pub crawl_attempt_id: Option<Uuid>,
+    /// Filing an SEC fact was parsed from
+    pub statement_id: Option<Uuid>,
+    pub processing_steps: Vec<ProcessingStep>,
+    /// Whether lineage was recorded at ingestion rather than inferred afterwards
+    pub recorded: bool,
+}
+
+impl From<DataLineage> for Provenance {
+    fn from(lineage: DataLineage) -> Self {
+        let processing_steps = lineage.steps();
+        Self {
+            source: lineage.source,
+            source_url: lineage.source_url.as_deref().map(redact_credentials),
+            crawled_at: Some(lineage.crawled_at),
+            crawl_attempt_id: lineage.crawl_attempt_id,
+            statement_id: lineage.statement_id,
+            processing_steps,
+            recorded: true,
+        }
+    }
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+/// Provenance of a data point, or `None` if the data point does not exist
+pub async fn data_point_provenance(
+    pool: &DatabasePool,
+    data_point_id: Uuid,
+) -> AppResult<Option<Provenance>> {
+    if let Some(lineage) = DataLineage::find_for_data_point(pool, data_point_id).await? {
+        return Ok(Some(lineage.into()));
+    }
+
+    let mut conn = pool.get().await.map_err(connection_error)?;
+
+    let point: Option<(Uuid, DateTime<Utc>, String)> = data_points::table
+        .inner_join(economic_series::table.inner_join(data_sources::table))
+        .filter(data_points::id.eq(data_point_id))
+        .select((
+            data_points::series_id,
+            data_points::created_at,
+            data_sources::name,
+        ))
+        .first(&mut conn)
+        .await
+        .optional()?;
+    let Some((series_id, stored_at, source)) = point else {
+        return Ok(None);
+    };
+
+    // The last successful crawl before the point was written is the one that wrote it
+    let attempt: Option<CrawlAttempt> = crawl_attempts::table
+        .filter(crawl_attempts::series_id.eq(series_id))
+        .filter(crawl_attempts::success.eq(true))
+        .filter(crawl_attempts::attempted_at.le(stored_at))
+        .order(crawl_attempts::attempted_at.desc())
+        .select(CrawlAttempt::as_select())
+        .first(&mut conn)
+        .await
+        .optional()?;
+
+    Ok(Some(Provenance {
+        source,
+        source_url: attempt
+            .as_ref()
+            .and_then(|attempt| attempt.crawl_url.as_deref())
+            .map(redact_credentials),
+        crawled_at: attempt.as_ref().map(|attempt| attempt.attempted_at),
+        crawl_attempt_id: attempt.map(|attempt| attempt.id),
+        statement_id: None,
+        processing_steps: Vec::new(),
+        recorded: false,
+    }))
+}
+
+/// Provenance of an SEC fact, or `None` if the line item does not exist
+pub async fn financial_line_item_provenance(
+    pool: &DatabasePool,
+    financial_line_item_id: Uuid,
+) -> AppResult<Option<Provenance>> {
+    if let Some(lineage) =
+        DataLineage::find_for_financial_line_item(pool, financial_line_item_id).await?
+    {
+        return Ok(Some(lineage.into()));
+    }
+
+    let mut conn = pool.get().await.map_err(connection_error)?;
+
+    let filing: Option<(Uuid, String, DateTime<Utc>, String)> = financial_line_items::table
+        .inner_join(financial_statements::table)
+        .filter(financial_line_items::id.eq(financial_line_item_id))
+        .select((
+            financial_statements::id,
+            financial_statements::document_url,
+            financial_statements::created_at,
+            financial_statements::xbrl_processing_status,
+        ))
+        .first(&mut conn)
+        .await
+        .optional()?;
+
+    Ok(filing.map(
+        |(statement_id, document_url, downloaded_at, status)| Provenance {
+            source: "SEC EDGAR".to_string(),
+            source_url: Some(redact_credentials(&document_url)),
+            crawled_at: Some(downloaded_at),
+            crawl_attempt_id: None,
+            statement_id: Some(statement_id),
+            processing_steps: vec![
+                ProcessingStep::new("xbrl_download"),
+                ProcessingStep::new("xbrl_parse").with_detail(format!("status: {}", status)),
+            ],
+            recorded: false,
+        },
+    ))
+}
+
+/// Remove credential query parameters from a crawl URL
+///
+/// Crawl attempts store the URL that was requested, which for some sources
+/// includes an API key. Strings that are not URLs are returned unchanged.
+pub fn redact_credentials(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let is_credential =
+        |name: &str| CREDENTIAL_PARAMS.contains(&name.to_ascii_lowercase().as_str());
+    if !parsed.query_pairs().any(|(name, _)| is_credential(&name)) {
+        return url.to_string();
+    }
+
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(name, _)| !is_credential(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    parsed.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_credentials() {
+        // REQUIREMENT: Provenance shows the URL a value was crawled from
+        // PURPOSE: Verify API keys are stripped from crawl URLs and other parameters kept
+        // This ensures provenance queries never expose data source credentials
+
+        assert_eq!(
+            redact_credentials(
+                "https://api.stlouisfed.org/fred/series/observations?series_id=GDP&api_key=secret&file_type=json"
+            ),
+            "https://api.stlouisfed.org/fred/series/observations?series_id=GDP&file_type=json"
+        );
+        assert_eq!(
+            redact_credentials("https://api.example.com/data?ApiKey=secret"),
+            "https://api.example.com/data"
+        );
+        assert_eq!(
+            redact_credentials("https://api.bls.gov/publicAPI/v2/timeseries/data/CUUR0000SA0"),
+            "https://api.bls.gov/publicAPI/v2/timeseries/data/CUUR0000SA0"
+        );
+        assert_eq!(
+            redact_credentials("unknown://FRED/GDP"),
+            "unknown://FRED/GDP"
+        );
+    }
+}
@@ -1,15 +1,219 @@
 use chrono::{DateTime, Duration, Utc};
+use diesel::pg::Pg;
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel::sql_types::{BigInt, Text};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+use tracing::{info, warn};
 use uuid::Uuid;
+use validator::Validate;
 
 use econ_graph_core::{
     database::DatabasePool,
-    error::AppResult,
-    models::{CrawlQueueItem, QueueStatistics, QueueStatus, UpdateCrawlQueueItem},
+    error::{AppError, AppResult},
+    models::{
+        CrawlQueueFilter, CrawlQueueItem, NewCrawlQueueItem, QueueDepth, QueueStatistics,
+        QueueStatus, UpdateCrawlQueueItem,
+    },
     schema::crawl_queue,
+    shutdown::shutdown_coordinator,
 };
 
+/// Dead-letter queue size above which an alert is raised
+pub const DEFAULT_DEAD_LETTER_ALERT_THRESHOLD: i64 = 100;
+
+/// Most items a single bulk requeue or cancel may touch
+pub const MAX_BULK_QUEUE_ITEMS: usize = 500;
+
+/// Statuses an operator may put back on the queue; `processing` covers stuck jobs
+const REQUEUEABLE_STATUSES: [QueueStatus; 5] = [
+    QueueStatus::Processing,
+    QueueStatus::Failed,
+    QueueStatus::Retrying,
+    QueueStatus::Cancelled,
+    QueueStatus::DeadLetter,
+];
+
+/// Statuses an operator may cancel; finished items are left alone
+const CANCELLABLE_STATUSES: [QueueStatus; 5] = [
+    QueueStatus::Pending,
+    QueueStatus::Processing,
+    QueueStatus::Failed,
+    QueueStatus::Retrying,
+    QueueStatus::DeadLetter,
+];
+
+/// Item a crawl request was queued as
+#[derive(Debug, Clone)]
+pub struct QueuedCrawl {
+    pub item: CrawlQueueItem,
+    /// Whether a pending item for the same series absorbed the request
+    pub coalesced: bool,
+}
+
+/// Add a crawl to the queue unless the series already has one pending
+///
+/// A pending item of the same source and series that runs at the same time,
+/// both due now or both scheduled for the same moment, absorbs the request
+/// and keeps the higher priority and retry budget of the two. Crawls scheduled
+/// for another time, such as one after an upcoming release, stay separate.
+pub async fn enqueue_item(
+    pool: &DatabasePool,
+    new_item: &NewCrawlQueueItem,
+) -> AppResult<QueuedCrawl> {
+    use crawl_queue::dsl;
+
+    new_item.validate()?;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let new_item = new_item.clone();
+    let now = Utc::now();
+    let queued = conn
+        .transaction::<_, AppError, _>(|conn| {
+            async move {
+                // Two requests for the same series must not both find nothing and insert
+                diesel::sql_query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+                    .bind::<Text, _>(format!(
+                        "crawl_queue:{}:{}",
+                        new_item.source, new_item.series_id
+                    ))
+                    .execute(conn)
+                    .await?;
+
+                let pending = dsl::crawl_queue
+                    .filter(dsl::source.eq(&new_item.source))
+                    .filter(dsl::series_id.eq(&new_item.series_id))
+                    .filter(dsl::status.eq(QueueStatus::Pending.to_string()))
+                    .filter(dsl::locked_by.is_null())
+                    .order(dsl::created_at.asc());
+                let existing = match new_item.scheduled_for.filter(|at| *at > now) {
+                    Some(at) => {
+                        pending
+                            .filter(dsl::scheduled_for.eq(at))
+                            .for_update()
+                            .first::<CrawlQueueItem>(conn)
+                            .await
+                    }
+                    None => {
+                        pending
+                            .filter(dsl::scheduled_for.is_null().or(dsl::scheduled_for.le(now)))
+                            .for_update()
+                            .first::<CrawlQueueItem>(conn)
+                            .await
+                    }
+                }
+                .optional()?;
+
+                let Some(existing) = existing else {
+                    let item = diesel::insert_into(dsl::crawl_queue)
+                        .values(&new_item)
+                        .get_result::<CrawlQueueItem>(conn)
+                        .await?;
+                    return Ok(QueuedCrawl {
+                        item,
+                        coalesced: false,
+                    });
+                };
+
+                let item = diesel::update(dsl::crawl_queue.find(existing.id))
+                    .set((
+                        dsl::priority.eq(existing.priority.max(new_item.priority)),
+                        dsl::max_retries.eq(existing.max_retries.max(new_item.max_retries)),
+                        dsl::updated_at.eq(Utc::now()),
+                    ))
+                    .get_result::<CrawlQueueItem>(conn)
+                    .await?;
+                Ok(QueuedCrawl {
+                    item,
+                    coalesced: true,
+                })
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if queued.coalesced {
+        CRAWLER_METRICS.record_queue_coalesced(&queued.item.source, 1);
+    }
+
+    Ok(queued)
+}
+
+#[derive(QueryableByName)]
+struct CoalescedCount {
+    #[diesel(sql_type = Text)]
+    source: String,
+    #[diesel(sql_type = BigInt)]
+    coalesced: i64,
+}
+
+/// Merge pending items that crawl the same series at the same time into one
+///
+/// Covers duplicates [`enqueue_item`] cannot prevent, such as items put back
+/// on the queue by a requeue. The oldest item of each group is kept with the
+/// group's highest priority and retry budget; the others are deleted. Items
+/// a worker has locked are left alone. Returns the number of items removed.
+pub async fn coalesce_pending_items(pool: &DatabasePool) -> AppResult<u64> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    // Due items all run now, so they group together; scheduled ones group by time
+    let counts = diesel::sql_query(
+        "WITH pending AS (
+             SELECT id, source, series_id, priority, max_retries, created_at,
+                    GREATEST(COALESCE(scheduled_for, NOW()), NOW()) AS runs_at
+             FROM crawl_queue
+             WHERE status = 'pending' AND locked_by IS NULL
+             FOR UPDATE SKIP LOCKED
+         ),
+         grouped AS (
+             SELECT id, priority, max_retries,
+                    first_value(id) OVER (
+                        PARTITION BY source, series_id, runs_at ORDER BY created_at, id
+                    ) AS keep_id
+             FROM pending
+         ),
+         merged AS (
+             SELECT keep_id, MAX(priority) AS priority, MAX(max_retries) AS max_retries
+             FROM grouped
+             GROUP BY keep_id
+             HAVING COUNT(*) > 1
+         ),
+         kept AS (
+             UPDATE crawl_queue cq
+             SET priority = merged.priority, max_retries = merged.max_retries, updated_at = NOW()
+             FROM merged
+             WHERE cq.id = merged.keep_id
+             RETURNING cq.id
+         ),
+         removed AS (
+             DELETE FROM crawl_queue cq
+             USING grouped
+             WHERE cq.id = grouped.id AND grouped.id <> grouped.keep_id
+             RETURNING cq.source
+         )
+         SELECT source, COUNT(*) AS coalesced FROM removed GROUP BY source",
+    )
+    .load::<CoalescedCount>(&mut conn)
+    .await?;
+
+    let mut total = 0;
+    for count in counts {
+        CRAWLER_METRICS.record_queue_coalesced(&count.source, count.coalesced as u64);
+        total += count.coalesced as u64;
+    }
+    if total > 0 {
+        info!("Coalesced {} duplicate pending crawl queue items", total);
+    }
+
+    Ok(total)
+}
+
 /// Get next queue items for processing using SKIP LOCKED
 /// This implements PostgreSQL's SKIP LOCKED feature for concurrent queue processing
 pub async fn get_next_queue_items(
@@ -136,9 +340,9 @@ pub async fn update_queue_item_for_retry(
 
     let new_retry_count = current_item.retry_count + 1;
     let new_status = if new_retry_count >= current_item.max_retries {
-        "failed".to_string() // Max retries exceeded
+        QueueStatus::DeadLetter.to_string() // Max retries exceeded, park for review
     } else {
-        "retrying".to_string()
+        QueueStatus::Retrying.to_string()
     };
 
     // Schedule retry with exponential backoff (2^retry_count minutes)
@@ -159,11 +363,21 @@ pub async fn update_queue_item_for_retry(
         updated_at: Utc::now(),
     };
 
+    let dead_lettered = update.status.as_deref() == Some("dead_letter");
+
     diesel::update(dsl::crawl_queue.filter(dsl::id.eq(item_id)))
         .set(&update)
         .execute(&mut conn)
         .await?;
 
+    if dead_lettered {
+        warn!(
+            "Queue item {} ({} {}) moved to dead-letter queue after {} attempts",
+            item_id, current_item.source, current_item.series_id, new_retry_count
+        );
+        CRAWLER_METRICS.record_dead_lettered(&current_item.source);
+    }
+
     Ok(())
 }
 
@@ -243,6 +457,12 @@ pub async fn get_queue_statistics(pool: &DatabasePool) -> AppResult<QueueStatist
         .first(&mut conn)
         .await?;
 
+    let dead_letter_items: i64 = dsl::crawl_queue
+        .filter(dsl::status.eq("dead_letter"))
+        .select(count(dsl::id))
+        .first(&mut conn)
+        .await?;
+
     // Get oldest pending item
     let oldest_pending: Option<DateTime<Utc>> = dsl::crawl_queue
         .filter(dsl::status.eq("pending"))
@@ -260,6 +480,7 @@ pub async fn get_queue_statistics(pool: &DatabasePool) -> AppResult<QueueStatist
         completed_items,
         failed_items,
         retrying_items,
+        dead_letter_items,
         oldest_pending,
         average_processing_time: avg_processing_time,
     })
@@ -332,11 +553,15 @@ pub async fn cleanup_old_queue_items_with_retention(
 }
 
 /// Get next item for processing by a specific worker (convenience method)
-/// This combines getting and locking an item in one operation
+/// This combines getting and locking an item in one operation; nothing is
+/// claimed once the process is draining for shutdown
 pub async fn get_and_lock_next_item(
     pool: &DatabasePool,
     worker_id: &str,
 ) -> AppResult<Option<CrawlQueueItem>> {
+    if shutdown_coordinator().is_draining() {
+        return Ok(None);
+    }
     // Use the model's built-in method which implements SKIP LOCKED
     CrawlQueueItem::get_next_for_processing(pool, worker_id).await
 }
@@ -396,6 +621,374 @@ pub async fn unlock_stuck_items(pool: &DatabasePool, timeout_minutes: i64) -> Ap
     Ok(unlocked_count)
 }
 
+/// List dead-lettered items for review, most recently failed first
+pub async fn list_dead_letter_items(
+    pool: &DatabasePool,
+    source: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> AppResult<Vec<CrawlQueueItem>> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        econ_graph_core::error::AppError::DatabaseError(format!(
+            "Failed to get database connection: {}",
+            e
+        ))
+    })?;
+
+    let mut query = dsl::crawl_queue
+        .filter(dsl::status.eq("dead_letter"))
+        .into_boxed();
+    if let Some(source) = source {
+        query = query.filter(dsl::source.eq(source));
+    }
+
+    let items = query
+        .order(dsl::updated_at.desc())
+        .limit(limit)
+        .offset(offset)
+        .load::<CrawlQueueItem>(&mut conn)
+        .await?;
+
+    Ok(items)
+}
+
+/// Count items in the dead-letter queue
+pub async fn count_dead_letter_items(pool: &DatabasePool) -> AppResult<i64> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        econ_graph_core::error::AppError::DatabaseError(format!(
+            "Failed to get database connection: {}",
+            e
+        ))
+    })?;
+
+    let count: i64 = dsl::crawl_queue
+        .filter(dsl::status.eq("dead_letter"))
+        .count()
+        .get_result(&mut conn)
+        .await?;
+
+    Ok(count)
+}
+
+/// Put a dead-lettered item back on the queue with a fresh retry budget
+///
+/// Returns `None` when the item does not exist or is not dead-lettered.
+pub async fn requeue_dead_letter_item(
+    pool: &DatabasePool,
+    item_id: Uuid,
+) -> AppResult<Option<CrawlQueueItem>> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        econ_graph_core::error::AppError::DatabaseError(format!(
+            "Failed to get database connection: {}",
+            e
+        ))
+    })?;
+
+    // error_message is kept so the last failure stays visible after requeueing
+    let item = diesel::update(
+        dsl::crawl_queue
+            .filter(dsl::id.eq(item_id))
+            .filter(dsl::status.eq("dead_letter")),
+    )
+    .set((
+        dsl::status.eq(QueueStatus::Pending.to_string()),
+        dsl::retry_count.eq(0),
+        dsl::scheduled_for.eq(None::<DateTime<Utc>>),
+        dsl::locked_by.eq(None::<String>),
+        dsl::locked_at.eq(None::<DateTime<Utc>>),
+        dsl::updated_at.eq(Utc::now()),
+    ))
+    .get_result::<CrawlQueueItem>(&mut conn)
+    .await
+    .optional()?;
+
+    if item.is_some() {
+        CRAWLER_METRICS.crawler_dead_letter_queue_size.dec();
+    }
+
+    Ok(item)
+}
+
+/// Permanently remove a dead-lettered item
+///
+/// Returns `false` when the item does not exist or is not dead-lettered.
+pub async fn discard_dead_letter_item(pool: &DatabasePool, item_id: Uuid) -> AppResult<bool> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        econ_graph_core::error::AppError::DatabaseError(format!(
+            "Failed to get database connection: {}",
+            e
+        ))
+    })?;
+
+    let deleted = diesel::delete(
+        dsl::crawl_queue
+            .filter(dsl::id.eq(item_id))
+            .filter(dsl::status.eq("dead_letter")),
+    )
+    .execute(&mut conn)
+    .await?;
+
+    if deleted > 0 {
+        CRAWLER_METRICS.crawler_dead_letter_queue_size.dec();
+    }
+
+    Ok(deleted > 0)
+}
+
+/// Queue items matching `filter` in processing order (priority, then age)
+pub async fn list_queue_items(
+    pool: &DatabasePool,
+    filter: &CrawlQueueFilter,
+    limit: i64,
+    offset: i64,
+) -> AppResult<Vec<CrawlQueueItem>> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let items = filtered_queue(filter)
+        .order((dsl::priority.desc(), dsl::created_at.asc(), dsl::id.asc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<CrawlQueueItem>(&mut conn)
+        .await?;
+
+    Ok(items)
+}
+
+/// Number of queue items matching `filter`
+pub async fn count_queue_items(pool: &DatabasePool, filter: &CrawlQueueFilter) -> AppResult<i64> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    Ok(filtered_queue(filter)
+        .count()
+        .get_result::<i64>(&mut conn)
+        .await?)
+}
+
+fn filtered_queue(filter: &CrawlQueueFilter) -> crawl_queue::BoxedQuery<'_, Pg> {
+    use crawl_queue::dsl;
+
+    let mut query = dsl::crawl_queue.into_boxed();
+    if let Some(status) = &filter.status {
+        query = query.filter(dsl::status.eq(status.to_string()));
+    }
+    if let Some(source) = &filter.source {
+        query = query.filter(dsl::source.eq(source));
+    }
+    if let Some(min_priority) = filter.min_priority {
+        query = query.filter(dsl::priority.ge(min_priority));
+    }
+    if let Some(max_priority) = filter.max_priority {
+        query = query.filter(dsl::priority.le(max_priority));
+    }
+    query
+}
+
+/// Put items back on the queue as pending with a fresh retry budget
+///
+/// Items that do not exist or are pending or completed are skipped; the
+/// requeued items are returned. Processing items are unlocked, so this also
+/// recovers jobs stuck on a crashed worker.
+pub async fn requeue_queue_items(
+    pool: &DatabasePool,
+    item_ids: &[Uuid],
+) -> AppResult<Vec<CrawlQueueItem>> {
+    use crawl_queue::dsl;
+
+    check_bulk_size(item_ids)?;
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    // error_message is kept so the last failure stays visible after requeueing
+    let items = diesel::update(
+        dsl::crawl_queue
+            .filter(dsl::id.eq_any(item_ids))
+            .filter(dsl::status.eq_any(status_names(&REQUEUEABLE_STATUSES))),
+    )
+    .set((
+        dsl::status.eq(QueueStatus::Pending.to_string()),
+        dsl::retry_count.eq(0),
+        dsl::scheduled_for.eq(None::<DateTime<Utc>>),
+        dsl::locked_by.eq(None::<String>),
+        dsl::locked_at.eq(None::<DateTime<Utc>>),
+        dsl::updated_at.eq(Utc::now()),
+    ))
+    .get_results::<CrawlQueueItem>(&mut conn)
+    .await?;
+
+    refresh_dead_letter_queue_size(pool).await?;
+    Ok(items)
+}
+
+/// Cancel items so no worker picks them up
+///
+/// Items that do not exist or are already completed or cancelled are
+/// skipped; the cancelled items are returned.
+pub async fn cancel_queue_items(
+    pool: &DatabasePool,
+    item_ids: &[Uuid],
+) -> AppResult<Vec<CrawlQueueItem>> {
+    use crawl_queue::dsl;
+
+    check_bulk_size(item_ids)?;
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let items = diesel::update(
+        dsl::crawl_queue
+            .filter(dsl::id.eq_any(item_ids))
+            .filter(dsl::status.eq_any(status_names(&CANCELLABLE_STATUSES))),
+    )
+    .set((
+        dsl::status.eq(QueueStatus::Cancelled.to_string()),
+        dsl::scheduled_for.eq(None::<DateTime<Utc>>),
+        dsl::locked_by.eq(None::<String>),
+        dsl::locked_at.eq(None::<DateTime<Utc>>),
+        dsl::updated_at.eq(Utc::now()),
+    ))
+    .get_results::<CrawlQueueItem>(&mut conn)
+    .await?;
+
+    refresh_dead_letter_queue_size(pool).await?;
+    Ok(items)
+}
+
+/// Push a source's outstanding work back until `until`
+///
+/// Called when the source's daily quota runs out. `item_id` is the item the
+/// worker was processing; it is unlocked along with every pending or retrying
+/// item of the source that would run earlier. Retry counts are left alone
+/// since nothing failed. Returns the number of deferred items.
+pub async fn defer_source_items(
+    pool: &DatabasePool,
+    source: &str,
+    item_id: Uuid,
+    until: DateTime<Utc>,
+    reason: &str,
+) -> AppResult<usize> {
+    use crawl_queue::dsl;
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let waiting = dsl::status
+        .eq_any(status_names(&[QueueStatus::Pending, QueueStatus::Retrying]))
+        .and(
+            dsl::scheduled_for
+                .is_null()
+                .or(dsl::scheduled_for.lt(until)),
+        );
+
+    let deferred = diesel::update(
+        dsl::crawl_queue
+            .filter(dsl::source.eq(source))
+            .filter(dsl::id.eq(item_id).or(waiting)),
+    )
+    .set((
+        dsl::status.eq(QueueStatus::Pending.to_string()),
+        dsl::scheduled_for.eq(Some(until)),
+        dsl::error_message.eq(Some(reason)),
+        dsl::locked_by.eq(None::<String>),
+        dsl::locked_at.eq(None::<DateTime<Utc>>),
+        dsl::updated_at.eq(Utc::now()),
+    ))
+    .execute(&mut conn)
+    .await?;
+
+    CRAWLER_METRICS.record_quota_deferrals(source, deferred as u64);
+    Ok(deferred)
+}
+
+fn check_bulk_size(item_ids: &[Uuid]) -> AppResult<()> {
+    if item_ids.len() > MAX_BULK_QUEUE_ITEMS {
+        return Err(AppError::ValidationError(format!(
+            "At most {} queue items can be changed at once",
+            MAX_BULK_QUEUE_ITEMS
+        )));
+    }
+    Ok(())
+}
+
+fn status_names(statuses: &[QueueStatus]) -> Vec<String> {
+    statuses.iter().map(ToString::to_string).collect()
+}
+
+/// Outstanding items (not completed or cancelled) per source and status
+pub async fn get_queue_depth(pool: &DatabasePool) -> AppResult<Vec<QueueDepth>> {
+    use crawl_queue::dsl;
+    use diesel::dsl::{count_star, min};
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let rows: Vec<(String, String, i64, Option<DateTime<Utc>>)> = dsl::crawl_queue
+        .filter(dsl::status.ne_all(status_names(&[
+            QueueStatus::Completed,
+            QueueStatus::Cancelled,
+        ])))
+        .group_by((dsl::source, dsl::status))
+        .select((dsl::source, dsl::status, count_star(), min(dsl::created_at)))
+        .order_by((dsl::source.asc(), dsl::status.asc()))
+        .load(&mut conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(source, status, items, oldest_created_at)| QueueDepth {
+            source,
+            status,
+            items,
+            oldest_created_at,
+        })
+        .collect())
+}
+
+/// Re-read the dead-letter queue size metric after a bulk change
+async fn refresh_dead_letter_queue_size(pool: &DatabasePool) -> AppResult<()> {
+    let size = count_dead_letter_items(pool).await?;
+    CRAWLER_METRICS.set_dead_letter_queue_size(size);
+    Ok(())
+}
+
+/// Refresh the dead-letter queue size metric and alert when it crosses `threshold`
+///
+/// Returns the current size.
+pub async fn check_dead_letter_queue(pool: &DatabasePool, threshold: i64) -> AppResult<i64> {
+    let size = count_dead_letter_items(pool).await?;
+    CRAWLER_METRICS.set_dead_letter_queue_size(size);
+
+    if dead_letter_threshold_exceeded(size, threshold) {
+        warn!(
+            "Dead-letter queue has {} items, above the alert threshold of {}",
+            size, threshold
+        );
+    }
+
+    Ok(size)
+}
+
+/// Whether the dead-letter queue size warrants an alert
+pub fn dead_letter_threshold_exceeded(size: i64, threshold: i64) -> bool {
+    size > threshold.max(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -592,16 +1185,260 @@ mod tests {
         let stats = get_queue_statistics(&pool).await.unwrap();
         assert_eq!(stats.retrying_items, 1);
 
-        // Second retry (should reach max retries and fail)
+        // Second retry (should reach max retries and be dead-lettered)
         update_queue_item_for_retry(&pool, created_item.id, Some("Still timing out".to_string()))
             .await
             .unwrap();
 
         let stats = get_queue_statistics(&pool).await.unwrap();
-        assert_eq!(stats.failed_items, 1);
+        assert_eq!(stats.dead_letter_items, 1);
+        assert_eq!(stats.failed_items, 0);
         assert_eq!(stats.retrying_items, 0);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_dead_letter_requeue_and_discard() {
+        // REQUIREMENT: Poison items are parked for admin review instead of retried forever
+        // PURPOSE: Verify dead-lettered items can be listed, requeued and discarded
+        // This ensures admins can recover transient failures and drop bad series IDs
+
+        let container = TestContainer::new().await;
+        container.clean_database().await.unwrap();
+        let pool = container.pool();
+
+        let mut dead_ids = Vec::new();
+        for series_id in ["BAD_SERIES", "FLAKY_SERIES"] {
+            let item = CrawlQueueItem::create(
+                &pool,
+                &NewCrawlQueueItem {
+                    source: "FRED".to_string(),
+                    series_id: series_id.to_string(),
+                    priority: 5,
+                    max_retries: 1,
+                    scheduled_for: None,
+                },
+            )
+            .await
+            .unwrap();
+            update_queue_item_for_retry(&pool, item.id, Some("HTTP 400".to_string()))
+                .await
+                .unwrap();
+            dead_ids.push(item.id);
+        }
+
+        let listed = list_dead_letter_items(&pool, Some("FRED"), 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(check_dead_letter_queue(&pool, 1).await.unwrap(), 2);
+
+        let requeued = requeue_dead_letter_item(&pool, dead_ids[1])
+            .await
+            .unwrap()
+            .expect("dead-lettered item should be requeued");
+        assert_eq!(requeued.status, "pending");
+        assert_eq!(requeued.retry_count, 0);
+        assert!(requeue_dead_letter_item(&pool, dead_ids[1])
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(discard_dead_letter_item(&pool, dead_ids[0]).await.unwrap());
+        assert!(!discard_dead_letter_item(&pool, dead_ids[1]).await.unwrap());
+        assert_eq!(count_dead_letter_items(&pool).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_queue_admin_filter_requeue_and_cancel() {
+        // REQUIREMENT: Operators manage the crawl queue without database access
+        // PURPOSE: Verify filtered listing, bulk requeue and cancel, and queue depth
+        // This ensures stuck and failed jobs can be recovered from the admin API
+
+        let container = TestContainer::new().await;
+        container.clean_database().await.unwrap();
+        let pool = container.pool();
+
+        let mut ids = Vec::new();
+        for (source, priority) in [("FRED", 9), ("FRED", 3), ("BLS", 5)] {
+            let item = CrawlQueueItem::create(
+                &pool,
+                &NewCrawlQueueItem {
+                    source: source.to_string(),
+                    series_id: format!("{}_{}", source, priority),
+                    priority,
+                    max_retries: 3,
+                    scheduled_for: None,
+                },
+            )
+            .await
+            .unwrap();
+            ids.push(item.id);
+        }
+        // A job stuck on a crashed worker
+        lock_queue_item(&pool, ids[0], "worker-1").await.unwrap();
+
+        let fred = CrawlQueueFilter {
+            source: Some("FRED".to_string()),
+            ..Default::default()
+        };
+        let listed = list_queue_items(&pool, &fred, 10, 0).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].id, ids[0], "Higher priority should come first");
+        assert_eq!(count_queue_items(&pool, &fred).await.unwrap(), 2);
+
+        let high_priority = CrawlQueueFilter {
+            min_priority: Some(5),
+            status: Some(QueueStatus::Pending),
+            ..Default::default()
+        };
+        assert_eq!(count_queue_items(&pool, &high_priority).await.unwrap(), 1);
+
+        let requeued = requeue_queue_items(&pool, &ids).await.unwrap();
+        assert_eq!(requeued.len(), 1, "Only the stuck item should be requeued");
+        assert_eq!(requeued[0].status, "pending");
+        assert!(requeued[0].locked_by.is_none());
+
+        let cancelled = cancel_queue_items(&pool, &ids[1..]).await.unwrap();
+        assert_eq!(cancelled.len(), 2);
+        assert!(cancel_queue_items(&pool, &ids[1..])
+            .await
+            .unwrap()
+            .is_empty());
+
+        let depth = get_queue_depth(&pool).await.unwrap();
+        assert_eq!(depth.len(), 1);
+        assert_eq!(depth[0].source, "FRED");
+        assert_eq!(depth[0].status, "pending");
+        assert_eq!(depth[0].items, 1);
+
+        let too_many = vec![Uuid::new_v4(); MAX_BULK_QUEUE_ITEMS + 1];
+        assert!(requeue_queue_items(&pool, &too_many).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_duplicate_crawls_are_coalesced() {
+        // REQUIREMENT: A series is not crawled twice for one request, wasting API quota
+        // PURPOSE: Verify pending items for the same series and run time merge into one with the highest priority
+        // This ensures repeated scheduling of a series costs one API call while crawls for a later release stay queued
+
+        let container = TestContainer::new().await;
+        container.clean_database().await.unwrap();
+        let pool = container.pool();
+        let scope = econ_graph_metrics::crawler::CrawlerMetrics::test_scope();
+
+        let request = |priority: i32, scheduled_for: Option<DateTime<Utc>>| NewCrawlQueueItem {
+            source: "FRED".to_string(),
+            series_id: "CPIAUCSL".to_string(),
+            priority,
+            max_retries: 3,
+            scheduled_for,
+        };
+        let release = Utc::now() + Duration::hours(2);
+
+        let first = enqueue_item(&pool, &request(3, None)).await.unwrap();
+        let second = enqueue_item(&pool, &request(8, None)).await.unwrap();
+        let after_release = enqueue_item(&pool, &request(8, Some(release)))
+            .await
+            .unwrap();
+        assert!(!first.coalesced);
+        assert!(second.coalesced);
+        assert_eq!(second.item.id, first.item.id);
+        assert_eq!(second.item.priority, 8);
+        assert!(!after_release.coalesced);
+
+        // Duplicates inserted directly, as a requeue would, are merged by the sweep
+        CrawlQueueItem::create(&pool, &request(10, None))
+            .await
+            .unwrap();
+        CrawlQueueItem::create(&pool, &request(5, Some(Utc::now() - Duration::minutes(5))))
+            .await
+            .unwrap();
+        assert_eq!(coalesce_pending_items(&pool).await.unwrap(), 2);
+
+        let items = list_queue_items(&pool, &CrawlQueueFilter::default(), 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        let due = items.iter().find(|item| item.id == first.item.id).unwrap();
+        assert_eq!(due.priority, 10);
+        assert!(items.iter().any(|item| item.id == after_release.item.id));
+
+        let coalesced = scope
+            .metrics()
+            .crawler_queue_coalesced_total
+            .with_label_values(&["FRED"])
+            .get();
+        assert_eq!(coalesced, 3);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_defer_source_items_until_quota_reset() {
+        // REQUIREMENT: Work for a source whose daily quota ran out waits for the reset
+        // PURPOSE: Verify the current and waiting items of that source are rescheduled only
+        // This ensures workers stop picking up the source without burning retries
+
+        let container = TestContainer::new().await;
+        container.clean_database().await.unwrap();
+        let pool = container.pool();
+
+        let mut ids = Vec::new();
+        for (source, series_id) in [("FRED", "GDP"), ("FRED", "UNRATE"), ("BLS", "CUUR0000SA0")] {
+            let item = CrawlQueueItem::create(
+                &pool,
+                &NewCrawlQueueItem {
+                    source: source.to_string(),
+                    series_id: series_id.to_string(),
+                    priority: 5,
+                    max_retries: 3,
+                    scheduled_for: None,
+                },
+            )
+            .await
+            .unwrap();
+            ids.push(item.id);
+        }
+        lock_queue_item(&pool, ids[0], "worker-1").await.unwrap();
+
+        let until = Utc::now() + Duration::hours(6);
+        let deferred = defer_source_items(&pool, "FRED", ids[0], until, "FRED quota exhausted")
+            .await
+            .unwrap();
+        assert_eq!(deferred, 2);
+
+        let fred = CrawlQueueFilter {
+            source: Some("FRED".to_string()),
+            ..Default::default()
+        };
+        for item in list_queue_items(&pool, &fred, 10, 0).await.unwrap() {
+            assert_eq!(item.status, "pending");
+            assert_eq!(item.retry_count, 0);
+            assert!(item.locked_by.is_none());
+            assert_eq!(
+                item.scheduled_for.map(|at| at.timestamp()),
+                Some(until.timestamp())
+            );
+        }
+
+        let next = get_and_lock_next_item(&pool, "worker-2").await.unwrap();
+        assert_eq!(next.map(|item| item.source), Some("BLS".to_string()));
+    }
+
+    #[test]
+    fn test_dead_letter_threshold_exceeded() {
+        // REQUIREMENT: Alert when the dead-letter queue grows past a threshold
+        // PURPOSE: Verify the alert fires only above the configured size
+        // This ensures a handful of poison items does not page anyone
+
+        assert!(!dead_letter_threshold_exceeded(0, 100));
+        assert!(!dead_letter_threshold_exceeded(100, 100));
+        assert!(dead_letter_threshold_exceeded(101, 100));
+        assert!(dead_letter_threshold_exceeded(1, -5));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_get_and_lock_next_item() {
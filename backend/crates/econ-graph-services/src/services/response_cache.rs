@@ -0,0 +1,523 @@
+/**
+ * REQUIREMENT: Repeated GraphQL GET queries are answered without re-running them, and clients
+ * can revalidate with ETags instead of downloading unchanged results
+ * PURPOSE: Keep serialized query responses in a bounded LRU cache keyed by query hash,
+ * variables and auth scope, and derive ETags from the updated_at of the series they read
+ * Write paths call invalidate_series (or invalidate_all) so in-process writes change ETags
+ * even when a series' updated_at does not move
+ * Responses whose GraphQL cache hints allow it are also kept for their max age and served
+ * without revalidation, whatever the request method
+ */
+use chrono::{DateTime, Utc};
+use diesel::dsl::max;
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    schema::economic_series,
+};
+use econ_graph_metrics::cache::CACHE_METRICS;
+
+/// Cache name used in metric labels
+pub const RESPONSE_CACHE_NAME: &str = "graphql_responses";
+
+/// Largest serialized response that will be cached, in bytes
+const MAX_ENTRY_BYTES: usize = 1024 * 1024;
+
+/// Hex SHA-256 of a query document, as used for persisted query hashes
+pub fn query_hash(query: &str) -> String {
+    format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+/// Identifies one cached response
+///
+/// Responses are only shared between requests with the same query, operation,
+/// variables and auth scope, so one user's data is never served to another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    pub query_hash: String,
+    pub operation_name: Option<String>,
+    /// Variables serialized as JSON
+    pub variables: String,
+    /// `public` for anonymous requests, `user:<id>` otherwise
+    pub scope: String,
+}
+
+impl ResponseCacheKey {
+    pub fn new(
+        query_hash: String,
+        operation_name: Option<String>,
+        variables: &serde_json::Value,
+        user_id: Option<Uuid>,
+    ) -> Self {
+        Self {
+            query_hash,
+            operation_name,
+            variables: variables.to_string(),
+            scope: match user_id {
+                Some(user_id) => format!("user:{}", user_id),
+                None => "public".to_string(),
+            },
+        }
+    }
+}
+
+/// Series IDs mentioned in a request's variables
+///
+/// Every string variable that parses as a UUID is taken, including those
+/// nested in lists and input objects; the ETag of the response follows the
+/// `updated_at` of these series.
+pub fn series_ids_in_variables(variables: &serde_json::Value) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    collect_uuids(variables, &mut ids);
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+fn collect_uuids(value: &serde_json::Value, ids: &mut Vec<Uuid>) {
+    match value {
+        serde_json::Value::String(s) => ids.extend(Uuid::parse_str(s).ok()),
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_uuids(value, ids);
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for value in fields.values() {
+                collect_uuids(value, ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Weak ETag of a response
+///
+/// Changes whenever the series the response reads are updated or the cache
+/// generation moves (see [`ResponseCache::generation`]).
+pub fn entity_tag(
+    key: &ResponseCacheKey,
+    series_version: Option<DateTime<Utc>>,
+    generation: u64,
+) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        key.query_hash.as_str(),
+        key.operation_name.as_deref().unwrap_or(""),
+        key.variables.as_str(),
+        key.scope.as_str(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    if let Some(version) = series_version {
+        hasher.update(version.timestamp_micros().to_be_bytes());
+    }
+    hasher.update(generation.to_be_bytes());
+
+    let digest = format!("{:x}", hasher.finalize());
+    format!("W/\"{}\"", &digest[..32])
+}
+
+/// Whether an `If-None-Match` header value matches `etag`
+pub fn if_none_match_matches(header: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    header
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == opaque(etag))
+}
+
+/// Latest `updated_at` of the given series, or of all series when none are given
+pub async fn series_version(
+    pool: &DatabasePool,
+    series_ids: &[Uuid],
+) -> AppResult<Option<DateTime<Utc>>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let mut query = economic_series::table
+        .select(max(economic_series::updated_at))
+        .into_boxed();
+    if !series_ids.is_empty() {
+        query = query.filter(economic_series::id.eq_any(series_ids.to_vec()));
+    }
+
+    Ok(query.first::<Option<DateTime<Utc>>>(&mut conn).await?)
+}
+
+struct CachedResponse {
+    body: Arc<String>,
+    /// ETag the response is served under; `None` for hinted responses
+    etag: Option<String>,
+    /// Max age of a hinted response, which replaces the cache's TTL
+    max_age: Option<Duration>,
+    series_ids: Vec<Uuid>,
+    cached_at: Instant,
+    /// Value of the access counter when the entry was last read or written
+    last_used: u64,
+}
+
+impl CachedResponse {
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.cached_at.elapsed() < self.max_age.unwrap_or(ttl)
+    }
+}
+
+#[derive(Default)]
+struct LruEntries {
+    responses: HashMap<ResponseCacheKey, CachedResponse>,
+    access_counter: u64,
+}
+
+impl LruEntries {
+    fn next_access(&mut self) -> u64 {
+        self.access_counter += 1;
+        self.access_counter
+    }
+}
+
+/// In-memory LRU cache of serialized GraphQL responses
+///
+/// An entry is only served while its ETag is still current, so a response is
+/// never returned after the series it read were updated, even by another
+/// process.
+pub struct ResponseCache {
+    entries: Mutex<LruEntries>,
+    generation: AtomicU64,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(512, Duration::from_secs(300))
+    }
+}
+
+impl ResponseCache {
+    /// Create a cache holding at most `capacity` responses for `ttl` each
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(LruEntries::default()),
+            generation: AtomicU64::new(0),
+            capacity: capacity.max(1),
+            ttl,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Counter that moves on every invalidation; part of every ETag
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Cached body for `key` if it is fresh and was stored under `etag`
+    pub fn get(&self, key: &ResponseCacheKey, etag: &str) -> Option<Arc<String>> {
+        self.lookup(key, Some(etag))
+    }
+
+    /// Cached body for `key` if it was stored by [`ResponseCache::insert_hinted`]
+    /// and is younger than its max age
+    pub fn get_hinted(&self, key: &ResponseCacheKey) -> Option<Arc<String>> {
+        self.lookup(key, None)
+    }
+
+    /// Look up an ETag entry when `etag` is given, a hinted entry otherwise
+    ///
+    /// An entry of the other kind is left alone.
+    fn lookup(&self, key: &ResponseCacheKey, etag: Option<&str>) -> Option<Arc<String>> {
+        let mut entries = self.lock();
+        let access = entries.next_access();
+
+        let stale = match entries.responses.get_mut(key) {
+            Some(response) if response.etag.is_some() != etag.is_some() => false,
+            Some(response) if response.etag.as_deref() == etag && response.is_fresh(self.ttl) => {
+                response.last_used = access;
+                CACHE_METRICS.record_hit(RESPONSE_CACHE_NAME);
+                return Some(response.body.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if stale {
+            entries.responses.remove(key);
+            CACHE_METRICS.record_eviction(RESPONSE_CACHE_NAME, "expired");
+            CACHE_METRICS.set_entries(RESPONSE_CACHE_NAME, entries.responses.len());
+        }
+        CACHE_METRICS.record_miss(RESPONSE_CACHE_NAME);
+        None
+    }
+
+    /// Store a response body, evicting the least recently used response if full
+    ///
+    /// Bodies larger than 1 MiB are not cached.
+    pub fn insert(
+        &self,
+        key: ResponseCacheKey,
+        etag: String,
+        body: String,
+        series_ids: Vec<Uuid>,
+    ) -> Arc<String> {
+        self.store(key, Some(etag), None, body, series_ids)
+    }
+
+    /// Store a response whose cache hints allow it to be reused for `max_age`
+    ///
+    /// Writes to `series_ids` and [`ResponseCache::invalidate_all`] still drop
+    /// it early.
+    pub fn insert_hinted(
+        &self,
+        key: ResponseCacheKey,
+        body: String,
+        series_ids: Vec<Uuid>,
+        max_age: Duration,
+    ) -> Arc<String> {
+        self.store(key, None, Some(max_age), body, series_ids)
+    }
+
+    fn store(
+        &self,
+        key: ResponseCacheKey,
+        etag: Option<String>,
+        max_age: Option<Duration>,
+        body: String,
+        series_ids: Vec<Uuid>,
+    ) -> Arc<String> {
+        let body = Arc::new(body);
+        if body.len() > MAX_ENTRY_BYTES {
+            return body;
+        }
+
+        let mut entries = self.lock();
+        let access = entries.next_access();
+
+        if !entries.responses.contains_key(&key) && entries.responses.len() >= self.capacity {
+            let ttl = self.ttl;
+            let before = entries.responses.len();
+            entries
+                .responses
+                .retain(|_, response| response.is_fresh(ttl));
+            for _ in entries.responses.len()..before {
+                CACHE_METRICS.record_eviction(RESPONSE_CACHE_NAME, "expired");
+            }
+
+            if entries.responses.len() >= self.capacity {
+                let least_recent = entries
+                    .responses
+                    .iter()
+                    .min_by_key(|(_, response)| response.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(least_recent) = least_recent {
+                    entries.responses.remove(&least_recent);
+                    CACHE_METRICS.record_eviction(RESPONSE_CACHE_NAME, "capacity");
+                }
+            }
+        }
+
+        entries.responses.insert(
+            key,
+            CachedResponse {
+                body: body.clone(),
+                etag,
+                max_age,
+                series_ids,
+                cached_at: Instant::now(),
+                last_used: access,
+            },
+        );
+        CACHE_METRICS.set_entries(RESPONSE_CACHE_NAME, entries.responses.len());
+
+        body
+    }
+
+    /// Drop responses that read a series; call after writing its data
+    pub fn invalidate_series(&self, series_id: Uuid) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+
+        let mut entries = self.lock();
+        let before = entries.responses.len();
+        entries
+            .responses
+            .retain(|_, response| !response.series_ids.contains(&series_id));
+
+        for _ in entries.responses.len()..before {
+            CACHE_METRICS.record_eviction(RESPONSE_CACHE_NAME, "invalidated");
+        }
+        CACHE_METRICS.set_entries(RESPONSE_CACHE_NAME, entries.responses.len());
+    }
+
+    /// Drop every cached response; call after writes that are not tied to a series
+    pub fn invalidate_all(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+
+        let mut entries = self.lock();
+        for _ in 0..entries.responses.len() {
+            CACHE_METRICS.record_eviction(RESPONSE_CACHE_NAME, "invalidated");
+        }
+        entries.responses.clear();
+        CACHE_METRICS.set_entries(RESPONSE_CACHE_NAME, 0);
+    }
+
+    /// Number of cached responses, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.lock().responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Process-wide response cache
+pub fn shared_response_cache() -> &'static ResponseCache {
+    static CACHE: OnceLock<ResponseCache> = OnceLock::new();
+    CACHE.get_or_init(ResponseCache::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn key(variables: serde_json::Value, user_id: Option<Uuid>) -> ResponseCacheKey {
+        ResponseCacheKey::new(
+            query_hash("query Series($id: ID!) { series(id: $id) { title } }"),
+            Some("Series".to_string()),
+            &variables,
+            user_id,
+        )
+    }
+
+    #[test]
+    fn test_entity_tag_follows_scope_version_and_generation() {
+        // REQUIREMENT: ETags change when the data behind a response changes
+        // PURPOSE: Verify ETags differ by auth scope, series version and cache generation
+        // This ensures clients never get a 304 for data that was updated or belongs to someone else
+
+        let series_id = Uuid::new_v4();
+        let variables = json!({ "id": series_id.to_string() });
+        let public = key(variables.clone(), None);
+        let private = key(variables, Some(Uuid::new_v4()));
+        let version = Some(Utc::now());
+
+        let etag = entity_tag(&public, version, 0);
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(etag, entity_tag(&public, version, 0));
+        assert_ne!(etag, entity_tag(&private, version, 0));
+        assert_ne!(
+            etag,
+            entity_tag(&public, Some(Utc::now() + chrono::Duration::seconds(1)), 0)
+        );
+        assert_ne!(etag, entity_tag(&public, version, 1));
+
+        assert!(if_none_match_matches(&etag, &etag));
+        assert!(if_none_match_matches(
+            &format!("\"other\", {}", &etag[2..]),
+            &etag
+        ));
+        assert!(if_none_match_matches("*", &etag));
+        assert!(!if_none_match_matches("\"other\"", &etag));
+    }
+
+    #[test]
+    fn test_series_ids_in_variables() {
+        // REQUIREMENT: Cached responses are tied to the series they read
+        // PURPOSE: Verify UUIDs are found anywhere in the variables and other values are ignored
+        // This ensures writes to a series invalidate every response that asked for it
+
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let variables = json!({
+            "seriesId": a.to_string(),
+            "filter": { "seriesIds": [b.to_string(), a.to_string()] },
+            "limit": 10,
+            "query": "gdp"
+        });
+
+        let mut expected = vec![a, b];
+        expected.sort();
+        assert_eq!(series_ids_in_variables(&variables), expected);
+        assert!(series_ids_in_variables(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_cache_serves_current_etag_and_invalidates_on_write() {
+        // REQUIREMENT: Cached responses are never served after their data changed
+        // PURPOSE: Verify entries are only returned for their ETag and dropped when a series is written
+        // This ensures the cache cannot outlive a write made by this process
+
+        let cache = ResponseCache::new(2, Duration::from_secs(60));
+        let series_id = Uuid::new_v4();
+        let entry = key(json!({ "id": series_id.to_string() }), None);
+        let etag = entity_tag(&entry, None, cache.generation());
+
+        cache.insert(
+            entry.clone(),
+            etag.clone(),
+            "{}".to_string(),
+            vec![series_id],
+        );
+        assert_eq!(
+            cache.get(&entry, &etag).as_deref().map(String::as_str),
+            Some("{}")
+        );
+        assert!(cache.get(&entry, "W/\"stale\"").is_none());
+        assert!(cache.is_empty());
+
+        cache.insert(
+            entry.clone(),
+            etag.clone(),
+            "{}".to_string(),
+            vec![series_id],
+        );
+        let generation = cache.generation();
+        cache.invalidate_series(series_id);
+        assert!(cache.is_empty());
+        assert!(cache.generation() > generation);
+    }
+
+    #[test]
+    fn test_hinted_entries_follow_their_max_age() {
+        // REQUIREMENT: Responses the cache hints allow are reused without recomputing them
+        // PURPOSE: Verify hinted entries are served without an ETag until their max age passes, and are never served by ETag
+        // This ensures metadata queries are answered from memory only for as long as their hints allow
+
+        let cache = ResponseCache::new(4, Duration::from_secs(60));
+        let entry = key(json!({}), None);
+
+        cache.insert_hinted(
+            entry.clone(),
+            "{}".to_string(),
+            Vec::new(),
+            Duration::from_secs(60),
+        );
+        assert_eq!(
+            cache.get_hinted(&entry).as_deref().map(String::as_str),
+            Some("{}")
+        );
+        let etag = entity_tag(&entry, None, cache.generation());
+        assert!(cache.get(&entry, &etag).is_none());
+        assert!(cache.get_hinted(&entry).is_some());
+
+        cache.insert_hinted(entry.clone(), "{}".to_string(), Vec::new(), Duration::ZERO);
+        assert!(cache.get_hinted(&entry).is_none());
+        assert!(cache.is_empty());
+
+        cache.insert(entry.clone(), etag, "{}".to_string(), Vec::new());
+        assert!(cache.get_hinted(&entry).is_none());
+    }
+}
@@ -0,0 +1,291 @@
+/**
+ * REQUIREMENT: Storage of data point revisions must not grow without bound
+ * PURPOSE: Apply a retention policy to old revisions as a scheduled maintenance job
+ * Every revision published within the retention window is kept. Older revisions are
+ * deleted unless they are an observation's latest revision, its original release (when
+ * the policy keeps originals), or part of a manual correction. A dry run reports what
+ * would be deleted, per data source, without deleting anything.
+ */
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::sql_types::{BigInt, Bool, Date, Text};
+use diesel::QueryableByName;
+use diesel_async::RunQueryDsl;
+use tracing::info;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+};
+
+/// How often the backend runs the retention job
+pub const DEFAULT_RETENTION_INTERVAL_SECONDS: u64 = 86400;
+
+/// Revisions deleted per statement, so a run never holds long locks
+pub const RETENTION_DELETE_BATCH_SIZE: i64 = 10_000;
+
+/// Condition on a data point `dp` matching revisions the policy removes
+///
+/// Binds: `$1` cutoff date, `$2` whether original releases are kept. A revision
+/// is the latest of its observation when no revision with a later
+/// `(revision_date, id)` exists, matching the latest-revision queries.
+const EXPIRED_REVISION: &str = "dp.revision_date < $1
+       AND NOT ($2 AND dp.is_original_release)
+       AND EXISTS (
+           SELECT 1 FROM data_points newer
+           WHERE newer.series_id = dp.series_id
+             AND newer.date = dp.date
+             AND (newer.revision_date, newer.id) > (dp.revision_date, dp.id))
+       AND NOT EXISTS (
+           SELECT 1 FROM data_point_corrections c
+           WHERE c.data_point_id = dp.id OR c.corrected_data_point_id = dp.id)";
+
+/// Which old revisions to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Revisions published within this many days are all kept
+    pub keep_all_days: i64,
+    /// Keep each observation's original release past the window
+    pub keep_original_release: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_all_days: 730,
+            keep_original_release: true,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Default policy with environment overrides
+    ///
+    /// - `REVISION_RETENTION_DAYS`: days within which every revision is kept
+    /// - `REVISION_RETENTION_KEEP_ORIGINAL=false`: also delete old original releases
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+
+        if let Some(days) = std::env::var("REVISION_RETENTION_DAYS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+        {
+            policy.keep_all_days = days;
+        }
+        if let Some(keep) = std::env::var("REVISION_RETENTION_KEEP_ORIGINAL")
+            .ok()
+            .and_then(|value| value.parse().ok())
+        {
+            policy.keep_original_release = keep;
+        }
+
+        policy
+    }
+
+    /// Reject windows that would delete revisions published today
+    pub fn validate(&self) -> AppResult<()> {
+        if self.keep_all_days < 1 {
+            return Err(AppError::ValidationError(format!(
+                "Revision retention must keep at least one day, got {}",
+                self.keep_all_days
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Revisions published before this date fall outside the window
+    pub fn cutoff(&self, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(self.keep_all_days)
+    }
+}
+
+/// Revisions a policy removes from one data source's series
+#[derive(Debug, Clone, PartialEq, QueryableByName)]
+pub struct SourceRetention {
+    #[diesel(sql_type = Text)]
+    pub source_name: String,
+    #[diesel(sql_type = BigInt)]
+    pub revisions: i64,
+    #[diesel(sql_type = BigInt)]
+    pub series: i64,
+}
+
+/// Outcome of a retention run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionReport {
+    pub policy: RetentionPolicy,
+    pub cutoff: NaiveDate,
+    pub dry_run: bool,
+    /// Revisions outside the policy, by data source, before the run
+    pub sources: Vec<SourceRetention>,
+    /// Revisions deleted; zero for a dry run
+    pub deleted: usize,
+}
+
+impl RetentionReport {
+    pub fn expired_revisions(&self) -> i64 {
+        self.sources.iter().map(|source| source.revisions).sum()
+    }
+
+    /// One line per data source plus a total, for logs and the CLI
+    pub fn render(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .sources
+            .iter()
+            .map(|source| {
+                format!(
+                    "{}: {} revisions in {} series",
+                    source.source_name, source.revisions, source.series
+                )
+            })
+            .collect();
+
+        let verb = if self.dry_run {
+            "would delete"
+        } else {
+            "deleted"
+        };
+        let count = if self.dry_run {
+            self.expired_revisions()
+        } else {
+            self.deleted as i64
+        };
+        lines.push(format!(
+            "Retention {} {} revisions published before {} (original releases {})",
+            verb,
+            count,
+            self.cutoff,
+            if self.policy.keep_original_release {
+                "kept"
+            } else {
+                "not kept"
+            }
+        ));
+
+        lines
+    }
+}
+
+/// Apply the retention policy to stored data point revisions
+///
+/// With `dry_run` only the report is computed. Deletion runs in batches of
+/// [`RETENTION_DELETE_BATCH_SIZE`] until no expired revision is left.
+pub async fn apply_retention(
+    pool: &DatabasePool,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> AppResult<RetentionReport> {
+    policy.validate()?;
+    let cutoff = policy.cutoff(Utc::now().date_naive());
+
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let sources = diesel::sql_query(format!(
+        "SELECT ds.name AS source_name, COUNT(*) AS revisions,
+                COUNT(DISTINCT dp.series_id) AS series
+         FROM data_points dp
+         JOIN economic_series es ON es.id = dp.series_id
+         JOIN data_sources ds ON ds.id = es.source_id
+         WHERE {}
+         GROUP BY ds.name
+         ORDER BY revisions DESC",
+        EXPIRED_REVISION
+    ))
+    .bind::<Date, _>(cutoff)
+    .bind::<Bool, _>(policy.keep_original_release)
+    .load::<SourceRetention>(&mut conn)
+    .await?;
+
+    let mut report = RetentionReport {
+        policy: *policy,
+        cutoff,
+        dry_run,
+        sources,
+        deleted: 0,
+    };
+    if dry_run || report.expired_revisions() == 0 {
+        return Ok(report);
+    }
+
+    loop {
+        let deleted = diesel::sql_query(format!(
+            "DELETE FROM data_points
+             WHERE id IN (SELECT dp.id FROM data_points dp WHERE {} LIMIT $3)",
+            EXPIRED_REVISION
+        ))
+        .bind::<Date, _>(cutoff)
+        .bind::<Bool, _>(policy.keep_original_release)
+        .bind::<BigInt, _>(RETENTION_DELETE_BATCH_SIZE)
+        .execute(&mut conn)
+        .await?;
+
+        report.deleted += deleted;
+        if (deleted as i64) < RETENTION_DELETE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    info!(
+        "Revision retention deleted {} revisions published before {}",
+        report.deleted, cutoff
+    );
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_policy_window() {
+        // REQUIREMENT: Configurable retention policy for old data revisions
+        // PURPOSE: Verify the retention window, its validation and the dry-run report
+        // This ensures recent revisions are never deleted and dry runs report what would go
+
+        let policy = RetentionPolicy::default();
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert_eq!(
+            policy.cutoff(today),
+            NaiveDate::from_ymd_opt(2023, 3, 2).unwrap()
+        );
+        assert!(policy.validate().is_ok());
+
+        let no_window = RetentionPolicy {
+            keep_all_days: 0,
+            ..policy
+        };
+        assert!(matches!(
+            no_window.validate(),
+            Err(AppError::ValidationError(_))
+        ));
+
+        let report = RetentionReport {
+            policy,
+            cutoff: policy.cutoff(today),
+            dry_run: true,
+            sources: vec![
+                SourceRetention {
+                    source_name: "FRED".to_string(),
+                    revisions: 120,
+                    series: 4,
+                },
+                SourceRetention {
+                    source_name: "BLS".to_string(),
+                    revisions: 30,
+                    series: 2,
+                },
+            ],
+            deleted: 0,
+        };
+        assert_eq!(report.expired_revisions(), 150);
+        let lines = report.render();
+        assert_eq!(lines[0], "FRED: 120 revisions in 4 series");
+        assert_eq!(
+            lines[2],
+            "Retention would delete 150 revisions published before 2023-03-02 (original releases kept)"
+        );
+    }
+}
@@ -1,18 +1,263 @@
 // REQUIREMENT: Full-text search service with PostgreSQL integration
 // PURPOSE: Implement comprehensive search functionality with spelling correction and synonyms
 // This service provides advanced search capabilities for economic time series data
+//
+// Series and companies carry trigger-maintained `search_vector` columns. Queries are
+// expanded with the `search_synonyms` dictionary, parsed with websearch_to_tsquery and
+// ranked with ts_rank_cd. When a query matches nothing, words missing from the
+// `search_vocabulary` view are replaced by their closest trigram match and the search
+// runs again with the corrected query. Matched words are highlighted with ts_headline
+// and series searches can count their matches per facet.
 
 use diesel::prelude::*;
-use diesel_async::RunQueryDsl;
-use econ_graph_core::database::DatabasePool;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use econ_graph_core::database::{DatabasePool, PooledConn};
 use econ_graph_core::error::{AppError, AppResult};
 use econ_graph_core::models::search::{
-    SearchParams, SearchSuggestion, SeriesSearchResult, SuggestionType,
+    CompanySearchResult, FacetCount, SearchFacets, SearchParams, SearchSortOrder, SearchSuggestion,
+    SeriesSearchResult, SuggestionType,
 };
+use econ_graph_core::schema::search_synonyms;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use validator::Validate;
 
+/// Longest phrase, in words, looked up in the synonym dictionary
+const MAX_SYNONYM_PHRASE_WORDS: usize = 3;
+
+/// Alternative phrasings ORed into a single query
+const MAX_QUERY_VARIANTS: usize = 12;
+
+/// Shortest word that is checked for misspelling
+const MIN_CORRECTED_WORD_LENGTH: usize = 3;
+
+/// Marks ts_headline puts around matched words, replaced by `<mark>` once the
+/// text is escaped; private use characters never occur in catalog text
+const HIGHLIGHT_START: char = '\u{E000}';
+const HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// Most values reported per facet, most frequent first
+const MAX_FACET_VALUES: usize = 20;
+
+/// Ranked results of a combined series and company search
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    pub series: Vec<SeriesSearchResult>,
+    pub companies: Vec<CompanySearchResult>,
+    /// Spelling-corrected query the results are for, when the original matched nothing
+    pub corrected_query: Option<String>,
+    /// Synonyms the query was expanded with
+    pub synonyms: Vec<String>,
+}
+
+/// A query in websearch_to_tsquery syntax, with the synonyms it was expanded with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedQuery {
+    pub websearch: String,
+    pub synonyms: Vec<String>,
+}
+
+/// Lowercase words of a query, without punctuation
+pub fn query_words(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Runs of up to three consecutive words, the candidates for synonym lookup
+pub fn query_phrases(words: &[String]) -> Vec<String> {
+    let mut phrases: Vec<String> = (1..=MAX_SYNONYM_PHRASE_WORDS.min(words.len()))
+        .flat_map(|len| words.windows(len).map(|window| window.join(" ")))
+        .collect();
+    phrases.sort();
+    phrases.dedup();
+    phrases
+}
+
+/// Expand a query with synonym groups
+///
+/// A group is a term and its synonyms, all interchangeable. For every phrase of
+/// the query found in a group, the query with that phrase replaced by each
+/// other member of the group is ORed in. Queries without synonyms are passed
+/// through unchanged, keeping any websearch syntax the user typed.
+pub fn expand_query(query: &str, groups: &[(String, Vec<String>)]) -> ExpandedQuery {
+    let words = query_words(query);
+    let mut variants: Vec<Vec<String>> = Vec::new();
+    let mut synonyms: Vec<String> = Vec::new();
+
+    for phrase in query_phrases(&words) {
+        let phrase_words = query_words(&phrase);
+        for (term, group_synonyms) in groups {
+            let members: Vec<Vec<String>> = std::iter::once(term)
+                .chain(group_synonyms)
+                .map(|member| query_words(member))
+                .filter(|member| !member.is_empty())
+                .collect();
+            if !members.contains(&phrase_words) {
+                continue;
+            }
+
+            for member in members.iter().filter(|member| **member != phrase_words) {
+                if variants.len() >= MAX_QUERY_VARIANTS {
+                    break;
+                }
+                let variant = replace_phrase(&words, &phrase_words, member);
+                if variant != words && !variants.contains(&variant) {
+                    variants.push(variant);
+                    let synonym = member.join(" ");
+                    if !synonyms.contains(&synonym) {
+                        synonyms.push(synonym);
+                    }
+                }
+            }
+        }
+    }
+
+    if variants.is_empty() {
+        return ExpandedQuery {
+            websearch: query.trim().to_string(),
+            synonyms,
+        };
+    }
+
+    // "or" is an operator in websearch syntax, so it is dropped from the words
+    let websearch = std::iter::once(words)
+        .chain(variants)
+        .map(|variant| {
+            variant
+                .into_iter()
+                .filter(|word| word != "or")
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    ExpandedQuery {
+        websearch,
+        synonyms,
+    }
+}
+
+/// `words` with the first occurrence of `phrase` replaced by `replacement`
+fn replace_phrase(words: &[String], phrase: &[String], replacement: &[String]) -> Vec<String> {
+    match words
+        .windows(phrase.len())
+        .position(|window| window == phrase)
+    {
+        Some(start) => words[..start]
+            .iter()
+            .chain(replacement)
+            .chain(&words[start + phrase.len()..])
+            .cloned()
+            .collect(),
+        None => words.to_vec(),
+    }
+}
+
+/// The query with misspelled words replaced, if any word was corrected
+pub fn corrected_query(words: &[String], corrections: &HashMap<String, String>) -> Option<String> {
+    if !words.iter().any(|word| corrections.contains_key(word)) {
+        return None;
+    }
+    Some(
+        words
+            .iter()
+            .map(|word| corrections.get(word).unwrap_or(word).as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Words worth checking for misspelling
+fn correctable_words(words: &[String]) -> Vec<String> {
+    let mut candidates: Vec<String> = words
+        .iter()
+        .filter(|word| word.chars().count() >= MIN_CORRECTED_WORD_LENGTH)
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .cloned()
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// HTML for a ts_headline result: the text escaped and matches wrapped in `<mark>`
+pub fn highlight_html(headline: &str) -> String {
+    let mut html = String::with_capacity(headline.len() + 16);
+    for c in headline.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+/// ts_headline options for titles, which are short enough to show whole
+fn title_headline_options() -> String {
+    format!(
+        "StartSel={}, StopSel={}, HighlightAll=true",
+        HIGHLIGHT_START, HIGHLIGHT_STOP
+    )
+}
+
+/// ts_headline options for descriptions: up to two fragments around matches
+fn description_headline_options() -> String {
+    format!(
+        "StartSel={}, StopSel={}, MaxWords=35, MinWords=15, MaxFragments=2",
+        HIGHLIGHT_START, HIGHLIGHT_STOP
+    )
+}
+
+/// Facet counts from rows of (facet, value, label, count), most frequent first
+fn facets_from_rows(rows: Vec<FacetRow>) -> SearchFacets {
+    let mut facets = SearchFacets::default();
+    for row in rows {
+        let values = match row.facet.as_str() {
+            "source" => &mut facets.sources,
+            "frequency" => &mut facets.frequencies,
+            "units" => &mut facets.units,
+            "geography" => &mut facets.geographies,
+            _ => continue,
+        };
+        values.push(FacetCount {
+            value: row.value,
+            label: row.label,
+            count: row.count,
+        });
+    }
+    for values in [
+        &mut facets.sources,
+        &mut facets.frequencies,
+        &mut facets.units,
+        &mut facets.geographies,
+    ] {
+        values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+        values.truncate(MAX_FACET_VALUES);
+    }
+    facets
+}
+
+/// ORDER BY clause of a series search
+fn series_order(sort_order: &SearchSortOrder) -> &'static str {
+    match sort_order {
+        SearchSortOrder::Relevance => "rank DESC, es.title ASC",
+        SearchSortOrder::Title => "es.title ASC",
+        SearchSortOrder::LastUpdated => "last_updated DESC, es.title ASC",
+        SearchSortOrder::StartDate => "start_date DESC, es.title ASC",
+    }
+}
+
 /// Service for handling full-text search operations
 pub struct SearchService {
     pool: Arc<DatabasePool>,
@@ -24,6 +269,13 @@ impl SearchService {
         Self { pool }
     }
 
+    async fn connection(&self) -> AppResult<PooledConn<'_>> {
+        self.pool.get().await.map_err(|e| {
+            error!("Failed to get database connection: {}", e);
+            AppError::ExternalApiError(format!("Connection error: {}", e))
+        })
+    }
+
     /// Perform full-text search for economic series with spelling correction
     pub async fn search_series(
         &self,
@@ -33,75 +285,365 @@ impl SearchService {
         // PURPOSE: Find economic series using advanced PostgreSQL search capabilities
 
         let start_time = std::time::Instant::now();
+        Self::validate(params)?;
+        let mut conn = self.connection().await?;
+
+        let (results, _) = Self::find_series(&mut conn, params).await?;
 
-        // Validate search parameters
+        info!(
+            "Search completed: query='{}', results={}, time={}ms",
+            params.query,
+            results.len(),
+            start_time.elapsed().as_millis()
+        );
+
+        Ok(results)
+    }
+
+    /// Full-text search for economic series, with the matches counted per facet
+    ///
+    /// Facets count every match, not only the page of results returned.
+    pub async fn search_series_with_facets(
+        &self,
+        params: &SearchParams,
+    ) -> AppResult<(Vec<SeriesSearchResult>, SearchFacets)> {
+        let start_time = std::time::Instant::now();
+        Self::validate(params)?;
+        let mut conn = self.connection().await?;
+
+        let (results, expanded) = Self::find_series(&mut conn, params).await?;
+        let facets = Self::query_facets(&mut conn, &expanded, params).await?;
+
+        info!(
+            "Faceted search completed: query='{}', results={}, time={}ms",
+            params.query,
+            results.len(),
+            start_time.elapsed().as_millis()
+        );
+
+        Ok((results, facets))
+    }
+
+    /// Series matching the query, or the spelling-corrected query if it matched nothing
+    async fn find_series(
+        conn: &mut AsyncPgConnection,
+        params: &SearchParams,
+    ) -> AppResult<(Vec<SeriesSearchResult>, ExpandedQuery)> {
+        let expanded = Self::expand(conn, &params.query).await?;
+        let results = Self::query_series(conn, &expanded, &params.query, params).await?;
+
+        if results.is_empty() {
+            if let Some(corrected) = Self::correct_spelling(conn, params).await? {
+                let expanded = Self::expand(conn, &corrected).await?;
+                let results = Self::query_series(conn, &expanded, &corrected, params).await?;
+                return Ok((results, expanded));
+            }
+        }
+
+        Ok((results, expanded))
+    }
+
+    /// Search series and companies together, ranked by relevance
+    ///
+    /// Paging and filters in `params` apply to series; companies are limited
+    /// to the same page size.
+    pub async fn search(&self, params: &SearchParams) -> AppResult<SearchResults> {
+        let start_time = std::time::Instant::now();
+        Self::validate(params)?;
+        let mut conn = self.connection().await?;
+
+        let mut query = params.query.clone();
+        let mut corrected_query = None;
+        let mut expanded = Self::expand(&mut conn, &query).await?;
+        let mut series = Self::query_series(&mut conn, &expanded, &query, params).await?;
+        let mut companies = Self::query_companies(&mut conn, &expanded, &query, params).await?;
+
+        if series.is_empty() && companies.is_empty() {
+            if let Some(corrected) = Self::correct_spelling(&mut conn, params).await? {
+                expanded = Self::expand(&mut conn, &corrected).await?;
+                series = Self::query_series(&mut conn, &expanded, &corrected, params).await?;
+                companies = Self::query_companies(&mut conn, &expanded, &corrected, params).await?;
+                query = corrected.clone();
+                corrected_query = Some(corrected);
+            }
+        }
+
+        info!(
+            "Search completed: query='{}', series={}, companies={}, time={}ms",
+            query,
+            series.len(),
+            companies.len(),
+            start_time.elapsed().as_millis()
+        );
+
+        Ok(SearchResults {
+            series,
+            companies,
+            corrected_query,
+            synonyms: expanded.synonyms,
+        })
+    }
+
+    /// Rebuild the vocabulary spelling suggestions are drawn from
+    ///
+    /// Run after large catalog changes; the view is refreshed concurrently so
+    /// searches keep working meanwhile.
+    pub async fn refresh_vocabulary(&self) -> AppResult<()> {
+        let mut conn = self.connection().await?;
+
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY search_vocabulary")
+            .execute(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Search vocabulary refresh failed: {}", e);
+                AppError::DatabaseError(format!("Failed to refresh search vocabulary: {}", e))
+            })?;
+
+        Ok(())
+    }
+
+    fn validate(params: &SearchParams) -> AppResult<()> {
         params.validate().map_err(|e| {
             warn!("Invalid search parameters: {:?}", e);
             AppError::Validation(format!("Invalid search parameters: {}", e))
-        })?;
+        })
+    }
 
-        let _conn = self.pool.get().await.map_err(|e| {
-            error!("Failed to get database connection: {}", e);
-            AppError::ExternalApiError(format!("Connection error: {}", e))
-        })?;
+    /// Expand a query with the synonym groups mentioning its phrases
+    async fn expand(conn: &mut AsyncPgConnection, query: &str) -> AppResult<ExpandedQuery> {
+        let phrases = query_phrases(&query_words(query));
+        if phrases.is_empty() {
+            return Ok(expand_query(query, &[]));
+        }
 
-        let search_query = params.query.clone();
-        let similarity_threshold = params.get_similarity_threshold();
-        let limit = params.get_limit();
-        let offset = params.get_offset();
-        let source_filter = params.source_id;
-        let frequency_filter = params.frequency.clone();
-        let include_inactive = params.should_include_inactive();
+        let groups = search_synonyms::table
+            .filter(
+                search_synonyms::term
+                    .eq_any(phrases.clone())
+                    .or(search_synonyms::synonyms.overlaps_with(phrases)),
+            )
+            .select((search_synonyms::term, search_synonyms::synonyms))
+            .load::<(String, Vec<String>)>(conn)
+            .await?;
 
-        let mut conn = self.pool.get().await.map_err(|e| {
-            error!("Failed to get database connection: {}", e);
-            AppError::ExternalApiError(format!("Connection error: {}", e))
-        })?;
+        Ok(expand_query(query, &groups))
+    }
 
-        let results = diesel::sql_query(
-            "SELECT es.id, es.title, es.description, es.external_id, es.source_id, es.frequency,
-                    es.units, es.start_date, es.end_date, es.last_updated, es.is_active,
-                    CASE WHEN es.title ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.5 END as rank,
-                    CASE WHEN es.title ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.5 END as similarity_score
-             FROM economic_series es
-             WHERE (es.title ILIKE '%' || $1 || '%' OR es.description ILIKE '%' || $1 || '%')
+    async fn query_series(
+        conn: &mut AsyncPgConnection,
+        expanded: &ExpandedQuery,
+        query: &str,
+        params: &SearchParams,
+    ) -> AppResult<Vec<SeriesSearchResult>> {
+        // Series without observations yet report the date they were added as their start.
+        // Geography and topic filters match catalog metadata by source and external ID.
+        // ts_headline is costly, so PostgreSQL evaluates it after the LIMIT.
+        let sql = format!(
+            "WITH q AS (SELECT websearch_to_tsquery('english', $1) AS tsq)
+             SELECT es.id, es.title, es.description, es.external_id, es.source_id, es.frequency,
+                    COALESCE(es.units, '') AS units,
+                    COALESCE(es.start_date, es.created_at::date) AS start_date,
+                    es.end_date,
+                    COALESCE(es.last_updated, es.updated_at) AT TIME ZONE 'UTC' AS last_updated,
+                    es.is_active,
+                    (ts_rank_cd(es.search_vector, q.tsq) + 0.2 * similarity(es.title, $2))::real AS rank,
+                    similarity(es.title, $2) AS similarity_score,
+                    ts_headline('english', es.title, q.tsq, $10) AS title_highlight,
+                    CASE WHEN es.description IS NULL THEN NULL
+                         ELSE ts_headline('english', es.description, q.tsq, $11)
+                    END AS description_highlight
+             FROM economic_series es, q
+             WHERE es.search_vector @@ q.tsq
              AND ($3::uuid IS NULL OR es.source_id = $3)
              AND ($4::text IS NULL OR es.frequency = $4)
              AND ($5::boolean OR es.is_active = true)
-             ORDER BY rank DESC, es.title ASC
-             LIMIT $6 OFFSET $7"
+             AND (($8::text IS NULL AND $9::text IS NULL) OR EXISTS (
+                 SELECT 1 FROM series_metadata sm
+                 WHERE sm.source_id = es.source_id AND sm.external_id = es.external_id
+                 AND ($8::text IS NULL OR lower(sm.geography) = lower($8))
+                 AND ($9::text IS NULL OR lower($9) = ANY(
+                     SELECT lower(topic) FROM unnest(sm.categories || sm.tags) AS topic))))
+             ORDER BY {}
+             LIMIT $6 OFFSET $7",
+            series_order(params.get_sort_order())
+        );
+
+        let rows = diesel::sql_query(sql)
+            .bind::<diesel::sql_types::Text, _>(&expanded.websearch)
+            .bind::<diesel::sql_types::Text, _>(query)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Uuid>, _>(params.source_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+                params.frequency.as_deref(),
+            )
+            .bind::<diesel::sql_types::Bool, _>(params.should_include_inactive())
+            .bind::<diesel::sql_types::Integer, _>(params.get_limit())
+            .bind::<diesel::sql_types::Integer, _>(params.get_offset())
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+                params.geography.as_deref(),
+            )
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+                params.topic.as_deref(),
+            )
+            .bind::<diesel::sql_types::Text, _>(title_headline_options())
+            .bind::<diesel::sql_types::Text, _>(description_headline_options())
+            .load::<SeriesSearchResultRow>(conn)
+            .await
+            .map_err(|e| {
+                error!("Search query execution failed: {}", e);
+                AppError::ExternalApiError(format!("Query execution error: {}", e))
+            })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| row.into_search_result())
+            .collect())
+    }
+
+    /// Matches of a series search counted per facet
+    ///
+    /// Each facet applies the search's other filters but not its own. Units
+    /// cannot be filtered on, so every facet counts all units.
+    async fn query_facets(
+        conn: &mut AsyncPgConnection,
+        expanded: &ExpandedQuery,
+        params: &SearchParams,
+    ) -> AppResult<SearchFacets> {
+        let rows = diesel::sql_query(
+            "WITH q AS (SELECT websearch_to_tsquery('english', $1) AS tsq),
+             m AS (
+                 SELECT es.source_id, ds.name AS source_name, es.frequency,
+                        COALESCE(es.units, '') AS units, sm.geography,
+                        ($2::uuid IS NULL OR es.source_id = $2) AS source_match,
+                        ($3::text IS NULL OR es.frequency = $3) AS frequency_match,
+                        ($5::text IS NULL OR lower(sm.geography) = lower($5)) AS geography_match
+                 FROM economic_series es
+                 JOIN data_sources ds ON ds.id = es.source_id
+                 LEFT JOIN series_metadata sm
+                     ON sm.source_id = es.source_id AND sm.external_id = es.external_id
+                 CROSS JOIN q
+                 WHERE es.search_vector @@ q.tsq
+                 AND ($4::boolean OR es.is_active = true)
+                 AND ($6::text IS NULL OR lower($6) = ANY(
+                     SELECT lower(topic) FROM unnest(sm.categories || sm.tags) AS topic))
+             )
+             SELECT 'source' AS facet, source_id::text AS value, MAX(source_name) AS label,
+                    COUNT(*) AS count
+             FROM m WHERE frequency_match AND geography_match GROUP BY source_id
+             UNION ALL
+             SELECT 'frequency', frequency, frequency, COUNT(*)
+             FROM m WHERE source_match AND geography_match GROUP BY frequency
+             UNION ALL
+             SELECT 'units', units, units, COUNT(*)
+             FROM m WHERE source_match AND frequency_match AND geography_match AND units <> ''
+             GROUP BY units
+             UNION ALL
+             SELECT 'geography', geography, geography, COUNT(*)
+             FROM m WHERE source_match AND frequency_match AND geography IS NOT NULL
+             GROUP BY geography",
+        )
+        .bind::<diesel::sql_types::Text, _>(&expanded.websearch)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Uuid>, _>(params.source_id)
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+            params.frequency.as_deref(),
         )
-        .bind::<diesel::sql_types::Text, _>(&search_query)
-        .bind::<diesel::sql_types::Float4, _>(similarity_threshold)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Uuid>, _>(source_filter)
-        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(frequency_filter.as_deref())
-        .bind::<diesel::sql_types::Bool, _>(include_inactive)
-        .bind::<diesel::sql_types::Integer, _>(limit)
-        .bind::<diesel::sql_types::Integer, _>(offset)
-        .load::<SeriesSearchResultRow>(&mut conn)
+        .bind::<diesel::sql_types::Bool, _>(params.should_include_inactive())
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(
+            params.geography.as_deref(),
+        )
+        .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(params.topic.as_deref())
+        .load::<FacetRow>(conn)
         .await
         .map_err(|e| {
-            error!("Search query execution failed: {}", e);
+            error!("Search facet query execution failed: {}", e);
             AppError::ExternalApiError(format!("Query execution error: {}", e))
         })?;
 
-        let execution_time = start_time.elapsed();
+        Ok(facets_from_rows(rows))
+    }
+
+    async fn query_companies(
+        conn: &mut AsyncPgConnection,
+        expanded: &ExpandedQuery,
+        query: &str,
+        params: &SearchParams,
+    ) -> AppResult<Vec<CompanySearchResult>> {
+        // An exact ticker match outranks any text match
+        let rows = diesel::sql_query(
+            "WITH q AS (SELECT websearch_to_tsquery('english', $1) AS tsq)
+             SELECT c.id, c.cik, c.ticker, c.name, c.industry, c.sector, c.is_active,
+                    (ts_rank_cd(c.search_vector, q.tsq)
+                     + CASE WHEN upper(c.ticker) = upper(btrim($2)) THEN 1.0 ELSE 0.0 END)::real AS rank
+             FROM companies c, q
+             WHERE (c.search_vector @@ q.tsq OR upper(c.ticker) = upper(btrim($2)))
+             AND ($3::boolean OR c.is_active = true)
+             ORDER BY rank DESC, c.name ASC
+             LIMIT $4",
+        )
+        .bind::<diesel::sql_types::Text, _>(&expanded.websearch)
+        .bind::<diesel::sql_types::Text, _>(query)
+        .bind::<diesel::sql_types::Bool, _>(params.should_include_inactive())
+        .bind::<diesel::sql_types::Integer, _>(params.get_limit())
+        .load::<CompanySearchResultRow>(conn)
+        .await
+        .map_err(|e| {
+            error!("Company search query execution failed: {}", e);
+            AppError::ExternalApiError(format!("Query execution error: {}", e))
+        })?;
 
-        // Convert database rows to search results
-        let search_results: Vec<SeriesSearchResult> = results
+        Ok(rows
             .into_iter()
             .map(|row| row.into_search_result())
-            .collect();
+            .collect())
+    }
 
-        // Log search analytics
-        info!(
-            "Search completed: query='{}', results={}, time={}ms",
-            params.query,
-            search_results.len(),
-            execution_time.as_millis()
-        );
+    /// Closest known word for each unknown word of the query
+    async fn spelling_corrections(
+        conn: &mut AsyncPgConnection,
+        words: &[String],
+        min_similarity: f32,
+    ) -> AppResult<Vec<CorrectionRow>> {
+        let candidates = correctable_words(words);
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        Ok(search_results)
+        diesel::sql_query(
+            "SELECT t.term, v.word, v.document_count, v.score
+             FROM unnest($1::text[]) AS t(term)
+             CROSS JOIN LATERAL (
+                 SELECT word, document_count, similarity(word, t.term) AS score
+                 FROM search_vocabulary
+                 WHERE word % t.term
+                 ORDER BY score DESC, document_count DESC
+                 LIMIT 1
+             ) v
+             WHERE v.score >= $2
+             AND NOT EXISTS (SELECT 1 FROM search_vocabulary known WHERE known.word = t.term)",
+        )
+        .bind::<diesel::sql_types::Array<diesel::sql_types::Text>, _>(candidates)
+        .bind::<diesel::sql_types::Float4, _>(min_similarity)
+        .load::<CorrectionRow>(conn)
+        .await
+        .map_err(|e| {
+            error!("Spelling correction query failed: {}", e);
+            AppError::ExternalApiError(format!("Query execution error: {}", e))
+        })
+    }
+
+    /// The query with misspelled words corrected, if any were found
+    async fn correct_spelling(
+        conn: &mut AsyncPgConnection,
+        params: &SearchParams,
+    ) -> AppResult<Option<String>> {
+        let words = query_words(&params.query);
+        let corrections: HashMap<String, String> =
+            Self::spelling_corrections(conn, &words, params.get_similarity_threshold())
+                .await?
+                .into_iter()
+                .map(|row| (row.term, row.word))
+                .collect();
+
+        Ok(corrected_query(&words, &corrections))
     }
 
     /// Get search suggestions for query completion and spelling correction
@@ -114,20 +656,18 @@ impl SearchService {
             return Ok(vec![]);
         }
 
-        let mut conn = self.pool.get().await.map_err(|e| {
-            error!("Failed to get database connection: {}", e);
-            AppError::ExternalApiError(format!("Connection error: {}", e))
-        })?;
+        let mut conn = self.connection().await?;
 
         let query = partial_query.to_lowercase().trim().to_string();
         let search_limit = limit.min(20);
 
         let suggestions = diesel::sql_query(
-            "SELECT DISTINCT title as word, 0.0 as rank, 'completion' as suggestion_type, 1 as match_count
+            "SELECT title AS word, COUNT(*) AS match_count
              FROM economic_series
              WHERE title ILIKE $1 AND is_active = true
+             GROUP BY title
              ORDER BY title ASC
-             LIMIT $2"
+             LIMIT $2",
         )
         .bind::<diesel::sql_types::Text, _>(&format!("{}%", query))
         .bind::<diesel::sql_types::Integer, _>(search_limit)
@@ -138,7 +678,7 @@ impl SearchService {
             AppError::ExternalApiError(format!("Query execution error: {}", e))
         })?;
 
-        let search_suggestions: Vec<SearchSuggestion> = suggestions
+        let mut search_suggestions: Vec<SearchSuggestion> = suggestions
             .into_iter()
             .take(limit as usize)
             .map(|row| SearchSuggestion {
@@ -149,6 +689,39 @@ impl SearchService {
             })
             .collect();
 
+        // Nothing starts with the query: offer it with misspelled words corrected
+        if search_suggestions.is_empty() {
+            let words = query_words(&query);
+            let corrections = Self::spelling_corrections(
+                &mut conn,
+                &words,
+                SearchParams::default().get_similarity_threshold(),
+            )
+            .await?;
+            let confidence = corrections
+                .iter()
+                .map(|row| row.score)
+                .fold(1.0f32, f32::min);
+            let match_count = corrections
+                .iter()
+                .map(|row| row.document_count)
+                .min()
+                .unwrap_or(0);
+            let corrections: HashMap<String, String> = corrections
+                .into_iter()
+                .map(|row| (row.term, row.word))
+                .collect();
+
+            if let Some(corrected) = corrected_query(&words, &corrections) {
+                search_suggestions.push(SearchSuggestion {
+                    suggestion: corrected,
+                    match_count: match_count as i32,
+                    suggestion_type: SuggestionType::Correction,
+                    confidence,
+                });
+            }
+        }
+
         Ok(search_suggestions)
     }
 }
@@ -182,6 +755,10 @@ struct SeriesSearchResultRow {
     pub rank: f32,
     #[diesel(sql_type = diesel::sql_types::Float4)]
     pub similarity_score: f32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title_highlight: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub description_highlight: Option<String>,
 }
 
 impl SeriesSearchResultRow {
@@ -200,18 +777,75 @@ impl SeriesSearchResultRow {
             is_active: self.is_active,
             rank: self.rank,
             similarity_score: self.similarity_score,
+            title_highlight: highlight_html(&self.title_highlight),
+            description_highlight: self.description_highlight.as_deref().map(highlight_html),
         }
     }
 }
 
 #[derive(QueryableByName, Debug)]
-struct SuggestionRow {
+struct CompanySearchResultRow {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub id: uuid::Uuid,
     #[diesel(sql_type = diesel::sql_types::Text)]
-    pub word: String,
+    pub cik: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub ticker: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub industry: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sector: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub is_active: bool,
     #[diesel(sql_type = diesel::sql_types::Float4)]
     pub rank: f32,
+}
+
+impl CompanySearchResultRow {
+    fn into_search_result(self) -> CompanySearchResult {
+        CompanySearchResult {
+            id: self.id,
+            cik: self.cik,
+            ticker: self.ticker,
+            name: self.name,
+            industry: self.industry,
+            sector: self.sector,
+            is_active: self.is_active,
+            rank: self.rank,
+        }
+    }
+}
+
+#[derive(QueryableByName, Debug)]
+struct CorrectionRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub term: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub word: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub document_count: i64,
+    #[diesel(sql_type = diesel::sql_types::Float4)]
+    pub score: f32,
+}
+
+#[derive(QueryableByName, Debug)]
+struct FacetRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub facet: String,
     #[diesel(sql_type = diesel::sql_types::Text)]
-    pub suggestion_type: String,
+    pub value: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub label: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
+}
+
+#[derive(QueryableByName, Debug)]
+struct SuggestionRow {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub word: String,
     #[diesel(sql_type = diesel::sql_types::BigInt)]
     pub match_count: i64,
 }
@@ -224,3 +858,123 @@ pub async fn search_series(
     let search_service = SearchService::new(Arc::new(pool.clone()));
     search_service.search_series(params).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<(String, Vec<String>)> {
+        vec![
+            (
+                "gdp".to_string(),
+                vec![
+                    "gross domestic product".to_string(),
+                    "economic output".to_string(),
+                ],
+            ),
+            (
+                "unemployment".to_string(),
+                vec!["jobless".to_string(), "out of work".to_string()],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_expand_query_with_synonyms() {
+        // REQUIREMENT: Searches find series described with synonyms of the query terms
+        // PURPOSE: Verify matching phrases are swapped for each synonym and the variants ORed together
+        // This ensures "gdp growth" also finds "Gross Domestic Product growth" while keeping "growth" required
+
+        let expanded = expand_query("GDP growth", &groups());
+        assert_eq!(
+            expanded.websearch,
+            "gdp growth OR gross domestic product growth OR economic output growth"
+        );
+        assert_eq!(
+            expanded.synonyms,
+            vec!["gross domestic product", "economic output"]
+        );
+
+        // Multi-word synonyms are recognized in the query
+        let expanded = expand_query("out of work rate", &groups());
+        assert_eq!(
+            expanded.websearch,
+            "out of work rate OR unemployment rate OR jobless rate"
+        );
+
+        // Queries without synonyms keep the user's websearch syntax
+        let plain = expand_query("inflation -\"core\"", &groups());
+        assert_eq!(plain.websearch, "inflation -\"core\"");
+        assert!(plain.synonyms.is_empty());
+    }
+
+    #[test]
+    fn test_query_phrases_and_corrections() {
+        // REQUIREMENT: Misspelled searches still find results
+        // PURPOSE: Verify phrase candidates and how corrected words are put back into the query
+        // This ensures "unemplyment rate" is retried as "unemployment rate"
+
+        let words = query_words("Unemplyment-rate, 2024");
+        assert_eq!(words, vec!["unemplyment", "rate", "2024"]);
+        assert_eq!(
+            query_phrases(&words),
+            vec![
+                "2024",
+                "rate",
+                "rate 2024",
+                "unemplyment",
+                "unemplyment rate",
+                "unemplyment rate 2024"
+            ]
+        );
+        assert_eq!(correctable_words(&words), vec!["rate", "unemplyment"]);
+
+        let corrections = HashMap::from([("unemplyment".to_string(), "unemployment".to_string())]);
+        assert_eq!(
+            corrected_query(&words, &corrections).as_deref(),
+            Some("unemployment rate 2024")
+        );
+        assert_eq!(corrected_query(&words, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_highlights_and_facets() {
+        // REQUIREMENT: Search results show highlighted snippets and facet counts
+        // PURPOSE: Verify ts_headline marks become <mark> tags around escaped text and facet rows are grouped and ordered
+        // This ensures the frontend can render snippets as HTML safely and list the most common facet values first
+
+        let headline = format!(
+            "{}Consumer{} Price Index <All Items> & \"Food\"",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        );
+        assert_eq!(
+            highlight_html(&headline),
+            "<mark>Consumer</mark> Price Index &lt;All Items&gt; &amp; &quot;Food&quot;"
+        );
+
+        let row = |facet: &str, value: &str, count: i64| FacetRow {
+            facet: facet.to_string(),
+            value: value.to_string(),
+            label: value.to_string(),
+            count,
+        };
+        let facets = facets_from_rows(vec![
+            row("frequency", "Quarterly", 3),
+            row("frequency", "Monthly", 12),
+            row("frequency", "Annual", 3),
+            row("units", "Percent", 7),
+            row("unknown", "ignored", 100),
+        ]);
+        let frequencies: Vec<(&str, i64)> = facets
+            .frequencies
+            .iter()
+            .map(|facet| (facet.value.as_str(), facet.count))
+            .collect();
+        assert_eq!(
+            frequencies,
+            vec![("Monthly", 12), ("Annual", 3), ("Quarterly", 3)]
+        );
+        assert_eq!(facets.units.len(), 1);
+        assert!(facets.sources.is_empty() && facets.geographies.is_empty());
+    }
+}
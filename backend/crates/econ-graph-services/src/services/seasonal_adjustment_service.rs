@@ -0,0 +1,329 @@
+/**
+ * REQUIREMENT: Seasonally adjusted views of series that are only published unadjusted
+ * PURPOSE: Compute a moving-average seasonal decomposition on demand and cache the result
+ * This gives users an adjusted line without waiting for the source agency to publish one
+ */
+use bigdecimal::ToPrimitive;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::DataQueryParams,
+};
+
+use super::series_service;
+
+/// Method label reported alongside computed results
+pub const SEASONAL_ADJUSTMENT_METHOD: &str = "classical-additive-moving-average";
+
+/// Upper bound on observations loaded for a single decomposition
+const MAX_OBSERVATIONS: i64 = 10_000;
+
+/// One observation split into its trend, seasonal and irregular components
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeasonalComponentPoint {
+    pub date: NaiveDate,
+    pub observed: f64,
+    /// Centered moving average; unavailable for the first and last half-period
+    pub trend: Option<f64>,
+    pub seasonal: f64,
+    pub irregular: Option<f64>,
+    pub adjusted: f64,
+}
+
+/// Seasonal decomposition of a whole series
+#[derive(Debug, Clone)]
+pub struct SeasonalAdjustmentResult {
+    pub series_id: Uuid,
+    pub frequency: String,
+    pub period: usize,
+    pub method: &'static str,
+    pub computed_at: DateTime<Utc>,
+    pub points: Vec<SeasonalComponentPoint>,
+}
+
+/// Cached decomposition keyed by the series version it was computed from
+struct CachedAdjustment {
+    series_updated_at: DateTime<Utc>,
+    cached_at: Instant,
+    result: Arc<SeasonalAdjustmentResult>,
+}
+
+/// Computes and caches seasonally adjusted series
+pub struct SeasonalAdjustmentService {
+    cache: RwLock<HashMap<Uuid, CachedAdjustment>>,
+    ttl: Duration,
+}
+
+impl Default for SeasonalAdjustmentService {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(3600))
+    }
+}
+
+impl SeasonalAdjustmentService {
+    /// Create a service whose cached results expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            cache: RwLock::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Get the seasonally adjusted series, computing it if the cache is stale
+    ///
+    /// Cached results are reused until the TTL expires or the series'
+    /// `updated_at` changes, so new crawls are picked up immediately.
+    pub async fn seasonally_adjusted(
+        &self,
+        pool: &DatabasePool,
+        series_id: Uuid,
+    ) -> AppResult<Arc<SeasonalAdjustmentResult>> {
+        let series = series_service::get_series_by_id(pool, series_id)
+            .await?
+            .ok_or_else(|| AppError::SeriesNotFound(series_id.to_string()))?;
+
+        if let Some(cached) = self.cache.read().await.get(&series_id) {
+            if cached.series_updated_at == series.updated_at
+                && cached.cached_at.elapsed() < self.ttl
+            {
+                return Ok(cached.result.clone());
+            }
+        }
+
+        let period = seasonal_period(&series.frequency).ok_or_else(|| {
+            AppError::InvalidTransformation(format!(
+                "Seasonal adjustment is not supported for {} series",
+                series.frequency
+            ))
+        })?;
+
+        let data_points = series_service::get_series_data(
+            pool,
+            DataQueryParams {
+                series_id,
+                start_date: None,
+                end_date: None,
+                original_only: None,
+                latest_revision_only: Some(true),
+                exclude_corrections: None,
+                limit: Some(MAX_OBSERVATIONS),
+                offset: None,
+            },
+        )
+        .await?;
+
+        let observations: Vec<(NaiveDate, f64)> = data_points
+            .iter()
+            .filter_map(|point| Some((point.date, point.value.as_ref()?.to_f64()?)))
+            .collect();
+
+        let result = Arc::new(SeasonalAdjustmentResult {
+            series_id,
+            frequency: series.frequency,
+            period,
+            method: SEASONAL_ADJUSTMENT_METHOD,
+            computed_at: Utc::now(),
+            points: decompose(&observations, period)?,
+        });
+
+        let mut cache = self.cache.write().await;
+        cache.retain(|_, entry| entry.cached_at.elapsed() < self.ttl);
+        cache.insert(
+            series_id,
+            CachedAdjustment {
+                series_updated_at: series.updated_at,
+                cached_at: Instant::now(),
+                result: result.clone(),
+            },
+        );
+
+        Ok(result)
+    }
+
+    /// Drop any cached decomposition for a series
+    pub async fn invalidate(&self, series_id: Uuid) {
+        self.cache.write().await.remove(&series_id);
+    }
+}
+
+/// Process-wide service instance
+///
+/// GraphQL schemas are built per request, so the cache has to live outside
+/// the schema to be shared between requests.
+pub fn shared_seasonal_adjustment_service() -> &'static SeasonalAdjustmentService {
+    static SERVICE: OnceLock<SeasonalAdjustmentService> = OnceLock::new();
+    SERVICE.get_or_init(SeasonalAdjustmentService::default)
+}
+
+/// Number of observations per seasonal cycle for a series frequency
+pub fn seasonal_period(frequency: &str) -> Option<usize> {
+    match frequency.to_lowercase().as_str() {
+        "monthly" | "m" => Some(12),
+        "quarterly" | "q" => Some(4),
+        _ => None,
+    }
+}
+
+/// Position of a date within its seasonal cycle
+fn season_index(date: NaiveDate, period: usize) -> usize {
+    let month = date.month0() as usize;
+    match period {
+        4 => month / 3,
+        _ => month % period,
+    }
+}
+
+/// Classical additive decomposition using a centered moving-average trend
+///
+/// The trend is a centered moving average over one period (2×m for even
+/// periods). Seasonal factors are the mean detrended value for each calendar
+/// position, normalized to sum to zero, and the adjusted series is the
+/// observed value minus its seasonal factor.
+pub fn decompose(
+    observations: &[(NaiveDate, f64)],
+    period: usize,
+) -> AppResult<Vec<SeasonalComponentPoint>> {
+    if period < 2 {
+        return Err(AppError::InvalidTransformation(
+            "Seasonal period must be at least 2".to_string(),
+        ));
+    }
+
+    if observations.len() < period * 2 {
+        return Err(AppError::InvalidTransformation(format!(
+            "Seasonal adjustment needs at least {} observations, found {}",
+            period * 2,
+            observations.len()
+        )));
+    }
+
+    let values: Vec<f64> = observations.iter().map(|(_, value)| *value).collect();
+    let trend = centered_moving_average(&values, period);
+
+    let mut sums = vec![0.0; period];
+    let mut counts = vec![0usize; period];
+    for ((date, value), trend) in observations.iter().zip(&trend) {
+        if let Some(trend) = trend {
+            let index = season_index(*date, period);
+            sums[index] += value - trend;
+            counts[index] += 1;
+        }
+    }
+
+    let mut factors: Vec<f64> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, count)| if *count > 0 { sum / *count as f64 } else { 0.0 })
+        .collect();
+    let mean_factor = factors.iter().sum::<f64>() / period as f64;
+    for factor in &mut factors {
+        *factor -= mean_factor;
+    }
+
+    Ok(observations
+        .iter()
+        .zip(trend)
+        .map(|((date, observed), trend)| {
+            let seasonal = factors[season_index(*date, period)];
+            let adjusted = observed - seasonal;
+            SeasonalComponentPoint {
+                date: *date,
+                observed: *observed,
+                trend,
+                seasonal,
+                irregular: trend.map(|trend| adjusted - trend),
+                adjusted,
+            }
+        })
+        .collect())
+}
+
+/// Centered moving average of one period, using a 2×m average for even periods
+fn centered_moving_average(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let half = period / 2;
+    (0..values.len())
+        .map(|i| {
+            if i < half || i + half >= values.len() {
+                return None;
+            }
+
+            let window = &values[i - half..=i + half];
+            let sum = if period % 2 == 0 {
+                window.iter().sum::<f64>() - 0.5 * (window[0] + window[window.len() - 1])
+            } else {
+                window.iter().sum::<f64>()
+            };
+            Some(sum / period as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarterly(values: &[f64]) -> Vec<(NaiveDate, f64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let date =
+                    NaiveDate::from_ymd_opt(2020 + (i / 4) as i32, (i % 4) as u32 * 3 + 1, 1)
+                        .unwrap();
+                (date, *value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decompose_removes_fixed_seasonal_pattern() {
+        // REQUIREMENT: Seasonally adjusted series for unadjusted source data
+        // PURPOSE: Verify a constant quarterly pattern on a linear trend is fully removed
+        // This ensures adjusted values follow the underlying trend users care about
+
+        let pattern = [3.0, -1.0, -4.0, 2.0];
+        let values: Vec<f64> = (0..16)
+            .map(|i| 100.0 + i as f64 * 2.0 + pattern[i % 4])
+            .collect();
+
+        let points = decompose(&quarterly(&values), 4).unwrap();
+
+        assert_eq!(points.len(), 16);
+        for (i, point) in points.iter().enumerate() {
+            assert!((point.seasonal - pattern[i % 4]).abs() < 1e-9);
+            assert!((point.adjusted - (100.0 + i as f64 * 2.0)).abs() < 1e-9);
+        }
+        assert!(points[0].trend.is_none());
+        assert!(points[15].trend.is_none());
+        assert!(points[2].irregular.unwrap().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decompose_requires_two_full_cycles() {
+        // REQUIREMENT: Seasonal factors must be estimated from real data
+        // PURPOSE: Verify short series are rejected instead of producing unstable factors
+        // This ensures the API reports an error rather than a misleading adjusted series
+
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        assert!(decompose(&quarterly(&values), 4).is_err());
+    }
+
+    #[test]
+    fn test_seasonal_period() {
+        // REQUIREMENT: Only frequencies with a defined seasonal cycle are adjusted
+        // PURPOSE: Verify frequency labels map to the expected period length
+        // This ensures annual and daily series are not decomposed with a wrong cycle
+
+        assert_eq!(seasonal_period("Monthly"), Some(12));
+        assert_eq!(seasonal_period("quarterly"), Some(4));
+        assert_eq!(seasonal_period("Annual"), None);
+        assert_eq!(seasonal_period("Daily"), None);
+    }
+}
@@ -0,0 +1,410 @@
+/**
+ * REQUIREMENT: Analysts are told when a series crosses a level they care about
+ * PURPOSE: Evaluate users' series alert rules whenever new observations are stored
+ * and deliver a notification when a rule's condition starts to hold
+ * A rule fires once per crossing; it re-arms when the condition stops holding
+ */
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Months, NaiveDate};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::services::notification_service::NotificationService;
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{AlertCondition, AlertRuleState, DataPoint, Notification, SeriesAlertRule},
+    schema::{data_points, economic_series},
+};
+
+/// How far before the exact year-ago date a year-ago observation may be
+///
+/// Weekly series do not land on the same calendar date every year.
+const YOY_LOOKBACK_DAYS: u64 = 7;
+
+/// Latest value per observation date, oldest first
+///
+/// Keeps the most recent revision of each date and drops missing values.
+pub fn latest_observations(points: Vec<DataPoint>) -> Vec<(NaiveDate, BigDecimal)> {
+    let mut latest: BTreeMap<NaiveDate, DataPoint> = BTreeMap::new();
+    for point in points {
+        match latest.get(&point.date) {
+            Some(existing) if existing.revision_date >= point.revision_date => {}
+            _ => {
+                latest.insert(point.date, point);
+            }
+        }
+    }
+
+    latest
+        .into_iter()
+        .filter_map(|(date, point)| point.value.map(|value| (date, value)))
+        .collect()
+}
+
+/// Year-over-year change in percent for each observation, if a year-ago value exists
+pub fn yoy_changes(observations: &[(NaiveDate, BigDecimal)]) -> Vec<Option<BigDecimal>> {
+    observations
+        .iter()
+        .map(|(date, value)| {
+            let year_ago = date.checked_sub_months(Months::new(12))?;
+            let earliest = year_ago.checked_sub_days(chrono::Days::new(YOY_LOOKBACK_DAYS))?;
+            let (_, previous) = observations
+                .iter()
+                .rev()
+                .find(|(d, _)| *d <= year_ago && *d >= earliest)?;
+            if previous.is_zero() {
+                return None;
+            }
+            Some((value - previous) / previous * BigDecimal::from(100))
+        })
+        .collect()
+}
+
+/// Evaluate a rule against a series' observations, oldest first
+///
+/// Returns `None` when there are not enough observations (or year-ago values)
+/// to decide, in which case the rule's state should be left alone.
+pub fn evaluate_rule(
+    condition: AlertCondition,
+    threshold: &BigDecimal,
+    window_periods: usize,
+    was_triggered: bool,
+    observations: &[(NaiveDate, BigDecimal)],
+) -> Option<AlertRuleState> {
+    let window_periods = window_periods.max(1);
+    if observations.len() < window_periods {
+        return None;
+    }
+
+    let metrics: Vec<Option<BigDecimal>> = if condition.is_yoy() {
+        yoy_changes(observations)
+    } else {
+        observations
+            .iter()
+            .map(|(_, value)| Some(value.clone()))
+            .collect()
+    };
+
+    let window = &metrics[metrics.len() - window_periods..];
+    let mut holds = true;
+    for metric in window {
+        holds &= condition.is_met(metric.as_ref()?, threshold);
+    }
+
+    let fired = (holds && !was_triggered).then(|| {
+        let (date, _) = observations.last().expect("window is not empty");
+        let metric = window
+            .last()
+            .cloned()
+            .flatten()
+            .expect("window metrics exist");
+        (*date, metric)
+    });
+
+    Some(AlertRuleState {
+        is_triggered: holds,
+        fired,
+    })
+}
+
+/// Notification text for a rule that fired
+pub fn alert_message(
+    rule: &SeriesAlertRule,
+    condition: AlertCondition,
+    series_title: &str,
+    date: NaiveDate,
+    metric: &BigDecimal,
+) -> String {
+    let metric = metric.round(4).normalized();
+    let threshold = rule.threshold.round(4).normalized();
+    let (measure, suffix) = if condition.is_yoy() {
+        ("year-over-year change", "%")
+    } else {
+        ("value", "")
+    };
+    let direction = match condition {
+        AlertCondition::ValueAbove | AlertCondition::YoyChangeAbove => "above",
+        AlertCondition::ValueBelow | AlertCondition::YoyChangeBelow => "below",
+    };
+    let persistence = if rule.window_periods > 1 {
+        format!(" for {} consecutive observations", rule.window_periods)
+    } else {
+        String::new()
+    };
+
+    format!(
+        "{}: {} of {}{} on {} is {} {}{}{} (alert \"{}\")",
+        series_title,
+        measure,
+        metric,
+        suffix,
+        date,
+        direction,
+        threshold,
+        suffix,
+        persistence,
+        rule.name
+    )
+}
+
+/// Evaluates series alert rules and notifies their owners
+pub struct SeriesAlertService {
+    pool: DatabasePool,
+}
+
+impl SeriesAlertService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Evaluate every active rule on a series, returning the notifications sent
+    #[tracing::instrument(name = "alerts.evaluate_series", skip(self))]
+    pub async fn evaluate_series(&self, series_id: Uuid) -> AppResult<Vec<Notification>> {
+        let rules = SeriesAlertRule::find_active_for_series(&self.pool, series_id).await?;
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let max_window = rules
+            .iter()
+            .map(|rule| rule.window_periods.max(1) as i64)
+            .max()
+            .unwrap_or(1);
+        let needs_yoy = rules
+            .iter()
+            .any(|rule| rule.condition().is_ok_and(|c| c.is_yoy()));
+        let observations = self
+            .recent_observations(series_id, max_window, needs_yoy)
+            .await?;
+        let series_title = self.series_title(series_id).await?;
+
+        let notification_service = NotificationService::new(self.pool.clone());
+        let mut notifications = Vec::new();
+        for rule in rules {
+            let condition = match rule.condition() {
+                Ok(condition) => condition,
+                Err(e) => {
+                    warn!("Skipping alert rule {}: {}", rule.id, e);
+                    continue;
+                }
+            };
+            let Some(state) = evaluate_rule(
+                condition,
+                &rule.threshold,
+                rule.window_periods as usize,
+                rule.is_triggered,
+                &observations,
+            ) else {
+                continue;
+            };
+
+            SeriesAlertRule::record_evaluation(&self.pool, rule.id, &state).await?;
+
+            if let Some((date, metric)) = &state.fired {
+                let message = alert_message(&rule, condition, &series_title, *date, metric);
+                info!("Alert rule {} fired: {}", rule.id, message);
+                notifications.extend(
+                    notification_service
+                        .notify_series_alert(&rule, message)
+                        .await?,
+                );
+            }
+        }
+
+        Ok(notifications)
+    }
+
+    /// Observations needed to evaluate windows of up to `max_window` periods
+    async fn recent_observations(
+        &self,
+        series_id: Uuid,
+        max_window: i64,
+        needs_yoy: bool,
+    ) -> AppResult<Vec<(NaiveDate, BigDecimal)>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let recent_dates: Vec<NaiveDate> = data_points::table
+            .filter(data_points::series_id.eq(series_id))
+            .filter(data_points::value.is_not_null())
+            .select(data_points::date)
+            .distinct()
+            .order(data_points::date.desc())
+            .limit(max_window)
+            .load(&mut conn)
+            .await?;
+        drop(conn);
+
+        let (Some(&latest), Some(&earliest)) = (recent_dates.first(), recent_dates.last()) else {
+            return Ok(Vec::new());
+        };
+        let start = if needs_yoy {
+            earliest
+                .checked_sub_months(Months::new(12))
+                .and_then(|date| date.checked_sub_days(chrono::Days::new(YOY_LOOKBACK_DAYS)))
+                .unwrap_or(earliest)
+        } else {
+            earliest
+        };
+
+        let points =
+            DataPoint::find_by_series_and_date_range(&self.pool, series_id, start, latest).await?;
+
+        Ok(latest_observations(points))
+    }
+
+    async fn series_title(&self, series_id: Uuid) -> AppResult<String> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let title = economic_series::table
+            .find(series_id)
+            .select(economic_series::title)
+            .first::<String>(&mut conn)
+            .await?;
+
+        Ok(title)
+    }
+}
+
+/// Evaluate alert rules after new observations were stored for a series
+///
+/// Failures are logged rather than returned so alerting never fails a crawl.
+pub async fn evaluate_alerts_after_update(pool: &DatabasePool, series_id: Uuid) {
+    if let Err(e) = SeriesAlertService::new(pool.clone())
+        .evaluate_series(series_id)
+        .await
+    {
+        warn!(
+            "Failed to evaluate alert rules for series {}: {}",
+            series_id, e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::str::FromStr;
+
+    fn date(year: i32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    fn observations(values: &[(i32, u32, &str)]) -> Vec<(NaiveDate, BigDecimal)> {
+        values
+            .iter()
+            .map(|(year, month, value)| (date(*year, *month), BigDecimal::from_str(value).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rule_fires_once_when_level_is_crossed() {
+        // REQUIREMENT: Analysts are told when unemployment crosses a level
+        // PURPOSE: Verify a value rule fires on the crossing, stays quiet while above, and re-arms
+        // This ensures users get one notification per crossing instead of one per release
+
+        let threshold = BigDecimal::from(5);
+        let below = observations(&[(2024, 1, "4.8"), (2024, 2, "4.9")]);
+        let crossed = observations(&[(2024, 1, "4.8"), (2024, 2, "4.9"), (2024, 3, "5.1")]);
+
+        let state =
+            evaluate_rule(AlertCondition::ValueAbove, &threshold, 1, false, &below).unwrap();
+        assert_eq!(
+            state,
+            AlertRuleState {
+                is_triggered: false,
+                fired: None
+            }
+        );
+
+        let state =
+            evaluate_rule(AlertCondition::ValueAbove, &threshold, 1, false, &crossed).unwrap();
+        assert!(state.is_triggered);
+        assert_eq!(
+            state.fired,
+            Some((date(2024, 3), BigDecimal::from_str("5.1").unwrap()))
+        );
+
+        let state =
+            evaluate_rule(AlertCondition::ValueAbove, &threshold, 1, true, &crossed).unwrap();
+        assert!(state.is_triggered);
+        assert_eq!(state.fired, None);
+
+        let state = evaluate_rule(AlertCondition::ValueAbove, &threshold, 1, true, &below).unwrap();
+        assert!(!state.is_triggered);
+
+        // Requiring two observations above the level delays the alert
+        let state =
+            evaluate_rule(AlertCondition::ValueAbove, &threshold, 2, false, &crossed).unwrap();
+        assert!(!state.is_triggered);
+    }
+
+    #[test]
+    fn test_yoy_rule_uses_year_ago_observation() {
+        // REQUIREMENT: Alerts on year-over-year changes, e.g. inflation below 2%
+        // PURPOSE: Verify YoY changes are computed from the year-ago value and need enough history
+        // This ensures YoY alerts are neither fired nor cleared on incomplete history
+
+        let history = observations(&[(2023, 3, "100"), (2024, 2, "101"), (2024, 3, "101.5")]);
+        let threshold = BigDecimal::from(2);
+
+        let changes = yoy_changes(&history);
+        assert_eq!(changes[0], None);
+        assert_eq!(changes[1], None);
+        assert_eq!(changes[2], Some(BigDecimal::from_str("1.5").unwrap()));
+
+        let state = evaluate_rule(
+            AlertCondition::YoyChangeBelow,
+            &threshold,
+            1,
+            false,
+            &history,
+        )
+        .unwrap();
+        assert!(state.is_triggered);
+        assert!(evaluate_rule(
+            AlertCondition::YoyChangeBelow,
+            &threshold,
+            2,
+            false,
+            &history
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_latest_observations_prefers_latest_revision() {
+        // REQUIREMENT: Alerts are evaluated on the current vintage of each observation
+        // PURPOSE: Verify revisions replace earlier values and missing values are dropped
+        // This ensures a revised figure, not the first estimate, decides whether an alert fires
+
+        let series_id = Uuid::new_v4();
+        let point = |month: u32, revision_day: u32, value: Option<&str>| DataPoint {
+            id: Uuid::new_v4(),
+            series_id,
+            date: date(2024, month),
+            value: value.map(|v| BigDecimal::from_str(v).unwrap()),
+            revision_date: NaiveDate::from_ymd_opt(2024, month + 1, revision_day).unwrap(),
+            is_original_release: revision_day == 1,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let latest = latest_observations(vec![
+            point(2, 15, Some("4.9")),
+            point(1, 1, Some("4.0")),
+            point(2, 1, Some("4.7")),
+            point(3, 1, None),
+        ]);
+
+        assert_eq!(latest, observations(&[(2024, 1, "4.0"), (2024, 2, "4.9")]));
+    }
+}
@@ -3,6 +3,7 @@
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
 use econ_graph_core::models::{DataSource, EconomicSeries, NewEconomicSeries};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, BEA_HOST};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
 use reqwest::Client;
 use reqwest::StatusCode;
@@ -94,6 +95,7 @@ async fn fetch_bea_datasets(client: &Client, api_key: &str) -> AppResult<Vec<Bea
         api_key
     );
 
+    shared_rate_limiter().acquire(BEA_HOST, Some(api_key)).await;
     let start = std::time::Instant::now();
     let response =
         client.get(&url).send().await.map_err(|e| {
@@ -2,12 +2,19 @@
 
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
-use econ_graph_core::models::{DataSource, EconomicSeries, NewEconomicSeries};
+use econ_graph_core::models::{
+    DataSource, EconomicSeries, NewEconomicSeries, NewSeriesMetadata, SeriesEnrichment,
+    SeriesMetadata,
+};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, BLS_HOST};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::bls_catalog::{catalog_surveys_from_env, sync_bls_survey};
+
 /// BLS API v2 response for surveys
 #[derive(Debug, Deserialize)]
 pub struct BlsSurveysResponse {
@@ -62,6 +69,17 @@ pub async fn discover_bls_series(
     let surveys = fetch_bls_surveys(client, api_key).await?;
     println!("Found {} BLS surveys", surveys.len());
 
+    let survey_names: HashMap<String, String> = surveys
+        .iter()
+        .map(|survey| {
+            (
+                survey.survey_abbreviation.clone(),
+                survey.survey_name.clone(),
+            )
+        })
+        .collect();
+    let catalog_surveys = catalog_surveys_from_env();
+
     // For each survey, discover series
     for survey in surveys {
         println!(
@@ -69,6 +87,26 @@ pub async fn discover_bls_series(
             survey.survey_name, survey.survey_abbreviation
         );
 
+        // Surveys with a synced catalog list every series; the rest fall back to known series
+        if catalog_surveys.contains(&survey.survey_abbreviation) {
+            match sync_bls_survey(
+                client,
+                pool,
+                bls_source.id,
+                &survey.survey_abbreviation,
+                &survey_names,
+            )
+            .await
+            {
+                Ok(report) => discovered_series.extend(report.added),
+                Err(e) => eprintln!(
+                    "BLS {} catalog sync failed: {}",
+                    survey.survey_abbreviation, e
+                ),
+            }
+            continue;
+        }
+
         let survey_series = get_known_bls_series_by_survey(&survey.survey_abbreviation);
 
         for series_info in survey_series {
@@ -83,9 +121,10 @@ pub async fn discover_bls_series(
 }
 
 /// Fetch all surveys from BLS API v2
-async fn fetch_bls_surveys(client: &Client, api_key: &str) -> AppResult<Vec<BlsSurvey>> {
+pub async fn fetch_bls_surveys(client: &Client, api_key: &str) -> AppResult<Vec<BlsSurvey>> {
     let url = "https://api.bls.gov/publicAPI/v2/surveys";
 
+    shared_rate_limiter().acquire(BLS_HOST, Some(api_key)).await;
     let start = std::time::Instant::now();
     let response =
         client.get(url).send().await.map_err(|e| {
@@ -202,5 +241,187 @@ async fn store_bls_series(
     };
 
     EconomicSeries::get_or_create(pool, &series_info.series_id, *source_id, &new_series).await?;
+
+    // Catalog entry for the enrichment pass
+    let new_metadata = NewSeriesMetadata {
+        source_id: *source_id,
+        external_id: series_info.series_id.clone(),
+        title: series_info.title.clone(),
+        description: series_info.description.clone(),
+        units: Some(series_info.units.clone()),
+        frequency: Some(series_info.frequency.clone()),
+        geographic_level: None,
+        data_url: Some(format!(
+            "https://data.bls.gov/timeseries/{}",
+            series_info.series_id
+        )),
+        api_endpoint: Some(format!(
+            "https://api.bls.gov/publicAPI/v2/timeseries/data/{}",
+            series_info.series_id
+        )),
+        is_active: true,
+    };
+    SeriesMetadata::get_or_create(pool, *source_id, &series_info.series_id, &new_metadata).await?;
     Ok(())
 }
+
+/// Surveys whose series all cover the whole United States
+const NATIONAL_SURVEYS: &[&str] = &["CE", "CI", "EI", "JT", "LN", "PC", "PR", "WP"];
+
+/// Enrichment decoded from a BLS series ID
+///
+/// BLS has no per-series catalog API, but IDs encode the survey (first two
+/// characters), seasonal adjustment and, for area surveys, the area:
+/// - CPI (`CU`, `CW`): area code at positions 4-7, `0000` for the U.S. city average
+///   and `0100`-`0400` for census regions, `S` codes for metro areas
+/// - LAUS (`LA`): area type at positions 3-4 (`ST` state, `CN` county, `MT` metro),
+///   then the state FIPS code
+/// - State and metro employment (`SM`): state FIPS code at positions 3-4, then a
+///   metro code that is `00000` for the whole state
+///
+/// `survey_names` maps survey abbreviations to names from the BLS surveys catalog.
+pub fn bls_enrichment(series_id: &str, survey_names: &HashMap<String, String>) -> SeriesEnrichment {
+    let survey = series_id.get(..2).unwrap_or_default();
+    let mut enrichment = SeriesEnrichment {
+        categories: survey_names.get(survey).cloned().into_iter().collect(),
+        tags: vec![survey.to_lowercase()],
+        ..Default::default()
+    };
+
+    match series_id.get(2..3) {
+        Some("S") => enrichment.tags.push("sa".to_string()),
+        Some("U") => enrichment.tags.push("nsa".to_string()),
+        _ => {}
+    }
+
+    let (level, geography) = match survey {
+        "CU" | "CW" => match series_id.get(4..8) {
+            Some("0000") => ("National", Some("United States")),
+            Some("0100") => ("Region", Some("Northeast")),
+            Some("0200") => ("Region", Some("Midwest")),
+            Some("0300") => ("Region", Some("South")),
+            Some("0400") => ("Region", Some("West")),
+            Some(area) if area.starts_with('S') => ("MSA", None),
+            _ => return enrichment,
+        },
+        "LA" => {
+            let state = series_id.get(5..7).and_then(state_name);
+            match series_id.get(3..5) {
+                Some("ST") => ("State", state),
+                Some("CN") => ("County", state),
+                Some("MT") => ("MSA", state),
+                _ => return enrichment,
+            }
+        }
+        "SM" => {
+            let state = series_id.get(3..5).and_then(state_name);
+            match series_id.get(5..10) {
+                Some("00000") => ("State", state),
+                Some(_) => ("MSA", state),
+                None => return enrichment,
+            }
+        }
+        survey if NATIONAL_SURVEYS.contains(&survey) => ("National", Some("United States")),
+        _ => return enrichment,
+    };
+
+    enrichment.geographic_level = Some(level.to_string());
+    enrichment.geography = geography.map(str::to_string);
+    enrichment
+}
+
+/// State (or territory) of a two-digit FIPS code
+fn state_name(fips: &str) -> Option<&'static str> {
+    let name = match fips {
+        "01" => "Alabama",
+        "02" => "Alaska",
+        "04" => "Arizona",
+        "05" => "Arkansas",
+        "06" => "California",
+        "08" => "Colorado",
+        "09" => "Connecticut",
+        "10" => "Delaware",
+        "11" => "District of Columbia",
+        "12" => "Florida",
+        "13" => "Georgia",
+        "15" => "Hawaii",
+        "16" => "Idaho",
+        "17" => "Illinois",
+        "18" => "Indiana",
+        "19" => "Iowa",
+        "20" => "Kansas",
+        "21" => "Kentucky",
+        "22" => "Louisiana",
+        "23" => "Maine",
+        "24" => "Maryland",
+        "25" => "Massachusetts",
+        "26" => "Michigan",
+        "27" => "Minnesota",
+        "28" => "Mississippi",
+        "29" => "Missouri",
+        "30" => "Montana",
+        "31" => "Nebraska",
+        "32" => "Nevada",
+        "33" => "New Hampshire",
+        "34" => "New Jersey",
+        "35" => "New Mexico",
+        "36" => "New York",
+        "37" => "North Carolina",
+        "38" => "North Dakota",
+        "39" => "Ohio",
+        "40" => "Oklahoma",
+        "41" => "Oregon",
+        "42" => "Pennsylvania",
+        "44" => "Rhode Island",
+        "45" => "South Carolina",
+        "46" => "South Dakota",
+        "47" => "Tennessee",
+        "48" => "Texas",
+        "49" => "Utah",
+        "50" => "Vermont",
+        "51" => "Virginia",
+        "53" => "Washington",
+        "54" => "West Virginia",
+        "55" => "Wisconsin",
+        "56" => "Wyoming",
+        "72" => "Puerto Rico",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bls_enrichment_from_series_id() {
+        // REQUIREMENT: Enrich series metadata from BLS catalogs
+        // PURPOSE: Verify survey, seasonal adjustment and area are decoded from BLS series IDs
+        // This ensures search can filter BLS series by geography and topic
+
+        let surveys = HashMap::from([
+            ("CU".to_string(), "Consumer Price Index".to_string()),
+            (
+                "LA".to_string(),
+                "Local Area Unemployment Statistics".to_string(),
+            ),
+        ]);
+
+        let cpi = bls_enrichment("CUUR0000SA0", &surveys);
+        assert_eq!(cpi.geographic_level.as_deref(), Some("National"));
+        assert_eq!(cpi.geography.as_deref(), Some("United States"));
+        assert_eq!(cpi.categories, vec!["Consumer Price Index"]);
+        assert_eq!(cpi.tags, vec!["cu", "nsa"]);
+
+        let california = bls_enrichment("LASST060000000000003", &surveys);
+        assert_eq!(california.geographic_level.as_deref(), Some("State"));
+        assert_eq!(california.geography.as_deref(), Some("California"));
+        assert_eq!(california.tags, vec!["la", "sa"]);
+
+        let texas_metro = bls_enrichment("SMU48191000000000001", &surveys);
+        assert_eq!(texas_metro.geographic_level.as_deref(), Some("MSA"));
+        assert_eq!(texas_metro.geography.as_deref(), Some("Texas"));
+        assert!(texas_metro.categories.is_empty());
+    }
+}
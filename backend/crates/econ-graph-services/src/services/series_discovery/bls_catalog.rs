@@ -0,0 +1,573 @@
+//! Full BLS series catalogs from the LABSTAT time series files
+//!
+//! The BLS API v2 lists surveys but not their series. Every survey's complete
+//! series list is published instead as a tab-separated `.series` file under
+//! `download.bls.gov/pub/time.series/<survey>/`, with the series ID, title,
+//! seasonal adjustment and first and last period of each series.
+//!
+//! A sync downloads the file of each survey, stores new and changed series in
+//! `economic_series` and `series_metadata` a page at a time, and marks series
+//! that dropped off the list inactive in `series_metadata`. Syncs are
+//! incremental: the file is requested only if it changed since the last sync
+//! (see [`BlsCatalogSync`]), and unchanged series are not rewritten.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{BlsCatalogSync, NewEconomicSeries};
+use econ_graph_core::schema::{economic_series, series_metadata};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+use reqwest::header::{
+    HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT,
+};
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::bls::bls_enrichment;
+
+/// Directory of the LABSTAT time series files
+pub const BLS_TIME_SERIES_URL: &str = "https://download.bls.gov/pub/time.series";
+
+/// Surveys synced by default: the Current Population Survey (CPS) and Current
+/// Employment Statistics (CES); `BLS_CATALOG_SURVEYS` overrides them
+pub const DEFAULT_BLS_CATALOG_SURVEYS: &[&str] = &["LN", "CE"];
+
+/// Series written per statement
+pub const BLS_CATALOG_PAGE_SIZE: usize = 1000;
+
+/// BLS refuses downloads without a descriptive user agent
+const DEFAULT_USER_AGENT: &str = "EconGraph/1.0 (series catalog sync)";
+
+/// A series as listed in a survey's `.series` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlsCatalogSeries {
+    pub series_id: String,
+    pub title: String,
+    pub frequency: String,
+    pub seasonal_adjustment: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Subset of stored series metadata compared during a sync
+#[derive(Debug, Clone, PartialEq, Eq, Queryable)]
+pub struct ExistingCatalogEntry {
+    pub external_id: String,
+    pub title: String,
+    pub frequency: Option<String>,
+    pub is_active: bool,
+}
+
+/// Outcome of syncing one survey's catalog
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BlsSurveySyncReport {
+    pub survey: String,
+    /// The file had not changed since the last sync, so nothing was read
+    pub not_modified: bool,
+    /// Series in the file
+    pub listed: usize,
+    /// Series IDs new to the catalog
+    pub added: Vec<String>,
+    /// Series whose title or frequency changed, or that reappeared
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Series that dropped off the list and were marked inactive
+    pub deactivated: usize,
+}
+
+/// Surveys to sync, from `BLS_CATALOG_SURVEYS` (comma-separated abbreviations)
+pub fn catalog_surveys_from_env() -> Vec<String> {
+    match std::env::var("BLS_CATALOG_SURVEYS") {
+        Ok(value) if !value.trim().is_empty() => value
+            .split(',')
+            .map(|survey| survey.trim().to_uppercase())
+            .filter(|survey| !survey.is_empty())
+            .collect(),
+        _ => DEFAULT_BLS_CATALOG_SURVEYS
+            .iter()
+            .map(|survey| survey.to_string())
+            .collect(),
+    }
+}
+
+/// Frequency of a BLS period code: `M05` is monthly, `Q02` quarterly, ...
+fn period_frequency(period: &str) -> Option<&'static str> {
+    match period.get(..1)? {
+        "M" => Some("Monthly"),
+        "Q" => Some("Quarterly"),
+        "S" => Some("Semiannual"),
+        "A" => Some("Annual"),
+        _ => None,
+    }
+}
+
+/// First day of a BLS period; annual averages (`M13`, `Q05`, `S03`) start in January
+fn period_start(year: &str, period: &str) -> Option<NaiveDate> {
+    let year = year.parse().ok()?;
+    let number: u32 = period.get(1..)?.parse().ok()?;
+    let month = match period.get(..1)? {
+        "M" if (1..=12).contains(&number) => number,
+        "M" if number == 13 => 1,
+        "Q" if (1..=4).contains(&number) => (number - 1) * 3 + 1,
+        "Q" if number == 5 => 1,
+        "S" if number == 2 => 7,
+        "S" | "A" => 1,
+        _ => return None,
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+/// Parse a `.series` file into its series
+///
+/// Columns differ between surveys, so they are found by name in the header
+/// row. Rows without a series ID or frequency are skipped.
+pub fn parse_series_file(contents: &str) -> AppResult<Vec<BlsCatalogSeries>> {
+    let mut lines = contents.lines();
+    let header: Vec<&str> = lines
+        .next()
+        .map(|line| line.split('\t').map(str::trim).collect())
+        .unwrap_or_default();
+    let column = |name: &str| header.iter().position(|column| *column == name);
+    let series_id_column = column("series_id").ok_or_else(|| {
+        AppError::ExternalApiError("BLS series file has no series_id column".to_string())
+    })?;
+    let title_column = column("series_title");
+    let seasonal_column = column("seasonal");
+    let periodicity_column = column("periodicity_code");
+    let begin = (column("begin_year"), column("begin_period"));
+    let end = (column("end_year"), column("end_period"));
+
+    let series = lines
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index).copied())
+                    .filter(|value| !value.is_empty())
+            };
+            let date = |(year, period): (Option<usize>, Option<usize>)| {
+                period_start(field(year)?, field(period)?)
+            };
+
+            let series_id = field(Some(series_id_column))?.to_string();
+            let frequency = field(periodicity_column)
+                .or_else(|| field(begin.1))
+                .and_then(period_frequency)?;
+            Some(BlsCatalogSeries {
+                title: field(title_column)
+                    .unwrap_or(&series_id)
+                    .chars()
+                    .take(500)
+                    .collect(),
+                frequency: frequency.to_string(),
+                seasonal_adjustment: match field(seasonal_column) {
+                    Some("S") => Some("Seasonally Adjusted".to_string()),
+                    Some("U") => Some("Not Seasonally Adjusted".to_string()),
+                    _ => None,
+                },
+                start_date: date(begin),
+                end_date: date(end),
+                series_id,
+            })
+        })
+        .collect();
+
+    Ok(series)
+}
+
+/// Work out which listed series need writing and which stored ones dropped off
+///
+/// Returns the series to upsert, the IDs to deactivate and a report with the
+/// counts filled in.
+pub fn plan_catalog_sync<'a>(
+    survey: &str,
+    listed: &'a [BlsCatalogSeries],
+    existing: &[ExistingCatalogEntry],
+) -> (Vec<&'a BlsCatalogSeries>, Vec<String>, BlsSurveySyncReport) {
+    let stored: HashMap<&str, &ExistingCatalogEntry> = existing
+        .iter()
+        .map(|entry| (entry.external_id.as_str(), entry))
+        .collect();
+    let listed_ids: HashSet<&str> = listed.iter().map(|s| s.series_id.as_str()).collect();
+
+    let mut report = BlsSurveySyncReport {
+        survey: survey.to_string(),
+        listed: listed.len(),
+        ..Default::default()
+    };
+    let mut upserts = Vec::new();
+
+    for series in listed {
+        match stored.get(series.series_id.as_str()) {
+            None => report.added.push(series.series_id.clone()),
+            Some(current)
+                if !current.is_active
+                    || current.title != series.title
+                    || current.frequency.as_deref() != Some(series.frequency.as_str()) =>
+            {
+                report.updated += 1
+            }
+            Some(_) => {
+                report.unchanged += 1;
+                continue;
+            }
+        }
+        upserts.push(series);
+    }
+
+    let deactivated: Vec<String> = existing
+        .iter()
+        .filter(|entry| entry.is_active && !listed_ids.contains(entry.external_id.as_str()))
+        .map(|entry| entry.external_id.clone())
+        .collect();
+    report.deactivated = deactivated.len();
+
+    (upserts, deactivated, report)
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = series_metadata)]
+struct CatalogMetadata<'a> {
+    source_id: Uuid,
+    external_id: &'a str,
+    title: &'a str,
+    frequency: Option<&'a str>,
+    geographic_level: Option<String>,
+    geography: Option<String>,
+    categories: Vec<String>,
+    tags: Vec<String>,
+    data_url: Option<String>,
+    api_endpoint: Option<String>,
+    is_active: bool,
+    last_discovered_at: Option<DateTime<Utc>>,
+    enriched_at: Option<DateTime<Utc>>,
+}
+
+fn connection_error(e: impl std::fmt::Display) -> AppError {
+    AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+}
+
+/// Sync the catalogs of `surveys`; a survey that fails is reported and skipped
+pub async fn sync_bls_catalog(
+    client: &Client,
+    pool: &DatabasePool,
+    source_id: Uuid,
+    surveys: &[String],
+    survey_names: &HashMap<String, String>,
+) -> Vec<AppResult<BlsSurveySyncReport>> {
+    let mut reports = Vec::with_capacity(surveys.len());
+    for survey in surveys {
+        let report = sync_bls_survey(client, pool, source_id, survey, survey_names).await;
+        match &report {
+            Ok(report) if report.not_modified => {
+                println!("BLS {} catalog unchanged since last sync", survey)
+            }
+            Ok(report) => println!(
+                "BLS {} catalog synced: {} listed, {} added, {} updated, {} deactivated",
+                survey,
+                report.listed,
+                report.added.len(),
+                report.updated,
+                report.deactivated
+            ),
+            Err(e) => eprintln!("BLS {} catalog sync failed: {}", survey, e),
+        }
+        reports.push(report);
+    }
+    reports
+}
+
+/// Sync one survey's catalog into `economic_series` and `series_metadata`
+pub async fn sync_bls_survey(
+    client: &Client,
+    pool: &DatabasePool,
+    source_id: Uuid,
+    survey: &str,
+    survey_names: &HashMap<String, String>,
+) -> AppResult<BlsSurveySyncReport> {
+    let survey = survey.to_uppercase();
+    let previous = BlsCatalogSync::find(pool, &survey).await?;
+    let Some(file) = fetch_series_file(client, &survey, previous.as_ref()).await? else {
+        return Ok(BlsSurveySyncReport {
+            survey,
+            not_modified: true,
+            ..Default::default()
+        });
+    };
+
+    let listed = parse_series_file(&file.contents)?;
+    let mut conn = pool.get().await.map_err(connection_error)?;
+    let existing: Vec<ExistingCatalogEntry> = series_metadata::table
+        .filter(series_metadata::source_id.eq(source_id))
+        .filter(series_metadata::external_id.like(format!("{}%", survey)))
+        .select((
+            series_metadata::external_id,
+            series_metadata::title,
+            series_metadata::frequency,
+            series_metadata::is_active,
+        ))
+        .load(&mut conn)
+        .await?;
+
+    let (upserts, deactivated, report) = plan_catalog_sync(&survey, &listed, &existing);
+    let now = Utc::now();
+
+    for page in upserts.chunks(BLS_CATALOG_PAGE_SIZE) {
+        let series_rows: Vec<NewEconomicSeries> = page
+            .iter()
+            .map(|series| NewEconomicSeries {
+                source_id,
+                external_id: series.series_id.clone(),
+                title: series.title.clone(),
+                description: None,
+                units: None,
+                frequency: series.frequency.clone(),
+                seasonal_adjustment: series.seasonal_adjustment.clone(),
+                start_date: series.start_date,
+                end_date: series.end_date,
+                is_active: true,
+                first_discovered_at: Some(now),
+                last_crawled_at: None,
+                first_missing_date: None,
+                crawl_status: None,
+                crawl_error_message: None,
+            })
+            .collect();
+        diesel::insert_into(economic_series::table)
+            .values(&series_rows)
+            .on_conflict((economic_series::source_id, economic_series::external_id))
+            .do_update()
+            .set((
+                economic_series::title.eq(excluded(economic_series::title)),
+                economic_series::frequency.eq(excluded(economic_series::frequency)),
+                economic_series::seasonal_adjustment
+                    .eq(excluded(economic_series::seasonal_adjustment)),
+                economic_series::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)
+            .await?;
+
+        let metadata_rows: Vec<CatalogMetadata> = page
+            .iter()
+            .map(|series| {
+                let enrichment = bls_enrichment(&series.series_id, survey_names);
+                CatalogMetadata {
+                    source_id,
+                    external_id: &series.series_id,
+                    title: &series.title,
+                    frequency: Some(series.frequency.as_str()),
+                    geographic_level: enrichment.geographic_level,
+                    geography: enrichment.geography,
+                    categories: enrichment.categories,
+                    tags: enrichment.tags,
+                    data_url: Some(format!(
+                        "https://data.bls.gov/timeseries/{}",
+                        series.series_id
+                    )),
+                    api_endpoint: Some(format!(
+                        "https://api.bls.gov/publicAPI/v2/timeseries/data/{}",
+                        series.series_id
+                    )),
+                    is_active: true,
+                    last_discovered_at: Some(now),
+                    enriched_at: Some(now),
+                }
+            })
+            .collect();
+        diesel::insert_into(series_metadata::table)
+            .values(&metadata_rows)
+            .on_conflict((series_metadata::source_id, series_metadata::external_id))
+            .do_update()
+            .set((
+                series_metadata::title.eq(excluded(series_metadata::title)),
+                series_metadata::frequency.eq(excluded(series_metadata::frequency)),
+                series_metadata::geographic_level.eq(excluded(series_metadata::geographic_level)),
+                series_metadata::geography.eq(excluded(series_metadata::geography)),
+                series_metadata::categories.eq(excluded(series_metadata::categories)),
+                series_metadata::tags.eq(excluded(series_metadata::tags)),
+                series_metadata::is_active.eq(true),
+                series_metadata::last_discovered_at
+                    .eq(excluded(series_metadata::last_discovered_at)),
+                series_metadata::enriched_at.eq(excluded(series_metadata::enriched_at)),
+            ))
+            .execute(&mut conn)
+            .await?;
+    }
+
+    for page in deactivated.chunks(BLS_CATALOG_PAGE_SIZE) {
+        diesel::update(
+            series_metadata::table
+                .filter(series_metadata::source_id.eq(source_id))
+                .filter(series_metadata::external_id.eq_any(page)),
+        )
+        .set(series_metadata::is_active.eq(false))
+        .execute(&mut conn)
+        .await?;
+    }
+
+    // Recorded last, so an interrupted sync downloads the file again
+    BlsCatalogSync::record(
+        pool,
+        &BlsCatalogSync {
+            survey: survey.clone(),
+            etag: file.etag,
+            last_modified: file.last_modified,
+            series_listed: listed.len() as i32,
+            synced_at: now,
+        },
+    )
+    .await?;
+    CRAWLER_METRICS.record_items_collected(
+        "economic",
+        "bls",
+        "catalog_series",
+        report.added.len() as u64,
+    );
+
+    Ok(report)
+}
+
+/// A downloaded `.series` file with its validators
+struct SeriesFile {
+    contents: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Download a survey's `.series` file, or `None` if it has not changed since `previous`
+async fn fetch_series_file(
+    client: &Client,
+    survey: &str,
+    previous: Option<&BlsCatalogSync>,
+) -> AppResult<Option<SeriesFile>> {
+    let survey = survey.to_lowercase();
+    let url = format!("{}/{}/{}.series", BLS_TIME_SERIES_URL, survey, survey);
+    let user_agent =
+        std::env::var("BLS_CATALOG_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.to_string());
+
+    let mut request = client.get(&url).header(USER_AGENT, user_agent);
+    if let Some(etag) = previous.and_then(|sync| sync.etag.as_deref()) {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = previous.and_then(|sync| sync.last_modified.as_deref()) {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let start = std::time::Instant::now();
+    let response = request.send().await.map_err(|e| {
+        AppError::ExternalApiError(format!("BLS series file request failed: {}", e))
+    })?;
+    let status = response.status();
+    CRAWLER_METRICS.record_request(
+        "economic",
+        "bls",
+        "/pub/time.series",
+        status.as_str(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(AppError::ExternalApiError(format!(
+            "BLS series file {} returned status: {}",
+            url, status
+        )));
+    }
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let contents = response.text().await.map_err(|e| {
+        AppError::ExternalApiError(format!("Failed to read BLS series file: {}", e))
+    })?;
+
+    Ok(Some(SeriesFile {
+        contents,
+        etag,
+        last_modified,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_series_file_and_plan_incremental_sync() {
+        // REQUIREMENT: Full BLS catalog sync so users can discover all CPS and CES series
+        // PURPOSE: Verify series files parse by header name and only new, changed or removed series are written
+        // This ensures repeat syncs of catalogs with tens of thousands of series stay cheap
+
+        let file = "series_id        \tlfst_code\tperiodicity_code\tseries_title\tseasonal\tbegin_year\tbegin_period\tend_year\tend_period\n\
+            LNS14000000      \t40\tM\t(Seas) Unemployment Rate\tS\t1948\tM01\t2024\tM09\n\
+            LNU04000000      \t40\tM\t(Unadj) Unemployment Rate\tU\t1948\tM01\t2024\tM13\n\
+            LNU04032231Q     \t40\tQ\t(Unadj) Unemployment Rate - Construction\tU\t2000\tQ01\t2024\tQ03\n\
+            \n";
+        let listed = parse_series_file(file).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(
+            listed[0],
+            BlsCatalogSeries {
+                series_id: "LNS14000000".to_string(),
+                title: "(Seas) Unemployment Rate".to_string(),
+                frequency: "Monthly".to_string(),
+                seasonal_adjustment: Some("Seasonally Adjusted".to_string()),
+                start_date: NaiveDate::from_ymd_opt(1948, 1, 1),
+                end_date: NaiveDate::from_ymd_opt(2024, 9, 1),
+            }
+        );
+        assert_eq!(listed[1].end_date, NaiveDate::from_ymd_opt(2024, 1, 1));
+        assert_eq!(listed[2].frequency, "Quarterly");
+        assert_eq!(listed[2].end_date, NaiveDate::from_ymd_opt(2024, 7, 1));
+
+        let existing = vec![
+            ExistingCatalogEntry {
+                external_id: "LNS14000000".to_string(),
+                title: "(Seas) Unemployment Rate".to_string(),
+                frequency: Some("Monthly".to_string()),
+                is_active: true,
+            },
+            ExistingCatalogEntry {
+                external_id: "LNU04000000".to_string(),
+                title: "Unemployment Rate".to_string(),
+                frequency: Some("Monthly".to_string()),
+                is_active: true,
+            },
+            ExistingCatalogEntry {
+                external_id: "LNU02000000".to_string(),
+                title: "Discontinued".to_string(),
+                frequency: Some("Monthly".to_string()),
+                is_active: true,
+            },
+        ];
+        let (upserts, deactivated, report) = plan_catalog_sync("LN", &listed, &existing);
+        let upserted: Vec<&str> = upserts.iter().map(|s| s.series_id.as_str()).collect();
+        assert_eq!(upserted, vec!["LNU04000000", "LNU04032231Q"]);
+        assert_eq!(deactivated, vec!["LNU02000000"]);
+        assert_eq!(report.added, vec!["LNU04032231Q"]);
+        assert_eq!(
+            (
+                report.listed,
+                report.updated,
+                report.unchanged,
+                report.deactivated
+            ),
+            (3, 1, 1, 1)
+        );
+
+        assert!(parse_series_file("title\tseasonal\n").is_err());
+    }
+}
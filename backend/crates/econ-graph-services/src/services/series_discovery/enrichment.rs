@@ -0,0 +1,105 @@
+//! Enrichment of discovered series with catalog metadata
+//!
+//! Discovery stores each series' title, units and frequency. This pass adds the
+//! categories, topic tags and geography the source catalogs know about, so
+//! search can filter series by area and topic. FRED exposes categories and tags
+//! per series; BLS has no per-series catalog, so its metadata is decoded from
+//! series IDs and the surveys catalog.
+
+use super::{bls, fred};
+use chrono::{Duration, Utc};
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{DataSource, SeriesMetadata};
+use reqwest::Client;
+use std::collections::HashMap;
+
+/// Series enriched per source in one pass
+pub const ENRICHMENT_BATCH_SIZE: i64 = 200;
+
+/// Enriched series are refreshed after this many days
+pub const ENRICHMENT_REFRESH_DAYS: i64 = 30;
+
+/// Series enriched and failed in one pass
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnrichmentSummary {
+    pub enriched: usize,
+    pub failed: usize,
+}
+
+/// Enrich FRED series with their categories and tags
+pub async fn enrich_fred_series(
+    client: &Client,
+    fred_api_key: &Option<String>,
+    pool: &DatabasePool,
+) -> AppResult<EnrichmentSummary> {
+    let api_key = fred_api_key
+        .as_ref()
+        .ok_or_else(|| AppError::ExternalApiError("FRED API key not configured".to_string()))?;
+
+    let fred_source = DataSource::get_or_create(pool, DataSource::fred()).await?;
+    let mut summary = EnrichmentSummary::default();
+
+    for metadata in due_for_enrichment(pool, &fred_source).await? {
+        match fred::fetch_fred_enrichment(client, api_key, &metadata.external_id).await {
+            Ok(enrichment) => {
+                SeriesMetadata::apply_enrichment(pool, metadata.id, &enrichment).await?;
+                summary.enriched += 1;
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to enrich FRED series {}: {}",
+                    metadata.external_id, e
+                );
+                summary.failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Enriched {} FRED series ({} failed)",
+        summary.enriched, summary.failed
+    );
+    Ok(summary)
+}
+
+/// Enrich BLS series with their survey and area
+pub async fn enrich_bls_series(
+    client: &Client,
+    bls_api_key: &Option<String>,
+    pool: &DatabasePool,
+) -> AppResult<EnrichmentSummary> {
+    let api_key = bls_api_key
+        .as_ref()
+        .ok_or_else(|| AppError::ExternalApiError("BLS API key not configured".to_string()))?;
+
+    let bls_source = DataSource::get_or_create(pool, DataSource::bls()).await?;
+    let survey_names: HashMap<String, String> = bls::fetch_bls_surveys(client, api_key)
+        .await?
+        .into_iter()
+        .map(|survey| (survey.survey_abbreviation, survey.survey_name))
+        .collect();
+    let mut summary = EnrichmentSummary::default();
+
+    for metadata in due_for_enrichment(pool, &bls_source).await? {
+        let enrichment = bls::bls_enrichment(&metadata.external_id, &survey_names);
+        SeriesMetadata::apply_enrichment(pool, metadata.id, &enrichment).await?;
+        summary.enriched += 1;
+    }
+
+    println!("Enriched {} BLS series", summary.enriched);
+    Ok(summary)
+}
+
+async fn due_for_enrichment(
+    pool: &DatabasePool,
+    source: &DataSource,
+) -> AppResult<Vec<SeriesMetadata>> {
+    SeriesMetadata::due_for_enrichment(
+        pool,
+        source.id,
+        Utc::now() - Duration::days(ENRICHMENT_REFRESH_DAYS),
+        ENRICHMENT_BATCH_SIZE,
+    )
+    .await
+}
@@ -0,0 +1,966 @@
+//! Eurostat dissemination API integration for series discovery
+//!
+//! Data is read from the Eurostat statistics API, which returns JSON-stat 2.0
+//! datasets: a cube of `id`/`size` dimensions whose values are stored in one
+//! row-major array (or a sparse object keyed by position). The cube is
+//! flattened into one series per combination of non-time dimension codes.
+//!
+//! Labels are requested in the language given by `EUROSTAT_LANGUAGE`
+//! (English, French or German; English by default). External ids are built
+//! from codes only, so changing the language never creates new series.
+//!
+//! Crawls are incremental: a one-period probe of each dataset returns its
+//! `updated` timestamp, datasets that have not changed since the last crawl
+//! are skipped, and changed ones are fetched from the last stored period on.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use econ_graph_core::database::DatabasePool;
+use econ_graph_core::error::{AppError, AppResult};
+use econ_graph_core::models::{
+    DataSource, EconomicSeries, NewEconomicSeries, NewSeriesMetadata, SeriesMetadata,
+    UpdateEconomicSeries,
+};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, EUROSTAT_HOST};
+use econ_graph_metrics::crawler::CRAWLER_METRICS;
+use reqwest::Client;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::imf::{frequency_name, parse_time_period};
+use crate::services::crawler::{CrawlOrigin, IngestionConfig, IngestionPipeline, RawObservation};
+
+/// Eurostat statistics API base URL
+const EUROSTAT_API_URL: &str = "https://ec.europa.eu/eurostat/api/dissemination/statistics/1.0";
+
+/// Environment variable selecting the label language
+pub const EUROSTAT_LANGUAGE_ENV: &str = "EUROSTAT_LANGUAGE";
+
+/// Languages the Eurostat API publishes labels in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EurostatLanguage {
+    #[default]
+    English,
+    French,
+    German,
+}
+
+impl EurostatLanguage {
+    /// Parse a language code such as `en` or `DE`
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_ascii_uppercase().as_str() {
+            "EN" => Some(Self::English),
+            "FR" => Some(Self::French),
+            "DE" => Some(Self::German),
+            _ => None,
+        }
+    }
+
+    /// Language from `EUROSTAT_LANGUAGE`, falling back to English
+    pub fn from_env() -> Self {
+        match std::env::var(EUROSTAT_LANGUAGE_ENV) {
+            Ok(code) => Self::from_code(&code).unwrap_or_else(|| {
+                println!(
+                    "Unsupported {} '{}', using English labels",
+                    EUROSTAT_LANGUAGE_ENV, code
+                );
+                Self::English
+            }),
+            Err(_) => Self::English,
+        }
+    }
+
+    /// Value of the `lang` query parameter
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::English => "EN",
+            Self::French => "FR",
+            Self::German => "DE",
+        }
+    }
+}
+
+/// Series requested from one Eurostat dataset
+#[derive(Debug, Clone, Copy)]
+pub struct EurostatDatasetSelection {
+    pub dataset: &'static str,
+    pub name: &'static str,
+    /// Codes requested per dimension id; dimensions not listed are returned in full
+    pub filters: &'static [(&'static str, &'static [&'static str])],
+    /// First period requested on a full load, e.g. "2000-01"
+    pub start_period: &'static str,
+}
+
+/// Reporting areas requested from every dataset
+const EUROPEAN_ECONOMIES: &[&str] = &[
+    "EU27_2020",
+    "EA20",
+    "DE",
+    "FR",
+    "IT",
+    "ES",
+    "NL",
+    "BE",
+    "AT",
+    "PL",
+    "SE",
+    "IE",
+];
+
+/// Datasets and series collected from Eurostat
+pub const EUROSTAT_DATASETS: &[EurostatDatasetSelection] = &[
+    EurostatDatasetSelection {
+        dataset: "prc_hicp_manr",
+        name: "HICP annual rate of change",
+        filters: &[
+            ("freq", &["M"]),
+            ("unit", &["RCH_A"]),
+            ("coicop", &["CP00"]),
+            ("geo", EUROPEAN_ECONOMIES),
+        ],
+        start_period: "2000-01",
+    },
+    EurostatDatasetSelection {
+        dataset: "une_rt_m",
+        name: "Unemployment rate",
+        filters: &[
+            ("freq", &["M"]),
+            ("s_adj", &["SA"]),
+            ("age", &["TOTAL"]),
+            ("unit", &["PC_ACT"]),
+            ("sex", &["T"]),
+            ("geo", EUROPEAN_ECONOMIES),
+        ],
+        start_period: "2000-01",
+    },
+    EurostatDatasetSelection {
+        dataset: "namq_10_gdp",
+        name: "Real GDP growth",
+        filters: &[
+            ("freq", &["Q"]),
+            ("unit", &["CLV_PCH_PRE"]),
+            ("s_adj", &["SCA"]),
+            ("na_item", &["B1GQ"]),
+            ("geo", EUROPEAN_ECONOMIES),
+        ],
+        start_period: "2000-Q1",
+    },
+    EurostatDatasetSelection {
+        dataset: "gov_10dd_edpt1",
+        name: "Government gross debt",
+        filters: &[
+            ("freq", &["A"]),
+            ("unit", &["PC_GDP"]),
+            ("sector", &["S13"]),
+            ("na_item", &["GD"]),
+            ("geo", EUROPEAN_ECONOMIES),
+        ],
+        start_period: "2000",
+    },
+];
+
+/// JSON-stat 2.0 dataset response
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonStatDataset {
+    pub label: Option<String>,
+    /// Last update of the dataset, RFC 3339
+    pub updated: Option<String>,
+    /// Dimension ids in value order
+    pub id: Vec<String>,
+    /// Number of categories per dimension, in `id` order
+    pub size: Vec<usize>,
+    pub dimension: HashMap<String, JsonStatDimension>,
+    #[serde(default)]
+    pub value: JsonStatValues,
+    pub role: Option<JsonStatRole>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonStatDimension {
+    pub label: Option<String>,
+    pub category: JsonStatCategory,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonStatCategory {
+    pub index: Option<JsonStatIndex>,
+    #[serde(default)]
+    pub label: HashMap<String, String>,
+}
+
+/// Category positions: `{"DE": 0, "FR": 1}` or `["DE", "FR"]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum JsonStatIndex {
+    Positions(HashMap<String, usize>),
+    Codes(Vec<String>),
+}
+
+/// Values as a dense array or a sparse object keyed by position
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum JsonStatValues {
+    Dense(Vec<Option<f64>>),
+    Sparse(HashMap<String, Option<f64>>),
+}
+
+impl Default for JsonStatValues {
+    fn default() -> Self {
+        Self::Sparse(HashMap::new())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JsonStatRole {
+    #[serde(default)]
+    pub time: Vec<String>,
+    #[serde(default)]
+    pub geo: Vec<String>,
+}
+
+impl JsonStatCategory {
+    /// Category codes in position order
+    pub fn codes(&self) -> Vec<String> {
+        match &self.index {
+            Some(JsonStatIndex::Codes(codes)) => codes.clone(),
+            Some(JsonStatIndex::Positions(positions)) => {
+                let mut codes: Vec<(&String, &usize)> = positions.iter().collect();
+                codes.sort_by_key(|(_, position)| **position);
+                codes.into_iter().map(|(code, _)| code.clone()).collect()
+            }
+            // A dimension with a single category may omit the index
+            None => {
+                let mut codes: Vec<String> = self.label.keys().cloned().collect();
+                codes.sort();
+                codes
+            }
+        }
+    }
+}
+
+impl JsonStatDataset {
+    /// Id of the time dimension
+    pub fn time_dimension(&self) -> Option<&str> {
+        self.role
+            .as_ref()
+            .and_then(|role| role.time.first())
+            .map(String::as_str)
+            .or_else(|| {
+                self.id
+                    .iter()
+                    .find(|id| id.eq_ignore_ascii_case("time"))
+                    .map(String::as_str)
+            })
+    }
+
+    /// Label of a category in the response language, falling back to the code
+    pub fn category_label(&self, dimension_id: &str, code: &str) -> String {
+        self.dimension
+            .get(dimension_id)
+            .and_then(|dimension| dimension.category.label.get(code))
+            .filter(|label| !label.trim().is_empty())
+            .cloned()
+            .unwrap_or_else(|| code.to_string())
+    }
+
+    /// When the dataset was last updated
+    ///
+    /// Eurostat writes offsets without a colon (`+0200`), which RFC 3339
+    /// parsing rejects, so both forms are accepted.
+    pub fn updated_at(&self) -> Option<DateTime<Utc>> {
+        let updated = self.updated.as_deref()?;
+        DateTime::parse_from_rfc3339(updated)
+            .or_else(|_| DateTime::parse_from_str(updated, "%Y-%m-%dT%H:%M:%S%z"))
+            .ok()
+            .map(|updated| updated.with_timezone(&Utc))
+    }
+}
+
+/// One series of a flattened dataset
+#[derive(Debug, Clone, PartialEq)]
+pub struct EurostatSeries {
+    /// Non-time dimension codes in dataset order, e.g. `[("freq", "M"), ("geo", "DE")]`
+    pub key: Vec<(String, String)>,
+    /// (period, value) pairs in period order, e.g. `("2024-03", 2.4)`
+    pub observations: Vec<(String, f64)>,
+}
+
+impl EurostatSeries {
+    /// Series key, e.g. `M.SA.TOTAL.PC_ACT.T.DE`
+    pub fn key_string(&self) -> String {
+        self.key
+            .iter()
+            .map(|(_, code)| code.as_str())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Code of a dimension, e.g. `geo`
+    pub fn code(&self, dimension_id: &str) -> Option<&str> {
+        self.key
+            .iter()
+            .find(|(id, _)| id == dimension_id)
+            .map(|(_, code)| code.as_str())
+    }
+}
+
+/// Split a JSON-stat cube into one series per non-time category combination
+///
+/// Values are addressed row-major: the last dimension in `id` varies
+/// fastest. Series without any value are left out.
+pub fn flatten_dataset(dataset: &JsonStatDataset) -> AppResult<Vec<EurostatSeries>> {
+    if dataset.id.len() != dataset.size.len() {
+        return Err(AppError::ExternalApiError(format!(
+            "JSON-stat dataset has {} dimension ids but {} sizes",
+            dataset.id.len(),
+            dataset.size.len()
+        )));
+    }
+    let time_dimension = dataset.time_dimension().ok_or_else(|| {
+        AppError::ExternalApiError("JSON-stat dataset has no time dimension".to_string())
+    })?;
+    let time_position = dataset
+        .id
+        .iter()
+        .position(|id| id == time_dimension)
+        .ok_or_else(|| {
+            AppError::ExternalApiError(format!(
+                "JSON-stat time dimension {} is not in the dataset ids",
+                time_dimension
+            ))
+        })?;
+
+    let codes = dataset
+        .id
+        .iter()
+        .zip(&dataset.size)
+        .map(|(id, size)| {
+            let codes = dataset
+                .dimension
+                .get(id)
+                .map(|dimension| dimension.category.codes())
+                .unwrap_or_default();
+            if codes.len() != *size {
+                return Err(AppError::ExternalApiError(format!(
+                    "JSON-stat dimension {} has {} categories but size {}",
+                    id,
+                    codes.len(),
+                    size
+                )));
+            }
+            Ok(codes)
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let mut strides = vec![1usize; dataset.size.len()];
+    for i in (0..dataset.size.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * dataset.size[i + 1];
+    }
+    let total: usize = dataset.size.iter().product();
+
+    let values: Vec<(usize, f64)> = match &dataset.value {
+        JsonStatValues::Dense(values) => values
+            .iter()
+            .enumerate()
+            .filter_map(|(position, value)| Some((position, (*value)?)))
+            .collect(),
+        JsonStatValues::Sparse(values) => values
+            .iter()
+            .filter_map(|(position, value)| Some((position.parse().ok()?, (*value)?)))
+            .collect(),
+    };
+
+    // Observations keyed by the category positions of the non-time dimensions
+    let mut series: BTreeMap<Vec<usize>, Vec<(usize, f64)>> = BTreeMap::new();
+    for (position, value) in values {
+        if position >= total {
+            continue;
+        }
+        let coordinates: Vec<usize> = strides
+            .iter()
+            .zip(&dataset.size)
+            .map(|(stride, size)| position / stride % size)
+            .collect();
+        let time_index = coordinates[time_position];
+        let key: Vec<usize> = coordinates
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != time_position)
+            .map(|(_, coordinate)| *coordinate)
+            .collect();
+        series.entry(key).or_default().push((time_index, value));
+    }
+
+    Ok(series
+        .into_iter()
+        .map(|(key, mut observations)| {
+            observations.sort_by_key(|(time_index, _)| *time_index);
+            let key = dataset
+                .id
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != time_position)
+                .zip(key)
+                .map(|((i, id), coordinate)| (id.clone(), codes[i][coordinate].clone()))
+                .collect();
+            EurostatSeries {
+                key,
+                observations: observations
+                    .into_iter()
+                    .map(|(time_index, value)| (codes[time_position][time_index].clone(), value))
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+/// Eurostat period code in SDMX form: `2024M03` becomes `2024-03`, `2024Q1` becomes `2024-Q1`
+pub fn normalize_period(period: &str) -> String {
+    match period.get(4..5) {
+        Some("M") => format!("{}-{}", &period[..4], &period[5..]),
+        Some("Q") => format!("{}-{}", &period[..4], &period[4..]),
+        _ => period.to_string(),
+    }
+}
+
+/// Period containing `date` at a frequency, as used by `sinceTimePeriod`
+pub fn period_for_date(date: NaiveDate, frequency_code: &str) -> String {
+    use chrono::Datelike;
+
+    match frequency_code {
+        "A" => date.format("%Y").to_string(),
+        "Q" => format!("{}-Q{}", date.year(), (date.month() - 1) / 3 + 1),
+        "M" => date.format("%Y-%m").to_string(),
+        _ => date.format("%Y-%m-%d").to_string(),
+    }
+}
+
+/// What a stored series needs to know about the previous crawl
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredSeriesState {
+    pub last_updated: Option<DateTime<Utc>>,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// How much of a dataset to fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EurostatUpdatePlan {
+    /// Nothing changed since every series was last crawled
+    Unchanged,
+    /// Fetch periods from the one containing this date on
+    Since(NaiveDate),
+    /// Fetch the whole history from the selection's start period
+    Full,
+}
+
+/// Decide how to refresh a dataset from its update time and the stored series
+///
+/// `stored` has one entry per series in the probe, `None` for series not
+/// stored yet. Any new series or series without data forces a full load.
+pub fn plan_update(
+    dataset_updated: Option<DateTime<Utc>>,
+    stored: &[Option<StoredSeriesState>],
+) -> EurostatUpdatePlan {
+    let Some(states) = stored.iter().copied().collect::<Option<Vec<_>>>() else {
+        return EurostatUpdatePlan::Full;
+    };
+    let Some(since) = states
+        .iter()
+        .map(|state| state.end_date)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|end_dates| end_dates.into_iter().min())
+    else {
+        return EurostatUpdatePlan::Full;
+    };
+
+    let up_to_date = dataset_updated.is_some_and(|updated| {
+        states
+            .iter()
+            .all(|state| state.last_updated.is_some_and(|crawled| crawled >= updated))
+    });
+    if up_to_date {
+        EurostatUpdatePlan::Unchanged
+    } else {
+        EurostatUpdatePlan::Since(since)
+    }
+}
+
+/// Discover Eurostat series and load their observations
+///
+/// Returns the external ids (`{dataset}.{key}`) of the series stored or
+/// found up to date.
+pub async fn discover_eurostat_series(
+    client: &Client,
+    pool: &DatabasePool,
+) -> AppResult<Vec<String>> {
+    let eurostat_source = DataSource::get_or_create(pool, DataSource::eurostat()).await?;
+    let language = EurostatLanguage::from_env();
+    let mut discovered_series = Vec::new();
+
+    for selection in EUROSTAT_DATASETS {
+        match discover_dataset(client, pool, &eurostat_source.id, selection, language).await {
+            Ok(series) => {
+                println!(
+                    "Processed {} series from Eurostat {} ({})",
+                    series.len(),
+                    selection.name,
+                    selection.dataset
+                );
+                discovered_series.extend(series);
+            }
+            Err(e) => {
+                println!("Eurostat dataset {} failed: {}", selection.dataset, e);
+                CRAWLER_METRICS.record_error("economic", "eurostat", "dataset_error");
+            }
+        }
+    }
+
+    println!(
+        "Discovered {} Eurostat series total",
+        discovered_series.len()
+    );
+    Ok(discovered_series)
+}
+
+/// Probe, then fetch, store and ingest the selected series of one dataset
+async fn discover_dataset(
+    client: &Client,
+    pool: &DatabasePool,
+    source_id: &Uuid,
+    selection: &EurostatDatasetSelection,
+    language: EurostatLanguage,
+) -> AppResult<Vec<String>> {
+    let probe = fetch_dataset(client, selection, language, &[("lastTimePeriod", "1")]).await?;
+    let probe_series = flatten_dataset(&probe)?;
+
+    let mut stored_states = Vec::with_capacity(probe_series.len());
+    for series in &probe_series {
+        let external_id = format!("{}.{}", selection.dataset, series.key_string());
+        let state = EconomicSeries::find_by_external_id(pool, &external_id, *source_id)
+            .await
+            .ok()
+            .map(|stored| StoredSeriesState {
+                last_updated: stored.last_updated,
+                end_date: stored.end_date,
+            });
+        stored_states.push(state);
+    }
+
+    let frequency_code = selection
+        .filters
+        .iter()
+        .find(|(id, _)| *id == "freq")
+        .and_then(|(_, codes)| codes.first())
+        .copied()
+        .unwrap_or("A");
+    let since_period = match plan_update(probe.updated_at(), &stored_states) {
+        EurostatUpdatePlan::Unchanged => {
+            println!(
+                "Eurostat {} unchanged since last crawl, skipping",
+                selection.dataset
+            );
+            return Ok(probe_series
+                .iter()
+                .map(|series| format!("{}.{}", selection.dataset, series.key_string()))
+                .collect());
+        }
+        EurostatUpdatePlan::Since(date) => period_for_date(date, frequency_code),
+        EurostatUpdatePlan::Full => selection.start_period.to_string(),
+    };
+
+    let dataset = fetch_dataset(
+        client,
+        selection,
+        language,
+        &[("sinceTimePeriod", since_period.as_str())],
+    )
+    .await?;
+    let series_list = flatten_dataset(&dataset)?;
+
+    let mut stored = Vec::new();
+    let mut observations = 0u64;
+    for series in &series_list {
+        match store_eurostat_series(pool, source_id, selection, &dataset, series).await {
+            Ok((external_id, stored_points)) => {
+                stored.push(external_id);
+                observations += stored_points as u64;
+            }
+            Err(e) => {
+                println!(
+                    "Failed to store Eurostat series {}.{}: {}",
+                    selection.dataset,
+                    series.key_string(),
+                    e
+                );
+                CRAWLER_METRICS.record_error("economic", "eurostat", "storage_error");
+            }
+        }
+    }
+
+    CRAWLER_METRICS.record_items_collected("economic", "eurostat", "series", stored.len() as u64);
+    CRAWLER_METRICS.record_items_collected("economic", "eurostat", "observations", observations);
+
+    Ok(stored)
+}
+
+/// Build the query string for a dataset request
+pub fn build_query(
+    selection: &EurostatDatasetSelection,
+    language: EurostatLanguage,
+    extra: &[(&str, &str)],
+) -> String {
+    let mut params = vec![
+        "format=JSON".to_string(),
+        format!("lang={}", language.code()),
+    ];
+    for (dimension, codes) in selection.filters {
+        for code in *codes {
+            params.push(format!("{}={}", dimension, code));
+        }
+    }
+    for (name, value) in extra {
+        params.push(format!("{}={}", name, value));
+    }
+    params.join("&")
+}
+
+/// GET a dataset, rate limited and recorded in the crawler metrics
+async fn fetch_dataset(
+    client: &Client,
+    selection: &EurostatDatasetSelection,
+    language: EurostatLanguage,
+    extra: &[(&str, &str)],
+) -> AppResult<JsonStatDataset> {
+    let url = format!(
+        "{}/data/{}?{}",
+        EUROSTAT_API_URL,
+        selection.dataset,
+        build_query(selection, language, extra)
+    );
+
+    shared_rate_limiter().acquire(EUROSTAT_HOST, None).await;
+    let start = std::time::Instant::now();
+    let response = client.get(&url).send().await.map_err(|e| {
+        CRAWLER_METRICS.record_error("economic", "eurostat", "network");
+        AppError::ExternalApiError(format!(
+            "Eurostat request for {} failed: {}",
+            selection.dataset, e
+        ))
+    })?;
+    let duration = start.elapsed().as_secs_f64();
+    let status = response.status();
+    CRAWLER_METRICS.record_request(
+        "economic",
+        "eurostat",
+        "/data/{dataset}",
+        status.as_str(),
+        duration,
+    );
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        CRAWLER_METRICS.record_rate_limit_hit("economic", "eurostat");
+    }
+
+    if !status.is_success() {
+        CRAWLER_METRICS.record_error("economic", "eurostat", "http_error");
+        return Err(AppError::ExternalApiError(format!(
+            "Eurostat API returned status {} for {}",
+            status, selection.dataset
+        )));
+    }
+
+    response.json().await.map_err(|e| {
+        CRAWLER_METRICS.record_error("economic", "eurostat", "parse_error");
+        AppError::ExternalApiError(format!(
+            "Failed to parse Eurostat response for {}: {}",
+            selection.dataset, e
+        ))
+    })
+}
+
+/// Store a Eurostat series, its metadata and its observations
+///
+/// Returns the external id and the number of data points written. The
+/// series' `last_updated` is set to now, which the next crawl compares
+/// against the dataset's update time.
+async fn store_eurostat_series(
+    pool: &DatabasePool,
+    source_id: &Uuid,
+    selection: &EurostatDatasetSelection,
+    dataset: &JsonStatDataset,
+    series: &EurostatSeries,
+) -> AppResult<(String, usize)> {
+    let key = series.key_string();
+    let external_id = format!("{}.{}", selection.dataset, key);
+    let frequency = series
+        .code("freq")
+        .and_then(frequency_name)
+        .unwrap_or("Unknown")
+        .to_string();
+    let label = |dimension_id: &str| {
+        series
+            .code(dimension_id)
+            .map(|code| dataset.category_label(dimension_id, code))
+    };
+
+    // e.g. "Unemployment rate, Germany (Seasonally adjusted data, Total, Total)"
+    let details: Vec<String> = series
+        .key
+        .iter()
+        .filter(|(id, _)| !matches!(id.as_str(), "freq" | "geo" | "unit"))
+        .map(|(id, code)| dataset.category_label(id, code))
+        .collect();
+    let mut title = selection.name.to_string();
+    if let Some(geo) = label("geo") {
+        title = format!("{}, {}", title, geo);
+    }
+    if !details.is_empty() {
+        title = format!("{} ({})", title, details.join(", "));
+    }
+    let title: String = title.chars().take(500).collect();
+    let units = label("unit");
+    let seasonal_adjustment = label("s_adj");
+    let geographic_level = match series.code("geo") {
+        Some(code) if code.starts_with("EU") || code.starts_with("EA") => "Economic area",
+        _ => "Country",
+    };
+
+    let observations: Vec<RawObservation> = series
+        .observations
+        .iter()
+        .filter_map(|(period, value)| {
+            Some(RawObservation {
+                date: parse_time_period(&normalize_period(period))?
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                value: Some(value.to_string()),
+                unit: None,
+            })
+        })
+        .collect();
+    let dates: Vec<NaiveDate> = observations
+        .iter()
+        .filter_map(|o| NaiveDate::parse_from_str(&o.date, "%Y-%m-%d").ok())
+        .collect();
+    let start_date = dates.iter().min().copied();
+    let end_date = dates.iter().max().copied();
+
+    let new_series = NewEconomicSeries {
+        source_id: *source_id,
+        external_id: external_id.clone(),
+        title: title.clone(),
+        description: Some(format!(
+            "{} from Eurostat dataset {}",
+            title, selection.dataset
+        )),
+        units: units.clone(),
+        frequency: frequency.clone(),
+        seasonal_adjustment: seasonal_adjustment.clone(),
+        start_date,
+        end_date,
+        is_active: true,
+        first_discovered_at: Some(Utc::now()),
+        last_crawled_at: None,
+        first_missing_date: None,
+        crawl_status: None,
+        crawl_error_message: None,
+    };
+    let economic_series =
+        EconomicSeries::get_or_create(pool, &external_id, *source_id, &new_series).await?;
+
+    let metadata = SeriesMetadata::get_or_create(
+        pool,
+        *source_id,
+        &external_id,
+        &NewSeriesMetadata {
+            source_id: *source_id,
+            external_id: external_id.clone(),
+            title: title.clone(),
+            description: Some(format!(
+                "{} ({}) series {}",
+                dataset.label.as_deref().unwrap_or(selection.name),
+                selection.dataset,
+                key
+            )),
+            units,
+            frequency: Some(frequency),
+            geographic_level: Some(geographic_level.to_string()),
+            data_url: Some(format!(
+                "https://ec.europa.eu/eurostat/databrowser/view/{}/default/table",
+                selection.dataset
+            )),
+            api_endpoint: Some(format!("{}/data/{}", EUROSTAT_API_URL, selection.dataset)),
+            is_active: true,
+        },
+    )
+    .await?;
+
+    let pipeline = IngestionPipeline::new(IngestionConfig::default()).with_origin(CrawlOrigin {
+        source_url: metadata.api_endpoint.clone(),
+        ..Default::default()
+    });
+    let report = pipeline
+        .ingest(pool, "eurostat", economic_series.id, &observations)
+        .await?;
+
+    // Incremental fetches only cover recent periods, so widen the stored range
+    let start_date = match (economic_series.start_date, start_date) {
+        (Some(stored), Some(fetched)) => Some(stored.min(fetched)),
+        (stored, fetched) => stored.or(fetched),
+    };
+    let end_date = match (economic_series.end_date, end_date) {
+        (Some(stored), Some(fetched)) => Some(stored.max(fetched)),
+        (stored, fetched) => stored.or(fetched),
+    };
+    EconomicSeries::update(
+        pool,
+        economic_series.id,
+        &UpdateEconomicSeries {
+            start_date,
+            end_date,
+            last_updated: Some(Utc::now()),
+            updated_at: Utc::now(),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok((external_id, report.stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn sample_dataset(value: serde_json::Value) -> JsonStatDataset {
+        serde_json::from_value(json!({
+            "version": "2.0",
+            "class": "dataset",
+            "label": "Unemployment by sex and age - monthly data",
+            "updated": "2024-04-30T23:00:00+0200",
+            "id": ["freq", "geo", "time"],
+            "size": [1, 2, 3],
+            "dimension": {
+                "freq": { "category": { "label": { "M": "Monthly" } } },
+                "geo": { "category": {
+                    "index": { "DE": 0, "FR": 1 },
+                    "label": { "DE": "Deutschland", "FR": "" }
+                } },
+                "time": { "category": { "index": ["2024-01", "2024-02", "2024-03"] } }
+            },
+            "value": value,
+            "role": { "time": ["time"], "geo": ["geo"] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_flatten_dataset_dense_and_sparse_values() {
+        // REQUIREMENT: Eurostat JSON-stat cubes are split into individual series
+        // PURPOSE: Verify row-major positions map to the right country and period for both value layouts
+        // This ensures observations are never attached to the wrong series
+
+        let dense = sample_dataset(json!([3.1, 3.2, null, 7.5, null, 7.3]));
+        let series = flatten_dataset(&dense).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].key_string(), "M.DE");
+        assert_eq!(
+            series[0].observations,
+            vec![("2024-01".to_string(), 3.1), ("2024-02".to_string(), 3.2)]
+        );
+        assert_eq!(series[1].code("geo"), Some("FR"));
+        assert_eq!(
+            series[1].observations,
+            vec![("2024-01".to_string(), 7.5), ("2024-03".to_string(), 7.3)]
+        );
+
+        let sparse = sample_dataset(json!({ "5": 7.3, "0": 3.1, "3": 7.5, "1": 3.2 }));
+        assert_eq!(flatten_dataset(&sparse).unwrap(), series);
+
+        let mut mismatched = sample_dataset(json!([]));
+        mismatched.size = vec![1, 3, 3];
+        assert!(flatten_dataset(&mismatched).is_err());
+    }
+
+    #[test]
+    fn test_labels_and_language_codes() {
+        // REQUIREMENT: Eurostat labels follow the configured language without affecting series ids
+        // PURPOSE: Verify language codes parse case-insensitively and missing labels fall back to codes
+        // This keeps titles readable when a translation is missing
+
+        let dataset = sample_dataset(json!([]));
+        assert_eq!(dataset.category_label("geo", "DE"), "Deutschland");
+        assert_eq!(dataset.category_label("geo", "FR"), "FR");
+        assert_eq!(dataset.category_label("freq", "M"), "Monthly");
+        assert_eq!(
+            dataset.updated_at(),
+            Some(Utc.with_ymd_and_hms(2024, 4, 30, 21, 0, 0).unwrap())
+        );
+
+        assert_eq!(
+            EurostatLanguage::from_code("de"),
+            Some(EurostatLanguage::German)
+        );
+        assert_eq!(
+            EurostatLanguage::from_code(" FR "),
+            Some(EurostatLanguage::French)
+        );
+        assert_eq!(EurostatLanguage::from_code("es"), None);
+        assert_eq!(EurostatLanguage::default().code(), "EN");
+
+        let query = build_query(
+            &EUROSTAT_DATASETS[1],
+            EurostatLanguage::German,
+            &[("sinceTimePeriod", "2024-01")],
+        );
+        assert!(query.starts_with("format=JSON&lang=DE&freq=M&"));
+        assert!(query.contains("&geo=DE&geo=FR&"));
+        assert!(query.ends_with("&sinceTimePeriod=2024-01"));
+    }
+
+    #[test]
+    fn test_incremental_update_plan() {
+        // REQUIREMENT: Eurostat crawls only fetch what changed since the last crawl
+        // PURPOSE: Verify unchanged datasets are skipped and changed ones resume from the oldest stored period
+        // This avoids downloading full histories every day
+
+        let updated = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+        let crawled_after = StoredSeriesState {
+            last_updated: Some(Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap()),
+            end_date: NaiveDate::from_ymd_opt(2024, 3, 31),
+        };
+        let crawled_before = StoredSeriesState {
+            last_updated: Some(Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap()),
+            end_date: NaiveDate::from_ymd_opt(2024, 2, 29),
+        };
+
+        assert_eq!(
+            plan_update(Some(updated), &[Some(crawled_after)]),
+            EurostatUpdatePlan::Unchanged
+        );
+        assert_eq!(
+            plan_update(Some(updated), &[Some(crawled_after), Some(crawled_before)]),
+            EurostatUpdatePlan::Since(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+        );
+        assert_eq!(
+            plan_update(None, &[Some(crawled_after)]),
+            EurostatUpdatePlan::Since(NaiveDate::from_ymd_opt(2024, 3, 31).unwrap())
+        );
+        assert_eq!(
+            plan_update(Some(updated), &[Some(crawled_after), None]),
+            EurostatUpdatePlan::Full
+        );
+        assert_eq!(plan_update(Some(updated), &[]), EurostatUpdatePlan::Full);
+
+        let date = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+        assert_eq!(period_for_date(date, "A"), "2024");
+        assert_eq!(period_for_date(date, "Q"), "2024-Q2");
+        assert_eq!(period_for_date(date, "M"), "2024-05");
+        assert_eq!(normalize_period("2024M05"), "2024-05");
+        assert_eq!(normalize_period("2024Q2"), "2024-Q2");
+        assert_eq!(normalize_period("2024-05"), "2024-05");
+    }
+}
@@ -2,7 +2,8 @@
 
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
-use econ_graph_core::models::DataSource;
+use econ_graph_core::models::{DataSource, SeriesEnrichment};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, FRED_HOST};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
 use reqwest::Client;
 use serde::Deserialize;
@@ -42,6 +43,32 @@ pub struct FredSeriesInfo {
     pub notes: Option<String>,
 }
 
+/// FRED API response for the categories of a series
+#[derive(Debug, Deserialize)]
+pub struct FredCategoriesResponse {
+    pub categories: Vec<FredCategory>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FredCategory {
+    pub id: i32,
+    pub name: String,
+    pub parent_id: i32,
+}
+
+/// FRED API response for the tags of a series
+#[derive(Debug, Deserialize)]
+pub struct FredTagsResponse {
+    pub tags: Vec<FredTag>,
+}
+
+/// FRED tag; `group_id` is e.g. `geo` (area), `geot` (area type) or `gen` (concept)
+#[derive(Debug, Clone, Deserialize)]
+pub struct FredTag {
+    pub name: String,
+    pub group_id: String,
+}
+
 /// Discover all FRED series by searching through categories
 pub async fn discover_fred_series(
     client: &Client,
@@ -96,6 +123,9 @@ pub async fn discover_fred_series(
             term, api_key
         );
 
+        shared_rate_limiter()
+            .acquire(FRED_HOST, Some(api_key.as_str()))
+            .await;
         let start = std::time::Instant::now();
         let response = client.get(&url).send().await.map_err(|e| {
             AppError::ExternalApiError(format!("FRED search request failed: {}", e))
@@ -150,7 +180,8 @@ async fn store_fred_series(
         description: series_info.notes.clone(),
         units: Some(series_info.units.clone()),
         frequency: Some(series_info.frequency.clone()),
-        geographic_level: Some("United States".to_string()),
+        // Set by the enrichment pass from the series' geography tags
+        geographic_level: None,
         data_url: Some(format!(
             "https://fred.stlouisfed.org/series/{}",
             series_info.id
@@ -172,6 +203,120 @@ async fn store_fred_series(
     Ok(())
 }
 
+/// Fetch the categories and tags of a FRED series
+pub async fn fetch_fred_enrichment(
+    client: &Client,
+    api_key: &str,
+    series_id: &str,
+) -> AppResult<SeriesEnrichment> {
+    let categories: FredCategoriesResponse = fetch_fred_catalog(
+        client,
+        api_key,
+        "/fred/series/categories",
+        &format!(
+            "https://api.stlouisfed.org/fred/series/categories?series_id={}&api_key={}&file_type=json",
+            series_id, api_key
+        ),
+    )
+    .await?;
+    let tags: FredTagsResponse = fetch_fred_catalog(
+        client,
+        api_key,
+        "/fred/series/tags",
+        &format!(
+            "https://api.stlouisfed.org/fred/series/tags?series_id={}&api_key={}&file_type=json",
+            series_id, api_key
+        ),
+    )
+    .await?;
+
+    Ok(fred_enrichment(&categories.categories, &tags.tags))
+}
+
+async fn fetch_fred_catalog<T: serde::de::DeserializeOwned>(
+    client: &Client,
+    api_key: &str,
+    endpoint: &str,
+    url: &str,
+) -> AppResult<T> {
+    shared_rate_limiter()
+        .acquire(FRED_HOST, Some(api_key))
+        .await;
+    let start = std::time::Instant::now();
+    let response = client.get(url).send().await.map_err(|e| {
+        AppError::ExternalApiError(format!("FRED {} request failed: {}", endpoint, e))
+    })?;
+    let status = response.status();
+    CRAWLER_METRICS.record_request(
+        "economic",
+        "fred",
+        endpoint,
+        status.as_str(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    if !status.is_success() {
+        if status.as_u16() == 429 {
+            CRAWLER_METRICS.record_rate_limit_hit("economic", "fred");
+        }
+        CRAWLER_METRICS.record_error("economic", "fred", "http_error");
+        return Err(AppError::ExternalApiError(format!(
+            "FRED {} request failed with status: {}",
+            endpoint, status
+        )));
+    }
+
+    response.json().await.map_err(|e| {
+        AppError::ExternalApiError(format!("Failed to parse FRED {} response: {}", endpoint, e))
+    })
+}
+
+/// Enrichment from a FRED series' categories and tags
+///
+/// The `geot` tag gives the kind of area and the `geo` tag the area itself;
+/// concept (`gen`) tags become the series' topic tags.
+pub fn fred_enrichment(categories: &[FredCategory], tags: &[FredTag]) -> SeriesEnrichment {
+    let tags_in = |group: &str| {
+        tags.iter()
+            .filter(move |tag| tag.group_id == group)
+            .map(|tag| tag.name.as_str())
+    };
+
+    let geographic_level = tags_in("geot").find_map(|name| match name {
+        "nation" => Some("National"),
+        "region" | "census region" => Some("Region"),
+        "state" => Some("State"),
+        "msa" => Some("MSA"),
+        "county" => Some("County"),
+        _ => None,
+    });
+
+    SeriesEnrichment {
+        geographic_level: geographic_level.map(str::to_string),
+        geography: tags_in("geo").next().map(fred_geography_name),
+        categories: categories.iter().map(|c| c.name.clone()).collect(),
+        tags: tags_in("gen").map(str::to_string).collect(),
+    }
+}
+
+/// Display name of a FRED geography tag, which are lowercase
+fn fred_geography_name(tag: &str) -> String {
+    match tag {
+        "usa" => "United States".to_string(),
+        _ => tag
+            .split(' ')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+    }
+}
+
 /// Get popular FRED series
 pub async fn get_popular_fred_series(
     client: &Client,
@@ -182,6 +327,9 @@ pub async fn get_popular_fred_series(
         api_key
     );
 
+    shared_rate_limiter()
+        .acquire(FRED_HOST, Some(api_key))
+        .await;
     let response = client.get(&url).send().await.map_err(|e| {
         AppError::ExternalApiError(format!("FRED popular series request failed: {}", e))
     })?;
@@ -218,6 +366,9 @@ pub async fn search_fred_series(
         query, api_key
     );
 
+    shared_rate_limiter()
+        .acquire(FRED_HOST, Some(api_key.as_str()))
+        .await;
     let response = client
         .get(&url)
         .send()
@@ -238,3 +389,45 @@ pub async fn search_fred_series(
 
     Ok(search_response.seriess)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str, group_id: &str) -> FredTag {
+        FredTag {
+            name: name.to_string(),
+            group_id: group_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fred_enrichment_from_tags() {
+        // REQUIREMENT: Enrich series metadata from FRED catalogs
+        // PURPOSE: Verify geography, categories and topic tags are taken from the right tag groups
+        // This ensures search can filter FRED series by geography and topic
+
+        let categories = vec![FredCategory {
+            id: 32447,
+            name: "Unemployment Rate".to_string(),
+            parent_id: 10,
+        }];
+        let tags = vec![
+            tag("state", "geot"),
+            tag("new york", "geo"),
+            tag("unemployment", "gen"),
+            tag("monthly", "freq"),
+            tag("bls", "src"),
+        ];
+
+        let enrichment = fred_enrichment(&categories, &tags);
+        assert_eq!(enrichment.geographic_level.as_deref(), Some("State"));
+        assert_eq!(enrichment.geography.as_deref(), Some("New York"));
+        assert_eq!(enrichment.categories, vec!["Unemployment Rate"]);
+        assert_eq!(enrichment.tags, vec!["unemployment"]);
+
+        let national = fred_enrichment(&[], &[tag("nation", "geot"), tag("usa", "geo")]);
+        assert_eq!(national.geographic_level.as_deref(), Some("National"));
+        assert_eq!(national.geography.as_deref(), Some("United States"));
+    }
+}
@@ -1,14 +1,36 @@
 //! IMF (International Monetary Fund) API integration for series discovery
+//!
+//! Data is read from the IMF SDMX_JSON service:
+//! 1. `Dataflow` lists the published datasets
+//! 2. `DataStructure/{dataset}` gives the key dimensions and their code lists
+//! 3. `CompactData/{dataset}/{key}` returns series and observations
+//!
+//! Series keys are built from [`IMF_DATASETS`] in the dimension order of the
+//! dataset's structure. Every returned series is stored as an economic series,
+//! its observations go through the ingestion pipeline into `data_points`, and
+//! it is registered in `series_metadata` with the dataset it came from.
 
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
-use econ_graph_core::models::{DataSource, EconomicSeries, NewEconomicSeries};
+use econ_graph_core::models::{
+    DataSource, EconomicSeries, NewEconomicSeries, NewSeriesMetadata, SeriesMetadata,
+};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, IMF_HOST};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
 use reqwest::Client;
 use reqwest::StatusCode;
 use serde::Deserialize;
+use serde_json::Value;
 use uuid::Uuid;
 
+use crate::services::crawler::{CrawlOrigin, IngestionConfig, IngestionPipeline, RawObservation};
+
+/// IMF SDMX_JSON service base URL
+const IMF_SDMX_URL: &str = "https://dataservices.imf.org/REST/SDMX_JSON.svc";
+
 /// IMF API response for datasets (SDMX format)
 #[derive(Debug, Deserialize)]
 pub struct ImfDatasetsResponse {
@@ -48,42 +70,166 @@ pub struct ImfKeyFamilyRef {
     pub key_family_id: String,
 }
 
-/// IMF series information structure
-#[derive(Debug, Clone)]
-pub struct ImfSeriesInfo {
-    pub series_id: String,
-    pub title: String,
-    pub description: Option<String>,
-    pub frequency: String,
-    pub units: String,
-    pub dataset: String,
-    pub country: Option<String>,
-    pub start_date: Option<String>,
-    pub end_date: Option<String>,
+/// Series requested from one IMF dataset
+#[derive(Debug, Clone, Copy)]
+pub struct ImfDatasetSelection {
+    pub dataset: &'static str,
+    pub name: &'static str,
+    /// Codes requested per dimension id; dimensions not listed are wildcards
+    pub dimensions: &'static [(&'static str, &'static [&'static str])],
+    /// First period requested, e.g. "1990"
+    pub start_period: &'static str,
+    /// Whether the dataset publishes projections dated in the future
+    pub includes_projections: bool,
+}
+
+/// Economies requested from every dataset (IMF reference area codes)
+const MAJOR_ECONOMIES: &[&str] = &[
+    "US", "GB", "DE", "FR", "IT", "JP", "CN", "CA", "AU", "BR", "IN", "KR", "MX", "ZA",
+];
+
+/// Datasets and series collected from the IMF
+pub const IMF_DATASETS: &[ImfDatasetSelection] = &[
+    ImfDatasetSelection {
+        dataset: "IFS",
+        name: "International Financial Statistics",
+        dimensions: &[
+            ("FREQ", &["M"]),
+            ("REF_AREA", MAJOR_ECONOMIES),
+            (
+                "INDICATOR",
+                &[
+                    "PCPI_IX",           // Consumer prices, all items
+                    "EREER_IX",          // Real effective exchange rate
+                    "FPOLM_PA",          // Monetary policy rate
+                    "ENDA_XDC_USD_RATE", // Exchange rate, domestic currency per USD
+                    "LUR_PT",            // Unemployment rate
+                ],
+            ),
+        ],
+        start_period: "1990",
+        includes_projections: false,
+    },
+    ImfDatasetSelection {
+        dataset: "WEO",
+        name: "World Economic Outlook",
+        dimensions: &[
+            ("FREQ", &["A"]),
+            ("REF_AREA", MAJOR_ECONOMIES),
+            (
+                "INDICATOR",
+                &[
+                    "NGDP_RPCH",   // Real GDP growth
+                    "PCPIPCH",     // Inflation, average consumer prices
+                    "LUR",         // Unemployment rate
+                    "GGXWDG_NGDP", // General government gross debt
+                    "BCA_NGDPD",   // Current account balance
+                ],
+            ),
+        ],
+        start_period: "1980",
+        includes_projections: true,
+    },
+];
+
+/// Key dimension of a dataset, in key order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImfDimension {
+    pub id: String,
+    pub codelist: String,
+}
+
+/// Dimensions and code names from a dataset's structure definition
+#[derive(Debug, Clone, Default)]
+pub struct ImfDataStructure {
+    pub dimensions: Vec<ImfDimension>,
+    /// Code descriptions keyed by (code list, code)
+    pub code_names: HashMap<(String, String), String>,
+}
+
+impl ImfDataStructure {
+    /// Description of a dimension code, falling back to the code itself
+    pub fn code_name(&self, dimension_id: &str, code: &str) -> String {
+        self.dimensions
+            .iter()
+            .find(|d| d.id == dimension_id)
+            .and_then(|d| {
+                self.code_names
+                    .get(&(d.codelist.clone(), code.to_string()))
+                    .cloned()
+            })
+            .unwrap_or_else(|| code.to_string())
+    }
+}
+
+/// Series returned by `CompactData`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImfSeries {
+    /// Dimension codes in key order, e.g. `[("FREQ", "M"), ("REF_AREA", "US"), ...]`
+    pub key: Vec<(String, String)>,
+    /// Power of ten the values are reported in
+    pub unit_mult: i32,
+    /// (period, value) pairs, e.g. `("2024-03", Some("312.2"))`
+    pub observations: Vec<(String, Option<String>)>,
+}
+
+impl ImfSeries {
+    /// Series key as used in requests, e.g. `M.US.PCPI_IX`
+    pub fn key_string(&self) -> String {
+        self.key
+            .iter()
+            .map(|(_, code)| code.as_str())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Code of a dimension, e.g. `FREQ`
+    pub fn code(&self, dimension_id: &str) -> Option<&str> {
+        self.key
+            .iter()
+            .find(|(id, _)| id == dimension_id)
+            .map(|(_, code)| code.as_str())
+    }
 }
 
-/// Discover IMF series using the IMF Data API
+/// Discover IMF series and load their observations
+///
+/// Returns the external ids (`{dataset}.{key}`) of the series stored.
 pub async fn discover_imf_series(client: &Client, pool: &DatabasePool) -> AppResult<Vec<String>> {
     let imf_source = DataSource::get_or_create(pool, DataSource::imf()).await?;
     let mut discovered_series = Vec::new();
 
-    // Get economic datasets from IMF API
     let datasets = fetch_imf_economic_datasets(client).await?;
     println!("Found {} IMF economic datasets", datasets.len());
 
-    // For each dataset, discover series
-    for dataset in datasets {
-        println!(
-            "Discovering series for dataset: {} ({})",
-            dataset.name[0].value, dataset.key_family_ref.key_family_id
-        );
-
-        let dataset_series = get_known_imf_series_by_dataset(&dataset.key_family_ref.key_family_id);
+    for selection in IMF_DATASETS {
+        let published = datasets.iter().any(|d| {
+            d.key_family_ref
+                .key_family_id
+                .eq_ignore_ascii_case(selection.dataset)
+        });
+        if !published {
+            println!(
+                "Skipping IMF dataset {}: not published by the SDMX service",
+                selection.dataset
+            );
+            continue;
+        }
 
-        for series_info in dataset_series {
-            // Store series metadata in database
-            store_imf_series(pool, &imf_source.id, &series_info).await?;
-            discovered_series.push(series_info.series_id);
+        match discover_dataset(client, pool, &imf_source.id, selection).await {
+            Ok(series) => {
+                println!(
+                    "Stored {} series from IMF {} ({})",
+                    series.len(),
+                    selection.name,
+                    selection.dataset
+                );
+                discovered_series.extend(series);
+            }
+            Err(e) => {
+                println!("IMF dataset {} failed: {}", selection.dataset, e);
+                CRAWLER_METRICS.record_error("economic", "imf", "dataset_error");
+            }
         }
     }
 
@@ -91,39 +237,66 @@ pub async fn discover_imf_series(client: &Client, pool: &DatabasePool) -> AppRes
     Ok(discovered_series)
 }
 
-/// Fetch economic datasets from IMF API
-async fn fetch_imf_economic_datasets(client: &Client) -> AppResult<Vec<ImfDataflow>> {
-    // IMF API doesn't require authentication
-    let url = "http://dataservices.imf.org/REST/SDMX_JSON.svc/Dataflow";
+/// Fetch, store and ingest the selected series of one dataset
+async fn discover_dataset(
+    client: &Client,
+    pool: &DatabasePool,
+    source_id: &Uuid,
+    selection: &ImfDatasetSelection,
+) -> AppResult<Vec<String>> {
+    let structure_json = fetch_json(
+        client,
+        &format!("DataStructure/{}", selection.dataset),
+        "/DataStructure/{dataset}",
+    )
+    .await?;
+    let structure = parse_data_structure(&structure_json)?;
 
-    let start = std::time::Instant::now();
-    let response =
-        client.get(url).send().await.map_err(|e| {
-            AppError::ExternalApiError(format!("IMF datasets request failed: {}", e))
-        })?;
-    let duration = start.elapsed().as_secs_f64();
-    let status = response.status();
-    CRAWLER_METRICS.record_request(
-        "economic",
-        "imf",
-        "/REST/SDMX_JSON.svc/Dataflow",
-        status.as_str(),
-        duration,
-    );
-    if status == StatusCode::TOO_MANY_REQUESTS {
-        CRAWLER_METRICS.record_rate_limit_hit("economic", "imf");
-    }
+    let key = build_series_key(&structure.dimensions, selection.dimensions)?;
+    let data_json = fetch_json(
+        client,
+        &format!(
+            "CompactData/{}/{}?startPeriod={}",
+            selection.dataset, key, selection.start_period
+        ),
+        "/CompactData/{dataset}/{key}",
+    )
+    .await?;
+    let series_list = parse_compact_data(&data_json, &structure.dimensions);
 
-    if !status.is_success() {
-        return Err(AppError::ExternalApiError(format!(
-            "IMF API returned status: {}",
-            status
-        )));
+    let mut stored = Vec::new();
+    let mut observations = 0u64;
+    for series in &series_list {
+        match store_imf_series(pool, source_id, selection, &structure, series).await {
+            Ok((external_id, stored_points)) => {
+                stored.push(external_id);
+                observations += stored_points as u64;
+            }
+            Err(e) => {
+                println!(
+                    "Failed to store IMF series {}.{}: {}",
+                    selection.dataset,
+                    series.key_string(),
+                    e
+                );
+                CRAWLER_METRICS.record_error("economic", "imf", "storage_error");
+            }
+        }
     }
 
-    let datasets_response: ImfDatasetsResponse = response.json().await.map_err(|e| {
-        AppError::ExternalApiError(format!("Failed to parse IMF datasets response: {}", e))
-    })?;
+    CRAWLER_METRICS.record_items_collected("economic", "imf", "series", stored.len() as u64);
+    CRAWLER_METRICS.record_items_collected("economic", "imf", "observations", observations);
+
+    Ok(stored)
+}
+
+/// Fetch economic datasets from IMF API
+async fn fetch_imf_economic_datasets(client: &Client) -> AppResult<Vec<ImfDataflow>> {
+    let json_response = fetch_json(client, "Dataflow", "/Dataflow").await?;
+    let datasets_response: ImfDatasetsResponse =
+        serde_json::from_value(json_response).map_err(|e| {
+            AppError::ExternalApiError(format!("Failed to parse IMF datasets response: {}", e))
+        })?;
 
     // Filter for economic datasets
     let economic_datasets: Vec<ImfDataflow> = datasets_response
@@ -132,7 +305,11 @@ async fn fetch_imf_economic_datasets(client: &Client) -> AppResult<Vec<ImfDatafl
         .dataflow
         .into_iter()
         .filter(|dataflow| {
-            let name = &dataflow.name[0].value.to_lowercase();
+            let name = &dataflow
+                .name
+                .first()
+                .map(|name| name.value.to_lowercase())
+                .unwrap_or_default();
             let id = &dataflow.key_family_ref.key_family_id.to_lowercase();
 
             // Filter for key economic datasets
@@ -155,144 +332,308 @@ async fn fetch_imf_economic_datasets(client: &Client) -> AppResult<Vec<ImfDatafl
     Ok(economic_datasets)
 }
 
-/// Get known IMF series organized by dataset
-fn get_known_imf_series_by_dataset(dataset_id: &str) -> Vec<ImfSeriesInfo> {
-    match dataset_id.to_uppercase().as_str() {
-        "IFS" => vec![
-            ImfSeriesInfo {
-                series_id: "IFS_US_PCPI_IX".to_string(),
-                title: "Consumer Price Index - United States".to_string(),
-                description: Some("Consumer Price Index from International Financial Statistics".to_string()),
-                frequency: "Monthly".to_string(),
-                units: "Index".to_string(),
-                dataset: "IFS".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-            ImfSeriesInfo {
-                series_id: "IFS_US_LP_IX".to_string(),
-                title: "Labor Force Participation Rate - United States".to_string(),
-                description: Some("Labor force participation rate from International Financial Statistics".to_string()),
-                frequency: "Monthly".to_string(),
-                units: "Percent".to_string(),
-                dataset: "IFS".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-            ImfSeriesInfo {
-                series_id: "IFS_US_EREER_IX".to_string(),
-                title: "Real Effective Exchange Rate - United States".to_string(),
-                description: Some("Real effective exchange rate from International Financial Statistics".to_string()),
-                frequency: "Monthly".to_string(),
-                units: "Index".to_string(),
-                dataset: "IFS".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-        ],
-        "BOP" => vec![
-            ImfSeriesInfo {
-                series_id: "BOP_US_CA_BP6_USD".to_string(),
-                title: "Current Account Balance - United States".to_string(),
-                description: Some("Current account balance from Balance of Payments".to_string()),
-                frequency: "Quarterly".to_string(),
-                units: "US Dollars".to_string(),
-                dataset: "BOP".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-            ImfSeriesInfo {
-                series_id: "BOP_US_FA_BP6_USD".to_string(),
-                title: "Financial Account Balance - United States".to_string(),
-                description: Some("Financial account balance from Balance of Payments".to_string()),
-                frequency: "Quarterly".to_string(),
-                units: "US Dollars".to_string(),
-                dataset: "BOP".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-        ],
-        "GFS" => vec![
-            ImfSeriesInfo {
-                series_id: "GFS_US_GGR_G01_GDP_PT".to_string(),
-                title: "General Government Revenue - United States".to_string(),
-                description: Some("General government revenue as percentage of GDP from Government Finance Statistics".to_string()),
-                frequency: "Annual".to_string(),
-                units: "Percent of GDP".to_string(),
-                dataset: "GFS".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-            ImfSeriesInfo {
-                series_id: "GFS_US_GGX_G01_GDP_PT".to_string(),
-                title: "General Government Expenditure - United States".to_string(),
-                description: Some("General government expenditure as percentage of GDP from Government Finance Statistics".to_string()),
-                frequency: "Annual".to_string(),
-                units: "Percent of GDP".to_string(),
-                dataset: "GFS".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-        ],
-        "WEO" => vec![
-            ImfSeriesInfo {
-                series_id: "WEO_US_NGDP_RPCH".to_string(),
-                title: "Real GDP Growth - United States".to_string(),
-                description: Some("Real GDP growth rate from World Economic Outlook".to_string()),
-                frequency: "Annual".to_string(),
-                units: "Percent".to_string(),
-                dataset: "WEO".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-            ImfSeriesInfo {
-                series_id: "WEO_US_PCPIPCH".to_string(),
-                title: "Inflation Rate - United States".to_string(),
-                description: Some("Inflation rate from World Economic Outlook".to_string()),
-                frequency: "Annual".to_string(),
-                units: "Percent".to_string(),
-                dataset: "WEO".to_string(),
-                country: Some("US".to_string()),
-                start_date: Some("1980-01-01".to_string()),
-                end_date: None,
-            },
-        ],
-        // For other datasets, return empty vector for now
-        // This can be expanded as we discover more series patterns
-        _ => vec![],
+/// GET a path of the SDMX_JSON service, rate limited and recorded in the crawler metrics
+async fn fetch_json(client: &Client, path: &str, endpoint: &str) -> AppResult<Value> {
+    let url = format!("{}/{}", IMF_SDMX_URL, path);
+
+    shared_rate_limiter().acquire(IMF_HOST, None).await;
+    let start = std::time::Instant::now();
+    let response = client.get(&url).send().await.map_err(|e| {
+        CRAWLER_METRICS.record_error("economic", "imf", "network");
+        AppError::ExternalApiError(format!("IMF request for {} failed: {}", path, e))
+    })?;
+    let duration = start.elapsed().as_secs_f64();
+    let status = response.status();
+    CRAWLER_METRICS.record_request("economic", "imf", endpoint, status.as_str(), duration);
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        CRAWLER_METRICS.record_rate_limit_hit("economic", "imf");
+    }
+
+    if !status.is_success() {
+        CRAWLER_METRICS.record_error("economic", "imf", "http_error");
+        return Err(AppError::ExternalApiError(format!(
+            "IMF API returned status {} for {}",
+            status, path
+        )));
+    }
+
+    response.json().await.map_err(|e| {
+        CRAWLER_METRICS.record_error("economic", "imf", "parse_error");
+        AppError::ExternalApiError(format!("Failed to parse IMF response for {}: {}", path, e))
+    })
+}
+
+/// SDMX-JSON renders single elements as objects and repeated ones as arrays
+fn one_or_many(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(value) => vec![value],
+    }
+}
+
+/// Text of an SDMX element: a plain string, `{"#text": ...}`, or a list of languages
+fn sdmx_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Object(fields) => fields.get("#text").and_then(sdmx_text),
+        Value::Array(values) => {
+            // Prefer English when several languages are given
+            values
+                .iter()
+                .find(|v| v.get("@xml:lang").and_then(Value::as_str) == Some("en"))
+                .or_else(|| values.first())
+                .and_then(sdmx_text)
+        }
+        _ => None,
+    }
+}
+
+/// Read the key dimensions and code descriptions from a `DataStructure` response
+pub fn parse_data_structure(json_response: &Value) -> AppResult<ImfDataStructure> {
+    let structure = json_response.get("Structure").ok_or_else(|| {
+        AppError::ExternalApiError("IMF data structure response has no Structure".to_string())
+    })?;
+
+    let key_family = one_or_many(structure.pointer("/KeyFamilies/KeyFamily"))
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            AppError::ExternalApiError("IMF data structure has no key family".to_string())
+        })?;
+
+    let dimensions: Vec<ImfDimension> = one_or_many(key_family.pointer("/Components/Dimension"))
+        .into_iter()
+        .filter_map(|dimension| {
+            Some(ImfDimension {
+                id: dimension.get("@conceptRef")?.as_str()?.to_string(),
+                codelist: dimension
+                    .get("@codelist")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect();
+    if dimensions.is_empty() {
+        return Err(AppError::ExternalApiError(
+            "IMF data structure has no key dimensions".to_string(),
+        ));
+    }
+
+    let mut code_names = HashMap::new();
+    for codelist in one_or_many(structure.pointer("/CodeLists/CodeList")) {
+        let Some(codelist_id) = codelist.get("@id").and_then(Value::as_str) else {
+            continue;
+        };
+        for code in one_or_many(codelist.get("Code")) {
+            let (Some(value), Some(description)) = (
+                code.get("@value").and_then(Value::as_str),
+                code.get("Description").and_then(sdmx_text),
+            ) else {
+                continue;
+            };
+            code_names.insert((codelist_id.to_string(), value.to_string()), description);
+        }
+    }
+
+    Ok(ImfDataStructure {
+        dimensions,
+        code_names,
+    })
+}
+
+/// Build a `CompactData` key such as `M.US+GB.PCPI_IX`
+///
+/// Codes are placed in the dataset's dimension order and joined with `+`;
+/// dimensions without a selection are left empty, which the API treats as
+/// a wildcard.
+pub fn build_series_key(
+    dimensions: &[ImfDimension],
+    selection: &[(&str, &[&str])],
+) -> AppResult<String> {
+    if let Some((unknown, _)) = selection
+        .iter()
+        .find(|(id, _)| !dimensions.iter().any(|d| d.id.eq_ignore_ascii_case(id)))
+    {
+        return Err(AppError::ValidationError(format!(
+            "IMF dataset has no {} dimension",
+            unknown
+        )));
+    }
+
+    Ok(dimensions
+        .iter()
+        .map(|dimension| {
+            selection
+                .iter()
+                .find(|(id, _)| dimension.id.eq_ignore_ascii_case(id))
+                .map(|(_, codes)| codes.join("+"))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join("."))
+}
+
+/// Read the series from a `CompactData` response
+pub fn parse_compact_data(json_response: &Value, dimensions: &[ImfDimension]) -> Vec<ImfSeries> {
+    one_or_many(json_response.pointer("/CompactData/DataSet/Series"))
+        .into_iter()
+        .filter_map(|series| {
+            let key = dimensions
+                .iter()
+                .map(|dimension| {
+                    let code = series.get(format!("@{}", dimension.id))?.as_str()?;
+                    Some((dimension.id.clone(), code.to_string()))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let unit_mult = series
+                .get("@UNIT_MULT")
+                .and_then(Value::as_str)
+                .and_then(|mult| mult.parse().ok())
+                .unwrap_or(0);
+
+            let observations = one_or_many(series.get("Obs"))
+                .into_iter()
+                .filter_map(|obs| {
+                    let period = obs.get("@TIME_PERIOD")?.as_str()?.to_string();
+                    let value = obs
+                        .get("@OBS_VALUE")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                    Some((period, value))
+                })
+                .collect();
+
+            Some(ImfSeries {
+                key,
+                unit_mult,
+                observations,
+            })
+        })
+        .collect()
+}
+
+/// Frequency name for an SDMX frequency code
+pub fn frequency_name(code: &str) -> Option<&'static str> {
+    match code {
+        "A" => Some("Annual"),
+        "Q" => Some("Quarterly"),
+        "M" => Some("Monthly"),
+        "W" => Some("Weekly"),
+        "D" | "B" => Some("Daily"),
+        _ => None,
     }
 }
 
-/// Store IMF series metadata in database
+/// Observation date for an SDMX period: `2023`, `2023-Q2`, `2023-04` or `2023-04-17`
+///
+/// Periods are dated on their last day, like other annual, quarterly and
+/// monthly series.
+pub fn parse_time_period(period: &str) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(period, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let (year, rest) = match period.split_once('-') {
+        Some((year, rest)) => (year, Some(rest)),
+        None => (period, None),
+    };
+    let year: i32 = year.parse().ok()?;
+    let month = match rest {
+        None => 12,
+        Some(quarter) if quarter.starts_with('Q') => match quarter[1..].parse::<u32>().ok()? {
+            quarter @ 1..=4 => quarter * 3,
+            _ => return None,
+        },
+        Some(month) => match month.parse::<u32>().ok()? {
+            month @ 1..=12 => month,
+            _ => return None,
+        },
+    };
+
+    let first_of_next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    first_of_next_month.pred_opt()
+}
+
+/// Unit label for an SDMX unit multiplier
+fn unit_multiplier_label(unit_mult: i32) -> String {
+    match unit_mult {
+        0 => "Units".to_string(),
+        3 => "Thousands".to_string(),
+        6 => "Millions".to_string(),
+        9 => "Billions".to_string(),
+        12 => "Trillions".to_string(),
+        n => format!("10^{}", n),
+    }
+}
+
+/// Store an IMF series, its metadata and its observations
+///
+/// Returns the external id and the number of data points written.
 async fn store_imf_series(
     pool: &DatabasePool,
     source_id: &Uuid,
-    series_info: &ImfSeriesInfo,
-) -> AppResult<()> {
+    selection: &ImfDatasetSelection,
+    structure: &ImfDataStructure,
+    series: &ImfSeries,
+) -> AppResult<(String, usize)> {
+    let key = series.key_string();
+    let external_id = format!("{}.{}", selection.dataset, key);
+    let frequency = series
+        .code("FREQ")
+        .and_then(frequency_name)
+        .unwrap_or("Unknown")
+        .to_string();
+
+    // Most specific dimension first, e.g. "Consumer Prices, All items, United States"
+    let title: String = series
+        .key
+        .iter()
+        .rev()
+        .filter(|(id, _)| id != "FREQ")
+        .map(|(id, code)| structure.code_name(id, code))
+        .collect::<Vec<_>>()
+        .join(", ")
+        .chars()
+        .take(500)
+        .collect();
+    let units = unit_multiplier_label(series.unit_mult);
+
+    let observations: Vec<RawObservation> = series
+        .observations
+        .iter()
+        .filter_map(|(period, value)| {
+            Some(RawObservation {
+                date: parse_time_period(period)?.format("%Y-%m-%d").to_string(),
+                value: value.clone(),
+                unit: None,
+            })
+        })
+        .collect();
+    let dates: Vec<NaiveDate> = observations
+        .iter()
+        .filter(|o| o.value.is_some())
+        .filter_map(|o| NaiveDate::parse_from_str(&o.date, "%Y-%m-%d").ok())
+        .collect();
+    let start_date = dates.iter().min().copied();
+    let end_date = dates.iter().max().copied();
+
     let new_series = NewEconomicSeries {
         source_id: *source_id,
-        external_id: series_info.series_id.clone(),
-        title: series_info.title.clone(),
-        description: series_info.description.clone(),
-        units: Some(series_info.units.clone()),
-        frequency: series_info.frequency.clone(),
+        external_id: external_id.clone(),
+        title: title.clone(),
+        description: Some(format!("{} from the IMF {}", title, selection.name)),
+        units: Some(units.clone()),
+        frequency: frequency.clone(),
         seasonal_adjustment: None, // IMF data varies by series
-        start_date: series_info
-            .start_date
-            .as_ref()
-            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
-        end_date: series_info
-            .end_date
-            .as_ref()
-            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+        start_date,
+        end_date,
         is_active: true,
         first_discovered_at: Some(chrono::Utc::now()),
         last_crawled_at: None,
@@ -300,7 +641,185 @@ async fn store_imf_series(
         crawl_status: None,
         crawl_error_message: None,
     };
+    let economic_series =
+        EconomicSeries::get_or_create(pool, &external_id, *source_id, &new_series).await?;
+
+    let metadata = SeriesMetadata::get_or_create(
+        pool,
+        *source_id,
+        &external_id,
+        &NewSeriesMetadata {
+            source_id: *source_id,
+            external_id: external_id.clone(),
+            title: title.clone(),
+            description: Some(format!(
+                "{} ({}) series {}",
+                selection.name, selection.dataset, key
+            )),
+            units: Some(units),
+            frequency: Some(frequency),
+            geographic_level: Some("Country".to_string()),
+            data_url: Some(format!(
+                "{}/CompactData/{}/{}",
+                IMF_SDMX_URL, selection.dataset, key
+            )),
+            api_endpoint: Some(format!(
+                "{}/CompactData/{}",
+                IMF_SDMX_URL, selection.dataset
+            )),
+            is_active: true,
+        },
+    )
+    .await?;
+
+    let pipeline = IngestionPipeline::new(IngestionConfig {
+        allow_future_dates: selection.includes_projections,
+        ..Default::default()
+    })
+    .with_origin(CrawlOrigin {
+        source_url: metadata.data_url.clone(),
+        ..Default::default()
+    });
+    let report = pipeline
+        .ingest(pool, "imf", economic_series.id, &observations)
+        .await?;
+
+    if let (Some(start_date), Some(end_date)) = (start_date, end_date) {
+        if economic_series.start_date != Some(start_date)
+            || economic_series.end_date != Some(end_date)
+        {
+            EconomicSeries::update_date_range(pool, economic_series.id, start_date, end_date)
+                .await?;
+        }
+    }
+
+    Ok((external_id, report.stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ifs_dimensions() -> Vec<ImfDimension> {
+        ["FREQ", "REF_AREA", "INDICATOR"]
+            .iter()
+            .map(|id| ImfDimension {
+                id: id.to_string(),
+                codelist: format!("CL_{}", id),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_series_key_follows_dimension_order() {
+        // REQUIREMENT: IMF series are requested with valid SDMX keys
+        // PURPOSE: Verify codes are placed in structure order, joined with '+', and unselected dimensions are wildcards
+        // This keeps requests correct when a dataset orders its dimensions differently from our selection
+
+        let dimensions = ifs_dimensions();
+        let selection: &[(&str, &[&str])] = &[
+            ("INDICATOR", &["PCPI_IX"]),
+            ("REF_AREA", &["US", "GB"]),
+            ("FREQ", &["M"]),
+        ];
+        assert_eq!(
+            build_series_key(&dimensions, selection).unwrap(),
+            "M.US+GB.PCPI_IX"
+        );
 
-    EconomicSeries::get_or_create(pool, &series_info.series_id, *source_id, &new_series).await?;
-    Ok(())
+        let indicator_only: &[(&str, &[&str])] = &[("INDICATOR", &["PCPI_IX"])];
+        assert_eq!(
+            build_series_key(&dimensions, indicator_only).unwrap(),
+            "..PCPI_IX"
+        );
+
+        let unknown_dimension: &[(&str, &[&str])] = &[("UNIT", &["USD"])];
+        assert!(build_series_key(&dimensions, unknown_dimension).is_err());
+    }
+
+    #[test]
+    fn test_parse_data_structure_and_compact_data() {
+        // REQUIREMENT: IMF SDMX-JSON responses are converted into series and observations
+        // PURPOSE: Verify single-element objects and arrays are both handled and code names are resolved
+        // This ensures one-series and one-observation responses are not dropped
+
+        let structure = parse_data_structure(&json!({
+            "Structure": {
+                "CodeLists": { "CodeList": [
+                    { "@id": "CL_FREQ", "Code": [
+                        { "@value": "M", "Description": { "@xml:lang": "en", "#text": "Monthly" } }
+                    ] },
+                    { "@id": "CL_REF_AREA", "Code": { "@value": "US", "Description": "United States" } },
+                    { "@id": "CL_INDICATOR", "Code": [
+                        { "@value": "PCPI_IX", "Description": [
+                            { "@xml:lang": "fr", "#text": "Prix a la consommation" },
+                            { "@xml:lang": "en", "#text": "Consumer Prices, All items" }
+                        ] }
+                    ] }
+                ] },
+                "KeyFamilies": { "KeyFamily": { "Components": { "Dimension": [
+                    { "@conceptRef": "FREQ", "@codelist": "CL_FREQ" },
+                    { "@conceptRef": "REF_AREA", "@codelist": "CL_REF_AREA" },
+                    { "@conceptRef": "INDICATOR", "@codelist": "CL_INDICATOR" }
+                ] } } }
+            }
+        }))
+        .unwrap();
+        assert_eq!(structure.dimensions, ifs_dimensions());
+        assert_eq!(
+            structure.code_name("INDICATOR", "PCPI_IX"),
+            "Consumer Prices, All items"
+        );
+        assert_eq!(structure.code_name("REF_AREA", "US"), "United States");
+        assert_eq!(structure.code_name("REF_AREA", "GB"), "GB");
+
+        let series = parse_compact_data(
+            &json!({
+                "CompactData": { "DataSet": { "Series": {
+                    "@FREQ": "M", "@REF_AREA": "US", "@INDICATOR": "PCPI_IX", "@UNIT_MULT": "0",
+                    "Obs": { "@TIME_PERIOD": "2024-01", "@OBS_VALUE": "308.4" }
+                } } }
+            }),
+            &structure.dimensions,
+        );
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].key_string(), "M.US.PCPI_IX");
+        assert_eq!(series[0].code("FREQ"), Some("M"));
+        assert_eq!(
+            series[0].observations,
+            vec![("2024-01".to_string(), Some("308.4".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_time_periods_and_frequencies() {
+        // REQUIREMENT: IMF observations are stored with proper dates and frequencies
+        // PURPOSE: Verify SDMX periods map to the last day of the period and codes to frequency names
+        // This keeps IMF series aligned with other annual, quarterly and monthly series
+
+        assert_eq!(
+            parse_time_period("2023"),
+            NaiveDate::from_ymd_opt(2023, 12, 31)
+        );
+        assert_eq!(
+            parse_time_period("2023-Q2"),
+            NaiveDate::from_ymd_opt(2023, 6, 30)
+        );
+        assert_eq!(
+            parse_time_period("2024-02"),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+        assert_eq!(
+            parse_time_period("2024-02-15"),
+            NaiveDate::from_ymd_opt(2024, 2, 15)
+        );
+        assert_eq!(parse_time_period("2024-Q5"), None);
+        assert_eq!(parse_time_period("2024-13"), None);
+
+        assert_eq!(frequency_name("A"), Some("Annual"));
+        assert_eq!(frequency_name("Q"), Some("Quarterly"));
+        assert_eq!(frequency_name("M"), Some("Monthly"));
+        assert_eq!(frequency_name("X"), None);
+    }
 }
@@ -5,11 +5,14 @@
 
 pub mod bea;
 pub mod bls;
+pub mod bls_catalog;
 pub mod boc;
 pub mod boe;
 pub mod boj;
 pub mod census;
 pub mod ecb;
+pub mod enrichment;
+pub mod eurostat;
 pub mod fhfa;
 pub mod fred;
 pub mod ilo;
@@ -23,6 +26,7 @@ pub mod wto;
 
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::AppResult;
+use enrichment::EnrichmentSummary;
 use reqwest::Client;
 
 /// Series discovery service for automated cataloging
@@ -86,6 +90,11 @@ impl SeriesDiscoveryService {
         ecb::discover_ecb_series(&self.client, pool).await
     }
 
+    /// Discover Eurostat series using the Eurostat dissemination API
+    pub async fn discover_eurostat_series(&self, pool: &DatabasePool) -> AppResult<Vec<String>> {
+        eurostat::discover_eurostat_series(&self.client, pool).await
+    }
+
     /// Discover OECD series using the OECD REST API
     pub async fn discover_oecd_series(&self, pool: &DatabasePool) -> AppResult<Vec<String>> {
         oecd::discover_oecd_series(&self.client, pool).await
@@ -141,6 +150,31 @@ impl SeriesDiscoveryService {
         fred::search_fred_series(&self.client, &self.fred_api_key, query).await
     }
 
+    /// Add catalog categories, tags and geography to discovered FRED and BLS series
+    ///
+    /// A source that fails (e.g. without an API key) is skipped.
+    pub async fn enrich_series_metadata(&self, pool: &DatabasePool) -> EnrichmentSummary {
+        let mut summary = EnrichmentSummary::default();
+
+        match enrichment::enrich_fred_series(&self.client, &self.fred_api_key, pool).await {
+            Ok(fred) => {
+                summary.enriched += fred.enriched;
+                summary.failed += fred.failed;
+            }
+            Err(e) => eprintln!("FRED series enrichment failed: {}", e),
+        }
+
+        match enrichment::enrich_bls_series(&self.client, &self.bls_api_key, pool).await {
+            Ok(bls) => {
+                summary.enriched += bls.enriched;
+                summary.failed += bls.failed;
+            }
+            Err(e) => eprintln!("BLS series enrichment failed: {}", e),
+        }
+
+        summary
+    }
+
     /// Discover all series from all sources
     pub async fn discover_all_series(&self, pool: &DatabasePool) -> AppResult<Vec<String>> {
         let mut all_series = Vec::new();
@@ -174,6 +208,10 @@ impl SeriesDiscoveryService {
             all_series.extend(ecb_series);
         }
 
+        if let Ok(eurostat_series) = self.discover_eurostat_series(pool).await {
+            all_series.extend(eurostat_series);
+        }
+
         if let Ok(oecd_series) = self.discover_oecd_series(pool).await {
             all_series.extend(oecd_series);
         }
@@ -214,6 +252,8 @@ impl SeriesDiscoveryService {
             all_series.extend(fhfa_series);
         }
 
+        self.enrich_series_metadata(pool).await;
+
         Ok(all_series)
     }
 }
@@ -1,18 +1,50 @@
 //! World Bank API integration for series discovery
-
+//!
+//! Discovery runs in two parts:
+//! 1. The indicator catalog (economic topics and the paged indicator
+//!    list) is stored as World Bank series.
+//! 2. A core set of indicators is downloaded for every country. Each
+//!    country/indicator pair is stored as its own series (`USA.NY.GDP.MKTP.CD`)
+//!    and in `global_economic_indicators`/`global_indicator_data`, which is
+//!    what the global analysis service reads.
+//!
+//! All requests go through the shared rate limiter and are recorded in the
+//! crawler metrics.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, NaiveDate};
 use econ_graph_core::database::DatabasePool;
 use econ_graph_core::error::{AppError, AppResult};
-use econ_graph_core::models::{DataSource, EconomicSeries, NewEconomicSeries};
+use econ_graph_core::models::{
+    Country, DataSource, EconomicSeries, GlobalEconomicIndicator, GlobalIndicatorData, NewCountry,
+    NewEconomicSeries, NewGlobalEconomicIndicator, NewGlobalIndicatorData,
+};
+use econ_graph_core::rate_limiter::{shared_rate_limiter, WORLD_BANK_HOST};
 use econ_graph_metrics::crawler::CRAWLER_METRICS;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::Deserialize;
+use serde_json::Value;
 use uuid::Uuid;
 
-/// World Bank API response for indicators
-#[derive(Debug, Deserialize)]
-pub struct WorldBankIndicatorsResponse {
-    pub indicator: Vec<WorldBankIndicator>,
-}
+/// World Bank Indicators API base URL
+const WORLD_BANK_API_URL: &str = "https://api.worldbank.org/v2";
+
+/// Records requested per page
+const WORLD_BANK_PAGE_SIZE: u32 = 1000;
+
+/// Most pages fetched for one listing, in case the API reports a bogus page count
+const MAX_PAGES: u32 = 500;
+
+/// Value stored in `global_indicator_data.data_source`
+///
+/// The name of the World Bank data source, so its access policy applies.
+const WORLD_BANK_DATA_SOURCE: &str = "World Bank Open Data";
+
+/// Region the API assigns to aggregates such as "World" or "Euro area"
+const AGGREGATE_REGION: &str = "Aggregates";
 
 #[derive(Debug, Deserialize)]
 pub struct WorldBankIndicator {
@@ -31,6 +63,144 @@ pub struct WorldBankSource {
     pub value: String,
 }
 
+/// Reference to a region, income level or other code list entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorldBankRef {
+    pub id: String,
+    pub value: String,
+}
+
+/// Country (or aggregate) from the `/country` listing
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorldBankCountry {
+    /// ISO 3166-1 alpha-3 code
+    pub id: String,
+    #[serde(rename = "iso2Code")]
+    pub iso2_code: String,
+    pub name: String,
+    pub region: WorldBankRef,
+    #[serde(rename = "incomeLevel")]
+    pub income_level: Option<WorldBankRef>,
+    pub longitude: Option<String>,
+    pub latitude: Option<String>,
+}
+
+impl WorldBankCountry {
+    /// Whether this is a real country rather than a regional or income aggregate
+    pub fn is_country(&self) -> bool {
+        self.region.value.trim() != AGGREGATE_REGION
+            && self.id.len() == 3
+            && self.iso2_code.len() == 2
+    }
+}
+
+/// One observation from `/country/{code}/indicator/{id}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorldBankObservation {
+    pub indicator: WorldBankRef,
+    #[serde(rename = "countryiso3code")]
+    pub country_iso3_code: String,
+    pub date: String,
+    pub value: Option<serde_json::Number>,
+}
+
+/// Paging header returned as the first element of every response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldBankPage {
+    pub page: u32,
+    pub pages: u32,
+    pub total: u32,
+}
+
+/// Indicator downloaded for every country for cross-country analysis
+#[derive(Debug, Clone, Copy)]
+pub struct CrossCountryIndicator {
+    pub code: &'static str,
+    /// Global analysis category, e.g. "GDP" or "Inflation"
+    pub category: &'static str,
+    pub subcategory: &'static str,
+    pub unit: &'static str,
+}
+
+/// Indicators collected per country
+///
+/// Categories match [`econ_graph_core::models::IndicatorCategory`] so the
+/// global analysis service can find them.
+pub const CROSS_COUNTRY_INDICATORS: &[CrossCountryIndicator] = &[
+    CrossCountryIndicator {
+        code: "NY.GDP.MKTP.CD",
+        category: "GDP",
+        subcategory: "Nominal GDP",
+        unit: "USD",
+    },
+    CrossCountryIndicator {
+        code: "NY.GDP.MKTP.KD.ZG",
+        category: "GDP",
+        subcategory: "Real GDP growth",
+        unit: "Percent",
+    },
+    CrossCountryIndicator {
+        code: "NY.GDP.PCAP.CD",
+        category: "GDP",
+        subcategory: "GDP per capita",
+        unit: "USD",
+    },
+    CrossCountryIndicator {
+        code: "FP.CPI.TOTL.ZG",
+        category: "Inflation",
+        subcategory: "Consumer prices",
+        unit: "Percent",
+    },
+    CrossCountryIndicator {
+        code: "SL.UEM.TOTL.ZS",
+        category: "Employment",
+        subcategory: "Unemployment rate",
+        unit: "Percent of labor force",
+    },
+    CrossCountryIndicator {
+        code: "NE.TRD.GNFS.ZS",
+        category: "Trade",
+        subcategory: "Trade openness",
+        unit: "Percent of GDP",
+    },
+    CrossCountryIndicator {
+        code: "NE.EXP.GNFS.CD",
+        category: "Trade",
+        subcategory: "Exports",
+        unit: "USD",
+    },
+    CrossCountryIndicator {
+        code: "NE.IMP.GNFS.CD",
+        category: "Trade",
+        subcategory: "Imports",
+        unit: "USD",
+    },
+    CrossCountryIndicator {
+        code: "BN.CAB.XOKA.GD.ZS",
+        category: "Trade",
+        subcategory: "Current account balance",
+        unit: "Percent of GDP",
+    },
+    CrossCountryIndicator {
+        code: "FR.INR.RINR",
+        category: "MonetaryPolicy",
+        subcategory: "Real interest rate",
+        unit: "Percent",
+    },
+    CrossCountryIndicator {
+        code: "GC.DOD.TOTL.GD.ZS",
+        category: "FiscalPolicy",
+        subcategory: "Central government debt",
+        unit: "Percent of GDP",
+    },
+    CrossCountryIndicator {
+        code: "SP.POP.TOTL",
+        category: "Demographics",
+        subcategory: "Population",
+        unit: "Persons",
+    },
+];
+
 /// World Bank series information structure
 #[derive(Debug, Clone)]
 pub struct WorldBankSeriesInfo {
@@ -45,23 +215,11 @@ pub struct WorldBankSeriesInfo {
     pub end_date: Option<String>,
 }
 
-/// Detailed metadata for a World Bank indicator
-#[derive(Debug, Clone)]
-struct WorldBankIndicatorMetadata {
-    pub frequency: String,
-    pub units: String,
-    pub country: Option<String>,
-    pub start_date: Option<String>,
-    pub end_date: Option<String>,
-}
-
 /// Discover World Bank series using the World Bank Indicators API
 ///
-/// This function implements a comprehensive discovery mechanism that:
-/// 1. Fetches indicators from multiple sources (topics, pagination, direct lookup)
-/// 2. Validates that indicators have actual data
-/// 3. Extracts comprehensive metadata
-/// 4. Stores series information in the database
+/// Stores the indicator catalog, the country list and the per-country
+/// series for [`CROSS_COUNTRY_INDICATORS`]. Returns the external ids of all
+/// series stored.
 pub async fn discover_world_bank_series(
     client: &Client,
     pool: &DatabasePool,
@@ -71,91 +229,91 @@ pub async fn discover_world_bank_series(
 
     println!("Starting World Bank series discovery...");
 
-    // Strategy 1: Get indicators from multiple economic topics
-    let mut topic_indicators = Vec::new();
-
-    // Economy & Growth (ID 3)
-    let economy_indicators = fetch_indicators_by_topic(client, "3").await?;
-    println!(
-        "Found {} indicators from Economy & Growth topic",
-        economy_indicators.len()
-    );
-    topic_indicators.extend(economy_indicators);
-
-    // Financial Sector (ID 7)
-    let financial_indicators = fetch_indicators_by_topic(client, "7").await?;
-    println!(
-        "Found {} indicators from Financial Sector topic",
-        financial_indicators.len()
-    );
-    topic_indicators.extend(financial_indicators);
-
-    // Trade (ID 11) - if available
-    if let Ok(trade_indicators) = fetch_indicators_by_topic(client, "11").await {
-        println!(
-            "Found {} indicators from Trade topic",
-            trade_indicators.len()
-        );
-        topic_indicators.extend(trade_indicators);
+    // Indicator catalog from economic topics and the full listing
+    let mut all_indicators = Vec::new();
+    for (topic_id, topic_name) in [
+        ("3", "Economy & Growth"),
+        ("7", "Financial Sector"),
+        ("21", "Trade"),
+    ] {
+        match fetch_indicators(client, &format!("topic/{}/indicator", topic_id)).await {
+            Ok(indicators) => {
+                println!(
+                    "Found {} indicators from {} topic",
+                    indicators.len(),
+                    topic_name
+                );
+                all_indicators.extend(indicators);
+            }
+            Err(e) => println!("Warning: {} topic unavailable: {}", topic_name, e),
+        }
     }
 
-    // Remove duplicates from topic indicators
-    topic_indicators.sort_by(|a, b| a.id.cmp(&b.id));
-    topic_indicators.dedup_by(|a, b| a.id == b.id);
-
+    let listed_indicators = fetch_indicators(client, "indicator").await?;
     println!(
-        "Total unique indicators from all topics: {}",
-        topic_indicators.len()
+        "Found {} indicators in the indicator listing",
+        listed_indicators.len()
     );
+    all_indicators.extend(listed_indicators.into_iter().filter(is_economic_indicator));
 
-    // Strategy 2: Get key economic indicators by direct lookup
-    let key_indicators = fetch_key_economic_indicators(client).await?;
-    println!("Found {} key economic indicators", key_indicators.len());
-
-    // Strategy 3: Get indicators from major countries (US, China, Germany, Japan, UK)
-    let country_indicators = fetch_indicators_for_major_countries(client).await?;
-    println!(
-        "Found {} indicators from major countries",
-        country_indicators.len()
-    );
-
-    // Strategy 4: Get indicators from paginated search (limited to avoid overwhelming)
-    let paginated_indicators = fetch_indicators_paginated(client, 10).await?; // First 10 pages
-    println!(
-        "Found {} indicators from paginated search",
-        paginated_indicators.len()
-    );
-
-    // Combine and deduplicate indicators
-    let mut all_indicators = topic_indicators;
-    all_indicators.extend(key_indicators);
-    all_indicators.extend(country_indicators);
-    all_indicators.extend(paginated_indicators);
-    // Remove duplicates based on indicator ID
     all_indicators.sort_by(|a, b| a.id.cmp(&b.id));
     all_indicators.dedup_by(|a, b| a.id == b.id);
-
     println!("Total unique indicators found: {}", all_indicators.len());
 
-    // Convert indicators to series and store in database
+    let mut indicator_names = HashMap::new();
     for indicator in all_indicators {
-        // Get detailed metadata for each indicator
-        let detailed_metadata = fetch_indicator_metadata(client, &indicator.id).await?;
         let series_info = WorldBankSeriesInfo {
             series_id: indicator.id.clone(),
             title: indicator.name.clone(),
             description: indicator.source_note.clone(),
-            frequency: detailed_metadata.frequency,
-            units: detailed_metadata.units,
+            // Nearly all World Development Indicators are annual
+            frequency: "Annual".to_string(),
+            units: "Various".to_string(),
             source: indicator.source.value.clone(),
-            country: detailed_metadata.country,
-            start_date: detailed_metadata.start_date,
-            end_date: detailed_metadata.end_date,
+            country: None,
+            start_date: Some("1960-01-01".to_string()),
+            end_date: None,
         };
 
-        // Store series metadata in database
         store_world_bank_series(pool, &world_bank_source.id, &series_info).await?;
         discovered_series.push(series_info.series_id);
+        indicator_names.insert(indicator.id, indicator.name);
+    }
+    CRAWLER_METRICS.record_items_collected(
+        "economic",
+        "world_bank",
+        "indicators",
+        discovered_series.len() as u64,
+    );
+
+    // Countries and their per-country series
+    let countries = store_countries(pool, fetch_countries(client).await?).await?;
+    println!("Stored {} World Bank countries", countries.len());
+
+    for indicator in CROSS_COUNTRY_INDICATORS {
+        match fetch_observations(client, indicator.code).await {
+            Ok(observations) => {
+                let name = indicator_names
+                    .get(indicator.code)
+                    .cloned()
+                    .or_else(|| observations.first().map(|o| o.indicator.value.clone()))
+                    .unwrap_or_else(|| indicator.code.to_string());
+                let stored = store_country_observations(
+                    pool,
+                    &world_bank_source.id,
+                    indicator,
+                    &name,
+                    &countries,
+                    observations,
+                )
+                .await?;
+                println!("Stored {} for {} countries", indicator.code, stored.len());
+                discovered_series.extend(stored);
+            }
+            Err(e) => {
+                println!("Warning: failed to download {}: {}", indicator.code, e);
+            }
+        }
     }
 
     println!(
@@ -165,286 +323,365 @@ pub async fn discover_world_bank_series(
     Ok(discovered_series)
 }
 
-/// Fetch indicators by topic ID (e.g., "3" for Economy & Growth)
-async fn fetch_indicators_by_topic(
+/// Fetch every indicator under `path` (e.g. `indicator` or `topic/3/indicator`)
+async fn fetch_indicators(client: &Client, path: &str) -> AppResult<Vec<WorldBankIndicator>> {
+    let records = fetch_all_pages(client, path, "").await?;
+    Ok(parse_records(records, "indicator"))
+}
+
+/// Fetch all countries and aggregates
+async fn fetch_countries(client: &Client) -> AppResult<Vec<WorldBankCountry>> {
+    let records = fetch_all_pages(client, "country", "").await?;
+    Ok(parse_records(records, "country"))
+}
+
+/// Fetch an indicator's observations for all countries
+async fn fetch_observations(
+    client: &Client,
+    indicator_code: &str,
+) -> AppResult<Vec<WorldBankObservation>> {
+    let path = format!("country/all/indicator/{}", indicator_code);
+    let records = fetch_all_pages(client, &path, "&date=1960:2100").await?;
+    Ok(parse_records(records, "observation"))
+}
+
+/// Fetch every page of a listing, following the page count in the response header
+async fn fetch_all_pages(client: &Client, path: &str, query: &str) -> AppResult<Vec<Value>> {
+    let (first, mut records) = fetch_page(client, path, query, 1).await?;
+
+    for page in 2..=first.pages.min(MAX_PAGES) {
+        let (_, page_records) = fetch_page(client, path, query, page).await?;
+        records.extend(page_records);
+    }
+
+    Ok(records)
+}
+
+/// Fetch one page of a listing
+async fn fetch_page(
     client: &Client,
-    topic_id: &str,
-) -> AppResult<Vec<WorldBankIndicator>> {
+    path: &str,
+    query: &str,
+    page: u32,
+) -> AppResult<(WorldBankPage, Vec<Value>)> {
     let url = format!(
-        "https://api.worldbank.org/v2/topic/{}/indicator?format=json&per_page=1000",
-        topic_id
+        "{}/{}?format=json&per_page={}&page={}{}",
+        WORLD_BANK_API_URL, path, WORLD_BANK_PAGE_SIZE, page, query
     );
 
+    shared_rate_limiter().acquire(WORLD_BANK_HOST, None).await;
     let start = std::time::Instant::now();
     let response = client.get(&url).send().await.map_err(|e| {
-        AppError::ExternalApiError(format!("World Bank topic indicators request failed: {}", e))
+        CRAWLER_METRICS.record_error("economic", "world_bank", "network");
+        AppError::ExternalApiError(format!("World Bank request for {} failed: {}", path, e))
     })?;
     let duration = start.elapsed().as_secs_f64();
     let status = response.status();
     CRAWLER_METRICS.record_request(
         "economic",
         "world_bank",
-        "/topic/indicator",
+        endpoint_label(path),
         status.as_str(),
         duration,
     );
+
     if !status.is_success() {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            CRAWLER_METRICS.record_rate_limit_hit("economic", "world_bank");
+        }
+        CRAWLER_METRICS.record_error("economic", "world_bank", "http_error");
         return Err(AppError::ExternalApiError(format!(
-            "World Bank API returned status: {}",
-            status
+            "World Bank API returned status {} for {}",
+            status, path
         )));
     }
 
-    let json_response: serde_json::Value = response.json().await.map_err(|e| {
+    let json_response: Value = response.json().await.map_err(|e| {
+        CRAWLER_METRICS.record_error("economic", "world_bank", "parse_error");
         AppError::ExternalApiError(format!("Failed to parse World Bank response: {}", e))
     })?;
 
-    extract_indicators_from_response(json_response)
+    split_response(json_response)
 }
 
-/// Fetch indicators for major countries to discover country-specific economic data
-async fn fetch_indicators_for_major_countries(
-    client: &Client,
-) -> AppResult<Vec<WorldBankIndicator>> {
-    // List of major countries/regions to fetch indicators for
-    let major_countries = vec![
-        "US", // United States
-        "CN", // China
-        "DE", // Germany
-        "JP", // Japan
-        "GB", // United Kingdom
-        "FR", // France
-        "IT", // Italy
-        "CA", // Canada
-        "AU", // Australia
-        "BR", // Brazil
-        "IN", // India
-        "RU", // Russia
-        "ZA", // South Africa
-        "MX", // Mexico
-        "KR", // South Korea
-    ];
-
-    let mut all_indicators = Vec::new();
+/// Metrics label for a request path, with codes replaced by placeholders
+fn endpoint_label(path: &str) -> &'static str {
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        ["indicator"] => "/indicator",
+        ["topic", _, "indicator"] => "/topic/{id}/indicator",
+        ["country"] => "/country",
+        ["country", _, "indicator", _] => "/country/{code}/indicator/{id}",
+        _ => "other",
+    }
+}
 
-    for country_code in major_countries {
-        // Get a sample of indicators for this country by fetching data for a common indicator
-        // This will help us discover what indicators are available for this country
-        if let Ok(indicators) = fetch_country_indicators_sample(client, country_code).await {
-            all_indicators.extend(indicators);
+/// Split a response into its paging header and records
+///
+/// Responses are `[header, records]`. Header numbers are sometimes sent as
+/// strings, `records` is null when nothing matched, and errors come back as
+/// `[{"message": [...]}]`.
+pub fn split_response(json_response: Value) -> AppResult<(WorldBankPage, Vec<Value>)> {
+    let mut parts = match json_response {
+        Value::Array(parts) => parts.into_iter(),
+        _ => {
+            return Err(AppError::ExternalApiError(
+                "World Bank API response is not an array".to_string(),
+            ))
         }
+    };
 
-        // Add delay to be polite to the API
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let header = parts.next().unwrap_or(Value::Null);
+    if let Some(message) = header.get("message") {
+        return Err(AppError::ExternalApiError(format!(
+            "World Bank API error: {}",
+            message
+        )));
     }
 
-    // Remove duplicates
-    all_indicators.sort_by(|a, b| a.id.cmp(&b.id));
-    all_indicators.dedup_by(|a, b| a.id == b.id);
+    let number = |field: &str| -> u32 {
+        match header.get(field) {
+            Some(Value::Number(n)) => n.as_u64().unwrap_or(0) as u32,
+            Some(Value::String(s)) => s.parse().unwrap_or(0),
+            _ => 0,
+        }
+    };
+    let page = WorldBankPage {
+        page: number("page"),
+        pages: number("pages"),
+        total: number("total"),
+    };
 
-    Ok(all_indicators)
-}
+    let records = match parts.next() {
+        Some(Value::Array(records)) => records,
+        _ => Vec::new(),
+    };
 
-/// Fetch a sample of indicators available for a specific country
-async fn fetch_country_indicators_sample(
-    client: &Client,
-    country_code: &str,
-) -> AppResult<Vec<WorldBankIndicator>> {
-    // Use a common economic indicator to discover what's available for this country
-    let test_indicators = vec![
-        "NY.GDP.MKTP.CD",    // GDP (current US$)
-        "FP.CPI.TOTL.ZG",    // Inflation, consumer prices (annual %)
-        "SL.UEM.TOTL.ZS",    // Unemployment, total (% of total labor force)
-        "NE.TRD.GNFS.ZS",    // Trade (% of GDP)
-        "GC.DOD.TOTL.GD.ZS", // Central government debt, total (% of GDP)
-    ];
+    Ok((page, records))
+}
 
-    let mut indicators = Vec::new();
+/// Deserialize records, skipping (and counting) any that don't match the expected shape
+fn parse_records<T: serde::de::DeserializeOwned>(records: Vec<Value>, kind: &str) -> Vec<T> {
+    let total = records.len();
+    let parsed: Vec<T> = records
+        .into_iter()
+        .filter_map(|record| serde_json::from_value(record).ok())
+        .collect();
 
-    for indicator_id in test_indicators {
-        // Try to fetch data for this indicator in this country
-        let url = format!(
-            "https://api.worldbank.org/v2/country/{}/indicator/{}?format=json&per_page=1",
-            country_code, indicator_id
+    let skipped = total - parsed.len();
+    if skipped > 0 {
+        println!(
+            "Warning: skipped {} malformed World Bank {} records",
+            skipped, kind
         );
+        CRAWLER_METRICS.record_error("economic", "world_bank", "parse_error");
+    }
 
-        if let Ok(response) = client.get(&url).send().await {
-            if response.status().is_success() {
-                if let Ok(json_response) = response.json::<serde_json::Value>().await {
-                    // If we get data, this indicator is available for this country
-                    if let Some(array) = json_response.as_array() {
-                        if array.len() >= 2
-                            && array[1].as_array().map_or(false, |arr| !arr.is_empty())
-                        {
-                            // Create a WorldBankIndicator for this combination
-                            let indicator = WorldBankIndicator {
-                                id: indicator_id.to_string(),
-                                name: format!("{} for {}", indicator_id, country_code),
-                                source: WorldBankSource {
-                                    id: "2".to_string(),
-                                    value: "World Development Indicators".to_string(),
-                                },
-                                source_note: Some(format!(
-                                    "Available for country: {}",
-                                    country_code
-                                )),
-                                source_organization: Some("World Bank".to_string()),
-                            };
-                            indicators.push(indicator);
-                        }
-                    }
-                }
-            }
-        }
+    parsed
+}
+
+/// Observation date for a World Bank period: `2023`, `2023Q2` or `2023M04`
+///
+/// Periods are dated on their last day, like other annual and quarterly series.
+pub fn parse_observation_date(period: &str) -> Option<NaiveDate> {
+    let year: i32 = period.get(..4)?.parse().ok()?;
+    let month = match (period.get(4..5), period.get(5..)) {
+        (None, _) => 12,
+        (Some("Q"), Some(quarter)) => match quarter.parse::<u32>().ok()? {
+            quarter @ 1..=4 => quarter * 3,
+            _ => return None,
+        },
+        (Some("M"), Some(month)) => match month.parse::<u32>().ok()? {
+            month @ 1..=12 => month,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let first_of_next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    first_of_next_month.pred_opt()
+}
+
+/// Frequency name for a World Bank period
+fn period_frequency(period: &str) -> &'static str {
+    match period.get(4..5) {
+        Some("Q") => "Quarterly",
+        Some("M") => "Monthly",
+        _ => "Annual",
     }
+}
 
-    Ok(indicators)
+/// External id of a country's series, e.g. `USA.NY.GDP.MKTP.CD`
+pub fn country_series_id(country_iso3_code: &str, indicator_code: &str) -> String {
+    format!("{}.{}", country_iso3_code, indicator_code)
 }
 
-/// Fetch key economic indicators by direct lookup
-async fn fetch_key_economic_indicators(client: &Client) -> AppResult<Vec<WorldBankIndicator>> {
-    // List of key economic indicators to fetch directly
-    let key_indicator_ids = vec![
-        "NY.GDP.MKTP.CD",    // GDP (current US$)
-        "NY.GDP.MKTP.KD.ZG", // GDP growth (annual %)
-        "FP.CPI.TOTL.ZG",    // Inflation, consumer prices (annual %)
-        "SL.UEM.TOTL.ZS",    // Unemployment, total (% of total labor force)
-        "FR.INR.RINR",       // Real interest rate (%)
-        "NE.TRD.GNFS.ZS",    // Trade (% of GDP)
-        "GC.DOD.TOTL.GD.ZS", // Central government debt, total (% of GDP)
-        "GC.REV.XGRT.GD.ZS", // Tax revenue (% of GDP)
-        "GC.XPN.TOTL.GD.ZS", // Expense (% of GDP)
-        "BN.CAB.XOKA.GD.ZS", // Current account balance (% of GDP)
-    ];
+/// Store the countries (not aggregates), keyed by ISO alpha-3 code
+async fn store_countries(
+    pool: &DatabasePool,
+    countries: Vec<WorldBankCountry>,
+) -> AppResult<HashMap<String, Country>> {
+    let mut stored = HashMap::new();
+
+    for country in countries.into_iter().filter(WorldBankCountry::is_country) {
+        let new_country = NewCountry {
+            iso_code: country.id.clone(),
+            iso_code_2: country.iso2_code.clone(),
+            name: country.name.clone(),
+            region: country.region.value.trim().to_string(),
+            sub_region: None,
+            income_group: country
+                .income_level
+                .as_ref()
+                .map(|level| level.value.trim().to_string())
+                .filter(|level| !level.is_empty()),
+            population: None,
+            gdp_usd: None,
+            gdp_per_capita_usd: None,
+            latitude: country
+                .latitude
+                .as_deref()
+                .and_then(|v| BigDecimal::from_str(v).ok()),
+            longitude: country
+                .longitude
+                .as_deref()
+                .and_then(|v| BigDecimal::from_str(v).ok()),
+            currency_code: None,
+            is_active: Some(true),
+        };
 
-    let mut indicators = Vec::new();
-    for indicator_id in key_indicator_ids {
-        if let Ok(indicator) = fetch_single_indicator(client, indicator_id).await {
-            indicators.push(indicator);
+        match Country::upsert(pool, &new_country).await {
+            Ok(country) => {
+                stored.insert(country.iso_code.clone(), country);
+            }
+            Err(e) => {
+                // Usually an alpha-2 code already used by another seeded country
+                println!("Warning: could not store country {}: {}", country.id, e);
+            }
         }
     }
 
-    Ok(indicators)
+    Ok(stored)
 }
 
-/// Fetch indicators from paginated search
-async fn fetch_indicators_paginated(
-    client: &Client,
-    max_pages: usize,
-) -> AppResult<Vec<WorldBankIndicator>> {
-    let mut all_indicators = Vec::new();
-    for page in 1..=max_pages {
-        let url = format!(
-            "https://api.worldbank.org/v2/indicator?format=json&per_page=100&page={}",
-            page
-        );
+/// Store one indicator's observations as per-country series and global indicator data
+///
+/// Returns the external ids of the series stored.
+async fn store_country_observations(
+    pool: &DatabasePool,
+    source_id: &Uuid,
+    indicator: &CrossCountryIndicator,
+    indicator_name: &str,
+    countries: &HashMap<String, Country>,
+    observations: Vec<WorldBankObservation>,
+) -> AppResult<Vec<String>> {
+    let mut by_country: HashMap<&str, Vec<(NaiveDate, Option<BigDecimal>, &'static str)>> =
+        HashMap::new();
+    for observation in &observations {
+        let Some(country) = countries.get(&observation.country_iso3_code) else {
+            continue;
+        };
+        let Some(date) = parse_observation_date(&observation.date) else {
+            continue;
+        };
+        let value = observation
+            .value
+            .as_ref()
+            .and_then(|v| BigDecimal::from_str(&v.to_string()).ok());
+        by_country
+            .entry(country.iso_code.as_str())
+            .or_default()
+            .push((date, value, period_frequency(&observation.date)));
+    }
 
-        let start = std::time::Instant::now();
-        let response = client.get(&url).send().await.map_err(|e| {
-            AppError::ExternalApiError(format!("World Bank paginated request failed: {}", e))
-        })?;
-        let duration = start.elapsed().as_secs_f64();
-        let status = response.status();
-        CRAWLER_METRICS.record_request(
-            "economic",
-            "world_bank",
-            "/indicator",
-            status.as_str(),
-            duration,
-        );
-        if !status.is_success() {
-            println!(
-                "Warning: World Bank API returned status {} for page {}",
-                status, page
-            );
+    let mut stored = Vec::new();
+    let mut observations_stored = 0u64;
+    for (iso_code, rows) in by_country {
+        let observed: Vec<NaiveDate> = rows
+            .iter()
+            .filter(|(_, value, _)| value.is_some())
+            .map(|(date, _, _)| *date)
+            .collect();
+        let (Some(start_date), Some(end_date)) = (
+            observed.iter().min().copied(),
+            observed.iter().max().copied(),
+        ) else {
+            // No values reported for this country
             continue;
+        };
+        let country = &countries[iso_code];
+        let frequency = rows[0].2;
+
+        let external_id = country_series_id(iso_code, indicator.code);
+        let new_series = NewEconomicSeries {
+            source_id: *source_id,
+            external_id: external_id.clone(),
+            title: format!("{} - {}", indicator_name, country.name),
+            description: Some(format!(
+                "{} for {} from the World Bank World Development Indicators",
+                indicator_name, country.name
+            )),
+            units: Some(indicator.unit.to_string()),
+            frequency: frequency.to_string(),
+            seasonal_adjustment: None,
+            start_date: Some(start_date),
+            end_date: Some(end_date),
+            is_active: true,
+            first_discovered_at: Some(chrono::Utc::now()),
+            last_crawled_at: None,
+            first_missing_date: None,
+            crawl_status: None,
+            crawl_error_message: None,
+        };
+        let series =
+            EconomicSeries::get_or_create(pool, &external_id, *source_id, &new_series).await?;
+        if series.start_date != Some(start_date) || series.end_date != Some(end_date) {
+            EconomicSeries::update_date_range(pool, series.id, start_date, end_date).await?;
         }
 
-        let json_response: serde_json::Value = response.json().await.map_err(|e| {
-            AppError::ExternalApiError(format!("Failed to parse World Bank response: {}", e))
-        })?;
-
-        let page_indicators = extract_indicators_from_response(json_response)?;
-
-        // Filter for economic indicators
-        let economic_indicators: Vec<WorldBankIndicator> = page_indicators
+        let global_indicator = GlobalEconomicIndicator::upsert(
+            pool,
+            &NewGlobalEconomicIndicator {
+                country_id: country.id,
+                indicator_code: indicator.code.to_string(),
+                indicator_name: indicator_name.chars().take(500).collect(),
+                category: indicator.category.to_string(),
+                subcategory: Some(indicator.subcategory.to_string()),
+                unit: Some(indicator.unit.to_string()),
+                frequency: frequency.to_string(),
+            },
+        )
+        .await?;
+
+        let current_year = chrono::Utc::now().year();
+        let data: Vec<NewGlobalIndicatorData> = rows
             .into_iter()
-            .filter(is_economic_indicator)
+            .map(|(date, value, _)| NewGlobalIndicatorData {
+                indicator_id: global_indicator.id,
+                date,
+                value,
+                // Latest-year figures are usually revised
+                is_preliminary: Some(date.year() >= current_year - 1),
+                data_source: WORLD_BANK_DATA_SOURCE.to_string(),
+            })
             .collect();
+        observations_stored += GlobalIndicatorData::upsert_batch(pool, &data).await? as u64;
 
-        all_indicators.extend(economic_indicators);
-        // Add delay to be polite to the API
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        stored.push(external_id);
     }
 
-    Ok(all_indicators)
-}
-
-/// Fetch a single indicator by ID
-async fn fetch_single_indicator(
-    client: &Client,
-    indicator_id: &str,
-) -> AppResult<WorldBankIndicator> {
-    let url = format!(
-        "https://api.worldbank.org/v2/indicator/{}?format=json",
-        indicator_id
-    );
-
-    let start = std::time::Instant::now();
-    let response = client.get(&url).send().await.map_err(|e| {
-        AppError::ExternalApiError(format!("World Bank single indicator request failed: {}", e))
-    })?;
-    let duration = start.elapsed().as_secs_f64();
-    let status = response.status();
-    CRAWLER_METRICS.record_request(
+    CRAWLER_METRICS.record_items_collected("economic", "world_bank", "series", stored.len() as u64);
+    CRAWLER_METRICS.record_items_collected(
         "economic",
         "world_bank",
-        "/indicator/{id}",
-        status.as_str(),
-        duration,
+        "observations",
+        observations_stored,
     );
-    if !status.is_success() {
-        return Err(AppError::ExternalApiError(format!(
-            "World Bank API returned status: {}",
-            status
-        )));
-    }
-
-    let json_response: serde_json::Value = response.json().await.map_err(|e| {
-        AppError::ExternalApiError(format!("Failed to parse World Bank response: {}", e))
-    })?;
 
-    let indicators = extract_indicators_from_response(json_response)?;
-    indicators.into_iter().next().ok_or_else(|| {
-        AppError::ExternalApiError(format!("No indicator found for ID: {}", indicator_id))
-    })
-}
-
-/// Extract indicators from World Bank API response
-fn extract_indicators_from_response(
-    json_response: serde_json::Value,
-) -> AppResult<Vec<WorldBankIndicator>> {
-    if let Some(array) = json_response.as_array() {
-        if array.len() >= 2 {
-            // Second element contains the actual data
-            Ok(
-                serde_json::from_value::<WorldBankIndicatorsResponse>(array[1].clone())
-                    .map_err(|e| {
-                        AppError::ExternalApiError(format!(
-                            "Failed to parse World Bank indicators: {}",
-                            e
-                        ))
-                    })?
-                    .indicator,
-            )
-        } else {
-            Ok(Vec::new())
-        }
-    } else {
-        Err(AppError::ExternalApiError(
-            "World Bank API response is not an array".to_string(),
-        ))
-    }
+    Ok(stored)
 }
 
 /// Check if an indicator is economic-related
@@ -503,23 +740,6 @@ fn is_economic_indicator(indicator: &WorldBankIndicator) -> bool {
     has_economic_keyword || has_economic_id_pattern
 }
 
-/// Fetch detailed metadata for a specific indicator
-async fn fetch_indicator_metadata(
-    client: &Client,
-    indicator_id: &str,
-) -> AppResult<WorldBankIndicatorMetadata> {
-    // For now, return default metadata since World Bank API doesn't provide
-    // detailed frequency/unit information in the indicator endpoint
-    // In a real implementation, we might need to fetch data samples to determine this
-    Ok(WorldBankIndicatorMetadata {
-        frequency: "Annual".to_string(), // Most World Bank data is annual
-        units: "Various".to_string(),    // World Bank uses various units
-        country: None,                   // Global indicators
-        start_date: Some("1960-01-01".to_string()), // World Bank data typically starts around 1960
-        end_date: None,
-    })
-}
-
 /// Store World Bank series metadata in database
 async fn store_world_bank_series(
     pool: &DatabasePool,
@@ -1,8 +1,10 @@
 //! Comprehensive tests for World Bank API integration
 
 use crate::services::series_discovery::world_bank::{
-    is_economic_indicator, WorldBankIndicator, WorldBankSeriesInfo,
+    country_series_id, is_economic_indicator, parse_observation_date, split_response,
+    WorldBankCountry, WorldBankIndicator, WorldBankPage, WorldBankSeriesInfo,
 };
+use chrono::NaiveDate;
 use econ_graph_core::models::{DataSource, EconomicSeries};
 use econ_graph_core::test_utils::TestContainer;
 use reqwest::Client;
@@ -94,6 +96,90 @@ fn test_is_economic_indicator() {
     assert!(is_economic_indicator(&trade_indicator));
 }
 
+/// Test paging header and record extraction (unit test - no network)
+#[test]
+fn test_split_response_reads_paging_header() {
+    // Header numbers arrive as a mix of numbers and strings
+    let response = serde_json::json!([
+        { "page": 1, "pages": "3", "per_page": "1000", "total": 2500 },
+        [{ "id": "NY.GDP.MKTP.CD" }, { "id": "FP.CPI.TOTL.ZG" }]
+    ]);
+    let (page, records) = split_response(response).unwrap();
+    assert_eq!(
+        page,
+        WorldBankPage {
+            page: 1,
+            pages: 3,
+            total: 2500
+        }
+    );
+    assert_eq!(records.len(), 2);
+
+    // No matching records
+    let (page, records) =
+        split_response(serde_json::json!([{ "page": 1, "pages": 0, "total": 0 }, null])).unwrap();
+    assert_eq!(page.pages, 0);
+    assert!(records.is_empty());
+
+    // API errors are reported instead of treated as empty pages
+    let error = serde_json::json!([{ "message": [{ "id": "120", "value": "Invalid value" }] }]);
+    assert!(split_response(error).is_err());
+}
+
+/// Test World Bank period parsing (unit test - no network)
+#[test]
+fn test_parse_observation_date() {
+    assert_eq!(
+        parse_observation_date("2023"),
+        NaiveDate::from_ymd_opt(2023, 12, 31)
+    );
+    assert_eq!(
+        parse_observation_date("2024Q1"),
+        NaiveDate::from_ymd_opt(2024, 3, 31)
+    );
+    assert_eq!(
+        parse_observation_date("2024M02"),
+        NaiveDate::from_ymd_opt(2024, 2, 29)
+    );
+    assert_eq!(parse_observation_date("2024Q5"), None);
+    assert_eq!(parse_observation_date("n/a"), None);
+}
+
+/// Test that aggregates are kept out of the country list (unit test - no network)
+#[test]
+fn test_country_filtering_and_series_ids() {
+    let country: WorldBankCountry = serde_json::from_value(serde_json::json!({
+        "id": "USA",
+        "iso2Code": "US",
+        "name": "United States",
+        "region": { "id": "NAC", "iso2code": "XU", "value": "North America" },
+        "incomeLevel": { "id": "HIC", "iso2code": "XD", "value": "High income" },
+        "capitalCity": "Washington D.C.",
+        "longitude": "-77.032",
+        "latitude": "38.8895"
+    }))
+    .unwrap();
+    assert!(country.is_country());
+
+    let aggregate: WorldBankCountry = serde_json::from_value(serde_json::json!({
+        "id": "WLD",
+        "iso2Code": "1W",
+        "name": "World",
+        "region": { "id": "NA", "iso2code": "NA", "value": "Aggregates" },
+        "incomeLevel": { "id": "NA", "iso2code": "NA", "value": "Aggregates" },
+        "capitalCity": "",
+        "longitude": "",
+        "latitude": ""
+    }))
+    .unwrap();
+    assert!(!aggregate.is_country());
+
+    assert_eq!(
+        country_series_id(&country.id, "NY.GDP.MKTP.CD"),
+        "USA.NY.GDP.MKTP.CD"
+    );
+}
+
 /// Test World Bank series metadata storage
 #[tokio::test]
 #[serial]
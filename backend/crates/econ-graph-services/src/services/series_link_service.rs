@@ -0,0 +1,433 @@
+/**
+ * REQUIREMENT: The same indicator published by several sources (GDP from FRED, the World Bank
+ * and Eurostat) can be found from any one of them
+ * PURPOSE: Let curators link equivalent series across data sources, suggest links with a
+ * title matcher, and list a series' equivalents in the order a user prefers them
+ * Equivalence follows links transitively, so a group only needs a chain of links
+ */
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, HashMap};
+use tracing::info;
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        equivalents_from_links, EconomicSeries, EquivalentSeries, NewSeriesLink, SeriesLink,
+        SeriesLinkSource,
+    },
+    schema::{economic_series, user_data_source_preferences},
+};
+
+/// Lowest title similarity at which the matcher links two series by default
+pub const DEFAULT_MIN_MATCH_CONFIDENCE: f64 = 0.6;
+
+/// Largest number of candidate series the matcher compares a series against
+const MAX_MATCH_CANDIDATES: i64 = 200;
+
+/// Words that say nothing about what a series measures
+const TITLE_STOPWORDS: &[&str] = &[
+    "a", "all", "an", "and", "at", "by", "for", "from", "in", "of", "on", "the", "to", "total",
+    "with",
+];
+
+/// A user's settings for a data source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePreference {
+    pub is_visible: bool,
+    pub is_favorite: bool,
+}
+
+impl Default for SourcePreference {
+    fn default() -> Self {
+        Self {
+            is_visible: true,
+            is_favorite: false,
+        }
+    }
+}
+
+/// A series equivalent to the requested one
+#[derive(Debug, Clone)]
+pub struct EquivalentSeriesMatch {
+    pub series: EconomicSeries,
+    pub confidence: f64,
+    pub curated: bool,
+    pub hops: usize,
+}
+
+/// Significant lowercase words of a series title
+pub fn title_tokens(title: &str) -> BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|token| !token.is_empty() && !TITLE_STOPWORDS.contains(&token.as_str()))
+        .collect()
+}
+
+/// How alike two series titles are, from 0 (no shared words) to 1 (same words)
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = title_tokens(a);
+    let b = title_tokens(b);
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Confidence the matcher has that `candidate` measures the same thing as `series`
+///
+/// Only series from another source with the same frequency qualify, and
+/// seasonally adjusted data never matches unadjusted data.
+pub fn match_confidence(series: &EconomicSeries, candidate: &EconomicSeries) -> Option<f64> {
+    if series.id == candidate.id
+        || series.source_id == candidate.source_id
+        || !series.frequency.eq_ignore_ascii_case(&candidate.frequency)
+    {
+        return None;
+    }
+    if let (Some(a), Some(b)) = (&series.seasonal_adjustment, &candidate.seasonal_adjustment) {
+        if !a.eq_ignore_ascii_case(b) {
+            return None;
+        }
+    }
+
+    let similarity = title_similarity(&series.title, &candidate.title);
+    (similarity > 0.0).then_some(similarity)
+}
+
+/// Order equivalent series from most to least preferred
+///
+/// Series from the user's favorite sources come first and series from
+/// sources they hid come last. Within that, active series beat discontinued
+/// ones, curated links beat heuristic ones, then higher confidence, more
+/// recent data, closer links and finally the title decide.
+pub fn order_by_preference(
+    matches: &mut [EquivalentSeriesMatch],
+    preferences: &HashMap<Uuid, SourcePreference>,
+) {
+    let preference = |m: &EquivalentSeriesMatch| {
+        preferences
+            .get(&m.series.source_id)
+            .copied()
+            .unwrap_or_default()
+    };
+
+    matches.sort_by(|a, b| {
+        let (pa, pb) = (preference(a), preference(b));
+        pb.is_visible
+            .cmp(&pa.is_visible)
+            .then_with(|| pb.is_favorite.cmp(&pa.is_favorite))
+            .then_with(|| b.series.is_active.cmp(&a.series.is_active))
+            .then_with(|| b.curated.cmp(&a.curated))
+            .then_with(|| {
+                b.confidence
+                    .partial_cmp(&a.confidence)
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| Reverse(a.series.end_date).cmp(&Reverse(b.series.end_date)))
+            .then_with(|| a.hops.cmp(&b.hops))
+            .then_with(|| a.series.title.cmp(&b.series.title))
+    });
+}
+
+/// Links equivalent series across data sources
+pub struct SeriesLinkService {
+    pool: DatabasePool,
+}
+
+impl SeriesLinkService {
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Series equivalent to `series_id`, most preferred first
+    ///
+    /// With a `user_id`, that user's favorite and hidden data sources shape
+    /// the order.
+    #[tracing::instrument(name = "series_links.equivalents", skip(self))]
+    pub async fn equivalent_series(
+        &self,
+        series_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> AppResult<Vec<EquivalentSeriesMatch>> {
+        let links = SeriesLink::find_connected(&self.pool, series_id).await?;
+        let equivalents = equivalents_from_links(series_id, &links);
+        if equivalents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut series = self
+            .load_series(equivalents.iter().map(|e| e.series_id).collect())
+            .await?;
+        let mut matches: Vec<EquivalentSeriesMatch> = equivalents
+            .into_iter()
+            .filter_map(|equivalent: EquivalentSeries| {
+                Some(EquivalentSeriesMatch {
+                    series: series.remove(&equivalent.series_id)?,
+                    confidence: equivalent.confidence,
+                    curated: equivalent.curated,
+                    hops: equivalent.hops,
+                })
+            })
+            .collect();
+
+        let preferences = match user_id {
+            Some(user_id) => self.source_preferences(user_id).await?,
+            None => HashMap::new(),
+        };
+        order_by_preference(&mut matches, &preferences);
+
+        Ok(matches)
+    }
+
+    /// Record a curator's decision that two series are equivalent
+    ///
+    /// Replaces a heuristic link between the same series.
+    #[tracing::instrument(name = "series_links.link", skip(self))]
+    pub async fn link(&self, a: Uuid, b: Uuid, curator_id: Uuid) -> AppResult<SeriesLink> {
+        let new_link = NewSeriesLink::new(a, b, SeriesLinkSource::Curated, 1.0, Some(curator_id))?;
+        let series = self.load_series(vec![a, b]).await?;
+        if series.len() < 2 {
+            return Err(AppError::NotFound("Series not found".to_string()));
+        }
+
+        SeriesLink::upsert_curated(&self.pool, &new_link).await
+    }
+
+    /// Remove the link between two series; returns whether one existed
+    #[tracing::instrument(name = "series_links.unlink", skip(self))]
+    pub async fn unlink(&self, a: Uuid, b: Uuid) -> AppResult<bool> {
+        SeriesLink::delete_pair(&self.pool, a, b).await
+    }
+
+    /// Link a series to similarly titled series from other sources
+    ///
+    /// Existing links, curated or not, are left alone. Returns the links
+    /// that were added.
+    #[tracing::instrument(name = "series_links.auto_link", skip(self))]
+    pub async fn auto_link(
+        &self,
+        series_id: Uuid,
+        min_confidence: f64,
+    ) -> AppResult<Vec<SeriesLink>> {
+        if !(min_confidence > 0.0 && min_confidence <= 1.0) {
+            return Err(AppError::ValidationError(
+                "Minimum confidence must be greater than 0 and at most 1".to_string(),
+            ));
+        }
+
+        let series = self
+            .load_series(vec![series_id])
+            .await?
+            .remove(&series_id)
+            .ok_or_else(|| AppError::NotFound("Series not found".to_string()))?;
+
+        let mut added = Vec::new();
+        for candidate in self.match_candidates(&series).await? {
+            let Some(confidence) = match_confidence(&series, &candidate) else {
+                continue;
+            };
+            if confidence < min_confidence {
+                continue;
+            }
+
+            let new_link = NewSeriesLink::new(
+                series.id,
+                candidate.id,
+                SeriesLinkSource::Heuristic,
+                confidence,
+                None,
+            )?;
+            if let Some(link) = SeriesLink::create_if_missing(&self.pool, &new_link).await? {
+                added.push(link);
+            }
+        }
+
+        info!(
+            series_id = %series_id,
+            links_added = added.len(),
+            "Linked series to similar series from other sources"
+        );
+        Ok(added)
+    }
+
+    /// Series from other sources that share the most distinctive title word
+    async fn match_candidates(&self, series: &EconomicSeries) -> AppResult<Vec<EconomicSeries>> {
+        let Some(keyword) = title_tokens(&series.title)
+            .into_iter()
+            .max_by_key(|token| token.len())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let candidates = economic_series::table
+            .filter(economic_series::source_id.ne(series.source_id))
+            .filter(economic_series::frequency.eq(&series.frequency))
+            .filter(economic_series::title.ilike(format!("%{}%", keyword)))
+            .limit(MAX_MATCH_CANDIDATES)
+            .select(EconomicSeries::as_select())
+            .load::<EconomicSeries>(&mut conn)
+            .await?;
+
+        Ok(candidates)
+    }
+
+    async fn load_series(&self, ids: Vec<Uuid>) -> AppResult<HashMap<Uuid, EconomicSeries>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let series = economic_series::table
+            .filter(economic_series::id.eq_any(ids))
+            .select(EconomicSeries::as_select())
+            .load::<EconomicSeries>(&mut conn)
+            .await?;
+
+        Ok(series.into_iter().map(|s| (s.id, s)).collect())
+    }
+
+    async fn source_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> AppResult<HashMap<Uuid, SourcePreference>> {
+        let mut conn = self.pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+
+        let preferences = user_data_source_preferences::table
+            .filter(user_data_source_preferences::user_id.eq(user_id))
+            .select((
+                user_data_source_preferences::data_source_id,
+                user_data_source_preferences::is_visible,
+                user_data_source_preferences::is_favorite,
+            ))
+            .load::<(Uuid, bool, bool)>(&mut conn)
+            .await?;
+
+        Ok(preferences
+            .into_iter()
+            .map(|(source_id, is_visible, is_favorite)| {
+                (
+                    source_id,
+                    SourcePreference {
+                        is_visible,
+                        is_favorite,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+
+    fn series(source_id: Uuid, title: &str, end_year: i32) -> EconomicSeries {
+        EconomicSeries {
+            id: Uuid::new_v4(),
+            source_id,
+            external_id: title.to_string(),
+            title: title.to_string(),
+            description: None,
+            units: None,
+            frequency: "Annual".to_string(),
+            seasonal_adjustment: None,
+            last_updated: None,
+            start_date: None,
+            end_date: NaiveDate::from_ymd_opt(end_year, 1, 1),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            first_discovered_at: None,
+            last_crawled_at: None,
+            first_missing_date: None,
+            crawl_status: None,
+            crawl_error_message: None,
+        }
+    }
+
+    fn heuristic(series: EconomicSeries, confidence: f64) -> EquivalentSeriesMatch {
+        EquivalentSeriesMatch {
+            series,
+            confidence,
+            curated: false,
+            hops: 1,
+        }
+    }
+
+    #[test]
+    fn test_match_confidence_compares_titles_across_sources() {
+        // REQUIREMENT: The matcher suggests equivalent series from other sources
+        // PURPOSE: Verify title similarity ignores filler words and skips same-source and SA/NSA pairs
+        // This ensures automatic links connect like with like
+
+        let fred = Uuid::new_v4();
+        let world_bank = Uuid::new_v4();
+        let gdp = series(fred, "Gross Domestic Product", 2024);
+        let wb_gdp = series(world_bank, "Gross domestic product, total", 2023);
+        let wb_gni = series(world_bank, "Gross national income", 2023);
+
+        assert_eq!(match_confidence(&gdp, &wb_gdp), Some(1.0));
+        assert_eq!(match_confidence(&gdp, &wb_gni), Some(0.2));
+        assert_eq!(
+            match_confidence(&gdp, &series(fred, "Gross Domestic Product", 2024)),
+            None
+        );
+
+        let mut adjusted = gdp.clone();
+        adjusted.seasonal_adjustment = Some("Seasonally Adjusted".to_string());
+        let mut unadjusted = wb_gdp.clone();
+        unadjusted.seasonal_adjustment = Some("Not Seasonally Adjusted".to_string());
+        assert_eq!(match_confidence(&adjusted, &unadjusted), None);
+    }
+
+    #[test]
+    fn test_order_by_preference_respects_user_sources() {
+        // REQUIREMENT: Users see equivalents from the sources they prefer first
+        // PURPOSE: Verify favorites lead, hidden sources trail, and curated links beat heuristic ones
+        // This ensures equivalentSeries suggests the series a user is most likely to want
+
+        let favorite_source = Uuid::new_v4();
+        let hidden_source = Uuid::new_v4();
+        let other_source = Uuid::new_v4();
+
+        let hidden = heuristic(series(hidden_source, "Hidden", 2024), 1.0);
+        let favorite = heuristic(series(favorite_source, "Favorite", 2020), 0.6);
+        let mut curated = heuristic(series(other_source, "Curated", 2020), 1.0);
+        curated.curated = true;
+        let recent = heuristic(series(other_source, "Recent", 2024), 1.0);
+
+        let preferences = HashMap::from([
+            (
+                favorite_source,
+                SourcePreference {
+                    is_visible: true,
+                    is_favorite: true,
+                },
+            ),
+            (
+                hidden_source,
+                SourcePreference {
+                    is_visible: false,
+                    is_favorite: false,
+                },
+            ),
+        ]);
+
+        let mut matches = vec![hidden, recent, curated, favorite];
+        order_by_preference(&mut matches, &preferences);
+        let titles: Vec<&str> = matches.iter().map(|m| m.series.title.as_str()).collect();
+        assert_eq!(titles, vec!["Favorite", "Curated", "Recent", "Hidden"]);
+    }
+}
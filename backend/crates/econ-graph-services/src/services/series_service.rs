@@ -1,19 +1,25 @@
-use chrono::Datelike;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::SelectableHelper;
 use diesel_async::RunQueryDsl;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
 use econ_graph_core::{
-    database::DatabasePool,
+    database::{estimate_row_count, DatabasePool},
     error::{AppError, AppResult},
     models::{
         DataPoint, DataQueryParams, DataTransformation, EconomicSeries, SeriesSearchParams,
-        TransformedDataPoint,
+        TransformedDataPoint, DATA_POINT_STREAM_BATCH_SIZE,
     },
-    schema::{data_points, economic_series},
+    schema::{data_point_corrections, data_points, economic_series},
 };
 
+use crate::services::data_point_cache::{shared_data_point_cache, DataPointCacheKey};
+
 /// **List Economic Series with Filtering**
 ///
 /// Retrieves a filtered list of economic time series from the database based on search parameters.
@@ -79,28 +85,7 @@ pub async fn list_series(
         ))
     })?;
 
-    let mut query = economic_series::table
-        .filter(economic_series::is_active.eq(params.is_active.unwrap_or(true)))
-        .into_boxed();
-
-    // Apply filters
-    if let Some(source_id) = params.source_id {
-        query = query.filter(economic_series::source_id.eq(source_id));
-    }
-
-    if let Some(frequency) = params.frequency {
-        query = query.filter(economic_series::frequency.eq(frequency));
-    }
-
-    if let Some(search_query) = params.query {
-        // Use PostgreSQL full-text search
-        let search_term = format!("%{}%", search_query);
-        query = query.filter(
-            economic_series::title
-                .ilike(search_term.clone())
-                .or(economic_series::description.ilike(search_term)),
-        );
-    }
+    let mut query = series_query(&params);
 
     // Apply pagination
     let limit = params.limit.unwrap_or(50).min(1000);
@@ -242,6 +227,7 @@ pub async fn get_series_by_id(
 ///     end_date: Some(NaiveDate::from_ymd_opt(2024, 11, 30).unwrap()),
 ///     original_only: Some(true),
 ///     latest_revision_only: Some(false),
+///     exclude_corrections: None,
 ///     limit: Some(12),
 ///     offset: Some(0),
 /// };
@@ -266,11 +252,158 @@ pub async fn get_series_data(
         ))
     })?;
 
+    let mut query = data_points_query(&params);
+
+    if let Some(latest_revision_only) = params.latest_revision_only {
+        if latest_revision_only {
+            // This is a complex query - for now, we'll handle it in the application layer
+            // In production, this should be optimized with a proper SQL query
+        }
+    }
+
+    // Apply pagination
+    let limit = params.limit.unwrap_or(1000).min(10000);
+    let offset = params.offset.unwrap_or(0);
+
+    query = query.limit(limit).offset(offset);
+
+    // Order by date; revision date and id keep the order stable for cursors
+    query = query.order_by((
+        data_points::date.asc(),
+        data_points::revision_date.asc(),
+        data_points::id.asc(),
+    ));
+
+    let mut data_points = query.load::<DataPoint>(&mut *conn).await?;
+
+    // Post-process for latest revision only if requested
+    if params.latest_revision_only.unwrap_or(false) {
+        data_points = filter_latest_revisions(data_points);
+    }
+
+    Ok(data_points)
+}
+
+/// Largest `totalCount` computed exactly; above it the planner estimate is returned
+pub const EXACT_COUNT_THRESHOLD: i64 = 10_000;
+
+/// Keyset position of a data point; series data is ordered by date, revision date and id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DataPointPosition {
+    pub date: NaiveDate,
+    pub revision_date: NaiveDate,
+    pub id: Uuid,
+}
+
+impl From<&DataPoint> for DataPointPosition {
+    fn from(point: &DataPoint) -> Self {
+        Self {
+            date: point.date,
+            revision_date: point.revision_date,
+            id: point.id,
+        }
+    }
+}
+
+/// Keyset position of a series in the series list, most recently updated first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesPosition {
+    pub last_updated: Option<DateTime<Utc>>,
+    pub id: Uuid,
+}
+
+impl From<&EconomicSeries> for SeriesPosition {
+    fn from(series: &EconomicSeries) -> Self {
+        Self {
+            last_updated: series.last_updated,
+            id: series.id,
+        }
+    }
+}
+
+/// One page of a keyset-paginated query
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub has_next_page: bool,
+}
+
+impl<T> Page<T> {
+    /// Build a page from rows fetched with a limit of one more than `first`
+    pub(crate) fn from_overfetch(mut items: Vec<T>, first: usize) -> Self {
+        let has_next_page = items.len() > first;
+        items.truncate(first);
+        Self {
+            items,
+            has_next_page,
+        }
+    }
+}
+
+/// Number of rows a paginated query pages through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowCount {
+    pub count: i64,
+    /// Whether `count` is the query planner's estimate rather than an exact count
+    pub is_estimate: bool,
+}
+
+/// Count rows exactly unless the planner expects more than [`EXACT_COUNT_THRESHOLD`]
+async fn row_count<F>(
+    pool: &DatabasePool,
+    template: &str,
+    args: Vec<String>,
+    exact: F,
+) -> AppResult<RowCount>
+where
+    F: std::future::Future<Output = AppResult<i64>>,
+{
+    let estimate = estimate_row_count(pool, template, args).await?;
+    if estimate > EXACT_COUNT_THRESHOLD {
+        return Ok(RowCount {
+            count: estimate,
+            is_estimate: true,
+        });
+    }
+
+    Ok(RowCount {
+        count: exact.await?,
+        is_estimate: false,
+    })
+}
+
+/// Filters of [`SeriesSearchParams`], without ordering or pagination
+fn series_query(params: &SeriesSearchParams) -> economic_series::BoxedQuery<'static, Pg> {
+    let mut query = economic_series::table
+        .filter(economic_series::is_active.eq(params.is_active.unwrap_or(true)))
+        .into_boxed();
+
+    if let Some(source_id) = params.source_id {
+        query = query.filter(economic_series::source_id.eq(source_id));
+    }
+
+    if let Some(frequency) = params.frequency.clone() {
+        query = query.filter(economic_series::frequency.eq(frequency));
+    }
+
+    if let Some(search_query) = &params.query {
+        let search_term = format!("%{}%", search_query);
+        query = query.filter(
+            economic_series::title
+                .ilike(search_term.clone())
+                .or(economic_series::description.ilike(search_term)),
+        );
+    }
+
+    query
+}
+
+/// Filters of [`DataQueryParams`], without ordering, pagination or revision selection
+fn data_points_query(params: &DataQueryParams) -> data_points::BoxedQuery<'static, Pg> {
     let mut query = data_points::table
         .filter(data_points::series_id.eq(params.series_id))
         .into_boxed();
 
-    // Apply date range filters
     if let Some(start_date) = params.start_date {
         query = query.filter(data_points::date.ge(start_date));
     }
@@ -279,37 +412,607 @@ pub async fn get_series_data(
         query = query.filter(data_points::date.le(end_date));
     }
 
-    // Apply revision filters
-    if let Some(original_only) = params.original_only {
-        if original_only {
-            query = query.filter(data_points::is_original_release.eq(true));
+    if params.original_only.unwrap_or(false) {
+        query = query.filter(data_points::is_original_release.eq(true));
+    }
+
+    if params.exclude_corrections.unwrap_or(false) {
+        query = query.filter(
+            data_points::id.ne_all(
+                data_point_corrections::table
+                    .filter(data_point_corrections::series_id.eq(params.series_id))
+                    .select(data_point_corrections::data_point_id),
+            ),
+        );
+    }
+
+    query
+}
+
+/// Page of series following `after` in the series list
+///
+/// Filters are those of [`list_series`]; `limit` and `offset` in `params` are ignored.
+pub async fn list_series_page(
+    pool: &DatabasePool,
+    params: &SeriesSearchParams,
+    after: Option<SeriesPosition>,
+    first: usize,
+) -> AppResult<Page<EconomicSeries>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let mut query = series_query(params);
+
+    // Series without an update time sort last
+    if let Some(after) = after {
+        query = match after.last_updated {
+            Some(last_updated) => query.filter(
+                economic_series::last_updated
+                    .lt(last_updated)
+                    .or(economic_series::last_updated
+                        .eq(last_updated)
+                        .and(economic_series::id.lt(after.id)))
+                    .or(economic_series::last_updated.is_null()),
+            ),
+            None => query.filter(
+                economic_series::last_updated
+                    .is_null()
+                    .and(economic_series::id.lt(after.id)),
+            ),
+        };
+    }
+
+    let series = query
+        .order_by((
+            economic_series::last_updated.desc().nulls_last(),
+            economic_series::id.desc(),
+        ))
+        .limit(first as i64 + 1)
+        .select(EconomicSeries::as_select())
+        .load::<EconomicSeries>(&mut *conn)
+        .await?;
+
+    Ok(Page::from_overfetch(series, first))
+}
+
+/// Number of series [`list_series_page`] pages through
+pub async fn count_series(pool: &DatabasePool, params: &SeriesSearchParams) -> AppResult<RowCount> {
+    let mut args = vec![params.is_active.unwrap_or(true).to_string()];
+    let mut template = "SELECT 1 FROM economic_series WHERE is_active = %1$L".to_string();
+    if let Some(source_id) = params.source_id {
+        args.push(source_id.to_string());
+        template.push_str(&format!(" AND source_id = %{}$L", args.len()));
+    }
+    if let Some(frequency) = &params.frequency {
+        args.push(frequency.clone());
+        template.push_str(&format!(" AND frequency = %{}$L", args.len()));
+    }
+    if let Some(search_query) = &params.query {
+        args.push(format!("%{}%", search_query));
+        template.push_str(&format!(
+            " AND (title ILIKE %{0}$L OR description ILIKE %{0}$L)",
+            args.len()
+        ));
+    }
+
+    row_count(pool, &template, args, async {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+        Ok(series_query(params)
+            .count()
+            .get_result::<i64>(&mut *conn)
+            .await?)
+    })
+    .await
+}
+
+/// Page of a series' data points following `after`, in date order
+///
+/// Filters are those of [`get_series_data`]; `limit` and `offset` in `params` are
+/// ignored. With `latest_revision_only` each date appears once and pages are
+/// keyed by date alone.
+pub async fn get_series_data_page(
+    pool: &DatabasePool,
+    params: &DataQueryParams,
+    after: Option<DataPointPosition>,
+    first: usize,
+) -> AppResult<Page<DataPoint>> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let mut query = data_points_query(params);
+
+    if params.latest_revision_only.unwrap_or(false) {
+        if let Some(after) = after {
+            query = query.filter(data_points::date.gt(after.date));
         }
+        query = query.distinct_on(data_points::date).order_by((
+            data_points::date.asc(),
+            data_points::revision_date.desc(),
+            data_points::id.desc(),
+        ));
+    } else {
+        if let Some(after) = after {
+            query = query.filter(
+                data_points::date
+                    .gt(after.date)
+                    .or(data_points::date
+                        .eq(after.date)
+                        .and(data_points::revision_date.gt(after.revision_date)))
+                    .or(data_points::date
+                        .eq(after.date)
+                        .and(data_points::revision_date.eq(after.revision_date))
+                        .and(data_points::id.gt(after.id))),
+            );
+        }
+        query = query.order_by((
+            data_points::date.asc(),
+            data_points::revision_date.asc(),
+            data_points::id.asc(),
+        ));
     }
 
-    if let Some(latest_revision_only) = params.latest_revision_only {
+    let points = query
+        .limit(first as i64 + 1)
+        .load::<DataPoint>(&mut *conn)
+        .await?;
+
+    Ok(Page::from_overfetch(points, first))
+}
+
+/// All data points [`get_series_data_page`] pages through, read page by page
+///
+/// Unlike [`get_series_data`] this is not capped, for transformations that need
+/// the whole window.
+pub async fn get_series_data_window(
+    pool: &DatabasePool,
+    params: &DataQueryParams,
+) -> AppResult<Vec<DataPoint>> {
+    let mut points = Vec::new();
+    let mut after = None;
+
+    loop {
+        let page = get_series_data_page(pool, params, after, DATA_POINT_STREAM_BATCH_SIZE as usize)
+            .await?;
+        points.extend(page.items);
+
+        if !page.has_next_page {
+            return Ok(points);
+        }
+        after = points.last().map(DataPointPosition::from);
+    }
+}
+
+/// Number of data points [`get_series_data_page`] pages through
+pub async fn count_series_data(
+    pool: &DatabasePool,
+    params: &DataQueryParams,
+) -> AppResult<RowCount> {
+    let latest_revision_only = params.latest_revision_only.unwrap_or(false);
+
+    let mut args = vec![params.series_id.to_string()];
+    let mut template = format!(
+        "SELECT {} FROM data_points WHERE series_id = %1$L",
         if latest_revision_only {
-            // This is a complex query - for now, we'll handle it in the application layer
-            // In production, this should be optimized with a proper SQL query
+            "DISTINCT date"
+        } else {
+            "1"
         }
+    );
+    if let Some(start_date) = params.start_date {
+        args.push(start_date.to_string());
+        template.push_str(&format!(" AND date >= %{}$L", args.len()));
+    }
+    if let Some(end_date) = params.end_date {
+        args.push(end_date.to_string());
+        template.push_str(&format!(" AND date <= %{}$L", args.len()));
+    }
+    if params.original_only.unwrap_or(false) {
+        template.push_str(" AND is_original_release");
+    }
+    if params.exclude_corrections.unwrap_or(false) {
+        template.push_str(
+            " AND id NOT IN (SELECT data_point_id FROM data_point_corrections WHERE series_id = %1$L)",
+        );
     }
 
-    // Apply pagination
-    let limit = params.limit.unwrap_or(1000).min(10000);
-    let offset = params.offset.unwrap_or(0);
+    row_count(pool, &template, args, async {
+        let mut conn = pool.get().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+        })?;
+        let query = data_points_query(params);
+        let count = if latest_revision_only {
+            query
+                .select(diesel::dsl::count_distinct(data_points::date))
+                .get_result::<i64>(&mut *conn)
+                .await?
+        } else {
+            query.count().get_result::<i64>(&mut *conn).await?
+        };
+        Ok(count)
+    })
+    .await
+}
 
-    query = query.limit(limit).offset(offset);
+/// Smallest `max_points` accepted for downsampling (LTTB keeps both endpoints)
+pub const MIN_DOWNSAMPLED_POINTS: usize = 3;
+/// Largest `max_points` accepted for downsampling
+pub const MAX_DOWNSAMPLED_POINTS: usize = 10_000;
 
-    // Order by date
-    query = query.order_by(data_points::date.asc());
+/// Server-side downsampling for chart rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownsamplingAlgorithm {
+    /// Largest-Triangle-Three-Buckets: keeps the points that shape the line
+    Lttb,
+    /// Minimum and maximum of each bucket: keeps every spike and trough
+    MinMax,
+}
 
-    let mut data_points = query.load::<DataPoint>(&mut *conn).await?;
+/// A series window reduced to at most `max_points` points for charting
+///
+/// The window is read through the data point cache, like transformed data.
+/// Points without a value cannot be plotted and are dropped.
+pub async fn get_downsampled_series_data(
+    pool: &DatabasePool,
+    params: &DataQueryParams,
+    max_points: usize,
+    algorithm: DownsamplingAlgorithm,
+) -> AppResult<Vec<DataPoint>> {
+    if !(MIN_DOWNSAMPLED_POINTS..=MAX_DOWNSAMPLED_POINTS).contains(&max_points) {
+        return Err(AppError::ValidationError(format!(
+            "maxPoints must be between {} and {}",
+            MIN_DOWNSAMPLED_POINTS, MAX_DOWNSAMPLED_POINTS
+        )));
+    }
 
-    // Post-process for latest revision only if requested
-    if params.latest_revision_only.unwrap_or(false) {
-        data_points = filter_latest_revisions(data_points);
+    let window = shared_data_point_cache()
+        .get_or_load(DataPointCacheKey::from_params(params), || {
+            get_series_data_window(pool, params)
+        })
+        .await?;
+
+    Ok(downsample(&window, max_points, algorithm))
+}
+
+/// Reduce date-ordered data points to at most `max_points`
+///
+/// Returns the valued points unchanged when they already fit.
+pub fn downsample(
+    points: &[DataPoint],
+    max_points: usize,
+    algorithm: DownsamplingAlgorithm,
+) -> Vec<DataPoint> {
+    let plotted: Vec<(f64, f64, &DataPoint)> = points
+        .iter()
+        .filter_map(|point| {
+            let y = point.value.as_ref()?.to_f64()?;
+            Some((point.date.num_days_from_ce() as f64, y, point))
+        })
+        .collect();
+
+    let max_points = max_points.max(MIN_DOWNSAMPLED_POINTS);
+    let indices = if plotted.len() <= max_points {
+        (0..plotted.len()).collect()
+    } else {
+        match algorithm {
+            DownsamplingAlgorithm::Lttb => lttb_indices(&plotted, max_points),
+            DownsamplingAlgorithm::MinMax => min_max_indices(&plotted, max_points),
+        }
+    };
+
+    indices
+        .into_iter()
+        .map(|index| plotted[index].2.clone())
+        .collect()
+}
+
+/// Indices kept by Largest-Triangle-Three-Buckets
+///
+/// The first and last points are always kept. The points between are split
+/// into `threshold - 2` buckets; from each, the point forming the largest
+/// triangle with the previously kept point and the next bucket's average is
+/// kept.
+fn lttb_indices(points: &[(f64, f64, &DataPoint)], threshold: usize) -> Vec<usize> {
+    let len = points.len();
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut kept = Vec::with_capacity(threshold);
+    let mut previous = 0;
+    kept.push(previous);
+
+    for bucket in 0..threshold - 2 {
+        // Average of the next bucket (the last point for the final bucket)
+        let next_start = ((bucket + 1) as f64 * bucket_size) as usize + 1;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(len);
+        let next = &points[next_start..next_end];
+        let average_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+        let average_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = next_start;
+        let (previous_x, previous_y) = (points[previous].0, points[previous].1);
+
+        let mut largest_area = -1.0;
+        for (index, point) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((previous_x - average_x) * (point.1 - previous_y)
+                - (previous_x - point.0) * (average_y - previous_y))
+                .abs();
+            if area > largest_area {
+                largest_area = area;
+                previous = index;
+            }
+        }
+        kept.push(previous);
     }
 
-    Ok(data_points)
+    kept.push(len - 1);
+    kept
+}
+
+/// Indices kept by min/max bucketing, in date order
+///
+/// Splits the points into `max_points / 2` buckets and keeps the lowest and
+/// highest point of each, so no extreme is ever dropped.
+fn min_max_indices(points: &[(f64, f64, &DataPoint)], max_points: usize) -> Vec<usize> {
+    let len = points.len();
+    let buckets = max_points / 2;
+    let mut kept = Vec::with_capacity(buckets * 2);
+
+    for bucket in 0..buckets {
+        let start = bucket * len / buckets;
+        let end = (bucket + 1) * len / buckets;
+        if start == end {
+            continue;
+        }
+
+        let mut min = start;
+        let mut max = start;
+        for (index, point) in points.iter().enumerate().take(end).skip(start) {
+            if point.1 < points[min].1 {
+                min = index;
+            }
+            if point.1 > points[max].1 {
+                max = index;
+            }
+        }
+
+        kept.push(min.min(max));
+        if min != max {
+            kept.push(min.max(max));
+        }
+    }
+
+    kept
+}
+
+/// Target frequency of a resampled series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResampleFrequency {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+/// How the observations of a period are combined into one value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResampleMethod {
+    /// Average of the period's observations, for rates and levels
+    Mean,
+    /// Total of the period's observations, for flows
+    Sum,
+    /// Last observation of the period, for stocks and prices
+    EndOfPeriod,
+    /// First observation of the period
+    StartOfPeriod,
+}
+
+/// Decimal places kept in resampled means
+const RESAMPLED_MEAN_SCALE: i64 = 10;
+
+/// Observation frequencies a series can be resampled from, finest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum SourceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+impl SourceFrequency {
+    pub(crate) fn parse(frequency: &str) -> Option<Self> {
+        let frequency = frequency.trim().to_lowercase();
+        match frequency.as_str() {
+            "d" => Some(Self::Daily),
+            "w" => Some(Self::Weekly),
+            "m" => Some(Self::Monthly),
+            "q" => Some(Self::Quarterly),
+            "a" | "y" => Some(Self::Annual),
+            _ if frequency.starts_with("daily") => Some(Self::Daily),
+            _ if frequency.starts_with("weekly") => Some(Self::Weekly),
+            _ if frequency.starts_with("monthly") => Some(Self::Monthly),
+            _ if frequency.starts_with("quarterly") => Some(Self::Quarterly),
+            _ if frequency.starts_with("annual") || frequency.starts_with("yearly") => {
+                Some(Self::Annual)
+            }
+            _ => None,
+        }
+    }
+
+    /// Observations a fully covered period holds, for calendar-aligned sources
+    fn observations_per(self, target: ResampleFrequency) -> Option<usize> {
+        match (self, target) {
+            (Self::Monthly, ResampleFrequency::Quarterly) => Some(3),
+            (Self::Monthly, ResampleFrequency::Annual) => Some(12),
+            (Self::Quarterly, ResampleFrequency::Annual) => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Largest gap between an observation and the period edge of a covered
+    /// period, allowing for weekends and holidays in business-day series
+    pub(crate) fn edge_tolerance_days(self) -> i64 {
+        match self {
+            Self::Daily => 4,
+            _ => 7,
+        }
+    }
+
+    pub(crate) fn rank(self) -> u8 {
+        self as u8
+    }
+}
+
+impl ResampleFrequency {
+    pub(crate) fn rank(self) -> u8 {
+        match self {
+            Self::Weekly => SourceFrequency::Weekly.rank(),
+            Self::Monthly => SourceFrequency::Monthly.rank(),
+            Self::Quarterly => SourceFrequency::Quarterly.rank(),
+            Self::Annual => SourceFrequency::Annual.rank(),
+        }
+    }
+
+    /// First day of the period `date` falls in; weeks start on Monday
+    pub fn period_start(self, date: NaiveDate) -> NaiveDate {
+        let start = match self {
+            Self::Weekly => {
+                return date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            Self::Monthly => NaiveDate::from_ymd_opt(date.year(), date.month(), 1),
+            Self::Quarterly => NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1),
+            Self::Annual => NaiveDate::from_ymd_opt(date.year(), 1, 1),
+        };
+        start.expect("first day of a period is a valid date")
+    }
+
+    /// Last day of the period starting on `start`
+    pub fn period_end(self, start: NaiveDate) -> NaiveDate {
+        let next = match self {
+            Self::Weekly => Some(start + chrono::Duration::days(7)),
+            Self::Monthly => start.checked_add_months(chrono::Months::new(1)),
+            Self::Quarterly => start.checked_add_months(chrono::Months::new(3)),
+            Self::Annual => start.checked_add_months(chrono::Months::new(12)),
+        };
+        next.and_then(|next| next.pred_opt())
+            .unwrap_or(NaiveDate::MAX)
+    }
+}
+
+/// One period of a resampled series
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResampledPoint {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub value: BigDecimal,
+    /// Valued observations combined into `value`
+    pub observation_count: usize,
+    /// Whether the observations cover the whole period
+    ///
+    /// Periods at the ends of the data window, or with missing observations,
+    /// are incomplete: their sums and means understate or skew the period.
+    pub is_complete: bool,
+}
+
+/// A series window aggregated to a lower frequency
+///
+/// The window is read through the data point cache, like downsampled data.
+/// Incomplete periods are left out unless `include_incomplete` is set.
+pub async fn get_resampled_series_data(
+    pool: &DatabasePool,
+    params: &DataQueryParams,
+    frequency: ResampleFrequency,
+    method: ResampleMethod,
+    include_incomplete: bool,
+) -> AppResult<Vec<ResampledPoint>> {
+    let series = get_series_by_id(pool, params.series_id)
+        .await?
+        .ok_or_else(|| AppError::SeriesNotFound(params.series_id.to_string()))?;
+
+    let window = shared_data_point_cache()
+        .get_or_load(DataPointCacheKey::from_params(params), || {
+            get_series_data_window(pool, params)
+        })
+        .await?;
+
+    let resampled = resample(&window, &series.frequency, frequency, method)?;
+
+    Ok(resampled
+        .into_iter()
+        .filter(|point| include_incomplete || point.is_complete)
+        .collect())
+}
+
+/// Aggregate date-ordered data points of a `source_frequency` series into periods
+///
+/// Points without a value are skipped. Resampling to the same or a higher
+/// frequency is rejected.
+pub fn resample(
+    points: &[DataPoint],
+    source_frequency: &str,
+    frequency: ResampleFrequency,
+    method: ResampleMethod,
+) -> AppResult<Vec<ResampledPoint>> {
+    let source = SourceFrequency::parse(source_frequency).ok_or_else(|| {
+        AppError::InvalidTransformation(format!(
+            "Cannot resample a series with {} frequency",
+            source_frequency
+        ))
+    })?;
+    if source.rank() >= frequency.rank() {
+        return Err(AppError::InvalidTransformation(format!(
+            "Cannot resample a {} series to {:?}; only lower frequencies are supported",
+            source_frequency.to_lowercase(),
+            frequency
+        )));
+    }
+
+    let valued: Vec<(NaiveDate, &BigDecimal)> = points
+        .iter()
+        .filter_map(|point| Some((point.date, point.value.as_ref()?)))
+        .collect();
+
+    let mut resampled = Vec::new();
+    let mut remaining = valued.as_slice();
+    while let Some((first_date, _)) = remaining.first() {
+        let period_start = frequency.period_start(*first_date);
+        let period_end = frequency.period_end(period_start);
+        let in_period = remaining.partition_point(|(date, _)| *date <= period_end);
+        let (period, rest) = remaining.split_at(in_period);
+        remaining = rest;
+
+        let values = period.iter().map(|(_, value)| *value);
+        let value = match method {
+            ResampleMethod::Sum => values.sum::<BigDecimal>(),
+            ResampleMethod::Mean => (values.sum::<BigDecimal>()
+                / BigDecimal::from(period.len() as u64))
+            .round(RESAMPLED_MEAN_SCALE),
+            ResampleMethod::EndOfPeriod => period[period.len() - 1].1.clone(),
+            ResampleMethod::StartOfPeriod => period[0].1.clone(),
+        };
+
+        let is_complete = match source.observations_per(frequency) {
+            Some(expected) => period.len() >= expected,
+            None => {
+                let tolerance = source.edge_tolerance_days();
+                (period[0].0 - period_start).num_days() < tolerance
+                    && (period_end - period[period.len() - 1].0).num_days() < tolerance
+            }
+        };
+
+        resampled.push(ResampledPoint {
+            period_start,
+            period_end,
+            value: value.normalized(),
+            observation_count: period.len(),
+            is_complete,
+        });
+    }
+
+    Ok(resampled)
 }
 
 /// Transform data points according to the specified transformation
@@ -498,7 +1201,7 @@ fn calculate_mom_changes(data_points: Vec<DataPoint>) -> Vec<TransformedDataPoin
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bigdecimal::BigDecimal;
+    use bigdecimal::{BigDecimal, FromPrimitive};
     use chrono::NaiveDate;
     use rust_decimal_macros::dec;
     use uuid::Uuid;
@@ -602,4 +1305,228 @@ mod tests {
             "YoY should be 10% increase"
         );
     }
+
+    fn daily_point(day: i64, value: Option<f64>) -> DataPoint {
+        let date = NaiveDate::from_ymd_opt(1995, 1, 1).unwrap() + chrono::Duration::days(day);
+        DataPoint {
+            id: Uuid::new_v4(),
+            series_id: Uuid::nil(),
+            date,
+            value: value.and_then(BigDecimal::from_f64),
+            revision_date: date,
+            is_original_release: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn as_xy(points: &[DataPoint]) -> Vec<(f64, f64)> {
+        points
+            .iter()
+            .map(|p| {
+                (
+                    p.date.num_days_from_ce() as f64,
+                    p.value.as_ref().unwrap().to_f64().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lttb_downsampling_fidelity() {
+        // REQUIREMENT: Charts of long daily series render from a few hundred points
+        // PURPOSE: Verify LTTB keeps the endpoints and the line stays within a visual error bound
+        // This ensures 30 years of daily data look the same after downsampling
+
+        let points: Vec<DataPoint> = (0..10_000)
+            .map(|day| {
+                let value = (day as f64 * std::f64::consts::TAU / 1_000.0).sin();
+                daily_point(day, Some(value))
+            })
+            .collect();
+
+        let sampled = downsample(&points, 500, DownsamplingAlgorithm::Lttb);
+
+        assert_eq!(sampled.len(), 500, "Should return exactly maxPoints points");
+        assert_eq!(
+            sampled[0].date, points[0].date,
+            "Should keep the first point"
+        );
+        assert_eq!(
+            sampled[499].date, points[9_999].date,
+            "Should keep the last point"
+        );
+        assert!(
+            sampled.windows(2).all(|pair| pair[0].date < pair[1].date),
+            "Should stay in date order"
+        );
+
+        // Interpolating between the kept points must reproduce every original
+        // point to within 2% of the amplitude
+        let kept = as_xy(&sampled);
+        let mut segment = 0;
+        for (x, y) in as_xy(&points) {
+            while kept[segment + 1].0 < x {
+                segment += 1;
+            }
+            let (x0, y0) = kept[segment];
+            let (x1, y1) = kept[segment + 1];
+            let interpolated = y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+            assert!(
+                (interpolated - y).abs() < 0.02,
+                "Error at day {} exceeds the fidelity bound",
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_max_downsampling_keeps_extremes() {
+        // REQUIREMENT: Spikes in volatile series survive downsampling
+        // PURPOSE: Verify min/max bucketing keeps every bucket's extremes and the global ones
+        // This ensures a one-day crash is still visible on a 30-year chart
+
+        let mut points: Vec<DataPoint> = (0..10_000)
+            .map(|day| daily_point(day, Some(100.0 + (day % 7) as f64)))
+            .collect();
+        points[4_321] = daily_point(4_321, Some(250.0));
+        points[7_654] = daily_point(7_654, Some(-40.0));
+
+        let sampled = downsample(&points, 400, DownsamplingAlgorithm::MinMax);
+        let values: Vec<f64> = as_xy(&sampled).into_iter().map(|(_, y)| y).collect();
+
+        assert!(sampled.len() <= 400, "Should not exceed maxPoints");
+        assert!(values.contains(&250.0), "Should keep the global maximum");
+        assert!(values.contains(&-40.0), "Should keep the global minimum");
+        assert!(
+            sampled.windows(2).all(|pair| pair[0].date < pair[1].date),
+            "Should stay in date order"
+        );
+
+        // Every original point lies within the range of its bucket's kept points
+        let bucket_len = points.len() / 200;
+        for (bucket, chunk) in as_xy(&points).chunks(bucket_len).enumerate() {
+            let kept = &values[bucket * 2..bucket * 2 + 2];
+            let (low, high) = (kept[0].min(kept[1]), kept[0].max(kept[1]));
+            assert!(chunk.iter().all(|(_, y)| (low..=high).contains(y)));
+        }
+    }
+
+    #[test]
+    fn test_downsample_short_series_unchanged() {
+        // REQUIREMENT: Downsampling never invents or drops plottable observations needlessly
+        // PURPOSE: Verify series that already fit are returned as-is, minus missing values
+        // This ensures short series render exactly as stored
+
+        let points = vec![
+            daily_point(0, Some(1.0)),
+            daily_point(1, None),
+            daily_point(2, Some(3.0)),
+            daily_point(3, Some(2.0)),
+        ];
+
+        for algorithm in [DownsamplingAlgorithm::Lttb, DownsamplingAlgorithm::MinMax] {
+            let sampled = downsample(&points, 100, algorithm);
+            assert_eq!(
+                as_xy(&sampled).len(),
+                3,
+                "Should drop only the missing value"
+            );
+            assert_eq!(sampled[1].date, points[2].date);
+        }
+    }
+
+    fn dated_point(date: NaiveDate, value: i64) -> DataPoint {
+        DataPoint {
+            value: Some(BigDecimal::from(value)),
+            date,
+            revision_date: date,
+            ..daily_point(0, None)
+        }
+    }
+
+    #[test]
+    fn test_resample_monthly_to_quarterly() {
+        // REQUIREMENT: Analysts align series of different frequencies
+        // PURPOSE: Verify each aggregation method and that partial quarters are flagged incomplete
+        // This ensures a quarter with one month of data is not mistaken for a full quarter
+
+        let points: Vec<DataPoint> = (1..=4)
+            .map(|month| {
+                dated_point(
+                    NaiveDate::from_ymd_opt(2024, month, 1).unwrap(),
+                    month as i64,
+                )
+            })
+            .collect();
+
+        let mean = resample(
+            &points,
+            "Monthly",
+            ResampleFrequency::Quarterly,
+            ResampleMethod::Mean,
+        )
+        .unwrap();
+        assert_eq!(mean.len(), 2);
+        assert_eq!(
+            mean[0].period_start,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+        assert_eq!(
+            mean[0].period_end,
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+        assert_eq!(mean[0].value, BigDecimal::from(2));
+        assert!(mean[0].is_complete);
+        assert_eq!(mean[1].observation_count, 1);
+        assert!(!mean[1].is_complete, "Q2 has only April");
+
+        let expected = [
+            (ResampleMethod::Sum, 6),
+            (ResampleMethod::EndOfPeriod, 3),
+            (ResampleMethod::StartOfPeriod, 1),
+        ];
+        for (method, value) in expected {
+            let quarters = resample(&points, "M", ResampleFrequency::Quarterly, method).unwrap();
+            assert_eq!(quarters[0].value, BigDecimal::from(value), "{:?}", method);
+        }
+
+        assert!(
+            resample(
+                &points,
+                "Quarterly",
+                ResampleFrequency::Monthly,
+                ResampleMethod::Mean
+            )
+            .is_err(),
+            "Should refuse to resample to a higher frequency"
+        );
+    }
+
+    #[test]
+    fn test_resample_business_daily_to_monthly() {
+        // REQUIREMENT: Daily series resample to monthly with correct handling of incomplete periods
+        // PURPOSE: Verify months starting on a weekend are complete and a window ending mid-month is not
+        // This ensures business-day series are not all reported as incomplete
+
+        // Weekdays from Mon 2024-01-01 to Wed 2024-02-14
+        let points: Vec<DataPoint> = (0..45)
+            .map(|day| NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Duration::days(day))
+            .filter(|date| date.weekday().num_days_from_monday() < 5)
+            .map(|date| dated_point(date, 1))
+            .collect();
+
+        let months = resample(
+            &points,
+            "Daily",
+            ResampleFrequency::Monthly,
+            ResampleMethod::Sum,
+        )
+        .unwrap();
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].value, BigDecimal::from(23));
+        assert!(months[0].is_complete);
+        assert!(!months[1].is_complete, "February stops on the 14th");
+    }
 }
@@ -0,0 +1,520 @@
+/**
+ * REQUIREMENT: Downstream consumers learn when new data lands without polling
+ * PURPOSE: Queue webhook deliveries for crawl events and send them as signed HTTP requests
+ * Deliveries are stored before they are sent, so they survive restarts. A failed
+ * delivery is retried with exponential backoff until MAX_DELIVERY_ATTEMPTS, after
+ * which it is marked failed.
+ */
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use econ_graph_core::{
+    database::DatabasePool,
+    error::{AppError, AppResult},
+    models::{
+        DataSource, DeliveryAttempt, EconomicSeries, NewWebhookDelivery, Webhook, WebhookDelivery,
+        WebhookEvent,
+    },
+    schema::{data_sources, economic_series},
+    secrets::{shared_secret_cipher, SecretCipher},
+};
+
+/// How often the backend sends due deliveries
+pub const DEFAULT_WEBHOOK_DISPATCH_INTERVAL_SECONDS: u64 = 15;
+
+/// Deliveries sent per dispatch run
+pub const WEBHOOK_DISPATCH_BATCH_SIZE: i64 = 50;
+
+/// Attempts before a delivery is marked failed
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry; doubled for each further attempt
+const RETRY_BASE_DELAY_SECONDS: i64 = 30;
+
+/// Longest delay between two attempts
+const RETRY_MAX_DELAY_SECONDS: i64 = 3600;
+
+/// How long an endpoint may take to answer
+const DELIVERY_TIMEOUT_SECONDS: u64 = 10;
+
+/// How long a claimed delivery is hidden from other backend instances
+const DELIVERY_LEASE_SECONDS: i64 = 300;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of "{timestamp}.{body}">`
+pub const SIGNATURE_HEADER: &str = "X-EconGraph-Signature";
+/// Header carrying the Unix timestamp that was signed
+pub const TIMESTAMP_HEADER: &str = "X-EconGraph-Timestamp";
+/// Header carrying the event type
+pub const EVENT_HEADER: &str = "X-EconGraph-Event";
+/// Header carrying the delivery ID, stable across retries
+pub const DELIVERY_HEADER: &str = "X-EconGraph-Delivery";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn keyed_mac(key: &[u8], message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac
+}
+
+/// HMAC-SHA256 (RFC 2104) of `message` under `key`
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    keyed_mac(key, message).finalize().into_bytes().into()
+}
+
+/// HMAC-SHA256 (RFC 2104) of `message` under `key`, hex encoded
+pub fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    hmac_sha256(key, message)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Whether `signature` is the hex HMAC-SHA256 of `message` under `key`
+///
+/// The comparison takes the same time wherever the signatures differ, so it
+/// does not reveal how much of a forged signature was right.
+pub fn verify_hmac_sha256_hex(key: &[u8], message: &[u8], signature: &str) -> bool {
+    let Some(signature) = decode_hex(signature) else {
+        return false;
+    };
+    keyed_mac(key, message).verify_slice(&signature).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Signature header value for a delivery body sent at `timestamp`
+///
+/// The timestamp is signed with the body so that a captured request cannot be
+/// replayed later with a fresh timestamp.
+pub fn sign_delivery(secret: &str, timestamp: i64, body: &str) -> String {
+    let message = format!("{}.{}", timestamp, body);
+    format!(
+        "sha256={}",
+        hmac_sha256_hex(secret.as_bytes(), message.as_bytes())
+    )
+}
+
+/// Whether a signature header value was produced by [`sign_delivery`]
+pub fn verify_delivery(secret: &str, timestamp: i64, body: &str, signature: &str) -> bool {
+    let message = format!("{}.{}", timestamp, body);
+    signature.strip_prefix("sha256=").is_some_and(|signature| {
+        verify_hmac_sha256_hex(secret.as_bytes(), message.as_bytes(), signature)
+    })
+}
+
+/// Delay before the next attempt after `attempts` failed ones, or `None` to give up
+pub fn retry_delay(attempts: i32) -> Option<Duration> {
+    if attempts >= MAX_DELIVERY_ATTEMPTS {
+        return None;
+    }
+
+    let exponent = attempts.clamp(1, 30) as u32 - 1;
+    let seconds = RETRY_BASE_DELAY_SECONDS
+        .saturating_mul(2i64.saturating_pow(exponent))
+        .min(RETRY_MAX_DELAY_SECONDS);
+    Some(Duration::seconds(seconds))
+}
+
+/// Body sent for an event
+pub fn event_payload(
+    event: WebhookEvent,
+    occurred_at: DateTime<Utc>,
+    data: serde_json::Value,
+) -> serde_json::Value {
+    serde_json::json!({
+        "event": event.as_str(),
+        "occurred_at": occurred_at,
+        "data": data,
+    })
+}
+
+/// Queue `payload` for every active webhook subscribed to `event` on the source or series
+async fn enqueue_event(
+    pool: &DatabasePool,
+    event: WebhookEvent,
+    source_id: Uuid,
+    series_id: Option<Uuid>,
+    payload: serde_json::Value,
+) -> AppResult<usize> {
+    let deliveries: Vec<NewWebhookDelivery> =
+        Webhook::subscribers(pool, event, source_id, series_id)
+            .await?
+            .into_iter()
+            .map(|webhook| NewWebhookDelivery {
+                webhook_id: webhook.id,
+                event_type: event.as_str().to_string(),
+                payload: payload.clone(),
+            })
+            .collect();
+
+    WebhookDelivery::enqueue(pool, &deliveries).await
+}
+
+async fn enqueue_series_updated(
+    pool: &DatabasePool,
+    series_id: Uuid,
+    new_points: usize,
+) -> AppResult<usize> {
+    let mut conn = pool.get().await.map_err(|e| {
+        AppError::DatabaseError(format!("Failed to get database connection: {}", e))
+    })?;
+
+    let (source_id, source_name, external_id, title, end_date) = economic_series::table
+        .inner_join(data_sources::table)
+        .filter(economic_series::id.eq(series_id))
+        .select((
+            economic_series::source_id,
+            data_sources::name,
+            economic_series::external_id,
+            economic_series::title,
+            economic_series::end_date,
+        ))
+        .first::<(Uuid, String, String, String, Option<chrono::NaiveDate>)>(&mut conn)
+        .await?;
+    drop(conn);
+
+    let payload = event_payload(
+        WebhookEvent::SeriesUpdated,
+        Utc::now(),
+        serde_json::json!({
+            "series_id": series_id,
+            "external_id": external_id,
+            "title": title,
+            "source": source_name,
+            "new_data_points": new_points,
+            "latest_date": end_date,
+        }),
+    );
+
+    enqueue_event(
+        pool,
+        WebhookEvent::SeriesUpdated,
+        source_id,
+        Some(series_id),
+        payload,
+    )
+    .await
+}
+
+/// Queue `series_updated` deliveries after new data points were stored
+///
+/// Failures are logged and never fail the crawl that stored the data.
+pub async fn publish_series_updated(pool: &DatabasePool, series_id: Uuid, new_points: usize) {
+    if let Err(e) = enqueue_series_updated(pool, series_id, new_points).await {
+        warn!(
+            "Failed to queue series_updated webhooks for series {}: {}",
+            series_id, e
+        );
+    }
+}
+
+async fn enqueue_crawl_failed(
+    pool: &DatabasePool,
+    source_name: &str,
+    external_id: &str,
+    error: &str,
+) -> AppResult<usize> {
+    let Some(source) = DataSource::find_by_name(pool, source_name).await? else {
+        return Ok(0);
+    };
+    let series_id = EconomicSeries::find_by_external_id(pool, external_id, source.id)
+        .await
+        .ok()
+        .map(|series| series.id);
+
+    let payload = event_payload(
+        WebhookEvent::CrawlFailed,
+        Utc::now(),
+        serde_json::json!({
+            "series_id": series_id,
+            "external_id": external_id,
+            "source": source.name,
+            "error": error,
+        }),
+    );
+
+    enqueue_event(
+        pool,
+        WebhookEvent::CrawlFailed,
+        source.id,
+        series_id,
+        payload,
+    )
+    .await
+}
+
+/// Queue `crawl_failed` deliveries after crawling a series failed
+///
+/// Failures are logged and never mask the crawl error itself.
+pub async fn publish_crawl_failed(
+    pool: &DatabasePool,
+    source_name: &str,
+    external_id: &str,
+    error: &str,
+) {
+    if let Err(e) = enqueue_crawl_failed(pool, source_name, external_id, error).await {
+        warn!(
+            "Failed to queue crawl_failed webhooks for {} {}: {}",
+            source_name, external_id, e
+        );
+    }
+}
+
+/// Encrypt the secrets of webhooks created before secrets were encrypted
+///
+/// Returns how many secrets were encrypted.
+pub async fn encrypt_plaintext_secrets(
+    pool: &DatabasePool,
+    cipher: &SecretCipher,
+) -> AppResult<usize> {
+    let webhooks = Webhook::with_plaintext_secret(pool).await?;
+    for webhook in &webhooks {
+        let encrypted = cipher.encrypt(&webhook.signing_secret(None)?, webhook.id.as_bytes())?;
+        Webhook::store_encrypted_secret(pool, webhook.id, encrypted).await?;
+    }
+
+    if !webhooks.is_empty() {
+        info!("Encrypted the secrets of {} webhooks", webhooks.len());
+    }
+    Ok(webhooks.len())
+}
+
+/// Outcome of a dispatch run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DispatchSummary {
+    pub delivered: usize,
+    /// Failed attempts that will be retried
+    pub retrying: usize,
+    /// Deliveries that ran out of attempts
+    pub failed: usize,
+}
+
+/// Sends due webhook deliveries
+pub struct WebhookDispatcher {
+    pool: DatabasePool,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(pool: DatabasePool) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECONDS))
+            .build()
+            .unwrap_or_default();
+
+        Self { pool, client }
+    }
+
+    /// Send up to [`WEBHOOK_DISPATCH_BATCH_SIZE`] due deliveries and record the outcomes
+    ///
+    /// Plaintext secrets left from before secrets were encrypted are
+    /// encrypted first, once an encryption key is configured.
+    pub async fn deliver_due(&self) -> AppResult<DispatchSummary> {
+        if let Ok(cipher) = shared_secret_cipher() {
+            if let Err(e) = encrypt_plaintext_secrets(&self.pool, cipher).await {
+                warn!("Failed to encrypt webhook secrets: {}", e);
+            }
+        }
+
+        let deliveries = WebhookDelivery::claim_due(
+            &self.pool,
+            WEBHOOK_DISPATCH_BATCH_SIZE,
+            Duration::seconds(DELIVERY_LEASE_SECONDS),
+        )
+        .await?;
+
+        let mut summary = DispatchSummary::default();
+        if deliveries.is_empty() {
+            return Ok(summary);
+        }
+
+        let mut webhook_ids: Vec<Uuid> = deliveries.iter().map(|d| d.webhook_id).collect();
+        webhook_ids.sort();
+        webhook_ids.dedup();
+        let webhooks: HashMap<Uuid, Webhook> = Webhook::find_by_ids(&self.pool, &webhook_ids)
+            .await?
+            .into_iter()
+            .map(|webhook| (webhook.id, webhook))
+            .collect();
+
+        for delivery in deliveries {
+            let attempt = match webhooks.get(&delivery.webhook_id) {
+                Some(webhook) if webhook.is_active => self.send(webhook, &delivery).await,
+                _ => DeliveryAttempt {
+                    response_status: None,
+                    error: Some("Webhook was paused".to_string()),
+                    retry_at: None,
+                },
+            };
+
+            match (&attempt.error, attempt.retry_at) {
+                (None, _) => summary.delivered += 1,
+                (Some(_), Some(_)) => summary.retrying += 1,
+                (Some(error), None) => {
+                    warn!(
+                        "Giving up webhook delivery {} to webhook {}: {}",
+                        delivery.id, delivery.webhook_id, error
+                    );
+                    summary.failed += 1;
+                }
+            }
+
+            WebhookDelivery::record_attempt(&self.pool, delivery.id, &attempt).await?;
+        }
+
+        info!(
+            "Webhook dispatch: {} delivered, {} to retry, {} failed",
+            summary.delivered, summary.retrying, summary.failed
+        );
+
+        Ok(summary)
+    }
+
+    /// Make one attempt at a delivery
+    async fn send(&self, webhook: &Webhook, delivery: &WebhookDelivery) -> DeliveryAttempt {
+        let secret = match webhook.signing_secret(shared_secret_cipher().ok()) {
+            Ok(secret) => secret,
+            Err(e) => {
+                return DeliveryAttempt {
+                    response_status: None,
+                    error: Some(format!("Cannot sign delivery: {}", e)),
+                    retry_at: retry_delay(delivery.attempts + 1).map(|delay| Utc::now() + delay),
+                }
+            }
+        };
+        let body = delivery.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+
+        let result = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign_delivery(secret.expose(), timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await;
+
+        let (response_status, error) = match result {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16() as i32), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16() as i32),
+                Some(format!("Endpoint answered {}", response.status())),
+            ),
+            Err(e) => (None, Some(format!("Request failed: {}", e))),
+        };
+
+        let retry_at = error
+            .as_ref()
+            .and_then(|_| retry_delay(delivery.attempts + 1))
+            .map(|delay| Utc::now() + delay);
+
+        DeliveryAttempt {
+            response_status,
+            error,
+            retry_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_signature() {
+        // REQUIREMENT: Webhook deliveries are signed with the webhook's secret
+        // PURPOSE: Verify HMAC-SHA256 against RFC 4231 and the signed timestamp
+        // This ensures consumers can verify deliveries with any standard HMAC library
+
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hmac_sha256_hex(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+
+        let body = r#"{"event":"series_updated"}"#;
+        let signature = sign_delivery("0123456789abcdef", 1_740_000_000, body);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(
+            signature,
+            format!(
+                "sha256={}",
+                hmac_sha256_hex(
+                    b"0123456789abcdef",
+                    format!("1740000000.{}", body).as_bytes()
+                )
+            )
+        );
+        assert_ne!(
+            signature,
+            sign_delivery("0123456789abcdef", 1_740_000_001, body)
+        );
+
+        assert!(verify_delivery(
+            "0123456789abcdef",
+            1_740_000_000,
+            body,
+            &signature
+        ));
+        assert!(!verify_delivery(
+            "0123456789abcdef",
+            1_740_000_001,
+            body,
+            &signature
+        ));
+        assert!(!verify_delivery(
+            "fedcba9876543210",
+            1_740_000_000,
+            body,
+            &signature
+        ));
+        assert!(!verify_delivery(
+            "0123456789abcdef",
+            1_740_000_000,
+            body,
+            signature.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_hmac_sha256_hex(b"key", b"message", "not hex"));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        // REQUIREMENT: Failed webhook deliveries are retried with exponential backoff
+        // PURPOSE: Verify the delay doubles per attempt, is capped, and retries stop
+        // This ensures a down endpoint is not hammered and deliveries eventually give up
+
+        assert_eq!(retry_delay(1), Some(Duration::seconds(30)));
+        assert_eq!(retry_delay(2), Some(Duration::seconds(60)));
+        assert_eq!(retry_delay(7), Some(Duration::seconds(1920)));
+        assert_eq!(retry_delay(MAX_DELIVERY_ATTEMPTS), None);
+    }
+}
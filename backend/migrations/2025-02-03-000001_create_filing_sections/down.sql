@@ -0,0 +1,3 @@
+-- Drop filing text sections
+DROP TRIGGER IF EXISTS update_filing_sections_updated_at ON filing_sections;
+DROP TABLE IF EXISTS filing_sections;
@@ -0,0 +1,34 @@
+-- Filing text sections extracted from 10-K/10-Q primary documents
+-- Stores narrative sections (Risk Factors, MD&A, ...) linked to their financial statement
+-- so they can be searched and summarized alongside the XBRL facts
+
+CREATE TABLE filing_sections (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    statement_id UUID NOT NULL REFERENCES financial_statements(id) ON DELETE CASCADE,
+
+    -- Section identification
+    section_type VARCHAR(50) NOT NULL, -- risk_factors, mdna, market_risk, business, legal_proceedings
+    item_number VARCHAR(10) NOT NULL, -- Item number as printed in the filing (1A, 7, 2, ...)
+    part VARCHAR(10), -- Part I / Part II for 10-Q filings
+    title VARCHAR(500) NOT NULL, -- Heading text as found in the document
+
+    -- Section content
+    content TEXT NOT NULL, -- Plain text with markup removed
+    word_count INTEGER NOT NULL DEFAULT 0,
+    content_hash VARCHAR(64) NOT NULL, -- SHA-256 of content for change detection
+    source_url TEXT, -- Primary document the section was extracted from
+
+    -- Timestamps
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_statement_section UNIQUE (statement_id, section_type)
+);
+
+CREATE INDEX idx_filing_sections_statement_id ON filing_sections(statement_id);
+CREATE INDEX idx_filing_sections_section_type ON filing_sections(section_type);
+CREATE INDEX idx_filing_sections_content ON filing_sections USING GIN (to_tsvector('english', content));
+
+CREATE TRIGGER update_filing_sections_updated_at
+    BEFORE UPDATE ON filing_sections
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,7 @@
+-- Drop organizations and organization-scoped sharing
+DROP INDEX IF EXISTS idx_chart_annotations_organization_id;
+ALTER TABLE chart_annotations DROP COLUMN IF EXISTS organization_id;
+
+DROP TABLE IF EXISTS organization_chart_shares;
+DROP TABLE IF EXISTS organization_members;
+DROP TABLE IF EXISTS organizations;
@@ -0,0 +1,67 @@
+-- Organizations for multi-tenant collaboration
+-- Replaces the free-form users.organization string with real tenancy: organizations,
+-- role-based memberships, and organization-scoped chart and annotation sharing
+
+-- ============================================================================
+-- ORGANIZATIONS
+-- ============================================================================
+
+CREATE TABLE organizations (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    name VARCHAR(255) NOT NULL,
+    slug VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE organization_members (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL DEFAULT 'member'
+        CHECK (role IN ('owner', 'admin', 'member', 'viewer')),
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_organization_member UNIQUE (organization_id, user_id)
+);
+
+-- ============================================================================
+-- ORGANIZATION SHARING
+-- ============================================================================
+
+-- Charts shared with every member of an organization
+CREATE TABLE organization_chart_shares (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    chart_id UUID NOT NULL, -- Custom chart identifier (same as chart_collaborators.chart_id)
+    shared_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    permission_level VARCHAR(20) NOT NULL DEFAULT 'view'
+        CHECK (permission_level IN ('view', 'comment', 'edit', 'admin')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_organization_chart_share UNIQUE (organization_id, chart_id)
+);
+
+-- Annotations scoped to an organization are visible to its members
+ALTER TABLE chart_annotations
+    ADD COLUMN organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_organization_members_organization_id ON organization_members(organization_id);
+CREATE INDEX idx_organization_members_user_id ON organization_members(user_id);
+CREATE INDEX idx_organization_chart_shares_organization_id ON organization_chart_shares(organization_id);
+CREATE INDEX idx_organization_chart_shares_chart_id ON organization_chart_shares(chart_id);
+CREATE INDEX idx_chart_annotations_organization_id ON chart_annotations(organization_id);
+
+CREATE TRIGGER update_organizations_updated_at BEFORE UPDATE ON organizations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_organization_members_updated_at BEFORE UPDATE ON organization_members
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_organization_chart_shares_updated_at BEFORE UPDATE ON organization_chart_shares
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,2 @@
+-- Drop saved charts
+DROP TABLE IF EXISTS saved_charts;
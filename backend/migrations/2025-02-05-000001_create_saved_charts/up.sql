@@ -0,0 +1,27 @@
+-- Saved charts for server-side dashboard persistence
+-- Stores each user's chart configuration, series selection, transformations and
+-- date range so the frontend no longer has to keep dashboards in local storage
+
+CREATE TABLE saved_charts (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    chart_config JSONB NOT NULL DEFAULT '{}',
+    series_ids UUID[] NOT NULL DEFAULT '{}',
+    transformations JSONB NOT NULL DEFAULT '{}', -- Series ID -> transformation name
+    start_date DATE,
+    end_date DATE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT saved_charts_date_range CHECK (
+        start_date IS NULL OR end_date IS NULL OR start_date <= end_date
+    )
+);
+
+CREATE INDEX idx_saved_charts_user_id ON saved_charts(user_id);
+CREATE INDEX idx_saved_charts_updated_at ON saved_charts(updated_at DESC);
+
+CREATE TRIGGER update_saved_charts_updated_at BEFORE UPDATE ON saved_charts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,8 @@
+-- Return dead-lettered items to the plain failed state
+DROP INDEX IF EXISTS idx_crawl_queue_dead_letter;
+
+UPDATE crawl_queue SET status = 'failed' WHERE status = 'dead_letter';
+
+ALTER TABLE crawl_queue DROP CONSTRAINT check_crawl_queue_status;
+ALTER TABLE crawl_queue ADD CONSTRAINT check_crawl_queue_status
+    CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'retrying', 'cancelled'));
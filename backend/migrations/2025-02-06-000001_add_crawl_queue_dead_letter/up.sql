@@ -0,0 +1,15 @@
+-- Dead-letter state for crawl queue items
+-- Items that exhaust their retries are parked as 'dead_letter' for admin review
+-- (requeue or discard) instead of sitting in crawl_queue as ordinary failures
+
+ALTER TABLE crawl_queue DROP CONSTRAINT check_crawl_queue_status;
+ALTER TABLE crawl_queue ADD CONSTRAINT check_crawl_queue_status
+    CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'retrying', 'cancelled', 'dead_letter'));
+
+-- Move items that already exhausted their retries into the dead-letter queue
+UPDATE crawl_queue
+SET status = 'dead_letter'
+WHERE status = 'failed' AND retry_count >= max_retries;
+
+CREATE INDEX idx_crawl_queue_dead_letter ON crawl_queue(updated_at DESC)
+WHERE status = 'dead_letter';
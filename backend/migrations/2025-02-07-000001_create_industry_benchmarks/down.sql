@@ -0,0 +1,2 @@
+-- Drop industry benchmarks
+DROP TABLE IF EXISTS industry_benchmarks;
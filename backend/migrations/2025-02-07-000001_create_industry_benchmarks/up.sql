@@ -0,0 +1,33 @@
+-- Industry benchmarks for financial ratios
+-- Stores the distribution of each ratio across peer groups (SIC code or sector)
+-- per fiscal period, so companies can be compared against their peers
+
+CREATE TABLE industry_benchmarks (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    group_type VARCHAR(20) NOT NULL, -- 'sic' or 'sector'
+    group_code VARCHAR(100) NOT NULL, -- SIC code or sector name
+    ratio_name VARCHAR(100) NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    fiscal_quarter INTEGER, -- NULL for annual periods
+    company_count INTEGER NOT NULL,
+    mean_value NUMERIC(20,6) NOT NULL,
+    median_value NUMERIC(20,6) NOT NULL,
+    p10_value NUMERIC(20,6) NOT NULL,
+    p25_value NUMERIC(20,6) NOT NULL,
+    p75_value NUMERIC(20,6) NOT NULL,
+    p90_value NUMERIC(20,6) NOT NULL,
+    calculated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT industry_benchmarks_group_type CHECK (group_type IN ('sic', 'sector')),
+    CONSTRAINT industry_benchmarks_company_count CHECK (company_count > 0),
+    CONSTRAINT industry_benchmarks_period_unique UNIQUE NULLS NOT DISTINCT
+        (group_type, group_code, ratio_name, fiscal_year, fiscal_quarter)
+);
+
+CREATE INDEX idx_industry_benchmarks_lookup
+    ON industry_benchmarks(ratio_name, group_type, group_code);
+
+CREATE TRIGGER update_industry_benchmarks_updated_at BEFORE UPDATE ON industry_benchmarks
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,2 @@
+-- Drop notifications
+DROP TABLE IF EXISTS notifications;
@@ -0,0 +1,26 @@
+-- In-app notifications for annotation collaboration
+-- Created when an annotation is assigned, replied to, commented on or resolved,
+-- so assignees and authors find out without polling each annotation
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE, -- Recipient
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL, -- User who triggered it
+    notification_type VARCHAR(50) NOT NULL,
+    subject_type VARCHAR(50) NOT NULL, -- Kind of record the notification is about
+    subject_id UUID NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    message TEXT NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_notification_type CHECK (notification_type IN (
+        'annotation_assigned', 'annotation_reply', 'annotation_comment', 'annotation_resolved'
+    )),
+    CONSTRAINT check_notification_subject_type CHECK (subject_type IN (
+        'chart_annotation', 'financial_annotation', 'annotation_assignment'
+    ))
+);
+
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_notifications_user_unread ON notifications(user_id) WHERE read_at IS NULL;
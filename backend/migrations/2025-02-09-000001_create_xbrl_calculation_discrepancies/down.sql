@@ -0,0 +1,2 @@
+-- Drop calculation linkbase inconsistencies
+DROP TABLE IF EXISTS xbrl_calculation_discrepancies;
@@ -0,0 +1,28 @@
+-- Calculation linkbase inconsistencies found in XBRL filings
+-- Each row is a summation (e.g. Assets = AssetsCurrent + AssetsNoncurrent) whose
+-- reported total does not match the weighted sum of its reported children
+
+CREATE TABLE xbrl_calculation_discrepancies (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    statement_id UUID NOT NULL REFERENCES financial_statements(id) ON DELETE CASCADE,
+
+    -- Where the summation is defined
+    link_role TEXT NOT NULL, -- Extended link role of the calculation network (statement)
+    parent_concept VARCHAR(255) NOT NULL, -- Total concept, e.g. us-gaap:Assets
+    child_concepts TEXT[] NOT NULL DEFAULT '{}', -- Contributing concepts that were reported
+
+    -- Which facts were compared
+    context_ref VARCHAR(255) NOT NULL,
+    unit_ref VARCHAR(100),
+
+    -- Values
+    reported_value NUMERIC(30,6) NOT NULL,
+    calculated_value NUMERIC(30,6) NOT NULL,
+    difference NUMERIC(30,6) NOT NULL, -- reported_value - calculated_value
+    tolerance NUMERIC(30,6) NOT NULL, -- Rounding tolerance derived from the facts' decimals
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_xbrl_calculation_discrepancies_statement_id ON xbrl_calculation_discrepancies(statement_id);
+CREATE INDEX idx_xbrl_calculation_discrepancies_parent_concept ON xbrl_calculation_discrepancies(parent_concept);
@@ -0,0 +1,14 @@
+-- Drop series alert rules and their notifications
+DELETE FROM notifications WHERE notification_type = 'series_alert_triggered';
+
+ALTER TABLE notifications DROP CONSTRAINT check_notification_type;
+ALTER TABLE notifications ADD CONSTRAINT check_notification_type CHECK (notification_type IN (
+    'annotation_assigned', 'annotation_reply', 'annotation_comment', 'annotation_resolved'
+));
+
+ALTER TABLE notifications DROP CONSTRAINT check_notification_subject_type;
+ALTER TABLE notifications ADD CONSTRAINT check_notification_subject_type CHECK (subject_type IN (
+    'chart_annotation', 'financial_annotation', 'annotation_assignment'
+));
+
+DROP TABLE IF EXISTS series_alert_rules;
@@ -0,0 +1,46 @@
+-- User-defined alerts on economic series
+-- A rule fires when its condition holds for the latest `window_periods`
+-- observations of a series, and fires again only after the condition has
+-- stopped holding in between
+
+CREATE TABLE series_alert_rules (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    condition VARCHAR(20) NOT NULL,
+    threshold NUMERIC(20,6) NOT NULL,
+    window_periods INTEGER NOT NULL DEFAULT 1,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    is_triggered BOOLEAN NOT NULL DEFAULT FALSE, -- Condition held at the last evaluation
+    last_evaluated_at TIMESTAMPTZ,
+    last_triggered_at TIMESTAMPTZ,
+    last_triggered_date DATE, -- Observation date that last fired the rule
+    last_triggered_value NUMERIC(20,6), -- Value or YoY change that fired the rule
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_series_alert_condition CHECK (condition IN (
+        'value_above', 'value_below', 'yoy_above', 'yoy_below'
+    )),
+    CONSTRAINT check_series_alert_window CHECK (window_periods BETWEEN 1 AND 36)
+);
+
+CREATE INDEX idx_series_alert_rules_user ON series_alert_rules(user_id);
+CREATE INDEX idx_series_alert_rules_series_active ON series_alert_rules(series_id) WHERE is_active;
+
+CREATE TRIGGER update_series_alert_rules_updated_at
+    BEFORE UPDATE ON series_alert_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Alert notifications share the notification feed
+ALTER TABLE notifications DROP CONSTRAINT check_notification_type;
+ALTER TABLE notifications ADD CONSTRAINT check_notification_type CHECK (notification_type IN (
+    'annotation_assigned', 'annotation_reply', 'annotation_comment', 'annotation_resolved',
+    'series_alert_triggered'
+));
+
+ALTER TABLE notifications DROP CONSTRAINT check_notification_subject_type;
+ALTER TABLE notifications ADD CONSTRAINT check_notification_subject_type CHECK (subject_type IN (
+    'chart_annotation', 'financial_annotation', 'annotation_assignment', 'series_alert_rule'
+));
@@ -0,0 +1,13 @@
+-- Planner row estimate for a query
+-- Used for totalCount on paginated connections, where an exact COUNT over
+-- tens of thousands of rows would cost more than the page itself
+
+CREATE OR REPLACE FUNCTION count_estimate(query TEXT)
+RETURNS BIGINT AS $$
+DECLARE
+    plan JSON;
+BEGIN
+    EXECUTE 'EXPLAIN (FORMAT JSON) ' || query INTO plan;
+    RETURN (plan->0->'Plan'->>'Plan Rows')::BIGINT;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,7 @@
+-- Drop canonical values of financial line items
+DROP INDEX IF EXISTS idx_financial_line_items_normalized_unit;
+
+ALTER TABLE financial_line_items
+    DROP COLUMN IF EXISTS fx_rate,
+    DROP COLUMN IF EXISTS normalized_unit,
+    DROP COLUMN IF EXISTS normalized_value;
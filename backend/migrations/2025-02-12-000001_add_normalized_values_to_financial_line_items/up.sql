@@ -0,0 +1,11 @@
+-- Canonical values of financial line items
+-- `value`/`unit` keep the fact as reported in the filing; the normalized
+-- columns hold it in canonical units (absolute USD, shares) so facts from
+-- different filers and currencies can be compared directly
+
+ALTER TABLE financial_line_items
+    ADD COLUMN normalized_value NUMERIC(20,6),
+    ADD COLUMN normalized_unit VARCHAR(50),
+    ADD COLUMN fx_rate NUMERIC(20,10);
+
+CREATE INDEX idx_financial_line_items_normalized_unit ON financial_line_items(normalized_unit);
@@ -0,0 +1,3 @@
+-- Drop manual data corrections
+-- Correcting revisions stay in data_points as ordinary revisions
+DROP TABLE IF EXISTS data_point_corrections;
@@ -0,0 +1,20 @@
+-- Manual data corrections
+-- A correction is written as a new revision of the observation (the
+-- original values are never overwritten); this table marks which revisions
+-- are corrections so they can be filtered out of queries, and why they were made
+
+CREATE TABLE data_point_corrections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Revision written by the correction
+    data_point_id UUID NOT NULL UNIQUE REFERENCES data_points(id) ON DELETE CASCADE,
+    -- Revision that was found to be wrong
+    corrected_data_point_id UUID REFERENCES data_points(id) ON DELETE SET NULL,
+    series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    corrected_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_data_point_correction_reason CHECK (length(trim(reason)) > 0)
+);
+
+CREATE INDEX idx_data_point_corrections_series ON data_point_corrections(series_id, created_at DESC);
@@ -0,0 +1,3 @@
+-- Drop encrypted data source credentials
+-- Sources fall back to the environment variables named in data_sources.api_key_name
+DROP TABLE IF EXISTS data_source_credentials;
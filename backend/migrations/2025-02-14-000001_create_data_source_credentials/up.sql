@@ -0,0 +1,19 @@
+-- Encrypted data source credentials
+-- API keys entered by administrators are stored AES-256-GCM encrypted; the
+-- encryption key never reaches the database, only the id of the key used
+
+CREATE TABLE data_source_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    data_source_id UUID NOT NULL UNIQUE REFERENCES data_sources(id) ON DELETE CASCADE,
+    -- Id of the encryption key, so keys can be rotated
+    key_id VARCHAR(100) NOT NULL,
+    nonce BYTEA NOT NULL,
+    ciphertext BYTEA NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_data_source_credential_nonce CHECK (octet_length(nonce) = 12)
+);
+
+CREATE INDEX idx_data_source_credentials_key_id ON data_source_credentials(key_id);
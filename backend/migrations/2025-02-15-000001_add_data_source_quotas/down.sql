@@ -0,0 +1,8 @@
+-- Drop per data source quotas and usage counters
+DROP TABLE IF EXISTS data_source_usage;
+
+ALTER TABLE data_sources
+    DROP CONSTRAINT IF EXISTS check_data_source_daily_request_quota,
+    DROP CONSTRAINT IF EXISTS check_data_source_daily_byte_quota,
+    DROP COLUMN IF EXISTS daily_request_quota,
+    DROP COLUMN IF EXISTS daily_byte_quota;
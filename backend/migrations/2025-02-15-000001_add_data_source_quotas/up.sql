@@ -0,0 +1,21 @@
+-- Daily bandwidth and request quotas per data source
+-- NULL quotas mean unlimited; usage is counted per UTC day so every crawler
+-- instance enforces the same budget
+
+ALTER TABLE data_sources
+    ADD COLUMN daily_byte_quota BIGINT,
+    ADD COLUMN daily_request_quota INTEGER,
+    ADD CONSTRAINT check_data_source_daily_byte_quota CHECK (daily_byte_quota IS NULL OR daily_byte_quota > 0),
+    ADD CONSTRAINT check_data_source_daily_request_quota CHECK (daily_request_quota IS NULL OR daily_request_quota > 0);
+
+CREATE TABLE data_source_usage (
+    data_source_id UUID NOT NULL REFERENCES data_sources(id) ON DELETE CASCADE,
+    usage_date DATE NOT NULL,
+    bytes_downloaded BIGINT NOT NULL DEFAULT 0,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (data_source_id, usage_date)
+);
+
+CREATE INDEX idx_data_source_usage_usage_date ON data_source_usage(usage_date);
@@ -0,0 +1,3 @@
+-- Drop learning progress and achievements
+DROP TABLE IF EXISTS learning_achievements;
+DROP TABLE IF EXISTS learning_progress;
@@ -0,0 +1,46 @@
+-- Learning progress and achievements for the educational content library
+-- Module, section, question and learning path IDs refer to the content
+-- library compiled into the backend, so they have no foreign keys
+
+CREATE TABLE learning_progress (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    module_id UUID NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'in_progress',
+    progress_percentage DOUBLE PRECISION NOT NULL DEFAULT 0,
+    time_spent_minutes INTEGER NOT NULL DEFAULT 0,
+    completed_sections UUID[] NOT NULL DEFAULT '{}',
+    completed_exercises UUID[] NOT NULL DEFAULT '{}',
+    -- Latest graded answer per assessment question
+    quiz_scores JSONB NOT NULL DEFAULT '[]',
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_accessed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+
+    CONSTRAINT uq_learning_progress_user_module UNIQUE (user_id, module_id),
+    CONSTRAINT check_learning_progress_status CHECK (status IN ('not_started', 'in_progress', 'completed', 'paused', 'abandoned')),
+    CONSTRAINT check_learning_progress_percentage CHECK (progress_percentage >= 0 AND progress_percentage <= 100),
+    CONSTRAINT check_learning_progress_time_spent CHECK (time_spent_minutes >= 0)
+);
+
+CREATE INDEX idx_learning_progress_user_id ON learning_progress(user_id, last_accessed_at DESC);
+
+CREATE TABLE learning_achievements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Identifies what was achieved, e.g. 'module_completion:<module id>', so
+    -- each achievement is awarded at most once per user
+    achievement_key VARCHAR(200) NOT NULL,
+    achievement_type VARCHAR(50) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    criteria TEXT NOT NULL,
+    earned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    module_id UUID,
+    learning_path_id UUID,
+    badge_url VARCHAR(500),
+
+    CONSTRAINT uq_learning_achievements_user_key UNIQUE (user_id, achievement_key)
+);
+
+CREATE INDEX idx_learning_achievements_user_id ON learning_achievements(user_id, earned_at DESC);
@@ -0,0 +1,2 @@
+-- Drop links between equivalent series
+DROP TABLE IF EXISTS series_links;
@@ -0,0 +1,21 @@
+-- Links between equivalent series published by different data sources
+-- (e.g. US GDP from FRED and from the World Bank). Each pair is stored once,
+-- with the smaller series ID first; equivalence follows links transitively.
+
+CREATE TABLE series_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE CASCADE,
+    linked_series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE CASCADE,
+    -- 'curated' links were made by a person, 'heuristic' links by the title matcher
+    link_source VARCHAR(20) NOT NULL,
+    confidence DOUBLE PRECISION NOT NULL DEFAULT 1,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_series_links_pair UNIQUE (series_id, linked_series_id),
+    CONSTRAINT check_series_links_order CHECK (series_id < linked_series_id),
+    CONSTRAINT check_series_links_source CHECK (link_source IN ('curated', 'heuristic')),
+    CONSTRAINT check_series_links_confidence CHECK (confidence > 0 AND confidence <= 1)
+);
+
+CREATE INDEX idx_series_links_linked_series_id ON series_links(linked_series_id);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_saved_charts_public;
+
+ALTER TABLE saved_charts DROP COLUMN IF EXISTS is_public;
@@ -0,0 +1,7 @@
+-- Public embeds of saved charts
+-- Owners opt a chart in to being rendered as an image at /embed/chart/{id}.png|svg,
+-- which serves documents and social previews without authentication
+
+ALTER TABLE saved_charts ADD COLUMN is_public BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_saved_charts_public ON saved_charts(id) WHERE is_public;
@@ -0,0 +1,13 @@
+DROP MATERIALIZED VIEW IF EXISTS search_vocabulary;
+DROP TABLE IF EXISTS search_synonyms;
+
+DROP TRIGGER IF EXISTS companies_search_vector_trigger ON companies;
+DROP FUNCTION IF EXISTS companies_search_vector_update();
+DROP INDEX IF EXISTS idx_companies_search_vector;
+ALTER TABLE companies DROP COLUMN IF EXISTS search_vector;
+
+DROP TRIGGER IF EXISTS economic_series_search_vector_trigger ON economic_series;
+DROP FUNCTION IF EXISTS economic_series_search_vector_update();
+DROP INDEX IF EXISTS idx_economic_series_title_trgm;
+DROP INDEX IF EXISTS idx_economic_series_search_vector;
+ALTER TABLE economic_series DROP COLUMN IF EXISTS search_vector;
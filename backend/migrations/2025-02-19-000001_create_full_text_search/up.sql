@@ -0,0 +1,182 @@
+-- Full-text search for series and companies
+-- search_vector columns are maintained by triggers and queried with raw SQL only,
+-- so they are not mapped in the Diesel schema. Synonyms expand queries before they
+-- reach websearch_to_tsquery; the trigram-indexed vocabulary backs spelling suggestions.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Economic series: title and external ID rank above description and units
+ALTER TABLE economic_series ADD COLUMN search_vector tsvector;
+
+CREATE OR REPLACE FUNCTION economic_series_search_vector_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector('english', coalesce(NEW.title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(NEW.external_id, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.description, '')), 'B') ||
+        setweight(to_tsvector('english', coalesce(NEW.units, '')), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER economic_series_search_vector_trigger
+    BEFORE INSERT OR UPDATE OF title, external_id, description, units ON economic_series
+    FOR EACH ROW EXECUTE FUNCTION economic_series_search_vector_update();
+
+-- Backfill without touching updated_at, which drives caching and change detection
+ALTER TABLE economic_series DISABLE TRIGGER update_economic_series_updated_at;
+UPDATE economic_series SET title = title;
+ALTER TABLE economic_series ENABLE TRIGGER update_economic_series_updated_at;
+
+CREATE INDEX idx_economic_series_search_vector ON economic_series USING GIN (search_vector);
+-- Also serves the ILIKE prefix matches behind search completions
+CREATE INDEX idx_economic_series_title_trgm ON economic_series USING GIN (title gin_trgm_ops);
+
+-- Companies: ticker and name rank above legal name and industry classification
+ALTER TABLE companies ADD COLUMN search_vector tsvector;
+
+CREATE OR REPLACE FUNCTION companies_search_vector_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector :=
+        setweight(to_tsvector('simple', coalesce(NEW.ticker, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(NEW.legal_name, '')), 'B') ||
+        setweight(to_tsvector('english',
+            coalesce(NEW.industry, '') || ' ' ||
+            coalesce(NEW.sector, '') || ' ' ||
+            coalesce(NEW.sic_description, '')), 'C');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER companies_search_vector_trigger
+    BEFORE INSERT OR UPDATE OF ticker, name, legal_name, industry, sector, sic_description ON companies
+    FOR EACH ROW EXECUTE FUNCTION companies_search_vector_update();
+
+ALTER TABLE companies DISABLE TRIGGER update_companies_updated_at;
+UPDATE companies SET name = name;
+ALTER TABLE companies ENABLE TRIGGER update_companies_updated_at;
+
+CREATE INDEX idx_companies_search_vector ON companies USING GIN (search_vector);
+
+-- Synonyms: each term is interchangeable with its synonyms in queries
+CREATE TABLE search_synonyms (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    term VARCHAR(100) NOT NULL UNIQUE,
+    synonyms TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT search_synonyms_term_lowercase CHECK (term = lower(term))
+);
+
+CREATE INDEX idx_search_synonyms_synonyms ON search_synonyms USING GIN (synonyms);
+
+CREATE TRIGGER update_search_synonyms_updated_at BEFORE UPDATE ON search_synonyms
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Seeded from economic_synonyms.syn
+INSERT INTO search_synonyms (term, synonyms) VALUES
+    ('gdp', ARRAY['gross domestic product', 'economic output', 'national income', 'total output']),
+    ('gnp', ARRAY['gross national product']),
+    ('real gdp', ARRAY['inflation adjusted gdp', 'constant dollar gdp']),
+    ('nominal gdp', ARRAY['current dollar gdp', 'unadjusted gdp']),
+    ('unemployment', ARRAY['jobless', 'joblessness', 'out of work']),
+    ('employment', ARRAY['jobs', 'labor', 'workforce', 'workers']),
+    ('labor force', ARRAY['workforce', 'working population']),
+    ('nonfarm payrolls', ARRAY['employment', 'jobs', 'payroll employment']),
+    ('jobless rate', ARRAY['unemployment rate']),
+    ('labor participation', ARRAY['workforce participation']),
+    ('inflation', ARRAY['price increases', 'rising prices', 'price growth']),
+    ('deflation', ARRAY['falling prices', 'price decreases']),
+    ('cpi', ARRAY['consumer price index', 'consumer prices']),
+    ('ppi', ARRAY['producer price index', 'producer prices']),
+    ('core inflation', ARRAY['underlying inflation', 'ex food energy']),
+    ('headline inflation', ARRAY['total inflation', 'all items inflation']),
+    ('price level', ARRAY['cost of living', 'price index']),
+    ('interest rates', ARRAY['rates', 'borrowing costs', 'lending rates']),
+    ('fed funds rate', ARRAY['federal funds rate', 'overnight rate', 'policy rate']),
+    ('discount rate', ARRAY['fed discount rate']),
+    ('prime rate', ARRAY['bank prime rate', 'lending rate']),
+    ('yield', ARRAY['return', 'interest return']),
+    ('bond yield', ARRAY['bond return', 'treasury yield']),
+    ('manufacturing', ARRAY['industry', 'industrial production', 'factory output']),
+    ('retail sales', ARRAY['consumer spending', 'retail trade']),
+    ('industrial production', ARRAY['manufacturing output', 'factory production']),
+    ('capacity utilization', ARRAY['factory usage', 'industrial capacity']),
+    ('business investment', ARRAY['capital spending', 'capex']),
+    ('productivity', ARRAY['output per hour', 'efficiency']),
+    ('housing', ARRAY['real estate', 'residential', 'homes']),
+    ('home sales', ARRAY['house sales', 'residential sales']),
+    ('housing starts', ARRAY['new construction', 'building permits']),
+    ('home prices', ARRAY['house prices', 'real estate prices']),
+    ('mortgage rates', ARRAY['home loan rates', 'housing finance rates']),
+    ('exports', ARRAY['foreign sales', 'overseas sales']),
+    ('imports', ARRAY['foreign purchases', 'overseas purchases']),
+    ('trade balance', ARRAY['trade deficit', 'trade surplus', 'net exports']),
+    ('exchange rates', ARRAY['currency rates', 'fx rates', 'foreign exchange']),
+    ('stock market', ARRAY['equity market', 'shares', 'equities']),
+    ('bond market', ARRAY['debt market', 'fixed income']),
+    ('market volatility', ARRAY['price swings', 'market fluctuations']),
+    ('market capitalization', ARRAY['market cap', 'market value']),
+    ('leading indicators', ARRAY['forward looking', 'predictive indicators']),
+    ('lagging indicators', ARRAY['backward looking', 'confirming indicators']),
+    ('coincident indicators', ARRAY['current indicators', 'real time indicators']),
+    ('economic growth', ARRAY['expansion', 'gdp growth', 'output growth']),
+    ('recession', ARRAY['contraction', 'downturn', 'economic decline']),
+    ('recovery', ARRAY['expansion', 'upturn', 'economic growth']),
+    ('government spending', ARRAY['public spending', 'fiscal spending']),
+    ('budget deficit', ARRAY['fiscal deficit', 'government shortfall']),
+    ('budget surplus', ARRAY['fiscal surplus', 'government excess']),
+    ('national debt', ARRAY['government debt', 'public debt']),
+    ('fiscal policy', ARRAY['government policy', 'budget policy']),
+    ('taxes', ARRAY['taxation', 'tax revenue', 'government revenue']),
+    ('population', ARRAY['demographics', 'people']),
+    ('working age', ARRAY['labor force age', 'employment age']),
+    ('retirement age', ARRAY['senior population', 'elderly']),
+    ('birth rate', ARRAY['fertility rate', 'population growth']),
+    ('migration', ARRAY['immigration', 'population movement']),
+    ('seasonally adjusted', ARRAY['sa', 'seasonal adjustment']),
+    ('not seasonally adjusted', ARRAY['nsa', 'unadjusted']),
+    ('annualized', ARRAY['yearly rate', 'annual rate']),
+    ('quarterly', ARRAY['q1', 'q2', 'q3', 'q4', 'quarter']),
+    ('monthly', ARRAY['month', 'per month']),
+    ('yearly', ARRAY['annual', 'per year', 'year over year']),
+    ('yoy', ARRAY['year over year', 'annual change']),
+    ('mom', ARRAY['month over month', 'monthly change']),
+    ('qoq', ARRAY['quarter over quarter', 'quarterly change']),
+    ('index', ARRAY['measure', 'indicator', 'gauge']),
+    ('rate', ARRAY['percentage', 'percent', 'ratio']),
+    ('level', ARRAY['amount', 'quantity', 'value']),
+    ('change', ARRAY['difference', 'variation', 'movement']),
+    ('growth', ARRAY['increase', 'expansion', 'rise']),
+    ('decline', ARRAY['decrease', 'fall', 'drop']),
+    ('trend', ARRAY['direction', 'pattern', 'movement']),
+    ('us', ARRAY['united states', 'usa', 'america']),
+    ('national', ARRAY['country', 'nationwide', 'federal']),
+    ('state', ARRAY['regional', 'local']),
+    ('metropolitan', ARRAY['metro', 'urban', 'city']),
+    ('rural', ARRAY['countryside', 'non urban']),
+    ('fed', ARRAY['federal reserve', 'central bank']),
+    ('bls', ARRAY['bureau of labor statistics']),
+    ('bea', ARRAY['bureau of economic analysis']),
+    ('census', ARRAY['census bureau']),
+    ('oecd', ARRAY['organisation for economic cooperation']),
+    ('imf', ARRAY['international monetary fund']),
+    ('pce', ARRAY['personal consumption expenditures']);
+
+-- Words appearing in series titles and company names, for spelling suggestions.
+-- Refreshed by SearchService::refresh_vocabulary.
+CREATE MATERIALIZED VIEW search_vocabulary AS
+SELECT word, ndoc AS document_count
+FROM ts_stat($$
+    SELECT to_tsvector('simple', title) FROM economic_series
+    UNION ALL
+    SELECT to_tsvector('simple', name) FROM companies
+$$)
+WHERE length(word) >= 3 AND word !~ '^[0-9.,]+$';
+
+CREATE UNIQUE INDEX idx_search_vocabulary_word ON search_vocabulary(word);
+CREATE INDEX idx_search_vocabulary_word_trgm ON search_vocabulary USING GIN (word gin_trgm_ops);
@@ -0,0 +1,2 @@
+-- Drop lineage records; the data points and facts they describe are kept
+DROP TABLE IF EXISTS data_lineage;
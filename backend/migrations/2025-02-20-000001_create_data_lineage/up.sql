@@ -0,0 +1,26 @@
+-- Where stored numbers came from
+-- Each row links one stored value to the crawl that fetched it: a data point
+-- to its crawl attempt, or an SEC fact (financial line item) to its filing.
+-- Processing steps are a JSON array of {"name", "detail"} objects in the
+-- order the ingestion pipeline applied them.
+
+CREATE TABLE data_lineage (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    data_point_id UUID UNIQUE REFERENCES data_points(id) ON DELETE CASCADE,
+    financial_line_item_id UUID UNIQUE REFERENCES financial_line_items(id) ON DELETE CASCADE,
+    -- Attempts and filings outlive their rows here only as the fields below
+    crawl_attempt_id UUID REFERENCES crawl_attempts(id) ON DELETE SET NULL,
+    statement_id UUID REFERENCES financial_statements(id) ON DELETE SET NULL,
+    source VARCHAR(50) NOT NULL,
+    source_url TEXT,
+    crawled_at TIMESTAMPTZ NOT NULL,
+    processing_steps JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_data_lineage_subject CHECK (
+        (data_point_id IS NULL) <> (financial_line_item_id IS NULL)
+    )
+);
+
+CREATE INDEX idx_data_lineage_crawl_attempt_id ON data_lineage(crawl_attempt_id);
+CREATE INDEX idx_data_lineage_statement_id ON data_lineage(statement_id);
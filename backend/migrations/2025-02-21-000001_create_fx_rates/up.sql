@@ -0,0 +1,19 @@
+-- Daily foreign exchange reference rates
+-- One row per currency and day: 1 unit of base_currency buys `rate` units of
+-- quote_currency. ECB reference rates are quoted against the euro, so rates
+-- between two other currencies are derived through EUR.
+
+CREATE TABLE fx_rates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    base_currency VARCHAR(3) NOT NULL,
+    quote_currency VARCHAR(3) NOT NULL,
+    rate_date DATE NOT NULL,
+    rate NUMERIC(20, 10) NOT NULL CHECK (rate > 0),
+    source VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_fx_rate UNIQUE (base_currency, quote_currency, rate_date)
+);
+
+-- Conversions look up the latest rate on or before a date
+CREATE INDEX idx_fx_rates_quote_date ON fx_rates(quote_currency, rate_date DESC);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS series_quality_scores;
@@ -0,0 +1,21 @@
+-- Data quality score per series
+-- Written by the data quality job: outliers among period-over-period changes,
+-- periods missing for the series' frequency, and staleness of the latest
+-- observation, combined into a score from 0 (unusable) to 1 (clean).
+-- checked_through is the newest data point creation time the score covers, so
+-- the next run only rescores series that received new points since.
+
+CREATE TABLE series_quality_scores (
+    series_id UUID PRIMARY KEY REFERENCES economic_series(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 1),
+    observation_count INTEGER NOT NULL,
+    outlier_count INTEGER NOT NULL,
+    missing_periods INTEGER NOT NULL,
+    last_observation_date DATE,
+    is_stale BOOLEAN NOT NULL,
+    checked_through TIMESTAMPTZ NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Dashboards list the worst series first
+CREATE INDEX idx_series_quality_scores_score ON series_quality_scores(score);
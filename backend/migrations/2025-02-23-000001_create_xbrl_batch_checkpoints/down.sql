@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_financial_line_items_fact;
+DROP TABLE IF EXISTS xbrl_batch_checkpoints;
@@ -0,0 +1,32 @@
+-- Progress of long-running XBRL batch processing jobs
+-- A batch walks stored filings in financial_statements.id order and records
+-- each filing's progress in its xbrl_processing_status (pending -> processing
+-- -> completed or failed). last_statement_id is the last filing that reached a
+-- final status; a restarted batch resumes after it, picking up the filing that
+-- was still processing when the job stopped. finished_at is set when a pass
+-- runs out of filings, and the next run starts a new pass from the beginning.
+
+CREATE TABLE xbrl_batch_checkpoints (
+    batch_name VARCHAR(100) PRIMARY KEY,
+    last_statement_id UUID,
+    filings_completed INTEGER NOT NULL DEFAULT 0,
+    filings_failed INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+-- Facts are upserted by concept, context and unit, so parsing a filing again
+-- updates its line items instead of duplicating them. Duplicates stored by
+-- earlier runs are removed first, keeping the oldest row since annotations and
+-- lineage reference line items by id.
+DELETE FROM financial_line_items newer
+USING financial_line_items older
+WHERE newer.statement_id = older.statement_id
+  AND newer.taxonomy_concept = older.taxonomy_concept
+  AND newer.context_ref = older.context_ref
+  AND newer.unit = older.unit
+  AND (older.created_at, older.id) < (newer.created_at, newer.id);
+
+CREATE UNIQUE INDEX idx_financial_line_items_fact
+    ON financial_line_items(statement_id, taxonomy_concept, context_ref, unit);
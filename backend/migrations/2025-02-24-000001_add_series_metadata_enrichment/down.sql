@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS idx_series_metadata_enriched_at;
+DROP INDEX IF EXISTS idx_series_metadata_tags;
+DROP INDEX IF EXISTS idx_series_metadata_categories;
+DROP INDEX IF EXISTS idx_series_metadata_geography;
+
+ALTER TABLE series_metadata
+    DROP COLUMN IF EXISTS enriched_at,
+    DROP COLUMN IF EXISTS tags,
+    DROP COLUMN IF EXISTS categories,
+    DROP COLUMN IF EXISTS geography;
@@ -0,0 +1,18 @@
+-- Category, tag and geography metadata from source catalogs
+-- Written by the series discovery enrichment pass. geographic_level holds the
+-- kind of area a series covers (National, State, MSA, County) and geography
+-- the area itself (e.g. "California"). categories are the catalog categories
+-- the source files the series under; tags are its topic keywords.
+-- enriched_at is NULL until the pass has run for a series.
+
+ALTER TABLE series_metadata
+    ADD COLUMN geography VARCHAR(255),
+    ADD COLUMN categories TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN enriched_at TIMESTAMPTZ;
+
+-- Search filters series by geography and topic
+CREATE INDEX idx_series_metadata_geography ON series_metadata(lower(geography));
+CREATE INDEX idx_series_metadata_categories ON series_metadata USING GIN(categories);
+CREATE INDEX idx_series_metadata_tags ON series_metadata USING GIN(tags);
+CREATE INDEX idx_series_metadata_enriched_at ON series_metadata(enriched_at);
@@ -0,0 +1,50 @@
+-- Webhooks notify downstream consumers when crawled data lands
+-- A webhook subscribes to events for one data source or one series.
+-- Deliveries are queued per webhook and retried with exponential backoff.
+
+CREATE TABLE webhooks (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    url TEXT NOT NULL,
+    secret VARCHAR(255) NOT NULL, -- Key for the HMAC-SHA256 signature of each delivery
+    source_id UUID REFERENCES data_sources(id) ON DELETE CASCADE,
+    series_id UUID REFERENCES economic_series(id) ON DELETE CASCADE,
+    event_types TEXT[] NOT NULL,
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_webhook_scope CHECK ((source_id IS NULL) <> (series_id IS NULL)),
+    CONSTRAINT check_webhook_event_types CHECK (
+        cardinality(event_types) > 0
+        AND event_types <@ ARRAY['series_updated', 'crawl_failed']::TEXT[]
+    )
+);
+
+CREATE INDEX idx_webhooks_source_active ON webhooks(source_id) WHERE is_active;
+CREATE INDEX idx_webhooks_series_active ON webhooks(series_id) WHERE is_active;
+
+CREATE TRIGGER update_webhooks_updated_at
+    BEFORE UPDATE ON webhooks
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ,
+    response_status INTEGER, -- HTTP status of the last attempt, if a response arrived
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_webhook_delivery_status CHECK (status IN ('pending', 'delivered', 'failed'))
+);
+
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at DESC);
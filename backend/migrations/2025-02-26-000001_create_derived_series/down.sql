@@ -0,0 +1,3 @@
+-- Drop derived series definitions; their economic series and data points are kept
+DROP TABLE IF EXISTS derived_series_inputs;
+DROP TABLE IF EXISTS derived_series;
@@ -0,0 +1,34 @@
+-- Derived series are formulas over existing series, e.g. real GDP = nominal / deflator * 100
+-- Each derived series stores its values in an ordinary economic series (series_id), so
+-- charts, transformations and alerts work on it unchanged. Inputs bind formula
+-- variables to series, which may themselves be derived.
+
+CREATE TABLE derived_series (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    series_id UUID NOT NULL UNIQUE REFERENCES economic_series(id) ON DELETE CASCADE,
+    formula TEXT NOT NULL,
+    formula_ast JSONB NOT NULL, -- Parsed formula, evaluated on every recompute
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    last_computed_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_derived_series_created_by ON derived_series(created_by);
+
+CREATE TRIGGER update_derived_series_updated_at
+    BEFORE UPDATE ON derived_series
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE derived_series_inputs (
+    derived_series_id UUID NOT NULL REFERENCES derived_series(id) ON DELETE CASCADE,
+    variable VARCHAR(64) NOT NULL,
+    -- An input series cannot be deleted while a formula uses it
+    input_series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE RESTRICT,
+
+    PRIMARY KEY (derived_series_id, variable),
+    CONSTRAINT check_derived_series_variable CHECK (variable ~ '^[A-Za-z_][A-Za-z0-9_]*$')
+);
+
+CREATE INDEX idx_derived_series_inputs_input ON derived_series_inputs(input_series_id);
@@ -0,0 +1,2 @@
+-- Drop export jobs; files left in export storage are not removed
+DROP TABLE IF EXISTS export_jobs;
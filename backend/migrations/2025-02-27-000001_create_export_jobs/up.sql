@@ -0,0 +1,42 @@
+-- Bulk exports are produced by a background worker instead of over HTTP
+-- A job names a set of series, a date range and a file format. The worker
+-- writes the file to export storage; users download it through a signed URL
+-- until it expires.
+
+CREATE TABLE export_jobs (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    series_ids UUID[] NOT NULL,
+    start_date DATE,
+    end_date DATE,
+    format VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    storage_key TEXT, -- Location of the file in export storage, once written
+    file_size_bytes BIGINT,
+    row_count BIGINT,
+    error_message TEXT,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ, -- When the file is deleted
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_export_job_format CHECK (format IN ('csv', 'parquet', 'xlsx')),
+    CONSTRAINT check_export_job_status CHECK (
+        status IN ('pending', 'running', 'completed', 'failed', 'expired')
+    ),
+    CONSTRAINT check_export_job_series CHECK (cardinality(series_ids) > 0),
+    CONSTRAINT check_export_job_dates CHECK (
+        start_date IS NULL OR end_date IS NULL OR start_date <= end_date
+    )
+);
+
+CREATE INDEX idx_export_jobs_pending ON export_jobs(created_at) WHERE status = 'pending';
+CREATE INDEX idx_export_jobs_running ON export_jobs(started_at) WHERE status = 'running';
+CREATE INDEX idx_export_jobs_expiring ON export_jobs(expires_at) WHERE status = 'completed';
+CREATE INDEX idx_export_jobs_user ON export_jobs(user_id, created_at DESC);
+
+CREATE TRIGGER update_export_jobs_updated_at
+    BEFORE UPDATE ON export_jobs
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
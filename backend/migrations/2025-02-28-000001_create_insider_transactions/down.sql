@@ -0,0 +1,3 @@
+-- Drop insider transactions and the Form 4 crawl log
+DROP TABLE IF EXISTS insider_crawl_days;
+DROP TABLE IF EXISTS insider_transactions;
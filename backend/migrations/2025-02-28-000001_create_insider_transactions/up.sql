@@ -0,0 +1,59 @@
+-- Insider transactions reported on SEC Form 4
+-- One row per transaction line of a Form 4 or 4/A, from both the
+-- non-derivative and the derivative tables. The issuer is kept by CIK so
+-- filings of companies not (yet) in the companies table are still stored.
+
+CREATE TABLE insider_transactions (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+
+    -- Filing
+    accession_number VARCHAR(20) NOT NULL,
+    form_type VARCHAR(10) NOT NULL, -- 4 or 4/A
+    filed_date DATE NOT NULL,
+    line_number INTEGER NOT NULL, -- Position of the transaction within the filing
+
+    -- Issuer
+    issuer_cik VARCHAR(10) NOT NULL,
+    issuer_name VARCHAR(255) NOT NULL,
+    issuer_ticker VARCHAR(10),
+
+    -- Insider and their relationship to the issuer
+    insider_cik VARCHAR(10) NOT NULL,
+    insider_name VARCHAR(255) NOT NULL,
+    is_director BOOLEAN NOT NULL DEFAULT FALSE,
+    is_officer BOOLEAN NOT NULL DEFAULT FALSE,
+    is_ten_percent_owner BOOLEAN NOT NULL DEFAULT FALSE,
+    is_other BOOLEAN NOT NULL DEFAULT FALSE,
+    officer_title VARCHAR(255),
+
+    -- Transaction
+    security_title VARCHAR(255) NOT NULL,
+    is_derivative BOOLEAN NOT NULL,
+    transaction_date DATE NOT NULL,
+    transaction_code VARCHAR(1) NOT NULL, -- P purchase, S sale, A award, M option exercise, ...
+    shares NUMERIC(24, 4) NOT NULL,
+    price_per_share NUMERIC(20, 6),
+    acquired_disposed VARCHAR(1) NOT NULL, -- A acquired, D disposed
+    shares_owned_after NUMERIC(24, 4),
+    ownership_nature VARCHAR(1) NOT NULL, -- D direct, I indirect
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_insider_transaction_line UNIQUE (accession_number, line_number),
+    CONSTRAINT insider_transactions_acquired_disposed_check CHECK (acquired_disposed IN ('A', 'D')),
+    CONSTRAINT insider_transactions_ownership_nature_check CHECK (ownership_nature IN ('D', 'I'))
+);
+
+CREATE INDEX idx_insider_transactions_issuer ON insider_transactions(issuer_cik, transaction_date DESC);
+CREATE INDEX idx_insider_transactions_insider ON insider_transactions(insider_cik);
+
+-- Days of the EDGAR daily index already crawled for Form 4 filings
+-- The incremental crawl resumes after the latest day recorded here.
+CREATE TABLE insider_crawl_days (
+    index_date DATE PRIMARY KEY,
+    filings_found INTEGER NOT NULL DEFAULT 0,
+    filings_stored INTEGER NOT NULL DEFAULT 0,
+    filings_failed INTEGER NOT NULL DEFAULT 0,
+    transactions_stored INTEGER NOT NULL DEFAULT 0,
+    crawled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,5 @@
+-- Drop 13F holdings, CUSIP mappings and the 13F crawl log
+DROP TABLE IF EXISTS institutional_crawl_days;
+DROP TABLE IF EXISTS cusip_mappings;
+DROP TABLE IF EXISTS institutional_holdings;
+DROP TABLE IF EXISTS institutional_filings;
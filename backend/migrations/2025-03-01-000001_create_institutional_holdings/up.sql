@@ -0,0 +1,86 @@
+-- Institutional holdings reported on SEC Form 13F-HR
+-- Managers with over $100M in US equities report their positions each
+-- quarter. institutional_filings keeps one row per 13F-HR or 13F-HR/A;
+-- institutional_holdings is the quarter's snapshot for each manager. An
+-- original filing or a restatement replaces the manager's snapshot for the
+-- quarter; an amendment that only adds holdings is appended to it.
+
+CREATE TABLE institutional_filings (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    accession_number VARCHAR(20) NOT NULL UNIQUE,
+    form_type VARCHAR(10) NOT NULL, -- 13F-HR or 13F-HR/A
+    amendment_type VARCHAR(20), -- RESTATEMENT or NEW HOLDINGS for amendments
+    manager_cik VARCHAR(10) NOT NULL,
+    manager_name VARCHAR(255) NOT NULL,
+    report_period DATE NOT NULL, -- Quarter end the holdings are reported for
+    filed_date DATE NOT NULL,
+    holdings_count INTEGER NOT NULL DEFAULT 0,
+    total_value_usd NUMERIC(24, 2) NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT institutional_filings_amendment_type_check
+        CHECK (amendment_type IS NULL OR amendment_type IN ('RESTATEMENT', 'NEW HOLDINGS'))
+);
+
+CREATE INDEX idx_institutional_filings_manager ON institutional_filings(manager_cik, report_period);
+
+CREATE TABLE institutional_holdings (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    filing_id UUID NOT NULL REFERENCES institutional_filings(id) ON DELETE CASCADE,
+    manager_cik VARCHAR(10) NOT NULL,
+    report_period DATE NOT NULL,
+
+    -- Security
+    cusip VARCHAR(9) NOT NULL,
+    issuer_name VARCHAR(255) NOT NULL,
+    title_of_class VARCHAR(150) NOT NULL,
+
+    -- Position; values are in dollars, including filings from before 2023
+    -- that reported thousands
+    value_usd NUMERIC(24, 2) NOT NULL,
+    shares NUMERIC(24, 4) NOT NULL,
+    share_type VARCHAR(3) NOT NULL, -- SH shares or PRN principal amount
+    put_call VARCHAR(4), -- Put or Call for option positions
+    investment_discretion VARCHAR(10) NOT NULL, -- SOLE, DFND or OTR
+    voting_sole BIGINT NOT NULL DEFAULT 0,
+    voting_shared BIGINT NOT NULL DEFAULT 0,
+    voting_none BIGINT NOT NULL DEFAULT 0,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT institutional_holdings_share_type_check CHECK (share_type IN ('SH', 'PRN')),
+    CONSTRAINT institutional_holdings_put_call_check CHECK (put_call IS NULL OR put_call IN ('Put', 'Call'))
+);
+
+CREATE INDEX idx_institutional_holdings_cusip ON institutional_holdings(cusip, report_period);
+CREATE INDEX idx_institutional_holdings_manager ON institutional_holdings(manager_cik, report_period);
+CREATE INDEX idx_institutional_holdings_filing ON institutional_holdings(filing_id);
+
+-- Companies the CUSIPs in 13F filings belong to
+-- A company can have several CUSIPs, one per share class.
+CREATE TABLE cusip_mappings (
+    cusip VARCHAR(9) PRIMARY KEY,
+    company_id UUID NOT NULL REFERENCES companies(id) ON DELETE CASCADE,
+    issuer_name VARCHAR(255) NOT NULL, -- Name as written in the 13F information table
+    source VARCHAR(20) NOT NULL, -- name_match or manual
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT cusip_mappings_source_check CHECK (source IN ('name_match', 'manual'))
+);
+
+CREATE INDEX idx_cusip_mappings_company ON cusip_mappings(company_id);
+
+CREATE TRIGGER update_cusip_mappings_updated_at
+    BEFORE UPDATE ON cusip_mappings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Days of the EDGAR daily index already crawled for 13F-HR filings
+CREATE TABLE institutional_crawl_days (
+    index_date DATE PRIMARY KEY,
+    filings_found INTEGER NOT NULL DEFAULT 0,
+    filings_stored INTEGER NOT NULL DEFAULT 0,
+    filings_failed INTEGER NOT NULL DEFAULT 0,
+    holdings_stored INTEGER NOT NULL DEFAULT 0,
+    crawled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
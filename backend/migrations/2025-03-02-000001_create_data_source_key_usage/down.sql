@@ -0,0 +1,2 @@
+-- Drop per API key usage; data_source_usage still holds the totals per source
+DROP TABLE IF EXISTS data_source_key_usage;
@@ -0,0 +1,20 @@
+-- Daily usage per API key of a data source
+-- A source may be configured with several API keys, and the crawler moves on
+-- to the next key when one is throttled. Keys are identified by a short hash;
+-- the keys themselves are never stored here.
+
+CREATE TABLE data_source_key_usage (
+    data_source_id UUID NOT NULL REFERENCES data_sources(id) ON DELETE CASCADE,
+    key_hash VARCHAR(16) NOT NULL,
+    usage_date DATE NOT NULL,
+    bytes_downloaded BIGINT NOT NULL DEFAULT 0,
+    request_count INTEGER NOT NULL DEFAULT 0,
+    -- Requests answered with HTTP 429
+    throttled_count INTEGER NOT NULL DEFAULT 0,
+    last_throttled_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (data_source_id, key_hash, usage_date)
+);
+
+CREATE INDEX idx_data_source_key_usage_usage_date ON data_source_key_usage(usage_date);
@@ -0,0 +1,8 @@
+-- Drop data source access policies and user subscription tiers
+ALTER TABLE users
+    DROP CONSTRAINT IF EXISTS check_user_subscription_tier,
+    DROP COLUMN IF EXISTS subscription_tier;
+
+ALTER TABLE data_sources
+    DROP CONSTRAINT IF EXISTS check_data_source_access_policy,
+    DROP COLUMN IF EXISTS access_policy;
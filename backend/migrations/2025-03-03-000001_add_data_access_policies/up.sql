@@ -0,0 +1,12 @@
+-- Access policies for licensed data sources
+-- A data source's observations are open to everyone ('public'), to signed-in
+-- users ('registered') or to premium subscribers ('premium'). Users carry a
+-- subscription tier, which is copied into their access tokens.
+
+ALTER TABLE data_sources
+    ADD COLUMN access_policy VARCHAR(20) NOT NULL DEFAULT 'public',
+    ADD CONSTRAINT check_data_source_access_policy CHECK (access_policy IN ('public', 'registered', 'premium'));
+
+ALTER TABLE users
+    ADD COLUMN subscription_tier VARCHAR(20) NOT NULL DEFAULT 'free',
+    ADD CONSTRAINT check_user_subscription_tier CHECK (subscription_tier IN ('free', 'premium'));
@@ -0,0 +1,17 @@
+DROP TRIGGER IF EXISTS economic_series_statistics_delete_trigger ON economic_series;
+DROP TRIGGER IF EXISTS economic_series_statistics_update_trigger ON economic_series;
+DROP TRIGGER IF EXISTS economic_series_statistics_insert_trigger ON economic_series;
+DROP FUNCTION IF EXISTS economic_series_statistics_update();
+
+DROP TRIGGER IF EXISTS data_points_statistics_delete_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_statistics_update_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_statistics_insert_trigger ON data_points;
+DROP FUNCTION IF EXISTS data_points_statistics_recompute();
+DROP FUNCTION IF EXISTS data_points_statistics_insert();
+
+DROP FUNCTION IF EXISTS refresh_catalog_statistics();
+DROP FUNCTION IF EXISTS refresh_data_source_statistics(UUID[]);
+DROP FUNCTION IF EXISTS refresh_series_statistics(UUID[]);
+
+DROP TABLE IF EXISTS data_source_statistics;
+DROP TABLE IF EXISTS series_statistics;
@@ -0,0 +1,234 @@
+-- Catalog statistics for admin dashboards
+-- Data point counts and coverage dates per series and per data source, kept
+-- up to date by statement-level triggers as data is ingested so dashboards never
+-- scan data_points. Inserts are applied incrementally; deletes and updates
+-- recompute only the series and sources they touch. refresh_catalog_statistics()
+-- recomputes everything and is run periodically to correct any drift.
+
+CREATE TABLE series_statistics (
+    series_id UUID PRIMARY KEY REFERENCES economic_series(id) ON DELETE CASCADE,
+    source_id UUID NOT NULL,
+    data_point_count BIGINT NOT NULL DEFAULT 0,
+    earliest_date DATE,
+    latest_date DATE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_series_statistics_source_id ON series_statistics(source_id);
+
+CREATE TABLE data_source_statistics (
+    source_id UUID PRIMARY KEY REFERENCES data_sources(id) ON DELETE CASCADE,
+    series_count BIGINT NOT NULL DEFAULT 0,
+    active_series_count BIGINT NOT NULL DEFAULT 0,
+    data_point_count BIGINT NOT NULL DEFAULT 0,
+    earliest_date DATE,
+    latest_date DATE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Recompute the statistics of some series from their data points
+CREATE OR REPLACE FUNCTION refresh_series_statistics(series_ids UUID[])
+RETURNS VOID AS $$
+    DELETE FROM series_statistics s
+    WHERE s.series_id = ANY(series_ids)
+      AND NOT EXISTS (SELECT 1 FROM data_points dp WHERE dp.series_id = s.series_id);
+
+    INSERT INTO series_statistics (series_id, source_id, data_point_count, earliest_date, latest_date, updated_at)
+    SELECT es.id, es.source_id, COUNT(dp.id), MIN(dp.date), MAX(dp.date), NOW()
+    FROM economic_series es
+    JOIN data_points dp ON dp.series_id = es.id
+    WHERE es.id = ANY(series_ids)
+    GROUP BY es.id, es.source_id
+    ON CONFLICT (series_id) DO UPDATE SET
+        source_id = EXCLUDED.source_id,
+        data_point_count = EXCLUDED.data_point_count,
+        earliest_date = EXCLUDED.earliest_date,
+        latest_date = EXCLUDED.latest_date,
+        updated_at = EXCLUDED.updated_at;
+$$ LANGUAGE sql;
+
+-- Recompute the statistics of some data sources from their series
+CREATE OR REPLACE FUNCTION refresh_data_source_statistics(source_ids UUID[])
+RETURNS VOID AS $$
+    INSERT INTO data_source_statistics (
+        source_id, series_count, active_series_count, data_point_count,
+        earliest_date, latest_date, updated_at
+    )
+    SELECT
+        ds.id,
+        (SELECT COUNT(*) FROM economic_series es WHERE es.source_id = ds.id),
+        (SELECT COUNT(*) FROM economic_series es WHERE es.source_id = ds.id AND es.is_active),
+        COALESCE((SELECT SUM(s.data_point_count) FROM series_statistics s WHERE s.source_id = ds.id), 0),
+        (SELECT MIN(s.earliest_date) FROM series_statistics s WHERE s.source_id = ds.id),
+        (SELECT MAX(s.latest_date) FROM series_statistics s WHERE s.source_id = ds.id),
+        NOW()
+    FROM data_sources ds
+    WHERE ds.id = ANY(source_ids)
+    ON CONFLICT (source_id) DO UPDATE SET
+        series_count = EXCLUDED.series_count,
+        active_series_count = EXCLUDED.active_series_count,
+        data_point_count = EXCLUDED.data_point_count,
+        earliest_date = EXCLUDED.earliest_date,
+        latest_date = EXCLUDED.latest_date,
+        updated_at = EXCLUDED.updated_at;
+$$ LANGUAGE sql;
+
+-- Recompute every statistic; run periodically and after bulk maintenance
+CREATE OR REPLACE FUNCTION refresh_catalog_statistics()
+RETURNS VOID AS $$
+    DELETE FROM series_statistics s
+    WHERE NOT EXISTS (SELECT 1 FROM data_points dp WHERE dp.series_id = s.series_id);
+
+    INSERT INTO series_statistics (series_id, source_id, data_point_count, earliest_date, latest_date, updated_at)
+    SELECT es.id, es.source_id, counts.points, counts.earliest, counts.latest, NOW()
+    FROM (
+        SELECT dp.series_id, COUNT(*) AS points, MIN(dp.date) AS earliest, MAX(dp.date) AS latest
+        FROM data_points dp
+        GROUP BY dp.series_id
+    ) counts
+    JOIN economic_series es ON es.id = counts.series_id
+    ON CONFLICT (series_id) DO UPDATE SET
+        source_id = EXCLUDED.source_id,
+        data_point_count = EXCLUDED.data_point_count,
+        earliest_date = EXCLUDED.earliest_date,
+        latest_date = EXCLUDED.latest_date,
+        updated_at = EXCLUDED.updated_at;
+
+    SELECT refresh_data_source_statistics(ARRAY(SELECT id FROM data_sources));
+$$ LANGUAGE sql;
+
+-- Ingestion: add the new points to their series and sources
+CREATE OR REPLACE FUNCTION data_points_statistics_insert()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO series_statistics (series_id, source_id, data_point_count, earliest_date, latest_date, updated_at)
+    SELECT es.id, es.source_id, added.points, added.earliest, added.latest, NOW()
+    FROM (
+        SELECT np.series_id, COUNT(*) AS points, MIN(np.date) AS earliest, MAX(np.date) AS latest
+        FROM new_points np
+        GROUP BY np.series_id
+    ) added
+    JOIN economic_series es ON es.id = added.series_id
+    ON CONFLICT (series_id) DO UPDATE SET
+        data_point_count = series_statistics.data_point_count + EXCLUDED.data_point_count,
+        earliest_date = LEAST(series_statistics.earliest_date, EXCLUDED.earliest_date),
+        latest_date = GREATEST(series_statistics.latest_date, EXCLUDED.latest_date),
+        updated_at = EXCLUDED.updated_at;
+
+    INSERT INTO data_source_statistics (source_id, data_point_count, earliest_date, latest_date, updated_at)
+    SELECT es.source_id, COUNT(*), MIN(np.date), MAX(np.date), NOW()
+    FROM new_points np
+    JOIN economic_series es ON es.id = np.series_id
+    GROUP BY es.source_id
+    ON CONFLICT (source_id) DO UPDATE SET
+        data_point_count = data_source_statistics.data_point_count + EXCLUDED.data_point_count,
+        earliest_date = LEAST(data_source_statistics.earliest_date, EXCLUDED.earliest_date),
+        latest_date = GREATEST(data_source_statistics.latest_date, EXCLUDED.latest_date),
+        updated_at = EXCLUDED.updated_at;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Deletes and updates may shrink a series' date range, so recompute the series touched
+CREATE OR REPLACE FUNCTION data_points_statistics_recompute()
+RETURNS TRIGGER AS $$
+DECLARE
+    touched_series UUID[];
+    touched_sources UUID[];
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        touched_series := ARRAY(SELECT DISTINCT series_id FROM old_points);
+    ELSE
+        touched_series := ARRAY(
+            SELECT o.series_id FROM old_points o JOIN new_points n ON n.id = o.id
+            WHERE o.series_id <> n.series_id OR o.date <> n.date
+            UNION
+            SELECT n.series_id FROM old_points o JOIN new_points n ON n.id = o.id
+            WHERE o.series_id <> n.series_id OR o.date <> n.date
+        );
+    END IF;
+
+    IF cardinality(touched_series) = 0 THEN
+        RETURN NULL;
+    END IF;
+
+    -- Read the sources first: the series' statistics may be removed below
+    touched_sources := ARRAY(
+        SELECT DISTINCT s.source_id FROM series_statistics s WHERE s.series_id = ANY(touched_series)
+        UNION
+        SELECT es.source_id FROM economic_series es WHERE es.id = ANY(touched_series)
+    );
+
+    PERFORM refresh_series_statistics(touched_series);
+    PERFORM refresh_data_source_statistics(touched_sources);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER data_points_statistics_insert_trigger
+    AFTER INSERT ON data_points
+    REFERENCING NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_statistics_insert();
+
+CREATE TRIGGER data_points_statistics_update_trigger
+    AFTER UPDATE ON data_points
+    REFERENCING OLD TABLE AS old_points NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_statistics_recompute();
+
+CREATE TRIGGER data_points_statistics_delete_trigger
+    AFTER DELETE ON data_points
+    REFERENCING OLD TABLE AS old_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_statistics_recompute();
+
+-- Series added, removed, moved between sources or (de)activated change source counts
+CREATE OR REPLACE FUNCTION economic_series_statistics_update()
+RETURNS TRIGGER AS $$
+DECLARE
+    touched_sources UUID[];
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        touched_sources := ARRAY(SELECT DISTINCT source_id FROM new_series);
+    ELSIF TG_OP = 'DELETE' THEN
+        touched_sources := ARRAY(SELECT DISTINCT source_id FROM old_series);
+    ELSE
+        UPDATE series_statistics s
+        SET source_id = n.source_id, updated_at = NOW()
+        FROM new_series n
+        WHERE s.series_id = n.id AND s.source_id <> n.source_id;
+
+        touched_sources := ARRAY(
+            SELECT o.source_id FROM old_series o JOIN new_series n ON n.id = o.id
+            WHERE o.source_id <> n.source_id OR o.is_active <> n.is_active
+            UNION
+            SELECT n.source_id FROM old_series o JOIN new_series n ON n.id = o.id
+            WHERE o.source_id <> n.source_id OR o.is_active <> n.is_active
+        );
+    END IF;
+
+    IF cardinality(touched_sources) > 0 THEN
+        PERFORM refresh_data_source_statistics(touched_sources);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER economic_series_statistics_insert_trigger
+    AFTER INSERT ON economic_series
+    REFERENCING NEW TABLE AS new_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_statistics_update();
+
+CREATE TRIGGER economic_series_statistics_update_trigger
+    AFTER UPDATE ON economic_series
+    REFERENCING OLD TABLE AS old_series NEW TABLE AS new_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_statistics_update();
+
+CREATE TRIGGER economic_series_statistics_delete_trigger
+    AFTER DELETE ON economic_series
+    REFERENCING OLD TABLE AS old_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_statistics_update();
+
+-- Backfill
+SELECT refresh_catalog_statistics();
@@ -0,0 +1,7 @@
+-- Drop dimensions of financial line items
+DROP INDEX IF EXISTS idx_financial_line_items_dimensions;
+
+ALTER TABLE financial_line_items
+    DROP COLUMN IF EXISTS period_end_date,
+    DROP COLUMN IF EXISTS period_start_date,
+    DROP COLUMN IF EXISTS dimensions;
@@ -0,0 +1,14 @@
+-- Dimensions of financial line items
+-- XBRL facts reported for a business segment, geography or other breakdown
+-- carry explicit members in their context (segment or scenario). `dimensions`
+-- maps each dimension (axis) to its member, e.g.
+-- {"us-gaap:StatementBusinessSegmentsAxis": "aapl:IPhoneMember"}, and is empty
+-- for facts about the entity as a whole. The period columns come from the same
+-- context, so facts of the periods a filing compares can be told apart
+
+ALTER TABLE financial_line_items
+    ADD COLUMN dimensions JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ADD COLUMN period_start_date DATE,
+    ADD COLUMN period_end_date DATE;
+
+CREATE INDEX idx_financial_line_items_dimensions ON financial_line_items USING GIN (dimensions);
@@ -0,0 +1,3 @@
+-- Drop the email delivery queue and email preferences
+DROP TABLE IF EXISTS email_deliveries;
+DROP TABLE IF EXISTS email_preferences;
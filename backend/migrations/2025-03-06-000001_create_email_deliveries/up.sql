@@ -0,0 +1,46 @@
+-- Email delivery queue and per-user email preferences
+-- Collaboration invites, triggered alerts and the weekly digest are rendered
+-- when they happen and queued here; a worker sends them through the configured
+-- transport (SMTP or SES) and retries failures with exponential backoff.
+-- Users without a preferences row get the defaults.
+
+CREATE TABLE email_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    invites BOOLEAN NOT NULL DEFAULT TRUE,
+    alerts BOOLEAN NOT NULL DEFAULT TRUE,
+    weekly_digest BOOLEAN NOT NULL DEFAULT FALSE,
+    last_digest_at TIMESTAMPTZ, -- When the last weekly digest was queued
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_email_preferences_digest ON email_preferences(last_digest_at) WHERE weekly_digest;
+
+CREATE TRIGGER update_email_preferences_updated_at
+    BEFORE UPDATE ON email_preferences
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE email_deliveries (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    recipient VARCHAR(255) NOT NULL,
+    template VARCHAR(50) NOT NULL,
+    subject TEXT NOT NULL,
+    text_body TEXT NOT NULL,
+    html_body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ,
+    last_error TEXT,
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_email_delivery_status CHECK (status IN ('pending', 'sent', 'failed')),
+    CONSTRAINT check_email_delivery_template CHECK (
+        template IN ('invite', 'alert_triggered', 'weekly_digest')
+    )
+);
+
+CREATE INDEX idx_email_deliveries_due ON email_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_email_deliveries_user ON email_deliveries(user_id, created_at DESC);
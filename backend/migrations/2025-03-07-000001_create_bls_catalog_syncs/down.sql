@@ -0,0 +1,2 @@
+-- Drop the BLS catalog sync state
+DROP TABLE IF EXISTS bls_catalog_syncs;
@@ -0,0 +1,11 @@
+-- Sync state of BLS survey catalogs
+-- Each survey's series list is a LABSTAT `.series` file; the validators of the
+-- last copy synced let the next sync skip a file that has not changed.
+
+CREATE TABLE bls_catalog_syncs (
+    survey VARCHAR(10) PRIMARY KEY, -- Survey abbreviation, e.g. LN or CE
+    etag TEXT,
+    last_modified TEXT,
+    series_listed INTEGER NOT NULL DEFAULT 0,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS data_points_rollups_delete_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_rollups_update_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_rollups_insert_trigger ON data_points;
+DROP FUNCTION IF EXISTS data_points_rollups_refresh();
+DROP FUNCTION IF EXISTS refresh_series_rollups(UUID[], DATE[]);
+
+DROP TABLE IF EXISTS series_rollups;
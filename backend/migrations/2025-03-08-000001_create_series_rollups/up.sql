@@ -0,0 +1,137 @@
+-- Monthly and yearly rollups of each series for dashboards
+-- First, minimum, maximum, mean and last value and the observation count per
+-- series and period, computed from the latest revision of each observation.
+-- Statement-level triggers on data_points recompute the years a statement
+-- touched, so revisions, corrections and deletes are reflected as well.
+
+CREATE TABLE series_rollups (
+    series_id UUID NOT NULL REFERENCES economic_series(id) ON DELETE CASCADE,
+    period VARCHAR(10) NOT NULL CHECK (period IN ('month', 'year')),
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    first_value NUMERIC NOT NULL,
+    min_value NUMERIC NOT NULL,
+    max_value NUMERIC NOT NULL,
+    mean_value NUMERIC NOT NULL,
+    last_value NUMERIC NOT NULL,
+    last_date DATE NOT NULL, -- Date of the last observation in the period
+    observation_count INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (series_id, period, period_start)
+);
+
+-- Recompute the rollups of the years the given (series, date) pairs fall in
+CREATE OR REPLACE FUNCTION refresh_series_rollups(series_ids UUID[], dates DATE[])
+RETURNS VOID AS $$
+    DELETE FROM series_rollups r
+    USING (
+        SELECT DISTINCT t.series_id, date_trunc('year', t.date)::date AS year_start
+        FROM unnest(series_ids, dates) AS t(series_id, date)
+    ) touched
+    WHERE r.series_id = touched.series_id
+      AND r.period_start >= touched.year_start
+      AND r.period_start < touched.year_start + INTERVAL '1 year';
+
+    WITH touched AS (
+        SELECT DISTINCT t.series_id, date_trunc('year', t.date)::date AS year_start
+        FROM unnest(series_ids, dates) AS t(series_id, date)
+    ),
+    observations AS (
+        -- Latest revision of each observation, as charts show it
+        SELECT DISTINCT ON (dp.series_id, dp.date) dp.series_id, dp.date, dp.value
+        FROM data_points dp
+        JOIN touched t ON t.series_id = dp.series_id
+            AND dp.date >= t.year_start
+            AND dp.date < t.year_start + INTERVAL '1 year'
+        ORDER BY dp.series_id, dp.date, dp.revision_date DESC, dp.created_at DESC
+    ),
+    periods AS (
+        SELECT series_id, 'month' AS period, date_trunc('month', date)::date AS period_start, date, value
+        FROM observations
+        WHERE value IS NOT NULL
+        UNION ALL
+        SELECT series_id, 'year', date_trunc('year', date)::date, date, value
+        FROM observations
+        WHERE value IS NOT NULL
+    )
+    INSERT INTO series_rollups (
+        series_id, period, period_start, period_end, first_value, min_value, max_value,
+        mean_value, last_value, last_date, observation_count, updated_at
+    )
+    SELECT
+        series_id,
+        period,
+        period_start,
+        (period_start + CASE period WHEN 'month' THEN INTERVAL '1 month' ELSE INTERVAL '1 year' END
+            - INTERVAL '1 day')::date,
+        (array_agg(value ORDER BY date))[1],
+        MIN(value),
+        MAX(value),
+        AVG(value),
+        (array_agg(value ORDER BY date DESC))[1],
+        MAX(date),
+        COUNT(*),
+        NOW()
+    FROM periods
+    GROUP BY series_id, period, period_start
+    -- A concurrent ingestion of the same series may have written the period first
+    ON CONFLICT (series_id, period, period_start) DO UPDATE SET
+        period_end = EXCLUDED.period_end,
+        first_value = EXCLUDED.first_value,
+        min_value = EXCLUDED.min_value,
+        max_value = EXCLUDED.max_value,
+        mean_value = EXCLUDED.mean_value,
+        last_value = EXCLUDED.last_value,
+        last_date = EXCLUDED.last_date,
+        observation_count = EXCLUDED.observation_count,
+        updated_at = EXCLUDED.updated_at;
+$$ LANGUAGE sql;
+
+-- Inserts, updates and deletes all recompute the years whose observations changed
+CREATE OR REPLACE FUNCTION data_points_rollups_refresh()
+RETURNS TRIGGER AS $$
+DECLARE
+    touched_series UUID[];
+    touched_dates DATE[];
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        SELECT array_agg(series_id), array_agg(date) INTO touched_series, touched_dates
+        FROM (SELECT DISTINCT series_id, date_trunc('year', date)::date AS date FROM new_points) t;
+    ELSIF TG_OP = 'DELETE' THEN
+        SELECT array_agg(series_id), array_agg(date) INTO touched_series, touched_dates
+        FROM (SELECT DISTINCT series_id, date_trunc('year', date)::date AS date FROM old_points) t;
+    ELSE
+        SELECT array_agg(series_id), array_agg(date) INTO touched_series, touched_dates
+        FROM (
+            SELECT series_id, date_trunc('year', date)::date AS date FROM old_points
+            UNION
+            SELECT series_id, date_trunc('year', date)::date FROM new_points
+        ) t;
+    END IF;
+
+    IF touched_series IS NOT NULL THEN
+        PERFORM refresh_series_rollups(touched_series, touched_dates);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER data_points_rollups_insert_trigger
+    AFTER INSERT ON data_points
+    REFERENCING NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_rollups_refresh();
+
+CREATE TRIGGER data_points_rollups_update_trigger
+    AFTER UPDATE ON data_points
+    REFERENCING OLD TABLE AS old_points NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_rollups_refresh();
+
+CREATE TRIGGER data_points_rollups_delete_trigger
+    AFTER DELETE ON data_points
+    REFERENCING OLD TABLE AS old_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_rollups_refresh();
+
+-- Backfill
+SELECT refresh_series_rollups(array_agg(series_id), array_agg(year_start))
+FROM (SELECT DISTINCT series_id, date_trunc('year', date)::date AS year_start FROM data_points) t;
@@ -0,0 +1,3 @@
+-- Drop mirror endpoints of data sources
+ALTER TABLE data_sources
+    DROP COLUMN IF EXISTS fallback_urls;
@@ -0,0 +1,6 @@
+-- Mirror endpoints per data source
+-- Crawlers send requests to base_url and switch to these, in order, when
+-- base_url keeps failing; they must accept the same paths as base_url
+
+ALTER TABLE data_sources
+    ADD COLUMN fallback_urls TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,11 @@
+DROP TRIGGER IF EXISTS data_points_changes_delete_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_changes_update_trigger ON data_points;
+DROP TRIGGER IF EXISTS data_points_changes_insert_trigger ON data_points;
+DROP TRIGGER IF EXISTS economic_series_changes_delete_trigger ON economic_series;
+DROP TRIGGER IF EXISTS economic_series_changes_update_trigger ON economic_series;
+DROP TRIGGER IF EXISTS economic_series_changes_insert_trigger ON economic_series;
+DROP FUNCTION IF EXISTS data_points_record_changes();
+DROP FUNCTION IF EXISTS economic_series_record_changes();
+DROP FUNCTION IF EXISTS sequence_change_feed();
+
+DROP TABLE IF EXISTS change_feed;
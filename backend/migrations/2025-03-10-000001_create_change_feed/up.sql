@@ -0,0 +1,123 @@
+-- Change feed of series and data point mutations
+-- Triggers record every change in the transaction that makes it. Concurrent
+-- transactions commit in a different order than they write, so rows are
+-- numbered afterwards by sequence_change_feed(), only once every transaction
+-- that could still add an earlier row has finished. Consumers tailing the
+-- feed by sequence therefore never skip a change.
+
+CREATE TABLE change_feed (
+    id BIGSERIAL PRIMARY KEY,
+    -- Position in the feed; NULL until sequenced
+    sequence BIGINT UNIQUE,
+    -- 'series' for economic_series rows, 'series_data' for a series' data points
+    entity_type VARCHAR(20) NOT NULL,
+    entity_id UUID NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    -- Transaction that made the change, as pg_current_xact_id()
+    txid BIGINT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_change_feed_entity_type CHECK (entity_type IN ('series', 'series_data')),
+    CONSTRAINT check_change_feed_operation CHECK (operation IN ('insert', 'update', 'delete'))
+);
+
+CREATE INDEX idx_change_feed_unsequenced ON change_feed(txid, id) WHERE sequence IS NULL;
+CREATE INDEX idx_change_feed_changed_at ON change_feed(changed_at);
+
+-- Number the rows of finished transactions, in transaction order
+CREATE OR REPLACE FUNCTION sequence_change_feed()
+RETURNS INTEGER AS $$
+DECLARE
+    horizon BIGINT;
+    sequenced INTEGER;
+BEGIN
+    -- One sequencer at a time, so positions are handed out in one order
+    PERFORM pg_advisory_xact_lock(hashtext('change_feed_sequencer'));
+
+    -- Transactions below the horizon have committed or rolled back
+    horizon := pg_snapshot_xmin(pg_current_snapshot())::text::bigint;
+
+    WITH last AS (
+        SELECT COALESCE(MAX(sequence), 0) AS sequence FROM change_feed
+    ),
+    pending AS (
+        SELECT id, ROW_NUMBER() OVER (ORDER BY txid, id) AS position
+        FROM change_feed
+        WHERE sequence IS NULL AND txid < horizon
+    )
+    UPDATE change_feed
+    SET sequence = last.sequence + pending.position
+    FROM pending, last
+    WHERE change_feed.id = pending.id;
+
+    GET DIAGNOSTICS sequenced = ROW_COUNT;
+    RETURN sequenced;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION economic_series_record_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO change_feed (entity_type, entity_id, operation, txid)
+        SELECT 'series', id, 'delete', pg_current_xact_id()::text::bigint FROM old_series;
+    ELSE
+        INSERT INTO change_feed (entity_type, entity_id, operation, txid)
+        SELECT 'series', id, lower(TG_OP), pg_current_xact_id()::text::bigint FROM new_series;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- One row per series whose data points a statement changed
+CREATE OR REPLACE FUNCTION data_points_record_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO change_feed (entity_type, entity_id, operation, txid)
+        SELECT DISTINCT 'series_data', series_id, 'delete', pg_current_xact_id()::text::bigint
+        FROM old_points;
+    ELSIF TG_OP = 'INSERT' THEN
+        INSERT INTO change_feed (entity_type, entity_id, operation, txid)
+        SELECT DISTINCT 'series_data', series_id, 'insert', pg_current_xact_id()::text::bigint
+        FROM new_points;
+    ELSE
+        INSERT INTO change_feed (entity_type, entity_id, operation, txid)
+        SELECT DISTINCT 'series_data', series_id, 'update', pg_current_xact_id()::text::bigint
+        FROM (SELECT series_id FROM old_points UNION SELECT series_id FROM new_points) t;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER economic_series_changes_insert_trigger
+    AFTER INSERT ON economic_series
+    REFERENCING NEW TABLE AS new_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_record_changes();
+
+CREATE TRIGGER economic_series_changes_update_trigger
+    AFTER UPDATE ON economic_series
+    REFERENCING NEW TABLE AS new_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_record_changes();
+
+CREATE TRIGGER economic_series_changes_delete_trigger
+    AFTER DELETE ON economic_series
+    REFERENCING OLD TABLE AS old_series
+    FOR EACH STATEMENT EXECUTE FUNCTION economic_series_record_changes();
+
+CREATE TRIGGER data_points_changes_insert_trigger
+    AFTER INSERT ON data_points
+    REFERENCING NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_record_changes();
+
+CREATE TRIGGER data_points_changes_update_trigger
+    AFTER UPDATE ON data_points
+    REFERENCING OLD TABLE AS old_points NEW TABLE AS new_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_record_changes();
+
+CREATE TRIGGER data_points_changes_delete_trigger
+    AFTER DELETE ON data_points
+    REFERENCING OLD TABLE AS old_points
+    FOR EACH STATEMENT EXECUTE FUNCTION data_points_record_changes();
@@ -0,0 +1,32 @@
+-- Put document contents back on the rows referencing them
+UPDATE financial_statements s
+SET xbrl_file_content = b.content,
+    xbrl_file_compressed = b.compression_type <> 'none',
+    xbrl_file_compression_type = b.compression_type::compression_type
+FROM document_blobs b
+WHERE s.xbrl_file_blob_hash = b.content_hash;
+
+UPDATE xbrl_taxonomy_schemas s
+SET file_content = b.content,
+    is_compressed = b.compression_type <> 'none',
+    compression_type = b.compression_type::compression_type
+FROM document_blobs b
+WHERE s.blob_hash = b.content_hash;
+
+UPDATE xbrl_taxonomy_linkbases s
+SET file_content = b.content,
+    is_compressed = b.compression_type <> 'none',
+    compression_type = b.compression_type::compression_type
+FROM document_blobs b
+WHERE s.blob_hash = b.content_hash;
+
+DROP TRIGGER IF EXISTS xbrl_taxonomy_linkbases_blob_references_trigger ON xbrl_taxonomy_linkbases;
+DROP TRIGGER IF EXISTS xbrl_taxonomy_schemas_blob_references_trigger ON xbrl_taxonomy_schemas;
+DROP TRIGGER IF EXISTS financial_statements_blob_references_trigger ON financial_statements;
+DROP FUNCTION IF EXISTS document_blob_references();
+
+ALTER TABLE xbrl_taxonomy_linkbases DROP COLUMN IF EXISTS blob_hash;
+ALTER TABLE xbrl_taxonomy_schemas DROP COLUMN IF EXISTS blob_hash;
+ALTER TABLE financial_statements DROP COLUMN IF EXISTS xbrl_file_blob_hash;
+
+DROP TABLE IF EXISTS document_blobs;
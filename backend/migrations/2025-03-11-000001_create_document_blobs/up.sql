@@ -0,0 +1,130 @@
+-- Content-addressed storage of SEC filing and taxonomy documents
+-- Identical files (the same us-gaap schema referenced by thousands of filings)
+-- are stored once, keyed by the SHA-256 of their uncompressed content. Rows
+-- referencing a blob are counted by triggers; blobs no longer referenced are
+-- removed by the document blob garbage collection.
+
+CREATE TABLE document_blobs (
+    -- Lowercase hex SHA-256 of the uncompressed content
+    content_hash VARCHAR(64) PRIMARY KEY,
+    content BYTEA NOT NULL,
+    -- Size of the uncompressed content
+    size_bytes BIGINT NOT NULL,
+    -- Size of the content as stored
+    stored_size_bytes BIGINT NOT NULL,
+    compression_type VARCHAR(10) NOT NULL DEFAULT 'none',
+    reference_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Last time the content was found to match its hash
+    verified_at TIMESTAMPTZ,
+
+    CONSTRAINT check_document_blobs_hash CHECK (content_hash ~ '^[0-9a-f]{64}$'),
+    CONSTRAINT check_document_blobs_compression CHECK (compression_type IN ('none', 'zstd')),
+    CONSTRAINT check_document_blobs_reference_count CHECK (reference_count >= 0)
+);
+
+CREATE INDEX idx_document_blobs_unreferenced ON document_blobs(content_hash) WHERE reference_count = 0;
+CREATE INDEX idx_document_blobs_verified_at ON document_blobs(verified_at NULLS FIRST);
+
+ALTER TABLE financial_statements
+    ADD COLUMN xbrl_file_blob_hash VARCHAR(64) REFERENCES document_blobs(content_hash);
+ALTER TABLE xbrl_taxonomy_schemas
+    ADD COLUMN blob_hash VARCHAR(64) REFERENCES document_blobs(content_hash);
+ALTER TABLE xbrl_taxonomy_linkbases
+    ADD COLUMN blob_hash VARCHAR(64) REFERENCES document_blobs(content_hash);
+
+CREATE INDEX idx_financial_statements_xbrl_file_blob_hash ON financial_statements(xbrl_file_blob_hash);
+CREATE INDEX idx_xbrl_taxonomy_schemas_blob_hash ON xbrl_taxonomy_schemas(blob_hash);
+CREATE INDEX idx_xbrl_taxonomy_linkbases_blob_hash ON xbrl_taxonomy_linkbases(blob_hash);
+
+-- Count references held in the column named by the trigger's argument
+CREATE OR REPLACE FUNCTION document_blob_references()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_hash VARCHAR(64);
+    new_hash VARCHAR(64);
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        old_hash := to_jsonb(OLD) ->> TG_ARGV[0];
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        new_hash := to_jsonb(NEW) ->> TG_ARGV[0];
+    END IF;
+
+    IF old_hash IS NOT DISTINCT FROM new_hash THEN
+        RETURN NULL;
+    END IF;
+    IF old_hash IS NOT NULL THEN
+        UPDATE document_blobs SET reference_count = reference_count - 1
+        WHERE content_hash = old_hash;
+    END IF;
+    IF new_hash IS NOT NULL THEN
+        UPDATE document_blobs SET reference_count = reference_count + 1
+        WHERE content_hash = new_hash;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER financial_statements_blob_references_trigger
+    AFTER INSERT OR DELETE OR UPDATE OF xbrl_file_blob_hash ON financial_statements
+    FOR EACH ROW EXECUTE FUNCTION document_blob_references('xbrl_file_blob_hash');
+
+CREATE TRIGGER xbrl_taxonomy_schemas_blob_references_trigger
+    AFTER INSERT OR DELETE OR UPDATE OF blob_hash ON xbrl_taxonomy_schemas
+    FOR EACH ROW EXECUTE FUNCTION document_blob_references('blob_hash');
+
+CREATE TRIGGER xbrl_taxonomy_linkbases_blob_references_trigger
+    AFTER INSERT OR DELETE OR UPDATE OF blob_hash ON xbrl_taxonomy_linkbases
+    FOR EACH ROW EXECUTE FUNCTION document_blob_references('blob_hash');
+
+-- Move stored documents into blobs. Uncompressed content is hashed here;
+-- compressed filings keep the hash recorded when they were downloaded.
+CREATE TEMPORARY TABLE document_blob_backfill AS
+SELECT 'financial_statements' AS source_table, id,
+       CASE WHEN xbrl_file_compressed AND xbrl_file_compression_type = 'zstd'
+            THEN lower(regexp_replace(xbrl_file_hash, '^sha256:', ''))
+            ELSE encode(sha256(xbrl_file_content), 'hex') END AS content_hash,
+       xbrl_file_content AS content,
+       COALESCE(xbrl_file_size_bytes, length(xbrl_file_content)) AS size_bytes,
+       CASE WHEN xbrl_file_compressed AND xbrl_file_compression_type = 'zstd'
+            THEN 'zstd' ELSE 'none' END AS compression_type
+FROM financial_statements
+WHERE xbrl_file_content IS NOT NULL
+  AND (NOT xbrl_file_compressed OR xbrl_file_compression_type IN ('none', 'zstd'))
+UNION ALL
+SELECT 'xbrl_taxonomy_schemas', id, encode(sha256(file_content), 'hex'), file_content,
+       length(file_content), 'none'
+FROM xbrl_taxonomy_schemas
+WHERE file_content IS NOT NULL AND NOT is_compressed
+UNION ALL
+SELECT 'xbrl_taxonomy_linkbases', id, encode(sha256(file_content), 'hex'), file_content,
+       length(file_content), 'none'
+FROM xbrl_taxonomy_linkbases
+WHERE file_content IS NOT NULL AND NOT is_compressed;
+
+-- Rows whose recorded hash is not a SHA-256 stay where they are
+DELETE FROM document_blob_backfill WHERE content_hash !~ '^[0-9a-f]{64}$';
+
+INSERT INTO document_blobs (content_hash, content, size_bytes, stored_size_bytes, compression_type)
+SELECT DISTINCT ON (content_hash) content_hash, content, size_bytes, length(content), compression_type
+FROM document_blob_backfill
+ORDER BY content_hash, compression_type DESC;
+
+UPDATE financial_statements s
+SET xbrl_file_blob_hash = b.content_hash, xbrl_file_content = NULL
+FROM document_blob_backfill b
+WHERE b.source_table = 'financial_statements' AND b.id = s.id;
+
+UPDATE xbrl_taxonomy_schemas s
+SET blob_hash = b.content_hash, file_content = NULL
+FROM document_blob_backfill b
+WHERE b.source_table = 'xbrl_taxonomy_schemas' AND b.id = s.id;
+
+UPDATE xbrl_taxonomy_linkbases s
+SET blob_hash = b.content_hash, file_content = NULL
+FROM document_blob_backfill b
+WHERE b.source_table = 'xbrl_taxonomy_linkbases' AND b.id = s.id;
+
+DROP TABLE document_blob_backfill;
@@ -0,0 +1,3 @@
+-- Drop release calendars
+DROP TRIGGER IF EXISTS update_release_calendars_updated_at ON release_calendars;
+DROP TABLE IF EXISTS release_calendars;
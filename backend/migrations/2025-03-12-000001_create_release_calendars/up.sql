@@ -0,0 +1,59 @@
+-- Release calendars of data sources and series
+-- A calendar describes when new observations are published (CPI monthly
+-- around the 12th at 8:30 ET, the employment report the first Friday).
+-- The release scheduler queues crawls right after each expected release, and
+-- calendars of series without one are learned from when their data arrived.
+
+CREATE TABLE release_calendars (
+    id UUID PRIMARY KEY DEFAULT uuidv7(),
+    -- Set for a calendar covering every series of a source with the frequency
+    source_id UUID REFERENCES data_sources(id) ON DELETE CASCADE,
+    -- Set for a calendar of one series; takes precedence over the source's
+    series_id UUID REFERENCES economic_series(id) ON DELETE CASCADE,
+    frequency VARCHAR(20) NOT NULL,
+    -- ISO weekday, 1 = Monday; the release day of weekly calendars, or with
+    -- week_of_month the n-th weekday of the month
+    day_of_week SMALLINT,
+    -- 1-4, or 5 for the last such weekday of the month
+    week_of_month SMALLINT,
+    -- Release day; months without that day release on their last day
+    day_of_month SMALLINT,
+    -- Month within the quarter (1-3) or year (1-12) of quarterly and annual releases
+    month_of_period SMALLINT NOT NULL DEFAULT 1,
+    -- Local time of the release in time_zone
+    release_time TIME NOT NULL,
+    time_zone VARCHAR(50) NOT NULL DEFAULT 'America/New_York',
+    -- How long after the release the crawl runs, giving the source time to publish
+    crawl_delay_minutes INTEGER NOT NULL DEFAULT 5,
+    -- Learned from data arrivals rather than entered by an admin
+    is_learned BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Share of past releases that fit a learned calendar
+    confidence DOUBLE PRECISION,
+    -- Releases a learned calendar was inferred from
+    sample_count INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    next_release_at TIMESTAMPTZ,
+    -- Release whose crawls were queued last
+    last_queued_release_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_release_calendar_scope CHECK ((source_id IS NULL) <> (series_id IS NULL)),
+    CONSTRAINT check_release_calendar_frequency CHECK (
+        frequency IN ('daily', 'weekly', 'monthly', 'quarterly', 'annual')
+    ),
+    CONSTRAINT check_release_calendar_day_of_week CHECK (day_of_week BETWEEN 1 AND 7),
+    CONSTRAINT check_release_calendar_week_of_month CHECK (week_of_month BETWEEN 1 AND 5),
+    CONSTRAINT check_release_calendar_day_of_month CHECK (day_of_month BETWEEN 1 AND 31),
+    CONSTRAINT check_release_calendar_month_of_period CHECK (month_of_period BETWEEN 1 AND 12),
+    CONSTRAINT check_release_calendar_crawl_delay CHECK (crawl_delay_minutes BETWEEN 0 AND 1440),
+    CONSTRAINT check_release_calendar_confidence CHECK (confidence BETWEEN 0 AND 1),
+    CONSTRAINT unique_release_calendar_series UNIQUE (series_id),
+    CONSTRAINT unique_release_calendar_source_frequency UNIQUE (source_id, frequency)
+);
+
+CREATE INDEX idx_release_calendars_next_release ON release_calendars(next_release_at) WHERE is_active;
+
+CREATE TRIGGER update_release_calendars_updated_at
+    BEFORE UPDATE ON release_calendars
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,3 @@
+-- Drop recorded document blob compression levels
+ALTER TABLE document_blobs DROP CONSTRAINT IF EXISTS check_document_blobs_compression_level;
+ALTER TABLE document_blobs DROP COLUMN IF EXISTS compression_level;
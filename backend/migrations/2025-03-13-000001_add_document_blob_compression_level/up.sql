@@ -0,0 +1,8 @@
+-- Zstandard level each document blob was last encoded at, so blobs can be
+-- recompressed when the configured level changes. The level is recorded even
+-- when compression did not make a document smaller and it was stored as is;
+-- NULL means compression was not tried, as for blobs written before this column.
+ALTER TABLE document_blobs ADD COLUMN compression_level SMALLINT;
+
+ALTER TABLE document_blobs ADD CONSTRAINT check_document_blobs_compression_level
+    CHECK (compression_level BETWEEN 1 AND 22);
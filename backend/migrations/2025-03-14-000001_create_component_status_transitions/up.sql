@@ -0,0 +1,17 @@
+-- Health transitions of the components shown on the public status page
+-- A row is written only when a component's status changes, so the status of a
+-- component at any time is that of its latest row before then. Uptime over a
+-- window is computed from the transitions in it plus the one before it.
+
+CREATE TABLE component_status_transitions (
+    id BIGSERIAL PRIMARY KEY,
+    -- 'database', 'graphql' or 'crawler:<data source name>'
+    component VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    message TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT check_component_status CHECK (status IN ('healthy', 'degraded', 'unhealthy'))
+);
+
+CREATE INDEX idx_component_status_transitions_component ON component_status_transitions(component, changed_at DESC);
@@ -0,0 +1,11 @@
+-- Return to plaintext webhook secrets
+-- Encrypted secrets cannot be decrypted in SQL, so webhooks without a
+-- plaintext secret are deleted and have to be registered again.
+DELETE FROM webhooks WHERE secret IS NULL;
+
+ALTER TABLE webhooks
+    DROP CONSTRAINT IF EXISTS check_webhook_secret,
+    DROP COLUMN IF EXISTS secret_ciphertext,
+    DROP COLUMN IF EXISTS secret_nonce,
+    DROP COLUMN IF EXISTS secret_key_id,
+    ALTER COLUMN secret SET NOT NULL;
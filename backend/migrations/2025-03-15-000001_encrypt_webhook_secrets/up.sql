@@ -0,0 +1,15 @@
+-- Webhook signing secrets are stored AES-256-GCM encrypted, like data source credentials
+-- Secrets of existing webhooks stay in `secret` until the webhook dispatcher
+-- encrypts them on its first run with an encryption key configured, which
+-- clears the plaintext column.
+
+ALTER TABLE webhooks
+    ALTER COLUMN secret DROP NOT NULL,
+    -- Id of the encryption key, so keys can be rotated
+    ADD COLUMN secret_key_id VARCHAR(100),
+    ADD COLUMN secret_nonce BYTEA,
+    ADD COLUMN secret_ciphertext BYTEA,
+    ADD CONSTRAINT check_webhook_secret CHECK (
+        secret IS NOT NULL
+        OR (secret_key_id IS NOT NULL AND octet_length(secret_nonce) = 12 AND secret_ciphertext IS NOT NULL)
+    );
@@ -0,0 +1,3 @@
+UPDATE global_indicator_data
+SET data_source = 'World Bank'
+WHERE data_source = 'World Bank Open Data';
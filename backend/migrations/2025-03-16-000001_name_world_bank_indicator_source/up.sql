@@ -0,0 +1,6 @@
+-- Record World Bank indicator data under the name of its data source
+-- Access policies are looked up by data source name, and the World Bank
+-- loader used to store "World Bank" rather than "World Bank Open Data".
+UPDATE global_indicator_data
+SET data_source = 'World Bank Open Data'
+WHERE data_source = 'World Bank';